@@ -0,0 +1,84 @@
+//! Benchmarks for the local copy engine (`copy_files_with_progress`).
+//!
+//! Run with: `cargo bench --bench copy_benchmarks`
+//! Results are saved to `target/criterion/` with HTML reports. Add `RUSTY_COMMANDER_BENCHMARK=1`
+//! to also print each run's timeline to stderr.
+//!
+//! Two scenario families, since they stress different parts of the engine:
+//! - **many_small**: per-file overhead (scan, conflict check, open/create, metadata copy,
+//!   progress bookkeeping). Throughput here is about files per second.
+//! - **few_large**: the byte path (`copyfile`/`copy_file_range`, chunking, throttling).
+//!   Throughput here is about bytes per second.
+//!
+//! Sources live in a temp dir and are built once per parameter; every iteration copies into a
+//! fresh destination so no run hits conflicts from the previous one.
+
+use criterion::{BatchSize, BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use std::fs;
+use std::path::PathBuf;
+
+use cmdr_lib::bench_support::copy_files;
+
+/// A source tree of `count` files of `size` bytes each, spread over a few subfolders so the
+/// scan and the directory-creation path are exercised too. Returns the temp dir (keep it alive)
+/// and the top-level source path.
+fn setup_source_tree(count: usize, size: usize) -> (tempfile::TempDir, PathBuf) {
+    let temp_dir = tempfile::tempdir().expect("tempdir");
+    let root = temp_dir.path().join("src");
+    let content: Vec<u8> = (0..size).map(|i| (i % 251) as u8).collect();
+    for i in 0..count {
+        let dir = root.join(format!("dir_{}", i % 8));
+        fs::create_dir_all(&dir).expect("mkdir");
+        fs::write(dir.join(format!("file_{i}.bin")), &content).expect("write");
+    }
+    (temp_dir, root)
+}
+
+fn bench_copy(c: &mut Criterion, group_name: &str, scenarios: &[(usize, usize)]) {
+    cmdr_lib::benchmark::init_benchmarking();
+    let mut group = c.benchmark_group(group_name);
+    group.sample_size(10);
+
+    for &(count, size) in scenarios {
+        let (_source_dir, source) = setup_source_tree(count, size);
+        let sources = vec![source];
+        group.throughput(Throughput::Bytes((count * size) as u64));
+        group.bench_with_input(
+            BenchmarkId::new(format!("{}_KiB", size / 1024), count),
+            &sources,
+            |b, sources| {
+                b.iter_batched(
+                    || tempfile::tempdir().expect("destination tempdir"),
+                    |destination| {
+                        copy_files(sources, destination.path()).expect("copy");
+                        destination
+                    },
+                    BatchSize::PerIteration,
+                )
+            },
+        );
+    }
+
+    group.finish();
+}
+
+/// Per-file overhead: lots of tiny files.
+fn bench_many_small_files(c: &mut Criterion) {
+    bench_copy(
+        c,
+        "copy_many_small",
+        &[(100, 4 * 1024), (1_000, 4 * 1024), (1_000, 64 * 1024)],
+    );
+}
+
+/// Byte throughput: a handful of big files.
+fn bench_few_large_files(c: &mut Criterion) {
+    bench_copy(
+        c,
+        "copy_few_large",
+        &[(1, 64 * 1024 * 1024), (4, 64 * 1024 * 1024), (2, 256 * 1024 * 1024)],
+    );
+}
+
+criterion_group!(benches, bench_many_small_files, bench_few_large_files);
+criterion_main!(benches);
@@ -1,9 +1,10 @@
 //! AI model download utilities with progress reporting and resume support.
 
 use super::extract::LLAMA_SERVER_BINARY;
-use super::{DownloadProgress, ModelInfo};
+use super::{AiIntegrityFailed, DownloadProgress, ModelInfo};
+use sha2::{Digest, Sha256};
 use std::fs;
-use std::io::Write;
+use std::io::{Read, Write};
 use std::path::Path;
 use tauri::{AppHandle, Runtime};
 use tauri_specta::Event as _;
@@ -12,10 +13,15 @@ use tauri_specta::Event as _;
 ///
 /// The `is_cancelled` parameter is a function that checks if the download should be cancelled.
 /// This allows the caller (manager.rs) to control cancellation via its internal state.
+///
+/// With `expected_sha256`, the file is hashed as it arrives (a resumed download hashes the
+/// bytes already on disk first). On mismatch the file is deleted, `ai-integrity-failed` is
+/// emitted, and this returns an error.
 pub async fn download_file<R: Runtime, F>(
     app: &AppHandle<R>,
     url: &str,
     dest: &Path,
+    expected_sha256: Option<&str>,
     is_cancelled: F,
 ) -> Result<(), String>
 where
@@ -31,6 +37,19 @@ where
         log::debug!("AI download: resuming from {} bytes", existing_size);
     }
 
+    let mut hasher = match expected_sha256 {
+        Some(_) if existing_size > 0 => {
+            let path = dest.to_path_buf();
+            let hasher = tauri::async_runtime::spawn_blocking(move || hash_existing(&path))
+                .await
+                .map_err(|e| format!("Hash task failed: {e}"))?
+                .map_err(|e| format!("Failed to read partial download: {e}"))?;
+            Some(hasher)
+        }
+        Some(_) => Some(Sha256::new()),
+        None => None,
+    };
+
     let mut request = client.get(url);
     if existing_size > 0 {
         request = request.header("Range", format!("bytes={existing_size}-"));
@@ -63,6 +82,9 @@ where
 
         let chunk = chunk.map_err(|e| format!("Download error: {e}"))?;
         file.write_all(&chunk).map_err(|e| format!("Write error: {e}"))?;
+        if let Some(ref mut hasher) = hasher {
+            hasher.update(&chunk);
+        }
         downloaded += chunk.len() as u64;
 
         // Emit progress at most every 200ms
@@ -95,13 +117,44 @@ where
     }
     .emit(app);
 
+    if let (Some(expected), Some(hasher)) = (expected_sha256, hasher) {
+        let actual = hex_lower(&hasher.finalize());
+        if !actual.eq_ignore_ascii_case(expected) {
+            crate::log_error!("AI download: SHA-256 mismatch. Expected {expected}, got {actual}");
+            drop(file);
+            let _ = fs::remove_file(dest);
+            let _ = AiIntegrityFailed.emit(app);
+            return Err(String::from(
+                "The downloaded model didn't match its SHA-256 checksum, so it was deleted. Try downloading it again.",
+            ));
+        }
+        log::debug!("AI download: SHA-256 verified");
+    }
+
     Ok(())
 }
 
+/// Hashes the bytes already on disk, so a resumed download's digest covers the whole file.
+fn hash_existing(path: &Path) -> std::io::Result<Sha256> {
+    let mut hasher = Sha256::new();
+    let mut file = fs::File::open(path)?;
+    let mut buf = vec![0u8; 1024 * 1024];
+    loop {
+        match file.read(&mut buf)? {
+            0 => return Ok(hasher),
+            n => hasher.update(&buf[..n]),
+        }
+    }
+}
+
+fn hex_lower(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
 /// Cleans up partial download files (binary and model).
 pub fn cleanup_partial(ai_dir: &Path, model: &ModelInfo) {
     let _ = fs::remove_file(ai_dir.join(LLAMA_SERVER_BINARY));
-    let _ = fs::remove_file(ai_dir.join(model.filename));
+    let _ = fs::remove_file(ai_dir.join(&*model.filename));
     // Also remove any dylibs that were extracted
     if let Ok(entries) = fs::read_dir(ai_dir) {
         for entry in entries.flatten() {
@@ -111,3 +164,23 @@ pub fn cleanup_partial(ai_dir: &Path, model: &ModelInfo) {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hash_existing_covers_the_whole_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("partial.gguf");
+        fs::write(&path, b"ab").unwrap();
+
+        // Resume: hash what's on disk, then the streamed rest
+        let mut hasher = hash_existing(&path).unwrap();
+        hasher.update(b"c");
+        assert_eq!(
+            hex_lower(&hasher.finalize()),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+}
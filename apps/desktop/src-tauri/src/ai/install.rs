@@ -2,6 +2,8 @@
 //!
 //! Drives the download → verify → first-launch install sequence (`start_ai_download` /
 //! `do_download`), its cancellation, the stale-partial cleanup, and `uninstall_ai`.
+//! `install_custom_model` / `uninstall_custom_model` do the same for a GGUF the user
+//! brings (local path or URL), recorded in `AiState::user_models`.
 //! The llama-server *process* lifecycle lives in [`super::server`]; this module only
 //! puts the files in place (or removes them) and then hands off to the server module
 //! for the initial health-checked launch.
@@ -12,11 +14,13 @@ use super::process::kill_and_reap_in_background;
 use super::server::{StartupOutcome, spawn_and_track_server, wait_for_server_health};
 use super::state::{MANAGER, ManagerState, get_ai_dir, get_current_model, save_state};
 use super::{
-    AiExtracting, AiInstallComplete, AiInstalling, AiVerifying, get_default_model, get_model_by_id,
-    is_local_ai_supported,
+    AVAILABLE_MODELS, AiExtracting, AiInstallComplete, AiInstalling, AiVerifying, DEFAULT_MODEL_ID,
+    USER_MODEL_ID_PREFIX, UserModel, get_default_model, get_model_by_id, is_local_ai_supported,
 };
 use crate::ignore_poison::IgnorePoison;
 use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
 use std::time::{SystemTime, UNIX_EPOCH};
 use tauri::{AppHandle, Runtime};
 use tauri_specta::Event as _;
@@ -24,6 +28,12 @@ use tauri_specta::Event as _;
 /// Stale partial downloads older than this are cleaned up at app start.
 const STALE_PARTIAL_SECONDS: u64 = 24 * 60 * 60; // 24 hours
 
+/// GGUF files start with this magic, then a little-endian u32 format version.
+const GGUF_MAGIC: &[u8; 4] = b"GGUF";
+
+/// GGUF format versions llama.cpp reads.
+const SUPPORTED_GGUF_VERSIONS: std::ops::RangeInclusive<u32> = 1..=3;
+
 /// Starts the AI download (binary + model).
 #[tauri::command]
 #[specta::specta]
@@ -57,6 +67,90 @@ pub async fn start_ai_download<R: Runtime>(app: AppHandle<R>) -> Result<(), Stri
     result
 }
 
+/// Installs a user-supplied GGUF from a local path or an http(s) URL, records it in
+/// `AiState::user_models`, and makes it the active local model. URLs go through the
+/// same resumable download (progress events, `cancel_ai_download`) as the built-ins.
+/// Returns the new model's ID.
+#[tauri::command]
+#[specta::specta]
+pub async fn install_custom_model<R: Runtime>(
+    app: AppHandle<R>,
+    path_or_url: String,
+    display_name: String,
+) -> Result<String, String> {
+    if !is_local_ai_supported() {
+        return Err(String::from("Local AI not supported on this hardware"));
+    }
+    let display_name = display_name.trim();
+    if display_name.is_empty() {
+        return Err(String::from("Model name can't be empty"));
+    }
+
+    // Shares the in-progress flag with `start_ai_download`: one model download at a time
+    {
+        let mut manager = MANAGER.lock_ignore_poison();
+        let Some(ref mut m) = *manager else {
+            return Err(String::from("AI manager not initialized"));
+        };
+        if m.download_in_progress {
+            return Err(String::from("Another AI download is in progress"));
+        }
+        m.download_in_progress = true;
+    }
+
+    let result = do_install_custom_model(&app, path_or_url.trim(), display_name).await;
+
+    {
+        let mut manager = MANAGER.lock_ignore_poison();
+        if let Some(ref mut m) = *manager {
+            m.download_in_progress = false;
+        }
+    }
+
+    result
+}
+
+/// Removes a user model's file and entry. Built-in models are refused; removing them is
+/// `uninstall_ai`'s job. If the model is the active one, the server stops and the
+/// installation falls back to the default model (not installed yet).
+#[tauri::command]
+#[specta::specta]
+pub async fn uninstall_custom_model(model_id: String) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || uninstall_custom_model_sync(&model_id))
+        .await
+        .map_err(|e| format!("Uninstall task failed: {e}"))?
+}
+
+fn uninstall_custom_model_sync(model_id: &str) -> Result<(), String> {
+    let mut manager = MANAGER.lock_ignore_poison();
+    let Some(ref mut m) = *manager else {
+        return Err(String::from("AI manager not initialized"));
+    };
+    let Some(index) = m.state.user_models.iter().position(|um| um.id == model_id) else {
+        return Err(format!("No user-installed model with ID {model_id}"));
+    };
+
+    if m.state.installed_model_id == model_id {
+        if let Some(token) = m.start_cancel.take() {
+            token.cancel();
+        }
+        if let Some(pid) = m.child_pid.take() {
+            kill_and_reap_in_background(pid);
+        }
+        m.state.installed = false;
+        m.state.port = None;
+        m.state.pid = None;
+        m.state.model_download_complete = false;
+        m.state.installed_model_id = DEFAULT_MODEL_ID.to_string();
+    }
+
+    let removed = m.state.user_models.remove(index);
+    let _ = fs::remove_file(m.ai_dir.join(&removed.filename));
+    save_state(&m.ai_dir, &m.state);
+    log::info!("AI: uninstalled user model {} ({})", removed.id, removed.display_name);
+    Ok(())
+}
+
 /// Cancels an in-progress download.
 #[tauri::command]
 #[specta::specta]
@@ -84,9 +178,16 @@ fn uninstall_ai_sync() {
         }
 
         // Delete files
-        let model = get_model_by_id(&m.state.installed_model_id).unwrap_or_else(get_default_model);
+        let model =
+            get_model_by_id(&m.state.installed_model_id, &m.state.user_models).unwrap_or_else(get_default_model);
         let _ = fs::remove_file(m.ai_dir.join(LLAMA_SERVER_BINARY));
-        let _ = fs::remove_file(m.ai_dir.join(model.filename));
+        let _ = fs::remove_file(m.ai_dir.join(&*model.filename));
+
+        // A user model's entry goes with its file; the next install starts from the default
+        if let Some(index) = m.state.user_models.iter().position(|um| um.id == model.id) {
+            m.state.user_models.remove(index);
+            m.state.installed_model_id = DEFAULT_MODEL_ID.to_string();
+        }
 
         // Reset state
         m.state.installed = false;
@@ -114,8 +215,9 @@ pub(super) fn cleanup_stale_partial_download(m: &mut ManagerState) {
         .as_secs();
 
     if now.saturating_sub(started) >= STALE_PARTIAL_SECONDS {
-        let model = get_model_by_id(&m.state.installed_model_id).unwrap_or_else(get_default_model);
-        let model_path = m.ai_dir.join(model.filename);
+        let model =
+            get_model_by_id(&m.state.installed_model_id, &m.state.user_models).unwrap_or_else(get_default_model);
+        let model_path = m.ai_dir.join(&*model.filename);
         if model_path.exists() {
             log::debug!(
                 "AI: cleaning up stale partial download (started {} hours ago)",
@@ -132,8 +234,14 @@ async fn do_download<R: Runtime>(app: &AppHandle<R>) -> Result<(), String> {
     let ai_dir = get_ai_dir(app);
     fs::create_dir_all(&ai_dir).map_err(|e| format!("Failed to create AI directory: {e}"))?;
 
-    // Get the model to download (use default for new installs)
+    // Get the model to download (use default for new installs). A user model can't be
+    // re-fetched from here: its source may be a local file.
     let model = get_current_model();
+    let model = if model.id.starts_with(USER_MODEL_ID_PREFIX) {
+        get_default_model()
+    } else {
+        model
+    };
     log::debug!("AI download: using model {} ({})", model.id, model.display_name);
 
     // Reset cancel flag and set the model ID we're installing
@@ -154,12 +262,12 @@ async fn do_download<R: Runtime>(app: &AppHandle<R>) -> Result<(), String> {
 
     // Check if cancelled before starting big download
     if is_cancel_requested() {
-        cleanup_partial(&ai_dir, model);
+        cleanup_partial(&ai_dir, &model);
         return Err(String::from("Download cancelled"));
     }
 
     // Step 2: Download GGUF model - this is the only network download
-    let model_path = ai_dir.join(model.filename);
+    let model_path = ai_dir.join(&*model.filename);
 
     // Track when this partial download started (for stale cleanup)
     {
@@ -174,7 +282,7 @@ async fn do_download<R: Runtime>(app: &AppHandle<R>) -> Result<(), String> {
         }
     }
 
-    download_file(app, model.url, &model_path, is_cancel_requested).await?;
+    download_file(app, &model.url, &model_path, model.sha256, is_cancel_requested).await?;
 
     // Step 3: Verify download integrity by checking file size
     let _ = AiVerifying.emit(app);
@@ -196,6 +304,12 @@ async fn do_download<R: Runtime>(app: &AppHandle<R>) -> Result<(), String> {
 
     log::debug!("AI download: model verified, {} bytes", actual_size);
 
+    finish_install(app, &ai_dir).await
+}
+
+/// Marks the active model installed, starts the server, and emits install complete once
+/// it's healthy. The model file (and binary) must already be in place.
+async fn finish_install<R: Runtime>(app: &AppHandle<R>, ai_dir: &Path) -> Result<(), String> {
     // Mark download as complete and update state
     {
         let mut manager = MANAGER.lock_ignore_poison();
@@ -219,7 +333,7 @@ async fn do_download<R: Runtime>(app: &AppHandle<R>) -> Result<(), String> {
         };
         spawn_and_track_server(m)?
     };
-    match wait_for_server_health(&ai_dir, pid, port, cancel).await {
+    match wait_for_server_health(ai_dir, pid, port, cancel).await {
         StartupOutcome::Ready => {}
         // The user switched away mid-install. The model is installed and state saved;
         // the server start was deliberately abandoned, so this isn't an install failure.
@@ -237,3 +351,191 @@ fn is_cancel_requested() -> bool {
     let manager = MANAGER.lock_ignore_poison();
     manager.as_ref().is_some_and(|m| m.cancel_requested)
 }
+
+async fn do_install_custom_model<R: Runtime>(
+    app: &AppHandle<R>,
+    source: &str,
+    display_name: &str,
+) -> Result<String, String> {
+    let ai_dir = get_ai_dir(app);
+    fs::create_dir_all(&ai_dir).map_err(|e| format!("Failed to create AI directory: {e}"))?;
+
+    let id = {
+        let mut manager = MANAGER.lock_ignore_poison();
+        let Some(ref mut m) = *manager else {
+            return Err(String::from("AI manager not initialized"));
+        };
+        m.cancel_requested = false;
+        unique_user_model_id(display_name, &m.state.user_models)
+    };
+    let filename = format!("{id}.gguf");
+    let model_path = ai_dir.join(&filename);
+    log::debug!("AI: installing user model {id} from {source}");
+
+    if is_url(source) {
+        // Fresh ID, so anything at this path is leftover junk, not a resumable partial
+        let _ = fs::remove_file(&model_path);
+        if let Err(e) = download_file(app, source, &model_path, None, is_cancel_requested).await {
+            let _ = fs::remove_file(&model_path);
+            return Err(e);
+        }
+    } else {
+        let src = PathBuf::from(crate::commands::file_system::expand_tilde(source));
+        if !src.is_file() {
+            return Err(format!("Not a file: {source}"));
+        }
+        let dest = model_path.clone();
+        tauri::async_runtime::spawn_blocking(move || link_or_copy(&src, &dest))
+            .await
+            .map_err(|e| format!("Copy task failed: {e}"))?
+            .map_err(|e| format!("Failed to copy the model file: {e}"))?;
+    }
+
+    let _ = AiVerifying.emit(app);
+    let size_bytes = match check_gguf_header(&model_path) {
+        Ok(()) => fs::metadata(&model_path)
+            .map(|meta| meta.len())
+            .map_err(|e| format!("Failed to read model file: {e}"))?,
+        Err(e) => {
+            let _ = fs::remove_file(&model_path);
+            return Err(e);
+        }
+    };
+
+    // Record it and make it active. The old model's server (if any) stops; its file stays.
+    {
+        let mut manager = MANAGER.lock_ignore_poison();
+        let Some(ref mut m) = *manager else {
+            return Err(String::from("AI manager not initialized"));
+        };
+        if let Some(token) = m.start_cancel.take() {
+            token.cancel();
+        }
+        if let Some(pid) = m.child_pid.take() {
+            kill_and_reap_in_background(pid);
+        }
+        m.state.user_models.push(UserModel {
+            id: id.clone(),
+            display_name: display_name.to_string(),
+            filename,
+            source: source.to_string(),
+            size_bytes,
+        });
+        m.state.installed_model_id = id.clone();
+        save_state(&m.ai_dir, &m.state);
+    }
+
+    let binary_path = ai_dir.join(LLAMA_SERVER_BINARY);
+    if !binary_path.exists() {
+        let _ = AiExtracting.emit(app);
+        extract_bundled_llama_server(app, &ai_dir)?;
+    }
+
+    finish_install(app, &ai_dir).await?;
+    Ok(id)
+}
+
+fn is_url(source: &str) -> bool {
+    source.starts_with("https://") || source.starts_with("http://")
+}
+
+/// Hard-links when possible (instant, no extra disk for a multi-GB file), else copies.
+/// Uninstalling removes only our link, never the user's original.
+fn link_or_copy(src: &Path, dest: &Path) -> std::io::Result<()> {
+    let _ = fs::remove_file(dest);
+    fs::hard_link(src, dest).or_else(|_| fs::copy(src, dest).map(|_| ()))
+}
+
+/// Checks that `path` starts with a GGUF header of a version llama.cpp reads.
+fn check_gguf_header(path: &Path) -> Result<(), String> {
+    let mut header = [0u8; 8];
+    fs::File::open(path)
+        .and_then(|mut f| f.read_exact(&mut header))
+        .map_err(|_| String::from("Not a GGUF model file (too short or unreadable)"))?;
+    if &header[..4] != GGUF_MAGIC {
+        return Err(String::from("Not a GGUF model file"));
+    }
+    let version = u32::from_le_bytes([header[4], header[5], header[6], header[7]]);
+    if !SUPPORTED_GGUF_VERSIONS.contains(&version) {
+        return Err(format!("Unsupported GGUF version {version}"));
+    }
+    Ok(())
+}
+
+/// `user-<slug of display_name>`, with a `-2`, `-3`… suffix if taken.
+fn unique_user_model_id(display_name: &str, user_models: &[UserModel]) -> String {
+    let mut slug = String::new();
+    for c in display_name.chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    let slug = slug.trim_end_matches('-');
+    let base = format!("{USER_MODEL_ID_PREFIX}{}", if slug.is_empty() { "model" } else { slug });
+
+    let taken = |id: &str| AVAILABLE_MODELS.iter().any(|m| m.id == id) || user_models.iter().any(|m| m.id == id);
+    if !taken(&base) {
+        return base;
+    }
+    (2..)
+        .map(|n| format!("{base}-{n}"))
+        .find(|id| !taken(id))
+        .expect("unbounded suffix search always finds a free ID")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn user_model(id: &str) -> UserModel {
+        UserModel {
+            id: id.to_string(),
+            display_name: String::from("Mine"),
+            filename: format!("{id}.gguf"),
+            source: String::from("/models/mine.gguf"),
+            size_bytes: 1_000,
+        }
+    }
+
+    #[test]
+    fn test_gguf_header_check() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("m.gguf");
+
+        fs::write(&path, b"GGUF\x03\x00\x00\x00rest").unwrap();
+        assert!(check_gguf_header(&path).is_ok());
+
+        fs::write(&path, b"GGUF\x09\x00\x00\x00").unwrap();
+        assert!(check_gguf_header(&path).is_err());
+
+        fs::write(&path, b"<html>not a model</html>").unwrap();
+        assert!(check_gguf_header(&path).is_err());
+
+        fs::write(&path, b"GGU").unwrap();
+        assert!(check_gguf_header(&path).is_err());
+    }
+
+    #[test]
+    fn test_unique_user_model_id() {
+        assert_eq!(unique_user_model_id("My Llama 8B!", &[]), "user-my-llama-8b");
+        assert_eq!(unique_user_model_id("   ", &[]), "user-model");
+
+        let existing = [user_model("user-mine"), user_model("user-mine-2")];
+        assert_eq!(unique_user_model_id("Mine", &existing), "user-mine-3");
+    }
+
+    #[test]
+    fn test_get_model_by_id_resolves_user_models() {
+        let users = [user_model("user-mine")];
+        let model = get_model_by_id("user-mine", &users).unwrap();
+        assert_eq!(model.filename, "user-mine.gguf");
+        assert_eq!(model.size_bytes, 1_000);
+        assert!(model.base_overhead_bytes > model.size_bytes);
+
+        // Built-ins still resolve, and a user model is invisible without its list
+        assert!(get_model_by_id(DEFAULT_MODEL_ID, &users).is_some());
+        assert!(get_model_by_id("user-mine", &[]).is_none());
+    }
+}
@@ -103,6 +103,10 @@ pub enum JobKind {
     AgentChat,
     /// Folder-name suggestions.
     FolderSuggestions,
+    /// Rename candidates for one file.
+    RenameSuggestions,
+    /// A proposed folder organization plan.
+    FolderPlan,
     /// Natural-language search translation.
     TranslateSearch,
     /// "Ask about selection" translation.
@@ -115,6 +119,8 @@ impl JobKind {
         match self {
             JobKind::AgentChat => "agent-chat",
             JobKind::FolderSuggestions => "folder-suggestions",
+            JobKind::RenameSuggestions => "rename-suggestions",
+            JobKind::FolderPlan => "folder-plan",
             JobKind::TranslateSearch => "translate-search",
             JobKind::TranslateSelection => "translate-selection",
         }
@@ -148,6 +154,16 @@ impl LlmLogContext {
         Self::one_shot(JobKind::FolderSuggestions)
     }
 
+    /// A rename-suggestions call.
+    pub fn rename_suggestions() -> Self {
+        Self::one_shot(JobKind::RenameSuggestions)
+    }
+
+    /// A folder-organization-plan call.
+    pub fn folder_plan() -> Self {
+        Self::one_shot(JobKind::FolderPlan)
+    }
+
     /// A natural-language-search translation call.
     pub fn translate_search() -> Self {
         Self::one_shot(JobKind::TranslateSearch)
@@ -19,7 +19,7 @@
 //!
 //! Uses `is_local_ai_supported()` to gate local-only operations (requires Apple Silicon).
 
-use super::process::{kill_process, kill_stale_llama_servers};
+use super::process::{AiServerConfig, kill_process, kill_stale_llama_servers};
 use super::state::{
     MANAGER, get_ai_dir, get_cloud_config, get_current_model, get_port, is_fully_installed, load_state,
     new_manager_state, save_state,
@@ -355,7 +355,7 @@ pub fn get_ai_runtime_status() -> AiRuntimeStatus {
     }
 }
 
-/// Stores provider + context size + OpenAI config in manager state.
+/// Stores provider + llama-server launch parameters + OpenAI config in manager state.
 /// If provider is `local` and model is installed and hardware is supported, starts the server
 /// in a background task. If provider is NOT `local` and a server is running, stops it.
 /// Returns immediately.
@@ -364,14 +364,14 @@ pub fn get_ai_runtime_status() -> AiRuntimeStatus {
 pub fn configure_ai<R: Runtime>(
     app: AppHandle<R>,
     provider: String,
-    context_size: u32,
+    server_config: AiServerConfig,
     cloud_api_key: String,
     cloud_base_url: String,
     cloud_model: String,
     cloud_requires_api_key: bool,
 ) -> Result<(), String> {
     log::debug!(
-        "AI configure: provider={provider}, context_size={context_size}, base_url={cloud_base_url}, model={cloud_model}, requires_api_key={cloud_requires_api_key}"
+        "AI configure: provider={provider}, server_config={server_config:?}, base_url={cloud_base_url}, model={cloud_model}, requires_api_key={cloud_requires_api_key}"
     );
 
     // Guard the BYOK key against plaintext exfiltration before we store config that
@@ -381,6 +381,15 @@ pub fn configure_ai<R: Runtime>(
         super::connection_check::validate_ai_base_url(&cloud_base_url, &cloud_api_key)?;
     }
 
+    // A bad value in settings (hand-edited file, older build) mustn't keep the server down
+    let server_config = match server_config.validate() {
+        Ok(()) => server_config,
+        Err(e) => {
+            log::warn!("AI configure: invalid server config ({e}), using defaults");
+            AiServerConfig::default()
+        }
+    };
+
     // Single lock: decide, stop, spawn (no race window for orphan processes)
     let spawn_result;
     {
@@ -404,7 +413,7 @@ pub fn configure_ai<R: Runtime>(
         }
 
         m.provider = provider.clone();
-        m.context_size = context_size;
+        m.server_config = server_config;
         m.cloud_api_key = cloud_api_key;
         m.cloud_base_url = cloud_base_url;
         m.cloud_model = cloud_model;
@@ -13,8 +13,13 @@
 //! Available models are defined in [`AVAILABLE_MODELS`]. To add a new model:
 //! 1. Find the GGUF file on HuggingFace
 //! 2. Get the exact file size: `curl -sIL "<url>" | grep -i content-length`
-//! 3. Add a new entry to `AVAILABLE_MODELS`
-//! 4. Update `DEFAULT_MODEL_ID` if the new model should be the default
+//! 3. Get its SHA-256: `curl -sIL "<url>" | grep -i x-linked-etag`
+//! 4. Add a new entry to `AVAILABLE_MODELS`
+//! 5. Update `DEFAULT_MODEL_ID` if the new model should be the default
+//!
+//! Users can also install their own GGUF (`install_custom_model`). Those live in
+//! [`AiState::user_models`], never in `AVAILABLE_MODELS`, and [`get_model_by_id`]
+//! resolves both.
 
 pub mod api_keys;
 pub mod client;
@@ -39,6 +44,7 @@ pub mod extract;
 pub mod install;
 pub mod llm_log;
 pub mod manager;
+pub mod plan;
 mod process;
 pub mod server;
 pub mod state;
@@ -51,6 +57,8 @@ pub mod translate_error;
 
 pub use translate_error::{AiTranslateError, AiTranslateErrorKind};
 
+use std::borrow::Cow;
+
 use serde::{Deserialize, Serialize};
 
 /// Returns true if local AI features (llama-server) can run on this hardware.
@@ -103,6 +111,10 @@ pub struct AiServerReady;
 #[derive(Debug, Clone, Serialize, Deserialize, specta::Type, tauri_specta::Event)]
 pub struct AiVerifying;
 
+/// A downloaded model's SHA-256 didn't match; the file was deleted.
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type, tauri_specta::Event)]
+pub struct AiIntegrityFailed;
+
 #[derive(Debug, Clone, Serialize, Deserialize, specta::Type, tauri_specta::Event)]
 pub struct AiInstalling;
 
@@ -116,16 +128,21 @@ pub struct AiExtracting;
 // Model registry
 // ============================================================================
 
-/// Information about an available AI model.
+/// Information about an available AI model. Built-ins borrow their strings; user models
+/// (see [`UserModel`]) own theirs.
 #[derive(Debug, Clone)]
 pub struct ModelInfo {
-    pub id: &'static str,
-    pub display_name: &'static str,
+    pub id: Cow<'static, str>,
+    pub display_name: Cow<'static, str>,
     /// GGUF filename stored locally.
-    pub filename: &'static str,
-    pub url: &'static str,
+    pub filename: Cow<'static, str>,
+    /// Download URL, or for a user model the local path or URL it was installed from.
+    pub url: Cow<'static, str>,
     /// For download verification. Get via: `curl -sIL "<url>" | grep -i content-length`
     pub size_bytes: u64,
+    /// Lowercase hex SHA-256 of the GGUF, checked as it downloads. Hugging Face serves it as
+    /// the LFS ETag: `curl -sIL "<url>" | grep -i x-linked-etag`. `None` skips the check.
+    pub sha256: Option<&'static str>,
     /// Bytes per token for KV cache (used for memory estimation).
     /// Derived from empirical measurement: ctx_size * kv_bytes_per_token = KV cache size.
     pub kv_bytes_per_token: u64,
@@ -137,20 +154,24 @@ pub struct ModelInfo {
 /// The first model in the list with `id == DEFAULT_MODEL_ID` is the default.
 pub const AVAILABLE_MODELS: &[ModelInfo] = &[
     ModelInfo {
-        id: "ministral-3b-instruct-q4km",
-        display_name: "Ministral 3B",
-        filename: "ministral-3b-instruct-q4km.gguf",
-        url: "https://huggingface.co/mistralai/Ministral-3-3B-Instruct-2512-GGUF/resolve/main/Ministral-3-3B-Instruct-2512-Q4_K_M.gguf",
+        id: Cow::Borrowed("ministral-3b-instruct-q4km"),
+        display_name: Cow::Borrowed("Ministral 3B"),
+        filename: Cow::Borrowed("ministral-3b-instruct-q4km.gguf"),
+        url: Cow::Borrowed(
+            "https://huggingface.co/mistralai/Ministral-3-3B-Instruct-2512-GGUF/resolve/main/Ministral-3-3B-Instruct-2512-Q4_K_M.gguf",
+        ),
         size_bytes: 2_147_023_008,          // ~2.0 GB
+        sha256: None,                       // TODO: pin (see the `sha256` field doc)
         kv_bytes_per_token: 106_496,        // ~0.1016 MiB per token
         base_overhead_bytes: 3_500_000_000, // ~3.5 GB (model weights + compute buffers)
     },
     ModelInfo {
-        id: "falcon-h1r-7b-q4km",
-        display_name: "Falcon H1R 7B",
-        filename: "falcon-h1r-7b-q4km.gguf",
-        url: "https://huggingface.co/tiiuae/Falcon-H1R-7B-GGUF/resolve/main/Falcon-H1R-7B-Q4_K_M.gguf",
+        id: Cow::Borrowed("falcon-h1r-7b-q4km"),
+        display_name: Cow::Borrowed("Falcon H1R 7B"),
+        filename: Cow::Borrowed("falcon-h1r-7b-q4km.gguf"),
+        url: Cow::Borrowed("https://huggingface.co/tiiuae/Falcon-H1R-7B-GGUF/resolve/main/Falcon-H1R-7B-Q4_K_M.gguf"),
         size_bytes: 4_598_343_712, // ~4.28 GB
+        sha256: None,              // TODO: pin (see the `sha256` field doc)
         kv_bytes_per_token: 106_496,
         base_overhead_bytes: 3_500_000_000,
     },
@@ -160,14 +181,63 @@ pub const AVAILABLE_MODELS: &[ModelInfo] = &[
 /// When adding a newer/better model, update this to make it the new default.
 pub const DEFAULT_MODEL_ID: &str = "ministral-3b-instruct-q4km";
 
-/// Returns the model info for the given ID, or None if not found.
-pub fn get_model_by_id(id: &str) -> Option<&'static ModelInfo> {
-    AVAILABLE_MODELS.iter().find(|m| m.id == id)
+/// Prefix of every user model ID, so one can never shadow a built-in.
+pub const USER_MODEL_ID_PREFIX: &str = "user-";
+
+/// KV cache size per token assumed for user models. The real figure depends on the
+/// architecture, which we don't parse, so this reuses the built-ins' measurement.
+const USER_MODEL_KV_BYTES_PER_TOKEN: u64 = 106_496;
+
+/// Compute buffers on top of the weights for user models (estimate; the built-ins
+/// measure ~1.5 GB beyond their file size).
+const USER_MODEL_COMPUTE_OVERHEAD_BYTES: u64 = 1_500_000_000;
+
+/// Returns the model info for the given ID, or None if not found. Checks the built-ins
+/// first, then `user_models` (pass `&state.user_models`; the caller usually already
+/// holds the manager lock).
+pub fn get_model_by_id(id: &str, user_models: &[UserModel]) -> Option<ModelInfo> {
+    AVAILABLE_MODELS
+        .iter()
+        .find(|m| m.id == id)
+        .cloned()
+        .or_else(|| user_models.iter().find(|m| m.id == id).map(UserModel::to_model_info))
 }
 
 /// Returns the default model info.
-pub fn get_default_model() -> &'static ModelInfo {
-    get_model_by_id(DEFAULT_MODEL_ID).expect("DEFAULT_MODEL_ID must exist in AVAILABLE_MODELS")
+pub fn get_default_model() -> ModelInfo {
+    get_model_by_id(DEFAULT_MODEL_ID, &[]).expect("DEFAULT_MODEL_ID must exist in AVAILABLE_MODELS")
+}
+
+/// A GGUF the user installed from a local path or URL. Persisted in
+/// [`AiState::user_models`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct UserModel {
+    /// Starts with [`USER_MODEL_ID_PREFIX`].
+    pub id: String,
+    pub display_name: String,
+    /// GGUF filename in the AI directory.
+    pub filename: String,
+    /// The local path or URL it was installed from.
+    pub source: String,
+    /// Actual size on disk, measured at install time.
+    pub size_bytes: u64,
+}
+
+impl UserModel {
+    fn to_model_info(&self) -> ModelInfo {
+        ModelInfo {
+            id: Cow::Owned(self.id.clone()),
+            display_name: Cow::Owned(self.display_name.clone()),
+            filename: Cow::Owned(self.filename.clone()),
+            url: Cow::Owned(self.source.clone()),
+            size_bytes: self.size_bytes,
+            // Already on disk when recorded, and we have no trusted digest to compare with
+            sha256: None,
+            kv_bytes_per_token: USER_MODEL_KV_BYTES_PER_TOKEN,
+            base_overhead_bytes: self.size_bytes + USER_MODEL_COMPUTE_OVERHEAD_BYTES,
+        }
+    }
 }
 
 /// Persisted AI state (stored in ai-state.json).
@@ -190,6 +260,9 @@ pub struct AiState {
     /// Unix timestamp, for stale cleanup.
     #[serde(default)]
     pub partial_download_started: Option<u64>,
+    /// Models the user installed themselves. The built-in list is `AVAILABLE_MODELS`.
+    #[serde(default)]
+    pub user_models: Vec<UserModel>,
 }
 
 fn default_model_id() -> String {
@@ -206,6 +279,7 @@ impl Default for AiState {
             dismissed_until: None,
             model_download_complete: false,
             partial_download_started: None,
+            user_models: Vec::new(),
         }
     }
 }
@@ -0,0 +1,591 @@
+//! Folder organization plans: the model proposes, the user confirms, the app applies.
+//!
+//! `propose_folder_plan` asks the AI for a JSON plan ("create folder X, move files matching Y
+//! into it"), parses it into typed [`PlannedAction`]s, and stages it under an opaque plan id.
+//! Nothing touches the disk until `apply_folder_plan`, which re-checks the plan against the
+//! folder as it is NOW and runs it through the managed create / rename / move pipelines, so
+//! each step is journaled and undoable like a user action.
+//!
+//! Every name a plan refers to is a single entry directly inside the pane's folder. An absolute
+//! path is accepted only when its parent is that folder; anything else (`..`, a nested path,
+//! another folder) rejects the whole plan. Local folders only: moves go through the local
+//! `move_files_start`.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+use genai::chat::ChatOptions;
+use regex::RegexBuilder;
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use super::suggestions::SuggestionContext;
+use crate::ai::llm_log::LlmLogContext;
+use crate::file_system::validation::validate_filename;
+use crate::file_system::write_operations::{create_directory_managed, rename_managed};
+use crate::file_system::{OperationEventSink, TauriEventSink, WriteOperationConfig, move_files_start};
+use crate::ignore_poison::IgnorePoison;
+use crate::operation_log::types::Initiator;
+use crate::search::query::glob_to_regex;
+
+/// More steps than this is a reorganization, not a plan to eyeball before confirming.
+const MAX_ACTIONS: usize = 50;
+/// A staged plan is applied within this window or dropped.
+const PLAN_TTL: Duration = Duration::from_secs(15 * 60);
+
+const PLAN_SYSTEM_PROMPT: &str =
+    "You organize one folder of a file manager. Reply with a single JSON object and nothing else.";
+
+/// One step of a confirmed plan. Names are entries directly inside the plan's folder.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, specta::Type)]
+#[serde(tag = "kind", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum PlannedAction {
+    /// Create an empty folder.
+    CreateFolder { name: String },
+    /// Move these entries into `destination`, an existing folder or one the plan creates
+    /// earlier. A pattern in the model's output is resolved to names when the plan is parsed.
+    Move { sources: Vec<String>, destination: String },
+    /// Rename an entry in place.
+    Rename { from: String, to: String },
+}
+
+/// A staged plan as the preview shows it.
+#[derive(Debug, Clone, Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct FolderPlan {
+    pub plan_id: String,
+    pub actions: Vec<PlannedAction>,
+}
+
+/// What `apply_folder_plan` got through. Moves are queued write operations that finish in the
+/// background; `error` is set when a step failed and the rest were skipped.
+#[derive(Debug, Clone, Default, Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct FolderPlanOutcome {
+    pub applied: usize,
+    pub move_operation_ids: Vec<String>,
+    pub error: Option<String>,
+}
+
+/// The model's wire shape, before names are checked and patterns resolved.
+#[derive(Debug, Deserialize)]
+struct RawPlan {
+    actions: Vec<RawAction>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+enum RawAction {
+    CreateFolder {
+        name: String,
+    },
+    Move {
+        #[serde(default)]
+        files: Vec<String>,
+        #[serde(default)]
+        pattern: Option<String>,
+        into: String,
+    },
+    Rename {
+        from: String,
+        to: String,
+    },
+}
+
+/// An entry directly inside the plan's folder.
+#[derive(Debug, Clone)]
+struct ScopeEntry {
+    name: String,
+    is_dir: bool,
+}
+
+/// The folder a plan is confined to, as it is on disk.
+#[derive(Debug)]
+struct PlanScope {
+    dir: PathBuf,
+    entries: Vec<ScopeEntry>,
+    /// Whether a pattern may match dotfiles (mirrors the pane's hidden-files setting).
+    include_hidden: bool,
+}
+
+impl PlanScope {
+    /// Reads `dir` directly. Not the listing cache: apply has to see the folder as it is now,
+    /// and a pattern has to match every entry, not only the rows the pane has loaded.
+    fn read(dir: &Path, include_hidden: bool) -> Result<Self, String> {
+        let read_dir = std::fs::read_dir(dir).map_err(|e| format!("Couldn't read {}: {e}", dir.display()))?;
+        let entries = read_dir
+            .filter_map(Result::ok)
+            .map(|entry| ScopeEntry {
+                name: entry.file_name().to_string_lossy().into_owned(),
+                // `file_type` doesn't follow symlinks, so a symlinked folder is never a move target.
+                is_dir: entry.file_type().is_ok_and(|t| t.is_dir()),
+            })
+            .collect();
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            entries,
+            include_hidden,
+        })
+    }
+}
+
+/// Pulls the JSON object out of a model reply: a fenced block if there is one, else the span
+/// from the first `{` to the last `}`.
+fn extract_json(response: &str) -> Option<&str> {
+    if let Some(start) = response.find("```") {
+        let body = &response[start + 3..];
+        let body = body.strip_prefix("json").unwrap_or(body);
+        if let Some(end) = body.find("```") {
+            return Some(body[..end].trim());
+        }
+    }
+    let start = response.find('{')?;
+    let end = response.rfind('}')?;
+    (start < end).then(|| &response[start..=end])
+}
+
+/// Parses a model reply into validated actions for `scope`. Any invalid step rejects the
+/// whole plan: a half-plan the user didn't ask for is worse than none.
+fn parse_plan(response: &str, scope: &PlanScope) -> Result<Vec<PlannedAction>, String> {
+    let json = extract_json(response).ok_or("The AI didn't return a plan")?;
+    let raw: RawPlan = serde_json::from_str(json).map_err(|e| format!("The AI returned a malformed plan: {e}"))?;
+    if raw.actions.is_empty() {
+        return Err("The AI's plan has no steps".to_string());
+    }
+    if raw.actions.len() > MAX_ACTIONS {
+        return Err(format!(
+            "The AI's plan has {} steps; at most {MAX_ACTIONS} are allowed",
+            raw.actions.len()
+        ));
+    }
+
+    let mut actions = Vec::with_capacity(raw.actions.len());
+    for action in raw.actions {
+        actions.push(match action {
+            RawAction::CreateFolder { name } => PlannedAction::CreateFolder {
+                name: entry_name(&name, &scope.dir)?,
+            },
+            RawAction::Rename { from, to } => PlannedAction::Rename {
+                from: entry_name(&from, &scope.dir)?,
+                to: entry_name(&to, &scope.dir)?,
+            },
+            RawAction::Move { files, pattern, into } => {
+                let destination = entry_name(&into, &scope.dir)?;
+                let mut sources = files
+                    .iter()
+                    .map(|file| entry_name(file, &scope.dir))
+                    .collect::<Result<Vec<_>, _>>()?;
+                if let Some(pattern) = pattern {
+                    sources.extend(match_pattern(&pattern, scope, &destination)?);
+                }
+                let mut seen = HashSet::new();
+                sources.retain(|name| seen.insert(name.to_lowercase()));
+                if sources.is_empty() {
+                    return Err(format!("A move into \"{destination}\" matches nothing"));
+                }
+                PlannedAction::Move { sources, destination }
+            }
+        });
+    }
+    check_plan(&actions, scope)?;
+    Ok(actions)
+}
+
+/// Normalizes a name the model gave to a single entry name inside `dir`, or rejects it.
+fn entry_name(raw: &str, dir: &Path) -> Result<String, String> {
+    let raw = raw.trim();
+    let name = if raw.starts_with('/') {
+        let path = Path::new(raw);
+        if path.parent() != Some(dir) {
+            return Err(format!("\"{raw}\" is outside the current pane"));
+        }
+        path.file_name().and_then(|n| n.to_str()).unwrap_or_default()
+    } else {
+        raw.strip_prefix("./").unwrap_or(raw)
+    };
+    if name.contains('/') || name == "." || name == ".." {
+        return Err(format!("\"{raw}\" is outside the current pane"));
+    }
+    validate_filename(name).map_err(|e| format!("\"{raw}\": {e}"))?;
+    Ok(name.to_string())
+}
+
+/// Entries matching a `*` / `?` glob, case-insensitively. Never matches `destination` itself,
+/// and dotfiles only when the pane shows them.
+fn match_pattern(pattern: &str, scope: &PlanScope, destination: &str) -> Result<Vec<String>, String> {
+    if pattern.contains('/') {
+        return Err(format!("Pattern \"{pattern}\" is outside the current pane"));
+    }
+    let regex = RegexBuilder::new(&glob_to_regex(pattern))
+        .case_insensitive(true)
+        .build()
+        .map_err(|e| format!("Pattern \"{pattern}\" is invalid: {e}"))?;
+    Ok(scope
+        .entries
+        .iter()
+        .filter(|entry| scope.include_hidden || !entry.name.starts_with('.'))
+        .filter(|entry| !entry.name.eq_ignore_ascii_case(destination))
+        .filter(|entry| regex.is_match(&entry.name))
+        .map(|entry| entry.name.clone())
+        .collect())
+}
+
+/// What a name refers to while a plan is simulated.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Slot {
+    File,
+    Folder,
+    /// A folder something moves into. Can't be renamed or moved afterwards.
+    Destination,
+    /// Moved away. The name stays taken so applying creates and renames before moves (see
+    /// [`apply_folder_plan`]) can't collide with an entry still waiting to move.
+    Gone,
+}
+
+/// Simulates the plan over `scope` (names case-insensitively, like the default macOS volume)
+/// and rejects the first step that can't run.
+fn check_plan(actions: &[PlannedAction], scope: &PlanScope) -> Result<(), String> {
+    let mut slots: HashMap<String, Slot> = scope
+        .entries
+        .iter()
+        .map(|entry| {
+            let slot = if entry.is_dir { Slot::Folder } else { Slot::File };
+            (entry.name.to_lowercase(), slot)
+        })
+        .collect();
+
+    for action in actions {
+        match action {
+            PlannedAction::CreateFolder { name } => {
+                if slots.contains_key(&name.to_lowercase()) {
+                    return Err(format!("\"{name}\" already exists"));
+                }
+                slots.insert(name.to_lowercase(), Slot::Folder);
+            }
+            PlannedAction::Rename { from, to } => {
+                let slot = match slots.get(&from.to_lowercase()) {
+                    Some(Slot::File) => Slot::File,
+                    Some(Slot::Folder) => Slot::Folder,
+                    Some(Slot::Destination) => return Err(format!("\"{from}\" is a move target and can't be renamed")),
+                    Some(Slot::Gone) | None => return Err(format!("\"{from}\" doesn't exist")),
+                };
+                if !from.eq_ignore_ascii_case(to) && slots.contains_key(&to.to_lowercase()) {
+                    return Err(format!("\"{to}\" already exists"));
+                }
+                slots.remove(&from.to_lowercase());
+                slots.insert(to.to_lowercase(), slot);
+            }
+            PlannedAction::Move { sources, destination } => {
+                match slots.get(&destination.to_lowercase()) {
+                    Some(Slot::Folder | Slot::Destination) => {}
+                    Some(Slot::File) => return Err(format!("\"{destination}\" isn't a folder")),
+                    Some(Slot::Gone) | None => return Err(format!("\"{destination}\" doesn't exist")),
+                }
+                for source in sources {
+                    match slots.get(&source.to_lowercase()) {
+                        _ if source.eq_ignore_ascii_case(destination) => {
+                            return Err(format!("\"{source}\" can't move into itself"));
+                        }
+                        Some(Slot::File | Slot::Folder) => {}
+                        Some(Slot::Destination) => return Err(format!("\"{source}\" is a move target and can't move")),
+                        Some(Slot::Gone) | None => return Err(format!("\"{source}\" doesn't exist")),
+                    }
+                    slots.insert(source.to_lowercase(), Slot::Gone);
+                }
+                slots.insert(destination.to_lowercase(), Slot::Destination);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn build_prompt(context: &SuggestionContext, request: &str) -> String {
+    let description = context.describe();
+    format!(
+        "The user wants this folder reorganized: {request}\n\
+         \n\
+         {description}\
+         Reply with JSON like {{\"actions\": [...]}}. Each action is one of:\n\
+         {{\"kind\": \"createFolder\", \"name\": \"Photos\"}}\n\
+         {{\"kind\": \"move\", \"files\": [\"a.jpg\"], \"pattern\": \"*.png\", \"into\": \"Photos\"}} \
+         (give files, a pattern, or both)\n\
+         {{\"kind\": \"rename\", \"from\": \"old name\", \"to\": \"new name\"}}\n\
+         Use plain names of items directly in this folder, never paths. Create a folder before moving into it. \
+         Keep the plan to what the user asked for."
+    )
+}
+
+struct StoredPlan {
+    /// The pane's volume, so the creates and renames journal against it.
+    volume_id: String,
+    dir: PathBuf,
+    include_hidden: bool,
+    actions: Vec<PlannedAction>,
+    expires_at: Instant,
+}
+
+static PLANS: LazyLock<Mutex<HashMap<String, StoredPlan>>> = LazyLock::new(|| Mutex::new(HashMap::new()));
+
+fn stage(plan: StoredPlan) -> String {
+    let plan_id = uuid::Uuid::new_v4().to_string();
+    let mut plans = PLANS.lock_ignore_poison();
+    plans.retain(|_, stored| stored.expires_at > Instant::now());
+    plans.insert(plan_id.clone(), plan);
+    plan_id
+}
+
+/// Removes a plan; an expired one is indistinguishable from a missing id.
+fn take(plan_id: &str) -> Option<StoredPlan> {
+    let stored = PLANS.lock_ignore_poison().remove(plan_id)?;
+    (stored.expires_at > Instant::now()).then_some(stored)
+}
+
+/// Asks the AI for a plan that does what `request` says to the folder at `current_path` on
+/// `volume_id`, and stages it for preview. Unlike suggestions this surfaces errors: the user asked for it.
+#[tauri::command]
+#[specta::specta]
+pub async fn propose_folder_plan(
+    app: AppHandle,
+    listing_id: String,
+    volume_id: String,
+    current_path: String,
+    include_hidden: bool,
+    request: String,
+) -> Result<FolderPlan, String> {
+    let Some(backend) = super::manager::resolve_backend().ready_or_log("AI folder plan") else {
+        return Err("AI isn't available. Check your AI settings.".to_string());
+    };
+
+    let context = SuggestionContext::assemble(&app, &listing_id, &current_path, include_hidden).await;
+    let prompt = build_prompt(&context, request.trim());
+    log::trace!("AI folder plan: prompt:\n{prompt}");
+
+    let options = ChatOptions::default().with_temperature(0.2).with_max_tokens(1500);
+    let backend = backend.with_log_context(LlmLogContext::folder_plan());
+    let response = super::client::chat_completion_with_empty_retry(&backend, PLAN_SYSTEM_PROMPT, &prompt, &options)
+        .await
+        .map_err(|e| {
+            log::warn!("AI folder plan: AI call failed: {e}");
+            format!("Couldn't get a plan from the AI: {e}")
+        })?;
+    log::trace!("AI folder plan: raw response:\n{response}");
+
+    let dir = PathBuf::from(&current_path);
+    let actions = tokio::task::spawn_blocking(move || {
+        let scope = PlanScope::read(&dir, include_hidden)?;
+        parse_plan(&response, &scope)
+    })
+    .await
+    .map_err(|e| format!("Couldn't check the plan: {e}"))??;
+    log::debug!("AI folder plan: staged {} actions for {current_path}", actions.len());
+
+    let plan_id = stage(StoredPlan {
+        volume_id,
+        dir: PathBuf::from(&current_path),
+        include_hidden,
+        actions: actions.clone(),
+        expires_at: Instant::now() + PLAN_TTL,
+    });
+    Ok(FolderPlan { plan_id, actions })
+}
+
+/// Applies a staged plan after the user confirmed it. The plan is consumed first, so a second
+/// click can't replay it. It's re-checked against the folder as it is now (the user may have
+/// changed it while the preview was open); then creates and renames run in order, and the moves
+/// are queued last, once every folder they target exists.
+#[tauri::command]
+#[specta::specta]
+pub async fn apply_folder_plan(app: AppHandle, plan_id: String) -> Result<FolderPlanOutcome, String> {
+    let plan = take(&plan_id).ok_or("This plan has expired. Ask again.")?;
+    let (dir, include_hidden) = (plan.dir.clone(), plan.include_hidden);
+    let scope = tokio::task::spawn_blocking(move || PlanScope::read(&dir, include_hidden))
+        .await
+        .map_err(|e| format!("Couldn't check the plan: {e}"))??;
+    check_plan(&plan.actions, &scope).map_err(|e| format!("The folder changed since the plan was made: {e}"))?;
+
+    let mut outcome = FolderPlanOutcome::default();
+    let (moves, steps): (Vec<_>, Vec<_>) = plan
+        .actions
+        .into_iter()
+        .partition(|action| matches!(action, PlannedAction::Move { .. }));
+
+    for action in steps {
+        let result = match action {
+            PlannedAction::CreateFolder { name } => create_directory_managed(
+                Some(plan.volume_id.clone()),
+                plan.dir.to_string_lossy().into_owned(),
+                name,
+                Initiator::Agent,
+            )
+            .await
+            .map(|_| ()),
+            PlannedAction::Rename { from, to } => {
+                rename_managed(
+                    plan.dir.join(from),
+                    plan.dir.join(to),
+                    false,
+                    plan.volume_id.clone(),
+                    Initiator::Agent,
+                )
+                .await
+            }
+            PlannedAction::Move { .. } => unreachable!("moves were partitioned out"),
+        };
+        if let Err(e) = result {
+            outcome.error = Some(e);
+            return Ok(outcome);
+        }
+        outcome.applied += 1;
+    }
+
+    let events: Arc<dyn OperationEventSink> = Arc::new(TauriEventSink::new(app));
+    for action in moves {
+        let PlannedAction::Move { sources, destination } = action else {
+            continue;
+        };
+        let sources = sources.iter().map(|name| plan.dir.join(name)).collect();
+        match move_files_start(
+            events.clone(),
+            sources,
+            plan.dir.join(destination),
+            WriteOperationConfig::default(),
+            vec![],
+            None,
+            Initiator::Agent,
+        )
+        .await
+        {
+            Ok(started) => {
+                outcome.applied += 1;
+                outcome.move_operation_ids.push(started.operation_id);
+            }
+            Err(e) => {
+                outcome.error = Some(format!("Couldn't start a move: {}", e.code()));
+                return Ok(outcome);
+            }
+        }
+    }
+    Ok(outcome)
+}
+
+/// Drops a staged plan the user dismissed.
+#[tauri::command]
+#[specta::specta]
+pub fn cancel_folder_plan(plan_id: String) {
+    take(&plan_id);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scope(entries: &[(&str, bool)]) -> PlanScope {
+        PlanScope {
+            dir: PathBuf::from("/Users/test/Downloads"),
+            entries: entries
+                .iter()
+                .map(|&(name, is_dir)| ScopeEntry {
+                    name: name.to_string(),
+                    is_dir,
+                })
+                .collect(),
+            include_hidden: false,
+        }
+    }
+
+    #[test]
+    fn parses_create_then_move_by_pattern() {
+        let scope = scope(&[
+            ("a.jpg", false),
+            ("B.JPG", false),
+            ("notes.txt", false),
+            (".c.jpg", false),
+        ]);
+        let response = r#"Here you go:
+```json
+{"actions": [
+  {"kind": "createFolder", "name": "Photos"},
+  {"kind": "move", "pattern": "*.jpg", "into": "Photos"}
+]}
+```"#;
+        let actions = parse_plan(response, &scope).unwrap();
+        assert_eq!(
+            actions,
+            vec![
+                PlannedAction::CreateFolder {
+                    name: "Photos".to_string()
+                },
+                PlannedAction::Move {
+                    sources: vec!["a.jpg".to_string(), "B.JPG".to_string()],
+                    destination: "Photos".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_paths_outside_the_pane() {
+        let scope = scope(&[("a.txt", false), ("Docs", true)]);
+        for into in ["../Elsewhere", "Docs/Sub", "/Users/test/Desktop/Docs", ".."] {
+            let response = format!(r#"{{"actions": [{{"kind": "move", "files": ["a.txt"], "into": "{into}"}}]}}"#);
+            let err = parse_plan(&response, &scope).unwrap_err();
+            assert!(err.contains("outside the current pane"), "{into}: {err}");
+        }
+    }
+
+    #[test]
+    fn accepts_absolute_paths_directly_inside_the_pane() {
+        let scope = scope(&[("a.txt", false), ("Docs", true)]);
+        let response = r#"{"actions": [{"kind": "move", "files": ["/Users/test/Downloads/a.txt"], "into": "Docs"}]}"#;
+        let actions = parse_plan(response, &scope).unwrap();
+        assert_eq!(
+            actions,
+            vec![PlannedAction::Move {
+                sources: vec!["a.txt".to_string()],
+                destination: "Docs".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn rejects_steps_that_cant_run() {
+        let scope = scope(&[("a.txt", false), ("Docs", true)]);
+        let cases = [
+            r#"{"actions": [{"kind": "createFolder", "name": "docs"}]}"#,
+            r#"{"actions": [{"kind": "move", "files": ["a.txt"], "into": "Missing"}]}"#,
+            r#"{"actions": [{"kind": "move", "files": ["ghost.txt"], "into": "Docs"}]}"#,
+            r#"{"actions": [{"kind": "move", "files": ["Docs"], "into": "a.txt"}]}"#,
+            r#"{"actions": [{"kind": "move", "pattern": "*.pdf", "into": "Docs"}]}"#,
+            r#"{"actions": [{"kind": "move", "files": ["a.txt"], "into": "Docs"}, {"kind": "rename", "from": "Docs", "to": "Papers"}]}"#,
+            r#"{"actions": [{"kind": "move", "files": ["a.txt"], "into": "Docs"}, {"kind": "createFolder", "name": "a.txt"}]}"#,
+            r#"{"actions": [{"kind": "delete", "name": "a.txt"}]}"#,
+            r#"{"actions": []}"#,
+            "no plan here",
+        ];
+        for response in cases {
+            assert!(parse_plan(response, &scope).is_err(), "accepted: {response}");
+        }
+    }
+
+    #[test]
+    fn rename_frees_the_old_name() {
+        let scope = scope(&[("draft.txt", false)]);
+        let response = r#"{"actions": [
+            {"kind": "rename", "from": "draft.txt", "to": "final.txt"},
+            {"kind": "createFolder", "name": "draft.txt"}
+        ]}"#;
+        assert_eq!(parse_plan(response, &scope).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn caps_the_number_of_actions() {
+        let scope = scope(&[]);
+        let actions: Vec<String> = (0..=MAX_ACTIONS)
+            .map(|i| format!(r#"{{"kind": "createFolder", "name": "f{i}"}}"#))
+            .collect();
+        let response = format!(r#"{{"actions": [{}]}}"#, actions.join(","));
+        assert!(parse_plan(&response, &scope).unwrap_err().contains("at most"));
+    }
+}
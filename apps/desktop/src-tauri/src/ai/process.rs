@@ -1,16 +1,84 @@
 //! llama-server process lifecycle management.
 
 use super::extract::LLAMA_SERVER_BINARY;
+use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::Path;
 
 /// Log file name for llama-server output (useful for debugging startup issues).
 pub const SERVER_LOG_FILENAME: &str = "llama-server.log";
 
+/// Context sizes must be a multiple of this (llama.cpp allocates the KV cache in such blocks).
+const CONTEXT_SIZE_STEP: u32 = 256;
+const MIN_CONTEXT_SIZE: u32 = 512;
+const MAX_CONTEXT_SIZE: u32 = 262_144;
+/// More layers than any model has; llama-server clamps to the real count.
+const MAX_GPU_LAYERS: u32 = 999;
+
+/// llama-server launch parameters. The frontend owns them (`ai.localContextSize`,
+/// `ai.localThreads`, `ai.localGpuLayers`, `ai.localPort`) and pushes them in via
+/// `configure_ai` / `update_ai_server_config`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AiServerConfig {
+    /// Tokens (`-c`).
+    pub context_size: u32,
+    /// CPU threads (`-t`). `None` lets llama-server pick.
+    pub threads: Option<u32>,
+    /// Layers offloaded to the GPU (`-ngl`). The default offloads all of them.
+    pub gpu_layers: u32,
+    /// Fixed port. `None` picks a free one on each start.
+    pub port: Option<u16>,
+}
+
+impl Default for AiServerConfig {
+    fn default() -> Self {
+        Self {
+            context_size: 4096,
+            threads: None,
+            gpu_layers: 99,
+            port: None,
+        }
+    }
+}
+
+impl AiServerConfig {
+    /// Checks every field's range. Threads are capped at the logical core count.
+    pub fn validate(&self) -> Result<(), String> {
+        if !(MIN_CONTEXT_SIZE..=MAX_CONTEXT_SIZE).contains(&self.context_size)
+            || !self.context_size.is_multiple_of(CONTEXT_SIZE_STEP)
+        {
+            return Err(format!(
+                "Context size must be a multiple of {CONTEXT_SIZE_STEP} between {MIN_CONTEXT_SIZE} and {MAX_CONTEXT_SIZE}"
+            ));
+        }
+        if let Some(threads) = self.threads {
+            let cores = std::thread::available_parallelism().map_or(1, |n| n.get()) as u32;
+            if threads == 0 || threads > cores {
+                return Err(format!("Thread count must be between 1 and {cores}"));
+            }
+        }
+        if self.gpu_layers > MAX_GPU_LAYERS {
+            return Err(format!("GPU layers must be at most {MAX_GPU_LAYERS}"));
+        }
+        if let Some(port) = self.port
+            && port < 1024
+        {
+            return Err(String::from("Port must be 1024 or higher"));
+        }
+        Ok(())
+    }
+}
+
 /// Spawns the llama-server process and returns its PID.
 ///
 /// The caller is responsible for health checking and state management.
-pub fn spawn_llama_server(ai_dir: &Path, model_filename: &str, port: u16, ctx_size: u32) -> Result<u32, String> {
+pub fn spawn_llama_server(
+    ai_dir: &Path,
+    model_filename: &str,
+    port: u16,
+    config: &AiServerConfig,
+) -> Result<u32, String> {
     let binary_path = ai_dir.join(LLAMA_SERVER_BINARY);
     let model_path = ai_dir.join(model_filename);
 
@@ -39,7 +107,8 @@ pub fn spawn_llama_server(ai_dir: &Path, model_filename: &str, port: u16, ctx_si
     let ai_dir_str = ai_dir.to_string_lossy();
     log::debug!("AI server: setting DYLD_LIBRARY_PATH to {}", ai_dir_str);
 
-    let child = std::process::Command::new(&binary_path)
+    let mut command = std::process::Command::new(&binary_path);
+    command
         .env("DYLD_LIBRARY_PATH", &*ai_dir_str)
         .current_dir(ai_dir)
         .arg("-m")
@@ -49,7 +118,7 @@ pub fn spawn_llama_server(ai_dir: &Path, model_filename: &str, port: u16, ctx_si
         .arg("--host")
         .arg("127.0.0.1")
         .arg("-c")
-        .arg(ctx_size.to_string())
+        .arg(config.context_size.to_string())
         .arg("--temp")
         .arg("0.6")
         .arg("--top-p")
@@ -58,7 +127,11 @@ pub fn spawn_llama_server(ai_dir: &Path, model_filename: &str, port: u16, ctx_si
         .arg("4096")
         .arg("--jinja")
         .arg("-ngl")
-        .arg("99")
+        .arg(config.gpu_layers.to_string());
+    if let Some(threads) = config.threads {
+        command.arg("-t").arg(threads.to_string());
+    }
+    let child = command
         .stdout(std::process::Stdio::from(log_file))
         .stderr(std::process::Stdio::from(log_file_stderr))
         .spawn()
@@ -180,3 +253,55 @@ pub fn is_process_alive(pid: u32) -> bool {
         false
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_server_config_is_valid() {
+        assert!(AiServerConfig::default().validate().is_ok());
+    }
+
+    #[test]
+    fn test_server_config_rejects_out_of_range_values() {
+        let base = AiServerConfig::default();
+        for bad in [
+            AiServerConfig {
+                context_size: 5000,
+                ..base
+            },
+            AiServerConfig {
+                context_size: 256,
+                ..base
+            },
+            AiServerConfig {
+                context_size: 524_288,
+                ..base
+            },
+            AiServerConfig {
+                threads: Some(0),
+                ..base
+            },
+            AiServerConfig {
+                threads: Some(100_000),
+                ..base
+            },
+            AiServerConfig {
+                gpu_layers: 1000,
+                ..base
+            },
+            AiServerConfig { port: Some(80), ..base },
+        ] {
+            assert!(bad.validate().is_err(), "{bad:?} should be rejected");
+        }
+
+        let custom = AiServerConfig {
+            context_size: 32_768,
+            threads: Some(1),
+            gpu_layers: 0,
+            port: Some(8080),
+        };
+        assert!(custom.validate().is_ok());
+    }
+}
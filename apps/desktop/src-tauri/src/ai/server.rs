@@ -9,8 +9,8 @@
 
 use super::extract::{LLAMA_SERVER_BINARY, extract_bundled_llama_server};
 use super::process::{
-    SERVER_LOG_FILENAME, find_available_port, is_process_alive, kill_and_reap_in_background, kill_stale_llama_servers,
-    log_diagnostics, read_log_tail, spawn_llama_server,
+    AiServerConfig, SERVER_LOG_FILENAME, find_available_port, is_process_alive, kill_and_reap_in_background,
+    kill_stale_llama_servers, log_diagnostics, read_log_tail, spawn_llama_server,
 };
 use super::state::{MANAGER, ManagerState, get_ai_dir, is_fully_installed, save_state};
 use super::{AiServerReady, AiStarting, get_default_model, get_model_by_id, is_local_ai_supported};
@@ -65,7 +65,7 @@ pub fn start_ai_server<R: Runtime>(app: AppHandle<R>, ctx_size: u32) -> Result<(
         let Some(ref mut m) = *manager else {
             return Err(String::from("AI manager not initialized"));
         };
-        m.context_size = ctx_size;
+        m.server_config.context_size = ctx_size;
 
         spawn_result = if is_fully_installed(m) && m.child_pid.is_none() {
             match spawn_and_track_server(m) {
@@ -90,6 +90,90 @@ pub fn start_ai_server<R: Runtime>(app: AppHandle<R>, ctx_size: u32) -> Result<(
     Ok(())
 }
 
+/// Applies new llama-server launch parameters and (re)starts the server with them when
+/// local AI is installed. If the server doesn't come up, it's restarted with
+/// [`AiServerConfig::default`]. Returns the config in effect, so the caller can tell a
+/// fallback happened. Waits for the health check (up to 60s).
+#[tauri::command]
+#[specta::specta]
+pub async fn update_ai_server_config<R: Runtime>(
+    app: AppHandle<R>,
+    config: AiServerConfig,
+) -> Result<AiServerConfig, String> {
+    if !is_local_ai_supported() {
+        return Err(String::from("Local AI not supported on this hardware"));
+    }
+    config.validate()?;
+
+    let error = match restart_with_config(&app, config).await {
+        Ok(()) => return Ok(config),
+        Err(e) => e,
+    };
+    let defaults = AiServerConfig::default();
+    if config == defaults {
+        return Err(error);
+    }
+    log::warn!("AI server: didn't start with {config:?} ({error}), falling back to defaults");
+    restart_with_config(&app, defaults).await?;
+    Ok(defaults)
+}
+
+/// Stops the server, stores `config`, and starts it again if installed. `Ok` covers a
+/// start that was cancelled (provider switched away meanwhile) and "not installed, so
+/// only stored".
+async fn restart_with_config<R: Runtime>(app: &AppHandle<R>, config: AiServerConfig) -> Result<(), String> {
+    let ai_dir = get_ai_dir(app);
+    let (pid, port, cancel) = {
+        let mut manager = MANAGER.lock_ignore_poison();
+        let Some(ref mut m) = *manager else {
+            return Err(String::from("AI manager not initialized"));
+        };
+        if let Some(token) = m.start_cancel.take() {
+            token.cancel();
+        }
+        if let Some(pid) = m.child_pid.take() {
+            log::info!("AI: restarting server (PID {pid}) with new parameters");
+            kill_and_reap_in_background(pid);
+            m.state.port = None;
+            m.state.pid = None;
+            save_state(&m.ai_dir, &m.state);
+        }
+        m.server_config = config;
+        if !is_fully_installed(m) {
+            return Ok(());
+        }
+        let spawned = spawn_and_track_server(m)?;
+        m.server_starting = true;
+        spawned
+    };
+
+    let _ = AiStarting.emit(app);
+    match wait_for_server_health(&ai_dir, pid, port, cancel).await {
+        StartupOutcome::Ready => {
+            handle_startup_outcome(StartupOutcome::Ready, pid, app);
+            Ok(())
+        }
+        StartupOutcome::Cancelled => {
+            handle_startup_outcome(StartupOutcome::Cancelled, pid, app);
+            Ok(())
+        }
+        StartupOutcome::Failed(e) => {
+            handle_startup_outcome(StartupOutcome::Failed(e.clone()), pid, app);
+            // Don't leave the dead attempt tracked; the fallback (or the user) starts fresh
+            let mut manager = MANAGER.lock_ignore_poison();
+            if let Some(ref mut m) = *manager
+                && m.child_pid == Some(pid)
+            {
+                m.child_pid = None;
+                m.state.port = None;
+                m.state.pid = None;
+                save_state(&m.ai_dir, &m.state);
+            }
+            Err(e)
+        }
+    }
+}
+
 /// Logs a startup outcome at the right severity, emits `AiServerReady` only on success, and
 /// clears `server_starting` unless a newer startup has taken over the slot. A `Cancelled`
 /// outcome (provider switched away, server stopped, or superseded) is deliberately quiet:
@@ -118,12 +202,15 @@ pub(super) fn handle_startup_outcome<R: Runtime>(outcome: StartupOutcome, pid: u
 /// Must be called while holding the MANAGER lock.
 /// Returns (pid, port) for the caller to health-check asynchronously.
 pub(super) fn spawn_and_track_server(m: &mut ManagerState) -> Result<(u32, u16, CancellationToken), String> {
-    let model = get_model_by_id(&m.state.installed_model_id).unwrap_or_else(get_default_model);
-    let port = find_available_port().ok_or("No available port")?;
+    let model = get_model_by_id(&m.state.installed_model_id, &m.state.user_models).unwrap_or_else(get_default_model);
+    let port = match m.server_config.port {
+        Some(port) => port,
+        None => find_available_port().ok_or("No available port")?,
+    };
 
     log::debug!(
-        "AI server: starting llama-server on port {port} with context size {}",
-        m.context_size
+        "AI server: starting llama-server on port {port} with {:?}",
+        m.server_config
     );
 
     // Supersede any previous in-flight startup: its health-check waiter should exit
@@ -135,7 +222,7 @@ pub(super) fn spawn_and_track_server(m: &mut ManagerState) -> Result<(u32, u16,
     // Belt-and-suspenders: stop any stale llama-servers before spawning a new one
     kill_stale_llama_servers(&m.ai_dir);
 
-    let pid = spawn_llama_server(&m.ai_dir, model.filename, port, m.context_size)?;
+    let pid = spawn_llama_server(&m.ai_dir, &model.filename, port, &m.server_config)?;
 
     // Track PID immediately (no race window where a process exists untracked)
     let cancel = CancellationToken::new();
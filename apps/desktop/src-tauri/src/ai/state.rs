@@ -8,6 +8,7 @@
 //! the install-status / model-info facts derived from disk.
 
 use super::extract::{LLAMA_SERVER_BINARY, REQUIRED_DYLIB};
+use super::process::AiServerConfig;
 use super::{AiState, ModelInfo, get_default_model, get_model_by_id};
 use crate::ignore_poison::IgnorePoison;
 use std::fs;
@@ -34,8 +35,8 @@ pub(super) struct ManagerState {
     pub(super) start_cancel: Option<tokio_util::sync::CancellationToken>,
     /// AI provider mode: "off", "cloud", or "local"
     pub(super) provider: String,
-    /// Context size for local llama-server
-    pub(super) context_size: u32,
+    /// Launch parameters for local llama-server (validated before they land here)
+    pub(super) server_config: AiServerConfig,
     /// Cloud-AI provider API key (stored here so suggestions.rs can read without settings files)
     pub(super) cloud_api_key: String,
     /// Cloud-AI provider base URL (e.g. `https://api.openai.com/v1`, `https://api.anthropic.com/v1/`)
@@ -63,7 +64,7 @@ pub(super) fn new_manager_state(ai_dir: PathBuf, state: AiState) -> ManagerState
         server_starting: false,
         start_cancel: None,
         provider: String::from("local"),
-        context_size: 4096,
+        server_config: AiServerConfig::default(),
         cloud_api_key: String::new(),
         cloud_base_url: String::from("https://api.openai.com/v1"),
         cloud_model: String::from("gpt-4o-mini"),
@@ -139,10 +140,10 @@ pub(super) fn format_bytes_gb(bytes: u64) -> String {
 
 /// Returns the model info for the currently selected/installed model.
 /// Falls back to default if the stored model ID is not in the registry.
-pub(super) fn get_current_model() -> &'static ModelInfo {
+pub(super) fn get_current_model() -> ModelInfo {
     let manager = MANAGER.lock_ignore_poison();
     if let Some(ref m) = *manager
-        && let Some(model) = get_model_by_id(&m.state.installed_model_id)
+        && let Some(model) = get_model_by_id(&m.state.installed_model_id, &m.state.user_models)
     {
         return model;
     }
@@ -178,8 +179,8 @@ pub(super) fn is_fully_installed(m: &ManagerState) -> bool {
     let dylib_exists = m.ai_dir.join(REQUIRED_DYLIB).exists();
 
     // Get model info based on installed model ID
-    let model = get_model_by_id(&m.state.installed_model_id).unwrap_or_else(get_default_model);
-    let model_path = m.ai_dir.join(model.filename);
+    let model = get_model_by_id(&m.state.installed_model_id, &m.state.user_models).unwrap_or_else(get_default_model);
+    let model_path = m.ai_dir.join(&*model.filename);
     let model_exists = model_path.exists();
 
     if !binary_exists || !dylib_exists {
@@ -193,8 +194,10 @@ pub(super) fn is_fully_installed(m: &ManagerState) -> bool {
     let model_complete = model_exists && m.state.model_download_complete;
 
     if model_exists && !m.state.model_download_complete {
-        // Double-check by file size in case state is stale
-        if let Ok(meta) = fs::metadata(&model_path)
+        // Double-check by file size in case state is stale. Not for a model with a pinned
+        // digest: a right-sized file may still be corrupt, so only the download marks it.
+        if model.sha256.is_none()
+            && let Ok(meta) = fs::metadata(&model_path)
             && meta.len() >= model.size_bytes
         {
             log::debug!("AI: model file size matches expected, marking as complete");
@@ -230,6 +233,7 @@ mod tests {
             dismissed_until: None,
             model_download_complete: true,
             partial_download_started: None,
+            user_models: Vec::new(),
         };
 
         let json = serde_json::to_string(&state).unwrap();
@@ -1,20 +1,38 @@
-//! Folder name suggestions powered by AI (local LLM or Cloud AI provider).
+//! Folder name and rename suggestions powered by AI (local LLM or Cloud AI provider).
 //!
-//! Builds a prompt from the current directory listing, calls the configured AI backend,
-//! and parses the response into validated folder name suggestions.
+//! Builds a prompt from a [`SuggestionContext`] (the current directory listing, summarized
+//! when large, plus what's selected in the focused pane), calls the configured AI backend, and
+//! parses the response into validated folder name suggestions. [`get_rename_suggestion`] does
+//! the same for renaming one file, grounded in its name, siblings, and (for text) first bytes.
 
 use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 use futures_util::StreamExt;
 use genai::chat::ChatOptions;
 use serde::Serialize;
 use tauri::ipc::Channel;
+use tauri::{AppHandle, Manager};
 
 use crate::ai::llm_log::LlmLogContext;
-use crate::file_system::get_file_at;
+use crate::file_system::extension::extension_of;
+use crate::file_system::extension_breakdown::{self, NO_EXTENSION};
+use crate::file_system::{get_file_at, get_total_count};
+use crate::mcp::PaneStateStore;
 
-/// Maximum number of file names to include in the prompt context.
+/// Maximum number of file names read from the listing (and checked against for duplicates).
+/// Folders with more entries than this are summarized in the prompt.
 const MAX_CONTEXT_ENTRIES: usize = 100;
+/// Names kept in the prompt for a summarized folder, next to its per-extension counts.
+const SUMMARY_SAMPLE_NAMES: usize = 30;
+/// Extensions listed for a summarized folder, most common first.
+const MAX_CONTEXT_EXTENSIONS: usize = 12;
+/// Selected names passed along, so a suggestion can be about moving them somewhere.
+const MAX_SELECTED_NAMES: usize = 20;
+/// The breakdown is cached or index-backed in the common case; past this, suggest without it
+/// rather than keep the dialog waiting on a slow walk.
+const BREAKDOWN_TIMEOUT: Duration = Duration::from_secs(2);
 /// Maximum number of suggestions to return.
 const MAX_SUGGESTIONS: usize = 5;
 
@@ -29,6 +47,7 @@ const SUGGESTION_SYSTEM_PROMPT: &str = "You are a pattern-matching assistant. Ca
 #[tauri::command]
 #[specta::specta]
 pub async fn get_folder_suggestions(
+    app: AppHandle,
     listing_id: String,
     current_path: String,
     include_hidden: bool,
@@ -43,7 +62,138 @@ pub async fn get_folder_suggestions(
         return Ok(Vec::new());
     };
 
-    get_suggestions_from_backend(&listing_id, &current_path, include_hidden, backend).await
+    let context = SuggestionContext::assemble(&app, &listing_id, &current_path, include_hidden).await;
+    get_suggestions_from_backend(&context, backend).await
+}
+
+/// What the prompt is grounded in: the folder's listing, summarized when it's large, and the
+/// selection in the focused pane.
+#[derive(Debug, Default)]
+pub(super) struct SuggestionContext {
+    current_path: String,
+    /// The first [`MAX_CONTEXT_ENTRIES`] names in listing order. Suggestions matching one
+    /// are dropped.
+    existing_names: Vec<String>,
+    /// Visible entries in the folder, which can be far more than `existing_names`.
+    total_entries: usize,
+    /// `(extension, file count)` for the folder and its subfolders, most common first. Only
+    /// filled for a summarized folder, from the extension breakdown.
+    extension_counts: Vec<(String, u64)>,
+    /// What's selected in the focused pane, when that pane shows this folder.
+    selected_names: Vec<String>,
+}
+
+impl SuggestionContext {
+    /// Reads the listing cache, the focused pane's live state, and (for a folder too big to
+    /// list in full) the extension breakdown.
+    pub(super) async fn assemble(app: &AppHandle, listing_id: &str, current_path: &str, include_hidden: bool) -> Self {
+        let existing_names = get_file_names(listing_id, include_hidden);
+        let total_entries = get_total_count(listing_id, include_hidden).unwrap_or(existing_names.len());
+        let selected_names = app
+            .try_state::<PaneStateStore>()
+            .map(|store| selected_names_in(&store, current_path))
+            .unwrap_or_default();
+
+        let mut context = Self {
+            current_path: current_path.to_string(),
+            existing_names,
+            total_entries,
+            extension_counts: Vec::new(),
+            selected_names,
+        };
+        if context.is_summarized() {
+            let path = PathBuf::from(current_path);
+            let breakdown = tokio::task::spawn_blocking(move || top_extensions(&path, MAX_CONTEXT_EXTENSIONS));
+            context.extension_counts = match tokio::time::timeout(BREAKDOWN_TIMEOUT, breakdown).await {
+                Ok(Ok(counts)) => counts,
+                _ => Vec::new(), // Timeout or JoinError: suggest from the names alone
+            };
+        }
+        context
+    }
+
+    /// Whether the folder holds more than the prompt lists by name.
+    fn is_summarized(&self) -> bool {
+        self.total_entries > MAX_CONTEXT_ENTRIES
+    }
+
+    /// The folder as the prompt describes it: path, names (sampled when summarized), file
+    /// types, and selection. Ends with a blank line.
+    pub(super) fn describe(&self) -> String {
+        let current_path = &self.current_path;
+        let (names, items_heading) = if self.is_summarized() {
+            let sample = &self.existing_names[..self.existing_names.len().min(SUMMARY_SAMPLE_NAMES)];
+            let heading = format!("Existing items (first {} of {}):", sample.len(), self.total_entries);
+            (sample, heading)
+        } else {
+            (&self.existing_names[..], "Existing items:".to_string())
+        };
+        let contents = names.join("\n");
+
+        let mut extra = String::new();
+        if !self.extension_counts.is_empty() {
+            let counts: Vec<String> = self
+                .extension_counts
+                .iter()
+                .map(|(extension, count)| {
+                    let label = if extension == NO_EXTENSION {
+                        "(no extension)"
+                    } else {
+                        extension
+                    };
+                    format!("{label}: {count}")
+                })
+                .collect();
+            extra.push_str(&format!(
+                "File types here and in subfolders (files per extension):\n{}\n\n",
+                counts.join("\n")
+            ));
+        }
+        if !self.selected_names.is_empty() {
+            extra.push_str(&format!(
+                "Currently selected (a good folder may be one to move these into):\n{}\n\n",
+                self.selected_names.join("\n")
+            ));
+        }
+
+        format!("Directory: {current_path}\n{items_heading}\n{contents}\n\n{extra}")
+    }
+}
+
+/// The focused pane's selected names, if it's showing `current_path`. Only loaded rows are
+/// known here, which covers any selection the user made by hand.
+fn selected_names_in(store: &PaneStateStore, current_path: &str) -> Vec<String> {
+    let pane = if store.get_focused_pane() == "right" {
+        store.get_right()
+    } else {
+        store.get_left()
+    };
+    if pane.path != current_path {
+        return Vec::new();
+    }
+    pane.selected_indices
+        .iter()
+        .filter_map(|&i| pane.files.get(i))
+        .map(|entry| entry.name.clone())
+        .take(MAX_SELECTED_NAMES)
+        .collect()
+}
+
+/// The `limit` most common extensions under `path`, by file count, among the ones the breakdown
+/// lists (the biggest by bytes). Empty when the breakdown fails (a non-local path, an
+/// unreadable folder).
+fn top_extensions(path: &std::path::Path, limit: usize) -> Vec<(String, u64)> {
+    let Ok(breakdown) = extension_breakdown::get_extension_breakdown(path) else {
+        return Vec::new();
+    };
+    let mut counts: Vec<(String, u64)> = breakdown
+        .extensions
+        .into_iter()
+        .map(|entry| (entry.extension, entry.stats.count))
+        .collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    counts.truncate(limit);
+    counts
 }
 
 /// Gets file names from the listing cache (up to MAX_CONTEXT_ENTRIES).
@@ -59,9 +209,8 @@ fn get_file_names(listing_id: &str, include_hidden: bool) -> Vec<String> {
 }
 
 /// Builds the prompt for folder name suggestions.
-fn build_prompt(current_path: &str, file_names: &[String]) -> String {
-    let contents = file_names.join("\n");
-
+fn build_prompt(context: &SuggestionContext) -> String {
+    let description = context.describe();
     format!(
         "Suggest {MAX_SUGGESTIONS} new folder names that fit naturally with the existing items. \
          IMPORTANT: Match the naming style exactly - same language, same letter case, same word structure. \
@@ -69,10 +218,7 @@ fn build_prompt(current_path: &str, file_names: &[String]) -> String {
          If existing names are in a specific language, suggest names in that same language. \
          Output ONLY the folder names, one per line. No numbers, bullets, dashes, markdown, or explanation.\n\
          \n\
-         Directory: {current_path}\n\
-         Existing items:\n\
-         {contents}\n\
-         \n\
+         {description}\
          {MAX_SUGGESTIONS} folder names:"
     )
 }
@@ -121,15 +267,18 @@ fn parse_suggestions(response: &str, existing_names: &[String]) -> Vec<String> {
 
 /// Calls the AI backend and returns parsed suggestions.
 async fn get_suggestions_from_backend(
-    listing_id: &str,
-    current_path: &str,
-    include_hidden: bool,
+    context: &SuggestionContext,
     backend: super::client::AiBackend,
 ) -> Result<Vec<String>, String> {
-    let file_names = get_file_names(listing_id, include_hidden);
-    let prompt = build_prompt(current_path, &file_names);
+    let prompt = build_prompt(context);
 
-    log::debug!("AI suggestions: calling AI with {} files in context", file_names.len());
+    log::debug!(
+        "AI suggestions: calling AI with {} of {} entries, {} extensions, {} selected in context",
+        context.existing_names.len(),
+        context.total_entries,
+        context.extension_counts.len(),
+        context.selected_names.len()
+    );
     log::trace!("AI suggestions: prompt:\n{prompt}");
 
     let options = ChatOptions::default()
@@ -141,7 +290,7 @@ async fn get_suggestions_from_backend(
     match super::client::chat_completion(&backend, SUGGESTION_SYSTEM_PROMPT, &prompt, &options).await {
         Ok(response) => {
             log::trace!("AI suggestions: raw response:\n{response}");
-            let suggestions = parse_suggestions(&response, &file_names);
+            let suggestions = parse_suggestions(&response, &context.existing_names);
             log::debug!(
                 "AI suggestions: got {} suggestions: {:?}",
                 suggestions.len(),
@@ -156,6 +305,239 @@ async fn get_suggestions_from_backend(
     }
 }
 
+// region: --- Rename suggestions ----------------------------------------------------
+
+/// Rename candidates returned for one file.
+const RENAME_SUGGESTIONS: usize = 3;
+/// Candidates asked for: a few spare, since some fail validation or repeat a sibling.
+const RENAME_CANDIDATES_REQUESTED: usize = 5;
+/// Sibling names passed along so candidates match the folder's naming style.
+const MAX_RENAME_SIBLINGS: usize = 30;
+/// Siblings read before sorting and trimming to [`MAX_RENAME_SIBLINGS`], so a huge folder
+/// doesn't cost a full listing.
+const MAX_RENAME_SIBLINGS_SCANNED: usize = 1000;
+/// Leading bytes of a text file shown to the model as a hint of what it's about.
+const RENAME_PREVIEW_BYTES: usize = 512;
+
+/// Suggests up to [`RENAME_SUGGESTIONS`] new names for the file or folder at `path`.
+///
+/// The prompt carries the name, its extension, some sibling names, and for a text file its
+/// first bytes. Every candidate keeps the original extension and passes
+/// [`check_rename_validity`](crate::commands::rename::check_rename_validity) without a
+/// conflict, so the UI can offer each one as-is. Like folder suggestions, a missing backend
+/// or a failed AI call returns `Ok(Vec::new())`.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_rename_suggestion(path: String) -> Result<Vec<String>, String> {
+    log::debug!("AI rename suggestions: get_rename_suggestion called for path={path}");
+
+    let Some(backend) = super::manager::resolve_backend().ready_or_log("AI rename suggestions") else {
+        return Ok(Vec::new());
+    };
+
+    let path = PathBuf::from(crate::commands::file_system::expand_tilde(&path));
+    let context = tokio::task::spawn_blocking(move || RenameContext::read(&path))
+        .await
+        .map_err(|e| format!("Task failed: {e}"))?;
+    let Some(context) = context else {
+        return Ok(Vec::new());
+    };
+
+    let prompt = build_rename_prompt(&context);
+    log::trace!("AI rename suggestions: prompt:\n{prompt}");
+
+    let options = ChatOptions::default()
+        .with_temperature(0.6)
+        .with_max_tokens(150)
+        .with_top_p(0.95);
+
+    let backend = backend.with_log_context(LlmLogContext::rename_suggestions());
+    let response = match super::client::chat_completion(&backend, SUGGESTION_SYSTEM_PROMPT, &prompt, &options).await {
+        Ok(response) => response,
+        Err(e) => {
+            log::warn!("AI rename suggestions: AI call failed: {e}");
+            return Ok(Vec::new()); // Graceful degradation: return empty on any error
+        }
+    };
+    log::trace!("AI rename suggestions: raw response:\n{response}");
+
+    let mut suggestions = Vec::new();
+    for candidate in parse_rename_candidates(&response, &context) {
+        if is_valid_rename(&context, &candidate).await {
+            suggestions.push(candidate);
+            if suggestions.len() >= RENAME_SUGGESTIONS {
+                break;
+            }
+        }
+    }
+    log::debug!(
+        "AI rename suggestions: got {} suggestions: {:?}",
+        suggestions.len(),
+        suggestions
+    );
+    Ok(suggestions)
+}
+
+/// What a rename prompt is grounded in.
+#[derive(Debug, Default)]
+struct RenameContext {
+    dir: String,
+    name: String,
+    /// Without the dot. `None` for folders and extensionless files.
+    extension: Option<String>,
+    /// Other names in the same folder, sorted, hidden ones left out.
+    sibling_names: Vec<String>,
+    /// The start of a text file, cut at a char boundary. `None` for folders and binary files.
+    text_preview: Option<String>,
+}
+
+impl RenameContext {
+    /// Reads the name, siblings, and preview from disk. `None` when `path` has no parent or
+    /// file name (like `/`), or doesn't exist.
+    fn read(path: &Path) -> Option<Self> {
+        let name = path.file_name()?.to_str()?.to_string();
+        let parent = path.parent()?;
+        let metadata = std::fs::symlink_metadata(path).ok()?;
+        let is_dir = metadata.is_dir();
+
+        let mut sibling_names: Vec<String> = std::fs::read_dir(parent)
+            .map(|entries| {
+                entries
+                    .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+                    .filter(|sibling| *sibling != name && !sibling.starts_with('.'))
+                    .take(MAX_RENAME_SIBLINGS_SCANNED)
+                    .collect()
+            })
+            .unwrap_or_default();
+        sibling_names.sort();
+        sibling_names.truncate(MAX_RENAME_SIBLINGS);
+
+        let (extension, text_preview) = if is_dir {
+            (None, None)
+        } else {
+            (extension_of(&name).map(str::to_string), read_text_preview(path))
+        };
+
+        Some(Self {
+            dir: parent.to_string_lossy().into_owned(),
+            name,
+            extension,
+            sibling_names,
+            text_preview,
+        })
+    }
+}
+
+/// The first [`RENAME_PREVIEW_BYTES`] of `path` if they look like text: valid UTF-8 (a
+/// multi-byte char cut off at the end is fine) with no NUL bytes.
+fn read_text_preview(path: &Path) -> Option<String> {
+    use std::io::Read;
+
+    let mut buffer = Vec::with_capacity(RENAME_PREVIEW_BYTES);
+    std::fs::File::open(path)
+        .ok()?
+        .take(RENAME_PREVIEW_BYTES as u64)
+        .read_to_end(&mut buffer)
+        .ok()?;
+    text_prefix(&buffer)
+}
+
+/// `bytes` as text, or `None` if they're empty, contain NUL, or aren't UTF-8 before the
+/// last (possibly truncated) char.
+fn text_prefix(bytes: &[u8]) -> Option<String> {
+    if bytes.is_empty() || bytes.contains(&0) {
+        return None;
+    }
+    let text = match std::str::from_utf8(bytes) {
+        Ok(text) => text,
+        // `error_len() == None` means the input just ends mid-char
+        Err(e) if e.error_len().is_none() => std::str::from_utf8(&bytes[..e.valid_up_to()]).ok()?,
+        Err(_) => return None,
+    };
+    let text = text.trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+/// Builds the prompt for rename suggestions.
+fn build_rename_prompt(context: &RenameContext) -> String {
+    let name = &context.name;
+    let extension_rule = match &context.extension {
+        Some(extension) => format!("Every name must end in \".{extension}\". "),
+        None => String::new(),
+    };
+    let mut extra = String::new();
+    if !context.sibling_names.is_empty() {
+        extra.push_str(&format!(
+            "Other items in the same folder:\n{}\n\n",
+            context.sibling_names.join("\n")
+        ));
+    }
+    if let Some(preview) = &context.text_preview {
+        extra.push_str(&format!("Beginning of the file:\n{preview}\n\n"));
+    }
+    format!(
+        "Suggest {RENAME_CANDIDATES_REQUESTED} better names for the item \"{name}\". \
+         Describe what it is, and match the naming style of the other items in its folder. \
+         {extension_rule}\
+         Output ONLY the names, one per line. No numbers, bullets, dashes, markdown, or explanation.\n\
+         \n\
+         {extra}\
+         {RENAME_CANDIDATES_REQUESTED} names:"
+    )
+}
+
+/// Parses the LLM response into rename candidates: sanitized, given the original extension,
+/// and deduped case-insensitively against the original name and each other. Not yet checked
+/// against the file system.
+fn parse_rename_candidates(response: &str, context: &RenameContext) -> Vec<String> {
+    let mut seen_lower = HashSet::from([context.name.to_lowercase()]);
+    response
+        .lines()
+        .filter_map(sanitize_one_line)
+        .filter_map(|name| with_extension(&name, context.extension.as_deref()))
+        .filter(|name| seen_lower.insert(name.to_lowercase()))
+        .take(RENAME_CANDIDATES_REQUESTED)
+        .collect()
+}
+
+/// Gives `candidate` the extension `extension`, replacing a different one the model added.
+/// Returns `None` if nothing is left of the candidate besides the extension.
+fn with_extension(candidate: &str, extension: Option<&str>) -> Option<String> {
+    let Some(extension) = extension else {
+        return Some(candidate.to_string());
+    };
+    let stem = match extension_of(candidate) {
+        // Only treat a short, space-free suffix as an extension: "v1.2 draft" keeps its dot
+        Some(existing) if existing.len() <= 10 && !existing.contains(' ') => {
+            &candidate[..candidate.len() - existing.len() - 1]
+        }
+        _ => candidate,
+    };
+    let stem = stem.trim_end_matches('.').trim_end();
+    (!stem.is_empty()).then(|| format!("{stem}.{extension}"))
+}
+
+/// Whether renaming to `candidate` is legal and conflict-free, per the same check the rename
+/// UI runs on typed names.
+async fn is_valid_rename(context: &RenameContext, candidate: &str) -> bool {
+    match crate::commands::rename::check_rename_validity(
+        context.dir.clone(),
+        context.name.clone(),
+        candidate.to_string(),
+        None,
+    )
+    .await
+    {
+        Ok(result) => result.valid && !result.has_conflict,
+        Err(e) => {
+            log::debug!("AI rename suggestions: couldn't validate {candidate:?}: {e}");
+            false
+        }
+    }
+}
+
+// endregion: --- Rename suggestions ------------------------------------------------
+
 // region: --- Streaming variant ----------------------------------------------------
 
 /// Wire-format event for streaming folder suggestions.
@@ -262,6 +644,7 @@ impl<'a> StreamingSanitizer<'a> {
 /// requires it. See `ai/CLAUDE.md` § Decisions.
 #[tauri::command]
 pub async fn stream_folder_suggestions(
+    app: AppHandle,
     request_id: String,
     listing_id: String,
     current_path: String,
@@ -287,11 +670,12 @@ pub async fn stream_folder_suggestions(
         return Ok(());
     };
 
-    let file_names = get_file_names(&listing_id, include_hidden);
-    let prompt = build_prompt(&current_path, &file_names);
+    let context = SuggestionContext::assemble(&app, &listing_id, &current_path, include_hidden).await;
+    let prompt = build_prompt(&context);
     log::debug!(
-        "AI suggestions stream: opening stream with {} files in context",
-        file_names.len()
+        "AI suggestions stream: opening stream with {} of {} entries in context",
+        context.existing_names.len(),
+        context.total_entries
     );
 
     let options = ChatOptions::default()
@@ -310,7 +694,7 @@ pub async fn stream_folder_suggestions(
             }
         };
 
-    let mut sanitizer = StreamingSanitizer::new(&file_names);
+    let mut sanitizer = StreamingSanitizer::new(&context.existing_names);
 
     loop {
         tokio::select! {
@@ -364,10 +748,19 @@ pub fn cancel_folder_suggestions(request_id: String) {
 mod tests {
     use super::*;
 
+    fn context(path: &str, names: Vec<String>) -> SuggestionContext {
+        SuggestionContext {
+            current_path: path.to_string(),
+            total_entries: names.len(),
+            existing_names: names,
+            ..SuggestionContext::default()
+        }
+    }
+
     #[test]
     fn test_build_prompt_includes_path_and_files() {
         let names = vec![String::from("src"), String::from("README.md")];
-        let prompt = build_prompt("/home/user/project", &names);
+        let prompt = build_prompt(&context("/home/user/project", names));
 
         assert!(prompt.contains("/home/user/project"));
         assert!(prompt.contains("src\nREADME.md"));
@@ -379,11 +772,37 @@ mod tests {
     #[test]
     fn test_build_prompt_empty_dir() {
         let names: Vec<String> = Vec::new();
-        let prompt = build_prompt("/empty", &names);
+        let prompt = build_prompt(&context("/empty", names));
         assert!(prompt.contains("/empty"));
         assert!(prompt.contains("Existing items:"));
     }
 
+    #[test]
+    fn test_build_prompt_summarizes_large_folders() {
+        let names: Vec<String> = (0..MAX_CONTEXT_ENTRIES).map(|i| format!("invoice-{i}.pdf")).collect();
+        let mut large = context("/docs", names);
+        large.total_entries = 2500;
+        large.extension_counts = vec![("pdf".to_string(), 2400), (NO_EXTENSION.to_string(), 3)];
+
+        let prompt = build_prompt(&large);
+
+        assert!(prompt.contains("Existing items (first 30 of 2500):"));
+        assert!(prompt.contains("invoice-29.pdf\n"));
+        assert!(!prompt.contains("invoice-30.pdf"));
+        assert!(prompt.contains("pdf: 2400\n(no extension): 3"));
+    }
+
+    #[test]
+    fn test_build_prompt_mentions_selection() {
+        let mut with_selection = context("/docs", vec![String::from("a.pdf"), String::from("b.pdf")]);
+        with_selection.selected_names = vec![String::from("b.pdf")];
+
+        let prompt = build_prompt(&with_selection);
+
+        assert!(prompt.contains("Currently selected"));
+        assert!(prompt.ends_with("b.pdf\n\n5 folder names:"));
+    }
+
     #[test]
     fn test_parse_suggestions_basic() {
         let response = "docs\ntests\nscripts\nconfig\nassets\n";
@@ -473,6 +892,109 @@ mod tests {
         assert_eq!(result, vec!["valid"]);
     }
 
+    // --- Rename suggestions ---
+
+    fn rename_context(name: &str, extension: Option<&str>) -> RenameContext {
+        RenameContext {
+            dir: String::from("/docs"),
+            name: name.to_string(),
+            extension: extension.map(str::to_string),
+            ..RenameContext::default()
+        }
+    }
+
+    #[test]
+    fn test_with_extension_keeps_or_replaces() {
+        assert_eq!(
+            with_extension("Q3 report", Some("pdf")).as_deref(),
+            Some("Q3 report.pdf")
+        );
+        assert_eq!(
+            with_extension("Q3 report.PDF", Some("pdf")).as_deref(),
+            Some("Q3 report.pdf")
+        );
+        assert_eq!(
+            with_extension("Q3 report.txt", Some("pdf")).as_deref(),
+            Some("Q3 report.pdf")
+        );
+        assert_eq!(
+            with_extension("v1.2 draft", Some("md")).as_deref(),
+            Some("v1.2 draft.md")
+        );
+        assert_eq!(with_extension(".pdf", Some("pdf")), None);
+        assert_eq!(with_extension("notes.txt", None).as_deref(), Some("notes.txt"));
+    }
+
+    #[test]
+    fn test_parse_rename_candidates_dedupes_and_skips_original() {
+        let context = rename_context("scan001.pdf", Some("pdf"));
+        let response = "1. Lease agreement\n- scan001\n**Lease agreement.pdf**\nRent receipt 2024.pdf\n";
+        let result = parse_rename_candidates(response, &context);
+        assert_eq!(result, vec!["Lease agreement.pdf", "Rent receipt 2024.pdf"]);
+    }
+
+    #[test]
+    fn test_parse_rename_candidates_caps_at_requested() {
+        let context = rename_context("a.txt", Some("txt"));
+        let response = (0..10).map(|i| format!("name{i}\n")).collect::<String>();
+        let result = parse_rename_candidates(&response, &context);
+        assert_eq!(result.len(), RENAME_CANDIDATES_REQUESTED);
+    }
+
+    #[test]
+    fn test_build_rename_prompt_includes_context() {
+        let mut context = rename_context("untitled.md", Some("md"));
+        context.sibling_names = vec![String::from("meeting-notes-2024-01.md")];
+        context.text_preview = Some(String::from("# Budget review"));
+
+        let prompt = build_rename_prompt(&context);
+
+        assert!(prompt.contains("\"untitled.md\""));
+        assert!(prompt.contains("must end in \".md\""));
+        assert!(prompt.contains("meeting-notes-2024-01.md"));
+        assert!(prompt.contains("# Budget review"));
+    }
+
+    #[test]
+    fn test_text_prefix() {
+        assert_eq!(text_prefix(b"  hello\n").as_deref(), Some("hello"));
+        assert_eq!(text_prefix(b"bin\0ary"), None);
+        assert_eq!(text_prefix(&[0xff, 0xfe, 0x41]), None);
+        assert_eq!(text_prefix(b""), None);
+        // "é" is two bytes; a read that stops after the first one is still text
+        assert_eq!(text_prefix(&[b'c', b'a', b'f', 0xc3]).as_deref(), Some("caf"));
+    }
+
+    #[test]
+    fn test_rename_context_reads_siblings_and_preview() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("draft.txt");
+        std::fs::write(&file, "Shopping list: eggs, milk").unwrap();
+        std::fs::write(dir.path().join("b-sibling.txt"), "").unwrap();
+        std::fs::write(dir.path().join(".hidden"), "").unwrap();
+        std::fs::create_dir(dir.path().join("a-folder")).unwrap();
+
+        let context = RenameContext::read(&file).unwrap();
+
+        assert_eq!(context.name, "draft.txt");
+        assert_eq!(context.extension.as_deref(), Some("txt"));
+        assert_eq!(context.sibling_names, vec!["a-folder", "b-sibling.txt"]);
+        assert_eq!(context.text_preview.as_deref(), Some("Shopping list: eggs, milk"));
+    }
+
+    #[test]
+    fn test_rename_context_folder_has_no_extension_or_preview() {
+        let dir = tempfile::tempdir().unwrap();
+        let folder = dir.path().join("photos.2024");
+        std::fs::create_dir(&folder).unwrap();
+
+        let context = RenameContext::read(&folder).unwrap();
+
+        assert_eq!(context.extension, None);
+        assert_eq!(context.text_preview, None);
+        assert!(RenameContext::read(&dir.path().join("missing")).is_none());
+    }
+
     // --- StreamingSanitizer ---
 
     fn collect(sanitizer: &mut StreamingSanitizer<'_>, chunks: &[&str]) -> Vec<String> {
@@ -0,0 +1,19 @@
+//! Hooks for the criterion benches in `benches/`.
+//!
+//! Benches link against `cmdr_lib` like any outside crate, so they only see its public API and
+//! can't reach the `pub(crate)` pipelines they measure. Each hook here is a thin, blocking
+//! wrapper over one such pipeline with default settings. Nothing in the app calls these.
+//!
+//! Timings land on the `benchmark` timeline too: call [`crate::benchmark::init_benchmarking`]
+//! and run with `RUSTY_COMMANDER_BENCHMARK=1` to see each run's phases on stderr.
+
+use std::path::{Path, PathBuf};
+
+use crate::file_system::write_operations::{WriteOperationConfig, copy_files_blocking};
+
+/// Copies `sources` into `destination` through the local copy engine (scan, disk-space
+/// preflight, per-file copy loop), blocking until it finishes. Same-name conflicts fail the
+/// copy, so give every run a fresh destination.
+pub fn copy_files(sources: &[PathBuf], destination: &Path) -> Result<(), String> {
+    copy_files_blocking(sources, destination, &WriteOperationConfig::default()).map_err(|e| format!("{e:?}"))
+}
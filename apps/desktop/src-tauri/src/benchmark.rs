@@ -2,6 +2,13 @@
 //!
 //! Enable with RUSTY_COMMANDER_BENCHMARK=1 environment variable.
 //! All events are logged to stderr with microsecond timestamps.
+//!
+//! Two output shapes:
+//! - `[TIMELINE]` lines from `log_event` / `TimedBlock`: human-readable, interleaved with the
+//!   frontend's events.
+//! - One JSON object per line from [`Span`]s: a phase (`get_file_range`, index enrichment,
+//!   icons, column widths) with its duration, tagged with the listing it belongs to, so a
+//!   script can sum one listing's cost per phase. See `docs/guides/benchmarking-file-loading.md`.
 
 // Benchmarks intentionally use eprintln! for raw stderr output (not log framework)
 #![allow(
@@ -9,8 +16,11 @@
     reason = "Benchmarks bypass log framework for raw stderr output"
 )]
 
+use std::sync::Mutex;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 
+use crate::ignore_poison::IgnorePoison;
+
 /// Global start time for relative timestamps
 static BENCHMARK_ENABLED: AtomicBool = AtomicBool::new(false);
 
@@ -108,3 +118,104 @@ impl Drop for TimedBlock {
         }
     }
 }
+
+// ============================================================================
+// Spans: per-phase cost of one listing, as JSON lines
+// ============================================================================
+
+/// Span ids, unique per process. `0` marks a disabled span.
+static NEXT_SPAN_ID: AtomicU64 = AtomicU64::new(1);
+
+/// The listing most recently started. Spans that can't name their listing (enrichment, icons)
+/// are charged to it, which matches what the user is waiting on right after a navigation.
+static CURRENT_TRACE: Mutex<Option<String>> = Mutex::new(None);
+
+/// Marks `listing_id` as the listing that unattributed spans belong to from now on.
+pub fn set_current_trace(listing_id: &str) {
+    if !is_enabled() {
+        return;
+    }
+    *CURRENT_TRACE.lock_ignore_poison() = Some(listing_id.to_string());
+}
+
+/// A timed phase. Prints one JSON line to stderr when dropped:
+///
+/// ```text
+/// {"type":"span","trace":"<listing id>","span":7,"name":"get_file_range","startUs":1200,"durUs":85,"fields":{"count":100}}
+/// ```
+///
+/// `startUs` is on the same epoch as the `[TIMELINE]` lines. Free when benchmarking is off: no
+/// clock reads, no allocations.
+#[must_use = "a span measures until it's dropped"]
+pub struct Span {
+    id: u64,
+    name: &'static str,
+    trace: Option<String>,
+    start: u64,
+    fields: serde_json::Map<String, serde_json::Value>,
+}
+
+impl Span {
+    fn disabled(name: &'static str) -> Self {
+        Self {
+            id: 0,
+            name,
+            trace: None,
+            start: 0,
+            fields: serde_json::Map::new(),
+        }
+    }
+
+    /// Attaches a field (entry count, id count, ...) to the span's JSON line.
+    pub fn with(mut self, key: &str, value: impl Into<serde_json::Value>) -> Self {
+        if self.id != 0 {
+            self.fields.insert(key.to_string(), value.into());
+        }
+        self
+    }
+}
+
+/// Starts a span charged to the current listing (see [`set_current_trace`]).
+pub fn span(name: &'static str) -> Span {
+    if !is_enabled() {
+        return Span::disabled(name);
+    }
+    let trace = CURRENT_TRACE.lock_ignore_poison().clone();
+    start_span(name, trace)
+}
+
+/// Starts a span charged to `listing_id`.
+pub fn span_for(name: &'static str, listing_id: &str) -> Span {
+    if !is_enabled() {
+        return Span::disabled(name);
+    }
+    start_span(name, Some(listing_id.to_string()))
+}
+
+fn start_span(name: &'static str, trace: Option<String>) -> Span {
+    Span {
+        id: NEXT_SPAN_ID.fetch_add(1, Ordering::Relaxed),
+        name,
+        trace,
+        start: now_micros(),
+        fields: serde_json::Map::new(),
+    }
+}
+
+impl Drop for Span {
+    fn drop(&mut self) {
+        if self.id == 0 {
+            return;
+        }
+        let line = serde_json::json!({
+            "type": "span",
+            "trace": self.trace,
+            "span": self.id,
+            "name": self.name,
+            "startUs": self.start,
+            "durUs": now_micros().saturating_sub(self.start),
+            "fields": std::mem::take(&mut self.fields),
+        });
+        eprintln!("{line}");
+    }
+}
@@ -0,0 +1,78 @@
+//! Cancel flags of in-flight long operations (compare, checksum, folder size, …), keyed by
+//! operation id. Each operation kind owns one `static` registry: its start command registers
+//! the id and passes the flag down, its cancel command flips it, and the start command
+//! unregisters when the work settles.
+
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use crate::ignore_poison::IgnorePoison;
+
+#[derive(Default)]
+pub struct CancelRegistry {
+    flags: Mutex<BTreeMap<String, Arc<AtomicBool>>>,
+}
+
+impl CancelRegistry {
+    pub const fn new() -> Self {
+        Self {
+            flags: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// Registers an operation under the caller-chosen `id` and returns its cancel flag. Pair
+    /// with [`unregister`](Self::unregister).
+    pub fn register(&self, id: &str) -> Arc<AtomicBool> {
+        let flag = Arc::new(AtomicBool::new(false));
+        self.flags
+            .lock_ignore_poison()
+            .insert(id.to_string(), Arc::clone(&flag));
+        flag
+    }
+
+    /// [`register`](Self::register) under a fresh `{prefix}-{uuid}` id, for operations whose
+    /// id the backend hands out.
+    pub fn register_new(&self, prefix: &str) -> (String, Arc<AtomicBool>) {
+        let id = format!("{prefix}-{}", uuid::Uuid::new_v4());
+        let flag = self.register(&id);
+        (id, flag)
+    }
+
+    pub fn unregister(&self, id: &str) {
+        self.flags.lock_ignore_poison().remove(id);
+    }
+
+    /// Flips the cancel flag of a running operation. Returns `false` if none with that id is
+    /// running (already finished, or never started).
+    pub fn cancel(&self, id: &str) -> bool {
+        match self.flags.lock_ignore_poison().get(id) {
+            Some(flag) => {
+                flag.store(true, Ordering::Relaxed);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cancel_flips_only_registered_operations() {
+        let registry = CancelRegistry::new();
+        let flag = registry.register("op");
+        let (other_id, other) = registry.register_new("walk");
+        assert!(other_id.starts_with("walk-"));
+
+        assert!(registry.cancel("op"));
+        assert!(flag.load(Ordering::Relaxed));
+        assert!(!other.load(Ordering::Relaxed));
+
+        registry.unregister("op");
+        assert!(!registry.cancel("op"));
+        assert!(registry.cancel(&other_id));
+    }
+}
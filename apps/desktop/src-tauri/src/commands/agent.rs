@@ -904,6 +904,7 @@ pub async fn apply_bulk_rename(
         volume_id,
         rows,
         crate::operation_log::types::Initiator::Agent,
+        false,
     )
     .map_err(IpcError::from_err)
 }
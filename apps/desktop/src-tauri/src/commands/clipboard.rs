@@ -12,6 +12,11 @@ use objc2::MainThreadMarker;
 use crate::file_system::get_paths_at_indices as ops_get_paths_at_indices;
 
 use crate::clipboard;
+#[cfg(target_os = "macos")]
+use crate::commands::file_system::{copy_files, move_files};
+use crate::file_system::{WriteOperationConfig, WriteOperationError, WriteOperationStartResult};
+#[cfg(target_os = "macos")]
+use crate::operation_log::types::Initiator;
 
 #[derive(serde::Serialize, specta::Type)]
 #[serde(rename_all = "camelCase")]
@@ -169,15 +174,12 @@ pub async fn cut_files_to_clipboard(
     Ok(count)
 }
 
-/// Reads file URLs from the system clipboard and checks whether they were cut.
-///
-/// If the clipboard contents no longer match the recorded cut state (the user copied
-/// something else), the stale cut state is automatically cleared.
+/// Reads the clipboard's file URLs (on the main thread) and whether they're the
+/// paths Cmdr cut. If the clipboard no longer matches the recorded cut state (the
+/// user copied something else, here or in another app), the stale cut state is
+/// cleared and the paths read as a copy.
 #[cfg(target_os = "macos")]
-#[tauri::command]
-#[specta::specta]
-pub async fn read_clipboard_files(app: tauri::AppHandle) -> Result<ClipboardReadResult, String> {
-    // Read from pasteboard on the main thread
+fn read_clipboard_paths(app: &tauri::AppHandle) -> Result<(Vec<PathBuf>, bool), String> {
     let (tx, rx) = std::sync::mpsc::channel();
     app.run_on_main_thread(move || {
         let mtm = MainThreadMarker::new().expect("run_on_main_thread runs on the main thread");
@@ -206,6 +208,19 @@ pub async fn read_clipboard_files(app: tauri::AppHandle) -> Result<ClipboardRead
         false
     };
 
+    Ok((clipboard_paths, is_cut))
+}
+
+/// Reads file URLs from the system clipboard and checks whether they were cut.
+///
+/// If the clipboard contents no longer match the recorded cut state (the user copied
+/// something else), the stale cut state is automatically cleared.
+#[cfg(target_os = "macos")]
+#[tauri::command]
+#[specta::specta]
+pub async fn read_clipboard_files(app: tauri::AppHandle) -> Result<ClipboardReadResult, String> {
+    let (clipboard_paths, is_cut) = read_clipboard_paths(&app)?;
+
     let paths: Vec<String> = clipboard_paths
         .iter()
         .map(|p| p.to_string_lossy().into_owned())
@@ -228,6 +243,42 @@ pub async fn read_clipboard_files(app: tauri::AppHandle) -> Result<ClipboardRead
     })
 }
 
+/// Pastes the clipboard's files into `destination` through the write pipeline: a
+/// move when they were cut in Cmdr (or `force_move`), a copy otherwise. The
+/// clipboard may come from Finder or any other app (pasteboard file URLs). Emits
+/// the usual write-progress / write-complete events, so the paste shows progress
+/// like any transfer. `None` when the clipboard holds no files.
+#[cfg(target_os = "macos")]
+#[tauri::command]
+#[specta::specta]
+pub async fn paste_clipboard_files(
+    app: tauri::AppHandle,
+    destination: String,
+    force_move: Option<bool>,
+    config: Option<WriteOperationConfig>,
+) -> Result<Option<WriteOperationStartResult>, WriteOperationError> {
+    let (paths, is_cut) = read_clipboard_paths(&app).map_err(|message| WriteOperationError::IoError {
+        path: destination.clone(),
+        message,
+    })?;
+    if paths.is_empty() {
+        return Ok(None);
+    }
+    let sources: Vec<String> = paths.iter().map(|p| p.to_string_lossy().into_owned()).collect();
+
+    let started = if is_cut || force_move.unwrap_or(false) {
+        let started = move_files(app, sources, destination, config, Some(Initiator::User)).await?;
+        // A cut pastes once, like Finder's "Move item here".
+        if is_cut {
+            clipboard::clear_cut_state();
+        }
+        started
+    } else {
+        copy_files(app, sources, destination, config, Some(Initiator::User)).await?
+    };
+    Ok(Some(started))
+}
+
 /// Reads plain text from the system clipboard.
 ///
 /// Used by the frontend to paste text into input fields. Going through Rust bypasses
@@ -371,6 +422,21 @@ pub async fn read_clipboard_files(_app: tauri::AppHandle) -> Result<ClipboardRea
     Err("Clipboard file operations are not yet supported on this platform".to_string())
 }
 
+#[cfg(not(target_os = "macos"))]
+#[tauri::command]
+#[specta::specta]
+pub async fn paste_clipboard_files(
+    _app: tauri::AppHandle,
+    destination: String,
+    _force_move: Option<bool>,
+    _config: Option<WriteOperationConfig>,
+) -> Result<Option<WriteOperationStartResult>, WriteOperationError> {
+    Err(WriteOperationError::IoError {
+        path: destination,
+        message: "Clipboard file operations are not yet supported on this platform".to_string(),
+    })
+}
+
 #[cfg(not(target_os = "macos"))]
 #[tauri::command]
 #[specta::specta]
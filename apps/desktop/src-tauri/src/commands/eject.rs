@@ -7,10 +7,12 @@ use crate::file_system::volume::eject::{self, EjectError};
 
 /// Ejects a volume. Picks the right teardown for the volume's kind.
 ///
-/// Returns `Ok(())` once the unmount or disconnect is initiated. The frontend
-/// shouldn't wait for the volume to fully disappear — `volume-unmounted` (for
-/// disk volumes) or `mtp-device-disconnected` (for MTP) will fire shortly
-/// after and panes rooted at the volume redirect to root.
+/// Returns `Ok(())` once the unmount or disconnect is done, after
+/// `volume-ejected` fired. Refuses with the blocking operation ids when a write
+/// op still touches the volume. The frontend shouldn't wait for the volume to
+/// fully disappear — `volume-unmounted` (for disk volumes) or
+/// `mtp-device-disconnected` (for MTP) will fire shortly after and panes
+/// rooted at the volume redirect to root.
 #[tauri::command]
 #[specta::specta]
 pub async fn eject_volume(volume_id: String) -> Result<(), IpcError> {
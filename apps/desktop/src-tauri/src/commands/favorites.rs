@@ -2,18 +2,31 @@
 //!
 //! Thin pass-throughs over the `favorites::store` module. Each mutation persists `favorites.json`
 //! (a filesystem write, so it runs on the blocking pool with a timeout) and then re-emits
-//! `volumes-changed` so both panes' switchers refresh live (subscribe-don't-poll). Listing rides
-//! the existing `list_volumes` / `volumes-changed` path, so there's no `list_favorites` command.
+//! `volumes-changed` so both panes' switchers refresh live (subscribe-don't-poll). The switcher's
+//! list rides the existing `list_volumes` / `volumes-changed` path; `list_favorites` serves the
+//! favorites sidebar, which also needs each entry's reachability.
 
 use tokio::time::Duration;
 
 use crate::commands::util::{IpcError, blocking_result_with_timeout};
-use crate::favorites::store;
+use crate::favorites::store::{self, FavoriteStatus};
 
 /// 5s matches the write timeout other persisting commands use. The store write is local-only, but a
 /// hung data-dir mount must never freeze the IPC thread.
 const PERSIST_TIMEOUT: Duration = Duration::from_secs(5);
 
+/// Every `exists` stat runs under this, so one hung mount can't stall the sidebar forever.
+const LIST_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Lists the favorites in order, each with its resolved path (following a remounted volume) and
+/// whether it's reachable right now. Unlike the switcher's list, unreachable favorites are included
+/// so the sidebar can show them greyed out.
+#[tauri::command]
+#[specta::specta]
+pub async fn list_favorites() -> Result<Vec<FavoriteStatus>, IpcError> {
+    blocking_result_with_timeout(LIST_TIMEOUT, || Ok(store::list_with_status())).await
+}
+
 /// Adds a favorite for `path`, deduping by normalized path. When `name` is omitted, the label
 /// defaults to the path's file name.
 #[tauri::command]
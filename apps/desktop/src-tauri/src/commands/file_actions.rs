@@ -1,12 +1,21 @@
 //! Direct file-action commands invoked from the command palette, context menus,
 //! and menu items: reveal in Finder, Get Info, open in the default editor, copy
-//! text to the clipboard, and the iCloud make-available-offline / remove-download
-//! pair. Thin pass-throughs that shell out or delegate to `file_system`.
+//! text to the clipboard, the iCloud make-available-offline / remove-download
+//! pair, the provider-agnostic `materialize_file` / `dematerialize_file` pair, and
+//! `resolve_default_action` (what Enter does on an entry). Thin pass-throughs that shell
+//! out or delegate to `file_system`.
 
 #[cfg(any(target_os = "macos", target_os = "linux"))]
 use std::process::Command;
+
+use serde::{Deserialize, Serialize};
 use tauri::{AppHandle, Runtime};
 use tauri_plugin_clipboard_manager::ClipboardExt;
+use tauri_specta::Event;
+
+use crate::commands::util::{IpcError, blocking_result_with_timeout};
+use crate::file_system::cloud_actions::MaterializeOutcome;
+use crate::file_system::default_action::{self, DefaultAction};
 
 /// Show a file in Finder (reveal in parent folder)
 #[tauri::command]
@@ -147,6 +156,61 @@ pub fn open_path(_path: String) -> Result<(), String> {
     Err("Open is not available on this platform".to_string())
 }
 
+/// LaunchServices on a cold cache (or a cloud-stub file) can take a while; never hold IPC longer.
+const OPEN_WITH_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Lists the apps the OS associates with `path`'s type, for an "Open With" submenu. The OS default
+/// comes first, except that the app last used for this extension (when it wasn't the default) is
+/// moved to the front. Empty on platforms without Launch Services.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_applications_for(
+    app: AppHandle,
+    path: String,
+) -> Result<Vec<crate::file_system::open_with::OpenWithApp>, crate::commands::util::IpcError> {
+    use crate::file_system::{open_with, open_with_recents};
+
+    crate::commands::util::blocking_result_with_timeout(OPEN_WITH_TIMEOUT, move || {
+        let path = std::path::Path::new(&path);
+        let last_used =
+            open_with::extension_cache_key(path).and_then(|ext| open_with_recents::last_used_for(&app, &ext));
+        Ok(open_with::order_for_frontend(
+            open_with::app_candidates_for(path),
+            last_used.as_deref(),
+        ))
+    })
+    .await
+}
+
+/// Opens `path` with the app whose bundle id is `app_identifier` (from `get_applications_for`), and
+/// remembers it for the extension when it isn't the default.
+#[tauri::command]
+#[specta::specta]
+pub async fn open_with(
+    app: AppHandle,
+    path: String,
+    app_identifier: String,
+) -> Result<(), crate::commands::util::IpcError> {
+    use crate::file_system::{open_with, open_with_recents};
+
+    crate::commands::util::blocking_result_with_timeout(OPEN_WITH_TIMEOUT, move || {
+        let app_path = open_with::app_path_for_bundle_id(&app_identifier)
+            .ok_or_else(|| format!("No app found for '{app_identifier}'"))?;
+        let file = std::path::PathBuf::from(&path);
+        // Same launch-free E2E swap as `open_path`.
+        #[cfg(not(feature = "playwright-e2e"))]
+        open_with::open_paths_with(std::slice::from_ref(&file), &app_path)?;
+        #[cfg(feature = "playwright-e2e")]
+        {
+            let _ = app_path;
+            open_mock::record(path);
+        }
+        open_with_recents::record_launch(&app, &file, &app_identifier);
+        Ok(())
+    })
+    .await
+}
+
 /// E2E variant: record the open request instead of launching an external app,
 /// so the suite never floods the desktop with orphan TextEdit/Preview windows.
 /// Specs can assert intent via `open_mock::snapshot` / `open_mock::clear`.
@@ -247,3 +311,68 @@ pub async fn cloud_remove_download(path: String) -> Result<(), String> {
         Err(_elapsed) => Err("Timed out reaching iCloud — give it another try".to_string()),
     }
 }
+
+/// Progress of a running `materialize_file`, at most every 200 ms. `bytes_done` is how much
+/// the provider has written so far, so it's approximate.
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type, tauri_specta::Event)]
+#[tauri_specta(event_name = "cloud-materialize-progress")]
+#[serde(rename_all = "camelCase")]
+pub struct CloudMaterializeProgressEvent {
+    pub path: String,
+    pub bytes_done: u64,
+    pub bytes_total: u64,
+}
+
+/// Downloads an online-only file (iCloud Drive, Dropbox, Google Drive) so it's available
+/// offline, streaming `cloud-materialize-progress` events. Returns `already_local` without
+/// downloading when the content is already on disk. Not timeout-wrapped: a big file
+/// legitimately takes minutes. A download that stops growing fails after two minutes instead.
+#[tauri::command]
+#[specta::specta]
+pub async fn materialize_file(app: AppHandle, path: String) -> Result<MaterializeOutcome, String> {
+    let work = tokio::task::spawn_blocking(move || {
+        let on_progress = |bytes_done, bytes_total| {
+            let _ = CloudMaterializeProgressEvent {
+                path: path.clone(),
+                bytes_done,
+                bytes_total,
+            }
+            .emit(&app);
+        };
+        crate::file_system::cloud_actions::materialize_file(std::path::Path::new(&path), &on_progress)
+    });
+    work.await.map_err(|e| format!("Download task failed: {e}"))?
+}
+
+/// Evicts a file's local copy back to online-only. Counterpart to `materialize_file`; a
+/// no-op for a file that's already online-only. Only iCloud Drive supports it.
+#[tauri::command]
+#[specta::specta]
+pub async fn dematerialize_file(path: String) -> Result<(), String> {
+    // 30s timeout: same hung-File-Provider risk as `cloud_remove_download`.
+    let work = tokio::task::spawn_blocking(move || {
+        crate::file_system::cloud_actions::dematerialize_file(std::path::Path::new(&path))
+    });
+    match tokio::time::timeout(tokio::time::Duration::from_secs(30), work).await {
+        Ok(joined) => joined.map_err(|e| e.to_string())?,
+        Err(_elapsed) => Err("Timed out reaching iCloud — give it another try".to_string()),
+    }
+}
+
+/// Resolves what Enter or a double-click should do on `path` (see
+/// `file_system/default_action.rs`). Pass `is_directory` from the listing entry when known;
+/// `None` stats the path (2 s read timeout), and a path that can't be stat'ed, such as an
+/// entry inside an archive, counts as a file.
+#[tauri::command]
+#[specta::specta]
+pub async fn resolve_default_action(path: String, is_directory: Option<bool>) -> Result<DefaultAction, IpcError> {
+    let path = std::path::PathBuf::from(path);
+    if let Some(is_directory) = is_directory {
+        return Ok(default_action::resolve_default_action(&path, is_directory));
+    }
+    blocking_result_with_timeout(tokio::time::Duration::from_secs(2), move || {
+        let is_directory = std::fs::metadata(&path).is_ok_and(|m| m.is_dir());
+        Ok(default_action::resolve_default_action(&path, is_directory))
+    })
+    .await
+}
@@ -0,0 +1,203 @@
+//! Folder analysis commands ("what's in this folder", disk-usage treemap, duplicate files,
+//! largest files, on-demand folder size, folder badge counts). The work lives in
+//! [`crate::file_system::extension_breakdown`], [`crate::file_system::dir_entry_count`],
+//! [`crate::file_system::treemap`], [`crate::file_system::duplicates`],
+//! [`crate::file_system::largest_files`], and [`crate::file_system::folder_size`]; these only resolve volumes, bound the wait, and stream
+//! results as events.
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use tauri_specta::Event;
+use tokio::time::Duration;
+
+use crate::commands::util::{IpcError, blocking_result_with_timeout};
+use crate::file_system::dir_entry_count::{self, DirEntryCount};
+use crate::file_system::duplicates::{self, DuplicateGroup, DuplicateOptions};
+use crate::file_system::extension_breakdown::{self, ExtensionBreakdown};
+use crate::file_system::folder_size::{self, FolderSizeResult, FolderSizeTotals};
+use crate::file_system::get_volume_manager;
+use crate::file_system::largest_files::{self, LargestFiles};
+use crate::file_system::treemap::{self, TreemapData};
+use crate::file_system::volume::DEFAULT_VOLUME_ID;
+
+/// An indexed folder answers in milliseconds; the disk fallback is capped by entry count, so
+/// this only bites on a hung mount. The walk keeps running after a timeout and fills the cache.
+const EXTENSION_BREAKDOWN_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Per-extension file count and total bytes for `path`'s whole subtree, from the drive index
+/// when it fully covers the folder, otherwise from a bounded disk walk.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_extension_breakdown(path: String) -> Result<ExtensionBreakdown, IpcError> {
+    let path = PathBuf::from(super::expand_tilde(&path));
+    blocking_result_with_timeout(EXTENSION_BREAKDOWN_TIMEOUT, move || {
+        extension_breakdown::get_extension_breakdown(&path)
+    })
+    .await
+}
+
+/// Same budget and reasoning as [`EXTENSION_BREAKDOWN_TIMEOUT`]: the disk fallback shares its
+/// entry cap.
+const LARGEST_FILES_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The `limit` largest files anywhere under `path` (at most 1000), biggest first, as
+/// `FileEntry`s the frontend can show as a flat listing. From the drive index when it fully
+/// covers the folder, otherwise from a bounded disk walk.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_largest_files(path: String, limit: u32) -> Result<LargestFiles, IpcError> {
+    let path = PathBuf::from(super::expand_tilde(&path));
+    blocking_result_with_timeout(LARGEST_FILES_TIMEOUT, move || {
+        largest_files::get_largest_files(&path, limit)
+    })
+    .await
+}
+
+/// One `COUNT` or one `read_dir`, so anything past this is a hung mount. Kept short because the
+/// tree view asks for many folders at once and a stuck one shouldn't hold a badge spinner long.
+const DIR_ENTRY_COUNT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// File and folder counts directly inside `path`, for the tree view's folder badges. Reads the
+/// drive index when it covers the folder, otherwise a bare `read_dir` without per-entry stats.
+/// `include_hidden` (default `true`) mirrors the "show hidden files" setting.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_dir_entry_count(path: String, include_hidden: Option<bool>) -> Result<DirEntryCount, IpcError> {
+    let path = PathBuf::from(super::expand_tilde(&path));
+    let include_hidden = include_hidden.unwrap_or(true);
+    blocking_result_with_timeout(DIR_ENTRY_COUNT_TIMEOUT, move || {
+        dir_entry_count::get_dir_entry_count(&path, include_hidden)
+    })
+    .await
+}
+
+/// Same budget and reasoning as [`EXTENSION_BREAKDOWN_TIMEOUT`].
+const TREEMAP_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Nested directory sizes under `path`, `depth` levels deep, with boxes under `min_fraction`
+/// of their parent folded into the parent's `other_size`. Aggregates only, no per-file entries.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_treemap_data(path: String, depth: u32, min_fraction: f64) -> Result<TreemapData, IpcError> {
+    let path = PathBuf::from(super::expand_tilde(&path));
+    blocking_result_with_timeout(TREEMAP_TIMEOUT, move || {
+        treemap::get_treemap_data(&path, depth, min_fraction)
+    })
+    .await
+}
+
+/// A newly confirmed group of identical files from a running duplicate search.
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type, tauri_specta::Event)]
+#[tauri_specta(event_name = "duplicate-group-found")]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicateGroupFoundEvent {
+    pub search_id: String,
+    pub group: DuplicateGroup,
+}
+
+/// Finds groups of identical files under `path` on `volume_id` and returns them all. Each
+/// group also streams as a `duplicate-group-found` event tagged with `search_id` as soon as
+/// it's confirmed, largest files first. Not timeout-wrapped: hashing a big tree legitimately
+/// takes longer than any IPC tier, so it's bounded by [`cancel_find_duplicates`] instead.
+#[tauri::command]
+#[specta::specta]
+pub async fn find_duplicates(
+    app: tauri::AppHandle,
+    search_id: String,
+    volume_id: String,
+    path: String,
+    options: Option<DuplicateOptions>,
+) -> Result<Vec<DuplicateGroup>, String> {
+    let root = if volume_id == DEFAULT_VOLUME_ID {
+        PathBuf::from(super::expand_tilde(&path))
+    } else {
+        PathBuf::from(path)
+    };
+    let resolved = get_volume_manager().resolve(&volume_id, Path::new(&root)).await;
+    let volume = resolved
+        .volume
+        .ok_or_else(|| format!("no volume registered for '{volume_id}'"))?;
+    let options = options.unwrap_or_default();
+
+    let cancelled = duplicates::ACTIVE_SEARCHES.register(&search_id);
+    let on_group = |group: &DuplicateGroup| {
+        let _ = DuplicateGroupFoundEvent {
+            search_id: search_id.clone(),
+            group: group.clone(),
+        }
+        .emit(&app);
+    };
+    let result = duplicates::find_duplicates(&*volume, &resolved.path, &options, &cancelled, &on_group).await;
+    duplicates::ACTIVE_SEARCHES.unregister(&search_id);
+    result.map_err(|e| e.to_string())
+}
+
+/// Cancels a running duplicate search. Returns `false` if it already finished.
+#[tauri::command]
+#[specta::specta]
+pub fn cancel_find_duplicates(search_id: String) -> bool {
+    duplicates::ACTIVE_SEARCHES.cancel(&search_id)
+}
+
+/// Running totals of a folder-size walk, at most every 200 ms.
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type, tauri_specta::Event)]
+#[tauri_specta(event_name = "folder-size-progress")]
+#[serde(rename_all = "camelCase")]
+pub struct FolderSizeProgressEvent {
+    pub operation_id: String,
+    pub totals: FolderSizeTotals,
+}
+
+/// The end of a folder-size walk: exactly one per `start_folder_size`. `result` is `None`
+/// when the walk failed, with the reason in `error`.
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type, tauri_specta::Event)]
+#[tauri_specta(event_name = "folder-size-complete")]
+#[serde(rename_all = "camelCase")]
+pub struct FolderSizeCompleteEvent {
+    pub operation_id: String,
+    pub result: Option<FolderSizeResult>,
+    pub error: Option<String>,
+}
+
+/// Starts a live recursive size walk of `path` and returns its operation id right away.
+/// Totals stream as `folder-size-progress` and end with one `folder-size-complete`; stop it
+/// with [`cancel_folder_size`]. With `write_to_index`, the walk is also stored in the drive
+/// index when possible, so the folder's size shows up instantly next time.
+#[tauri::command]
+#[specta::specta]
+pub fn start_folder_size(app: tauri::AppHandle, path: String, write_to_index: Option<bool>) -> String {
+    let path = PathBuf::from(super::expand_tilde(&path));
+    let (operation_id, cancelled) = folder_size::ACTIVE_WALKS.register_new("folder-size");
+    let id = operation_id.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let on_progress = |totals| {
+            let _ = FolderSizeProgressEvent {
+                operation_id: id.clone(),
+                totals,
+            }
+            .emit(&app);
+        };
+        let result = folder_size::compute_folder_size(&path, write_to_index.unwrap_or(false), &cancelled, &on_progress);
+        folder_size::ACTIVE_WALKS.unregister(&id);
+        let (result, error) = match result {
+            Ok(result) => (Some(result), None),
+            Err(e) => (None, Some(e)),
+        };
+        let _ = FolderSizeCompleteEvent {
+            operation_id: id,
+            result,
+            error,
+        }
+        .emit(&app);
+    });
+    operation_id
+}
+
+/// Cancels a running folder-size walk. Returns `false` if it already finished. The walk still
+/// ends with a `folder-size-complete`, flagged `cancelled`.
+#[tauri::command]
+#[specta::specta]
+pub fn cancel_folder_size(operation_id: String) -> bool {
+    folder_size::ACTIVE_WALKS.cancel(&operation_id)
+}
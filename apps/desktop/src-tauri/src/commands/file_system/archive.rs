@@ -1,5 +1,6 @@
-//! Archive-password commands: store or clear the per-archive password an
-//! encrypted archive needs to browse or extract.
+//! Archive commands: store or clear the per-archive password an encrypted
+//! archive needs to browse or extract, list an archive's contents, and extract
+//! a whole archive into a folder.
 //!
 //! The password lives on the resolved [`ArchiveVolume`] (remember-for-this-archive
 //! — see its docs), which `VolumeManager::resolve` mints per archive and
@@ -9,9 +10,24 @@
 //! `NeedsPassword` (browse) / `ArchiveNeedsPassword` (extract) signal.
 
 use std::path::Path;
+use std::sync::Arc;
 
-use crate::file_system::get_volume_manager;
 use crate::file_system::volume::backends::archive::ArchiveVolume;
+use crate::file_system::{
+    Volume, VolumeCopyConfig, WriteOperationError, WriteOperationStartResult, get_volume_manager,
+};
+use crate::operation_log::types::Initiator;
+
+/// One entry of [`list_archive`]: its path inside the archive (`/`-separated,
+/// no leading slash), uncompressed size, and whether it's a directory.
+#[derive(Debug, Clone, serde::Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveListEntry {
+    pub path: String,
+    /// `None` for directories.
+    pub size: Option<u64>,
+    pub is_directory: bool,
+}
 
 /// Stores `password` for the archive at `archive_path` on `parent_volume_id`,
 /// overwriting any previous one (so a fresh attempt replaces a rejected password).
@@ -41,6 +57,110 @@ pub async fn clear_archive_password(parent_volume_id: String, archive_path: Stri
     with_archive(&parent_volume_id, &archive_path, ArchiveVolume::clear_password).await
 }
 
+/// Lists every entry in the archive at `archive_path` without extracting
+/// anything: reads the cached index only. Entries whose names would escape the
+/// archive root (`..`) are not listed.
+#[tauri::command]
+#[specta::specta]
+pub async fn list_archive(parent_volume_id: String, archive_path: String) -> Result<Vec<ArchiveListEntry>, String> {
+    let volume = archive_volume(&parent_volume_id, &archive_path).await?;
+    let archive = as_archive(&volume, &archive_path)?;
+    let contents = archive.contents().await.map_err(|e| e.to_string())?;
+    Ok(contents
+        .entries
+        .into_iter()
+        .map(|node| ArchiveListEntry {
+            path: node.path,
+            size: node.size,
+            is_directory: node.is_dir,
+        })
+        .collect())
+}
+
+/// Extracts the whole archive at `archive_path` into `dest_path` on
+/// `dest_volume_id`. The top-level entries go through [`super::copy_between_volumes`]
+/// with `config`, so conflicts (`write-conflict` prompts or a pre-set
+/// `ConflictResolution`), per-entry progress, cancel, and the journal all work
+/// like any extract-by-copy.
+///
+/// Zip Slip: an archive with any `..` entry is refused up front with a typed
+/// `InvalidName` (what the frontend renders for a `write-error`), naming the
+/// first escaping entry; every one is logged. Those entries are never in the
+/// index, so they couldn't be written anyway — refusing says so instead of
+/// silently extracting a partial archive.
+#[allow(clippy::too_many_arguments, reason = "Tauri command params must be top-level args")]
+#[tauri::command]
+#[specta::specta]
+pub async fn extract_archive(
+    app: tauri::AppHandle,
+    parent_volume_id: String,
+    archive_path: String,
+    dest_volume_id: String,
+    dest_path: String,
+    config: Option<VolumeCopyConfig>,
+    initiator: Option<Initiator>,
+) -> Result<WriteOperationStartResult, WriteOperationError> {
+    let io_error = |message: String| WriteOperationError::IoError {
+        path: archive_path.clone(),
+        message,
+    };
+    let volume = archive_volume(&parent_volume_id, &archive_path)
+        .await
+        .map_err(io_error)?;
+    let archive = as_archive(&volume, &archive_path).map_err(io_error)?;
+    let contents = archive.contents().await.map_err(|e| io_error(e.to_string()))?;
+
+    if let Some(first) = contents.escaping.first() {
+        for name in &contents.escaping {
+            log::warn!(target: "archive", "refusing to extract {archive_path}: entry '{name}' escapes the destination");
+        }
+        return Err(WriteOperationError::InvalidName {
+            path: first.clone(),
+            message: format!(
+                "{} in this archive would be written outside the destination folder.",
+                crate::pluralize::pluralize_with(contents.escaping.len() as u64, "entry", "entries")
+            ),
+        });
+    }
+
+    let sources: Vec<String> = contents
+        .entries
+        .iter()
+        .filter(|node| !node.path.contains('/'))
+        .map(|node| Path::new(&archive_path).join(&node.path).to_string_lossy().into_owned())
+        .collect();
+    if sources.is_empty() {
+        return Err(io_error("The archive is empty.".to_string()));
+    }
+    super::copy_between_volumes(
+        app,
+        parent_volume_id,
+        sources,
+        dest_volume_id,
+        dest_path,
+        config,
+        initiator,
+    )
+    .await
+}
+
+/// Resolves `(parent_volume_id, archive_path)` to the registered volume that
+/// serves it. Pair with [`as_archive`] to reach the [`ArchiveVolume`].
+async fn archive_volume(parent_volume_id: &str, archive_path: &str) -> Result<Arc<dyn Volume>, String> {
+    get_volume_manager()
+        .resolve(parent_volume_id, Path::new(archive_path))
+        .await
+        .volume
+        .ok_or_else(|| format!("no volume registered for '{parent_volume_id}'"))
+}
+
+fn as_archive<'a>(volume: &'a Arc<dyn Volume>, archive_path: &str) -> Result<&'a ArchiveVolume, String> {
+    volume
+        .as_any()
+        .downcast_ref::<ArchiveVolume>()
+        .ok_or_else(|| format!("'{archive_path}' is not an archive"))
+}
+
 /// Resolves `(parent_volume_id, archive_path)` to its [`ArchiveVolume`] and runs
 /// `action` on it. Errs only when the path doesn't resolve to an archive (a
 /// programming error or a since-changed path), never on a wrong password.
@@ -49,16 +169,7 @@ async fn with_archive(
     archive_path: &str,
     action: impl FnOnce(&ArchiveVolume),
 ) -> Result<(), String> {
-    let resolved = get_volume_manager()
-        .resolve(parent_volume_id, Path::new(archive_path))
-        .await;
-    let volume = resolved
-        .volume
-        .ok_or_else(|| format!("no volume registered for '{parent_volume_id}'"))?;
-    let archive = volume
-        .as_any()
-        .downcast_ref::<ArchiveVolume>()
-        .ok_or_else(|| format!("'{archive_path}' is not a password-protected archive"))?;
-    action(archive);
+    let volume = archive_volume(parent_volume_id, archive_path).await?;
+    action(as_archive(&volume, archive_path)?);
     Ok(())
 }
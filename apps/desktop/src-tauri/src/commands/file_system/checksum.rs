@@ -0,0 +1,113 @@
+//! File and folder checksum commands. The hashing lives in [`crate::file_system::checksum`]
+//! and [`crate::file_system::tree_hash`]; these only move it off the async runtime and stream
+//! progress as `checksum-progress` / `tree-hash-progress` events.
+
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+use tauri_specta::Event;
+
+use crate::file_system::checksum::{self, ChecksumAlgorithm, ChecksumResult};
+use crate::file_system::tree_hash::{self, TreeHashProgress, TreeHashResult};
+
+/// Progress of a running checksum, at most every 200 ms. Small files finish before the first one.
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type, tauri_specta::Event)]
+#[tauri_specta(event_name = "checksum-progress")]
+#[serde(rename_all = "camelCase")]
+pub struct ChecksumProgressEvent {
+    pub operation_id: String,
+    pub bytes_done: u64,
+    pub bytes_total: u64,
+}
+
+/// Hashes the file at `path` with `algorithm` and returns the hex digest. Not
+/// timeout-wrapped: a multi-GB file legitimately takes longer than any IPC tier, so it's
+/// bounded by [`cancel_checksum`] with the same `operation_id` instead.
+#[tauri::command]
+#[specta::specta]
+pub async fn compute_checksum(
+    app: tauri::AppHandle,
+    operation_id: String,
+    path: String,
+    algorithm: ChecksumAlgorithm,
+) -> Result<ChecksumResult, String> {
+    let path = PathBuf::from(super::expand_tilde(&path));
+    let cancelled = checksum::ACTIVE_CHECKSUMS.register(&operation_id);
+    let id = operation_id.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        let on_progress = |bytes_done, bytes_total| {
+            let _ = ChecksumProgressEvent {
+                operation_id: id.clone(),
+                bytes_done,
+                bytes_total,
+            }
+            .emit(&app);
+        };
+        checksum::compute_checksum(&path, algorithm, &cancelled, &on_progress)
+    })
+    .await;
+    checksum::ACTIVE_CHECKSUMS.unregister(&operation_id);
+    match result {
+        Ok(result) => result.map_err(|e| e.to_string()),
+        Err(e) => Err(format!("Checksum task failed: {e}")),
+    }
+}
+
+/// Cancels a running checksum. Returns `false` if it already finished.
+#[tauri::command]
+#[specta::specta]
+pub fn cancel_checksum(operation_id: String) -> bool {
+    checksum::ACTIVE_CHECKSUMS.cancel(&operation_id)
+}
+
+/// Progress of a running folder hash, at most every 200 ms.
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type, tauri_specta::Event)]
+#[tauri_specta(event_name = "tree-hash-progress")]
+#[serde(rename_all = "camelCase")]
+pub struct TreeHashProgressEvent {
+    pub operation_id: String,
+    pub files: u64,
+    pub bytes: u64,
+    pub reused_subtrees: u64,
+}
+
+/// Computes the Merkle hash of the folder at `path` (see [`crate::file_system::tree_hash`]).
+/// With `use_index`, subfolders the drive index vouches for reuse their stored hashes; pass
+/// `false` right after writing to the folder. Bounded by [`cancel_tree_hash`], not a timeout.
+#[tauri::command]
+#[specta::specta]
+pub async fn compute_tree_hash(
+    app: tauri::AppHandle,
+    operation_id: String,
+    path: String,
+    use_index: bool,
+) -> Result<TreeHashResult, String> {
+    let path = PathBuf::from(super::expand_tilde(&path));
+    let cancelled = tree_hash::ACTIVE_TREE_HASHES.register(&operation_id);
+    let id = operation_id.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        let on_progress = |progress: TreeHashProgress| {
+            let _ = TreeHashProgressEvent {
+                operation_id: id.clone(),
+                files: progress.files,
+                bytes: progress.bytes,
+                reused_subtrees: progress.reused_subtrees,
+            }
+            .emit(&app);
+        };
+        tree_hash::compute_tree_hash(&path, use_index, &cancelled, &on_progress)
+    })
+    .await;
+    tree_hash::ACTIVE_TREE_HASHES.unregister(&operation_id);
+    match result {
+        Ok(result) => result.map_err(|e| e.to_string()),
+        Err(e) => Err(format!("Folder hash task failed: {e}")),
+    }
+}
+
+/// Cancels a running folder hash. Returns `false` if it already finished.
+#[tauri::command]
+#[specta::specta]
+pub fn cancel_tree_hash(operation_id: String) -> bool {
+    tree_hash::ACTIVE_TREE_HASHES.cancel(&operation_id)
+}
@@ -0,0 +1,116 @@
+//! Directory and file comparison commands. The walk itself lives in
+//! [`crate::file_system::compare`] and the two-file diff in
+//! [`crate::file_system::file_diff`]; these only resolve volumes, stream batches
+//! as `directory-compare-batch` events, and map errors to strings.
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use tauri_specta::Event;
+
+use crate::file_system::Volume;
+use crate::file_system::compare::{self, CompareEntry, CompareOptions, CompareSide};
+use crate::file_system::file_diff::{self, FileDiffOptions, FileDiffResult};
+use crate::file_system::get_volume_manager;
+use crate::file_system::volume::DEFAULT_VOLUME_ID;
+
+/// A batch of newly classified entries from a running comparison. Files of a
+/// directory arrive together; a directory's own row arrives after its subtree.
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type, tauri_specta::Event)]
+#[tauri_specta(event_name = "directory-compare-batch")]
+#[serde(rename_all = "camelCase")]
+pub struct DirectoryCompareBatchEvent {
+    pub compare_id: String,
+    pub entries: Vec<CompareEntry>,
+}
+
+/// Compares `left_path` on `left_volume_id` against `right_path` on
+/// `right_volume_id` and returns every entry. Batches stream as
+/// `directory-compare-batch` events tagged with `compare_id` while the walk runs,
+/// so a large tree fills the view incrementally. Not timeout-wrapped: the walk
+/// goes through async `Volume` calls and is bounded by [`cancel_directory_compare`]
+/// instead, since a big tree legitimately takes longer than any IPC tier.
+#[allow(clippy::too_many_arguments, reason = "Tauri command params must be top-level args")]
+#[tauri::command]
+#[specta::specta]
+pub async fn compare_directories(
+    app: tauri::AppHandle,
+    compare_id: String,
+    left_volume_id: String,
+    left_path: String,
+    right_volume_id: String,
+    right_path: String,
+    options: Option<CompareOptions>,
+) -> Result<Vec<CompareEntry>, String> {
+    let left = resolve_side(&left_volume_id, left_path).await?;
+    let right = resolve_side(&right_volume_id, right_path).await?;
+    let options = options.unwrap_or_default();
+
+    let cancelled = compare::ACTIVE_COMPARES.register(&compare_id);
+    let on_batch = |entries: &[CompareEntry]| {
+        let _ = DirectoryCompareBatchEvent {
+            compare_id: compare_id.clone(),
+            entries: entries.to_vec(),
+        }
+        .emit(&app);
+    };
+    let result = compare::compare_directories(&left, &right, &options, &cancelled, &on_batch).await;
+    compare::ACTIVE_COMPARES.unregister(&compare_id);
+    result.map_err(|e| e.to_string())
+}
+
+/// Cancels a running comparison. Returns `false` if it already finished.
+#[tauri::command]
+#[specta::specta]
+pub fn cancel_directory_compare(compare_id: String) -> bool {
+    compare::ACTIVE_COMPARES.cancel(&compare_id)
+}
+
+/// Compares two local files: whether they're identical, the first differing byte
+/// with a hex window around it, and (with `options.line_diff`) a line diff for text.
+/// Not timeout-wrapped, like `compute_checksum`: two multi-GB files legitimately take
+/// longer than any IPC tier, so it's bounded by [`cancel_file_diff`] with the same
+/// `operation_id` instead.
+#[tauri::command]
+#[specta::specta]
+pub async fn diff_files(
+    operation_id: String,
+    left_path: String,
+    right_path: String,
+    options: Option<FileDiffOptions>,
+) -> Result<FileDiffResult, String> {
+    let left = PathBuf::from(super::expand_tilde(&left_path));
+    let right = PathBuf::from(super::expand_tilde(&right_path));
+    let options = options.unwrap_or_default();
+    let cancelled = file_diff::ACTIVE_DIFFS.register(&operation_id);
+    let result = tokio::task::spawn_blocking(move || file_diff::diff_files(&left, &right, &options, &cancelled)).await;
+    file_diff::ACTIVE_DIFFS.unregister(&operation_id);
+    match result {
+        Ok(result) => result.map_err(|e| e.to_string()),
+        Err(e) => Err(format!("File comparison task failed: {e}")),
+    }
+}
+
+/// Cancels a running file diff. Returns `false` if it already finished.
+#[tauri::command]
+#[specta::specta]
+pub fn cancel_file_diff(operation_id: String) -> bool {
+    file_diff::ACTIVE_DIFFS.cancel(&operation_id)
+}
+
+async fn resolve_side(volume_id: &str, path: String) -> Result<CompareSide, String> {
+    let root = if volume_id == DEFAULT_VOLUME_ID {
+        PathBuf::from(super::expand_tilde(&path))
+    } else {
+        PathBuf::from(path)
+    };
+    let resolved = get_volume_manager().resolve(volume_id, Path::new(&root)).await;
+    let volume: Arc<dyn Volume> = resolved
+        .volume
+        .ok_or_else(|| format!("no volume registered for '{volume_id}'"))?;
+    Ok(CompareSide {
+        volume,
+        root: resolved.path,
+    })
+}
@@ -0,0 +1,30 @@
+//! File info panel commands. The lookup lives in [`crate::file_system::file_info`]; these only
+//! move it off the async runtime and wire up cancellation.
+
+use std::path::PathBuf;
+
+use crate::file_system::file_info::{self, FileInfo};
+
+/// Extended details for the file at `path`: kind, image dimensions, media duration, line count,
+/// and the added/opened dates. Not timeout-wrapped: counting a big text file's lines can take a
+/// while, so it's bounded by [`cancel_file_info`] with the same `request_id` instead. Cached per
+/// path while the file's mtime and size are unchanged.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_file_info(request_id: String, path: String) -> Result<FileInfo, String> {
+    let path = PathBuf::from(super::expand_tilde(&path));
+    let cancelled = file_info::ACTIVE_REQUESTS.register(&request_id);
+    let result = tokio::task::spawn_blocking(move || file_info::get_file_info(&path, &cancelled)).await;
+    file_info::ACTIVE_REQUESTS.unregister(&request_id);
+    match result {
+        Ok(result) => result.map_err(|e| e.to_string()),
+        Err(e) => Err(format!("File info task failed: {e}")),
+    }
+}
+
+/// Cancels a running file info lookup. Returns `false` if it already finished.
+#[tauri::command]
+#[specta::specta]
+pub fn cancel_file_info(request_id: String) -> bool {
+    file_info::ACTIVE_REQUESTS.cancel(&request_id)
+}
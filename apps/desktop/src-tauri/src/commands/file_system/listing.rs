@@ -3,27 +3,35 @@
 use crate::file_system::get_files_at_indices as ops_get_files_at_indices;
 use crate::file_system::get_paths_at_indices as ops_get_paths_at_indices;
 use crate::file_system::{
-    BriefColumnsError, DirectorySortMode, FileEntry, ListingStartResult, ListingStats, ResortResult, SortColumn,
-    SortOrder, StreamingListingStartResult, cancel_listing as ops_cancel_listing,
+    BriefColumnsError, DirectorySortMode, FileEntry, ListingChunkMode, ListingStartResult, ListingStats, ResortResult,
+    SortColumn, SortOrder, StreamingListingStartResult, cancel_listing as ops_cancel_listing,
     compute_brief_column_text_widths as ops_compute_brief_column_text_widths, find_file_index as ops_find_file_index,
-    find_file_indices as ops_find_file_indices,
+    find_file_indices as ops_find_file_indices, find_next_match_in_listing as ops_find_next_match_in_listing,
     fuzzy_find_first_match_in_listing as ops_fuzzy_find_first_match_in_listing, get_file_at as ops_get_file_at,
     get_file_range as ops_get_file_range, get_listing_stats as ops_get_listing_stats,
     get_total_count as ops_get_total_count, get_volume_manager, list_directory_end as ops_list_directory_end,
     list_directory_start_streaming as ops_list_directory_start_streaming,
-    list_directory_start_with_volume as ops_list_directory_start_with_volume,
+    list_directory_start_with_volume as ops_list_directory_start_with_volume, prefetch_listing as ops_prefetch_listing,
     refresh_listing_index_sizes as ops_refresh_listing_index_sizes, resort_listing as ops_resort_listing,
 };
 use std::path::{Path, PathBuf};
 use tokio::time::Duration;
 
 use crate::commands::util::{IpcError, TimedOut, blocking_result_with_timeout, blocking_with_timeout_flag};
+use crate::file_system::listing::columns::{self, ColumnSetting, ColumnsError};
+use crate::file_system::listing::eviction::{self, ListingCacheStats};
+use crate::file_system::listing::flat::{self, FlatListingOptions};
+use crate::file_system::listing::tree::{self, TreeChildrenOptions, TreeNode};
 use crate::file_system::validation::{MAX_NAME_BYTES, MAX_PATH_BYTES};
+use crate::menu::ViewMode;
 
 use super::expand_tilde;
 
 const PATH_EXISTS_TIMEOUT: Duration = Duration::from_secs(2);
 const TAGS_TIMEOUT: Duration = Duration::from_secs(2);
+/// Read tier, stretched: one tree level costs a `readdir` per subfolder when the index
+/// doesn't cover it.
+const TREE_CHILDREN_TIMEOUT: Duration = Duration::from_secs(5);
 /// Tag writes are the 5 s "write" tier per `commands/CLAUDE.md`. A `setxattr` on a
 /// hung mount can block; the timeout keeps it off the IPC thread (the blocking task
 /// runs to completion, but the IPC handler returns).
@@ -200,7 +208,8 @@ pub async fn list_directory_start(
 }
 
 /// Returns immediately; reads in background.
-/// Emits listing-progress, listing-complete, listing-error, listing-cancelled.
+/// Emits listing-progress, listing-chunk (huge folders only), listing-complete, listing-error,
+/// listing-cancelled. `chunk_mode` defaults to `Incremental`.
 #[tauri::command]
 #[specta::specta]
 #[allow(clippy::too_many_arguments, reason = "Tauri commands require top-level arguments")]
@@ -213,6 +222,7 @@ pub async fn list_directory_start_streaming(
     sort_order: SortOrder,
     directory_sort_mode: Option<DirectorySortMode>,
     listing_id: String,
+    chunk_mode: Option<ListingChunkMode>,
 ) -> Result<StreamingListingStartResult, String> {
     // Foreground activity: the user navigated THIS volume. Attributing it is what
     // lets the NAS index scan and SMB transfers back off for the share the user is
@@ -234,18 +244,75 @@ pub async fn list_directory_start_streaming(
         sort_by,
         sort_order,
         dir_sort_mode,
+        chunk_mode.unwrap_or_default(),
         listing_id,
     )
     .await
     .map_err(|e| format!("Failed to start directory listing '{}': {}", path, e))
 }
 
+/// Starts a flattened listing of everything below `path` (see `listing/flat.rs`). Returns
+/// immediately and emits the same events as `list_directory_start_streaming`; entry names
+/// are paths relative to `path`. `cancel_listing` and `list_directory_end` apply as usual.
+#[tauri::command]
+#[specta::specta]
+#[allow(clippy::too_many_arguments, reason = "Tauri commands require top-level arguments")]
+pub fn list_directory_flat(
+    app: tauri::AppHandle,
+    volume_id: String,
+    path: String,
+    options: FlatListingOptions,
+    sort_by: SortColumn,
+    sort_order: SortOrder,
+    directory_sort_mode: Option<DirectorySortMode>,
+    listing_id: String,
+) -> StreamingListingStartResult {
+    crate::priority::foreground::note_foreground_activity_on(&volume_id);
+    let expanded_path = if volume_id == "root" { expand_tilde(&path) } else { path };
+    flat::list_directory_flat_start(
+        app,
+        &volume_id,
+        Path::new(&expanded_path),
+        options,
+        sort_by,
+        sort_order,
+        directory_sort_mode.unwrap_or_default(),
+        listing_id,
+    )
+}
+
+/// Returns the subfolders of `path` for the sidebar tree, each with a `has_children` flag
+/// (see `listing/tree.rs`). One level per call; the frontend fetches deeper levels on expand.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_tree_children(
+    volume_id: String,
+    path: String,
+    options: TreeChildrenOptions,
+) -> Result<Vec<TreeNode>, IpcError> {
+    let expanded_path = if volume_id == "root" { expand_tilde(&path) } else { path };
+    blocking_result_with_timeout(TREE_CHILDREN_TIMEOUT, move || {
+        tree::get_tree_children(&volume_id, Path::new(&expanded_path), &options)
+    })
+    .await
+}
+
 #[tauri::command]
 #[specta::specta]
 pub fn cancel_listing(listing_id: String) {
     ops_cancel_listing(&listing_id);
 }
 
+/// Warms the listing for `path` in the background, so navigating there skips the disk read.
+/// The frontend calls this for the folder the cursor rests on. Returns immediately; the
+/// pre-fetch is best-effort and never reports an error (see `listing/prefetch.rs`).
+#[tauri::command]
+#[specta::specta]
+pub async fn prefetch_listing(volume_id: String, path: String) {
+    let path = PathBuf::from(expand_tilde(&path));
+    tokio::spawn(async move { ops_prefetch_listing(&volume_id, &path).await });
+}
+
 #[allow(clippy::too_many_arguments, reason = "Tauri commands require top-level arguments")]
 #[tauri::command]
 #[specta::specta]
@@ -299,6 +366,7 @@ pub fn get_total_count(listing_id: String, include_hidden: bool) -> Result<usize
 ///   retries after `ensureFontMetricsLoaded` resolves.
 /// - `invalid_items_per_column`: caller sent 0; FE clamps to >= 1 normally.
 /// - `listing_not_found:{id}`: listing already ended (or never started).
+/// - `listing_not_ready:{id}`: listing was evicted and is being re-read; a `directory-diff` follows.
 /// - Anything else is a pass-through (cache-lock poisoning etc.).
 #[tauri::command]
 #[specta::specta]
@@ -315,12 +383,33 @@ pub async fn get_brief_column_text_widths(
                 BriefColumnsError::FontMetricsNotReady => "font_metrics_not_ready".to_string(),
                 BriefColumnsError::InvalidItemsPerColumn => "invalid_items_per_column".to_string(),
                 BriefColumnsError::ListingNotFound(id) => format!("listing_not_found:{}", id),
+                BriefColumnsError::ListingNotReady(id) => format!("listing_not_ready:{}", id),
                 BriefColumnsError::Other(msg) => msg,
             })
     })
     .await
 }
 
+/// The ordered column set (ids + widths) for `view_mode`, or the defaults if the user never
+/// chose one. Backed by `listing-columns.json`.
+#[tauri::command]
+#[specta::specta]
+pub fn get_columns(app: tauri::AppHandle, view_mode: ViewMode) -> Vec<ColumnSetting> {
+    columns::get_columns(&app, view_mode)
+}
+
+/// Stores the ordered column set for `view_mode` and returns it as stored (widths clamped).
+/// Refused when the Name column is missing or a column is listed twice.
+#[tauri::command]
+#[specta::specta]
+pub fn set_columns(
+    app: tauri::AppHandle,
+    view_mode: ViewMode,
+    columns: Vec<ColumnSetting>,
+) -> Result<Vec<ColumnSetting>, ColumnsError> {
+    columns::set_columns(&app, view_mode, columns)
+}
+
 #[tauri::command]
 #[specta::specta]
 pub fn find_file_index(listing_id: String, name: String, include_hidden: bool) -> Result<Option<usize>, String> {
@@ -352,6 +441,21 @@ pub async fn find_first_fuzzy_match(
     ops_fuzzy_find_first_match_in_listing(&listing_id, &query, include_hidden).map_err(IpcError::from_err)
 }
 
+/// Type-to-select: returns the backend index of the next entry whose name starts with `prefix`
+/// (case-insensitive), searching from `from_index` in the listing's sort order and wrapping around.
+/// Pass the previous result + 1 to cycle through matches. Same index space as
+/// `find_first_fuzzy_match`; the frontend drives the highlight.
+#[tauri::command]
+#[specta::specta]
+pub async fn find_next_match(
+    listing_id: String,
+    prefix: String,
+    from_index: usize,
+    include_hidden: bool,
+) -> Result<Option<usize>, IpcError> {
+    ops_find_next_match_in_listing(&listing_id, &prefix, from_index, include_hidden).map_err(IpcError::from_err)
+}
+
 #[tauri::command]
 #[specta::specta]
 pub fn get_file_at(listing_id: String, index: usize, include_hidden: bool) -> Result<Option<FileEntry>, String> {
@@ -458,6 +562,14 @@ pub fn get_listing_stats(
     ops_get_listing_stats(&listing_id, include_hidden, selected_indices.as_deref())
 }
 
+/// Approximate listing cache memory against its cap, plus how many listings are held and how
+/// many were evicted (they re-list on their next access).
+#[tauri::command]
+#[specta::specta]
+pub fn get_listing_cache_stats() -> ListingCacheStats {
+    eviction::get_listing_cache_stats()
+}
+
 /// Re-enriches cached listing entries with fresh drive index data.
 #[tauri::command]
 #[specta::specta]
@@ -589,6 +701,7 @@ mod refresh_listing_tests {
                 sort_by: SortColumn::Name,
                 sort_order: SortOrder::Ascending,
                 directory_sort_mode: DirectorySortMode::LikeFiles,
+                generation: 0,
                 sequence: AtomicU64::new(1),
                 created_at: std::time::Instant::now(),
                 last_accessed_ms: AtomicU64::new(0),
@@ -1,19 +1,27 @@
 //! Tauri commands for file system operations.
 
+mod analysis;
 mod archive;
+mod checksum;
+mod compare;
 mod drag;
 #[cfg(any(feature = "playwright-e2e", debug_assertions))]
 mod e2e_support;
+mod file_info;
 mod git;
 mod listing;
 mod stat;
 mod volume_copy;
 mod write_ops;
 
+pub use analysis::*;
 pub use archive::*;
+pub use checksum::*;
+pub use compare::*;
 pub use drag::*;
 #[cfg(any(feature = "playwright-e2e", debug_assertions))]
 pub use e2e_support::*;
+pub use file_info::*;
 pub use git::*;
 pub use listing::*;
 pub use stat::*;
@@ -1,6 +1,7 @@
 //! Tauri commands for cross-volume copy/move operations.
 
 use crate::file_system::Volume;
+use crate::file_system::conflict_policies;
 use crate::file_system::{
     OperationEventSink, ScanConflict, TauriEventSink, VolumeCopyConfig, VolumeCopyScanResult, WriteOperationError,
     WriteOperationStartResult, compress_start as ops_compress_start, copy_between_volumes as ops_copy_between_volumes,
@@ -88,7 +89,14 @@ pub async fn copy_between_volumes(
         path: dest_volume_id.clone(),
         message: format!("Destination volume '{}' not found", dest_volume_id),
     })?;
-    let config = config.unwrap_or_default();
+    let mut config = config.unwrap_or_default();
+    conflict_policies::apply_remembered_policy(
+        &mut config.conflict_resolution,
+        &source_volume_id,
+        &source_paths,
+        &dest_volume_id,
+        Path::new(&dest_path),
+    );
     let events: Arc<dyn OperationEventSink> = Arc::new(TauriEventSink::new(app));
 
     if dest_resolved.is_archive {
@@ -153,7 +161,14 @@ pub async fn move_between_volumes(
         path: dest_volume_id.clone(),
         message: format!("Destination volume '{}' not found", dest_volume_id),
     })?;
-    let config = config.unwrap_or_default();
+    let mut config = config.unwrap_or_default();
+    conflict_policies::apply_remembered_policy(
+        &mut config.conflict_resolution,
+        &source_volume_id,
+        &source_paths,
+        &dest_volume_id,
+        Path::new(&dest_path),
+    );
     let events: Arc<dyn OperationEventSink> = Arc::new(TauriEventSink::new(app));
 
     // Move OUT of a zip. Takes precedence over the dest-archive branch: a
@@ -257,6 +272,7 @@ pub async fn compress_files(
         config.conflict_resolution,
         config.progress_interval_ms,
         config.compression_level,
+        config.archive_symlinks,
         initiator.unwrap_or(Initiator::User),
     )
     .await
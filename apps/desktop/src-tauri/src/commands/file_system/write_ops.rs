@@ -1,29 +1,34 @@
 //! Tauri commands for write operations (create, copy, move, delete, trash) and scan preview.
 
 use crate::file_system::write_operations::{
-    ConflictResolution, ScanPreviewStartResult, cancel_scan_preview as ops_cancel_scan_preview,
-    create_directory_managed as ops_create_directory_managed, create_file_managed as ops_create_file_managed,
-    get_scan_preview_totals as ops_get_scan_preview_totals, resolve_write_conflict as ops_resolve_write_conflict,
-    start_scan_preview as ops_start_scan_preview,
+    ConflictResolution, PermissionsInfo, ScanPreviewStartResult, SetPermissionsResult, SymlinkResolution,
+    cancel_scan_preview as ops_cancel_scan_preview, create_directory_managed as ops_create_directory_managed,
+    create_file_managed as ops_create_file_managed, get_scan_preview_totals as ops_get_scan_preview_totals,
+    resolve_write_conflict as ops_resolve_write_conflict, start_scan_preview as ops_start_scan_preview,
 };
 use crate::file_system::{
     OperationEventSink, OperationSnapshot, OperationStatus, OperationSummary, SortColumn, SortOrder, TauriEventSink,
-    WriteOperationConfig, WriteOperationError, WriteOperationStartResult,
+    TrashInfo, WriteOperationConfig, WriteOperationError, WriteOperationStartResult,
     cancel_all_write_operations as ops_cancel_all_write_operations, cancel_operation as ops_cancel_operation,
     cancel_operations as ops_cancel_operations, cancel_write_operation as ops_cancel_write_operation,
     copy_files_start as ops_copy_files_start, delete_files_start as ops_delete_files_start,
-    get_operation_status as ops_get_operation_status, get_volume_manager,
-    list_active_operations as ops_list_active_operations, list_operations as ops_list_operations,
-    move_files_start as ops_move_files_start, pause_all as ops_pause_all, pause_operation as ops_pause_operation,
+    empty_trash_start as ops_empty_trash_start, get_operation_status as ops_get_operation_status,
+    get_trash_info as ops_get_trash_info, get_volume_manager, list_active_operations as ops_list_active_operations,
+    list_operations as ops_list_operations, move_files_start as ops_move_files_start,
+    move_operation as ops_move_operation, pause_all as ops_pause_all, pause_operation as ops_pause_operation,
     resume_all as ops_resume_all, resume_operation as ops_resume_operation, trash_files_start as ops_trash_files_start,
 };
 use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::time::Duration;
 
-use crate::commands::util::IpcError;
+use crate::commands::util::{IpcError, blocking_result_with_timeout, blocking_with_timeout};
 use crate::file_system::Volume;
+use crate::file_system::conflict_policies::{self, DirPair, DirPairPolicy};
+use crate::file_system::listing::FileEntry;
+use crate::file_system::move_targets::{self, RecentMoveTarget};
 use crate::file_system::volume::backends::archive;
+use crate::file_system::write_operations as ops;
 use crate::operation_log::types::Initiator;
 
 use super::expand_tilde;
@@ -128,6 +133,76 @@ pub async fn create_file(
     .map_err(IpcError::from_err)
 }
 
+/// Creates a symlink at `link_path` pointing at `target` and returns the new entry. Local
+/// filesystem only. An existing entry at `link_path` is an error unless `force` (never a folder).
+#[tauri::command]
+#[specta::specta]
+pub async fn create_symlink(target: String, link_path: String, force: Option<bool>) -> Result<FileEntry, IpcError> {
+    let target = PathBuf::from(expand_tilde(&target));
+    let link_path = PathBuf::from(expand_tilde(&link_path));
+    blocking_result_with_timeout(Duration::from_secs(5), move || {
+        ops::create_symlink(&target, &link_path, force.unwrap_or(false))
+    })
+    .await
+}
+
+/// Creates a hard link at `link_path` to the file `target` and returns the new entry. Rejects
+/// folders, an existing `link_path`, and a link on a different drive than its target.
+#[tauri::command]
+#[specta::specta]
+pub async fn create_hardlink(target: String, link_path: String) -> Result<FileEntry, IpcError> {
+    let target = PathBuf::from(expand_tilde(&target));
+    let link_path = PathBuf::from(expand_tilde(&link_path));
+    blocking_result_with_timeout(Duration::from_secs(5), move || {
+        ops::create_hardlink(&target, &link_path)
+    })
+    .await
+}
+
+/// Where the symlink at `path` points, and whether it's broken.
+#[tauri::command]
+#[specta::specta]
+pub async fn resolve_symlink(path: String) -> Result<SymlinkResolution, IpcError> {
+    let path = PathBuf::from(expand_tilde(&path));
+    blocking_result_with_timeout(Duration::from_secs(5), move || ops::resolve_symlink(&path)).await
+}
+
+/// The mode, owner, and group of `path`, for the permissions editor.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_permissions(path: String) -> Result<PermissionsInfo, IpcError> {
+    let path = PathBuf::from(expand_tilde(&path));
+    blocking_result_with_timeout(Duration::from_secs(5), move || ops::get_permissions(&path)).await
+}
+
+/// Sets `path`'s permission bits to `mode` (`0o7777` mask). A recursive change on a folder
+/// first returns an item-count estimate and changes nothing; call again with `confirmed` to
+/// start it as an op (write-* events, `operationType: set_permissions`, cancellable).
+#[tauri::command]
+#[specta::specta]
+pub async fn set_permissions(
+    app: tauri::AppHandle,
+    path: String,
+    mode: u32,
+    recursive: bool,
+    confirmed: Option<bool>,
+    config: Option<WriteOperationConfig>,
+    initiator: Option<Initiator>,
+) -> Result<SetPermissionsResult, WriteOperationError> {
+    let path = PathBuf::from(expand_tilde(&path));
+    let events: Arc<dyn OperationEventSink> = Arc::new(TauriEventSink::new(app));
+    ops::set_permissions_start(
+        events,
+        path,
+        mode,
+        recursive,
+        confirmed.unwrap_or(false),
+        config.unwrap_or_default(),
+        initiator.unwrap_or(Initiator::User),
+    )
+    .await
+}
+
 /// Expands tilde for local (`root`) parents only; volume paths are
 /// volume-relative and must never be tilde-expanded.
 fn expand_parent(volume_id: Option<&str>, parent_path: &str) -> String {
@@ -154,7 +229,8 @@ pub async fn copy_files(
 ) -> Result<WriteOperationStartResult, WriteOperationError> {
     let sources: Vec<PathBuf> = sources.iter().map(|s| PathBuf::from(expand_tilde(s))).collect();
     let destination = PathBuf::from(expand_tilde(&destination));
-    let config = config.unwrap_or_default();
+    let mut config = config.unwrap_or_default();
+    conflict_policies::apply_remembered_policy(&mut config.conflict_resolution, "root", &sources, "root", &destination);
 
     // A copy INTO or OUT of an archive doesn't belong on the local fast-path
     // (extract-out routes through `copy_between_volumes`; write-in is read-only).
@@ -189,7 +265,8 @@ pub async fn move_files(
 ) -> Result<WriteOperationStartResult, WriteOperationError> {
     let sources: Vec<PathBuf> = sources.iter().map(|s| PathBuf::from(expand_tilde(s))).collect();
     let destination = PathBuf::from(expand_tilde(&destination));
-    let config = config.unwrap_or_default();
+    let mut config = config.unwrap_or_default();
+    conflict_policies::apply_remembered_policy(&mut config.conflict_resolution, "root", &sources, "root", &destination);
 
     // A move touching an archive doesn't belong on the local fast-path (moving
     // into or out of a zip is read-only until mutation lands).
@@ -210,6 +287,20 @@ pub async fn move_files(
     .await
 }
 
+/// Returns the last destination folders used by local moves, newest first, for a "quick move"
+/// picker. Targets that can't be reached right now (unmounted volume) stay in the list with
+/// `available: false`. If the reachability check hangs, every target comes back unavailable.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_recent_move_targets() -> Vec<RecentMoveTarget> {
+    blocking_with_timeout(
+        Duration::from_secs(2),
+        move_targets::recent_move_targets_unchecked(),
+        move_targets::get_recent_move_targets,
+    )
+    .await
+}
+
 /// Recursively deletes files and directories. Same events as `copy_files`.
 /// When `volume_id` is provided and is not "root", routes through the Volume trait.
 #[tauri::command]
@@ -263,6 +354,29 @@ pub async fn trash_files(
     .await
 }
 
+/// Item count and total size of everything in the Trash, for the "Empty Trash" confirmation.
+/// Walks every trashed tree, so it runs on the blocking pool.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_trash_info() -> Result<TrashInfo, String> {
+    tokio::task::spawn_blocking(ops_get_trash_info)
+        .await
+        .map_err(|e| format!("Trash info task failed: {e}"))
+}
+
+/// Permanently deletes everything in the Trash (including per-volume trashes). Same events as
+/// `delete_files` (`operationType: delete`); cancel with `cancel_write_operation`.
+#[tauri::command]
+#[specta::specta]
+pub async fn empty_trash(
+    app: tauri::AppHandle,
+    config: Option<WriteOperationConfig>,
+    initiator: Option<Initiator>,
+) -> Result<WriteOperationStartResult, WriteOperationError> {
+    let events: Arc<dyn OperationEventSink> = Arc::new(TauriEventSink::new(app));
+    ops_empty_trash_start(events, config.unwrap_or_default(), initiator.unwrap_or(Initiator::User)).await
+}
+
 #[tauri::command]
 #[specta::specta]
 pub fn cancel_write_operation(operation_id: String, rollback: bool) {
@@ -296,6 +410,9 @@ pub async fn start_scan_preview(
     // Compress-mode scans set this so the local walk samples a compressed-size
     // estimate. Ignored for remote sources (never sampled). `None` == false.
     sample_for_estimate: Option<bool>,
+    // Mirrors the op's `WriteOperationConfig::include_hidden` so the preview's
+    // totals match what the op transfers. Local scans only. `None` == true.
+    include_hidden: Option<bool>,
 ) -> ScanPreviewStartResult {
     let volume_id = source_volume_id.unwrap_or_else(|| "root".to_string());
     let is_local = volume_id == "root";
@@ -319,6 +436,7 @@ pub async fn start_scan_preview(
         sort_order,
         progress_interval,
         sample_for_estimate.unwrap_or(false),
+        include_hidden.unwrap_or(true),
     )
 }
 
@@ -342,10 +460,54 @@ pub fn check_scan_preview_status(
 }
 
 /// In Stop mode, the operation pauses on conflict and waits for this call to proceed.
+/// `remember_for_pair` also stores `resolution` as the default for that folder pair, so later
+/// operations between the same folders don't prompt. The operation resolves even if that save fails.
 #[tauri::command]
 #[specta::specta]
-pub fn resolve_write_conflict(operation_id: String, resolution: ConflictResolution, apply_to_all: bool) {
+pub async fn resolve_write_conflict(
+    operation_id: String,
+    resolution: ConflictResolution,
+    apply_to_all: bool,
+    remember_for_pair: Option<DirPair>,
+) {
     ops_resolve_write_conflict(&operation_id, resolution, apply_to_all);
+    if let Some(pair) = remember_for_pair
+        && let Err(e) = blocking_result_with_timeout(Duration::from_secs(5), move || {
+            conflict_policies::set_policy(&pair, resolution)
+        })
+        .await
+    {
+        log::warn!("Couldn't remember conflict policy for operation {operation_id}: {e:?}");
+    }
+}
+
+/// Remembers `policy` as the conflict policy for copies and moves from `pair.source_dir` into
+/// `pair.destination_dir`. Operations that would prompt use it instead. `stop` forgets the pair.
+#[tauri::command]
+#[specta::specta]
+pub async fn set_default_conflict_policy(pair: DirPair, policy: ConflictResolution) -> Result<(), IpcError> {
+    blocking_result_with_timeout(Duration::from_secs(5), move || {
+        conflict_policies::set_policy(&pair, policy)
+    })
+    .await
+}
+
+/// The remembered per-folder-pair conflict policies, most recently set first.
+#[tauri::command]
+#[specta::specta]
+pub fn list_default_conflict_policies() -> Vec<DirPairPolicy> {
+    conflict_policies::list_policies()
+}
+
+/// Forgets the remembered policy for `pair`, or all of them when `pair` is omitted. Returns how
+/// many were removed.
+#[tauri::command]
+#[specta::specta]
+pub async fn clear_default_conflict_policies(pair: Option<DirPair>) -> Result<u32, IpcError> {
+    blocking_result_with_timeout(Duration::from_secs(5), move || {
+        conflict_policies::clear_policies(pair.as_ref()).map(|n| n as u32)
+    })
+    .await
 }
 
 #[tauri::command]
@@ -390,6 +552,15 @@ pub fn cancel_operations(operation_ids: Vec<String>) {
     ops_cancel_operations(&operation_ids);
 }
 
+/// Moves a queued operation ahead of `before_operation_id`, or to the back of the
+/// queue when it's `None`. Backs the queue window's drag-reorder. Running/paused
+/// ops and stale ids are a no-op.
+#[tauri::command]
+#[specta::specta]
+pub fn move_operation(operation_id: String, before_operation_id: Option<String>) {
+    ops_move_operation(&operation_id, before_operation_id.as_deref());
+}
+
 /// Pauses one Running operation. It parks at the next between-files boundary and
 /// its lifecycle status flips to `paused` in `operations-changed`. A paused op
 /// keeps holding its lane slots. Pausing a Queued/Done op is a no-op.
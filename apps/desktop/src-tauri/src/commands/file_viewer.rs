@@ -5,7 +5,7 @@ use tokio::time::Duration;
 use super::util::{IpcError, blocking_result_with_timeout};
 use crate::file_viewer::{
     self, EncodingOptions, FileEncoding, LineChunk, RangeEnd, SearchMode, SearchPollResult, SeekTarget, ViewerError,
-    ViewerOpenResult, ViewerSessionStatus,
+    ViewerOpenMode, ViewerOpenResult, ViewerSessionStatus,
 };
 use log::debug;
 use tauri::Manager;
@@ -53,12 +53,15 @@ const READ_RANGE_TIMEOUT: Duration = Duration::from_secs(60);
 /// free the session when the user closes the window via the titlebar X (a path
 /// that never fires the FE `viewer_close` IPC). Pass an empty string when there's
 /// no owning window (no mapping is recorded).
+///
+/// `mode` forces text or hex; `None` picks from the content (see `ViewerOpenMode`).
 #[tauri::command]
 #[specta::specta]
 pub async fn viewer_open(
     path: String,
     volume_id: String,
     window_label: String,
+    mode: Option<ViewerOpenMode>,
 ) -> Result<ViewerOpenResult, ViewerError> {
     let timeout = open_timeout_for(&path);
     // Typed `ViewerError` (not a stringified `IpcError`) so the FE can render friendly
@@ -67,7 +70,7 @@ pub async fn viewer_open(
     match tokio::time::timeout(
         timeout,
         tokio::task::spawn_blocking(move || {
-            let result = file_viewer::open_session(&path, &volume_id)?;
+            let result = file_viewer::open_session_with_mode(&path, &volume_id, mode.unwrap_or_default())?;
             file_viewer::register_window_session(&window_label, &result.session_id);
             Ok(result)
         }),
@@ -339,6 +342,24 @@ pub async fn viewer_reload(session_id: String) -> Result<(), String> {
     blocking_viewer_op(move || file_viewer::reload(&session_id).map_err(|e| e.to_string())).await
 }
 
+/// Peeks are cheap by design (one bounded head read or one index lookup), so a peek slower
+/// than this is a stuck mount, and the pane should show the error rather than wait.
+const PEEK_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// A bounded preview of `path` for the inactive pane's quick look: text, an image token,
+/// a hex dump, or a folder's entry count and indexed size. No session is opened, so the FE
+/// can call this on every (debounced) cursor move. `max_bytes` of 0 picks the default.
+#[tauri::command]
+#[specta::specta]
+pub async fn peek_file(path: String, max_bytes: u32) -> Result<file_viewer::PeekResult, IpcError> {
+    let path = std::path::PathBuf::from(crate::commands::file_system::expand_tilde(&path));
+    blocking_result_with_timeout(PEEK_TIMEOUT, move || {
+        file_viewer::peek_file(&path, max_bytes as usize)
+            .map_err(|e| format!("Couldn't peek at {}: {e}", path.display()))
+    })
+    .await
+}
+
 /// Sets up a viewer-specific menu on the given window (adds "Word wrap" to View submenu).
 ///
 /// macOS has no per-window menus (one app-level menu bar, tauri-apps/tauri#5768): `window.set_menu`
@@ -26,12 +26,18 @@ pub fn store_font_metrics<R: tauri::Runtime>(
     Ok(())
 }
 
-/// Checks if font metrics are available for a font ID.
+/// Checks if font metrics are available for a font ID. A font that was evicted from the
+/// in-memory cache is reloaded from disk first, so the frontend only re-measures fonts it
+/// has never stored.
 ///
 /// # Arguments
+/// * `app` - Tauri app handle for accessing app data directory
 /// * `font_id` - Font identifier to check
 #[tauri::command]
 #[specta::specta]
-pub fn has_font_metrics(font_id: String) -> bool {
+pub fn has_font_metrics(app: tauri::AppHandle, font_id: String) -> bool {
+    if !font_metrics::has_metrics(&font_id) {
+        font_metrics::init_font_metrics(&app, &font_id);
+    }
     font_metrics::has_metrics(&font_id)
 }
@@ -1,14 +1,14 @@
 //! IPC commands for the "Go to path" dialog.
 //!
-//! Thin pass-throughs over the `go_to_path` module. `resolve_go_to_path` touches
-//! the filesystem (`metadata` / `exists`), so it runs on the blocking pool with a
-//! timeout: a hung mount must never freeze IPC.
+//! Thin pass-throughs over the `go_to_path` module. `resolve_go_to_path` and
+//! `complete_go_to_path` touch the filesystem (`metadata` / `read_dir`), so they
+//! run on the blocking pool with a timeout: a hung mount must never freeze IPC.
 
 use tokio::time::Duration;
 
 use crate::commands::util::{IpcError, blocking_result_with_timeout};
 use crate::go_to_path::history::{self, RecentPathEntry};
-use crate::go_to_path::{self, GoToPathResolution};
+use crate::go_to_path::{self, GoToPathResolution, PathCompletion};
 
 /// 2s matches the read timeout other filesystem-touching commands use.
 const RESOLVE_TIMEOUT: Duration = Duration::from_secs(2);
@@ -21,6 +21,14 @@ pub async fn resolve_go_to_path(input: String, base_dir: String) -> Result<GoToP
     blocking_result_with_timeout(RESOLVE_TIMEOUT, move || Ok(go_to_path::resolve(&input, &base_dir))).await
 }
 
+/// Lists children of the folder `partial` points into that match its last
+/// segment, for the dialog's as-you-type suggestions.
+#[tauri::command]
+#[specta::specta]
+pub async fn complete_go_to_path(partial: String, base_dir: String) -> Result<Vec<PathCompletion>, IpcError> {
+    blocking_result_with_timeout(RESOLVE_TIMEOUT, move || Ok(go_to_path::complete(&partial, &base_dir))).await
+}
+
 /// Reads the persisted recent-path entries (newest first).
 #[tauri::command]
 #[specta::specta]
@@ -2,13 +2,16 @@
 //!
 //! Thin wrappers around `indexing` module functions, exposed to the frontend via Tauri commands.
 
+use std::path::PathBuf;
 use std::sync::OnceLock;
 
 use serde::{Deserialize, Serialize};
 use tauri::AppHandle;
+use tauri_specta::Event;
 
 #[cfg(any(target_os = "macos", target_os = "linux"))]
 use crate::indexing::SmbIndexGateReason;
+use crate::indexing::reconcile::reindex::{self, ReindexProgress, ReindexResult};
 use crate::indexing::{
     self, IndexDebugStatusResponse, IndexStatusResponse, ROOT_VOLUME_ID, VolumeIndexStatus, store::DirStats,
 };
@@ -298,6 +301,118 @@ pub async fn rescan_drive_index(app: AppHandle, volume_id: String) -> Result<Ena
     enable_drive_index(app, volume_id).await
 }
 
+/// Pauses a drive's running full scan without tearing it down: the walk holds where it is
+/// and the watcher keeps buffering, so resuming doesn't re-read finished folders. Progress
+/// events stop while paused. Only a fresh local scan can pause; errors otherwise.
+#[tauri::command]
+#[specta::specta]
+pub async fn pause_drive_index_scan(volume_id: String) -> Result<(), String> {
+    indexing::pause_scan(&volume_id)
+}
+
+/// Resumes a scan paused with `pause_drive_index_scan`. A no-op when none is paused.
+#[tauri::command]
+#[specta::specta]
+pub async fn resume_drive_index_scan(volume_id: String) -> Result<(), String> {
+    indexing::resume_scan(&volume_id)
+}
+
+/// Compacts a volume's index DB: a full `VACUUM` (run on the index writer, between its
+/// batches) then a WAL truncate. Returns the file size before and after. Errors mid-scan.
+#[tauri::command]
+#[specta::specta]
+pub async fn compact_index(volume_id: String) -> Result<indexing::writer::CompactStats, String> {
+    indexing::compact_index(&volume_id).await
+}
+
+/// Replaces the index exclusion patterns (`indexing.excludePatterns`: folder names like
+/// `node_modules`, or trailing paths like `Library/Caches`) and prunes folders they newly
+/// cover from every index, in the background. Live-applied; the frontend persists the setting
+/// and calls this on change. Removing a pattern doesn't re-add anything until the folder's
+/// parent is opened or the drive is rescanned.
+#[tauri::command]
+#[specta::specta]
+pub fn set_index_exclusions(patterns: Vec<String>) {
+    indexing::apply_user_exclusions(Some(&patterns));
+    tauri::async_runtime::spawn_blocking(|| {
+        for volume_id in indexing::all_registered_volume_ids() {
+            if let Err(e) = indexing::prune_user_excluded(&volume_id) {
+                log::warn!("{e}");
+            }
+        }
+    });
+}
+
+/// Sets the fresh-scan throttle (`indexing.maxEntriesPerSec` and `indexing.onlyOnAcPower`).
+/// Live-applied: a scan that's already running picks it up on its next folder.
+#[tauri::command]
+#[specta::specta]
+pub fn set_index_throttle(throttle: indexing::ScanThrottleSettings) {
+    indexing::set_user_scan_throttle(throttle);
+}
+
+/// Running counts of a folder re-index, at most every 200 ms.
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type, tauri_specta::Event)]
+#[tauri_specta(event_name = "reindex-progress")]
+#[serde(rename_all = "camelCase")]
+pub struct ReindexProgressEvent {
+    pub operation_id: String,
+    pub progress: ReindexProgress,
+}
+
+/// The end of a folder re-index: exactly one per `reindex_subtree`. `result` is `None` when
+/// the re-index couldn't run, with the reason in `error`.
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type, tauri_specta::Event)]
+#[tauri_specta(event_name = "reindex-complete")]
+#[serde(rename_all = "camelCase")]
+pub struct ReindexCompleteEvent {
+    pub operation_id: String,
+    pub result: Option<ReindexResult>,
+    pub error: Option<String>,
+}
+
+/// Re-walks one folder of the local `root` index and corrects its entries and sizes ("Refresh
+/// sizes for this folder"), leaving the rest of the drive alone. Returns the operation id right
+/// away; counts stream as `reindex-progress` and end with one `reindex-complete`. Stop it with
+/// [`cancel_reindex_subtree`]; re-indexing the same folder again resumes where it stopped.
+#[tauri::command]
+#[specta::specta]
+pub fn reindex_subtree(app: AppHandle, path: String) -> String {
+    let path = PathBuf::from(super::file_system::expand_tilde(&path));
+    let (operation_id, cancelled) = reindex::ACTIVE_REINDEXES.register_new("reindex");
+    let id = operation_id.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let on_progress = |progress| {
+            let _ = ReindexProgressEvent {
+                operation_id: id.clone(),
+                progress,
+            }
+            .emit(&app);
+        };
+        let result = reindex::reindex_subtree(&path, &cancelled, &on_progress);
+        reindex::ACTIVE_REINDEXES.unregister(&id);
+        let (result, error) = match result {
+            Ok(result) => (Some(result), None),
+            Err(e) => (None, Some(e)),
+        };
+        let _ = ReindexCompleteEvent {
+            operation_id: id,
+            result,
+            error,
+        }
+        .emit(&app);
+    });
+    operation_id
+}
+
+/// Cancels a running folder re-index. Returns `false` if it already finished. The re-index
+/// still ends with a `reindex-complete`, flagged `cancelled`.
+#[tauri::command]
+#[specta::specta]
+pub fn cancel_reindex_subtree(operation_id: String) -> bool {
+    reindex::ACTIVE_REINDEXES.cancel(&operation_id)
+}
+
 // ── App handle for handle-free callers (the MCP `indexing` tool) ─────
 //
 // `enable`/`rescan` need a concrete `AppHandle` (they spawn the indexer and emit
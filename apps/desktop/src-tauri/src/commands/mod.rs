@@ -28,14 +28,16 @@ pub mod mtp;
 #[cfg(any(target_os = "macos", target_os = "linux"))]
 pub mod network;
 pub mod operation_log;
+pub mod palette;
 pub mod quick_look;
 pub mod rename;
 pub mod restricted_paths;
 pub mod search;
 pub mod selection;
+pub mod session;
 pub mod settings;
 pub mod smb_diagnostics;
-pub mod sync_status; // Has both macOS and non-macOS implementations
+pub mod sync_status;
 mod util;
 #[cfg(target_os = "macos")]
 pub mod volumes;
@@ -4,7 +4,9 @@ use log::debug;
 use serde::{Deserialize, Serialize};
 
 use crate::file_system::FileEntry;
-use crate::mtp::{self, ConnectedDeviceInfo, MtpConnectionError, MtpDeviceInfo, MtpObjectInfo, MtpStorageInfo};
+use crate::mtp::{
+    self, ConnectedDeviceInfo, MtpConnectionError, MtpDeviceCapabilities, MtpDeviceInfo, MtpObjectInfo, MtpStorageInfo,
+};
 use tauri::AppHandle;
 
 /// Result of scanning an MTP path for copy operation.
@@ -93,6 +95,20 @@ pub async fn get_mtp_device_info(device_id: String) -> Option<ConnectedDeviceInf
     mtp::connection_manager().get_device_info(&device_id).await
 }
 
+/// Gets the operations a connected MTP device supports.
+///
+/// Lets the UI disable actions the device would refuse (rename, move, create folder, and so on)
+/// instead of failing them at runtime. Returns `None` if the device is not connected.
+///
+/// # Arguments
+///
+/// * `device_id` - The device ID to query
+#[tauri::command]
+#[specta::specta]
+pub async fn get_mtp_capabilities(device_id: String) -> Option<MtpDeviceCapabilities> {
+    mtp::connection_manager().get_capabilities(&device_id).await
+}
+
 /// Gets the ptpcamerad workaround command for macOS.
 ///
 /// Returns the Terminal command that users can run to work around
@@ -135,7 +151,8 @@ pub async fn get_mtp_storages(device_id: String) -> Vec<MtpStorageInfo> {
 ///
 /// # Returns
 ///
-/// A vector of FileEntry objects, sorted with directories first.
+/// A vector of FileEntry objects in device order. Panes sort them through the
+/// listing pipeline, by the pane's sort column and order.
 #[tauri::command]
 #[specta::specta]
 pub async fn list_mtp_directory(
@@ -157,6 +174,28 @@ pub async fn list_mtp_directory(
     result
 }
 
+/// Re-reads a directory on an MTP device, bypassing the listing cache.
+///
+/// Use for an explicit refresh: `list_mtp_directory` may serve a cached listing
+/// for a few seconds, which hides changes another app made on the phone.
+///
+/// # Arguments
+///
+/// * `device_id` - The connected device ID
+/// * `storage_id` - The storage ID within the device
+/// * `path` - Virtual path to re-read (for example, "/" or "/DCIM")
+#[tauri::command]
+#[specta::specta]
+pub async fn refresh_mtp_directory(
+    device_id: String,
+    storage_id: u32,
+    path: String,
+) -> Result<Vec<FileEntry>, MtpConnectionError> {
+    mtp::connection_manager()
+        .refresh_directory(&device_id, storage_id, &path)
+        .await
+}
+
 // ============================================================================
 // Phase 4: File Operations
 // ============================================================================
@@ -248,6 +287,63 @@ pub async fn move_mtp_object(
         .await
 }
 
+/// Moves an object from one storage of an MTP device to a folder on another (internal
+/// storage to SD card, say). Uses a cross-storage `MoveObject` when the device accepts it,
+/// else copies through a temp file and deletes the source, emitting `mtp-storage-move-progress`
+/// after each file. Open listings of both folders get a `directory-diff`.
+///
+/// # Arguments
+///
+/// * `device_id` - The connected device ID
+/// * `from_storage_id` - The storage the object is on
+/// * `object_path` - Current path of the object
+/// * `to_storage_id` - The storage to move it to
+/// * `new_parent_path` - Destination folder path on `to_storage_id`
+#[tauri::command]
+#[specta::specta]
+pub async fn move_mtp_object_between_storages(
+    app: AppHandle,
+    device_id: String,
+    from_storage_id: u32,
+    object_path: String,
+    to_storage_id: u32,
+    new_parent_path: String,
+) -> Result<MtpObjectInfo, MtpConnectionError> {
+    mtp::connection_manager()
+        .move_between_storages(
+            &device_id,
+            from_storage_id,
+            &object_path,
+            to_storage_id,
+            &new_parent_path,
+            Some(&app),
+        )
+        .await
+}
+
+/// Returns the embedded thumbnail of an MTP object as base64-encoded JPEG bytes, or `None` when
+/// it has none (folders, formats without one, devices without `GetThumb`). Thumbnails are cached
+/// per device, so re-rendering a grid doesn't go back to the device.
+///
+/// # Arguments
+///
+/// * `device_id` - The connected device ID
+/// * `storage_id` - The storage ID within the device
+/// * `object_path` - Virtual path on the device
+#[tauri::command]
+#[specta::specta]
+pub async fn get_mtp_thumbnail(
+    device_id: String,
+    storage_id: u32,
+    object_path: String,
+) -> Result<Option<String>, MtpConnectionError> {
+    use base64::Engine;
+    let thumbnail = mtp::connection_manager()
+        .get_thumbnail(&device_id, storage_id, &object_path)
+        .await?;
+    Ok(thumbnail.map(|bytes| base64::engine::general_purpose::STANDARD.encode(bytes.as_slice())))
+}
+
 // ============================================================================
 // Phase 5: Copy/Export Operations
 // ============================================================================
@@ -1,4 +1,5 @@
-//! IPC commands for the operation log's read side (the query API).
+//! IPC commands for the operation log's read side (the query API), plus undo /
+//! redo over the rollback engine.
 //!
 //! Thin pass-throughs over [`crate::operation_log::query`]: the business logic
 //! (filtering, paging, dir-path resolution) lives in the query module; these
@@ -7,8 +8,10 @@
 
 use tauri::AppHandle;
 
-use crate::operation_log::query::{self, OperationDetail};
+use crate::operation_log::query::{self, OperationDetail, OperationHistoryEntry};
+use crate::operation_log::rollback::RollbackRefusal;
 use crate::operation_log::store::{OperationLogStoreError, OperationRow, open_read_connection, operation_log_db_path};
+use crate::operation_log::undo::{self, UndoDispatch, UndoRefusal, UndoState};
 
 /// Resolve the `operation-log.db` path and run `read` on a read-only connection,
 /// off the IPC thread. A missing/unopened DB (the journal failed to start) yields
@@ -47,8 +50,17 @@ pub async fn get_recent_operation_log_entries(
     .await
 }
 
+/// Recently settled operations (newest first) with their duration and a one-item
+/// summary, for the Activity panel. Survives restarts: it reads the journal.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_operation_history(app: AppHandle, limit: u32) -> Result<Vec<OperationHistoryEntry>, String> {
+    with_read_connection(app, Vec::new(), move |conn| query::operation_history(conn, limit)).await
+}
+
 /// One operation's header plus a page of its items (dir prefixes resolved to full
-/// paths). `None` when the operation is absent.
+/// paths). `None` when the operation is absent. `errors_only` pages just the
+/// failed items; `None` means all of them.
 #[tauri::command]
 #[specta::specta]
 pub async fn get_operation_log_detail(
@@ -56,9 +68,42 @@ pub async fn get_operation_log_detail(
     operation_id: String,
     item_limit: u32,
     item_offset: u32,
+    errors_only: Option<bool>,
 ) -> Result<Option<OperationDetail>, String> {
+    let errors_only = errors_only.unwrap_or(false);
     with_read_connection(app, None, move |conn| {
-        query::get_operation(conn, &operation_id, item_limit, item_offset)
+        query::get_operation(conn, &operation_id, item_limit, item_offset, errors_only)
+    })
+    .await
+}
+
+/// The next undo and redo, for the Edit menu labels.
+#[tauri::command]
+#[specta::specta]
+pub fn get_undo_state() -> UndoState {
+    undo::with_stack(|stack| stack.state())
+}
+
+/// Undo the most recent move, rename, or trash by rolling it back. Returns after
+/// dispatch; the reversal runs as a managed op.
+#[tauri::command]
+#[specta::specta]
+pub async fn undo_last_operation(app: AppHandle) -> Result<UndoDispatch, UndoRefusal> {
+    tauri::async_runtime::spawn_blocking(move || {
+        crate::file_system::write_operations::rollback::undo_last_operation(&app)
+    })
+    .await
+    // A panicked dispatch never reached the engine; report it like a missing op.
+    .unwrap_or(Err(UndoRefusal::Rollback(RollbackRefusal::UnknownOperation)))
+}
+
+/// Redo the most recently undone operation. Same dispatch contract as undo.
+#[tauri::command]
+#[specta::specta]
+pub async fn redo_last_operation(app: AppHandle) -> Result<UndoDispatch, UndoRefusal> {
+    tauri::async_runtime::spawn_blocking(move || {
+        crate::file_system::write_operations::rollback::redo_last_operation(&app)
     })
     .await
+    .unwrap_or(Err(UndoRefusal::Rollback(RollbackRefusal::UnknownOperation)))
 }
@@ -0,0 +1,47 @@
+//! IPC commands for the command palette's backend action registry.
+//!
+//! Thin pass-throughs over [`crate::palette`]. The one thing added here is the
+//! clipboard check behind "Paste": the pasteboard is read on the main thread
+//! (NSPasteboard isn't thread-safe), so the caller's context never decides it.
+
+use tauri::AppHandle;
+
+use crate::palette::{self, PaletteAction, PaletteContext, PaletteInvokeError};
+
+/// Lists every palette action with `enabled` evaluated against the focused
+/// pane's `context` and the current clipboard.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_palette_actions(app: AppHandle, context: PaletteContext) -> Vec<PaletteAction> {
+    let context = PaletteContext {
+        clipboard_has_paths: clipboard_has_paths(&app),
+        ..context
+    };
+    palette::actions_for(&context)
+}
+
+/// Runs a palette action by id, through the same `execute-command` route a menu
+/// click takes.
+#[tauri::command]
+#[specta::specta]
+pub fn invoke_palette_action(app: AppHandle, id: String) -> Result<(), PaletteInvokeError> {
+    palette::invoke(&app, &id)
+}
+
+/// Whether the system clipboard holds at least one file path. Any failure reads
+/// as "no", which only greys out Paste.
+#[cfg(target_os = "macos")]
+fn clipboard_has_paths(app: &AppHandle) -> bool {
+    let (tx, rx) = std::sync::mpsc::channel();
+    let dispatched = app.run_on_main_thread(move || {
+        let mtm = objc2::MainThreadMarker::new().expect("run_on_main_thread runs on the main thread");
+        let _ = tx.send(crate::clipboard::read_file_urls_from_clipboard(mtm));
+    });
+    dispatched.is_ok() && rx.recv().is_ok_and(|paths| paths.is_ok_and(|paths| !paths.is_empty()))
+}
+
+/// Linux has no file clipboard yet (see `clipboard/CLAUDE.md`).
+#[cfg(not(target_os = "macos"))]
+fn clipboard_has_paths(_app: &AppHandle) -> bool {
+    false
+}
@@ -6,13 +6,16 @@
 //! 5 s rename), and map errors to `IpcError`.
 
 use std::path::PathBuf;
+use std::sync::Arc;
 use tokio::time::Duration;
 
 use super::file_system::expand_tilde;
 use super::util::{IpcError, timeout_detached};
 use crate::file_system::write_operations::trash::trash_single_journaled;
 use crate::file_system::write_operations::{
-    RenameValidityResult, check_rename_permission_sync, check_rename_validity_impl, rename_managed,
+    BatchRenamePreviewRow, BatchRenameRule, RenameValidityResult, TauriEventSink, WriteOperationStartResult,
+    check_rename_permission_sync, check_rename_validity_impl, load_batch_rename_sources, preview_batch_rename,
+    rename_managed, start_batch_rename,
 };
 
 // ============================================================================
@@ -128,6 +131,62 @@ pub async fn rename_file(
     .await
 }
 
+/// Previews a rule-based batch rename: one row per path with its new name and
+/// any problem (invalid name, two files mapping to one name, an existing file
+/// in the way). `paths` order drives the `{n}` counter. Nothing is renamed.
+#[tauri::command]
+#[specta::specta]
+pub async fn preview_batch_rename_rule(
+    paths: Vec<String>,
+    rule: BatchRenameRule,
+    volume_id: Option<String>,
+) -> Result<Vec<BatchRenamePreviewRow>, IpcError> {
+    let volume_id_str = volume_id.unwrap_or_else(|| "root".to_string());
+    let paths = batch_rename_paths(paths, &volume_id_str);
+
+    // Detached for the same reason as `check_rename_validity`, which this runs
+    // once per changed row.
+    timeout_detached(Duration::from_secs(10), async move {
+        let sources = load_batch_rename_sources(paths, &volume_id_str).await;
+        Ok::<_, String>(preview_batch_rename(&sources, &rule, &volume_id_str).await)
+    })
+    .await
+}
+
+/// Applies a rule-based batch rename as one managed operation. The preview is
+/// recomputed and must be clean; if any rename then fails, the ones that landed
+/// are renamed back and the operation reports an error.
+#[tauri::command]
+#[specta::specta]
+pub async fn batch_rename(
+    app: tauri::AppHandle,
+    paths: Vec<String>,
+    rule: BatchRenameRule,
+    volume_id: Option<String>,
+) -> Result<WriteOperationStartResult, IpcError> {
+    let volume_id_str = volume_id.unwrap_or_else(|| "root".to_string());
+    let paths = batch_rename_paths(paths, &volume_id_str);
+
+    timeout_detached(Duration::from_secs(10), async move {
+        let sources = load_batch_rename_sources(paths, &volume_id_str).await;
+        start_batch_rename(Arc::new(TauriEventSink::new(app)), sources, rule, volume_id_str).await
+    })
+    .await
+}
+
+fn batch_rename_paths(paths: Vec<String>, volume_id: &str) -> Vec<PathBuf> {
+    paths
+        .into_iter()
+        .map(|path| {
+            if volume_id == "root" {
+                PathBuf::from(expand_tilde(&path))
+            } else {
+                PathBuf::from(path)
+            }
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2,6 +2,7 @@
 //!
 //! Thin wrappers around `search` module functions, exposed to the frontend via Tauri commands.
 
+use std::path::Path;
 use std::sync::atomic::Ordering;
 
 use serde::Serialize;
@@ -9,6 +10,8 @@ use serde::Serialize;
 use genai::chat::ChatOptions;
 
 use crate::ai::AiTranslateError;
+use crate::file_system::listing::get_single_entry;
+use crate::file_system::{get_volume_manager, volume};
 use crate::search::{self, ParsedScope, SearchQuery, SearchResult, VolumeLoad};
 
 use crate::search::ai::{self, query_builder as ai_query_builder};
@@ -281,6 +284,47 @@ pub fn apply_recent_searches_max_count(app: tauri::AppHandle, max_count: u32) ->
     Ok(())
 }
 
+// ============================================================================
+// Search-results volume IPC
+// ============================================================================
+
+/// Opens search results as a browsable volume: registers a transient
+/// `search-{uuid}` volume whose root lists `paths` (absolute, possibly spanning
+/// many folders and volumes), and returns its id. Paths that no longer exist are
+/// dropped. Pair with `close_search_results_volume` when the pane leaves the results.
+#[tauri::command]
+#[specta::specta]
+pub async fn open_search_results_volume(name: String, paths: Vec<String>) -> Result<String, String> {
+    // Stats every result, so keep it off the async runtime.
+    let entries = tokio::task::spawn_blocking(move || {
+        paths
+            .iter()
+            .filter_map(|path| match get_single_entry(Path::new(path)) {
+                Ok(entry) => Some(entry),
+                Err(e) => {
+                    log::debug!(target: "search", "Dropping search result {path}: {e}");
+                    None
+                }
+            })
+            .collect::<Vec<_>>()
+    })
+    .await
+    .map_err(|e| format!("Search results task failed: {e}"))?;
+    Ok(volume::register_search_results(name, entries))
+}
+
+/// Unregisters a volume opened by `open_search_results_volume`. Refuses any other
+/// volume id, so a stale frontend id can't drop a real volume.
+#[tauri::command]
+#[specta::specta]
+pub fn close_search_results_volume(volume_id: String) -> Result<(), String> {
+    if !volume_id.starts_with(volume::SEARCH_RESULTS_VOLUME_PREFIX) {
+        return Err(format!("Not a search results volume: {volume_id}"));
+    }
+    get_volume_manager().unregister(&volume_id);
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,54 @@
+//! IPC commands for pane session persistence and per-pane navigation history. Thin
+//! pass-throughs over `crate::session`.
+
+use crate::session::history::{self, HistoryPane, NavigationEntry, NavigationHistory};
+use crate::session::{self, Session};
+
+/// Snapshots both panes (volume, path, sort, view mode, cursor name, tabs) to app
+/// data. The frontend calls it on quit and whenever it wants a checkpoint.
+#[tauri::command]
+#[specta::specta]
+pub fn save_session(app: tauri::AppHandle) -> Result<Session, String> {
+    session::save_session(&app)
+}
+
+/// Returns the saved session with unavailable panes set to `None`, reconnecting
+/// MTP devices first. `None` when there's no usable session; never blocks
+/// startup on a corrupt file or a dead mount.
+#[tauri::command]
+#[specta::specta]
+pub async fn restore_session(app: tauri::AppHandle) -> Option<Session> {
+    session::restore_session(&app).await
+}
+
+/// Records a navigation in `pane`'s history (dropping any forward entries) and returns the
+/// updated history. Pushing the location already shown is a no-op.
+#[tauri::command]
+#[specta::specta]
+pub fn push_history(pane: HistoryPane, volume_id: String, path: String) -> NavigationHistory {
+    history::with_history(pane, |h| {
+        h.push(NavigationEntry { volume_id, path });
+        h.clone()
+    })
+}
+
+/// Steps `pane` back one entry and returns the location to navigate to, or `None` at the start.
+#[tauri::command]
+#[specta::specta]
+pub fn go_back(pane: HistoryPane) -> Option<NavigationEntry> {
+    history::with_history(pane, |h| h.back())
+}
+
+/// Steps `pane` forward one entry and returns the location to navigate to, or `None` at the end.
+#[tauri::command]
+#[specta::specta]
+pub fn go_forward(pane: HistoryPane) -> Option<NavigationEntry> {
+    history::with_history(pane, |h| h.forward())
+}
+
+/// Returns `pane`'s full history, oldest first, for back/forward menus and "recent locations".
+#[tauri::command]
+#[specta::specta]
+pub fn get_history(pane: HistoryPane) -> NavigationHistory {
+    history::get(pane)
+}
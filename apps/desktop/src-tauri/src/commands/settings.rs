@@ -1,9 +1,15 @@
 //! Settings-related commands.
 
+use std::path::Path;
+use std::time::Duration;
+
 use tauri::{AppHandle, Manager};
 
+use crate::commands::util::{IpcError, blocking_result_with_timeout};
+use crate::file_system::listing::GitIgnoredMode;
 use crate::file_system::{
-    set_direct_smb_enabled, set_filter_safe_save_artifacts, set_smb_concurrency, update_debounce_ms,
+    WatcherListingStats, WatcherVolumeKind, set_direct_smb_enabled, set_filter_safe_save_artifacts,
+    set_smb_concurrency, update_debounce_ms, update_debounce_override_ms, watcher_stats,
 };
 use crate::ignore_poison::IgnorePoison;
 use crate::menu::{
@@ -28,14 +34,30 @@ pub fn find_available_port(start_port: u16) -> Option<u16> {
     crate::net::find_available_port(start_port)
 }
 
-/// Updates the file watcher debounce duration in milliseconds.
-/// This affects newly created watchers; existing watchers keep their original duration.
+/// Updates the general file watcher debounce duration in milliseconds, the default for every
+/// volume kind without an override. Active watchers pick it up right away.
 #[tauri::command]
 #[specta::specta]
 pub fn update_file_watcher_debounce(debounce_ms: u64) {
     update_debounce_ms(debounce_ms);
 }
 
+/// Sets (`Some`) or clears (`None`) the file watcher debounce override for one volume kind.
+/// Active watchers pick it up right away.
+#[tauri::command]
+#[specta::specta]
+pub fn update_file_watcher_debounce_for_volume_kind(volume_kind: WatcherVolumeKind, debounce_ms: Option<u64>) {
+    update_debounce_override_ms(volume_kind, debounce_ms);
+}
+
+/// Per-listing watcher counters (events received, diffs emitted, effective debounce) for tuning
+/// the debounce and diagnosing flickering folders. Counters reset when a listing closes.
+#[tauri::command]
+#[specta::specta]
+pub fn get_watcher_stats() -> Vec<WatcherListingStats> {
+    watcher_stats()
+}
+
 /// Returns the absolute path the frontend's `tauri-plugin-store` should load for
 /// a given store file (for example `settings.json`, `shortcuts.json`,
 /// `app-status.json`), but ONLY when this is an isolated
@@ -132,6 +154,31 @@ pub fn set_smb_concurrency_cmd(value: u16) {
     set_smb_concurrency(value as usize);
 }
 
+/// Update the listing cache's memory cap. Inactive listings past it are evicted right away
+/// and re-listed when next touched. Pushed live from the frontend whenever
+/// `advanced.listingCacheMemoryMb` changes.
+#[tauri::command]
+#[specta::specta]
+pub fn set_listing_cache_memory_mb(value: u64) {
+    crate::file_system::listing::eviction::set_cap_mb(value);
+}
+
+/// Update the settings `resolve_default_action` reads. Takes the raw stored values and
+/// parses them leniently (see `file_system/default_action.rs`). Pushed live from the
+/// frontend whenever `behavior.archiveEnterBehavior`, `behavior.fileEnterAction`, or
+/// `behavior.openWithApps` changes; all three travel together.
+#[tauri::command]
+#[specta::specta]
+pub fn set_default_action_settings(archive_enter_behavior: String, file_enter_action: String, open_with_apps: String) {
+    crate::file_system::default_action::set_settings(
+        crate::file_system::default_action::DefaultActionSettings::from_setting_values(
+            Some(&archive_enter_behavior),
+            Some(&file_enter_action),
+            Some(&open_with_apps),
+        ),
+    );
+}
+
 /// Turn LLM call logging on or off. When on, every AI model request and response is written
 /// to `{app data dir}/llm-logs/` for debugging (local only, never transmitted). Pushed live
 /// from the frontend whenever `advanced.logLlmCalls` changes; runtime-toggleable, no restart.
@@ -274,6 +321,38 @@ pub async fn set_show_virtual_git_portal(enabled: bool) {
     crate::file_system::git::watcher::refresh_all_virtual_listings_after_toggle();
 }
 
+/// Sets how listings treat git-ignored entries: not at all, greyed out, or left out. Pushed
+/// live from the frontend whenever `fileExplorer.git.ignoredFiles` changes. Open local
+/// listings are re-marked right away and get a diff where their rows changed.
+#[tauri::command]
+#[specta::specta]
+pub fn set_git_ignored_mode(mode: GitIgnoredMode) {
+    crate::file_system::listing::set_git_ignored_mode(mode);
+}
+
+/// Writes a settings export (built by the frontend's `settings-transfer.ts`) to `out_path`.
+/// The backend never builds or applies exports itself: the frontend registry validates every
+/// field, and only the frontend store writes `settings.json`. 5 s write timeout.
+#[tauri::command]
+#[specta::specta]
+pub async fn write_settings_export(out_path: String, contents: String) -> Result<(), IpcError> {
+    blocking_result_with_timeout(Duration::from_secs(5), move || {
+        crate::settings::transfer::write_export(Path::new(&out_path), &contents)
+    })
+    .await
+}
+
+/// Reads a settings export for the frontend to validate and apply. Refuses files over
+/// 1 MB and anything that isn't a JSON object. 2 s read timeout.
+#[tauri::command]
+#[specta::specta]
+pub async fn read_settings_export(in_path: String) -> Result<String, IpcError> {
+    blocking_result_with_timeout(Duration::from_secs(2), move || {
+        crate::settings::transfer::read_export(Path::new(&in_path))
+    })
+    .await
+}
+
 /// Update menu accelerator for a command.
 /// Called from frontend when keyboard shortcuts are changed.
 #[tauri::command]
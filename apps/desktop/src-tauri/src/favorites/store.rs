@@ -22,8 +22,14 @@
 //!   a rename, and a user can re-add a path they removed, so the id must outlive the path string.
 //! - `add` dedups by normalized path: re-adding an existing path moves it to the end (keeps its id),
 //!   so the user's existing label and position context isn't silently dropped.
-//! - Schema-versioned: a parse error or version mismatch quarantines the file aside and starts
-//!   fresh, so a stray hand-edit can't break the switcher forever.
+//! - Schema-versioned and migration-safe: an OLDER version is migrated in memory and rewritten on the
+//!   next mutation (v1 → v2 only added optional fields). A parse error or a NEWER version (a
+//!   downgrade) quarantines the file aside and starts fresh, so a stray hand-edit can't break the
+//!   switcher forever.
+//! - A favorite on a non-root volume (external drive, network share) also records that volume's
+//!   registry id and its path relative to the mount root, so it follows a remount to a new mount
+//!   point (`/Volumes/Backup` → `/Volumes/Backup 1`). SMB volume ids are server/share-derived, so
+//!   they're stable across remounts.
 //! - The disk file is never locked across an `.await`; the in-memory mutex guard is always dropped
 //!   before any `fs` call.
 
@@ -42,8 +48,9 @@ const BUNDLE_ID: &str = "com.veszelovszki.cmdr";
 /// Filename inside `{app_data_dir}/`.
 const FAVORITES_FILE_NAME: &str = "favorites.json";
 
-/// Bump when the on-disk shape changes in an incompatible way.
-const CURRENT_SCHEMA_VERSION: u32 = 1;
+/// Bump when the on-disk shape changes, and teach [`migrate`] the step from the previous version.
+/// v2: added `volumeId` / `relativePath`.
+const CURRENT_SCHEMA_VERSION: u32 = 2;
 
 /// A single favorite, persisted verbatim and serialized to the frontend.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
@@ -55,6 +62,24 @@ pub struct Favorite {
     pub path: String,
     /// Display label. Defaults to the path's file name on add; the user can override via rename.
     pub name: String,
+    /// Registry id of the non-root volume `path` was on when added (see the module doc). `None` for
+    /// boot-volume paths and for v1 entries.
+    #[serde(default)]
+    pub volume_id: Option<String>,
+    /// `path` relative to `volume_id`'s mount root. Set exactly when `volume_id` is.
+    #[serde(default)]
+    pub relative_path: Option<String>,
+}
+
+/// A favorite plus its live state, for [`list_with_status`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct FavoriteStatus {
+    pub favorite: Favorite,
+    /// Where the favorite points right now: `path`, or the remounted location when its volume moved.
+    pub resolved_path: String,
+    /// Whether `resolved_path` currently exists. `false` when its volume isn't mounted.
+    pub reachable: bool,
 }
 
 /// On-disk shape. `_schemaVersion` lets future versions detect incompatible files.
@@ -124,6 +149,8 @@ fn default_favorites() -> Vec<Favorite> {
             id: new_id(),
             path: path.to_string_lossy().to_string(),
             name: name.to_string(),
+            volume_id: None,
+            relative_path: None,
         })
         .collect()
 }
@@ -159,10 +186,54 @@ fn normalize_for_dedup(path: &str) -> String {
 // Pure core (testable without disk or an AppHandle)
 // ---------------------------------------------------------------------------
 
+/// Splits `path` into `(volume_id, relative_path)` when it lives on a registered non-root volume.
+fn volume_location(path: &str) -> Option<(String, String)> {
+    let manager = crate::file_system::get_volume_manager();
+    let volume_id = manager.mount_id_for_path(path)?;
+    let volume = manager.get(&volume_id)?;
+    let relative = Path::new(path).strip_prefix(volume.root()).ok()?;
+    Some((volume_id, relative.to_string_lossy().to_string()))
+}
+
+/// Where `favorite` points now. When its volume is registered under a different mount root than the
+/// stored `path` implies, that's a remount: rebase `relative_path` onto the live root. Otherwise the
+/// stored `path` stands (including while the volume is unmounted).
+pub fn resolve_path(favorite: &Favorite) -> String {
+    let (Some(volume_id), Some(relative)) = (&favorite.volume_id, &favorite.relative_path) else {
+        return favorite.path.clone();
+    };
+    match crate::file_system::get_volume_manager().get(volume_id) {
+        Some(volume) => rebase(volume.root(), relative),
+        None => favorite.path.clone(),
+    }
+}
+
+fn rebase(root: &Path, relative: &str) -> String {
+    if relative.is_empty() {
+        root.to_string_lossy().to_string()
+    } else {
+        root.join(relative).to_string_lossy().to_string()
+    }
+}
+
+/// Upgrades a store read from an older schema version. Pure; the caller persists on the next write.
+fn migrate(mut store: FavoritesStore) -> FavoritesStore {
+    // v1 → v2: `volume_id` / `relative_path` are new optional fields; serde already defaulted them.
+    // They stay `None` (a v1 favorite keeps working by path) until the user re-adds it.
+    store.schema_version = CURRENT_SCHEMA_VERSION;
+    store
+}
+
 /// Adds a favorite, deduping by normalized path. If the path already exists, moves the existing entry
-/// to the end and applies an explicit `name` override when given (keeping its id). Returns the id of
-/// the affected entry.
-fn add_to_store(store: &mut FavoritesStore, path: &str, name: Option<String>) -> String {
+/// to the end and applies an explicit `name` override when given (keeping its id). `location` is the
+/// path's `(volume_id, relative_path)`, refreshed on a re-add. Returns the id of the affected entry.
+fn add_to_store(
+    store: &mut FavoritesStore,
+    path: &str,
+    name: Option<String>,
+    location: Option<(String, String)>,
+) -> String {
+    let (volume_id, relative_path) = location.unzip();
     let normalized = normalize_for_dedup(path);
     if let Some(pos) = store
         .favorites
@@ -173,6 +244,8 @@ fn add_to_store(store: &mut FavoritesStore, path: &str, name: Option<String>) ->
         if let Some(name) = name {
             existing.name = name;
         }
+        existing.volume_id = volume_id;
+        existing.relative_path = relative_path;
         let id = existing.id.clone();
         store.favorites.push(existing);
         return id;
@@ -184,6 +257,8 @@ fn add_to_store(store: &mut FavoritesStore, path: &str, name: Option<String>) ->
         id: id.clone(),
         path: path.to_string(),
         name: label,
+        volume_id,
+        relative_path,
     });
     id
 }
@@ -286,6 +361,14 @@ fn read_store_from_path(path: &Path) -> Option<FavoritesStore> {
 
     match serde_json::from_str::<FavoritesStore>(&contents) {
         Ok(store) if store.schema_version == CURRENT_SCHEMA_VERSION => Some(store),
+        Ok(store) if store.schema_version < CURRENT_SCHEMA_VERSION => {
+            log::info!(
+                target: "favorites::store",
+                "Migrating favorites from schema {} to {}",
+                store.schema_version, CURRENT_SCHEMA_VERSION
+            );
+            Some(migrate(store))
+        }
         Ok(store) => {
             log::warn!(
                 target: "favorites::store",
@@ -395,11 +478,39 @@ pub fn list() -> Vec<Favorite> {
     load_or_seed()
 }
 
+/// Returns the favorites in order with their resolved path and whether it's reachable right now.
+/// Stats every favorite, so call it off the main thread. Honors the FDA-pending skip (see
+/// `favorites/CLAUDE.md`): a TCC-protected path is reported reachable without a stat while the
+/// gate is pending.
+pub fn list_with_status() -> Vec<FavoriteStatus> {
+    let fda_pending = crate::fda_gate::is_fda_pending_runtime();
+    list()
+        .into_iter()
+        .map(|favorite| {
+            let resolved_path = resolve_path(&favorite);
+            let volume_mounted = favorite
+                .volume_id
+                .as_deref()
+                .is_none_or(|id| crate::file_system::get_volume_manager().get(id).is_some());
+            let protected =
+                crate::restricted_paths::tcc_paths::is_potentially_tcc_restricted(Path::new(&resolved_path));
+            let reachable = volume_mounted && ((fda_pending && protected) || Path::new(&resolved_path).exists());
+            FavoriteStatus {
+                favorite,
+                resolved_path,
+                reachable,
+            }
+        })
+        .collect()
+}
+
 /// Adds a favorite for `path`, deduping by normalized path (a re-add moves the existing entry to the
-/// end). When `name` is `None`, the label defaults to the path's file name.
+/// end). When `name` is `None`, the label defaults to the path's file name. A path on a non-root
+/// volume also records that volume's id and the mount-relative path.
 pub fn add(path: &str, name: Option<String>) {
+    let location = volume_location(path);
     mutate_and_persist(|store| {
-        add_to_store(store, path, name);
+        add_to_store(store, path, name, location);
         true
     });
 }
@@ -440,6 +551,8 @@ mod tests {
                     id: new_id(),
                     path: path.to_string(),
                     name: name.to_string(),
+                    volume_id: None,
+                    relative_path: None,
                 })
                 .collect(),
         }
@@ -450,7 +563,7 @@ mod tests {
     #[test]
     fn add_appends_new_path_with_derived_name() {
         let mut store = FavoritesStore::default();
-        add_to_store(&mut store, "/Users/x/Projects", None);
+        add_to_store(&mut store, "/Users/x/Projects", None, None);
         assert_eq!(store.favorites.len(), 1);
         assert_eq!(store.favorites[0].path, "/Users/x/Projects");
         assert_eq!(
@@ -462,18 +575,18 @@ mod tests {
     #[test]
     fn add_uses_explicit_name_when_given() {
         let mut store = FavoritesStore::default();
-        add_to_store(&mut store, "/Users/x/Projects", Some("Work".to_string()));
+        add_to_store(&mut store, "/Users/x/Projects", Some("Work".to_string()), None);
         assert_eq!(store.favorites[0].name, "Work");
     }
 
     #[test]
     fn add_dedups_by_normalized_path_and_keeps_id() {
         let mut store = FavoritesStore::default();
-        let first_id = add_to_store(&mut store, "/Users/x/a", None);
-        add_to_store(&mut store, "/Users/x/b", None);
+        let first_id = add_to_store(&mut store, "/Users/x/a", None, None);
+        add_to_store(&mut store, "/Users/x/b", None, None);
 
         // Re-add `/a` with a trailing slash: should dedup (not grow) and keep the original id.
-        let dup_id = add_to_store(&mut store, "/Users/x/a/", None);
+        let dup_id = add_to_store(&mut store, "/Users/x/a/", None, None);
         assert_eq!(store.favorites.len(), 2, "trailing-slash re-add should collapse");
         assert_eq!(dup_id, first_id, "re-add keeps the existing id");
         // The re-added entry moves to the end.
@@ -484,8 +597,8 @@ mod tests {
     #[test]
     fn add_re_add_with_name_overrides_existing_label() {
         let mut store = FavoritesStore::default();
-        add_to_store(&mut store, "/Users/x/a", None);
-        add_to_store(&mut store, "/Users/x/a", Some("Renamed".to_string()));
+        add_to_store(&mut store, "/Users/x/a", None, None);
+        add_to_store(&mut store, "/Users/x/a", Some("Renamed".to_string()), None);
         assert_eq!(store.favorites.len(), 1);
         assert_eq!(store.favorites[0].name, "Renamed");
     }
@@ -493,8 +606,8 @@ mod tests {
     #[test]
     fn ids_are_unique_across_adds() {
         let mut store = FavoritesStore::default();
-        add_to_store(&mut store, "/a", None);
-        add_to_store(&mut store, "/b", None);
+        add_to_store(&mut store, "/a", None, None);
+        add_to_store(&mut store, "/b", None, None);
         assert_ne!(store.favorites[0].id, store.favorites[1].id);
     }
 
@@ -578,6 +691,8 @@ mod tests {
             id: "abc-123".to_string(),
             path: "/Users/test/Documents".to_string(),
             name: "Documents".to_string(),
+            volume_id: None,
+            relative_path: None,
         };
         let json = serde_json::to_string_pretty(&f).unwrap();
         assert!(json.contains("\"path\": \"/Users/test/Documents\""));
@@ -589,9 +704,9 @@ mod tests {
     fn store_serialization_carries_schema_version() {
         let store = FavoritesStore::default();
         let json = serde_json::to_string_pretty(&store).unwrap();
-        assert!(json.contains("\"_schemaVersion\": 1"));
+        assert!(json.contains("\"_schemaVersion\": 2"));
         let back: FavoritesStore = serde_json::from_str(&json).unwrap();
-        assert_eq!(back.schema_version, 1);
+        assert_eq!(back.schema_version, 2);
         assert!(back.favorites.is_empty());
     }
 
@@ -646,9 +761,9 @@ mod tests {
         let path = dir.path().join(FAVORITES_FILE_NAME);
 
         let mut store = FavoritesStore::default();
-        add_to_store(&mut store, "/Users/x/a", None);
-        let removed_id = add_to_store(&mut store, "/Users/x/b", None);
-        add_to_store(&mut store, "/Users/x/c", Some("See".to_string()));
+        add_to_store(&mut store, "/Users/x/a", None, None);
+        let removed_id = add_to_store(&mut store, "/Users/x/b", None, None);
+        add_to_store(&mut store, "/Users/x/c", Some("See".to_string()), None);
         remove_from_store(&mut store, &removed_id);
         write_store_to_path(&path, &store).expect("write");
 
@@ -674,16 +789,55 @@ mod tests {
     }
 
     #[test]
-    fn schema_version_mismatch_quarantines_and_starts_fresh() {
+    fn newer_schema_version_quarantines_and_starts_fresh() {
         let dir = tempfile::tempdir().expect("tempdir");
         let path = dir.path().join(FAVORITES_FILE_NAME);
-        fs::write(&path, r#"{"_schemaVersion": 2, "favorites": []}"#).expect("write");
+        fs::write(&path, r#"{"_schemaVersion": 3, "favorites": []}"#).expect("write");
 
         let store = read_store_from_path(&path).expect("mismatch reads as Some(empty)");
         assert!(store.favorites.is_empty());
         assert!(path.with_extension("json.broken").exists());
     }
 
+    #[test]
+    fn v1_file_migrates_in_place_keeping_every_favorite() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join(FAVORITES_FILE_NAME);
+        let v1 = r#"{"_schemaVersion": 1, "favorites": [{"id": "a", "path": "/Users/x/a", "name": "A"}]}"#;
+        fs::write(&path, v1).expect("write");
+
+        let store = read_store_from_path(&path).expect("present");
+        assert_eq!(store.schema_version, CURRENT_SCHEMA_VERSION);
+        assert_eq!(store.favorites.len(), 1);
+        assert_eq!(store.favorites[0].path, "/Users/x/a");
+        assert_eq!(store.favorites[0].volume_id, None);
+        assert!(path.exists(), "an older file is migrated, never quarantined");
+    }
+
+    // -- volume-relative location --
+
+    #[test]
+    fn add_records_the_volume_location_and_a_re_add_refreshes_it() {
+        let mut store = FavoritesStore::default();
+        let location = Some(("backup-disk".to_string(), "photos/2024".to_string()));
+        add_to_store(&mut store, "/Volumes/Backup/photos/2024", None, location);
+        assert_eq!(store.favorites[0].volume_id.as_deref(), Some("backup-disk"));
+        assert_eq!(store.favorites[0].relative_path.as_deref(), Some("photos/2024"));
+
+        add_to_store(&mut store, "/Volumes/Backup/photos/2024", None, None);
+        assert_eq!(store.favorites.len(), 1);
+        assert_eq!(store.favorites[0].volume_id, None);
+    }
+
+    #[test]
+    fn rebase_joins_the_relative_path_onto_the_new_mount_root() {
+        assert_eq!(
+            rebase(Path::new("/Volumes/Backup 1"), "photos/2024"),
+            "/Volumes/Backup 1/photos/2024"
+        );
+        assert_eq!(rebase(Path::new("/Volumes/Backup 1"), ""), "/Volumes/Backup 1");
+    }
+
     #[test]
     fn present_but_unreadable_reads_as_some_empty_and_never_reseeds() {
         // A present-but-unreadable file (here simulated by a directory at the favorites path, which
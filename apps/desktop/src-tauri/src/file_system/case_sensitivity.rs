@@ -0,0 +1,157 @@
+//! Case sensitivity of a destination filesystem, and case-only name collisions.
+//!
+//! APFS and HFS+ are case-insensitive by default, ext4 and most SMB servers backed by Linux
+//! are case-sensitive, and a case-sensitive APFS volume is one checkbox away. So the answer
+//! is probed, not guessed from the filesystem kind: [`is_case_sensitive`] stats an existing
+//! child under its case-flipped name and checks whether it lands on the same inode. The
+//! result is cached per device, so a copy probes its destination once.
+//!
+//! Name comparison is Unicode lowercase folding, which is what APFS and HFS+ do for the names
+//! users actually type. Normalization differences (NFC vs NFD) are out of scope here.
+
+use std::collections::HashMap;
+use std::io;
+use std::os::unix::fs::MetadataExt;
+use std::path::Path;
+use std::sync::{LazyLock, Mutex};
+
+use crate::ignore_poison::IgnorePoison;
+
+/// Children looked at when searching for a name to probe with. A folder whose first entries
+/// have no cased letter ("001.jpg", "002.jpg", …) rarely gets better further down.
+const MAX_PROBE_CANDIDATES: usize = 64;
+
+/// Probed answers keyed by `st_dev`.
+static BY_DEVICE: LazyLock<Mutex<HashMap<u64, bool>>> = LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// `true` when `a` and `b` are different names that a case-insensitive filesystem treats as
+/// the same one.
+pub fn is_case_only_match(a: &str, b: &str) -> bool {
+    a != b && a.to_lowercase() == b.to_lowercase()
+}
+
+/// Whether the filesystem holding `dir` tells `README.md` and `readme.md` apart.
+///
+/// Probes once per device and caches the answer. When nothing in `dir` can be probed (empty,
+/// unreadable, or no child name with a cased letter), falls back to the platform default
+/// (case-insensitive on macOS, case-sensitive on Linux) without caching, so a later call on a
+/// fuller folder can still get a real answer.
+pub fn is_case_sensitive(dir: &Path) -> bool {
+    let Ok(dev) = std::fs::metadata(dir).map(|m| m.dev()) else {
+        return platform_default();
+    };
+    if let Some(&cached) = BY_DEVICE.lock_ignore_poison().get(&dev) {
+        return cached;
+    }
+    match probe(dir, dev) {
+        Some(sensitive) => {
+            log::debug!(
+                target: "case_sensitivity",
+                "Probed {}: case-{}sensitive",
+                dir.display(),
+                if sensitive { "" } else { "in" }
+            );
+            BY_DEVICE.lock_ignore_poison().insert(dev, sensitive);
+            sensitive
+        }
+        None => platform_default(),
+    }
+}
+
+fn platform_default() -> bool {
+    !cfg!(target_os = "macos")
+}
+
+/// Stats a child of `dir` under its case-flipped name. Same inode means case-insensitive,
+/// not found (or a different file) means case-sensitive. Only children on `dev` count, so a
+/// mount point inside `dir` can't answer for the wrong filesystem.
+fn probe(dir: &Path, dev: u64) -> Option<bool> {
+    let entries = std::fs::read_dir(dir).ok()?;
+    for entry in entries.flatten().take(MAX_PROBE_CANDIDATES) {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let flipped = flip_case(&name);
+        if flipped == name {
+            continue;
+        }
+        let Ok(original) = entry.metadata() else { continue };
+        if original.dev() != dev {
+            continue;
+        }
+        return match std::fs::symlink_metadata(dir.join(&flipped)) {
+            Ok(meta) => Some(meta.ino() != original.ino()),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Some(true),
+            Err(_) => None,
+        };
+    }
+    None
+}
+
+fn flip_case(name: &str) -> String {
+    name.chars()
+        .flat_map(|c| -> Vec<char> {
+            if c.is_lowercase() {
+                c.to_uppercase().collect()
+            } else {
+                c.to_lowercase().collect()
+            }
+        })
+        .collect()
+}
+
+/// The name of an entry in `dir` that differs from `name` only by case, if there is one and
+/// `name` itself isn't also there (on a case-sensitive filesystem both can exist). Reads the
+/// whole folder, so call it once per clash, not per file.
+pub fn find_case_variant(dir: &Path, name: &str) -> Option<String> {
+    let mut variant = None;
+    for entry in std::fs::read_dir(dir).ok()?.flatten() {
+        let entry_name = entry.file_name().to_string_lossy().into_owned();
+        if entry_name == name {
+            return None;
+        }
+        if variant.is_none() && is_case_only_match(&entry_name, name) {
+            variant = Some(entry_name);
+        }
+    }
+    variant
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn case_only_match_ignores_identical_and_unrelated_names() {
+        assert!(is_case_only_match("README.md", "readme.md"));
+        assert!(is_case_only_match("Ärger.txt", "ärger.txt"));
+        assert!(!is_case_only_match("readme.md", "readme.md"));
+        assert!(!is_case_only_match("readme.md", "readme.txt"));
+    }
+
+    #[test]
+    fn probe_matches_what_the_filesystem_does() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        std::fs::write(dir.path().join("Probe.txt"), b"x").expect("write");
+        let actually_insensitive = dir.path().join("pROBE.TXT").exists();
+        let dev = std::fs::metadata(dir.path()).expect("stat").dev();
+
+        assert_eq!(probe(dir.path(), dev), Some(!actually_insensitive));
+        assert_eq!(
+            probe(tempfile::tempdir().expect("tempdir").path(), dev),
+            None,
+            "empty dir"
+        );
+    }
+
+    #[test]
+    fn find_case_variant_only_reports_a_differently_cased_sibling() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        std::fs::write(dir.path().join("readme.md"), b"x").expect("write");
+
+        assert_eq!(
+            find_case_variant(dir.path(), "README.md"),
+            Some("readme.md".to_string())
+        );
+        assert_eq!(find_case_variant(dir.path(), "readme.md"), None);
+        assert_eq!(find_case_variant(dir.path(), "other.md"), None);
+    }
+}
@@ -18,9 +18,22 @@
 //! menu items only for files under `~/Library/Mobile Documents/com~apple~CloudDocs/`
 //! (iCloud Drive). For Dropbox/GDrive/OneDrive items the menu items don't appear;
 //! the user has to use the provider's own client (or Finder).
+//!
+//! [`materialize_file`] is the exception: it works for every supported provider because it
+//! doesn't ask the provider anything. Reading an online-only file makes the kernel fault it
+//! in through its File Provider, same as the copy pre-pass in
+//! `write_operations/cloud_materialize.rs`. Progress is the file's allocated size growing
+//! on disk, polled, so it's approximate and moves in the provider's write-sized steps.
+//! [`dematerialize_file`] has no such back door and stays iCloud Drive only.
+use std::os::unix::fs::MetadataExt;
 use std::path::Path;
 #[cfg(target_os = "macos")]
 use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::file_system::sync_status::{self, CloudProvider};
 
 /// Subdirectory under `$HOME` for iCloud Drive items.
 #[cfg(target_os = "macos")]
@@ -123,10 +136,130 @@ pub fn request_download(_path: &Path) -> Result<(), String> {
     Err("Cloud actions are only available on macOS".to_string())
 }
 
-#[cfg(all(test, target_os = "macos"))]
+/// How often [`materialize_file`] reports progress while the provider downloads.
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(200);
+
+/// A download whose size hasn't grown for this long counts as stuck. The read stays blocked
+/// in the kernel (it can't be interrupted), but the caller gets its answer.
+const STALL_TIMEOUT: Duration = Duration::from_secs(2 * 60);
+
+/// What [`materialize_file`] did.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "snake_case")]
+pub enum MaterializeOutcome {
+    /// The file already had its content locally; nothing was downloaded.
+    AlreadyLocal,
+    Downloaded,
+}
+
+/// Bytes the file occupies on disk right now. Grows as the provider writes the content.
+fn allocated_bytes(path: &Path) -> u64 {
+    std::fs::symlink_metadata(path).map_or(0, |m| m.blocks() * 512)
+}
+
+/// Downloads an online-only file so its content is local, calling `on_progress(bytes_done,
+/// bytes_total)` at most every [`PROGRESS_INTERVAL`] while it waits. A file that's already
+/// local returns [`MaterializeOutcome::AlreadyLocal`] without a progress call. Errors for
+/// folders, for online-only files outside a supported provider's folder, and for downloads
+/// that fail or stall for [`STALL_TIMEOUT`].
+pub fn materialize_file(path: &Path, on_progress: &dyn Fn(u64, u64)) -> Result<MaterializeOutcome, String> {
+    let metadata = std::fs::symlink_metadata(path).map_err(|e| e.to_string())?;
+    if metadata.is_dir() {
+        return Err("Only files can be downloaded, not folders".to_string());
+    }
+    if !sync_status::is_online_only(path) {
+        return Ok(MaterializeOutcome::AlreadyLocal);
+    }
+    let Some(provider) = sync_status::cloud_provider_for_path(path) else {
+        return Err(
+            "This file is online-only, but Cmdr doesn't support its cloud provider. Download it from the provider's app."
+                .to_string(),
+        );
+    };
+    log::debug!(target: "cloud_actions", "materialize_file: path={path:?} provider={provider:?}");
+
+    let bytes_total = metadata.len();
+    on_progress(0, bytes_total);
+    let worker = {
+        let path = path.to_path_buf();
+        std::thread::spawn(move || sync_status::materialize(&path))
+    };
+    let mut last_growth = (0u64, Instant::now());
+    while !worker.is_finished() {
+        std::thread::sleep(PROGRESS_INTERVAL);
+        let bytes_done = allocated_bytes(path).min(bytes_total);
+        if bytes_done != last_growth.0 {
+            last_growth = (bytes_done, Instant::now());
+        } else if last_growth.1.elapsed() >= STALL_TIMEOUT {
+            // The worker stays parked in its read; it ends whenever the provider answers.
+            return Err(format!(
+                "{} stopped sending the file — give it another try",
+                provider.display_name()
+            ));
+        }
+        on_progress(bytes_done, bytes_total);
+    }
+    worker
+        .join()
+        .map_err(|_| "The download thread panicked".to_string())?
+        .map_err(|e| format!("Couldn't download the file from {}: {e}", provider.display_name()))?;
+    on_progress(bytes_total, bytes_total);
+    Ok(MaterializeOutcome::Downloaded)
+}
+
+/// Evicts a file's local copy back to online-only. A file that's already online-only is a
+/// no-op. Only iCloud Drive lets a third-party app do this (see the module docs); other
+/// providers get an error that names them.
+pub fn dematerialize_file(path: &Path) -> Result<(), String> {
+    let metadata = std::fs::symlink_metadata(path).map_err(|e| e.to_string())?;
+    if metadata.is_dir() {
+        return Err("Only files can be made online-only, not folders".to_string());
+    }
+    if sync_status::is_online_only(path) {
+        return Ok(());
+    }
+    match sync_status::cloud_provider_for_path(path) {
+        Some(CloudProvider::ICloud) => evict_item(path),
+        Some(provider) => Err(format!(
+            "{} doesn't let other apps free up its files. Use its own app or Finder instead.",
+            provider.display_name()
+        )),
+        None => Err("This file isn't in a cloud-synced folder".to_string()),
+    }
+}
+
+#[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn materialize_file_is_a_no_op_for_local_files() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let file = dir.path().join("local.txt");
+        std::fs::write(&file, b"content").expect("write");
+        let calls = std::cell::Cell::new(0);
+        let outcome = materialize_file(&file, &|_, _| calls.set(calls.get() + 1)).expect("materialize");
+        assert_eq!(outcome, MaterializeOutcome::AlreadyLocal);
+        assert_eq!(calls.get(), 0);
+    }
+
+    #[test]
+    fn materialize_file_rejects_folders_and_missing_paths() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        assert!(materialize_file(dir.path(), &|_, _| {}).is_err());
+        assert!(materialize_file(&dir.path().join("missing"), &|_, _| {}).is_err());
+    }
+
+    #[test]
+    fn dematerialize_file_outside_a_cloud_folder_is_an_error() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let file = dir.path().join("local.txt");
+        std::fs::write(&file, b"content").expect("write");
+        let err = dematerialize_file(&file).expect_err("not a cloud file");
+        assert!(err.contains("cloud-synced"), "got {err}");
+    }
+
+    #[cfg(target_os = "macos")]
     fn set_test_home() {
         // SAFETY: tests run sequentially in the same process; setting HOME during
         // the test is fine because we only read it from these helpers.
@@ -135,6 +268,7 @@ mod tests {
         }
     }
 
+    #[cfg(target_os = "macos")]
     #[test]
     fn is_in_icloud_drive_matches_icloud() {
         set_test_home();
@@ -146,6 +280,7 @@ mod tests {
         )));
     }
 
+    #[cfg(target_os = "macos")]
     #[test]
     fn is_in_icloud_drive_excludes_third_party() {
         set_test_home();
@@ -161,6 +296,7 @@ mod tests {
         )));
     }
 
+    #[cfg(target_os = "macos")]
     #[test]
     fn is_in_icloud_drive_negative() {
         set_test_home();
@@ -0,0 +1,456 @@
+//! Directory comparison ("compare folders").
+//!
+//! Walks two directory trees side by side through the [`Volume`] trait, so a local
+//! folder compares against an MTP or SMB one the same way it compares against
+//! another local folder. Each name in the union of both sides becomes one
+//! [`CompareEntry`] annotated `OnlyLeft`, `OnlyRight`, `Differ`, or `Same`.
+//!
+//! Files match on size plus mtime (within [`CompareOptions::mtime_tolerance_secs`],
+//! because FAT and some MTP devices store 2-second timestamps). With
+//! `compare_contents`, same-size files are compared byte by byte instead, streamed
+//! chunk by chunk from both volumes so a multi-GB pair never lands in memory.
+//!
+//! Results stream level by level through `on_batch`: a directory's files are
+//! reported as soon as both sides are listed, and the directory's own entry is
+//! reported after its subtree, once we know whether anything under it differs.
+//! The caller also gets the full merged list back when the walk finishes.
+
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use serde::{Deserialize, Serialize};
+
+use crate::cancel_registry::CancelRegistry;
+use crate::file_system::listing::FileEntry;
+use crate::file_system::volume::{Volume, VolumeError, VolumeReadStream};
+use crate::ignore_poison::IgnorePoison;
+
+/// How a name compares across the two sides.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+pub enum CompareStatus {
+    OnlyLeft,
+    OnlyRight,
+    /// Present on both sides but different: size, mtime, or contents differ, one
+    /// side is a file and the other a directory, or (for directories) something
+    /// in the subtree differs.
+    Differ,
+    Same,
+}
+
+/// One row of the side-by-side view.
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct CompareEntry {
+    /// Path relative to both compared roots, `/`-separated, no leading slash.
+    pub relative_path: String,
+    pub name: String,
+    /// `true` when the entry is a directory on every side it exists on. A
+    /// file-vs-directory clash reports `false` with status `Differ`.
+    pub is_directory: bool,
+    pub status: CompareStatus,
+    pub left_size: Option<u64>,
+    pub right_size: Option<u64>,
+    pub left_modified_at: Option<u64>,
+    pub right_modified_at: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct CompareOptions {
+    /// Descend into directories present on both sides. Without it, such
+    /// directories count as `Same` (only the top level is compared).
+    pub recursive: bool,
+    /// Compare the bytes of same-size files instead of trusting mtime.
+    pub compare_contents: bool,
+    /// Max mtime drift (seconds) still treated as equal.
+    pub mtime_tolerance_secs: u64,
+}
+
+impl Default for CompareOptions {
+    fn default() -> Self {
+        Self {
+            recursive: true,
+            compare_contents: false,
+            mtime_tolerance_secs: 2,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum CompareError {
+    Cancelled,
+    Volume { side: &'static str, error: VolumeError },
+}
+
+impl std::fmt::Display for CompareError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Cancelled => write!(f, "Comparison cancelled"),
+            Self::Volume { side, error } => write!(f, "Couldn't read the {side} side: {error}"),
+        }
+    }
+}
+
+/// Cancel flags of in-flight comparisons.
+pub static ACTIVE_COMPARES: CancelRegistry = CancelRegistry::new();
+
+/// One side of a comparison: a volume plus the directory on it.
+pub struct CompareSide {
+    pub volume: Arc<dyn Volume>,
+    pub root: PathBuf,
+}
+
+struct CompareContext<'a> {
+    left: &'a CompareSide,
+    right: &'a CompareSide,
+    options: &'a CompareOptions,
+    cancelled: &'a AtomicBool,
+    on_batch: &'a (dyn Fn(&[CompareEntry]) + Sync),
+    results: Mutex<Vec<CompareEntry>>,
+}
+
+impl CompareContext<'_> {
+    fn report(&self, batch: Vec<CompareEntry>) {
+        if batch.is_empty() {
+            return;
+        }
+        (self.on_batch)(&batch);
+        self.results.lock_ignore_poison().extend(batch);
+    }
+
+    fn check_cancelled(&self) -> Result<(), CompareError> {
+        if self.cancelled.load(Ordering::Relaxed) {
+            Err(CompareError::Cancelled)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Compares `left` against `right`, calling `on_batch` as results become known,
+/// and returns every entry once the walk completes.
+pub async fn compare_directories(
+    left: &CompareSide,
+    right: &CompareSide,
+    options: &CompareOptions,
+    cancelled: &AtomicBool,
+    on_batch: &(dyn Fn(&[CompareEntry]) + Sync),
+) -> Result<Vec<CompareEntry>, CompareError> {
+    let ctx = CompareContext {
+        left,
+        right,
+        options,
+        cancelled,
+        on_batch,
+        results: Mutex::new(Vec::new()),
+    };
+    compare_level(&ctx, PathBuf::new()).await?;
+    Ok(ctx
+        .results
+        .into_inner()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()))
+}
+
+/// Compares one directory level and recurses into shared subdirectories.
+/// Resolves to `true` when everything at and under `relative` is the same.
+fn compare_level<'a>(
+    ctx: &'a CompareContext<'a>,
+    relative: PathBuf,
+) -> Pin<Box<dyn Future<Output = Result<bool, CompareError>> + Send + 'a>> {
+    Box::pin(async move {
+        ctx.check_cancelled()?;
+        let left_dir = ctx.left.root.join(&relative);
+        let right_dir = ctx.right.root.join(&relative);
+        let left_entries = ctx
+            .left
+            .volume
+            .list_directory(&left_dir, None)
+            .await
+            .map_err(|error| CompareError::Volume { side: "left", error })?;
+        let right_entries = ctx
+            .right
+            .volume
+            .list_directory(&right_dir, None)
+            .await
+            .map_err(|error| CompareError::Volume { side: "right", error })?;
+
+        let mut all_same = true;
+        let mut files = Vec::new();
+        let mut shared_dirs = Vec::new();
+        for (name, pair) in pair_by_name(left_entries, right_entries) {
+            let relative_path = join_relative(&relative, &name);
+            match pair {
+                (Some(l), Some(r)) if l.is_directory && r.is_directory => shared_dirs.push((relative_path, l, r)),
+                (l, r) => {
+                    let status = match (&l, &r) {
+                        (Some(l), Some(r)) => compare_files(ctx, &relative_path, l, r).await?,
+                        (Some(_), None) => CompareStatus::OnlyLeft,
+                        _ => CompareStatus::OnlyRight,
+                    };
+                    all_same &= status == CompareStatus::Same;
+                    files.push(build_entry(relative_path, name, status, l.as_ref(), r.as_ref()));
+                }
+            }
+        }
+        ctx.report(files);
+
+        for (relative_path, l, r) in shared_dirs {
+            let subtree_same = if ctx.options.recursive {
+                compare_level(ctx, PathBuf::from(&relative_path)).await?
+            } else {
+                true
+            };
+            all_same &= subtree_same;
+            let status = if subtree_same {
+                CompareStatus::Same
+            } else {
+                CompareStatus::Differ
+            };
+            let name = l.name.clone();
+            ctx.report(vec![build_entry(relative_path, name, status, Some(&l), Some(&r))]);
+        }
+        Ok(all_same)
+    })
+}
+
+/// Pairs entries by exact name, ordered by name so output is deterministic
+/// regardless of each backend's listing order.
+fn pair_by_name(
+    left: Vec<FileEntry>,
+    right: Vec<FileEntry>,
+) -> BTreeMap<String, (Option<FileEntry>, Option<FileEntry>)> {
+    let mut pairs: BTreeMap<String, (Option<FileEntry>, Option<FileEntry>)> = BTreeMap::new();
+    for entry in left {
+        pairs.entry(entry.name.clone()).or_default().0 = Some(entry);
+    }
+    for entry in right {
+        pairs.entry(entry.name.clone()).or_default().1 = Some(entry);
+    }
+    pairs
+}
+
+fn join_relative(parent: &Path, name: &str) -> String {
+    if parent.as_os_str().is_empty() {
+        name.to_string()
+    } else {
+        format!("{}/{}", parent.to_string_lossy(), name)
+    }
+}
+
+async fn compare_files(
+    ctx: &CompareContext<'_>,
+    relative_path: &str,
+    left: &FileEntry,
+    right: &FileEntry,
+) -> Result<CompareStatus, CompareError> {
+    if left.is_directory != right.is_directory {
+        return Ok(CompareStatus::Differ);
+    }
+    if left.size != right.size {
+        return Ok(CompareStatus::Differ);
+    }
+    if ctx.options.compare_contents && !left.is_symlink && !right.is_symlink {
+        let same = contents_equal(ctx, relative_path).await?;
+        return Ok(if same {
+            CompareStatus::Same
+        } else {
+            CompareStatus::Differ
+        });
+    }
+    Ok(metadata_status(left, right, ctx.options.mtime_tolerance_secs))
+}
+
+/// Size + mtime verdict for two non-directory entries. Missing mtimes on both
+/// sides (some MTP devices) fall back to size alone.
+fn metadata_status(left: &FileEntry, right: &FileEntry, tolerance_secs: u64) -> CompareStatus {
+    if left.size != right.size {
+        return CompareStatus::Differ;
+    }
+    match (left.modified_at, right.modified_at) {
+        (Some(l), Some(r)) if l.abs_diff(r) > tolerance_secs => CompareStatus::Differ,
+        (Some(_), None) | (None, Some(_)) => CompareStatus::Differ,
+        _ => CompareStatus::Same,
+    }
+}
+
+/// Streams both files and compares them chunk by chunk. Backends return chunks
+/// of different sizes, so each side keeps a small carry-over buffer.
+async fn contents_equal(ctx: &CompareContext<'_>, relative_path: &str) -> Result<bool, CompareError> {
+    let mut left = ctx
+        .left
+        .volume
+        .open_read_stream(&ctx.left.root.join(relative_path))
+        .await
+        .map_err(|error| CompareError::Volume { side: "left", error })?;
+    let mut right = ctx
+        .right
+        .volume
+        .open_read_stream(&ctx.right.root.join(relative_path))
+        .await
+        .map_err(|error| CompareError::Volume { side: "right", error })?;
+
+    let mut left_buf: Vec<u8> = Vec::new();
+    let mut right_buf: Vec<u8> = Vec::new();
+    loop {
+        ctx.check_cancelled()?;
+        let left_done = fill(&mut *left, &mut left_buf, "left").await?;
+        let right_done = fill(&mut *right, &mut right_buf, "right").await?;
+        let common = left_buf.len().min(right_buf.len());
+        if left_buf[..common] != right_buf[..common] {
+            return Ok(false);
+        }
+        left_buf.drain(..common);
+        right_buf.drain(..common);
+        if left_done || right_done {
+            // One side ended: equal only if the other has nothing left either.
+            let left_rest = left_done || fill(&mut *left, &mut left_buf, "left").await?;
+            let right_rest = right_done || fill(&mut *right, &mut right_buf, "right").await?;
+            return Ok(left_rest && right_rest && left_buf.is_empty() && right_buf.is_empty());
+        }
+    }
+}
+
+/// Appends the next chunk to `buf` unless it already holds data. Returns `true`
+/// when the stream is exhausted and `buf` is empty.
+async fn fill(stream: &mut dyn VolumeReadStream, buf: &mut Vec<u8>, side: &'static str) -> Result<bool, CompareError> {
+    if !buf.is_empty() {
+        return Ok(false);
+    }
+    match stream.next_chunk().await {
+        Some(Ok(chunk)) => {
+            buf.extend_from_slice(&chunk);
+            Ok(false)
+        }
+        Some(Err(error)) => Err(CompareError::Volume { side, error }),
+        None => Ok(true),
+    }
+}
+
+fn build_entry(
+    relative_path: String,
+    name: String,
+    status: CompareStatus,
+    left: Option<&FileEntry>,
+    right: Option<&FileEntry>,
+) -> CompareEntry {
+    let is_directory = left.is_none_or(|e| e.is_directory) && right.is_none_or(|e| e.is_directory);
+    CompareEntry {
+        relative_path,
+        name,
+        is_directory,
+        status,
+        left_size: left.and_then(|e| e.size),
+        right_size: right.and_then(|e| e.size),
+        left_modified_at: left.and_then(|e| e.modified_at),
+        right_modified_at: right.and_then(|e| e.modified_at),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file_system::volume::InMemoryVolume;
+
+    async fn side(files: &[(&str, &[u8])], dirs: &[&str]) -> CompareSide {
+        let volume = InMemoryVolume::new("test");
+        for dir in dirs {
+            volume.create_directory(Path::new(dir)).await.expect("create dir");
+        }
+        for (path, content) in files {
+            volume.create_file(Path::new(path), content).await.expect("create file");
+            volume.set_modified_at(Path::new(path), Some(1_700_000_000));
+        }
+        CompareSide {
+            volume: Arc::new(volume),
+            root: PathBuf::from("/"),
+        }
+    }
+
+    async fn run(left: &CompareSide, right: &CompareSide, options: CompareOptions) -> Vec<CompareEntry> {
+        let cancelled = AtomicBool::new(false);
+        compare_directories(left, right, &options, &cancelled, &|_| {})
+            .await
+            .expect("compare")
+    }
+
+    fn status_of(entries: &[CompareEntry], relative_path: &str) -> CompareStatus {
+        entries
+            .iter()
+            .find(|e| e.relative_path == relative_path)
+            .unwrap_or_else(|| panic!("no entry for {relative_path}"))
+            .status
+    }
+
+    #[tokio::test]
+    async fn classifies_each_side() {
+        let left = side(&[("/a.txt", b"same"), ("/left.txt", b"l"), ("/b.txt", b"one")], &[]).await;
+        let right = side(&[("/a.txt", b"same"), ("/right.txt", b"r"), ("/b.txt", b"three")], &[]).await;
+
+        let entries = run(&left, &right, CompareOptions::default()).await;
+
+        assert_eq!(entries.len(), 4);
+        assert_eq!(status_of(&entries, "a.txt"), CompareStatus::Same);
+        assert_eq!(status_of(&entries, "b.txt"), CompareStatus::Differ);
+        assert_eq!(status_of(&entries, "left.txt"), CompareStatus::OnlyLeft);
+        assert_eq!(status_of(&entries, "right.txt"), CompareStatus::OnlyRight);
+    }
+
+    #[tokio::test]
+    async fn directory_differs_when_its_subtree_does() {
+        let left = side(&[("/docs/x.txt", b"x"), ("/same/y.txt", b"y")], &["/docs", "/same"]).await;
+        let right = side(&[("/docs/x.txt", b"z"), ("/same/y.txt", b"y")], &["/docs", "/same"]).await;
+
+        let entries = run(&left, &right, CompareOptions::default()).await;
+
+        assert_eq!(status_of(&entries, "docs/x.txt"), CompareStatus::Differ);
+        assert_eq!(status_of(&entries, "docs"), CompareStatus::Differ);
+        assert_eq!(status_of(&entries, "same"), CompareStatus::Same);
+    }
+
+    #[tokio::test]
+    async fn content_mode_catches_same_size_different_bytes() {
+        let left = side(&[("/f.bin", b"abcd")], &[]).await;
+        let right = side(&[("/f.bin", b"abce")], &[]).await;
+
+        let by_metadata = run(&left, &right, CompareOptions::default()).await;
+        assert_eq!(status_of(&by_metadata, "f.bin"), CompareStatus::Same);
+
+        let by_contents = run(
+            &left,
+            &right,
+            CompareOptions {
+                compare_contents: true,
+                ..CompareOptions::default()
+            },
+        )
+        .await;
+        assert_eq!(status_of(&by_contents, "f.bin"), CompareStatus::Differ);
+    }
+
+    #[tokio::test]
+    async fn file_versus_directory_is_a_difference() {
+        let left = side(&[("/thing", b"file")], &[]).await;
+        let right = side(&[], &["/thing"]).await;
+
+        let entries = run(&left, &right, CompareOptions::default()).await;
+
+        assert_eq!(status_of(&entries, "thing"), CompareStatus::Differ);
+        assert!(!entries[0].is_directory);
+    }
+
+    #[test]
+    fn mtime_within_tolerance_is_same() {
+        let mut left = FileEntry::new("a".into(), "/a".into(), false, false);
+        left.size = Some(1);
+        left.modified_at = Some(100);
+        let mut right = left.clone();
+        right.modified_at = Some(102);
+        assert_eq!(metadata_status(&left, &right, 2), CompareStatus::Same);
+        right.modified_at = Some(103);
+        assert_eq!(metadata_status(&left, &right, 2), CompareStatus::Differ);
+    }
+}
@@ -0,0 +1,310 @@
+//! Remembered conflict policies per folder pair (`conflict-policies.json`).
+//!
+//! A user who always overwrites when syncing `~/Work` to the backup drive can tell Cmdr once
+//! ("remember for this pair" in the conflict prompt, or `set_default_conflict_policy`). Copy and
+//! move commands consult this store before starting: an operation that would prompt (`Stop`) takes
+//! the remembered policy instead. An explicit policy from the caller always wins.
+//!
+//! A pair is the folder the sources live in plus the destination folder, each with its volume id,
+//! matched exactly (no inheritance from parent folders). Storing `Stop` forgets the pair.
+//!
+//! Same shape as `move_targets.rs`: in-memory cache loaded lazily, durable temp-then-rename write,
+//! a schema-versioned file that's quarantined on a parse error, and a store path resolved once from
+//! app setup via [`init`].
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+use serde::{Deserialize, Serialize};
+
+use crate::file_system::write_operations::ConflictResolution;
+use crate::ignore_poison::IgnorePoison;
+
+/// Bump when the on-disk shape changes in an incompatible way.
+const CURRENT_SCHEMA_VERSION: u32 = 1;
+
+/// Filename inside `{app_data_dir}/`.
+const POLICIES_FILENAME: &str = "conflict-policies.json";
+
+/// Cap on remembered pairs, dropping the least recently set. Far above any hand-curated list.
+const MAX_POLICIES: usize = 200;
+
+/// A source folder and a destination folder, each on its volume.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DirPair {
+    pub source_volume_id: String,
+    pub source_dir: String,
+    pub destination_volume_id: String,
+    pub destination_dir: String,
+}
+
+/// A remembered policy, as stored and as returned to the frontend.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DirPairPolicy {
+    pub pair: DirPair,
+    pub policy: ConflictResolution,
+    /// Unix epoch milliseconds of when the policy was last set.
+    pub updated_at: i64,
+}
+
+/// On-disk shape. Newest first.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PoliciesStore {
+    #[serde(rename = "_schemaVersion")]
+    schema_version: u32,
+    #[serde(default)]
+    policies: Vec<DirPairPolicy>,
+}
+
+/// `None` until the first access loads from disk.
+static CACHE: OnceLock<Mutex<Option<PoliciesStore>>> = OnceLock::new();
+
+/// Serializes the cache → disk flush so concurrent writers can't clobber each other.
+static DISK_LOCK: OnceLock<Mutex<()>> = OnceLock::new();
+
+/// `{app_data_dir}/conflict-policies.json`, set once from app setup. Unset (tests, or an
+/// unresolvable data dir) keeps the store in memory only.
+static STORE_PATH: OnceLock<PathBuf> = OnceLock::new();
+
+fn cache() -> &'static Mutex<Option<PoliciesStore>> {
+    CACHE.get_or_init(|| Mutex::new(None))
+}
+
+fn disk_lock() -> &'static Mutex<()> {
+    DISK_LOCK.get_or_init(|| Mutex::new(()))
+}
+
+/// Drops a trailing slash so `/a/b/` and `/a/b` name the same pair. The root stays `/`.
+fn normalize_dir(dir: &str) -> String {
+    let trimmed = dir.trim_end_matches('/');
+    if trimmed.is_empty() && dir.starts_with('/') {
+        "/".to_string()
+    } else {
+        trimmed.to_string()
+    }
+}
+
+fn normalize_pair(pair: &DirPair) -> DirPair {
+    DirPair {
+        source_volume_id: pair.source_volume_id.clone(),
+        source_dir: normalize_dir(&pair.source_dir),
+        destination_volume_id: pair.destination_volume_id.clone(),
+        destination_dir: normalize_dir(&pair.destination_dir),
+    }
+}
+
+/// Sets (or, for `Stop`, forgets) the policy for `pair`, moving it to the front.
+fn apply_set(store: &mut PoliciesStore, pair: &DirPair, policy: ConflictResolution, now_ms: i64) {
+    store.policies.retain(|p| p.pair != *pair);
+    if policy == ConflictResolution::Stop {
+        return;
+    }
+    store.policies.insert(
+        0,
+        DirPairPolicy {
+            pair: pair.clone(),
+            policy,
+            updated_at: now_ms,
+        },
+    );
+    store.policies.truncate(MAX_POLICIES);
+}
+
+fn read_store_from_path(path: &Path) -> PoliciesStore {
+    let _ = fs::remove_file(path.with_extension("json.tmp"));
+    let Ok(contents) = fs::read_to_string(path) else {
+        return PoliciesStore::default();
+    };
+    match serde_json::from_str::<PoliciesStore>(&contents) {
+        Ok(store) if store.schema_version == CURRENT_SCHEMA_VERSION => store,
+        result => {
+            log::warn!(
+                target: "conflict_policies",
+                "Ignoring unreadable conflict policies at {path:?} ({:?}); starting fresh",
+                result.map(|s| s.schema_version)
+            );
+            let _ = fs::rename(path, path.with_extension("json.broken"));
+            PoliciesStore::default()
+        }
+    }
+}
+
+fn write_store_to_path(path: &Path, store: &PoliciesStore) -> std::io::Result<()> {
+    let json = serde_json::to_string_pretty(store).map_err(std::io::Error::other)?;
+    crate::config::durable_write_json(path, &path.with_extension("json.tmp"), &json)
+}
+
+/// Runs `f` on the loaded store, loading it from disk first if needed.
+fn with_store<T>(f: impl FnOnce(&mut PoliciesStore) -> T) -> T {
+    let mut guard = cache().lock_ignore_poison();
+    let store = guard.get_or_insert_with(|| {
+        let mut store = STORE_PATH.get().map(|p| read_store_from_path(p)).unwrap_or_default();
+        store.schema_version = CURRENT_SCHEMA_VERSION;
+        store
+    });
+    f(store)
+}
+
+/// Applies `f` to the store and flushes the result to disk. Unlike the move-target recents, a
+/// policy is something the user asked for, so a failed write is reported.
+fn update_store<T>(f: impl FnOnce(&mut PoliciesStore) -> T) -> Result<T, String> {
+    let (result, snapshot) = with_store(|store| {
+        let result = f(store);
+        (result, store.clone())
+    });
+    let Some(store_path) = STORE_PATH.get() else {
+        return Ok(result);
+    };
+    let _disk_guard = disk_lock().lock_ignore_poison();
+    write_store_to_path(store_path, &snapshot).map_err(|e| format!("Couldn't save conflict policies: {e}"))?;
+    Ok(result)
+}
+
+/// Resolves the store path. Call once from app setup, before any copy or move can start.
+pub fn init<R: tauri::Runtime>(app: &tauri::AppHandle<R>) {
+    if let Ok(dir) = crate::config::resolved_app_data_dir(app) {
+        let _ = STORE_PATH.set(dir.join(POLICIES_FILENAME));
+    }
+}
+
+/// Remembers `policy` for `pair`. `Stop` forgets the pair instead.
+pub fn set_policy(pair: &DirPair, policy: ConflictResolution) -> Result<(), String> {
+    let pair = normalize_pair(pair);
+    let now_ms = chrono::Utc::now().timestamp_millis();
+    update_store(|store| apply_set(store, &pair, policy, now_ms))
+}
+
+/// The remembered policy for `pair`, if any.
+pub fn policy_for(pair: &DirPair) -> Option<ConflictResolution> {
+    let pair = normalize_pair(pair);
+    with_store(|store| store.policies.iter().find(|p| p.pair == pair).map(|p| p.policy))
+}
+
+/// All remembered policies, most recently set first.
+pub fn list_policies() -> Vec<DirPairPolicy> {
+    with_store(|store| store.policies.clone())
+}
+
+/// Forgets the policy for `pair`, or every policy when `pair` is `None`. Returns how many went.
+pub fn clear_policies(pair: Option<&DirPair>) -> Result<usize, String> {
+    let pair = pair.map(normalize_pair);
+    update_store(|store| {
+        let before = store.policies.len();
+        match &pair {
+            Some(pair) => store.policies.retain(|p| p.pair != *pair),
+            None => store.policies.clear(),
+        }
+        before - store.policies.len()
+    })
+}
+
+/// The folder every source lives in, or `None` when they're spread over several (no single pair).
+pub fn common_source_dir(sources: &[PathBuf]) -> Option<PathBuf> {
+    let mut parents = sources.iter().map(|s| s.parent());
+    let first = parents.next()??;
+    parents.all(|p| p == Some(first)).then(|| first.to_path_buf())
+}
+
+/// Swaps a prompting (`Stop`) policy for the one remembered for this operation's folder pair.
+/// Explicit policies pass through untouched.
+pub fn apply_remembered_policy(
+    resolution: &mut ConflictResolution,
+    source_volume_id: &str,
+    sources: &[PathBuf],
+    destination_volume_id: &str,
+    destination: &Path,
+) {
+    if *resolution != ConflictResolution::Stop {
+        return;
+    }
+    let Some(source_dir) = common_source_dir(sources) else {
+        return;
+    };
+    let pair = DirPair {
+        source_volume_id: source_volume_id.to_string(),
+        source_dir: source_dir.to_string_lossy().into_owned(),
+        destination_volume_id: destination_volume_id.to_string(),
+        destination_dir: destination.to_string_lossy().into_owned(),
+    };
+    if let Some(policy) = policy_for(&pair) {
+        log::debug!(
+            target: "conflict_policies",
+            "Using remembered conflict policy {policy:?} for {} -> {}",
+            pair.source_dir,
+            pair.destination_dir
+        );
+        *resolution = policy;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pair(src: &str, dst: &str) -> DirPair {
+        DirPair {
+            source_volume_id: "root".to_string(),
+            source_dir: src.to_string(),
+            destination_volume_id: "root".to_string(),
+            destination_dir: dst.to_string(),
+        }
+    }
+
+    #[test]
+    fn apply_set_replaces_moves_to_front_and_stop_forgets() {
+        let mut store = PoliciesStore::default();
+        apply_set(&mut store, &pair("/a", "/b"), ConflictResolution::Skip, 1);
+        apply_set(&mut store, &pair("/c", "/d"), ConflictResolution::Rename, 2);
+        apply_set(&mut store, &pair("/a", "/b"), ConflictResolution::Overwrite, 3);
+
+        assert_eq!(store.policies.len(), 2);
+        assert_eq!(store.policies[0].pair, pair("/a", "/b"));
+        assert_eq!(store.policies[0].policy, ConflictResolution::Overwrite);
+        assert_eq!(store.policies[0].updated_at, 3);
+
+        apply_set(&mut store, &pair("/a", "/b"), ConflictResolution::Stop, 4);
+        assert_eq!(store.policies.len(), 1);
+        assert_eq!(store.policies[0].pair, pair("/c", "/d"));
+    }
+
+    #[test]
+    fn pairs_are_directional_and_ignore_trailing_slashes() {
+        assert_eq!(normalize_pair(&pair("/a/", "/")), pair("/a", "/"));
+        assert_ne!(pair("/a", "/b"), pair("/b", "/a"));
+    }
+
+    #[test]
+    fn common_source_dir_needs_one_shared_parent() {
+        let same = [PathBuf::from("/w/a.txt"), PathBuf::from("/w/b.txt")];
+        assert_eq!(common_source_dir(&same), Some(PathBuf::from("/w")));
+        let spread = [PathBuf::from("/w/a.txt"), PathBuf::from("/x/b.txt")];
+        assert_eq!(common_source_dir(&spread), None);
+        assert_eq!(common_source_dir(&[]), None);
+    }
+
+    #[test]
+    fn save_then_load_round_trip_and_quarantine() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join(POLICIES_FILENAME);
+        let mut store = PoliciesStore {
+            schema_version: CURRENT_SCHEMA_VERSION,
+            ..PoliciesStore::default()
+        };
+        apply_set(
+            &mut store,
+            &pair("/w", "/Volumes/Backup/w"),
+            ConflictResolution::OverwriteOlder,
+            42,
+        );
+        write_store_to_path(&path, &store).expect("write");
+        assert_eq!(read_store_from_path(&path), store);
+
+        fs::write(&path, "{ not json").expect("write garbage");
+        assert_eq!(read_store_from_path(&path), PoliciesStore::default());
+        assert!(path.with_extension("json.broken").exists());
+    }
+}
@@ -0,0 +1,305 @@
+//! What Enter (or a double-click) does on an entry: step into it, open it in the viewer, or
+//! hand it to an app. The single policy the frontend asks through `resolve_default_action`
+//! instead of branching on entry types itself.
+//!
+//! **Inputs.** The entry's type (folder or file, and by extension whether it's an archive,
+//! an Office/Java package, or a macOS bundle) plus three settings the frontend pushes via
+//! `set_default_action_settings` and `lib.rs` applies at startup from `settings.json`:
+//! - `behavior.archiveEnterBehavior`: per-format Browse / Open / Ask for `zip` and `bundle`
+//!   (the same JSON object `archive-enter-policy.ts` reads).
+//! - `behavior.fileEnterAction`: `open` (the default app) or `viewer` (the built-in viewer)
+//!   for ordinary files.
+//! - `behavior.openWithApps`: a JSON object of lowercased extension → app bundle id that
+//!   overrides the default app per extension.
+//!
+//! Settings are parsed leniently, like the frontend's parser: a malformed value falls back
+//! to the defaults for that setting, so it can never wedge the Enter key.
+//!
+//! **Inside archives.** An entry inside an archive (`…/foo.zip/readme.txt`) has no file on
+//! disk for an app to open, so files there resolve to the viewer, and nested archives browse.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::{LazyLock, RwLock};
+
+use serde::{Deserialize, Serialize};
+
+use crate::file_system::volume::backends::archive::{archive_boundary_candidate, has_supported_archive_extension};
+use crate::ignore_poison::RwLockIgnorePoison;
+
+/// What the frontend should do with the entry.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(tag = "kind", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum DefaultAction {
+    /// Step into the folder (or bundle).
+    Navigate,
+    /// Step into the archive as a read-only folder.
+    BrowseArchive,
+    /// Let the user pick Browse or Open (the archive/bundle popup).
+    Ask,
+    /// Open the file in the built-in viewer.
+    OpenInViewer,
+    /// Hand the entry to its default app.
+    OpenWithDefaultApp,
+    /// Open the entry with the app the user chose for its extension.
+    OpenWithApp { bundle_id: String, app_path: String },
+}
+
+/// Browse / Open / Ask, per `behavior.archiveEnterBehavior` format.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum EnterChoice {
+    Browse,
+    Open,
+    #[default]
+    Ask,
+}
+
+/// `behavior.fileEnterAction`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum FileEnterAction {
+    #[default]
+    Open,
+    Viewer,
+}
+
+/// The parsed settings the policy reads.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DefaultActionSettings {
+    pub zip: EnterChoice,
+    pub bundle: EnterChoice,
+    pub file_action: FileEnterAction,
+    /// Lowercased extension → app bundle id.
+    pub open_with: BTreeMap<String, String>,
+}
+
+/// Zip-based packages users mean as documents, not folders. Mirrors `OOXML_EXTENSIONS` in
+/// `archive-enter-policy.ts`; always opened, whatever the `zip` choice.
+const PACKAGE_EXTENSIONS: &[&str] = &["docx", "xlsx", "pptx", "jar", "apk"];
+
+/// macOS bundle directory extensions. Mirrors `BUNDLE_EXTENSIONS` in `archive-enter-policy.ts`.
+const BUNDLE_EXTENSIONS: &[&str] = &["app", "bundle", "framework"];
+
+static SETTINGS: LazyLock<RwLock<DefaultActionSettings>> =
+    LazyLock::new(|| RwLock::new(DefaultActionSettings::default()));
+
+impl DefaultActionSettings {
+    /// Parses the three raw setting values as stored in `settings.json`. `None` or a
+    /// malformed value means that setting's default.
+    pub fn from_setting_values(
+        archive_enter_behavior: Option<&str>,
+        file_enter_action: Option<&str>,
+        open_with_apps: Option<&str>,
+    ) -> Self {
+        // Field by field, so one bad value doesn't discard the other.
+        let archive: serde_json::Map<String, serde_json::Value> = archive_enter_behavior
+            .and_then(|json| serde_json::from_str(json).ok())
+            .unwrap_or_default();
+        let choice = |key: &str| {
+            archive
+                .get(key)
+                .and_then(|value| serde_json::from_value::<EnterChoice>(value.clone()).ok())
+        };
+        let open_with: BTreeMap<String, String> = open_with_apps
+            .and_then(|json| serde_json::from_str::<BTreeMap<String, serde_json::Value>>(json).ok())
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|(ext, app)| {
+                let app = app.as_str()?.trim();
+                let ext = ext.trim().trim_start_matches('.').to_lowercase();
+                (!ext.is_empty() && !app.is_empty()).then(|| (ext, app.to_string()))
+            })
+            .collect();
+        Self {
+            zip: choice("zip").unwrap_or_default(),
+            bundle: choice("bundle").unwrap_or_default(),
+            file_action: file_enter_action
+                .and_then(|value| serde_json::from_value(serde_json::Value::String(value.to_string())).ok())
+                .unwrap_or_default(),
+            open_with,
+        }
+    }
+}
+
+/// Replaces the settings the policy reads.
+pub fn set_settings(settings: DefaultActionSettings) {
+    *SETTINGS.write_ignore_poison() = settings;
+}
+
+fn extension_of(path: &Path) -> Option<String> {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .and_then(crate::file_system::extension::extension_of)
+        .map(str::to_lowercase)
+}
+
+fn is_inside_archive(path: &Path) -> bool {
+    archive_boundary_candidate(path).is_some_and(|(_, inner)| !inner.as_os_str().is_empty())
+}
+
+fn apply_choice(choice: EnterChoice, browse: DefaultAction) -> DefaultAction {
+    match choice {
+        EnterChoice::Browse => browse,
+        EnterChoice::Open => DefaultAction::OpenWithDefaultApp,
+        EnterChoice::Ask => DefaultAction::Ask,
+    }
+}
+
+/// Pure policy: the action for `path` given whether it's a folder and the settings.
+/// `app_path_for` resolves a bundle id to an installed app; an app that's gone falls back to
+/// the default app.
+pub fn resolve(
+    path: &Path,
+    is_directory: bool,
+    settings: &DefaultActionSettings,
+    app_path_for: impl Fn(&str) -> Option<String>,
+) -> DefaultAction {
+    let extension = extension_of(path);
+    let has_extension_in = |list: &[&str]| extension.as_deref().is_some_and(|ext| list.contains(&ext));
+    let inside_archive = is_inside_archive(path);
+
+    if is_directory {
+        if !inside_archive && has_extension_in(BUNDLE_EXTENSIONS) {
+            return apply_choice(settings.bundle, DefaultAction::Navigate);
+        }
+        return DefaultAction::Navigate;
+    }
+    let is_archive = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .is_some_and(has_supported_archive_extension);
+    if inside_archive {
+        // A nested archive browses straight in, like the pane already does there.
+        return if is_archive {
+            DefaultAction::BrowseArchive
+        } else {
+            DefaultAction::OpenInViewer
+        };
+    }
+    if is_archive {
+        return apply_choice(settings.zip, DefaultAction::BrowseArchive);
+    }
+    if has_extension_in(PACKAGE_EXTENSIONS) {
+        return DefaultAction::OpenWithDefaultApp;
+    }
+    if let Some(bundle_id) = extension.as_ref().and_then(|ext| settings.open_with.get(ext)) {
+        match app_path_for(bundle_id) {
+            Some(app_path) => {
+                return DefaultAction::OpenWithApp {
+                    bundle_id: bundle_id.clone(),
+                    app_path,
+                };
+            }
+            None => log::debug!(target: "default_action", "Per-extension app `{bundle_id}` isn't installed"),
+        }
+    }
+    match settings.file_action {
+        FileEnterAction::Open => DefaultAction::OpenWithDefaultApp,
+        FileEnterAction::Viewer => DefaultAction::OpenInViewer,
+    }
+}
+
+/// [`resolve`] against the current settings and the installed apps.
+pub fn resolve_default_action(path: &Path, is_directory: bool) -> DefaultAction {
+    let settings = SETTINGS.read_ignore_poison().clone();
+    #[cfg(target_os = "macos")]
+    let app_path_for = |bundle_id: &str| {
+        crate::file_system::open_with::app_path_for_bundle_id(bundle_id).map(|p| p.to_string_lossy().to_string())
+    };
+    // Per-extension apps are bundle ids, which only LaunchServices resolves.
+    #[cfg(not(target_os = "macos"))]
+    let app_path_for = |_: &str| None;
+    resolve(path, is_directory, &settings, app_path_for)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resolve_with(path: &str, is_directory: bool, settings: &DefaultActionSettings) -> DefaultAction {
+        resolve(Path::new(path), is_directory, settings, |bundle_id| {
+            (bundle_id == "com.example.editor").then(|| "/Applications/Editor.app".to_string())
+        })
+    }
+
+    #[test]
+    fn defaults_navigate_folders_ask_for_archives_and_open_files() {
+        let settings = DefaultActionSettings::default();
+        assert_eq!(resolve_with("/a/docs", true, &settings), DefaultAction::Navigate);
+        assert_eq!(resolve_with("/a/Cmdr.app", true, &settings), DefaultAction::Ask);
+        assert_eq!(resolve_with("/a/b.zip", false, &settings), DefaultAction::Ask);
+        assert_eq!(
+            resolve_with("/a/report.docx", false, &settings),
+            DefaultAction::OpenWithDefaultApp
+        );
+        assert_eq!(
+            resolve_with("/a/notes.txt", false, &settings),
+            DefaultAction::OpenWithDefaultApp
+        );
+    }
+
+    #[test]
+    fn entries_inside_an_archive_navigate_or_view() {
+        let settings = DefaultActionSettings::default();
+        assert_eq!(resolve_with("/a/b.zip/inner", true, &settings), DefaultAction::Navigate);
+        assert_eq!(
+            resolve_with("/a/b.zip/inner/readme.txt", false, &settings),
+            DefaultAction::OpenInViewer
+        );
+        assert_eq!(
+            resolve_with("/a/b.zip/Tool.app", true, &settings),
+            DefaultAction::Navigate
+        );
+        assert_eq!(
+            resolve_with("/a/b.zip/nested.zip", false, &settings),
+            DefaultAction::BrowseArchive
+        );
+    }
+
+    #[test]
+    fn settings_pick_browse_viewer_and_per_extension_apps() {
+        let settings = DefaultActionSettings::from_setting_values(
+            Some(r#"{"zip":"browse","bundle":"open"}"#),
+            Some("viewer"),
+            Some(r#"{".MD":"com.example.editor","log":"com.example.gone"}"#),
+        );
+        assert_eq!(resolve_with("/a/b.zip", false, &settings), DefaultAction::BrowseArchive);
+        assert_eq!(
+            resolve_with("/a/Cmdr.app", true, &settings),
+            DefaultAction::OpenWithDefaultApp
+        );
+        assert_eq!(
+            resolve_with("/a/notes.txt", false, &settings),
+            DefaultAction::OpenInViewer
+        );
+        assert_eq!(
+            resolve_with("/a/README.md", false, &settings),
+            DefaultAction::OpenWithApp {
+                bundle_id: "com.example.editor".into(),
+                app_path: "/Applications/Editor.app".into(),
+            }
+        );
+        // The chosen app isn't installed: fall back to the file action.
+        assert_eq!(
+            resolve_with("/a/app.log", false, &settings),
+            DefaultAction::OpenInViewer
+        );
+    }
+
+    #[test]
+    fn malformed_settings_fall_back_to_defaults() {
+        let settings = DefaultActionSettings::from_setting_values(
+            Some(r#"{"zip":"explode","bundle":"browse"}"#),
+            Some("nope"),
+            Some("["),
+        );
+        assert_eq!(
+            settings,
+            DefaultActionSettings {
+                bundle: EnterChoice::Browse,
+                ..DefaultActionSettings::default()
+            }
+        );
+    }
+}
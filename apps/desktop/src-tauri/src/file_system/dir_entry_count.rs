@@ -0,0 +1,81 @@
+//! Cheap "how many things are in this folder" counts for the tree view's folder badges.
+//!
+//! Answered from the drive index when it fully covers the folder (one `COUNT` over the
+//! `parent_id` index, see `indexing::child_counts`), otherwise by a bare `read_dir` that reads
+//! only the dirent type, never a full stat. Symlinks count as files either way, even when they
+//! point at a folder, so both sources agree.
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+/// A directory's immediate children, split by kind.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DirEntryCount {
+    /// Everything that isn't a real folder: files, symlinks, sockets, and so on.
+    pub files: u64,
+    pub directories: u64,
+    /// `true` when the drive index answered, `false` for a `read_dir` count.
+    pub from_index: bool,
+}
+
+/// Counts `path`'s immediate children, skipping dotfiles unless `include_hidden`. Blocking:
+/// does SQL or disk I/O.
+pub fn get_dir_entry_count(path: &Path, include_hidden: bool) -> Result<DirEntryCount, String> {
+    let indexed = crate::indexing::child_counts(&path.to_string_lossy(), include_hidden).unwrap_or_else(|e| {
+        log::debug!(target: "dir_entry_count", "Index lookup failed for {path:?}, reading instead: {e}");
+        None
+    });
+    if let Some((files, directories)) = indexed {
+        return Ok(DirEntryCount {
+            files,
+            directories,
+            from_index: true,
+        });
+    }
+    read_dir_count(path, include_hidden)
+}
+
+fn read_dir_count(path: &Path, include_hidden: bool) -> Result<DirEntryCount, String> {
+    let entries = std::fs::read_dir(path).map_err(|e| format!("Couldn't read {}: {e}", path.display()))?;
+    let mut count = DirEntryCount::default();
+    for entry in entries.flatten() {
+        if !include_hidden && entry.file_name().to_string_lossy().starts_with('.') {
+            continue;
+        }
+        // `file_type` comes from the dirent on macOS and Linux, so this stays stat-free.
+        match entry.file_type() {
+            Ok(kind) if kind.is_dir() => count.directories += 1,
+            _ => count.files += 1,
+        }
+    }
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_dir_count_splits_kinds_and_honors_hidden() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        std::fs::create_dir(dir.path().join("sub")).expect("mkdir");
+        std::fs::create_dir(dir.path().join(".git")).expect("mkdir");
+        std::fs::write(dir.path().join("sub/deep.txt"), b"x").expect("write");
+        std::fs::write(dir.path().join("a.txt"), b"x").expect("write");
+        std::fs::write(dir.path().join(".env"), b"x").expect("write");
+        std::os::unix::fs::symlink(dir.path().join("sub"), dir.path().join("link")).expect("symlink");
+
+        let all = read_dir_count(dir.path(), true).expect("count");
+        assert_eq!((all.files, all.directories, all.from_index), (3, 2, false));
+        let visible = read_dir_count(dir.path(), false).expect("count");
+        assert_eq!((visible.files, visible.directories), (2, 1));
+    }
+
+    #[test]
+    fn read_dir_count_errors_on_a_missing_folder() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        assert!(read_dir_count(&dir.path().join("nope"), true).is_err());
+    }
+}
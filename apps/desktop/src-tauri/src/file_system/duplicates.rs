@@ -0,0 +1,412 @@
+//! Duplicate-file detection ("find duplicates") for disk cleanup.
+//!
+//! Two passes, cheapest first. Files are grouped by size, and only sizes shared by two or
+//! more files survive; then each surviving group is confirmed by hashing contents with BLAKE3,
+//! so a tree of mostly-unique sizes reads almost nothing. Files under
+//! [`DuplicateOptions::min_size`] never enter either pass.
+//!
+//! The size pass comes from the drive index when the volume is local and its index fully
+//! covers the folder (one SQL query, see `indexing::files_sharing_a_size`); otherwise it walks
+//! the tree through the [`Volume`] trait, so MTP and SMB folders work the same way. Hashing
+//! always streams through the volume, chunk by chunk, so a multi-GB file never lands in memory.
+//!
+//! Confirmed groups stream through `on_group` as soon as each size group is hashed, largest
+//! files first (the biggest savings surface first). Hardlinks to the same inode are one file,
+//! not duplicates: deleting one frees nothing.
+//!
+//! Hashes of local files are cached for the session, keyed by path, size, and mtime, so a
+//! re-run (after deleting some of the copies, say) only reads files that changed. The cache is
+//! in memory on purpose: a `file_hashes` table in the drive index would need a schema bump,
+//! and the index has no migrations, so every user would pay a full rescan for it.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{LazyLock, Mutex};
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+
+use crate::cancel_registry::CancelRegistry;
+use crate::file_system::volume::{Volume, VolumeError};
+use crate::ignore_poison::IgnorePoison;
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct DuplicateOptions {
+    /// Files smaller than this (bytes) are ignored. Tiny files are rarely worth cleaning up
+    /// and are the most likely to be identical by accident (empty files, one-line configs).
+    pub min_size: u64,
+}
+
+impl Default for DuplicateOptions {
+    fn default() -> Self {
+        Self { min_size: 1024 }
+    }
+}
+
+/// Files with identical contents.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicateGroup {
+    /// Size of each file in bytes.
+    pub size: u64,
+    /// BLAKE3 hash of the contents, hex.
+    pub hash: String,
+    /// Two or more paths on the scanned volume, sorted.
+    pub paths: Vec<String>,
+}
+
+#[derive(Debug)]
+pub enum DuplicateError {
+    Cancelled,
+    Volume(VolumeError),
+}
+
+impl std::fmt::Display for DuplicateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Cancelled => write!(f, "Duplicate search cancelled"),
+            Self::Volume(error) => write!(f, "Couldn't read the folder: {error}"),
+        }
+    }
+}
+
+/// Most hashes [`HASH_CACHE`] holds. Past it the cache starts over; at ~200 bytes an entry
+/// that's about 10 MB.
+const MAX_CACHED_HASHES: usize = 50_000;
+
+/// What a cached hash is valid for: the same file with the same size and (nanosecond) mtime.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct HashKey {
+    path: PathBuf,
+    size: u64,
+    modified: SystemTime,
+}
+
+/// BLAKE3 hashes of local files, keyed by their on-disk path.
+static HASH_CACHE: LazyLock<Mutex<HashMap<HashKey, String>>> = LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Cancel flags of in-flight duplicate searches.
+pub static ACTIVE_SEARCHES: CancelRegistry = CancelRegistry::new();
+
+/// A file that made it past the size filter.
+struct Candidate {
+    path: PathBuf,
+    size: u64,
+    inode: Option<u64>,
+}
+
+fn check_cancelled(cancelled: &AtomicBool) -> Result<(), DuplicateError> {
+    if cancelled.load(Ordering::Relaxed) {
+        Err(DuplicateError::Cancelled)
+    } else {
+        Ok(())
+    }
+}
+
+/// Finds groups of identical files under `root` on `volume`, calling `on_group` for each one
+/// as it's confirmed, and returns them all once the search completes.
+pub async fn find_duplicates(
+    volume: &dyn Volume,
+    root: &Path,
+    options: &DuplicateOptions,
+    cancelled: &AtomicBool,
+    on_group: &(dyn Fn(&DuplicateGroup) + Sync),
+) -> Result<Vec<DuplicateGroup>, DuplicateError> {
+    let candidates = match candidates_from_index(volume, root, options.min_size) {
+        Some(candidates) => candidates,
+        None => walk_candidates(volume, root, options.min_size, cancelled).await?,
+    };
+
+    let mut groups = Vec::new();
+    for same_size in group_by_size(candidates) {
+        check_cancelled(cancelled)?;
+        for group in confirm_by_hash(volume, same_size, cancelled).await? {
+            on_group(&group);
+            groups.push(group);
+        }
+    }
+    Ok(groups)
+}
+
+/// The size pass from the drive index, for a local volume whose index fully covers `root`.
+/// `None` means "walk instead".
+fn candidates_from_index(volume: &dyn Volume, root: &Path, min_size: u64) -> Option<Vec<Candidate>> {
+    let local_root = on_disk_path(volume, root)?;
+    let rows = crate::indexing::files_sharing_a_size(&local_root.to_string_lossy(), min_size).unwrap_or_else(|e| {
+        log::debug!(target: "duplicates", "Index lookup failed for {local_root:?}, walking instead: {e}");
+        None
+    })?;
+    Some(
+        rows.into_iter()
+            .map(|(relative, size, inode)| Candidate {
+                path: root.join(relative),
+                size,
+                inode,
+            })
+            .collect(),
+    )
+}
+
+/// Where a volume path lives on disk, for local volumes. Same mapping `LocalPosixVolume`
+/// applies: a volume path may already be absolute on disk, or relative to the volume root.
+fn on_disk_path(volume: &dyn Volume, path: &Path) -> Option<PathBuf> {
+    let base = volume.local_path()?;
+    Some(if path.starts_with(&base) {
+        path.to_path_buf()
+    } else {
+        base.join(path.strip_prefix("/").unwrap_or(path))
+    })
+}
+
+/// The size pass by walking `root` through the volume. Symlinks are skipped, and so are
+/// subfolders that can't be listed; only an unreadable `root` fails the search.
+async fn walk_candidates(
+    volume: &dyn Volume,
+    root: &Path,
+    min_size: u64,
+    cancelled: &AtomicBool,
+) -> Result<Vec<Candidate>, DuplicateError> {
+    let mut candidates = Vec::new();
+    let mut pending = vec![root.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        check_cancelled(cancelled)?;
+        let entries = match volume.list_directory(&dir, None).await {
+            Ok(entries) => entries,
+            Err(error) if dir == root => return Err(DuplicateError::Volume(error)),
+            Err(error) => {
+                log::debug!(target: "duplicates", "Skipping unreadable folder {dir:?}: {error}");
+                continue;
+            }
+        };
+        for entry in entries {
+            if entry.is_symlink {
+                continue;
+            }
+            let path = dir.join(&entry.name);
+            if entry.is_directory {
+                pending.push(path);
+            } else if let Some(size) = entry.size.filter(|&size| size >= min_size) {
+                candidates.push(Candidate {
+                    path,
+                    size,
+                    inode: entry.inode,
+                });
+            }
+        }
+    }
+    Ok(candidates)
+}
+
+/// Groups candidates by size, dropping hardlinks to an already-seen inode and sizes held by a
+/// single file. Largest size first.
+fn group_by_size(candidates: Vec<Candidate>) -> Vec<Vec<Candidate>> {
+    let mut seen_inodes = HashSet::new();
+    let mut by_size: HashMap<u64, Vec<Candidate>> = HashMap::new();
+    for candidate in candidates {
+        if let Some(inode) = candidate.inode
+            && !seen_inodes.insert(inode)
+        {
+            continue;
+        }
+        by_size.entry(candidate.size).or_default().push(candidate);
+    }
+    let mut groups: Vec<Vec<Candidate>> = by_size.into_values().filter(|group| group.len() > 1).collect();
+    groups.sort_by_key(|group| std::cmp::Reverse(group[0].size));
+    groups
+}
+
+/// Hashes every file of one size group and returns the sets that share a hash. A file that
+/// can't be read (vanished, permission denied) just drops out of the group.
+async fn confirm_by_hash(
+    volume: &dyn Volume,
+    same_size: Vec<Candidate>,
+    cancelled: &AtomicBool,
+) -> Result<Vec<DuplicateGroup>, DuplicateError> {
+    let size = same_size[0].size;
+    let mut by_hash: HashMap<String, Vec<String>> = HashMap::new();
+    for candidate in same_size {
+        match cached_hash(volume, &candidate.path, cancelled).await {
+            Ok(hash) => by_hash
+                .entry(hash)
+                .or_default()
+                .push(candidate.path.to_string_lossy().into_owned()),
+            Err(DuplicateError::Cancelled) => return Err(DuplicateError::Cancelled),
+            Err(error) => log::debug!(target: "duplicates", "Skipping {:?}: {error}", candidate.path),
+        }
+    }
+    let mut groups: Vec<DuplicateGroup> = by_hash
+        .into_iter()
+        .filter(|(_, paths)| paths.len() > 1)
+        .map(|(hash, mut paths)| {
+            paths.sort();
+            DuplicateGroup { size, hash, paths }
+        })
+        .collect();
+    groups.sort_by(|a, b| a.paths.cmp(&b.paths));
+    Ok(groups)
+}
+
+/// [`hash_file`] through [`HASH_CACHE`]. The file is stat'ed fresh rather than trusting the
+/// index's mtime, since a stale row would serve an old hash. Files on non-local volumes (or
+/// that can't be stat'ed) are always hashed and never cached.
+async fn cached_hash(volume: &dyn Volume, path: &Path, cancelled: &AtomicBool) -> Result<String, DuplicateError> {
+    let key = on_disk_path(volume, path).and_then(|disk_path| {
+        let metadata = std::fs::metadata(&disk_path).ok()?;
+        Some(HashKey {
+            path: disk_path,
+            size: metadata.len(),
+            modified: metadata.modified().ok()?,
+        })
+    });
+    if let Some(key) = &key
+        && let Some(hash) = HASH_CACHE.lock_ignore_poison().get(key)
+    {
+        return Ok(hash.clone());
+    }
+    let hash = hash_file(volume, path, cancelled).await?;
+    if let Some(key) = key {
+        let mut cache = HASH_CACHE.lock_ignore_poison();
+        if cache.len() >= MAX_CACHED_HASHES {
+            cache.clear();
+        }
+        cache.insert(key, hash.clone());
+    }
+    Ok(hash)
+}
+
+async fn hash_file(volume: &dyn Volume, path: &Path, cancelled: &AtomicBool) -> Result<String, DuplicateError> {
+    let mut stream = volume.open_read_stream(path).await.map_err(DuplicateError::Volume)?;
+    let mut hasher = blake3::Hasher::new();
+    while let Some(chunk) = stream.next_chunk().await {
+        if cancelled.load(Ordering::Relaxed) {
+            stream.cancel_and_release().await;
+            return Err(DuplicateError::Cancelled);
+        }
+        hasher.update(&chunk.map_err(DuplicateError::Volume)?);
+    }
+    Ok(hasher.finalize().to_hex().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file_system::volume::InMemoryVolume;
+
+    async fn volume_with(files: &[(&str, &[u8])], dirs: &[&str]) -> InMemoryVolume {
+        let volume = InMemoryVolume::new("test");
+        for dir in dirs {
+            volume.create_directory(Path::new(dir)).await.expect("create dir");
+        }
+        for (path, content) in files {
+            volume.create_file(Path::new(path), content).await.expect("create file");
+        }
+        volume
+    }
+
+    async fn run(volume: &InMemoryVolume, min_size: u64) -> (Vec<DuplicateGroup>, usize) {
+        let cancelled = AtomicBool::new(false);
+        let streamed = Mutex::new(0);
+        let groups = find_duplicates(
+            volume,
+            Path::new("/"),
+            &DuplicateOptions { min_size },
+            &cancelled,
+            &|_| *streamed.lock_ignore_poison() += 1,
+        )
+        .await
+        .expect("find duplicates");
+        (groups, streamed.into_inner().unwrap_or_else(|p| p.into_inner()))
+    }
+
+    #[tokio::test]
+    async fn same_size_files_are_confirmed_by_contents_across_folders() {
+        let volume = volume_with(
+            &[
+                ("/a.txt", b"hello"),
+                ("/sub/b.txt", b"hello"),
+                ("/c.txt", b"world"),
+                ("/d.txt", b"unique size"),
+            ],
+            &["/sub"],
+        )
+        .await;
+
+        let (groups, streamed) = run(&volume, 1).await;
+
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].size, 5);
+        assert_eq!(groups[0].paths, vec!["/a.txt".to_string(), "/sub/b.txt".to_string()]);
+        assert_eq!(groups[0].hash, blake3::hash(b"hello").to_hex().to_string());
+        assert_eq!(streamed, 1);
+    }
+
+    #[tokio::test]
+    async fn files_under_the_minimum_size_are_ignored() {
+        let volume = volume_with(
+            &[("/a", b"xy"), ("/b", b"xy"), ("/c", b"longer"), ("/d", b"longer")],
+            &[],
+        )
+        .await;
+
+        let (groups, _) = run(&volume, 3).await;
+
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].paths, vec!["/c".to_string(), "/d".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn local_hashes_are_cached_until_the_mtime_changes() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let volume = crate::file_system::volume::LocalPosixVolume::new("test", dir.path());
+        let file = dir.path().join("a.bin");
+        std::fs::write(&file, b"first").expect("write");
+        let cancelled = AtomicBool::new(false);
+        let first = cached_hash(&volume, Path::new("/a.bin"), &cancelled)
+            .await
+            .expect("hash");
+        let modified = std::fs::metadata(&file).and_then(|m| m.modified()).expect("mtime");
+
+        // Same size, same mtime: the cached hash comes back without reading the new contents.
+        std::fs::write(&file, b"other").expect("rewrite");
+        let handle = std::fs::File::options().write(true).open(&file).expect("open");
+        handle.set_modified(modified).expect("restore mtime");
+        assert_eq!(
+            cached_hash(&volume, Path::new("/a.bin"), &cancelled)
+                .await
+                .expect("hash"),
+            first
+        );
+
+        handle
+            .set_modified(modified + std::time::Duration::from_secs(1))
+            .expect("touch");
+        let fresh = cached_hash(&volume, Path::new("/a.bin"), &cancelled)
+            .await
+            .expect("hash");
+        assert_eq!(fresh, blake3::hash(b"other").to_hex().to_string());
+    }
+
+    #[test]
+    fn hardlinks_collapse_to_one_file_and_groups_come_largest_first() {
+        let candidate = |path: &str, size, inode| Candidate {
+            path: PathBuf::from(path),
+            size,
+            inode,
+        };
+        let groups = group_by_size(vec![
+            candidate("/small1", 10, Some(1)),
+            candidate("/small2", 10, Some(2)),
+            candidate("/big", 99, Some(3)),
+            candidate("/big-hardlink", 99, Some(3)),
+            candidate("/big-copy", 99, Some(4)),
+            candidate("/linked", 50, Some(5)),
+            candidate("/linked-again", 50, Some(5)),
+        ]);
+
+        let sizes: Vec<u64> = groups.iter().map(|group| group[0].size).collect();
+        assert_eq!(sizes, vec![99, 10]);
+        assert_eq!(groups[0].len(), 2);
+    }
+}
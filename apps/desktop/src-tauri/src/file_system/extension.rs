@@ -0,0 +1,27 @@
+//! The one definition of a file name's extension, shared by the default-action
+//! policy, the extension breakdown, and AI rename suggestions.
+
+/// The extension of `name` (no dot, case preserved): the part after the last dot,
+/// when both it and the part before are non-empty. So `archive.tar.gz` → `gz`,
+/// `.config.json` → `json`, and `.bashrc`, `Makefile`, `odd.` have none.
+pub(crate) fn extension_of(name: &str) -> Option<&str> {
+    match name.rsplit_once('.') {
+        Some((stem, extension)) if !stem.is_empty() && !extension.is_empty() => Some(extension),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extension_splits_on_the_last_dot_and_treats_dotfiles_as_extensionless() {
+        assert_eq!(extension_of("photo.JPG"), Some("JPG"));
+        assert_eq!(extension_of("archive.tar.gz"), Some("gz"));
+        assert_eq!(extension_of(".bashrc"), None);
+        assert_eq!(extension_of(".config.json"), Some("json"));
+        assert_eq!(extension_of("Makefile"), None);
+        assert_eq!(extension_of("odd."), None);
+    }
+}
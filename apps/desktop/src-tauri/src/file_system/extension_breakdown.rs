@@ -0,0 +1,261 @@
+//! "What's in this folder": file count and bytes per extension for a directory's whole subtree.
+//!
+//! Answered from the drive index when it fully covers the subtree (one grouped SQL query, see
+//! `indexing::extension_totals`), otherwise by a bounded disk walk. Extensions are lowercased
+//! and split on the last dot, the same way the listing's extension sort reads them: `.bashrc`,
+//! `Makefile`, and `odd.` have no extension and land in the [`NO_EXTENSION`] bucket.
+//!
+//! Only the [`MAX_EXTENSIONS`] biggest extensions by bytes are listed, largest first; the long
+//! tail is summed into one `other` bucket so a chart stays readable.
+//!
+//! Results are cached briefly per path, so a chart that re-renders (or a user flipping between
+//! pie and bar) doesn't re-walk a large tree.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use walkdir::WalkDir;
+
+use crate::ignore_poison::IgnorePoison;
+
+/// Bucket key for files without an extension. Can't collide with a real one: an extension is
+/// never empty.
+pub const NO_EXTENSION: &str = "";
+
+/// Walk cap for the disk fallback. Past this many entries the breakdown is returned as-is with
+/// `truncated: true`, so a misclicked `/` can't pin a core for minutes.
+const MAX_WALK_ENTRIES: usize = 200_000;
+
+/// Extensions listed by name. The rest are summed into [`ExtensionBreakdown::other`].
+const MAX_EXTENSIONS: usize = 30;
+
+/// How long a computed breakdown is served from the cache.
+const CACHE_TTL: Duration = Duration::from_secs(30);
+
+/// Cached paths beyond this are dropped wholesale. A handful of folders is the realistic
+/// working set; this only keeps a long session from growing the map without bound.
+const MAX_CACHED_PATHS: usize = 32;
+
+/// Totals for one extension.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ExtensionStats {
+    pub count: u64,
+    pub total_bytes: u64,
+}
+
+/// Totals for one named extension in [`ExtensionBreakdown::extensions`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ExtensionEntry {
+    /// Lowercased, no dot. Files without one are under [`NO_EXTENSION`].
+    pub extension: String,
+    #[serde(flatten)]
+    pub stats: ExtensionStats,
+}
+
+/// A directory's per-extension breakdown.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ExtensionBreakdown {
+    /// The [`MAX_EXTENSIONS`] biggest extensions by total bytes, largest first.
+    pub extensions: Vec<ExtensionEntry>,
+    /// Everything past the listed extensions, summed. Zero when nothing was left out.
+    pub other: ExtensionStats,
+    /// `true` when the drive index answered, `false` for a disk walk.
+    pub from_index: bool,
+    /// `true` when the disk walk hit [`MAX_WALK_ENTRIES`]; the totals are then a lower bound.
+    pub truncated: bool,
+}
+
+/// Per-extension totals while a breakdown is being gathered. Keys are lowercased, so `JPG` and
+/// `jpg` merge.
+#[derive(Default)]
+struct Tally(BTreeMap<String, ExtensionStats>);
+
+impl Tally {
+    fn add(&mut self, extension: &str, count: u64, bytes: u64) {
+        let stats = self.0.entry(extension.to_lowercase()).or_default();
+        stats.count += count;
+        stats.total_bytes += bytes;
+    }
+
+    /// The biggest `limit` extensions, size-descending (ties by name), plus the rest summed.
+    fn into_breakdown(self, limit: usize, from_index: bool, truncated: bool) -> ExtensionBreakdown {
+        let mut extensions: Vec<ExtensionEntry> = self
+            .0
+            .into_iter()
+            .map(|(extension, stats)| ExtensionEntry { extension, stats })
+            .collect();
+        extensions.sort_by(|a, b| {
+            b.stats
+                .total_bytes
+                .cmp(&a.stats.total_bytes)
+                .then_with(|| a.extension.cmp(&b.extension))
+        });
+        let mut other = ExtensionStats::default();
+        for rest in extensions.drain(extensions.len().min(limit)..) {
+            other.count += rest.stats.count;
+            other.total_bytes += rest.stats.total_bytes;
+        }
+        ExtensionBreakdown {
+            extensions,
+            other,
+            from_index,
+            truncated,
+        }
+    }
+}
+
+static CACHE: LazyLock<Mutex<HashMap<PathBuf, (Instant, ExtensionBreakdown)>>> = LazyLock::new(Default::default);
+
+/// The extension (no dot, case preserved) `name` is bucketed under. Mirrors the listing's
+/// extension sort and the index query.
+fn bucket_of(name: &str) -> &str {
+    crate::file_system::extension::extension_of(name).unwrap_or(NO_EXTENSION)
+}
+
+/// Walks `root` without following symlinks, stopping after `max_entries` entries. Unreadable
+/// subdirectories are skipped rather than failing the whole breakdown.
+fn walk_breakdown(root: &Path, max_entries: usize) -> (Tally, bool) {
+    let mut tally = Tally::default();
+    let mut truncated = false;
+    for (seen, entry) in WalkDir::new(root)
+        .min_depth(1)
+        .follow_links(false)
+        .into_iter()
+        .enumerate()
+    {
+        if seen >= max_entries {
+            truncated = true;
+            break;
+        }
+        let Ok(entry) = entry else { continue };
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let bytes = entry.metadata().map(|m| m.len()).unwrap_or(0);
+        tally.add(bucket_of(&entry.file_name().to_string_lossy()), 1, bytes);
+    }
+    (tally, truncated)
+}
+
+/// Computes (or serves from cache) the breakdown for `path`. Blocking: does SQL or disk I/O.
+pub fn get_extension_breakdown(path: &Path) -> Result<ExtensionBreakdown, String> {
+    if let Some((at, cached)) = CACHE.lock_ignore_poison().get(path)
+        && at.elapsed() < CACHE_TTL
+    {
+        return Ok(cached.clone());
+    }
+    if !path.is_dir() {
+        return Err(format!("Not a folder: {}", path.display()));
+    }
+
+    let indexed = crate::indexing::extension_totals(&path.to_string_lossy()).unwrap_or_else(|e| {
+        log::debug!(target: "extension_breakdown", "Index lookup failed for {path:?}, walking instead: {e}");
+        None
+    });
+    let breakdown = match indexed {
+        Some(rows) => {
+            let mut tally = Tally::default();
+            for (extension, count, bytes) in rows {
+                tally.add(&extension, count, bytes);
+            }
+            tally.into_breakdown(MAX_EXTENSIONS, true, false)
+        }
+        None => {
+            let (tally, truncated) = walk_breakdown(path, MAX_WALK_ENTRIES);
+            tally.into_breakdown(MAX_EXTENSIONS, false, truncated)
+        }
+    };
+
+    let mut cache = CACHE.lock_ignore_poison();
+    if cache.len() >= MAX_CACHED_PATHS {
+        cache.clear();
+    }
+    cache.insert(path.to_path_buf(), (Instant::now(), breakdown.clone()));
+    Ok(breakdown)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extensionless_names_bucket_under_no_extension() {
+        assert_eq!(bucket_of("photo.JPG"), "JPG");
+        assert_eq!(bucket_of(".bashrc"), NO_EXTENSION);
+        assert_eq!(bucket_of("odd."), NO_EXTENSION);
+    }
+
+    #[test]
+    fn walk_lowercases_merges_and_flags_truncation() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        std::fs::create_dir(dir.path().join("sub")).expect("mkdir");
+        std::fs::write(dir.path().join("a.jpg"), [0u8; 10]).expect("write");
+        std::fs::write(dir.path().join("sub/b.JPG"), [0u8; 5]).expect("write");
+        std::fs::write(dir.path().join("README"), [0u8; 1]).expect("write");
+
+        let (tally, truncated) = walk_breakdown(dir.path(), MAX_WALK_ENTRIES);
+        assert!(!truncated);
+        assert_eq!(
+            tally.0.get("jpg"),
+            Some(&ExtensionStats {
+                count: 2,
+                total_bytes: 15
+            })
+        );
+        assert_eq!(tally.0.get(NO_EXTENSION).map(|s| s.count), Some(1));
+        assert_eq!(tally.0.len(), 2);
+
+        assert!(walk_breakdown(dir.path(), 2).1);
+    }
+
+    #[test]
+    fn breakdown_lists_the_biggest_extensions_first_and_sums_the_rest() {
+        let mut tally = Tally::default();
+        tally.add("txt", 5, 10);
+        tally.add("mp4", 1, 900);
+        tally.add("jpg", 3, 300);
+        tally.add("png", 2, 300);
+        tally.add("md", 4, 7);
+
+        let breakdown = tally.into_breakdown(3, true, false);
+
+        let listed: Vec<(&str, u64)> = breakdown
+            .extensions
+            .iter()
+            .map(|e| (e.extension.as_str(), e.stats.total_bytes))
+            .collect();
+        assert_eq!(listed, vec![("mp4", 900), ("jpg", 300), ("png", 300)]);
+        assert_eq!(
+            breakdown.other,
+            ExtensionStats {
+                count: 9,
+                total_bytes: 17
+            }
+        );
+    }
+
+    #[test]
+    fn breakdown_under_the_limit_has_an_empty_other_bucket() {
+        let mut tally = Tally::default();
+        tally.add("JPG", 1, 4);
+        tally.add("jpg", 1, 6);
+
+        let breakdown = tally.into_breakdown(MAX_EXTENSIONS, false, false);
+
+        assert_eq!(breakdown.extensions.len(), 1);
+        assert_eq!(
+            breakdown.extensions[0].stats,
+            ExtensionStats {
+                count: 2,
+                total_bytes: 10
+            }
+        );
+        assert_eq!(breakdown.other, ExtensionStats::default());
+    }
+}
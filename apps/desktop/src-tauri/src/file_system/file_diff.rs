@@ -0,0 +1,384 @@
+//! Two-file comparison ("are these the same?") for a two-pane selection.
+//!
+//! The identical / first-difference check streams both files in fixed-size chunks, so two
+//! multi-GB disk images never land in memory, and checks the cancel flag between chunks.
+//! On a mismatch it reopens both files at the differing offset to read a small hex window
+//! around it.
+//!
+//! The optional line diff is the one part that reads whole files: it runs only for files
+//! that both look like UTF-8 text and fit under [`MAX_LINE_DIFF_BYTES`], and reports why
+//! it was skipped otherwise.
+//!
+//! The caller picks the operation id; [`ACTIVE_DIFFS`] maps it to the cancel flag.
+
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use serde::{Deserialize, Serialize};
+use similar::{ChangeTag, TextDiff};
+
+use crate::cancel_registry::CancelRegistry;
+
+/// Read size per chunk, per side.
+const CHUNK_SIZE: usize = 1 << 20;
+
+/// Largest file (per side) the line diff loads.
+const MAX_LINE_DIFF_BYTES: u64 = 4 * 1024 * 1024;
+
+/// Unchanged lines kept around each change in a hunk.
+const LINE_DIFF_CONTEXT: usize = 3;
+
+/// Leading bytes checked for a NUL when deciding whether a file is text.
+const BINARY_SNIFF_BYTES: usize = 8 * 1024;
+
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct FileDiffOptions {
+    /// Also compute a line-level diff when both files are text.
+    pub line_diff: bool,
+    /// Bytes shown on each side of the first difference in the hex window.
+    pub context_bytes: u32,
+}
+
+impl Default for FileDiffOptions {
+    fn default() -> Self {
+        Self {
+            line_diff: false,
+            context_bytes: 16,
+        }
+    }
+}
+
+/// Where the two files first differ, with a hex window around it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ByteDifference {
+    /// Offset of the first differing byte. Equal to the shorter file's size when one
+    /// file is a prefix of the other.
+    pub offset: u64,
+    /// Offset of the first byte in both hex windows.
+    pub window_start: u64,
+    /// Space-separated lowercase hex bytes, shorter near a file's end.
+    pub left_hex: String,
+    pub right_hex: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub enum DiffLineKind {
+    Equal,
+    /// Only in the left file.
+    Removed,
+    /// Only in the right file.
+    Added,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DiffLine {
+    pub kind: DiffLineKind,
+    /// 1-based line number in the left file, `None` for an added line.
+    pub left_line: Option<u32>,
+    /// 1-based line number in the right file, `None` for a removed line.
+    pub right_line: Option<u32>,
+    /// The line without its line ending.
+    pub text: String,
+}
+
+/// A run of changes with up to [`LINE_DIFF_CONTEXT`] unchanged lines around it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DiffHunk {
+    pub lines: Vec<DiffLine>,
+}
+
+/// The line diff, or why there isn't one.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase", rename_all_fields = "camelCase", tag = "kind")]
+pub enum LineDiff {
+    Hunks {
+        hunks: Vec<DiffHunk>,
+    },
+    /// At least one file isn't UTF-8 text.
+    Binary,
+    /// At least one file is over the line-diff size cap.
+    TooLarge {
+        max_bytes: u64,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct FileDiffResult {
+    pub identical: bool,
+    pub left_size: u64,
+    pub right_size: u64,
+    /// `None` when the files are identical.
+    pub first_difference: Option<ByteDifference>,
+    /// Set only when the line diff was asked for and the files differ.
+    pub line_diff: Option<LineDiff>,
+}
+
+#[derive(Debug)]
+pub enum FileDiffError {
+    Cancelled,
+    Io { side: &'static str, error: std::io::Error },
+}
+
+impl std::fmt::Display for FileDiffError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Cancelled => write!(f, "File comparison cancelled"),
+            Self::Io { side, error } => write!(f, "Couldn't read the {side} file: {error}"),
+        }
+    }
+}
+
+/// Cancel flags of in-flight file diffs.
+pub static ACTIVE_DIFFS: CancelRegistry = CancelRegistry::new();
+
+/// Compares the files at `left` and `right`. Blocking: call it off the async runtime.
+pub fn diff_files(
+    left: &Path,
+    right: &Path,
+    options: &FileDiffOptions,
+    cancelled: &AtomicBool,
+) -> Result<FileDiffResult, FileDiffError> {
+    let left_io = |error| FileDiffError::Io { side: "left", error };
+    let right_io = |error| FileDiffError::Io { side: "right", error };
+    let mut left_file = File::open(left).map_err(left_io)?;
+    let mut right_file = File::open(right).map_err(right_io)?;
+    let left_size = left_file.metadata().map_err(left_io)?.len();
+    let right_size = right_file.metadata().map_err(right_io)?.len();
+
+    let Some(offset) = first_difference(&mut left_file, &mut right_file, cancelled)? else {
+        return Ok(FileDiffResult {
+            identical: true,
+            left_size,
+            right_size,
+            first_difference: None,
+            line_diff: None,
+        });
+    };
+
+    let context = u64::from(options.context_bytes);
+    let window_start = offset.saturating_sub(context);
+    let window_len = offset - window_start + context;
+    let left_window = read_window(&mut left_file, window_start, window_len).map_err(left_io)?;
+    let right_window = read_window(&mut right_file, window_start, window_len).map_err(right_io)?;
+
+    let line_diff = if options.line_diff {
+        Some(line_diff(left, right, left_size.max(right_size))?)
+    } else {
+        None
+    };
+
+    Ok(FileDiffResult {
+        identical: false,
+        left_size,
+        right_size,
+        first_difference: Some(ByteDifference {
+            offset,
+            window_start,
+            left_hex: hex_spaced(&left_window),
+            right_hex: hex_spaced(&right_window),
+        }),
+        line_diff,
+    })
+}
+
+/// Streams both files and returns the offset of the first differing byte, or `None` when
+/// they're identical. A file that's a prefix of the other differs at its own length.
+fn first_difference(left: &mut File, right: &mut File, cancelled: &AtomicBool) -> Result<Option<u64>, FileDiffError> {
+    let mut left_buf = vec![0u8; CHUNK_SIZE];
+    let mut right_buf = vec![0u8; CHUNK_SIZE];
+    let mut offset: u64 = 0;
+    loop {
+        if cancelled.load(Ordering::Relaxed) {
+            return Err(FileDiffError::Cancelled);
+        }
+        let left_len = read_full(left, &mut left_buf).map_err(|error| FileDiffError::Io { side: "left", error })?;
+        let right_len = read_full(right, &mut right_buf).map_err(|error| FileDiffError::Io { side: "right", error })?;
+        let common = left_len.min(right_len);
+        if let Some(index) = left_buf[..common]
+            .iter()
+            .zip(&right_buf[..common])
+            .position(|(l, r)| l != r)
+        {
+            return Ok(Some(offset + index as u64));
+        }
+        if left_len != right_len {
+            return Ok(Some(offset + common as u64));
+        }
+        if left_len == 0 {
+            return Ok(None);
+        }
+        offset += left_len as u64;
+    }
+}
+
+/// Fills `buf` unless the file ends first, so both sides advance in lockstep even when
+/// `read` returns short counts. Returns the bytes read; `0` means end of file.
+fn read_full(file: &mut File, buf: &mut [u8]) -> std::io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match file.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(error) if error.kind() == std::io::ErrorKind::Interrupted => {}
+            Err(error) => return Err(error),
+        }
+    }
+    Ok(filled)
+}
+
+fn read_window(file: &mut File, start: u64, len: u64) -> std::io::Result<Vec<u8>> {
+    file.seek(SeekFrom::Start(start))?;
+    let mut window = Vec::new();
+    file.take(len).read_to_end(&mut window)?;
+    Ok(window)
+}
+
+fn hex_spaced(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect::<Vec<_>>().join(" ")
+}
+
+fn line_diff(left: &Path, right: &Path, larger_size: u64) -> Result<LineDiff, FileDiffError> {
+    if larger_size > MAX_LINE_DIFF_BYTES {
+        return Ok(LineDiff::TooLarge {
+            max_bytes: MAX_LINE_DIFF_BYTES,
+        });
+    }
+    let left_bytes = std::fs::read(left).map_err(|error| FileDiffError::Io { side: "left", error })?;
+    let right_bytes = std::fs::read(right).map_err(|error| FileDiffError::Io { side: "right", error })?;
+    let (Some(left_text), Some(right_text)) = (as_text(&left_bytes), as_text(&right_bytes)) else {
+        return Ok(LineDiff::Binary);
+    };
+    Ok(LineDiff::Hunks {
+        hunks: line_hunks(left_text, right_text),
+    })
+}
+
+/// The bytes as text, or `None` for a binary file (a NUL near the start, or not UTF-8).
+fn as_text(bytes: &[u8]) -> Option<&str> {
+    if bytes[..bytes.len().min(BINARY_SNIFF_BYTES)].contains(&0) {
+        return None;
+    }
+    std::str::from_utf8(bytes).ok()
+}
+
+fn line_hunks(left: &str, right: &str) -> Vec<DiffHunk> {
+    let diff = TextDiff::from_lines(left, right);
+    diff.grouped_ops(LINE_DIFF_CONTEXT)
+        .iter()
+        .map(|group| DiffHunk {
+            lines: group
+                .iter()
+                .flat_map(|op| diff.iter_changes(op))
+                .map(|change| DiffLine {
+                    kind: match change.tag() {
+                        ChangeTag::Equal => DiffLineKind::Equal,
+                        ChangeTag::Delete => DiffLineKind::Removed,
+                        ChangeTag::Insert => DiffLineKind::Added,
+                    },
+                    left_line: change.old_index().map(line_number),
+                    right_line: change.new_index().map(line_number),
+                    text: change.value().trim_end_matches(['\n', '\r']).to_string(),
+                })
+                .collect(),
+        })
+        .collect()
+}
+
+fn line_number(index: usize) -> u32 {
+    u32::try_from(index + 1).unwrap_or(u32::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn diff(left: &[u8], right: &[u8], options: FileDiffOptions) -> FileDiffResult {
+        let dir = tempfile::tempdir().unwrap();
+        let left_path = dir.path().join("left");
+        let right_path = dir.path().join("right");
+        std::fs::write(&left_path, left).unwrap();
+        std::fs::write(&right_path, right).unwrap();
+        diff_files(&left_path, &right_path, &options, &AtomicBool::new(false)).unwrap()
+    }
+
+    #[test]
+    fn identical_files() {
+        let result = diff(b"same bytes", b"same bytes", FileDiffOptions::default());
+        assert!(result.identical);
+        assert_eq!(result.first_difference, None);
+    }
+
+    #[test]
+    fn first_difference_has_a_hex_window() {
+        let options = FileDiffOptions {
+            context_bytes: 2,
+            ..FileDiffOptions::default()
+        };
+        let result = diff(b"abcdefgh", b"abcdXfgh", options);
+        let difference = result.first_difference.unwrap();
+        assert_eq!(difference.offset, 4);
+        assert_eq!(difference.window_start, 2);
+        assert_eq!(difference.left_hex, "63 64 65 66");
+        assert_eq!(difference.right_hex, "63 64 58 66");
+    }
+
+    #[test]
+    fn difference_past_the_first_chunk() {
+        let left = vec![7u8; CHUNK_SIZE + 10];
+        let mut right = left.clone();
+        right[CHUNK_SIZE + 3] = 8;
+        let result = diff(&left, &right, FileDiffOptions::default());
+        assert_eq!(result.first_difference.unwrap().offset, (CHUNK_SIZE + 3) as u64);
+    }
+
+    #[test]
+    fn prefix_differs_at_the_shorter_length() {
+        let result = diff(b"abc", b"abcdef", FileDiffOptions::default());
+        assert!(!result.identical);
+        assert_eq!(result.first_difference.unwrap().offset, 3);
+        assert_eq!((result.left_size, result.right_size), (3, 6));
+    }
+
+    #[test]
+    fn line_diff_for_text_files() {
+        let options = FileDiffOptions {
+            line_diff: true,
+            ..FileDiffOptions::default()
+        };
+        let result = diff(b"one\ntwo\nthree\n", b"one\n2\nthree\n", options);
+        let Some(LineDiff::Hunks { hunks }) = result.line_diff else {
+            panic!("expected hunks");
+        };
+        let changed: Vec<_> = hunks[0]
+            .lines
+            .iter()
+            .filter(|line| line.kind != DiffLineKind::Equal)
+            .map(|line| (line.kind, line.left_line, line.right_line, line.text.as_str()))
+            .collect();
+        assert_eq!(
+            changed,
+            vec![
+                (DiffLineKind::Removed, Some(2), None, "two"),
+                (DiffLineKind::Added, None, Some(2), "2"),
+            ]
+        );
+    }
+
+    #[test]
+    fn line_diff_skips_binary_files() {
+        let options = FileDiffOptions {
+            line_diff: true,
+            ..FileDiffOptions::default()
+        };
+        let result = diff(b"a\0b", b"a\0c", options);
+        assert_eq!(result.line_diff, Some(LineDiff::Binary));
+    }
+}
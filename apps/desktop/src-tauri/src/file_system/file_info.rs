@@ -0,0 +1,385 @@
+//! Extended, slower-to-read file details for the info panel: kind, image dimensions, media
+//! duration, text line count, and the macOS added/opened dates that listings leave empty.
+//!
+//! Listings stay fast by reading only `stat`; this fills in the rest for one file on request.
+//! On macOS the Spotlight attributes (`MDItemCopyAttribute`) come first, since the index already
+//! holds kind, pixel size, and duration; anything missing is computed from the file itself:
+//! image dimensions from the header, line count by streaming the file. Off macOS only that
+//! minimal computed set is available, with an extension-based kind.
+//!
+//! Results are cached per path and `(mtime, size)`, so reopening the panel on an unchanged file
+//! costs one `stat`. The caller picks the request id, and [`ACTIVE_REQUESTS`] holds its cancel
+//! flag, which the line count checks per chunk.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Read;
+use std::os::unix::fs::MetadataExt;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{LazyLock, Mutex};
+
+use serde::{Deserialize, Serialize};
+
+use crate::cancel_registry::CancelRegistry;
+use crate::file_viewer::content_kind::{CLASSIFY_HEAD_LEN, ViewerContentKind, classify_viewer_content};
+use crate::ignore_poison::IgnorePoison;
+
+/// Read size per chunk while counting lines. A cancel lands within one chunk.
+const CHUNK_SIZE: usize = 1 << 20;
+
+/// Text files larger than this get no line count: counting would take seconds for a number
+/// nobody reads at that size.
+const LINE_COUNT_MAX_BYTES: u64 = 256 * 1024 * 1024;
+
+/// Cached results kept. One entry per file the user has inspected, so this is generous.
+const CACHE_CAPACITY: usize = 2_000;
+
+/// Extended details for one file. Every field but `path` and `kind` is best-effort.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct FileInfo {
+    pub path: String,
+    /// Human-readable kind, like Finder's "Kind" column ("PNG image", "Folder").
+    pub kind: String,
+    /// Unix seconds: when the file was added to its folder. macOS only.
+    pub added_at: Option<u64>,
+    /// Unix seconds: when the file was last opened. macOS: Spotlight's content-access date;
+    /// elsewhere the access time, which `relatime` mounts update at most daily.
+    pub opened_at: Option<u64>,
+    pub image_width: Option<u32>,
+    pub image_height: Option<u32>,
+    /// Audio/video duration. macOS only (from Spotlight).
+    pub duration_seconds: Option<f64>,
+    /// Newline count for text files (a last line without a trailing newline counts too).
+    pub line_count: Option<u64>,
+}
+
+#[derive(Debug)]
+pub enum FileInfoError {
+    Cancelled,
+    Io(std::io::Error),
+}
+
+impl std::fmt::Display for FileInfoError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Cancelled => write!(f, "File info cancelled"),
+            Self::Io(error) => write!(f, "Couldn't read the file: {error}"),
+        }
+    }
+}
+
+impl From<std::io::Error> for FileInfoError {
+    fn from(error: std::io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+/// Cancel flags of in-flight file info lookups.
+pub static ACTIVE_REQUESTS: CancelRegistry = CancelRegistry::new();
+
+/// What a cached result was computed from. Any change means the file changed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Fingerprint {
+    mtime: i64,
+    mtime_nsec: i64,
+    size: u64,
+}
+
+impl Fingerprint {
+    fn of(meta: &std::fs::Metadata) -> Self {
+        Self {
+            mtime: meta.mtime(),
+            mtime_nsec: meta.mtime_nsec(),
+            size: meta.len(),
+        }
+    }
+}
+
+static CACHE: LazyLock<Mutex<HashMap<PathBuf, (Fingerprint, FileInfo)>>> = LazyLock::new(|| Mutex::new(HashMap::new()));
+
+fn cache_insert(path: PathBuf, fingerprint: Fingerprint, info: FileInfo) {
+    let mut cache = CACHE.lock_ignore_poison();
+    if cache.len() >= CACHE_CAPACITY && !cache.contains_key(&path) {
+        // No recency order is kept; dropping any one entry bounds memory just as well.
+        if let Some(evict) = cache.keys().next().cloned() {
+            cache.remove(&evict);
+        }
+    }
+    cache.insert(path, (fingerprint, info));
+}
+
+/// Reads the extended details of `path` (a symlink's own, not its target's). Blocking: call it
+/// off the async runtime. Served from the cache while the file's mtime and size are unchanged.
+pub fn get_file_info(path: &Path, cancelled: &AtomicBool) -> Result<FileInfo, FileInfoError> {
+    let meta = std::fs::symlink_metadata(path)?;
+    let fingerprint = Fingerprint::of(&meta);
+    if let Some((cached_fingerprint, info)) = CACHE.lock_ignore_poison().get(path)
+        && *cached_fingerprint == fingerprint
+    {
+        return Ok(info.clone());
+    }
+
+    let info = compute(path, &meta, cancelled)?;
+    cache_insert(path.to_path_buf(), fingerprint, info.clone());
+    Ok(info)
+}
+
+fn compute(path: &Path, meta: &std::fs::Metadata, cancelled: &AtomicBool) -> Result<FileInfo, FileInfoError> {
+    let mut info = FileInfo {
+        path: path.to_string_lossy().into_owned(),
+        kind: String::new(),
+        added_at: None,
+        opened_at: None,
+        image_width: None,
+        image_height: None,
+        duration_seconds: None,
+        line_count: None,
+    };
+
+    #[cfg(target_os = "macos")]
+    {
+        let macos_meta = super::macos_metadata::get_macos_metadata(path);
+        info.added_at = macos_meta.added_at;
+        info.opened_at = macos_meta.opened_at;
+        let spotlight = spotlight::attributes(path);
+        info.kind = spotlight.kind.unwrap_or_default();
+        info.image_width = spotlight.pixel_width;
+        info.image_height = spotlight.pixel_height;
+        info.duration_seconds = spotlight.duration_seconds;
+    }
+    #[cfg(not(target_os = "macos"))]
+    {
+        info.opened_at = u64::try_from(meta.atime()).ok();
+    }
+
+    if info.kind.is_empty() {
+        info.kind = fallback_kind(path, meta);
+    }
+    if !meta.is_file() {
+        return Ok(info);
+    }
+
+    let head = read_head(path)?;
+    let ext = path.extension().and_then(|e| e.to_str());
+    match classify_viewer_content(&head, ext, true) {
+        ViewerContentKind::Image if info.image_width.is_none() => {
+            if let Some((width, height)) = crate::file_viewer::media::read_image_dimensions(path) {
+                info.image_width = Some(width);
+                info.image_height = Some(height);
+            }
+        }
+        ViewerContentKind::Text if looks_like_text(&head) && meta.len() <= LINE_COUNT_MAX_BYTES => {
+            info.line_count = Some(count_lines(path, cancelled)?);
+        }
+        _ => {}
+    }
+    Ok(info)
+}
+
+fn read_head(path: &Path) -> std::io::Result<Vec<u8>> {
+    let mut head = Vec::with_capacity(CLASSIFY_HEAD_LEN);
+    File::open(path)?
+        .take(CLASSIFY_HEAD_LEN as u64)
+        .read_to_end(&mut head)?;
+    Ok(head)
+}
+
+/// Binary formats almost always hold a NUL byte early on; text (UTF-8, Latin-1) never does.
+/// UTF-16 text has NULs too, and gets no line count; that's rare enough to accept.
+fn looks_like_text(head: &[u8]) -> bool {
+    !head.contains(&0)
+}
+
+fn count_lines(path: &Path, cancelled: &AtomicBool) -> Result<u64, FileInfoError> {
+    let mut file = File::open(path)?;
+    let mut buf = vec![0u8; CHUNK_SIZE];
+    let mut lines = 0u64;
+    let mut last_byte = None;
+    loop {
+        if cancelled.load(Ordering::Relaxed) {
+            return Err(FileInfoError::Cancelled);
+        }
+        let n = match file.read(&mut buf) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e.into()),
+        };
+        lines += memchr::memchr_iter(b'\n', &buf[..n]).count() as u64;
+        last_byte = Some(buf[n - 1]);
+    }
+    // An unterminated last line is still a line.
+    if last_byte.is_some_and(|b| b != b'\n') {
+        lines += 1;
+    }
+    Ok(lines)
+}
+
+/// Finder-style kind from what `stat` and the extension say, for when Spotlight has nothing.
+fn fallback_kind(path: &Path, meta: &std::fs::Metadata) -> String {
+    let file_type = meta.file_type();
+    if file_type.is_symlink() {
+        return "Symbolic link".to_string();
+    }
+    if file_type.is_dir() {
+        return "Folder".to_string();
+    }
+    if !file_type.is_file() {
+        return "Special file".to_string();
+    }
+    match path.extension().and_then(|e| e.to_str()).filter(|e| !e.is_empty()) {
+        Some(ext) => format!("{} file", ext.to_uppercase()),
+        None if meta.mode() & 0o111 != 0 => "Executable".to_string(),
+        None => "Document".to_string(),
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod spotlight {
+    use std::path::Path;
+
+    use core_foundation::base::{CFRelease, CFTypeRef, TCFType};
+    use core_foundation::number::CFNumber;
+    use core_foundation::string::{CFString, CFStringRef};
+
+    /// The Spotlight attributes the info panel uses. Each is `None` when the index has no value
+    /// (not indexed, indexing off for the volume, or not applicable to the file).
+    #[derive(Default)]
+    pub struct SpotlightAttributes {
+        pub kind: Option<String>,
+        pub pixel_width: Option<u32>,
+        pub pixel_height: Option<u32>,
+        pub duration_seconds: Option<f64>,
+    }
+
+    // Opaque MDItem handle.
+    #[repr(C)]
+    struct __MDItem(std::ffi::c_void);
+    type MDItemRef = *mut __MDItem;
+
+    // SAFETY: the standard CoreServices MDItem C signatures, as in `importance::last_used`.
+    // `MDItemCreate` and `MDItemCopyAttribute` both return +1 references the caller releases.
+    #[link(name = "CoreServices", kind = "framework")]
+    unsafe extern "C" {
+        fn MDItemCreate(allocator: CFTypeRef, path: CFStringRef) -> MDItemRef;
+        fn MDItemCopyAttribute(item: MDItemRef, name: CFStringRef) -> CFTypeRef;
+    }
+
+    /// A +1 attribute value, or `None` when the item has no such attribute.
+    fn copy_attribute(item: MDItemRef, name: &'static str) -> Option<CFTypeRef> {
+        let attr_name = CFString::from_static_string(name);
+        // SAFETY: `item` is a live, non-null MDItemRef (checked by the caller); `attr_name` is a
+        // live CFString for the call. The result is a +1 Copy reference the caller owns.
+        let value = unsafe { MDItemCopyAttribute(item, attr_name.as_concrete_TypeRef()) };
+        (!value.is_null()).then_some(value)
+    }
+
+    fn string_attribute(item: MDItemRef, name: &'static str) -> Option<String> {
+        let value = copy_attribute(item, name)?;
+        // SAFETY: `value` is the +1 reference from `MDItemCopyAttribute`; `kMDItemKind` is
+        // documented as a CFString. The create-rule wrap takes over that reference and releases it.
+        let string = unsafe { CFString::wrap_under_create_rule(value as CFStringRef) };
+        Some(string.to_string())
+    }
+
+    fn number_attribute(item: MDItemRef, name: &'static str) -> Option<f64> {
+        let value = copy_attribute(item, name)?;
+        // SAFETY: `value` is the +1 reference from `MDItemCopyAttribute`; the pixel-size and
+        // duration attributes are documented as CFNumbers. The wrap takes over that reference.
+        let number = unsafe { CFNumber::wrap_under_create_rule(value as _) };
+        number.to_f64()
+    }
+
+    pub fn attributes(path: &Path) -> SpotlightAttributes {
+        let Some(path_str) = path.to_str() else {
+            return SpotlightAttributes::default();
+        };
+        let cf_path = CFString::new(path_str);
+        // SAFETY: `cf_path` is a live CFString for the call; a null allocator is the default.
+        // The result is a +1 Create reference, released below.
+        let item = unsafe { MDItemCreate(std::ptr::null(), cf_path.as_concrete_TypeRef()) };
+        if item.is_null() {
+            return SpotlightAttributes::default();
+        }
+        let attributes = objc2::rc::autoreleasepool(|_| SpotlightAttributes {
+            kind: string_attribute(item, "kMDItemKind"),
+            pixel_width: number_attribute(item, "kMDItemPixelWidth").map(|w| w as u32),
+            pixel_height: number_attribute(item, "kMDItemPixelHeight").map(|h| h as u32),
+            duration_seconds: number_attribute(item, "kMDItemDurationSeconds"),
+        });
+        // SAFETY: balances the +1 from `MDItemCreate`; `item` isn't used after this.
+        unsafe { CFRelease(item as CFTypeRef) };
+        attributes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn info_of(path: &Path) -> FileInfo {
+        get_file_info(path, &AtomicBool::new(false)).expect("file info")
+    }
+
+    #[test]
+    fn counts_lines_of_text_files() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let terminated = dir.path().join("a.txt");
+        std::fs::write(&terminated, "one\ntwo\nthree\n").expect("write");
+        let unterminated = dir.path().join("b.txt");
+        std::fs::write(&unterminated, "one\ntwo").expect("write");
+        let empty = dir.path().join("c.txt");
+        std::fs::write(&empty, "").expect("write");
+
+        assert_eq!(info_of(&terminated).line_count, Some(3));
+        assert_eq!(info_of(&unterminated).line_count, Some(2));
+        assert_eq!(info_of(&empty).line_count, Some(0));
+    }
+
+    #[test]
+    fn binary_files_get_no_line_count() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("blob.bin");
+        std::fs::write(&path, [0u8, 1, 2, b'\n', 0, 3]).expect("write");
+        assert_eq!(info_of(&path).line_count, None);
+    }
+
+    #[test]
+    fn reads_image_dimensions() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("pixel.png");
+        image::RgbImage::new(3, 2).save(&path).expect("save png");
+
+        let info = info_of(&path);
+        assert_eq!((info.image_width, info.image_height), (Some(3), Some(2)));
+        assert_eq!(info.line_count, None);
+    }
+
+    #[test]
+    fn folders_have_a_kind_and_nothing_else() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let info = info_of(dir.path());
+        assert!(!info.kind.is_empty());
+        assert_eq!(info.line_count, None);
+        assert_eq!(info.image_width, None);
+    }
+
+    #[test]
+    fn cache_follows_the_file_and_cancel_stops_counting() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("notes.txt");
+        std::fs::write(&path, "a\n").expect("write");
+        assert_eq!(info_of(&path).line_count, Some(1));
+
+        // A different size is a different fingerprint, even within the same mtime tick.
+        std::fs::write(&path, "a\nb\nc\n").expect("rewrite");
+        assert_eq!(info_of(&path).line_count, Some(3));
+
+        let fresh = dir.path().join("fresh.txt");
+        std::fs::write(&fresh, "x\n").expect("write");
+        let result = get_file_info(&fresh, &AtomicBool::new(true));
+        assert!(matches!(result, Err(FileInfoError::Cancelled)));
+    }
+}
@@ -195,6 +195,24 @@ pub fn detect_filesystem_for_path(_path: &std::path::Path) -> FilesystemInfo {
     FilesystemInfo::from_raw_type(None)
 }
 
+/// Whether `path` is on a network filesystem (SMB, NFS, AFP, WebDAV, sshfs, ...). One `statfs`
+/// on macOS, a `/proc/mounts` lookup on Linux. Either can block on a hung mount, so keep it off
+/// the async runtime and away from volume discovery.
+#[cfg(target_os = "macos")]
+pub fn is_on_network_filesystem(path: &std::path::Path) -> bool {
+    crate::volumes::is_network_fs_type(crate::volumes::get_fs_type(&path.to_string_lossy()).as_deref())
+}
+
+#[cfg(target_os = "linux")]
+pub fn is_on_network_filesystem(path: &std::path::Path) -> bool {
+    crate::file_system::linux_mounts::is_network_filesystem_linux(path)
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux")))]
+pub fn is_on_network_filesystem(_path: &std::path::Path) -> bool {
+    false
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
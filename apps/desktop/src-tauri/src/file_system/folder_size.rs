@@ -0,0 +1,110 @@
+//! "Calculate size" for a folder the drive index doesn't cover: a live recursive walk with
+//! running totals.
+//!
+//! The walk is the indexer's own (`indexing::scanner::folder_size`), so exclusions, hardlink
+//! dedup, and the hung-mount guard match what an indexed folder would report. Totals are
+//! physical (on-disk) bytes, like `recursive_physical_size`.
+//!
+//! Cancellable through [`ACTIVE_WALKS`], under an operation id the start command mints.
+
+use std::path::Path;
+use std::sync::atomic::AtomicBool;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::cancel_registry::CancelRegistry;
+use crate::indexing::scanner::ScanProgress;
+
+/// Gap between two `on_progress` calls. Matches the write ops' default.
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(200);
+
+/// How often the reporting thread checks whether the walk finished, so a small folder returns
+/// right away instead of after a full progress interval.
+const POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// Running or final totals of a folder-size walk.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct FolderSizeTotals {
+    /// Files, folders, and symlinks below the folder (the folder itself not included).
+    pub entries: u64,
+    pub dirs: u64,
+    pub physical_bytes: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct FolderSizeResult {
+    pub totals: FolderSizeTotals,
+    /// `true` when the walk stopped early; `totals` is then a lower bound.
+    pub cancelled: bool,
+    /// `true` when the walk was written into the drive index, so the next size query is instant.
+    pub written_to_index: bool,
+}
+
+/// Cancel flags of in-flight folder-size walks.
+pub static ACTIVE_WALKS: CancelRegistry = CancelRegistry::new();
+
+fn totals_of(progress: &ScanProgress) -> FolderSizeTotals {
+    let snap = progress.snapshot();
+    FolderSizeTotals {
+        entries: snap.entries_scanned,
+        dirs: snap.dirs_found,
+        physical_bytes: snap.bytes_scanned,
+    }
+}
+
+/// Walks `path` and returns its recursive size, calling `on_progress` with the running totals
+/// every [`PROGRESS_INTERVAL`]. With `write_to_index`, the walk is stored in the drive index
+/// when it can be (see `indexing::scanner::folder_size`). Blocking: call it off the async runtime.
+pub fn compute_folder_size(
+    path: &Path,
+    write_to_index: bool,
+    cancelled: &AtomicBool,
+    on_progress: &dyn Fn(FolderSizeTotals),
+) -> Result<FolderSizeResult, String> {
+    if !path.is_dir() {
+        return Err(format!("Not a folder: {}", path.display()));
+    }
+    let progress = ScanProgress::new();
+    let result = std::thread::scope(|s| {
+        let walk = s.spawn(|| crate::indexing::scanner::folder_size(path, write_to_index, cancelled, &progress));
+        let mut last_progress = Instant::now();
+        while !walk.is_finished() {
+            std::thread::sleep(POLL_INTERVAL);
+            if last_progress.elapsed() >= PROGRESS_INTERVAL && !walk.is_finished() {
+                on_progress(totals_of(&progress));
+                last_progress = Instant::now();
+            }
+        }
+        walk.join()
+    });
+    let (summary, written_to_index) = result
+        .map_err(|_| "Folder size walk panicked".to_string())?
+        .map_err(|e| e.to_string())?;
+    Ok(FolderSizeResult {
+        totals: FolderSizeTotals {
+            entries: summary.total_entries,
+            dirs: summary.total_dirs,
+            physical_bytes: summary.total_physical_bytes,
+        },
+        cancelled: summary.was_cancelled,
+        written_to_index,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cancelled_walk_reports_cancelled() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        std::fs::write(dir.path().join("a.bin"), b"x").expect("write");
+
+        let result = compute_folder_size(dir.path(), false, &AtomicBool::new(true), &|_| {}).expect("size");
+
+        assert!(result.cancelled);
+    }
+}
@@ -406,6 +406,7 @@ fn watcher_invalidates_branches_listing_on_new_branch() {
                 sort_by: SortColumn::Name,
                 sort_order: SortOrder::Ascending,
                 directory_sort_mode: DirectorySortMode::LikeFiles,
+                generation: 0,
                 sequence: AtomicU64::new(0),
                 created_at: std::time::Instant::now(),
                 last_accessed_ms: AtomicU64::new(0),
@@ -0,0 +1,151 @@
+//! "Jump to largest files": the N biggest files anywhere under a folder, to find space hogs.
+//!
+//! Answered from the drive index when it fully covers the subtree (one `ORDER BY size DESC
+//! LIMIT N` query, see `indexing::largest_files`), otherwise by a bounded disk walk that keeps
+//! only the top N in a min-heap, so memory stays at N entries however big the tree is.
+//!
+//! Results are full [`FileEntry`]s, stat'ed fresh, so the frontend can show them as a flat
+//! listing and act on them (open, reveal, delete) like any other entry. An index row whose
+//! file is gone by the time it's stat'ed is dropped rather than shown stale.
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use walkdir::WalkDir;
+
+use crate::file_system::FileEntry;
+use crate::file_system::listing::get_single_entry;
+
+/// Most files one call returns. A longer list stops being a "jump to" and becomes a listing.
+pub const MAX_LIMIT: u32 = 1000;
+
+/// Walk cap for the disk fallback, same as the extension breakdown's. Past this many entries
+/// the result is the largest files seen so far, with `truncated: true`.
+const MAX_WALK_ENTRIES: usize = 200_000;
+
+/// The largest files under a folder.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct LargestFiles {
+    /// Biggest first. `path` is absolute; `name` is the file name only.
+    pub entries: Vec<FileEntry>,
+    /// `true` when the drive index answered, `false` for a disk walk.
+    pub from_index: bool,
+    /// `true` when the disk walk hit [`MAX_WALK_ENTRIES`]; larger files may exist further in.
+    pub truncated: bool,
+}
+
+/// Walks `root` without following symlinks and keeps the `limit` largest files, biggest first.
+/// Unreadable subdirectories are skipped rather than failing the whole search.
+fn walk_largest(root: &Path, limit: usize, max_entries: usize) -> (Vec<(u64, PathBuf)>, bool) {
+    let mut heap: BinaryHeap<Reverse<(u64, PathBuf)>> = BinaryHeap::with_capacity(limit + 1);
+    let mut truncated = false;
+    for (seen, entry) in WalkDir::new(root)
+        .min_depth(1)
+        .follow_links(false)
+        .into_iter()
+        .enumerate()
+    {
+        if seen >= max_entries {
+            truncated = true;
+            break;
+        }
+        let Ok(entry) = entry else { continue };
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+        if heap.len() == limit && heap.peek().is_some_and(|Reverse((smallest, _))| size <= *smallest) {
+            continue;
+        }
+        heap.push(Reverse((size, entry.into_path())));
+        if heap.len() > limit {
+            heap.pop();
+        }
+    }
+    // Ascending `Reverse` order is descending size.
+    let files = heap.into_sorted_vec().into_iter().map(|Reverse(file)| file).collect();
+    (files, truncated)
+}
+
+/// Finds the `limit` largest files under `path` (clamped to [`MAX_LIMIT`]). Blocking: does SQL
+/// or disk I/O, then one stat per result.
+pub fn get_largest_files(path: &Path, limit: u32) -> Result<LargestFiles, String> {
+    if !path.is_dir() {
+        return Err(format!("Not a folder: {}", path.display()));
+    }
+    let limit = limit.clamp(1, MAX_LIMIT);
+
+    let indexed = crate::indexing::largest_files(&path.to_string_lossy(), limit).unwrap_or_else(|e| {
+        log::debug!(target: "largest_files", "Index lookup failed for {path:?}, walking instead: {e}");
+        None
+    });
+    let (paths, from_index, truncated): (Vec<PathBuf>, bool, bool) = match indexed {
+        Some(rows) => (rows.into_iter().map(|(rel, _)| path.join(rel)).collect(), true, false),
+        None => {
+            let (files, truncated) = walk_largest(path, limit as usize, MAX_WALK_ENTRIES);
+            (files.into_iter().map(|(_, p)| p).collect(), false, truncated)
+        }
+    };
+
+    let entries = paths.iter().filter_map(|p| get_single_entry(p).ok()).collect();
+    Ok(LargestFiles {
+        entries,
+        from_index,
+        truncated,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn write_file(dir: &Path, name: &str, size: usize) {
+        fs::write(dir.join(name), vec![0u8; size]).expect("write");
+    }
+
+    #[test]
+    fn walk_keeps_only_the_largest_biggest_first() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let sub = dir.path().join("sub");
+        fs::create_dir(&sub).expect("mkdir");
+        write_file(dir.path(), "small", 10);
+        write_file(&sub, "huge", 900);
+        write_file(dir.path(), "mid", 300);
+        write_file(&sub, "tiny", 1);
+
+        let (files, truncated) = walk_largest(dir.path(), 2, MAX_WALK_ENTRIES);
+
+        assert!(!truncated);
+        assert_eq!(files, vec![(900, sub.join("huge")), (300, dir.path().join("mid"))]);
+    }
+
+    #[test]
+    fn walk_reports_truncation_at_the_entry_cap() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        for i in 0..5 {
+            write_file(dir.path(), &format!("f{i}"), i + 1);
+        }
+        let (files, truncated) = walk_largest(dir.path(), 10, 3);
+        assert!(truncated);
+        assert_eq!(files.len(), 3);
+    }
+
+    #[test]
+    fn returns_file_entries_with_absolute_paths() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        write_file(dir.path(), "a.bin", 20);
+        write_file(dir.path(), "b.bin", 40);
+
+        let result = get_largest_files(dir.path(), 5).expect("largest");
+
+        let names: Vec<_> = result.entries.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["b.bin", "a.bin"]);
+        assert_eq!(result.entries[0].path, dir.path().join("b.bin").to_string_lossy());
+        assert_eq!(result.entries[0].size, Some(40));
+        assert!(get_largest_files(&dir.path().join("a.bin"), 5).is_err());
+    }
+}
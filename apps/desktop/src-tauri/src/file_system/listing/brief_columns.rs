@@ -20,6 +20,7 @@ use std::time::Instant;
 
 use crate::file_system::listing::caching::LISTING_CACHE;
 use crate::file_system::listing::metadata::FileEntry;
+use crate::file_system::listing::{eviction, git_ignore};
 
 /// Errors from `compute_brief_column_text_widths`. Internal to the backend;
 /// the IPC command wrapper maps these to `IpcError` for the wire.
@@ -33,6 +34,8 @@ pub enum BriefColumnsError {
     InvalidItemsPerColumn,
     /// The listing ID isn't in `LISTING_CACHE` (already ended, or never started).
     ListingNotFound(String),
+    /// The listing was evicted and is being re-read; a `directory-diff` follows when it lands.
+    ListingNotReady(String),
     /// Catch-all for cache-lock poisoning etc.
     Other(String),
 }
@@ -88,8 +91,10 @@ pub fn compute_brief_column_text_widths(
         return Err(BriefColumnsError::InvalidItemsPerColumn);
     }
 
+    let _span = crate::benchmark::span_for("brief_column_widths", listing_id);
     let start = Instant::now();
 
+    eviction::restore_if_evicted(listing_id).map_err(|e| BriefColumnsError::ListingNotReady(e.0))?;
     let cache = LISTING_CACHE
         .read()
         .map_err(|e| BriefColumnsError::Other(format!("Failed to acquire cache lock: {}", e)))?;
@@ -99,11 +104,11 @@ pub fn compute_brief_column_text_widths(
         .ok_or_else(|| BriefColumnsError::ListingNotFound(listing_id.to_string()))?;
 
     // Materialize visible entries into a Vec so we can index by position cheaply.
-    let visible: Vec<&FileEntry> = if include_hidden {
-        listing.entries.iter().collect()
-    } else {
-        listing.entries.iter().filter(|e| is_visible(e)).collect()
-    };
+    let visible: Vec<&FileEntry> = listing
+        .entries
+        .iter()
+        .filter(|e| (include_hidden || is_visible(e)) && !git_ignore::hides(e))
+        .collect();
 
     // Total cells (display slots): visible entries + ".." if has_parent.
     let total_cells = visible.len() + usize::from(has_parent);
@@ -16,10 +16,15 @@ use super::brief_columns::{BriefColumnsError, compute_brief_column_text_widths};
 use super::caching_test_support::{TestListing, TestListingGuard, unique_test_id};
 use super::metadata::FileEntry;
 
+/// Room for every font id these tests seed, so a parallel test's `store_metrics`
+/// can't evict one mid-assertion.
+const TEST_FONT_CACHE_CAPACITY: usize = 1024;
+
 /// Seeds `font_metrics` with a uniform `width_per_char` for ASCII printable code
 /// points, under a font id unique to this call, so any filename of length N
 /// measures to `N * width_per_char`. Returns the font id.
 fn seed_font(width_per_char: f32) -> String {
+    crate::font_metrics::set_cache_capacity(TEST_FONT_CACHE_CAPACITY).expect("set font cache capacity");
     let font_id = unique_test_id("brief-columns-font");
     let mut widths = HashMap::new();
     // ASCII printable range covers all test filenames; the `..` literal too.
@@ -58,6 +58,15 @@ pub(crate) fn epoch_millis_now() -> u64 {
     LISTING_EPOCH.elapsed().as_millis() as u64
 }
 
+/// Source of `CachedListing::generation`. Starts at 1 so test fixtures can use 0 freely.
+static NEXT_LISTING_GENERATION: AtomicU64 = AtomicU64::new(1);
+
+/// A fresh generation for a listing pass. Unique per pass, even when a pass reuses a
+/// listing id (a cancelled streaming listing restarted under the same id).
+pub(crate) fn next_listing_generation() -> u64 {
+    NEXT_LISTING_GENERATION.fetch_add(1, Ordering::Relaxed)
+}
+
 /// Idle window after which an untouched listing is treated as orphaned and reaped.
 ///
 /// **Deliberately generous (six hours).** A listing legitimately lives for the entire
@@ -94,6 +103,11 @@ pub(crate) struct CachedListing {
     pub sort_order: SortOrder,
     /// How directories are sorted relative to the current sort column
     pub directory_sort_mode: DirectorySortMode,
+    /// Which listing pass filled this entry (see `next_listing_generation`). Writers that
+    /// read the entries, do slow work, then write back (watcher and MTP re-reads) pass the
+    /// generation they read, and `update_listing_entries` drops the write if the listing
+    /// was replaced meanwhile, so a superseded pass can't patch its entries into a new one.
+    pub generation: u64,
     /// Monotonic sequence number for `directory-diff` events. Incremented each time
     /// the cache is patched (by watcher, notify_mutation, or manual refresh).
     /// Lives on the listing so it works for all volume types, including SMB/MTP
@@ -215,6 +229,7 @@ pub(crate) fn start_orphan_listing_reaper() {
         loop {
             tokio::time::sleep(REAPER_SWEEP_INTERVAL).await;
             let reaped = reap_orphaned_listings();
+            super::eviction::forget_stale_tombstones(ORPHAN_IDLE_WINDOW);
             if !reaped.is_empty() {
                 log::info!(
                     target: "listing_cache",
@@ -783,13 +798,13 @@ async fn notify_full_refresh(
         sort_entries(&mut sorted, *sort_by, *sort_order, *dir_sort_mode);
 
         // Get old entries for diff computation
-        let old_entries = {
+        let (old_entries, generation) = {
             let cache = match LISTING_CACHE.read() {
                 Ok(c) => c,
                 Err(_) => continue,
             };
             match cache.get(listing_id.as_str()) {
-                Some(listing) => listing.entries.clone(),
+                Some(listing) => (listing.entries.clone(), listing.generation),
                 None => continue,
             }
         };
@@ -800,7 +815,9 @@ async fn notify_full_refresh(
         }
 
         // Update cache
-        crate::file_system::listing::operations::update_listing_entries(listing_id, sorted);
+        if !crate::file_system::listing::operations::update_listing_entries(listing_id, generation, sorted) {
+            continue;
+        }
 
         enqueue_diff(listing_id, changes);
     }
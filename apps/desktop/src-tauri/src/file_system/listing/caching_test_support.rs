@@ -156,6 +156,7 @@ impl TestListing {
                 sort_by: self.sort_by,
                 sort_order: self.sort_order,
                 directory_sort_mode: self.directory_sort_mode,
+                generation: 0,
                 sequence: AtomicU64::new(self.sequence),
                 created_at: Instant::now(),
                 last_accessed_ms: AtomicU64::new(self.last_accessed_ms),
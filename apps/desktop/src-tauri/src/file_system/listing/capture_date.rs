@@ -0,0 +1,184 @@
+//! Photo capture dates (EXIF `DateTimeOriginal`) for the "Captured" sort column.
+//!
+//! Read only for files with an image extension, and only from the first
+//! [`HEADER_READ_LIMIT`] bytes: JPEG, HEIF and most TIFF-based raws keep their EXIF block
+//! near the start, and a 50 MB raw shouldn't be read whole to sort a folder. A file with
+//! no (readable) EXIF date gets `None`, and the sort falls back to its mtime.
+//!
+//! Parsed dates are cached per path and mtime, so re-sorting a photo folder or
+//! re-listing it after an unrelated change doesn't re-open every file.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::Path;
+use std::sync::{LazyLock, Mutex};
+
+use chrono::{FixedOffset, Local, NaiveDate, TimeZone};
+use rayon::prelude::*;
+
+use crate::file_system::listing::metadata::FileEntry;
+use crate::file_system::listing::sorting::SortColumn;
+use crate::ignore_poison::IgnorePoison;
+
+/// Extensions worth opening. Formats `exif` reads from a container: JPEG, TIFF (and the
+/// TIFF-based raws), HEIF, PNG and WebP.
+const IMAGE_EXTENSIONS: &[&str] = &[
+    "jpg", "jpeg", "tif", "tiff", "dng", "nef", "cr2", "arw", "heic", "heif", "png", "webp",
+];
+
+/// Bytes read per file before giving up on finding the EXIF block.
+const HEADER_READ_LIMIT: u64 = 1024 * 1024;
+
+/// Cached paths before the cache is dropped and rebuilt. Plenty for a few large photo
+/// folders; a plain clear keeps it simpler than an LRU for a cache this cheap to refill.
+const MAX_CACHED_PATHS: usize = 50_000;
+
+#[derive(Clone, Copy)]
+struct CachedCapture {
+    modified_at: Option<u64>,
+    captured_at: Option<u64>,
+}
+
+static CAPTURE_CACHE: LazyLock<Mutex<HashMap<String, CachedCapture>>> = LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Whether `name` has an extension we read EXIF from.
+pub(crate) fn is_image_name(name: &str) -> bool {
+    Path::new(name)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| IMAGE_EXTENSIONS.iter().any(|known| ext.eq_ignore_ascii_case(known)))
+}
+
+/// The capture date of the image at `path` as Unix seconds, or `None` when it has none.
+/// `modified_at` keys the cache: a re-saved file is read again.
+pub(crate) fn captured_at(path: &str, modified_at: Option<u64>) -> Option<u64> {
+    if let Some(cached) = CAPTURE_CACHE.lock_ignore_poison().get(path)
+        && cached.modified_at == modified_at
+    {
+        return cached.captured_at;
+    }
+    let captured_at = read_captured_at(Path::new(path));
+    let mut cache = CAPTURE_CACHE.lock_ignore_poison();
+    if cache.len() >= MAX_CACHED_PATHS {
+        cache.clear();
+    }
+    cache.insert(
+        path.to_string(),
+        CachedCapture {
+            modified_at,
+            captured_at,
+        },
+    );
+    captured_at
+}
+
+/// [`captured_at`] for a bare path (the extended-metadata batch has no entry to take the
+/// mtime from): `None` for a non-image without touching the disk.
+pub(crate) fn captured_at_of_path(path: &str) -> Option<u64> {
+    if !is_image_name(path) {
+        return None;
+    }
+    let modified_at = std::fs::metadata(path)
+        .ok()?
+        .modified()
+        .ok()
+        .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|elapsed| elapsed.as_secs());
+    captured_at(path, modified_at)
+}
+
+/// Fills `captured_at` on every image file in `entries` (in parallel: each one is a
+/// file open and a small read). Directories and non-images are left `None`.
+pub(crate) fn fill_captured_at(entries: &mut [FileEntry]) {
+    entries
+        .par_iter_mut()
+        .filter(|entry| !entry.is_directory && is_image_name(&entry.name))
+        .for_each(|entry| entry.captured_at = captured_at(&entry.path, entry.modified_at));
+}
+
+/// Fills capture dates before a Captured sort. Other sort columns skip the reads, and so
+/// do volumes without `std::fs` access (MTP), whose entry paths aren't real paths.
+pub(crate) fn fill_for_sort(volume_id: &str, sort_by: SortColumn, entries: &mut [FileEntry]) {
+    if sort_by != SortColumn::Captured {
+        return;
+    }
+    let local = crate::file_system::get_volume_manager()
+        .get(volume_id)
+        .is_some_and(|volume| volume.supports_local_fs_access());
+    if local {
+        fill_captured_at(entries);
+    }
+}
+
+fn read_captured_at(path: &Path) -> Option<u64> {
+    let file = File::open(path).ok()?;
+    let mut reader = BufReader::new(file.take(HEADER_READ_LIMIT));
+    let exif = exif::Reader::new().read_from_container(&mut reader).ok()?;
+    let field = exif.get_field(exif::Tag::DateTimeOriginal, exif::In::PRIMARY)?;
+    let exif::Value::Ascii(ref values) = field.value else {
+        return None;
+    };
+    let mut date = exif::DateTime::from_ascii(values.first()?).ok()?;
+    if let Some(offset) = exif.get_field(exif::Tag::OffsetTimeOriginal, exif::In::PRIMARY)
+        && let exif::Value::Ascii(ref values) = offset.value
+        && let Some(value) = values.first()
+    {
+        // A malformed offset just leaves the date in local time.
+        let _ = date.parse_offset(value);
+    }
+    to_unix_seconds(&date)
+}
+
+/// Converts an EXIF date to Unix seconds. EXIF dates are wall-clock time; without an
+/// `OffsetTimeOriginal` we assume the camera was set to this machine's time zone.
+fn to_unix_seconds(date: &exif::DateTime) -> Option<u64> {
+    let naive = NaiveDate::from_ymd_opt(i32::from(date.year), u32::from(date.month), u32::from(date.day))?
+        .and_hms_opt(u32::from(date.hour), u32::from(date.minute), u32::from(date.second))?;
+    let timestamp = match date.offset {
+        Some(minutes) => FixedOffset::east_opt(i32::from(minutes) * 60)?
+            .from_local_datetime(&naive)
+            .single()?
+            .timestamp(),
+        None => Local.from_local_datetime(&naive).earliest()?.timestamp(),
+    };
+    u64::try_from(timestamp).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn image_extensions_match_case_insensitively() {
+        assert!(is_image_name("IMG_0001.JPG"));
+        assert!(is_image_name("scan.tiff"));
+        assert!(is_image_name("photo.heic"));
+        assert!(!is_image_name("notes.txt"));
+        assert!(!is_image_name("jpg"));
+    }
+
+    #[test]
+    fn exif_date_with_offset_converts_to_utc() {
+        let mut date = exif::DateTime::from_ascii(b"2024:06:01 12:30:00").unwrap();
+        date.parse_offset(b"+02:00").unwrap();
+        assert_eq!(to_unix_seconds(&date), Some(1_717_237_800));
+    }
+
+    #[test]
+    fn blank_exif_date_is_rejected() {
+        // Cameras with an unset clock write all-blank or all-zero dates.
+        assert!(exif::DateTime::from_ascii(b"    :  :     :  :  ").is_err());
+        let zero = exif::DateTime::from_ascii(b"0000:00:00 00:00:00");
+        assert!(zero.is_err() || to_unix_seconds(&zero.unwrap()).is_none());
+    }
+
+    #[test]
+    fn non_image_and_missing_files_have_no_capture_date() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("fake.jpg");
+        std::fs::write(&path, b"not a jpeg").unwrap();
+        assert_eq!(captured_at(&path.to_string_lossy(), Some(1)), None);
+        assert_eq!(captured_at("/nonexistent/cmdr/photo.jpg", None), None);
+    }
+}
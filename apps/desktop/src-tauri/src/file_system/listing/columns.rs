@@ -0,0 +1,257 @@
+//! The user's column set per view mode (`listing-columns.json`): which columns show, in what
+//! order, and at what width.
+//!
+//! This is view state the backend persists, not a `settings.json` key: the frontend owns that
+//! file and the backend never writes it (see `settings/CLAUDE.md`). Same shape as
+//! `open_with_recents.rs`: in-memory cache loaded lazily, durable temp-then-rename write, and a
+//! schema-versioned file that's quarantined on a parse error.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+use serde::{Deserialize, Serialize};
+
+use crate::ignore_poison::IgnorePoison;
+use crate::menu::ViewMode;
+
+/// Bump when the on-disk shape changes in an incompatible way.
+const CURRENT_SCHEMA_VERSION: u32 = 1;
+
+/// Filename inside `{app_data_dir}/`.
+const COLUMNS_FILENAME: &str = "listing-columns.json";
+
+/// Narrowest width a stored column keeps, so a stray drag can't make it unreachable.
+const MIN_COLUMN_WIDTH: u32 = 24;
+
+/// A column the file list can show.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub enum ColumnId {
+    Name,
+    Extension,
+    Size,
+    Modified,
+    Created,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ColumnSetting {
+    pub id: ColumnId,
+    /// Width in CSS pixels, or `None` to shrink-wrap to the content (the default).
+    pub width: Option<u32>,
+}
+
+/// Why a column set was refused.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, specta::Type)]
+#[serde(rename_all = "camelCase", rename_all_fields = "camelCase", tag = "kind")]
+pub enum ColumnsError {
+    /// The Name column can't be hidden: it's the row's click and rename target.
+    MissingName,
+    Duplicate {
+        id: ColumnId,
+    },
+}
+
+impl std::fmt::Display for ColumnsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingName => write!(f, "The Name column can't be hidden"),
+            Self::Duplicate { id } => write!(f, "Column {id:?} is listed twice"),
+        }
+    }
+}
+
+/// On-disk shape. A missing view mode means "the defaults".
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ColumnsStore {
+    #[serde(rename = "_schemaVersion")]
+    schema_version: u32,
+    #[serde(default)]
+    full: Option<Vec<ColumnSetting>>,
+    #[serde(default)]
+    brief: Option<Vec<ColumnSetting>>,
+}
+
+impl ColumnsStore {
+    fn slot(&mut self, view_mode: ViewMode) -> &mut Option<Vec<ColumnSetting>> {
+        match view_mode {
+            ViewMode::Full => &mut self.full,
+            ViewMode::Brief => &mut self.brief,
+        }
+    }
+}
+
+/// `None` until the first access loads from disk.
+static CACHE: OnceLock<Mutex<Option<ColumnsStore>>> = OnceLock::new();
+
+/// Serializes the cache → disk flush so concurrent calls can't clobber each other.
+static DISK_LOCK: OnceLock<Mutex<()>> = OnceLock::new();
+
+fn cache() -> &'static Mutex<Option<ColumnsStore>> {
+    CACHE.get_or_init(|| Mutex::new(None))
+}
+
+fn disk_lock() -> &'static Mutex<()> {
+    DISK_LOCK.get_or_init(|| Mutex::new(()))
+}
+
+/// The built-in column set: Full shows what it always has, Brief is names only.
+pub fn default_columns(view_mode: ViewMode) -> Vec<ColumnSetting> {
+    let ids: &[ColumnId] = match view_mode {
+        ViewMode::Full => &[ColumnId::Name, ColumnId::Extension, ColumnId::Size, ColumnId::Modified],
+        ViewMode::Brief => &[ColumnId::Name],
+    };
+    ids.iter().map(|&id| ColumnSetting { id, width: None }).collect()
+}
+
+/// Checks a column set and clamps its widths. Order is kept as given.
+fn validate(columns: Vec<ColumnSetting>) -> Result<Vec<ColumnSetting>, ColumnsError> {
+    let mut seen = Vec::with_capacity(columns.len());
+    for column in &columns {
+        if seen.contains(&column.id) {
+            return Err(ColumnsError::Duplicate { id: column.id });
+        }
+        seen.push(column.id);
+    }
+    if !seen.contains(&ColumnId::Name) {
+        return Err(ColumnsError::MissingName);
+    }
+    Ok(columns
+        .into_iter()
+        .map(|column| ColumnSetting {
+            width: column.width.map(|w| w.max(MIN_COLUMN_WIDTH)),
+            ..column
+        })
+        .collect())
+}
+
+fn read_store_from_path(path: &Path) -> ColumnsStore {
+    let _ = fs::remove_file(path.with_extension("json.tmp"));
+    let Ok(contents) = fs::read_to_string(path) else {
+        return ColumnsStore::default();
+    };
+    match serde_json::from_str::<ColumnsStore>(&contents) {
+        Ok(store) if store.schema_version == CURRENT_SCHEMA_VERSION => store,
+        result => {
+            log::warn!(
+                target: "listing::columns",
+                "Ignoring unreadable column settings at {path:?} ({:?}); starting fresh",
+                result.map(|s| s.schema_version)
+            );
+            let _ = fs::rename(path, path.with_extension("json.broken"));
+            ColumnsStore::default()
+        }
+    }
+}
+
+fn write_store_to_path(path: &Path, store: &ColumnsStore) -> std::io::Result<()> {
+    let json = serde_json::to_string_pretty(store).map_err(std::io::Error::other)?;
+    crate::config::durable_write_json(path, &path.with_extension("json.tmp"), &json)
+}
+
+fn store_path<R: tauri::Runtime>(app: &tauri::AppHandle<R>) -> Option<PathBuf> {
+    crate::config::resolved_app_data_dir(app)
+        .ok()
+        .map(|dir| dir.join(COLUMNS_FILENAME))
+}
+
+/// Runs `f` on the loaded store, loading it from disk first if needed.
+fn with_store<R: tauri::Runtime, T>(app: &tauri::AppHandle<R>, f: impl FnOnce(&mut ColumnsStore) -> T) -> T {
+    let mut guard = cache().lock_ignore_poison();
+    let store = guard.get_or_insert_with(|| {
+        let mut store = store_path(app).map(|p| read_store_from_path(&p)).unwrap_or_default();
+        store.schema_version = CURRENT_SCHEMA_VERSION;
+        store
+    });
+    f(store)
+}
+
+/// The ordered column set for `view_mode`: the user's, or the defaults if they never chose one.
+pub fn get_columns<R: tauri::Runtime>(app: &tauri::AppHandle<R>, view_mode: ViewMode) -> Vec<ColumnSetting> {
+    with_store(app, |store| store.slot(view_mode).clone()).unwrap_or_else(|| default_columns(view_mode))
+}
+
+/// Stores the ordered column set for `view_mode` and returns it as stored (widths clamped).
+/// Setting the defaults forgets the entry, so a later change of defaults reaches this user too.
+/// The disk write is best-effort: a failure is logged and the in-memory set still applies.
+pub fn set_columns<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    view_mode: ViewMode,
+    columns: Vec<ColumnSetting>,
+) -> Result<Vec<ColumnSetting>, ColumnsError> {
+    let columns = validate(columns)?;
+    let stored = (columns != default_columns(view_mode)).then(|| columns.clone());
+    let snapshot = with_store(app, |store| {
+        let slot = store.slot(view_mode);
+        if *slot == stored {
+            return None;
+        }
+        *slot = stored;
+        Some(store.clone())
+    });
+    if let (Some(snapshot), Some(path)) = (snapshot, store_path(app)) {
+        let _disk_guard = disk_lock().lock_ignore_poison();
+        if let Err(e) = write_store_to_path(&path, &snapshot) {
+            log::warn!(target: "listing::columns", "Couldn't write column settings: {e}");
+        }
+    }
+    Ok(columns)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn column(id: ColumnId, width: Option<u32>) -> ColumnSetting {
+        ColumnSetting { id, width }
+    }
+
+    #[test]
+    fn validate_keeps_order_and_clamps_widths() {
+        let columns = vec![
+            column(ColumnId::Size, Some(3)),
+            column(ColumnId::Name, Some(240)),
+            column(ColumnId::Created, None),
+        ];
+        assert_eq!(
+            validate(columns),
+            Ok(vec![
+                column(ColumnId::Size, Some(MIN_COLUMN_WIDTH)),
+                column(ColumnId::Name, Some(240)),
+                column(ColumnId::Created, None),
+            ])
+        );
+    }
+
+    #[test]
+    fn validate_refuses_a_missing_name_or_duplicates() {
+        assert_eq!(
+            validate(vec![column(ColumnId::Size, None)]),
+            Err(ColumnsError::MissingName)
+        );
+        assert_eq!(
+            validate(vec![column(ColumnId::Name, None), column(ColumnId::Name, Some(100))]),
+            Err(ColumnsError::Duplicate { id: ColumnId::Name })
+        );
+    }
+
+    #[test]
+    fn round_trip_and_corrupt_file_recovery() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join(COLUMNS_FILENAME);
+        let store = ColumnsStore {
+            schema_version: CURRENT_SCHEMA_VERSION,
+            full: Some(vec![column(ColumnId::Name, Some(300)), column(ColumnId::Created, None)]),
+            brief: None,
+        };
+        write_store_to_path(&path, &store).expect("write");
+        assert_eq!(read_store_from_path(&path), store);
+
+        fs::write(&path, "{broken").expect("write garbage");
+        assert_eq!(read_store_from_path(&path).full, None);
+        assert!(path.with_extension("json.broken").exists());
+    }
+}
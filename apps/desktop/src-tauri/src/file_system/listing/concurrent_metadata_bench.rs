@@ -0,0 +1,67 @@
+//! Opt-in benchmark: serial vs concurrent metadata reads when listing one folder.
+//!
+//! Ignored by default; it only shows anything on a high-latency mount. Point
+//! `CMDR_LISTING_BENCH_DIR` at a folder with a few thousand entries on an OS-mounted SMB (or
+//! NFS) share:
+//!
+//! ```text
+//! CMDR_LISTING_BENCH_DIR=/Volumes/share/photos \
+//!   cargo test -p cmdr --lib concurrent_metadata_listing_bench -- --ignored --nocapture
+//! ```
+//!
+//! Runs alternate between the two modes so neither one consistently gets the server's warm
+//! attribute cache, and medians are reported, not means. A local folder is a useful control:
+//! there the concurrent mode should be no faster (and may be slightly slower).
+
+#![allow(
+    clippy::print_stdout,
+    reason = "a benchmark's whole product is the numbers it prints, and `cargo test --nocapture` is where they have to land. Scoped to this `#[ignore]`d module."
+)]
+
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use super::reading::{list_directory_core, list_directory_core_concurrent};
+
+const ITERS: usize = 7;
+const WARMUP: usize = 1;
+
+fn median_ms(v: &mut [Duration]) -> f64 {
+    v.sort_unstable();
+    v[v.len() / 2].as_secs_f64() * 1000.0
+}
+
+#[test]
+#[ignore = "needs a folder on a network mount; set CMDR_LISTING_BENCH_DIR"]
+fn concurrent_metadata_listing_bench() {
+    let Ok(dir) = std::env::var("CMDR_LISTING_BENCH_DIR") else {
+        panic!("set CMDR_LISTING_BENCH_DIR to a folder on the mount to benchmark");
+    };
+    let dir = Path::new(&dir);
+
+    let mut serial = Vec::new();
+    let mut concurrent = Vec::new();
+    let mut entries = 0;
+    for i in 0..WARMUP + ITERS {
+        let start = Instant::now();
+        entries = list_directory_core(dir).expect("serial listing").len();
+        let serial_time = start.elapsed();
+        let start = Instant::now();
+        let concurrent_entries = list_directory_core_concurrent(dir).expect("concurrent listing").len();
+        let concurrent_time = start.elapsed();
+        assert_eq!(entries, concurrent_entries, "the folder changed mid-bench");
+        if i >= WARMUP {
+            serial.push(serial_time);
+            concurrent.push(concurrent_time);
+        }
+    }
+
+    let serial_ms = median_ms(&mut serial);
+    let concurrent_ms = median_ms(&mut concurrent);
+    println!("{} ({entries} entries, {ITERS} runs each, medians)", dir.display());
+    println!("  serial:     {serial_ms:>9.1} ms");
+    println!(
+        "  concurrent: {concurrent_ms:>9.1} ms  ({:.2}x)",
+        serial_ms / concurrent_ms
+    );
+}
@@ -18,7 +18,7 @@ use std::time::Duration;
 use tauri_specta::Event as _;
 
 use crate::file_system::listing::increment_sequence;
-use crate::file_system::watcher::{DiffChange, DirectoryDiff, WATCHER_MANAGER};
+use crate::file_system::watcher::{DiffChange, DirectoryDiff, WATCHER_MANAGER, record_diff_emitted};
 
 /// Trailing flush window. Below human perception for single events; at high
 /// event rates collapses bursts into at most 1000 / `FLUSH_WINDOW_MS` emits per
@@ -93,6 +93,19 @@ fn flush(listing_id: &str) {
         return;
     }
 
+    record_diff_emitted(listing_id, changes.len());
+    emit(listing_id, changes);
+}
+
+/// Emits a change-less `directory-diff` so the pane re-fetches `listing_id` from the
+/// cache. Used after `eviction` re-lists an evicted listing in the background: the
+/// accessors that hit the tombstone returned "not ready", and this tells the frontend
+/// the entries are back.
+pub(crate) fn emit_refetch(listing_id: &str) {
+    emit(listing_id, Vec::new());
+}
+
+fn emit(listing_id: &str, changes: Vec<DiffChange>) {
     let Some(sequence) = increment_sequence(listing_id) else {
         return; // listing gone
     };
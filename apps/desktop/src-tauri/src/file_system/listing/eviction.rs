@@ -0,0 +1,350 @@
+//! Memory cap for `LISTING_CACHE`: evicts the least-recently-used listings nobody is looking at
+//! once the cache's approximate size passes the cap, and re-lists them transparently when a
+//! pane touches them again.
+//!
+//! **What's never evicted.** A listing a pane currently displays (matched on volume + path
+//! against the pane state the frontend mirrors into `mcp::pane_state`), one touched within
+//! [`ACTIVE_GRACE`] (covers the gap between a listing starting and its pane state arriving),
+//! a flat listing (`flat.rs`; it spans a whole subtree), and any listing on a volume without
+//! `std::fs` access (SMB, MTP) or inside an archive: re-reading those is slow or needs the
+//! async volume layer, which the sync accessors can't wait on.
+//!
+//! **Eviction** drops the entries and the watcher but keeps a small tombstone (volume, path,
+//! sort, sequence). The next accessor call for that id returns "not ready" and starts a
+//! background re-read (blocking pool, [`RELIST_TIMEOUT`]). Once the folder is read and sorted
+//! the listing is back under the same id, the watcher restarts, and a change-less
+//! `directory-diff` makes the pane re-fetch. Sizes are estimates (struct size plus string
+//! capacities), not allocator truth.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::file_system::listing::caching::{CachedListing, LISTING_CACHE, epoch_millis_now, next_listing_generation};
+use crate::file_system::listing::metadata::FileEntry;
+use crate::file_system::listing::sorting::{DirectorySortMode, SortColumn, SortOrder, sort_entries};
+use crate::file_system::listing::{capture_date, diff_emitter, flat, reading};
+use crate::ignore_poison::{IgnorePoison, RwLockIgnorePoison};
+
+/// Default cap, matching the `advanced.listingCacheMemoryMb` setting's default.
+pub(crate) const DEFAULT_CAP_MB: u64 = 512;
+
+/// Lowest cap accepted, so a typo can't make every background listing re-read on each access.
+const MIN_CAP_MB: u64 = 64;
+
+/// A listing touched this recently counts as displayed, whatever the pane state says.
+const ACTIVE_GRACE: Duration = Duration::from_secs(2 * 60);
+
+/// Ceiling on a background re-read; past it the tombstone is dropped and the listing ends.
+/// Times out the join handle, not the read: a hung mount keeps one blocking-pool thread.
+const RELIST_TIMEOUT: Duration = Duration::from_secs(30);
+
+static CAP_BYTES: AtomicU64 = AtomicU64::new(DEFAULT_CAP_MB * 1024 * 1024);
+
+/// What a pane shows, as mirrored from the frontend: `(volume_id, path)` for left and right.
+static DISPLAYED: LazyLock<Mutex<[Option<(String, PathBuf)>; 2]>> = LazyLock::new(|| Mutex::new([None, None]));
+
+/// What's needed to re-list an evicted listing under its old id.
+#[derive(Clone)]
+struct Tombstone {
+    volume_id: String,
+    path: PathBuf,
+    sort_by: SortColumn,
+    sort_order: SortOrder,
+    directory_sort_mode: DirectorySortMode,
+    /// The listing's `directory-diff` sequence, continued after the re-list so the
+    /// frontend's ordering check keeps working.
+    sequence: u64,
+    evicted_at: Instant,
+    /// A background re-read is running; later accessors just wait for it.
+    relisting: bool,
+}
+
+/// An accessor hit an evicted listing. The re-read is running in the background and the
+/// pane gets a `directory-diff` when it lands, so callers report this and carry on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ListingNotReady(pub String);
+
+impl fmt::Display for ListingNotReady {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Listing is being re-read: {}", self.0)
+    }
+}
+
+impl From<ListingNotReady> for String {
+    fn from(e: ListingNotReady) -> Self {
+        e.to_string()
+    }
+}
+
+static EVICTED: LazyLock<Mutex<HashMap<String, Tombstone>>> = LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Listing cache memory, for `get_listing_cache_stats`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ListingCacheStats {
+    /// Listings currently held in memory.
+    pub listing_count: usize,
+    /// Listings evicted and waiting to be re-listed on their next access.
+    pub evicted_count: usize,
+    /// Approximate bytes held by all in-memory listings.
+    pub approx_bytes: u64,
+    pub cap_bytes: u64,
+}
+
+/// Sets the cap in MB (clamped to at least [`MIN_CAP_MB`]) and evicts down to it right away.
+pub fn set_cap_mb(mb: u64) {
+    CAP_BYTES.store(mb.max(MIN_CAP_MB) * 1024 * 1024, Ordering::Relaxed);
+    enforce_cap();
+}
+
+/// Records the folder a pane displays. `side` is `0` for left and `1` for right; `None`
+/// clears it (a network or search-results view, which isn't a cached listing).
+pub(crate) fn set_displayed(side: usize, location: Option<(String, PathBuf)>) {
+    if let Some(slot) = DISPLAYED.lock_ignore_poison().get_mut(side) {
+        *slot = location;
+    }
+}
+
+fn is_displayed(volume_id: &str, path: &Path) -> bool {
+    DISPLAYED
+        .lock_ignore_poison()
+        .iter()
+        .flatten()
+        .any(|(v, p)| v == volume_id && p == path)
+}
+
+/// Approximate heap + inline bytes of one entry.
+fn entry_bytes(entry: &FileEntry) -> u64 {
+    let optional = |s: &Option<String>| s.as_ref().map_or(0, String::capacity);
+    (size_of::<FileEntry>()
+        + entry.name.capacity()
+        + entry.path.capacity()
+        + entry.owner.capacity()
+        + entry.group.capacity()
+        + entry.icon_id.capacity()
+        + entry
+            .tags
+            .iter()
+            .map(|t| size_of_val(t) + t.name.capacity())
+            .sum::<usize>()
+        + optional(&entry.redirect_to_path)
+        + optional(&entry.display_size)
+        + optional(&entry.display_size_tooltip)) as u64
+}
+
+/// Approximate bytes held by a listing's entries.
+pub(crate) fn listing_bytes(entries: &[FileEntry]) -> u64 {
+    entries.iter().map(entry_bytes).sum()
+}
+
+/// One cached listing as the eviction policy sees it.
+pub(crate) struct EvictionCandidate<'a> {
+    pub id: &'a str,
+    pub bytes: u64,
+    pub last_accessed_ms: u64,
+    /// `false` for displayed, recently touched, or non-local listings.
+    pub evictable: bool,
+}
+
+/// Pure policy: which ids to evict so the total fits in `cap_bytes`, least recently used
+/// first. May return fewer than needed (then the cap simply isn't met) when the rest is
+/// pinned.
+pub(crate) fn ids_to_evict(candidates: &[EvictionCandidate<'_>], cap_bytes: u64) -> Vec<String> {
+    let mut total: u64 = candidates.iter().map(|c| c.bytes).sum();
+    let mut evictable: Vec<&EvictionCandidate<'_>> = candidates.iter().filter(|c| c.evictable).collect();
+    evictable.sort_by_key(|c| c.last_accessed_ms);
+    let mut ids = Vec::new();
+    for candidate in evictable {
+        if total <= cap_bytes {
+            break;
+        }
+        total -= candidate.bytes;
+        ids.push(candidate.id.to_string());
+    }
+    ids
+}
+
+fn supports_relisting(volume_id: &str, path: &Path) -> bool {
+    crate::file_system::volume::backends::archive::archive_boundary_candidate(path).is_none()
+        && !crate::file_system::git::is_virtual(path)
+        && crate::file_system::get_volume_manager()
+            .get(volume_id)
+            .is_some_and(|volume| volume.supports_local_fs_access() && volume.supports_watching())
+}
+
+/// Evicts least-recently-used listings until the cache fits the cap. Called after a listing
+/// lands in the cache and when the cap changes. Returns the evicted ids.
+pub(crate) fn enforce_cap() -> Vec<String> {
+    let cap_bytes = CAP_BYTES.load(Ordering::Relaxed);
+    let now_ms = epoch_millis_now();
+    let grace_ms = ACTIVE_GRACE.as_millis() as u64;
+    let ids = {
+        let cache = LISTING_CACHE.read_ignore_poison();
+        let candidates: Vec<EvictionCandidate<'_>> = cache
+            .iter()
+            .map(|(id, listing)| {
+                let last_accessed_ms = listing.last_accessed_ms.load(Ordering::Relaxed);
+                EvictionCandidate {
+                    id,
+                    bytes: listing_bytes(&listing.entries),
+                    last_accessed_ms,
+                    evictable: now_ms.saturating_sub(last_accessed_ms) >= grace_ms
+                        && !is_displayed(&listing.volume_id, &listing.path)
+                        && !flat::is_flat(id)
+                        && supports_relisting(&listing.volume_id, &listing.path),
+                }
+            })
+            .collect();
+        ids_to_evict(&candidates, cap_bytes)
+    };
+    for id in &ids {
+        evict(id);
+    }
+    if !ids.is_empty() {
+        log::info!(
+            target: "listing_cache",
+            "Evicted {} to stay under the {} MB listing cache cap",
+            crate::pluralize::pluralize(ids.len() as u64, "inactive listing"),
+            cap_bytes / (1024 * 1024),
+        );
+    }
+    ids
+}
+
+/// Drops `listing_id`'s entries and watcher, keeping a tombstone to re-list it from.
+pub(crate) fn evict(listing_id: &str) {
+    let Some(listing) = LISTING_CACHE.write_ignore_poison().remove(listing_id) else {
+        return;
+    };
+    crate::file_system::watcher::stop_watching(listing_id);
+    crate::file_system::listing::diff_emitter::drop_pending(listing_id);
+    EVICTED.lock_ignore_poison().insert(
+        listing_id.to_string(),
+        Tombstone {
+            volume_id: listing.volume_id,
+            path: listing.path,
+            sort_by: listing.sort_by,
+            sort_order: listing.sort_order,
+            directory_sort_mode: listing.directory_sort_mode,
+            sequence: listing.sequence.load(Ordering::Relaxed),
+            evicted_at: Instant::now(),
+            relisting: false,
+        },
+    );
+}
+
+/// Checks `listing_id` against the tombstones; `Ok` when it isn't evicted. Every accessor
+/// that looks a listing up by id calls this first. An evicted id returns [`ListingNotReady`]
+/// and, on the first hit, starts the background re-read. The tombstone lock is only held to
+/// flip the `relisting` flag, never across I/O.
+pub(crate) fn restore_if_evicted(listing_id: &str) -> Result<(), ListingNotReady> {
+    let job = {
+        let mut evicted = EVICTED.lock_ignore_poison();
+        let Some(tombstone) = evicted.get_mut(listing_id) else {
+            return Ok(());
+        };
+        if tombstone.relisting {
+            None
+        } else {
+            tombstone.relisting = true;
+            Some(tombstone.clone())
+        }
+    };
+    if let Some(job) = job {
+        let listing_id = listing_id.to_string();
+        tauri::async_runtime::spawn(async move {
+            let path = job.path.clone();
+            let read = tauri::async_runtime::spawn_blocking(move || read_sorted(job));
+            let entries = match tokio::time::timeout(RELIST_TIMEOUT, read).await {
+                Ok(Ok(Ok(entries))) => Some(entries),
+                Ok(Ok(Err(e))) => {
+                    log::debug!(target: "listing_cache", "Couldn't re-list evicted `{listing_id}` at {path:?}: {e}");
+                    None
+                }
+                Ok(Err(e)) => {
+                    log::warn!(target: "listing_cache", "Re-list task for `{listing_id}` panicked: {e}");
+                    None
+                }
+                Err(_) => {
+                    log::warn!(target: "listing_cache", "Re-listing `{listing_id}` at {path:?} timed out");
+                    None
+                }
+            };
+            finish_relist(&listing_id, entries);
+        });
+    }
+    Err(ListingNotReady(listing_id.to_string()))
+}
+
+/// The blocking half of a re-list: read the folder, enrich it, and sort it as it was.
+fn read_sorted(job: Tombstone) -> Result<Vec<FileEntry>, String> {
+    let mut entries = reading::list_directory_core(&job.path).map_err(|e| e.to_string())?;
+    crate::indexing::enrich_entries_with_index_on_volume(&job.volume_id, &mut entries);
+    capture_date::fill_for_sort(&job.volume_id, job.sort_by, &mut entries);
+    sort_entries(&mut entries, job.sort_by, job.sort_order, job.directory_sort_mode);
+    Ok(entries)
+}
+
+/// Swaps the tombstone for the re-read listing. `None` entries (the folder is gone or the
+/// read timed out) just drop the tombstone, so accessors report "Listing not found" as for
+/// any ended listing. A tombstone that's already gone means the pane ended the listing
+/// meanwhile, and the entries are dropped.
+fn finish_relist(listing_id: &str, entries: Option<Vec<FileEntry>>) {
+    let Some(tombstone) = EVICTED.lock_ignore_poison().remove(listing_id) else {
+        return;
+    };
+    let Some(entries) = entries else {
+        return;
+    };
+    LISTING_CACHE.write_ignore_poison().insert(
+        listing_id.to_string(),
+        CachedListing {
+            volume_id: tombstone.volume_id,
+            path: tombstone.path.clone(),
+            entries,
+            sort_by: tombstone.sort_by,
+            sort_order: tombstone.sort_order,
+            directory_sort_mode: tombstone.directory_sort_mode,
+            generation: next_listing_generation(),
+            sequence: AtomicU64::new(tombstone.sequence),
+            created_at: Instant::now(),
+            last_accessed_ms: AtomicU64::new(epoch_millis_now()),
+        },
+    );
+    if let Err(e) = crate::file_system::watcher::start_watching(listing_id, &tombstone.path) {
+        log::warn!(target: "listing_cache", "Failed to restart watcher for re-listed `{listing_id}`: {e}");
+    }
+    diff_emitter::emit_refetch(listing_id);
+}
+
+/// Forgets an evicted listing's tombstone (its pane ended it).
+pub(crate) fn forget(listing_id: &str) {
+    EVICTED.lock_ignore_poison().remove(listing_id);
+}
+
+/// Drops tombstones older than `max_age`: their pane ended without telling us. Called from
+/// the orphan reaper's sweep.
+pub(crate) fn forget_stale_tombstones(max_age: Duration) {
+    EVICTED
+        .lock_ignore_poison()
+        .retain(|_, tombstone| tombstone.evicted_at.elapsed() < max_age);
+}
+
+/// Current listing cache memory against the cap.
+pub fn get_listing_cache_stats() -> ListingCacheStats {
+    // Tombstones first: `finish_relist` takes them before the cache.
+    let evicted_count = EVICTED.lock_ignore_poison().len();
+    let cache = LISTING_CACHE.read_ignore_poison();
+    ListingCacheStats {
+        listing_count: cache.len(),
+        evicted_count,
+        approx_bytes: cache.values().map(|listing| listing_bytes(&listing.entries)).sum(),
+        cap_bytes: CAP_BYTES.load(Ordering::Relaxed),
+    }
+}
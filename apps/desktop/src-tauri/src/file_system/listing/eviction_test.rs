@@ -0,0 +1,89 @@
+//! Listing cache memory cap tests: the pure LRU policy, and evict → background re-list.
+//!
+//! Nothing here calls `enforce_cap`: it walks the process-global cache and would evict
+//! concurrently-running tests' fixtures. The wired tests drive `evict` and
+//! `restore_if_evicted` on ids they own.
+
+use std::time::{Duration, Instant};
+
+use super::caching::LISTING_CACHE;
+use super::caching_test_support::TestListing;
+use super::eviction::{EvictionCandidate, ListingNotReady, evict, ids_to_evict, listing_bytes, restore_if_evicted};
+use super::metadata::FileEntry;
+use super::operations::get_total_count;
+use crate::ignore_poison::RwLockIgnorePoison;
+
+fn candidate(id: &str, bytes: u64, last_accessed_ms: u64, evictable: bool) -> EvictionCandidate<'_> {
+    EvictionCandidate {
+        id,
+        bytes,
+        last_accessed_ms,
+        evictable,
+    }
+}
+
+#[test]
+fn evicts_least_recently_used_until_under_the_cap() {
+    let candidates = [
+        candidate("newest", 100, 30, true),
+        candidate("oldest", 100, 10, true),
+        candidate("middle", 100, 20, true),
+    ];
+    assert_eq!(ids_to_evict(&candidates, 150), vec!["oldest", "middle"]);
+    assert!(ids_to_evict(&candidates, 300).is_empty());
+}
+
+#[test]
+fn pinned_listings_are_never_evicted_even_over_the_cap() {
+    let candidates = [
+        candidate("displayed", 1_000, 0, false),
+        candidate("background", 100, 50, true),
+    ];
+    assert_eq!(ids_to_evict(&candidates, 10), vec!["background"]);
+}
+
+#[test]
+fn listing_bytes_grow_with_names() {
+    let short = FileEntry::new("a".into(), "/a".into(), false, false);
+    let long = FileEntry::new("a".repeat(200), format!("/{}", "a".repeat(200)), false, false);
+    assert!(listing_bytes(&[long]) > listing_bytes(&[short]) + 300);
+    assert_eq!(listing_bytes(&[]), 0);
+}
+
+/// Polls until `listing_id` is back in the cache; the re-list runs on the blocking pool.
+fn wait_for_relist(listing_id: &str) {
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while !LISTING_CACHE.read_ignore_poison().contains_key(listing_id) {
+        assert!(Instant::now() < deadline, "`{listing_id}` wasn't re-listed");
+        std::thread::sleep(Duration::from_millis(10));
+    }
+}
+
+#[test]
+fn evicted_listing_is_re_listed_in_the_background() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    std::fs::write(dir.path().join("one.txt"), b"1").expect("write");
+    std::fs::write(dir.path().join("two.txt"), b"2").expect("write");
+    let listing = TestListing::new().path(dir.path()).sequence(7).insert("evict-relist");
+
+    evict(listing.id());
+    assert!(!LISTING_CACHE.read_ignore_poison().contains_key(listing.id()));
+
+    // The first access reports "not ready" and starts the re-read; a second one doesn't
+    // start another.
+    assert_eq!(
+        get_total_count(listing.id(), true),
+        Err(ListingNotReady(listing.id().to_string()).to_string())
+    );
+    assert_eq!(
+        restore_if_evicted(listing.id()),
+        Err(ListingNotReady(listing.id().to_string()))
+    );
+
+    // It comes back under the same id, read fresh from disk, with its sequence continued
+    // (7, or 8 once the refetch diff has taken its number).
+    wait_for_relist(listing.id());
+    assert_eq!(get_total_count(listing.id(), true), Ok(2));
+    assert!(listing.with_listing(|l| l.sequence.load(std::sync::atomic::Ordering::Relaxed)) >= 7);
+    assert_eq!(restore_if_evicted(listing.id()), Ok(()));
+}
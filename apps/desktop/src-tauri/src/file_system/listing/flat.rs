@@ -0,0 +1,331 @@
+//! Flattened ("branch") view: everything below a folder as one listing, each entry named by
+//! its path relative to that folder (`src/lib/main.rs`), so the standard pipeline (sorting,
+//! virtual scroll, selection, batch accessors) works on it unchanged.
+//!
+//! **Sources.** When the drive index vouches for the whole subtree
+//! ([`crate::indexing::subtree_entries`]), entries come from one index query and the view is
+//! instant. Otherwise the tree is walked with `std::fs`, depth-first, streaming unsorted
+//! `listing-chunk` events every [`FLAT_CHUNK_SIZE`] entries and checking the listing's cancel
+//! token between folders, so ESC on a deep tree stops promptly. Only volumes with `std::fs`
+//! access can be flattened.
+//!
+//! **Bounds.** `max_depth` limits how far down the walk goes, `exclude_globs` (gitignore
+//! syntax, relative to the root) prunes whole folders before they're read, and
+//! [`MAX_FLAT_ENTRIES`] caps the result so flattening `/` can't exhaust memory.
+//!
+//! **Not live.** A flat listing has no watcher (one watch per folder of a deep tree is too
+//! costly) and is never evicted by the memory cap (`eviction.rs` can only re-list a single
+//! folder). The frontend refreshes it by starting a new one.
+
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, LazyLock, Mutex};
+
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use serde::{Deserialize, Serialize};
+
+use crate::file_system::listing::caching::{CachedListing, LISTING_CACHE, epoch_millis_now};
+use crate::file_system::listing::metadata::FileEntry;
+use crate::file_system::listing::reading::process_dir_entry;
+use crate::file_system::listing::sorting::{DirectorySortMode, SortColumn, SortOrder, sort_entries};
+use crate::file_system::listing::streaming::{
+    ListingEventSink, ListingStatus, StreamingListingStartResult, StreamingListingState, TauriListingEventSink,
+    register_streaming_state, unregister_streaming_state,
+};
+use crate::file_system::listing::{capture_date, eviction};
+use crate::ignore_poison::{IgnorePoison, RwLockIgnorePoison};
+
+/// Depth used when the caller passes 0.
+pub const DEFAULT_MAX_DEPTH: u32 = 16;
+
+/// Hard cap on `max_depth`.
+const MAX_MAX_DEPTH: u32 = 64;
+
+/// Entries per `listing-chunk` event during a walk.
+pub(crate) const FLAT_CHUNK_SIZE: usize = 2_000;
+
+/// Most entries a flat listing holds. The walk stops there and logs it.
+pub const MAX_FLAT_ENTRIES: usize = 500_000;
+
+/// How to flatten. Every field has a default, so the frontend can send `{}`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct FlatListingOptions {
+    /// Levels below the root to include: 1 is the root's children only. 0 picks
+    /// [`DEFAULT_MAX_DEPTH`]; anything above 64 is clamped.
+    pub max_depth: u32,
+    /// Gitignore-style patterns (`node_modules/`, `*.log`, `build/**`) matched against paths
+    /// relative to the root. An excluded folder isn't descended into.
+    pub exclude_globs: Vec<String>,
+    /// Whether dotfiles and dot-folders (and everything below them) are included.
+    pub include_hidden: bool,
+    /// Whether folders get a row of their own. Off by default: a branch view lists files.
+    pub include_directories: bool,
+}
+
+impl FlatListingOptions {
+    fn depth(&self) -> u32 {
+        match self.max_depth {
+            0 => DEFAULT_MAX_DEPTH,
+            n => n.min(MAX_MAX_DEPTH),
+        }
+    }
+}
+
+/// Ids of cached listings that are flat, so eviction leaves them alone.
+static FLAT_LISTINGS: LazyLock<Mutex<HashSet<String>>> = LazyLock::new(|| Mutex::new(HashSet::new()));
+
+/// Whether `listing_id` is a flat listing.
+pub(crate) fn is_flat(listing_id: &str) -> bool {
+    FLAT_LISTINGS.lock_ignore_poison().contains(listing_id)
+}
+
+/// Forgets that `listing_id` was flat (its pane ended it).
+pub(crate) fn forget(listing_id: &str) {
+    FLAT_LISTINGS.lock_ignore_poison().remove(listing_id);
+}
+
+/// Starts flattening `path` in the background and returns right away, like
+/// `list_directory_start_streaming`. Emits `listing-opening`, unsorted `listing-chunk`s while
+/// walking, `listing-read-complete`, then `listing-complete` (or `listing-error` /
+/// `listing-cancelled`). `cancel_listing` stops it.
+#[allow(clippy::too_many_arguments, reason = "Mirrors list_directory_start_streaming")]
+pub fn list_directory_flat_start(
+    app: tauri::AppHandle,
+    volume_id: &str,
+    path: &Path,
+    options: FlatListingOptions,
+    sort_by: SortColumn,
+    sort_order: SortOrder,
+    dir_sort_mode: DirectorySortMode,
+    listing_id: String,
+) -> StreamingListingStartResult {
+    let state = register_streaming_state(&listing_id);
+    let events: Arc<dyn ListingEventSink> = Arc::new(TauriListingEventSink::new(app));
+    let volume_id = volume_id.to_string();
+    let path = path.to_path_buf();
+    let id = listing_id.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        if let Err(message) = read_flat(
+            events.as_ref(),
+            &id,
+            &state,
+            &volume_id,
+            &path,
+            &options,
+            sort_by,
+            sort_order,
+            dir_sort_mode,
+        ) {
+            events.emit_error(&id, message, None);
+        }
+        unregister_streaming_state(&id, &state);
+    });
+    StreamingListingStartResult {
+        listing_id,
+        status: ListingStatus::Loading,
+    }
+}
+
+/// The blocking body of [`list_directory_flat_start`]: reads the flat entries, sorts them,
+/// and caches them under `listing_id`. Returns `Err` only for failures worth a
+/// `listing-error` (unknown or non-local volume, unreadable root, bad exclude pattern); a
+/// cancel emits `listing-cancelled` and returns `Ok`.
+#[allow(
+    clippy::too_many_arguments,
+    reason = "Streaming operation requires many state parameters"
+)]
+pub(crate) fn read_flat(
+    events: &dyn ListingEventSink,
+    listing_id: &str,
+    state: &StreamingListingState,
+    volume_id: &str,
+    root: &Path,
+    options: &FlatListingOptions,
+    sort_by: SortColumn,
+    sort_order: SortOrder,
+    dir_sort_mode: DirectorySortMode,
+) -> Result<(), String> {
+    events.emit_opening(listing_id);
+    let volume = crate::file_system::get_volume_manager()
+        .get(volume_id)
+        .ok_or_else(|| format!("Volume not found: {volume_id}"))?;
+    if !volume.supports_local_fs_access() {
+        return Err("This volume can't be shown as a flat list".to_string());
+    }
+    let excludes = build_excludes(root, &options.exclude_globs)?;
+
+    let mut entries = match from_index(root, options, &excludes) {
+        Some(entries) => entries,
+        None => {
+            let on_chunk = |chunk: Vec<FileEntry>, loaded_count: usize| {
+                if !state.cancelled.load(Ordering::Relaxed) {
+                    events.emit_chunk(listing_id, chunk, loaded_count, false);
+                }
+            };
+            walk(root, options, &excludes, &state.cancelled, on_chunk)
+                .map_err(|e| format!("Couldn't read {}: {e}", root.display()))?
+        }
+    };
+    if state.cancelled.load(Ordering::Relaxed) {
+        events.emit_cancelled(listing_id);
+        return Ok(());
+    }
+    events.emit_read_complete(listing_id, entries.len());
+
+    crate::indexing::enrich_entries_with_index_on_volume(volume_id, &mut entries);
+    capture_date::fill_for_sort(volume_id, sort_by, &mut entries);
+    sort_entries(&mut entries, sort_by, sort_order, dir_sort_mode);
+    let total_count = entries.len();
+
+    {
+        // Cancel check under the cache lock, as in `read_directory_with_progress`.
+        let mut cache = LISTING_CACHE.write_ignore_poison();
+        if state.cancelled.load(Ordering::Relaxed) {
+            events.emit_cancelled(listing_id);
+            return Ok(());
+        }
+        FLAT_LISTINGS.lock_ignore_poison().insert(listing_id.to_string());
+        cache.insert(
+            listing_id.to_string(),
+            CachedListing {
+                volume_id: volume_id.to_string(),
+                path: root.to_path_buf(),
+                entries,
+                sort_by,
+                sort_order,
+                directory_sort_mode: dir_sort_mode,
+                generation: state.generation,
+                sequence: std::sync::atomic::AtomicU64::new(0),
+                created_at: std::time::Instant::now(),
+                last_accessed_ms: std::sync::atomic::AtomicU64::new(epoch_millis_now()),
+            },
+        );
+    }
+    eviction::enforce_cap();
+    events.emit_complete(listing_id, total_count, volume.root().to_string_lossy().to_string());
+    Ok(())
+}
+
+/// Compiles `globs` into one matcher rooted at `root`.
+pub(crate) fn build_excludes(root: &Path, globs: &[String]) -> Result<Gitignore, String> {
+    let mut builder = GitignoreBuilder::new(root);
+    for glob in globs.iter().map(|g| g.trim()).filter(|g| !g.is_empty()) {
+        builder
+            .add_line(None, glob)
+            .map_err(|e| format!("Invalid exclude pattern `{glob}`: {e}"))?;
+    }
+    builder.build().map_err(|e| format!("Invalid exclude patterns: {e}"))
+}
+
+/// Reads the flat entries from the drive index, or `None` when the index can't vouch for
+/// the whole subtree (not indexed, or changed since the last scan) and the caller must walk.
+fn from_index(root: &Path, options: &FlatListingOptions, excludes: &Gitignore) -> Option<Vec<FileEntry>> {
+    let indexed = match crate::indexing::subtree_entries(&root.to_string_lossy()) {
+        Ok(indexed) => indexed?,
+        Err(e) => {
+            log::debug!(target: "listing::flat", "Index lookup failed for {root:?}, walking instead: {e}");
+            return None;
+        }
+    };
+    let max_depth = options.depth() as usize;
+    let mut entries = Vec::new();
+    for item in indexed {
+        let rel = item.relative_path.as_str();
+        if rel.split('/').count() > max_depth
+            || (!options.include_hidden && rel.split('/').any(|c| c.starts_with('.')))
+            || excludes.matched_path_or_any_parents(rel, item.is_directory).is_ignore()
+            || (item.is_directory && !options.include_directories)
+        {
+            continue;
+        }
+        if entries.len() == MAX_FLAT_ENTRIES {
+            log_truncated(root);
+            break;
+        }
+        entries.push(FileEntry {
+            size: if item.is_directory { None } else { item.logical_size },
+            modified_at: item.modified_at,
+            ..FileEntry::new(
+                rel.to_string(),
+                root.join(rel).to_string_lossy().to_string(),
+                item.is_directory,
+                item.is_symlink,
+            )
+        });
+    }
+    Some(entries)
+}
+
+/// Walks `root` depth-first, up to `options.depth()` levels. Calls `on_chunk(chunk,
+/// loaded_count)` every [`FLAT_CHUNK_SIZE`] entries and once more for the rest. Folders
+/// below the root that can't be read are skipped; an unreadable root is an error. Stops
+/// early (returning what it has) once `cancelled` is set.
+pub(crate) fn walk(
+    root: &Path,
+    options: &FlatListingOptions,
+    excludes: &Gitignore,
+    cancelled: &AtomicBool,
+    mut on_chunk: impl FnMut(Vec<FileEntry>, usize),
+) -> std::io::Result<Vec<FileEntry>> {
+    let max_depth = options.depth();
+    let mut entries = Vec::new();
+    let mut emitted = 0;
+    // (folder, its path relative to root with a trailing `/`, its depth)
+    let mut stack = vec![(root.to_path_buf(), String::new(), 1u32)];
+    'walk: while let Some((dir, prefix, depth)) = stack.pop() {
+        if cancelled.load(Ordering::Relaxed) {
+            break;
+        }
+        let read_dir = match std::fs::read_dir(&dir) {
+            Ok(read_dir) => read_dir,
+            Err(e) if prefix.is_empty() => return Err(e),
+            Err(e) => {
+                log::debug!(target: "listing::flat", "Skipping unreadable folder {dir:?}: {e}");
+                continue;
+            }
+        };
+        for dir_entry in read_dir.flatten() {
+            let Some(mut entry) = process_dir_entry(&dir_entry) else {
+                continue;
+            };
+            if !options.include_hidden && entry.name.starts_with('.') {
+                continue;
+            }
+            let rel = format!("{prefix}{}", entry.name);
+            if excludes.matched(&rel, entry.is_directory).is_ignore() {
+                continue;
+            }
+            // Symlinked folders stay leaves: following them risks cycles and leaving the tree.
+            let descend = entry.is_directory && !entry.is_symlink && depth < max_depth;
+            if descend {
+                stack.push((dir_entry.path(), format!("{rel}/"), depth + 1));
+            }
+            if entry.is_directory && !options.include_directories {
+                continue;
+            }
+            if entries.len() == MAX_FLAT_ENTRIES {
+                log_truncated(root);
+                break 'walk;
+            }
+            entry.name = rel;
+            entries.push(entry);
+            if entries.len() - emitted == FLAT_CHUNK_SIZE {
+                on_chunk(entries[emitted..].to_vec(), entries.len());
+                emitted = entries.len();
+            }
+        }
+    }
+    if entries.len() > emitted {
+        on_chunk(entries[emitted..].to_vec(), entries.len());
+    }
+    Ok(entries)
+}
+
+fn log_truncated(root: &Path) {
+    log::warn!(
+        target: "listing::flat",
+        "Flat listing of {root:?} stopped at {MAX_FLAT_ENTRIES} entries"
+    );
+}
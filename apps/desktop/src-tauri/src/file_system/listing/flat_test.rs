@@ -0,0 +1,170 @@
+//! Flat listing tests: the walk's bounds (depth, excludes, hidden), chunking, cancellation,
+//! and a full `read_flat` into the cache.
+
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+
+use crate::file_system::listing::caching::LISTING_CACHE;
+use crate::file_system::listing::flat::{
+    FLAT_CHUNK_SIZE, FlatListingOptions, build_excludes, is_flat, read_flat, walk,
+};
+use crate::file_system::listing::metadata::FileEntry;
+use crate::file_system::listing::operations::{get_file_range, list_directory_end};
+use crate::file_system::listing::sorting::{DirectorySortMode, SortColumn, SortOrder};
+use crate::file_system::listing::streaming::{CollectorListingEventSink, StreamingListingState};
+use crate::file_system::volume::LocalPosixVolume;
+use crate::ignore_poison::{IgnorePoison, RwLockIgnorePoison};
+
+fn tree() -> tempfile::TempDir {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let root = dir.path();
+    for folder in ["src/lib", "node_modules/pkg", ".git"] {
+        std::fs::create_dir_all(root.join(folder)).expect("mkdir");
+    }
+    for file in [
+        "README.md",
+        "src/main.rs",
+        "src/lib/util.rs",
+        "node_modules/pkg/index.js",
+        ".git/HEAD",
+        "debug.log",
+    ] {
+        std::fs::write(root.join(file), b"x").expect("write");
+    }
+    dir
+}
+
+fn names(entries: &[FileEntry]) -> Vec<String> {
+    let mut names: Vec<String> = entries.iter().map(|e| e.name.clone()).collect();
+    names.sort();
+    names
+}
+
+fn walk_names(root: &Path, options: &FlatListingOptions) -> Vec<String> {
+    let excludes = build_excludes(root, &options.exclude_globs).expect("excludes");
+    names(&walk(root, options, &excludes, &AtomicBool::new(false), |_, _| {}).expect("walk"))
+}
+
+#[test]
+fn walk_lists_files_by_relative_path() {
+    let dir = tree();
+    assert_eq!(
+        walk_names(dir.path(), &FlatListingOptions::default()),
+        vec![
+            "README.md",
+            "debug.log",
+            "node_modules/pkg/index.js",
+            "src/lib/util.rs",
+            "src/main.rs"
+        ]
+    );
+}
+
+#[test]
+fn walk_honors_depth_excludes_and_hidden() {
+    let dir = tree();
+    let shallow = FlatListingOptions {
+        max_depth: 2,
+        exclude_globs: vec!["node_modules/".into(), "*.log".into()],
+        ..FlatListingOptions::default()
+    };
+    assert_eq!(walk_names(dir.path(), &shallow), vec!["README.md", "src/main.rs"]);
+
+    let everything = FlatListingOptions {
+        include_hidden: true,
+        include_directories: true,
+        ..FlatListingOptions::default()
+    };
+    let all = walk_names(dir.path(), &everything);
+    assert!(all.contains(&".git/HEAD".to_string()), "got {all:?}");
+    assert!(all.contains(&"src/lib".to_string()), "got {all:?}");
+}
+
+#[test]
+fn invalid_exclude_pattern_is_an_error() {
+    assert!(build_excludes(Path::new("/tmp"), &["[unclosed".into()]).is_err());
+}
+
+#[test]
+fn walk_streams_full_chunks_then_the_rest() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    for i in 0..FLAT_CHUNK_SIZE + 5 {
+        std::fs::write(dir.path().join(format!("f{i}")), b"").expect("write");
+    }
+    let excludes = build_excludes(dir.path(), &[]).expect("excludes");
+    let mut chunks = Vec::new();
+    let entries = walk(
+        dir.path(),
+        &FlatListingOptions::default(),
+        &excludes,
+        &AtomicBool::new(false),
+        |chunk, loaded| chunks.push((chunk.len(), loaded)),
+    )
+    .expect("walk");
+    assert_eq!(entries.len(), FLAT_CHUNK_SIZE + 5);
+    assert_eq!(
+        chunks,
+        vec![(FLAT_CHUNK_SIZE, FLAT_CHUNK_SIZE), (5, FLAT_CHUNK_SIZE + 5)]
+    );
+}
+
+#[test]
+fn cancelled_walk_stops_before_reading() {
+    let dir = tree();
+    let excludes = build_excludes(dir.path(), &[]).expect("excludes");
+    let entries = walk(
+        dir.path(),
+        &FlatListingOptions::default(),
+        &excludes,
+        &AtomicBool::new(true),
+        |_, _| {},
+    )
+    .expect("walk");
+    assert!(entries.is_empty());
+}
+
+#[test]
+fn read_flat_caches_the_listing() {
+    let dir = tree();
+    let volume_id = format!("flat-test-{}", uuid::Uuid::new_v4());
+    let listing_id = format!("flat-listing-{}", uuid::Uuid::new_v4());
+    crate::file_system::get_volume_manager()
+        .register(&volume_id, Arc::new(LocalPosixVolume::new("Flat test", dir.path())));
+    let events = CollectorListingEventSink::new();
+    let state = StreamingListingState {
+        cancelled: Arc::new(AtomicBool::new(false)),
+        cancel_notify: tokio::sync::Notify::new(),
+        generation: 0,
+    };
+    let options = FlatListingOptions {
+        exclude_globs: vec!["node_modules".into()],
+        ..FlatListingOptions::default()
+    };
+
+    read_flat(
+        &events,
+        &listing_id,
+        &state,
+        &volume_id,
+        dir.path(),
+        &options,
+        SortColumn::Name,
+        SortOrder::Ascending,
+        DirectorySortMode::default(),
+    )
+    .expect("read_flat");
+
+    assert_eq!(
+        events.complete.lock_ignore_poison().as_slice(),
+        [(listing_id.clone(), 4)]
+    );
+    assert!(is_flat(&listing_id));
+    let range = get_file_range(&listing_id, 0, 10, true).expect("range");
+    assert_eq!(range.len(), 4);
+    assert!(LISTING_CACHE.read_ignore_poison().contains_key(&listing_id));
+
+    list_directory_end(&listing_id);
+    assert!(!is_flat(&listing_id));
+    crate::file_system::get_volume_manager().unregister(&volume_id);
+}
@@ -32,6 +32,7 @@ use nucleo_matcher::{
 
 use crate::file_system::listing::caching::LISTING_CACHE;
 use crate::file_system::listing::metadata::FileEntry;
+use crate::file_system::listing::{eviction, git_ignore};
 
 /// Returns the **visible-space** index of the highest-scoring fuzzy match for `query`,
 /// or `None` if no entry matches.
@@ -71,7 +72,9 @@ pub fn find_first_match(entries: &[FileEntry], query: &str, include_hidden: bool
     // Iterate the visible sequence directly so the returned index matches the
     // cursor space used by `getFileAt` / `getFileRange` (which iterate via
     // `visible_entries(...).nth(index)` in `operations.rs`).
-    let visible = entries.iter().filter(|e| include_hidden || !e.name.starts_with('.'));
+    let visible = entries
+        .iter()
+        .filter(|e| (include_hidden || !e.name.starts_with('.')) && !git_ignore::hides(e));
 
     for (visible_idx, entry) in visible.enumerate() {
         let haystack = Utf32Str::new(&entry.name, &mut haystack_buf);
@@ -99,6 +102,7 @@ pub fn fuzzy_find_first_match_in_listing(
     include_hidden: bool,
 ) -> Result<Option<usize>, String> {
     let started = Instant::now();
+    eviction::restore_if_evicted(listing_id)?;
     let cache = LISTING_CACHE
         .read()
         .map_err(|_| "Failed to acquire cache lock".to_string())?;
@@ -121,6 +125,78 @@ pub fn fuzzy_find_first_match_in_listing(
     Ok(result)
 }
 
+/// Returns the **visible-space** index of the first entry whose name starts with `prefix`
+/// (case-insensitive), searching from `from_index` (inclusive) to the end of the listing and then
+/// wrapping around to the start. `None` when nothing matches or `prefix` is empty.
+///
+/// This is the "type to select" counterpart to the fuzzy [`find_first_match`]: deterministic
+/// prefix semantics, in the listing's current sort order (the cached entries are already sorted).
+/// To cycle through matches, call again with `from_index` = previous result + 1. A `from_index`
+/// past the end wraps to the start. Index space and hidden-file handling match `find_first_match`.
+pub fn find_next_prefix_match(
+    entries: &[FileEntry],
+    prefix: &str,
+    from_index: usize,
+    include_hidden: bool,
+) -> Option<usize> {
+    if prefix.is_empty() {
+        return None;
+    }
+    let matching: Vec<usize> = entries
+        .iter()
+        .filter(|e| (include_hidden || !e.name.starts_with('.')) && !git_ignore::hides(e))
+        .enumerate()
+        .filter(|(_, e)| starts_with_ignore_case(&e.name, prefix))
+        .map(|(visible_idx, _)| visible_idx)
+        .collect();
+    // `matching` is ascending, so the first hit at or after `from_index` wins; none means wrap.
+    matching
+        .iter()
+        .copied()
+        .find(|&idx| idx >= from_index)
+        .or_else(|| matching.first().copied())
+}
+
+/// Unicode-aware case-insensitive `starts_with` that doesn't allocate per entry.
+fn starts_with_ignore_case(name: &str, prefix: &str) -> bool {
+    let mut name_chars = name.chars().flat_map(char::to_lowercase);
+    prefix
+        .chars()
+        .flat_map(char::to_lowercase)
+        .all(|p| name_chars.next() == Some(p))
+}
+
+/// Lock-and-delegate wrapper for [`find_next_prefix_match`], like
+/// [`fuzzy_find_first_match_in_listing`].
+pub fn find_next_match_in_listing(
+    listing_id: &str,
+    prefix: &str,
+    from_index: usize,
+    include_hidden: bool,
+) -> Result<Option<usize>, String> {
+    let started = Instant::now();
+    eviction::restore_if_evicted(listing_id)?;
+    let cache = LISTING_CACHE
+        .read()
+        .map_err(|_| "Failed to acquire cache lock".to_string())?;
+
+    let listing = cache
+        .get(listing_id)
+        .ok_or_else(|| format!("Listing not found: {}", listing_id))?;
+
+    let result = find_next_prefix_match(&listing.entries, prefix, from_index, include_hidden);
+    log::debug!(
+        target: "type_to_jump",
+        "listing_id={} prefix_len={} from_index={} result_index={:?} elapsed_us={}",
+        listing_id,
+        prefix.chars().count(),
+        from_index,
+        result,
+        started.elapsed().as_micros(),
+    );
+    Ok(result)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -247,4 +323,32 @@ mod tests {
         let idx = find_first_match(&entries, "resume", true).expect("should match");
         assert_eq!(idx, 1, "ASCII 'resume' should fold into 'Résumé.pdf'");
     }
+
+    #[test]
+    fn prefix_match_searches_from_index_and_wraps() {
+        let entries = vec![entry("alpha"), entry("beta"), entry("Alps"), entry("gamma")];
+        assert_eq!(find_next_prefix_match(&entries, "al", 0, true), Some(0));
+        assert_eq!(find_next_prefix_match(&entries, "al", 1, true), Some(2));
+        // Past the last match: wraps back to the first one.
+        assert_eq!(find_next_prefix_match(&entries, "al", 3, true), Some(0));
+        assert_eq!(find_next_prefix_match(&entries, "al", 99, true), Some(0));
+    }
+
+    #[test]
+    fn prefix_match_is_not_fuzzy_and_empty_prefix_matches_nothing() {
+        let entries = vec![entry("README.md"), entry("Cargo.toml")];
+        // "rdm" fuzzy-matches README but isn't a prefix of anything.
+        assert_eq!(find_next_prefix_match(&entries, "rdm", 0, true), None);
+        assert_eq!(find_next_prefix_match(&entries, "", 0, true), None);
+        assert_eq!(find_next_prefix_match(&entries, "readme.md.bak", 0, true), None);
+    }
+
+    #[test]
+    fn prefix_match_uses_visible_space_indices() {
+        let entries = vec![entry(".bashrc"), entry("bin"), entry("boot")];
+        assert_eq!(find_next_prefix_match(&entries, "b", 0, false), Some(0));
+        assert_eq!(find_next_prefix_match(&entries, "b", 1, false), Some(1));
+        // With hidden shown, `.bashrc` doesn't start with "b" but shifts the indices.
+        assert_eq!(find_next_prefix_match(&entries, "b", 0, true), Some(1));
+    }
 }
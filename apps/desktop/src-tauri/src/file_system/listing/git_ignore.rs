@@ -0,0 +1,250 @@
+//! `.gitignore` marking for local listings.
+//!
+//! With the `fileExplorer.git.ignoredFiles` setting on, every entry a local listing reads
+//! gets `FileEntry::is_git_ignored` from the rules git itself would apply: each `.gitignore`
+//! from the listed folder up to the repo root (nearest wins), `.git/info/exclude`, and the
+//! global excludes file. Matching uses the `ignore` crate's gitignore engine. `Dim` only sets
+//! the flag (the frontend greys those rows out); `Hide` also drops them from every read, like
+//! dotfiles with hidden files off, but independently of that toggle.
+//!
+//! The compiled rules are cached per folder. The watcher drops a folder's entry (and its
+//! subfolders') when a `.gitignore` in it changes, then re-reads the listing so the flags
+//! follow. Edits to a parent folder's `.gitignore` show up on the next navigation.
+
+use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::{Arc, LazyLock, Mutex};
+
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use serde::{Deserialize, Serialize};
+
+use crate::file_system::listing::caching::LISTING_CACHE;
+use crate::file_system::listing::metadata::FileEntry;
+use crate::file_system::watcher::DiffChange;
+
+/// How listings treat entries the repo's ignore rules match.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub enum GitIgnoredMode {
+    /// Don't read ignore files at all. `is_git_ignored` stays `false`.
+    #[default]
+    Off,
+    /// Flag ignored entries so the frontend can grey them out.
+    Dim,
+    /// Flag ignored entries and leave them out of the listing.
+    Hide,
+}
+
+impl GitIgnoredMode {
+    fn from_u8(value: u8) -> Self {
+        match value {
+            1 => Self::Dim,
+            2 => Self::Hide,
+            _ => Self::Off,
+        }
+    }
+
+    fn as_u8(self) -> u8 {
+        match self {
+            Self::Off => 0,
+            Self::Dim => 1,
+            Self::Hide => 2,
+        }
+    }
+}
+
+/// Set from the `fileExplorer.git.ignoredFiles` setting at startup and on every change.
+static MODE: AtomicU8 = AtomicU8::new(0);
+
+/// Compiled rules per listed folder; `None` for folders outside any repo.
+static MATCHERS: LazyLock<Mutex<HashMap<PathBuf, Option<Arc<DirMatcher>>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// The cache is cleared wholesale past this many folders. Navigation revisits few folders,
+/// and a rebuild is a handful of small file reads.
+const MAX_CACHED_DIRS: usize = 256;
+
+pub fn git_ignored_mode() -> GitIgnoredMode {
+    GitIgnoredMode::from_u8(MODE.load(Ordering::Relaxed))
+}
+
+/// Whether reads should leave `entry` out under the current mode.
+pub(crate) fn hides(entry: &FileEntry) -> bool {
+    entry.is_git_ignored && git_ignored_mode() == GitIgnoredMode::Hide
+}
+
+/// Sets the mode and brings open local listings in line: flags are recomputed in place, and
+/// each listing whose visible rows changed gets a `modify` diff, which makes the frontend
+/// refetch its count and rows.
+pub fn set_git_ignored_mode(mode: GitIgnoredMode) {
+    let previous = GitIgnoredMode::from_u8(MODE.swap(mode.as_u8(), Ordering::Relaxed));
+    if previous == mode {
+        return;
+    }
+    let hide_changed = (previous == GitIgnoredMode::Hide) != (mode == GitIgnoredMode::Hide);
+    // Rules cached before the mode went off may be stale by now.
+    if let Ok(mut matchers) = MATCHERS.lock() {
+        matchers.clear();
+    }
+
+    // Build the matchers before taking the cache's write lock: it means reading files.
+    let listings: Vec<(String, String, PathBuf)> = match LISTING_CACHE.read() {
+        Ok(cache) => cache
+            .iter()
+            .map(|(id, listing)| (id.clone(), listing.volume_id.clone(), listing.path.clone()))
+            .collect(),
+        Err(_) => return,
+    };
+    let matchers: Vec<(String, Option<Arc<DirMatcher>>)> = listings
+        .into_iter()
+        .filter(|(_, volume_id, _)| is_local_volume(volume_id))
+        .map(|(id, _, path)| {
+            let matcher = if mode == GitIgnoredMode::Off {
+                None
+            } else {
+                matcher_for(&path)
+            };
+            (id, matcher)
+        })
+        .collect();
+
+    let mut diffs: Vec<(String, Vec<DiffChange>)> = Vec::new();
+    if let Ok(mut cache) = LISTING_CACHE.write() {
+        for (listing_id, matcher) in matchers {
+            let Some(listing) = cache.get_mut(&listing_id) else {
+                continue;
+            };
+            let mut changes = Vec::new();
+            for (index, entry) in listing.entries.iter_mut().enumerate() {
+                let ignored = matcher
+                    .as_ref()
+                    .is_some_and(|m| m.is_ignored(Path::new(&entry.path), entry.is_directory));
+                if ignored != entry.is_git_ignored || (hide_changed && ignored) {
+                    entry.is_git_ignored = ignored;
+                    changes.push(DiffChange {
+                        change_type: "modify".to_string(),
+                        entry: entry.clone(),
+                        index,
+                    });
+                }
+            }
+            if !changes.is_empty() {
+                diffs.push((listing_id, changes));
+            }
+        }
+    }
+    for (listing_id, changes) in diffs {
+        crate::file_system::listing::diff_emitter::enqueue_diff(&listing_id, changes);
+    }
+}
+
+fn is_local_volume(volume_id: &str) -> bool {
+    crate::file_system::get_volume_manager()
+        .get(volume_id)
+        .is_some_and(|v| v.local_path().is_some())
+}
+
+/// The rules for `dir`'s children, or `None` with the mode off or outside a repo. Only for
+/// folders on the local file system. Fetch once per folder read, then `mark` each batch.
+pub(crate) fn active_matcher(dir: &Path) -> Option<Arc<DirMatcher>> {
+    if git_ignored_mode() == GitIgnoredMode::Off {
+        return None;
+    }
+    matcher_for(dir)
+}
+
+/// Drops the cached rules for `dir` and every folder below it. Called by the watcher when a
+/// `.gitignore` in `dir` changes.
+pub(crate) fn invalidate(dir: &Path) {
+    if let Ok(mut matchers) = MATCHERS.lock() {
+        matchers.retain(|cached, _| !cached.starts_with(dir));
+    }
+}
+
+fn matcher_for(dir: &Path) -> Option<Arc<DirMatcher>> {
+    if let Ok(matchers) = MATCHERS.lock()
+        && let Some(cached) = matchers.get(dir)
+    {
+        return cached.clone();
+    }
+    let built = DirMatcher::build(dir).map(Arc::new);
+    if let Ok(mut matchers) = MATCHERS.lock() {
+        if matchers.len() >= MAX_CACHED_DIRS {
+            matchers.clear();
+        }
+        matchers.insert(dir.to_path_buf(), built.clone());
+    }
+    built
+}
+
+/// Every ignore rule set that applies to one folder's children, nearest first.
+pub(crate) struct DirMatcher {
+    layers: Vec<Gitignore>,
+}
+
+impl DirMatcher {
+    /// Collects the rule sets for `dir`, or `None` when it isn't inside a work tree (or is
+    /// inside `.git` itself, or doesn't exist on disk, like a folder inside an archive).
+    pub(crate) fn build(dir: &Path) -> Option<Self> {
+        if !dir.is_dir() || dir.components().any(|c| c == Component::Normal(".git".as_ref())) {
+            return None;
+        }
+        let repo_root = dir.ancestors().find(|a| a.join(".git").exists())?;
+
+        let mut layers = Vec::new();
+        for folder in dir.ancestors() {
+            let file = folder.join(".gitignore");
+            if file.is_file() {
+                let (rules, error) = Gitignore::new(&file);
+                if let Some(e) = error {
+                    log::debug!("git_ignore: partly unreadable {}: {}", file.display(), e);
+                }
+                layers.push(rules);
+            }
+            if folder == repo_root {
+                break;
+            }
+        }
+
+        let mut exclude = GitignoreBuilder::new(repo_root);
+        if let Some(e) = exclude.add(repo_root.join(".git/info/exclude"))
+            && e.io_error().is_none_or(|io| io.kind() != std::io::ErrorKind::NotFound)
+        {
+            log::debug!("git_ignore: .git/info/exclude in {}: {}", repo_root.display(), e);
+        }
+        if let Ok(rules) = exclude.build() {
+            layers.push(rules);
+        }
+        let (global, _) = GitignoreBuilder::new(repo_root).build_global();
+        layers.push(global);
+
+        layers.retain(|l| !l.is_empty());
+        Some(Self { layers })
+    }
+
+    /// Sets `is_git_ignored` on `entries`, which must be children of this matcher's folder.
+    pub(crate) fn mark(&self, entries: &mut [FileEntry]) {
+        for entry in entries {
+            entry.is_git_ignored = self.is_ignored(Path::new(&entry.path), entry.is_directory);
+        }
+    }
+
+    /// Whether git would ignore `path`. The nearest rule set with a verdict decides, so a
+    /// `!keep.log` in a subfolder beats `*.log` at the root.
+    pub(crate) fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        for layer in &self.layers {
+            if !path.starts_with(layer.path()) {
+                continue;
+            }
+            let verdict = layer.matched_path_or_any_parents(path, is_dir);
+            if verdict.is_ignore() {
+                return true;
+            }
+            if verdict.is_whitelist() {
+                return false;
+            }
+        }
+        false
+    }
+}
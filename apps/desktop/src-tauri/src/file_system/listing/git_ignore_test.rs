@@ -0,0 +1,37 @@
+//! Tests for `.gitignore` marking (`git_ignore.rs`).
+
+use super::git_ignore::DirMatcher;
+
+#[test]
+fn nearest_gitignore_wins_and_ignored_parents_cover_children() {
+    let tmp = tempfile::tempdir().unwrap();
+    let root = tmp.path();
+    std::fs::create_dir(root.join(".git")).unwrap();
+    std::fs::write(root.join(".gitignore"), "*.log\nbuild/\n").unwrap();
+    std::fs::create_dir_all(root.join("src")).unwrap();
+    std::fs::write(root.join("src/.gitignore"), "!keep.log\n").unwrap();
+    std::fs::create_dir_all(root.join("build/out")).unwrap();
+
+    let top = DirMatcher::build(root).unwrap();
+    assert!(top.is_ignored(&root.join("debug.log"), false));
+    assert!(top.is_ignored(&root.join("build"), true));
+    assert!(!top.is_ignored(&root.join("src"), true));
+
+    let src = DirMatcher::build(&root.join("src")).unwrap();
+    assert!(src.is_ignored(&root.join("src/other.log"), false));
+    assert!(!src.is_ignored(&root.join("src/keep.log"), false));
+
+    let nested = DirMatcher::build(&root.join("build/out")).unwrap();
+    assert!(nested.is_ignored(&root.join("build/out/app"), false));
+}
+
+#[test]
+fn folders_outside_a_work_tree_or_inside_git_have_no_matcher() {
+    let tmp = tempfile::tempdir().unwrap();
+    std::fs::write(tmp.path().join(".gitignore"), "*\n").unwrap();
+    assert!(DirMatcher::build(tmp.path()).is_none());
+
+    std::fs::create_dir_all(tmp.path().join(".git/refs")).unwrap();
+    assert!(DirMatcher::build(&tmp.path().join(".git/refs")).is_none());
+    assert!(DirMatcher::build(&tmp.path().join("missing")).is_none());
+}
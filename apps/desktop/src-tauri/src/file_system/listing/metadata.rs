@@ -6,6 +6,8 @@ use std::path::Path;
 use std::sync::{LazyLock, RwLock};
 use uzers::{get_group_by_gid, get_user_by_uid};
 
+use crate::file_system::sync_status::SyncStatus;
+
 /// Cache for uid→username resolution.
 pub(crate) static OWNER_CACHE: LazyLock<RwLock<HashMap<u32, String>>> = LazyLock::new(|| RwLock::new(HashMap::new()));
 /// Cache for gid→groupname resolution.
@@ -131,6 +133,10 @@ pub struct FileEntry {
     pub added_at: Option<u64>,
     /// When the file was last opened (macOS only)
     pub opened_at: Option<u64>,
+    /// When the photo was taken (EXIF `DateTimeOriginal`), for image files only. Filled
+    /// by the extended-metadata load and, on demand, for the Captured sort. `None` for
+    /// non-images, images without an EXIF date, and entries not enriched yet.
+    pub captured_at: Option<u64>,
     pub permissions: u32,
     pub owner: String,
     pub group: String,
@@ -188,6 +194,16 @@ pub struct FileEntry {
     /// `display_size` is set. Example: "12 commits ahead, 3 commits behind
     /// `origin/main`". Doubles as the aria-label for screen readers.
     pub display_size_tooltip: Option<String>,
+    /// Whether the repo's ignore rules (`.gitignore`, `.git/info/exclude`, global excludes)
+    /// match this entry. Only computed for local listings with the
+    /// `fileExplorer.git.ignoredFiles` setting on; `false` otherwise. The frontend greys these
+    /// rows out; in `Hide` mode the backend leaves them out instead. See `git_ignore.rs`.
+    pub is_git_ignored: bool,
+    /// Cloud state read from the stat flags during listing: `OnlineOnly` for a cloud
+    /// placeholder (iCloud Drive, Dropbox, Google Drive), `None` otherwise. Telling synced
+    /// from uploading or downloading takes an XPC call per file, so that stays with the
+    /// visible-range `get_sync_status`. Always `None` off macOS. See `sync_status.rs`.
+    pub sync_status: Option<SyncStatus>,
 }
 
 impl FileEntry {
@@ -210,6 +226,7 @@ impl FileEntry {
             created_at: None,
             added_at: None,
             opened_at: None,
+            captured_at: None,
             permissions: 0,
             owner: String::new(),
             group: String::new(),
@@ -225,6 +242,8 @@ impl FileEntry {
             redirect_to_path: None,
             display_size: None,
             display_size_tooltip: None,
+            is_git_ignored: false,
+            sync_status: None,
         }
     }
 }
@@ -335,4 +354,6 @@ pub struct ExtendedMetadata {
     pub added_at: Option<u64>,
     /// macOS only.
     pub opened_at: Option<u64>,
+    /// EXIF capture date; image files only, every platform.
+    pub captured_at: Option<u64>,
 }
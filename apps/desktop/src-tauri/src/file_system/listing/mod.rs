@@ -2,27 +2,36 @@
 
 pub(crate) mod brief_columns;
 pub(crate) mod caching;
+pub(crate) mod capture_date;
+pub(crate) mod columns;
 pub(crate) mod diff_emitter;
+pub(crate) mod eviction;
+pub(crate) mod flat;
 pub(crate) mod fuzzy_jump;
+pub(crate) mod git_ignore;
 pub(crate) mod metadata;
 pub(crate) mod operations;
+pub(crate) mod prefetch;
 pub(crate) mod reading;
 pub(crate) mod sorting;
 pub(crate) mod streaming;
+pub(crate) mod tree;
 
 // Re-export types so they're available both externally and locally in this module
 // (call sites import them from `crate::file_system::listing` directly).
 pub use brief_columns::{BriefColumnsError, compute_brief_column_text_widths};
-pub use fuzzy_jump::fuzzy_find_first_match_in_listing;
+pub use fuzzy_jump::{find_next_match_in_listing, fuzzy_find_first_match_in_listing};
+pub use git_ignore::{GitIgnoredMode, set_git_ignored_mode};
 pub use metadata::{ExtendedMetadata, FileEntry};
 pub use operations::{
     ListingStartResult, ListingStats, ResortResult, find_file_index, find_file_indices, get_file_at, get_file_range,
     get_listing_stats, get_total_count, list_directory_end, list_directory_start_with_volume,
     refresh_listing_index_sizes, resort_listing,
 };
-pub use reading::{get_single_entry, list_directory_core};
+pub use prefetch::prefetch_listing;
+pub use reading::{get_single_entry, list_directory_core, list_directory_core_chunked, list_directory_core_concurrent};
 pub use sorting::{DirectorySortMode, SortColumn, SortOrder};
-pub use streaming::{StreamingListingStartResult, cancel_listing, list_directory_start_streaming};
+pub use streaming::{ListingChunkMode, StreamingListingStartResult, cancel_listing, list_directory_start_streaming};
 
 // Batch accessors (used by drag, clipboard, and transfer dialogs)
 pub use operations::{get_files_at_indices, get_paths_at_indices};
@@ -35,7 +44,10 @@ pub(crate) use caching::{
 // Notification API for volume mutations
 #[cfg(any(target_os = "macos", target_os = "linux"))]
 pub(crate) use operations::get_listings_by_volume_prefix;
-pub(crate) use operations::{get_listing_entries, update_listing_entries};
+pub(crate) use operations::{end_listings_on_volume, get_listing_entries, update_listing_entries};
+// Eject pauses a volume's watchers before unmounting (`file_system::volume::eject`).
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+pub(crate) use operations::{pause_watchers_on_volume, resume_watchers};
 
 #[cfg(test)]
 mod brief_columns_test;
@@ -48,8 +60,16 @@ mod caching_test;
 #[cfg(test)]
 pub(crate) mod caching_test_support;
 #[cfg(test)]
+mod concurrent_metadata_bench;
+#[cfg(test)]
 mod diff_emitter_test;
 #[cfg(test)]
+mod eviction_test;
+#[cfg(test)]
+mod flat_test;
+#[cfg(test)]
+mod git_ignore_test;
+#[cfg(test)]
 mod hidden_files_test;
 #[cfg(test)]
 mod operations_test;
@@ -59,3 +79,5 @@ mod sorting_test;
 mod stats_test;
 #[cfg(test)]
 mod streaming_test;
+#[cfg(test)]
+mod tree_test;
@@ -12,18 +12,26 @@ use crate::benchmark;
 use crate::file_system::listing::caching::{CachedListing, LISTING_CACHE};
 use crate::file_system::listing::metadata::FileEntry;
 use crate::file_system::listing::sorting::{DirectorySortMode, SortColumn, SortOrder, sort_entries};
-use crate::file_system::watcher::{start_watching, stop_watching};
+use crate::file_system::listing::{capture_date, eviction, flat, git_ignore};
+use crate::file_system::watcher::{is_watching, start_watching, stop_watching};
 
 /// Returns true if the entry is not a hidden dotfile.
 fn is_visible(entry: &FileEntry) -> bool {
     !entry.name.starts_with('.')
 }
 
+/// The entries a read shows: dotfiles only with `include_hidden`, and git-ignored entries
+/// not at all in the `Hide` mode (`git_ignore.rs`), whatever `include_hidden` says.
 fn visible_entries<'a>(entries: &'a [FileEntry], include_hidden: bool) -> Box<dyn Iterator<Item = &'a FileEntry> + 'a> {
-    if include_hidden {
-        Box::new(entries.iter())
-    } else {
-        Box::new(entries.iter().filter(|e| is_visible(e)))
+    let hide_ignored = git_ignore::git_ignored_mode() == git_ignore::GitIgnoredMode::Hide;
+    match (include_hidden, hide_ignored) {
+        (true, false) => Box::new(entries.iter()),
+        (false, false) => Box::new(entries.iter().filter(|e| is_visible(e))),
+        (_, true) => Box::new(
+            entries
+                .iter()
+                .filter(move |e| (include_hidden || is_visible(e)) && !e.is_git_ignored),
+        ),
     }
 }
 
@@ -75,6 +83,7 @@ pub async fn list_directory_start_with_volume(
 
     // Generate listing ID
     let listing_id = Uuid::new_v4().to_string();
+    benchmark::set_current_trace(&listing_id);
 
     let total_count = visible_entries(&all_entries, include_hidden).count();
 
@@ -85,6 +94,7 @@ pub async fn list_directory_start_with_volume(
     if !is_archive {
         crate::indexing::enrich_entries_with_index_on_volume(volume_id, &mut all_entries);
         crate::indexing::trigger_verification(volume_id, &path.to_string_lossy());
+        capture_date::fill_for_sort(volume_id, sort_by, &mut all_entries);
     }
 
     // Sort the entries
@@ -101,6 +111,7 @@ pub async fn list_directory_start_with_volume(
                 sort_by,
                 sort_order,
                 directory_sort_mode: dir_sort_mode,
+                generation: crate::file_system::listing::caching::next_listing_generation(),
                 sequence: std::sync::atomic::AtomicU64::new(0),
                 created_at: std::time::Instant::now(),
                 last_accessed_ms: std::sync::atomic::AtomicU64::new(
@@ -119,6 +130,8 @@ pub async fn list_directory_start_with_volume(
         // Continue anyway - watcher is optional enhancement
     }
 
+    eviction::enforce_cap();
+
     benchmark::log_event("list_directory_start RETURNING");
     Ok(ListingStartResult {
         listing_id,
@@ -134,10 +147,15 @@ pub fn list_directory_end(listing_id: &str) {
     // Drop any pending coalesced diff for this listing
     crate::file_system::listing::diff_emitter::drop_pending(listing_id);
 
-    // Remove from listing cache
+    // Reset its coalescing counters (`get_watcher_stats`)
+    crate::file_system::watcher::clear_watcher_stats(listing_id);
+
+    // Remove from listing cache, or forget it if it was evicted
     if let Ok(mut cache) = LISTING_CACHE.write() {
         cache.remove(listing_id);
     }
+    eviction::forget(listing_id);
+    flat::forget(listing_id);
 }
 
 // ============================================================================
@@ -151,6 +169,10 @@ pub fn get_file_range(
     count: usize,
     include_hidden: bool,
 ) -> Result<Vec<FileEntry>, String> {
+    let _span = benchmark::span_for("get_file_range", listing_id)
+        .with("start", start)
+        .with("count", count);
+    eviction::restore_if_evicted(listing_id)?;
     let cache = LISTING_CACHE.read().map_err(|_| "Failed to acquire cache lock")?;
 
     let listing = cache
@@ -170,6 +192,7 @@ pub fn get_file_range(
 
 /// Gets total count of entries in a cached listing.
 pub fn get_total_count(listing_id: &str, include_hidden: bool) -> Result<usize, String> {
+    eviction::restore_if_evicted(listing_id)?;
     let cache = LISTING_CACHE.read().map_err(|_| "Failed to acquire cache lock")?;
 
     let listing = cache
@@ -183,6 +206,7 @@ pub fn get_total_count(listing_id: &str, include_hidden: bool) -> Result<usize,
 
 /// Finds the index of a file by name in a cached listing.
 pub fn find_file_index(listing_id: &str, name: &str, include_hidden: bool) -> Result<Option<usize>, String> {
+    eviction::restore_if_evicted(listing_id)?;
     let cache = LISTING_CACHE.read().map_err(|_| "Failed to acquire cache lock")?;
 
     let listing = cache
@@ -203,6 +227,7 @@ pub fn find_file_indices(
     names: &[String],
     include_hidden: bool,
 ) -> Result<HashMap<String, usize>, String> {
+    eviction::restore_if_evicted(listing_id)?;
     let cache = LISTING_CACHE.read().map_err(|_| "Failed to acquire cache lock")?;
 
     let listing = cache
@@ -225,6 +250,7 @@ pub fn find_file_indices(
 
 /// Gets a single file at the given index.
 pub fn get_file_at(listing_id: &str, index: usize, include_hidden: bool) -> Result<Option<FileEntry>, String> {
+    eviction::restore_if_evicted(listing_id)?;
     let cache = LISTING_CACHE.read().map_err(|_| "Failed to acquire cache lock")?;
 
     let listing = cache
@@ -262,6 +288,7 @@ pub fn get_paths_at_indices(
     include_hidden: bool,
     has_parent: bool,
 ) -> Result<Vec<PathBuf>, String> {
+    eviction::restore_if_evicted(listing_id)?;
     let cache = LISTING_CACHE.read().map_err(|_| "Failed to acquire cache lock")?;
 
     let listing = cache
@@ -300,6 +327,7 @@ pub fn get_files_at_indices(
     selected_indices: &[usize],
     include_hidden: bool,
 ) -> Result<Vec<FileEntry>, String> {
+    eviction::restore_if_evicted(listing_id)?;
     let cache = LISTING_CACHE.read().map_err(|_| "Failed to acquire cache lock")?;
 
     let listing = cache
@@ -353,6 +381,7 @@ pub fn resort_listing(
     selected_indices: Option<&[usize]>,
     all_selected: bool,
 ) -> Result<ResortResult, String> {
+    eviction::restore_if_evicted(listing_id)?;
     let mut cache = LISTING_CACHE.write().map_err(|_| "Failed to acquire cache lock")?;
 
     let listing = cache
@@ -378,6 +407,7 @@ pub fn resort_listing(
     // Refresh index data before re-sorting (cache entries may not have fresh sizes)
     let volume_id = listing.volume_id.clone();
     crate::indexing::enrich_entries_with_index_on_volume(&volume_id, &mut listing.entries);
+    capture_date::fill_for_sort(&volume_id, sort_by, &mut listing.entries);
 
     // Re-sort the entries
     sort_entries(&mut listing.entries, sort_by, sort_order, dir_sort_mode);
@@ -413,31 +443,48 @@ pub fn resort_listing(
 // Internal cache accessors for file watcher
 // ============================================================================
 
-/// Gets entries and path from the listing cache (for watcher diff computation).
-/// Returns None if listing not found.
-pub(crate) fn get_listing_entries(listing_id: &str) -> Option<(PathBuf, Vec<FileEntry>)> {
+/// Gets path, entries, and generation from the listing cache (for watcher diff computation).
+/// Returns None if listing not found. Pass the generation back to [`update_listing_entries`].
+pub(crate) fn get_listing_entries(listing_id: &str) -> Option<(PathBuf, Vec<FileEntry>, u64)> {
     let cache = LISTING_CACHE.read().ok()?;
     let listing = cache.get(listing_id)?;
-    Some((listing.path.clone(), listing.entries.clone()))
+    Some((listing.path.clone(), listing.entries.clone(), listing.generation))
 }
 
 /// Updates the entries in the listing cache (after watcher detects changes).
 /// Re-sorts using the stored sort parameters so the cache stays consistent.
-pub(crate) fn update_listing_entries(listing_id: &str, entries: Vec<FileEntry>) {
-    if let Ok(mut cache) = LISTING_CACHE.write()
-        && let Some(listing) = cache.get_mut(listing_id)
-    {
-        listing.touch();
-        let mut entries = entries;
-        crate::indexing::enrich_entries_with_index_on_volume(&listing.volume_id, &mut entries);
-        sort_entries(
-            &mut entries,
-            listing.sort_by,
-            listing.sort_order,
-            listing.directory_sort_mode,
+///
+/// `generation` is the one the caller read the old entries under. If the listing was
+/// replaced since (a cancelled listing restarted under the same id), the write is dropped:
+/// the entries and the diff the caller computed describe a listing that no longer exists.
+/// Returns whether the write landed; callers skip emitting their diff when it didn't.
+pub(crate) fn update_listing_entries(listing_id: &str, generation: u64, entries: Vec<FileEntry>) -> bool {
+    let Ok(mut cache) = LISTING_CACHE.write() else {
+        return false;
+    };
+    let Some(listing) = cache.get_mut(listing_id) else {
+        return false;
+    };
+    if listing.generation != generation {
+        log::debug!(
+            "update_listing_entries: dropping write for superseded listing_id={} (generation {} != {})",
+            listing_id,
+            generation,
+            listing.generation
         );
-        listing.entries = entries;
+        return false;
     }
+    listing.touch();
+    let mut entries = entries;
+    crate::indexing::enrich_entries_with_index_on_volume(&listing.volume_id, &mut entries);
+    sort_entries(
+        &mut entries,
+        listing.sort_by,
+        listing.sort_order,
+        listing.directory_sort_mode,
+    );
+    listing.entries = entries;
+    true
 }
 
 /// Gets all listings for volumes matching a specific prefix.
@@ -445,8 +492,8 @@ pub(crate) fn update_listing_entries(listing_id: &str, entries: Vec<FileEntry>)
 /// Used by MTP file watching to find all listings belonging to a device.
 /// MTP volume IDs have the format "mtp-{device_id}:{storage_id}".
 ///
-/// Returns: Vec<(listing_id, volume_id, path, entries)>
-pub(crate) fn get_listings_by_volume_prefix(prefix: &str) -> Vec<(String, String, PathBuf, Vec<FileEntry>)> {
+/// Returns: Vec<(listing_id, volume_id, path, entries, generation)>
+pub(crate) fn get_listings_by_volume_prefix(prefix: &str) -> Vec<(String, String, PathBuf, Vec<FileEntry>, u64)> {
     let cache = match LISTING_CACHE.read() {
         Ok(c) => c,
         Err(_) => return Vec::new(),
@@ -461,11 +508,83 @@ pub(crate) fn get_listings_by_volume_prefix(prefix: &str) -> Vec<(String, String
                 listing.volume_id.clone(),
                 listing.path.clone(),
                 listing.entries.clone(),
+                listing.generation,
             )
         })
         .collect()
 }
 
+/// Ends every listing on a volume that was just unmounted: stops its watcher, drops any
+/// pending diff, and removes it from the cache, exactly like `list_directory_end`.
+///
+/// Matches by volume id, and also by path under `volume_root` so a listing opened through
+/// a path-derived id (or before an SMB upgrade re-keyed the volume) isn't left watching a
+/// path that no longer exists. The frontend navigates away on `volume-unmounted`; its own
+/// later `list_directory_end` for these ids is a no-op.
+///
+/// Returns the ended listing ids.
+pub(crate) fn end_listings_on_volume(volume_id: &str, volume_root: &Path) -> Vec<String> {
+    let listing_ids: Vec<String> = listings_on_volume(volume_id, volume_root)
+        .into_iter()
+        .map(|(id, _)| id)
+        .collect();
+
+    for listing_id in &listing_ids {
+        list_directory_end(listing_id);
+    }
+    listing_ids
+}
+
+/// Stops the file watchers of every listing on a volume that's about to be ejected, keeping
+/// the listings themselves so the panes stay usable. A live FSEvents/inotify watch can hold
+/// the volume open and fail the unmount. Matches like `end_listings_on_volume`.
+///
+/// Returns the `(listing_id, path)` pairs it stopped, for `resume_watchers` if the eject fails.
+pub(crate) fn pause_watchers_on_volume(volume_id: &str, volume_root: &Path) -> Vec<(String, PathBuf)> {
+    let paused: Vec<(String, PathBuf)> = listings_on_volume(volume_id, volume_root)
+        .into_iter()
+        .filter(|(id, _)| is_watching(id))
+        .collect();
+    for (listing_id, _) in &paused {
+        stop_watching(listing_id);
+    }
+    paused
+}
+
+/// Re-arms the watchers `pause_watchers_on_volume` stopped. Skips listings the frontend
+/// ended in the meantime.
+pub(crate) fn resume_watchers(paused: &[(String, PathBuf)]) {
+    for (listing_id, path) in paused {
+        let still_listed = LISTING_CACHE.read().is_ok_and(|cache| cache.contains_key(listing_id));
+        if !still_listed {
+            continue;
+        }
+        if let Err(e) = start_watching(listing_id, path) {
+            log::warn!(
+                "Couldn't re-arm watcher for listing {listing_id} at {}: {e}",
+                path.display()
+            );
+        }
+    }
+}
+
+/// `(listing_id, path)` for every cached listing on a volume: by volume id, and by path
+/// under `volume_root` (see `end_listings_on_volume`).
+fn listings_on_volume(volume_id: &str, volume_root: &Path) -> Vec<(String, PathBuf)> {
+    // Never match everything: the boot volume doesn't unmount, and `/` prefixes every path.
+    let match_by_path = volume_root != Path::new("/");
+    match LISTING_CACHE.read() {
+        Ok(cache) => cache
+            .iter()
+            .filter(|(_, listing)| {
+                listing.volume_id == volume_id || (match_by_path && listing.path.starts_with(volume_root))
+            })
+            .map(|(id, listing)| (id.clone(), listing.path.clone()))
+            .collect(),
+        Err(_) => Vec::new(),
+    }
+}
+
 // ============================================================================
 // Listing statistics for selection info display
 // ============================================================================
@@ -503,6 +622,7 @@ pub fn get_listing_stats(
     include_hidden: bool,
     selected_indices: Option<&[usize]>,
 ) -> Result<ListingStats, String> {
+    eviction::restore_if_evicted(listing_id)?;
     let cache = LISTING_CACHE.read().map_err(|_| "Failed to acquire cache lock")?;
 
     let listing = cache
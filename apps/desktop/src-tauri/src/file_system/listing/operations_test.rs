@@ -95,6 +95,83 @@ fn test_list_directory_core_is_sorted() {
     assert!(entries[0].is_directory);
 }
 
+#[test]
+fn test_list_directory_core_chunked_hands_out_every_entry_then_sorts() {
+    use super::reading::{CHUNKED_LISTING_MIN_ENTRIES, list_directory_core_chunked};
+    use std::sync::atomic::AtomicBool;
+
+    let temp_dir = tempfile::tempdir().expect("tempdir");
+    for i in 0..CHUNKED_LISTING_MIN_ENTRIES {
+        fs::write(temp_dir.path().join(format!("f{i:05}")), "").expect("write");
+    }
+
+    let cancel = AtomicBool::new(false);
+    let mut chunks: Vec<Vec<String>> = Vec::new();
+    let entries = list_directory_core_chunked(temp_dir.path(), false, &cancel, &mut |chunk| {
+        chunks.push(chunk.into_iter().map(|e| e.name).collect());
+    })
+    .expect("chunked listing");
+
+    assert!(chunks.len() > 1, "a folder this big must arrive in several chunks");
+    assert!(
+        chunks[0].len() < chunks[1].len(),
+        "the first chunk is the small first screenful"
+    );
+    let mut chunked: Vec<String> = chunks.concat();
+    chunked.sort();
+    let listed: Vec<String> = entries.iter().map(|e| e.name.clone()).collect();
+    assert_eq!(
+        chunked, listed,
+        "chunks cover every entry exactly once; the result is sorted"
+    );
+
+    // A small folder gets no chunks, and a flipped flag stops the read.
+    let small = tempfile::tempdir().expect("tempdir");
+    fs::write(small.path().join("one.txt"), "").expect("write");
+    let mut small_chunks = 0;
+    list_directory_core_chunked(small.path(), false, &cancel, &mut |_| small_chunks += 1).expect("small listing");
+    assert_eq!(small_chunks, 0);
+
+    cancel.store(true, std::sync::atomic::Ordering::Relaxed);
+    let err = list_directory_core_chunked(temp_dir.path(), false, &cancel, &mut |_| {}).expect_err("cancelled");
+    assert_eq!(err.kind(), std::io::ErrorKind::Interrupted);
+}
+
+#[test]
+fn test_concurrent_metadata_listing_matches_the_serial_one() {
+    use super::reading::{CHUNKED_LISTING_MIN_ENTRIES, list_directory_core_chunked, list_directory_core_concurrent};
+    use std::sync::atomic::AtomicBool;
+
+    let temp_dir = tempfile::tempdir().expect("tempdir");
+    for i in 0..CHUNKED_LISTING_MIN_ENTRIES {
+        fs::write(temp_dir.path().join(format!("f{i:05}")), "x".repeat(i % 7)).expect("write");
+    }
+    fs::create_dir(temp_dir.path().join("sub")).expect("mkdir");
+    std::os::unix::fs::symlink(temp_dir.path().join("gone"), temp_dir.path().join("broken")).expect("symlink");
+
+    let serial = list_directory_core(temp_dir.path()).expect("serial listing");
+    let concurrent = list_directory_core_concurrent(temp_dir.path()).expect("concurrent listing");
+    assert_eq!(
+        serde_json::to_value(&serial).expect("json"),
+        serde_json::to_value(&concurrent).expect("json"),
+        "same entries, same metadata, same order"
+    );
+
+    // Chunks come out in read order either way, so both modes hand out identical chunks.
+    let cancel = AtomicBool::new(false);
+    let mut serial_chunks: Vec<Vec<String>> = Vec::new();
+    list_directory_core_chunked(temp_dir.path(), false, &cancel, &mut |chunk| {
+        serial_chunks.push(chunk.into_iter().map(|e| e.name).collect());
+    })
+    .expect("serial chunked listing");
+    let mut concurrent_chunks: Vec<Vec<String>> = Vec::new();
+    list_directory_core_chunked(temp_dir.path(), true, &cancel, &mut |chunk| {
+        concurrent_chunks.push(chunk.into_iter().map(|e| e.name).collect());
+    })
+    .expect("concurrent chunked listing");
+    assert_eq!(serial_chunks.concat(), concurrent_chunks.concat());
+}
+
 #[test]
 fn test_get_extended_metadata_batch() {
     let temp_dir = std::env::temp_dir().join("cmdr_extended_test");
@@ -190,6 +267,7 @@ fn test_cancel_listing_sets_flag() {
     let state = Arc::new(StreamingListingState {
         cancelled: Arc::new(AtomicBool::new(false)),
         cancel_notify: tokio::sync::Notify::new(),
+        generation: 0,
     });
 
     // Store it in the streaming state cache
@@ -383,3 +461,37 @@ async fn test_list_directory_start_with_volume_unknown_volume() {
     assert!(result.is_err());
     assert!(result.unwrap_err().to_string().contains("not found"));
 }
+
+/// Tests that unmount teardown ends listings on the volume (by id or by path under its root)
+/// and leaves every other listing alone.
+#[test]
+fn test_end_listings_on_volume_ends_only_that_volumes_listings() {
+    use super::caching_test_support::TestListing;
+    use std::path::Path;
+
+    let by_id = TestListing::new()
+        .volume("usb-unmount-test")
+        .path("/Volumes/UnmountTest/Photos")
+        .insert("unmount-by-id");
+    let by_path = TestListing::new()
+        .path("/Volumes/UnmountTest/Docs")
+        .insert("unmount-by-path");
+    let sibling = TestListing::new()
+        .path("/Volumes/UnmountTestOther/Docs")
+        .insert("unmount-sibling");
+    let local = TestListing::new().path("/Users/test").insert("unmount-local");
+
+    let mut ended = super::end_listings_on_volume("usb-unmount-test", Path::new("/Volumes/UnmountTest"));
+    ended.sort();
+    let mut expected = vec![by_id.id().to_string(), by_path.id().to_string()];
+    expected.sort();
+
+    assert_eq!(ended, expected);
+    assert!(!by_id.is_cached());
+    assert!(!by_path.is_cached());
+    assert!(
+        sibling.is_cached(),
+        "a sibling volume sharing a name prefix must survive"
+    );
+    assert!(local.is_cached());
+}
@@ -0,0 +1,233 @@
+//! Background pre-fetch of adjacent directory listings.
+//!
+//! After a navigation completes, the parent folder is read in the background, and the frontend
+//! can ask for the folder under the cursor via `prefetch_listing`. Both land here, in a small
+//! side cache that is NOT part of `LISTING_CACHE`: a pre-fetched listing has no listing id, no
+//! watcher, and no sort. When the user actually navigates there,
+//! `read_directory_with_progress` [`take`]s the entries instead of reading the disk, then
+//! enriches, sorts, caches, and starts the watcher exactly as for a fresh read. So the watcher
+//! only ever starts for a displayed listing.
+//!
+//! Because nothing watches a pre-fetched listing, it's only handed out while it's provably
+//! fresh: younger than [`MAX_AGE`] and with the folder's mtime unchanged since the read (an
+//! add, remove, or rename inside the folder bumps it). Only volumes with local FS access are
+//! pre-fetched. MTP serializes every request on the device, so a speculative read would delay
+//! the real one, and archives are cheap to open anyway.
+//!
+//! The cache is capped at [`MAX_PREFETCHED`] listings of at most [`MAX_PREFETCHED_ENTRIES`]
+//! entries, and the memory watchdog clears it wholesale (see [`clear`]).
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, Mutex, Once};
+use std::time::{Duration, Instant, SystemTime};
+
+use crate::file_system::listing::caching::get_cached_listing;
+use crate::file_system::listing::metadata::FileEntry;
+use crate::ignore_poison::IgnorePoison;
+
+/// Listings kept at once. Parent plus a few cursor stops per pane is the realistic working set.
+const MAX_PREFETCHED: usize = 8;
+
+/// Folders bigger than this aren't kept: they're the expensive ones to hold, and the streaming
+/// read shows progress for them anyway.
+const MAX_PREFETCHED_ENTRIES: usize = 20_000;
+
+/// Oldest pre-fetched listing that's still handed out. Short, because the mtime check can't
+/// see a file inside the folder changing size.
+const MAX_AGE: Duration = Duration::from_secs(30);
+
+/// Pre-fetches running at once. Extra requests are dropped, not queued: by the time a queue
+/// drained, the cursor would have moved on.
+const MAX_IN_FLIGHT: usize = 2;
+
+struct Prefetched {
+    volume_id: String,
+    path: PathBuf,
+    entries: Vec<FileEntry>,
+    fetched_at: Instant,
+    dir_modified: Option<SystemTime>,
+}
+
+/// The cached listings, oldest first.
+static PREFETCHED: LazyLock<Mutex<Vec<Prefetched>>> = LazyLock::new(|| Mutex::new(Vec::new()));
+
+/// `(volume_id, path)` of the pre-fetches currently reading.
+static IN_FLIGHT: LazyLock<Mutex<HashSet<(String, PathBuf)>>> = LazyLock::new(|| Mutex::new(HashSet::new()));
+
+static REGISTER_STOP_HOOK: Once = Once::new();
+
+/// Drops every pre-fetched listing. Runs from the memory watchdog's stop hook.
+pub(crate) fn clear() {
+    PREFETCHED.lock_ignore_poison().clear();
+}
+
+fn is_fresh(entry: &Prefetched) -> bool {
+    entry.fetched_at.elapsed() < MAX_AGE
+}
+
+fn store(entry: Prefetched) {
+    let mut prefetched = PREFETCHED.lock_ignore_poison();
+    prefetched.retain(|p| is_fresh(p) && !(p.volume_id == entry.volume_id && p.path == entry.path));
+    if prefetched.len() >= MAX_PREFETCHED {
+        prefetched.remove(0);
+    }
+    prefetched.push(entry);
+}
+
+fn has_fresh(volume_id: &str, path: &Path) -> bool {
+    PREFETCHED
+        .lock_ignore_poison()
+        .iter()
+        .any(|p| p.volume_id == volume_id && p.path == path && is_fresh(p))
+}
+
+/// The folder's mtime, read off the async runtime: an OS-mounted share can stall a `stat`.
+async fn dir_modified(path: &Path) -> Option<SystemTime> {
+    let path = path.to_path_buf();
+    tokio::task::spawn_blocking(move || std::fs::metadata(path).ok()?.modified().ok())
+        .await
+        .ok()
+        .flatten()
+}
+
+/// Removes and returns the pre-fetched entries for `(volume_id, path)` if they're still
+/// fresh. Unsorted and unenriched: the caller treats them like a fresh `list_directory` result.
+pub(crate) async fn take(volume_id: &str, path: &Path) -> Option<Vec<FileEntry>> {
+    let entry = {
+        let mut prefetched = PREFETCHED.lock_ignore_poison();
+        let index = prefetched
+            .iter()
+            .position(|p| p.volume_id == volume_id && p.path == path)?;
+        prefetched.remove(index)
+    };
+    if !is_fresh(&entry) || entry.dir_modified.is_none() || dir_modified(path).await != entry.dir_modified {
+        log::debug!(target: "listing_prefetch", "Discarding stale pre-fetch of {}", path.display());
+        return None;
+    }
+    Some(entry.entries)
+}
+
+/// Reads `path` on `volume_id` into the pre-fetch cache, unless it's already displayed,
+/// already pre-fetched, or the volume isn't cheap to read speculatively. Never fails: a
+/// pre-fetch that can't run just doesn't happen.
+pub async fn prefetch_listing(volume_id: &str, path: &Path) {
+    REGISTER_STOP_HOOK.call_once(|| crate::indexing::register_subsystem_stop_hook(Box::new(clear)));
+
+    if crate::file_system::git::is_virtual(path)
+        || get_cached_listing(volume_id, path).is_some()
+        || has_fresh(volume_id, path)
+    {
+        return;
+    }
+    let key = (volume_id.to_string(), path.to_path_buf());
+    {
+        let mut in_flight = IN_FLIGHT.lock_ignore_poison();
+        if in_flight.len() >= MAX_IN_FLIGHT || !in_flight.insert(key.clone()) {
+            return;
+        }
+    }
+    prefetch_uncached(volume_id, path).await;
+    IN_FLIGHT.lock_ignore_poison().remove(&key);
+}
+
+async fn prefetch_uncached(volume_id: &str, path: &Path) {
+    let resolved = crate::file_system::get_volume_manager().resolve(volume_id, path).await;
+    let Some(volume) = resolved.volume else { return };
+    if resolved.is_archive || !volume.supports_local_fs_access() {
+        return;
+    }
+    // Read the mtime first: a change landing mid-read then shows up as a mismatch in `take`.
+    let modified = dir_modified(path).await;
+    match volume.list_directory(path, None).await {
+        Ok(entries) if entries.len() <= MAX_PREFETCHED_ENTRIES => {
+            log::debug!(
+                target: "listing_prefetch",
+                "Pre-fetched {} ({} entries)",
+                path.display(),
+                entries.len()
+            );
+            store(Prefetched {
+                volume_id: volume_id.to_string(),
+                path: path.to_path_buf(),
+                entries,
+                fetched_at: Instant::now(),
+                dir_modified: modified,
+            });
+        }
+        Ok(_) => {}
+        Err(e) => log::debug!(target: "listing_prefetch", "Pre-fetch of {} failed: {e}", path.display()),
+    }
+}
+
+/// The navigation heuristic: once `path` is displayed, warm its parent so "go up" is instant.
+/// The folder under the cursor comes from the frontend through `prefetch_listing`, because
+/// only the frontend knows where the cursor rests.
+pub(crate) fn prefetch_adjacent(volume_id: &str, path: &Path) {
+    let Some(parent) = path.parent() else { return };
+    let volume_id = volume_id.to_string();
+    let parent = parent.to_path_buf();
+    tauri::async_runtime::spawn(async move { prefetch_listing(&volume_id, &parent).await });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Both tests share the process-global cache. Async-aware, since one holds it across awaits.
+    static TEST_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+    fn prefetched(path: &str, age: Duration) -> Prefetched {
+        Prefetched {
+            volume_id: "prefetch-test".to_string(),
+            path: PathBuf::from(path),
+            entries: Vec::new(),
+            fetched_at: Instant::now() - age,
+            dir_modified: None,
+        }
+    }
+
+    #[test]
+    fn store_caps_replaces_and_drops_stale_listings() {
+        let _guard = TEST_LOCK.blocking_lock();
+        clear();
+        store(prefetched("/stale", MAX_AGE + Duration::from_secs(1)));
+        for i in 0..MAX_PREFETCHED {
+            store(prefetched(&format!("/dir{i}"), Duration::ZERO));
+        }
+        store(prefetched("/dir3", Duration::ZERO));
+
+        let paths: Vec<PathBuf> = PREFETCHED.lock_ignore_poison().iter().map(|p| p.path.clone()).collect();
+        assert_eq!(paths.len(), MAX_PREFETCHED);
+        assert!(!paths.contains(&PathBuf::from("/stale")));
+        assert_eq!(paths.last(), Some(&PathBuf::from("/dir3")));
+        assert_eq!(paths.iter().filter(|p| *p == Path::new("/dir3")).count(), 1);
+        assert!(has_fresh("prefetch-test", Path::new("/dir0")));
+        clear();
+    }
+
+    #[tokio::test]
+    async fn take_hands_out_once_and_only_while_the_folder_is_unchanged() {
+        let _guard = TEST_LOCK.lock().await;
+        clear();
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut entry = prefetched(&dir.path().to_string_lossy(), Duration::ZERO);
+        entry.dir_modified = dir_modified(dir.path()).await;
+        store(entry);
+
+        assert!(take("prefetch-test", dir.path()).await.is_some());
+        assert!(
+            take("prefetch-test", dir.path()).await.is_none(),
+            "taken listings are gone"
+        );
+
+        let mut entry = prefetched(&dir.path().to_string_lossy(), Duration::ZERO);
+        entry.dir_modified = Some(SystemTime::UNIX_EPOCH);
+        store(entry);
+        assert!(
+            take("prefetch-test", dir.path()).await.is_none(),
+            "mtime mismatch is stale"
+        );
+        clear();
+    }
+}
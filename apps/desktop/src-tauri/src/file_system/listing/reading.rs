@@ -11,15 +11,18 @@
 use std::fs;
 use std::os::unix::fs::{MetadataExt, PermissionsExt};
 use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use crate::benchmark;
 use crate::file_system::listing::metadata::{ExtendedMetadata, FileEntry, get_group_name, get_owner_name};
 use crate::file_system::listing::sorting::{DirectorySortMode, SortColumn, SortOrder, sort_entries};
+use crate::file_system::listing::{capture_date, git_ignore};
+use crate::file_system::sync_status;
 
 /// Lists the contents of a directory with full metadata (including macOS extended metadata).
 ///
 /// Calls `list_directory_core()` for the fast path, then enriches entries
-/// with macOS-specific metadata (addedAt, openedAt).
+/// with macOS-specific metadata (addedAt, openedAt) and image capture dates.
 pub fn list_directory(path: &Path) -> Result<Vec<FileEntry>, std::io::Error> {
     let overall_start = std::time::Instant::now();
 
@@ -46,6 +49,8 @@ pub fn list_directory(path: &Path) -> Result<Vec<FileEntry>, std::io::Error> {
         entry.extended_metadata_loaded = true;
     }
 
+    capture_date::fill_captured_at(&mut entries);
+
     log::debug!(
         "list_directory: path={}, entries={}, total={}ms",
         path.display(),
@@ -63,7 +68,14 @@ pub fn list_directory(path: &Path) -> Result<Vec<FileEntry>, std::io::Error> {
 ///
 /// Use `get_extended_metadata_batch()` to fetch extended metadata later.
 pub fn list_directory_core(path: &Path) -> Result<Vec<FileEntry>, std::io::Error> {
-    list_directory_core_impl(path, None)
+    list_directory_core_impl(path, false, None, None)
+}
+
+/// Like `list_directory_core`, but stats entries on a small bounded pool (see
+/// `METADATA_WORKERS`). Only worth it where each stat is a network round trip; on a local disk
+/// the extra threads just contend. The result is identical to `list_directory_core`'s.
+pub fn list_directory_core_concurrent(path: &Path) -> Result<Vec<FileEntry>, std::io::Error> {
+    list_directory_core_impl(path, true, None, None)
 }
 
 /// Like `list_directory_core`, but calls `on_progress(loaded_count)` every ~200ms
@@ -72,15 +84,57 @@ pub fn list_directory_core_with_progress(
     path: &Path,
     on_progress: &dyn Fn(usize),
 ) -> Result<Vec<FileEntry>, std::io::Error> {
-    list_directory_core_impl(path, Some(on_progress))
+    list_directory_core_impl(path, false, Some(on_progress), None)
+}
+
+/// Like `list_directory_core`, but for huge folders (at least `CHUNKED_LISTING_MIN_ENTRIES`)
+/// hands the entries to `on_chunk` in read order while the stat loop runs, so the first
+/// screenful can render long before the last entry is read. Smaller folders get no chunks.
+///
+/// Checks `cancel` per entry (per batch with `concurrent_metadata`, see
+/// `list_directory_core_concurrent`) and bails with `ErrorKind::Interrupted` once it flips. The
+/// returned listing is the same as `list_directory_core`'s (all entries, sorted).
+pub fn list_directory_core_chunked(
+    path: &Path,
+    concurrent_metadata: bool,
+    cancel: &AtomicBool,
+    on_chunk: &mut dyn FnMut(Vec<FileEntry>),
+) -> Result<Vec<FileEntry>, std::io::Error> {
+    list_directory_core_impl(path, concurrent_metadata, None, Some(ChunkedRead { cancel, on_chunk }))
 }
 
 /// Interval between progress callbacks during the stat loop.
 const PROGRESS_REPORT_INTERVAL: std::time::Duration = std::time::Duration::from_millis(200);
 
+/// Folders with fewer entries than this are read whole: they list in well under a frame
+/// budget, so chunking them would only add events.
+pub const CHUNKED_LISTING_MIN_ENTRIES: usize = 10_000;
+
+/// Size of the first chunk: about one tall screenful, so the first paint comes quickly.
+const FIRST_CHUNK_LEN: usize = 256;
+
+/// Size of every later chunk. Big enough that a 500k-entry folder is ~100 events.
+const CHUNK_LEN: usize = 5_000;
+
+/// Stat threads per batch when metadata is read concurrently. Enough to hide most of an SMB or
+/// NFS round trip without flooding the server with requests for one folder.
+const METADATA_WORKERS: usize = 8;
+
+/// Entries stat'd per concurrent batch. Matches `FIRST_CHUNK_LEN` so the first chunk still goes
+/// out after one batch, and keeps cancellation and progress checks frequent.
+const METADATA_BATCH_LEN: usize = FIRST_CHUNK_LEN;
+
+/// Where `list_directory_core_impl` sends chunks, and the flag that stops it.
+struct ChunkedRead<'a> {
+    cancel: &'a AtomicBool,
+    on_chunk: &'a mut dyn FnMut(Vec<FileEntry>),
+}
+
 fn list_directory_core_impl(
     path: &Path,
+    concurrent_metadata: bool,
     on_progress: Option<&dyn Fn(usize)>,
+    mut chunked: Option<ChunkedRead<'_>>,
 ) -> Result<Vec<FileEntry>, std::io::Error> {
     benchmark::log_event("list_directory_core START");
     let overall_start = std::time::Instant::now();
@@ -88,30 +142,36 @@ fn list_directory_core_impl(
 
     benchmark::log_event("readdir START");
     let read_start = std::time::Instant::now();
-    let dir_entries: Vec<_> = fs::read_dir(path)?.collect();
+    let dir_entries = fs::read_dir(path)?.collect::<Result<Vec<_>, _>>()?;
     let read_dir_time = read_start.elapsed();
     benchmark::log_event_value("readdir END, count", dir_entries.len());
 
+    // `.gitignore` rules for this folder, when the setting is on and it's in a repo
+    let ignore_rules = git_ignore::active_matcher(path);
+
     benchmark::log_event("stat_loop START");
     let mut last_progress = std::time::Instant::now();
-    for entry in dir_entries {
-        let entry = entry?;
-        match process_dir_entry(&entry) {
-            Some(file_entry) => entries.push(file_entry),
-            None => {
-                // Permission denied or broken symlink: return minimal entry
-                let is_symlink = entry.file_type().map(|ft| ft.is_symlink()).unwrap_or(false);
-                let name = entry.file_name().to_string_lossy().to_string();
-                entries.push(FileEntry {
-                    icon_id: if is_symlink {
-                        "symlink-broken".to_string()
-                    } else {
-                        "file".to_string()
-                    },
-                    extended_metadata_loaded: true, // Nothing to load for broken entries
-                    ..FileEntry::new(name, entry.path().to_string_lossy().to_string(), false, is_symlink)
-                });
-            }
+    let emit_chunks = dir_entries.len() >= CHUNKED_LISTING_MIN_ENTRIES;
+    // Entries before this index were already handed out as chunks.
+    let mut chunked_up_to = 0;
+    let batch_len = if concurrent_metadata { METADATA_BATCH_LEN } else { 1 };
+    for batch in dir_entries.chunks(batch_len) {
+        if let Some(read) = &chunked
+            && read.cancel.load(Ordering::Relaxed)
+        {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::Interrupted,
+                "Listing cancelled",
+            ));
+        }
+        let batch_start = entries.len();
+        if concurrent_metadata {
+            entries.extend(stat_batch_concurrently(batch));
+        } else {
+            entries.extend(batch.iter().map(entry_or_placeholder));
+        }
+        if let Some(rules) = &ignore_rules {
+            rules.mark(&mut entries[batch_start..]);
         }
 
         if let Some(cb) = on_progress
@@ -120,6 +180,20 @@ fn list_directory_core_impl(
             cb(entries.len());
             last_progress = std::time::Instant::now();
         }
+
+        if emit_chunks && let Some(read) = &mut chunked {
+            let chunk_len = if chunked_up_to == 0 { FIRST_CHUNK_LEN } else { CHUNK_LEN };
+            if entries.len() - chunked_up_to >= chunk_len {
+                (read.on_chunk)(entries[chunked_up_to..].to_vec());
+                chunked_up_to = entries.len();
+            }
+        }
+    }
+    if emit_chunks
+        && let Some(read) = &mut chunked
+        && chunked_up_to < entries.len()
+    {
+        (read.on_chunk)(entries[chunked_up_to..].to_vec());
     }
     benchmark::log_event_value("stat_loop END, entries", entries.len());
 
@@ -135,7 +209,7 @@ fn list_directory_core_impl(
 
     let total_time = overall_start.elapsed();
     log::debug!(
-        "list_directory_core: path={}, entries={}, read_dir={}ms, total={}ms",
+        "list_directory_core: path={}, entries={}, concurrent_metadata={concurrent_metadata}, read_dir={}ms, total={}ms",
         path.display(),
         entries.len(),
         read_dir_time.as_millis(),
@@ -170,6 +244,41 @@ fn list_directory_core_impl(
     Ok(entries)
 }
 
+/// Stats `batch` on up to `METADATA_WORKERS` scoped threads, each taking a contiguous slice, and
+/// joins them in slice order. The result is in read order, exactly as a serial loop would give,
+/// so the sort that follows is deterministic.
+fn stat_batch_concurrently(batch: &[fs::DirEntry]) -> Vec<FileEntry> {
+    let slice_len = batch.len().div_ceil(METADATA_WORKERS).max(1);
+    std::thread::scope(|scope| {
+        let workers: Vec<_> = batch
+            .chunks(slice_len)
+            .map(|slice| scope.spawn(move || slice.iter().map(entry_or_placeholder).collect::<Vec<_>>()))
+            .collect();
+        workers
+            .into_iter()
+            .flat_map(|worker| worker.join().expect("stat worker doesn't panic"))
+            .collect()
+    })
+}
+
+/// `process_dir_entry`, or a minimal entry when it can't be stat'd.
+fn entry_or_placeholder(entry: &fs::DirEntry) -> FileEntry {
+    process_dir_entry(entry).unwrap_or_else(|| {
+        // Permission denied or broken symlink: return minimal entry
+        let is_symlink = entry.file_type().map(|ft| ft.is_symlink()).unwrap_or(false);
+        let name = entry.file_name().to_string_lossy().to_string();
+        FileEntry {
+            icon_id: if is_symlink {
+                "symlink-broken".to_string()
+            } else {
+                "file".to_string()
+            },
+            extended_metadata_loaded: true, // Nothing to load for broken entries
+            ..FileEntry::new(name, entry.path().to_string_lossy().to_string(), false, is_symlink)
+        }
+    })
+}
+
 /// Gets metadata for a single file or directory path.
 ///
 /// Used when we need metadata for a single path rather than listing
@@ -225,7 +334,7 @@ pub fn get_single_entry(path: &Path) -> Result<FileEntry, std::io::Error> {
         None
     };
 
-    Ok(FileEntry {
+    let mut entry = FileEntry {
         size: if metadata.is_file() { Some(metadata.len()) } else { None },
         physical_size,
         inode,
@@ -234,8 +343,13 @@ pub fn get_single_entry(path: &Path) -> Result<FileEntry, std::io::Error> {
         permissions: metadata.permissions().mode(),
         owner,
         group,
+        sync_status: sync_status::status_from_metadata(metadata),
         ..FileEntry::new(name, path.to_string_lossy().to_string(), is_dir, is_symlink)
-    })
+    };
+    if let Some(rules) = path.parent().and_then(git_ignore::active_matcher) {
+        rules.mark(std::slice::from_mut(&mut entry));
+    }
+    Ok(entry)
 }
 
 /// Process a single directory entry into a FileEntry.
@@ -300,6 +414,7 @@ pub(crate) fn process_dir_entry(entry: &fs::DirEntry) -> Option<FileEntry> {
         permissions: metadata.permissions().mode(),
         owner,
         group,
+        sync_status: sync_status::status_from_metadata(&metadata),
         ..FileEntry::new(name, entry.path().to_string_lossy().to_string(), is_dir, is_symlink)
     })
 }
@@ -307,7 +422,8 @@ pub(crate) fn process_dir_entry(entry: &fs::DirEntry) -> Option<FileEntry> {
 /// Fetches extended metadata for a batch of file paths.
 ///
 /// Called after the initial directory listing to populate
-/// macOS-specific metadata (addedAt, openedAt) without blocking initial render.
+/// macOS-specific metadata (addedAt, openedAt) and image capture dates without
+/// blocking initial render.
 #[cfg(target_os = "macos")]
 pub fn get_extended_metadata_batch(paths: Vec<String>) -> Vec<ExtendedMetadata> {
     benchmark::log_event_value("get_extended_metadata_batch START, count", paths.len());
@@ -317,9 +433,10 @@ pub fn get_extended_metadata_batch(paths: Vec<String>) -> Vec<ExtendedMetadata>
             let path = Path::new(&path_str);
             let macos_meta = crate::file_system::macos_metadata::get_macos_metadata(path);
             ExtendedMetadata {
-                path: path_str,
                 added_at: macos_meta.added_at,
                 opened_at: macos_meta.opened_at,
+                captured_at: capture_date::captured_at_of_path(&path_str),
+                path: path_str,
             }
         })
         .collect();
@@ -332,10 +449,14 @@ pub fn get_extended_metadata_batch(paths: Vec<String>) -> Vec<ExtendedMetadata>
     benchmark::log_event_value("get_extended_metadata_batch (non-macOS), count", paths.len());
     paths
         .into_iter()
-        .map(|path_str| ExtendedMetadata {
-            path: path_str,
-            added_at: None,
-            opened_at: None,
+        .map(|path_str| {
+            let captured_at = capture_date::captured_at_of_path(&path_str);
+            ExtendedMetadata {
+                path: path_str,
+                added_at: None,
+                opened_at: None,
+                captured_at,
+            }
         })
         .collect()
 }
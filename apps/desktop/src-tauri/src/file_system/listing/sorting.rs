@@ -18,6 +18,9 @@ pub enum SortColumn {
     Size,
     Modified,
     Created,
+    /// Photo capture date (EXIF `DateTimeOriginal`), falling back to the modified date for
+    /// files without one. See `capture_date.rs`.
+    Captured,
 }
 
 /// Sort order (ascending or descending).
@@ -67,7 +70,7 @@ fn extract_extension_for_sort(name: &str) -> (bool, bool, String) {
 }
 
 /// Compares two strings using natural (alphanumeric) sort, case-insensitive.
-fn compare_names_natural(a: &str, b: &str) -> std::cmp::Ordering {
+pub(crate) fn compare_names_natural(a: &str, b: &str) -> std::cmp::Ordering {
     alphanumeric_sort::compare_str(a.to_lowercase(), b.to_lowercase())
 }
 
@@ -87,6 +90,12 @@ fn known_dir_size(e: &FileEntry) -> Option<u64> {
     }
 }
 
+/// The Captured sort key: the EXIF capture date, or the modified date when the file has
+/// none (not an image, no EXIF, or not enriched yet).
+fn captured_or_modified(e: &FileEntry) -> Option<u64> {
+    e.captured_at.or(e.modified_at)
+}
+
 /// Returns a comparator that orders `FileEntry` values according to the given sort params.
 ///
 /// Directories always come first, then files. Within each group the comparator
@@ -200,6 +209,12 @@ pub fn entry_comparator(
                 (Some(_), None) => std::cmp::Ordering::Greater,
                 (Some(a_time), Some(b_time)) => a_time.cmp(&b_time),
             },
+            SortColumn::Captured => match (captured_or_modified(a), captured_or_modified(b)) {
+                (None, None) => compare_names_natural(&a.name, &b.name),
+                (None, Some(_)) => std::cmp::Ordering::Less,
+                (Some(_), None) => std::cmp::Ordering::Greater,
+                (Some(a_time), Some(b_time)) => a_time.cmp(&b_time),
+            },
         };
 
         // Apply sort order
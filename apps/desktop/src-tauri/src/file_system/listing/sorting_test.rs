@@ -1,7 +1,7 @@
 //! Tests for file sorting logic.
 //!
 //! These tests verify that sort_entries correctly sorts files by
-//! name, extension, size, modified date, and created date.
+//! name, extension, size, modified date, created date, and capture date.
 
 use super::sorting::DirectorySortMode;
 use super::sorting::sort_entries;
@@ -477,6 +477,28 @@ fn test_sort_by_created() {
     assert_eq!(names, vec!["oldest.txt", "middle.txt", "newest.txt"]);
 }
 
+#[test]
+fn test_sort_by_captured_falls_back_to_modified() {
+    // Taken long before it was copied here (mtime 1700000003).
+    let mut old_photo = make_entry("old_photo.jpg", false, Some(100), Some(1700000003));
+    old_photo.captured_at = Some(1500000000);
+    let mut entries = vec![
+        make_entry("notes.txt", false, Some(100), Some(1700000002)),
+        old_photo,
+        make_entry("no_exif.png", false, Some(100), Some(1700000001)),
+    ];
+
+    sort_entries(
+        &mut entries,
+        SortColumn::Captured,
+        SortOrder::Ascending,
+        DirectorySortMode::LikeFiles,
+    );
+
+    let names: Vec<&str> = entries.iter().map(|e| e.name.as_str()).collect();
+    assert_eq!(names, vec!["old_photo.jpg", "no_exif.png", "notes.txt"]);
+}
+
 #[test]
 fn test_dotfiles_sorted_before_regular_files_by_name() {
     let mut entries = vec![
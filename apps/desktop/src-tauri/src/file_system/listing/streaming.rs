@@ -11,8 +11,10 @@ use std::sync::{Arc, LazyLock, RwLock};
 use tauri_specta::Event;
 
 use crate::benchmark;
-use crate::file_system::listing::caching::{CachedListing, LISTING_CACHE};
+use crate::file_system::listing::caching::{CachedListing, LISTING_CACHE, next_listing_generation};
+use crate::file_system::listing::metadata::FileEntry;
 use crate::file_system::listing::sorting::{DirectorySortMode, SortColumn, SortOrder, sort_entries};
+use crate::file_system::listing::{capture_date, eviction, git_ignore};
 use crate::file_system::volume::VolumeError;
 use crate::file_system::volume::friendly_error::{
     ListingError, archive_needs_password_listing_error, archive_unreadable_listing_error, enrich_with_provider,
@@ -87,6 +89,37 @@ pub struct ListingCancelledEvent {
     pub listing_id: String,
 }
 
+/// How a streaming listing hands out entries while a huge folder is still being read.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub enum ListingChunkMode {
+    /// Each chunk is sorted by the listing's sort params before it's sent, so the UI can
+    /// merge chunks into a running order that refines as more arrive.
+    #[default]
+    Incremental,
+    /// Chunks come in read order and nothing already shown moves until `listing-complete`,
+    /// which carries the one final sort. For views where reordering rows would jitter.
+    Stable,
+}
+
+/// Chunk event payload (emitted while a huge folder is read, before read-complete)
+///
+/// Only backends that read incrementally send chunks, and only for folders big enough to
+/// need them (local: `CHUNKED_LISTING_MIN_ENTRIES`). Chunks are a preview: the cached
+/// listing that `listing-complete` announces is the source of truth.
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type, Event)]
+#[serde(rename_all = "camelCase")]
+#[tauri_specta(event_name = "listing-chunk")]
+pub struct ListingChunkEvent {
+    pub listing_id: String,
+    /// The chunk's entries, hidden ones already dropped unless the listing includes them.
+    pub entries: Vec<FileEntry>,
+    /// Entries read so far, this chunk included (hidden ones too).
+    pub loaded_count: usize,
+    /// `entries` is sorted by the listing's sort params (`Incremental` mode).
+    pub sorted: bool,
+}
+
 /// Read-complete event payload (emitted when read_dir finishes, before sorting/caching)
 #[derive(Debug, Clone, Serialize, Deserialize, specta::Type, Event)]
 #[serde(rename_all = "camelCase")]
@@ -113,12 +146,47 @@ pub struct StreamingListingState {
     pub cancelled: Arc<AtomicBool>,
     /// Async signal for `select!`-based cancellation during the listing I/O.
     pub cancel_notify: tokio::sync::Notify,
+    /// Stamped on the `CachedListing` this pass inserts. Cancelling drops the cached
+    /// listing only if it still carries this generation, so a restart under the same id
+    /// never loses its own entries to the cancel of the pass before it.
+    pub generation: u64,
 }
 
 /// Cache for streaming state (separate from completed listings cache)
 pub(crate) static STREAMING_STATE: LazyLock<RwLock<HashMap<String, Arc<StreamingListingState>>>> =
     LazyLock::new(|| RwLock::new(HashMap::new()));
 
+/// Registers a new streaming pass for `listing_id` and returns its state.
+///
+/// A pass still registered under the same id is superseded: it's cancelled here, so its
+/// reader discards whatever it reads instead of caching it over this pass's entries.
+pub(crate) fn register_streaming_state(listing_id: &str) -> Arc<StreamingListingState> {
+    let state = Arc::new(StreamingListingState {
+        cancelled: Arc::new(AtomicBool::new(false)),
+        cancel_notify: tokio::sync::Notify::new(),
+        generation: next_listing_generation(),
+    });
+    let superseded = match STREAMING_STATE.write() {
+        Ok(mut cache) => cache.insert(listing_id.to_string(), Arc::clone(&state)),
+        Err(_) => None,
+    };
+    if let Some(old) = superseded {
+        old.cancelled.store(true, Ordering::Relaxed);
+        old.cancel_notify.notify_waiters();
+    }
+    state
+}
+
+/// Removes `state` from `STREAMING_STATE` once its pass is done, unless a newer pass has
+/// taken over the id (then the entry is that pass's, and its cancel must keep working).
+pub(crate) fn unregister_streaming_state(listing_id: &str, state: &Arc<StreamingListingState>) {
+    if let Ok(mut cache) = STREAMING_STATE.write()
+        && cache.get(listing_id).is_some_and(|current| Arc::ptr_eq(current, state))
+    {
+        cache.remove(listing_id);
+    }
+}
+
 // ============================================================================
 // Event sink trait (decouples streaming from Tauri)
 // ============================================================================
@@ -129,6 +197,7 @@ pub(crate) static STREAMING_STATE: LazyLock<RwLock<HashMap<String, Arc<Streaming
 pub(crate) trait ListingEventSink: Send + Sync {
     fn emit_opening(&self, listing_id: &str);
     fn emit_progress(&self, listing_id: &str, loaded_count: usize);
+    fn emit_chunk(&self, listing_id: &str, entries: Vec<FileEntry>, loaded_count: usize, sorted: bool);
     fn emit_read_complete(&self, listing_id: &str, total_count: usize);
     fn emit_complete(&self, listing_id: &str, total_count: usize, volume_root: String);
     fn emit_error(&self, listing_id: &str, message: String, error: Option<ListingError>);
@@ -162,6 +231,16 @@ impl ListingEventSink for TauriListingEventSink {
         .emit(&self.app);
     }
 
+    fn emit_chunk(&self, listing_id: &str, entries: Vec<FileEntry>, loaded_count: usize, sorted: bool) {
+        let _ = ListingChunkEvent {
+            listing_id: listing_id.to_string(),
+            entries,
+            loaded_count,
+            sorted,
+        }
+        .emit(&self.app);
+    }
+
     fn emit_read_complete(&self, listing_id: &str, total_count: usize) {
         let _ = ListingReadCompleteEvent {
             listing_id: listing_id.to_string(),
@@ -211,6 +290,8 @@ impl ListingEventSink for TauriListingEventSink {
 pub(crate) struct CollectorListingEventSink {
     pub opening: std::sync::Mutex<Vec<String>>,
     pub progress: std::sync::Mutex<Vec<(String, usize)>>,
+    /// `(listing_id, entry names, loaded_count, sorted)` per chunk.
+    pub chunks: std::sync::Mutex<Vec<(String, Vec<String>, usize, bool)>>,
     pub read_complete: std::sync::Mutex<Vec<(String, usize)>>,
     pub complete: std::sync::Mutex<Vec<(String, usize)>>,
     pub errors: std::sync::Mutex<Vec<(String, String)>>,
@@ -223,6 +304,7 @@ impl CollectorListingEventSink {
         Self {
             opening: std::sync::Mutex::new(Vec::new()),
             progress: std::sync::Mutex::new(Vec::new()),
+            chunks: std::sync::Mutex::new(Vec::new()),
             read_complete: std::sync::Mutex::new(Vec::new()),
             complete: std::sync::Mutex::new(Vec::new()),
             errors: std::sync::Mutex::new(Vec::new()),
@@ -243,6 +325,13 @@ impl ListingEventSink for CollectorListingEventSink {
             .push((listing_id.to_string(), loaded_count));
     }
 
+    fn emit_chunk(&self, listing_id: &str, entries: Vec<FileEntry>, loaded_count: usize, sorted: bool) {
+        let names = entries.into_iter().map(|e| e.name).collect();
+        self.chunks
+            .lock_ignore_poison()
+            .push((listing_id.to_string(), names, loaded_count, sorted));
+    }
+
     fn emit_read_complete(&self, listing_id: &str, total_count: usize) {
         self.read_complete
             .lock_ignore_poison()
@@ -284,22 +373,16 @@ pub async fn list_directory_start_streaming(
     sort_by: SortColumn,
     sort_order: SortOrder,
     dir_sort_mode: DirectorySortMode,
+    chunk_mode: ListingChunkMode,
     listing_id: String,
 ) -> Result<StreamingListingStartResult, std::io::Error> {
     // Reset benchmark epoch for this navigation
     benchmark::reset_epoch();
     benchmark::log_event_value("list_directory_start_streaming CALLED", path.display());
+    benchmark::set_current_trace(&listing_id);
 
-    // Create streaming state with cancellation flag
-    let state = Arc::new(StreamingListingState {
-        cancelled: Arc::new(AtomicBool::new(false)),
-        cancel_notify: tokio::sync::Notify::new(),
-    });
-
-    // Store state for cancellation
-    if let Ok(mut cache) = STREAMING_STATE.write() {
-        cache.insert(listing_id.clone(), Arc::clone(&state));
-    }
+    // Create and store streaming state for cancellation
+    let state = register_streaming_state(&listing_id);
 
     // Clone values for the spawned task
     let listing_id_for_spawn = listing_id.clone();
@@ -324,13 +407,12 @@ pub async fn list_directory_start_streaming(
             sort_by,
             sort_order,
             dir_sort_mode,
+            chunk_mode,
         )
         .await;
 
         // Clean up streaming state
-        if let Ok(mut cache) = STREAMING_STATE.write() {
-            cache.remove(&listing_id_for_cleanup);
-        }
+        unregister_streaming_state(&listing_id_for_cleanup, &state);
 
         // Handle task result
         match result {
@@ -406,6 +488,7 @@ pub(crate) async fn read_directory_with_progress(
     sort_by: SortColumn,
     sort_order: SortOrder,
     dir_sort_mode: DirectorySortMode,
+    chunk_mode: ListingChunkMode,
 ) -> Result<(), VolumeError> {
     benchmark::log_event("read_directory_with_progress START");
     log::debug!(
@@ -448,6 +531,13 @@ pub(crate) async fn read_directory_with_progress(
     let events_for_progress = Arc::clone(events);
     let listing_id_for_progress = listing_id.to_string();
     let cancel_for_task = Arc::clone(&state.cancelled);
+    // A fresh background pre-fetch of this folder (see `prefetch.rs`) stands in for the read.
+    // Archives are never pre-fetched, so there's nothing to take for them.
+    let prefetched = if is_archive {
+        None
+    } else {
+        super::prefetch::take(volume_id, path).await
+    };
 
     let mut listing_task = tokio::spawn(async move {
         // Stall-probe: marker logged as the FIRST executable line inside the spawned task.
@@ -462,6 +552,14 @@ pub(crate) async fn read_directory_with_progress(
             listing_id_for_progress,
             path_for_task.display(),
         );
+        if let Some(entries) = prefetched {
+            log::debug!(
+                "Using pre-fetched listing for {} ({} entries)",
+                path_for_task.display(),
+                entries.len()
+            );
+            return Ok(entries);
+        }
         let on_progress = |p: crate::file_system::volume::ListingProgress| {
             // A cancelled listing keeps running until the backend reaches a safe
             // boundary (see the cancel arm below), but its listing_id is spent —
@@ -476,8 +574,23 @@ pub(crate) async fn read_directory_with_progress(
             // files + dirs for that.
             events_for_progress.emit_progress(&listing_id_for_progress, p.entries());
         };
+        // Same quiet-after-cancel rule as progress. Chunks skip index enrichment, so a
+        // by-size order of folders may still shift once `listing-complete` lands.
+        let chunked_count = std::sync::atomic::AtomicUsize::new(0);
+        let on_chunk = |mut chunk: Vec<FileEntry>| {
+            let loaded_count = chunked_count.fetch_add(chunk.len(), Ordering::Relaxed) + chunk.len();
+            if cancel_for_task.load(Ordering::Relaxed) {
+                return;
+            }
+            chunk.retain(|e| (include_hidden || !e.name.starts_with('.')) && !git_ignore::hides(e));
+            let sorted = chunk_mode == ListingChunkMode::Incremental;
+            if sorted {
+                sort_entries(&mut chunk, sort_by, sort_order, dir_sort_mode);
+            }
+            events_for_progress.emit_chunk(&listing_id_for_progress, chunk, loaded_count, sorted);
+        };
         volume_for_task
-            .list_directory_with_cancel(&path_for_task, Some(&on_progress), Some(&cancel_for_task))
+            .list_directory_chunked(&path_for_task, Some(&on_progress), &on_chunk, &cancel_for_task)
             .await
     });
 
@@ -505,7 +618,16 @@ pub(crate) async fn read_directory_with_progress(
         }
     };
 
-    let mut entries = entries_result?;
+    // A backend that honors the token can fail with `Cancelled` before the notify lands;
+    // that's the cancel winning the race, not a listing error.
+    let mut entries = match entries_result {
+        Err(_) if state.cancelled.load(Ordering::Relaxed) => {
+            benchmark::log_event("read_directory_with_progress CANCELLED (backend bailed)");
+            events.emit_cancelled(listing_id);
+            return Ok(());
+        }
+        result => result?,
+    };
     let read_dir_time = read_start.elapsed();
     benchmark::log_event_value("read_dir COMPLETE, entries", entries.len());
 
@@ -526,6 +648,7 @@ pub(crate) async fn read_directory_with_progress(
     if !is_archive {
         crate::indexing::enrich_entries_with_index_on_volume(volume_id, &mut entries);
         crate::indexing::trigger_verification(volume_id, &path.to_string_lossy());
+        capture_date::fill_for_sort(volume_id, sort_by, &mut entries);
     }
     let enrich_ms = enrich_start.elapsed().as_millis();
 
@@ -537,11 +660,10 @@ pub(crate) async fn read_directory_with_progress(
     benchmark::log_event("sort END");
 
     // Calculate counts based on include_hidden setting
-    let total_count = if include_hidden {
-        entries.len()
-    } else {
-        entries.iter().filter(|e| !e.name.starts_with('.')).count()
-    };
+    let total_count = entries
+        .iter()
+        .filter(|e| (include_hidden || !e.name.starts_with('.')) && !git_ignore::hides(e))
+        .count();
 
     // Cache the completed listing, with atomic cancellation check.
     // We check cancellation WHILE holding the cache lock to avoid a race condition:
@@ -567,6 +689,7 @@ pub(crate) async fn read_directory_with_progress(
                 sort_by,
                 sort_order,
                 directory_sort_mode: dir_sort_mode,
+                generation: state.generation,
                 sequence: std::sync::atomic::AtomicU64::new(0),
                 created_at: std::time::Instant::now(),
                 last_accessed_ms: std::sync::atomic::AtomicU64::new(
@@ -592,6 +715,9 @@ pub(crate) async fn read_directory_with_progress(
     }
     let watcher_start_ms = watcher_start_t.elapsed().as_millis();
 
+    // A new listing may push the cache over its memory cap; inactive ones make room.
+    eviction::enforce_cap();
+
     // Volume root for the event (the FE uses it to decide "at volume root"). For
     // an archive this is the `.zip` path, so the FE breadcrumb renders
     // `…/foo.zip/inner` and treats the archive root as the volume root.
@@ -620,6 +746,10 @@ pub(crate) async fn read_directory_with_progress(
     let emit_t = std::time::Instant::now();
     events.emit_complete(listing_id, total_count, volume_root);
     let to_complete_emit_ms = emit_t.elapsed().as_millis();
+
+    if !is_archive {
+        super::prefetch::prefetch_adjacent(volume_id, path);
+    }
     let total_ms = total_start.elapsed().as_millis();
 
     // Consolidated INFO log for the listing pipeline (Phase 1 instrumentation).
@@ -648,13 +778,24 @@ pub(crate) async fn read_directory_with_progress(
 
 /// Cancels an in-progress streaming listing.
 ///
-/// Sets the cancellation flag, which will be checked by the background task.
+/// Sets the cancellation flag, which will be checked by the background task, and drops
+/// the cached listing if this pass already inserted it. Both happen against the cache
+/// lock the insert holds while checking the flag, so the pass's entries are either never
+/// cached or removed here: nothing of a cancelled pass survives into a restart.
 pub fn cancel_listing(listing_id: &str) {
-    if let Ok(cache) = STREAMING_STATE.read()
-        && let Some(state) = cache.get(listing_id)
+    let state = match STREAMING_STATE.read() {
+        Ok(cache) => cache.get(listing_id).cloned(),
+        Err(_) => None,
+    };
+    let Some(state) = state else {
+        return;
+    };
+    state.cancelled.store(true, Ordering::Relaxed);
+    state.cancel_notify.notify_waiters();
+    if let Ok(mut cache) = LISTING_CACHE.write()
+        && cache.get(listing_id).is_some_and(|l| l.generation == state.generation)
     {
-        state.cancelled.store(true, Ordering::Relaxed);
-        state.cancel_notify.notify_waiters();
-        benchmark::log_event_value("cancel_listing", listing_id);
+        cache.remove(listing_id);
     }
+    benchmark::log_event_value("cancel_listing", listing_id);
 }
@@ -13,7 +13,7 @@ use crate::file_system::listing::caching::LISTING_CACHE;
 use crate::file_system::listing::metadata::FileEntry;
 use crate::file_system::listing::sorting::{DirectorySortMode, SortColumn, SortOrder};
 use crate::file_system::listing::streaming::{
-    CollectorListingEventSink, ListingEventSink, StreamingListingState, read_directory_with_progress,
+    CollectorListingEventSink, ListingChunkMode, ListingEventSink, StreamingListingState, read_directory_with_progress,
 };
 use crate::file_system::volume::{InMemoryVolume, ListingProgress, Volume, VolumeError};
 use crate::test_support::wait_until_async;
@@ -51,6 +51,7 @@ fn new_state() -> Arc<StreamingListingState> {
     Arc::new(StreamingListingState {
         cancelled: Arc::new(AtomicBool::new(false)),
         cancel_notify: tokio::sync::Notify::new(),
+        generation: 0,
     })
 }
 
@@ -79,6 +80,7 @@ async fn test_streaming_list_populates_cache() {
         SortColumn::Name,
         SortOrder::Ascending,
         DirectorySortMode::LikeFiles,
+        ListingChunkMode::Incremental,
     )
     .await;
 
@@ -131,6 +133,7 @@ async fn test_streaming_list_emits_opening_and_complete() {
         SortColumn::Name,
         SortOrder::Ascending,
         DirectorySortMode::LikeFiles,
+        ListingChunkMode::Incremental,
     )
     .await;
 
@@ -178,6 +181,7 @@ async fn test_streaming_list_cancellation() {
         SortColumn::Name,
         SortOrder::Ascending,
         DirectorySortMode::LikeFiles,
+        ListingChunkMode::Incremental,
     )
     .await;
 
@@ -218,6 +222,7 @@ async fn test_streaming_list_volume_not_found() {
         SortColumn::Name,
         SortOrder::Ascending,
         DirectorySortMode::LikeFiles,
+        ListingChunkMode::Incremental,
     )
     .await;
 
@@ -251,6 +256,7 @@ async fn test_streaming_list_empty_directory() {
         SortColumn::Name,
         SortOrder::Ascending,
         DirectorySortMode::LikeFiles,
+        ListingChunkMode::Incremental,
     )
     .await;
 
@@ -426,6 +432,7 @@ async fn test_cancel_unwinds_the_listing_instead_of_aborting_it() {
                 SortColumn::Name,
                 SortOrder::Ascending,
                 DirectorySortMode::LikeFiles,
+                ListingChunkMode::Incremental,
             )
             .await
         })
@@ -461,3 +468,296 @@ async fn test_cancel_unwinds_the_listing_instead_of_aborting_it() {
 
     cleanup(volume_id, listing_id);
 }
+
+/// A volume whose first listing is stuck until released and then returns a stale view
+/// (`stale.txt`), ignoring its cancel token like a hung network drive. Every later
+/// listing answers at once with the current view.
+struct StuckFirstListingVolume {
+    root: std::path::PathBuf,
+    calls: std::sync::atomic::AtomicUsize,
+    started: Arc<AtomicBool>,
+    stuck_finished: Arc<AtomicBool>,
+    release: Arc<tokio::sync::Notify>,
+}
+
+impl Volume for StuckFirstListingVolume {
+    fn name(&self) -> &str {
+        "Stuck first listing volume"
+    }
+
+    fn root(&self) -> &Path {
+        &self.root
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn list_directory<'a>(
+        &'a self,
+        _path: &'a Path,
+        _on_progress: Option<&'a (dyn Fn(ListingProgress) + Sync)>,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<FileEntry>, VolumeError>> + Send + 'a>> {
+        let first = self.calls.fetch_add(1, Ordering::SeqCst) == 0;
+        Box::pin(async move {
+            if !first {
+                return Ok(vec![test_entry("a.txt", false), test_entry("b.txt", false)]);
+            }
+            self.started.store(true, Ordering::SeqCst);
+            self.release.notified().await;
+            self.stuck_finished.store(true, Ordering::SeqCst);
+            Ok(vec![test_entry("stale.txt", false), test_entry("a.txt", false)])
+        })
+    }
+
+    fn get_metadata<'a>(
+        &'a self,
+        _path: &'a Path,
+    ) -> Pin<Box<dyn Future<Output = Result<FileEntry, VolumeError>> + Send + 'a>> {
+        Box::pin(async { Err(VolumeError::NotFound("not implemented".to_string())) })
+    }
+
+    fn exists<'a>(&'a self, _path: &'a Path) -> Pin<Box<dyn Future<Output = bool> + Send + 'a>> {
+        Box::pin(async { true })
+    }
+
+    fn is_directory<'a>(
+        &'a self,
+        _path: &'a Path,
+    ) -> Pin<Box<dyn Future<Output = Result<bool, VolumeError>> + Send + 'a>> {
+        Box::pin(async { Ok(true) })
+    }
+}
+
+/// Cancelling a streaming listing mid-flight and restarting it under the same id must
+/// leave only the restart's entries: the cancelled pass's late result, its cleanup, and a
+/// watcher write read under its generation all miss the new listing.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_cancel_then_restart_leaves_no_ghost_entries() {
+    use crate::file_system::listing::operations::update_listing_entries;
+    use crate::file_system::listing::streaming::{
+        STREAMING_STATE, cancel_listing, register_streaming_state, unregister_streaming_state,
+    };
+
+    let volume_id = &format!("test-restart-{}", uuid::Uuid::new_v4());
+    let listing_id = &format!("listing-restart-{}", uuid::Uuid::new_v4());
+
+    let volume = Arc::new(StuckFirstListingVolume {
+        root: std::path::PathBuf::from("/"),
+        calls: std::sync::atomic::AtomicUsize::new(0),
+        started: Arc::new(AtomicBool::new(false)),
+        stuck_finished: Arc::new(AtomicBool::new(false)),
+        release: Arc::new(tokio::sync::Notify::new()),
+    });
+    let started = Arc::clone(&volume.started);
+    let stuck_finished = Arc::clone(&volume.stuck_finished);
+    let release = Arc::clone(&volume.release);
+    crate::file_system::get_volume_manager().register(volume_id, Arc::clone(&volume) as Arc<dyn Volume>);
+
+    let sink = Arc::new(CollectorListingEventSink::new());
+    let events: Arc<dyn ListingEventSink> = Arc::clone(&sink) as Arc<dyn ListingEventSink>;
+    let read = |state: Arc<StreamingListingState>| {
+        let events = Arc::clone(&events);
+        let volume_id = volume_id.clone();
+        let listing_id = listing_id.clone();
+        tokio::spawn(async move {
+            read_directory_with_progress(
+                &events,
+                &listing_id,
+                &state,
+                &volume_id,
+                Path::new("/"),
+                true,
+                SortColumn::Name,
+                SortOrder::Ascending,
+                DirectorySortMode::LikeFiles,
+                ListingChunkMode::Incremental,
+            )
+            .await
+        })
+    };
+
+    // Pass 1: start, then cancel while the backend is stuck.
+    let first = register_streaming_state(listing_id);
+    let first_read = read(Arc::clone(&first));
+    wait_until_async(FLAG_FLIPS_WITHIN, "the first listing to start", || {
+        started.load(Ordering::SeqCst)
+    })
+    .await;
+    cancel_listing(listing_id);
+
+    // Pass 2 under the same id. Pass 1's cleanup lands late, after the restart registered.
+    let second = register_streaming_state(listing_id);
+    assert!(second.generation > first.generation);
+    unregister_streaming_state(listing_id, &first);
+    assert!(
+        STREAMING_STATE
+            .read()
+            .unwrap()
+            .get(listing_id.as_str())
+            .is_some_and(|s| Arc::ptr_eq(s, &second)),
+        "the superseded pass's cleanup must not unregister the restart"
+    );
+
+    // The stuck backend now returns its stale view, after the restart began.
+    release.notify_one();
+    assert!(first_read.await.expect("first pass must not panic").is_ok());
+    let second_read = read(Arc::clone(&second));
+    assert!(second_read.await.expect("second pass must not panic").is_ok());
+    wait_until_async(FLAG_FLIPS_WITHIN, "the stuck first listing to return", || {
+        stuck_finished.load(Ordering::SeqCst)
+    })
+    .await;
+
+    // A watcher re-read that snapshotted pass 1's generation must not land.
+    let stale = vec![test_entry("stale.txt", false), test_entry("a.txt", false)];
+    assert!(!update_listing_entries(listing_id, first.generation, stale));
+
+    {
+        let cache = LISTING_CACHE.read().unwrap();
+        let cached = cache.get(listing_id).expect("the restart's listing should be cached");
+        assert_eq!(cached.generation, second.generation);
+        let names: Vec<&str> = cached.entries.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, ["a.txt", "b.txt"], "no duplicate or ghost entries");
+    }
+    assert_eq!(sink.complete.lock().unwrap().len(), 1, "only the restart completes");
+
+    // Cancel still reaches the live pass, and drops the listing it cached.
+    cancel_listing(listing_id);
+    assert!(second.cancelled.load(Ordering::Relaxed));
+    assert!(LISTING_CACHE.read().unwrap().get(listing_id).is_none());
+
+    unregister_streaming_state(listing_id, &second);
+    cleanup(volume_id, listing_id);
+}
+
+/// A volume that reads in two chunks, in an order no sort produces, with one hidden entry.
+struct TwoChunkVolume {
+    root: std::path::PathBuf,
+}
+
+impl TwoChunkVolume {
+    fn chunks() -> [Vec<FileEntry>; 2] {
+        [
+            vec![test_entry("zulu.txt", false), test_entry(".hidden", false)],
+            vec![test_entry("mike.txt", false), test_entry("alpha.txt", false)],
+        ]
+    }
+}
+
+impl Volume for TwoChunkVolume {
+    fn name(&self) -> &str {
+        "Two chunk volume"
+    }
+
+    fn root(&self) -> &Path {
+        &self.root
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn list_directory<'a>(
+        &'a self,
+        _path: &'a Path,
+        _on_progress: Option<&'a (dyn Fn(ListingProgress) + Sync)>,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<FileEntry>, VolumeError>> + Send + 'a>> {
+        Box::pin(async { Ok(Self::chunks().concat()) })
+    }
+
+    fn list_directory_chunked<'a>(
+        &'a self,
+        _path: &'a Path,
+        _on_progress: Option<&'a (dyn Fn(ListingProgress) + Sync)>,
+        on_chunk: &'a (dyn Fn(Vec<FileEntry>) + Sync),
+        _cancel: &'a Arc<AtomicBool>,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<FileEntry>, VolumeError>> + Send + 'a>> {
+        Box::pin(async move {
+            for chunk in Self::chunks() {
+                on_chunk(chunk);
+            }
+            Ok(Self::chunks().concat())
+        })
+    }
+
+    fn get_metadata<'a>(
+        &'a self,
+        _path: &'a Path,
+    ) -> Pin<Box<dyn Future<Output = Result<FileEntry, VolumeError>> + Send + 'a>> {
+        Box::pin(async { Err(VolumeError::NotFound("not implemented".to_string())) })
+    }
+
+    fn exists<'a>(&'a self, _path: &'a Path) -> Pin<Box<dyn Future<Output = bool> + Send + 'a>> {
+        Box::pin(async { true })
+    }
+
+    fn is_directory<'a>(
+        &'a self,
+        _path: &'a Path,
+    ) -> Pin<Box<dyn Future<Output = Result<bool, VolumeError>> + Send + 'a>> {
+        Box::pin(async { Ok(true) })
+    }
+}
+
+/// `Incremental` chunks arrive sorted, `Stable` ones in read order; both drop hidden
+/// entries when the listing hides them, count every entry read, and end in the same
+/// fully sorted cached listing.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_chunks_follow_the_chunk_mode() {
+    for (mode, sorted) in [(ListingChunkMode::Incremental, true), (ListingChunkMode::Stable, false)] {
+        let volume_id = &format!("test-chunks-{}", uuid::Uuid::new_v4());
+        let listing_id = &format!("listing-chunks-{}", uuid::Uuid::new_v4());
+        let volume = Arc::new(TwoChunkVolume {
+            root: std::path::PathBuf::from("/"),
+        });
+        crate::file_system::get_volume_manager().register(volume_id, volume as Arc<dyn Volume>);
+
+        let sink = Arc::new(CollectorListingEventSink::new());
+        let events: Arc<dyn ListingEventSink> = Arc::clone(&sink) as Arc<dyn ListingEventSink>;
+        let result = read_directory_with_progress(
+            &events,
+            listing_id,
+            &new_state(),
+            volume_id,
+            Path::new("/"),
+            false,
+            SortColumn::Name,
+            SortOrder::Ascending,
+            DirectorySortMode::LikeFiles,
+            mode,
+        )
+        .await;
+        assert!(result.is_ok(), "{mode:?}: {result:?}");
+
+        {
+            let chunks = sink.chunks.lock().unwrap();
+            assert_eq!(chunks.len(), 2, "{mode:?}");
+            assert_eq!(chunks[0].1, ["zulu.txt"], "{mode:?}: hidden entries are dropped");
+            assert_eq!(chunks[0].2, 2, "{mode:?}: the count includes hidden entries");
+            let second: Vec<&str> = chunks[1].1.iter().map(String::as_str).collect();
+            let expected_second = if sorted {
+                ["alpha.txt", "mike.txt"]
+            } else {
+                ["mike.txt", "alpha.txt"]
+            };
+            assert_eq!(second, expected_second, "{mode:?}");
+            assert_eq!(chunks[1].2, 4, "{mode:?}");
+            assert!(chunks.iter().all(|c| c.3 == sorted), "{mode:?}");
+        }
+        {
+            let cache = LISTING_CACHE.read().unwrap();
+            let names: Vec<&str> = cache
+                .get(listing_id)
+                .expect("listing should be cached")
+                .entries
+                .iter()
+                .map(|e| e.name.as_str())
+                .filter(|name| !name.starts_with('.'))
+                .collect();
+            assert_eq!(names, ["alpha.txt", "mike.txt", "zulu.txt"], "{mode:?}");
+        }
+
+        cleanup(volume_id, listing_id);
+    }
+}
@@ -0,0 +1,137 @@
+//! Sidebar tree data source: one folder's subfolders at a time, each with a `has_children`
+//! flag so the tree can draw an expand arrow without walking further. Expanding a node asks
+//! for its level; nothing is fetched eagerly. Unlike a pane listing, nothing is cached and
+//! there's no listing id: a node is just `(volume_id, path)`.
+//!
+//! **Sources.** When the drive index covers the folder ([`crate::indexing::tree_children`]),
+//! one index query answers the whole level. Otherwise the folder is read with `std::fs`, plus
+//! one `read_dir` per subfolder that stops at its first visible subfolder. Only real folders
+//! count; symlinks to folders are left out, matching the index's `child_counts`.
+//!
+//! **Filters.** `include_hidden` and `exclude_globs` apply to both the nodes and their
+//! `has_children` flag, so a folder holding only `.git` shows no arrow with hidden files off.
+//! Patterns use gitignore syntax relative to the volume root, like the flat listing's.
+//!
+//! **Freshness.** The frontend re-fetches an expanded node when an `index-dir-updated` event
+//! names its path (the index reports a changed folder's whole ancestor chain).
+
+use std::path::{Path, PathBuf};
+
+use ignore::gitignore::Gitignore;
+use serde::{Deserialize, Serialize};
+
+use crate::file_system::listing::flat::build_excludes;
+use crate::file_system::listing::sorting::compare_names_natural;
+
+/// Which subfolders a tree level shows. Both fields default, so the frontend can send `{}`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TreeChildrenOptions {
+    /// Whether dot-folders are shown (and count toward `has_children`).
+    pub include_hidden: bool,
+    /// Gitignore-style patterns (`node_modules`, `/Volumes`) matched against paths relative
+    /// to the volume root. An excluded folder is neither shown nor counted.
+    pub exclude_globs: Vec<String>,
+}
+
+/// One subfolder in the sidebar tree.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TreeNode {
+    pub name: String,
+    pub path: String,
+    /// Whether the folder has at least one visible subfolder, so the node can expand.
+    pub has_children: bool,
+}
+
+/// The visible subfolders of `path` on `volume_id`, sorted by name (natural, case-insensitive).
+pub fn get_tree_children(volume_id: &str, path: &Path, options: &TreeChildrenOptions) -> Result<Vec<TreeNode>, String> {
+    let volume = crate::file_system::get_volume_manager()
+        .get(volume_id)
+        .ok_or_else(|| format!("Volume not found: {volume_id}"))?;
+    if !volume.supports_local_fs_access() {
+        return Err("This volume can't be shown as a tree".to_string());
+    }
+    let excludes = build_excludes(volume.root(), &options.exclude_globs)?;
+    let filter = NodeFilter {
+        include_hidden: options.include_hidden,
+        excludes: &excludes,
+    };
+
+    let mut nodes = match from_index(path, &filter) {
+        Some(nodes) => nodes,
+        None => from_disk(path, &filter).map_err(|e| format!("Couldn't read {}: {e}", path.display()))?,
+    };
+    nodes.sort_by(|a, b| compare_names_natural(&a.name, &b.name));
+    Ok(nodes)
+}
+
+/// The hidden and exclude rules, applied to a folder by its full path.
+struct NodeFilter<'a> {
+    include_hidden: bool,
+    excludes: &'a Gitignore,
+}
+
+impl NodeFilter<'_> {
+    fn shows(&self, name: &str, path: &Path) -> bool {
+        (self.include_hidden || !name.starts_with('.')) && !self.excludes.matched(path, true).is_ignore()
+    }
+}
+
+fn from_index(path: &Path, filter: &NodeFilter<'_>) -> Option<Vec<TreeNode>> {
+    let rows = match crate::indexing::tree_children(&path.to_string_lossy()) {
+        Ok(rows) => rows?,
+        Err(e) => {
+            log::debug!(target: "listing::tree", "Index lookup failed for {path:?}, reading the disk instead: {e}");
+            return None;
+        }
+    };
+    let nodes = rows
+        .into_iter()
+        .filter_map(|row| {
+            let child = path.join(&row.name);
+            if !filter.shows(&row.name, &child) {
+                return None;
+            }
+            let has_children = row
+                .subdir_names
+                .iter()
+                .any(|name| filter.shows(name, &child.join(name)));
+            Some(node(row.name, child, has_children))
+        })
+        .collect();
+    Some(nodes)
+}
+
+fn from_disk(path: &Path, filter: &NodeFilter<'_>) -> std::io::Result<Vec<TreeNode>> {
+    let mut nodes = Vec::new();
+    for (name, child) in visible_subfolders(path, filter)? {
+        let has_children = visible_subfolders(&child, filter).is_ok_and(|mut subfolders| subfolders.next().is_some());
+        nodes.push(node(name, child, has_children));
+    }
+    Ok(nodes)
+}
+
+/// `(name, path)` of each real, visible subfolder of `path`, lazily. Unreadable entries are
+/// skipped rather than failing the level.
+fn visible_subfolders(
+    path: &Path,
+    filter: &NodeFilter<'_>,
+) -> std::io::Result<impl Iterator<Item = (String, PathBuf)>> {
+    Ok(std::fs::read_dir(path)?.flatten().filter_map(move |entry| {
+        if !entry.file_type().is_ok_and(|t| t.is_dir()) {
+            return None;
+        }
+        let name = entry.file_name().to_string_lossy().to_string();
+        let child = entry.path();
+        filter.shows(&name, &child).then_some((name, child))
+    }))
+}
+
+fn node(name: String, path: PathBuf, has_children: bool) -> TreeNode {
+    TreeNode {
+        name,
+        path: path.to_string_lossy().to_string(),
+        has_children,
+    }
+}
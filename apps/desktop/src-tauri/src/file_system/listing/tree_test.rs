@@ -0,0 +1,88 @@
+//! Sidebar tree tests: one level of real subfolders, `has_children` under the hidden and
+//! exclude rules, and the local-volume requirement.
+
+use std::sync::Arc;
+
+use crate::file_system::listing::tree::{TreeChildrenOptions, TreeNode, get_tree_children};
+use crate::file_system::volume::LocalPosixVolume;
+
+fn tree() -> tempfile::TempDir {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let root = dir.path();
+    for folder in [
+        "src/lib",
+        "Docs",
+        "only-hidden/.git",
+        "node_modules/pkg",
+        "with-excluded/node_modules",
+        ".config/app",
+        "folder10",
+        "folder9",
+    ] {
+        std::fs::create_dir_all(root.join(folder)).expect("mkdir");
+    }
+    std::fs::write(root.join("README.md"), b"x").expect("write");
+    std::fs::write(root.join("Docs/guide.md"), b"x").expect("write");
+    #[cfg(unix)]
+    std::os::unix::fs::symlink(root.join("src"), root.join("src-link")).expect("symlink");
+    dir
+}
+
+fn children(root: &std::path::Path, path: &std::path::Path, options: &TreeChildrenOptions) -> Vec<TreeNode> {
+    let volume_id = format!("tree-test-{}", uuid::Uuid::new_v4());
+    crate::file_system::get_volume_manager().register(&volume_id, Arc::new(LocalPosixVolume::new("Tree test", root)));
+    let nodes = get_tree_children(&volume_id, path, options).expect("tree children");
+    crate::file_system::get_volume_manager().unregister(&volume_id);
+    nodes
+}
+
+fn summary(nodes: &[TreeNode]) -> Vec<(&str, bool)> {
+    nodes.iter().map(|n| (n.name.as_str(), n.has_children)).collect()
+}
+
+#[test]
+fn lists_real_subfolders_sorted_with_has_children() {
+    let dir = tree();
+    let nodes = children(dir.path(), dir.path(), &TreeChildrenOptions::default());
+    assert_eq!(
+        summary(&nodes),
+        vec![
+            ("Docs", false),
+            ("folder9", false),
+            ("folder10", false),
+            ("node_modules", true),
+            ("only-hidden", false),
+            ("src", true),
+            ("with-excluded", true),
+        ]
+    );
+    assert_eq!(nodes[5].path, dir.path().join("src").to_string_lossy());
+}
+
+#[test]
+fn hidden_and_excluded_folders_are_neither_shown_nor_counted() {
+    let dir = tree();
+    let options = TreeChildrenOptions {
+        include_hidden: true,
+        exclude_globs: vec!["node_modules".into()],
+    };
+    let nodes = children(dir.path(), dir.path(), &options);
+    let names = summary(&nodes);
+    assert!(names.contains(&(".config", true)), "got {names:?}");
+    assert!(names.contains(&("only-hidden", true)), "got {names:?}");
+    assert!(names.contains(&("with-excluded", false)), "got {names:?}");
+    assert!(!names.iter().any(|(name, _)| *name == "node_modules"), "got {names:?}");
+}
+
+#[test]
+fn expanding_a_node_lists_its_level() {
+    let dir = tree();
+    let nodes = children(dir.path(), &dir.path().join("src"), &TreeChildrenOptions::default());
+    assert_eq!(summary(&nodes), vec![("lib", false)]);
+}
+
+#[test]
+fn unknown_volume_is_an_error() {
+    let dir = tree();
+    assert!(get_tree_children("no-such-volume", dir.path(), &TreeChildrenOptions::default()).is_err());
+}
@@ -1,10 +1,23 @@
 //! File system module - operations, watchers, volumes, and providers.
 
+pub mod case_sensitivity;
+pub mod checksum;
 pub mod cloud_actions;
+pub mod compare;
+pub mod conflict_policies;
+pub mod default_action;
+pub mod dir_entry_count;
+pub mod duplicates;
+pub(crate) mod extension;
+pub mod extension_breakdown;
+pub mod file_diff;
+pub mod file_info;
 #[cfg(target_os = "macos")]
 pub(crate) mod file_provider;
 pub mod filesystem_kind;
+pub mod folder_size;
 pub mod git;
+pub mod largest_files;
 #[cfg(target_os = "linux")]
 pub(crate) mod linux_mounts;
 pub(crate) mod listing;
@@ -12,15 +25,17 @@ pub(crate) mod listing;
 mod macos_metadata;
 #[cfg(test)]
 mod mock_provider;
-#[cfg(target_os = "macos")]
+pub mod move_targets;
 pub mod open_with;
+pub mod open_with_recents;
 #[cfg(test)]
 mod provider;
 #[cfg(test)]
 mod real_provider;
-#[cfg(target_os = "macos")]
 pub mod sync_status;
 pub mod tags;
+pub mod tree_hash;
+pub mod treemap;
 pub mod validation;
 pub mod volume;
 pub(crate) mod watcher;
@@ -33,11 +48,11 @@ use std::sync::{Arc, LazyLock};
 #[allow(unused_imports, reason = "Public API re-exports for future use")]
 pub use listing::ExtendedMetadata;
 pub use listing::{
-    BriefColumnsError, DirectorySortMode, FileEntry, ListingStartResult, ListingStats, ResortResult, SortColumn,
-    SortOrder, StreamingListingStartResult, cancel_listing, compute_brief_column_text_widths, find_file_index,
-    find_file_indices, fuzzy_find_first_match_in_listing, get_file_at, get_file_range, get_listing_stats,
-    get_total_count, list_directory_end, list_directory_start_streaming, list_directory_start_with_volume,
-    refresh_listing_index_sizes, resort_listing,
+    BriefColumnsError, DirectorySortMode, FileEntry, ListingChunkMode, ListingStartResult, ListingStats, ResortResult,
+    SortColumn, SortOrder, StreamingListingStartResult, cancel_listing, compute_brief_column_text_widths,
+    find_file_index, find_file_indices, find_next_match_in_listing, fuzzy_find_first_match_in_listing, get_file_at,
+    get_file_range, get_listing_stats, get_total_count, list_directory_end, list_directory_start_streaming,
+    list_directory_start_with_volume, prefetch_listing, refresh_listing_index_sizes, resort_listing,
 };
 // Batch accessors (used by drag, clipboard, and transfer dialogs)
 pub use listing::{get_files_at_indices, get_paths_at_indices};
@@ -54,30 +69,35 @@ pub use volume::SmbVolume;
 pub use volume::manager::VolumeManager;
 #[allow(unused_imports, reason = "Public API re-exports for future use")]
 pub use volume::{
-    BatchScanResult, CopyScanResult, InMemoryVolume, LocalPosixVolume, MutationEvent, ScanConflict, SourceItemInfo,
-    SpaceInfo, Volume, VolumeError,
+    BatchScanResult, CopyScanResult, InMemoryVolume, LocalPosixVolume, MutationEvent, ScanConflict, ScanConflictKind,
+    SourceItemInfo, SpaceInfo, Volume, VolumeError,
 };
 // Watcher management - init_watcher_manager must be called from lib.rs
 #[cfg(feature = "playwright-e2e")]
 pub use watcher::flush_all_watchers;
-pub use watcher::{init_watcher_manager, update_debounce_ms};
+pub use watcher::{
+    WatcherListingStats, WatcherVolumeKind, debounce_for, init_watcher_manager, update_debounce_ms,
+    update_debounce_override_ms, watcher_stats,
+};
 // Diff types for file watching (used by MTP module for unified diff events)
 #[cfg(any(target_os = "macos", target_os = "linux"))]
 pub(crate) use watcher::compute_diff;
 // Re-export write operation types
 pub use write_operations::{
-    OperationEventSink, OperationStatus, OperationSummary, TauriEventSink, WriteOperationConfig, WriteOperationError,
-    WriteOperationStartResult, busy_volume_ids, cancel_all_write_operations, cancel_write_operation, copy_files_start,
-    delete_files_start, get_operation_status, init_busy_volume_emitter, list_active_operations, move_files_start,
+    OperationEventSink, OperationStatus, OperationSummary, TauriEventSink, TrashInfo, WriteOperationConfig,
+    WriteOperationError, WriteOperationStartResult, busy_volume_ids, cancel_all_write_operations,
+    cancel_write_operation, copy_files_start, delete_files_start, empty_trash_start, get_operation_status,
+    get_trash_info, init_busy_volume_emitter, list_active_operations, move_files_start, operations_on_volume,
     trash_files_start,
 };
 // Re-export the operation manager surface (queue + lifecycle). `LifecycleStatus`
 // and `OperationsChanged` are reached directly via `write_operations::` (the IPC
 // event registration and snapshot field), so they don't need a re-export here.
 pub use write_operations::{
-    OperationSnapshot, cancel_operation, cancel_operations, init_operation_event_emitter, list_operations, pause_all,
-    pause_operation, resume_all, resume_operation,
+    OperationSnapshot, cancel_operation, cancel_operations, init_operation_event_emitter, list_operations,
+    move_operation, pause_all, pause_operation, resume_all, resume_operation,
 };
+pub(crate) use write_operations::{cancel_all_operations, cancel_operations_on_volume, wait_for_operations_to_settle};
 // Re-export volume copy types and functions
 /// Copy/move INTO a zip routing (the command layer routes an archive destination
 /// here). Crate-internal — not part of the public write-ops surface.
@@ -222,6 +242,25 @@ pub fn get_volume_manager() -> &'static VolumeManager {
     &VOLUME_MANAGER
 }
 
+/// Stop everything still pointing at a volume that's gone: cancel the write ops
+/// that touch it (a copy onto a yanked drive ends with `DeviceDisconnected`
+/// instead of hanging) and end its listings, which closes their watchers. The
+/// frontend navigates its panes away on the `volume-unmounted` event. Both
+/// platforms' unmount handlers call this before unregistering the volume.
+pub(crate) fn release_unmounted_volume(volume_id: &str, volume_path: &str) {
+    let cancelled = cancel_operations_on_volume(volume_id, volume_path);
+    let ended = listing::end_listings_on_volume(volume_id, std::path::Path::new(volume_path));
+    if !cancelled.is_empty() || !ended.is_empty() {
+        log::debug!(
+            "Released unmounted volume {} ({}): {} op(s) cancelled, {} listing(s) ended",
+            volume_id,
+            volume_path,
+            cancelled.len(),
+            ended.len()
+        );
+    }
+}
+
 /// Upgrades all existing SMB mounts to direct smb2 connections (background task).
 ///
 /// Scans all registered volumes, finds those on `smbfs`, and tries to establish
@@ -0,0 +1,231 @@
+//! Recently used move destinations (`recent-move-targets.json`), for a "quick move" picker.
+//!
+//! Every started (non-dry-run) local move records its destination folder here, newest first, so the
+//! frontend can offer "Move to…" shortcuts without a folder picker. Pure metadata around the move
+//! pipeline: a failed write only loses a recent, never a move.
+//!
+//! Same shape as `open_with_recents.rs`: in-memory cache loaded lazily, durable temp-then-rename
+//! write, and a schema-versioned file that's quarantined on a parse error. The one difference is the
+//! store path: `move_files_start` has no `AppHandle`, so app setup resolves it once via [`init`].
+//!
+//! Entries are never dropped because their folder is missing: a target on an ejected drive or an
+//! offline share is listed with `available: false`, and comes back to life when the volume remounts.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+use serde::{Deserialize, Serialize};
+
+use crate::ignore_poison::IgnorePoison;
+
+/// Bump when the on-disk shape changes in an incompatible way.
+const CURRENT_SCHEMA_VERSION: u32 = 1;
+
+/// Filename inside `{app_data_dir}/`.
+const TARGETS_FILENAME: &str = "recent-move-targets.json";
+
+/// Fixed cap on remembered destinations. Matches the go-to-path recents (digit keys 1-9, 0).
+const MAX_TARGETS: usize = 10;
+
+/// A persisted destination, newest first in the store.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StoredTarget {
+    path: String,
+    /// Unix epoch milliseconds of the most recent move into `path`.
+    last_used: i64,
+}
+
+/// On-disk shape.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TargetsStore {
+    #[serde(rename = "_schemaVersion")]
+    schema_version: u32,
+    #[serde(default)]
+    targets: Vec<StoredTarget>,
+}
+
+/// A recent move destination as returned to the frontend.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct RecentMoveTarget {
+    pub path: String,
+    /// Unix epoch milliseconds of the most recent move into `path`.
+    pub last_used: i64,
+    /// `false` when the folder can't be reached right now (its volume is unmounted, or it's gone).
+    pub available: bool,
+}
+
+/// `None` until the first access loads from disk.
+static CACHE: OnceLock<Mutex<Option<TargetsStore>>> = OnceLock::new();
+
+/// Serializes the cache → disk flush so concurrent moves can't clobber each other.
+static DISK_LOCK: OnceLock<Mutex<()>> = OnceLock::new();
+
+/// `{app_data_dir}/recent-move-targets.json`, set once from app setup. Unset (tests, or an
+/// unresolvable data dir) keeps the store in memory only.
+static STORE_PATH: OnceLock<PathBuf> = OnceLock::new();
+
+fn cache() -> &'static Mutex<Option<TargetsStore>> {
+    CACHE.get_or_init(|| Mutex::new(None))
+}
+
+fn disk_lock() -> &'static Mutex<()> {
+    DISK_LOCK.get_or_init(|| Mutex::new(()))
+}
+
+/// Moves `path` to the front (deduped, timestamp bumped), capped at [`MAX_TARGETS`].
+fn apply_move(store: &mut TargetsStore, path: &str, now_ms: i64) {
+    store.targets.retain(|t| t.path != path);
+    store.targets.insert(
+        0,
+        StoredTarget {
+            path: path.to_string(),
+            last_used: now_ms,
+        },
+    );
+    store.targets.truncate(MAX_TARGETS);
+}
+
+/// Annotates stored targets with their current reachability. `is_available` is injected so tests
+/// don't depend on what's mounted.
+fn to_recent_targets(store: &TargetsStore, is_available: impl Fn(&Path) -> bool) -> Vec<RecentMoveTarget> {
+    store
+        .targets
+        .iter()
+        .map(|t| RecentMoveTarget {
+            path: t.path.clone(),
+            last_used: t.last_used,
+            available: is_available(Path::new(&t.path)),
+        })
+        .collect()
+}
+
+fn read_store_from_path(path: &Path) -> TargetsStore {
+    let _ = fs::remove_file(path.with_extension("json.tmp"));
+    let Ok(contents) = fs::read_to_string(path) else {
+        return TargetsStore::default();
+    };
+    match serde_json::from_str::<TargetsStore>(&contents) {
+        Ok(store) if store.schema_version == CURRENT_SCHEMA_VERSION => store,
+        result => {
+            log::warn!(
+                target: "move_targets",
+                "Ignoring unreadable recent move targets at {path:?} ({:?}); starting fresh",
+                result.map(|s| s.schema_version)
+            );
+            let _ = fs::rename(path, path.with_extension("json.broken"));
+            TargetsStore::default()
+        }
+    }
+}
+
+fn write_store_to_path(path: &Path, store: &TargetsStore) -> std::io::Result<()> {
+    let json = serde_json::to_string_pretty(store).map_err(std::io::Error::other)?;
+    crate::config::durable_write_json(path, &path.with_extension("json.tmp"), &json)
+}
+
+/// Runs `f` on the loaded store, loading it from disk first if needed.
+fn with_store<T>(f: impl FnOnce(&mut TargetsStore) -> T) -> T {
+    let mut guard = cache().lock_ignore_poison();
+    let store = guard.get_or_insert_with(|| {
+        let mut store = STORE_PATH.get().map(|p| read_store_from_path(p)).unwrap_or_default();
+        store.schema_version = CURRENT_SCHEMA_VERSION;
+        store
+    });
+    f(store)
+}
+
+/// Resolves the store path. Call once from app setup, before any move can start.
+pub fn init<R: tauri::Runtime>(app: &tauri::AppHandle<R>) {
+    if let Ok(dir) = crate::config::resolved_app_data_dir(app) {
+        let _ = STORE_PATH.set(dir.join(TARGETS_FILENAME));
+    }
+}
+
+/// Records `destination` as the most recent move target. Disk write is best-effort.
+pub fn record_move_target(destination: &Path) {
+    let path = destination.to_string_lossy();
+    let now_ms = chrono::Utc::now().timestamp_millis();
+    let snapshot = with_store(|store| {
+        apply_move(store, &path, now_ms);
+        store.clone()
+    });
+    let Some(store_path) = STORE_PATH.get() else {
+        return;
+    };
+    let _disk_guard = disk_lock().lock_ignore_poison();
+    if let Err(e) = write_store_to_path(store_path, &snapshot) {
+        log::warn!(target: "move_targets", "Couldn't write recent move targets: {e}");
+    }
+}
+
+/// The remembered move targets, newest first, each checked for reachability. Stats every entry, so
+/// call it off the main thread (a hung network mount blocks the `is_dir` call).
+pub fn get_recent_move_targets() -> Vec<RecentMoveTarget> {
+    let store = with_store(|store| store.clone());
+    to_recent_targets(&store, Path::is_dir)
+}
+
+/// The remembered move targets without touching the filesystem, all marked unavailable. The fallback
+/// when the reachability check times out.
+pub fn recent_move_targets_unchecked() -> Vec<RecentMoveTarget> {
+    let store = with_store(|store| store.clone());
+    to_recent_targets(&store, |_| false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn apply_move_dedupes_to_front_and_caps() {
+        let mut store = TargetsStore::default();
+        for i in 0..12 {
+            apply_move(&mut store, &format!("/t/{i}"), i);
+        }
+        assert_eq!(store.targets.len(), MAX_TARGETS);
+        assert_eq!(store.targets[0].path, "/t/11");
+        assert_eq!(store.targets[MAX_TARGETS - 1].path, "/t/2");
+
+        apply_move(&mut store, "/t/5", 100);
+        assert_eq!(store.targets.len(), MAX_TARGETS);
+        assert_eq!(store.targets[0].path, "/t/5");
+        assert_eq!(store.targets[0].last_used, 100);
+        assert_eq!(store.targets.iter().filter(|t| t.path == "/t/5").count(), 1);
+    }
+
+    #[test]
+    fn unreachable_targets_are_kept_and_marked_unavailable() {
+        let mut store = TargetsStore::default();
+        apply_move(&mut store, "/Volumes/Ejected/Inbox", 1);
+        apply_move(&mut store, "/Users/x/Archive", 2);
+
+        let targets = to_recent_targets(&store, |p| p.starts_with("/Users"));
+
+        assert_eq!(targets.len(), 2);
+        assert_eq!(targets[0].path, "/Users/x/Archive");
+        assert!(targets[0].available);
+        assert_eq!(targets[1].path, "/Volumes/Ejected/Inbox");
+        assert!(!targets[1].available);
+    }
+
+    #[test]
+    fn save_then_load_round_trip_and_quarantine() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join(TARGETS_FILENAME);
+        let mut store = TargetsStore {
+            schema_version: CURRENT_SCHEMA_VERSION,
+            ..TargetsStore::default()
+        };
+        apply_move(&mut store, "/Users/x/Archive", 42);
+        write_store_to_path(&path, &store).expect("write");
+        assert_eq!(read_store_from_path(&path), store);
+
+        fs::write(&path, "{ not json").expect("write garbage");
+        assert_eq!(read_store_from_path(&path), TargetsStore::default());
+        assert!(path.with_extension("json.broken").exists());
+    }
+}
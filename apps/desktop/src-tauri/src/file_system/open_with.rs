@@ -8,6 +8,10 @@
 //! - **Launching** the chosen app with the full selection via
 //!   `openURLs:withApplicationAtURL:configuration:completionHandler:` (one launch, multi-URL).
 //!
+//! - **IPC listing** for the frontend's "Open With" submenu (`get_applications_for`): the same
+//!   candidates as [`OpenWithApp`] rows (no icons), with the last-used non-default app for the
+//!   extension (`open_with_recents.rs`) moved to the front.
+//!
 //! Threading: `URLsForApplicationsToOpenURL:` is a synchronous LaunchServices call. On
 //! cloud-stub files it descends into FileProvider XPC, which can blow rayon's 2 MB stack.
 //! We use dedicated 8 MB-stack OS threads (same pattern as `sync_status.rs`).
@@ -16,6 +20,13 @@ use std::path::{Path, PathBuf};
 
 /// Metadata for one candidate app.
 #[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    not(target_os = "macos"),
+    allow(
+        dead_code,
+        reason = "only LaunchServices produces candidates; other platforms list none"
+    )
+)]
 pub struct AppCandidate {
     pub bundle_id: String,
     pub display_name: String,
@@ -28,6 +39,13 @@ pub struct AppCandidate {
 /// RGBA icon bytes plus dimensions, sized for a macOS context-menu item.
 /// `IconMenuItem` accepts owned RGBA via `tauri::image::Image::new_owned`.
 #[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    not(target_os = "macos"),
+    allow(
+        dead_code,
+        reason = "only LaunchServices produces candidates; other platforms list none"
+    )
+)]
 pub struct AppIcon {
     pub rgba: Vec<u8>,
     pub width: u32,
@@ -35,6 +53,7 @@ pub struct AppIcon {
 }
 
 /// "Open with" data for a (possibly multi-) selection.
+#[cfg(target_os = "macos")]
 #[derive(Clone, Debug, Default)]
 pub struct OpenWithChoices {
     /// Apps that can open every file in the selection. First entry is the OS default
@@ -42,8 +61,44 @@ pub struct OpenWithChoices {
     pub candidates: Vec<AppCandidate>,
 }
 
+/// One "Open with" candidate as the frontend sees it.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenWithApp {
+    /// Pass this to `open_with` as `app_identifier`.
+    pub bundle_id: String,
+    pub display_name: String,
+    pub app_path: String,
+    /// The OS default app for this file type.
+    pub is_default: bool,
+    /// The app last used for this extension (never the default; see `open_with_recents.rs`).
+    pub is_last_used: bool,
+}
+
+/// Turns the candidate list (OS default first) into [`OpenWithApp`] rows, moving the last-used
+/// app to the front when it's still a candidate. Pure function for testing.
+pub fn order_for_frontend(candidates: Vec<AppCandidate>, last_used: Option<&str>) -> Vec<OpenWithApp> {
+    let mut apps: Vec<OpenWithApp> = candidates
+        .into_iter()
+        .enumerate()
+        .map(|(idx, c)| OpenWithApp {
+            is_last_used: idx != 0 && last_used == Some(c.bundle_id.as_str()),
+            is_default: idx == 0,
+            bundle_id: c.bundle_id,
+            display_name: c.display_name,
+            app_path: c.app_path.to_string_lossy().to_string(),
+        })
+        .collect();
+    if let Some(pos) = apps.iter().position(|a| a.is_last_used) {
+        let last_used = apps.remove(pos);
+        apps.insert(0, last_used);
+    }
+    apps
+}
+
 /// Picks the apps that appear in *every* per-file candidate list, preserving the order
 /// from the first list. Pure function for testing.
+#[cfg(any(target_os = "macos", test))]
 pub fn intersect_candidate_lists(lists: &[Vec<PathBuf>]) -> Vec<PathBuf> {
     let Some(first) = lists.first() else {
         return Vec::new();
@@ -288,6 +343,25 @@ mod imp {
     /// stacks. The caller blocks until results are ready; this should be invoked from
     /// a `spawn_blocking` task in Tauri command code.
     pub fn compute_open_with_choices(paths: Vec<PathBuf>) -> OpenWithChoices {
+        query_choices(paths, true)
+    }
+
+    /// Like [`compute_open_with_choices`] for one file, without loading icons (the frontend
+    /// renders its own). Same threading contract.
+    pub fn app_candidates_for(path: &Path) -> Vec<AppCandidate> {
+        query_choices(vec![path.to_path_buf()], false).candidates
+    }
+
+    /// Resolves a bundle id to the app's path via LaunchServices, or `None` if no such app.
+    pub fn app_path_for_bundle_id(bundle_id: &str) -> Option<PathBuf> {
+        autoreleasepool(|_| {
+            let workspace = NSWorkspace::sharedWorkspace();
+            let url = workspace.URLForApplicationWithBundleIdentifier(&NSString::from_str(bundle_id))?;
+            Some(PathBuf::from(url.path()?.to_string()))
+        })
+    }
+
+    fn query_choices(paths: Vec<PathBuf>, with_icons: bool) -> OpenWithChoices {
         if paths.is_empty() {
             return OpenWithChoices::default();
         }
@@ -302,7 +376,7 @@ mod imp {
                     .filter_map(|app_path| {
                         let bundle_id = read_bundle_identifier(&app_path)?;
                         let display_name = read_app_display_name(&app_path);
-                        let icon = load_app_icon(&app_path);
+                        let icon = if with_icons { load_app_icon(&app_path) } else { None };
                         Some(AppCandidate {
                             bundle_id,
                             display_name,
@@ -387,21 +461,25 @@ mod imp {
 }
 
 #[cfg(target_os = "macos")]
-pub use imp::{compute_open_with_choices, open_paths_with, pick_app_via_open_panel, start_invalidation_observer};
+pub use imp::{
+    app_candidates_for, app_path_for_bundle_id, compute_open_with_choices, open_paths_with, pick_app_via_open_panel,
+    start_invalidation_observer,
+};
 
+/// No Launch Services off macOS: the "Open With" submenu degrades to empty.
 #[cfg(not(target_os = "macos"))]
-pub fn compute_open_with_choices(_paths: Vec<PathBuf>) -> OpenWithChoices {
-    OpenWithChoices::default()
+pub fn app_candidates_for(_path: &Path) -> Vec<AppCandidate> {
+    Vec::new()
 }
 
 #[cfg(not(target_os = "macos"))]
-pub fn open_paths_with(_paths: &[PathBuf], _app_path: &Path) -> Result<(), String> {
-    Err("Open with is only available on macOS".to_string())
+pub fn app_path_for_bundle_id(_bundle_id: &str) -> Option<PathBuf> {
+    None
 }
 
 #[cfg(not(target_os = "macos"))]
-pub fn pick_app_via_open_panel() -> Option<PathBuf> {
-    None
+pub fn open_paths_with(_paths: &[PathBuf], _app_path: &Path) -> Result<(), String> {
+    Err("Open with is only available on macOS".to_string())
 }
 
 #[cfg(not(target_os = "macos"))]
@@ -459,6 +537,38 @@ mod tests {
         assert!(intersect_candidate_lists(&lists).is_empty());
     }
 
+    fn candidate(bundle_id: &str) -> AppCandidate {
+        AppCandidate {
+            bundle_id: bundle_id.to_string(),
+            display_name: bundle_id.to_string(),
+            app_path: PathBuf::from(format!("/Applications/{bundle_id}.app")),
+            icon: None,
+        }
+    }
+
+    #[test]
+    fn frontend_order_moves_last_used_first_and_keeps_the_default_flag() {
+        let apps = order_for_frontend(
+            vec![candidate("preview"), candidate("gimp"), candidate("krita")],
+            Some("krita"),
+        );
+
+        let ids: Vec<&str> = apps.iter().map(|a| a.bundle_id.as_str()).collect();
+        assert_eq!(ids, vec!["krita", "preview", "gimp"]);
+        assert!(apps[0].is_last_used && !apps[0].is_default);
+        assert!(apps[1].is_default && !apps[1].is_last_used);
+    }
+
+    #[test]
+    fn frontend_order_ignores_a_last_used_app_that_is_gone_or_default() {
+        let gone = order_for_frontend(vec![candidate("preview"), candidate("gimp")], Some("krita"));
+        assert_eq!(gone[0].bundle_id, "preview");
+        assert!(gone.iter().all(|a| !a.is_last_used));
+
+        let default = order_for_frontend(vec![candidate("preview"), candidate("gimp")], Some("preview"));
+        assert!(default[0].is_default && !default[0].is_last_used);
+    }
+
     #[test]
     fn ext_key_lowercases() {
         assert_eq!(extension_cache_key(Path::new("foo.PNG")).as_deref(), Some("png"));
@@ -0,0 +1,169 @@
+//! Last-used "Open with" app per file extension (`open-with-recents.json`).
+//!
+//! When the user opens a file with an app that ISN'T the OS default for its type, we remember that
+//! app's bundle id under the lowercased extension, so `get_applications_for` can offer it first next
+//! time. Opening with the default app forgets the entry (the default is already offered).
+//!
+//! This is app state, not a user setting: the frontend owns `settings.json` and the backend never
+//! writes it (see `settings/CLAUDE.md`), so it lives in its own file next to the other backend-owned
+//! stores. Same shape as `go_to_path/history.rs`: in-memory cache loaded lazily, durable
+//! temp-then-rename write, and a schema-versioned file that's quarantined on a parse error.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+use serde::{Deserialize, Serialize};
+
+use crate::ignore_poison::IgnorePoison;
+
+/// Bump when the on-disk shape changes in an incompatible way.
+const CURRENT_SCHEMA_VERSION: u32 = 1;
+
+/// Filename inside `{app_data_dir}/`.
+const RECENTS_FILENAME: &str = "open-with-recents.json";
+
+/// On-disk shape. `lastUsed` maps a lowercased extension to a bundle id.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RecentsStore {
+    #[serde(rename = "_schemaVersion")]
+    schema_version: u32,
+    #[serde(default)]
+    last_used: BTreeMap<String, String>,
+}
+
+/// `None` until the first access loads from disk.
+static CACHE: OnceLock<Mutex<Option<RecentsStore>>> = OnceLock::new();
+
+/// Serializes the cache → disk flush so concurrent launches can't clobber each other.
+static DISK_LOCK: OnceLock<Mutex<()>> = OnceLock::new();
+
+fn cache() -> &'static Mutex<Option<RecentsStore>> {
+    CACHE.get_or_init(|| Mutex::new(None))
+}
+
+fn disk_lock() -> &'static Mutex<()> {
+    DISK_LOCK.get_or_init(|| Mutex::new(()))
+}
+
+/// Records a launch in the store. Returns whether anything changed (so a no-op skips the write).
+fn apply_launch(store: &mut RecentsStore, extension: &str, bundle_id: &str, is_default: bool) -> bool {
+    if is_default {
+        store.last_used.remove(extension).is_some()
+    } else {
+        store
+            .last_used
+            .insert(extension.to_string(), bundle_id.to_string())
+            .as_deref()
+            != Some(bundle_id)
+    }
+}
+
+fn read_store_from_path(path: &Path) -> RecentsStore {
+    let _ = fs::remove_file(path.with_extension("json.tmp"));
+    let Ok(contents) = fs::read_to_string(path) else {
+        return RecentsStore::default();
+    };
+    match serde_json::from_str::<RecentsStore>(&contents) {
+        Ok(store) if store.schema_version == CURRENT_SCHEMA_VERSION => store,
+        result => {
+            log::warn!(
+                target: "open_with::recents",
+                "Ignoring unreadable open-with recents at {path:?} ({:?}); starting fresh",
+                result.map(|s| s.schema_version)
+            );
+            let _ = fs::rename(path, path.with_extension("json.broken"));
+            RecentsStore::default()
+        }
+    }
+}
+
+fn write_store_to_path(path: &Path, store: &RecentsStore) -> std::io::Result<()> {
+    let json = serde_json::to_string_pretty(store).map_err(std::io::Error::other)?;
+    crate::config::durable_write_json(path, &path.with_extension("json.tmp"), &json)
+}
+
+fn store_path<R: tauri::Runtime>(app: &tauri::AppHandle<R>) -> Option<PathBuf> {
+    crate::config::resolved_app_data_dir(app)
+        .ok()
+        .map(|dir| dir.join(RECENTS_FILENAME))
+}
+
+/// Runs `f` on the loaded store, loading it from disk first if needed.
+fn with_store<R: tauri::Runtime, T>(app: &tauri::AppHandle<R>, f: impl FnOnce(&mut RecentsStore) -> T) -> T {
+    let mut guard = cache().lock_ignore_poison();
+    let store = guard.get_or_insert_with(|| {
+        let mut store = store_path(app).map(|p| read_store_from_path(&p)).unwrap_or_default();
+        store.schema_version = CURRENT_SCHEMA_VERSION;
+        store
+    });
+    f(store)
+}
+
+/// The bundle id last used to open files with this (lowercased) extension, if it wasn't the default.
+pub fn last_used_for<R: tauri::Runtime>(app: &tauri::AppHandle<R>, extension: &str) -> Option<String> {
+    with_store(app, |store| store.last_used.get(extension).cloned())
+}
+
+/// Records that `file` was opened with `bundle_id`: remembered for its extension when that app isn't
+/// the OS default for the type, forgotten when it is. Queries LaunchServices (cached per extension),
+/// so call it off the main thread. Extension-less files aren't tracked. Disk write is best-effort.
+pub fn record_launch<R: tauri::Runtime>(app: &tauri::AppHandle<R>, file: &Path, bundle_id: &str) {
+    let Some(extension) = super::open_with::extension_cache_key(file) else {
+        return;
+    };
+    let is_default = super::open_with::app_candidates_for(file)
+        .first()
+        .is_some_and(|default| default.bundle_id == bundle_id);
+    let snapshot = with_store(app, |store| {
+        apply_launch(store, &extension, bundle_id, is_default).then(|| store.clone())
+    });
+    let (Some(snapshot), Some(path)) = (snapshot, store_path(app)) else {
+        return;
+    };
+    let _disk_guard = disk_lock().lock_ignore_poison();
+    if let Err(e) = write_store_to_path(&path, &snapshot) {
+        log::warn!(target: "open_with::recents", "Couldn't write open-with recents: {e}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn non_default_launch_is_remembered_and_default_launch_forgets_it() {
+        let mut store = RecentsStore::default();
+
+        assert!(apply_launch(&mut store, "png", "com.pixelmator.pro", false));
+        assert_eq!(
+            store.last_used.get("png").map(String::as_str),
+            Some("com.pixelmator.pro")
+        );
+        // Same app again: nothing to write.
+        assert!(!apply_launch(&mut store, "png", "com.pixelmator.pro", false));
+
+        assert!(apply_launch(&mut store, "png", "com.apple.Preview", true));
+        assert!(store.last_used.is_empty());
+        assert!(!apply_launch(&mut store, "png", "com.apple.Preview", true));
+    }
+
+    #[test]
+    fn round_trip_and_corrupt_file_recovery() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join(RECENTS_FILENAME);
+        let mut store = RecentsStore {
+            schema_version: CURRENT_SCHEMA_VERSION,
+            ..RecentsStore::default()
+        };
+        apply_launch(&mut store, "md", "com.microsoft.VSCode", false);
+        write_store_to_path(&path, &store).expect("write");
+        assert_eq!(read_store_from_path(&path), store);
+
+        fs::write(&path, "{broken").expect("write garbage");
+        assert!(read_store_from_path(&path).last_used.is_empty());
+        assert!(path.with_extension("json.broken").exists());
+    }
+}
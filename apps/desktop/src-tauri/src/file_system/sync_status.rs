@@ -1,13 +1,20 @@
-//! Cloud sync status detection for macOS File Provider.
+//! Cloud sync status detection for iCloud Drive, Dropbox, and Google Drive.
 //!
 //! Detects file sync states:
-//! - Synced: Local content matches cloud
+//! - Synced: Local content matches cloud (the file is local)
 //! - OnlineOnly: Stub file, content in cloud only
 //! - Uploading: Local changes being uploaded
 //! - Downloading: Cloud content being fetched
 //!
-//! Detection uses stat() for fast online-only detection.
-//! For uploading/downloading states, we use NSURL resource values.
+//! The provider is recognized by where the file lives ([`cloud_provider_for_path`]). All
+//! three ship as File Provider clients on current macOS, so the markers are shared:
+//! stat()'s `SF_DATALESS` flag for online-only stubs (fast, also read during listing into
+//! `FileEntry::sync_status`), and NSURL ubiquitous resource values for uploading and
+//! downloading. Dropbox and Google Drive don't always answer the ubiquitous keys, so a
+//! local file under their folders counts as synced when NSURL has nothing to say.
+//!
+//! Other platforms have no placeholder files: every status is `Unknown` and
+//! [`materialize`] is a no-op.
 //!
 //! Parallelism uses dedicated OS threads (not rayon) because the NSURL calls
 //! make synchronous XPC round-trips to FileProvider daemons. These are I/O-bound
@@ -19,9 +26,6 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::Path;
 
-/// macOS SF_DATALESS flag indicating a stub/online-only file.
-const SF_DATALESS: u32 = 0x40000000;
-
 /// Sync status for a file in a cloud-synced folder (Dropbox, iCloud, etc.).
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
 #[serde(rename_all = "snake_case")]
@@ -36,79 +40,187 @@ pub enum SyncStatus {
     Unknown,
 }
 
-/// Gets sync status for a single file.
-///
-/// Uses stat() for fast online-only detection, then NSURL for upload/download state.
-fn get_sync_status(path: &Path) -> SyncStatus {
-    use std::os::macos::fs::MetadataExt;
-
-    // Get file metadata
-    let metadata = match std::fs::metadata(path) {
-        Ok(m) => m,
-        Err(_) => return SyncStatus::Unknown,
-    };
-
-    // Check if file is a stub (online-only) via SF_DATALESS flag
-    let flags = metadata.st_flags();
-    let is_dataless = (flags & SF_DATALESS) != 0;
-
-    if is_dataless {
-        // File is a stub - could be online-only or downloading
-        // Try to detect downloading state via NSURL
-        if is_downloading(path) {
-            SyncStatus::Downloading
-        } else {
-            SyncStatus::OnlineOnly
-        }
-    } else {
-        // File has local content - could be synced or uploading
-        // Use is_cloud_file() to check if this is actually a cloud file
-        match is_uploading_cloud_file(path) {
-            Some(true) => SyncStatus::Uploading,
-            Some(false) => SyncStatus::Synced,
-            None => SyncStatus::Unknown, // Not a cloud file
+/// A cloud storage client whose folder a path lives in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CloudProvider {
+    ICloud,
+    Dropbox,
+    GoogleDrive,
+}
+
+impl CloudProvider {
+    /// The provider's name as users know it, for error messages.
+    pub fn display_name(self) -> &'static str {
+        match self {
+            CloudProvider::ICloud => "iCloud Drive",
+            CloudProvider::Dropbox => "Dropbox",
+            CloudProvider::GoogleDrive => "Google Drive",
         }
     }
 }
 
-/// Checks if file is currently uploading via NSURL resource values.
-/// Returns None if file is not a cloud file.
-fn is_uploading_cloud_file(path: &Path) -> Option<bool> {
-    get_ubiquitous_bool(path, "NSURLUbiquitousItemIsUploadingKey")
+/// The provider whose synced folder holds `path`, recognized by location under `$HOME`.
+pub fn cloud_provider_for_path(path: &Path) -> Option<CloudProvider> {
+    let home = std::env::var_os("HOME")?;
+    provider_under_home(path, Path::new(&home))
 }
 
-/// Checks if file is currently downloading via NSURL resource values.
-fn is_downloading(path: &Path) -> bool {
-    get_ubiquitous_bool(path, "NSURLUbiquitousItemIsDownloadingKey").unwrap_or(false)
+/// Pure part of [`cloud_provider_for_path`]. Covers the File Provider locations
+/// (`~/Library/CloudStorage/Dropbox…`, `…/GoogleDrive-<account>`), iCloud's ubiquity
+/// containers (`~/Library/Mobile Documents`), and the legacy `~/Dropbox` folder.
+pub(crate) fn provider_under_home(path: &Path, home: &Path) -> Option<CloudProvider> {
+    if path.starts_with(home.join("Library/Mobile Documents")) {
+        return Some(CloudProvider::ICloud);
+    }
+    if path.starts_with(home.join("Dropbox")) {
+        return Some(CloudProvider::Dropbox);
+    }
+    let folder = path
+        .strip_prefix(home.join("Library/CloudStorage"))
+        .ok()?
+        .iter()
+        .next()?;
+    let folder = folder.to_str()?;
+    if folder.starts_with("Dropbox") {
+        Some(CloudProvider::Dropbox)
+    } else if folder.starts_with("GoogleDrive-") {
+        Some(CloudProvider::GoogleDrive)
+    } else {
+        None
+    }
 }
 
-/// Gets a boolean ubiquitous item property from NSURL.
-fn get_ubiquitous_bool(path: &Path, key: &str) -> Option<bool> {
-    use objc2::rc::{Retained, autoreleasepool};
-    use objc2_foundation::{NSNumber, NSString, NSURL};
-
-    // Drain autoreleased ObjC objects (NSURL, NSString) created per call.
-    // Called from spawned threads that lack AppKit's autorelease pool.
-    autoreleasepool(|_| {
-        let path_str = path.to_str()?;
-        let ns_path = NSString::from_str(path_str);
-        let url = NSURL::fileURLWithPath(&ns_path);
-
-        let key = NSString::from_str(key);
-        let mut value: Option<Retained<objc2::runtime::AnyObject>> = None;
-        // SAFETY: `url` is a valid NSURL, `key` a valid NSString, and `&mut value` a valid
-        // `&mut Option<Retained<_>>` out-param. On success objc2 stores an already-retained
-        // object there per its out-param convention, so the `Retained` owns one reference.
-        let success = unsafe { url.getResourceValue_forKey_error(&mut value, &key) };
-
-        if success.is_ok() {
-            value.and_then(|obj| obj.downcast::<NSNumber>().ok().map(|n| n.boolValue()))
+#[cfg(target_os = "macos")]
+mod imp {
+    use super::{CloudProvider, SyncStatus, cloud_provider_for_path};
+    use std::io::Read;
+    use std::path::Path;
+
+    /// macOS SF_DATALESS flag indicating a stub/online-only file.
+    const SF_DATALESS: u32 = 0x40000000;
+
+    /// Online-only from the stat flags alone; `None` when the file has local content (then
+    /// only NSURL can tell synced from uploading). Cheap enough for every listed entry.
+    pub fn status_from_metadata(metadata: &std::fs::Metadata) -> Option<SyncStatus> {
+        use std::os::macos::fs::MetadataExt;
+        (metadata.st_flags() & SF_DATALESS != 0).then_some(SyncStatus::OnlineOnly)
+    }
+
+    /// Gets sync status for a single file.
+    ///
+    /// Uses stat() for fast online-only detection, then NSURL for upload/download state.
+    pub fn get_sync_status(path: &Path) -> SyncStatus {
+        // Get file metadata
+        let metadata = match std::fs::metadata(path) {
+            Ok(m) => m,
+            Err(_) => return SyncStatus::Unknown,
+        };
+
+        if status_from_metadata(&metadata).is_some() {
+            // File is a stub - could be online-only or downloading
+            // Try to detect downloading state via NSURL
+            if is_downloading(path) {
+                SyncStatus::Downloading
+            } else {
+                SyncStatus::OnlineOnly
+            }
         } else {
-            None
+            // File has local content - could be synced or uploading
+            // Use is_cloud_file() to check if this is actually a cloud file
+            match is_uploading_cloud_file(path) {
+                Some(true) => SyncStatus::Uploading,
+                Some(false) => SyncStatus::Synced,
+                None => match cloud_provider_for_path(path) {
+                    Some(CloudProvider::Dropbox | CloudProvider::GoogleDrive) => SyncStatus::Synced,
+                    Some(CloudProvider::ICloud) | None => SyncStatus::Unknown, // Not a cloud file
+                },
+            }
         }
-    })
+    }
+
+    /// Whether `path` is an online-only stub right now.
+    pub fn is_online_only(path: &Path) -> bool {
+        std::fs::symlink_metadata(path).is_ok_and(|m| status_from_metadata(&m).is_some())
+    }
+
+    /// Downloads an online-only file's content. Reading its first byte makes the kernel
+    /// fault the whole file in through its File Provider, which blocks until the provider
+    /// delivers (or fails). A file with local content returns right away.
+    pub fn materialize(path: &Path) -> std::io::Result<()> {
+        if !is_online_only(path) {
+            return Ok(());
+        }
+        let mut byte = [0u8; 1];
+        let _read = std::fs::File::open(path)?.read(&mut byte)?;
+        if is_online_only(path) {
+            return Err(std::io::Error::other("the cloud provider didn't download the file"));
+        }
+        Ok(())
+    }
+
+    /// Checks if file is currently uploading via NSURL resource values.
+    /// Returns None if file is not a cloud file.
+    fn is_uploading_cloud_file(path: &Path) -> Option<bool> {
+        get_ubiquitous_bool(path, "NSURLUbiquitousItemIsUploadingKey")
+    }
+
+    /// Checks if file is currently downloading via NSURL resource values.
+    fn is_downloading(path: &Path) -> bool {
+        get_ubiquitous_bool(path, "NSURLUbiquitousItemIsDownloadingKey").unwrap_or(false)
+    }
+
+    /// Gets a boolean ubiquitous item property from NSURL.
+    fn get_ubiquitous_bool(path: &Path, key: &str) -> Option<bool> {
+        use objc2::rc::{Retained, autoreleasepool};
+        use objc2_foundation::{NSNumber, NSString, NSURL};
+
+        // Drain autoreleased ObjC objects (NSURL, NSString) created per call.
+        // Called from spawned threads that lack AppKit's autorelease pool.
+        autoreleasepool(|_| {
+            let path_str = path.to_str()?;
+            let ns_path = NSString::from_str(path_str);
+            let url = NSURL::fileURLWithPath(&ns_path);
+
+            let key = NSString::from_str(key);
+            let mut value: Option<Retained<objc2::runtime::AnyObject>> = None;
+            // SAFETY: `url` is a valid NSURL, `key` a valid NSString, and `&mut value` a valid
+            // `&mut Option<Retained<_>>` out-param. On success objc2 stores an already-retained
+            // object there per its out-param convention, so the `Retained` owns one reference.
+            let success = unsafe { url.getResourceValue_forKey_error(&mut value, &key) };
+
+            if success.is_ok() {
+                value.and_then(|obj| obj.downcast::<NSNumber>().ok().map(|n| n.boolValue()))
+            } else {
+                None
+            }
+        })
+    }
 }
 
+#[cfg(not(target_os = "macos"))]
+mod imp {
+    use super::SyncStatus;
+    use std::path::Path;
+
+    pub fn status_from_metadata(_metadata: &std::fs::Metadata) -> Option<SyncStatus> {
+        None
+    }
+
+    pub fn get_sync_status(_path: &Path) -> SyncStatus {
+        SyncStatus::Unknown
+    }
+
+    pub fn is_online_only(_path: &Path) -> bool {
+        false
+    }
+
+    pub fn materialize(_path: &Path) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+pub use imp::{get_sync_status, is_online_only, materialize, status_from_metadata};
+
 /// 8 MB stack per thread: enough for deep FileProvider XPC call chains.
 const THREAD_STACK_SIZE: usize = 8 * 1024 * 1024;
 
@@ -122,6 +234,9 @@ pub fn get_sync_statuses(paths: Vec<String>) -> HashMap<String, SyncStatus> {
     if paths.is_empty() {
         return HashMap::new();
     }
+    if !cfg!(target_os = "macos") {
+        return paths.into_iter().map(|path| (path, SyncStatus::Unknown)).collect();
+    }
 
     let num_threads = paths
         .len()
@@ -173,4 +288,40 @@ mod tests {
             "\"downloading\""
         );
     }
+
+    #[test]
+    fn providers_are_recognized_by_location() {
+        let home = Path::new("/Users/test");
+        let provider = |p: &str| provider_under_home(Path::new(p), home);
+        assert_eq!(
+            provider("/Users/test/Library/Mobile Documents/com~apple~CloudDocs/a.txt"),
+            Some(CloudProvider::ICloud)
+        );
+        assert_eq!(
+            provider("/Users/test/Library/CloudStorage/Dropbox/a.txt"),
+            Some(CloudProvider::Dropbox)
+        );
+        assert_eq!(
+            provider("/Users/test/Library/CloudStorage/Dropbox-Work/a.txt"),
+            Some(CloudProvider::Dropbox)
+        );
+        assert_eq!(provider("/Users/test/Dropbox/a.txt"), Some(CloudProvider::Dropbox));
+        assert_eq!(
+            provider("/Users/test/Library/CloudStorage/GoogleDrive-me@example.com/My Drive/a"),
+            Some(CloudProvider::GoogleDrive)
+        );
+        assert_eq!(provider("/Users/test/Library/CloudStorage/OneDrive-Personal/a"), None);
+        assert_eq!(provider("/Users/test/Documents/a.txt"), None);
+        assert_eq!(provider("/Users/test/Library/CloudStorage"), None);
+    }
+
+    #[test]
+    fn a_regular_file_has_local_content() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let file = dir.path().join("a.txt");
+        std::fs::write(&file, b"local").expect("write");
+        assert!(!is_online_only(&file));
+        assert_eq!(status_from_metadata(&std::fs::metadata(&file).expect("stat")), None);
+        materialize(&file).expect("no-op for local content");
+    }
 }
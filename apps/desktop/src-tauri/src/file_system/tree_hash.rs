@@ -0,0 +1,419 @@
+//! Content hash of a whole folder, for "has this folder changed?" checks.
+//!
+//! Merkle-style: each folder's hash covers its children sorted by name, namely every file's
+//! name, size, and BLAKE3 content hash, every symlink's name and target (never followed), and
+//! every subfolder's name and own hash. Sorting makes the result independent of the order
+//! `read_dir` hands entries back, and the root's own name isn't part of it, so two identical
+//! trees hash the same wherever they live. Sync verification and duplicate-folder detection
+//! compare these.
+//!
+//! When the drive index covers the folder (fully listed in the current epoch, see
+//! `indexing::subtree_entries`), every subfolder gets a fingerprint from its index rows, and a
+//! hash stored at that fingerprint (`indexing::stored_subtree_hash`) is reused without reading
+//! a byte below it. Fresh hashes of folders with at least [`MIN_FILES_TO_STORE`] files are
+//! stored back, but only when the walk saw the same files and bytes the index did. The index
+//! trails the disk by the watcher's latency, so a check right after a write should pass
+//! `use_index: false`.
+//!
+//! Cancellable like a checksum: by the caller-picked operation id, through [`ACTIVE_TREE_HASHES`].
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Read;
+use std::os::unix::ffi::OsStrExt;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::cancel_registry::CancelRegistry;
+use crate::indexing::store::SubtreeEntry;
+
+/// Read size per chunk, as in `checksum.rs`.
+const CHUNK_SIZE: usize = 1 << 20;
+
+/// Minimum gap between two `on_progress` calls. Matches the write ops' default.
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Smaller folders aren't worth a `meta` row: hashing them again is cheaper than the lookup.
+const MIN_FILES_TO_STORE: u64 = 32;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TreeHashProgress {
+    /// Files hashed so far, including those under reused subfolders.
+    pub files: u64,
+    pub bytes: u64,
+    /// Subfolders whose stored hash was reused instead of reading them.
+    pub reused_subtrees: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TreeHashResult {
+    /// Lowercase hex BLAKE3 of the root folder's node.
+    pub hash: String,
+    pub files: u64,
+    pub bytes: u64,
+    pub reused_subtrees: u64,
+}
+
+#[derive(Debug)]
+pub enum TreeHashError {
+    Cancelled,
+    NotAFolder(PathBuf),
+    /// Anything below the root that can't be read fails the hash: skipping it would report a
+    /// tree that isn't the one on disk.
+    Io {
+        path: PathBuf,
+        error: std::io::Error,
+    },
+}
+
+impl std::fmt::Display for TreeHashError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Cancelled => write!(f, "Folder hash cancelled"),
+            Self::NotAFolder(path) => write!(f, "Not a folder: {}", path.display()),
+            Self::Io { path, error } => write!(f, "Couldn't read {}: {error}", path.display()),
+        }
+    }
+}
+
+/// Cancel flags of in-flight folder hashes.
+pub static ACTIVE_TREE_HASHES: CancelRegistry = CancelRegistry::new();
+
+/// Hashes the folder at `root`. With `use_index`, subfolders the index vouches for are reused
+/// from earlier runs. Blocking: call it off the async runtime. `on_progress` fires at most
+/// once per [`PROGRESS_INTERVAL`].
+pub fn compute_tree_hash(
+    root: &Path,
+    use_index: bool,
+    cancelled: &AtomicBool,
+    on_progress: &dyn Fn(TreeHashProgress),
+) -> Result<TreeHashResult, TreeHashError> {
+    if !root.is_dir() {
+        return Err(TreeHashError::NotAFolder(root.to_path_buf()));
+    }
+    let indexed = if use_index {
+        index_fingerprints(root)
+    } else {
+        HashMap::new()
+    };
+    let mut walk = Walk {
+        cancelled,
+        on_progress,
+        progress: TreeHashProgress::default(),
+        last_progress: Instant::now(),
+        indexed,
+        buf: vec![0u8; CHUNK_SIZE],
+    };
+    let node = walk.hash_dir(root, "")?;
+    Ok(TreeHashResult {
+        hash: node.hash.to_hex().to_string(),
+        files: node.files,
+        bytes: node.bytes,
+        reused_subtrees: walk.progress.reused_subtrees,
+    })
+}
+
+/// Fingerprints of `root` and every folder below it, keyed by relative path (`""` is the
+/// root). Empty when the index can't vouch for the subtree, which means "hash everything".
+fn index_fingerprints(root: &Path) -> HashMap<String, IndexedDir> {
+    match crate::indexing::subtree_entries(&root.to_string_lossy()) {
+        Ok(Some(entries)) => fingerprints(&entries),
+        Ok(None) => HashMap::new(),
+        Err(e) => {
+            log::debug!(target: "tree_hash", "Index lookup failed for {root:?}, hashing everything: {e}");
+            HashMap::new()
+        }
+    }
+}
+
+/// What the index knows about one folder's subtree.
+struct IndexedDir {
+    /// Hex BLAKE3 over the names, kinds, sizes, and mtimes of everything below.
+    fingerprint: String,
+    files: u64,
+    bytes: u64,
+}
+
+fn fingerprints(entries: &[SubtreeEntry]) -> HashMap<String, IndexedDir> {
+    let mut children: HashMap<&str, Vec<&SubtreeEntry>> = HashMap::new();
+    for entry in entries {
+        children.entry(parent_of(&entry.relative_path)).or_default().push(entry);
+    }
+    let mut out = HashMap::new();
+    fingerprint_dir("", &mut children, &mut out);
+    out
+}
+
+fn parent_of(relative_path: &str) -> &str {
+    relative_path.rsplit_once('/').map_or("", |(parent, _)| parent)
+}
+
+fn name_of(relative_path: &str) -> &str {
+    relative_path.rsplit_once('/').map_or(relative_path, |(_, name)| name)
+}
+
+fn fingerprint_dir<'a>(
+    rel: &str,
+    children: &mut HashMap<&'a str, Vec<&'a SubtreeEntry>>,
+    out: &mut HashMap<String, IndexedDir>,
+) {
+    let mut entries = children.remove(rel).unwrap_or_default();
+    // Siblings share their parent's prefix, so this is a sort by name.
+    entries.sort_by(|a, b| a.relative_path.cmp(&b.relative_path));
+    let mut hasher = blake3::Hasher::new();
+    let (mut files, mut bytes) = (0u64, 0u64);
+    for entry in entries {
+        update_with_len(&mut hasher, name_of(&entry.relative_path).as_bytes());
+        hasher.update(&[u8::from(entry.is_directory), u8::from(entry.is_symlink)]);
+        hasher.update(&entry.modified_at.unwrap_or(0).to_le_bytes());
+        if entry.is_directory && !entry.is_symlink {
+            fingerprint_dir(&entry.relative_path, children, out);
+            if let Some(dir) = out.get(&entry.relative_path) {
+                hasher.update(dir.fingerprint.as_bytes());
+                files += dir.files;
+                bytes += dir.bytes;
+            }
+        } else {
+            let size = entry.logical_size.unwrap_or(0);
+            hasher.update(&size.to_le_bytes());
+            if !entry.is_symlink {
+                files += 1;
+                bytes += size;
+            }
+        }
+    }
+    out.insert(
+        rel.to_string(),
+        IndexedDir {
+            fingerprint: hasher.finalize().to_hex().to_string(),
+            files,
+            bytes,
+        },
+    );
+}
+
+/// Length-prefixed, so `ab` + `c` and `a` + `bc` can't hash alike.
+fn update_with_len(hasher: &mut blake3::Hasher, bytes: &[u8]) {
+    hasher.update(&(bytes.len() as u64).to_le_bytes());
+    hasher.update(bytes);
+}
+
+/// A hashed folder and what it covered.
+struct Node {
+    hash: blake3::Hash,
+    files: u64,
+    bytes: u64,
+}
+
+struct Walk<'a> {
+    cancelled: &'a AtomicBool,
+    on_progress: &'a dyn Fn(TreeHashProgress),
+    progress: TreeHashProgress,
+    last_progress: Instant,
+    indexed: HashMap<String, IndexedDir>,
+    buf: Vec<u8>,
+}
+
+impl Walk<'_> {
+    fn check_cancelled(&self) -> Result<(), TreeHashError> {
+        if self.cancelled.load(Ordering::Relaxed) {
+            Err(TreeHashError::Cancelled)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn tick(&mut self) {
+        if self.last_progress.elapsed() >= PROGRESS_INTERVAL {
+            (self.on_progress)(self.progress);
+            self.last_progress = Instant::now();
+        }
+    }
+
+    fn hash_dir(&mut self, dir: &Path, rel: &str) -> Result<Node, TreeHashError> {
+        self.check_cancelled()?;
+        let indexed = self.indexed.get(rel).map(|d| (d.fingerprint.clone(), d.files, d.bytes));
+        if let Some((fingerprint, files, bytes)) = &indexed
+            && let Some(stored) = crate::indexing::stored_subtree_hash(&dir.to_string_lossy(), fingerprint)
+            && let Ok(hash) = blake3::Hash::from_hex(&stored)
+        {
+            self.progress.files += files;
+            self.progress.bytes += bytes;
+            self.progress.reused_subtrees += 1;
+            self.tick();
+            return Ok(Node {
+                hash,
+                files: *files,
+                bytes: *bytes,
+            });
+        }
+
+        let io = |error: std::io::Error| TreeHashError::Io {
+            path: dir.to_path_buf(),
+            error,
+        };
+        let mut children = Vec::new();
+        for entry in std::fs::read_dir(dir).map_err(io)? {
+            let entry = entry.map_err(io)?;
+            let file_type = entry.file_type().map_err(io)?;
+            children.push((entry.file_name(), file_type));
+        }
+        children.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut hasher = blake3::Hasher::new();
+        let (mut files, mut bytes) = (0u64, 0u64);
+        for (name, file_type) in children {
+            let path = dir.join(&name);
+            if file_type.is_symlink() {
+                let target = std::fs::read_link(&path).map_err(|error| TreeHashError::Io {
+                    path: path.clone(),
+                    error,
+                })?;
+                hasher.update(b"l");
+                update_with_len(&mut hasher, name.as_bytes());
+                update_with_len(&mut hasher, target.as_os_str().as_bytes());
+            } else if file_type.is_dir() {
+                let name_str = name.to_string_lossy();
+                let child_rel = if rel.is_empty() {
+                    name_str.into_owned()
+                } else {
+                    format!("{rel}/{name_str}")
+                };
+                let node = self.hash_dir(&path, &child_rel)?;
+                hasher.update(b"d");
+                update_with_len(&mut hasher, name.as_bytes());
+                hasher.update(node.hash.as_bytes());
+                files += node.files;
+                bytes += node.bytes;
+            } else if file_type.is_file() {
+                let (hash, size) = self.hash_file(&path)?;
+                hasher.update(b"f");
+                update_with_len(&mut hasher, name.as_bytes());
+                hasher.update(&size.to_le_bytes());
+                hasher.update(hash.as_bytes());
+                files += 1;
+                bytes += size;
+            } else {
+                // Sockets, FIFOs, devices: opening one could block, so only the name counts.
+                hasher.update(b"o");
+                update_with_len(&mut hasher, name.as_bytes());
+            }
+        }
+        let hash = hasher.finalize();
+
+        if let Some((fingerprint, indexed_files, indexed_bytes)) = indexed
+            && files >= MIN_FILES_TO_STORE
+            && (files, bytes) == (indexed_files, indexed_bytes)
+        {
+            crate::indexing::store_subtree_hash(&dir.to_string_lossy(), &fingerprint, &hash.to_hex());
+        }
+        Ok(Node { hash, files, bytes })
+    }
+
+    /// BLAKE3 of the file's contents and the number of bytes read.
+    fn hash_file(&mut self, path: &Path) -> Result<(blake3::Hash, u64), TreeHashError> {
+        let io = |error: std::io::Error| TreeHashError::Io {
+            path: path.to_path_buf(),
+            error,
+        };
+        let mut file = File::open(path).map_err(io)?;
+        let mut hasher = blake3::Hasher::new();
+        let mut size = 0u64;
+        loop {
+            self.check_cancelled()?;
+            let n = match file.read(&mut self.buf) {
+                Ok(0) => break,
+                Ok(n) => n,
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(io(e)),
+            };
+            hasher.update(&self.buf[..n]);
+            size += n as u64;
+            self.progress.bytes += n as u64;
+            self.tick();
+        }
+        self.progress.files += 1;
+        Ok((hasher.finalize(), size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(root: &Path, relative: &str, content: &[u8]) {
+        let path = root.join(relative);
+        std::fs::create_dir_all(path.parent().expect("parent")).expect("mkdir");
+        std::fs::write(path, content).expect("write");
+    }
+
+    fn hash_of(root: &Path) -> TreeHashResult {
+        compute_tree_hash(root, false, &AtomicBool::new(false), &|_| {}).expect("tree hash")
+    }
+
+    #[test]
+    fn identical_trees_hash_alike_regardless_of_creation_order() {
+        let a = tempfile::tempdir().expect("tempdir");
+        let b = tempfile::tempdir().expect("tempdir");
+        write(a.path(), "one.txt", b"1");
+        write(a.path(), "sub/two.txt", b"22");
+        write(a.path(), "sub/deeper/three.txt", b"333");
+        write(b.path(), "sub/deeper/three.txt", b"333");
+        write(b.path(), "sub/two.txt", b"22");
+        write(b.path(), "one.txt", b"1");
+
+        let (left, right) = (hash_of(a.path()), hash_of(b.path()));
+
+        assert_eq!(left, right);
+        assert_eq!((left.files, left.bytes), (3, 6));
+    }
+
+    #[test]
+    fn content_name_and_empty_folder_changes_change_the_hash() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        write(dir.path(), "sub/file.txt", b"abc");
+        let original = hash_of(dir.path()).hash;
+
+        write(dir.path(), "sub/file.txt", b"abd");
+        let edited = hash_of(dir.path()).hash;
+        std::fs::rename(dir.path().join("sub/file.txt"), dir.path().join("sub/renamed.txt")).expect("rename");
+        let renamed = hash_of(dir.path()).hash;
+        std::fs::create_dir(dir.path().join("empty")).expect("mkdir");
+        let with_empty = hash_of(dir.path()).hash;
+
+        assert_ne!(original, edited);
+        assert_ne!(edited, renamed);
+        assert_ne!(renamed, with_empty);
+    }
+
+    #[test]
+    fn index_fingerprints_change_up_the_chain_only() {
+        let entry = |path: &str, is_directory, size, mtime| SubtreeEntry {
+            relative_path: path.to_string(),
+            is_directory,
+            is_symlink: false,
+            logical_size: size,
+            modified_at: Some(mtime),
+        };
+        let before = vec![
+            entry("a", true, None, 1),
+            entry("a/x.bin", false, Some(10), 1),
+            entry("b", true, None, 1),
+            entry("b/y.bin", false, Some(20), 1),
+        ];
+        let mut after = before.clone();
+        after[1].modified_at = Some(2);
+
+        let (old, new) = (fingerprints(&before), fingerprints(&after));
+
+        assert_ne!(old[""].fingerprint, new[""].fingerprint);
+        assert_ne!(old["a"].fingerprint, new["a"].fingerprint);
+        assert_eq!(old["b"].fingerprint, new["b"].fingerprint);
+        assert_eq!((new[""].files, new[""].bytes), (2, 30));
+    }
+}
@@ -0,0 +1,218 @@
+//! Disk-usage treemap data: a directory's recursive size, split into subdirectories down to a
+//! requested depth.
+//!
+//! Only directory aggregates cross IPC, never per-file entries, so even a deep tree stays a few
+//! KB. A child smaller than `min_fraction` of its parent is pruned and its bytes fold into the
+//! parent's `other_size`, together with the parent's own files.
+//!
+//! Sizes are physical (on-disk) bytes. They come from the drive index's `dir_stats` when it
+//! fully covers the folder (one depth-capped SQL query, see `indexing::subdir_sizes`), and from
+//! a bounded disk walk otherwise.
+
+use std::collections::{HashMap, HashSet};
+use std::os::unix::fs::MetadataExt;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use walkdir::WalkDir;
+
+/// Deepest level a caller can ask for. Past this the boxes are too small to draw anyway.
+const MAX_DEPTH: u32 = 8;
+
+/// Walk cap for the disk fallback, as in `extension_breakdown`.
+const MAX_WALK_ENTRIES: usize = 200_000;
+
+/// One directory box.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TreemapNode {
+    pub name: String,
+    /// Recursive physical size in bytes.
+    pub size: u64,
+    /// Bytes not covered by `children`: the directory's own files plus pruned subdirectories.
+    pub other_size: u64,
+    /// Largest first. Empty at the depth limit.
+    pub children: Vec<TreemapNode>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TreemapData {
+    pub root: TreemapNode,
+    /// `true` when the drive index answered, `false` for a disk walk.
+    pub from_index: bool,
+    /// `true` when the disk walk hit its entry cap; sizes are then lower bounds.
+    pub truncated: bool,
+}
+
+/// Builds the tree from `(relative_path, size)` rows, root at `""`, `/`-separated. Rows whose
+/// parent is missing are dropped.
+fn build_tree(root_name: &str, rows: Vec<(String, u64)>, min_fraction: f64) -> TreemapNode {
+    let mut sizes: HashMap<String, u64> = HashMap::new();
+    let mut children_of: HashMap<String, Vec<String>> = HashMap::new();
+    for (relative, size) in rows {
+        if !relative.is_empty() {
+            let parent = relative.rsplit_once('/').map_or("", |(parent, _)| parent);
+            children_of
+                .entry(parent.to_string())
+                .or_default()
+                .push(relative.clone());
+        }
+        sizes.insert(relative, size);
+    }
+    build_node(root_name.to_string(), "", &sizes, &children_of, min_fraction)
+}
+
+fn build_node(
+    name: String,
+    relative: &str,
+    sizes: &HashMap<String, u64>,
+    children_of: &HashMap<String, Vec<String>>,
+    min_fraction: f64,
+) -> TreemapNode {
+    let size = sizes.get(relative).copied().unwrap_or(0);
+    let threshold = size as f64 * min_fraction;
+    let mut children: Vec<TreemapNode> = children_of
+        .get(relative)
+        .into_iter()
+        .flatten()
+        .filter(|child| {
+            sizes
+                .get(*child)
+                .is_some_and(|&child_size| child_size > 0 && child_size as f64 >= threshold)
+        })
+        .map(|child| {
+            let child_name = child.rsplit_once('/').map_or(child.as_str(), |(_, name)| name);
+            build_node(child_name.to_string(), child, sizes, children_of, min_fraction)
+        })
+        .collect();
+    children.sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.name.cmp(&b.name)));
+    let covered: u64 = children.iter().map(|child| child.size).sum();
+    TreemapNode {
+        name,
+        size,
+        other_size: size.saturating_sub(covered),
+        children,
+    }
+}
+
+/// Sizes by walking `root`, attributed to each ancestor directory down to `depth` levels.
+/// Symlinks aren't followed and hardlinked files count once. Returns the rows and whether the
+/// walk was cut short.
+fn walk_sizes(root: &Path, depth: u32, max_entries: usize) -> (Vec<(String, u64)>, bool) {
+    let mut sizes: HashMap<String, u64> = HashMap::from([(String::new(), 0)]);
+    let mut seen_inodes = HashSet::new();
+    let mut truncated = false;
+    for (seen, entry) in WalkDir::new(root)
+        .min_depth(1)
+        .follow_links(false)
+        .into_iter()
+        .enumerate()
+    {
+        if seen >= max_entries {
+            truncated = true;
+            break;
+        }
+        let Ok(entry) = entry else { continue };
+        let Ok(relative) = entry.path().strip_prefix(root) else {
+            continue;
+        };
+        let components: Vec<String> = relative
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy().into_owned())
+            .collect();
+        if entry.file_type().is_dir() {
+            // Register the box even while it's empty, as long as it's within the depth.
+            if components.len() <= depth as usize {
+                sizes.entry(components.join("/")).or_insert(0);
+            }
+            continue;
+        }
+        let Ok(metadata) = entry.metadata() else { continue };
+        if !metadata.is_file() || (metadata.nlink() > 1 && !seen_inodes.insert(metadata.ino())) {
+            continue;
+        }
+        let bytes = metadata.blocks() * 512;
+        // The file's own name isn't a box: attribute to its folder and every ancestor.
+        let folder_levels = (components.len() - 1).min(depth as usize);
+        for level in 0..=folder_levels {
+            *sizes.entry(components[..level].join("/")).or_insert(0) += bytes;
+        }
+    }
+    (sizes.into_iter().collect(), truncated)
+}
+
+/// Treemap data for `path` down to `depth` levels (capped at [`MAX_DEPTH`]), pruning boxes
+/// under `min_fraction` (clamped to `0.0..=1.0`) of their parent. Blocking: SQL or disk I/O.
+pub fn get_treemap_data(path: &Path, depth: u32, min_fraction: f64) -> Result<TreemapData, String> {
+    if !path.is_dir() {
+        return Err(format!("Not a folder: {}", path.display()));
+    }
+    let depth = depth.min(MAX_DEPTH);
+    let min_fraction = if min_fraction.is_finite() {
+        min_fraction.clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    let root_name = path.file_name().map_or_else(
+        || path.to_string_lossy().into_owned(),
+        |n| n.to_string_lossy().into_owned(),
+    );
+
+    let indexed = crate::indexing::subdir_sizes(&path.to_string_lossy(), depth).unwrap_or_else(|e| {
+        log::debug!(target: "treemap", "Index lookup failed for {path:?}, walking instead: {e}");
+        None
+    });
+    let (rows, from_index, truncated) = match indexed {
+        Some(rows) => (rows, true, false),
+        None => {
+            let (rows, truncated) = walk_sizes(path, depth, MAX_WALK_ENTRIES);
+            (rows, false, truncated)
+        }
+    };
+    Ok(TreemapData {
+        root: build_tree(&root_name, rows, min_fraction),
+        from_index,
+        truncated,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows(pairs: &[(&str, u64)]) -> Vec<(String, u64)> {
+        pairs.iter().map(|(path, size)| (path.to_string(), *size)).collect()
+    }
+
+    #[test]
+    fn small_children_fold_into_other_size() {
+        let tree = build_tree(
+            "root",
+            rows(&[("", 1000), ("big", 700), ("big/inner", 600), ("tiny", 5), ("mid", 200)]),
+            0.1,
+        );
+
+        let names: Vec<&str> = tree.children.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["big", "mid"]);
+        assert_eq!(tree.other_size, 100);
+        assert_eq!(tree.children[0].children[0].name, "inner");
+        assert_eq!(tree.children[0].other_size, 100);
+    }
+
+    #[test]
+    fn walk_attributes_files_to_ancestors_up_to_the_depth() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        std::fs::create_dir_all(dir.path().join("a/b/c")).expect("mkdir");
+        std::fs::write(dir.path().join("a/b/c/deep.bin"), vec![1u8; 8192]).expect("write");
+        std::fs::write(dir.path().join("top.bin"), vec![1u8; 8192]).expect("write");
+
+        let (rows, truncated) = walk_sizes(dir.path(), 1, MAX_WALK_ENTRIES);
+        let sizes: HashMap<String, u64> = rows.into_iter().collect();
+
+        assert!(!truncated);
+        assert_eq!(sizes.len(), 2, "only the root and depth-1 folders: {sizes:?}");
+        assert!(sizes[""] >= sizes["a"]);
+        assert!(sizes["a"] > 0);
+    }
+}
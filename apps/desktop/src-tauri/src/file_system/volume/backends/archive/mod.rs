@@ -54,5 +54,5 @@ pub use read::{
     BytesSource, DEFAULT_TAIL_CACHE_LEN, LocalFileSource, QuarantineReason, SanitizedName, SubtreeExtractReader,
     SubtreeMember, TailCachedSource, TarCodec, format_for_name, format_for_path, sanitize_entry_name,
 };
-pub use volume::ArchiveVolume;
+pub use volume::{ArchiveContents, ArchiveVolume};
 pub use watch::active_watch_count;
@@ -46,6 +46,9 @@ pub enum AddSource {
     LocalPath(PathBuf),
     /// In-memory bytes: a mkfile (empty), or a small resident payload / test input.
     Bytes(Vec<u8>),
+    /// A symlink entry holding this target path (a compress with
+    /// `ArchiveSymlinkMode::Store`). Written with `add_symlink`, no data stream.
+    Symlink(String),
 }
 
 /// One file to add to the archive at `inner_path` (`/`-separated, archive-root-relative).
@@ -275,6 +278,18 @@ pub fn apply(archive_path: &Path, changeset: &Changeset, hooks: &dyn MutationHoo
     // Added files, stream-compressed chunk-by-chunk (never whole-buffered).
     for add in &changeset.adds {
         checkpoint(hooks)?;
+        if let AddSource::Symlink(target) = &add.source {
+            writer
+                .add_symlink(
+                    add.inner_path.trim_matches('/'),
+                    target,
+                    add_entry_options(add, changeset.compression_level),
+                )
+                .map_err(MutationError::Zip)?;
+            progress.entries_done += 1;
+            hooks.on_progress(progress);
+            continue;
+        }
         writer
             .start_file(
                 add.inner_path.trim_matches('/'),
@@ -369,6 +384,8 @@ fn stream_added_entry(
                 hooks.on_progress(*progress);
             }
         }
+        // Written by the caller via `add_symlink`; there's no byte stream.
+        AddSource::Symlink(_) => {}
     }
     Ok(())
 }
@@ -426,6 +443,7 @@ fn add_source_len(add: &AddEntry) -> u64 {
     match &add.source {
         AddSource::Bytes(bytes) => bytes.len() as u64,
         AddSource::LocalPath(path) => std::fs::metadata(path).map(|m| m.len()).unwrap_or(0),
+        AddSource::Symlink(_) => 0,
     }
 }
 
@@ -214,6 +214,31 @@ fn mkfile_writes_a_zero_byte_entry() {
     assert!(unzip_accepts(&path));
 }
 
+#[test]
+fn symlink_add_writes_a_symlink_entry_holding_the_target() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let path = write_zip(tmp.path(), "a", &build_zip(&[stored("keep.txt", b"k".to_vec())]));
+
+    let changeset = Changeset {
+        adds: vec![AddEntry {
+            inner_path: "link".to_string(),
+            source: AddSource::Symlink("keep.txt".to_string()),
+        }],
+        ..Default::default()
+    };
+    apply(&path, &changeset, &NoHooks).expect("apply symlink add");
+
+    let file = File::open(&path).expect("open result archive");
+    let mut archive = ZipArchive::new(file).expect("result archive parses");
+    let mut link = archive.by_name("link").expect("symlink entry present");
+    assert!(link.is_symlink(), "entry must carry the symlink mode bits");
+    let mut target = String::new();
+    link.read_to_string(&mut target).expect("read link target");
+    assert_eq!(target, "keep.txt");
+    drop(link);
+    assert!(unzip_accepts(&path));
+}
+
 // ---- Data-safety: cancel + crash ---------------------------------------------
 
 /// Hooks that report cancelled once at least one progress tick has landed, and
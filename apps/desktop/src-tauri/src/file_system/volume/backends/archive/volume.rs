@@ -42,7 +42,7 @@ use zeroize::Zeroizing;
 
 use super::{
     ArchiveByteSource, ArchiveEntryReader, ArchiveError, ArchiveFormat, ArchiveIndex, ArchiveIndexCache, ArchiveNode,
-    DEFAULT_TAIL_CACHE_LEN, LocalFileSource, SubtreeExtractReader, TailCachedSource,
+    DEFAULT_TAIL_CACHE_LEN, LocalFileSource, QuarantineReason, SubtreeExtractReader, TailCachedSource,
 };
 use crate::file_system::listing::FileEntry;
 use crate::file_system::volume::{
@@ -313,6 +313,42 @@ impl ArchiveVolume {
             }) as Box<dyn VolumeReadStream>)
         })
     }
+
+    /// Every entry in the archive (files and directories, root excluded), in
+    /// tree order, plus the raw names the sanitizer quarantined for `..`
+    /// traversal. Reads only the cached index — nothing is decompressed. Backs
+    /// `list_archive` and the extract pre-flight, which refuses an archive with
+    /// any escaping entry.
+    pub async fn contents(&self) -> Result<ArchiveContents, VolumeError> {
+        let index = self.index().await?;
+        let mut entries = Vec::new();
+        let mut pending = vec![String::new()];
+        while let Some(dir) = pending.pop() {
+            let Some(children) = index.list(&dir) else { continue };
+            for child in children {
+                if child.is_dir {
+                    pending.push(child.path.clone());
+                }
+                entries.push(child);
+            }
+        }
+        let escaping = index
+            .quarantined()
+            .iter()
+            .filter(|(_, reason)| *reason == QuarantineReason::ParentTraversal)
+            .map(|(raw, _)| raw.clone())
+            .collect();
+        Ok(ArchiveContents { entries, escaping })
+    }
+}
+
+/// What [`ArchiveVolume::contents`] returns.
+pub struct ArchiveContents {
+    /// Every browsable entry, directories included.
+    pub entries: Vec<ArchiveNode>,
+    /// Raw names of entries dropped because a `..` component would place them
+    /// outside the extraction root (Zip Slip).
+    pub escaping: Vec<String>,
 }
 
 impl Volume for ArchiveVolume {
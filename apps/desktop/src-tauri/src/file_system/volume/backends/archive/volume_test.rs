@@ -10,7 +10,7 @@ use std::sync::atomic::AtomicBool;
 
 use super::super::test_fixtures::{
     FixtureFile, build_encrypted_7z, build_zip, build_zipcrypto_zip, deflated, dir, encrypted_entry,
-    overstate_record_count, set_first_entry_encrypted, stored,
+    overstate_record_count, patch_equal_len, set_first_entry_encrypted, stored,
 };
 use super::*;
 use crate::file_system::volume::backends::InMemoryVolume;
@@ -121,6 +121,36 @@ async fn lists_root_nested_and_synthetic_directories() {
     assert_eq!(synthetic.modified_at, None);
 }
 
+#[tokio::test]
+async fn contents_walks_every_entry_and_reports_escaping_names() {
+    let mut bytes = build_zip(&[
+        stored("a.txt", "hello"),
+        deflated("dir/sub/c.txt", "deep"),
+        stored("zz/evil.txt", "pwned"),
+    ]);
+    patch_equal_len(&mut bytes, b"zz/evil.txt", b"../evil.txt");
+    let archive = TestArchive::from_bytes(bytes);
+
+    let contents = archive.volume().contents().await.unwrap();
+
+    let mut paths: Vec<(&str, bool)> = contents
+        .entries
+        .iter()
+        .map(|node| (node.path.as_str(), node.is_dir))
+        .collect();
+    paths.sort_unstable();
+    assert_eq!(
+        paths,
+        vec![
+            ("a.txt", false),
+            ("dir", true),
+            ("dir/sub", true),
+            ("dir/sub/c.txt", false)
+        ]
+    );
+    assert_eq!(contents.escaping, vec!["../evil.txt".to_string()]);
+}
+
 #[tokio::test]
 async fn metadata_maps_size_name_and_transparent_path() {
     let archive = TestArchive::from_entries(&[deflated("dir/file.txt", "abcde")]);
@@ -96,6 +96,7 @@ fn seed_listing(volume_id: &str, path: &Path, entries: Vec<FileEntry>) -> String
             sort_by: SortColumn::Name,
             sort_order: SortOrder::Ascending,
             directory_sort_mode: DirectorySortMode::LikeFiles,
+            generation: 0,
             sequence: AtomicU64::new(0),
             created_at: Instant::now(),
             last_accessed_ms: AtomicU64::new(0),
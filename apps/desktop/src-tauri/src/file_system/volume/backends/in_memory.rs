@@ -4,7 +4,10 @@
 //! including create, delete, and list. Useful for unit and integration tests
 //! without touching the real file system.
 
-use super::{CopyScanResult, LaneKey, ScanConflict, SourceItemInfo, SpaceInfo, Volume, VolumeError, VolumeReadStream};
+use super::{
+    CopyScanResult, LaneKey, ScanConflict, ScanConflictKind, SourceItemInfo, SpaceInfo, Volume, VolumeError,
+    VolumeReadStream,
+};
 use crate::file_system::listing::FileEntry;
 use crate::ignore_poison::IgnorePoison;
 use crate::ignore_poison::RwLockIgnorePoison;
@@ -761,6 +764,7 @@ impl Volume for InMemoryVolume {
                         dest_modified,
                         source_is_directory: item.is_directory,
                         dest_is_directory: existing.is_directory,
+                        kind: ScanConflictKind::Exists,
                     });
                 }
             }
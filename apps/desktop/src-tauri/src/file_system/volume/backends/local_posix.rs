@@ -1,21 +1,27 @@
 //! Local POSIX file system volume implementation.
 
 use super::{
-    CopyScanResult, ScanConflict, SourceItemInfo, SpaceInfo, Volume, VolumeError, VolumeReadStream, VolumeScanner,
-    VolumeWatcher,
+    CopyScanResult, ScanConflict, ScanConflictKind, SourceItemInfo, SpaceInfo, Volume, VolumeError, VolumeReadStream,
+    VolumeScanner, VolumeWatcher,
 };
+use crate::file_system::case_sensitivity::{is_case_only_match, is_case_sensitive};
+use crate::file_system::filesystem_kind::is_on_network_filesystem;
 use crate::file_system::git;
-use crate::file_system::listing::{FileEntry, get_single_entry, list_directory_core};
+use crate::file_system::listing::{
+    FileEntry, get_single_entry, list_directory_core, list_directory_core_chunked, list_directory_core_concurrent,
+};
 #[cfg(feature = "playwright-e2e")]
 use crate::ignore_poison::IgnorePoison;
 use crate::indexing::scanner::{self, ScanConfig, ScanError, ScanHandle, ScanSummary};
 use crate::indexing::watch::watcher::{DriveWatcher, FsChangeEvent, WatcherError};
 use crate::indexing::writer::IndexWriter;
+use std::collections::{HashMap, HashSet};
 use std::future::Future;
 use std::io;
 use std::path::{Path, PathBuf};
 use std::pin::Pin;
 use std::sync::atomic::AtomicBool;
+use std::sync::{Arc, OnceLock};
 use tokio::sync::mpsc;
 use tokio::task::spawn_blocking;
 use walkdir::WalkDir;
@@ -76,6 +82,10 @@ pub(crate) fn rename_local_exclusive(source: &Path, destination: &Path) -> io::R
 pub struct LocalPosixVolume {
     name: String,
     root: PathBuf,
+    /// Whether `root` sits on a network filesystem, so listings stat concurrently. Probed on the
+    /// first listing rather than in `new`: the probe is a `statfs`, which can hang on a dead
+    /// mount, and volumes are constructed during discovery. Shared with the blocking readers.
+    network_root: Arc<OnceLock<bool>>,
     /// Raw errno to inject on the next `list_directory` call. Cleared after use.
     #[cfg(feature = "playwright-e2e")]
     injected_error: std::sync::Mutex<Option<i32>>,
@@ -91,6 +101,7 @@ impl LocalPosixVolume {
         Self {
             name: name.into(),
             root: root.into(),
+            network_root: Arc::new(OnceLock::new()),
             #[cfg(feature = "playwright-e2e")]
             injected_error: std::sync::Mutex::new(None),
         }
@@ -111,6 +122,14 @@ impl LocalPosixVolume {
         self.resolve_internal(path)
     }
 
+    /// The lazy network probe behind `prefers_concurrent_metadata`, detached from `self` so the
+    /// blocking listing readers can run it off the async runtime.
+    fn network_root_probe(&self) -> impl FnOnce() -> bool + Send + 'static {
+        let network_root = Arc::clone(&self.network_root);
+        let root = self.root.clone();
+        move || *network_root.get_or_init(|| is_on_network_filesystem(&root))
+    }
+
     fn resolve_internal(&self, path: &Path) -> PathBuf {
         if path.as_os_str().is_empty() || path == Path::new(".") {
             self.root.clone()
@@ -165,12 +184,17 @@ impl Volume for LocalPosixVolume {
             }
         }
         let abs_path = self.resolve(path);
+        let concurrent_metadata = self.network_root_probe();
         Box::pin(async move {
             spawn_blocking(move || {
                 if let Some(routed) = git::try_route_listing(&abs_path) {
                     return routed;
                 }
-                list_directory_core(&abs_path).map_err(VolumeError::from)
+                if concurrent_metadata() {
+                    list_directory_core_concurrent(&abs_path).map_err(VolumeError::from)
+                } else {
+                    list_directory_core(&abs_path).map_err(VolumeError::from)
+                }
             })
             .await
             .expect("spawn_blocking listing closure doesn't panic and the task is uncancelable")
@@ -180,6 +204,50 @@ impl Volume for LocalPosixVolume {
     // list_directory_with_progress: delegate to the trait default (which calls list_directory).
     // The `on_progress` callback is not `Send`, so it can't go into `spawn_blocking`.
 
+    fn list_directory_chunked<'a>(
+        &'a self,
+        path: &'a Path,
+        _on_progress: Option<&'a (dyn Fn(crate::file_system::volume::ListingProgress) + Sync)>,
+        on_chunk: &'a (dyn Fn(Vec<FileEntry>) + Sync),
+        cancel: &'a std::sync::Arc<AtomicBool>,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<FileEntry>, VolumeError>> + Send + 'a>> {
+        #[cfg(feature = "playwright-e2e")]
+        {
+            if self.injected_error.lock_ignore_poison().is_some() {
+                return self.list_directory(path, None);
+            }
+        }
+        let abs_path = self.resolve(path);
+        let concurrent_metadata = self.network_root_probe();
+        let cancel = std::sync::Arc::clone(cancel);
+        Box::pin(async move {
+            // `on_chunk` is only borrowed, so it can't go into `spawn_blocking` either: the
+            // blocking read sends its chunks back here over a channel instead.
+            let (tx, mut rx) = mpsc::unbounded_channel();
+            let read = spawn_blocking(move || {
+                if let Some(routed) = git::try_route_listing(&abs_path) {
+                    return routed;
+                }
+                list_directory_core_chunked(&abs_path, concurrent_metadata(), &cancel, &mut |chunk| {
+                    let _ = tx.send(chunk);
+                })
+                .map_err(|e| {
+                    if e.kind() == io::ErrorKind::Interrupted && cancel.load(std::sync::atomic::Ordering::Relaxed) {
+                        VolumeError::Cancelled("Listing cancelled".to_string())
+                    } else {
+                        VolumeError::from(e)
+                    }
+                })
+            });
+            // The channel closes when the blocking read returns and drops its sender.
+            while let Some(chunk) = rx.recv().await {
+                on_chunk(chunk);
+            }
+            read.await
+                .expect("spawn_blocking listing closure doesn't panic and the task is uncancelable")
+        })
+    }
+
     #[cfg(feature = "playwright-e2e")]
     fn inject_error(&self, errno: i32) {
         *self.injected_error.lock_ignore_poison() = Some(errno);
@@ -299,6 +367,10 @@ impl Volume for LocalPosixVolume {
         true
     }
 
+    fn prefers_concurrent_metadata(&self) -> bool {
+        self.network_root_probe()()
+    }
+
     fn listing_is_watched(&self, path: &Path) -> bool {
         // Resolve relative-to-volume paths to their absolute form so the comparison
         // against `LISTING_CACHE` (which stores absolute paths) lines up.
@@ -617,82 +689,29 @@ impl Volume for LocalPosixVolume {
                     .map_err(VolumeError::from)?;
             }
 
-            // Open destination file on the blocking pool.
-            let dest_for_open = dest_abs.clone();
-            let mut file = spawn_blocking(move || std::fs::File::create(&dest_for_open))
-                .await
-                .expect("spawn_blocking File::create closure doesn't panic and the task is uncancelable")
-                .map_err(VolumeError::from)?;
-
-            let mut bytes_written = 0u64;
-            while let Some(chunk_result) = stream.next_chunk().await {
-                let chunk = chunk_result?;
-                if chunk.is_empty() {
-                    continue;
+            // Stream into a `.part` sibling and rename it over `dest` only once every
+            // byte is on disk, so a failed or cancelled import (a phone unplugged
+            // mid-photo) never leaves a truncated file under the real name. The
+            // random tag keeps a user's own `photo.jpg.part` out of harm's way.
+            let part_abs = part_path_for(&dest_abs);
+            let bytes_written = match stream_into_file(&part_abs, size, stream.as_mut(), on_progress).await {
+                Ok(n) => n,
+                Err(e) => {
+                    remove_part_file(&part_abs).await;
+                    return Err(e);
                 }
-                let chunk_len = chunk.len() as u64;
-
-                // Write the chunk on the blocking pool.
-                let (file_ret, write_res) = spawn_blocking(move || {
-                    use std::io::Write;
-                    let res = file.write_all(&chunk);
-                    (file, res)
-                })
+            };
+            let (part_for_rename, dest_for_rename) = (part_abs.clone(), dest_abs.clone());
+            let renamed = spawn_blocking(move || std::fs::rename(&part_for_rename, &dest_for_rename))
                 .await
-                .expect("spawn_blocking write_all closure doesn't panic and the task is uncancelable");
-                file = file_ret;
-                write_res.map_err(VolumeError::from)?;
-
-                bytes_written += chunk_len;
-
-                if on_progress(bytes_written, size) == std::ops::ControlFlow::Break(()) {
-                    // Drop the file handle and try to clean up the partial file.
-                    drop(file);
-                    let partial = dest_abs.clone();
-                    let _ = spawn_blocking(move || std::fs::remove_file(&partial)).await;
-                    return Err(VolumeError::Cancelled("Operation cancelled by user".to_string()));
-                }
+                .expect("spawn_blocking rename closure doesn't panic and the task is uncancelable");
+            if let Err(e) = renamed {
+                remove_part_file(&part_abs).await;
+                return Err(VolumeError::from(e));
             }
 
-            // Make the file durable before signalling success. A bare
-            // `file.flush()` is a userspace no-op on a raw `std::fs::File`, so
-            // without `sync_data` the bytes would live only in the OS page
-            // cache. A cross-volume copy/move landing on a local disk (MTP →
-            // Local, SMB → Local, USB import) all flows through this method, so
-            // reporting "complete" here without an fdatasync would let the user
-            // eject / sleep and lose data (on a move, from both sides). This
-            // gives the same "durable as each file completes" property the
-            // local-FS chunked copy path already has (`transfer/chunked_copy.rs`
-            // → `dst_file.sync_data()`): a crash mid-batch leaves earlier files
-            // safe.
-            //
-            // Best-effort on error, matching `durability::flush_created_destinations`:
-            // a failed `sync_data` is logged under `target: "write_durability"`,
-            // NOT propagated. The bytes are written either way, and failing a
-            // completed multi-GB transfer at the final fsync is worse UX than
-            // accepting a small durability-window risk on a filesystem that
-            // can't sync.
-            let dest_for_sync = dest_abs.clone();
-            file = spawn_blocking(move || {
-                use std::io::Write;
-                // Userspace flush first (harmless no-op on a raw File, but
-                // correct if the writer is ever wrapped in a BufWriter).
-                let _ = file.flush();
-                if let Err(e) = file.sync_data() {
-                    log::warn!(
-                        target: "write_durability",
-                        "write_from_stream: fdatasync failed for {}: {e}",
-                        dest_for_sync.display()
-                    );
-                }
-                file
-            })
-            .await
-            .expect("spawn_blocking sync_data closure doesn't panic and the task is uncancelable");
-            drop(file);
-
             // Best-effort: fsync the parent directory so the new file's
-            // directory entry (the create) is durable too. Some filesystems
+            // directory entry (the rename) is durable too. Some filesystems
             // reject directory fsync; log and continue.
             if let Some(parent) = dest_abs.parent() {
                 let parent = parent.to_path_buf();
@@ -725,13 +744,50 @@ impl Volume for LocalPosixVolume {
         let source_items: Vec<SourceItemInfo> = source_items.to_vec();
         Box::pin(async move {
             spawn_blocking(move || {
+                // One listing of the destination serves both the exact and the case-only
+                // lookups. A missing destination folder just means nothing clashes.
+                let dest_names: Vec<String> = std::fs::read_dir(&dest_abs)
+                    .map(|entries| {
+                        entries
+                            .flatten()
+                            .map(|e| e.file_name().to_string_lossy().into_owned())
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                let exact: HashSet<&str> = dest_names.iter().map(String::as_str).collect();
+                let folded: HashMap<String, &str> = dest_names.iter().map(|n| (n.to_lowercase(), n.as_str())).collect();
+                let dest_is_case_sensitive = is_case_sensitive(&dest_abs);
                 let mut conflicts = Vec::new();
 
-                for item in &source_items {
-                    let dest_file_path = dest_abs.join(&item.name);
-                    if dest_file_path.exists()
-                        && let Ok(meta) = std::fs::metadata(&dest_file_path)
-                    {
+                for (index, item) in source_items.iter().enumerate() {
+                    let (dest_name, kind) = if exact.contains(item.name.as_str()) {
+                        (item.name.as_str(), ScanConflictKind::Exists)
+                    } else if let Some(variant) = folded.get(&item.name.to_lowercase()) {
+                        (*variant, ScanConflictKind::CaseOnly)
+                    } else {
+                        // Two sources that differ only by case land on one name on a
+                        // case-insensitive destination: the second would overwrite the first.
+                        if !dest_is_case_sensitive
+                            && let Some(earlier) = source_items[..index]
+                                .iter()
+                                .find(|s| is_case_only_match(&s.name, &item.name))
+                        {
+                            conflicts.push(ScanConflict {
+                                source_path: item.name.clone(),
+                                dest_path: dest_abs.join(&earlier.name).to_string_lossy().to_string(),
+                                source_size: item.size,
+                                dest_size: earlier.size,
+                                source_modified: item.modified,
+                                dest_modified: earlier.modified,
+                                source_is_directory: item.is_directory,
+                                dest_is_directory: earlier.is_directory,
+                                kind: ScanConflictKind::CaseOnly,
+                            });
+                        }
+                        continue;
+                    };
+                    let dest_file_path = dest_abs.join(dest_name);
+                    if let Ok(meta) = std::fs::metadata(&dest_file_path) {
                         let dest_modified = meta
                             .modified()
                             .ok()
@@ -746,6 +802,7 @@ impl Volume for LocalPosixVolume {
                             dest_modified,
                             source_is_directory: item.is_directory,
                             dest_is_directory: meta.is_dir(),
+                            kind,
                         });
                     }
                 }
@@ -775,6 +832,94 @@ impl Volume for LocalPosixVolume {
     }
 }
 
+// ── Streaming writes ──────────────────────────────────────────────────
+
+/// The in-progress sibling `write_from_stream` streams into: `<name>.cmdr-<tag>.part`.
+fn part_path_for(dest: &Path) -> PathBuf {
+    let name = dest
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let tag = uuid::Uuid::new_v4().simple().to_string();
+    dest.with_file_name(format!("{}.cmdr-{}.part", name, &tag[..8]))
+}
+
+/// Writes `stream` into a fresh file at `path` and makes it durable. Returns the byte count, or
+/// `Cancelled` when `on_progress` breaks. Leaves any partial file for the caller to remove.
+async fn stream_into_file(
+    path: &Path,
+    size: u64,
+    stream: &mut dyn VolumeReadStream,
+    on_progress: &(dyn Fn(u64, u64) -> std::ops::ControlFlow<()> + Sync),
+) -> Result<u64, VolumeError> {
+    let path_for_open = path.to_path_buf();
+    let mut file = spawn_blocking(move || std::fs::File::create(&path_for_open))
+        .await
+        .expect("spawn_blocking File::create closure doesn't panic and the task is uncancelable")
+        .map_err(VolumeError::from)?;
+
+    let mut bytes_written = 0u64;
+    while let Some(chunk_result) = stream.next_chunk().await {
+        let chunk = chunk_result?;
+        if chunk.is_empty() {
+            continue;
+        }
+        let chunk_len = chunk.len() as u64;
+
+        // Write the chunk on the blocking pool.
+        let (file_ret, write_res) = spawn_blocking(move || {
+            use std::io::Write;
+            let res = file.write_all(&chunk);
+            (file, res)
+        })
+        .await
+        .expect("spawn_blocking write_all closure doesn't panic and the task is uncancelable");
+        file = file_ret;
+        write_res.map_err(VolumeError::from)?;
+
+        bytes_written += chunk_len;
+
+        if on_progress(bytes_written, size) == std::ops::ControlFlow::Break(()) {
+            return Err(VolumeError::Cancelled("Operation cancelled by user".to_string()));
+        }
+    }
+
+    // Make the file durable before signalling success. A bare `file.flush()` is a userspace
+    // no-op on a raw `std::fs::File`, so without `sync_data` the bytes would live only in the
+    // OS page cache. A cross-volume copy/move landing on a local disk (MTP → Local, SMB →
+    // Local, USB import) all flows through here, so reporting "complete" without an fdatasync
+    // would let the user eject / sleep and lose data (on a move, from both sides). This gives
+    // the same "durable as each file completes" property the local-FS chunked copy path has
+    // (`transfer/chunked_copy.rs` → `dst_file.sync_data()`).
+    //
+    // Best-effort on error, matching `durability::flush_created_destinations`: a failed
+    // `sync_data` is logged under `target: "write_durability"`, NOT propagated. The bytes are
+    // written either way, and failing a completed multi-GB transfer at the final fsync is worse
+    // UX than accepting a small durability-window risk on a filesystem that can't sync.
+    let path_for_sync = path.to_path_buf();
+    spawn_blocking(move || {
+        use std::io::Write;
+        // Userspace flush first (harmless no-op on a raw File, but correct if the writer is
+        // ever wrapped in a BufWriter).
+        let _ = file.flush();
+        if let Err(e) = file.sync_data() {
+            log::warn!(
+                target: "write_durability",
+                "write_from_stream: fdatasync failed for {}: {e}",
+                path_for_sync.display()
+            );
+        }
+    })
+    .await
+    .expect("spawn_blocking sync_data closure doesn't panic and the task is uncancelable");
+    Ok(bytes_written)
+}
+
+async fn remove_part_file(path: &Path) {
+    let path = path.to_path_buf();
+    let _ = spawn_blocking(move || std::fs::remove_file(&path)).await;
+}
+
 // ── Indexing trait implementations ────────────────────────────────────
 
 /// Scanner for local POSIX volumes using the guarded walker's parallel directory traversal.
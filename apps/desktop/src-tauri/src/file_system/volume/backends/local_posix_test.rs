@@ -1,7 +1,9 @@
 //! Tests for LocalPosixVolume.
 
 use super::*;
+use std::future::Future;
 use std::path::Path;
+use std::pin::Pin;
 
 #[test]
 fn test_new_creates_volume_with_correct_name_and_root() {
@@ -536,6 +538,60 @@ async fn test_write_from_stream_multichunk_is_durable_and_correct() {
     let _ = fs::remove_dir_all(&vol_dir);
 }
 
+/// Yields one chunk, then fails like a source device unplugged mid-file.
+struct FailsAfterFirstChunk {
+    first: Option<Vec<u8>>,
+}
+
+impl VolumeReadStream for FailsAfterFirstChunk {
+    fn next_chunk(&mut self) -> Pin<Box<dyn Future<Output = Option<Result<Vec<u8>, VolumeError>>> + Send + '_>> {
+        Box::pin(async move {
+            Some(match self.first.take() {
+                Some(chunk) => Ok(chunk),
+                None => Err(VolumeError::DeviceDisconnected("unplugged".to_string())),
+            })
+        })
+    }
+    fn total_size(&self) -> u64 {
+        8
+    }
+    fn bytes_read(&self) -> u64 {
+        if self.first.is_some() { 0 } else { 4 }
+    }
+}
+
+#[tokio::test]
+async fn test_write_from_stream_failure_leaves_no_partial_and_keeps_existing_dest() {
+    use std::fs;
+
+    let vol_dir = std::env::temp_dir().join("cmdr_write_from_stream_partial_test");
+    let _ = fs::remove_dir_all(&vol_dir);
+    fs::create_dir_all(&vol_dir).unwrap();
+    fs::write(vol_dir.join("kept.jpg"), b"original").unwrap();
+
+    let volume = LocalPosixVolume::new("Test", vol_dir.to_str().unwrap());
+    for name in ["new.jpg", "kept.jpg"] {
+        let stream = Box::new(FailsAfterFirstChunk {
+            first: Some(b"half".to_vec()),
+        });
+        let result = volume
+            .write_from_stream(Path::new(name), 8, stream, &|_, _| std::ops::ControlFlow::Continue(()))
+            .await;
+        assert!(matches!(result, Err(VolumeError::DeviceDisconnected(_))));
+    }
+
+    // No truncated `new.jpg`, no leftover `.part`, and the file that was already there is intact.
+    let mut names: Vec<String> = fs::read_dir(&vol_dir)
+        .unwrap()
+        .map(|e| e.unwrap().file_name().to_string_lossy().into_owned())
+        .collect();
+    names.sort();
+    assert_eq!(names, vec!["kept.jpg".to_string()]);
+    assert_eq!(fs::read(vol_dir.join("kept.jpg")).unwrap(), b"original");
+
+    let _ = fs::remove_dir_all(&vol_dir);
+}
+
 #[tokio::test]
 async fn test_scan_for_conflicts_no_conflicts() {
     use std::fs;
@@ -614,6 +670,56 @@ async fn test_scan_for_conflicts_with_conflicts() {
     let _ = fs::remove_dir_all(&test_dir);
 }
 
+#[tokio::test]
+async fn test_scan_for_conflicts_reports_case_only_clashes() {
+    use crate::file_system::case_sensitivity::is_case_sensitive;
+    use crate::file_system::volume::ScanConflictKind;
+    use std::fs;
+
+    let test_dir = std::env::temp_dir().join("cmdr_conflicts_case_test");
+    let _ = fs::remove_dir_all(&test_dir);
+    fs::create_dir_all(&test_dir).unwrap();
+    fs::write(test_dir.join("readme.md"), "existing").unwrap();
+    fs::write(test_dir.join("same.txt"), "existing").unwrap();
+
+    let volume = LocalPosixVolume::new("Test", test_dir.to_str().unwrap());
+    let item = |name: &str| SourceItemInfo {
+        name: name.to_string(),
+        size: 1,
+        modified: None,
+        is_directory: false,
+    };
+    let source_items = vec![
+        item("README.md"),
+        item("same.txt"),
+        item("Notes.txt"),
+        item("NOTES.txt"),
+    ];
+
+    let conflicts = volume.scan_for_conflicts(&source_items, Path::new("")).await.unwrap();
+
+    let readme = conflicts.iter().find(|c| c.source_path == "README.md").unwrap();
+    assert_eq!(readme.kind, ScanConflictKind::CaseOnly);
+    assert!(
+        readme.dest_path.ends_with("readme.md"),
+        "names the existing file: {}",
+        readme.dest_path
+    );
+    assert_eq!(readme.dest_size, 8);
+    let same = conflicts.iter().find(|c| c.source_path == "same.txt").unwrap();
+    assert_eq!(same.kind, ScanConflictKind::Exists);
+    // Source-vs-source clashes only matter where both would land on one name.
+    let notes = conflicts.iter().find(|c| c.source_path == "NOTES.txt");
+    if is_case_sensitive(&test_dir) {
+        assert!(notes.is_none());
+    } else {
+        assert_eq!(notes.unwrap().kind, ScanConflictKind::CaseOnly);
+    }
+    assert!(!conflicts.iter().any(|c| c.source_path == "Notes.txt"));
+
+    let _ = fs::remove_dir_all(&test_dir);
+}
+
 #[tokio::test]
 async fn test_get_space_info() {
     // Test against /tmp which should exist on any POSIX system
@@ -706,6 +812,7 @@ fn test_listing_is_watched_flips_with_watcher_lifecycle() {
                 sort_by: SortColumn::Name,
                 sort_order: SortOrder::Ascending,
                 directory_sort_mode: DirectorySortMode::LikeFiles,
+                generation: 0,
                 sequence: AtomicU64::new(0),
                 created_at: std::time::Instant::now(),
                 last_accessed_ms: AtomicU64::new(0),
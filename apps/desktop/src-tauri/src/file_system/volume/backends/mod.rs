@@ -16,6 +16,7 @@ mod in_memory;
 mod local_posix;
 #[cfg(any(target_os = "macos", target_os = "linux"))]
 pub mod mtp;
+mod search_results;
 #[cfg(any(target_os = "macos", target_os = "linux"))]
 pub mod smb;
 #[cfg(any(target_os = "macos", target_os = "linux"))]
@@ -30,6 +31,7 @@ pub(crate) use local_posix::get_space_info_for_path;
 pub(crate) use local_posix::rename_local_exclusive;
 #[cfg(any(target_os = "macos", target_os = "linux"))]
 pub use mtp::MtpVolume;
+pub use search_results::{SEARCH_RESULTS_VOLUME_PREFIX, SearchResultsVolume, register_search_results};
 #[cfg(any(target_os = "macos", target_os = "linux"))]
 pub use smb::SmbVolume;
 
@@ -45,6 +47,8 @@ pub(crate) use super::{
 mod in_memory_test;
 #[cfg(test)]
 mod local_posix_test;
+#[cfg(test)]
+mod search_results_test;
 // `mtp_test` is gated on the same platforms as the `mtp` module it tests (the
 // other two backends are cross-platform, so their test mods aren't gated).
 // `mtp_archive_test` also needs the `virtual-mtp` feature (every test in it runs
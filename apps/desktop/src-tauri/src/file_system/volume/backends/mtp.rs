@@ -4,8 +4,8 @@
 //! the standard file listing pipeline (same icons, sorting, view modes as local files).
 
 use super::{
-    BatchScanResult, CopyScanResult, LaneKey, MutationEvent, ScanConflict, SourceItemInfo, SpaceInfo, Volume,
-    VolumeError, VolumeReadStream,
+    BatchScanResult, CopyScanResult, LaneKey, MutationEvent, ScanConflict, ScanConflictKind, SourceItemInfo, SpaceInfo,
+    Volume, VolumeError, VolumeReadStream,
 };
 use crate::file_system::listing::FileEntry;
 use crate::file_system::listing::caching::try_get_watched_listing;
@@ -771,6 +771,7 @@ impl Volume for MtpVolume {
                         dest_modified,
                         source_is_directory: item.is_directory,
                         dest_is_directory: existing.is_directory,
+                        kind: ScanConflictKind::Exists,
                     });
                 }
             }
@@ -827,12 +828,11 @@ impl Volume for MtpVolume {
         self.open_read_stream_at_offset(path, 0)
     }
 
-    // Reads in bounded windows from `offset` forward. Since the copy path
-    // (`CheckpointStream`) parks in place between windows rather than releasing +
-    // reopening, NOTHING calls this with a non-zero offset anymore; it's reached
-    // only via `open_read_stream`'s `offset == 0`. Keep the offset parameter
-    // (the resumable primitive is correct and cheap) — don't "clean it up" as
-    // dead just because the non-zero path currently has no caller.
+    // Reads in bounded windows from `offset` forward. Pause and foreground yield
+    // park in place (`CheckpointStream`), so a non-zero offset comes only from
+    // the copy path's `ResumingStream` reopening after a session reset or
+    // timeout mid-file. A reset clears the path cache; `open_read_session`
+    // re-walks from the storage root like any uncached path.
     fn open_read_stream_at_offset<'a>(
         &'a self,
         path: &'a Path,
@@ -843,7 +843,7 @@ impl Volume for MtpVolume {
 
             // The window size (`mtp_read_window()`, shrinkable in tests) and the
             // start `offset` (non-zero resumes a reopened read; see
-            // `CheckpointStream`) are baked into the `WindowedDownload` here.
+            // `ResumingStream`) are baked into the `WindowedDownload` here.
             let session = connection_manager()
                 .open_read_session(&self.device_id, self.storage_id, &mtp_path, offset, mtp_read_window())
                 .await
@@ -571,7 +571,7 @@ async fn bounded_window_read_assembles_byte_exact() {
         .expect("virtual-mtp connect should succeed");
     let storage_id = info.storages.first().expect("virtual device should have storages").id;
 
-    // Prime the path cache (resolve_path_to_handle is cache-only).
+    // Prime the path cache, as a browse to the volume would.
     connection_manager()
         .list_directory(&device_id, storage_id, "/")
         .await
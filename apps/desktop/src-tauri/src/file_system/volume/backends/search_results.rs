@@ -0,0 +1,265 @@
+//! Virtual volume over a fixed set of search results.
+//!
+//! Shows the output of `search_files` or `find_duplicates` in a normal pane: the
+//! volume's root lists the stored `FileEntry` rows (which span many real
+//! directories), so sorting, selection, and the whole listing pipeline work on
+//! them unchanged. Each row keeps its real absolute path, and everything past
+//! the root listing delegates to the real volume containing that path
+//! (`VolumeManager::containing`): metadata, descending into a result folder,
+//! reads for a copy, delete, and rename.
+//!
+//! Registered transiently under `search-{uuid}` (see [`register_search_results`]);
+//! the caller unregisters it when the pane leaves the results.
+
+use super::{CopyScanResult, MutationEvent, Volume, VolumeError, VolumeReadStream};
+use crate::file_system::get_volume_manager;
+use crate::file_system::listing::FileEntry;
+use crate::file_system::listing::caching::{DirectoryChange, notify_directory_changed};
+use crate::file_system::volume::ListingProgress;
+use crate::ignore_poison::RwLockIgnorePoison;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::sync::{Arc, RwLock};
+
+/// Prefix of every search-results volume id.
+pub const SEARCH_RESULTS_VOLUME_PREFIX: &str = "search-";
+
+/// A read-mostly volume whose root lists a fixed set of real files and folders.
+pub struct SearchResultsVolume {
+    name: String,
+    /// Virtual root (`search://{uuid}`). Never a real path, so it can't shadow a
+    /// real volume in `VolumeManager::containing` or `find_by_root`.
+    root: PathBuf,
+    /// The result rows, each with its real absolute `path`. Pruned and patched
+    /// as delete/rename go through this volume.
+    results: RwLock<Vec<FileEntry>>,
+    /// Whether every result lives on a local-FS volume, so a copy out of the
+    /// results can take the native local copy path (see [`Volume::local_path`]).
+    all_local: bool,
+}
+
+impl SearchResultsVolume {
+    /// Creates a results volume. `all_local` is whether every result's containing
+    /// volume supports local FS access (see [`register_search_results`]).
+    pub fn new(name: impl Into<String>, root: impl Into<PathBuf>, results: Vec<FileEntry>, all_local: bool) -> Self {
+        Self {
+            name: name.into(),
+            root: root.into(),
+            results: RwLock::new(results),
+            all_local,
+        }
+    }
+
+    /// Whether `path` names the results root (the flat list) rather than a real path.
+    fn is_results_root(&self, path: &Path) -> bool {
+        path.as_os_str().is_empty() || path == Path::new(".") || path == Path::new("/") || path == self.root
+    }
+
+    /// Drops the result at `path` and any result nested under it.
+    fn forget(&self, path: &Path) {
+        self.results
+            .write_ignore_poison()
+            .retain(|entry| !Path::new(&entry.path).starts_with(path));
+    }
+
+    /// Repoints the result at `from` (and any nested under it) to `to`.
+    fn repoint(&self, from: &Path, to: &Path) {
+        for entry in self.results.write_ignore_poison().iter_mut() {
+            if let Ok(rest) = Path::new(&entry.path).strip_prefix(from) {
+                let new_path = if rest.as_os_str().is_empty() {
+                    to.to_path_buf()
+                } else {
+                    to.join(rest)
+                };
+                if rest.as_os_str().is_empty()
+                    && let Some(name) = to.file_name()
+                {
+                    entry.name = name.to_string_lossy().into_owned();
+                }
+                entry.path = new_path.to_string_lossy().into_owned();
+            }
+        }
+    }
+}
+
+/// The real volume containing `path`, and `path` relative to its root.
+fn real_volume_for(path: &Path) -> Result<(String, Arc<dyn Volume>, PathBuf), VolumeError> {
+    let manager = get_volume_manager();
+    let not_found = || VolumeError::NotFound(path.display().to_string());
+    let (id, relative) = manager.containing(path).ok_or_else(not_found)?;
+    let volume = manager.get(&id).ok_or_else(not_found)?;
+    Ok((id, volume, relative))
+}
+
+impl Volume for SearchResultsVolume {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn root(&self) -> &Path {
+        &self.root
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn list_directory<'a>(
+        &'a self,
+        path: &'a Path,
+        on_progress: Option<&'a (dyn Fn(ListingProgress) + Sync)>,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<FileEntry>, VolumeError>> + Send + 'a>> {
+        Box::pin(async move {
+            if self.is_results_root(path) {
+                return Ok(self.results.read_ignore_poison().clone());
+            }
+            // Descending into a result folder browses the real folder.
+            let (_, volume, relative) = real_volume_for(path)?;
+            volume.list_directory(&relative, on_progress).await
+        })
+    }
+
+    fn get_metadata<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> Pin<Box<dyn Future<Output = Result<FileEntry, VolumeError>> + Send + 'a>> {
+        Box::pin(async move {
+            if self.is_results_root(path) {
+                return Ok(FileEntry::new(
+                    self.name.clone(),
+                    self.root.to_string_lossy().into_owned(),
+                    true,
+                    false,
+                ));
+            }
+            let (_, volume, relative) = real_volume_for(path)?;
+            volume.get_metadata(&relative).await
+        })
+    }
+
+    fn exists<'a>(&'a self, path: &'a Path) -> Pin<Box<dyn Future<Output = bool> + Send + 'a>> {
+        Box::pin(async move {
+            if self.is_results_root(path) {
+                return true;
+            }
+            match real_volume_for(path) {
+                Ok((_, volume, relative)) => volume.exists(&relative).await,
+                Err(_) => false,
+            }
+        })
+    }
+
+    fn is_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> Pin<Box<dyn Future<Output = Result<bool, VolumeError>> + Send + 'a>> {
+        Box::pin(async move {
+            if self.is_results_root(path) {
+                return Ok(true);
+            }
+            let (_, volume, relative) = real_volume_for(path)?;
+            volume.is_directory(&relative).await
+        })
+    }
+
+    fn delete<'a>(&'a self, path: &'a Path) -> Pin<Box<dyn Future<Output = Result<(), VolumeError>> + Send + 'a>> {
+        Box::pin(async move {
+            if self.is_results_root(path) {
+                return Err(VolumeError::NotSupported);
+            }
+            let (_, volume, relative) = real_volume_for(path)?;
+            volume.delete(&relative).await?;
+            self.forget(path);
+            Ok(())
+        })
+    }
+
+    /// Renames within ONE real volume. A rename whose ends resolve to different
+    /// volumes is a move, which goes through the transfer pipeline instead.
+    fn rename<'a>(
+        &'a self,
+        from: &'a Path,
+        to: &'a Path,
+        force: bool,
+    ) -> Pin<Box<dyn Future<Output = Result<(), VolumeError>> + Send + 'a>> {
+        Box::pin(async move {
+            if self.is_results_root(from) || self.is_results_root(to) {
+                return Err(VolumeError::NotSupported);
+            }
+            let (from_id, volume, from_relative) = real_volume_for(from)?;
+            let (to_id, _, to_relative) = real_volume_for(to)?;
+            if from_id != to_id {
+                return Err(VolumeError::NotSupported);
+            }
+            volume.rename(&from_relative, &to_relative, force).await?;
+            self.repoint(from, to);
+            Ok(())
+        })
+    }
+
+    /// Forwards the mutation to the real volume (so a pane showing the real
+    /// folder updates too), then re-reads the results listing: rows from
+    /// different folders can share a name, so a by-name diff could hit the wrong one.
+    fn notify_mutation<'a>(
+        &'a self,
+        volume_id: &'a str,
+        parent_path: &'a Path,
+        mutation: MutationEvent,
+    ) -> Pin<Box<dyn Future<Output = ()> + Send + 'a>> {
+        Box::pin(async move {
+            if let Ok((real_id, volume, _)) = real_volume_for(parent_path) {
+                volume.notify_mutation(&real_id, parent_path, mutation).await;
+            }
+            notify_directory_changed(volume_id, &self.root, DirectoryChange::FullRefresh);
+        })
+    }
+
+    fn supports_export(&self) -> bool {
+        true
+    }
+
+    /// `/` when every result is on a local-FS volume: the rows carry absolute
+    /// paths, so `"/".join(path)` is the real path and the native copy applies.
+    fn local_path(&self) -> Option<PathBuf> {
+        self.all_local.then(|| PathBuf::from("/"))
+    }
+
+    fn scan_for_copy<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> Pin<Box<dyn Future<Output = Result<CopyScanResult, VolumeError>> + Send + 'a>> {
+        Box::pin(async move {
+            let (_, volume, relative) = real_volume_for(path)?;
+            volume.scan_for_copy(&relative).await
+        })
+    }
+
+    fn open_read_stream<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> Pin<Box<dyn Future<Output = Result<Box<dyn VolumeReadStream>, VolumeError>> + Send + 'a>> {
+        Box::pin(async move {
+            let (_, volume, relative) = real_volume_for(path)?;
+            volume.open_read_stream(&relative).await
+        })
+    }
+}
+
+/// Builds a results volume over `entries` and registers it under a fresh
+/// `search-{uuid}` id, which it returns. Unregister it with
+/// `VolumeManager::unregister` once the pane leaves the results.
+pub fn register_search_results(name: impl Into<String>, entries: Vec<FileEntry>) -> String {
+    let uuid = uuid::Uuid::new_v4();
+    let manager = get_volume_manager();
+    let all_local = entries.iter().all(|entry| {
+        manager
+            .containing(Path::new(&entry.path))
+            .and_then(|(id, _)| manager.get(&id))
+            .is_some_and(|volume| volume.supports_local_fs_access())
+    });
+    let volume = SearchResultsVolume::new(name, format!("search://{uuid}"), entries, all_local);
+    let volume_id = format!("{SEARCH_RESULTS_VOLUME_PREFIX}{uuid}");
+    manager.register(&volume_id, Arc::new(volume));
+    volume_id
+}
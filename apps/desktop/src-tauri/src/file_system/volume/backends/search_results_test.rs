@@ -0,0 +1,113 @@
+//! Tests for SearchResultsVolume.
+
+use super::*;
+use crate::file_system::get_volume_manager;
+use crate::file_system::listing::get_single_entry;
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Registers a `LocalPosixVolume` over a fresh temp dir holding `a/one.txt` and
+/// `b/two.txt`, plus a results volume over those two files. Returns the temp dir
+/// (keep it alive), the real volume's id, and the results volume's id.
+fn setup(tag: &str) -> (tempfile::TempDir, String, String) {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let root = dir.path().canonicalize().expect("canonicalize");
+    fs::create_dir_all(root.join("a")).expect("mkdir a");
+    fs::create_dir_all(root.join("b")).expect("mkdir b");
+    fs::write(root.join("a/one.txt"), b"1").expect("write one");
+    fs::write(root.join("b/two.txt"), b"22").expect("write two");
+
+    let real_id = format!("sr-real-{tag}");
+    get_volume_manager().register(&real_id, Arc::new(LocalPosixVolume::new("Temp", &root)));
+
+    let entries = vec![
+        get_single_entry(&root.join("a/one.txt")).expect("entry one"),
+        get_single_entry(&root.join("b/two.txt")).expect("entry two"),
+    ];
+    let results_id = register_search_results("Results", entries);
+    (dir, real_id, results_id)
+}
+
+fn teardown(real_id: &str, results_id: &str) {
+    get_volume_manager().unregister(results_id);
+    get_volume_manager().unregister(real_id);
+}
+
+#[tokio::test]
+async fn test_root_lists_results_across_directories() {
+    let (dir, real_id, results_id) = setup("list");
+    let volume = get_volume_manager().get(&results_id).expect("registered");
+    assert!(results_id.starts_with(SEARCH_RESULTS_VOLUME_PREFIX));
+
+    let mut names: Vec<String> = volume
+        .list_directory(Path::new(""), None)
+        .await
+        .expect("list root")
+        .into_iter()
+        .map(|e| e.name)
+        .collect();
+    names.sort();
+    assert_eq!(names, vec!["one.txt", "two.txt"]);
+    assert!(volume.is_directory(Path::new("")).await.expect("root is dir"));
+
+    // Descending into a result's real folder browses the real volume.
+    let real_a = dir.path().canonicalize().expect("canonicalize").join("a");
+    let listed = volume.list_directory(&real_a, None).await.expect("list real folder");
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0].name, "one.txt");
+
+    teardown(&real_id, &results_id);
+}
+
+#[tokio::test]
+async fn test_metadata_and_delete_delegate_to_real_volume() {
+    let (dir, real_id, results_id) = setup("delete");
+    let volume = get_volume_manager().get(&results_id).expect("registered");
+    let two = dir.path().canonicalize().expect("canonicalize").join("b/two.txt");
+
+    let meta = volume.get_metadata(&two).await.expect("metadata");
+    assert_eq!(meta.name, "two.txt");
+    assert_eq!(meta.size, Some(2));
+    assert!(volume.local_path().is_some(), "all results are local");
+
+    volume.delete(&two).await.expect("delete");
+    assert!(!two.exists(), "the real file is gone");
+    let remaining = volume.list_directory(Path::new(""), None).await.expect("list root");
+    assert_eq!(remaining.len(), 1);
+    assert_eq!(remaining[0].name, "one.txt");
+
+    teardown(&real_id, &results_id);
+}
+
+#[tokio::test]
+async fn test_rename_repoints_the_result() {
+    let (dir, real_id, results_id) = setup("rename");
+    let volume = get_volume_manager().get(&results_id).expect("registered");
+    let root = dir.path().canonicalize().expect("canonicalize");
+
+    volume
+        .rename(&root.join("a/one.txt"), &root.join("a/uno.txt"), false)
+        .await
+        .expect("rename");
+    assert!(root.join("a/uno.txt").exists());
+    let listed = volume.list_directory(Path::new(""), None).await.expect("list root");
+    let renamed = listed.iter().find(|e| e.name == "uno.txt").expect("renamed row");
+    assert_eq!(Path::new(&renamed.path), root.join("a/uno.txt"));
+
+    teardown(&real_id, &results_id);
+}
+
+#[tokio::test]
+async fn test_paths_outside_any_volume_are_not_found() {
+    let volume = SearchResultsVolume::new("Results", "search://none", Vec::new(), false);
+    assert!(volume.local_path().is_none());
+    assert!(
+        volume
+            .list_directory(Path::new(""), None)
+            .await
+            .expect("list")
+            .is_empty()
+    );
+    assert!(!volume.exists(Path::new("relative/nowhere")).await);
+}
@@ -381,6 +381,7 @@ impl SmbVolume {
                         dest_modified,
                         source_is_directory: item.is_directory,
                         dest_is_directory: existing.is_directory,
+                        kind: ScanConflictKind::Exists,
                     });
                 }
             }
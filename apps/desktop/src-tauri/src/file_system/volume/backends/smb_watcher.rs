@@ -26,9 +26,6 @@ use unicode_normalization::UnicodeNormalization;
 /// Maximum events for a single directory before emitting `FullRefresh`.
 const WATCHER_BATCH_THRESHOLD: usize = 50;
 
-/// Debounce window: after receiving a batch of events, wait this long for more.
-const WATCHER_DEBOUNCE: Duration = Duration::from_millis(200);
-
 /// Converts a watcher filename (NFC from server) to an NFD display path
 /// suitable for macOS mount paths.
 fn to_nfd_display_path(mount_path: &Path, relative: &str) -> PathBuf {
@@ -294,9 +291,12 @@ pub(super) async fn run_smb_watcher(
                 // on the wire, so events that arrive during this debounce
                 // window land in the next response, not a server-side gap.
                 // The debounce here exists only to batch FE notifications.
+                // The window is the user's network debounce setting, read per batch so changes
+                // apply to running watchers.
+                let debounce = crate::file_system::debounce_for(crate::file_system::WatcherVolumeKind::Network);
                 loop {
                     let more = tokio::select! {
-                        result = tokio::time::timeout(WATCHER_DEBOUNCE, watcher.next_events()) => {
+                        result = tokio::time::timeout(debounce, watcher.next_events()) => {
                             match result {
                                 Ok(Ok(more_events)) => Some(more_events),
                                 Ok(Err(_)) => None,
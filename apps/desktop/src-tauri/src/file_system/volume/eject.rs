@@ -13,7 +13,11 @@
 //!   safe to unplug; on DMG-mounted disk images, `eject` is the verb that
 //!   detaches the image (`unmount` would leave it attached).
 //!
-//! Non-ejectable volumes return an error.
+//! Non-ejectable volumes return an error, and so does a volume a write op is
+//! still touching (naming the blocking op ids). Disk volumes get their index
+//! stopped and their listings' file watchers paused before the unmount; the OS
+//! flushes pending writes as part of it. A successful eject emits
+//! `volume-ejected`.
 //!
 //! The `commands::eject` IPC layer is a thin delegate over [`eject`]: it maps the
 //! typed [`EjectError`] to the wire `IpcError` (including the timeout flag).
@@ -105,8 +109,8 @@ pub fn decide_eject_action(ctx: &EjectContext) -> Result<EjectAction, EjectDecis
 #[derive(Debug)]
 pub enum EjectError {
     /// A write op is reading from or writing to this volume; refuse to tear it
-    /// down mid-transfer.
-    Busy,
+    /// down mid-transfer. Carries the blocking op ids, sorted.
+    Busy { operation_ids: Vec<String> },
     /// `volume_id` isn't registered in `VolumeManager` (a race: unmounted mid-op).
     VolumeNotFound { volume_id: String },
     /// The kind dispatch couldn't pick an action.
@@ -124,9 +128,10 @@ impl std::fmt::Display for EjectError {
             // The picker disables Eject for busy volumes, so reaching here means a
             // race (or an MCP / automation caller); the message tells the user to
             // retry once the transfer finishes.
-            Self::Busy => write!(
+            Self::Busy { operation_ids } => write!(
                 f,
-                "operations are in progress on this device. Eject again once they finish"
+                "operations are in progress on this device ({}). Eject again once they finish",
+                operation_ids.join(", ")
             ),
             Self::VolumeNotFound { volume_id } => write!(f, "Volume not found: {}", volume_id),
             Self::Decision(e) => write!(f, "{}", e),
@@ -140,10 +145,11 @@ impl std::error::Error for EjectError {}
 
 /// Ejects a volume. Picks the right teardown for the volume's kind.
 ///
-/// Returns `Ok(())` once the unmount or disconnect is initiated. The frontend
-/// shouldn't wait for the volume to fully disappear — `volume-unmounted` (for
-/// disk volumes) or `mtp-device-disconnected` (for MTP) will fire shortly
-/// after and panes rooted at the volume redirect to root.
+/// Returns `Ok(())` once the unmount or disconnect is done, after emitting
+/// `volume-ejected`. The frontend shouldn't wait for the volume to fully
+/// disappear — `volume-unmounted` (for disk volumes) or
+/// `mtp-device-disconnected` (for MTP) will fire shortly after and panes rooted
+/// at the volume redirect to root.
 pub async fn eject(volume_id: &str) -> Result<(), EjectError> {
     use crate::file_system::get_volume_manager;
 
@@ -152,8 +158,10 @@ pub async fn eject(volume_id: &str) -> Result<(), EjectError> {
     // here means a race (or an MCP / automation caller); refuse rather than
     // disconnect mid-transfer and risk a truncated file. See the volume picker's
     // `volumes-busy-changed` wiring.
-    if crate::file_system::busy_volume_ids().iter().any(|id| id == volume_id) {
-        return Err(EjectError::Busy);
+    let operation_ids = crate::file_system::operations_on_volume(volume_id);
+    if !operation_ids.is_empty() {
+        log::info!(target: "eject", "Refusing to eject {volume_id}: busy with {operation_ids:?}");
+        return Err(EjectError::Busy { operation_ids });
     }
 
     // MTP volumes use ID format `{device_id}:{storage_id}` and aren't
@@ -190,16 +198,22 @@ pub async fn eject(volume_id: &str) -> Result<(), EjectError> {
     })
     .map_err(EjectError::Decision)?;
 
-    match action {
+    let result = match action {
         EjectAction::MtpDisconnect { device_id } => mtp_disconnect(&device_id).await,
         // For disk volumes, stop the index BEFORE the unmount (the wedge-safe point).
         // MTP tears its index down through the disconnect hook, so it isn't stopped
         // here.
         EjectAction::DiskutilUnmount => {
-            stop_index_then_unmount(volume_id, || diskutil_run("unmount", &mount_path)).await
+            stop_index_then_unmount(volume_id, &mount_path, || diskutil_run("unmount", &mount_path)).await
         }
-        EjectAction::DiskutilEject => stop_index_then_unmount(volume_id, || diskutil_run("eject", &mount_path)).await,
+        EjectAction::DiskutilEject => {
+            stop_index_then_unmount(volume_id, &mount_path, || diskutil_run("eject", &mount_path)).await
+        }
+    };
+    if result.is_ok() {
+        crate::volume_broadcast::emit_volume_ejected(volume_id);
     }
+    result
 }
 
 /// Stop the volume's index (if any) BEFORE running the unmount/eject.
@@ -211,13 +225,22 @@ pub async fn eject(volume_id: &str) -> Result<(), EjectError> {
 /// panic). The ordering is unconditional: the index stop is awaited to completion,
 /// then the unmount runs. `unmount` is a parameter so the ordering can be asserted
 /// in a test without a real volume or `diskutil`. No-op stop for an unindexed volume.
-async fn stop_index_then_unmount<F, Fut>(volume_id: &str, unmount: F) -> Result<(), EjectError>
+///
+/// The file watchers of listings on the volume are paused right before the unmount
+/// for the same reason (an open watch holds the volume), and re-armed if it fails so
+/// the panes keep updating.
+async fn stop_index_then_unmount<F, Fut>(volume_id: &str, mount_path: &str, unmount: F) -> Result<(), EjectError>
 where
     F: FnOnce() -> Fut,
     Fut: Future<Output = Result<(), EjectError>>,
 {
     stop_index_blocking(volume_id).await;
-    unmount().await
+    let paused = crate::file_system::listing::pause_watchers_on_volume(volume_id, std::path::Path::new(mount_path));
+    let result = unmount().await;
+    if result.is_err() {
+        crate::file_system::listing::resume_watchers(&paused);
+    }
+    result
 }
 
 /// Stop `volume_id`'s index on the blocking pool, awaited so the stop COMPLETES
@@ -526,7 +549,7 @@ mod tests {
         let observed = Arc::clone(&active_when_unmount_ran);
         let vid_for_unmount = vid.to_string();
 
-        let result = stop_index_then_unmount(vid, || async move {
+        let result = stop_index_then_unmount(vid, "/Volumes/cmdr-test-eject-stop-order", || async move {
             // Record the index state at the exact moment the unmount would run.
             observed.store(indexing::is_active(&vid_for_unmount), Ordering::SeqCst);
             Ok(())
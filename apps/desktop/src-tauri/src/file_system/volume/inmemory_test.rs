@@ -214,6 +214,7 @@ fn test_streaming_state_lifecycle() {
     let state = Arc::new(StreamingListingState {
         cancelled: Arc::new(AtomicBool::new(false)),
         cancel_notify: tokio::sync::Notify::new(),
+        generation: 0,
     });
 
     // Insert into cache
@@ -260,6 +261,7 @@ fn test_multiple_concurrent_streaming_states() {
             Arc::new(StreamingListingState {
                 cancelled: Arc::new(AtomicBool::new(false)),
                 cancel_notify: tokio::sync::Notify::new(),
+                generation: 0,
             })
         })
         .collect();
@@ -4,8 +4,9 @@
 //! It tracks both the available volumes and which one is the current default.
 
 use super::Volume;
+use crate::indexing::paths::firmlinks;
 use std::collections::{HashMap, VecDeque};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex, RwLock};
 
 /// Archive routing (`resolve`, `.zip`-boundary predicates, the archive LRU, and
@@ -124,6 +125,38 @@ impl VolumeManager {
             .map(|(id, _)| id.clone())
     }
 
+    /// Resolve the registered volume containing `path`: the one whose root is
+    /// the longest ancestor (or equal) of it, plus `path` relative to that root
+    /// (empty for the root itself).
+    ///
+    /// The in-process counterpart to the `resolve_path_volume` command, for
+    /// internal callers (enrichment, reveal, operations) that already know the
+    /// volume must be registered and can't afford a `statfs`. Unlike
+    /// [`mount_id_for_path`](Self::mount_id_for_path), `root` (`/`) takes part,
+    /// so any absolute local path resolves. `path` goes through
+    /// `firmlinks::normalize_path` first, so `/System/Volumes/Data/Users/…` lands
+    /// on `root` like `/Users/…` does. Matching is component-wise, and the most
+    /// specific root wins, so a volume mounted inside another volume's subtree
+    /// claims its own paths. Two volumes with the same root (test doubles) tie-break
+    /// on the smaller id, so the answer doesn't depend on `HashMap` order.
+    pub fn containing(&self, path: &Path) -> Option<(String, PathBuf)> {
+        let normalized = PathBuf::from(firmlinks::normalize_path(&path.to_string_lossy()));
+        let volumes = self.volumes.read().ok()?;
+        let (id, root) = volumes
+            .iter()
+            .map(|(id, v)| (id, v.root()))
+            .filter(|(_, root)| normalized.starts_with(root))
+            .max_by(|(a_id, a_root), (b_id, b_root)| {
+                a_root
+                    .as_os_str()
+                    .len()
+                    .cmp(&b_root.as_os_str().len())
+                    .then_with(|| b_id.cmp(a_id))
+            })?;
+        let relative = normalized.strip_prefix(root).ok()?.to_path_buf();
+        Some((id.clone(), relative))
+    }
+
     /// Gets the default volume.
     pub fn default_volume(&self) -> Option<Arc<dyn Volume>> {
         let default_id = self.default_volume_id.read().ok()?.clone()?;
@@ -278,6 +311,69 @@ mod tests {
         assert_eq!(manager.mount_id_for_path("/Users/me"), None);
     }
 
+    #[test]
+    fn containing_resolves_to_the_most_specific_root_with_a_relative_path() {
+        use crate::file_system::LocalPosixVolume;
+
+        let manager = VolumeManager::new();
+        manager.register("root", Arc::new(LocalPosixVolume::new("Root", "/")));
+        manager.register("ext", Arc::new(LocalPosixVolume::new("Ext", "/Volumes/X")));
+        // Mounted inside `ext`'s subtree: its paths must resolve to it, not `ext`.
+        manager.register("nested", Arc::new(LocalPosixVolume::new("Nested", "/Volumes/X/mnt/Y")));
+
+        let resolve = |p: &str| manager.containing(Path::new(p));
+        assert_eq!(
+            resolve("/Volumes/X/mnt/Y/deep/file.txt"),
+            Some(("nested".to_string(), PathBuf::from("deep/file.txt")))
+        );
+        assert_eq!(
+            resolve("/Volumes/X/mnt/Y"),
+            Some(("nested".to_string(), PathBuf::new()))
+        );
+        // The nested mount's parent dir still belongs to the outer volume.
+        assert_eq!(
+            resolve("/Volumes/X/mnt"),
+            Some(("ext".to_string(), PathBuf::from("mnt")))
+        );
+        // A component-boundary sibling falls through to `root`.
+        assert_eq!(
+            resolve("/Volumes/XY/z"),
+            Some(("root".to_string(), PathBuf::from("Volumes/XY/z")))
+        );
+        assert_eq!(
+            resolve("/Users/me"),
+            Some(("root".to_string(), PathBuf::from("Users/me")))
+        );
+    }
+
+    #[test]
+    fn containing_returns_none_when_no_root_matches() {
+        use crate::file_system::LocalPosixVolume;
+
+        let manager = VolumeManager::new();
+        manager.register("ext", Arc::new(LocalPosixVolume::new("Ext", "/Volumes/X")));
+        assert_eq!(manager.containing(Path::new("/Users/me")), None);
+        assert_eq!(manager.containing(Path::new("relative/path")), None);
+    }
+
+    #[cfg(target_os = "macos")]
+    #[test]
+    fn containing_normalizes_the_path_before_matching() {
+        use crate::file_system::LocalPosixVolume;
+
+        // `/tmp` is a symlink to `/private/tmp`; roots are canonical, user paths often aren't.
+        let manager = VolumeManager::new();
+        manager.register("root", Arc::new(LocalPosixVolume::new("Root", "/")));
+        manager.register(
+            "scratch",
+            Arc::new(LocalPosixVolume::new("Scratch", "/private/tmp/cmdr-vol")),
+        );
+        assert_eq!(
+            manager.containing(Path::new("/tmp/cmdr-vol/a.txt")),
+            Some(("scratch".to_string(), PathBuf::from("a.txt")))
+        );
+    }
+
     #[test]
     fn test_find_by_root_returns_registered_entry() {
         let manager = VolumeManager::new();
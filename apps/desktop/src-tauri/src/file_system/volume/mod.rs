@@ -143,6 +143,7 @@ pub trait VolumeWatcher: Send + Sync {
 /// - `InMemoryVolume`: In-memory file system for testing
 /// - `MtpVolume`: MTP device storage (natively async)
 /// - `SmbVolume`: SMB share storage (natively async via smb2)
+/// - `SearchResultsVolume`: search results spanning many folders, delegating to the real volumes
 ///
 /// All path parameters are relative to the volume root. The volume handles
 /// translating these to actual storage locations.
@@ -217,6 +218,27 @@ pub trait Volume: Send + Sync {
         self.list_directory(path, on_progress)
     }
 
+    /// Chunked version of [`list_directory_with_cancel`](Self::list_directory_with_cancel)
+    /// for the streaming listing: hands entries to `on_chunk` in read order while the
+    /// listing runs, so a huge folder's first screenful can render before the last entry
+    /// is read. The returned listing is complete and sorted, exactly as without chunks.
+    ///
+    /// Backends decide whether a folder is worth chunking; a small one may produce no
+    /// chunks at all. `LocalPosixVolume` chunks folders of at least
+    /// `CHUNKED_LISTING_MIN_ENTRIES` and stops on `cancel` between entries.
+    ///
+    /// Default impl produces no chunks and delegates to `list_directory_with_cancel`.
+    fn list_directory_chunked<'a>(
+        &'a self,
+        path: &'a Path,
+        on_progress: Option<&'a (dyn Fn(ListingProgress) + Sync)>,
+        on_chunk: &'a (dyn Fn(Vec<FileEntry>) + Sync),
+        cancel: &'a std::sync::Arc<AtomicBool>,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<FileEntry>, VolumeError>> + Send + 'a>> {
+        let _ = on_chunk;
+        self.list_directory_with_cancel(path, on_progress, Some(cancel))
+    }
+
     /// List a directory for the BACKGROUND index scan.
     ///
     /// Same result as `list_directory_with_cancel` with no progress callback, but
@@ -548,6 +570,17 @@ pub trait Volume: Send + Sync {
         true
     }
 
+    /// Whether listing this volume should stat entries concurrently (a small bounded pool in
+    /// `listing::reading`) instead of one by one. Worth it only where each stat is a network
+    /// round trip, like an OS-mounted SMB or NFS share; on local disks concurrent stats just
+    /// thrash. Backends whose listing already carries metadata (SMB over smb2, MTP) have no
+    /// per-entry stats to overlap.
+    ///
+    /// Default `false`: listings stay serial unless a backend opts in.
+    fn prefers_concurrent_metadata(&self) -> bool {
+        false
+    }
+
     /// Returns `true` when the listing at `path` is currently being kept in sync
     /// by a live watcher on this volume. Used by
     /// `file_system::listing::caching::try_get_watched_listing` to decide whether
@@ -870,11 +903,11 @@ pub trait Volume: Send + Sync {
     /// remaining tail), so a resumed transfer's progress stays anchored to the
     /// whole file; `bytes_read()` counts only this segment.
     ///
-    /// A resumable-read primitive. The copy path no longer reopens at an offset
-    /// (pause and foreground yield park in place between bounded windows in the
-    /// transfer wrapper `CheckpointStream`, so nothing calls this with a non-zero
-    /// offset today), but MTP keeps it correct: a non-zero `offset` streams
-    /// exactly `[offset, size)` with no gap or overlap.
+    /// A resumable-read primitive: the transfer wrapper `ResumingStream` calls it
+    /// with a non-zero `offset` to pick a copy back up after a transient source
+    /// error mid-file (pause and foreground yield park in place instead). A
+    /// non-zero `offset` must stream exactly `[offset, size)` with no gap or
+    /// overlap.
     ///
     /// Default is `NotSupported`; only MTP implements it. `MtpVolume`'s
     /// `open_read_stream` routes through it with `offset == 0`.
@@ -1064,6 +1097,7 @@ pub(crate) use backends::rename_local_exclusive;
 pub use backends::{InMemoryVolume, LocalPosixVolume};
 #[cfg(any(target_os = "macos", target_os = "linux"))]
 pub use backends::{MtpVolume, SmbVolume};
+pub use backends::{SEARCH_RESULTS_VOLUME_PREFIX, SearchResultsVolume, register_search_results};
 
 // `smb` is re-exported as a module path because callers reach into it for
 // `SmbConnectionParams` / `connect_smb_volume` / `set_app_handle`.
@@ -77,6 +77,7 @@ fn insert_listing(id: &str, volume_id: &str, path: &str, entries: Vec<FileEntry>
             sort_by: SortColumn::Name,
             sort_order: SortOrder::Ascending,
             directory_sort_mode: DirectorySortMode::LikeFiles,
+            generation: 0,
             sequence: AtomicU64::new(1),
             created_at: std::time::Instant::now(),
             last_accessed_ms: AtomicU64::new(0),
@@ -195,10 +196,8 @@ async fn mtp_scan_cold_cache_still_uses_parent_grouping() {
     let (device_id, vol, vid, _guard) = connect_virtual_device().await;
     get_volume_manager().register(&vid, vol.clone() as Arc<dyn Volume>);
 
-    // MTP needs the parent's path-handle cached before it can list any path
-    // (`resolve_path_to_handle` is cache-only; only `/` is auto-known). Walk
-    // root first so `/Documents` and `/DCIM` get into the path-handle cache.
-    // We don't care about the entries here, just the side effect on the cache.
+    // List the root first to check the fixture layout (deeper paths would
+    // resolve on their own, by walking down from the root).
     let root = vol.list_directory(Path::new("/"), None).await.expect("listing /");
     assert!(
         root.iter().any(|e| e.name == "Documents") && root.iter().any(|e| e.name == "DCIM"),
@@ -66,6 +66,7 @@ fn insert_listing(id: &str, volume_id: &str, path: &str, entries: Vec<FileEntry>
             sort_by: SortColumn::Name,
             sort_order: SortOrder::Ascending,
             directory_sort_mode: DirectorySortMode::LikeFiles,
+            generation: 0,
             sequence: AtomicU64::new(1),
             created_at: std::time::Instant::now(),
             last_accessed_ms: AtomicU64::new(0),
@@ -163,6 +163,20 @@ pub struct BatchScanResult {
     pub per_path: Vec<(PathBuf, CopyScanResult)>,
 }
 
+/// What kind of clash a [`ScanConflict`] is.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "snake_case")]
+pub enum ScanConflictKind {
+    /// An item with exactly the source's name exists at the destination.
+    #[default]
+    Exists,
+    /// The names differ only by case (`README.md` vs `readme.md`). Either an existing
+    /// destination item (which a case-insensitive destination would overwrite, and a
+    /// case-sensitive one would keep next to the copy), or another source item that lands on
+    /// the same name on a case-insensitive destination. `dest_path` names the other item.
+    CaseOnly,
+}
+
 /// A conflict detected during pre-copy scanning: a source item that already exists at the
 /// destination.
 #[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
@@ -187,6 +201,9 @@ pub struct ScanConflict {
     /// `true` when the destination item is a directory (from the dest listing
     /// entry). See `source_is_directory`.
     pub dest_is_directory: bool,
+    /// Backends that don't look for case-only clashes always report `Exists`.
+    #[serde(default)]
+    pub kind: ScanConflictKind,
 }
 
 /// Space information for a volume.
@@ -342,6 +359,7 @@ mod scan_conflict_serde_tests {
             dest_modified: Some(1_700_000_001),
             source_is_directory: true,
             dest_is_directory: true,
+            kind: ScanConflictKind::Exists,
         };
 
         let json = serde_json::to_string(&conflict).unwrap();
@@ -365,10 +383,20 @@ mod scan_conflict_serde_tests {
             dest_modified: None,
             source_is_directory: true,
             dest_is_directory: false,
+            kind: ScanConflictKind::CaseOnly,
         };
 
         let back: ScanConflict = serde_json::from_str(&serde_json::to_string(&conflict).unwrap()).unwrap();
         assert!(back.source_is_directory);
         assert!(!back.dest_is_directory);
+        assert_eq!(back.kind, ScanConflictKind::CaseOnly);
+    }
+
+    #[test]
+    fn scan_conflict_without_kind_deserializes_as_exists() {
+        let json = r#"{"sourcePath":"a","destPath":"/dst/a","sourceSize":1,"destSize":2,"sourceModified":null,"destModified":null,"sourceIsDirectory":false,"destIsDirectory":false}"#;
+
+        let back: ScanConflict = serde_json::from_str(json).unwrap();
+        assert_eq!(back.kind, ScanConflictKind::Exists);
     }
 }
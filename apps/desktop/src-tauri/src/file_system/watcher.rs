@@ -3,8 +3,8 @@
 //! Watches directories for changes and emits `directory-diff` events to frontend.
 //! Uses the unified LISTING_CACHE from operations.rs (no duplicate cache).
 //! Two processing paths: incremental (stat + classify individual events, patch cache
-//! in-place via cache helpers) and full re-read fallback (> 500 events or unknown
-//! event kinds).
+//! in-place via cache helpers) and full re-read fallback (> 500 events, unknown
+//! event kinds, or a changed `.gitignore`).
 
 use notify_debouncer_full::{
     DebounceEventResult, DebouncedEvent, Debouncer, RecommendedCache, new_debouncer,
@@ -13,7 +13,8 @@ use notify_debouncer_full::{
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
-use std::sync::{LazyLock, RwLock};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{LazyLock, Mutex, RwLock};
 use std::time::Duration;
 use tauri::AppHandle;
 use tauri_specta::Event as _;
@@ -27,19 +28,71 @@ use crate::indexing::paths::firmlinks;
 /// Default debounce duration in milliseconds (used if not configured)
 const DEFAULT_DEBOUNCE_MS: u64 = 200;
 
+/// MTP's floor when it has no override: devices emit bursts of events during bulk copies, so it
+/// never goes below its historical 500 ms even when the general value is lower.
+const MTP_MIN_DEFAULT_DEBOUNCE_MS: u64 = 500;
+
 /// Configured debounce duration in milliseconds (set by frontend via update_debounce_ms)
-static DEBOUNCE_MS: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(DEFAULT_DEBOUNCE_MS);
+static DEBOUNCE_MS: AtomicU64 = AtomicU64::new(DEFAULT_DEBOUNCE_MS);
+
+/// Per-volume-kind overrides in milliseconds, indexed by [`WatcherVolumeKind::index`]. `0` means
+/// "no override": the kind follows the general value.
+static DEBOUNCE_OVERRIDES_MS: [AtomicU64; 3] = [AtomicU64::new(0), AtomicU64::new(0), AtomicU64::new(0)];
+
+/// Which debounce setting a watcher follows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub enum WatcherVolumeKind {
+    /// Local disks: the FSEvents / inotify watcher in this module.
+    Local,
+    /// OS-mounted network shares (same watcher as local) and direct SMB volumes (`smb_watcher`).
+    Network,
+    /// MTP devices (the device event loop's debouncer).
+    Mtp,
+}
 
-/// Updates the file watcher debounce duration.
-/// This affects newly started watchers; existing watchers keep their original duration.
+impl WatcherVolumeKind {
+    fn index(self) -> usize {
+        match self {
+            Self::Local => 0,
+            Self::Network => 1,
+            Self::Mtp => 2,
+        }
+    }
+}
+
+/// Updates the general file watcher debounce duration, the default for every volume kind without
+/// an override. Active watchers that follow it are restarted with the new duration.
 pub fn update_debounce_ms(ms: u64) {
-    DEBOUNCE_MS.store(ms, std::sync::atomic::Ordering::Relaxed);
+    DEBOUNCE_MS.store(ms, Ordering::Relaxed);
     log::debug!("File watcher debounce updated to {} ms", ms);
+    reapply_debounce();
 }
 
-/// Gets the current debounce duration in milliseconds.
-fn get_debounce_ms() -> u64 {
-    DEBOUNCE_MS.load(std::sync::atomic::Ordering::Relaxed)
+/// Sets (`Some`) or clears (`None`) the debounce override for one volume kind, then restarts the
+/// active watchers it affects. SMB and MTP read the value on every batch, so they pick it up
+/// without a restart.
+pub fn update_debounce_override_ms(kind: WatcherVolumeKind, ms: Option<u64>) {
+    DEBOUNCE_OVERRIDES_MS[kind.index()].store(ms.unwrap_or(0), Ordering::Relaxed);
+    log::debug!("File watcher debounce override for {kind:?} set to {ms:?} ms");
+    reapply_debounce();
+}
+
+/// The debounce duration watchers on `kind` volumes use right now: the override if set, else the
+/// general value (for MTP, at least [`MTP_MIN_DEFAULT_DEBOUNCE_MS`]).
+pub fn debounce_for(kind: WatcherVolumeKind) -> Duration {
+    let ms = match DEBOUNCE_OVERRIDES_MS[kind.index()].load(Ordering::Relaxed) {
+        0 => {
+            let general = DEBOUNCE_MS.load(Ordering::Relaxed);
+            if kind == WatcherVolumeKind::Mtp {
+                general.max(MTP_MIN_DEFAULT_DEBOUNCE_MS)
+            } else {
+                general
+            }
+        }
+        ms => ms,
+    };
+    Duration::from_millis(ms)
 }
 
 /// Global watcher manager
@@ -83,6 +136,11 @@ pub struct DirectoryDeletedEvent {
 pub(crate) struct WatchedDirectory {
     #[allow(dead_code, reason = "Debouncer must be held to keep watching")]
     debouncer: Debouncer<RecommendedWatcher, RecommendedCache>,
+    /// What `start_watching` was given, so a debounce change can rebuild the debouncer.
+    path: PathBuf,
+    kind: WatcherVolumeKind,
+    /// The duration the debouncer was built with.
+    debounce: Duration,
 }
 
 /// Manages file watchers for directories
@@ -129,11 +187,28 @@ pub fn app_handle_present() -> bool {
 /// Note: Initial entries are read from LISTING_CACHE when needed.
 pub fn start_watching(listing_id: &str, path: &Path) -> Result<(), String> {
     log::debug!("start_watching: listing_id={}, path={}", listing_id, path.display());
-    let listing_id_owned = listing_id.to_string();
-    let listing_for_closure = listing_id_owned.clone();
+    // OS-mounted shares come through here too (as local paths); they follow the network setting.
+    let kind = if crate::file_system::filesystem_kind::is_on_network_filesystem(path) {
+        WatcherVolumeKind::Network
+    } else {
+        WatcherVolumeKind::Local
+    };
+    let watched = watch_directory(listing_id, path, kind)?;
+
+    // Store in manager (no entries - we use LISTING_CACHE)
+    let mut manager = WATCHER_MANAGER.write().map_err(|_| "Failed to acquire watcher lock")?;
+
+    manager.watches.insert(listing_id.to_string(), watched);
+
+    Ok(())
+}
+
+/// Builds the debouncer for one listing with `kind`'s current debounce and starts it.
+fn watch_directory(listing_id: &str, path: &Path, kind: WatcherVolumeKind) -> Result<WatchedDirectory, String> {
+    let listing_for_closure = listing_id.to_string();
 
     // Create the debouncer with a callback that handles changes
-    let debounce_duration = Duration::from_millis(get_debounce_ms());
+    let debounce_duration = debounce_for(kind);
     let mut debouncer = new_debouncer(
         debounce_duration,
         None, // No tick rate limit
@@ -158,12 +233,38 @@ pub fn start_watching(listing_id: &str, path: &Path) -> Result<(), String> {
         .watch(path, RecursiveMode::NonRecursive)
         .map_err(|e| format!("Failed to watch path: {}", e))?;
 
-    // Store in manager (no entries - we use LISTING_CACHE)
-    let mut manager = WATCHER_MANAGER.write().map_err(|_| "Failed to acquire watcher lock")?;
+    Ok(WatchedDirectory {
+        debouncer,
+        path: path.to_path_buf(),
+        kind,
+        debounce: debounce_duration,
+    })
+}
 
-    manager.watches.insert(listing_id_owned, WatchedDirectory { debouncer });
+/// Rebuilds every active watcher whose debounce no longer matches its kind's setting. The new
+/// debouncer starts before the old one is dropped, so changes in between are seen (at worst
+/// twice, which the incremental diff absorbs) rather than missed.
+fn reapply_debounce() {
+    let Ok(mut manager) = WATCHER_MANAGER.write() else {
+        return;
+    };
+    for (listing_id, watched) in manager.watches.iter_mut() {
+        if watched.debounce == debounce_for(watched.kind) {
+            continue;
+        }
+        match watch_directory(listing_id, &watched.path, watched.kind) {
+            Ok(rebuilt) => *watched = rebuilt,
+            Err(e) => log::warn!("Couldn't re-apply watcher debounce for listing {listing_id}: {e}"),
+        }
+    }
+}
 
-    Ok(())
+/// Whether a watcher is running for a given listing. Listings on volumes that
+/// watch through their own backend (SMB, MTP) have none here.
+pub fn is_watching(listing_id: &str) -> bool {
+    WATCHER_MANAGER
+        .read()
+        .is_ok_and(|manager| manager.watches.contains_key(listing_id))
 }
 
 /// Stop watching a directory for a given listing.
@@ -174,6 +275,107 @@ pub fn stop_watching(listing_id: &str) {
     }
 }
 
+// ============================================================================
+// Coalescing stats (debounce tuning)
+// ============================================================================
+
+/// Per-listing counters behind `get_watcher_stats`. Plain integers under one mutex: they're
+/// bumped once per debounce window and once per emitted diff, never per file.
+#[derive(Debug, Clone, Copy, Default)]
+struct WatchCounters {
+    events_received: u64,
+    batches: u64,
+    full_rereads: u64,
+    diffs_emitted: u64,
+    changes_emitted: u64,
+}
+
+static WATCHER_STATS: LazyLock<Mutex<HashMap<String, WatchCounters>>> = LazyLock::new(|| Mutex::new(HashMap::new()));
+
+fn bump_stats(listing_id: &str, f: impl FnOnce(&mut WatchCounters)) {
+    if let Ok(mut stats) = WATCHER_STATS.lock() {
+        f(stats.entry(listing_id.to_string()).or_default());
+    }
+}
+
+/// Counts one `directory-diff` emit carrying `changes` changes. Called by the diff emitter, so
+/// it covers every volume kind, not just the listings this module watches.
+pub(crate) fn record_diff_emitted(listing_id: &str, changes: usize) {
+    bump_stats(listing_id, |c| {
+        c.diffs_emitted += 1;
+        c.changes_emitted += changes as u64;
+    });
+}
+
+/// Drops a listing's counters. Called from `list_directory_end`, so a reopened folder starts
+/// from zero.
+pub(crate) fn clear_watcher_stats(listing_id: &str) {
+    if let Ok(mut stats) = WATCHER_STATS.lock() {
+        stats.remove(listing_id);
+    }
+}
+
+/// How one listing's file-system events were coalesced, for diagnosing flicker reports.
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct WatcherListingStats {
+    pub listing_id: String,
+    pub path: String,
+    /// File-system events the debouncer delivered. It already merges repeats per path, so a
+    /// burst of writes to one file counts once per window.
+    pub events_received: u64,
+    /// Debounce windows that fired with events.
+    pub batches: u64,
+    /// Batches handled by re-reading the whole folder (too many or ambiguous events).
+    pub full_rereads: u64,
+    /// `directory-diff` events sent to the frontend, after the diff emitter's own coalescing.
+    pub diffs_emitted: u64,
+    /// Changes those diffs carried in total.
+    pub changes_emitted: u64,
+    /// The debounce this listing's watcher runs with. `None` when the volume watches through its
+    /// own backend (direct SMB, MTP), which only the diff counters cover.
+    pub debounce_ms: Option<u64>,
+}
+
+/// Snapshot of every active listing's counters: each listing this module watches plus any
+/// other listing that emitted a diff. Sorted by path.
+pub fn watcher_stats() -> Vec<WatcherListingStats> {
+    let counters = WATCHER_STATS.lock().map(|s| s.clone()).unwrap_or_default();
+    let watched: HashMap<String, (PathBuf, Duration)> = WATCHER_MANAGER
+        .read()
+        .map(|m| {
+            m.watches
+                .iter()
+                .map(|(id, w)| (id.clone(), (w.path.clone(), w.debounce)))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let ids: HashSet<&String> = counters.keys().chain(watched.keys()).collect();
+    let mut stats: Vec<WatcherListingStats> = ids
+        .into_iter()
+        .filter_map(|id| {
+            let c = counters.get(id).copied().unwrap_or_default();
+            let (path, debounce_ms) = match watched.get(id) {
+                Some((path, debounce)) => (path.clone(), Some(debounce.as_millis() as u64)),
+                None => (crate::file_system::listing::caching::get_listing_path(id)?, None),
+            };
+            Some(WatcherListingStats {
+                listing_id: id.clone(),
+                path: path.to_string_lossy().to_string(),
+                events_received: c.events_received,
+                batches: c.batches,
+                full_rereads: c.full_rereads,
+                diffs_emitted: c.diffs_emitted,
+                changes_emitted: c.changes_emitted,
+                debounce_ms,
+            })
+        })
+        .collect();
+    stats.sort_by(|a, b| a.path.cmp(&b.path));
+    stats
+}
+
 /// Maps an FSEvents/inotify path to the watched listing's path space, returning the
 /// rebased path when the event is for a direct child of the watched directory.
 ///
@@ -212,12 +414,28 @@ pub(super) fn rebase_event_path(event_path: &Path, dir_path: &Path, canonical_di
 ///
 /// Falls back to `handle_directory_change` when events are too numerous or ambiguous.
 fn handle_directory_change_incremental(listing_id: &str, events: Vec<DebouncedEvent>) {
-    // Fallback: too many events or ambiguous event kinds
-    if events.len() > 500
+    // A changed `.gitignore` can flip any sibling's `is_git_ignored`: drop the folder's
+    // compiled rules and re-read it whole.
+    let gitignore_changed = events
+        .iter()
+        .flat_map(|e| &e.paths)
+        .any(|p| p.file_name().is_some_and(|n| n == ".gitignore"));
+    if gitignore_changed && let Some((_, dir_path)) = get_listing_volume_id_and_path(listing_id) {
+        crate::file_system::listing::git_ignore::invalidate(&dir_path);
+    }
+
+    // Fallback: too many events, ambiguous event kinds, or new ignore rules
+    let full_reread = gitignore_changed
+        || events.len() > 500
         || events
             .iter()
-            .any(|e| matches!(e.kind, EventKind::Any | EventKind::Other))
-    {
+            .any(|e| matches!(e.kind, EventKind::Any | EventKind::Other));
+    bump_stats(listing_id, |c| {
+        c.events_received += events.len() as u64;
+        c.batches += 1;
+        c.full_rereads += u64::from(full_reread);
+    });
+    if full_reread {
         let lid = listing_id.to_string();
         // `tauri::async_runtime::spawn` instead of `tokio::spawn` because this
         // closure runs on the notify-rs debouncer thread, which has no Tokio
@@ -398,7 +616,7 @@ pub async fn handle_directory_change(listing_id: &str) {
     };
 
     // Get old entries and path from the unified LISTING_CACHE
-    let Some((path, old_entries)) = get_listing_entries(listing_id) else {
+    let Some((path, old_entries, generation)) = get_listing_entries(listing_id) else {
         return; // Listing no longer exists
     };
 
@@ -501,7 +719,9 @@ pub async fn handle_directory_change(listing_id: &str) {
     }
 
     // Update the unified LISTING_CACHE with new entries
-    update_listing_entries(listing_id, new_entries);
+    if !update_listing_entries(listing_id, generation, new_entries) {
+        return; // The listing was replaced while we re-read; this diff is for the old one
+    }
 
     crate::file_system::listing::diff_emitter::enqueue_diff(listing_id, changes);
 }
@@ -587,4 +807,5 @@ fn is_entry_modified(old: &FileEntry, new: &FileEntry) -> bool {
         || old.permissions != new.permissions
         || old.is_directory != new.is_directory
         || old.is_symlink != new.is_symlink
+        || old.is_git_ignored != new.is_git_ignored
 }
@@ -319,6 +319,7 @@ async fn test_handle_directory_change_refreshes_from_volume() {
                 sort_by: SortColumn::Name,
                 sort_order: SortOrder::Ascending,
                 directory_sort_mode: DirectorySortMode::LikeFiles,
+                generation: 0,
                 sequence: std::sync::atomic::AtomicU64::new(0),
                 created_at: std::time::Instant::now(),
                 last_accessed_ms: std::sync::atomic::AtomicU64::new(0),
@@ -383,6 +384,7 @@ async fn test_handle_directory_change_detects_new_entries() {
                 sort_by: SortColumn::Name,
                 sort_order: SortOrder::Ascending,
                 directory_sort_mode: DirectorySortMode::LikeFiles,
+                generation: 0,
                 sequence: std::sync::atomic::AtomicU64::new(0),
                 created_at: std::time::Instant::now(),
                 last_accessed_ms: std::sync::atomic::AtomicU64::new(0),
@@ -416,3 +418,43 @@ async fn test_handle_directory_change_detects_new_entries() {
     }
     get_volume_manager().unregister(&volume_id);
 }
+
+#[test]
+fn watcher_stats_count_emitted_diffs_until_cleared() {
+    use crate::file_system::listing::caching::{CachedListing, LISTING_CACHE};
+    use crate::file_system::listing::sorting::{DirectorySortMode, SortColumn, SortOrder};
+    use crate::file_system::watcher::{clear_watcher_stats, record_diff_emitted, watcher_stats};
+
+    let listing_id = format!("listing-stats-{}", uuid::Uuid::new_v4());
+    {
+        let mut cache = LISTING_CACHE.write().unwrap();
+        cache.insert(
+            listing_id.clone(),
+            CachedListing {
+                volume_id: "stats-volume".to_string(),
+                path: PathBuf::from("/stats"),
+                entries: Vec::new(),
+                sort_by: SortColumn::Name,
+                sort_order: SortOrder::Ascending,
+                directory_sort_mode: DirectorySortMode::LikeFiles,
+                generation: 0,
+                sequence: std::sync::atomic::AtomicU64::new(0),
+                created_at: std::time::Instant::now(),
+                last_accessed_ms: std::sync::atomic::AtomicU64::new(0),
+            },
+        );
+    }
+
+    record_diff_emitted(&listing_id, 3);
+    record_diff_emitted(&listing_id, 2);
+    let stats = watcher_stats();
+    let row = stats.iter().find(|s| s.listing_id == listing_id).unwrap();
+    assert_eq!((row.diffs_emitted, row.changes_emitted), (2, 5));
+    assert_eq!(row.path, "/stats");
+    // Not watched by this module, so no debounce of its own.
+    assert_eq!(row.debounce_ms, None);
+
+    clear_watcher_stats(&listing_id);
+    LISTING_CACHE.write().unwrap().remove(&listing_id);
+    assert!(!watcher_stats().iter().any(|s| s.listing_id == listing_id));
+}
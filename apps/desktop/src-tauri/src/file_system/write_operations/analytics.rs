@@ -51,6 +51,8 @@ pub(super) fn emit_completion_analytics(event: &WriteCompleteEvent) {
         // Explicit no-op arms (not a catch-all `_`) so a future op type can't
         // silently skip analytics without a compile error here.
         WriteOperationType::Rename | WriteOperationType::CreateFolder | WriteOperationType::CreateFile => {}
+        // A chmod is a settings change, not a file-management action worth counting.
+        WriteOperationType::SetPermissions => {}
     }
 }
 
@@ -24,7 +24,7 @@ use super::super::OperationEventSink;
 use super::super::archive_remote_edit::{self, RemoteEditError};
 use super::super::scratch_dir::ScratchDir;
 use super::super::state::WriteOperationState;
-use super::super::types::{ConflictResolution, WriteOperationError, WriteOperationStartResult};
+use super::super::types::{ArchiveSymlinkMode, ConflictResolution, WriteOperationError, WriteOperationStartResult};
 use super::copy_into::route_archive_copy_into_with_provenance;
 use crate::file_system::get_volume_manager;
 use crate::file_system::volume::Volume;
@@ -155,6 +155,11 @@ impl Drop for SeedTempGuard {
 /// [`route_archive_copy_into`] to add the sources as one changeset. Reuses
 /// `WriteOperationType::ArchiveEdit` — compress has no distinct backend op type;
 /// its identity lives in the frontend.
+///
+/// Sources keep their paths relative to their common ancestor (bare names for
+/// one folder's selection). `symlinks` picks skip, store, or follow. A target
+/// inside a source folder is left out of its own archive; a target that IS one
+/// of the sources is refused before the seed could overwrite it.
 #[allow(
     clippy::too_many_arguments,
     reason = "mirrors route_archive_copy_into's cross-volume→archive seam (source handle, paths, dest, parent id, policy)"
@@ -168,8 +173,21 @@ pub(crate) async fn compress_start(
     conflict: ConflictResolution,
     progress_interval_ms: u64,
     compression_level: Option<i64>,
+    symlinks: ArchiveSymlinkMode,
     initiator: crate::operation_log::types::Initiator,
 ) -> Result<WriteOperationStartResult, WriteOperationError> {
+    let dest_is_local = get_volume_manager()
+        .get(&parent_volume_id)
+        .is_none_or(|parent| parent.supports_local_fs_access());
+    if dest_is_local
+        && let Some(root) = source_volume.local_path()
+        && source_paths.iter().any(|src| root.join(src) == dest_zip_full_path)
+    {
+        return Err(WriteOperationError::SameLocation {
+            path: dest_zip_full_path.display().to_string(),
+        });
+    }
+
     // Seed a valid empty zip at the target so the copy-into has a real archive to
     // open. The seed must be visible to `route_archive_copy_into`'s parent-aware
     // path: a LOCAL parent edits the file in place, so a local-FS seed works; a
@@ -206,6 +224,7 @@ pub(crate) async fn compress_start(
         progress_interval_ms,
         false,
         compression_level,
+        symlinks,
         super::super::journal::ArchiveProvenance::compress(net_new, initiator),
     )
     .await
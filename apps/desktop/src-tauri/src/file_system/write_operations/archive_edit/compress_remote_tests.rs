@@ -73,6 +73,7 @@ async fn compress_onto_a_remote_parent_seeds_and_packs_local_files() {
         ConflictResolution::Overwrite,
         0,
         None,
+        crate::file_system::write_operations::ArchiveSymlinkMode::Skip,
         crate::operation_log::types::Initiator::User,
     )
     .await
@@ -131,6 +132,7 @@ async fn compress_onto_a_remote_parent_overwrites_an_existing_zip_with_a_fresh_a
         ConflictResolution::Overwrite,
         0,
         None,
+        crate::file_system::write_operations::ArchiveSymlinkMode::Skip,
         crate::operation_log::types::Initiator::User,
     )
     .await
@@ -183,6 +185,7 @@ async fn compress_onto_an_mtp_style_remote_parent_seeds_and_packs() {
         ConflictResolution::Overwrite,
         0,
         None,
+        crate::file_system::write_operations::ArchiveSymlinkMode::Skip,
         crate::operation_log::types::Initiator::User,
     )
     .await
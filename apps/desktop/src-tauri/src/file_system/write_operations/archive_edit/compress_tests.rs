@@ -86,6 +86,7 @@ async fn compress_start_packs_local_files_into_a_new_zip() {
         ConflictResolution::Overwrite,
         0,
         None,
+        crate::file_system::write_operations::ArchiveSymlinkMode::Skip,
         crate::operation_log::types::Initiator::User,
     )
     .await
@@ -147,6 +148,7 @@ async fn compress_journals_subkind_and_net_new_from_the_driver() {
         ConflictResolution::Overwrite,
         0,
         None,
+        crate::file_system::write_operations::ArchiveSymlinkMode::Skip,
         Initiator::User,
     )
     .await
@@ -215,6 +217,7 @@ async fn compress_start_packs_a_directory_subtree() {
         ConflictResolution::Overwrite,
         0,
         None,
+        crate::file_system::write_operations::ArchiveSymlinkMode::Skip,
         crate::operation_log::types::Initiator::User,
     )
     .await
@@ -276,6 +279,7 @@ async fn compress_payload_at(dir: &Path, tag: &str, level: Option<i64>, payload:
         ConflictResolution::Overwrite,
         0,
         level,
+        crate::file_system::write_operations::ArchiveSymlinkMode::Skip,
         crate::operation_log::types::Initiator::User,
     )
     .await
@@ -357,3 +361,149 @@ async fn compress_out_of_range_level_clamps_instead_of_failing() {
         "level 42 must clamp to level 9"
     );
 }
+
+/// Compresses `paths` from a local volume rooted at `src_root` into `dest` and
+/// waits for the terminal complete event.
+async fn compress_and_wait(
+    src_root: &Path,
+    paths: &[&str],
+    dest: &Path,
+    symlinks: crate::file_system::write_operations::ArchiveSymlinkMode,
+) -> Arc<CollectorEventSink> {
+    use crate::file_system::volume::backends::LocalPosixVolume;
+
+    let source_volume: Arc<dyn Volume> = Arc::new(LocalPosixVolume::new("src", src_root.to_path_buf()));
+    let events = Arc::new(CollectorEventSink::new());
+    compress_start(
+        Arc::clone(&events) as Arc<dyn OperationEventSink>,
+        source_volume,
+        paths.iter().map(|path| PathBuf::from(*path)).collect(),
+        dest.to_path_buf(),
+        unique_lane_id(),
+        ConflictResolution::Overwrite,
+        0,
+        None,
+        symlinks,
+        crate::operation_log::types::Initiator::User,
+    )
+    .await
+    .expect("start compress");
+    wait_until_async(Duration::from_secs(5), "the write-complete event", || {
+        !events.complete.lock_ignore_poison().is_empty()
+    })
+    .await;
+    events
+}
+
+/// Sources from different folders keep their structure below the common
+/// ancestor, so two same-named files both land instead of colliding.
+#[tokio::test]
+async fn compress_keeps_paths_relative_to_the_common_ancestor() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let src_root = tmp.path().join("src");
+    std::fs::create_dir_all(src_root.join("a")).expect("mkdir a");
+    std::fs::create_dir_all(src_root.join("b")).expect("mkdir b");
+    std::fs::write(src_root.join("a/note.txt"), b"from a").expect("wa");
+    std::fs::write(src_root.join("b/note.txt"), b"from b").expect("wb");
+    let dest = tmp.path().join("notes.zip");
+
+    compress_and_wait(
+        &src_root,
+        &["a/note.txt", "b/note.txt"],
+        &dest,
+        crate::file_system::write_operations::ArchiveSymlinkMode::Skip,
+    )
+    .await;
+
+    assert_eq!(read_entry(&dest, "a/note.txt").as_deref(), Some(b"from a".as_slice()));
+    assert_eq!(read_entry(&dest, "b/note.txt").as_deref(), Some(b"from b".as_slice()));
+}
+
+/// `Store` writes the link as a symlink entry; `Follow` packs the target's bytes.
+#[cfg(unix)]
+#[tokio::test]
+async fn compress_stores_or_follows_symlinks_per_config() {
+    use crate::file_system::write_operations::ArchiveSymlinkMode;
+
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let src_root = tmp.path().join("src");
+    std::fs::create_dir_all(src_root.join("dir")).expect("mkdir dir");
+    std::fs::write(src_root.join("dir/real.txt"), b"payload").expect("w real");
+    std::os::unix::fs::symlink("real.txt", src_root.join("dir/link")).expect("symlink");
+
+    let stored = tmp.path().join("stored.zip");
+    compress_and_wait(&src_root, &["dir"], &stored, ArchiveSymlinkMode::Store).await;
+    let file = std::fs::File::open(&stored).expect("open stored zip");
+    let mut archive = ZipArchive::new(file).expect("stored zip parses");
+    let mut link = archive.by_name("dir/link").expect("the link is archived");
+    assert!(link.is_symlink(), "Store must keep the link a link");
+    let mut target = Vec::new();
+    link.read_to_end(&mut target).expect("read link target");
+    assert_eq!(target, b"real.txt");
+    drop(link);
+
+    let followed = tmp.path().join("followed.zip");
+    compress_and_wait(&src_root, &["dir"], &followed, ArchiveSymlinkMode::Follow).await;
+    assert_eq!(
+        read_entry(&followed, "dir/link").as_deref(),
+        Some(b"payload".as_slice())
+    );
+}
+
+/// A target inside the folder being compressed is left out of its own archive.
+#[tokio::test]
+async fn compress_leaves_a_target_inside_the_source_folder_out_of_the_archive() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let src_root = tmp.path().join("src");
+    std::fs::create_dir_all(src_root.join("project")).expect("mkdir project");
+    std::fs::write(src_root.join("project/main.rs"), b"fn main() {}").expect("w main");
+    let dest = src_root.join("project/project.zip");
+
+    let events = compress_and_wait(
+        &src_root,
+        &["project"],
+        &dest,
+        crate::file_system::write_operations::ArchiveSymlinkMode::Skip,
+    )
+    .await;
+
+    assert_eq!(
+        read_entry(&dest, "project/main.rs").as_deref(),
+        Some(b"fn main() {}".as_slice())
+    );
+    assert!(
+        read_entry(&dest, "project/project.zip").is_none(),
+        "the archive must not contain itself"
+    );
+    assert_eq!(events.complete.lock_ignore_poison()[0].files_skipped, 0);
+}
+
+/// A target that IS one of the sources is refused before the seed overwrites it.
+#[tokio::test]
+async fn compress_refuses_a_target_that_is_one_of_its_sources() {
+    use crate::file_system::volume::backends::LocalPosixVolume;
+
+    let tmp = tempfile::tempdir().expect("tempdir");
+    std::fs::write(tmp.path().join("data.zip"), b"precious").expect("w source");
+    let source_volume: Arc<dyn Volume> = Arc::new(LocalPosixVolume::new("src", tmp.path().to_path_buf()));
+
+    let result = compress_start(
+        Arc::new(CollectorEventSink::new()) as Arc<dyn OperationEventSink>,
+        source_volume,
+        vec![PathBuf::from("data.zip")],
+        tmp.path().join("data.zip"),
+        unique_lane_id(),
+        ConflictResolution::Overwrite,
+        0,
+        None,
+        crate::file_system::write_operations::ArchiveSymlinkMode::Skip,
+        crate::operation_log::types::Initiator::User,
+    )
+    .await;
+
+    assert!(matches!(result, Err(WriteOperationError::SameLocation { .. })));
+    assert_eq!(
+        std::fs::read(tmp.path().join("data.zip")).expect("read source"),
+        b"precious"
+    );
+}
@@ -129,6 +129,7 @@ fn prompt_archive_conflict(
         size_difference,
         source_is_directory: false,
         destination_is_directory: is_file_to_folder,
+        is_case_only: false,
     });
 
     // Blocking recv: the planner runs on the blocking pool (like the local-FS Stop
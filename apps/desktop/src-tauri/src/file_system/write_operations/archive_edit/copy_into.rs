@@ -23,8 +23,8 @@ use super::super::state::{WriteOperationState, WriteSettledGuard};
 use super::super::transfer::volume_copy::delete_volume_path_recursive;
 use super::super::transfer::volume_strategy::pull_path_to_local;
 use super::super::types::{
-    ConflictResolution, WriteCancelledEvent, WriteCompleteEvent, WriteErrorEvent, WriteOperationError,
-    WriteOperationStartResult, WriteOperationType,
+    ArchiveSymlinkMode, ConflictResolution, WriteCancelledEvent, WriteCompleteEvent, WriteErrorEvent,
+    WriteOperationError, WriteOperationStartResult, WriteOperationType,
 };
 use super::conflicts::{ConflictMode, conditional_overwrites, find_unique_inner, resolve_effective};
 use super::engine::{MutatorHooks, PlanError, delete_move_sources, run_managed_edit, to_write_error};
@@ -85,6 +85,7 @@ pub(crate) async fn route_archive_copy_into(
         progress_interval_ms,
         is_move,
         compression_level,
+        ArchiveSymlinkMode::Skip,
         super::super::journal::ArchiveProvenance::edit(crate::operation_log::types::Initiator::User),
     )
     .await
@@ -92,7 +93,7 @@ pub(crate) async fn route_archive_copy_into(
 
 /// Like [`route_archive_copy_into`] but with an explicit [`ArchiveProvenance`], so
 /// the compress driver can supply `subkind = compress` + the net-new flag the
-/// journal can't derive (Finding 3).
+/// journal can't derive (Finding 3), and its own [`ArchiveSymlinkMode`].
 #[allow(
     clippy::too_many_arguments,
     reason = "same seam as route_archive_copy_into plus provenance"
@@ -107,6 +108,7 @@ pub(crate) async fn route_archive_copy_into_with_provenance(
     progress_interval_ms: u64,
     is_move: bool,
     compression_level: Option<i64>,
+    symlinks: ArchiveSymlinkMode,
     prov: super::super::journal::ArchiveProvenance,
 ) -> Result<WriteOperationStartResult, WriteOperationError> {
     // A LOCAL source volume's root — `Some` skips the pull (the changeset walks
@@ -143,6 +145,7 @@ pub(crate) async fn route_archive_copy_into_with_provenance(
         is_move,
         progress_interval_ms,
         compression_level,
+        symlinks,
         prov,
     )
     .await
@@ -225,12 +228,22 @@ async fn materialize_sources(
         scratch.path().to_path_buf(),
     ));
 
+    // Pull each source to its archive-relative spot, so sources from different
+    // folders that share a name don't land on each other in the scratch dir.
     let mut absolute = Vec::with_capacity(source_paths.len());
-    for src in source_paths {
-        let Some(name) = src.file_name().and_then(|n| n.to_str()) else {
+    for (src, relative) in source_paths.iter().zip(source_relative_names(source_paths)) {
+        let Some(relative) = relative else {
             continue;
         };
-        let dest_path = scratch.path().join(name);
+        let dest_path = scratch.path().join(&relative);
+        if let Some(parent) = dest_path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| {
+                PlanError::Op(WriteOperationError::IoError {
+                    path: parent.display().to_string(),
+                    message: e.to_string(),
+                })
+            })?;
+        }
         pull_one_source(source_volume, src, &dest_volume, &dest_path, state).await?;
         absolute.push(dest_path);
     }
@@ -292,15 +305,18 @@ fn build_copy_into_changeset(
     absolute_sources: &[PathBuf],
     dest_inner: &str,
     conflict: ConflictResolution,
+    symlinks: ArchiveSymlinkMode,
 ) -> Result<CopyIntoPlan, WriteOperationError> {
     let mut mode = ConflictMode::Policy(conflict);
-    build_copy_into_changeset_inner(archive_path, absolute_sources, dest_inner, &mut mode).map_err(|e| match e {
-        PlanError::Op(w) => w,
-        // A pre-resolved policy never prompts, so it can't be cancelled here.
-        PlanError::Cancelled => WriteOperationError::Cancelled {
-            message: "the archive copy was cancelled".to_string(),
+    build_copy_into_changeset_inner(archive_path, absolute_sources, dest_inner, symlinks, &mut mode).map_err(
+        |e| match e {
+            PlanError::Op(w) => w,
+            // A pre-resolved policy never prompts, so it can't be cancelled here.
+            PlanError::Cancelled => WriteOperationError::Cancelled {
+                message: "the archive copy was cancelled".to_string(),
+            },
         },
-    })
+    )
 }
 
 /// Walks the local sources and builds the changeset with INTERACTIVE per-file
@@ -311,6 +327,7 @@ fn build_copy_into_changeset_interactive(
     archive_path: &Path,
     absolute_sources: &[PathBuf],
     dest_inner: &str,
+    symlinks: ArchiveSymlinkMode,
     events: &dyn OperationEventSink,
     operation_id: &str,
     state: &Arc<WriteOperationState>,
@@ -322,16 +339,21 @@ fn build_copy_into_changeset_interactive(
         state,
         apply_to_all: &mut latch,
     };
-    build_copy_into_changeset_inner(archive_path, absolute_sources, dest_inner, &mut mode)
+    build_copy_into_changeset_inner(archive_path, absolute_sources, dest_inner, symlinks, &mut mode)
 }
 
 /// The shared copy-into walk. Resolves each FILE collision via `mode`; directory
 /// collisions merge silently (an existing archive dir is never re-added and never
 /// prompts — the app-wide dir-vs-dir rule).
+///
+/// Symlinks follow `symlinks`: skipped (and counted), stored as link entries, or
+/// followed into what they point at. The archive being written (and its
+/// in-progress temp) is never added, even when it sits inside a source tree.
 fn build_copy_into_changeset_inner(
     archive_path: &Path,
     absolute_sources: &[PathBuf],
     dest_inner: &str,
+    symlinks: ArchiveSymlinkMode,
     mode: &mut ConflictMode<'_>,
 ) -> Result<CopyIntoPlan, PlanError> {
     let source = LocalFileSource::open(archive_path).map_err(|e| {
@@ -354,12 +376,18 @@ fn build_copy_into_changeset_inner(
     let mut planned: HashSet<String> = HashSet::new();
     let mut skipped_count: usize = 0;
 
-    for src in absolute_sources {
-        let Some(name) = src.file_name().and_then(|n| n.to_str()) else {
+    let follow = symlinks == ArchiveSymlinkMode::Follow;
+    for (src, relative) in absolute_sources.iter().zip(source_relative_names(absolute_sources)) {
+        let Some(relative) = relative else {
             continue;
         };
-        let base_inner = join_inner_str(dest_inner, name);
-        let meta = std::fs::symlink_metadata(src).map_err(|e| {
+        let base_inner = join_inner_str(dest_inner, &relative);
+        let meta = if follow {
+            std::fs::metadata(src)
+        } else {
+            std::fs::symlink_metadata(src)
+        }
+        .map_err(|e| {
             PlanError::Op(WriteOperationError::ReadError {
                 path: src.display().to_string(),
                 message: e.to_string(),
@@ -367,7 +395,14 @@ fn build_copy_into_changeset_inner(
         })?;
 
         if meta.is_dir() {
-            for entry in WalkDir::new(src).into_iter().filter_map(Result::ok) {
+            for entry in WalkDir::new(src)
+                .follow_links(follow)
+                .into_iter()
+                .filter_map(Result::ok)
+            {
+                if is_archive_being_written(entry.path(), archive_path) {
+                    continue;
+                }
                 let rel = entry.path().strip_prefix(src).unwrap_or_else(|_| entry.path());
                 let inner = if rel.as_os_str().is_empty() {
                     base_inner.clone()
@@ -383,6 +418,20 @@ fn build_copy_into_changeset_inner(
                     plan_file_add(
                         inner,
                         entry.path(),
+                        AddSource::LocalPath(entry.path().to_path_buf()),
+                        archive_path,
+                        &index,
+                        mode,
+                        &mut adds,
+                        &mut deletes,
+                        &mut planned,
+                        &mut skipped_count,
+                    )?;
+                } else if let Some(source) = stored_symlink(entry.path(), symlinks) {
+                    plan_file_add(
+                        inner,
+                        entry.path(),
+                        source,
                         archive_path,
                         &index,
                         mode,
@@ -402,9 +451,26 @@ fn build_copy_into_changeset_inner(
                 }
             }
         } else if meta.is_file() {
+            if is_archive_being_written(src, archive_path) {
+                continue;
+            }
             plan_file_add(
                 base_inner,
                 src,
+                AddSource::LocalPath(src.to_path_buf()),
+                archive_path,
+                &index,
+                mode,
+                &mut adds,
+                &mut deletes,
+                &mut planned,
+                &mut skipped_count,
+            )?;
+        } else if let Some(source) = stored_symlink(src, symlinks) {
+            plan_file_add(
+                base_inner,
+                src,
+                source,
                 archive_path,
                 &index,
                 mode,
@@ -435,6 +501,64 @@ fn build_copy_into_changeset_inner(
     })
 }
 
+/// Each source's path inside the archive, relative to the sources' common
+/// ancestor. Sources from one folder (the usual selection) map to their bare
+/// names; sources from different folders (a search-results selection) keep
+/// the folder structure below their deepest shared ancestor, so same-named
+/// files from two folders don't collide. `None` for a source with no name.
+fn source_relative_names(sources: &[PathBuf]) -> Vec<Option<String>> {
+    let mut parents = sources.iter().filter_map(|src| src.parent());
+    let ancestor = parents.next().map(|first| {
+        parents.fold(first.to_path_buf(), |common, parent| {
+            common
+                .components()
+                .zip(parent.components())
+                .take_while(|(a, b)| a == b)
+                .map(|(a, _)| a)
+                .collect()
+        })
+    });
+    sources
+        .iter()
+        .map(|src| {
+            src.file_name()?;
+            let relative = ancestor
+                .as_deref()
+                .and_then(|ancestor| src.strip_prefix(ancestor).ok())
+                .filter(|relative| !relative.as_os_str().is_empty())
+                .unwrap_or_else(|| Path::new(src.file_name().unwrap_or_default()));
+            Some(relative.to_string_lossy().replace('\\', "/"))
+        })
+        .collect()
+}
+
+/// Whether `path` is the archive this edit writes, or the mutator's in-progress
+/// temp sibling of it (`foo.zip.cmdr-tmp-<uuid>`). A compress whose target lands
+/// inside a source folder must not pack itself.
+fn is_archive_being_written(path: &Path, archive_path: &Path) -> bool {
+    if path == archive_path {
+        return true;
+    }
+    let (Some(name), Some(archive_name)) = (path.file_name(), archive_path.file_name()) else {
+        return false;
+    };
+    path.parent() == archive_path.parent()
+        && name
+            .to_string_lossy()
+            .starts_with(&format!("{}.cmdr-tmp-", archive_name.to_string_lossy()))
+}
+
+/// The add source for a symlink under [`ArchiveSymlinkMode::Store`]: the link
+/// itself, as its target path. `None` for any other mode, a special file, or an
+/// unreadable link — the caller counts those as skipped.
+fn stored_symlink(path: &Path, symlinks: ArchiveSymlinkMode) -> Option<AddSource> {
+    if symlinks != ArchiveSymlinkMode::Store {
+        return None;
+    }
+    let target = std::fs::read_link(path).ok()?;
+    Some(AddSource::Symlink(target.to_string_lossy().into_owned()))
+}
+
 /// Resolves one file's conflict (via `mode`) against the index + already-planned
 /// paths and appends the resulting add (and a delete, for an overwrite). Mutates
 /// the accumulators in place.
@@ -445,6 +569,7 @@ fn build_copy_into_changeset_inner(
 fn plan_file_add(
     inner: String,
     src_path: &Path,
+    source: AddSource,
     archive_path: &Path,
     index: &ArchiveIndex,
     mode: &mut ConflictMode<'_>,
@@ -496,7 +621,7 @@ fn plan_file_add(
     planned.insert(target.clone());
     adds.push(AddEntry {
         inner_path: target,
-        source: AddSource::LocalPath(src_path.to_path_buf()),
+        source,
     });
     Ok(())
 }
@@ -539,8 +664,25 @@ async fn archive_copy_into_start(
     is_move: bool,
     progress_interval_ms: u64,
     compression_level: Option<i64>,
+    symlinks: ArchiveSymlinkMode,
     prov: super::super::journal::ArchiveProvenance,
 ) -> Result<WriteOperationStartResult, WriteOperationError> {
+    // A MOVE into an archive inside one of its own sources would delete the
+    // archive along with the source after the commit. Copies and compresses just
+    // leave the archive out of the walk (`is_archive_being_written`).
+    if is_move
+        && let Some(root) = &src_local_root
+        && let Some(src) = source_paths
+            .iter()
+            .map(|p| root.join(p))
+            .find(|src| archive_path.starts_with(src))
+    {
+        return Err(WriteOperationError::DestinationInsideSource {
+            source: src.display().to_string(),
+            destination: archive_path.display().to_string(),
+        });
+    }
+
     let operation_id = Uuid::new_v4().to_string();
     let state = Arc::new(WriteOperationState::new(Duration::from_millis(progress_interval_ms)));
 
@@ -620,12 +762,13 @@ async fn archive_copy_into_start(
                                     working,
                                     &absolute_sources,
                                     &dest_inner,
+                                    symlinks,
                                     &*events_for_blocking,
                                     &op_id_for_blocking,
                                     &state_for_blocking,
                                 )?
                             } else {
-                                build_copy_into_changeset(working, &absolute_sources, &dest_inner, conflict)
+                                build_copy_into_changeset(working, &absolute_sources, &dest_inner, conflict, symlinks)
                                     .map_err(PlanError::Op)?
                             };
                             // The user's compression level governs every newly added
@@ -664,6 +807,9 @@ async fn archive_copy_into_start(
                         files_processed: final_progress.entries_changed,
                         files_skipped: skipped_count,
                         bytes_processed: final_progress.bytes_total,
+                        errors: Vec::new(),
+                        files_verified: 0,
+                        resumed_reads: 0,
                     });
                 }
                 Err(PlanError::Cancelled) => {
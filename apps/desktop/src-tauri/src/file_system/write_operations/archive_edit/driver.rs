@@ -193,6 +193,9 @@ pub(crate) async fn archive_edit_start(
                         files_processed: final_progress.entries_changed,
                         files_skipped: skipped_count,
                         bytes_processed: final_progress.bytes_total,
+                        errors: Vec::new(),
+                        files_verified: 0,
+                        resumed_reads: 0,
                     });
                 }
                 Err(PlanError::Cancelled) => {
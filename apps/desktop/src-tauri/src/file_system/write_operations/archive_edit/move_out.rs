@@ -215,6 +215,9 @@ pub(crate) async fn route_archive_move_out(
                         files_processed: files_extracted,
                         files_skipped,
                         bytes_processed: bytes_extracted,
+                        errors: Vec::new(),
+                        files_verified: 0,
+                        resumed_reads: 0,
                     }),
                     Some(err) => events.emit_error(WriteErrorEvent::new(op_id.clone(), WriteOperationType::Move, err)),
                 }
@@ -261,6 +264,9 @@ pub(crate) async fn route_archive_move_out(
                         files_processed: files_extracted,
                         files_skipped,
                         bytes_processed: bytes_extracted,
+                        errors: Vec::new(),
+                        files_verified: 0,
+                        resumed_reads: 0,
                     }),
                     // The durable prefix moved out, but a later source failed to
                     // extract — surface the failure. A retry moves the rest (it
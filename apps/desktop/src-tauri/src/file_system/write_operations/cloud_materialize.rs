@@ -0,0 +1,85 @@
+//! Downloads online-only cloud sources (iCloud Drive, Dropbox, Google Drive placeholders)
+//! before a copy writes anything.
+//!
+//! Copying a placeholder would otherwise hydrate it mid-copy, inside the per-file copy
+//! syscall: the bar stalls on one file for as long as the provider takes, with no hint why,
+//! and a provider that's offline fails the copy halfway through. Doing it up front, file by
+//! file under a `Materializing` phase, shows the wait for what it is and fails before the
+//! destination has been touched. Off macOS nothing is ever online-only, so this is a no-op.
+
+use std::sync::Arc;
+
+use super::cancellable::run_cancellable;
+use super::scan_cache::FileInfo;
+use super::state::{WriteOperationState, update_operation_status};
+use super::types::{
+    OperationEventSink, WriteOperationError, WriteOperationPhase, WriteOperationType, WriteProgressEvent,
+};
+use crate::file_system::sync_status;
+
+/// Materializes every online-only file in `files`, emitting one `Materializing` progress
+/// event per file (files and bytes count only the placeholders). Cancellable between and
+/// during downloads; a failed download fails the operation with a `ReadError`.
+pub(super) fn materialize_online_only_sources(
+    events: &dyn OperationEventSink,
+    operation_id: &str,
+    operation_type: WriteOperationType,
+    state: &Arc<WriteOperationState>,
+    files: &[FileInfo],
+) -> Result<(), WriteOperationError> {
+    let pending: Vec<&FileInfo> = files
+        .iter()
+        .filter(|file| !file.is_symlink && sync_status::is_online_only(&file.path))
+        .collect();
+    if pending.is_empty() {
+        return Ok(());
+    }
+    let files_total = pending.len();
+    let bytes_total: u64 = pending.iter().map(|file| file.size).sum();
+    log::info!(
+        target: "cloud_materialize",
+        "Downloading {} ({bytes_total} bytes) before copying, op={operation_id}",
+        crate::pluralize::pluralize(files_total as u64, "online-only file"),
+    );
+
+    let mut bytes_done = 0u64;
+    for (files_done, file) in pending.into_iter().enumerate() {
+        let current_file = file.path.file_name().map(|n| n.to_string_lossy().to_string());
+        state.emit_progress_via_sink(
+            events,
+            WriteProgressEvent::new(
+                operation_id.to_string(),
+                operation_type,
+                WriteOperationPhase::Materializing,
+                current_file.clone(),
+                files_done,
+                files_total,
+                bytes_done,
+                bytes_total,
+            ),
+        );
+        update_operation_status(
+            operation_id,
+            WriteOperationPhase::Materializing,
+            current_file,
+            files_done,
+            files_total,
+            bytes_done,
+            bytes_total,
+        );
+        let path = file.path.clone();
+        run_cancellable(
+            move || {
+                sync_status::materialize(&path).map_err(|e| WriteOperationError::ReadError {
+                    path: path.display().to_string(),
+                    message: format!("Couldn't download it from the cloud: {e}"),
+                })
+            },
+            state,
+            "cloud_materialize",
+            operation_id,
+        )?;
+        bytes_done += file.size;
+    }
+    Ok(())
+}
@@ -9,6 +9,7 @@ use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
+use crate::file_system::case_sensitivity;
 use crate::ignore_poison::IgnorePoison;
 
 use super::durability::lookup_indexed_size;
@@ -17,6 +18,7 @@ use super::state::WriteOperationState;
 use super::types::{
     ConflictInfo, ConflictResolution, OperationEventSink, WriteConflictEvent, WriteOperationConfig, WriteOperationError,
 };
+use super::validation::validate_path_length;
 
 // ============================================================================
 // Apply-to-all state (two-bucket latches)
@@ -129,6 +131,26 @@ pub(super) fn resolve_conflict(
         config.conflict_resolution
     };
 
+    // `README.md` landing on an existing `readme.md` of a case-insensitive
+    // destination: the write would go through to the differently-named file.
+    // Only an answer to a prompt about THIS file may overwrite it, so a
+    // configured or latched Overwrite variant is turned into a prompt.
+    let is_case_only = is_case_only_clash(dest_path);
+    let resolution = match resolution {
+        ConflictResolution::Overwrite | ConflictResolution::OverwriteSmaller | ConflictResolution::OverwriteOlder
+            if is_case_only =>
+        {
+            log::info!(
+                target: "conflict_resolution",
+                "{:?} not applied to case-only clash at {}, asking",
+                resolution,
+                dest_path.display()
+            );
+            ConflictResolution::Stop
+        }
+        other => other,
+    };
+
     match resolution {
         ConflictResolution::Stop => {
             // Emit conflict event for frontend to handle. Folder sizes come
@@ -145,7 +167,7 @@ pub(super) fn resolve_conflict(
             } else {
                 None
             };
-            let event = build_conflict_event(
+            let mut event = build_conflict_event(
                 operation_id,
                 source,
                 dest_path,
@@ -154,6 +176,7 @@ pub(super) fn resolve_conflict(
                 source_size_for_dir,
                 destination_size_for_dir,
             );
+            event.is_case_only = is_case_only;
             // Store the oneshot sender BEFORE emitting the event. A responder
             // (the FE's `resolve_write_conflict`, which takes the stored sender)
             // can only answer a conflict it has observed; if the event reached it
@@ -211,6 +234,17 @@ pub(super) fn resolve_conflict(
     }
 }
 
+/// `true` when `dest_path` only "exists" because the destination folder holds
+/// an item whose name differs by case and the filesystem folds case. Reads the
+/// folder, but only on case-insensitive destinations and only for a clash.
+fn is_case_only_clash(dest_path: &Path) -> bool {
+    let (Some(parent), Some(name)) = (dest_path.parent(), dest_path.file_name()) else {
+        return false;
+    };
+    !case_sensitivity::is_case_sensitive(parent)
+        && case_sensitivity::find_case_variant(parent, &name.to_string_lossy()).is_some()
+}
+
 /// Maps the conditional variants (`OverwriteSmaller` / `OverwriteOlder`) to a
 /// concrete `Overwrite` or `Skip` for the file at hand, based on its source/dest
 /// metadata. Non-conditional variants pass through unchanged. Comparisons are
@@ -303,6 +337,16 @@ fn apply_resolution(
             // incoming bytes. The overwrite path consumes the placeholder cleanly
             // and the reservation still closes the race window.
             let unique_path = find_unique_name(dest_path);
+            // The ` (N)` suffix can push a name that fit past the length limit.
+            // Fail the file rather than let the copy hit ENAMETOOLONG, and give
+            // back the placeholder if one got reserved (Linux allows longer paths
+            // than our limit).
+            if let Err(e) = validate_path_length(&unique_path) {
+                if fs::symlink_metadata(&unique_path).is_ok_and(|m| m.is_file() && m.len() == 0) {
+                    let _ = fs::remove_file(&unique_path);
+                }
+                return Err(e);
+            }
             Ok(Some(ResolvedDestination {
                 path: unique_path,
                 needs_safe_overwrite: true,
@@ -457,6 +501,7 @@ fn build_conflict_event(
         size_difference,
         source_is_directory,
         destination_is_directory,
+        is_case_only: false,
     }
 }
 
@@ -1145,6 +1190,26 @@ mod find_unique_name_tests {
         let unique = find_unique_name(&target);
         assert_eq!(unique.file_name().unwrap().to_string_lossy(), "README (1)");
     }
+
+    #[test]
+    fn rename_fails_when_the_suffix_overflows_the_name_limit() {
+        let temp = TempDir::new().unwrap();
+        // 253 bytes: fits, but "<stem> (1).x" is 257.
+        let target = temp.path().join(format!("{}.x", "a".repeat(251)));
+        fs::write(&target, b"x").unwrap();
+
+        let result = apply_resolution(ConflictResolution::Rename, &target);
+
+        assert!(
+            matches!(result, Err(WriteOperationError::IoError { .. })),
+            "expected a length error, got {result:?}"
+        );
+        assert_eq!(
+            fs::read_dir(temp.path()).unwrap().count(),
+            1,
+            "no placeholder may be left behind"
+        );
+    }
 }
 
 #[cfg(test)]
@@ -1402,4 +1467,49 @@ mod stop_branch_store_before_emit_tests {
             "the clash is file-vs-file"
         );
     }
+
+    /// A configured Overwrite doesn't apply to `README.md` landing on an
+    /// existing `readme.md`: the clash prompts, flagged as case-only. Only
+    /// observable where the filesystem folds case (APFS / HFS+ by default).
+    #[test]
+    fn case_only_clash_prompts_despite_configured_overwrite() {
+        let dir = TempDir::new().unwrap();
+        let src = dir.path().join("src").join("README.md");
+        let dest_dir = dir.path().join("dest");
+        fs::create_dir_all(src.parent().unwrap()).unwrap();
+        fs::create_dir_all(&dest_dir).unwrap();
+        fs::write(&src, b"SRC").unwrap();
+        fs::write(dest_dir.join("readme.md"), b"DEST").unwrap();
+        if case_sensitivity::is_case_sensitive(&dest_dir) {
+            return;
+        }
+
+        let state = Arc::new(WriteOperationState::new(Duration::from_millis(0)));
+        let events = AnswerOnConflictSink {
+            inner: CollectorEventSink::new(),
+            state: Arc::clone(&state),
+            resolution: ConflictResolution::Skip,
+        };
+        let config = WriteOperationConfig {
+            conflict_resolution: ConflictResolution::Overwrite,
+            ..WriteOperationConfig::default()
+        };
+        let mut latch = ApplyToAll::default();
+
+        let result = resolve_conflict(
+            &src,
+            &dest_dir.join("README.md"),
+            &config,
+            &events,
+            "op-case-only",
+            &state,
+            &mut latch,
+        );
+
+        assert!(matches!(result, Ok(None)), "the prompt's Skip wins, got {result:?}");
+        let conflicts = events.inner.conflicts.lock_ignore_poison();
+        assert_eq!(conflicts.len(), 1);
+        assert!(conflicts[0].is_case_only);
+        assert_eq!(fs::read(dest_dir.join("readme.md")).unwrap(), b"DEST");
+    }
 }
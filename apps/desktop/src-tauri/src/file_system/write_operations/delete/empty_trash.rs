@@ -0,0 +1,301 @@
+//! Emptying the Trash: find every trash directory the user owns, report its size, and hand
+//! its contents to the local delete walker.
+//!
+//! Locations:
+//! - macOS: `~/.Trash`, plus `{mount}/.Trashes/{uid}` on each mounted local volume (where
+//!   Finder puts items trashed from a USB stick or disk image).
+//! - Linux (freedesktop.org trash spec): `$XDG_DATA_HOME/Trash`, plus `{mount}/.Trash/{uid}`
+//!   and `{mount}/.Trash-{uid}` per volume. Each has `files/` (the items) and `info/` (their
+//!   `.trashinfo` records); both are emptied, only `files/` is counted.
+//!
+//! **Never touches anything outside these directories.** A location whose directory (or any
+//! of its trash-owned parents) is a symlink is skipped, so a planted link can't redirect the
+//! delete; the items are the directories' direct children, and the walker treats symlinks
+//! inside them as links (never following them).
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+use crate::file_system::get_volume_manager;
+
+/// Trash size summary for the "Empty Trash" confirmation.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TrashInfo {
+    /// Top-level trashed items (what Finder counts), across every location.
+    pub item_count: usize,
+    /// Total size of everything in the trash, in bytes. Symlinks count as links.
+    pub total_bytes: u64,
+    /// The trash directories found, for display and diagnostics.
+    pub locations: Vec<String>,
+}
+
+/// One trash directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(in crate::file_system::write_operations) struct TrashLocation {
+    /// Holds the trashed items themselves.
+    pub items_dir: PathBuf,
+    /// Holds per-item metadata that goes with the items (Linux `info/`). Emptied, not counted.
+    pub info_dir: Option<PathBuf>,
+    /// The volume this trash lives on, when it isn't the home trash. Marked busy while emptying.
+    pub volume_id: Option<String>,
+}
+
+/// Whether `path` is a directory and not a symlink to one, and neither is `trusted_root`'s
+/// descendant chain up to it. `trusted_root` is the mount point or home dir the trash hangs off.
+fn is_real_dir_under(path: &Path, trusted_root: &Path) -> bool {
+    let Ok(rest) = path.strip_prefix(trusted_root) else {
+        return false;
+    };
+    let mut current = trusted_root.to_path_buf();
+    for component in rest.components() {
+        current.push(component);
+        match fs::symlink_metadata(&current) {
+            Ok(meta) if meta.is_dir() && !meta.file_type().is_symlink() => {}
+            _ => return false,
+        }
+    }
+    true
+}
+
+/// Every candidate trash location for `uid`, given the home trash base and the mounted
+/// volumes' roots. Existence isn't checked here; see [`trash_locations`].
+fn candidate_locations(
+    home_base: Option<PathBuf>,
+    volume_roots: &[(String, PathBuf)],
+    uid: u32,
+) -> Vec<(PathBuf, TrashLocation)> {
+    let mut candidates = Vec::new();
+    if cfg!(target_os = "macos") {
+        if let Some(home) = home_base {
+            let items_dir = home.join(".Trash");
+            candidates.push((
+                home,
+                TrashLocation {
+                    items_dir,
+                    info_dir: None,
+                    volume_id: None,
+                },
+            ));
+        }
+        for (id, root) in volume_roots {
+            candidates.push((
+                root.clone(),
+                TrashLocation {
+                    items_dir: root.join(".Trashes").join(uid.to_string()),
+                    info_dir: None,
+                    volume_id: Some(id.clone()),
+                },
+            ));
+        }
+    } else {
+        let spec_location = |base: PathBuf, volume_id: Option<String>| TrashLocation {
+            items_dir: base.join("files"),
+            info_dir: Some(base.join("info")),
+            volume_id,
+        };
+        if let Some(data) = home_base {
+            candidates.push((data.clone(), spec_location(data.join("Trash"), None)));
+        }
+        for (id, root) in volume_roots {
+            let shared = root.join(".Trash").join(uid.to_string());
+            candidates.push((root.clone(), spec_location(shared, Some(id.clone()))));
+            let own = root.join(format!(".Trash-{uid}"));
+            candidates.push((root.clone(), spec_location(own, Some(id.clone()))));
+        }
+    }
+    candidates
+}
+
+/// The trash locations that exist right now and pass the symlink check.
+pub(in crate::file_system::write_operations) fn trash_locations() -> Vec<TrashLocation> {
+    // SAFETY: `getuid` reads the process's real UID; always safe, no args or pointers.
+    let uid = unsafe { libc::getuid() };
+    let home_base = if cfg!(target_os = "macos") {
+        dirs::home_dir()
+    } else {
+        dirs::data_dir()
+    };
+    // Local volumes other than the boot volume (whose trash is the home trash).
+    let volume_roots: Vec<(String, PathBuf)> = get_volume_manager()
+        .list_volumes_with_handles()
+        .into_iter()
+        .filter(|(_, volume)| volume.supports_local_fs_access())
+        .filter_map(|(id, volume)| volume.local_path().map(|root| (id, root)))
+        .filter(|(_, root)| root != Path::new("/"))
+        .collect();
+    existing_locations(candidate_locations(home_base, &volume_roots, uid))
+}
+
+fn existing_locations(candidates: Vec<(PathBuf, TrashLocation)>) -> Vec<TrashLocation> {
+    let mut locations: Vec<TrashLocation> = candidates
+        .into_iter()
+        .filter(|(root, location)| is_real_dir_under(&location.items_dir, root))
+        .map(|(root, mut location)| {
+            location.info_dir = location.info_dir.filter(|info| is_real_dir_under(info, &root));
+            location
+        })
+        .collect();
+    locations.dedup_by(|a, b| a.items_dir == b.items_dir);
+    locations
+}
+
+fn children(dir: &Path) -> Vec<PathBuf> {
+    match fs::read_dir(dir) {
+        Ok(entries) => entries.flatten().map(|e| e.path()).collect(),
+        Err(e) => {
+            log::warn!(target: "empty_trash", "Couldn't read trash dir {}: {e}", dir.display());
+            Vec::new()
+        }
+    }
+}
+
+/// The top-level paths to delete: every item in every location, plus their info records.
+pub(in crate::file_system::write_operations) fn trash_contents(locations: &[TrashLocation]) -> Vec<PathBuf> {
+    locations
+        .iter()
+        .flat_map(|location| {
+            let mut paths = children(&location.items_dir);
+            if let Some(info_dir) = &location.info_dir {
+                paths.extend(children(info_dir));
+            }
+            paths
+        })
+        .collect()
+}
+
+/// Size of `path` in bytes, recursing into directories without following symlinks.
+fn tree_bytes(path: &Path) -> u64 {
+    walkdir::WalkDir::new(path)
+        .follow_links(false)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry.metadata().ok())
+        .filter(|meta| !meta.is_dir())
+        .map(|meta| meta.len())
+        .sum()
+}
+
+fn info_for(locations: &[TrashLocation]) -> TrashInfo {
+    let mut info = TrashInfo {
+        locations: locations.iter().map(|l| l.items_dir.display().to_string()).collect(),
+        ..TrashInfo::default()
+    };
+    for location in locations {
+        for item in children(&location.items_dir) {
+            // Finder's own folder metadata isn't a trashed item (it's still deleted, and sized).
+            if item.file_name().is_none_or(|n| n != ".DS_Store") {
+                info.item_count += 1;
+            }
+            info.total_bytes += tree_bytes(&item);
+        }
+    }
+    info
+}
+
+/// Counts and sizes everything in the trash. Walks every trashed tree, so call it off the
+/// main thread.
+pub fn get_trash_info() -> TrashInfo {
+    info_for(&trash_locations())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn location(items_dir: PathBuf) -> TrashLocation {
+        TrashLocation {
+            items_dir,
+            info_dir: None,
+            volume_id: None,
+        }
+    }
+
+    #[test]
+    fn info_counts_top_level_items_and_sums_bytes_without_following_links() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let trash = tmp.path().join("trash");
+        let outside = tmp.path().join("outside");
+        fs::create_dir_all(trash.join("folder/sub")).expect("mkdir");
+        fs::create_dir_all(&outside).expect("mkdir outside");
+        fs::write(trash.join("a.txt"), b"12345").expect("write a");
+        fs::write(trash.join("folder/sub/b.bin"), vec![0u8; 100]).expect("write b");
+        fs::write(outside.join("big.bin"), vec![0u8; 10_000]).expect("write big");
+        std::os::unix::fs::symlink(&outside, trash.join("link")).expect("symlink");
+
+        let info = info_for(&[location(trash.clone())]);
+
+        assert_eq!(info.item_count, 3);
+        assert!(info.total_bytes >= 105, "got {}", info.total_bytes);
+        assert!(info.total_bytes < 10_000, "the link's target must not be counted");
+        assert_eq!(info.locations, vec![trash.display().to_string()]);
+    }
+
+    #[test]
+    fn symlinked_trash_dirs_are_skipped() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let root = tmp.path().join("volume");
+        let elsewhere = tmp.path().join("elsewhere");
+        fs::create_dir_all(&root).expect("mkdir root");
+        fs::create_dir_all(elsewhere.join("501")).expect("mkdir elsewhere");
+        // A planted `.Trashes` link pointing at a folder that must never be emptied.
+        std::os::unix::fs::symlink(&elsewhere, root.join(".Trashes")).expect("symlink");
+        let candidate = (root.clone(), location(root.join(".Trashes/501")));
+        assert!(existing_locations(vec![candidate]).is_empty());
+
+        fs::create_dir_all(root.join("real/501")).expect("mkdir real");
+        let candidate = (root.clone(), location(root.join("real/501")));
+        assert_eq!(existing_locations(vec![candidate]).len(), 1);
+    }
+
+    #[test]
+    fn contents_include_items_and_info_records() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let base = tmp.path().join("Trash");
+        fs::create_dir_all(base.join("files")).expect("mkdir files");
+        fs::create_dir_all(base.join("info")).expect("mkdir info");
+        fs::write(base.join("files/doc.txt"), b"x").expect("write doc");
+        fs::write(base.join("info/doc.txt.trashinfo"), b"[Trash Info]").expect("write info");
+        let loc = TrashLocation {
+            items_dir: base.join("files"),
+            info_dir: Some(base.join("info")),
+            volume_id: None,
+        };
+
+        let mut contents = trash_contents(std::slice::from_ref(&loc));
+        contents.sort();
+
+        assert_eq!(
+            contents,
+            vec![base.join("files/doc.txt"), base.join("info/doc.txt.trashinfo")]
+        );
+        assert_eq!(info_for(&[loc]).item_count, 1, "info records aren't items");
+    }
+
+    #[test]
+    fn volume_candidates_use_the_platform_layout() {
+        let roots = vec![("usb".to_string(), PathBuf::from("/Volumes/USB"))];
+        let candidates = candidate_locations(Some(PathBuf::from("/home/x")), &roots, 501);
+        let dirs: Vec<PathBuf> = candidates.into_iter().map(|(_, l)| l.items_dir).collect();
+        if cfg!(target_os = "macos") {
+            assert_eq!(
+                dirs,
+                vec![
+                    PathBuf::from("/home/x/.Trash"),
+                    PathBuf::from("/Volumes/USB/.Trashes/501")
+                ]
+            );
+        } else {
+            assert_eq!(
+                dirs,
+                vec![
+                    PathBuf::from("/home/x/Trash/files"),
+                    PathBuf::from("/Volumes/USB/.Trash/501/files"),
+                    PathBuf::from("/Volumes/USB/.Trash-501/files"),
+                ]
+            );
+        }
+    }
+}
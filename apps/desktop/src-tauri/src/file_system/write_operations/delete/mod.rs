@@ -3,14 +3,19 @@
 //! The local-FS walker uses `walkdir` + `fs::remove_file`. The volume-aware
 //! variant uses the `Volume` trait so MTP / SMB / future remote backends
 //! work the same way. Trash routes to the OS-native trash (macOS
-//! `trashItemAtURL`, Linux `trash` crate).
+//! `trashItemAtURL`, Linux `trash` crate); emptying the Trash locates the trash
+//! directories and feeds their contents to the local walker.
 //!
 //! See `CLAUDE.md` in this directory for delete walker semantics, the
 //! oracle-aware fast path, trash, and the volume-delete preview-reuse path.
 
+mod empty_trash;
 pub(crate) mod trash;
 mod walker;
 
+pub use empty_trash::{TrashInfo, get_trash_info};
+pub(in crate::file_system::write_operations) use empty_trash::{trash_contents, trash_locations};
+
 pub(in crate::file_system::write_operations) use walker::{
     delete_files_with_progress_inner, delete_volume_files_with_progress_inner,
 };
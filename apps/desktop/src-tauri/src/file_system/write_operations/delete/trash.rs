@@ -370,6 +370,9 @@ pub(in crate::file_system::write_operations) fn trash_files_with_progress(
         files_processed: items_done,
         files_skipped: 0,
         bytes_processed: bytes_done,
+        errors: Vec::new(),
+        files_verified: 0,
+        resumed_reads: 0,
     });
 
     // Log partial failures
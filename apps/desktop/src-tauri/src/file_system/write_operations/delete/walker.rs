@@ -54,6 +54,7 @@ pub(in crate::file_system::write_operations) fn delete_files_with_progress_inner
                 WriteOperationType::Delete,
                 config.sort_column,
                 config.sort_order,
+                config.include_hidden,
             )?
         }
     } else {
@@ -65,6 +66,7 @@ pub(in crate::file_system::write_operations) fn delete_files_with_progress_inner
             WriteOperationType::Delete,
             config.sort_column,
             config.sort_order,
+            config.include_hidden,
         )?
     };
 
@@ -245,6 +247,9 @@ pub(in crate::file_system::write_operations) fn delete_files_with_progress_inner
         files_processed: files_done,
         files_skipped: 0,
         bytes_processed: bytes_done,
+        errors: Vec::new(),
+        files_verified: 0,
+        resumed_reads: 0,
     });
 
     Ok(())
@@ -955,6 +960,9 @@ pub(in crate::file_system::write_operations) async fn delete_volume_files_with_p
         files_processed: files_done,
         files_skipped: 0,
         bytes_processed: bytes_done,
+        errors: Vec::new(),
+        files_verified: 0,
+        resumed_reads: 0,
     });
 
     Ok(())
@@ -86,10 +86,43 @@ impl WriteOperationError {
     pub(crate) fn is_expected_recoverable(&self) -> bool {
         matches!(self, WriteOperationError::ArchiveNeedsPassword { .. })
     }
+
+    /// Stable machine-readable code for this error, sent as `WriteErrorEvent::code`
+    /// so the FE can localize and branch without matching on messages.
+    ///
+    /// These strings are a wire contract: never rename one. A new variant gets a
+    /// new code; the exhaustive match makes forgetting one a compile error.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::SourceNotFound { .. } => "SOURCE_NOT_FOUND",
+            Self::DestinationExists { .. } => "DEST_EXISTS",
+            Self::PermissionDenied { .. } => "PERMISSION_DENIED",
+            Self::InsufficientSpace { .. } => "DISK_FULL",
+            Self::SameLocation { .. } => "SAME_LOCATION",
+            Self::DestinationInsideSource { .. } => "DEST_INSIDE_SOURCE",
+            Self::SymlinkLoop { .. } => "SYMLINK_LOOP",
+            Self::Cancelled { .. } => "CANCELLED",
+            Self::DeviceDisconnected { .. } => "DEVICE_DISCONNECTED",
+            Self::ReadOnlyDevice { .. } => "DEST_NOT_WRITABLE",
+            Self::FileLocked { .. } => "FILE_LOCKED",
+            Self::TrashNotSupported { .. } => "TRASH_NOT_SUPPORTED",
+            Self::ConnectionInterrupted { .. } => "CONNECTION_INTERRUPTED",
+            Self::ReadError { .. } => "READ_FAILED",
+            Self::WriteError { .. } => "WRITE_FAILED",
+            Self::NameTooLong { .. } => "NAME_TOO_LONG",
+            Self::InvalidName { .. } => "INVALID_NAME",
+            Self::DeletePending { .. } => "DELETE_PENDING",
+            Self::FilesTooLargeForFilesystem { .. } => "FILE_TOO_LARGE_FOR_FILESYSTEM",
+            Self::ArchiveNeedsPassword { .. } => "ARCHIVE_NEEDS_PASSWORD",
+            Self::VerificationFailed { .. } => "VERIFICATION_FAILED",
+            Self::IoError { .. } => "IO_ERROR",
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use super::super::types::{WriteErrorEvent, WriteOperationType};
     use super::*;
 
     #[test]
@@ -107,6 +140,53 @@ mod tests {
         }
     }
 
+    #[test]
+    fn code_is_stable_per_variant() {
+        let cases = [
+            (
+                WriteOperationError::InsufficientSpace {
+                    required: 10,
+                    available: 1,
+                    volume_name: None,
+                },
+                "DISK_FULL",
+            ),
+            (
+                WriteOperationError::DestinationInsideSource {
+                    source: "/a".to_string(),
+                    destination: "/a/b".to_string(),
+                },
+                "DEST_INSIDE_SOURCE",
+            ),
+            (
+                WriteOperationError::ReadOnlyDevice {
+                    path: "/x".to_string(),
+                    device_name: None,
+                },
+                "DEST_NOT_WRITABLE",
+            ),
+            (
+                WriteOperationError::SourceNotFound { path: "/x".to_string() },
+                "SOURCE_NOT_FOUND",
+            ),
+        ];
+        for (err, code) in cases {
+            assert_eq!(err.code(), code, "{err:?}");
+        }
+    }
+
+    #[test]
+    fn write_error_event_serializes_the_code_next_to_the_typed_error() {
+        let event = WriteErrorEvent::new(
+            "op-1".to_string(),
+            WriteOperationType::Copy,
+            WriteOperationError::FileLocked { path: "/x".to_string() },
+        );
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["code"], "FILE_LOCKED");
+        assert_eq!(json["error"]["type"], "file_locked");
+    }
+
     #[test]
     fn genuine_failures_are_not_expected_recoverable() {
         let failures = [
@@ -120,6 +120,19 @@ impl EtaEstimator {
         }
     }
 
+    /// Shift the current phase's clock forward by `paused_for`, so the next
+    /// `update` measures Δt from where the op stopped rather than across the
+    /// pause. Without this, the first sample after a long pause averages the
+    /// progress over the idle gap and the ETA jumps to hours.
+    ///
+    /// No-op if no phase is active.
+    pub fn skip_pause(&mut self, paused_for: Duration) {
+        if let Some(state) = self.state.as_mut() {
+            state.started_at += paused_for;
+            state.last_t += paused_for;
+        }
+    }
+
     /// Update the estimator with the latest counters and return the current stats.
     ///
     /// `now` is injected (not read from `Instant::now()` internally) so tests can
@@ -817,4 +830,41 @@ mod tests {
             "files_per_second after first post-seed sample = {fps}, expected ~100",
         );
     }
+
+    #[test]
+    fn paused_time_is_left_out_of_the_rate() {
+        let start = Instant::now();
+        let mut est = EtaEstimator::new();
+        for i in 0..=5 {
+            est.update(
+                at(start, i * 1000),
+                WriteOperationPhase::Copying,
+                i * 100_000_000,
+                10_000_000_000,
+                (i * 5) as usize,
+                500,
+            );
+        }
+
+        // Paused for a minute, then one more second at 100 MB/s.
+        est.skip_pause(Duration::from_secs(60));
+        let stats = est.update(
+            at(start, 66_000),
+            WriteOperationPhase::Copying,
+            600_000_000,
+            10_000_000_000,
+            30,
+            500,
+        );
+        let bps = stats.bytes_per_second;
+        assert!(
+            (99_000_000..=101_000_000).contains(&bps),
+            "bytes_per_second after a skipped pause = {bps}, expected ~100 MB/s",
+        );
+        assert!(
+            stats.eta_seconds.is_some_and(|eta| eta < 120),
+            "ETA = {:?}",
+            stats.eta_seconds
+        );
+    }
 }
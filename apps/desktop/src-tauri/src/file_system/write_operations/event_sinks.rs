@@ -14,7 +14,7 @@ use crate::ignore_poison::IgnorePoison;
 use super::analytics::emit_completion_analytics;
 use super::types::{
     ConflictInfo, DryRunResult, ScanProgressEvent, WriteCancelledEvent, WriteCompleteEvent, WriteConflictEvent,
-    WriteErrorEvent, WriteOperationError, WriteOperationType, WriteProgressEvent, WriteSettledEvent,
+    WriteErrorEvent, WriteOperationError, WriteOperationType, WriteProgressEvent, WriteRenamedEvent, WriteSettledEvent,
     WriteSourceItemDoneEvent,
 };
 use crate::indexing::read::expected_totals;
@@ -84,11 +84,12 @@ impl WriteProgressEvent {
 impl WriteErrorEvent {
     /// Construct a `WriteErrorEvent` from the typed `error`. The FE renders all
     /// user-facing copy and the category/retry classification from the typed
-    /// variant; no rendered prose crosses IPC.
+    /// variant; no rendered prose crosses IPC. `code` is derived from it.
     pub fn new(operation_id: String, operation_type: WriteOperationType, error: WriteOperationError) -> Self {
         Self {
             operation_id,
             operation_type,
+            code: error.code().to_string(),
             error,
         }
     }
@@ -123,6 +124,10 @@ pub trait OperationEventSink: Send + Sync {
     /// these to delete exactly the fully-extracted sources from the archive, so a
     /// partial move converges on retry. Default no-op for every other sink.
     fn note_source_landed_clean(&self, _source: &std::path::Path) {}
+
+    /// A destination name was rewritten for the target filesystem (see
+    /// `name_sanitizer`). Only local copy sanitizes, so default no-op.
+    fn emit_renamed(&self, _event: WriteRenamedEvent) {}
 }
 
 /// Tauri-backed event sink: calls `app.emit()` for each event.
@@ -136,6 +141,17 @@ impl TauriEventSink {
     }
 }
 
+/// The `DeviceDisconnected` error to report instead of `event` when the op was stopped by
+/// `cancel_operations_on_volume` (see `state::take_unmount_cancellation`).
+fn unmount_error_for(event: &WriteCancelledEvent) -> Option<WriteErrorEvent> {
+    let volume_path = super::state::take_unmount_cancellation(&event.operation_id)?;
+    Some(WriteErrorEvent::new(
+        event.operation_id.clone(),
+        event.operation_type,
+        WriteOperationError::DeviceDisconnected { path: volume_path },
+    ))
+}
+
 impl OperationEventSink for TauriEventSink {
     fn emit_progress(&self, event: WriteProgressEvent) {
         let _ = event.emit(&self.app);
@@ -156,6 +172,12 @@ impl OperationEventSink for TauriEventSink {
         let _ = event.emit(&self.app);
     }
     fn emit_cancelled(&self, event: WriteCancelledEvent) {
+        // An op the volume watcher stopped because its drive vanished ends as an error naming
+        // the volume, not as a cancel the user never asked for.
+        if let Some(error_event) = unmount_error_for(&event) {
+            self.emit_error(error_event);
+            return;
+        }
         crate::mcp::terminal_ops::record(
             &event.operation_id,
             event.operation_type,
@@ -189,6 +211,26 @@ impl OperationEventSink for TauriEventSink {
     fn emit_settled(&self, event: WriteSettledEvent) {
         let _ = event.emit(&self.app);
     }
+    fn emit_renamed(&self, event: WriteRenamedEvent) {
+        let _ = event.emit(&self.app);
+    }
+}
+
+/// Event sink that drops everything. For headless runs that only care about the outcome, like
+/// the copy benches (see [`copy_files_blocking`](super::copy_files_blocking)).
+pub(crate) struct DiscardEventSink;
+
+impl OperationEventSink for DiscardEventSink {
+    fn emit_progress(&self, _event: WriteProgressEvent) {}
+    fn emit_complete(&self, _event: WriteCompleteEvent) {}
+    fn emit_cancelled(&self, _event: WriteCancelledEvent) {}
+    fn emit_error(&self, _event: WriteErrorEvent) {}
+    fn emit_conflict(&self, _event: WriteConflictEvent) {}
+    fn emit_source_item_done(&self, _event: WriteSourceItemDoneEvent) {}
+    fn emit_scan_progress(&self, _event: ScanProgressEvent) {}
+    fn emit_scan_conflict(&self, _conflict: ConflictInfo) {}
+    fn emit_dry_run_complete(&self, _result: DryRunResult) {}
+    fn emit_settled(&self, _event: WriteSettledEvent) {}
 }
 
 /// Test event sink: stores events for inspection.
@@ -207,6 +249,7 @@ pub(crate) struct CollectorEventSink {
     pub scan_conflicts: std::sync::Mutex<Vec<ConflictInfo>>,
     pub dry_run: std::sync::Mutex<Vec<DryRunResult>>,
     pub settled: std::sync::Mutex<Vec<WriteSettledEvent>>,
+    pub renamed: std::sync::Mutex<Vec<WriteRenamedEvent>>,
 }
 
 #[cfg(test)]
@@ -222,6 +265,7 @@ impl CollectorEventSink {
             scan_conflicts: std::sync::Mutex::new(Vec::new()),
             dry_run: std::sync::Mutex::new(Vec::new()),
             settled: std::sync::Mutex::new(Vec::new()),
+            renamed: std::sync::Mutex::new(Vec::new()),
         }
     }
 }
@@ -256,4 +300,7 @@ impl OperationEventSink for CollectorEventSink {
     fn emit_settled(&self, event: WriteSettledEvent) {
         self.settled.lock_ignore_poison().push(event);
     }
+    fn emit_renamed(&self, event: WriteRenamedEvent) {
+        self.renamed.lock_ignore_poison().push(event);
+    }
 }
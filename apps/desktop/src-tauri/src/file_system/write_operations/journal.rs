@@ -9,10 +9,13 @@
 //! `operation_log/DETAILS.md` § Capture). Every function no-ops when no journal
 //! is installed, and never fails the operation.
 
-use std::path::Path;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, Mutex};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 use crate::file_system::volume::DEFAULT_VOLUME_ID;
+use crate::ignore_poison::IgnorePoison;
 use crate::operation_log::capture::FinalizeInputs;
 use crate::operation_log::types::RowRole;
 use crate::operation_log::types::{ArchiveSubkind, EntryType, ExecutionStatus, Initiator, ItemOutcome, OpKind};
@@ -34,6 +37,7 @@ pub(super) fn op_kind_of(t: WriteOperationType) -> OpKind {
         WriteOperationType::CreateFolder => OpKind::CreateFolder,
         WriteOperationType::CreateFile => OpKind::CreateFile,
         WriteOperationType::ArchiveEdit => OpKind::ArchiveEdit,
+        WriteOperationType::SetPermissions => OpKind::SetPermissions,
     }
 }
 
@@ -161,6 +165,22 @@ pub(super) fn record_local_leaf(
     );
 }
 
+/// Record a local file that failed under `continue_on_error`: a `failed` row
+/// with no dest, so the operation's history can list what didn't make it.
+/// Rollback only reverses `done` rows, so this row is never acted on.
+pub(super) fn record_local_failure(op_id: &str, source: &Path, size: u64) {
+    record_local_leaf(
+        op_id,
+        EntryType::File,
+        source,
+        None,
+        Some(size as i64),
+        None,
+        false,
+        ItemOutcome::Failed,
+    );
+}
+
 /// Record one `rollback_unit` row on a real (SMB / MTP / local) volume: the
 /// volume-aware sibling of [`record_local_leaf`]. `source` lives on
 /// `source_volume_id`; `dest` bundles the destination volume id + path (they may
@@ -276,6 +296,45 @@ pub(super) fn record_search_leaf(
     );
 }
 
+/// Rows held back per `(op_id, top-level source)` while a `continue_on_error`
+/// atomic source is in flight. A source that fails is rolled back as a whole, so
+/// its rows are dropped instead of landing as `done` for files that no longer exist.
+static HELD_ROWS: LazyLock<Mutex<HashMap<(String, PathBuf), Vec<JournalItem>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Start holding the rows recorded under `source_root` for `op_id` until
+/// [`release_held_rows`] or [`discard_held_rows`].
+pub(super) fn hold_rows_under(op_id: &str, source_root: &Path) {
+    HELD_ROWS
+        .lock_ignore_poison()
+        .insert((op_id.to_string(), source_root.to_path_buf()), Vec::new());
+}
+
+/// Journal the rows held under `source_root` (the source landed whole), or every
+/// held row of `op_id` when `source_root` is `None` (the loop ended; a cancel keeps
+/// what it reached).
+pub(super) fn release_held_rows(op_id: &str, source_root: Option<&Path>) {
+    let released: Vec<JournalItem> = {
+        let mut held = HELD_ROWS.lock_ignore_poison();
+        let keys: Vec<(String, PathBuf)> = held
+            .keys()
+            .filter(|(op, root)| op == op_id && source_root.is_none_or(|r| r == root))
+            .cloned()
+            .collect();
+        keys.iter().filter_map(|k| held.remove(k)).flatten().collect()
+    };
+    if !released.is_empty() {
+        journal_record_items(op_id, released);
+    }
+}
+
+/// Drop the rows held under `source_root`: the source failed and was rolled back.
+pub(super) fn discard_held_rows(op_id: &str, source_root: &Path) {
+    HELD_ROWS
+        .lock_ignore_poison()
+        .remove(&(op_id.to_string(), source_root.to_path_buf()));
+}
+
 /// The shared record core: split the source (and optional dest) into interned dir
 /// prefix + leaf name, carry the explicit volume ids, and hand one [`JournalItem`]
 /// to the writer. `seq` is assigned by the journal in recording order.
@@ -300,24 +359,33 @@ fn record_row(
         }
         None => (None, None, None),
     };
-    journal_record_items(
-        op_id,
-        vec![JournalItem {
-            seq: 0,
-            entry_type,
-            row_role,
-            source_volume_id: source_volume_id.to_string(),
-            source_dir,
-            source_name,
-            dest_volume_id,
-            dest_dir,
-            dest_name,
-            size,
-            mtime,
-            outcome,
-            overwrote,
-        }],
-    );
+    let item = JournalItem {
+        seq: 0,
+        entry_type,
+        row_role,
+        source_volume_id: source_volume_id.to_string(),
+        source_dir,
+        source_name,
+        dest_volume_id,
+        dest_dir,
+        dest_name,
+        size,
+        mtime,
+        outcome,
+        overwrote,
+    };
+    {
+        let mut held = HELD_ROWS.lock_ignore_poison();
+        if let Some(rows) = held
+            .iter_mut()
+            .find(|((op, root), _)| op == op_id && source.starts_with(root))
+            .map(|(_, rows)| rows)
+        {
+            rows.push(item);
+            return;
+        }
+    }
+    journal_record_items(op_id, vec![item]);
 }
 
 /// Record the directories a copy created as first-class `dir` rows (D2, Finding
@@ -18,7 +18,7 @@ use super::{copy_files_with_progress_inner, delete_files_with_progress_inner};
 use crate::operation_log::TestJournalGuard;
 use crate::operation_log::capture::WriterJournal;
 use crate::operation_log::store::{open_read_connection, operation_log_db_path, read_operation, read_operation_items};
-use crate::operation_log::types::{EntryType, ExecutionStatus, Initiator, OpKind, RollbackState, RowRole};
+use crate::operation_log::types::{EntryType, ExecutionStatus, Initiator, ItemOutcome, OpKind, RollbackState, RowRole};
 use crate::operation_log::writer::OperationLogWriter;
 
 /// Install a fresh temp-DB journal as the process-global one and hand back the
@@ -178,6 +178,41 @@ fn overwriting_copy_finalizes_not_rollbackable() {
     );
 }
 
+/// `continue_on_error` + `atomic_sources`: rows under a held source only land once
+/// it completes. A source rolled back after a failure leaves its `failed` row and no
+/// `done` rows for files that no longer exist.
+#[test]
+fn held_atomic_source_rows_land_only_when_the_source_completes() {
+    let (_journal, _jdir, jdb) = install_journal();
+    let op_id = "op-copy-atomic-held";
+    let kept = std::path::Path::new("/src/kept");
+    let dropped = std::path::Path::new("/src/dropped");
+    journal::open_local_op(op_id, OpKind::Copy, Initiator::User, 0, Some("root"));
+    journal::hold_rows_under(op_id, kept);
+    journal::hold_rows_under(op_id, dropped);
+    for (root, name) in [(kept, "a.txt"), (dropped, "b.txt")] {
+        journal::record_local_leaf(
+            op_id,
+            EntryType::File,
+            &root.join(name),
+            Some(&std::path::Path::new("/dst").join(name)),
+            Some(1),
+            None,
+            false,
+            ItemOutcome::Done,
+        );
+    }
+    journal::discard_held_rows(op_id, dropped);
+    journal::record_local_failure(op_id, &dropped.join("c.txt"), 1);
+    journal::release_held_rows(op_id, Some(kept));
+    journal::finalize_op(op_id, OpKind::Copy, ExecutionStatus::Done);
+
+    let conn = open_read_connection(&jdb).expect("read conn");
+    let items = read_operation_items(&conn, op_id, 1000).expect("items");
+    let rows: Vec<(&str, ItemOutcome)> = items.iter().map(|i| (i.source_name.as_str(), i.outcome)).collect();
+    assert_eq!(rows, vec![("c.txt", ItemOutcome::Failed), ("a.txt", ItemOutcome::Done)]);
+}
+
 #[test]
 fn same_fs_move_journals_the_top_level_item_as_rollback_unit() {
     let (_journal, _jdir, jdb) = install_journal();
@@ -0,0 +1,223 @@
+//! Symlink and hardlink creation, and symlink resolution, on the local filesystem.
+//!
+//! Plain syscalls, not managed ops: a link is one metadata write on a local-FS
+//! path, so the command layer runs these on the blocking pool under its usual
+//! IPC timeout. On success the new entry is pushed into any open listing of its
+//! folder (`notify_directory_changed`) and returned, so the pane can refresh and
+//! put the cursor on it without waiting for the watcher.
+
+use std::fs;
+use std::os::unix::fs::MetadataExt;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+use crate::file_system::get_volume_manager;
+use crate::file_system::listing::FileEntry;
+use crate::file_system::listing::caching::{DirectoryChange, notify_directory_changed};
+use crate::file_system::listing::get_single_entry;
+use crate::file_system::volume::DEFAULT_VOLUME_ID;
+use crate::file_system::volume::backends::archive;
+
+/// Where a symlink points, for showing dangling links distinctly.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SymlinkResolution {
+    /// The link's stored target, verbatim (may be relative to the link's folder).
+    pub target: String,
+    /// The fully resolved absolute target. `None` when the link is broken.
+    pub resolved_path: Option<String>,
+    /// The target (after following every link in the chain) doesn't exist, or the chain loops.
+    pub is_broken: bool,
+    /// The resolved target is a directory.
+    pub is_directory: bool,
+}
+
+/// The link's folder and leaf name, validated: the folder must exist and not be inside an archive.
+fn split_link_path(link_path: &Path) -> Result<(PathBuf, String), String> {
+    let name = link_path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .filter(|n| !n.is_empty())
+        .ok_or_else(|| "Link name cannot be empty".to_string())?;
+    let parent = link_path
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .ok_or_else(|| format!("'{}' has no parent folder", link_path.display()))?;
+    if archive::path_crosses_archive_boundary(parent) {
+        return Err("Links can't be created inside an archive".to_string());
+    }
+    if !parent.is_dir() {
+        return Err(format!("Folder '{}' doesn't exist", parent.display()));
+    }
+    Ok((parent.to_path_buf(), name))
+}
+
+/// Pushes the new link into open listings of `parent` and returns its entry.
+fn report_created(link_path: &Path, parent: &Path, replaced: bool) -> Result<FileEntry, String> {
+    let entry = get_single_entry(link_path).map_err(|e| format!("Couldn't read the new link: {e}"))?;
+    let volume_id = get_volume_manager()
+        .containing(parent)
+        .map(|(id, _)| id)
+        .unwrap_or_else(|| DEFAULT_VOLUME_ID.to_string());
+    let change = if replaced {
+        DirectoryChange::Modified(entry.clone())
+    } else {
+        DirectoryChange::Added(entry.clone())
+    };
+    notify_directory_changed(&volume_id, parent, change);
+    Ok(entry)
+}
+
+/// Creates a symlink at `link_path` pointing at `target` (stored verbatim, so a
+/// relative target stays relative). The target needn't exist. An existing entry
+/// at `link_path` is an error unless `force`, which replaces a file or link
+/// atomically (never a folder).
+pub fn create_symlink(target: &Path, link_path: &Path, force: bool) -> Result<FileEntry, String> {
+    let (parent, name) = split_link_path(link_path)?;
+    if target.as_os_str().is_empty() {
+        return Err("Link target cannot be empty".to_string());
+    }
+
+    let existing = fs::symlink_metadata(link_path).ok();
+    match &existing {
+        Some(_) if !force => return Err(format!("'{name}' already exists")),
+        Some(meta) if meta.is_dir() => return Err(format!("'{name}' is a folder and can't be replaced by a link")),
+        _ => {}
+    }
+
+    crate::downloads::note_pending_write_for_cmdr(link_path);
+    if existing.is_some() {
+        // Create beside it, then rename over it: the old entry is never missing.
+        let temp = parent.join(format!(".{name}.cmdr-link-{}", uuid::Uuid::new_v4()));
+        std::os::unix::fs::symlink(target, &temp).map_err(|e| format!("Couldn't create link: {e}"))?;
+        if let Err(e) = fs::rename(&temp, link_path) {
+            let _ = fs::remove_file(&temp);
+            return Err(format!("Couldn't replace '{name}': {e}"));
+        }
+    } else {
+        std::os::unix::fs::symlink(target, link_path).map_err(|e| match e.kind() {
+            std::io::ErrorKind::AlreadyExists => format!("'{name}' already exists"),
+            std::io::ErrorKind::PermissionDenied => format!("Permission denied: cannot create '{name}'"),
+            _ => format!("Couldn't create link: {e}"),
+        })?;
+    }
+    report_created(link_path, &parent, existing.is_some())
+}
+
+/// Creates a hard link at `link_path` to the file `target`. Rejects folders,
+/// symlinks, an existing `link_path`, and a link on a different filesystem than
+/// the target (compared by device id, before the syscall's cryptic `EXDEV`).
+pub fn create_hardlink(target: &Path, link_path: &Path) -> Result<FileEntry, String> {
+    let (parent, name) = split_link_path(link_path)?;
+    let target_meta = fs::symlink_metadata(target).map_err(|_| format!("'{}' doesn't exist", target.display()))?;
+    if target_meta.is_dir() {
+        return Err("Folders can't be hard linked".to_string());
+    }
+    if target_meta.file_type().is_symlink() {
+        return Err("Symbolic links can't be hard linked; link to the file they point at".to_string());
+    }
+    if fs::symlink_metadata(link_path).is_ok() {
+        return Err(format!("'{name}' already exists"));
+    }
+    let parent_meta = fs::metadata(&parent).map_err(|e| format!("Couldn't read '{}': {e}", parent.display()))?;
+    if parent_meta.dev() != target_meta.dev() {
+        return Err("Hard links can't cross drives: the link must be on the same drive as its target".to_string());
+    }
+
+    crate::downloads::note_pending_write_for_cmdr(link_path);
+    fs::hard_link(target, link_path).map_err(|e| match e.kind() {
+        std::io::ErrorKind::AlreadyExists => format!("'{name}' already exists"),
+        std::io::ErrorKind::PermissionDenied => format!("Permission denied: cannot create '{name}'"),
+        _ => format!("Couldn't create hard link: {e}"),
+    })?;
+    report_created(link_path, &parent, false)
+}
+
+/// Reads where the symlink at `path` points and whether that target exists.
+pub fn resolve_symlink(path: &Path) -> Result<SymlinkResolution, String> {
+    let meta = fs::symlink_metadata(path).map_err(|_| format!("'{}' doesn't exist", path.display()))?;
+    if !meta.file_type().is_symlink() {
+        return Err(format!("'{}' isn't a symbolic link", path.display()));
+    }
+    let target = fs::read_link(path).map_err(|e| format!("Couldn't read link: {e}"))?;
+    // `canonicalize` follows the whole chain; it fails on a missing target or a loop.
+    let resolved = fs::canonicalize(path).ok();
+    Ok(SymlinkResolution {
+        target: target.to_string_lossy().into_owned(),
+        is_broken: resolved.is_none(),
+        is_directory: resolved.as_deref().is_some_and(Path::is_dir),
+        resolved_path: resolved.map(|p| p.to_string_lossy().into_owned()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn symlink_refuses_existing_unless_forced() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let dir = tmp.path();
+        fs::write(dir.join("a.txt"), b"a").expect("write a");
+        fs::write(dir.join("b.txt"), b"b").expect("write b");
+        let link = dir.join("link");
+
+        let entry = create_symlink(Path::new("a.txt"), &link, false).expect("create");
+        assert!(entry.is_symlink);
+        assert_eq!(fs::read_link(&link).expect("read_link"), Path::new("a.txt"));
+
+        let err = create_symlink(Path::new("b.txt"), &link, false).expect_err("exists");
+        assert!(err.contains("already exists"), "{err}");
+
+        create_symlink(Path::new("b.txt"), &link, true).expect("forced replace");
+        assert_eq!(fs::read_link(&link).expect("read_link"), Path::new("b.txt"));
+
+        fs::create_dir(dir.join("folder")).expect("mkdir");
+        let err = create_symlink(Path::new("a.txt"), &dir.join("folder"), true).expect_err("folder");
+        assert!(err.contains("folder"), "{err}");
+    }
+
+    #[test]
+    fn hardlink_shares_the_inode_and_rejects_folders_and_existing() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let dir = tmp.path();
+        fs::write(dir.join("data.bin"), b"1234").expect("write");
+
+        create_hardlink(&dir.join("data.bin"), &dir.join("twin.bin")).expect("hardlink");
+        let a = fs::metadata(dir.join("data.bin")).expect("meta a");
+        let b = fs::metadata(dir.join("twin.bin")).expect("meta b");
+        assert_eq!((a.dev(), a.ino()), (b.dev(), b.ino()));
+        assert_eq!(a.nlink(), 2);
+
+        assert!(create_hardlink(&dir.join("data.bin"), &dir.join("twin.bin")).is_err());
+        fs::create_dir(dir.join("folder")).expect("mkdir");
+        assert!(create_hardlink(&dir.join("folder"), &dir.join("folder-link")).is_err());
+        assert!(create_hardlink(&dir.join("missing"), &dir.join("x")).is_err());
+    }
+
+    #[test]
+    fn resolve_reports_broken_and_live_links() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let dir = tmp.path();
+        fs::create_dir(dir.join("real")).expect("mkdir");
+        std::os::unix::fs::symlink("real", dir.join("live")).expect("symlink live");
+        std::os::unix::fs::symlink("gone", dir.join("dangling")).expect("symlink dangling");
+
+        let live = resolve_symlink(&dir.join("live")).expect("resolve live");
+        assert_eq!(live.target, "real");
+        assert!(!live.is_broken);
+        assert!(live.is_directory);
+        assert_eq!(
+            live.resolved_path.map(PathBuf::from),
+            Some(dir.join("real").canonicalize().expect("canonicalize"))
+        );
+
+        let dangling = resolve_symlink(&dir.join("dangling")).expect("resolve dangling");
+        assert_eq!(dangling.target, "gone");
+        assert!(dangling.is_broken);
+        assert!(dangling.resolved_path.is_none());
+
+        assert!(resolve_symlink(&dir.join("real")).is_err(), "not a link");
+    }
+}
@@ -65,6 +65,7 @@ use std::future::Future;
 use std::pin::Pin;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, LazyLock, Mutex, OnceLock};
+use std::time::{Duration, Instant};
 
 use crate::file_system::volume::LaneKey;
 use crate::ignore_poison::IgnorePoison;
@@ -213,6 +214,9 @@ impl ManagerInner {
     }
 }
 
+/// How often `wait_for_operations_to_settle` re-checks the registry.
+const SETTLE_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
 /// Lane budget per lane in v1: serialize within a lane. v2 makes this
 /// per-lane and configurable (e.g. FTP = min(5, server limit)).
 const LANE_BUDGET: usize = 1;
@@ -526,6 +530,42 @@ impl OperationManager {
         was_queued
     }
 
+    /// Moves a Queued op within the FIFO order: to just before `before` (any
+    /// active op), or to the back when `before` is `None`. Returns `true` if it
+    /// moved, `false` if the op isn't Queued or `before` is no longer registered
+    /// (a stale drag from the queue window; moving to the back instead would
+    /// surprise the user).
+    ///
+    /// Only a Queued op moves: a Running op already holds its lanes, so its
+    /// position no longer decides anything. No admission pass runs either.
+    /// Every pass leaves no Queued op with all its lanes free, and reordering
+    /// frees no lane, so the new order only matters at the next settle.
+    pub(crate) fn move_queued(&self, operation_id: &str, before: Option<&str>) -> bool {
+        let moved = {
+            let mut inner = self.inner.lock_ignore_poison();
+            let queued = inner
+                .records
+                .get(operation_id)
+                .is_some_and(|r| r.status == LifecycleStatus::Queued);
+            let anchor_ok = before.is_none_or(|b| b != operation_id && inner.records.contains_key(b));
+            if queued && anchor_ok {
+                inner.order.retain(|id| id != operation_id);
+                let at = before
+                    .and_then(|b| inner.order.iter().position(|id| id == b))
+                    .unwrap_or(inner.order.len());
+                inner.order.insert(at, operation_id.to_string());
+                true
+            } else {
+                false
+            }
+        };
+        if moved {
+            log::info!(target: "op_manager", "move queued op={operation_id} before={before:?}");
+            self.emit_changed();
+        }
+        moved
+    }
+
     /// Flips a Running op's record between `Running` and `Paused` and re-emits
     /// `operations-changed`. Pause does NOT touch lanes (a paused Running op
     /// keeps its slots — we don't want a queued op to start and then fight it on
@@ -574,6 +614,23 @@ impl OperationManager {
             .collect()
     }
 
+    /// Ids of every active op (Queued, Running, or Paused) whose descriptor
+    /// touches `volume_id`, FIFO order.
+    fn ids_touching_volume(&self, volume_id: &str) -> Vec<String> {
+        let inner = self.inner.lock_ignore_poison();
+        inner
+            .order
+            .iter()
+            .filter(|id| {
+                inner
+                    .records
+                    .get(*id)
+                    .is_some_and(|r| r.descriptor.volume_ids.iter().any(|v| v == volume_id))
+            })
+            .cloned()
+            .collect()
+    }
+
     /// Ids of all currently `Paused` ops, for `resume_all`.
     fn paused_ids(&self) -> Vec<String> {
         let inner = self.inner.lock_ignore_poison();
@@ -754,6 +811,61 @@ pub fn cancel_operations(operation_ids: &[String]) {
     }
 }
 
+/// Moves a queued operation ahead of `before_operation_id`, or to the back of
+/// the queue when `None`, so it's admitted in that order once lanes free.
+/// Running, paused, and unknown ops stay put. Backs the
+/// `move_operation(id, before)` IPC command (the queue window's drag-reorder).
+pub fn move_operation(operation_id: &str, before_operation_id: Option<&str>) {
+    manager().move_queued(operation_id, before_operation_id);
+}
+
+/// Cancels every operation touching a volume that was just unmounted, so a copy
+/// onto a yanked drive ends promptly instead of hanging on a conflict prompt, a
+/// pause, or I/O against a dead mount. A Queued op is dropped like a user cancel;
+/// a Running/Paused op stops (keep-partials) and reports a `DeviceDisconnected`
+/// error naming `volume_path` rather than a plain `write-cancelled`. Called from
+/// the platform volume watchers before the volume is unregistered. Returns the
+/// affected op ids.
+pub(crate) fn cancel_operations_on_volume(volume_id: &str, volume_path: &str) -> Vec<String> {
+    let ids = manager().ids_touching_volume(volume_id);
+    for id in &ids {
+        if !manager().cancel_if_queued(id) {
+            log::info!(target: "op_manager", "volume {volume_id} unmounted, stopping op={id}");
+            super::state::cancel_write_operation_for_unmount(id, volume_path);
+        }
+    }
+    ids
+}
+
+/// Cancels every registered operation, for app shutdown. Queued ops are dropped
+/// before they spawn; Running/Paused ones stop at their next safe point through
+/// the keep-partials path (already-copied files stay, the last partial is
+/// deleted). Returns how many ops were registered. Pair with
+/// [`wait_for_operations_to_settle`] so they actually get there before exit.
+pub(crate) fn cancel_all_operations() -> usize {
+    let ids: Vec<String> = manager().list().into_iter().map(|s| s.operation_id).collect();
+    for id in &ids {
+        cancel_operation(id);
+    }
+    ids.len()
+}
+
+/// Blocks the calling thread until no op is registered or `timeout` passes.
+/// Returns `true` if everything settled in time. The ops settle on the app
+/// runtime, so this is safe to call from the main thread's close handler.
+pub(crate) fn wait_for_operations_to_settle(timeout: Duration) -> bool {
+    let deadline = Instant::now() + timeout;
+    loop {
+        if manager().list().is_empty() {
+            return true;
+        }
+        if Instant::now() >= deadline {
+            return false;
+        }
+        std::thread::sleep(SETTLE_POLL_INTERVAL);
+    }
+}
+
 /// Pauses one Running operation: parks it at its next between-files boundary and
 /// flips its `LifecycleStatus` to `Paused` (re-emitting `operations-changed`).
 /// A paused op keeps its lane slots. Pausing a Queued op is a v1 no-op (it isn't
@@ -766,6 +878,7 @@ pub fn pause_operation(operation_id: &str) {
     // do nothing and risk a Paused-but-Queued limbo.
     if manager().set_paused(operation_id, true) {
         super::state::pause_write_operation(operation_id);
+        emit_write_paused(operation_id, true);
     }
 }
 
@@ -776,6 +889,25 @@ pub fn pause_operation(operation_id: &str) {
 pub fn resume_operation(operation_id: &str) {
     if manager().set_paused(operation_id, false) {
         super::state::resume_write_operation(operation_id);
+        emit_write_paused(operation_id, false);
+    }
+}
+
+/// Emits `write-paused` for a pause/resume that flipped a record, with the op's
+/// running total of paused time.
+fn emit_write_paused(operation_id: &str, paused: bool) {
+    let Some(app) = OPERATIONS_APP.get() else {
+        return;
+    };
+    use tauri_specta::Event as _;
+    let paused_ms = super::state::paused_time(operation_id).map_or(0, |d| d.as_millis() as u64);
+    let payload = super::types::WritePausedEvent {
+        operation_id: operation_id.to_string(),
+        paused,
+        paused_ms,
+    };
+    if let Err(e) = payload.emit(app) {
+        log::warn!(target: "op_manager", "failed to emit write-paused: {e}");
     }
 }
 
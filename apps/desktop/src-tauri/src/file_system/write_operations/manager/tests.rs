@@ -7,7 +7,7 @@
 //! unique operation ids + lane keys to stay correct under nextest's in-process
 //! parallelism.
 
-use super::super::state::busy_volume_ids;
+use super::super::state::{busy_volume_ids, operations_on_volume};
 use super::*;
 use crate::test_support::wait_until_async;
 use std::sync::atomic::{AtomicU64, Ordering};
@@ -345,6 +345,72 @@ async fn cancel_if_queued_returns_false_for_running_op() {
     let _ = rel_tx.send(());
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn moved_queued_op_is_admitted_ahead_of_an_older_one() {
+    let lane = unique("lane");
+    let op_a = unique("reorder-a");
+    let op_b = unique("reorder-b");
+    let op_c = unique("reorder-c");
+
+    let (a_started_tx, a_started_rx) = oneshot::channel();
+    let (a_rel_tx, a_rel_rx) = oneshot::channel();
+    manager().spawn_managed(
+        descriptor(&op_a, vec![&lane]),
+        fresh_state(),
+        gated_deferred(op_a.clone(), a_started_tx, a_rel_rx),
+    );
+    a_started_rx.await.expect("A started");
+
+    let (b_started_tx, mut b_started_rx) = oneshot::channel();
+    let (b_rel_tx, b_rel_rx) = oneshot::channel();
+    manager().spawn_managed(
+        descriptor(&op_b, vec![&lane]),
+        fresh_state(),
+        gated_deferred(op_b.clone(), b_started_tx, b_rel_rx),
+    );
+    let (c_started_tx, c_started_rx) = oneshot::channel();
+    let (c_rel_tx, c_rel_rx) = oneshot::channel();
+    manager().spawn_managed(
+        descriptor(&op_c, vec![&lane]),
+        fresh_state(),
+        gated_deferred(op_c.clone(), c_started_tx, c_rel_rx),
+    );
+
+    // The running op and a stale anchor don't move; C ahead of B does.
+    assert!(!manager().move_queued(&op_a, None), "a Running op keeps its place");
+    assert!(
+        !manager().move_queued(&op_c, Some("mgr-test-gone")),
+        "a stale anchor is a no-op"
+    );
+    assert!(manager().move_queued(&op_c, Some(&op_b)));
+    let order: Vec<String> = manager()
+        .list()
+        .into_iter()
+        .map(|s| s.operation_id)
+        .filter(|id| [&op_a, &op_b, &op_c].contains(&id))
+        .collect();
+    assert_eq!(order, vec![op_a.clone(), op_c.clone(), op_b.clone()]);
+
+    // Settle A: the lane goes to C, the moved op, while B keeps waiting.
+    let _ = a_rel_tx.send(());
+    tokio::time::timeout(Duration::from_secs(2), c_started_rx)
+        .await
+        .expect("C must be admitted first after the reorder")
+        .expect("C started");
+    assert_eq!(manager().status_of(&op_b), Some(LifecycleStatus::Queued));
+    assert!(
+        b_started_rx.try_recv().is_err(),
+        "B must not start while C holds the lane"
+    );
+
+    let _ = c_rel_tx.send(());
+    tokio::time::timeout(Duration::from_secs(2), b_started_rx)
+        .await
+        .expect("B is admitted once C settles")
+        .expect("B started");
+    let _ = b_rel_tx.send(());
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn panicking_op_releases_its_lane_without_spawning_next() {
     // The Drop safety net frees a panicking op's lane, but must NOT spawn the
@@ -630,6 +696,11 @@ async fn run_instant_marks_volume_busy_and_registers_record_for_op_duration() {
         busy_volume_ids().contains(&vol),
         "volume must be busy while the instant op runs"
     );
+    assert_eq!(
+        operations_on_volume(&vol),
+        vec![op_id.clone()],
+        "the eject guard must be able to name the op holding the volume"
+    );
     assert!(
         manager()
             .list()
@@ -647,6 +718,7 @@ async fn run_instant_marks_volume_busy_and_registers_record_for_op_duration() {
         !busy_volume_ids().contains(&vol),
         "volume must no longer be busy after the op finishes"
     );
+    assert!(operations_on_volume(&vol).is_empty());
     assert!(
         !manager().list().iter().any(|o| o.operation_id == op),
         "the record must be removed after the op finishes"
@@ -863,3 +935,68 @@ async fn run_instant_returns_the_ops_result_unchanged() {
         "record removed after a failed instant op"
     );
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn cancel_operations_on_volume_stops_running_drops_queued_and_spares_other_volumes() {
+    use super::super::state::{OperationIntent, load_intent, take_unmount_cancellation};
+
+    let volume = unique("usb");
+    let other_volume = unique("other-usb");
+    let lane = unique("lane");
+    let other_lane = unique("other-lane");
+    let op_running = unique("unmount-running");
+    let op_queued = unique("unmount-queued");
+    let op_other = unique("unmount-other");
+    let with_volume = |op: &str, lane: &str, volume: &str| OperationDescriptor {
+        volume_ids: vec![volume.to_string()],
+        ..descriptor(op, vec![lane])
+    };
+
+    let running_state = fresh_state();
+    let (started_tx, started_rx) = oneshot::channel();
+    let (rel_tx, rel_rx) = oneshot::channel();
+    manager().spawn_managed(
+        with_volume(&op_running, &lane, &volume),
+        Arc::clone(&running_state),
+        gated_deferred(op_running.clone(), started_tx, rel_rx),
+    );
+    started_rx.await.expect("running op started");
+
+    let (queued_started_tx, _queued_started_rx) = oneshot::channel();
+    let (_queued_rel_tx, queued_rel_rx) = oneshot::channel();
+    manager().spawn_managed(
+        with_volume(&op_queued, &lane, &volume),
+        fresh_state(),
+        gated_deferred(op_queued.clone(), queued_started_tx, queued_rel_rx),
+    );
+    assert_eq!(manager().status_of(&op_queued), Some(LifecycleStatus::Queued));
+
+    let other_state = fresh_state();
+    let (other_started_tx, other_started_rx) = oneshot::channel();
+    let (other_rel_tx, other_rel_rx) = oneshot::channel();
+    manager().spawn_managed(
+        with_volume(&op_other, &other_lane, &other_volume),
+        Arc::clone(&other_state),
+        gated_deferred(op_other.clone(), other_started_tx, other_rel_rx),
+    );
+    other_started_rx.await.expect("other-volume op started");
+
+    let affected = cancel_operations_on_volume(&volume, "/Volumes/Yanked");
+
+    assert_eq!(affected, vec![op_running.clone(), op_queued.clone()]);
+    assert_eq!(
+        manager().status_of(&op_queued),
+        None,
+        "queued op dropped without spawning"
+    );
+    assert_eq!(load_intent(&running_state.intent), OperationIntent::Stopped);
+    assert_eq!(
+        take_unmount_cancellation(&op_running).as_deref(),
+        Some("/Volumes/Yanked")
+    );
+    assert_eq!(load_intent(&other_state.intent), OperationIntent::Running);
+    assert_eq!(take_unmount_cancellation(&op_other), None);
+
+    let _ = rel_tx.send(());
+    let _ = other_rel_tx.send(());
+}
@@ -20,6 +20,7 @@ mod analytics;
 mod archive_edit;
 mod archive_remote_edit;
 mod cancellable;
+mod cloud_materialize;
 mod compress_estimate;
 mod conflict;
 mod create;
@@ -30,11 +31,14 @@ mod eta;
 mod event_sinks;
 mod journal;
 mod journal_search;
+mod links;
 mod manager;
+mod name_sanitizer;
 mod operation_intent;
 mod overwrite;
 #[cfg(target_os = "macos")]
 mod paste_clipboard;
+mod permissions;
 mod rename;
 pub(crate) mod rollback;
 mod scan;
@@ -64,7 +68,10 @@ use uuid::Uuid;
 
 use crate::file_system::volume::LaneKey;
 use crate::operation_log::types::{Initiator, OpKind};
-use delete::{delete_files_with_progress_inner, delete_volume_files_with_progress_inner};
+use delete::{
+    delete_files_with_progress_inner, delete_volume_files_with_progress_inner, trash_contents, trash_locations,
+};
+use event_sinks::DiscardEventSink;
 use manager::OperationDescriptor;
 #[cfg(not(test))]
 use state::WriteOperationState;
@@ -85,10 +92,15 @@ use validation::{
 };
 
 // Re-export public types
+pub use delete::{TrashInfo, get_trash_info};
+pub use links::{SymlinkResolution, create_hardlink, create_symlink, resolve_symlink};
+pub use permissions::{
+    PermissionsEstimate, PermissionsInfo, SetPermissionsResult, estimate_permissions_change, get_permissions,
+};
 pub use scan_preview::{cancel_scan_preview, get_scan_preview_totals, start_scan_preview};
 pub use state::{
     VolumesBusyChanged, busy_volume_ids, cancel_all_write_operations, cancel_write_operation, get_operation_status,
-    init_busy_volume_emitter, list_active_operations, resolve_write_conflict,
+    init_busy_volume_emitter, list_active_operations, operations_on_volume, resolve_write_conflict,
 };
 // Operation manager: the single scheduler + registry every write op flows
 // through. `OperationsChanged` / `OperationSnapshot` are the thin
@@ -98,8 +110,10 @@ pub use state::{
 // helpers back the new `list_operations` / `cancel_operation(s)` IPC.
 pub use manager::{
     LifecycleStatus, OperationSnapshot, OperationSummaryText, OperationsChanged, cancel_operation, cancel_operations,
-    init_operation_event_emitter, list_operations, pause_all, pause_operation, resume_all, resume_operation,
+    init_operation_event_emitter, list_operations, move_operation, pause_all, pause_operation, resume_all,
+    resume_operation,
 };
+pub(crate) use manager::{cancel_all_operations, cancel_operations_on_volume, wait_for_operations_to_settle};
 // Managed instant mutations (rename / mkdir / mkfile) + rename validation. The
 // thin IPC commands (`commands/rename.rs`, `commands/file_system/write_ops.rs`)
 // call these; `RenameValidityResult` rides into `bindings.ts` via the
@@ -108,8 +122,9 @@ pub(crate) use create::{create_directory_managed, create_file_managed};
 #[cfg(target_os = "macos")]
 pub(crate) use paste_clipboard::write_payload_to_dir;
 pub(crate) use rename::{
-    BulkRenameFingerprint, BulkRenameRow, RenameValidityResult, check_rename_permission_sync,
-    check_rename_validity_impl, rename_managed, start_bulk_rename,
+    BatchRenamePreviewRow, BatchRenameRule, BulkRenameFingerprint, BulkRenameRow, RenameValidityResult,
+    check_rename_permission_sync, check_rename_validity_impl, load_batch_rename_sources, preview_batch_rename,
+    rename_managed, start_batch_rename, start_bulk_rename,
 };
 // External busy-volume seam for the drag-out fulfillment service (see
 // `lifecycle/state.rs` § "External busy-volume seam"). `pub(crate)` so only in-crate
@@ -124,7 +139,8 @@ pub use types::{
     ScanPreviewCompleteEvent, ScanPreviewErrorEvent, ScanPreviewProgressEvent, ScanPreviewStartResult,
     ScanPreviewTotals, ScanProgressEvent, SortColumn, SortOrder, WriteCancelledEvent, WriteCompleteEvent,
     WriteConflictEvent, WriteErrorEvent, WriteOperationConfig, WriteOperationError, WriteOperationPhase,
-    WriteOperationStartResult, WriteOperationType, WriteProgressEvent, WriteSettledEvent, WriteSourceItemDoneEvent,
+    WriteOperationStartResult, WriteOperationType, WritePausedEvent, WriteProgressEvent, WriteRenamedEvent,
+    WriteSettledEvent, WriteSourceItemDoneEvent,
 };
 
 // Re-export for tests (these are pub(crate) in validation.rs and state.rs)
@@ -154,7 +170,7 @@ pub(crate) use types::CollectorEventSink;
 // Re-export volume copy types and functions
 pub use transfer::volume_copy::{copy_between_volumes, scan_for_volume_copy};
 pub use transfer::volume_move::move_between_volumes;
-pub use types::{VolumeCopyConfig, VolumeCopyScanResult};
+pub use types::{ArchiveSymlinkMode, VolumeCopyConfig, VolumeCopyScanResult};
 // Copy/move INTO a zip: the command layer routes an archive destination here
 // (the whole transfer becomes one `{ add }` changeset) instead of the per-file
 // cross-volume engine.
@@ -404,6 +420,29 @@ pub async fn copy_files_start(
     .await
 }
 
+/// Runs a local copy to completion on the calling thread: the same validation and
+/// `copy_files_with_progress_inner` pipeline as [`copy_files_start`], minus the operation manager,
+/// events, and journal. For the criterion copy benches (`benches/copy_benchmarks.rs`, reached
+/// through `crate::bench_support`); the run is bracketed with `benchmark` timeline events.
+pub(crate) fn copy_files_blocking(
+    sources: &[PathBuf],
+    destination: &std::path::Path,
+    config: &WriteOperationConfig,
+) -> Result<(), WriteOperationError> {
+    let _timed = crate::benchmark::TimedBlock::new("copy_files_blocking");
+    crate::benchmark::log_event_value("copy_files_blocking sources", sources.len());
+    validate_sources(sources)?;
+    validate_destination_not_inside_source(sources, destination)?;
+    ensure_destination_dir(destination)?;
+    validate_destination_writable(destination)?;
+    validate_not_same_location(sources, destination)?;
+    let state = Arc::new(WriteOperationState::new(Duration::from_millis(
+        config.progress_interval_ms,
+    )));
+    let op_id = Uuid::new_v4().to_string();
+    copy_files_with_progress_inner(&DiscardEventSink, &op_id, &state, sources, destination, config)
+}
+
 /// Starts a move operation in the background.
 ///
 /// Uses instant rename() for same-filesystem moves.
@@ -446,6 +485,10 @@ pub async fn move_files_start(
             ensure_destination_dir(&destination)?;
             validate_destination_writable(&destination)?;
             validate_not_same_location(&sources, &destination)?;
+            if !config.dry_run {
+                // Feeds the "quick move" picker. Recorded once the move is known to be valid.
+                crate::file_system::move_targets::record_move_target(&destination);
+            }
             move_files_with_progress_inner(&*events, &op_id, &state, &sources, &destination, &config)
         },
     )
@@ -651,6 +694,131 @@ pub async fn trash_files_start(
     .await
 }
 
+/// Starts emptying the Trash in the background: permanently deletes everything in every trash
+/// location the user owns (see `delete::empty_trash`), through the local delete walker, so it
+/// reports per-file `write-progress` and honors cancel like any delete. Runs as a `Delete` op.
+pub async fn empty_trash_start(
+    events: Arc<dyn OperationEventSink>,
+    config: WriteOperationConfig,
+    initiator: Initiator,
+) -> Result<WriteOperationStartResult, WriteOperationError> {
+    let locations = trash_locations();
+    log::info!(target: "empty_trash", "empty_trash_start: {} trash location(s)", locations.len());
+
+    // Trashes on external volumes mark those volumes busy (no eject mid-empty).
+    let mut volume_ids: Vec<String> = locations.iter().filter_map(|l| l.volume_id.clone()).collect();
+    volume_ids.dedup();
+    let config = WriteOperationConfig {
+        // Trash is full of dotfiles (`.DS_Store`, Linux `info/`); skipping them would leave
+        // every folder non-empty.
+        include_hidden: true,
+        dry_run: false,
+        preview_id: None,
+        ..config
+    };
+    let summary = OperationSummaryText {
+        source: Some("Trash".to_string()),
+        destination: None,
+    };
+    start_write_operation(
+        events,
+        WriteOperationType::Delete,
+        initiator,
+        config.progress_interval_ms,
+        volume_ids,
+        vec![LaneKey::new(crate::file_system::volume::DEFAULT_VOLUME_ID)],
+        summary,
+        locations.len().max(1) as u64,
+        move |events, op_id, state| {
+            // Enumerated here, on the blocking thread, so a huge trash doesn't stall the IPC call.
+            let sources = trash_contents(&locations);
+            delete_files_with_progress_inner(&*events, &op_id, &state, &sources, &config)
+        },
+    )
+    .await
+}
+
+/// Sets the permission bits of `path` to `mode`.
+///
+/// Not `recursive` (or a file): a single `chmod`, applied before this returns
+/// (`SetPermissionsResult::Applied`). Recursive on a folder: without `confirmed`,
+/// only counts the tree and returns `NeedsConfirmation` with the estimate; with it,
+/// starts a managed `SetPermissions` op over the whole tree (`Started`). The op
+/// honors `config.progress_interval_ms` and `config.continue_on_error`.
+///
+/// Failures come back typed: a file the user doesn't own is `PermissionDenied`.
+pub async fn set_permissions_start(
+    events: Arc<dyn OperationEventSink>,
+    path: PathBuf,
+    mode: u32,
+    recursive: bool,
+    confirmed: bool,
+    config: WriteOperationConfig,
+    initiator: Initiator,
+) -> Result<SetPermissionsResult, WriteOperationError> {
+    log::info!(
+        "set_permissions_start: path={:?}, mode={:o}, recursive={}, confirmed={}",
+        path,
+        mode,
+        recursive,
+        confirmed
+    );
+    let task_failed = |e: tokio::task::JoinError| WriteOperationError::IoError {
+        path: String::new(),
+        message: format!("Task failed: {e}"),
+    };
+
+    let probe = path.clone();
+    let is_dir = tokio::task::spawn_blocking(move || permissions::validate_target(&probe, mode))
+        .await
+        .map_err(task_failed)??;
+    if !recursive || !is_dir {
+        let permissions = tokio::task::spawn_blocking(move || permissions::set_single(&path, mode))
+            .await
+            .map_err(task_failed)??;
+        return Ok(SetPermissionsResult::Applied { permissions });
+    }
+    if !confirmed {
+        let estimate = tokio::task::spawn_blocking(move || estimate_permissions_change(&path))
+            .await
+            .map_err(task_failed)?;
+        return Ok(SetPermissionsResult::NeedsConfirmation { estimate });
+    }
+
+    // A tree on an ejectable drive marks that drive busy (no eject mid-change).
+    let volume_ids: Vec<String> = crate::file_system::get_volume_manager()
+        .containing(&path)
+        .map(|(id, _)| id)
+        .filter(|id| id != crate::file_system::volume::DEFAULT_VOLUME_ID)
+        .into_iter()
+        .collect();
+    let summary = path_summary(std::slice::from_ref(&path), None);
+    let started = start_write_operation(
+        events,
+        WriteOperationType::SetPermissions,
+        initiator,
+        config.progress_interval_ms,
+        volume_ids.clone(),
+        local_lanes(&volume_ids),
+        summary,
+        1,
+        move |events, op_id, state| {
+            permissions::set_permissions_recursive_inner(
+                &*events,
+                &op_id,
+                &state,
+                &path,
+                mode,
+                config.continue_on_error,
+            )
+        },
+    )
+    .await?;
+    Ok(SetPermissionsResult::Started {
+        operation_id: started.operation_id,
+    })
+}
+
 #[cfg(test)]
 mod journal_capture_tests;
 #[cfg(test)]
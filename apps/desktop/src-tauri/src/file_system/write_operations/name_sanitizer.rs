@@ -0,0 +1,249 @@
+//! Destination-name sanitization for copies onto restrictive filesystems.
+//!
+//! FAT32, exFAT, NTFS, and SMB shares reject names that APFS and ext4 take for
+//! granted: `:` and `?`, control characters, trailing dots and spaces, and DOS
+//! device names like `CON`. With `WriteOperationConfig::sanitize_names` on, the
+//! local copy picks a rule set from the destination's filesystem (probed once
+//! per op) and rewrites every offending path component before writing. Each
+//! rename is logged and reported as a `write-renamed` event.
+//!
+//! Two source names can sanitize to the same destination name (`a:b` and
+//! `a_b`). The second one then meets the first at the destination and goes
+//! through the op's normal conflict resolution, like any other clash.
+
+use std::collections::HashSet;
+use std::ffi::OsStr;
+use std::path::{Component, Path, PathBuf};
+
+use super::types::{OperationEventSink, WriteRenamedEvent};
+use crate::file_system::filesystem_kind::{FilesystemKind, detect_filesystem_for_path};
+
+/// What an illegal character becomes.
+const REPLACEMENT: char = '_';
+
+/// Characters Windows-family filesystems refuse anywhere in a name.
+const WINDOWS_ILLEGAL: &[char] = &['<', '>', ':', '"', '/', '\\', '|', '?', '*'];
+
+/// DOS device names, reserved with or without an extension (`CON`, `con.txt`).
+const WINDOWS_RESERVED: &[&str] = &[
+    "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8", "COM9", "LPT1", "LPT2",
+    "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+/// A filesystem's naming rules. One family today; the enum leaves room for
+/// others (HFS+'s `:` only, for example) without touching call sites.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum NameRules {
+    /// FAT32, exFAT, NTFS, and SMB: the Win32 naming rules.
+    Windows,
+}
+
+impl NameRules {
+    /// The rules names on `kind` must follow, or `None` when it takes any name
+    /// the source could have (every POSIX filesystem, MTP, unknown).
+    pub(crate) fn for_filesystem(kind: FilesystemKind) -> Option<Self> {
+        match kind {
+            FilesystemKind::Fat32 | FilesystemKind::ExFat | FilesystemKind::Ntfs | FilesystemKind::Smb => {
+                Some(Self::Windows)
+            }
+            FilesystemKind::Apfs
+            | FilesystemKind::HfsPlus
+            | FilesystemKind::Ext4
+            | FilesystemKind::Btrfs
+            | FilesystemKind::Xfs
+            | FilesystemKind::Zfs
+            | FilesystemKind::Mtp
+            | FilesystemKind::Other => None,
+        }
+    }
+
+    /// The safe form of `name`, or `None` when it's already valid here.
+    pub(crate) fn sanitize(self, name: &str) -> Option<String> {
+        match self {
+            Self::Windows => sanitize_windows(name),
+        }
+    }
+}
+
+fn sanitize_windows(name: &str) -> Option<String> {
+    let mut safe: String = name
+        .chars()
+        .map(|c| {
+            if c.is_control() || WINDOWS_ILLEGAL.contains(&c) {
+                REPLACEMENT
+            } else {
+                c
+            }
+        })
+        .collect();
+
+    // Windows silently drops trailing dots and spaces, so `notes.` and `notes`
+    // would collide. Replace them instead to keep the names apart.
+    let kept = safe.trim_end_matches(['.', ' ']).len();
+    let trailing = safe.len() - kept;
+    if trailing > 0 {
+        safe.truncate(kept);
+        safe.extend(std::iter::repeat_n(REPLACEMENT, trailing));
+    }
+
+    // `CON.txt` is as reserved as `CON`: mark the stem, keep the extension.
+    let stem_len = safe.find('.').unwrap_or(safe.len());
+    if WINDOWS_RESERVED
+        .iter()
+        .any(|r| r.eq_ignore_ascii_case(&safe[..stem_len]))
+    {
+        safe.insert(stem_len, REPLACEMENT);
+    }
+
+    (safe != name).then_some(safe)
+}
+
+/// Rewrites destination paths under one op's destination root and collects a
+/// `write-renamed` event per rewritten component, reported once even though
+/// every file below a renamed folder passes through it.
+pub(crate) struct NameSanitizer {
+    rules: NameRules,
+    destination: PathBuf,
+    operation_id: String,
+    reported: HashSet<PathBuf>,
+    pending: Vec<WriteRenamedEvent>,
+}
+
+impl NameSanitizer {
+    /// Probes `destination`'s filesystem once and returns a sanitizer for it,
+    /// or `None` when the filesystem takes every name as-is.
+    pub(crate) fn for_destination(destination: &Path, operation_id: &str) -> Option<Self> {
+        let filesystem = detect_filesystem_for_path(destination);
+        let rules = NameRules::for_filesystem(filesystem.kind)?;
+        log::debug!(
+            "name_sanitizer: {:?} rules for {} ({:?}) op={}",
+            rules,
+            destination.display(),
+            filesystem.kind,
+            operation_id
+        );
+        Some(Self::with_rules(rules, destination, operation_id))
+    }
+
+    pub(crate) fn with_rules(rules: NameRules, destination: &Path, operation_id: &str) -> Self {
+        Self {
+            rules,
+            destination: destination.to_path_buf(),
+            operation_id: operation_id.to_string(),
+            reported: HashSet::new(),
+            pending: Vec::new(),
+        }
+    }
+
+    /// Returns `dest` with every component below the destination root made
+    /// legal. `source` is the item `dest` was derived from; its trailing
+    /// components line up with `dest`'s, which names the source of each rename.
+    /// Paths outside the destination root come back unchanged.
+    pub(crate) fn sanitize(&mut self, source: &Path, dest: &Path) -> PathBuf {
+        let Ok(relative) = dest.strip_prefix(&self.destination) else {
+            return dest.to_path_buf();
+        };
+        let components: Vec<&OsStr> = relative
+            .components()
+            .filter_map(|c| match c {
+                Component::Normal(name) => Some(name),
+                _ => None,
+            })
+            .collect();
+
+        let mut out = self.destination.clone();
+        for (i, component) in components.iter().enumerate() {
+            // Non-UTF-8 names can't be checked reliably; leave them to the OS.
+            let Some(name) = component.to_str() else {
+                out.push(component);
+                continue;
+            };
+            let Some(safe) = self.rules.sanitize(name) else {
+                out.push(component);
+                continue;
+            };
+            out.push(&safe);
+            if self.reported.insert(out.clone()) {
+                let depth_from_leaf = components.len() - 1 - i;
+                let source_item = source.ancestors().nth(depth_from_leaf).unwrap_or(source);
+                log::info!(
+                    "name_sanitizer: renamed {:?} to {:?} for {} op={}",
+                    name,
+                    safe,
+                    source_item.display(),
+                    self.operation_id
+                );
+                self.pending.push(WriteRenamedEvent {
+                    operation_id: self.operation_id.clone(),
+                    source_path: source_item.display().to_string(),
+                    original_name: name.to_string(),
+                    new_name: safe,
+                });
+            }
+        }
+        out
+    }
+
+    /// Emits the renames collected since the last flush.
+    pub(crate) fn flush(&mut self, events: &dyn OperationEventSink) {
+        for event in self.pending.drain(..) {
+            events.emit_renamed(event);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn windows(name: &str) -> Option<String> {
+        NameRules::Windows.sanitize(name)
+    }
+
+    #[test]
+    fn windows_rules_replace_illegal_characters_and_trailing_dots() {
+        assert_eq!(windows("notes.txt"), None);
+        assert_eq!(windows("10:30 meeting?.md").as_deref(), Some("10_30 meeting_.md"));
+        assert_eq!(windows("tab\there").as_deref(), Some("tab_here"));
+        assert_eq!(windows("draft. ").as_deref(), Some("draft__"));
+        assert_eq!(windows("con.txt").as_deref(), Some("con_.txt"));
+        assert_eq!(windows("LPT1").as_deref(), Some("LPT1_"));
+        // Only exact device names are reserved.
+        assert_eq!(windows("console.log"), None);
+    }
+
+    #[test]
+    fn only_windows_family_filesystems_get_rules() {
+        assert_eq!(
+            NameRules::for_filesystem(FilesystemKind::Fat32),
+            Some(NameRules::Windows)
+        );
+        assert_eq!(NameRules::for_filesystem(FilesystemKind::Smb), Some(NameRules::Windows));
+        assert_eq!(NameRules::for_filesystem(FilesystemKind::Apfs), None);
+        assert_eq!(NameRules::for_filesystem(FilesystemKind::Mtp), None);
+    }
+
+    #[test]
+    fn sanitizer_rewrites_each_component_and_reports_each_rename_once() {
+        let mut sanitizer = NameSanitizer::with_rules(NameRules::Windows, Path::new("/dst"), "op-1");
+        let first = sanitizer.sanitize(Path::new("/src/Q&A?/a.txt"), Path::new("/dst/Q&A?/a.txt"));
+        let second = sanitizer.sanitize(Path::new("/src/Q&A?/b:c.txt"), Path::new("/dst/Q&A?/b:c.txt"));
+        assert_eq!(first, PathBuf::from("/dst/Q&A_/a.txt"));
+        assert_eq!(second, PathBuf::from("/dst/Q&A_/b_c.txt"));
+
+        let sink = super::super::event_sinks::CollectorEventSink::new();
+        sanitizer.flush(&sink);
+        let renamed = sink.renamed.lock().unwrap();
+        let pairs: Vec<(&str, &str, &str)> = renamed
+            .iter()
+            .map(|e| (e.source_path.as_str(), e.original_name.as_str(), e.new_name.as_str()))
+            .collect();
+        assert_eq!(
+            pairs,
+            vec![
+                ("/src/Q&A?", "Q&A?", "Q&A_"),
+                ("/src/Q&A?/b:c.txt", "b:c.txt", "b_c.txt")
+            ]
+        );
+    }
+}
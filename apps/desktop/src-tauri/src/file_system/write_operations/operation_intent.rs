@@ -10,6 +10,7 @@
 use crate::ignore_poison::IgnorePoison;
 use std::sync::Condvar;
 use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+use std::time::{Duration, Instant};
 
 // ============================================================================
 // Operation intent (state machine for cancellation)
@@ -98,6 +99,15 @@ pub struct PauseGate {
     condvar_mutex: std::sync::Mutex<()>,
     condvar: Condvar,
     notify: tokio::sync::Notify,
+    /// When the current pause began, and the length of the finished ones. Feeds
+    /// the `write-paused` event and lets the ETA estimator skip the gap.
+    timing: std::sync::Mutex<PauseTiming>,
+}
+
+#[derive(Default)]
+struct PauseTiming {
+    since: Option<Instant>,
+    total: Duration,
 }
 
 impl PauseGate {
@@ -107,6 +117,7 @@ impl PauseGate {
             condvar_mutex: std::sync::Mutex::new(()),
             condvar: Condvar::new(),
             notify: tokio::sync::Notify::new(),
+            timing: std::sync::Mutex::new(PauseTiming::default()),
         }
     }
 
@@ -118,15 +129,31 @@ impl PauseGate {
     /// Sets the paused flag. The op parks at its next loop-boundary gate. A
     /// no-op-on-double-pause: setting an already-set flag changes nothing.
     pub fn pause(&self) {
+        self.timing.lock_ignore_poison().since.get_or_insert_with(Instant::now);
         self.paused.store(true, Ordering::Release);
     }
 
     /// Clears the paused flag and wakes any waiter (sync condvar + async
     /// notify). Safe to call when not paused (just wakes spurious waiters,
     /// which re-check the flag and continue).
-    pub fn resume(&self) {
+    ///
+    /// Returns how long this pause lasted, or `None` if the op wasn't paused.
+    pub fn resume(&self) -> Option<Duration> {
+        let paused_for = {
+            let mut timing = self.timing.lock_ignore_poison();
+            let paused_for = timing.since.take().map(|since| since.elapsed());
+            timing.total += paused_for.unwrap_or_default();
+            paused_for
+        };
         self.paused.store(false, Ordering::Release);
         self.wake();
+        paused_for
+    }
+
+    /// Total time spent paused so far, including a pause still in progress.
+    pub fn paused_time(&self) -> Duration {
+        let timing = self.timing.lock_ignore_poison();
+        timing.total + timing.since.map(|since| since.elapsed()).unwrap_or_default()
     }
 
     /// Wakes any parked waiter WITHOUT clearing the paused flag. The cancel path
@@ -249,6 +276,24 @@ mod tests {
         assert!(!gate.is_paused());
     }
 
+    #[test]
+    fn pause_gate_accumulates_paused_time_across_pauses() {
+        let gate = PauseGate::new();
+        assert_eq!(gate.resume(), None, "resuming a running op reports no pause");
+        assert_eq!(gate.paused_time(), Duration::ZERO);
+
+        gate.pause();
+        // allowed-test-sleep: the pause length is wall time by design.
+        std::thread::sleep(Duration::from_millis(20));
+        gate.pause(); // a second pause doesn't restart the clock
+        let first = gate.resume().expect("was paused");
+        assert!(first >= Duration::from_millis(20), "first pause lasted {first:?}");
+
+        gate.pause();
+        let second = gate.resume().expect("was paused");
+        assert_eq!(gate.paused_time(), first + second);
+    }
+
     #[test]
     fn wait_while_paused_sync_returns_immediately_when_not_paused() {
         // Not paused → the wait must be a no-op (no deadlock, no condvar park).
@@ -0,0 +1,422 @@
+//! Reading and changing permission bits (chmod) on the local filesystem.
+//!
+//! A single item is one `chmod` syscall: applied at once on the blocking pool, never an op. A
+//! recursive change is a managed `SetPermissions` op (progress, cancel, pause, the local drive's
+//! lane), and it's two-step: without `confirmed`, `set_permissions_start` only counts the tree
+//! ([`estimate_permissions_change`]) and returns the estimate, so the user sees "Change 48,210
+//! items?" before anything changes. Ownership (chown) isn't offered: giving a file away needs root.
+//!
+//! Symlinks have no mode of their own (chmod follows them), so a link named directly is rejected
+//! and links inside a tree are skipped, never followed. The tree is changed deepest-first, so a
+//! mode that drops a folder's `x` bit doesn't lock the walk out of its own contents.
+
+use std::fs;
+use std::os::unix::fs::{MetadataExt, PermissionsExt};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use serde::Serialize;
+
+use super::error_classification::classify_io_error;
+use super::state::{WriteOperationState, is_cancelled, update_operation_status};
+use super::types::{
+    IoResultExt, OperationEventSink, ScanProgressEvent, WriteCancelledEvent, WriteCompleteEvent, WriteOperationError,
+    WriteOperationPhase, WriteOperationType, WriteProgressEvent,
+};
+use crate::file_system::get_volume_manager;
+use crate::file_system::listing::caching::{DirectoryChange, notify_directory_changed};
+use crate::file_system::listing::get_single_entry;
+use crate::file_system::listing::metadata::{get_group_name, get_owner_name};
+use crate::file_system::volume::DEFAULT_VOLUME_ID;
+use crate::file_system::volume::backends::archive;
+
+/// The chmod-able bits: permissions plus setuid, setgid, and sticky.
+const MODE_MASK: u32 = 0o7777;
+
+/// The estimate stops counting here: past this, the exact number doesn't change the decision.
+const ESTIMATE_CAP: usize = 100_000;
+
+/// An item's current mode and ownership, for the permissions editor.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct PermissionsInfo {
+    /// Permission bits plus setuid/setgid/sticky (`0o7777` mask), e.g. `0o755`.
+    pub mode: u32,
+    /// Owner's user name, or the numeric uid when it doesn't resolve.
+    pub owner: String,
+    /// Group name, or the numeric gid when it doesn't resolve.
+    pub group: String,
+    pub uid: u32,
+    pub gid: u32,
+    pub is_directory: bool,
+}
+
+/// How many items a recursive change would touch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct PermissionsEstimate {
+    /// Items found, the folder itself included. Symlinks aren't counted (they're skipped).
+    pub entry_count: usize,
+    /// Counting stopped at the cap, so the tree holds at least `entry_count` items.
+    pub capped: bool,
+}
+
+/// What `set_permissions` did.
+#[derive(Debug, Clone, Serialize, specta::Type)]
+#[serde(tag = "kind", rename_all = "snake_case", rename_all_fields = "camelCase")]
+pub enum SetPermissionsResult {
+    /// A single item (or a recursive request on a file) was changed; here's its new state.
+    Applied { permissions: PermissionsInfo },
+    /// A recursive request that wasn't confirmed yet. Nothing changed; call again with
+    /// `confirmed` once the user has seen the count.
+    NeedsConfirmation { estimate: PermissionsEstimate },
+    /// The recursive change is running as a managed op; follow it through the `write-*` events.
+    Started { operation_id: String },
+}
+
+/// Reads the mode, owner, and group of `path` (a symlink's own, not its target's).
+pub fn get_permissions(path: &Path) -> Result<PermissionsInfo, String> {
+    let meta = fs::symlink_metadata(path).map_err(|e| format!("Couldn't read '{}': {e}", path.display()))?;
+    Ok(info_from(&meta))
+}
+
+fn info_from(meta: &fs::Metadata) -> PermissionsInfo {
+    PermissionsInfo {
+        mode: meta.mode() & MODE_MASK,
+        owner: get_owner_name(meta.uid()),
+        group: get_group_name(meta.gid()),
+        uid: meta.uid(),
+        gid: meta.gid(),
+        is_directory: meta.is_dir(),
+    }
+}
+
+/// Checks `mode` and `path` before any change. Returns whether `path` is a folder.
+pub(super) fn validate_target(path: &Path, mode: u32) -> Result<bool, WriteOperationError> {
+    let display = || path.display().to_string();
+    if mode & !MODE_MASK != 0 {
+        return Err(WriteOperationError::IoError {
+            path: display(),
+            message: format!("{mode:o} isn't a valid permission mode"),
+        });
+    }
+    if archive::path_is_inside_archive(path) {
+        return Err(WriteOperationError::ReadOnlyDevice {
+            path: display(),
+            device_name: None,
+        });
+    }
+    let meta = fs::symlink_metadata(path).with_path(path)?;
+    if meta.file_type().is_symlink() {
+        return Err(WriteOperationError::IoError {
+            path: display(),
+            message: "Symbolic links have no permissions of their own".to_string(),
+        });
+    }
+    Ok(meta.is_dir())
+}
+
+fn apply_mode(path: &Path, mode: u32) -> Result<(), WriteOperationError> {
+    fs::set_permissions(path, fs::Permissions::from_mode(mode)).with_path(path)
+}
+
+/// Pushes the changed entry into open listings of its folder.
+fn report_changed(path: &Path) {
+    let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) else {
+        return;
+    };
+    let Ok(entry) = get_single_entry(path) else {
+        return;
+    };
+    let volume_id = get_volume_manager()
+        .containing(parent)
+        .map(|(id, _)| id)
+        .unwrap_or_else(|| DEFAULT_VOLUME_ID.to_string());
+    notify_directory_changed(&volume_id, parent, DirectoryChange::Modified(entry));
+}
+
+/// Changes one item's mode and returns its new state. The item must already be validated.
+pub(super) fn set_single(path: &Path, mode: u32) -> Result<PermissionsInfo, WriteOperationError> {
+    apply_mode(path, mode)?;
+    report_changed(path);
+    let meta = fs::symlink_metadata(path).with_path(path)?;
+    Ok(info_from(&meta))
+}
+
+fn count_entries(path: &Path, cap: usize) -> PermissionsEstimate {
+    let mut entry_count = 0;
+    for entry in walkdir::WalkDir::new(path).follow_links(false).into_iter().flatten() {
+        if entry.file_type().is_symlink() {
+            continue;
+        }
+        entry_count += 1;
+        if entry_count >= cap {
+            return PermissionsEstimate {
+                entry_count,
+                capped: true,
+            };
+        }
+    }
+    PermissionsEstimate {
+        entry_count,
+        capped: false,
+    }
+}
+
+/// Counts what a recursive change of `path` would touch, stopping at a cap. Walks the tree, so
+/// call it off the main thread.
+pub fn estimate_permissions_change(path: &Path) -> PermissionsEstimate {
+    count_entries(path, ESTIMATE_CAP)
+}
+
+fn cancelled(events: &dyn OperationEventSink, operation_id: &str, files_processed: usize) -> WriteOperationError {
+    events.emit_cancelled(WriteCancelledEvent {
+        operation_id: operation_id.to_string(),
+        operation_type: WriteOperationType::SetPermissions,
+        files_processed,
+        // The previous modes aren't kept, so there's nothing to roll back to.
+        rolled_back: false,
+    });
+    WriteOperationError::Cancelled {
+        message: "Operation cancelled by user".to_string(),
+    }
+}
+
+/// Sets `mode` on `root` and everything under it, deepest first, skipping symlinks. Emits scan
+/// progress while listing the tree, then `SettingPermissions` progress per item. With
+/// `continue_on_error`, items that fail (typically not owned by the user) are collected into
+/// `WriteCompleteEvent::errors` and the rest still change; otherwise the first failure ends the op.
+pub(super) fn set_permissions_recursive_inner(
+    events: &dyn OperationEventSink,
+    operation_id: &str,
+    state: &WriteOperationState,
+    root: &Path,
+    mode: u32,
+    continue_on_error: bool,
+) -> Result<(), WriteOperationError> {
+    let mut errors = Vec::new();
+    let mut targets: Vec<PathBuf> = Vec::new();
+    let mut last_progress_time = Instant::now();
+
+    // Phase 1: list the tree. `contents_first` yields children before their folder.
+    for entry in walkdir::WalkDir::new(root).follow_links(false).contents_first(true) {
+        if is_cancelled(&state.intent) {
+            return Err(cancelled(events, operation_id, 0));
+        }
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(e) => {
+                let path = e.path().unwrap_or(root).to_path_buf();
+                let error = match e.into_io_error() {
+                    Some(io) => classify_io_error(&io, path.display().to_string()),
+                    // Not following links, so the only non-I/O walk error can't really happen.
+                    None => WriteOperationError::SymlinkLoop {
+                        path: path.display().to_string(),
+                    },
+                };
+                if !continue_on_error {
+                    return Err(error);
+                }
+                errors.push(error);
+                continue;
+            }
+        };
+        if entry.file_type().is_symlink() {
+            continue;
+        }
+        targets.push(entry.into_path());
+        if last_progress_time.elapsed() >= state.progress_interval {
+            events.emit_scan_progress(ScanProgressEvent {
+                operation_id: operation_id.to_string(),
+                operation_type: WriteOperationType::SetPermissions,
+                files_found: targets.len(),
+                bytes_found: 0,
+                conflicts_found: 0,
+                current_path: targets.last().map(|p| p.display().to_string()),
+            });
+            last_progress_time = Instant::now();
+        }
+    }
+
+    // Phase 2: apply. No bytes move, so byte counts stay 0.
+    let files_total = targets.len();
+    state.emit_progress_via_sink(
+        events,
+        WriteProgressEvent::new(
+            operation_id.to_string(),
+            WriteOperationType::SetPermissions,
+            WriteOperationPhase::SettingPermissions,
+            None,
+            0,
+            files_total,
+            0,
+            0,
+        ),
+    );
+    update_operation_status(
+        operation_id,
+        WriteOperationPhase::SettingPermissions,
+        None,
+        0,
+        files_total,
+        0,
+        0,
+    );
+
+    let mut files_done = 0;
+    for path in &targets {
+        if is_cancelled(&state.intent) {
+            return Err(cancelled(events, operation_id, files_done));
+        }
+        state.pause_gate.wait_while_paused_sync(&state.intent);
+
+        match apply_mode(path, mode) {
+            Ok(()) => files_done += 1,
+            Err(e) if continue_on_error => errors.push(e),
+            Err(e) => return Err(e),
+        }
+
+        if last_progress_time.elapsed() >= state.progress_interval {
+            let current_file = path.file_name().map(|n| n.to_string_lossy().into_owned());
+            state.emit_progress_via_sink(
+                events,
+                WriteProgressEvent::new(
+                    operation_id.to_string(),
+                    WriteOperationType::SetPermissions,
+                    WriteOperationPhase::SettingPermissions,
+                    current_file.clone(),
+                    files_done,
+                    files_total,
+                    0,
+                    0,
+                ),
+            );
+            update_operation_status(
+                operation_id,
+                WriteOperationPhase::SettingPermissions,
+                current_file,
+                files_done,
+                files_total,
+                0,
+                0,
+            );
+            last_progress_time = Instant::now();
+        }
+    }
+
+    // Items inside the tree are picked up by the watcher; the root's row updates right away.
+    report_changed(root);
+    events.emit_complete(WriteCompleteEvent {
+        operation_id: operation_id.to_string(),
+        operation_type: WriteOperationType::SetPermissions,
+        files_processed: files_done,
+        files_skipped: 0,
+        bytes_processed: 0,
+        errors,
+        files_verified: 0,
+        resumed_reads: 0,
+    });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::Ordering;
+
+    use super::*;
+    use crate::file_system::write_operations::operation_intent::OperationIntent;
+    use crate::file_system::write_operations::test_support::TestOperationGuard;
+    use crate::file_system::write_operations::types::CollectorEventSink;
+    use crate::ignore_poison::IgnorePoison;
+
+    fn mode_of(path: &Path) -> u32 {
+        fs::symlink_metadata(path).expect("metadata").mode() & MODE_MASK
+    }
+
+    /// `root/a.txt`, `root/sub/b.txt`, and `root/link` pointing at `outside.txt` beside `root`.
+    fn tree(tmp: &Path) -> PathBuf {
+        let root = tmp.join("root");
+        fs::create_dir_all(root.join("sub")).expect("mkdir");
+        fs::write(root.join("a.txt"), b"a").expect("write a");
+        fs::write(root.join("sub/b.txt"), b"b").expect("write b");
+        fs::write(tmp.join("outside.txt"), b"o").expect("write outside");
+        fs::set_permissions(tmp.join("outside.txt"), fs::Permissions::from_mode(0o644)).expect("chmod outside");
+        std::os::unix::fs::symlink(tmp.join("outside.txt"), root.join("link")).expect("symlink");
+        root
+    }
+
+    #[test]
+    fn estimate_counts_items_but_not_links_and_stops_at_the_cap() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let root = tree(tmp.path());
+
+        let estimate = estimate_permissions_change(&root);
+        assert_eq!(estimate.entry_count, 4, "root, sub, a.txt, b.txt");
+        assert!(!estimate.capped);
+
+        let capped = count_entries(&root, 2);
+        assert_eq!(capped.entry_count, 2);
+        assert!(capped.capped);
+    }
+
+    #[test]
+    fn recursive_change_applies_to_the_tree_and_skips_links() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let root = tree(tmp.path());
+        let op = TestOperationGuard::register("chmod-tree");
+        let sink = CollectorEventSink::new();
+
+        set_permissions_recursive_inner(&sink, op.id(), op.state(), &root, 0o700, false).expect("chmod");
+
+        for path in [&root, &root.join("sub"), &root.join("a.txt"), &root.join("sub/b.txt")] {
+            assert_eq!(mode_of(path), 0o700, "{}", path.display());
+        }
+        assert_eq!(
+            mode_of(&tmp.path().join("outside.txt")),
+            0o644,
+            "the link's target is untouched"
+        );
+        let complete = sink.complete.lock_ignore_poison();
+        assert_eq!(complete.len(), 1);
+        assert_eq!(complete[0].files_processed, 4);
+        assert!(complete[0].errors.is_empty());
+    }
+
+    #[test]
+    fn cancelled_change_emits_cancelled_and_stops() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let root = tree(tmp.path());
+        fs::set_permissions(root.join("a.txt"), fs::Permissions::from_mode(0o644)).expect("chmod a");
+        let op = TestOperationGuard::register("chmod-cancel");
+        op.state()
+            .intent
+            .store(OperationIntent::Stopped as u8, Ordering::Relaxed);
+        let sink = CollectorEventSink::new();
+
+        let result = set_permissions_recursive_inner(&sink, op.id(), op.state(), &root, 0o600, false);
+
+        assert!(matches!(result, Err(WriteOperationError::Cancelled { .. })));
+        assert_eq!(sink.cancelled.lock_ignore_poison().len(), 1);
+        assert_eq!(mode_of(&root.join("a.txt")), 0o644);
+    }
+
+    #[test]
+    fn validation_rejects_bad_modes_and_links() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let root = tree(tmp.path());
+
+        assert!(validate_target(&root, 0o755).expect("folder"));
+        assert!(!validate_target(&root.join("a.txt"), 0o644).expect("file"));
+        assert!(validate_target(&root.join("a.txt"), 0o10000).is_err());
+        assert!(validate_target(&root.join("link"), 0o644).is_err());
+        assert!(matches!(
+            validate_target(&root.join("missing"), 0o644),
+            Err(WriteOperationError::SourceNotFound { .. })
+        ));
+
+        let info = set_single(&root.join("a.txt"), 0o640).expect("single");
+        assert_eq!(info.mode, 0o640);
+        assert!(!info.is_directory);
+        assert_eq!(get_permissions(&root.join("a.txt")).expect("read").mode, 0o640);
+    }
+}
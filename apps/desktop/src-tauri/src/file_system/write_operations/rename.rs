@@ -26,8 +26,12 @@ use crate::file_system::volume::backends::archive::mutator::Changeset;
 use crate::operation_log::types::{Initiator, OpKind};
 
 mod bulk;
+mod rule;
 
 pub(crate) use bulk::{BulkRenameFingerprint, BulkRenameRow, start_bulk_rename};
+pub(crate) use rule::{
+    BatchRenamePreviewRow, BatchRenameRule, load_batch_rename_sources, preview_batch_rename, start_batch_rename,
+};
 
 /// Result of a rename validity check.
 #[derive(Debug, Clone, serde::Serialize, specta::Type)]
@@ -17,10 +17,11 @@ use uuid::Uuid;
 
 use super::super::event_sinks::OperationEventSink;
 use super::super::manager::{self, OperationDescriptor, OperationSummaryText};
+use super::super::operation_intent::OperationIntent;
 use super::super::state::{WriteOperationState, WriteSettledGuard, is_cancelled, update_operation_status};
 use super::super::types::{
-    WriteCancelledEvent, WriteCompleteEvent, WriteOperationStartResult, WriteOperationType, WriteProgressEvent,
-    WriteSourceItemDoneEvent,
+    WriteCancelledEvent, WriteCompleteEvent, WriteErrorEvent, WriteOperationError, WriteOperationStartResult,
+    WriteOperationType, WriteProgressEvent, WriteSourceItemDoneEvent,
 };
 use crate::file_system::volume::{LaneKey, Volume, rename_local_exclusive};
 use crate::operation_log::types::{EntryType, ExecutionStatus, Initiator, ItemOutcome, OpKind};
@@ -69,11 +70,17 @@ impl BulkRenameOutcome {
 /// Starts one queued, same-volume batch rename. The caller has already resolved
 /// the proposal id and exact accepted-preflight subset; this layer receives only
 /// immutable backend rows, never frontend paths or names.
+///
+/// With `rollback_on_failure`, the batch is all-or-nothing: if any row fails or
+/// is skipped, the rows that did land are renamed back through the same engine
+/// and the operation ends in an error instead of a completion. Ask Cmdr applies
+/// partial batches on purpose, so it passes `false`.
 pub(crate) fn start_bulk_rename(
     events: Arc<dyn OperationEventSink>,
     volume_id: String,
     rows: Vec<BulkRenameRow>,
     initiator: Initiator,
+    rollback_on_failure: bool,
 ) -> Result<WriteOperationStartResult, String> {
     if rows.is_empty() {
         return Err("Choose at least one rename to apply.".to_string());
@@ -135,16 +142,38 @@ pub(crate) fn start_bulk_rename(
                 rows_for_task.len() as u64,
             );
 
-            let run = if uses_local_paths {
-                let rows = rows_for_task.clone();
-                let intent = Arc::clone(&state_for_task.intent);
-                match tokio::task::spawn_blocking(move || bulk_rename_local(&rows, &intent)).await {
-                    Ok(result) => result,
-                    Err(join_error) => BulkRenameRun::failed(rows_for_task.len(), join_error.to_string()),
+            let mut run = run_bulk_rename(
+                &rows_for_task,
+                &volume_id_for_task,
+                uses_local_paths,
+                Arc::clone(&state_for_task.intent),
+            )
+            .await;
+            let failed_row = (rollback_on_failure && !run.cancelled)
+                .then(|| {
+                    run.outcomes
+                        .iter()
+                        .position(|outcome| *outcome != BulkRenameOutcome::Done)
+                })
+                .flatten();
+            if failed_row.is_some() {
+                let (indices, reverse_rows) = reverse_done_rows(&rows_for_task, &run.outcomes);
+                // A fresh intent: a cancel that arrives mid-batch must not strand
+                // the rollback halfway.
+                let intent = Arc::new(AtomicU8::new(OperationIntent::Running as u8));
+                let reverse_run = run_bulk_rename(&reverse_rows, &volume_id_for_task, uses_local_paths, intent).await;
+                for (index, outcome) in indices.into_iter().zip(reverse_run.outcomes) {
+                    if outcome == BulkRenameOutcome::Done {
+                        run.outcomes[index] = BulkRenameOutcome::Skipped;
+                    } else {
+                        log::warn!(
+                            target: "bulk_rename",
+                            "rollback could not restore {}",
+                            rows_for_task[index].source.display()
+                        );
+                    }
                 }
-            } else {
-                bulk_rename_remote(&rows_for_task, &volume_id_for_task, &state_for_task.intent).await
-            };
+            }
 
             if uses_local_paths {
                 for (row, outcome) in rows_for_task.iter().zip(run.outcomes.iter()) {
@@ -167,7 +196,26 @@ pub(crate) fn start_bulk_rename(
                 &rows_for_task,
                 &run.outcomes,
             );
-            if run.cancelled {
+            if let Some(failed_row) = failed_row {
+                let rolled_back = run.processed() == 0;
+                let message = if rolled_back {
+                    "The batch was rolled back because this file couldn't be renamed.".to_string()
+                } else {
+                    format!(
+                        "This file couldn't be renamed, and {} already renamed files couldn't be restored.",
+                        run.processed()
+                    )
+                };
+                events_for_task.emit_error(WriteErrorEvent::new(
+                    operation_id_for_task.clone(),
+                    WriteOperationType::Rename,
+                    WriteOperationError::WriteError {
+                        path: rows_for_task[failed_row].source.to_string_lossy().to_string(),
+                        message,
+                    },
+                ));
+                super::super::journal::finalize_op(&operation_id_for_task, OpKind::Rename, ExecutionStatus::Failed);
+            } else if run.cancelled {
                 events_for_task.emit_cancelled(WriteCancelledEvent {
                     operation_id: operation_id_for_task.clone(),
                     operation_type: WriteOperationType::Rename,
@@ -182,6 +230,9 @@ pub(crate) fn start_bulk_rename(
                     files_processed: rows_for_task.len(),
                     files_skipped: run.skipped(),
                     bytes_processed: 0,
+                    errors: Vec::new(),
+                    files_verified: 0,
+                    resumed_reads: 0,
                 });
                 super::super::journal::finalize_op(&operation_id_for_task, OpKind::Rename, ExecutionStatus::Done);
             }
@@ -227,6 +278,51 @@ impl BulkRenameRun {
     }
 }
 
+async fn run_bulk_rename(
+    rows: &[BulkRenameRow],
+    volume_id: &str,
+    uses_local_paths: bool,
+    intent: Arc<AtomicU8>,
+) -> BulkRenameRun {
+    if uses_local_paths {
+        let rows_for_blocking = rows.to_vec();
+        match tokio::task::spawn_blocking(move || bulk_rename_local(&rows_for_blocking, &intent)).await {
+            Ok(result) => result,
+            Err(join_error) => BulkRenameRun::failed(rows.len(), join_error.to_string()),
+        }
+    } else {
+        bulk_rename_remote(rows, volume_id, &intent).await
+    }
+}
+
+/// Builds the undo batch for an all-or-nothing run: every row that landed,
+/// pointed back at its source, with the fingerprint re-keyed to where the file
+/// now lives. Returns the original row indices alongside, in the same order.
+fn reverse_done_rows(rows: &[BulkRenameRow], outcomes: &[BulkRenameOutcome]) -> (Vec<usize>, Vec<BulkRenameRow>) {
+    rows.iter()
+        .zip(outcomes)
+        .enumerate()
+        .filter(|(_, (row, outcome))| **outcome == BulkRenameOutcome::Done && row.source != row.destination)
+        .map(|(index, (row, _))| {
+            let expected_fingerprint = match &row.expected_fingerprint {
+                BulkRenameFingerprint::Remote { size, modified, .. } => BulkRenameFingerprint::Remote {
+                    normalized_path: normalized_path(&row.destination),
+                    size: *size,
+                    modified: *modified,
+                },
+                local => local.clone(),
+            };
+            let reverse = BulkRenameRow {
+                row_id: format!("{}-rollback", row.row_id),
+                source: row.destination.clone(),
+                destination: row.source.clone(),
+                expected_fingerprint,
+            };
+            (index, reverse)
+        })
+        .unzip()
+}
+
 /// One collision-safe unit in a batch rename. Direct steps consume a free
 /// destination. A cycle rotates through one temporary name, while a case-only
 /// change uses one because the volume may treat both spellings as the same key.
@@ -661,6 +757,30 @@ fn note_rename_write(from: &Path, to: &Path) {
     crate::downloads::note_pending_write_for_cmdr(to);
 }
 
+/// Captures the identity a batch rename row is checked against at execution
+/// time, for callers (like the rule-based batch rename) that build rows from
+/// a fresh preview rather than a stored preflight. `None` for directories and
+/// missing paths, which the bulk engine doesn't rename.
+pub(crate) async fn capture_bulk_rename_fingerprint(volume_id: &str, path: &Path) -> Option<BulkRenameFingerprint> {
+    if volume_id == "root" {
+        let path = path.to_path_buf();
+        return tokio::task::spawn_blocking(move || local_fingerprint(&path))
+            .await
+            .ok()
+            .flatten();
+    }
+    let volume = crate::file_system::get_volume_manager().get(volume_id)?;
+    let metadata = volume.get_metadata(path).await.ok()?;
+    if metadata.is_directory {
+        return None;
+    }
+    Some(BulkRenameFingerprint::Remote {
+        normalized_path: normalized_path(path),
+        size: metadata.size,
+        modified: metadata.modified_at.map(|value| value as i64),
+    })
+}
+
 fn local_fingerprint(path: &Path) -> Option<BulkRenameFingerprint> {
     let metadata = std::fs::symlink_metadata(path).ok()?;
     if metadata.file_type().is_dir() {
@@ -295,3 +295,33 @@ fn bulk_local_rename_preserves_a_destination_created_after_review() {
     assert_no_staging_paths(&tmp);
     let _ = fs::remove_dir_all(&tmp);
 }
+
+#[test]
+fn reverse_rows_undo_only_the_renames_that_landed() {
+    let tmp = create_test_dir("rollback");
+    let first = tmp.join("a.txt");
+    let second = tmp.join("b.txt");
+    fs::write(&first, "a").expect("write fixture");
+    fs::write(&second, "b").expect("write fixture");
+    let rows = vec![
+        local_row("first", first.clone(), tmp.join("a-renamed.txt")),
+        local_row("second", second.clone(), tmp.join("b-renamed.txt")),
+    ];
+    fs::write(&second, "changed after review").expect("change fixture after fingerprint");
+
+    let run = bulk_rename_local(&rows, &AtomicU8::new(OperationIntent::Running as u8));
+    assert_eq!(run.outcomes, vec![BulkRenameOutcome::Done, BulkRenameOutcome::Skipped]);
+
+    let (indices, reverse) = reverse_done_rows(&rows, &run.outcomes);
+    assert_eq!(indices, vec![0]);
+    let reverse_run = bulk_rename_local(&reverse, &AtomicU8::new(OperationIntent::Running as u8));
+
+    assert_eq!(reverse_run.outcomes, vec![BulkRenameOutcome::Done]);
+    assert_eq!(fs::read_to_string(&first).expect("read restored source"), "a");
+    assert!(
+        !tmp.join("a-renamed.txt").exists(),
+        "rollback must remove the renamed name"
+    );
+    assert_no_staging_paths(&tmp);
+    let _ = fs::remove_dir_all(&tmp);
+}
@@ -0,0 +1,450 @@
+//! Rule-driven batch rename: turns a selection plus a [`BatchRenameRule`] into
+//! reviewed `(old, new)` rows, then hands the accepted rows to the same
+//! collision-safe bulk engine Ask Cmdr uses (`bulk.rs`).
+//!
+//! The rule pipeline, per file, in this order:
+//!
+//! 1. Split the name into stem + extension (the last `.`; a leading dot is part
+//!    of the stem, so `.bashrc` has no extension).
+//! 2. Find/replace on the stem (literal or regex, optionally on the extension too).
+//! 3. Case transform on the stem.
+//! 4. Render the template. Tokens: `{name}` (the transformed stem), `{ext}` (the
+//!    extension WITH its dot, empty when none), `{n}` (the counter, zero-padded),
+//!    `{date}` (mtime as `YYYY-MM-DD`), `{time}` (mtime as `HH-MM-SS`). Dates come
+//!    from the filesystem mtime in local time, never EXIF.
+//!
+//! [`plan_batch_rename`] is pure (no I/O) so the rule semantics and the
+//! duplicate-target check are unit-testable; [`preview_batch_rename`] adds the
+//! per-target `check_rename_validity_impl` pass against the real directory.
+//! [`start_batch_rename`] re-runs that preview, refuses if any row has a
+//! problem, and starts the bulk engine with rollback on, so the batch lands
+//! whole or not at all.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use regex::RegexBuilder;
+
+use super::super::event_sinks::OperationEventSink;
+use super::super::types::WriteOperationStartResult;
+use super::bulk::{BulkRenameRow, capture_bulk_rename_fingerprint, start_bulk_rename};
+use crate::file_system::validation::ValidationError;
+use crate::operation_log::types::Initiator;
+
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum CaseTransform {
+    #[default]
+    Keep,
+    Lower,
+    Upper,
+    /// First letter of each whitespace-, `-`-, or `_`-separated word upper, the
+    /// rest lower.
+    Title,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct FindReplace {
+    pub find: String,
+    /// May reference regex groups (`$1`, `${name}`) when `regex` is set.
+    pub replace: String,
+    #[serde(default)]
+    pub regex: bool,
+    #[serde(default)]
+    pub case_sensitive: bool,
+    /// Also apply to the extension (default: stem only, so `.jpg` survives).
+    #[serde(default)]
+    pub include_extension: bool,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase", default)]
+pub(crate) struct BatchRenameRule {
+    pub find_replace: Option<FindReplace>,
+    pub case: CaseTransform,
+    /// Output name template; see the module docs for tokens.
+    pub template: String,
+    pub counter_start: u64,
+    pub counter_step: u64,
+    /// Minimum digits for `{n}`; shorter numbers are zero-padded.
+    pub counter_padding: usize,
+}
+
+impl Default for BatchRenameRule {
+    fn default() -> Self {
+        Self {
+            find_replace: None,
+            case: CaseTransform::Keep,
+            template: "{name}{ext}".to_string(),
+            counter_start: 1,
+            counter_step: 1,
+            counter_padding: 1,
+        }
+    }
+}
+
+/// One file going into a batch rename, in the order the counter should follow
+/// (the listing's current sort order, as the frontend sends it).
+#[derive(Debug, Clone)]
+pub(crate) struct BatchRenameSource {
+    pub path: PathBuf,
+    pub modified_at: Option<u64>,
+}
+
+/// Why a preview row can't be applied.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, specta::Type)]
+#[serde(rename_all = "camelCase", tag = "kind")]
+pub(crate) enum BatchRenameProblem {
+    /// The rule itself is broken (for example, an invalid regex). Every row gets it.
+    InvalidRule { message: String },
+    /// The new name fails filename or path-length validation.
+    InvalidName { error: ValidationError },
+    /// Another row in this batch maps to the same target.
+    DuplicateTarget { other_source: String },
+    /// A file outside the batch already has the target name.
+    TargetExists,
+}
+
+#[derive(Debug, Clone, serde::Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BatchRenamePreviewRow {
+    pub source_path: String,
+    pub new_name: String,
+    pub new_path: String,
+    pub problem: Option<BatchRenameProblem>,
+}
+
+impl BatchRenamePreviewRow {
+    pub fn is_unchanged(&self) -> bool {
+        self.source_path == self.new_path
+    }
+}
+
+/// Renders the new names and flags in-batch duplicates. No I/O.
+pub(crate) fn plan_batch_rename(sources: &[BatchRenameSource], rule: &BatchRenameRule) -> Vec<BatchRenamePreviewRow> {
+    let matcher = match compile_find(rule.find_replace.as_ref()) {
+        Ok(matcher) => matcher,
+        Err(message) => {
+            return sources
+                .iter()
+                .map(|source| {
+                    let path = source.path.to_string_lossy().to_string();
+                    BatchRenamePreviewRow {
+                        new_name: file_name(&source.path),
+                        new_path: path.clone(),
+                        source_path: path,
+                        problem: Some(BatchRenameProblem::InvalidRule {
+                            message: message.clone(),
+                        }),
+                    }
+                })
+                .collect();
+        }
+    };
+
+    let mut rows: Vec<BatchRenamePreviewRow> = sources
+        .iter()
+        .enumerate()
+        .map(|(index, source)| {
+            let new_name = render_name(
+                &file_name(&source.path),
+                index,
+                source.modified_at,
+                rule,
+                matcher.as_ref(),
+            );
+            let new_path = source.path.with_file_name(&new_name);
+            BatchRenamePreviewRow {
+                source_path: source.path.to_string_lossy().to_string(),
+                new_name,
+                new_path: new_path.to_string_lossy().to_string(),
+                problem: None,
+            }
+        })
+        .collect();
+
+    // Two rows landing on one target is an error for both: applying either would
+    // clobber the other's file. Compared the way the bulk engine compares paths
+    // (case- and normalization-insensitive on macOS).
+    let mut first_claim: HashMap<String, usize> = HashMap::new();
+    for index in 0..rows.len() {
+        let key = crate::indexing::store::normalize_for_comparison(&rows[index].new_path);
+        match first_claim.get(&key) {
+            Some(&other) => {
+                let other_source = rows[other].source_path.clone();
+                let this_source = rows[index].source_path.clone();
+                rows[index]
+                    .problem
+                    .get_or_insert(BatchRenameProblem::DuplicateTarget { other_source });
+                rows[other].problem.get_or_insert(BatchRenameProblem::DuplicateTarget {
+                    other_source: this_source,
+                });
+            }
+            None => {
+                first_claim.insert(key, index);
+            }
+        }
+    }
+    rows
+}
+
+/// [`plan_batch_rename`] plus a `check_rename_validity_impl` pass per changed
+/// row: name validation, path length, and conflicts with files OUTSIDE the
+/// batch. A sibling that is itself being renamed away doesn't count as a
+/// conflict; the bulk engine orders chains and swaps so it's freed first.
+pub(crate) async fn preview_batch_rename(
+    sources: &[BatchRenameSource],
+    rule: &BatchRenameRule,
+    volume_id: &str,
+) -> Vec<BatchRenamePreviewRow> {
+    let mut rows = plan_batch_rename(sources, rule);
+    let leaving: HashSet<String> = rows
+        .iter()
+        .filter(|row| !row.is_unchanged())
+        .map(|row| crate::indexing::store::normalize_for_comparison(&row.source_path))
+        .collect();
+
+    for row in rows
+        .iter_mut()
+        .filter(|row| row.problem.is_none() && !row.is_unchanged())
+    {
+        let source = Path::new(&row.source_path);
+        let dir = source
+            .parent()
+            .unwrap_or_else(|| Path::new(""))
+            .to_string_lossy()
+            .to_string();
+        let old_name = file_name(source);
+        match super::check_rename_validity_impl(dir, old_name, row.new_name.clone(), volume_id.to_string()).await {
+            Ok(validity) if !validity.valid => {
+                row.problem = validity
+                    .error
+                    .map(|error| BatchRenameProblem::InvalidName { error })
+                    .or(Some(BatchRenameProblem::TargetExists));
+            }
+            Ok(validity) if validity.has_conflict && !validity.is_case_only_rename => {
+                let target = crate::indexing::store::normalize_for_comparison(&row.new_path);
+                if !leaving.contains(&target) {
+                    row.problem = Some(BatchRenameProblem::TargetExists);
+                }
+            }
+            Ok(_) => {}
+            Err(message) => row.problem = Some(BatchRenameProblem::InvalidRule { message }),
+        }
+    }
+    rows
+}
+
+/// Reads each path's mtime for the `{date}`/`{time}` tokens, keeping the
+/// caller's order (it drives `{n}`). A path that can't be read keeps `None`;
+/// the preview's validity pass reports it.
+pub(crate) async fn load_batch_rename_sources(paths: Vec<PathBuf>, volume_id: &str) -> Vec<BatchRenameSource> {
+    if volume_id == "root" {
+        let for_blocking = paths.clone();
+        let loaded = tokio::task::spawn_blocking(move || {
+            for_blocking
+                .into_iter()
+                .map(|path| {
+                    let modified_at = std::fs::symlink_metadata(&path)
+                        .and_then(|metadata| metadata.modified())
+                        .ok()
+                        .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
+                        .map(|time| time.as_secs());
+                    BatchRenameSource { path, modified_at }
+                })
+                .collect()
+        })
+        .await;
+        return loaded.unwrap_or_else(|_| {
+            paths
+                .into_iter()
+                .map(|path| BatchRenameSource {
+                    path,
+                    modified_at: None,
+                })
+                .collect()
+        });
+    }
+
+    let volume = crate::file_system::get_volume_manager().get(volume_id);
+    let mut sources = Vec::with_capacity(paths.len());
+    for path in paths {
+        let modified_at = match &volume {
+            Some(volume) => volume
+                .get_metadata(&path)
+                .await
+                .ok()
+                .and_then(|entry| entry.modified_at),
+            None => None,
+        };
+        sources.push(BatchRenameSource { path, modified_at });
+    }
+    sources
+}
+
+/// Applies a rule as one all-or-nothing managed operation. The preview is
+/// recomputed here rather than trusted from the frontend, so a file that
+/// appeared since the user's review still blocks the batch.
+pub(crate) async fn start_batch_rename(
+    events: Arc<dyn OperationEventSink>,
+    sources: Vec<BatchRenameSource>,
+    rule: BatchRenameRule,
+    volume_id: String,
+) -> Result<WriteOperationStartResult, String> {
+    let preview = preview_batch_rename(&sources, &rule, &volume_id).await;
+    if let Some(row) = preview.iter().find(|row| row.problem.is_some()) {
+        return Err(format!(
+            "Can't rename \"{}\" to \"{}\". Check the preview for details.",
+            file_name(Path::new(&row.source_path)),
+            row.new_name
+        ));
+    }
+
+    let mut rows = Vec::new();
+    for (index, row) in preview.iter().filter(|row| !row.is_unchanged()).enumerate() {
+        let source = PathBuf::from(&row.source_path);
+        let expected_fingerprint = capture_bulk_rename_fingerprint(&volume_id, &source)
+            .await
+            .ok_or_else(|| format!("\"{}\" is a folder or no longer exists.", file_name(&source)))?;
+        rows.push(BulkRenameRow {
+            row_id: format!("rule-{index}"),
+            destination: PathBuf::from(&row.new_path),
+            source,
+            expected_fingerprint,
+        });
+    }
+    if rows.is_empty() {
+        return Err("This rule doesn't change any names.".to_string());
+    }
+    start_bulk_rename(events, volume_id, rows, Initiator::User, true)
+}
+
+type FindMatcher = regex::Regex;
+
+/// Compiles the find pattern once per batch. Literal finds go through
+/// `regex::escape`, so both modes share one replace path (and literal mode
+/// gets case-insensitivity for free).
+fn compile_find(find_replace: Option<&FindReplace>) -> Result<Option<FindMatcher>, String> {
+    let Some(find_replace) = find_replace.filter(|fr| !fr.find.is_empty()) else {
+        return Ok(None);
+    };
+    let pattern = if find_replace.regex {
+        find_replace.find.clone()
+    } else {
+        regex::escape(&find_replace.find)
+    };
+    RegexBuilder::new(&pattern)
+        .case_insensitive(!find_replace.case_sensitive)
+        .build()
+        .map(Some)
+        .map_err(|e| format!("Invalid pattern: {e}"))
+}
+
+fn render_name(
+    original: &str,
+    index: usize,
+    modified_at: Option<u64>,
+    rule: &BatchRenameRule,
+    matcher: Option<&FindMatcher>,
+) -> String {
+    let (mut stem, mut ext) = split_extension(original);
+    if let (Some(matcher), Some(find_replace)) = (matcher, rule.find_replace.as_ref()) {
+        // Literal replacements must not expand `$` as a group reference.
+        let replace = if find_replace.regex {
+            find_replace.replace.clone()
+        } else {
+            find_replace.replace.replace('$', "$$")
+        };
+        if find_replace.include_extension {
+            let replaced = matcher
+                .replace_all(&format!("{stem}{ext}"), replace.as_str())
+                .into_owned();
+            (stem, ext) = split_extension(&replaced);
+        } else {
+            stem = matcher.replace_all(&stem, replace.as_str()).into_owned();
+        }
+    }
+    let stem = apply_case(&stem, &rule.case);
+
+    let counter = rule
+        .counter_start
+        .saturating_add((index as u64).saturating_mul(rule.counter_step));
+    let counter = format!("{counter:0width$}", width = rule.counter_padding);
+    let local_mtime = modified_at
+        .and_then(|secs| i64::try_from(secs).ok())
+        .and_then(|secs| chrono::DateTime::from_timestamp(secs, 0))
+        .map(|utc| utc.with_timezone(&chrono::Local));
+    let date = local_mtime
+        .map(|t| t.format("%Y-%m-%d").to_string())
+        .unwrap_or_default();
+    let time = local_mtime
+        .map(|t| t.format("%H-%M-%S").to_string())
+        .unwrap_or_default();
+
+    let tokens = [
+        ("{name}", stem.as_str()),
+        ("{ext}", ext.as_str()),
+        ("{n}", counter.as_str()),
+        ("{date}", date.as_str()),
+        ("{time}", time.as_str()),
+    ];
+    // One left-to-right pass, so a value that itself reads like a token (a file
+    // named `report {n}.txt`) lands verbatim instead of being expanded again.
+    let mut out = String::with_capacity(rule.template.len() + original.len());
+    let mut rest = rule.template.as_str();
+    while let Some(open) = rest.find('{') {
+        out.push_str(&rest[..open]);
+        rest = &rest[open..];
+        match tokens.iter().find(|(token, _)| rest.starts_with(token)) {
+            Some((token, value)) => {
+                out.push_str(value);
+                rest = &rest[token.len()..];
+            }
+            None => {
+                out.push('{');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Splits at the last `.`, keeping the dot on the extension. A name whose only
+/// dot is the first character (`.bashrc`) has no extension.
+fn split_extension(name: &str) -> (String, String) {
+    match name.rfind('.') {
+        Some(dot) if dot > 0 => (name[..dot].to_string(), name[dot..].to_string()),
+        _ => (name.to_string(), String::new()),
+    }
+}
+
+fn apply_case(stem: &str, case: &CaseTransform) -> String {
+    match case {
+        CaseTransform::Keep => stem.to_string(),
+        CaseTransform::Lower => stem.to_lowercase(),
+        CaseTransform::Upper => stem.to_uppercase(),
+        CaseTransform::Title => {
+            let mut out = String::with_capacity(stem.len());
+            let mut at_word_start = true;
+            for c in stem.chars() {
+                if at_word_start {
+                    out.extend(c.to_uppercase());
+                } else {
+                    out.extend(c.to_lowercase());
+                }
+                at_word_start = c.is_whitespace() || c == '-' || c == '_';
+            }
+            out
+        }
+    }
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name().unwrap_or_default().to_string_lossy().to_string()
+}
+
+#[cfg(test)]
+mod tests;
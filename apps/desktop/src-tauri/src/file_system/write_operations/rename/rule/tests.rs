@@ -0,0 +1,199 @@
+use super::*;
+
+fn sources(names: &[&str]) -> Vec<BatchRenameSource> {
+    names
+        .iter()
+        .map(|name| BatchRenameSource {
+            path: PathBuf::from("/photos").join(name),
+            modified_at: None,
+        })
+        .collect()
+}
+
+fn new_names(rows: &[BatchRenamePreviewRow]) -> Vec<&str> {
+    rows.iter().map(|row| row.new_name.as_str()).collect()
+}
+
+#[test]
+fn default_rule_keeps_every_name() {
+    let rows = plan_batch_rename(&sources(&["a.jpg", ".bashrc", "notes"]), &BatchRenameRule::default());
+
+    assert_eq!(new_names(&rows), vec!["a.jpg", ".bashrc", "notes"]);
+    assert!(rows.iter().all(|row| row.is_unchanged() && row.problem.is_none()));
+}
+
+#[test]
+fn literal_find_replace_touches_only_the_stem_and_ignores_dollar_signs() {
+    let rule = BatchRenameRule {
+        find_replace: Some(FindReplace {
+            find: "jpg".to_string(),
+            replace: "$1 photo".to_string(),
+            regex: false,
+            case_sensitive: false,
+            include_extension: false,
+        }),
+        ..BatchRenameRule::default()
+    };
+
+    let rows = plan_batch_rename(&sources(&["JPG export.jpg"]), &rule);
+
+    assert_eq!(new_names(&rows), vec!["$1 photo export.jpg"]);
+}
+
+#[test]
+fn regex_find_replace_expands_capture_groups() {
+    let rule = BatchRenameRule {
+        find_replace: Some(FindReplace {
+            find: r"^IMG_(\d+)$".to_string(),
+            replace: "holiday-$1".to_string(),
+            regex: true,
+            case_sensitive: true,
+            include_extension: false,
+        }),
+        ..BatchRenameRule::default()
+    };
+
+    let rows = plan_batch_rename(&sources(&["IMG_0042.jpg", "img_0043.jpg"]), &rule);
+
+    assert_eq!(new_names(&rows), vec!["holiday-0042.jpg", "img_0043.jpg"]);
+}
+
+#[test]
+fn invalid_regex_flags_every_row_and_keeps_names() {
+    let rule = BatchRenameRule {
+        find_replace: Some(FindReplace {
+            find: "(unclosed".to_string(),
+            replace: String::new(),
+            regex: true,
+            case_sensitive: false,
+            include_extension: false,
+        }),
+        ..BatchRenameRule::default()
+    };
+
+    let rows = plan_batch_rename(&sources(&["a.txt", "b.txt"]), &rule);
+
+    assert!(rows.iter().all(|row| row.is_unchanged()));
+    assert!(
+        rows.iter()
+            .all(|row| matches!(row.problem, Some(BatchRenameProblem::InvalidRule { .. })))
+    );
+}
+
+#[test]
+fn case_transforms_apply_to_the_stem() {
+    let names = ["my-holiday_photo one.JPG"];
+    let render = |case| {
+        let rule = BatchRenameRule {
+            case,
+            ..BatchRenameRule::default()
+        };
+        plan_batch_rename(&sources(&names), &rule)[0].new_name.clone()
+    };
+
+    assert_eq!(render(CaseTransform::Lower), "my-holiday_photo one.JPG");
+    assert_eq!(render(CaseTransform::Upper), "MY-HOLIDAY_PHOTO ONE.JPG");
+    assert_eq!(render(CaseTransform::Title), "My-Holiday_Photo One.JPG");
+}
+
+#[test]
+fn counter_follows_start_step_and_padding() {
+    let rule = BatchRenameRule {
+        template: "trip {n}{ext}".to_string(),
+        counter_start: 8,
+        counter_step: 2,
+        counter_padding: 3,
+        ..BatchRenameRule::default()
+    };
+
+    let rows = plan_batch_rename(&sources(&["x.jpg", "y.jpg", "z.png"]), &rule);
+
+    assert_eq!(new_names(&rows), vec!["trip 008.jpg", "trip 010.jpg", "trip 012.png"]);
+}
+
+#[test]
+fn token_text_inside_a_name_is_not_expanded() {
+    let rule = BatchRenameRule {
+        template: "{name} {n}{ext}".to_string(),
+        ..BatchRenameRule::default()
+    };
+
+    let rows = plan_batch_rename(&sources(&["report {n}.txt", "{ext}.md"]), &rule);
+
+    assert_eq!(new_names(&rows), vec!["report {n} 1.txt", "{ext} 2.md"]);
+}
+
+#[test]
+fn date_tokens_come_from_mtime_and_are_empty_without_one() {
+    let rule = BatchRenameRule {
+        template: "{date}_{time}{ext}".to_string(),
+        ..BatchRenameRule::default()
+    };
+    let mtime = 1_700_000_000;
+    let expected = chrono::DateTime::from_timestamp(mtime as i64, 0)
+        .expect("valid timestamp")
+        .with_timezone(&chrono::Local)
+        .format("%Y-%m-%d_%H-%M-%S.txt")
+        .to_string();
+    let with_mtime = vec![
+        BatchRenameSource {
+            path: PathBuf::from("/photos/a.txt"),
+            modified_at: Some(mtime),
+        },
+        BatchRenameSource {
+            path: PathBuf::from("/photos/b.txt"),
+            modified_at: None,
+        },
+    ];
+
+    let rows = plan_batch_rename(&with_mtime, &rule);
+
+    assert_eq!(rows[0].new_name, expected);
+    assert_eq!(rows[1].new_name, "_.txt");
+}
+
+#[test]
+fn two_sources_mapping_to_one_target_both_report_the_collision() {
+    let rule = BatchRenameRule {
+        template: "same{ext}".to_string(),
+        ..BatchRenameRule::default()
+    };
+
+    let rows = plan_batch_rename(&sources(&["a.txt", "b.txt", "c.md"]), &rule);
+
+    assert_eq!(
+        rows[0].problem,
+        Some(BatchRenameProblem::DuplicateTarget {
+            other_source: "/photos/b.txt".to_string()
+        })
+    );
+    assert_eq!(
+        rows[1].problem,
+        Some(BatchRenameProblem::DuplicateTarget {
+            other_source: "/photos/a.txt".to_string()
+        })
+    );
+    assert_eq!(rows[2].problem, None);
+}
+
+#[test]
+fn distinct_targets_with_a_partial_regex_match_are_not_flagged() {
+    let rule = BatchRenameRule {
+        find_replace: Some(FindReplace {
+            find: r"^(a|b)$".to_string(),
+            replace: String::new(),
+            regex: true,
+            case_sensitive: true,
+            include_extension: false,
+        }),
+        template: "{name}{n}{ext}".to_string(),
+        counter_start: 1,
+        ..BatchRenameRule::default()
+    };
+
+    let rows = plan_batch_rename(&sources(&["a.txt", "2.txt"]), &rule);
+
+    // `a.txt` → `1.txt`, `2.txt` → `22.txt`: distinct targets, no problem rows.
+    assert_eq!(new_names(&rows), vec!["1.txt", "22.txt"]);
+    assert!(rows.iter().all(|row| row.problem.is_none()));
+}
@@ -14,6 +14,9 @@
 //! after the reversal finishes: the inverse is an async managed op, so the caller
 //! polls the original op's `rollback_state` until it leaves `rolling_back` to
 //! observe the terminal result (the "dispatch then poll" contract).
+//!
+//! [`undo_last_operation`] / [`redo_last_operation`] pop the in-memory stack in
+//! [`crate::operation_log::undo`] and go through the same dispatch.
 
 use std::future::Future;
 use std::pin::Pin;
@@ -28,6 +31,7 @@ use crate::operation_log::rollback::{
     InversePlan, RollbackDispatch, RollbackRefusal, execute_rollback, inverse_kind, rollback_operation,
 };
 use crate::operation_log::types::{Initiator, OpKind};
+use crate::operation_log::undo::{self, UndoDispatch, UndoEntry, UndoRefusal};
 use crate::operation_log::writer::OperationLogWriter;
 
 use super::manager::{ManagedTaskGuard, OperationDescriptor, OperationSummaryText, manager};
@@ -74,6 +78,69 @@ pub fn dispatch_rollback<R: Runtime>(
     })
 }
 
+/// Which stack [`step_undo_stack`] pops.
+#[derive(Clone, Copy)]
+enum UndoDirection {
+    Undo,
+    Redo,
+}
+
+/// Undo the newest entry on the undo stack by rolling it back. A copy or delete
+/// marker is dropped with a typed refusal; see [`crate::operation_log::undo`].
+pub fn undo_last_operation<R: Runtime>(app: &AppHandle<R>) -> Result<UndoDispatch, UndoRefusal> {
+    step_undo_stack(app, UndoDirection::Undo)
+}
+
+/// Redo the newest undone entry by rolling back the inverse op its undo created.
+pub fn redo_last_operation<R: Runtime>(app: &AppHandle<R>) -> Result<UndoDispatch, UndoRefusal> {
+    step_undo_stack(app, UndoDirection::Redo)
+}
+
+fn step_undo_stack<R: Runtime>(app: &AppHandle<R>, direction: UndoDirection) -> Result<UndoDispatch, UndoRefusal> {
+    let popped = undo::with_stack(|stack| match direction {
+        UndoDirection::Undo => stack.pop_undo(),
+        UndoDirection::Redo => stack.pop_redo(),
+    });
+    let restore = |entry: UndoEntry| {
+        undo::with_stack(|stack| match direction {
+            UndoDirection::Undo => stack.restore_undo(entry),
+            UndoDirection::Redo => stack.restore_redo(entry),
+        });
+    };
+
+    let entry = popped.ok_or(match direction {
+        UndoDirection::Undo => UndoRefusal::NothingToUndo,
+        UndoDirection::Redo => UndoRefusal::NothingToRedo,
+    })?;
+    if !entry.undoable {
+        return Err(UndoRefusal::NotUndoable { op_kind: entry.kind });
+    }
+    if entry.running {
+        restore(entry);
+        return Err(UndoRefusal::StillRunning);
+    }
+
+    // Pop first and dispatch outside the lock: the inverse's own journal record
+    // points take the same lock from its managed task.
+    match dispatch_rollback(app, &entry.op_id, Initiator::User) {
+        Ok(dispatch) => {
+            let kind = entry.kind;
+            let inverse_op_id = dispatch.inverse_op_id.clone();
+            undo::with_stack(|stack| match direction {
+                UndoDirection::Undo => stack.undone(entry, dispatch.inverse_op_id),
+                UndoDirection::Redo => stack.redone(entry, dispatch.inverse_op_id),
+            });
+            Ok(UndoDispatch { kind, inverse_op_id })
+        }
+        Err(refusal) => {
+            if undo::is_transient(&refusal) {
+                restore(entry);
+            }
+            Err(UndoRefusal::Rollback(refusal))
+        }
+    }
+}
+
 /// Register the inverse operation with the manager. Runs synchronously inside
 /// [`rollback_operation`]'s spawn hook: a volume that dropped between the gate and
 /// here is a synchronous spawn failure (Finding 3) — returned typed so the entry
@@ -43,6 +43,15 @@ pub(super) struct WalkContext<'a, E> {
     /// other callers pass `None`. Must stay cheap (a channel push) so it never
     /// lands on the walk's critical path.
     pub(super) on_file: Option<OnFileHook<'a>>,
+    /// `false` skips dot-named children (and so a dot-folder's whole subtree).
+    /// The walk's root is never filtered: a source the user picked is in.
+    pub(super) include_hidden: bool,
+}
+
+/// Whether `path`'s last component is a dotfile / dot-folder, the same rule as
+/// the listing's hidden-files toggle.
+pub(super) fn is_hidden(path: &Path) -> bool {
+    path.file_name().is_some_and(|n| n.as_encoded_bytes().starts_with(b"."))
 }
 
 /// Recursively walks a directory tree, collecting files and directories.
@@ -151,8 +160,12 @@ pub(super) fn walk_dir_recursive<E>(
         } else {
             let entries = fs::read_dir(path).map_err(|e| (ctx.on_io_error)(path, e))?;
             for entry in entries.flatten() {
+                let child = entry.path();
+                if !ctx.include_hidden && is_hidden(&child) {
+                    continue;
+                }
                 walk_dir_recursive(
-                    &entry.path(),
+                    &child,
                     source_root,
                     files,
                     dirs,
@@ -213,6 +226,9 @@ fn walk_cached_entries<E>(
         if (ctx.is_cancelled)() {
             return Err((ctx.on_cancelled)());
         }
+        if !ctx.include_hidden && entry.name.starts_with('.') {
+            continue;
+        }
         let child_path = PathBuf::from(&entry.path);
         if entry.is_directory && !entry.is_symlink {
             // Recurse: the oracle re-applies inside `walk_dir_recursive`, so a
@@ -549,6 +565,8 @@ pub(super) fn sort_files(files: &mut [FileInfo], column: SortColumn, order: Sort
             SortColumn::Size => a.size.cmp(&b.size),
             SortColumn::Modified => a.modified.cmp(&b.modified),
             SortColumn::Created => a.created.cmp(&b.created),
+            // Copy order only; reading EXIF mid-scan isn't worth it.
+            SortColumn::Captured => a.modified.cmp(&b.modified),
         };
         match order {
             SortOrder::Ascending => cmp,
@@ -570,6 +588,7 @@ pub(super) fn scan_sources(
     operation_type: WriteOperationType,
     sort_column: SortColumn,
     sort_order: SortOrder,
+    include_hidden: bool,
 ) -> Result<ScanResult, WriteOperationError> {
     let progress_interval = state.progress_interval;
 
@@ -584,6 +603,7 @@ pub(super) fn scan_sources(
                 sort_column,
                 sort_order,
                 progress_interval,
+                include_hidden,
             )
         },
         state,
@@ -606,6 +626,7 @@ fn scan_sources_internal(
     sort_column: SortColumn,
     sort_order: SortOrder,
     progress_interval: Duration,
+    include_hidden: bool,
 ) -> Result<ScanResult, WriteOperationError> {
     let mut files = Vec::new();
     let mut dirs = Vec::new();
@@ -679,6 +700,7 @@ fn scan_sources_internal(
         },
         // The real copy/move/delete scan never samples for a compress estimate.
         on_file: None,
+        include_hidden,
     };
 
     // Local FS scan goes through `LocalPosixVolume`, which is always registered as
@@ -770,6 +792,7 @@ pub(super) fn dry_run_scan(
     operation_id: &str,
     operation_type: WriteOperationType,
     progress_interval: Duration,
+    include_hidden: bool,
 ) -> Result<DryRunScanResult, WriteOperationError> {
     run_cancellable_scoped(
         || {
@@ -781,6 +804,7 @@ pub(super) fn dry_run_scan(
                 operation_id,
                 operation_type,
                 progress_interval,
+                include_hidden,
             )
         },
         state,
@@ -790,6 +814,10 @@ pub(super) fn dry_run_scan(
 }
 
 /// Internal dry-run scan implementation (runs in background thread).
+#[allow(
+    clippy::too_many_arguments,
+    reason = "Internal helper passes through all required context"
+)]
 fn dry_run_scan_internal(
     sources: &[PathBuf],
     destination: &Path,
@@ -798,6 +826,7 @@ fn dry_run_scan_internal(
     operation_id: &str,
     operation_type: WriteOperationType,
     progress_interval: Duration,
+    include_hidden: bool,
 ) -> Result<DryRunScanResult, WriteOperationError> {
     let mut files_found = 0usize;
     let mut bytes_found = 0u64;
@@ -820,6 +849,7 @@ fn dry_run_scan_internal(
             &progress_interval,
             &mut last_progress_time,
             &mut visited,
+            include_hidden,
         )?;
     }
 
@@ -859,6 +889,7 @@ fn dry_run_scan_recursive(
     progress_interval: &Duration,
     last_progress_time: &mut Instant,
     visited: &mut HashSet<PathBuf>,
+    include_hidden: bool,
 ) -> Result<(), WriteOperationError> {
     // Check cancellation
     if super::state::is_cancelled(&state.intent) {
@@ -911,8 +942,12 @@ fn dry_run_scan_recursive(
         let entries = fs::read_dir(path).with_path(path)?;
 
         for entry in entries.flatten() {
+            let child = entry.path();
+            if !include_hidden && is_hidden(&child) {
+                continue;
+            }
             dry_run_scan_recursive(
-                &entry.path(),
+                &child,
                 source_root,
                 dest_root,
                 files_found,
@@ -925,6 +960,7 @@ fn dry_run_scan_recursive(
                 progress_interval,
                 last_progress_time,
                 visited,
+                include_hidden,
             )?;
         }
     } else {
@@ -964,6 +1000,7 @@ pub(super) fn handle_dry_run(
     operation_type: WriteOperationType,
     progress_interval: Duration,
     max_conflicts_to_show: usize,
+    include_hidden: bool,
 ) -> Result<bool, WriteOperationError> {
     use super::types::DryRunResult;
 
@@ -979,6 +1016,7 @@ pub(super) fn handle_dry_run(
         operation_id,
         operation_type,
         progress_interval,
+        include_hidden,
     )?;
 
     let conflicts_count = scan_result.conflicts.len();
@@ -1074,10 +1112,10 @@ mod tests {
     /// Run the walker over `root`, with `progress_interval = 0` so the
     /// callback fires on every entry. Captures progress payloads for assertions.
     fn run_walker(root: &Path) -> WalkOutcome {
-        run_walker_with_sources(&[root.to_path_buf()])
+        run_walker_with_sources(&[root.to_path_buf()], true)
     }
 
-    fn run_walker_with_sources(sources: &[PathBuf]) -> WalkOutcome {
+    fn run_walker_with_sources(sources: &[PathBuf], include_hidden: bool) -> WalkOutcome {
         let mut files = Vec::new();
         let mut dirs = Vec::new();
         let mut total_bytes = 0u64;
@@ -1096,6 +1134,7 @@ mod tests {
                 captured.borrow_mut().push((cur_file, cur_dir));
             },
             on_file: None,
+            include_hidden,
         };
         for source in sources {
             let source_root = source.parent().unwrap_or(source);
@@ -1194,7 +1233,7 @@ mod tests {
         fs::write(&original, vec![0u8; 5000]).unwrap();
         fs::hard_link(&original, dir_b.join("file.bin")).unwrap();
 
-        let outcome = run_walker_with_sources(&[dir_a.clone(), dir_b.clone()]);
+        let outcome = run_walker_with_sources(&[dir_a.clone(), dir_b.clone()], true);
         assert_eq!(outcome.files.len(), 2);
         assert_eq!(
             outcome.bytes, 10000,
@@ -1289,4 +1328,29 @@ mod tests {
         assert_eq!(event.expected_files_total, None);
         assert_eq!(event.expected_bytes_total, None);
     }
+
+    #[test]
+    fn walker_without_hidden_skips_dotfiles_and_dot_folder_subtrees_but_not_the_source() {
+        let temp = tempfile::tempdir().unwrap();
+        let root = temp.path().join(".selected");
+        fs::create_dir_all(root.join(".git/objects")).unwrap();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join(".git/objects/pack"), vec![0u8; 500]).unwrap();
+        fs::write(root.join(".DS_Store"), vec![0u8; 50]).unwrap();
+        fs::write(root.join("src/main.rs"), vec![0u8; 10]).unwrap();
+        fs::write(root.join("src/.env"), vec![0u8; 5]).unwrap();
+
+        let without = run_walker_with_sources(std::slice::from_ref(&root), false);
+        let with = run_walker_with_sources(std::slice::from_ref(&root), true);
+
+        let names: Vec<_> = without
+            .files
+            .iter()
+            .map(|f| f.path.file_name().unwrap().to_owned())
+            .collect();
+        assert_eq!(names, vec![std::ffi::OsString::from("main.rs")]);
+        assert_eq!(without.bytes, 10, "totals match the filtered file list");
+        assert_eq!(with.files.len(), 4);
+        assert_eq!(with.bytes, 565);
+    }
 }
@@ -38,6 +38,8 @@ use crate::indexing::read::expected_totals;
 /// `sample_for_estimate` turns on the compressed-size sampler for the LOCAL
 /// walk only (compress-mode scans). It's ignored for volume/remote scans, which
 /// never sample (the estimate is suppressed there). See `compress_estimate`.
+/// `include_hidden == false` leaves dotfiles out of the LOCAL walk, matching
+/// `WriteOperationConfig::include_hidden` on the op that reuses the result.
 #[allow(
     clippy::too_many_arguments,
     reason = "IPC pass-through mirroring the command's parameter list"
@@ -51,6 +53,7 @@ pub fn start_scan_preview(
     sort_order: SortOrder,
     progress_interval_ms: u64,
     sample_for_estimate: bool,
+    include_hidden: bool,
 ) -> ScanPreviewStartResult {
     let preview_id = Uuid::new_v4().to_string();
     let preview_id_clone = preview_id.clone();
@@ -83,6 +86,7 @@ pub fn start_scan_preview(
                 sort_order,
                 state,
                 sample_for_estimate,
+                include_hidden,
             );
         });
     }
@@ -135,6 +139,10 @@ pub fn cancel_scan_preview(preview_id: &str) {
 /// rides the complete event. The worker is joined after the walk (usually
 /// already done, since it ran concurrently) and cancels with the scan; a
 /// sampling failure degrades to "no estimate" and never affects the scan.
+#[allow(
+    clippy::too_many_arguments,
+    reason = "Thread entry point; takes the start call's parameters by value"
+)]
 fn run_scan_preview(
     app: tauri::AppHandle,
     preview_id: String,
@@ -143,6 +151,7 @@ fn run_scan_preview(
     sort_order: SortOrder,
     state: Arc<ScanPreviewState>,
     sample_for_estimate: bool,
+    include_hidden: bool,
 ) {
     use tauri_specta::Event;
 
@@ -222,6 +231,7 @@ fn run_scan_preview(
                 .emit(&app);
             },
             on_file: sample_for_estimate.then_some(&send_sample as &dyn Fn(&Path, u64)),
+            include_hidden,
         };
         // Local FS scan preview uses the "root" volume ID. The oracle short-circuits
         // any subtree currently open in a pane with a live FSEvents watcher.
@@ -4,8 +4,8 @@
 
 use crate::ignore_poison::IgnorePoison;
 use std::collections::{HashMap, HashSet};
-use std::path::PathBuf;
-use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU8, AtomicUsize, Ordering};
 use std::sync::{Arc, LazyLock, OnceLock, RwLock};
 use std::time::{Duration, Instant};
 
@@ -91,6 +91,10 @@ pub struct WriteOperationState {
     /// own the per-leaf record points don't take the volume ids as params (they're
     /// called from ~80 test sites), mirroring how `op_id` reaches them.
     pub journal_volumes: Option<(String, String)>,
+    /// How many times a streaming copy reopened its source mid-file after a
+    /// transient error (MTP session reset, timeout) instead of failing the file.
+    /// Bumped by `transfer::resuming_stream`; the volume copy logs it on completion.
+    pub resumed_reads: AtomicUsize,
 }
 
 impl WriteOperationState {
@@ -107,6 +111,7 @@ impl WriteOperationState {
             backend_cancel: Arc::new(AtomicBool::new(false)),
             pause_gate: PauseGate::new(),
             journal_volumes: None,
+            resumed_reads: AtomicUsize::new(0),
         }
     }
 
@@ -314,6 +319,7 @@ pub(super) fn unregister_operation_status(operation_id: &str) {
     } else {
         None
     };
+    UNMOUNT_CANCELLED.lock_ignore_poison().remove(operation_id);
     // Lower the priority transfer gauge with the SAME ids the register raised it
     // with (they ride the cache entry, so the pair can't drift). A double
     // unregister removed nothing and lowers nothing.
@@ -420,6 +426,22 @@ pub fn busy_volume_ids() -> Vec<String> {
     ids
 }
 
+/// Returns the IDs of the ops touching `volume_id` (sorted). The `eject_volume`
+/// guard names them when it refuses, so the caller knows what to wait for or
+/// cancel.
+pub fn operations_on_volume(volume_id: &str) -> Vec<String> {
+    let Ok(cache) = OPERATION_STATUS_CACHE.read() else {
+        return Vec::new();
+    };
+    let mut ids: Vec<String> = cache
+        .iter()
+        .filter(|(_, status)| status.volume_ids.iter().any(|id| id == volume_id))
+        .map(|(op_id, _)| op_id.clone())
+        .collect();
+    ids.sort();
+    ids
+}
+
 /// Recomputes the busy set and emits `volumes-busy-changed` only when its
 /// membership changed. Called from register/unregister (the only two points
 /// where membership can change), so it's panic-safe: unregister runs from the
@@ -559,6 +581,27 @@ pub fn cancel_write_operation(operation_id: &str, rollback: bool) {
     }
 }
 
+/// Ops stopped because a volume they touch was unmounted: op id → that volume's mount path.
+/// The Tauri sink consults it when the op reports `write-cancelled`, and surfaces a
+/// `DeviceDisconnected` error instead, so the user sees why the copy ended. Cleared on unregister,
+/// so an op that failed on its own before noticing the cancel doesn't leak an entry.
+static UNMOUNT_CANCELLED: LazyLock<std::sync::Mutex<HashMap<String, String>>> =
+    LazyLock::new(|| std::sync::Mutex::new(HashMap::new()));
+
+/// Stops a running op (keeping partial files: the volume is gone, so there's nothing to roll
+/// back) and remembers that `volume_path` vanishing was the reason.
+pub(super) fn cancel_write_operation_for_unmount(operation_id: &str, volume_path: &str) {
+    UNMOUNT_CANCELLED
+        .lock_ignore_poison()
+        .insert(operation_id.to_string(), volume_path.to_string());
+    cancel_write_operation(operation_id, false);
+}
+
+/// Takes the unmounted volume path an op was stopped for, if it was stopped by an unmount.
+pub(crate) fn take_unmount_cancellation(operation_id: &str) -> Option<String> {
+    UNMOUNT_CANCELLED.lock_ignore_poison().remove(operation_id)
+}
+
 /// Stops all in-progress write operations without rollback.
 ///
 /// Used as a safety net when the frontend is tearing down (beforeunload, hot-reload).
@@ -604,12 +647,21 @@ pub(super) fn resume_write_operation(operation_id: &str) -> bool {
     if let Ok(cache) = WRITE_OPERATION_STATE.read()
         && let Some(state) = cache.get(operation_id)
     {
-        state.pause_gate.resume();
+        if let Some(paused_for) = state.pause_gate.resume() {
+            // Keep the pause out of the throughput the ETA is based on.
+            state.estimator.lock_ignore_poison().skip_pause(paused_for);
+        }
         return true;
     }
     false
 }
 
+/// Total time `operation_id` has spent paused, or `None` if it isn't live.
+pub(super) fn paused_time(operation_id: &str) -> Option<Duration> {
+    let cache = WRITE_OPERATION_STATE.read().ok()?;
+    cache.get(operation_id).map(|state| state.pause_gate.paused_time())
+}
+
 /// Resolves a pending conflict for an in-progress write operation.
 ///
 /// When an operation encounters a conflict in Stop mode, it emits a WriteConflictEvent
@@ -690,6 +742,21 @@ impl CopyTransaction {
         }
     }
 
+    /// Rolls back only what was created at or below `root`, and forgets it, so
+    /// a later full [`rollback`](Self::rollback) or the end-of-op bookkeeping
+    /// doesn't see it again. Used by `continue_on_error` to undo one atomic
+    /// source while the rest of the operation keeps its files.
+    pub fn rollback_under(&mut self, root: &Path) {
+        for file in self.created_files.iter().rev().filter(|p| p.starts_with(root)) {
+            let _ = std::fs::remove_file(file);
+        }
+        for dir in self.created_dirs.iter().rev().filter(|p| p.starts_with(root)) {
+            let _ = std::fs::remove_dir(dir);
+        }
+        self.created_files.retain(|p| !p.starts_with(root));
+        self.created_dirs.retain(|p| !p.starts_with(root));
+    }
+
     /// Marks the transaction as committed, preventing rollback on drop.
     pub fn commit(mut self) {
         self.committed = true;
@@ -1252,4 +1319,32 @@ mod tests {
             "Drop must unregister on unwind, not only on the happy path"
         );
     }
+
+    // ---- progress enrichment ----
+
+    #[test]
+    fn emitted_progress_carries_rates_but_no_eta_during_warm_up() {
+        use crate::file_system::write_operations::types::{CollectorEventSink, WriteOperationPhase};
+
+        let state = WriteOperationState::new(Duration::from_millis(200));
+        let sink = CollectorEventSink::new();
+        let event = WriteProgressEvent::new(
+            "op".to_string(),
+            WriteOperationType::Copy,
+            WriteOperationPhase::Copying,
+            None,
+            1,
+            10,
+            1_000,
+            10_000,
+        );
+
+        state.emit_progress_via_sink(&sink, event);
+
+        let emitted = sink.progress.lock_ignore_poison();
+        assert_eq!(emitted.len(), 1);
+        assert!(emitted[0].bytes_per_second.is_some(), "every emit is enriched");
+        assert!(emitted[0].files_per_second.is_some());
+        assert_eq!(emitted[0].eta_seconds, None, "one sample is not enough for an ETA");
+    }
 }
@@ -58,11 +58,15 @@ pub fn is_network_filesystem(path: &Path) -> bool {
 ///
 /// The optional progress callback is called after each chunk with
 /// (bytes_copied_so_far, total_bytes).
+///
+/// The optional `source_hasher` gets every chunk read from `source`, so a
+/// verified copy has the source hash without a second read.
 pub fn chunked_copy_with_metadata(
     source: &Path,
     dest: &Path,
     cancelled: &Arc<AtomicU8>,
     progress_callback: Option<ChunkedCopyProgressFn>,
+    source_hasher: Option<&mut blake3::Hasher>,
 ) -> Result<u64, WriteOperationError> {
     log::debug!(
         "chunked_copy: starting chunked copy from {} to {}",
@@ -74,7 +78,7 @@ pub fn chunked_copy_with_metadata(
     let source_size = std::fs::metadata(source).map(|m| m.len()).unwrap_or(0);
 
     // 1. Chunked data copy with cancellation checks
-    let bytes = copy_data_chunked(source, dest, cancelled, source_size, progress_callback)?;
+    let bytes = copy_data_chunked(source, dest, cancelled, source_size, progress_callback, source_hasher)?;
 
     // 2. Copy all metadata (best effort - log warnings but don't fail)
     if let Err(e) = copy_metadata(source, dest) {
@@ -103,6 +107,7 @@ fn copy_data_chunked(
     cancelled: &Arc<AtomicU8>,
     source_size: u64,
     progress_callback: Option<ChunkedCopyProgressFn>,
+    mut source_hasher: Option<&mut blake3::Hasher>,
 ) -> Result<u64, WriteOperationError> {
     let mut src_file = std::fs::File::open(source).map_err(|e| WriteOperationError::ReadError {
         path: source.display().to_string(),
@@ -140,6 +145,9 @@ fn copy_data_chunked(
         if bytes_read == 0 {
             break; // EOF
         }
+        if let Some(hasher) = source_hasher.as_deref_mut() {
+            hasher.update(&buffer[..bytes_read]);
+        }
 
         dst_file
             .write_all(&buffer[..bytes_read])
@@ -193,8 +201,10 @@ fn copy_metadata(source: &Path, dest: &Path) -> Result<(), WriteOperationError>
     Ok(())
 }
 
-/// Copies extended attributes from source to destination.
-fn copy_xattrs(source: &Path, dest: &Path) -> Result<(), WriteOperationError> {
+/// Copies extended attributes from source to destination. Best-effort per
+/// attribute: a filesystem without xattr support, or one attribute the
+/// destination refuses, never fails the copy.
+pub(super) fn copy_xattrs(source: &Path, dest: &Path) -> Result<(), WriteOperationError> {
     // List all xattrs on source
     let xattrs = match xattr::list(source) {
         Ok(iter) => iter.collect::<Vec<_>>(),
@@ -235,6 +245,18 @@ fn copy_xattrs(source: &Path, dest: &Path) -> Result<(), WriteOperationError> {
     Ok(())
 }
 
+/// The attribute macOS stamps on downloaded files; Gatekeeper checks it on first open.
+pub(super) const QUARANTINE_XATTR: &str = "com.apple.quarantine";
+
+/// Removes the quarantine flag from `dest`, if it has one.
+pub(super) fn strip_quarantine(dest: &Path) {
+    if matches!(xattr::get(dest, QUARANTINE_XATTR), Ok(Some(_)))
+        && let Err(e) = xattr::remove(dest, QUARANTINE_XATTR)
+    {
+        log::debug!("strip_quarantine: failed to remove from {}: {}", dest.display(), e);
+    }
+}
+
 /// Copies ACLs (access control lists) from source to destination.
 #[cfg(any(target_os = "macos", target_os = "linux", target_os = "freebsd"))]
 fn copy_acls(source: &Path, dest: &Path) -> Result<(), WriteOperationError> {
@@ -325,7 +347,7 @@ mod tests {
         fs::write(&src, "Hello, chunked copy!").unwrap();
 
         let cancelled = Arc::new(AtomicU8::new(0));
-        let result = chunked_copy_with_metadata(&src, &dst, &cancelled, None);
+        let result = chunked_copy_with_metadata(&src, &dst, &cancelled, None, None);
 
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), 20); // "Hello, chunked copy!" is 20 bytes
@@ -347,7 +369,7 @@ mod tests {
 
         // Pre-cancelled
         let cancelled = Arc::new(AtomicU8::new(2));
-        let result = chunked_copy_with_metadata(&src, &dst, &cancelled, None);
+        let result = chunked_copy_with_metadata(&src, &dst, &cancelled, None, None);
 
         assert!(matches!(result, Err(WriteOperationError::Cancelled { .. })));
         // Partial file cleanup is now async/best-effort (fires on a detached thread),
@@ -368,7 +390,7 @@ mod tests {
         fs::set_permissions(&src, fs::Permissions::from_mode(0o755)).unwrap();
 
         let cancelled = Arc::new(AtomicU8::new(0));
-        let result = chunked_copy_with_metadata(&src, &dst, &cancelled, None);
+        let result = chunked_copy_with_metadata(&src, &dst, &cancelled, None, None);
 
         assert!(result.is_ok());
         let dst_perms = fs::metadata(&dst).unwrap().permissions().mode();
@@ -386,7 +408,7 @@ mod tests {
         fs::write(&src, "").unwrap();
 
         let cancelled = Arc::new(AtomicU8::new(0));
-        let result = chunked_copy_with_metadata(&src, &dst, &cancelled, None);
+        let result = chunked_copy_with_metadata(&src, &dst, &cancelled, None, None);
 
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), 0);
@@ -424,7 +446,7 @@ mod tests {
         filetime::set_file_mtime(&src, target_mtime).unwrap();
 
         let cancelled = Arc::new(AtomicU8::new(0));
-        chunked_copy_with_metadata(&src, &dst, &cancelled, None).unwrap();
+        chunked_copy_with_metadata(&src, &dst, &cancelled, None, None).unwrap();
 
         let dst_meta = fs::metadata(&dst).unwrap();
         let dst_mtime = filetime::FileTime::from_last_modification_time(&dst_meta);
@@ -459,7 +481,7 @@ mod tests {
         }
 
         let cancelled = Arc::new(AtomicU8::new(0));
-        chunked_copy_with_metadata(&src, &dst, &cancelled, None).unwrap();
+        chunked_copy_with_metadata(&src, &dst, &cancelled, None, None).unwrap();
 
         let roundtripped = xattr::get(&dst, key).unwrap();
         assert_eq!(
@@ -490,7 +512,7 @@ mod tests {
         fs::write(&src, &payload).unwrap();
 
         let cancelled = Arc::new(AtomicU8::new(0));
-        let result = chunked_copy_with_metadata(&src, &dst, &cancelled, None).unwrap();
+        let result = chunked_copy_with_metadata(&src, &dst, &cancelled, None, None).unwrap();
         assert_eq!(
             result, expected,
             "returned byte count must equal source length over multiple chunks"
@@ -500,6 +522,22 @@ mod tests {
         cleanup_temp_dir(&temp_dir);
     }
 
+    #[test]
+    fn chunked_copy_hashes_every_chunk_read() {
+        let temp_dir = create_temp_dir("hashing");
+        let src = temp_dir.join("source.bin");
+        let dst = temp_dir.join("dest.bin");
+        let payload: Vec<u8> = (0..CHUNK_SIZE * 2 + 777).map(|i| (i % 251) as u8).collect();
+        fs::write(&src, &payload).unwrap();
+
+        let cancelled = Arc::new(AtomicU8::new(0));
+        let mut hasher = blake3::Hasher::new();
+        chunked_copy_with_metadata(&src, &dst, &cancelled, None, Some(&mut hasher)).unwrap();
+        assert_eq!(hasher.finalize(), blake3::hash(&payload));
+
+        cleanup_temp_dir(&temp_dir);
+    }
+
     #[test]
     fn test_chunked_copy_progress_callback() {
         use std::sync::atomic::AtomicU64;
@@ -525,7 +563,7 @@ mod tests {
             assert_eq!(total, expected_size);
         };
 
-        let result = chunked_copy_with_metadata(&src, &dst, &cancelled, Some(&progress_cb));
+        let result = chunked_copy_with_metadata(&src, &dst, &cancelled, Some(&progress_cb), None);
 
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), expected_size);
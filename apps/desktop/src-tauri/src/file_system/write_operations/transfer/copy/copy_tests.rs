@@ -216,3 +216,88 @@ fn copy_empty_directory_does_not_clobber_same_named_dest_file() {
     assert!(dest.is_file(), "the existing dest file must survive");
     assert_eq!(fs::read(&dest).unwrap(), b"existing user data");
 }
+
+/// A local copy keeps the source's extended attributes on every strategy
+/// (clonefile, `copy_file_range`, chunked), and `strip_quarantine` drops only
+/// `com.apple.quarantine`. The quarantine half runs on macOS only: Linux
+/// refuses attribute names outside its `user.` / `trusted.` namespaces.
+#[test]
+fn copy_preserves_xattrs_and_strips_quarantine_on_request() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let src_dir = tmp.path().join("src");
+    fs::create_dir_all(&src_dir).unwrap();
+    let src_file = src_dir.join("download.sh");
+    fs::write(&src_file, b"echo hi").unwrap();
+    if let Err(e) = xattr::set(&src_file, "user.cmdr_test_tag", b"kept") {
+        // Some CI / tmpfs setups disallow user xattrs; the test is opportunistic.
+        log::warn!("copy_preserves_xattrs_and_strips_quarantine_on_request: xattr unsupported ({e}); skipping");
+        return;
+    }
+    #[cfg(target_os = "macos")]
+    xattr::set(&src_file, "com.apple.quarantine", b"0081;00000000;Safari;").unwrap();
+
+    for strip in [false, true] {
+        let dst_dir = tmp.path().join(format!("dst-{strip}"));
+        fs::create_dir_all(&dst_dir).unwrap();
+        let config = WriteOperationConfig {
+            strip_quarantine: strip,
+            ..WriteOperationConfig::default()
+        };
+
+        copy_files_with_progress_inner(
+            &CollectorEventSink::new(),
+            "op-local-copy-xattrs",
+            &make_state(200),
+            std::slice::from_ref(&src_file),
+            &dst_dir,
+            &config,
+        )
+        .expect("copy");
+
+        let copied = dst_dir.join("download.sh");
+        assert_eq!(
+            xattr::get(&copied, "user.cmdr_test_tag").unwrap().as_deref(),
+            Some(b"kept".as_slice())
+        );
+        #[cfg(target_os = "macos")]
+        assert_eq!(xattr::get(&copied, "com.apple.quarantine").unwrap().is_some(), !strip);
+    }
+}
+
+/// With `verify`, every copied file is read back and counted in the complete
+/// event; a symlink isn't a file to verify. Without it the count stays 0.
+#[test]
+fn verified_copy_counts_each_file_it_read_back() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let src_dir = tmp.path().join("src");
+    let dst_dir = tmp.path().join("dst");
+    fs::create_dir_all(src_dir.join("nested")).unwrap();
+    fs::create_dir_all(&dst_dir).unwrap();
+    fs::write(src_dir.join("a.txt"), b"alpha").unwrap();
+    fs::write(src_dir.join("nested/b.bin"), vec![7u8; 2_500_000]).unwrap();
+    std::os::unix::fs::symlink("a.txt", src_dir.join("link")).unwrap();
+
+    for (verify, expected) in [(true, 2), (false, 0)] {
+        let dest = dst_dir.join(format!("verify-{verify}"));
+        fs::create_dir_all(&dest).unwrap();
+        let events = Arc::new(CollectorEventSink::new());
+        let config = WriteOperationConfig {
+            verify,
+            ..WriteOperationConfig::default()
+        };
+        copy_files_with_progress_inner(
+            &*events,
+            "op-verify",
+            &make_state(200),
+            std::slice::from_ref(&src_dir),
+            &dest,
+            &config,
+        )
+        .expect("copy succeeds");
+
+        let complete = events.complete.lock().unwrap();
+        assert_eq!(complete[0].files_processed, 3);
+        assert_eq!(complete[0].files_verified, expected, "verify={verify}");
+        assert_eq!(fs::read(dest.join("src/nested/b.bin")).unwrap(), vec![7u8; 2_500_000]);
+    }
+}
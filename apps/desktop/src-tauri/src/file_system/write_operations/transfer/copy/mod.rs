@@ -12,13 +12,15 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use super::super::cancellable::run_cancellable;
+use super::super::cloud_materialize::materialize_online_only_sources;
 use super::super::conflict::ApplyToAll;
 use super::super::durability::flush_created_destinations;
+use super::super::name_sanitizer::NameSanitizer;
 use super::super::scan::{
     SourceItemTracker, handle_dry_run, scan_sources, take_cached_scan_result, top_level_source_path,
 };
 use super::super::state::{
-    CopyTransaction, OperationIntent, WriteOperationState, load_intent, update_operation_status,
+    CopyTransaction, FileInfo, OperationIntent, WriteOperationState, load_intent, update_operation_status,
 };
 use super::super::types::{
     ConflictResolution, OperationEventSink, WriteCancelledEvent, WriteCompleteEvent, WriteErrorEvent,
@@ -113,6 +115,7 @@ pub(in crate::file_system::write_operations) fn copy_files_with_progress_inner(
         WriteOperationType::Copy,
         state.progress_interval,
         config.max_conflicts_to_show,
+        config.include_hidden,
     )? {
         return Ok(());
     }
@@ -147,6 +150,7 @@ pub(in crate::file_system::write_operations) fn copy_files_with_progress_inner(
                 WriteOperationType::Copy,
                 config.sort_column,
                 config.sort_order,
+                config.include_hidden,
             )?
         }
     } else {
@@ -163,6 +167,7 @@ pub(in crate::file_system::write_operations) fn copy_files_with_progress_inner(
             WriteOperationType::Copy,
             config.sort_column,
             config.sort_order,
+            config.include_hidden,
         )?
     };
     log::debug!(
@@ -189,6 +194,25 @@ pub(in crate::file_system::write_operations) fn copy_files_with_progress_inner(
     // filesystems with no known limit.
     validate_file_sizes_for_filesystem(destination, &scan_result.files)?;
 
+    // Online-only cloud sources download first, under their own phase, so the copy
+    // below never stalls inside a syscall waiting on a provider.
+    materialize_online_only_sources(
+        events,
+        operation_id,
+        WriteOperationType::Copy,
+        state,
+        &scan_result.files,
+    )?;
+
+    // Names the destination filesystem would reject (`:` on FAT32, say) get
+    // rewritten on the way. `None` when not asked for or the filesystem takes
+    // every name.
+    let mut sanitizer = if config.sanitize_names {
+        NameSanitizer::for_destination(destination, operation_id)
+    } else {
+        None
+    };
+
     // Phase 2: Copy files in sorted order with rollback support
     let mut transaction = CopyTransaction::new();
     let mut apply_to_all_resolution = ApplyToAll::default();
@@ -323,6 +347,17 @@ pub(in crate::file_system::write_operations) fn copy_files_with_progress_inner(
 
     let mut file_iter = files_for_loop.iter();
 
+    // `continue_on_error` bookkeeping: top-level sources the user wants all or
+    // nothing of, and the ones among them that already failed (and were rolled
+    // back). Their remaining files are skipped rather than copied.
+    let atomic_names: HashSet<&str> = config.atomic_sources.iter().map(String::as_str).collect();
+    let mut failed_atomic: HashSet<PathBuf> = HashSet::new();
+    // An atomic source's journal rows wait until it lands whole, so a rolled-back
+    // one leaves no `done` rows behind.
+    hold_atomic_journal_rows(operation_id, &files_for_loop, &atomic_names);
+    // Files read back and matched against their source (`config.verify`).
+    let mut files_verified = 0usize;
+
     let outcome = drive_transfer_serial_sync(
         events,
         state,
@@ -338,6 +373,12 @@ pub(in crate::file_system::write_operations) fn copy_files_with_progress_inner(
             let file_info = file_iter
                 .next()
                 .expect("file_iter aligned with driver iteration over source_paths");
+            let top_level = top_level_source_path(file_info);
+            if failed_atomic.contains(&top_level) {
+                return Ok(TransferOutcome::Skipped {
+                    bytes_accounted: file_info.size,
+                });
+            }
             log::debug!(
                 "copy_files_with_progress: copying file {} ({} bytes)",
                 file_info.path.display(),
@@ -349,9 +390,14 @@ pub(in crate::file_system::write_operations) fn copy_files_with_progress_inner(
             // consistent across iterations.
             let mut local_files = ctx.files_done_so_far;
             let mut local_bytes = ctx.bytes_done_so_far;
-            copy_single_item(
+            let mut dest_path = file_info.dest_path(destination);
+            if let Some(sanitizer) = sanitizer.as_mut() {
+                dest_path = sanitizer.sanitize(&file_info.path, &dest_path);
+                sanitizer.flush(ctx.events);
+            }
+            let copied = copy_single_item(
                 &file_info.path,
-                file_info.dest_path(destination),
+                dest_path,
                 file_info.is_symlink,
                 file_info.size,
                 &mut local_files,
@@ -369,10 +415,48 @@ pub(in crate::file_system::write_operations) fn copy_files_with_progress_inner(
                 &mut created_dirs,
                 &mut dir_remap,
                 &mut already_synced,
-            )?;
+            );
+            if let Ok(true) = copied {
+                files_verified += 1;
+            }
+            if let Err(e) = copied {
+                if !config.continue_on_error || matches!(e, WriteOperationError::Cancelled { .. }) {
+                    return Err(e);
+                }
+                let is_atomic = top_level
+                    .file_name()
+                    .and_then(|n| n.to_str())
+                    .is_some_and(|n| atomic_names.contains(n));
+                if is_atomic && let Some(name) = top_level.file_name() {
+                    let mut dest_top = destination.join(name);
+                    if let Some(sanitizer) = sanitizer.as_mut() {
+                        dest_top = sanitizer.sanitize(&top_level, &dest_top);
+                    }
+                    let dest_top = dir_remap.get(&dest_top).cloned().unwrap_or(dest_top);
+                    log::info!(
+                        "copy_files_with_progress: rolling back atomic source {} after a failure op={}",
+                        top_level.display(),
+                        operation_id
+                    );
+                    transaction.rollback_under(&dest_top);
+                    created_dirs.retain(|d| !d.starts_with(&dest_top));
+                    crate::file_system::write_operations::journal::discard_held_rows(operation_id, &top_level);
+                    failed_atomic.insert(top_level);
+                }
+                crate::file_system::write_operations::journal::record_local_failure(
+                    operation_id,
+                    &file_info.path,
+                    file_info.size,
+                );
+                return Ok(TransferOutcome::Failed {
+                    error: e,
+                    bytes_accounted: file_info.size,
+                });
+            }
             let bytes_delta = local_bytes.saturating_sub(ctx.bytes_done_so_far);
 
             if let Some(source_path) = tracker.record(file_info) {
+                crate::file_system::write_operations::journal::release_held_rows(operation_id, Some(&source_path));
                 ctx.events.emit_source_item_done(WriteSourceItemDoneEvent {
                     operation_id: operation_id.to_string(),
                     source_path: source_path.display().to_string(),
@@ -394,6 +478,10 @@ pub(in crate::file_system::write_operations) fn copy_files_with_progress_inner(
         },
     );
 
+    // Whatever is still held belongs to sources a cancel or stop cut short; they
+    // keep what they reached, like any other source.
+    crate::file_system::write_operations::journal::release_held_rows(operation_id, None);
+
     let files_done = outcome.files_done;
     let bytes_done = outcome.bytes_done;
 
@@ -433,13 +521,19 @@ pub(in crate::file_system::write_operations) fn copy_files_with_progress_inner(
                 return Ok(());
             }
 
+            // An atomic source that failed was rolled back; don't bring its
+            // empty folders back.
+            scan_result
+                .dirs
+                .retain(|d| !failed_atomic.iter().any(|top| d.starts_with(top)));
+
             // Land the scanned directories the per-file loop didn't create:
             // empty dirs (and branches of only empty dirs) have no files, so
             // without this they'd silently never arrive at the destination.
             // Outcomes mirror the loop's arms: Cancelled keeps what's copied
             // (commit, so the Drop safety-net can't roll it back), any other
             // error rolls back like `PostLoopIntent::Failed`.
-            if let Err(e) = create_scanned_dirs_at_destination(
+            let landed = create_scanned_dirs_at_destination(
                 &scan_result.dirs,
                 sources,
                 destination,
@@ -447,7 +541,12 @@ pub(in crate::file_system::write_operations) fn copy_files_with_progress_inner(
                 &mut transaction,
                 &mut created_dirs,
                 &dir_remap,
-            ) {
+                sanitizer.as_mut(),
+            );
+            if let Some(sanitizer) = sanitizer.as_mut() {
+                sanitizer.flush(events);
+            }
+            if let Err(e) = landed {
                 if matches!(e, WriteOperationError::Cancelled { .. }) {
                     transaction.commit();
                     events.emit_cancelled(WriteCancelledEvent {
@@ -491,10 +590,12 @@ pub(in crate::file_system::write_operations) fn copy_files_with_progress_inner(
             transaction.commit();
 
             log::info!(
-                "copy_files_with_progress: completed op={} files={} bytes={}",
+                "copy_files_with_progress: completed op={} files={} bytes={} failed={} verified={}",
                 operation_id,
                 files_done,
-                bytes_done
+                bytes_done,
+                outcome.errors.len(),
+                files_verified
             );
 
             events.emit_complete(WriteCompleteEvent {
@@ -503,6 +604,9 @@ pub(in crate::file_system::write_operations) fn copy_files_with_progress_inner(
                 files_processed: files_done,
                 files_skipped: outcome.files_skipped,
                 bytes_processed: bytes_done,
+                errors: outcome.errors,
+                files_verified,
+                resumed_reads: 0,
             });
             Ok(())
         }
@@ -579,6 +683,24 @@ pub(in crate::file_system::write_operations) fn copy_files_with_progress_inner(
     }
 }
 
+/// Holds back the journal rows of every `atomic_sources` top-level source in
+/// `files` until that source lands whole (`journal::hold_rows_under`).
+pub(super) fn hold_atomic_journal_rows(operation_id: &str, files: &[&FileInfo], atomic_names: &HashSet<&str>) {
+    if atomic_names.is_empty() {
+        return;
+    }
+    let tops: HashSet<PathBuf> = files.iter().map(|fi| top_level_source_path(fi)).collect();
+    for top in tops {
+        if top
+            .file_name()
+            .and_then(|n| n.to_str())
+            .is_some_and(|n| atomic_names.contains(n))
+        {
+            crate::file_system::write_operations::journal::hold_rows_under(operation_id, &top);
+        }
+    }
+}
+
 #[cfg(test)]
 #[path = "copy_tests.rs"]
 mod tests;
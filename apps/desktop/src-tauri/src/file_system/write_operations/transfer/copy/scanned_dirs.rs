@@ -6,6 +6,7 @@ use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
+use crate::file_system::write_operations::name_sanitizer::NameSanitizer;
 use crate::file_system::write_operations::state::{CopyTransaction, WriteOperationState, is_cancelled};
 use crate::file_system::write_operations::types::WriteOperationError;
 use crate::file_system::write_operations::validation::path_exists_or_is_symlink;
@@ -20,8 +21,9 @@ use crate::file_system::write_operations::validation::path_exists_or_is_symlink;
 ///
 /// Mirrors `FileInfo::dest_path`'s mapping (the path relative to its top-level
 /// source's parent, joined onto `destination`), honors active folder→file
-/// Rename redirects via `dir_remap`, and records created dirs in the
-/// transaction for rollback.
+/// Rename redirects via `dir_remap`, rewrites names through `sanitizer` when
+/// the copy sanitizes them, and records created dirs in the transaction for
+/// rollback.
 ///
 /// Data-safety: a dest path that already holds ANYTHING is left untouched — a
 /// same-named dir is a merge (nothing to create), and a same-named file is a
@@ -35,6 +37,7 @@ pub(in crate::file_system::write_operations::transfer) fn create_scanned_dirs_at
     transaction: &mut CopyTransaction,
     created_dirs: &mut HashSet<PathBuf>,
     dir_remap: &HashMap<PathBuf, PathBuf>,
+    mut sanitizer: Option<&mut NameSanitizer>,
 ) -> Result<(), WriteOperationError> {
     // `scanned_dirs` is deepest-first (the delete order); reverse so parents
     // come before children.
@@ -47,6 +50,10 @@ pub(in crate::file_system::write_operations::transfer) fn create_scanned_dirs_at
         let Some(dest) = dir_dest_path(dir, sources, destination) else {
             continue;
         };
+        let dest = match sanitizer.as_deref_mut() {
+            Some(sanitizer) => sanitizer.sanitize(dir, &dest),
+            None => dest,
+        };
         let dest = super::apply_dir_remap(&dest, dir_remap);
         if created_dirs.contains(&dest) || path_exists_or_is_symlink(&dest) {
             continue;
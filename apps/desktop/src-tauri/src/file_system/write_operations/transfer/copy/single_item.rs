@@ -12,8 +12,9 @@ use std::time::{Duration, Instant};
 #[cfg(target_os = "macos")]
 use super::super::macos_copy::copy_symlink;
 
-use super::super::chunked_copy::ChunkedCopyProgressFn;
+use super::super::chunked_copy::{ChunkedCopyProgressFn, strip_quarantine};
 use super::super::copy_strategy::copy_file_with_strategy;
+use super::super::verify::verify_copy;
 
 use crate::file_system::write_operations::conflict::{ApplyToAll, resolve_conflict};
 use crate::file_system::write_operations::overwrite::safe_overwrite_dir;
@@ -83,6 +84,10 @@ fn record_file_done(
 /// Ensures parent directories exist before copying.
 /// Used by both copy and cross-filesystem move operations.
 ///
+/// Returns `true` when the file was copied and then verified (`config.verify`):
+/// read back from the destination with a hash matching the source's. `false` for
+/// symlinks, skips, and unverified copies.
+///
 /// Note: The parent-directory-creation and conflict-resolution pattern here is similar to
 /// `merge_move_directory` in `move_op.rs`. The duplication is intentional: copy has progress
 /// tracking, symlink handling, byte counting, strategy selection, and transaction recording
@@ -124,7 +129,7 @@ pub(in crate::file_system::write_operations::transfer) fn copy_single_item(
     // reflink). The end-of-op flush pass skips these so a long chunked batch
     // isn't fsynced twice. See `durability::flush_created_destinations`.
     already_synced: &mut HashSet<PathBuf>,
-) -> Result<(), WriteOperationError> {
+) -> Result<bool, WriteOperationError> {
     let progress_ctx = PerFileCtx {
         events,
         state,
@@ -262,7 +267,7 @@ pub(in crate::file_system::write_operations::transfer) fn copy_single_item(
                         // in by scan stays consistent across skip paths.
                         let _ = fs::symlink_metadata(source).with_path(source)?;
                         record_file_done(&progress_ctx, source, write_weight, files_done, bytes_done);
-                        return Ok(());
+                        return Ok(false);
                     }
                 }
             }
@@ -327,7 +332,7 @@ pub(in crate::file_system::write_operations::transfer) fn copy_single_item(
                 None => {
                     // Skip this file but still count it toward progress
                     record_file_done(&progress_ctx, source, write_weight, files_done, bytes_done);
-                    return Ok(());
+                    return Ok(false);
                 }
             }
         } else {
@@ -387,6 +392,7 @@ pub(in crate::file_system::write_operations::transfer) fn copy_single_item(
         );
         transaction.record_file(actual_dest);
         record_file_done(&progress_ctx, source, write_weight, files_done, bytes_done);
+        Ok(false)
     } else {
         // Handle regular file
         // Pre-fix this branch used `dest_path.exists()`, which follows symlinks
@@ -407,7 +413,7 @@ pub(in crate::file_system::write_operations::transfer) fn copy_single_item(
                 None => {
                     // Skip this file but still count it toward progress
                     record_file_done(&progress_ctx, source, write_weight, files_done, bytes_done);
-                    return Ok(());
+                    return Ok(false);
                 }
             }
         } else {
@@ -424,7 +430,7 @@ pub(in crate::file_system::write_operations::transfer) fn copy_single_item(
                 source.display()
             );
             record_file_done(&progress_ctx, source, write_weight, files_done, bytes_done);
-            return Ok(());
+            return Ok(false);
         }
 
         // Check cancellation before copy
@@ -487,17 +493,24 @@ pub(in crate::file_system::write_operations::transfer) fn copy_single_item(
             }
         };
 
+        // With `verify`, chunked copy hashes the source as it reads it; native
+        // copies leave the hasher untouched and `verify_copy` reads the source.
+        let mut source_hasher = config.verify.then(blake3::Hasher::new);
         let outcome = copy_file_with_strategy(
             source,
             &actual_dest,
             needs_safe_overwrite,
             &state.intent,
             Some(progress_cb),
+            source_hasher.as_mut(),
         )?;
         // Byte accounting uses `write_weight` below (matches the scan's
         // `total_bytes` even when a clonefile reports 0 copied bytes), so the
         // strategy's own byte count is intentionally unused here.
         let _ = outcome.bytes;
+        if config.strip_quarantine {
+            strip_quarantine(&actual_dest);
+        }
 
         // If the strategy already flushed this file (chunked copy) or a flush
         // is moot (APFS clonefile / reflink), record it so the end-of-op flush
@@ -508,6 +521,18 @@ pub(in crate::file_system::write_operations::transfer) fn copy_single_item(
             already_synced.insert(actual_dest.clone());
         }
 
+        if config.verify {
+            let source_hash = source_hasher.filter(|_| outcome.source_hashed).map(|h| h.finalize());
+            if let Err(e) = verify_copy(source, &actual_dest, source_hash, &state.intent) {
+                // A copy that doesn't match its source is worse than none: drop it
+                // rather than leave it behind under `continue_on_error`.
+                if matches!(e, WriteOperationError::VerificationFailed { .. }) {
+                    let _ = fs::remove_file(&actual_dest);
+                }
+                return Err(e);
+            }
+        }
+
         // Final accounting credits the full write weight (the file's size).
         // We use `write_weight` rather than the strategy's returned byte count
         // so the per-file milestone matches the scan's `total_bytes` exactly
@@ -524,7 +549,6 @@ pub(in crate::file_system::write_operations::transfer) fn copy_single_item(
         );
         transaction.record_file(actual_dest.clone());
         record_file_done(&progress_ctx, source, write_weight, files_done, bytes_done);
+        Ok(config.verify)
     }
-
-    Ok(())
 }
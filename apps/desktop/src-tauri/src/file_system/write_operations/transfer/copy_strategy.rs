@@ -37,7 +37,7 @@ use super::chunked_copy::ChunkedCopyProgressFn;
 #[cfg(any(target_os = "macos", target_os = "linux"))]
 use super::chunked_copy::chunked_copy_with_metadata;
 #[cfg(target_os = "linux")]
-use super::chunked_copy::is_network_filesystem;
+use super::chunked_copy::{copy_xattrs, is_network_filesystem};
 
 // ============================================================================
 // macOS: APFS clonefile detection
@@ -116,10 +116,15 @@ fn is_apfs(path: &Path) -> bool {
 /// only in the page cache (Linux `copy_file_range` without reflink, the
 /// `std::fs::copy` fallback), so the caller must flush the destination before
 /// reporting completion.
+///
+/// `source_hashed` is `true` when the strategy fed every source byte into the
+/// caller's `source_hasher` (chunked copy). Native copies never read through
+/// our code, so a verified copy hashes their source in a separate pass.
 #[derive(Debug, Clone, Copy)]
 pub(super) struct StrategyCopyOutcome {
     pub bytes: u64,
     pub already_durable: bool,
+    pub source_hashed: bool,
 }
 
 /// Copies file contents using the best strategy for the source/destination combination.
@@ -134,6 +139,7 @@ pub(super) fn copy_file_with_strategy(
     needs_safe_overwrite: bool,
     cancelled: &Arc<AtomicU8>,
     progress_callback: Option<ChunkedCopyProgressFn>,
+    source_hasher: Option<&mut blake3::Hasher>,
 ) -> Result<StrategyCopyOutcome, WriteOperationError> {
     if is_same_apfs_volume(source, dest) {
         log::debug!(
@@ -151,6 +157,7 @@ pub(super) fn copy_file_with_strategy(
         Ok(StrategyCopyOutcome {
             bytes,
             already_durable: true,
+            source_hashed: false,
         })
     } else {
         log::debug!(
@@ -159,10 +166,12 @@ pub(super) fn copy_file_with_strategy(
             dest.display()
         );
         // Chunked copy `sync_data`s the file itself before returning.
-        let bytes = chunked_copy_with_metadata(source, dest, cancelled, progress_callback)?;
+        let hashing = source_hasher.is_some();
+        let bytes = chunked_copy_with_metadata(source, dest, cancelled, progress_callback, source_hasher)?;
         Ok(StrategyCopyOutcome {
             bytes,
             already_durable: true,
+            source_hashed: hashing,
         })
     }
 }
@@ -174,6 +183,7 @@ pub(super) fn copy_file_with_strategy(
     needs_safe_overwrite: bool,
     cancelled: &Arc<AtomicU8>,
     progress_callback: Option<ChunkedCopyProgressFn>,
+    source_hasher: Option<&mut blake3::Hasher>,
 ) -> Result<StrategyCopyOutcome, WriteOperationError> {
     if is_network_filesystem(source) || is_network_filesystem(dest) {
         log::debug!(
@@ -182,26 +192,34 @@ pub(super) fn copy_file_with_strategy(
             dest.display()
         );
         // Chunked copy `sync_data`s the file itself before returning.
-        let bytes = chunked_copy_with_metadata(source, dest, cancelled, progress_callback)?;
+        let hashing = source_hasher.is_some();
+        let bytes = chunked_copy_with_metadata(source, dest, cancelled, progress_callback, source_hasher)?;
         Ok(StrategyCopyOutcome {
             bytes,
             already_durable: true,
+            source_hashed: hashing,
         })
     } else if needs_safe_overwrite {
         // `safe_overwrite_file` uses `std::fs::copy` on Linux, which leaves the
         // bytes in the page cache; the caller flushes in the end-of-op pass.
+        // Neither it nor `copy_file_range` carries xattrs over, so copy them
+        // like the chunked path does.
         let bytes = safe_overwrite_file(source, dest)?;
+        copy_xattrs(source, dest)?;
         Ok(StrategyCopyOutcome {
             bytes,
             already_durable: false,
+            source_hashed: false,
         })
     } else {
         // `copy_file_range(2)` doesn't flush (and reflink shares CoW extents,
         // but we can't cheaply tell here), so the caller flushes the dest.
         let bytes = copy_single_file_linux(source, dest, false, cancelled, progress_callback)?;
+        copy_xattrs(source, dest)?;
         Ok(StrategyCopyOutcome {
             bytes,
             already_durable: false,
+            source_hashed: false,
         })
     }
 }
@@ -213,8 +231,9 @@ pub(super) fn copy_file_with_strategy(
     needs_safe_overwrite: bool,
     cancelled: &Arc<AtomicU8>,
     progress_callback: Option<ChunkedCopyProgressFn>,
+    source_hasher: Option<&mut blake3::Hasher>,
 ) -> Result<StrategyCopyOutcome, WriteOperationError> {
-    let _ = (cancelled, progress_callback); // Unused on this platform
+    let _ = (cancelled, progress_callback, source_hasher); // Unused on this platform
     let bytes = if needs_safe_overwrite {
         safe_overwrite_file(source, dest)?
     } else {
@@ -224,6 +243,7 @@ pub(super) fn copy_file_with_strategy(
     Ok(StrategyCopyOutcome {
         bytes,
         already_durable: false,
+        source_hashed: false,
     })
 }
 
@@ -253,7 +273,7 @@ mod tests {
         fs::write(&src, "Hello, copy strategy!").unwrap();
 
         let cancelled = Arc::new(AtomicU8::new(0));
-        let result = copy_file_with_strategy(&src, &dst, false, &cancelled, None);
+        let result = copy_file_with_strategy(&src, &dst, false, &cancelled, None, None);
 
         assert!(result.is_ok());
         assert_eq!(result.unwrap().bytes, 21);
@@ -273,7 +293,7 @@ mod tests {
         fs::write(&dst, "Old content").unwrap();
 
         let cancelled = Arc::new(AtomicU8::new(0));
-        let result = copy_file_with_strategy(&src, &dst, true, &cancelled, None);
+        let result = copy_file_with_strategy(&src, &dst, true, &cancelled, None, None);
 
         assert!(result.is_ok());
         assert!(dst.exists());
@@ -294,7 +314,7 @@ mod tests {
         fs::set_permissions(&src, fs::Permissions::from_mode(0o755)).unwrap();
 
         let cancelled = Arc::new(AtomicU8::new(0));
-        let result = copy_file_with_strategy(&src, &dst, false, &cancelled, None);
+        let result = copy_file_with_strategy(&src, &dst, false, &cancelled, None, None);
 
         assert!(result.is_ok());
         let dst_perms = fs::metadata(&dst).unwrap().permissions().mode();
@@ -385,7 +405,7 @@ mod tests {
         fs::write(&src, "").unwrap();
 
         let cancelled = Arc::new(AtomicU8::new(0));
-        let result = copy_file_with_strategy(&src, &dst, false, &cancelled, None);
+        let result = copy_file_with_strategy(&src, &dst, false, &cancelled, None, None);
 
         assert!(result.is_ok());
         assert_eq!(result.unwrap().bytes, 0);
@@ -18,7 +18,9 @@ pub(super) mod linux_copy;
 #[cfg(target_os = "macos")]
 pub(crate) mod macos_copy;
 pub(super) mod move_op;
+pub(super) mod resuming_stream;
 pub(super) mod transfer_driver;
+mod verify;
 pub(super) mod volume_cleanup;
 pub(super) mod volume_conflict;
 pub(super) mod volume_copy;
@@ -6,19 +6,22 @@ use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use super::super::cancellable::remove_dir_all_in_background;
+use super::super::cloud_materialize::materialize_online_only_sources;
 use super::super::conflict::{ApplyToAll, resolve_conflict};
 use super::super::durability::flush_created_destinations;
 use super::super::overwrite::safe_overwrite_dir;
-use super::super::scan::{SourceItemTracker, handle_dry_run, scan_sources, take_cached_scan_result};
+use super::super::scan::{
+    SourceItemTracker, handle_dry_run, is_hidden, scan_sources, take_cached_scan_result, top_level_source_path,
+};
 use super::super::state::{
-    CopyTransaction, OperationIntent, WriteOperationState, load_intent, update_operation_status,
+    CopyTransaction, FileInfo, OperationIntent, WriteOperationState, load_intent, update_operation_status,
 };
 use super::super::types::{
     IoResultExt, OperationEventSink, WriteCancelledEvent, WriteCompleteEvent, WriteErrorEvent, WriteOperationConfig,
     WriteOperationError, WriteOperationPhase, WriteOperationType, WriteProgressEvent, WriteSourceItemDoneEvent,
 };
 use super::super::validation::{is_same_filesystem, path_exists_or_is_symlink, validate_file_sizes_for_filesystem};
-use super::copy::copy_single_item;
+use super::copy::{copy_single_item, hold_atomic_journal_rows};
 
 // ============================================================================
 // Move rollback tracking
@@ -141,6 +144,7 @@ pub(in crate::file_system::write_operations) fn move_files_with_progress_inner(
         WriteOperationType::Move,
         state.progress_interval,
         config.max_conflicts_to_show,
+        config.include_hidden,
     )? {
         return Ok(());
     }
@@ -149,8 +153,11 @@ pub(in crate::file_system::write_operations) fn move_files_with_progress_inner(
     let same_fs = sources
         .iter()
         .all(|s| is_same_filesystem(s, destination).unwrap_or(false));
+    // Renaming a folder takes its dotfiles along, so a move that leaves them
+    // out has to copy the visible part and delete only that.
+    let must_filter_hidden = !config.include_hidden && sources.iter().any(|s| s.is_dir() && !s.is_symlink());
 
-    if same_fs {
+    if same_fs && !must_filter_hidden {
         // Use instant rename for each source
         move_with_rename(events, operation_id, state, sources, destination, config)
     } else {
@@ -360,6 +367,9 @@ fn move_with_rename(
         files_processed: files_done,
         files_skipped,
         bytes_processed: 0, // Rename doesn't track bytes
+        errors: Vec::new(),
+        files_verified: 0,
+        resumed_reads: 0,
     });
 
     Ok(())
@@ -487,7 +497,7 @@ fn move_with_staging(
     config: &WriteOperationConfig,
 ) -> Result<(), WriteOperationError> {
     // Phase 1: Scan (or reuse cached preview results)
-    let scan_result = if let Some(preview_id) = &config.preview_id {
+    let mut scan_result = if let Some(preview_id) = &config.preview_id {
         // Volume scans cache aggregate stats with an empty `files` list; the
         // per-file move loop needs the file list, so treat an empty-files
         // cache hit the same as a miss and fall through to a fresh local scan.
@@ -514,6 +524,7 @@ fn move_with_staging(
                 WriteOperationType::Move,
                 config.sort_column,
                 config.sort_order,
+                config.include_hidden,
             )?
         }
     } else {
@@ -525,6 +536,7 @@ fn move_with_staging(
             WriteOperationType::Move,
             config.sort_column,
             config.sort_order,
+            config.include_hidden,
         )?
     };
 
@@ -534,6 +546,15 @@ fn move_with_staging(
     // known limit. (Same-FS moves rename in place and never reach here.)
     validate_file_sizes_for_filesystem(destination, &scan_result.files)?;
 
+    // Online-only cloud sources download before staging, as in copy.
+    materialize_online_only_sources(
+        events,
+        operation_id,
+        WriteOperationType::Move,
+        state,
+        &scan_result.files,
+    )?;
+
     // Create staging directory
     let staging_dir = destination.join(format!(".cmdr-staging-{}", operation_id));
     fs::create_dir(&staging_dir).map_err(|e| WriteOperationError::IoError {
@@ -594,15 +615,34 @@ fn move_with_staging(
 
     let mut tracker = SourceItemTracker::new(&scan_result.files);
 
+    // `continue_on_error`: originals whose copy failed (they stay at the source,
+    // like skipped ones), the collected errors, and the atomic top-level
+    // sources that failed as a whole (their staged part is discarded).
+    let atomic_names: HashSet<&str> = config.atomic_sources.iter().map(String::as_str).collect();
+    let mut failed_source_paths: HashSet<PathBuf> = HashSet::new();
+    let mut failed_atomic: HashSet<PathBuf> = HashSet::new();
+    let mut errors: Vec<WriteOperationError> = Vec::new();
+    // An atomic source's journal rows wait until it stages whole, so a discarded
+    // one leaves no `done` rows behind.
+    let staged_files: Vec<&FileInfo> = scan_result.files.iter().collect();
+    hold_atomic_journal_rows(operation_id, &staged_files, &atomic_names);
+
     let copy_result: Result<(), WriteOperationError> = (|| {
         for file_info in &scan_result.files {
+            let top_level = top_level_source_path(file_info);
+            if failed_atomic.contains(&top_level) {
+                files_done += 1;
+                bytes_done += file_info.size;
+                files_skipped += 1;
+                continue;
+            }
             log::debug!(
                 "move_with_staging: copying file {} ({} bytes) to staging",
                 file_info.path.display(),
                 file_info.size
             );
             // Copy to staging directory instead of final destination
-            copy_single_item(
+            let copied = copy_single_item(
                 &file_info.path,
                 file_info.dest_path(&staging_dir),
                 file_info.is_symlink,
@@ -624,9 +664,42 @@ fn move_with_staging(
                 &mut created_dirs,
                 &mut dir_remap,
                 &mut already_synced,
-            )?;
+            );
+            if let Err(e) = copied {
+                if !config.continue_on_error || matches!(e, WriteOperationError::Cancelled { .. }) {
+                    return Err(e);
+                }
+                log::warn!(
+                    "move_with_staging: continuing past failed file {} op={}: {:?}",
+                    file_info.path.display(),
+                    operation_id,
+                    e
+                );
+                let is_atomic = top_level
+                    .file_name()
+                    .and_then(|n| n.to_str())
+                    .is_some_and(|n| atomic_names.contains(n));
+                if is_atomic && let Some(name) = top_level.file_name() {
+                    let staged_top = staging_dir.join(name);
+                    let staged_top = dir_remap.get(&staged_top).cloned().unwrap_or(staged_top);
+                    transaction.rollback_under(&staged_top);
+                    let _ = fs::remove_dir_all(&staged_top);
+                    created_dirs.retain(|d| !d.starts_with(&staged_top));
+                    super::super::journal::discard_held_rows(operation_id, &top_level);
+                    failed_source_paths.insert(top_level.clone());
+                    failed_atomic.insert(top_level);
+                } else {
+                    failed_source_paths.insert(file_info.path.clone());
+                }
+                super::super::journal::record_local_failure(operation_id, &file_info.path, file_info.size);
+                files_done += 1;
+                bytes_done += file_info.size;
+                errors.push(e);
+                continue;
+            }
 
             if let Some(source_path) = tracker.record(file_info) {
+                super::super::journal::release_held_rows(operation_id, Some(&source_path));
                 events.emit_source_item_done(WriteSourceItemDoneEvent {
                     operation_id: operation_id.to_string(),
                     source_path: source_path.display().to_string(),
@@ -635,6 +708,7 @@ fn move_with_staging(
         }
         Ok(())
     })();
+    super::super::journal::release_held_rows(operation_id, None);
 
     if let Err(e) = copy_result {
         // Cleanup staging directory in background (may block on network mounts)
@@ -652,6 +726,11 @@ fn move_with_staging(
     // and Phase 4's source delete then DESTROYED it — gone from the source
     // without ever arriving at the destination. Staging it here lets it ride
     // the normal rename + cleanup machinery.
+    // An atomic source that failed was discarded from staging; keep its empty
+    // folders out too, or Phase 3 would land them.
+    scan_result
+        .dirs
+        .retain(|d| !failed_atomic.iter().any(|top| d.starts_with(top)));
     if let Err(e) = super::copy::create_scanned_dirs_at_destination(
         &scan_result.dirs,
         sources,
@@ -660,6 +739,7 @@ fn move_with_staging(
         &mut transaction,
         &mut created_dirs,
         &dir_remap,
+        None,
     ) {
         remove_dir_all_in_background(staging_dir.clone());
         events.emit_error(WriteErrorEvent::new(
@@ -676,7 +756,7 @@ fn move_with_staging(
     // be silent data loss (the user clicked Skip to keep both copies). Holds
     // both whole top-level sources (single-file / type-mismatch skip) and
     // per-child paths inside a directory merge.
-    let mut skipped_source_paths: HashSet<PathBuf> = HashSet::new();
+    let mut skipped_source_paths: HashSet<PathBuf> = failed_source_paths;
 
     // Phase 3: Atomic rename from staging to final destination
     let rename_result: Result<(), WriteOperationError> = (|| {
@@ -689,6 +769,12 @@ fn move_with_staging(
             let staged_path = staging_dir.join(file_name);
             let final_path = destination.join(file_name);
 
+            // Nothing of this source was staged (it failed under
+            // `continue_on_error`), so there's nothing to put in place.
+            if !path_exists_or_is_symlink(&staged_path) && skipped_source_paths.iter().any(|p| p.starts_with(source)) {
+                continue;
+            }
+
             // When both staged and final are directories, merge recursively.
             // No MoveTransaction needed here: staging cleanup handles rollback.
             let mut staging_move_tx = MoveTransaction::new();
@@ -813,7 +899,15 @@ fn move_with_staging(
     // Phase 4: Delete source files (only after the destination is durable on
     // disk), skipping any source (or source child) whose copy was discarded on
     // Skip.
-    delete_sources_after_move(events, operation_id, state, sources, files_done, &skipped_source_paths)?;
+    delete_sources_after_move(
+        events,
+        operation_id,
+        state,
+        sources,
+        files_done,
+        &skipped_source_paths,
+        config.include_hidden,
+    )?;
 
     // Phase 5: Remove empty staging directory
     let _ = fs::remove_dir(&staging_dir);
@@ -825,6 +919,9 @@ fn move_with_staging(
         files_processed: files_done,
         files_skipped,
         bytes_processed: bytes_done,
+        errors,
+        files_verified: 0,
+        resumed_reads: 0,
     });
 
     Ok(())
@@ -839,7 +936,9 @@ fn move_with_staging(
 /// wholesale via `remove_dir_all`. A directory source WITH skipped descendants
 /// is walked: every non-skipped child is deleted and directories are removed
 /// only once they're empty, so the skipped child's original survives inside a
-/// surviving source directory.
+/// surviving source directory. With `include_hidden == false` the dotfiles the
+/// scan left out never reached the destination either, so they're kept the
+/// same way.
 fn delete_sources_after_move(
     events: &dyn OperationEventSink,
     operation_id: &str,
@@ -847,6 +946,7 @@ fn delete_sources_after_move(
     sources: &[PathBuf],
     files_done: usize,
     skipped_source_paths: &HashSet<PathBuf>,
+    include_hidden: bool,
 ) -> Result<(), WriteOperationError> {
     for source in sources {
         // Check cancellation
@@ -874,8 +974,8 @@ fn delete_sources_after_move(
                 // Fast path: nothing under this source was skipped, so the whole
                 // tree landed and can be removed wholesale.
                 let has_skipped_descendant = skipped_source_paths.iter().any(|p| p.starts_with(source));
-                if has_skipped_descendant {
-                    delete_dir_preserving_skipped(source, skipped_source_paths)?;
+                if has_skipped_descendant || !include_hidden {
+                    delete_dir_preserving_skipped(source, skipped_source_paths, include_hidden)?;
                 } else {
                     fs::remove_dir_all(source).with_path(source)?;
                 }
@@ -897,21 +997,23 @@ fn delete_sources_after_move(
 /// `skipped_source_paths`, and removes a directory only once it's empty. A
 /// directory that still holds a skipped child (directly or transitively) is
 /// left in place. Used by the cross-FS source-delete phase when some children
-/// were Skipped and the parent therefore can't be removed wholesale.
+/// were Skipped and the parent therefore can't be removed wholesale. Hidden
+/// children are kept too when `include_hidden` is `false`.
 fn delete_dir_preserving_skipped(
     dir: &Path,
     skipped_source_paths: &HashSet<PathBuf>,
+    include_hidden: bool,
 ) -> Result<(), WriteOperationError> {
     let entries = fs::read_dir(dir).with_path(dir)?;
     for entry in entries {
         let child = entry.with_path(dir)?.path();
-        if skipped_source_paths.contains(&child) {
+        if skipped_source_paths.contains(&child) || (!include_hidden && is_hidden(&child)) {
             continue;
         }
         if fs::symlink_metadata(&child).map(|m| m.is_dir()).unwrap_or(false) {
             let has_skipped_descendant = skipped_source_paths.iter().any(|p| p.starts_with(&child));
-            if has_skipped_descendant {
-                delete_dir_preserving_skipped(&child, skipped_source_paths)?;
+            if has_skipped_descendant || !include_hidden {
+                delete_dir_preserving_skipped(&child, skipped_source_paths, include_hidden)?;
             } else {
                 fs::remove_dir_all(&child).with_path(&child)?;
             }
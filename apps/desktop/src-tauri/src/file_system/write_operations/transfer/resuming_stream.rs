@@ -0,0 +1,199 @@
+//! Resume-after-hiccup for cross-volume streaming copies.
+//!
+//! `ResumingStream` is a `VolumeReadStream` decorator that `volume_strategy`'s
+//! `stream_pipe_file` wraps the raw source stream in (inside `CheckpointStream`).
+//! When a chunk fails with a transient source error (an MTP session reset or a
+//! timeout), it waits for the device to come back and reopens the source at the
+//! byte it stopped at via `Volume::open_read_stream_at_offset`, so a phone that
+//! hiccups halfway through a 4 GB video doesn't cost the whole file. Sources that
+//! can't resume (`NotSupported`) surface the original error unchanged.
+
+use std::future::Future;
+use std::path::PathBuf;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use super::super::state::{WriteOperationState, is_cancelled};
+use crate::file_system::volume::{Volume, VolumeError, VolumeReadStream};
+
+/// Waits before each reopen attempt after a transient read error. The first gap
+/// covers an MTP session reopen (~1.5 s); the later ones give a slow device room
+/// to settle. Once they're used up without a successful chunk, the original error
+/// surfaces. Tests shrink it so a resume doesn't cost seconds.
+#[cfg(not(test))]
+const RESUME_BACKOFF: &[Duration] = &[
+    Duration::from_secs(2),
+    Duration::from_secs(4),
+    Duration::from_secs(8),
+    Duration::from_secs(15),
+    Duration::from_secs(15),
+];
+#[cfg(test)]
+const RESUME_BACKOFF: &[Duration] = &[Duration::from_millis(5), Duration::from_millis(10)];
+
+/// How often a backoff wait re-checks for cancellation.
+const CANCEL_POLL_SLICE: Duration = Duration::from_millis(100);
+
+/// Errors worth waiting out: the device is still attached and a retry a few
+/// seconds later works. `DeviceDisconnected` is NOT here: the device is gone.
+fn is_transient(err: &VolumeError) -> bool {
+    matches!(
+        err,
+        VolumeError::DeviceSessionReset(_) | VolumeError::ConnectionTimeout(_)
+    )
+}
+
+/// Wraps a source read stream so a transient mid-file read error reopens the
+/// source at the current offset instead of failing the file.
+///
+/// **Byte exactness.** Chunks pass through untouched and `bytes_yielded` counts
+/// them, so the reopen asks for exactly `[bytes_yielded, total)`. The reopened
+/// stream's `total_size()` must match the original: a different size means the
+/// file changed on the device mid-copy, and splicing two versions together would
+/// be a corrupt file, so the original error surfaces instead.
+///
+/// Each successful reopen bumps `WriteOperationState::resumed_reads`, which the
+/// volume copy reports in its completion log and `WriteCompleteEvent`.
+pub(super) struct ResumingStream {
+    inner: Box<dyn VolumeReadStream>,
+    source_volume: Arc<dyn Volume>,
+    source_path: PathBuf,
+    state: Arc<WriteOperationState>,
+    /// `total_size()` of the first open; every reopen must report the same.
+    total: u64,
+    bytes_yielded: u64,
+    /// Reopen attempts since the last chunk that came through. Indexes
+    /// `RESUME_BACKOFF`, so a device that keeps failing gives up eventually.
+    failures: usize,
+}
+
+impl VolumeReadStream for ResumingStream {
+    fn next_chunk(&mut self) -> Pin<Box<dyn Future<Output = Option<Result<Vec<u8>, VolumeError>>> + Send + '_>> {
+        Box::pin(async move {
+            loop {
+                match self.inner.next_chunk().await {
+                    Some(Ok(chunk)) => {
+                        self.failures = 0;
+                        self.bytes_yielded += chunk.len() as u64;
+                        return Some(Ok(chunk));
+                    }
+                    Some(Err(e)) if is_transient(&e) => {
+                        if let Err(e) = self.reopen_after(e).await {
+                            return Some(Err(e));
+                        }
+                    }
+                    other => return other,
+                }
+            }
+        })
+    }
+
+    fn total_size(&self) -> u64 {
+        self.total
+    }
+
+    fn bytes_read(&self) -> u64 {
+        self.bytes_yielded
+    }
+}
+
+impl ResumingStream {
+    pub(super) fn new(
+        inner: Box<dyn VolumeReadStream>,
+        source_volume: Arc<dyn Volume>,
+        source_path: PathBuf,
+        state: Arc<WriteOperationState>,
+    ) -> Self {
+        let total = inner.total_size();
+        Self {
+            inner,
+            source_volume,
+            source_path,
+            state,
+            total,
+            bytes_yielded: 0,
+            failures: 0,
+        }
+    }
+
+    /// Waits out `err` and swaps in a stream reopened at `bytes_yielded`. Returns
+    /// `err` itself when the backoff is used up, the source can't resume, or the
+    /// file changed size; `Cancelled` when the user cancels during a wait.
+    async fn reopen_after(&mut self, err: VolumeError) -> Result<(), VolumeError> {
+        loop {
+            let Some(&delay) = RESUME_BACKOFF.get(self.failures) else {
+                log::warn!(
+                    "resuming_stream: giving up on {} at byte {} after {} attempts: {}",
+                    self.source_path.display(),
+                    self.bytes_yielded,
+                    self.failures,
+                    err
+                );
+                return Err(err);
+            };
+            self.failures += 1;
+            log::info!(
+                "resuming_stream: {} failed at byte {} ({}); reopening in {:?}",
+                self.source_path.display(),
+                self.bytes_yielded,
+                err,
+                delay
+            );
+            if !self.sleep_unless_cancelled(delay).await {
+                return Err(VolumeError::Cancelled("Operation cancelled by user".to_string()));
+            }
+
+            match self
+                .source_volume
+                .open_read_stream_at_offset(&self.source_path, self.bytes_yielded)
+                .await
+            {
+                Ok(stream) if stream.total_size() == self.total => {
+                    self.inner = stream;
+                    self.state.resumed_reads.fetch_add(1, Ordering::Relaxed);
+                    log::info!(
+                        "resuming_stream: resumed {} at byte {}",
+                        self.source_path.display(),
+                        self.bytes_yielded
+                    );
+                    return Ok(());
+                }
+                Ok(stream) => {
+                    log::warn!(
+                        "resuming_stream: {} changed size on the source ({} -> {}); not resuming",
+                        self.source_path.display(),
+                        self.total,
+                        stream.total_size()
+                    );
+                    return Err(err);
+                }
+                Err(reopen_err) if is_transient(&reopen_err) => continue,
+                Err(reopen_err) => {
+                    log::debug!(
+                        "resuming_stream: can't reopen {}: {}",
+                        self.source_path.display(),
+                        reopen_err
+                    );
+                    return Err(err);
+                }
+            }
+        }
+    }
+
+    /// Sleeps for `delay` in short slices. Returns `false` as soon as the op is
+    /// cancelled.
+    async fn sleep_unless_cancelled(&self, delay: Duration) -> bool {
+        let mut left = delay;
+        while !left.is_zero() {
+            if is_cancelled(&self.state.intent) {
+                return false;
+            }
+            let slice = left.min(CANCEL_POLL_SLICE);
+            tokio::time::sleep(slice).await;
+            left -= slice;
+        }
+        !is_cancelled(&self.state.intent)
+    }
+}
@@ -96,6 +96,7 @@ where
     let mut bytes_done = 0u64;
     let mut files_skipped = 0usize;
     let mut bytes_skipped = 0u64;
+    let mut errors = Vec::new();
     let mut last_progress_time = Instant::now();
 
     // ---- Pre-known-conflicts bulk-skip prelude. ----
@@ -146,6 +147,7 @@ where
                 bytes_done,
                 files_skipped,
                 bytes_skipped,
+                errors,
                 intent: PostLoopIntent::Cancelled,
             };
         }
@@ -228,6 +230,7 @@ where
                         bytes_done,
                         files_skipped,
                         bytes_skipped,
+                        errors,
                         intent: PostLoopIntent::Failed(e),
                     };
                 }
@@ -298,12 +301,33 @@ where
                     );
                 }
             }
+            Ok(TransferOutcome::Failed { error, bytes_accounted }) => {
+                files_done += 1;
+                bytes_done += bytes_accounted;
+                errors.push(error);
+                if last_progress_time.elapsed() >= progress_interval {
+                    last_progress_time = Instant::now();
+                    emit_progress_and_status(
+                        events,
+                        state,
+                        operation_id,
+                        config.operation_type,
+                        config.phase,
+                        source_path.file_name().map(|n| n.to_string_lossy().to_string()),
+                        files_done,
+                        total_files,
+                        bytes_done,
+                        total_bytes,
+                    );
+                }
+            }
             Err(WriteOperationError::Cancelled { .. }) => {
                 return TransferLoopOutcome {
                     files_done,
                     bytes_done,
                     files_skipped,
                     bytes_skipped,
+                    errors,
                     intent: PostLoopIntent::Cancelled,
                 };
             }
@@ -313,6 +337,7 @@ where
                     bytes_done,
                     files_skipped,
                     bytes_skipped,
+                    errors,
                     intent: PostLoopIntent::Failed(e),
                 };
             }
@@ -330,6 +355,7 @@ where
             bytes_done,
             files_skipped,
             bytes_skipped,
+            errors,
             intent: PostLoopIntent::Cancelled,
         };
     }
@@ -339,6 +365,7 @@ where
         bytes_done,
         files_skipped,
         bytes_skipped,
+        errors,
         intent: PostLoopIntent::Completed,
     }
 }
@@ -198,6 +198,15 @@ pub(super) enum TransferOutcome {
     /// `files_done` and adds `bytes_accounted` to `bytes_done` so the progress
     /// bar reflects the skip immediately.
     Skipped { bytes_accounted: u64 },
+    /// Source failed, but the op runs with `continue_on_error`, so the loop
+    /// keeps going. The driver counts it as done (adding `bytes_accounted` so
+    /// the bar still reaches 100 %) and collects `error` into
+    /// [`TransferLoopOutcome::errors`]. A closure that wants fail-fast returns
+    /// `Err` instead.
+    Failed {
+        error: WriteOperationError,
+        bytes_accounted: u64,
+    },
 }
 
 /// Driver's return value to the caller.
@@ -214,6 +223,9 @@ pub(super) struct TransferLoopOutcome {
     pub bytes_done: u64,
     pub files_skipped: usize,
     pub bytes_skipped: u64,
+    /// Per-source failures the closure reported as `TransferOutcome::Failed`,
+    /// in iteration order. Always empty without `continue_on_error`.
+    pub errors: Vec<WriteOperationError>,
     pub intent: PostLoopIntent,
 }
 
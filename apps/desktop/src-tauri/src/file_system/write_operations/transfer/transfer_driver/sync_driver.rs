@@ -36,7 +36,8 @@ use super::{
 ///    `PostLoopIntent::Cancelled`. b. Skip pre-known-conflict sources (no closure invocation). c.
 ///    Call `transfer_one`. On `Ok`, update counters. On `Err` with
 ///    `WriteOperationError::Cancelled`, return `PostLoopIntent::Cancelled`. On any other `Err`,
-///    return `PostLoopIntent::Failed`.
+///    return `PostLoopIntent::Failed`. On `Ok(TransferOutcome::Failed)` (the closure's
+///    `continue_on_error` verdict), collect the error and keep going.
 /// 3. Return `PostLoopIntent::Completed` if the loop drained without incident.
 ///
 /// # Why no async / no boxed-future variant
@@ -68,6 +69,7 @@ where
     let mut bytes_done = 0u64;
     let mut files_skipped = 0usize;
     let mut bytes_skipped = 0u64;
+    let mut errors = Vec::new();
 
     // ---- Pre-known-conflicts bulk-skip prelude. ----
     // The caller has already filtered the source set (sync ops are per-file
@@ -122,6 +124,7 @@ where
                 bytes_done,
                 files_skipped,
                 bytes_skipped,
+                errors,
                 intent: PostLoopIntent::Cancelled,
             };
         }
@@ -187,12 +190,36 @@ where
                     total_bytes,
                 );
             }
+            Ok(TransferOutcome::Failed { error, bytes_accounted }) => {
+                log::warn!(
+                    "drive_transfer_serial_sync: continuing past failed source {} for op={}: {:?}",
+                    source_path.display(),
+                    operation_id,
+                    error
+                );
+                files_done += 1;
+                bytes_done += bytes_accounted;
+                errors.push(error);
+                emit_progress_and_status(
+                    events,
+                    state,
+                    operation_id,
+                    config.operation_type,
+                    config.phase,
+                    source_path.file_name().map(|n| n.to_string_lossy().to_string()),
+                    files_done,
+                    total_files,
+                    bytes_done,
+                    total_bytes,
+                );
+            }
             Err(WriteOperationError::Cancelled { .. }) => {
                 return TransferLoopOutcome {
                     files_done,
                     bytes_done,
                     files_skipped,
                     bytes_skipped,
+                    errors,
                     intent: PostLoopIntent::Cancelled,
                 };
             }
@@ -202,6 +229,7 @@ where
                     bytes_done,
                     files_skipped,
                     bytes_skipped,
+                    errors,
                     intent: PostLoopIntent::Failed(e),
                 };
             }
@@ -213,6 +241,7 @@ where
         bytes_done,
         files_skipped,
         bytes_skipped,
+        errors,
         intent: PostLoopIntent::Completed,
     }
 }
@@ -283,6 +283,60 @@ fn sync_driver_skipped_outcome_bumps_counters_and_emits_progress() {
     unregister_operation_status(&op_id);
 }
 
+#[test]
+fn sync_driver_failed_outcome_collects_the_error_and_keeps_going() {
+    let op_id = unique_op_id("sync-failed-outcome");
+    let state = make_state();
+    let _op_guard = install_state(&op_id, Arc::clone(&state));
+    register_operation_status(&op_id, WriteOperationType::Copy, vec![]);
+    let sink = CollectorEventSink::new();
+    let log = CallLog::new();
+    let log_clone = Arc::clone(&log);
+
+    let outcome = drive_transfer_serial_sync(
+        &sink,
+        &state,
+        &op_id,
+        &paths(&["/a", "/locked", "/c"]),
+        3,
+        300,
+        0,
+        0,
+        &HashSet::new(),
+        &copy_config(),
+        |ctx| {
+            log_clone.record(ctx.source_path, None);
+            if ctx.source_path == Path::new("/locked") {
+                Ok(TransferOutcome::Failed {
+                    error: WriteOperationError::PermissionDenied {
+                        path: "/locked".to_string(),
+                        message: "locked".to_string(),
+                    },
+                    bytes_accounted: 100,
+                })
+            } else {
+                Ok(TransferOutcome::Transferred { bytes: 100 })
+            }
+        },
+    );
+
+    assert!(matches!(outcome.intent, PostLoopIntent::Completed));
+    assert_eq!(
+        log.sources(),
+        paths(&["/a", "/locked", "/c"]),
+        "the loop ran past the failure"
+    );
+    assert_eq!(outcome.files_done, 3);
+    assert_eq!(outcome.bytes_done, 300, "a failure still fills its share of the bar");
+    assert_eq!(outcome.files_skipped, 0, "a failure isn't a skip");
+    assert_eq!(outcome.errors.len(), 1);
+    assert!(matches!(
+        &outcome.errors[0],
+        WriteOperationError::PermissionDenied { path, .. } if path == "/locked"
+    ));
+    unregister_operation_status(&op_id);
+}
+
 #[test]
 fn sync_driver_bulk_skip_emits_one_progress_event() {
     let op_id = unique_op_id("sync-bulk-skip-event");
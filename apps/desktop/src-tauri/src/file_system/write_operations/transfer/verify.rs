@@ -0,0 +1,100 @@
+//! Post-copy verification for `WriteOperationConfig::verify`.
+//!
+//! The copied file is re-read from the destination and its BLAKE3 hash compared
+//! with the source's. Chunked copy hashes the source while it reads it (see
+//! `chunked_copy_with_metadata`'s `source_hasher`). Native copies (`copyfile(3)`,
+//! `copy_file_range(2)`, `std::fs::copy`) never pass the bytes through our code,
+//! so for those the source gets its own read pass here.
+//!
+//! BLAKE3 rather than a dedicated non-cryptographic hash: it's already the
+//! content hash for duplicate detection and checksums, and it outruns the disk
+//! either way.
+
+use std::io::Read;
+use std::path::Path;
+use std::sync::atomic::AtomicU8;
+
+use super::super::state::is_cancelled;
+use super::super::types::WriteOperationError;
+
+/// Read buffer for the verification pass. Matches chunked copy's chunk size, so
+/// cancellation is checked as often.
+const VERIFY_CHUNK_SIZE: usize = 1024 * 1024;
+
+/// Hashes a file in chunks, checking cancellation between chunks.
+pub(super) fn hash_file(path: &Path, cancelled: &AtomicU8) -> Result<blake3::Hash, WriteOperationError> {
+    let read_error = |e: std::io::Error| WriteOperationError::ReadError {
+        path: path.display().to_string(),
+        message: format!("Couldn't read file to verify it: {}", e),
+    };
+    let mut file = std::fs::File::open(path).map_err(read_error)?;
+    let mut hasher = blake3::Hasher::new();
+    let mut buffer = vec![0u8; VERIFY_CHUNK_SIZE];
+    loop {
+        if is_cancelled(cancelled) {
+            return Err(WriteOperationError::Cancelled {
+                message: "Operation cancelled by user".to_string(),
+            });
+        }
+        let read = file.read(&mut buffer).map_err(read_error)?;
+        if read == 0 {
+            return Ok(hasher.finalize());
+        }
+        hasher.update(&buffer[..read]);
+    }
+}
+
+/// Checks that `dest` holds the same bytes as `source`. `source_hash` is the hash
+/// the copy computed while reading, or `None` to read the source again here.
+///
+/// Fails with `VerificationFailed { path: dest }` on a mismatch.
+pub(super) fn verify_copy(
+    source: &Path,
+    dest: &Path,
+    source_hash: Option<blake3::Hash>,
+    cancelled: &AtomicU8,
+) -> Result<(), WriteOperationError> {
+    let expected = match source_hash {
+        Some(hash) => hash,
+        None => hash_file(source, cancelled)?,
+    };
+    let actual = hash_file(dest, cancelled)?;
+    if actual != expected {
+        log::warn!(
+            "verify: {} doesn't match its source {} after the copy",
+            dest.display(),
+            source.display()
+        );
+        return Err(WriteOperationError::VerificationFailed {
+            path: dest.display().to_string(),
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verify_copy_passes_identical_files_and_flags_a_changed_byte() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("a.bin");
+        let dest = dir.path().join("b.bin");
+        let payload: Vec<u8> = (0..VERIFY_CHUNK_SIZE + 10).map(|i| (i % 199) as u8).collect();
+        std::fs::write(&source, &payload).unwrap();
+        std::fs::write(&dest, &payload).unwrap();
+        let running = AtomicU8::new(0);
+
+        verify_copy(&source, &dest, None, &running).unwrap();
+        verify_copy(&source, &dest, Some(blake3::hash(&payload)), &running).unwrap();
+
+        let mut corrupted = payload.clone();
+        corrupted[VERIFY_CHUNK_SIZE + 3] ^= 1;
+        std::fs::write(&dest, &corrupted).unwrap();
+        match verify_copy(&source, &dest, None, &running) {
+            Err(WriteOperationError::VerificationFailed { path }) => assert_eq!(path, dest.display().to_string()),
+            other => panic!("expected VerificationFailed, got {other:?}"),
+        }
+    }
+}
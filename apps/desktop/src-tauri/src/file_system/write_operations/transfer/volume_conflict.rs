@@ -218,6 +218,7 @@ pub(super) async fn resolve_volume_conflict(
                 size_difference,
                 source_is_directory,
                 destination_is_directory,
+                is_case_only: false,
             });
 
             // Wait for user to call resolve_write_conflict.
@@ -194,6 +194,8 @@ pub async fn copy_between_volumes(
             max_conflicts_to_show: config.max_conflicts_to_show,
             preview_id: config.preview_id,
             pre_known_conflicts: config.pre_known_conflicts,
+            strip_quarantine: config.strip_quarantine,
+            include_hidden: config.include_hidden,
             ..Default::default()
         };
 
@@ -442,6 +444,17 @@ fn format_skipped_suffix(files_skipped: usize, bytes_skipped: u64) -> String {
     )
 }
 
+/// Formats the trailing ", resumed N read(s)" annotation for the completion log:
+/// how often a file's source stream was reopened mid-file after a transient
+/// error (see `resuming_stream.rs`). Empty when nothing resumed.
+fn format_resumed_suffix(resumed_reads: usize) -> String {
+    match resumed_reads {
+        0 => String::new(),
+        1 => ", resumed 1 read".to_string(),
+        n => format!(", resumed {n} reads"),
+    }
+}
+
 /// Bumps `files_done` and `bytes_done` for a skipped source and (throttled)
 /// emits a `write-progress` event. Without this, a "Skip all" choice silently
 /// runs through dozens of conflicts with the progress bar pinned at 0% — the
@@ -1746,11 +1759,12 @@ pub(crate) async fn copy_volumes_with_progress(
     if copy_error.is_none() && !is_cancelled(&state.intent) {
         // All files copied successfully
         log::info!(
-            "copy_volumes_with_progress: completed op={} files={} bytes={}{}",
+            "copy_volumes_with_progress: completed op={} files={} bytes={}{}{}",
             operation_id,
             files_done,
             bytes_done,
             format_skipped_suffix(files_skipped, bytes_skipped),
+            format_resumed_suffix(state.resumed_reads.load(Ordering::Relaxed)),
         );
 
         // Journal the directories the copy created as `dir` rows on the dest
@@ -1767,6 +1781,9 @@ pub(crate) async fn copy_volumes_with_progress(
             files_processed: files_done,
             files_skipped,
             bytes_processed: bytes_done,
+            errors: Vec::new(),
+            files_verified: 0,
+            resumed_reads: state.resumed_reads.load(Ordering::Relaxed),
         });
 
         return Ok(());
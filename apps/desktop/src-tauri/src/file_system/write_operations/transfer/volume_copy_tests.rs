@@ -43,6 +43,13 @@ fn test_format_skipped_suffix_plural() {
     );
 }
 
+#[test]
+fn test_format_resumed_suffix() {
+    assert_eq!(format_resumed_suffix(0), "");
+    assert_eq!(format_resumed_suffix(1), ", resumed 1 read");
+    assert_eq!(format_resumed_suffix(3), ", resumed 3 reads");
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_scan_for_volume_copy_empty_source_returns_error_without_space_info() {
     // InMemoryVolume without configured space_info returns NotSupported for get_space_info
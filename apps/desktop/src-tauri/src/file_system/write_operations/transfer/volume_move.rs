@@ -105,6 +105,8 @@ pub async fn move_between_volumes(
             max_conflicts_to_show: config.max_conflicts_to_show,
             preview_id: config.preview_id,
             pre_known_conflicts: config.pre_known_conflicts,
+            strip_quarantine: config.strip_quarantine,
+            include_hidden: config.include_hidden,
             ..Default::default()
         };
 
@@ -666,6 +668,9 @@ pub(crate) async fn move_volumes_with_progress(
                 files_processed: files_done,
                 files_skipped,
                 bytes_processed: bytes_done,
+                errors: Vec::new(),
+                files_verified: 0,
+                resumed_reads: 0,
             });
             Ok(())
         }
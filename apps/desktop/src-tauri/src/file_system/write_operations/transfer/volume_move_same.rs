@@ -591,6 +591,9 @@ pub(crate) async fn move_within_same_volume_with_progress(
                 files_processed: files_moved,
                 files_skipped,
                 bytes_processed: bytes_moved,
+                errors: Vec::new(),
+                files_verified: 0,
+                resumed_reads: 0,
             });
             Ok(())
         }
@@ -19,6 +19,7 @@ use super::super::conflict::ApplyToAll;
 use super::super::state::WriteOperationState;
 use super::super::types::{OperationEventSink, VolumeCopyConfig, WriteOperationError};
 use super::checkpoint_stream::CheckpointStream;
+use super::resuming_stream::ResumingStream;
 use super::volume_conflict::{ResolvedConflict, resolve_volume_conflict};
 use super::volume_preflight::SourceHint;
 use crate::file_system::listing::FileEntry;
@@ -377,6 +378,16 @@ async fn stream_pipe_file(
             .open_read_stream_with_hint(source_path, source_size_hint)
             .await?;
         let size = stream.total_size();
+        // Innermost wrapper: a transient source error mid-file (MTP session
+        // reset, timeout) reopens the source at the current offset instead of
+        // failing the file. Sources without `open_read_stream_at_offset` surface
+        // the original error. See `resuming_stream.rs`.
+        let stream: Box<dyn VolumeReadStream> = Box::new(ResumingStream::new(
+            stream,
+            Arc::clone(source_volume),
+            source_path.to_path_buf(),
+            Arc::clone(state),
+        ));
         // Wrap so a paused op parks (and a long copy yields to foreground)
         // between bounded windows. `size` is read off the raw stream first — the
         // wrapper forwards `total_size()` unchanged, so the destination still sees
@@ -767,6 +778,9 @@ mod dest_yield_tests;
 #[path = "volume_strategy_pause_tests.rs"]
 mod pause_tests;
 #[cfg(test)]
+#[path = "volume_strategy_resume_tests.rs"]
+mod resume_tests;
+#[cfg(test)]
 #[path = "volume_strategy_sequential_tests.rs"]
 mod sequential_tests;
 #[cfg(test)]
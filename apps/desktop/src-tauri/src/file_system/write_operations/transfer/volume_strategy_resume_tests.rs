@@ -0,0 +1,145 @@
+//! Resume-after-transient-error tests for `volume_strategy.rs`'s
+//! `copy_single_path`.
+//!
+//! `stream_pipe_file` wraps the source in a `ResumingStream`, so a read that
+//! fails mid-file with a session reset or timeout reopens the source at the
+//! byte it stopped at instead of failing the file. The `HiccupSource` double
+//! fails its first stream once at a chunk boundary and records every open, so
+//! the tests pin the reopen offset and the byte-exact destination, and a whole
+//! copy reports the resume in its `WriteCompleteEvent`. A failure the source
+//! can't resume from must leave nothing behind in the destination folder.
+
+use super::super::volume_copy::copy_volumes_with_progress;
+use super::test_support::{HiccupSource, REL_CHUNK, RelLog, make_state, rel_expected_bytes};
+use super::*;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::Ordering;
+use std::sync::{Arc, Mutex as StdMutex};
+
+use crate::file_system::volume::{InMemoryVolume, LocalPosixVolume, Volume, VolumeError};
+use crate::file_system::write_operations::types::{CollectorEventSink, VolumeCopyConfig};
+
+const FAIL_AT: u64 = 5 * REL_CHUNK as u64;
+
+fn fresh_dest_dir(name: &str) -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(name);
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+async fn copy_from(
+    source: &Arc<dyn Volume>,
+    dest: &Arc<dyn Volume>,
+    state: &Arc<WriteOperationState>,
+) -> Result<u64, VolumeError> {
+    copy_single_path(
+        source,
+        Path::new("/photo.jpg"),
+        false,
+        None,
+        dest,
+        Path::new("photo.jpg"),
+        state,
+        &CreatedPaths::default(),
+        &|_, _| ControlFlow::Continue(()),
+        &|_| {},
+        None,
+    )
+    .await
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn session_reset_mid_file_resumes_at_the_failed_offset() {
+    let dest_dir = fresh_dest_dir("cmdr_resume_session_reset");
+    let log = Arc::new(StdMutex::new(RelLog::default()));
+    let source: Arc<dyn Volume> = Arc::new(HiccupSource {
+        log: Arc::clone(&log),
+        fail_at: FAIL_AT,
+        error: VolumeError::DeviceSessionReset("session reopening".to_string()),
+        resumable: true,
+    });
+    let dest: Arc<dyn Volume> = Arc::new(LocalPosixVolume::new("Dest", dest_dir.to_str().unwrap()));
+    let state = make_state();
+
+    let bytes = copy_from(&source, &dest, &state)
+        .await
+        .expect("a session reset mid-file must resume, not fail the copy");
+
+    let expected = rel_expected_bytes();
+    assert_eq!(bytes, expected.len() as u64);
+    assert_eq!(
+        std::fs::read(dest_dir.join("photo.jpg")).unwrap(),
+        expected,
+        "the resumed copy must be byte-exact: no gap or overlap at the reopen"
+    );
+    assert_eq!(
+        log.lock().unwrap().opens,
+        vec![0, FAIL_AT],
+        "one reopen, at the failed offset"
+    );
+    assert_eq!(state.resumed_reads.load(Ordering::Relaxed), 1);
+
+    let _ = std::fs::remove_dir_all(&dest_dir);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn a_whole_copy_reports_its_resumes_in_the_completion_event() {
+    let source: Arc<dyn Volume> = Arc::new(HiccupSource {
+        log: Arc::new(StdMutex::new(RelLog::default())),
+        fail_at: FAIL_AT,
+        error: VolumeError::DeviceSessionReset("session reopening".to_string()),
+        resumable: true,
+    });
+    let dest: Arc<dyn Volume> = Arc::new(InMemoryVolume::new("Dest").with_space_info(10_000_000, 10_000_000));
+    let events = Arc::new(CollectorEventSink::new());
+
+    let result = copy_volumes_with_progress(
+        events.clone(),
+        "test-op-resume",
+        &make_state(),
+        Arc::clone(&source),
+        &[PathBuf::from("/photo.jpg")],
+        Arc::clone(&dest),
+        Path::new("/"),
+        &VolumeCopyConfig::default(),
+    )
+    .await;
+
+    assert!(result.is_ok(), "copy should succeed: {result:?}");
+    let complete = events.complete.lock().unwrap();
+    assert_eq!(complete.len(), 1);
+    assert_eq!(complete[0].resumed_reads, 1);
+    assert_eq!(complete[0].bytes_processed, rel_expected_bytes().len() as u64);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn unresumable_failure_surfaces_the_error_and_leaves_no_partial() {
+    let dest_dir = fresh_dest_dir("cmdr_resume_unsupported");
+    let log = Arc::new(StdMutex::new(RelLog::default()));
+    let source: Arc<dyn Volume> = Arc::new(HiccupSource {
+        log: Arc::clone(&log),
+        fail_at: FAIL_AT,
+        error: VolumeError::ConnectionTimeout("read window timed out".to_string()),
+        resumable: false,
+    });
+    let dest: Arc<dyn Volume> = Arc::new(LocalPosixVolume::new("Dest", dest_dir.to_str().unwrap()));
+    let state = make_state();
+
+    let err = copy_from(&source, &dest, &state)
+        .await
+        .expect_err("a source that can't reopen at an offset must fail the file");
+
+    assert!(
+        matches!(err, VolumeError::ConnectionTimeout(_)),
+        "the original error surfaces, not the reopen's NotSupported: {err:?}"
+    );
+    let left: Vec<_> = std::fs::read_dir(&dest_dir).unwrap().collect();
+    assert!(
+        left.is_empty(),
+        "no truncated file or `.part` may be left behind: {left:?}"
+    );
+    assert_eq!(state.resumed_reads.load(Ordering::Relaxed), 0);
+
+    let _ = std::fs::remove_dir_all(&dest_dir);
+}
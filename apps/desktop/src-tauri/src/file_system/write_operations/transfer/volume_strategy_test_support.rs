@@ -1,6 +1,7 @@
 //! Shared fixtures and test doubles for the `volume_strategy.rs` test suites
 //! (`volume_strategy_copy_tests.rs`, `volume_strategy_pause_tests.rs`,
-//! `volume_strategy_yield_tests.rs`, `volume_strategy_stale_handle_tests.rs`).
+//! `volume_strategy_yield_tests.rs`, `volume_strategy_stale_handle_tests.rs`,
+//! `volume_strategy_resume_tests.rs`).
 //!
 //! Holds the custom `Volume` / `VolumeReadStream` doubles every suite shares
 //! plus the auto-yield tuning override. Items are `pub(super)` so the sibling
@@ -17,7 +18,7 @@ use std::sync::{Arc, Mutex as StdMutex};
 use std::time::Duration;
 
 use crate::file_system::listing::FileEntry;
-use crate::file_system::volume::{ListingProgress, Volume, VolumeError, VolumeReadStream};
+use crate::file_system::volume::{CopyScanResult, ListingProgress, Volume, VolumeError, VolumeReadStream};
 use crate::ignore_poison::IgnorePoison;
 
 pub(super) fn make_state() -> Arc<WriteOperationState> {
@@ -367,6 +368,137 @@ pub(super) fn rel_expected_bytes() -> Vec<u8> {
     (0..REL_TOTAL as u64).map(|p| (p % 256) as u8).collect()
 }
 
+// ========================================================================
+// Mid-file transient source error (resume): the offset-pattern stream, but the
+// first open fails once partway through, the way an MTP read does when the
+// device's session resets.
+// ========================================================================
+
+/// A [`ReleasingStream`] that returns `error` once its position reaches
+/// `fail_at`, then ends (a failed MTP read session is single-use).
+pub(super) struct HiccupStream {
+    inner: ReleasingStream,
+    fail_at: Option<u64>,
+    error: VolumeError,
+}
+
+impl VolumeReadStream for HiccupStream {
+    fn next_chunk(&mut self) -> Pin<Box<dyn Future<Output = Option<Result<Vec<u8>, VolumeError>>> + Send + '_>> {
+        Box::pin(async move {
+            match self.fail_at {
+                Some(at) if self.inner.pos >= at => {
+                    self.fail_at = None;
+                    self.inner.pos = REL_TOTAL as u64;
+                    Some(Err(self.error.clone()))
+                }
+                _ => self.inner.next_chunk().await,
+            }
+        })
+    }
+
+    fn total_size(&self) -> u64 {
+        self.inner.total_size()
+    }
+
+    fn bytes_read(&self) -> u64 {
+        self.inner.bytes_read()
+    }
+}
+
+/// A source whose FIRST stream fails with `error` at byte `fail_at`. Later
+/// opens (at any offset) read cleanly when `resumable`; otherwise a non-zero
+/// offset is `NotSupported`, like every backend but MTP. Records every open in
+/// `log.opens`.
+pub(super) struct HiccupSource {
+    pub(super) log: Arc<StdMutex<RelLog>>,
+    pub(super) fail_at: u64,
+    pub(super) error: VolumeError,
+    pub(super) resumable: bool,
+}
+
+impl Volume for HiccupSource {
+    fn name(&self) -> &str {
+        "hiccup-source"
+    }
+    fn root(&self) -> &Path {
+        Path::new("/")
+    }
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+    fn list_directory<'a>(
+        &'a self,
+        _path: &'a Path,
+        _on_progress: Option<&'a (dyn Fn(ListingProgress) + Sync)>,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<FileEntry>, VolumeError>> + Send + 'a>> {
+        Box::pin(async { Ok(Vec::new()) })
+    }
+    fn get_metadata<'a>(
+        &'a self,
+        _path: &'a Path,
+    ) -> Pin<Box<dyn Future<Output = Result<FileEntry, VolumeError>> + Send + 'a>> {
+        Box::pin(async { Err(VolumeError::NotSupported) })
+    }
+    fn exists<'a>(&'a self, _path: &'a Path) -> Pin<Box<dyn Future<Output = bool> + Send + 'a>> {
+        Box::pin(async { true })
+    }
+    fn is_directory<'a>(
+        &'a self,
+        _path: &'a Path,
+    ) -> Pin<Box<dyn Future<Output = Result<bool, VolumeError>> + Send + 'a>> {
+        Box::pin(async { Ok(false) })
+    }
+    fn supports_streaming(&self) -> bool {
+        true
+    }
+    fn scan_for_copy<'a>(
+        &'a self,
+        _path: &'a Path,
+    ) -> Pin<Box<dyn Future<Output = Result<CopyScanResult, VolumeError>> + Send + 'a>> {
+        // One file, so a whole `copy_volumes_with_progress` run gets past its preflight scan.
+        Box::pin(async {
+            Ok(CopyScanResult {
+                file_count: 1,
+                dir_count: 0,
+                total_bytes: REL_TOTAL as u64,
+                dedup_bytes: REL_TOTAL as u64,
+                top_level_is_directory: false,
+            })
+        })
+    }
+    fn open_read_stream<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> Pin<Box<dyn Future<Output = Result<Box<dyn VolumeReadStream>, VolumeError>> + Send + 'a>> {
+        self.open_read_stream_at_offset(path, 0)
+    }
+    fn open_read_stream_at_offset<'a>(
+        &'a self,
+        _path: &'a Path,
+        offset: u64,
+    ) -> Pin<Box<dyn Future<Output = Result<Box<dyn VolumeReadStream>, VolumeError>> + Send + 'a>> {
+        Box::pin(async move {
+            if offset > 0 && !self.resumable {
+                return Err(VolumeError::NotSupported);
+            }
+            let mut log = self.log.lock_ignore_poison();
+            let first = log.opens.is_empty();
+            log.opens.push(offset);
+            Ok(Box::new(HiccupStream {
+                inner: ReleasingStream {
+                    log: Arc::clone(&self.log),
+                    pos: offset,
+                    emitted_here: 0,
+                    released: false,
+                    gate: None,
+                },
+                fail_at: first.then_some(self.fail_at),
+                error: self.error.clone(),
+            }) as Box<dyn VolumeReadStream>)
+        })
+    }
+}
+
 // ========================================================================
 // Foreground auto-yield doubles and tuning override.
 // ========================================================================
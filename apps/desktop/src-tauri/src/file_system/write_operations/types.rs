@@ -36,6 +36,10 @@ pub use super::event_sinks::OperationEventSink;
 /// that flows through `spawn_managed` with a real progress bar and the parent
 /// drive's lane, NOT the instant path (a rewrite is not a metadata syscall). It
 /// crosses the wire as `archive_edit`.
+///
+/// `SetPermissions` is a recursive chmod (`permissions.rs`): managed, with
+/// progress and cancel, on the local drive's lane. A single-item chmod is a
+/// plain syscall and never becomes an op. It crosses the wire as `set_permissions`.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, specta::Type)]
 #[serde(rename_all = "snake_case")]
 pub enum WriteOperationType {
@@ -47,6 +51,7 @@ pub enum WriteOperationType {
     CreateFolder,
     CreateFile,
     ArchiveEdit,
+    SetPermissions,
 }
 
 /// Phase of the operation (for progress reporting).
@@ -55,6 +60,8 @@ pub enum WriteOperationType {
 pub enum WriteOperationPhase {
     /// Scanning source files to calculate total size
     Scanning,
+    /// Downloading online-only cloud sources before copying them (see `cloud_materialize.rs`)
+    Materializing,
     /// Copying files (for copy and cross-filesystem move)
     Copying,
     /// Deleting files (for delete, and cleanup phase of cross-filesystem move)
@@ -69,6 +76,8 @@ pub enum WriteOperationPhase {
     /// piece…" so the bar doesn't sit frozen at 100% pretending the work is
     /// done. See `transfer/CLAUDE.md` § "Durability".
     Flushing,
+    /// Changing the mode of every item in a tree (recursive `set_permissions`)
+    SettingPermissions,
 }
 
 // ============================================================================
@@ -167,6 +176,19 @@ pub struct WriteCompleteEvent {
     pub files_processed: usize,
     pub files_skipped: usize,
     pub bytes_processed: u64,
+    /// Items that failed while the op ran with `continue_on_error`, one per
+    /// path, in the order they failed. Always empty otherwise: without it the
+    /// first failure ends the op with `write-error` instead.
+    #[serde(default)]
+    pub errors: Vec<WriteOperationError>,
+    /// Files whose copy was read back and matched the source. Only a copy run
+    /// with `verify` counts these; always 0 otherwise.
+    #[serde(default)]
+    pub files_verified: usize,
+    /// Times a cross-volume copy reopened its source mid-file after a transient
+    /// read error (`ResumingStream`). Always 0 for other ops.
+    #[serde(default)]
+    pub resumed_reads: usize,
 }
 
 /// Error event payload.
@@ -181,6 +203,8 @@ pub struct WriteCompleteEvent {
 pub struct WriteErrorEvent {
     pub operation_id: String,
     pub operation_type: WriteOperationType,
+    /// Stable code for `error` (`WriteOperationError::code`), like `"DISK_FULL"`.
+    pub code: String,
     pub error: WriteOperationError,
 }
 
@@ -194,6 +218,19 @@ pub struct WriteSourceItemDoneEvent {
     pub source_path: String,
 }
 
+/// Emitted once per destination path component that `sanitize_names` rewrote,
+/// so the FE can tell the user which names changed on the way.
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type, Event)]
+#[serde(rename_all = "camelCase")]
+#[tauri_specta(event_name = "write-renamed")]
+pub struct WriteRenamedEvent {
+    pub operation_id: String,
+    /// The source file or folder whose name was rewritten.
+    pub source_path: String,
+    pub original_name: String,
+    pub new_name: String,
+}
+
 /// Cancelled event payload.
 #[derive(Debug, Clone, Serialize, Deserialize, specta::Type, Event)]
 #[serde(rename_all = "camelCase")]
@@ -235,6 +272,18 @@ pub struct WriteSettledEvent {
     pub volume_id: Option<String>,
 }
 
+/// Emitted when an operation is paused or resumed (`pause_operation` /
+/// `resume_operation`). `paused_ms` is the total time it has spent paused so
+/// far; the ETA in `write-progress` already leaves that time out.
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type, Event)]
+#[serde(rename_all = "camelCase")]
+#[tauri_specta(event_name = "write-paused")]
+pub struct WritePausedEvent {
+    pub operation_id: String,
+    pub paused: bool,
+    pub paused_ms: u64,
+}
+
 /// Conflict event payload (emitted when Stop mode encounters a conflict).
 #[derive(Debug, Clone, Serialize, Deserialize, specta::Type, Event)]
 #[serde(rename_all = "camelCase")]
@@ -273,6 +322,12 @@ pub struct WriteConflictEvent {
     /// `source_is_directory`.
     #[serde(default)]
     pub destination_is_directory: bool,
+    /// `true` when the destination item's name differs from the source's only
+    /// by case (`readme.md` vs `README.md`) and the filesystem folds case, so
+    /// overwriting replaces a differently-named file. Such a clash always
+    /// prompts, even under a configured or "apply to all" overwrite.
+    #[serde(default)]
+    pub is_case_only: bool,
 }
 
 /// Progress event during scanning phase (emitted in dry-run mode).
@@ -468,6 +523,11 @@ pub enum WriteOperationError {
         path: String,
         wrong_attempt: bool,
     },
+    /// The copy's bytes don't match the source's when read back (`verify` on).
+    /// `path` is the destination; the bad copy is removed.
+    VerificationFailed {
+        path: String,
+    },
     /// Catch-all for genuinely unexpected IO errors.
     IoError {
         path: String,
@@ -528,6 +588,41 @@ pub struct WriteOperationConfig {
     /// `VolumeCopyConfig::pre_known_conflicts` for the full rationale.
     #[serde(default)]
     pub pre_known_conflicts: Vec<String>,
+    /// Drop `com.apple.quarantine` from copied files, so downloaded apps and
+    /// scripts open without the Gatekeeper prompt. Every other extended
+    /// attribute is kept.
+    #[serde(default)]
+    pub strip_quarantine: bool,
+    /// When `false`, dotfiles (and everything below a dot-folder) inside the
+    /// selected folders are left out of the scan, so counts, byte totals, and
+    /// the transfer all agree. A source the user selected directly is always
+    /// included, hidden or not. The frontend mirrors the pane's hidden-files toggle.
+    #[serde(default = "default_include_hidden")]
+    pub include_hidden: bool,
+    /// Keep going when a file fails instead of stopping the whole operation.
+    /// Failures are collected per path into `WriteCompleteEvent::errors`, and
+    /// everything that succeeded stays at the destination. Local copy and
+    /// cross-filesystem move only (a same-filesystem move renames whole items
+    /// and still stops at the first failure); a move keeps the source of every
+    /// file that failed.
+    #[serde(default)]
+    pub continue_on_error: bool,
+    /// Top-level source filenames that must land whole or not at all. With
+    /// `continue_on_error`, a failure anywhere inside one of these rolls back
+    /// what was already copied for it and skips the rest of it; every other
+    /// source keeps its successful files. Ignored without `continue_on_error`
+    /// (then the whole operation is atomic anyway).
+    #[serde(default)]
+    pub atomic_sources: Vec<String>,
+    /// Rewrite destination names the target filesystem would reject (FAT32,
+    /// exFAT, NTFS, SMB) instead of failing on them. Each rename is reported via
+    /// `write-renamed`. Local copy only. See `name_sanitizer`.
+    #[serde(default)]
+    pub sanitize_names: bool,
+    /// Re-read every copied file and compare its BLAKE3 hash with the source's.
+    /// A mismatch fails that file with `VerificationFailed`. Local copy only.
+    #[serde(default)]
+    pub verify: bool,
 }
 
 impl Default for WriteOperationConfig {
@@ -541,6 +636,12 @@ impl Default for WriteOperationConfig {
             preview_id: None,
             max_conflicts_to_show: default_max_conflicts_to_show(),
             pre_known_conflicts: Vec::new(),
+            strip_quarantine: false,
+            include_hidden: true,
+            continue_on_error: false,
+            atomic_sources: Vec::new(),
+            sanitize_names: false,
+            verify: false,
         }
     }
 }
@@ -549,6 +650,10 @@ fn default_progress_interval() -> u64 {
     200
 }
 
+fn default_include_hidden() -> bool {
+    true
+}
+
 fn default_max_conflicts_to_show() -> usize {
     100
 }
@@ -703,6 +808,33 @@ pub struct VolumeCopyConfig {
     /// 1..=9 (an out-of-range level hard-errors the edit, not clamps).
     #[serde(default)]
     pub compression_level: Option<i64>,
+    /// How a compress treats symlinks among its sources. Ignored by every other
+    /// op: copy/move INTO an existing archive always skips them (see
+    /// [`ArchiveSymlinkMode::Skip`]).
+    #[serde(default)]
+    pub archive_symlinks: ArchiveSymlinkMode,
+    /// See `WriteOperationConfig::strip_quarantine`. Only the local↔local path
+    /// honors it; other destinations don't carry xattrs.
+    #[serde(default)]
+    pub strip_quarantine: bool,
+    /// See `WriteOperationConfig::include_hidden`. Only the local↔local path
+    /// honors it.
+    #[serde(default = "default_include_hidden")]
+    pub include_hidden: bool,
+}
+
+/// What a zip write does with a symlink it meets in the sources.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub enum ArchiveSymlinkMode {
+    /// Leave it out and count it as skipped. The only safe choice for a MOVE into
+    /// an archive, which must not delete a source whose bytes didn't land.
+    #[default]
+    Skip,
+    /// Store the link itself (a zip symlink entry holding the target path).
+    Store,
+    /// Archive what the link points at: a file's bytes, or a directory's tree.
+    Follow,
 }
 
 impl Default for VolumeCopyConfig {
@@ -714,6 +846,9 @@ impl Default for VolumeCopyConfig {
             preview_id: None,
             pre_known_conflicts: Vec::new(),
             compression_level: None,
+            archive_symlinks: ArchiveSymlinkMode::Skip,
+            strip_quarantine: false,
+            include_hidden: true,
         }
     }
 }
@@ -729,6 +864,9 @@ impl From<&WriteOperationConfig> for VolumeCopyConfig {
             // `WriteOperationConfig` is the legacy local-only path (no archive
             // routing rides it), so the level has no source here.
             compression_level: None,
+            archive_symlinks: ArchiveSymlinkMode::Skip,
+            strip_quarantine: config.strip_quarantine,
+            include_hidden: config.include_hidden,
         }
     }
 }
@@ -761,6 +899,7 @@ mod write_conflict_event_serde_tests {
             size_difference: source_size.map(|s| 4_096_i64 - s as i64),
             source_is_directory: true,
             destination_is_directory: true,
+            is_case_only: false,
         }
     }
 
@@ -9,8 +9,8 @@
 //!
 //! 1. `FileEncoding` — the enum of encodings the viewer supports, plus BOM and
 //!    label metadata. Auto-detection via `detect()` reads the first 64 KB of a
-//!    file (BOM sniff + UTF-8 fast path + UTF-16 parity heuristic + Western
-//!    Latin-1 fallback).
+//!    file (BOM sniff + UTF-8 fast path + UTF-16 parity heuristic + Shift-JIS
+//!    kana heuristic + Western Latin-1 fallback).
 //! 2. `NewlineScanner` / `find_newlines` — emits the absolute byte offset of
 //!    every `0x0A` byte that constitutes a `U+000A` code unit. ASCII-compatible
 //!    encodings use the SIMD-accelerated `memchr` fast path; UTF-16 uses an
@@ -29,7 +29,7 @@ use serde::{Deserialize, Serialize};
 ///
 /// The variants are deliberately narrow: every entry is something a user is
 /// likely to need (UTF-8 + BOM, the Western single-byte family, UTF-16 in both
-/// orders). EBCDIC, UTF-32, UTF-7, and the various DOS / Mac code pages are
+/// orders, Shift-JIS for Japanese logs from Windows tools). EBCDIC, UTF-32, UTF-7, and the various DOS / Mac code pages are
 /// out of scope until requested; `encoding_rs` supports them so extending later
 /// is just an enum + dropdown addition.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, specta::Type)]
@@ -43,6 +43,7 @@ pub enum FileEncoding {
     UsAscii,
     Utf16Le,
     Utf16Be,
+    ShiftJis,
 }
 
 /// Coarse grouping for the encoding dropdown's `<optgroup>` split.
@@ -51,6 +52,7 @@ pub enum FileEncoding {
 pub enum EncodingGroup {
     Unicode,
     Western,
+    Japanese,
 }
 
 impl FileEncoding {
@@ -61,11 +63,18 @@ impl FileEncoding {
     /// of a non-newline code unit (for instance `U+010A LATIN CAPITAL LETTER A
     /// WITH BREVE` in LE is `0A 01`), so `memchr` would emit spurious newlines.
     /// UTF-16 takes the [`NewlineScanner`] path instead.
+    ///
+    /// Shift-JIS is safe: its trail bytes are `0x40-0xFC`, so `0x0A` is always a
+    /// newline.
     pub fn is_ascii_newline_compatible(self) -> bool {
         match self {
-            Self::Utf8 | Self::Utf8WithBom | Self::Windows1252 | Self::Iso8859_1 | Self::MacRoman | Self::UsAscii => {
-                true
-            }
+            Self::Utf8
+            | Self::Utf8WithBom
+            | Self::Windows1252
+            | Self::Iso8859_1
+            | Self::MacRoman
+            | Self::UsAscii
+            | Self::ShiftJis => true,
             Self::Utf16Le | Self::Utf16Be => false,
         }
     }
@@ -95,6 +104,7 @@ impl FileEncoding {
             Self::UsAscii => "US-ASCII",
             Self::Utf16Le => "UTF-16 LE",
             Self::Utf16Be => "UTF-16 BE",
+            Self::ShiftJis => "Japanese (Shift JIS)",
         }
     }
 
@@ -103,6 +113,7 @@ impl FileEncoding {
         match self {
             Self::Utf8 | Self::Utf8WithBom | Self::Utf16Le | Self::Utf16Be => EncodingGroup::Unicode,
             Self::Windows1252 | Self::Iso8859_1 | Self::MacRoman | Self::UsAscii => EncodingGroup::Western,
+            Self::ShiftJis => EncodingGroup::Japanese,
         }
     }
 
@@ -123,6 +134,7 @@ impl FileEncoding {
             Self::MacRoman => encoding_rs::MACINTOSH,
             Self::Utf16Le => encoding_rs::UTF_16LE,
             Self::Utf16Be => encoding_rs::UTF_16BE,
+            Self::ShiftJis => encoding_rs::SHIFT_JIS,
         }
     }
 }
@@ -158,7 +170,9 @@ pub fn same_byte_layout(a: FileEncoding, b: FileEncoding) -> bool {
 ///    AND the decoded code units being mostly text (not NUL / control), is
 ///    enough confidence. The text-quality gate keeps binaries (which also park
 ///    30%+ of bytes in one parity slot) out of UTF-16.
-/// 4. **Fallback** → `Windows1252` (the right default for high-bit Latin-1
+/// 4. **Shift-JIS heuristic**: the head decodes as Shift-JIS without errors AND
+///    enough of its non-ASCII characters are kana. See [`looks_like_shift_jis`].
+/// 5. **Fallback** → `Windows1252` (the right default for high-bit Latin-1
 ///    bytes; ISO-8859-1 is a strict subset).
 ///
 /// Returns `Utf8` for empty files (sensible default; matches the viewer's
@@ -199,9 +213,48 @@ pub fn detect_from_head(head: &[u8]) -> FileEncoding {
     if std::str::from_utf8(head).is_ok() {
         return FileEncoding::Utf8;
     }
+    if looks_like_shift_jis(head) {
+        return FileEncoding::ShiftJis;
+    }
     FileEncoding::Windows1252
 }
 
+/// Minimum share of kana among the non-ASCII characters before a clean Shift-JIS
+/// decode is trusted.
+///
+/// Japanese prose is kana-heavy (particles and inflections are hiragana), so real
+/// Shift-JIS sits well above this. Western text misread as Shift-JIS decodes to
+/// scattered kanji and half-width katakana instead: full-width kana need a `0x82` /
+/// `0x83` lead byte, which is `‚` / `ƒ` in Windows-1252 and rare in practice.
+const SHIFT_JIS_MIN_KANA_RATIO: f64 = 0.2;
+
+/// Shift-JIS heuristic (no BOM exists for it).
+///
+/// The head must decode as Shift-JIS with no malformed sequences (a lead byte cut
+/// off by the 64 KB read budget is forgiven), and at least
+/// [`SHIFT_JIS_MIN_KANA_RATIO`] of the decoded non-ASCII characters must be
+/// full-width hiragana or katakana (`U+3040-U+30FF`).
+fn looks_like_shift_jis(head: &[u8]) -> bool {
+    let decoded = encoding_rs::SHIFT_JIS
+        .decode_without_bom_handling_and_without_replacement(head)
+        .or_else(|| {
+            let trimmed = &head[..head.len().saturating_sub(1)];
+            encoding_rs::SHIFT_JIS.decode_without_bom_handling_and_without_replacement(trimmed)
+        });
+    let Some(text) = decoded else {
+        return false;
+    };
+    let mut non_ascii = 0usize;
+    let mut kana = 0usize;
+    for c in text.chars().filter(|c| !c.is_ascii()) {
+        non_ascii += 1;
+        if ('\u{3040}'..='\u{30FF}').contains(&c) {
+            kana += 1;
+        }
+    }
+    non_ascii > 0 && kana as f64 / non_ascii as f64 >= SHIFT_JIS_MIN_KANA_RATIO
+}
+
 /// UTF-16 parity heuristic (no-BOM case).
 ///
 /// ASCII text under UTF-16 LE: `00 XX 00 XX …`. The low byte `XX` lives at the
@@ -92,6 +92,30 @@ fn detect_high_bit_invalid_utf8_falls_back_to_windows1252() {
     assert_eq!(detect_from_head(buf), FileEncoding::Windows1252);
 }
 
+#[test]
+fn detect_kana_heavy_shift_jis() {
+    let (buf, _, _) = encoding_rs::SHIFT_JIS.encode("ログを開きました。\nファイルは正常です。\n");
+    assert_eq!(detect_from_head(&buf), FileEncoding::ShiftJis);
+    // A lead byte cut off by the read budget doesn't spoil it.
+    assert_eq!(detect_from_head(&buf[..buf.len() - 2]), FileEncoding::ShiftJis);
+}
+
+#[test]
+fn detect_western_text_that_happens_to_decode_as_shift_jis_stays_windows1252() {
+    // Every high byte here is a valid single-byte half-width katakana in Shift-JIS,
+    // so only the kana-ratio gate keeps it Western.
+    let buf = b"\xC4rger, \xD6l und \xDCberma\xDF\n";
+    assert_eq!(detect_from_head(buf), FileEncoding::Windows1252);
+}
+
+#[test]
+fn decode_line_shift_jis() {
+    let (buf, _, _) = encoding_rs::SHIFT_JIS.encode("正常です");
+    assert_eq!(decode_line(&buf, FileEncoding::ShiftJis), "正常です");
+    assert!(FileEncoding::ShiftJis.is_ascii_newline_compatible());
+    assert!(same_byte_layout(FileEncoding::Utf8, FileEncoding::ShiftJis));
+}
+
 #[test]
 fn detect_utf16_le_no_bom_by_parity() {
     // "Hello, world!" in UTF-16 LE without BOM.
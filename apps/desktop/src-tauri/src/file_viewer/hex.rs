@@ -0,0 +1,313 @@
+//! HexBackend: a hex dump of the file, 16 bytes per line.
+//!
+//! Each line is one `offset  hex  |ascii|` row (the same layout as `peek`'s binary
+//! preview), so line numbers and byte offsets convert exactly: row `n` starts at byte
+//! `n * 16`. Every seek is exact and the line count is known up front, with no scan.
+//! Rows are formatted on demand from a positioned read.
+//!
+//! Search treats a query made only of hex digits and whitespace (`DE AD BE EF`,
+//! `deadbeef`) as a byte pattern and matches the raw bytes, across row boundaries too.
+//! Any other query (or a regex) runs against the formatted rows, so it finds ASCII
+//! gutter text the way a text search would.
+
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use memchr::memmem;
+
+use crate::ignore_poison::IgnorePoison;
+
+use super::search_matcher::{LineScan, Matcher, scan_line_with_matcher};
+use super::{
+    BackendCapabilities, FileViewerBackend, LineChunk, MAX_SEARCH_MATCHES, SearchMatch, SeekTarget, ViewerError,
+};
+
+/// Bytes per row.
+pub(super) const HEX_ROW_BYTES: usize = 16;
+
+/// Read size for search. A whole number of rows, so row-wise search never splits one.
+const SEARCH_CHUNK_SIZE: usize = 1024 * 1024;
+
+pub struct HexBackend {
+    path: PathBuf,
+    total_bytes: u64,
+    file_name: String,
+}
+
+impl HexBackend {
+    pub fn open(path: &Path) -> Result<Self, ViewerError> {
+        let metadata = std::fs::metadata(path).map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => ViewerError::NotFound {
+                path: path.display().to_string(),
+            },
+            _ => ViewerError::from(e),
+        })?;
+        if metadata.is_dir() {
+            return Err(ViewerError::IsDirectory);
+        }
+
+        let file_name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| path.display().to_string());
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            total_bytes: metadata.len(),
+            file_name,
+        })
+    }
+
+    fn row_count(&self) -> u64 {
+        self.total_bytes.div_ceil(HEX_ROW_BYTES as u64)
+    }
+
+    /// The row a seek lands on, clamped to the last row.
+    fn resolve_row(&self, target: &SeekTarget) -> u64 {
+        let row = match target {
+            SeekTarget::Line(line) => *line as u64,
+            SeekTarget::ByteOffset(offset) => offset / HEX_ROW_BYTES as u64,
+            SeekTarget::Fraction(f) => (f.clamp(0.0, 1.0) * self.total_bytes as f64) as u64 / HEX_ROW_BYTES as u64,
+        };
+        row.min(self.row_count().saturating_sub(1))
+    }
+
+    /// Matches `pattern` against the raw bytes. Reports each hit on the row it starts
+    /// in, highlighting its hex digits up to that row's end.
+    fn search_bytes(
+        &self,
+        pattern: &[u8],
+        cancel: &AtomicBool,
+        results: &Mutex<Vec<SearchMatch>>,
+        progress: &Mutex<u64>,
+    ) -> Result<u64, ViewerError> {
+        let finder = memmem::Finder::new(pattern);
+        let mut file = File::open(&self.path)?;
+        let mut chunk = vec![0u8; SEARCH_CHUNK_SIZE];
+        // The tail of the previous buffer, too short to hold a whole match, carried over
+        // so a match straddling two reads is still found (and found once).
+        let mut buffer: Vec<u8> = Vec::with_capacity(SEARCH_CHUNK_SIZE + pattern.len());
+        let mut buffer_start: u64 = 0;
+        let mut scanned: u64 = 0;
+
+        loop {
+            if cancel.load(Ordering::Relaxed) {
+                break;
+            }
+            let bytes_read = file.read(&mut chunk)?;
+            if bytes_read == 0 {
+                break;
+            }
+            buffer.extend_from_slice(&chunk[..bytes_read]);
+            scanned += bytes_read as u64;
+
+            for pos in finder.find_iter(&buffer) {
+                if cancel.load(Ordering::Relaxed) {
+                    *progress.lock_ignore_poison() = scanned;
+                    return Ok(scanned);
+                }
+                let mut matches = results.lock_ignore_poison();
+                matches.push(byte_match(buffer_start + pos as u64, pattern.len()));
+                if matches.len() >= MAX_SEARCH_MATCHES {
+                    *progress.lock_ignore_poison() = scanned;
+                    return Ok(scanned);
+                }
+            }
+
+            let keep = (pattern.len() - 1).min(buffer.len());
+            buffer_start += (buffer.len() - keep) as u64;
+            buffer.drain(..buffer.len() - keep);
+            *progress.lock_ignore_poison() = scanned;
+        }
+
+        *progress.lock_ignore_poison() = scanned;
+        Ok(scanned)
+    }
+
+    /// Runs `matcher` over the formatted rows.
+    fn search_rows(
+        &self,
+        matcher: &Matcher,
+        cancel: &AtomicBool,
+        results: &Mutex<Vec<SearchMatch>>,
+        progress: &Mutex<u64>,
+    ) -> Result<u64, ViewerError> {
+        let mut file = File::open(&self.path)?;
+        let mut chunk = vec![0u8; SEARCH_CHUNK_SIZE];
+        let mut scanned: u64 = 0;
+
+        loop {
+            if cancel.load(Ordering::Relaxed) {
+                break;
+            }
+            let bytes_read = read_full(&mut file, &mut chunk)?;
+            if bytes_read == 0 {
+                break;
+            }
+            for (i, row) in chunk[..bytes_read].chunks(HEX_ROW_BYTES).enumerate() {
+                let offset = scanned + (i * HEX_ROW_BYTES) as u64;
+                let line = hex_row(offset, row);
+                let line_number = (offset / HEX_ROW_BYTES as u64) as usize;
+                match scan_line_with_matcher(matcher, &line, line_number, offset, cancel, results) {
+                    LineScan::Done => {}
+                    LineScan::HitLimit | LineScan::Cancelled => {
+                        *progress.lock_ignore_poison() = offset;
+                        return Ok(offset);
+                    }
+                }
+            }
+            scanned += bytes_read as u64;
+            *progress.lock_ignore_poison() = scanned;
+        }
+
+        *progress.lock_ignore_poison() = scanned;
+        Ok(scanned)
+    }
+}
+
+impl FileViewerBackend for HexBackend {
+    fn get_lines(&self, target: &SeekTarget, count: usize) -> Result<LineChunk, ViewerError> {
+        let row = self.resolve_row(target);
+        let offset = row * HEX_ROW_BYTES as u64;
+
+        let mut file = File::open(&self.path)?;
+        file.seek(SeekFrom::Start(offset))?;
+        let mut bytes = Vec::new();
+        file.take((count * HEX_ROW_BYTES) as u64).read_to_end(&mut bytes)?;
+
+        let lines = bytes
+            .chunks(HEX_ROW_BYTES)
+            .enumerate()
+            .map(|(i, chunk)| hex_row(offset + (i * HEX_ROW_BYTES) as u64, chunk))
+            .collect();
+
+        Ok(LineChunk {
+            lines,
+            first_line_number: row as usize,
+            byte_offset: offset,
+            total_lines: Some(self.row_count() as usize),
+            total_bytes: self.total_bytes,
+        })
+    }
+
+    fn extend_to_boxed(&self, new_size: u64, _cancel: &AtomicBool) -> Result<Box<dyn FileViewerBackend>, ViewerError> {
+        Ok(Box::new(Self {
+            path: self.path.clone(),
+            total_bytes: new_size,
+            file_name: self.file_name.clone(),
+        }))
+    }
+
+    fn search(
+        &self,
+        matcher: &Matcher,
+        cancel: &AtomicBool,
+        results: &Mutex<Vec<SearchMatch>>,
+        progress: &Mutex<u64>,
+    ) -> Result<u64, ViewerError> {
+        if let Matcher::Literal { needle, .. } = matcher
+            && let Some(pattern) = parse_hex_pattern(needle)
+        {
+            return self.search_bytes(&pattern, cancel, results, progress);
+        }
+        self.search_rows(matcher, cancel, results, progress)
+    }
+
+    fn capabilities(&self) -> BackendCapabilities {
+        BackendCapabilities {
+            supports_line_seek: true,
+            supports_byte_seek: true,
+            supports_fraction_seek: true,
+            knows_total_lines: true,
+        }
+    }
+
+    fn total_bytes(&self) -> u64 {
+        self.total_bytes
+    }
+
+    fn total_lines(&self) -> Option<usize> {
+        Some(self.row_count() as usize)
+    }
+
+    fn file_name(&self) -> &str {
+        &self.file_name
+    }
+}
+
+/// Formats one row: `offset  hex  |ascii|`, the hex padded to a full row so the ASCII
+/// gutter lines up on a short last row.
+pub(super) fn hex_row(offset: u64, bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(80);
+    out.push_str(&format!("{offset:08x} "));
+    for i in 0..HEX_ROW_BYTES {
+        if i == HEX_ROW_BYTES / 2 {
+            out.push(' ');
+        }
+        match bytes.get(i) {
+            Some(b) => out.push_str(&format!(" {b:02x}")),
+            None => out.push_str("   "),
+        }
+    }
+    out.push_str("  |");
+    out.extend(bytes.iter().map(|&b| {
+        if b.is_ascii_graphic() || b == b' ' {
+            b as char
+        } else {
+            '.'
+        }
+    }));
+    out.push('|');
+    out
+}
+
+/// Column of byte `index`'s first hex digit in the row starting at `row_offset`. Rows
+/// are ASCII, so this is also the UTF-16 column.
+fn hex_column(row_offset: u64, index: usize) -> usize {
+    let offset_width = format!("{row_offset:08x}").len();
+    let gap = usize::from(index >= HEX_ROW_BYTES / 2);
+    offset_width + 1 + gap + 3 * index + 1
+}
+
+/// The `SearchMatch` for `len` bytes at `offset`, highlighted on the row it starts in.
+fn byte_match(offset: u64, len: usize) -> SearchMatch {
+    let row_offset = offset - offset % HEX_ROW_BYTES as u64;
+    let first = (offset - row_offset) as usize;
+    let last = (first + len - 1).min(HEX_ROW_BYTES - 1);
+    let column = hex_column(row_offset, first);
+    SearchMatch {
+        line: (row_offset / HEX_ROW_BYTES as u64) as usize,
+        column,
+        length: hex_column(row_offset, last) + 2 - column,
+        byte_offset: row_offset,
+    }
+}
+
+/// Parses `DE AD BE EF` / `deadbeef` into bytes. `None` unless the query is only hex
+/// digits and whitespace with an even digit count (each byte written as two digits).
+pub(super) fn parse_hex_pattern(query: &str) -> Option<Vec<u8>> {
+    let digits: Vec<u8> = query.bytes().filter(|b| !b.is_ascii_whitespace()).collect();
+    if digits.is_empty() || !digits.len().is_multiple_of(2) || !digits.iter().all(u8::is_ascii_hexdigit) {
+        return None;
+    }
+    digits
+        .chunks(2)
+        .map(|pair| u8::from_str_radix(std::str::from_utf8(pair).ok()?, 16).ok())
+        .collect()
+}
+
+/// Fills `buf` as far as the file allows, so row-wise search only sees a partial row at
+/// EOF.
+fn read_full(file: &mut File, buf: &mut [u8]) -> std::io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match file.read(&mut buf[filled..])? {
+            0 => break,
+            n => filled += n,
+        }
+    }
+    Ok(filled)
+}
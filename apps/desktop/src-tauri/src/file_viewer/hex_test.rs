@@ -0,0 +1,166 @@
+//! Tests for HexBackend.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::sync::atomic::AtomicBool;
+
+use super::hex::{HexBackend, hex_row, parse_hex_pattern};
+use super::search_matcher::{Matcher, SearchMode};
+use super::{FileViewerBackend, SearchMatch, SeekTarget};
+
+fn literal_matcher(query: &str) -> Matcher {
+    Matcher::build(
+        query,
+        SearchMode {
+            use_regex: false,
+            case_sensitive: false,
+        },
+    )
+    .expect("test query must build")
+}
+
+fn create_test_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("cmdr_viewer_hex_{}", name));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).expect("Failed to create test directory");
+    dir
+}
+
+fn cleanup(path: &Path) {
+    let _ = fs::remove_dir_all(path);
+}
+
+fn write_test_file(dir: &Path, name: &str, content: &[u8]) -> PathBuf {
+    let file = dir.join(name);
+    fs::write(&file, content).unwrap();
+    file
+}
+
+fn run_search(backend: &HexBackend, query: &str) -> Vec<SearchMatch> {
+    let cancel = AtomicBool::new(false);
+    let results = Mutex::new(Vec::new());
+    let progress = Mutex::new(0u64);
+    backend
+        .search(&literal_matcher(query), &cancel, &results, &progress)
+        .unwrap();
+    results.into_inner().unwrap()
+}
+
+#[test]
+fn hex_row_pads_a_short_row_so_the_gutter_lines_up() {
+    let full = hex_row(0x10, b"0123456789abcdef");
+    assert_eq!(
+        full,
+        "00000010  30 31 32 33 34 35 36 37  38 39 61 62 63 64 65 66  |0123456789abcdef|"
+    );
+
+    let short = hex_row(0x20, b"AB\0");
+    assert!(short.starts_with("00000020  41 42 00 "));
+    assert!(short.ends_with("  |AB.|"));
+    assert_eq!(short.find('|'), full.find('|'));
+}
+
+#[test]
+fn open_knows_rows_up_front() {
+    let dir = create_test_dir("open");
+    let file = write_test_file(&dir, "data.bin", &[0u8; 33]);
+
+    let backend = HexBackend::open(&file).unwrap();
+    assert_eq!(backend.file_name(), "data.bin");
+    assert_eq!(backend.total_bytes(), 33);
+    assert_eq!(backend.total_lines(), Some(3));
+    let caps = backend.capabilities();
+    assert!(caps.supports_byte_seek && caps.supports_fraction_seek && caps.supports_line_seek);
+
+    cleanup(&dir);
+}
+
+#[test]
+fn open_directory_fails() {
+    let dir = create_test_dir("open_dir");
+    assert!(HexBackend::open(&dir).is_err());
+    cleanup(&dir);
+}
+
+#[test]
+fn byte_offset_seek_lands_on_the_containing_row() {
+    let dir = create_test_dir("seek");
+    let content: Vec<u8> = (0..=255u8).collect();
+    let file = write_test_file(&dir, "data.bin", &content);
+    let backend = HexBackend::open(&file).unwrap();
+
+    let chunk = backend.get_lines(&SeekTarget::ByteOffset(0x35), 2).unwrap();
+    assert_eq!(chunk.first_line_number, 3);
+    assert_eq!(chunk.byte_offset, 0x30);
+    assert_eq!(chunk.total_lines, Some(16));
+    assert_eq!(chunk.lines.len(), 2);
+    assert!(chunk.lines[0].starts_with("00000030  30 31"));
+    assert!(chunk.lines[1].starts_with("00000040  40 41"));
+
+    // Past the end clamps to the last row.
+    let tail = backend.get_lines(&SeekTarget::Fraction(1.0), 5).unwrap();
+    assert_eq!(tail.first_line_number, 15);
+    assert_eq!(tail.lines.len(), 1);
+
+    cleanup(&dir);
+}
+
+#[test]
+fn parse_hex_pattern_accepts_only_whole_bytes() {
+    assert_eq!(parse_hex_pattern("DE AD be ef"), Some(vec![0xde, 0xad, 0xbe, 0xef]));
+    assert_eq!(parse_hex_pattern("cafe"), Some(vec![0xca, 0xfe]));
+    assert_eq!(parse_hex_pattern("abc"), None);
+    assert_eq!(parse_hex_pattern("hello"), None);
+    assert_eq!(parse_hex_pattern("  "), None);
+}
+
+#[test]
+fn hex_pattern_matches_across_a_row_boundary() {
+    let dir = create_test_dir("search_bytes");
+    let mut content = vec![0u8; 32];
+    content[14..18].copy_from_slice(&[0xde, 0xad, 0xbe, 0xef]);
+    let file = write_test_file(&dir, "data.bin", &content);
+    let backend = HexBackend::open(&file).unwrap();
+
+    let matches = run_search(&backend, "DE AD BE EF");
+    assert_eq!(matches.len(), 1);
+    let m = &matches[0];
+    assert_eq!(m.line, 0);
+    assert_eq!(m.byte_offset, 0);
+    // Highlights the part of the match on its first row: `de ad`.
+    let row = &backend.get_lines(&SeekTarget::Line(0), 1).unwrap().lines[0];
+    assert_eq!(&row[m.column..m.column + m.length], "de ad");
+
+    cleanup(&dir);
+}
+
+#[test]
+fn hex_pattern_straddling_two_reads_is_found_once() {
+    let dir = create_test_dir("search_chunks");
+    let at = 1024 * 1024 - 2;
+    let mut content = vec![0u8; at + 64];
+    content[at..at + 4].copy_from_slice(&[0xca, 0xfe, 0xba, 0xbe]);
+    let file = write_test_file(&dir, "data.bin", &content);
+    let backend = HexBackend::open(&file).unwrap();
+
+    let matches = run_search(&backend, "cafebabe");
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].line, at / 16);
+
+    cleanup(&dir);
+}
+
+#[test]
+fn other_queries_search_the_formatted_rows() {
+    let dir = create_test_dir("search_text");
+    let file = write_test_file(&dir, "data.bin", b"\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0hello\0world");
+    let backend = HexBackend::open(&file).unwrap();
+
+    let matches = run_search(&backend, "world");
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].line, 1);
+    assert_eq!(matches[0].byte_offset, 16);
+
+    cleanup(&dir);
+}
@@ -1,20 +1,23 @@
-//! File viewer module: on-demand line serving with three backend strategies.
+//! File viewer module: on-demand line serving with four backend strategies.
 //!
 //! Backends:
 //! - `FullLoadBackend`: loads entire file into memory (small files, <1 MB)
 //! - `LineIndexBackend`: sparse line-offset index, O(lines/256) memory
 //! - `ByteSeekBackend`: byte-offset seeking, no pre-scan needed (instant open)
+//! - `HexBackend`: 16-byte hex dump rows for binary files, exact seeks by row
 
 mod archive_extract;
 mod byte_seek;
 pub mod content_kind;
 pub mod encoding;
 mod full_load;
+mod hex;
 mod line_index;
 pub mod media;
 mod media_backend;
 pub mod media_protocol;
 mod media_session;
+pub mod peek;
 mod range_read;
 mod search_matcher;
 pub mod session;
@@ -31,12 +34,16 @@ mod encoding_test;
 #[cfg(test)]
 mod full_load_test;
 #[cfg(test)]
+mod hex_test;
+#[cfg(test)]
 mod line_index_test;
 #[cfg(test)]
 mod media_protocol_test;
 #[cfg(test)]
 mod media_session_test;
 #[cfg(test)]
+mod peek_test;
+#[cfg(test)]
 mod search_cancel_test_support;
 #[cfg(test)]
 mod search_matcher_test;
@@ -49,13 +56,14 @@ pub use archive_extract::init_archive_extract_dir;
 pub use content_kind::{ViewerContentKind, classify_viewer_content};
 pub use encoding::FileEncoding;
 pub use media_session::MediaDimensions;
+pub use peek::{PeekResult, peek_file};
 pub use range_read::RangeEnd;
 pub use search_matcher::{Matcher, SearchMode};
 pub use session::{
-    EncodingOptions, SearchPollResult, ViewerOpenResult, ViewerSessionStatus, cancel_read, close_session,
-    close_session_for_window, get_encoding_options, get_lines, get_session_status, init_app_handle, open_session,
-    open_session_as_text, read_range, register_window_session, reload, search_cancel, search_poll, search_start,
-    set_encoding, set_tail_mode, write_range_to_file,
+    EncodingOptions, SearchPollResult, ViewerOpenMode, ViewerOpenResult, ViewerSessionStatus, cancel_read,
+    close_session, close_session_for_window, get_encoding_options, get_lines, get_session_status, init_app_handle,
+    open_session, open_session_as_text, open_session_with_mode, read_range, register_window_session, reload,
+    search_cancel, search_poll, search_start, set_encoding, set_tail_mode, write_range_to_file,
 };
 
 use serde::Serialize;
@@ -0,0 +1,185 @@
+//! Quick-look "peek" for the inactive pane: a bounded preview of whatever the cursor is on,
+//! cheap enough for the frontend to request on every (debounced) selection change.
+//!
+//! No viewer session is opened. A file is classified from one head read with the same
+//! pieces the viewer uses ([`classify_viewer_content`] for images,
+//! [`encoding::detect_from_head`] + [`encoding::decode_line`] for text); a folder is
+//! answered from the drive index where it can be. Nothing here walks a tree or reads a
+//! file past `max_bytes`.
+
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+use serde::Serialize;
+
+use super::content_kind::{CLASSIFY_HEAD_LEN, ViewerContentKind, classify_viewer_content, media_mime};
+use super::encoding::{self, FileEncoding};
+use super::hex::{HEX_ROW_BYTES, hex_row};
+use super::media::{self, MediaEntry};
+use crate::file_system::dir_entry_count;
+
+/// Read budget when the caller passes 0.
+pub const DEFAULT_PEEK_BYTES: usize = 16 * 1024;
+
+/// Hard cap on `max_bytes`, so a careless caller can't turn a peek into a full read.
+pub const MAX_PEEK_BYTES: usize = 256 * 1024;
+
+/// Bytes shown in a binary preview's hex dump, whatever `max_bytes` allows. Enough rows
+/// to recognize a format by eye; more is noise in a side pane.
+const HEX_PREVIEW_BYTES: usize = 512;
+
+/// What the peek pane should render. Tagged by `kind` so the frontend can switch on it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, specta::Type)]
+#[serde(rename_all = "camelCase", rename_all_fields = "camelCase", tag = "kind")]
+pub enum PeekResult {
+    Text {
+        text: String,
+        encoding: FileEncoding,
+        /// File size in bytes.
+        size: u64,
+        /// `true` when the file is longer than the preview.
+        truncated: bool,
+    },
+    /// An image, served through the viewer's `cmdr-media://` scheme. The frontend drops
+    /// `media_token` with `media_index_drop_thumbnail_tokens` once the preview is gone.
+    Image {
+        media_token: String,
+        mime: String,
+        size: u64,
+    },
+    /// Anything that isn't text or a displayable image (including PDFs).
+    Binary {
+        /// Classic `offset  hex  |ascii|` rows for the first bytes of the file.
+        hex_dump: String,
+        size: u64,
+    },
+    Directory {
+        /// Immediate children (files and folders), or `None` when the folder can't be read.
+        entry_count: Option<u64>,
+        /// Recursive logical size from the drive index, or `None` when the index doesn't
+        /// cover the folder. Never computed by walking.
+        total_size: Option<u64>,
+        /// `false` when `total_size` is a lower bound (the index hasn't finished the subtree).
+        total_size_complete: bool,
+    },
+}
+
+/// Peeks at `path`. `max_bytes` bounds the read (0 picks [`DEFAULT_PEEK_BYTES`], anything
+/// above [`MAX_PEEK_BYTES`] is clamped). Blocking: does disk I/O and maybe an index query.
+pub fn peek_file(path: &Path, max_bytes: usize) -> std::io::Result<PeekResult> {
+    let metadata = std::fs::metadata(path)?;
+    if metadata.is_dir() {
+        return Ok(peek_directory(path));
+    }
+    let size = metadata.len();
+    let budget = match max_bytes {
+        0 => DEFAULT_PEEK_BYTES,
+        n => n.min(MAX_PEEK_BYTES),
+    };
+    let head = read_head(path, budget.max(CLASSIFY_HEAD_LEN))?;
+    Ok(classify_head(path, &head, size, budget))
+}
+
+/// Pure part of [`peek_file`] for a file: decides the kind from `head` and builds the preview
+/// from at most `budget` bytes of it. Only the image case touches shared state (a media token).
+pub(super) fn classify_head(path: &Path, head: &[u8], size: u64, budget: usize) -> PeekResult {
+    let ext = path.extension().and_then(|e| e.to_str());
+    match classify_viewer_content(head, ext, true) {
+        ViewerContentKind::Image => {
+            let mime = media_mime(head, ViewerContentKind::Image)
+                .unwrap_or("application/octet-stream")
+                .to_string();
+            let media_token = media::mint_token(MediaEntry {
+                canonical_path: path.to_path_buf(),
+                kind: ViewerContentKind::Image,
+                mime: mime.clone(),
+            });
+            PeekResult::Image {
+                media_token,
+                mime,
+                size,
+            }
+        }
+        ViewerContentKind::Pdf => binary(head, size),
+        ViewerContentKind::Text => {
+            // Detect on the whole head (minus a cut-off trailing character, which would
+            // otherwise read as invalid UTF-8), then preview only the budget.
+            let encoding = encoding::detect_from_head(without_partial_utf8_tail(head));
+            let preview = &head[..head.len().min(budget)];
+            if looks_binary(preview, encoding) {
+                return binary(head, size);
+            }
+            let text = encoding::decode_line(text_bytes(preview, encoding), encoding);
+            PeekResult::Text {
+                text,
+                encoding,
+                size,
+                truncated: (preview.len() as u64) < size,
+            }
+        }
+    }
+}
+
+fn binary(head: &[u8], size: u64) -> PeekResult {
+    PeekResult::Binary {
+        hex_dump: hex_dump(&head[..head.len().min(HEX_PREVIEW_BYTES)]),
+        size,
+    }
+}
+
+/// A NUL byte in single-byte or UTF-8 text means it isn't text. UTF-16 is full of NULs by
+/// design, so it's trusted as detected.
+pub(super) fn looks_binary(head: &[u8], encoding: FileEncoding) -> bool {
+    !matches!(encoding, FileEncoding::Utf16Le | FileEncoding::Utf16Be) && head.contains(&0)
+}
+
+/// Drops the BOM and, for UTF-8, a multi-byte character the read budget cut in half, so
+/// the preview doesn't end in a replacement character.
+fn text_bytes(bytes: &[u8], encoding: FileEncoding) -> &[u8] {
+    let bytes = bytes.strip_prefix(encoding.bom_bytes()).unwrap_or(bytes);
+    if matches!(encoding, FileEncoding::Utf8 | FileEncoding::Utf8WithBom) {
+        return without_partial_utf8_tail(bytes);
+    }
+    bytes
+}
+
+/// `bytes` without a trailing, incomplete UTF-8 sequence. Anything else is returned as is.
+fn without_partial_utf8_tail(bytes: &[u8]) -> &[u8] {
+    match std::str::from_utf8(bytes) {
+        Err(e) if e.error_len().is_none() => &bytes[..e.valid_up_to()],
+        _ => bytes,
+    }
+}
+
+fn peek_directory(path: &Path) -> PeekResult {
+    let entry_count = dir_entry_count::get_dir_entry_count(path, true)
+        .ok()
+        .map(|count| count.files + count.directories);
+    let stats = crate::indexing::get_dir_stats(&path.to_string_lossy()).unwrap_or_else(|e| {
+        log::debug!(target: "file_viewer::peek", "Index lookup failed for {path:?}: {e}");
+        None
+    });
+    PeekResult::Directory {
+        entry_count,
+        total_size: stats.as_ref().map(|s| s.recursive_size),
+        total_size_complete: stats.is_some_and(|s| s.recursive_size_complete),
+    }
+}
+
+pub(super) fn read_head(path: &Path, len: usize) -> std::io::Result<Vec<u8>> {
+    let mut head = Vec::with_capacity(len);
+    File::open(path)?.take(len as u64).read_to_end(&mut head)?;
+    Ok(head)
+}
+
+/// Formats `bytes` as `offset  hex  |ascii|` rows, 16 bytes per row, in the hex
+/// viewer's row layout.
+pub(super) fn hex_dump(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len() * 5);
+    for (row, chunk) in bytes.chunks(HEX_ROW_BYTES).enumerate() {
+        out.push_str(&hex_row((row * HEX_ROW_BYTES) as u64, chunk));
+        out.push('\n');
+    }
+    out
+}
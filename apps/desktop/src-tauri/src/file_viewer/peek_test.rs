@@ -0,0 +1,87 @@
+//! Unit tests for the peek preview.
+
+use std::path::Path;
+
+use super::encoding::FileEncoding;
+use super::peek::{PeekResult, classify_head, hex_dump, peek_file};
+
+#[test]
+fn short_text_is_whole_and_not_truncated() {
+    let result = classify_head(Path::new("notes.txt"), b"hello\nworld\n", 12, 1024);
+    assert_eq!(
+        result,
+        PeekResult::Text {
+            text: "hello\nworld\n".to_string(),
+            encoding: FileEncoding::Utf8,
+            size: 12,
+            truncated: false,
+        }
+    );
+}
+
+#[test]
+fn text_is_cut_at_the_budget_without_splitting_a_character() {
+    // "aé" is 61 c3 a9; a 2-byte budget lands inside the é.
+    let result = classify_head(Path::new("notes.txt"), "aé and more".as_bytes(), 12, 2);
+    let PeekResult::Text { text, truncated, .. } = result else {
+        panic!("expected text, got {result:?}");
+    };
+    assert_eq!(text, "a");
+    assert!(truncated);
+}
+
+#[test]
+fn nul_bytes_make_a_file_binary() {
+    let result = classify_head(Path::new("data.bin"), b"\x7fELF\x02\x01\x01\x00\x00\x00", 10, 1024);
+    assert!(matches!(result, PeekResult::Binary { size: 10, .. }), "got {result:?}");
+}
+
+#[test]
+fn utf16_text_is_not_mistaken_for_binary() {
+    let bytes: Vec<u8> = [0xFF, 0xFE]
+        .into_iter()
+        .chain("hi".encode_utf16().flat_map(u16::to_le_bytes))
+        .collect();
+    let result = classify_head(Path::new("notes.txt"), &bytes, bytes.len() as u64, 1024);
+    assert!(
+        matches!(&result, PeekResult::Text { text, encoding: FileEncoding::Utf16Le, .. } if text == "hi"),
+        "got {result:?}"
+    );
+}
+
+#[test]
+fn png_magic_peeks_as_an_image() {
+    let mut head = b"\x89PNG\r\n\x1a\n".to_vec();
+    head.resize(64, 0);
+    let result = classify_head(Path::new("shot.png"), &head, 64, 1024);
+    assert!(
+        matches!(&result, PeekResult::Image { mime, .. } if mime == "image/png"),
+        "got {result:?}"
+    );
+}
+
+#[test]
+fn hex_dump_pads_the_last_row() {
+    assert_eq!(
+        hex_dump(b"AB\x00"),
+        "00000000  41 42 00                                          |AB.|\n"
+    );
+}
+
+#[test]
+fn directory_peek_counts_children() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    std::fs::write(dir.path().join("a.txt"), b"a").expect("write");
+    std::fs::create_dir(dir.path().join("sub")).expect("mkdir");
+    let result = peek_file(dir.path(), 0).expect("peek");
+    assert!(
+        matches!(
+            result,
+            PeekResult::Directory {
+                entry_count: Some(2),
+                ..
+            }
+        ),
+        "got {result:?}"
+    );
+}
@@ -15,16 +15,18 @@ use arc_swap::ArcSwap;
 use crate::commands::file_system::expand_tilde;
 use crate::ignore_poison::IgnorePoison;
 use log::debug;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use tauri::{AppHandle, Emitter};
 
 use super::byte_seek::ByteSeekBackend;
 use super::content_kind::ViewerContentKind;
 use super::encoding::{FileEncoding, detect, same_byte_layout};
 use super::full_load::FullLoadBackend;
+use super::hex::HexBackend;
 use super::line_index::LineIndexBackend;
 use super::media;
 use super::media_session::{self, MediaDimensions};
+use super::peek::{looks_binary, read_head};
 use super::range_read::{RangeEnd, read_range as do_read_range};
 use super::search_matcher::{Matcher, SearchMode};
 use super::watcher::{VIEWER_WATCHER_MANAGER, WatcherEvent};
@@ -56,6 +58,19 @@ pub enum BackendType {
     FullLoad,
     ByteSeek,
     LineIndex,
+    /// Hex dump rows (`HexBackend`). Binary files open in it; never upgraded.
+    Hex,
+}
+
+/// How `viewer_open` should present the file. `Auto` picks from the content: media
+/// inline, binary (a NUL in the head) as hex, everything else as text.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub enum ViewerOpenMode {
+    #[default]
+    Auto,
+    Text,
+    Hex,
 }
 
 /// One row in the encoding dropdown.
@@ -77,7 +92,7 @@ pub struct EncodingOptions {
     pub all: Vec<EncodingChoice>,
 }
 
-/// All encodings the viewer offers, in dropdown order (Unicode first, then Western).
+/// All encodings the viewer offers, in dropdown order (Unicode, Western, then Japanese).
 fn all_encoding_choices() -> Vec<EncodingChoice> {
     use FileEncoding::*;
     [
@@ -89,6 +104,7 @@ fn all_encoding_choices() -> Vec<EncodingChoice> {
         Iso8859_1,
         MacRoman,
         UsAscii,
+        ShiftJis,
     ]
     .iter()
     .map(|enc| EncodingChoice {
@@ -136,6 +152,11 @@ pub struct ViewerSessionStatus {
     pub backend_type: BackendType,
     pub is_indexing: bool,
     pub total_lines: Option<usize>,
+    /// Encoding the lines are decoded with: the detected one unless the user picked
+    /// another via `viewer_set_encoding`.
+    pub encoding: FileEncoding,
+    /// What auto-detection chose at open time.
+    pub detected_encoding: FileEncoding,
 }
 
 /// Status of an ongoing search.
@@ -323,6 +344,9 @@ pub fn close_session_for_window(window_label: &str) {
 /// Number of initial lines to return on open.
 const INITIAL_LINE_COUNT: usize = 200;
 
+/// Head bytes checked for a NUL when `ViewerOpenMode::Auto` decides between text and hex.
+const BINARY_SNIFF_LEN: usize = 8 * 1024;
+
 /// Maximum time to spend building the line index before giving up.
 /// If indexing takes longer, the session stays in ByteSeek (streaming) mode.
 /// This prevents hammering slow disks or network drives.
@@ -337,22 +361,32 @@ pub(super) fn generate_session_id() -> String {
 ///
 /// Classifies the file by magic bytes first: an `Image` / `Pdf` on a local volume
 /// opens as a media session (no text backend; the bytes are served via the
-/// `cmdr-media://` scheme), everything else as a text session that picks a backend by
-/// file size:
+/// `cmdr-media://` scheme), a binary file as a hex session, everything else as a
+/// text session that picks a backend by file size:
 /// - Under 1 MB: FullLoad (instant, full random access)
 /// - Over 1 MB: ByteSeek first (instant open), then upgrades to LineIndex in background
 pub fn open_session(path: &str, volume_id: &str) -> Result<ViewerOpenResult, ViewerError> {
-    open_session_inner(path, volume_id, /*force_text=*/ false)
+    open_session_with_mode(path, volume_id, ViewerOpenMode::Auto)
 }
 
 /// Opens a fresh, full text session regardless of content kind. Backs the "View as
 /// text" override: a media session isn't upgraded in place; the FE swaps to the
 /// session this returns. Reuses the text path verbatim.
 pub fn open_session_as_text(path: &str, volume_id: &str) -> Result<ViewerOpenResult, ViewerError> {
-    open_session_inner(path, volume_id, /*force_text=*/ true)
+    open_session_with_mode(path, volume_id, ViewerOpenMode::Text)
+}
+
+/// [`open_session`] with the presentation chosen by the caller. `Text` and `Hex` skip
+/// content detection entirely.
+pub fn open_session_with_mode(
+    path: &str,
+    volume_id: &str,
+    mode: ViewerOpenMode,
+) -> Result<ViewerOpenResult, ViewerError> {
+    open_session_inner(path, volume_id, mode)
 }
 
-fn open_session_inner(path: &str, volume_id: &str, force_text: bool) -> Result<ViewerOpenResult, ViewerError> {
+fn open_session_inner(path: &str, volume_id: &str, mode: ViewerOpenMode) -> Result<ViewerOpenResult, ViewerError> {
     let expanded = expand_tilde(path);
     let requested = PathBuf::from(&expanded);
 
@@ -379,21 +413,34 @@ fn open_session_inner(path: &str, volume_id: &str, force_text: bool) -> Result<V
     let metadata = std::fs::metadata(&file_path)?;
     let file_size = metadata.len();
 
-    // Classify by magic bytes (unless the caller forced text, e.g. "View as text").
+    // Classify by magic bytes (unless the caller picked a mode, e.g. "View as text").
     // A media kind (Image/Pdf on a local volume) opens a no-op session that serves bytes
     // via `cmdr-media://`; the whole media-open path lives in `media_session.rs`.
     // An extracted image/PDF renders inline too: the media session serves the temp via
     // `cmdr-media://` and inherits the same `extract_cleanup`, so closing it deletes the
     // temp. `try_open_media` returns `None` (falls through to text) for non-media kinds.
-    if !force_text && let Some(result) = media_session::try_open_media(&file_path, file_size, extract_cleanup.clone()) {
+    if mode == ViewerOpenMode::Auto
+        && let Some(result) = media_session::try_open_media(&file_path, file_size, extract_cleanup.clone())
+    {
         return result;
     }
 
     // Auto-detect encoding at open time. Used as the initial encoding for every backend.
     let detected_encoding = detect(&file_path).unwrap_or(FileEncoding::Utf8);
 
+    // Same binary test as the peek pane: a NUL in the head of a non-UTF-16 file.
+    let as_hex = match mode {
+        ViewerOpenMode::Hex => true,
+        ViewerOpenMode::Text => false,
+        ViewerOpenMode::Auto => {
+            read_head(&file_path, BINARY_SNIFF_LEN).is_ok_and(|head| looks_binary(&head, detected_encoding))
+        }
+    };
+
     let (backend_box, backend_type, upgrading): (Box<dyn FileViewerBackend>, BackendType, Option<Arc<AtomicBool>>) =
-        if file_size <= FULL_LOAD_THRESHOLD {
+        if as_hex {
+            (Box::new(HexBackend::open(&file_path)?), BackendType::Hex, None)
+        } else if file_size <= FULL_LOAD_THRESHOLD {
             let b = FullLoadBackend::open_with_encoding(&file_path, detected_encoding)?;
             (Box::new(b), BackendType::FullLoad, None)
         } else {
@@ -579,6 +626,8 @@ pub fn get_session_status(session_id: &str) -> Result<ViewerSessionStatus, Viewe
         backend_type: session.backend_type.lock_ignore_poison().clone(),
         is_indexing,
         total_lines: backend.total_lines(),
+        encoding: *session.encoding.lock_ignore_poison(),
+        detected_encoding: session.detected_encoding,
     })
 }
 
@@ -656,7 +705,7 @@ pub fn search_start(session_id: &str, query: String, mode: SearchMode) -> Result
     };
 
     // Get the file path from the session to open a fresh file handle in the search thread
-    let path = {
+    let (path, hex) = {
         let mut sessions = SESSIONS.lock_ignore_poison();
         let session = sessions
             .get_mut(session_id)
@@ -664,10 +713,11 @@ pub fn search_start(session_id: &str, query: String, mode: SearchMode) -> Result
                 session_id: session_id.to_string(),
             })?;
         session.search = Some(search_state);
-        session.path.clone()
+        let hex = matches!(*session.backend_type.lock_ignore_poison(), BackendType::Hex);
+        (session.path.clone(), hex)
     };
 
-    spawn_search_worker(path, matcher, cancel, matches, bytes_scanned, status);
+    spawn_search_worker(path, hex, matcher, cancel, matches, bytes_scanned, status);
     Ok(())
 }
 
@@ -677,6 +727,7 @@ pub fn search_start(session_id: &str, query: String, mode: SearchMode) -> Result
 /// viewer-search plan).
 fn spawn_search_worker(
     path: PathBuf,
+    hex: bool,
     matcher: Matcher,
     cancel: Arc<AtomicBool>,
     matches: Arc<Mutex<Vec<SearchMatch>>>,
@@ -692,8 +743,14 @@ fn spawn_search_worker(
     thread::spawn(move || {
         let watchdog_handle = thread::spawn(move || run_search_watchdog(watchdog_cancel, watchdog_status));
 
-        // Use ByteSeekBackend for streaming search (low memory, works on any file)
-        let backend = match ByteSeekBackend::open(&path) {
+        // Use ByteSeekBackend for streaming search (low memory, works on any file). A hex
+        // session searches its own rows instead, so matches land on hex line numbers.
+        let opened: Result<Box<dyn FileViewerBackend>, ViewerError> = if hex {
+            HexBackend::open(&path).map(|b| Box::new(b) as Box<dyn FileViewerBackend>)
+        } else {
+            ByteSeekBackend::open(&path).map(|b| Box::new(b) as Box<dyn FileViewerBackend>)
+        };
+        let backend = match opened {
             Ok(b) => b,
             Err(_) => {
                 finalize_search_status(&status, &cancel, /*errored=*/ true);
@@ -969,6 +1026,12 @@ pub fn set_encoding(session_id: &str, new_encoding: FileEncoding) -> Result<(),
         let session = sessions.get(session_id).ok_or_else(|| ViewerError::SessionNotFound {
             session_id: session_id.to_string(),
         })?;
+        // Hex rows show raw bytes, so there's nothing to re-decode. Keep the choice for a
+        // later switch back to text.
+        if matches!(*session.backend_type.lock_ignore_poison(), BackendType::Hex) {
+            *session.encoding.lock_ignore_poison() = new_encoding;
+            return Ok(());
+        }
         path = session.path.clone();
         was_full_load = matches!(*session.backend_type.lock_ignore_poison(), BackendType::FullLoad);
         current_encoding = *session.encoding.lock_ignore_poison();
@@ -1185,12 +1248,13 @@ pub fn set_tail_mode(session_id: &str, enabled: bool) -> Result<(), ViewerError>
 
 /// Reopen the active backend from scratch with the session's current encoding.
 /// Called by the FE's reload toast or by the watcher's rotation handler.
-/// Choice of backend mirrors `open_session`: FullLoad under the threshold,
-/// otherwise ByteSeek (an in-flight LineIndex upgrade isn't restarted here;
-/// the next `get_lines` settles into the right backend).
+/// Choice of backend mirrors `open_session`: a hex session stays hex, otherwise
+/// FullLoad under the threshold, otherwise ByteSeek (an in-flight LineIndex upgrade
+/// isn't restarted here; the next `get_lines` settles into the right backend).
 pub fn reload(session_id: &str) -> Result<(), ViewerError> {
     let path;
     let encoding;
+    let was_hex;
     {
         let sessions = SESSIONS.lock_ignore_poison();
         let session = sessions.get(session_id).ok_or_else(|| ViewerError::SessionNotFound {
@@ -1198,16 +1262,21 @@ pub fn reload(session_id: &str) -> Result<(), ViewerError> {
         })?;
         path = session.path.clone();
         encoding = *session.encoding.lock_ignore_poison();
+        was_hex = matches!(*session.backend_type.lock_ignore_poison(), BackendType::Hex);
     }
 
     let metadata = std::fs::metadata(&path)?;
     let file_size = metadata.len();
-    let new_backend: Box<dyn FileViewerBackend> = if file_size <= FULL_LOAD_THRESHOLD {
+    let new_backend: Box<dyn FileViewerBackend> = if was_hex {
+        Box::new(HexBackend::open(&path)?)
+    } else if file_size <= FULL_LOAD_THRESHOLD {
         Box::new(FullLoadBackend::open_with_encoding(&path, encoding)?)
     } else {
         Box::new(ByteSeekBackend::open_with_encoding(&path, encoding)?)
     };
-    let new_type = if file_size <= FULL_LOAD_THRESHOLD {
+    let new_type = if was_hex {
+        BackendType::Hex
+    } else if file_size <= FULL_LOAD_THRESHOLD {
         BackendType::FullLoad
     } else {
         BackendType::ByteSeek
@@ -138,6 +138,53 @@ fn open_directory_fails() {
     cleanup(&dir);
 }
 
+#[test]
+fn binary_file_opens_as_hex_unless_text_is_forced() {
+    let dir = create_test_dir("binary_hex");
+    let file = write_test_file(&dir, "data.bin", "MZ\0\0\u{1}\u{2}hello\0\0\0\0\0\0\0\0\0\0world");
+    let path = file.to_str().unwrap();
+
+    let result = session::open_session(path, "root").unwrap();
+    assert!(matches!(result.backend_type, session::BackendType::Hex));
+    assert_eq!(result.total_lines, Some(2));
+    assert!(result.initial_lines.lines[0].starts_with("00000000  4d 5a 00 00"));
+    let sid = &result.session_id;
+
+    session::search_start(sid, "02 68 65".to_string(), literal_mode()).unwrap();
+    wait_until(Duration::from_secs(1), "the hex search to complete", || {
+        let poll = session::search_poll(sid, 0).expect("search poll");
+        if !matches!(poll.status, SearchStatus::Done) {
+            return false;
+        }
+        assert_eq!(poll.new_matches.len(), 1);
+        assert_eq!(poll.new_matches[0].line, 0);
+        true
+    });
+    session::close_session(sid).unwrap();
+
+    let text = session::open_session_as_text(path, "root").unwrap();
+    assert!(matches!(text.backend_type, session::BackendType::FullLoad));
+    session::close_session(&text.session_id).unwrap();
+
+    cleanup(&dir);
+}
+
+#[test]
+fn hex_mode_opens_a_text_file_as_hex() {
+    let dir = create_test_dir("forced_hex");
+    let file = write_test_file(&dir, "test.txt", "hello\nworld\n");
+
+    let result = session::open_session_with_mode(file.to_str().unwrap(), "root", session::ViewerOpenMode::Hex).unwrap();
+    assert!(matches!(result.backend_type, session::BackendType::Hex));
+    assert_eq!(
+        result.initial_lines.lines,
+        vec!["00000000  68 65 6c 6c 6f 0a 77 6f  72 6c 64 0a              |hello.world.|"]
+    );
+
+    session::close_session(&result.session_id).unwrap();
+    cleanup(&dir);
+}
+
 #[test]
 fn get_lines_after_open() {
     let dir = create_test_dir("get_lines");
@@ -1761,3 +1808,24 @@ fn test_set_encoding_ascii_compatible_is_instant() {
     session::close_session(&sid).unwrap();
     cleanup(&dir);
 }
+
+#[test]
+fn status_reports_detected_and_forced_encoding() {
+    let dir = create_test_dir("status_encoding");
+    let file = dir.join("sjis.log");
+    let (bytes, _, _) = encoding_rs::SHIFT_JIS.encode("ログを開きました。\nファイルは正常です。\n");
+    fs::write(&file, &bytes).unwrap();
+
+    let result = session::open_session(file.to_str().unwrap(), "root").unwrap();
+    let sid = result.session_id;
+    assert_eq!(result.encoding, FileEncoding::ShiftJis);
+    assert_eq!(result.initial_lines.lines[0], "ログを開きました。");
+
+    session::set_encoding(&sid, FileEncoding::Windows1252).unwrap();
+    let status = session::get_session_status(&sid).unwrap();
+    assert_eq!(status.encoding, FileEncoding::Windows1252);
+    assert_eq!(status.detected_encoding, FileEncoding::ShiftJis);
+
+    session::close_session(&sid).unwrap();
+    cleanup(&dir);
+}
@@ -2,15 +2,103 @@
 //!
 //! This module manages character width metrics for fonts used in the file explorer.
 //! It stores width mappings in memory and on disk, and provides functions to calculate
-//! text widths and find maximum widths across multiple strings.
+//! text widths and find maximum widths across multiple strings. The in-memory cache is
+//! bounded: past [`DEFAULT_CACHE_CAPACITY`] fonts, the least recently used one is evicted,
+//! and reloads from its disk copy the next time it's asked for.
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{LazyLock, RwLock};
 
+/// Fonts kept in memory. Each text-size scale is its own font ID, so this covers a user
+/// trying out many sizes without the cache growing forever. [`set_cache_capacity`] changes it.
+const DEFAULT_CACHE_CAPACITY: usize = 16;
+
+/// Default tab stop spacing in em. Browsers default to `tab-size: 8` spaces, about 2 em.
+const DEFAULT_TAB_WIDTH_EM: f32 = 2.0;
+
 /// Cache for font metrics, keyed by font ID (like "system-400-12")
-static METRICS_CACHE: LazyLock<RwLock<HashMap<String, FontMetrics>>> = LazyLock::new(|| RwLock::new(HashMap::new()));
+static METRICS_CACHE: LazyLock<RwLock<MetricsCache>> =
+    LazyLock::new(|| RwLock::new(MetricsCache::with_capacity(DEFAULT_CACHE_CAPACITY)));
+
+/// LRU map of font ID → metrics.
+///
+/// Recency is an atomic stamp per entry, so a lookup only needs the read lock and width
+/// calculations keep running in parallel. Only an insert takes the write lock, and it scans
+/// for the oldest stamp, which is cheap at this capacity.
+#[derive(Debug)]
+struct MetricsCache {
+    capacity: usize,
+    entries: HashMap<String, CachedMetrics>,
+    /// Source of use stamps. Higher is more recent.
+    clock: AtomicU64,
+}
+
+#[derive(Debug)]
+struct CachedMetrics {
+    metrics: FontMetrics,
+    last_used: AtomicU64,
+}
+
+impl MetricsCache {
+    fn with_capacity(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            entries: HashMap::new(),
+            clock: AtomicU64::new(0),
+        }
+    }
+
+    fn tick(&self) -> u64 {
+        self.clock.fetch_add(1, Ordering::Relaxed)
+    }
+
+    /// Changes the capacity, evicting the least recently used fonts if it shrinks.
+    fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity.max(1);
+        self.evict_down_to(self.capacity);
+    }
+
+    /// Inserts or replaces `font_id`, marking it as just used. Evicts the least recently
+    /// used fonts to stay within capacity.
+    fn insert(&mut self, font_id: String, metrics: FontMetrics) {
+        let last_used = AtomicU64::new(self.tick());
+        if !self.entries.contains_key(&font_id) {
+            self.evict_down_to(self.capacity - 1);
+        }
+        self.entries.insert(font_id, CachedMetrics { metrics, last_used });
+    }
+
+    /// Evicts the least recently used fonts until at most `max_len` remain.
+    fn evict_down_to(&mut self, max_len: usize) {
+        while self.entries.len() > max_len {
+            let Some(oldest) = self
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used.load(Ordering::Relaxed))
+                .map(|(id, _)| id.clone())
+            else {
+                break;
+            };
+            self.entries.remove(&oldest);
+            log::debug!("Font metrics: Evicted least recently used font: {}", oldest);
+        }
+    }
+
+    /// Looks up `font_id` and counts it as a use.
+    fn get(&self, font_id: &str) -> Option<&FontMetrics> {
+        let entry = self.entries.get(font_id)?;
+        entry.last_used.store(self.tick(), Ordering::Relaxed);
+        Some(&entry.metrics)
+    }
+
+    /// Whether `font_id` is cached. Doesn't count as a use.
+    fn contains(&self, font_id: &str) -> bool {
+        self.entries.contains_key(font_id)
+    }
+}
 
 /// Font metrics for a specific font configuration.
 /// Stores character widths and an average width for fallback.
@@ -24,6 +112,13 @@ pub struct FontMetrics {
     widths: HashMap<u32, f32>,
     /// Average width for unmeasured characters
     average_width: f32,
+    /// Tab stop spacing in em. A rendering choice, not a measurement, so it isn't stored on disk.
+    #[serde(skip, default = "default_tab_width_em")]
+    tab_width_em: f32,
+}
+
+fn default_tab_width_em() -> f32 {
+    DEFAULT_TAB_WIDTH_EM
 }
 
 impl FontMetrics {
@@ -40,20 +135,77 @@ impl FontMetrics {
             font_id,
             widths,
             average_width,
+            tab_width_em: DEFAULT_TAB_WIDTH_EM,
         }
     }
 
-    /// Gets the width of a character, falling back to average if not found
+    /// Sets the tab stop spacing in em (defaults to [`DEFAULT_TAB_WIDTH_EM`]).
+    #[allow(
+        dead_code,
+        reason = "Brief mode uses the default; callers with their own `tab-size` set it"
+    )]
+    pub fn with_tab_width_em(mut self, tab_width_em: f32) -> Self {
+        self.tab_width_em = tab_width_em;
+        self
+    }
+
+    /// Gets the width of a character, falling back to average if not found. Unmeasured
+    /// wide (CJK) characters fall back to twice the average, since they render full-width.
     fn get_char_width(&self, code_point: u32) -> f32 {
-        self.widths.get(&code_point).copied().unwrap_or(self.average_width)
+        match self.widths.get(&code_point) {
+            Some(&width) => width,
+            None if is_wide_code_point(code_point) => self.average_width * 2.0,
+            None => self.average_width,
+        }
     }
 
-    /// Calculates the total width of a text string
+    /// The font size in pixels, from the font ID's last segment ("system-400-12" → 12).
+    /// Falls back to the width of "M", then to twice the average width.
+    fn em_width(&self) -> f32 {
+        self.font_id
+            .rsplit('-')
+            .next()
+            .and_then(|size| size.parse::<f32>().ok())
+            .filter(|size| *size > 0.0)
+            .or_else(|| self.widths.get(&('M' as u32)).copied())
+            .unwrap_or(self.average_width * 2.0)
+    }
+
+    /// Calculates the total width of a text string. A tab advances to the next multiple of
+    /// the tab width, like a browser's `tab-size`.
     pub fn calculate_text_width(&self, text: &str) -> f32 {
-        text.chars().map(|c| self.get_char_width(c as u32)).sum()
+        let tab_stop = self.tab_width_em * self.em_width();
+        text.chars().fold(0.0, |x, c| {
+            if c == '\t' && tab_stop > 0.0 {
+                (x / tab_stop).floor() * tab_stop + tab_stop
+            } else {
+                x + self.get_char_width(c as u32)
+            }
+        })
     }
 }
 
+/// Whether a code point is a full-width East Asian character (CJK ideographs, kana,
+/// Hangul, full-width forms). Covers the main blocks, not the full `EastAsianWidth` table.
+fn is_wide_code_point(code_point: u32) -> bool {
+    matches!(
+        code_point,
+        0x1100..=0x115F // Hangul Jamo (leading consonants)
+            | 0x2E80..=0x303E // CJK radicals, Kangxi radicals, CJK symbols and punctuation
+            | 0x3041..=0x33FF // Hiragana, Katakana, Bopomofo, Hangul compatibility Jamo, CJK compatibility
+            | 0x3400..=0x4DBF // CJK Unified Ideographs Extension A
+            | 0x4E00..=0x9FFF // CJK Unified Ideographs
+            | 0xA000..=0xA4CF // Yi
+            | 0xAC00..=0xD7A3 // Hangul syllables
+            | 0xF900..=0xFAFF // CJK Compatibility Ideographs
+            | 0xFE30..=0xFE4F // CJK compatibility forms
+            | 0xFF00..=0xFF60 // Full-width forms
+            | 0xFFE0..=0xFFE6 // Full-width signs
+            | 0x20000..=0x2FFFD // CJK Unified Ideographs Extensions B and later
+            | 0x30000..=0x3FFFD // CJK Unified Ideographs Extension G and later
+    )
+}
+
 /// Stores font metrics in memory cache
 pub fn store_metrics(font_id: String, widths: HashMap<u32, f32>) -> Result<(), String> {
     let metrics = FontMetrics::new(font_id.clone(), widths);
@@ -65,11 +217,25 @@ pub fn store_metrics(font_id: String, widths: HashMap<u32, f32>) -> Result<(), S
     Ok(())
 }
 
-/// Checks if metrics are available for a font ID
+/// Sets how many fonts the in-memory cache holds, evicting the least recently used ones
+/// if it shrinks.
+#[allow(
+    dead_code,
+    reason = "Used by the Brief column tests, which seed many font IDs in parallel"
+)]
+pub fn set_cache_capacity(capacity: usize) -> Result<(), String> {
+    let mut cache = METRICS_CACHE
+        .write()
+        .map_err(|e| format!("Failed to acquire cache lock: {}", e))?;
+    cache.set_capacity(capacity);
+    Ok(())
+}
+
+/// Checks if metrics are available in memory for a font ID
 pub fn has_metrics(font_id: &str) -> bool {
     METRICS_CACHE
         .read()
-        .map(|cache| cache.contains_key(font_id))
+        .map(|cache| cache.contains(font_id))
         .unwrap_or(false)
 }
 
@@ -120,7 +286,8 @@ pub fn save_to_disk<R: tauri::Runtime>(
     Ok(())
 }
 
-/// Initializes font metrics by loading from disk if available
+/// Loads font metrics from disk into the cache if available. Also how a font evicted from
+/// the cache comes back without the frontend re-measuring it.
 pub fn init_font_metrics<R: tauri::Runtime>(app: &tauri::AppHandle<R>, font_id: &str) {
     if let Some(metrics) = load_from_disk(app, font_id)
         && let Ok(mut cache) = METRICS_CACHE.write()
@@ -131,7 +298,7 @@ pub fn init_font_metrics<R: tauri::Runtime>(app: &tauri::AppHandle<R>, font_id:
 }
 
 /// Loads every `*.bin` file from the on-disk font-metrics directory into the
-/// in-memory cache.
+/// in-memory cache, up to its capacity.
 ///
 /// With user-controlled text scaling, the same install can have measurements
 /// for several font sizes side-by-side (`system-400-12`, `system-400-15`, …).
@@ -146,12 +313,20 @@ pub fn load_all_metrics_from_disk<R: tauri::Runtime>(app: &tauri::AppHandle<R>)
         return;
     };
 
+    // Oldest first, so when there are more files than the cache holds, the most recently
+    // stored sizes are the ones that stay.
+    let mut paths: Vec<_> = entries
+        .flatten()
+        .filter(|entry| entry.path().extension().and_then(|s| s.to_str()) == Some("bin"))
+        .map(|entry| {
+            let modified = entry.metadata().and_then(|m| m.modified()).ok();
+            (modified, entry.path())
+        })
+        .collect();
+    paths.sort();
+
     let mut loaded = 0usize;
-    for entry in entries.flatten() {
-        let path = entry.path();
-        if path.extension().and_then(|s| s.to_str()) != Some("bin") {
-            continue;
-        }
+    for (_, path) in paths {
         let Some(font_id) = path.file_stem().and_then(|s| s.to_str()) else {
             continue;
         };
@@ -168,3 +343,6 @@ pub fn load_all_metrics_from_disk<R: tauri::Runtime>(app: &tauri::AppHandle<R>)
         log::debug!("Font metrics: Loaded {loaded} cached size(s) from disk");
     }
 }
+
+#[cfg(test)]
+mod mod_test;
@@ -0,0 +1,120 @@
+//! Unit tests for the bounded font-metrics cache. Each test builds its own
+//! `MetricsCache` rather than touching the process-global `METRICS_CACHE`.
+
+use super::*;
+
+fn metrics(font_id: &str) -> FontMetrics {
+    FontMetrics::new(font_id.to_string(), HashMap::from([('a' as u32, 7.0)]))
+}
+
+fn cache_with(capacity: usize, font_ids: &[&str]) -> MetricsCache {
+    let mut cache = MetricsCache::with_capacity(capacity);
+    for font_id in font_ids {
+        cache.insert(font_id.to_string(), metrics(font_id));
+    }
+    cache
+}
+
+#[test]
+fn inserting_past_capacity_evicts_the_oldest() {
+    let cache = cache_with(3, &["system-400-12", "system-400-13", "system-400-14", "system-400-15"]);
+
+    assert!(!cache.contains("system-400-12"));
+    assert!(cache.contains("system-400-13"));
+    assert!(cache.contains("system-400-14"));
+    assert!(cache.contains("system-400-15"));
+}
+
+#[test]
+fn a_lookup_counts_as_a_use() {
+    let mut cache = cache_with(3, &["system-400-12", "system-400-13", "system-400-14"]);
+    assert!(cache.get("system-400-12").is_some());
+
+    cache.insert("system-400-15".to_string(), metrics("system-400-15"));
+
+    assert!(cache.contains("system-400-12"));
+    assert!(!cache.contains("system-400-13"));
+}
+
+#[test]
+fn replacing_a_cached_font_evicts_nothing() {
+    let mut cache = cache_with(2, &["system-400-12", "system-400-13"]);
+
+    cache.insert("system-400-12".to_string(), metrics("system-400-12"));
+
+    assert!(cache.contains("system-400-12"));
+    assert!(cache.contains("system-400-13"));
+}
+
+#[test]
+fn shrinking_the_capacity_evicts_the_oldest() {
+    let mut cache = cache_with(3, &["system-400-12", "system-400-13", "system-400-14"]);
+    assert!(cache.get("system-400-12").is_some());
+
+    cache.set_capacity(2);
+
+    assert!(cache.contains("system-400-12"));
+    assert!(!cache.contains("system-400-13"));
+    assert!(cache.contains("system-400-14"));
+}
+
+#[test]
+fn growing_the_capacity_keeps_more_fonts() {
+    let mut cache = cache_with(1, &["system-400-12"]);
+
+    cache.set_capacity(2);
+    cache.insert("system-400-13".to_string(), metrics("system-400-13"));
+
+    assert!(cache.contains("system-400-12"));
+    assert!(cache.contains("system-400-13"));
+}
+
+#[test]
+fn cached_metrics_measure_text() {
+    let cache = cache_with(1, &["system-400-12"]);
+
+    let width = cache.get("system-400-12").map(|m| m.calculate_text_width("aaa"));
+
+    assert_eq!(width, Some(21.0));
+    assert!(cache.get("system-400-13").is_none());
+}
+
+#[test]
+fn calculate_text_width_expands_tabs_to_tab_stops() {
+    let metrics = FontMetrics::new("system-400-10".to_string(), HashMap::from([('a' as u32, 5.0)]));
+
+    // 2 em tab stops at 10 px/em: "a" ends at 5, the tab jumps to 20
+    assert_eq!(metrics.calculate_text_width("a\ta"), 25.0);
+    assert_eq!(metrics.calculate_text_width("\ta"), 25.0);
+    assert_eq!(metrics.calculate_text_width("aaaaa\ta"), 45.0);
+}
+
+#[test]
+fn calculate_text_width_uses_a_custom_tab_width() {
+    let metrics =
+        FontMetrics::new("system-400-10".to_string(), HashMap::from([('a' as u32, 5.0)])).with_tab_width_em(4.0);
+
+    // 4 em tab stops at 10 px/em: "a" ends at 5, the tab jumps to 40
+    assert_eq!(metrics.calculate_text_width("a\ta"), 45.0);
+    assert_eq!(metrics.calculate_text_width("aaaaaaaaa\ta"), 85.0);
+}
+
+#[test]
+fn calculate_text_width_doubles_unmeasured_cjk() {
+    let metrics = FontMetrics::new("system-400-10".to_string(), HashMap::from([('a' as u32, 5.0)]));
+
+    assert_eq!(metrics.calculate_text_width("中文"), 20.0);
+    assert_eq!(metrics.calculate_text_width("a中"), 15.0);
+    // Unmeasured non-CJK stays at the average
+    assert_eq!(metrics.calculate_text_width("é"), 5.0);
+}
+
+#[test]
+fn measured_cjk_uses_its_own_width() {
+    let metrics = FontMetrics::new(
+        "system-400-10".to_string(),
+        HashMap::from([('a' as u32, 5.0), ('中' as u32, 9.0)]),
+    );
+
+    assert_eq!(metrics.calculate_text_width("中"), 9.0);
+}
@@ -7,15 +7,21 @@
 //!
 //! ## What `resolve` does
 //!
-//! 1. Expands a leading `~` via `crate::commands::file_system::expand_tilde`.
+//! 1. Expands a leading `~` via `crate::commands::file_system::expand_tilde`, then
+//!    `$VAR` / `${VAR}` from the environment (an unset one is `Invalid`).
 //! 2. Joins a relative `input` against `base_dir` (the focused pane's path).
 //! 3. **Lexically** normalizes `.` / `..` components (see the no-canonicalize
 //!    decision below) without touching the disk.
-//! 4. Classifies the lexical path against the **local** filesystem into
-//!    [`GoToPathResolution`].
+//! 4. Refuses to walk into a volume mount point (`/Volumes/<name>`, `/media/…`)
+//!    that nothing is mounted on: it's `NotMounted`, never a jump into the empty
+//!    leftover folder.
+//! 5. Classifies the lexical path against the **local** filesystem into
+//!    [`GoToPathResolution`], with the registered volume that contains it.
 //!
 //! `resolve` is a pure function (no `AppHandle`) so it's unit-testable over a
 //! `tempfile::tempdir()`. The async command wraps it in `blocking_with_timeout`.
+//! [`complete`] runs the same steps on everything up to the last `/` and lists
+//! the matching children of that folder, for as-you-type suggestions.
 //!
 //! ## Decision: backend owns resolution
 //!
@@ -53,6 +59,17 @@ use std::path::{Component, Path, PathBuf};
 use serde::{Deserialize, Serialize};
 
 use crate::commands::file_system::expand_tilde;
+use crate::file_system::VolumeManager;
+
+/// Children returned by [`complete`]. The dialog shows a short list, not a browser.
+const MAX_COMPLETIONS: usize = 50;
+
+/// Folders volumes mount under, as path patterns (`*` matches one component). The
+/// component after a match is the mount point.
+#[cfg(target_os = "macos")]
+const MOUNT_PARENTS: &[&str] = &["/Volumes"];
+#[cfg(not(target_os = "macos"))]
+const MOUNT_PARENTS: &[&str] = &["/mnt", "/media/*", "/run/media/*"];
 
 /// The outcome of resolving a typed "go to path" input against the local
 /// filesystem. The variant is the contract the frontend branches on; never
@@ -64,8 +81,9 @@ use crate::commands::file_system::expand_tilde;
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
 #[serde(tag = "kind", rename_all = "camelCase", rename_all_fields = "camelCase")]
 pub enum GoToPathResolution {
-    /// The resolved path is an existing directory. Navigate into it.
-    Directory { path: String },
+    /// The resolved path is an existing directory. Navigate into it. `volume_id` is
+    /// the registered volume containing it (`None` if the registry doesn't know one).
+    Directory { path: String, volume_id: Option<String> },
     /// The resolved path is an existing file. `path` is the canonical normalized
     /// full path (recorded into recents); `parent_dir` / `file_name` drive the
     /// navigate-to-parent + select.
@@ -73,10 +91,14 @@ pub enum GoToPathResolution {
         path: String,
         parent_dir: String,
         file_name: String,
+        volume_id: Option<String>,
     },
     /// The resolved path doesn't exist. `ancestor_dir` is the nearest existing
     /// ancestor (worst case `/`). Navigate there and fire an INFO toast.
     NearestAncestor { requested: String, ancestor_dir: String },
+    /// The path is on a volume that isn't mounted (`mount_point` is missing or a
+    /// leftover empty folder). Nothing navigates; the dialog says so.
+    NotMounted { requested: String, mount_point: String },
     /// Defensive: the input was empty or couldn't be turned into a path.
     Invalid { reason: String },
 }
@@ -124,12 +146,105 @@ fn nearest_existing_ancestor(path: &Path) -> PathBuf {
     }
 }
 
+/// Expands `$VAR` and `${VAR}` from the environment. A `$` not followed by a name
+/// stays literal; an unset variable is an error naming it.
+fn expand_env_vars(input: &str) -> Result<String, String> {
+    let mut out = String::with_capacity(input.len());
+    let mut rest = input;
+    while let Some(dollar) = rest.find('$') {
+        out.push_str(&rest[..dollar]);
+        let after = &rest[dollar + 1..];
+        let (name, consumed) = match after.strip_prefix('{') {
+            Some(braced) => match braced.find('}') {
+                Some(end) if end > 0 => (&braced[..end], end + 2),
+                _ => return Err("A `${` in the path has no variable name or closing `}`.".to_string()),
+            },
+            None => {
+                let len = after
+                    .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                    .unwrap_or(after.len());
+                (&after[..len], len)
+            }
+        };
+        if name.is_empty() {
+            out.push('$');
+        } else {
+            let value = std::env::var(name).map_err(|_| format!("${name} isn't set."))?;
+            out.push_str(&value);
+        }
+        rest = &after[consumed..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+/// Turns a typed input into an absolute, lexically normalized path: `~`, then
+/// environment variables, then the join against `base_dir`.
+fn expand_input(input: &str, base_dir: &str) -> Result<PathBuf, String> {
+    let expanded = expand_env_vars(&expand_tilde(input))?;
+    let expanded_path = Path::new(&expanded);
+    let joined = if expanded_path.is_absolute() {
+        expanded_path.to_path_buf()
+    } else {
+        Path::new(base_dir).join(expanded_path)
+    };
+    Ok(lexical_normalize(&joined))
+}
+
+/// The volume mount point `path` sits under (`/Volumes/<name>` on macOS), if any.
+fn volume_mount_point(path: &Path) -> Option<PathBuf> {
+    let components: Vec<Component> = path.components().collect();
+    MOUNT_PARENTS.iter().find_map(|parent| {
+        let pattern: Vec<Component> = Path::new(parent).components().collect();
+        let matches = components.len() > pattern.len()
+            && pattern
+                .iter()
+                .zip(&components)
+                .all(|(want, got)| want == got || (want.as_os_str() == "*" && matches!(got, Component::Normal(_))));
+        matches.then(|| components[..=pattern.len()].iter().collect())
+    })
+}
+
+/// The mount point the OS reports for `path` (walking up past missing parts).
+#[cfg(target_os = "macos")]
+fn mount_point_of(path: &Path) -> Option<String> {
+    crate::volumes::get_mount_point(&path.to_string_lossy()).map(|(mount_point, _)| mount_point)
+}
+
+#[cfg(target_os = "linux")]
+fn mount_point_of(path: &Path) -> Option<String> {
+    crate::volumes_linux::get_mount_point(&path.to_string_lossy()).map(|(mount_point, _)| mount_point)
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux")))]
+fn mount_point_of(_path: &Path) -> Option<String> {
+    None
+}
+
+/// The mount point `path` needs but that has nothing mounted on it. A symlink
+/// there (macOS's `/Volumes/Macintosh HD` → `/`) counts as mounted. Only reads
+/// the mount table (`statfs` / `/proc/mounts`), never lists the folder, so it
+/// can't trigger an automount.
+fn unmounted_mount_point(path: &Path) -> Option<PathBuf> {
+    let mount_point = volume_mount_point(path)?;
+    if std::fs::symlink_metadata(&mount_point).is_ok_and(|meta| meta.file_type().is_symlink()) {
+        return None;
+    }
+    let mounted_at = mount_point_of(&mount_point)?;
+    (Path::new(&mounted_at) != mount_point).then_some(mount_point)
+}
+
 /// Resolves a typed input against `base_dir` into a [`GoToPathResolution`].
 ///
 /// Pure: no `AppHandle`, no IPC. The disk is only read via `exists` / `metadata`
 /// for classification; the async command wraps this in `blocking_with_timeout`
 /// so a hung mount can't freeze IPC.
 pub fn resolve(input: &str, base_dir: &str) -> GoToPathResolution {
+    resolve_in(input, base_dir, crate::file_system::get_volume_manager())
+}
+
+/// [`resolve`] against an explicit volume registry (tests pass an empty one).
+fn resolve_in(input: &str, base_dir: &str, volumes: &VolumeManager) -> GoToPathResolution {
     let trimmed = input.trim();
     if trimmed.is_empty() {
         return GoToPathResolution::Invalid {
@@ -137,17 +252,13 @@ pub fn resolve(input: &str, base_dir: &str) -> GoToPathResolution {
         };
     }
 
-    // Expand `~` first, then join relative inputs against the focused pane's dir.
-    let expanded = expand_tilde(trimmed);
-    let expanded_path = Path::new(&expanded);
-    let joined = if expanded_path.is_absolute() {
-        expanded_path.to_path_buf()
-    } else {
-        Path::new(base_dir).join(expanded_path)
+    // Expand `~` and `$VAR`, then join relative inputs against the focused pane's dir.
+    let normalized = match expand_input(trimmed, base_dir) {
+        Ok(path) => path,
+        Err(reason) => return GoToPathResolution::Invalid { reason },
     };
-
-    let normalized = lexical_normalize(&joined);
     let normalized_str = normalized.to_string_lossy().to_string();
+    let volume_id = volumes.containing(&normalized).map(|(id, _)| id);
 
     // A path that crosses into a supported archive is browsed as a folder, even
     // though the inner path (or the `.zip` file itself) isn't a real directory on
@@ -155,12 +266,27 @@ pub fn resolve(input: &str, base_dir: &str) -> GoToPathResolution {
     // (a real archive FILE + zip magic) so a directory named `foo.zip` or a
     // mislabeled file still classifies normally below.
     if crate::file_system::volume::backends::archive::path_crosses_archive_boundary(&normalized) {
-        return GoToPathResolution::Directory { path: normalized_str };
+        return GoToPathResolution::Directory {
+            path: normalized_str,
+            volume_id,
+        };
+    }
+
+    // Before any `metadata`: stat-ing into a stale `/Volumes/X` would "succeed" on
+    // the leftover folder and jump into an empty directory on the boot drive.
+    if let Some(mount_point) = unmounted_mount_point(&normalized) {
+        return GoToPathResolution::NotMounted {
+            requested: normalized_str,
+            mount_point: mount_point.to_string_lossy().to_string(),
+        };
     }
 
     // `metadata` follows symlinks, so a symlinked dir/file classifies as its target.
     match std::fs::metadata(&normalized) {
-        Ok(meta) if meta.is_dir() => GoToPathResolution::Directory { path: normalized_str },
+        Ok(meta) if meta.is_dir() => GoToPathResolution::Directory {
+            path: normalized_str,
+            volume_id,
+        },
         Ok(_) => {
             // Existing non-directory (file, symlink-to-file, etc.). Navigate to
             // the parent and select the entry. A path that exists always has a
@@ -177,6 +303,7 @@ pub fn resolve(input: &str, base_dir: &str) -> GoToPathResolution {
                 path: normalized_str,
                 parent_dir,
                 file_name,
+                volume_id,
             }
         }
         Err(_) => {
@@ -190,11 +317,78 @@ pub fn resolve(input: &str, base_dir: &str) -> GoToPathResolution {
     }
 }
 
+/// One as-you-type suggestion: a child of the folder the input points into.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct PathCompletion {
+    pub name: String,
+    /// The full normalized path, so accepting a suggestion is one `resolve`.
+    pub path: String,
+    pub is_directory: bool,
+}
+
+/// Lists children of the folder `partial` points into whose names start with the
+/// text after its last `/` (case-insensitively). Folders first, then by name.
+/// Dotfiles only show once the typed prefix starts with `.`. Empty when the
+/// folder doesn't exist, can't be read, or is an unmounted mount point.
+pub fn complete(partial: &str, base_dir: &str) -> Vec<PathCompletion> {
+    let partial = partial.trim_start();
+    let (dir_part, prefix) = match partial.rfind('/') {
+        Some(slash) => (&partial[..=slash], &partial[slash + 1..]),
+        // A bare `~` completes inside home, like a shell.
+        None if partial == "~" => ("~/", ""),
+        None => ("", partial),
+    };
+    let Ok(dir) = expand_input(if dir_part.is_empty() { "." } else { dir_part }, base_dir) else {
+        return Vec::new();
+    };
+    // Listing `/Volumes` itself is fine (`read_dir` doesn't enter its children);
+    // listing inside a stale mount point would show the leftover empty folder.
+    if unmounted_mount_point(&dir).is_some() {
+        return Vec::new();
+    }
+    let Ok(entries) = std::fs::read_dir(&dir) else {
+        return Vec::new();
+    };
+
+    let prefix_folded = prefix.to_lowercase();
+    let show_hidden = prefix.starts_with('.');
+    let mut completions: Vec<PathCompletion> = entries
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if (!show_hidden && name.starts_with('.')) || !name.to_lowercase().starts_with(&prefix_folded) {
+                return None;
+            }
+            // `file_type` doesn't follow symlinks; a symlink to a folder still completes as one.
+            let is_directory = entry.file_type().is_ok_and(|t| t.is_dir())
+                || (entry.file_type().is_ok_and(|t| t.is_symlink()) && entry.path().is_dir());
+            Some(PathCompletion {
+                path: dir.join(&name).to_string_lossy().into_owned(),
+                name,
+                is_directory,
+            })
+        })
+        .collect();
+    completions.sort_by(|a, b| {
+        b.is_directory
+            .cmp(&a.is_directory)
+            .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+    });
+    completions.truncate(MAX_COMPLETIONS);
+    completions
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::fs;
 
+    /// Resolves against an empty volume registry, so `volume_id` is always `None`.
+    fn resolve(input: &str, base_dir: &str) -> GoToPathResolution {
+        resolve_in(input, base_dir, &VolumeManager::new())
+    }
+
     #[test]
     fn existing_dir_resolves_to_directory() {
         let dir = tempfile::tempdir().expect("tempdir");
@@ -205,7 +399,8 @@ mod tests {
         assert_eq!(
             res,
             GoToPathResolution::Directory {
-                path: sub.to_string_lossy().to_string()
+                path: sub.to_string_lossy().to_string(),
+                volume_id: None,
             }
         );
     }
@@ -223,7 +418,8 @@ mod tests {
         assert_eq!(
             res,
             GoToPathResolution::Directory {
-                path: inner.to_string_lossy().to_string()
+                path: inner.to_string_lossy().to_string(),
+                volume_id: None,
             }
         );
 
@@ -258,6 +454,7 @@ mod tests {
                 path: file.to_string_lossy().to_string(),
                 parent_dir: dir.path().to_string_lossy().to_string(),
                 file_name: "notes.txt".to_string(),
+                volume_id: None,
             }
         );
     }
@@ -270,7 +467,8 @@ mod tests {
         assert_eq!(
             res,
             GoToPathResolution::Directory {
-                path: home.to_string_lossy().to_string()
+                path: home.to_string_lossy().to_string(),
+                volume_id: None,
             }
         );
     }
@@ -285,7 +483,8 @@ mod tests {
         assert_eq!(
             res,
             GoToPathResolution::Directory {
-                path: sub.to_string_lossy().to_string()
+                path: sub.to_string_lossy().to_string(),
+                volume_id: None,
             }
         );
     }
@@ -302,7 +501,8 @@ mod tests {
         assert_eq!(
             res,
             GoToPathResolution::Directory {
-                path: a.to_string_lossy().to_string()
+                path: a.to_string_lossy().to_string(),
+                volume_id: None,
             }
         );
     }
@@ -318,7 +518,8 @@ mod tests {
         assert_eq!(
             res,
             GoToPathResolution::Directory {
-                path: dir.path().to_string_lossy().to_string()
+                path: dir.path().to_string_lossy().to_string(),
+                volume_id: None,
             }
         );
     }
@@ -367,12 +568,14 @@ mod tests {
             path: "/Users/x/a.txt".to_string(),
             parent_dir: "/Users/x".to_string(),
             file_name: "a.txt".to_string(),
+            volume_id: Some("root".to_string()),
         };
         let json = serde_json::to_string(&res).unwrap();
         assert!(json.contains("\"kind\":\"file\""), "got {json}");
         assert!(json.contains("\"path\""), "got {json}");
         assert!(json.contains("\"parentDir\""), "got {json}");
         assert!(json.contains("\"fileName\""), "got {json}");
+        assert!(json.contains("\"volumeId\":\"root\""), "got {json}");
 
         let ancestor = GoToPathResolution::NearestAncestor {
             requested: "/tmp/nope".to_string(),
@@ -382,4 +585,82 @@ mod tests {
         assert!(json.contains("\"kind\":\"nearestAncestor\""), "got {json}");
         assert!(json.contains("\"ancestorDir\""), "got {json}");
     }
+
+    #[test]
+    fn env_vars_expand_in_both_forms() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let sub = dir.path().join("sub");
+        fs::create_dir(&sub).expect("create sub");
+        // SAFETY: test-only, and the name is unique to this test.
+        unsafe { std::env::set_var("CMDR_GO_TO_PATH_TEST_DIR", dir.path()) };
+
+        for input in ["$CMDR_GO_TO_PATH_TEST_DIR/sub", "${CMDR_GO_TO_PATH_TEST_DIR}/sub"] {
+            assert_eq!(
+                resolve(input, "/tmp"),
+                GoToPathResolution::Directory {
+                    path: sub.to_string_lossy().to_string(),
+                    volume_id: None,
+                },
+                "input {input}"
+            );
+        }
+    }
+
+    #[test]
+    fn unset_env_var_is_invalid_and_a_lone_dollar_stays_literal() {
+        assert_eq!(
+            expand_env_vars("$CMDR_SURELY_UNSET_VAR/x"),
+            Err("$CMDR_SURELY_UNSET_VAR isn't set.".to_string())
+        );
+        assert!(expand_env_vars("${CMDR_UNCLOSED").is_err());
+        assert_eq!(expand_env_vars("/tmp/$ and $-"), Ok("/tmp/$ and $-".to_string()));
+        assert!(matches!(
+            resolve("$CMDR_SURELY_UNSET_VAR", "/tmp"),
+            GoToPathResolution::Invalid { .. }
+        ));
+    }
+
+    #[test]
+    fn mount_point_is_the_component_under_a_mount_parent() {
+        let parent = Path::new(MOUNT_PARENTS[0]);
+        let (input, expected) = if parent.components().any(|c| c.as_os_str() == "*") {
+            (Path::new("/media/alice/Stick/docs"), Path::new("/media/alice/Stick"))
+        } else {
+            (Path::new("/Volumes/Stick/docs"), Path::new("/Volumes/Stick"))
+        };
+        assert_eq!(volume_mount_point(input).as_deref(), Some(expected));
+        assert_eq!(volume_mount_point(parent), None);
+        assert_eq!(volume_mount_point(Path::new("/tmp/Volumes/Stick")), None);
+    }
+
+    #[test]
+    fn completion_matches_prefix_case_insensitively_with_folders_first() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        fs::write(dir.path().join("Docs.txt"), b"").expect("write file");
+        fs::create_dir(dir.path().join("documents")).expect("create dir");
+        fs::create_dir(dir.path().join("other")).expect("create dir");
+        fs::create_dir(dir.path().join(".dotfolder")).expect("create dir");
+
+        let base = dir.path().to_str().unwrap();
+        let names: Vec<_> = complete("do", base).into_iter().map(|c| c.name).collect();
+        assert_eq!(names, vec!["documents", "Docs.txt"]);
+
+        let typed = format!("{base}/DOC");
+        let first = complete(&typed, "/").remove(0);
+        assert_eq!(first.path, dir.path().join("documents").to_string_lossy());
+        assert!(first.is_directory);
+    }
+
+    #[test]
+    fn completion_hides_dotfiles_until_the_prefix_starts_with_a_dot() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        fs::create_dir(dir.path().join(".config")).expect("create dir");
+        fs::create_dir(dir.path().join("code")).expect("create dir");
+        let base = dir.path().to_str().unwrap();
+
+        let names = |partial: &str| complete(partial, base).into_iter().map(|c| c.name).collect::<Vec<_>>();
+        assert_eq!(names(""), vec!["code"]);
+        assert_eq!(names("."), vec![".config"]);
+        assert!(complete("missing/", base).is_empty());
+    }
 }
@@ -257,6 +257,7 @@ pub fn custom_folder_icon_ids(directory_paths: Vec<String>) -> Vec<String> {
 ///
 /// Returns a map of icon_id -> data URL.
 pub fn get_icons(icon_ids: Vec<String>, use_app_icons_for_documents: bool) -> HashMap<String, String> {
+    let _span = crate::benchmark::span("get_icons").with("ids", icon_ids.len());
     let mut result = HashMap::new();
 
     // Real-folder icon ids (`special:downloads`, `pkg:/Applications/Safari.app`,
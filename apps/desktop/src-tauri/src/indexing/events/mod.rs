@@ -46,6 +46,11 @@ pub struct IndexScanProgressEvent {
     /// Resolved post-dedup physical bytes scanned so far, the tier-2 progress
     /// numerator (apples-to-apples with `volume_used_bytes`).
     pub bytes_scanned: u64,
+    /// Entries scanned per second since the previous progress event.
+    pub entries_per_sec: u64,
+    /// `true` while the user's scan throttle (rate cap, or AC-only mode on battery) is
+    /// holding the scan back, so the UI can say it's indexing slowly on purpose.
+    pub throttled: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, specta::Type, Event)]
@@ -177,6 +182,44 @@ pub struct IndexFreshnessChangedEvent {
     pub freshness: super::lifecycle::freshness::Freshness,
 }
 
+/// Emitted once per app session, after the first local scan that had to skip
+/// directories with permission denied. Tells the user the index is incomplete, so
+/// the frontend can offer Full Disk Access (see `permissions.rs`).
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type, Event)]
+#[tauri_specta(event_name = "scan-permission-warning")]
+#[serde(rename_all = "camelCase")]
+pub struct ScanPermissionWarningEvent {
+    pub volume_id: String,
+    /// How many directories the scan couldn't read.
+    pub skipped_count: u64,
+    /// The first few skipped paths (sorted), at most [`PERMISSION_WARNING_SAMPLE_LEN`].
+    pub sample_paths: Vec<String>,
+}
+
+/// Paths carried by [`ScanPermissionWarningEvent`]; the full list stays in the log.
+const PERMISSION_WARNING_SAMPLE_LEN: usize = 20;
+
+/// Set once [`ScanPermissionWarningEvent`] went out, so rescans don't nag.
+static PERMISSION_WARNING_SENT: AtomicBool = AtomicBool::new(false);
+
+/// Emit `scan-permission-warning` for `skipped_inaccessible`, unless it's empty or
+/// the warning already went out this session.
+pub(crate) fn emit_scan_permission_warning_once(app: &AppHandle, volume_id: &str, skipped_inaccessible: &[String]) {
+    if skipped_inaccessible.is_empty() || PERMISSION_WARNING_SENT.swap(true, Ordering::Relaxed) {
+        return;
+    }
+    let _ = ScanPermissionWarningEvent {
+        volume_id: volume_id.to_string(),
+        skipped_count: skipped_inaccessible.len() as u64,
+        sample_paths: skipped_inaccessible
+            .iter()
+            .take(PERMISSION_WARNING_SAMPLE_LEN)
+            .cloned()
+            .collect(),
+    }
+    .emit(app);
+}
+
 /// Emit an `index-rescan-notification` event and log the reason at INFO level.
 pub(super) fn emit_rescan_notification(app: &AppHandle, volume_id: &str, reason: RescanReason, details: String) {
     log::info!("Index rescan triggered ({reason:?}): {details}");
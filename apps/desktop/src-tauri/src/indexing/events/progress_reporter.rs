@@ -17,7 +17,7 @@
 
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use tauri::AppHandle;
 use tauri::async_runtime::JoinHandle;
@@ -56,6 +56,9 @@ pub(crate) struct ScanProgressReporter {
     partial_agg_source: AggSource,
     /// Tick counter; gates partial-aggregation passes via `partial_agg`.
     tick: u64,
+    /// Entry count and time of the previous tick, for the event's `entries_per_sec`.
+    last_entries: u64,
+    last_tick_at: Instant,
 }
 
 impl ScanProgressReporter {
@@ -77,6 +80,8 @@ impl ScanProgressReporter {
             volume_id,
             partial_agg_source,
             tick: 0,
+            last_entries: 0,
+            last_tick_at: Instant::now(),
         }
     }
 
@@ -85,12 +90,26 @@ impl ScanProgressReporter {
     /// hot-path collection live in the tested `partial_agg` helpers; this body
     /// just snapshots and sends.
     fn tick(&mut self) {
+        // A paused scan has nothing new to report; the frontend keeps its last numbers.
+        if self.progress.paused.load(Ordering::Relaxed) {
+            self.last_tick_at = Instant::now();
+            return;
+        }
         let snap = self.progress.snapshot();
+        let now = Instant::now();
+        let entries_per_sec = entries_per_sec(
+            snap.entries_scanned.saturating_sub(self.last_entries),
+            now.duration_since(self.last_tick_at),
+        );
+        self.last_entries = snap.entries_scanned;
+        self.last_tick_at = now;
         let _ = IndexScanProgressEvent {
             volume_id: self.volume_id.clone(),
             entries_scanned: snap.entries_scanned,
             dirs_found: snap.dirs_found,
             bytes_scanned: snap.bytes_scanned,
+            entries_per_sec,
+            throttled: self.progress.throttled.load(Ordering::Relaxed),
         }
         .emit(&self.app);
 
@@ -147,3 +166,9 @@ impl ScanProgressReporter {
         })
     }
 }
+
+/// `entries` over `elapsed` as a whole-number rate. Zero for a zero-length interval.
+fn entries_per_sec(entries: u64, elapsed: Duration) -> u64 {
+    let millis = elapsed.as_millis() as u64;
+    if millis == 0 { 0 } else { entries * 1000 / millis }
+}
@@ -790,6 +790,11 @@ impl IndexManager {
                 // A FAT/exFAT drive's derived inodes are untrusted, so the scanner
                 // stores `inode: None` (keeping the rename pre-pass inert).
                 inodes_trustworthy: space.inodes_trustworthy(),
+                // The user's rate cap / AC-only mode, live: `set_index_throttle` reaches
+                // this scan mid-walk.
+                throttle: scanner::user_scan_throttle(),
+                // The user's exclusion patterns, live in the same way.
+                exclude_globs: scanner::user_exclude_globs(),
                 ..ScanConfig::default()
             };
             scanner::scan_volume(config, &self.writer).map_err(|e| format!("Failed to start scan: {e}"))?
@@ -877,6 +882,33 @@ impl IndexManager {
         }
     }
 
+    /// Pause the active full scan without tearing anything down: the walk holds where it is
+    /// and the watcher keeps buffering, so [`resume_scan`](Self::resume_scan) picks up
+    /// without re-reading finished folders. Only a fresh local scan can pause.
+    pub fn pause_scan(&mut self) -> Result<(), String> {
+        match &self.scan_handle {
+            Some(handle) if self.scanning.load(Ordering::Relaxed) => {
+                if handle.pause() {
+                    log::info!("Paused the scan of '{}'", self.volume_id);
+                    Ok(())
+                } else {
+                    Err("This scan can't be paused".to_string())
+                }
+            }
+            _ => Err("No scan is running".to_string()),
+        }
+    }
+
+    /// Resume a scan paused with [`pause_scan`](Self::pause_scan). A no-op when none is.
+    pub fn resume_scan(&mut self) {
+        if let Some(handle) = &self.scan_handle
+            && handle.is_paused()
+        {
+            handle.resume();
+            log::info!("Resumed the scan of '{}'", self.volume_id);
+        }
+    }
+
     /// Get the current index status.
     pub fn get_status(&self) -> Result<IndexStatusResponse, String> {
         let index_status = self
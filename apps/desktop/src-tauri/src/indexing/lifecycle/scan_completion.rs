@@ -18,7 +18,7 @@ use crate::ignore_poison::IgnorePoison;
 use crate::indexing::IndexPathSpace;
 use crate::indexing::events::{
     ActivityPhase, DEBUG_STATS, IndexAggregationCompleteEvent, IndexDirUpdatedEvent, IndexScanAbortedEvent,
-    IndexScanCompleteEvent, RescanReason, emit_rescan_notification, set_phase_for,
+    IndexScanCompleteEvent, RescanReason, emit_rescan_notification, emit_scan_permission_warning_once, set_phase_for,
 };
 use crate::indexing::reconcile::reconciler::{self, EventReconciler};
 use crate::indexing::scanner::{ScanError, ScanSummary};
@@ -182,6 +182,7 @@ pub(super) async fn run_scan_completion(params: ScanCompletion) {
                 duration_ms: summary.duration_ms,
             }
             .emit(&app);
+            emit_scan_permission_warning_once(&app, &volume_id, &summary.skipped_inaccessible);
 
             // Tell the writer how many entries the scan produced, so it
             // can report flushing progress as it drains remaining
@@ -53,6 +53,9 @@ pub(crate) type VolumeId = String;
 /// is indexed.
 pub(crate) const ROOT_VOLUME_ID: &str = "root";
 
+/// Maintenance ticks (30 s each) between two idle full-VACUUM checks.
+const IDLE_VACUUM_EVERY_TICKS: u32 = 20;
+
 // ── Indexing state machine ────────────────────────────────────────────
 
 /// Lifecycle phases of one volume's index. Single source of truth for whether
@@ -765,19 +768,41 @@ fn start_indexing_for(
             // `fail_index` can tear the manager down out of the registry.
             spawn_failure_supervisor(app.clone(), volume_id.to_string(), failure_signal);
 
+            // Drop rows the exclusion patterns cover but this index still holds: the first
+            // start under the default patterns, or patterns changed while the drive was away.
+            // A no-op when the index was last pruned for the same set.
+            let prune_volume_id = volume_id.to_string();
+            tauri::async_runtime::spawn_blocking(move || {
+                if let Err(e) = crate::indexing::prune_user_excluded(&prune_volume_id) {
+                    log::warn!("{e}");
+                }
+            });
+
             // Periodic DB maintenance every 30 s: reclaim free pages from
             // deletes/rescans (`IncrementalVacuum`) AND truncate the WAL file
             // so its high-water mark doesn't sit on disk (`WalCheckpoint`).
-            // Both stop automatically when the writer channel closes.
+            // Every 20th tick (~10 min), if the writer queue is empty, also ask
+            // for a full VACUUM, which the writer only runs on a fragmented file
+            // (`Compact` without a reply). All stop when the writer channel closes.
             tauri::async_runtime::spawn(async move {
+                let mut tick: u32 = 0;
                 loop {
                     tokio::time::sleep(Duration::from_secs(30)).await;
+                    tick = tick.wrapping_add(1);
                     if writer_for_maintenance.send(WriteMessage::IncrementalVacuum).is_err() {
                         break;
                     }
                     if writer_for_maintenance.send(WriteMessage::WalCheckpoint).is_err() {
                         break;
                     }
+                    if tick.is_multiple_of(IDLE_VACUUM_EVERY_TICKS)
+                        && writer_for_maintenance.queue_depth() == 0
+                        && writer_for_maintenance
+                            .send(WriteMessage::Compact { reply: None })
+                            .is_err()
+                    {
+                        break;
+                    }
                 }
             });
         }
@@ -1104,6 +1129,43 @@ pub fn stop_scan(volume_id: &str) -> Result<(), String> {
     }
 }
 
+/// Run a full VACUUM + WAL truncate on a volume's index DB and return its size before and
+/// after. Refused mid-scan: the scan's writes would just regrow the file, and the VACUUM
+/// would stall them for its whole run.
+pub async fn compact_index(volume_id: &str) -> Result<crate::indexing::writer::CompactStats, String> {
+    let writer = match get_writer_and_scanning_for(volume_id) {
+        Some((writer, false)) => writer,
+        Some((_, true)) => return Err("The drive index is still scanning. Try again when it's done.".to_string()),
+        None => return Err("Indexing not initialized".to_string()),
+    };
+    writer
+        .compact()
+        .await
+        .map_err(|e| format!("Couldn't compact the index: {e}"))
+}
+
+/// Pause the active scan for a volume, keeping its watcher and progress (see
+/// [`IndexManager::pause_scan`]).
+pub fn pause_scan(volume_id: &str) -> Result<(), String> {
+    let mut reg = INDEX_REGISTRY.lock().map_err(|e| format!("Lock poisoned: {e}"))?;
+    match reg.get_mut(volume_id).map(|i| &mut i.phase) {
+        Some(IndexPhase::Running(mgr)) => mgr.pause_scan(),
+        _ => Err("Indexing not initialized".to_string()),
+    }
+}
+
+/// Resume a paused scan for a volume.
+pub fn resume_scan(volume_id: &str) -> Result<(), String> {
+    let mut reg = INDEX_REGISTRY.lock().map_err(|e| format!("Lock poisoned: {e}"))?;
+    match reg.get_mut(volume_id).map(|i| &mut i.phase) {
+        Some(IndexPhase::Running(mgr)) => {
+            mgr.resume_scan();
+            Ok(())
+        }
+        _ => Err("Indexing not initialized".to_string()),
+    }
+}
+
 /// Snapshot the ready-to-score volume ids WITH their typed kind. The importance and
 /// media-index schedulers' startup sweeps use this to branch typed on the kind (score
 /// Local + SMB, exclude MTP) without re-deriving the kind from the volume-id
@@ -45,15 +45,20 @@ pub(crate) use lifecycle::state::get_freshness;
 pub(crate) use lifecycle::state::reserve_initializing_index_for_test;
 pub(crate) use lifecycle::state::{IndexVolumeKind, all_registered_volume_ids, ready_volumes_with_kind, volume_kind};
 pub use lifecycle::state::{
-    clear_index, disable_drive_index_persist_intent, force_scan, init, is_active, is_failed, should_auto_start,
-    should_auto_start_indexing, start_indexing, stop_indexing, stop_scan, trigger_verification,
+    clear_index, compact_index, disable_drive_index_persist_intent, force_scan, init, is_active, is_failed, pause_scan,
+    resume_scan, should_auto_start, should_auto_start_indexing, start_indexing, stop_indexing, stop_scan,
+    trigger_verification,
 };
 pub(crate) use paths::routing::{IndexPathSpace, index_read_path, volume_id_for_local_path};
 pub use read::queries::{
-    get_debug_status, get_dir_stats, get_dir_stats_batch, get_status, get_volume_index_status,
-    get_volume_index_status_for_path, list_dir_children,
+    child_counts, extension_totals, files_sharing_a_size, get_debug_status, get_dir_stats, get_dir_stats_batch,
+    get_status, get_volume_index_status, get_volume_index_status_for_path, largest_files, list_dir_children,
+    subdir_sizes, subtree_entries, tree_children,
 };
+pub use read::subtree_hashes::{store_subtree_hash, stored_subtree_hash};
+pub(crate) use reconcile::exclusion_prune::{apply_user_exclusions, prune_user_excluded};
 pub use resources::subsystem_stop::register_subsystem_stop_hook;
+pub(crate) use scanner::{ScanThrottleSettings, set_user_scan_throttle};
 pub use store::IndexFailure;
 
 #[cfg(any(target_os = "macos", target_os = "linux"))]
@@ -250,6 +250,7 @@ pub fn enrich_entries_with_index(entries: &mut [FileEntry]) {
 /// dir `(id, name)` pairs via `idx_parent`, then batch-fetches their
 /// `dir_stats` by integer IDs. Two indexed queries total.
 pub fn enrich_entries_with_index_on_volume(volume_id: &str, entries: &mut [FileEntry]) {
+    let _span = crate::benchmark::span("enrich_entries_with_index").with("entries", entries.len());
     // Skip if no index is registered for this volume: `get_read_pool_for`
     // returns `None`, which IS the "no index registered" signal (root's pool
     // lives in `READ_POOL`; a non-root volume's pool lives in its registry
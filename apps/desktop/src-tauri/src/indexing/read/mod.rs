@@ -8,8 +8,10 @@
 //!   mutation.
 //! - [`expected_totals`]: index-derived write-op progress-bar denominators.
 //! - [`pending_sizes`]: the per-directory "size updating" hourglass marked-set.
+//! - [`subtree_hashes`]: cached folder content hashes for `file_system::tree_hash`.
 
 pub(crate) mod enrichment;
 pub mod expected_totals;
 pub(crate) mod pending_sizes;
 pub(crate) mod queries;
+pub(crate) mod subtree_hashes;
@@ -9,6 +9,8 @@
 
 use std::sync::atomic::Ordering;
 
+use rusqlite::Connection;
+
 use super::enrichment::get_read_pool_for;
 use super::pending_sizes::get_pending_sizes_for;
 use crate::indexing::events::{DEBUG_STATS, IndexDebugStatusResponse, IndexStatusResponse, VolumeIndexStatus};
@@ -19,7 +21,7 @@ use crate::indexing::lifecycle::state::{
 use crate::indexing::paths::firmlinks;
 use crate::indexing::paths::routing::{index_read_path, volume_id_for_local_path};
 use crate::indexing::reconcile::reconciler;
-use crate::indexing::store::{self, DirStats, IndexStore};
+use crate::indexing::store::{self, DirStats, IndexStore, IndexStoreError};
 
 /// Per-volume index status for the per-drive freshness badge.
 ///
@@ -352,6 +354,112 @@ pub fn list_dir_children(path: &str) -> Result<Option<Vec<store::EntryRow>>, Str
     })?
 }
 
+/// Runs `f` on the index entry for `path` when the owning volume's index fully covers that
+/// subtree (`min_subtree_epoch > 0`). `Ok(None)` otherwise: no live index, path not indexed,
+/// or a partially scanned subtree, where an index answer would silently undercount. Callers
+/// fall back to walking the disk.
+fn with_covered_subtree<T>(
+    path: &str,
+    f: impl FnOnce(&Connection, i64) -> Result<T, IndexStoreError>,
+) -> Result<Option<T>, String> {
+    let volume_id = volume_id_for_local_path(path);
+    let pool = match get_read_pool_for(&volume_id) {
+        Some(p) => p,
+        None => return Ok(None),
+    };
+    let normalized = firmlinks::normalize_path(path);
+    let index_path = match index_read_path(&volume_id, &normalized) {
+        Some(p) => p,
+        None => return Ok(None),
+    };
+    pool.with_conn(|conn| {
+        let entry_id =
+            match store::resolve_path(conn, &index_path).map_err(|e| format!("Couldn't resolve path: {e}"))? {
+                Some(id) => id,
+                None => return Ok(None),
+            };
+        let covered = IndexStore::get_dir_stats_by_id(conn, entry_id)
+            .map_err(|e| format!("Couldn't get dir stats: {e}"))?
+            .is_some_and(|s| s.min_subtree_epoch > 0);
+        if !covered {
+            return Ok(None);
+        }
+        f(conn, entry_id)
+            .map(Some)
+            .map_err(|e| format!("Couldn't query subtree: {e}"))
+    })?
+}
+
+/// `(files, directories)` directly inside `path`, read from the owning volume's index (see
+/// [`IndexStore::child_counts_by_id`]). `Ok(None)` unless the index fully covers the folder
+/// (see [`with_covered_subtree`]).
+pub fn child_counts(path: &str, include_hidden: bool) -> Result<Option<(u64, u64)>, String> {
+    with_covered_subtree(path, |conn, id| {
+        IndexStore::child_counts_by_id(conn, id, include_hidden)
+    })
+}
+
+/// `path`'s real subdirectories with their own subdirectory names, read from the owning
+/// volume's index (see [`IndexStore::tree_children_by_id`]). `Ok(None)` unless the index fully
+/// covers the folder (see [`with_covered_subtree`]).
+pub fn tree_children(path: &str) -> Result<Option<Vec<store::TreeChildRow>>, String> {
+    with_covered_subtree(path, IndexStore::tree_children_by_id)
+}
+
+/// Per-extension `(extension, file_count, logical_bytes)` totals for a directory's whole
+/// subtree, read from the owning volume's index in one grouped query (see
+/// [`IndexStore::extension_totals_by_id`]). Extensions come back case as stored. `Ok(None)`
+/// unless the index fully covers the subtree (see [`with_covered_subtree`]).
+pub fn extension_totals(path: &str) -> Result<Option<Vec<(String, u64, u64)>>, String> {
+    with_covered_subtree(path, IndexStore::extension_totals_by_id)
+}
+
+/// Duplicate-detection candidates under `path` from the index: `(relative_path, size, inode)`
+/// for every file of at least `min_size` bytes whose size another such file shares (see
+/// [`IndexStore::files_sharing_a_size_by_id`]). `Ok(None)` unless the index fully covers the
+/// subtree (see [`with_covered_subtree`]).
+pub fn files_sharing_a_size(path: &str, min_size: u64) -> Result<Option<Vec<(String, u64, Option<u64>)>>, String> {
+    with_covered_subtree(path, |conn, root_id| {
+        IndexStore::files_sharing_a_size_by_id(conn, root_id, min_size)
+    })
+}
+
+/// The `limit` largest files under `path` from the index, as `(relative_path, size)` rows,
+/// biggest first (see [`IndexStore::largest_files_by_id`]). `Ok(None)` unless the index fully
+/// covers the subtree (see [`with_covered_subtree`]).
+pub fn largest_files(path: &str, limit: u32) -> Result<Option<Vec<(String, u64)>>, String> {
+    with_covered_subtree(path, |conn, root_id| {
+        IndexStore::largest_files_by_id(conn, root_id, limit)
+    })
+}
+
+/// Recursive physical sizes of `path` and its subdirectories down to `max_depth` levels, as
+/// `(relative_path, size)` rows with the root at `""` (see
+/// [`IndexStore::subdir_sizes_by_id`]). `Ok(None)` unless the index fully covers the subtree
+/// (see [`with_covered_subtree`]).
+pub fn subdir_sizes(path: &str, max_depth: u32) -> Result<Option<Vec<(String, u64)>>, String> {
+    with_covered_subtree(path, |conn, root_id| {
+        IndexStore::subdir_sizes_by_id(conn, root_id, max_depth)
+    })
+}
+
+/// Every entry below `path` from the index (see [`IndexStore::subtree_entries_by_id`]), for
+/// fingerprinting subtrees before hashing them. Stricter than the other subtree queries:
+/// `Ok(None)` unless the subtree is fully covered AND every folder in it was listed in the
+/// current epoch, since a stale row would let a changed folder pass as unchanged.
+pub fn subtree_entries(path: &str) -> Result<Option<Vec<store::SubtreeEntry>>, String> {
+    with_covered_subtree(path, |conn, root_id| {
+        let current_epoch = IndexStore::read_current_epoch(conn).unwrap_or(1);
+        let fresh =
+            IndexStore::get_dir_stats_by_id(conn, root_id)?.is_some_and(|s| s.min_subtree_epoch >= current_epoch);
+        if !fresh {
+            return Ok(None);
+        }
+        IndexStore::subtree_entries_by_id(conn, root_id).map(Some)
+    })
+    .map(Option::flatten)
+}
+
 /// Batch lookup of dir_stats, resolving the owning volume from the paths. The
 /// IPC `get_dir_stats_batch` sends one directory's children, which all live on
 /// one volume; resolving from the first path is sufficient. Routes via
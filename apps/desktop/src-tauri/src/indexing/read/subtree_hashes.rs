@@ -0,0 +1,60 @@
+//! Subtree content hashes, cached in the owning volume's `meta` table for
+//! `file_system::tree_hash`.
+//!
+//! A hash is stored under `subtree_hash:<index path>` together with the
+//! fingerprint of the index rows it was computed against (names, kinds, sizes,
+//! mtimes of everything below). A lookup only hits when the caller's fresh
+//! fingerprint matches, so any change the index has seen below the folder
+//! invalidates the entry without a separate eviction pass. The key survives a
+//! rescan (`TruncateData` keeps `meta`) and is simply overwritten on the next
+//! hash of that folder.
+//!
+//! Writes go through the volume's writer thread (`WriteMessage::UpdateMeta`),
+//! keeping the single-writer-per-DB invariant; a store while a full scan runs
+//! is dropped, since the rows it was fingerprinted against are being rebuilt.
+
+use super::enrichment::get_read_pool_for;
+use crate::indexing::lifecycle::state::get_writer_and_scanning_for;
+use crate::indexing::paths::firmlinks;
+use crate::indexing::paths::routing::{index_read_path, volume_id_for_local_path};
+use crate::indexing::store::IndexStore;
+use crate::indexing::writer::WriteMessage;
+
+const KEY_PREFIX: &str = "subtree_hash:";
+
+/// `(volume_id, meta key)` for `path`, or `None` when no index covers it.
+fn meta_key(path: &str) -> Option<(String, String)> {
+    let volume_id = volume_id_for_local_path(path);
+    let index_path = index_read_path(&volume_id, &firmlinks::normalize_path(path))?;
+    Some((volume_id, format!("{KEY_PREFIX}{index_path}")))
+}
+
+/// The hash stored for `path` if it was computed at `fingerprint`, else `None`.
+pub fn stored_subtree_hash(path: &str, fingerprint: &str) -> Option<String> {
+    let (volume_id, key) = meta_key(path)?;
+    let pool = get_read_pool_for(&volume_id)?;
+    let value = pool
+        .with_conn(|conn| IndexStore::get_meta(conn, &key))
+        .ok()?
+        .ok()
+        .flatten()?;
+    let (stored_fingerprint, hash) = value.split_once(':')?;
+    (stored_fingerprint == fingerprint).then(|| hash.to_string())
+}
+
+/// Queues `hash` for `path`, computed at `fingerprint`, onto the volume's writer.
+/// Best-effort: a volume without a running index, or one mid-scan, keeps nothing.
+pub fn store_subtree_hash(path: &str, fingerprint: &str, hash: &str) {
+    let Some((volume_id, key)) = meta_key(path) else {
+        return;
+    };
+    let Some((writer, false)) = get_writer_and_scanning_for(&volume_id) else {
+        return;
+    };
+    if let Err(e) = writer.send(WriteMessage::UpdateMeta {
+        key,
+        value: format!("{fingerprint}:{hash}"),
+    }) {
+        log::debug!("subtree_hashes: couldn't store hash for {path}: {e}");
+    }
+}
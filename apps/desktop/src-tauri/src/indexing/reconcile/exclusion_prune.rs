@@ -0,0 +1,161 @@
+//! Drops already-indexed folders that the user's exclusion patterns now cover.
+//!
+//! A new pattern stops the scanner, the reconciler, and the live event path from adding
+//! anything under a matching folder (`scanner::should_exclude`), but the rows a previous scan
+//! stored stay until something deletes them. [`prune_user_excluded`] finds those folders with
+//! one table scan (names first, then the full path of each candidate), and sends a
+//! `DeleteSubtreeById` per outermost match. The writer propagates the removed sizes up the
+//! ancestor chain like for a live folder removal.
+//!
+//! Each index records the pattern set it was last pruned for (`meta.exclusions_pruned`), so the
+//! prune runs again only when that set changes: on `set_index_exclusions`, and when an index
+//! starts under patterns it hasn't seen (an existing index meeting the default patterns, or a
+//! drive that was unplugged while they changed).
+//!
+//! Removing a pattern deletes nothing and re-adds nothing by itself: the folders come back
+//! through the verifier when the user opens their parent, or with the next full rescan.
+
+use crate::indexing::scanner::{self, UserExclusion};
+use crate::indexing::store::IndexStore;
+use crate::indexing::writer::WriteMessage;
+
+/// Puts `patterns` in force for every scan, reconcile, and live event from now on. `None`
+/// (the setting was never saved) means [`scanner::DEFAULT_USER_EXCLUSIONS`]. Doesn't prune:
+/// run [`prune_user_excluded`] per volume afterwards.
+pub(crate) fn apply_user_exclusions(patterns: Option<&[String]>) {
+    let globs = scanner::user_exclude_globs();
+    let in_force = match patterns {
+        Some(patterns) => globs.set(patterns),
+        None => globs.set(scanner::DEFAULT_USER_EXCLUSIONS),
+    };
+    log::debug!("Index exclusion patterns: {} in force", in_force.len());
+}
+
+/// The outermost of `paths`: drops every path that sits under another one in the list.
+fn outermost(mut paths: Vec<(String, i64)>) -> Vec<(String, i64)> {
+    // Component order keeps each folder's descendants right behind it (`a/x/z` before `a/x-y`).
+    paths.sort_by(|(a, _), (b, _)| a.split('/').cmp(b.split('/')));
+    let mut kept: Vec<(String, i64)> = Vec::with_capacity(paths.len());
+    for (path, id) in paths {
+        let nested = kept.last().is_some_and(|(outer, _)| {
+            path.strip_prefix(outer.as_str())
+                .is_some_and(|rest| rest.starts_with('/'))
+        });
+        if !nested {
+            kept.push((path, id));
+        }
+    }
+    kept
+}
+
+/// The meta key holding the pattern set an index was last pruned for.
+const PRUNED_PATTERNS_KEY: &str = "exclusions_pruned";
+
+/// `patterns` as stored under [`PRUNED_PATTERNS_KEY`]: one per line, sorted, so the order the
+/// user listed them in doesn't count as a change.
+fn pattern_set_key(patterns: &[UserExclusion]) -> String {
+    let mut lines: Vec<String> = patterns.iter().map(UserExclusion::to_pattern).collect();
+    lines.sort_unstable();
+    lines.join("\n")
+}
+
+/// Whether an indexed path (absolute on the boot disk, mount-relative elsewhere) matches.
+fn path_matches(patterns: &[UserExclusion], path: &str) -> bool {
+    let components: Vec<&str> = path.split('/').filter(|c| !c.is_empty()).collect();
+    patterns.iter().any(|p| p.matches_tail(&components))
+}
+
+/// Deletes every indexed folder on `volume_id` that matches a live user exclusion pattern,
+/// unless the index was already pruned for exactly these patterns. Returns how many subtrees it
+/// queued for deletion; 0 when the volume isn't indexed. Blocking: one full scan of the
+/// directory rows.
+pub(crate) fn prune_user_excluded(volume_id: &str) -> Result<usize, String> {
+    let patterns = scanner::user_exclude_globs().patterns();
+    let Some((writer, _scanning)) = crate::indexing::lifecycle::state::get_writer_and_scanning_for(volume_id) else {
+        return Ok(0);
+    };
+    let conn = IndexStore::open_read_connection(&writer.db_path())
+        .map_err(|e| format!("Couldn't open the index for {volume_id}: {e}"))?;
+    let set_key = pattern_set_key(&patterns);
+    let last_pruned = IndexStore::get_meta(&conn, PRUNED_PATTERNS_KEY)
+        .map_err(|e| format!("Couldn't read the index for {volume_id}: {e}"))?;
+    if last_pruned.as_deref() == Some(set_key.as_str()) {
+        return Ok(0);
+    }
+
+    let mut matches = Vec::new();
+    if !patterns.is_empty() {
+        let candidates = IndexStore::directory_ids_where(&conn, |name| patterns.iter().any(|p| p.matches_name(name)))
+            .map_err(|e| format!("Couldn't read the index for {volume_id}: {e}"))?;
+        for id in candidates {
+            match IndexStore::reconstruct_path(&conn, id) {
+                Ok(path) if path_matches(&patterns, &path) => matches.push((path, id)),
+                Ok(_) => {}
+                Err(e) => log::debug!("Exclusion prune: can't rebuild the path of entry {id}: {e}"),
+            }
+        }
+    }
+
+    let pruned = outermost(matches);
+    for (_, id) in &pruned {
+        writer
+            .send(WriteMessage::DeleteSubtreeById(*id))
+            .map_err(|e| format!("Couldn't prune the index for {volume_id}: {e}"))?;
+    }
+    // Queued behind the deletes, so a quit before they land leaves the old set and re-prunes.
+    writer
+        .send(WriteMessage::UpdateMeta {
+            key: PRUNED_PATTERNS_KEY.to_string(),
+            value: set_key,
+        })
+        .map_err(|e| format!("Couldn't prune the index for {volume_id}: {e}"))?;
+    if !pruned.is_empty() {
+        log::info!(
+            "Exclusion prune: removing {} excluded folders from the {volume_id} index",
+            pruned.len()
+        );
+    }
+    Ok(pruned.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn outermost_drops_nested_matches_only() {
+        let paths = vec![
+            ("/a/node_modules/x/node_modules".to_string(), 3),
+            ("/a/node_modules".to_string(), 2),
+            ("/a/node_modules_b".to_string(), 4),
+            ("/a/node_modules-c".to_string(), 6),
+            ("/b/.git".to_string(), 5),
+        ];
+        let kept: Vec<i64> = outermost(paths).into_iter().map(|(_, id)| id).collect();
+        assert_eq!(kept, vec![2, 6, 4, 5]);
+    }
+
+    #[test]
+    fn pattern_set_key_ignores_order_and_duplicate_slashes() {
+        let parse = |raws: &[&str]| -> Vec<UserExclusion> {
+            raws.iter().map(|raw| UserExclusion::parse(raw).unwrap()).collect()
+        };
+        assert_eq!(
+            pattern_set_key(&parse(&["node_modules", "Library/Caches"])),
+            pattern_set_key(&parse(&["/Library//Caches/", "node_modules"])),
+        );
+        assert_ne!(
+            pattern_set_key(&parse(&["node_modules"])),
+            pattern_set_key(&parse(&["node_modules", ".git"])),
+        );
+        assert_eq!(pattern_set_key(&[]), "");
+    }
+
+    #[test]
+    fn path_matches_needs_the_whole_trailing_pattern() {
+        let patterns = vec![UserExclusion::parse("Library/Caches").unwrap()];
+        assert!(path_matches(&patterns, "/Users/me/Library/Caches"));
+        assert!(path_matches(&patterns, "Library/Caches"));
+        assert!(!path_matches(&patterns, "/Users/me/Caches"));
+    }
+}
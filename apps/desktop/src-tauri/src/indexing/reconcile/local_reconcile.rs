@@ -346,6 +346,7 @@ fn summary(entries: u64, dirs: u64, physical_bytes: u64, start: Instant, cancell
         total_physical_bytes: physical_bytes,
         duration_ms: start.elapsed().as_millis() as u64,
         was_cancelled: cancelled,
+        skipped_inaccessible: Vec::new(),
     }
 }
 
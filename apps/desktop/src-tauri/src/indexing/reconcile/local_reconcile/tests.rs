@@ -143,6 +143,7 @@ fn no_panic_passes_the_result_through() {
         total_physical_bytes: 42,
         duration_ms: 1,
         was_cancelled: false,
+        skipped_inaccessible: Vec::new(),
     };
     let passed = run_catching_panics(|| Ok(summary.clone()));
     assert!(matches!(passed, Ok(s) if s.total_entries == 7 && s.total_physical_bytes == 42));
@@ -7,9 +7,15 @@
 //!   hang-tolerant `GuardedReader`, cost budget).
 //! - [`verifier`]: per-navigation `read_dir` diff that corrects the directory
 //!   the user is looking at.
+//! - [`exclusion_prune`]: the user's index exclusion patterns, and the sweep
+//!   that drops folders they newly cover.
+//! - [`reindex`]: user-started "refresh sizes for this folder", a paced,
+//!   cancellable, resumable reconcile of one subtree.
 
+pub(crate) mod exclusion_prune;
 pub(crate) mod local_reconcile;
 pub(crate) mod reconciler;
+pub(crate) mod reindex;
 pub(crate) mod verifier;
 
 // Reconcile rescan: perf guard (ignored bench) + correctness regression tests.
@@ -533,6 +533,36 @@ impl ReconcileSummary {
     }
 }
 
+/// Running counts a [`WalkControl::on_dir_listed`] observer sees after each listed dir.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct WalkProgress {
+    pub dirs_listed: u64,
+    /// Dirs found but not listed yet. Grows as the walk discovers subdirectories.
+    pub dirs_queued: u64,
+    pub added: u64,
+    pub removed: u64,
+    pub updated: u64,
+}
+
+/// Extra controls for a user-started walk (`reindex.rs`). The live callers run
+/// [`reconcile_subtree`], which uses the defaults: start at the root, no pacing,
+/// no observer.
+#[derive(Default)]
+pub(crate) struct WalkControl<'a> {
+    /// Dirs to walk INSTEAD of starting at the root: the `unvisited` of an earlier,
+    /// cancelled walk. Each is re-resolved by path (its id may be gone), and ones the
+    /// index no longer has are dropped: their parent's listing already healed them.
+    pub resume_from: Vec<PathBuf>,
+    /// Flush the writer every this many listed dirs (0 = never), so a large walk
+    /// waits for the writer instead of queueing its whole diff ahead of live events.
+    pub pace_every_dirs: usize,
+    /// Called after each listed dir.
+    pub on_dir_listed: Option<&'a mut dyn FnMut(WalkProgress)>,
+    /// Filled when the walk is cancelled: every dir it didn't list yet, for a later
+    /// walk's `resume_from`. Dirs already listed are stamped and need no second pass.
+    pub unvisited: Vec<PathBuf>,
+}
+
 /// This walk's accumulated wait on the writer queue, and a rearm for the next one.
 /// `reconcile_subtree` arms the probe at its start, so every read covers exactly
 /// one walk. See `writer::wait_probe`.
@@ -834,6 +864,18 @@ pub(crate) fn reconcile_subtree(
     conn: &Connection,
     writer: &IndexWriter,
     cancelled: &AtomicBool,
+) -> Result<ReconcileSummary, String> {
+    reconcile_subtree_with(root, space, conn, writer, cancelled, &mut WalkControl::default())
+}
+
+/// [`reconcile_subtree`] with resume, pacing, and progress hooks (see [`WalkControl`]).
+pub(crate) fn reconcile_subtree_with(
+    root: &Path,
+    space: &IndexPathSpace,
+    conn: &Connection,
+    writer: &IndexWriter,
+    cancelled: &AtomicBool,
+    control: &mut WalkControl<'_>,
 ) -> Result<ReconcileSummary, String> {
     let start = Instant::now();
     // Arm the writer-wait probe, discarding whatever ran on this thread before.
@@ -973,7 +1015,16 @@ pub(crate) fn reconcile_subtree(
     };
 
     let mut queue: VecDeque<(PathBuf, i64)> = VecDeque::new();
-    queue.push_back((root.to_path_buf(), root_id));
+    if control.resume_from.is_empty() {
+        queue.push_back((root.to_path_buf(), root_id));
+    } else {
+        for dir_path in control.resume_from.drain(..) {
+            let path_str = space.absolute(&dir_path.to_string_lossy());
+            if let Ok(Some(id)) = space.resolve_abs(conn, &path_str) {
+                queue.push_back((dir_path, id));
+            }
+        }
+    }
 
     // Collect newly-created directories so we can flush the writer, resolve their IDs,
     // and then queue them for recursive processing.
@@ -981,6 +1032,10 @@ pub(crate) fn reconcile_subtree(
 
     while let Some((dir_path, dir_id)) = queue.pop_front() {
         if cancelled.load(Ordering::Relaxed) {
+            control.unvisited = std::iter::once(dir_path)
+                .chain(queue.drain(..).map(|(path, _)| path))
+                .chain(new_dir_paths.drain(..))
+                .collect();
             break;
         }
 
@@ -1025,6 +1080,22 @@ pub(crate) fn reconcile_subtree(
             new_dir_paths.push(dir_path.join(child_name));
         }
 
+        if let Some(on_dir_listed) = control.on_dir_listed.as_mut() {
+            on_dir_listed(WalkProgress {
+                dirs_listed: listed_dir_ids.len() as u64,
+                dirs_queued: (queue.len() + new_dir_paths.len()) as u64,
+                added,
+                removed,
+                updated,
+            });
+        }
+        if control.pace_every_dirs > 0
+            && listed_dir_ids.len().is_multiple_of(control.pace_every_dirs)
+            && let Err(e) = writer.flush_blocking()
+        {
+            log::warn!("reconcile_subtree: pacing flush failed: {e}");
+        }
+
         // If we found new directories and the queue is empty (current level done),
         // flush the writer so the read connection can resolve the new IDs.
         if !new_dir_paths.is_empty() && queue.is_empty() {
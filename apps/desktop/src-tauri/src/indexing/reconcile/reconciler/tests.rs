@@ -1481,6 +1481,58 @@ fn reconcile_subtree_indexes_new_directory_not_in_db() {
     assert_eq!(children.len(), 2, "both child files should be indexed");
 }
 
+/// A cancelled walk hands back the dirs it didn't list, and a walk resumed from them
+/// picks up there: the second pass indexes what the first never reached.
+#[test]
+fn reconcile_subtree_with_resumes_from_unvisited_dirs() {
+    let (writer, dir, conn) = setup_test_writer();
+    let db_path = dir.path().join("test-reconciler.db");
+
+    let test_dir = non_excluded_tempdir();
+    let parent = test_dir.path().join("parent");
+    std::fs::create_dir_all(parent.join("sub")).unwrap();
+    std::fs::write(parent.join("sub").join("deep.txt"), "deep").unwrap();
+    ensure_path_in_db(&db_path, &parent.to_string_lossy(), &writer);
+
+    let mut control = WalkControl::default();
+    let summary = reconcile_subtree_with(
+        &parent,
+        &IndexPathSpace::root(),
+        &conn,
+        &writer,
+        &AtomicBool::new(true),
+        &mut control,
+    )
+    .unwrap();
+    assert_eq!(summary.added, 0, "a walk cancelled up front lists nothing");
+    assert_eq!(control.unvisited, vec![parent.clone()]);
+
+    let mut seen = Vec::new();
+    let mut on_dir_listed = |progress: WalkProgress| seen.push(progress.dirs_listed);
+    let mut resumed = WalkControl {
+        resume_from: std::mem::take(&mut control.unvisited),
+        pace_every_dirs: 1,
+        on_dir_listed: Some(&mut on_dir_listed),
+        ..WalkControl::default()
+    };
+    let summary = reconcile_subtree_with(
+        &parent,
+        &IndexPathSpace::root(),
+        &conn,
+        &writer,
+        &AtomicBool::new(false),
+        &mut resumed,
+    )
+    .unwrap();
+    assert_eq!(summary.added, 2, "sub and deep.txt");
+    assert!(resumed.unvisited.is_empty());
+    drop(resumed);
+    assert_eq!(seen, vec![1, 2]);
+
+    writer.flush_blocking().unwrap();
+    writer.shutdown();
+}
+
 /// A reconcile-discovered subtree must be stamped `listed_epoch = current`
 /// for every dir it lists (including empty ones), and ancestor coverage must
 /// lift. Without the mark, the subtree stays `listed_epoch = 0` forever and
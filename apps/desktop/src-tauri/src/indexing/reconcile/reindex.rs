@@ -0,0 +1,167 @@
+//! User-started "refresh sizes for this folder": a targeted re-index of one subtree.
+//!
+//! For a user who changed a lot offline and doesn't trust the journal replay, without paying
+//! for a full rescan. The walk is [`reconcile_subtree_with`], the same non-destructive diff the
+//! verifier and `MustScanSubDirs` use: each directory is listed, compared with its index rows,
+//! and only the differences go through the writer, so the folder keeps showing its old sizes
+//! (never an empty subtree, as `scanner::scan_subtree`'s delete-then-reinsert would) until the
+//! corrections land. The live writer propagates every correction up the ancestor chain; a final
+//! `ComputeSubtreeAggregates` then recomputes the folder's own aggregates from its rows, which
+//! also fixes sizes that drifted while every entry was right.
+//!
+//! The walk flushes the writer every [`PACE_EVERY_DIRS`] dirs, so live events keep flowing
+//! while a big tree is diffed. A cancelled walk remembers the dirs it didn't reach (in memory,
+//! per folder), and the next re-index of the same folder resumes from them.
+//!
+//! Boot disk only: that's the index whose path space is known without the volume's manager.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use super::reconciler::{WalkControl, WalkProgress, reconcile_subtree_with};
+use crate::cancel_registry::CancelRegistry;
+use crate::ignore_poison::IgnorePoison;
+use crate::indexing::paths::firmlinks;
+use crate::indexing::store::IndexStore;
+use crate::indexing::writer::WriteMessage;
+use crate::indexing::{IndexPathSpace, ROOT_VOLUME_ID, scanner, volume_id_for_local_path};
+
+/// Listed dirs between two writer flushes.
+const PACE_EVERY_DIRS: usize = 200;
+
+/// Gap between two `on_progress` calls. Matches the folder-size walk.
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Running or final counts of a re-index.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ReindexProgress {
+    pub dirs_listed: u64,
+    /// Dirs found but not listed yet.
+    pub dirs_queued: u64,
+    /// Index rows added, removed, and updated so far.
+    pub added: u64,
+    pub removed: u64,
+    pub updated: u64,
+}
+
+impl From<WalkProgress> for ReindexProgress {
+    fn from(p: WalkProgress) -> Self {
+        Self {
+            dirs_listed: p.dirs_listed,
+            dirs_queued: p.dirs_queued,
+            added: p.added,
+            removed: p.removed,
+            updated: p.updated,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ReindexResult {
+    pub progress: ReindexProgress,
+    /// `true` when the walk stopped early.
+    pub cancelled: bool,
+    /// `true` when a cancelled walk left dirs to resume from on the next re-index of this folder.
+    pub resumable: bool,
+}
+
+/// Cancel flags of in-flight re-indexes.
+pub static ACTIVE_REINDEXES: CancelRegistry = CancelRegistry::new();
+
+/// Dirs a cancelled re-index didn't reach, keyed by the folder it was started on.
+static RESUME_POINTS: LazyLock<Mutex<HashMap<PathBuf, Vec<PathBuf>>>> = LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Re-walks `path` and corrects its index rows, calling `on_progress` with the running counts
+/// at most every [`PROGRESS_INTERVAL`]. Resumes a cancelled earlier run on the same folder.
+/// Blocking: call it off the async runtime.
+pub fn reindex_subtree(
+    path: &Path,
+    cancelled: &AtomicBool,
+    on_progress: &dyn Fn(ReindexProgress),
+) -> Result<ReindexResult, String> {
+    if !path.is_dir() {
+        return Err(format!("Not a folder: {}", path.display()));
+    }
+    let path_str = firmlinks::normalize_path(&path.to_string_lossy());
+    if volume_id_for_local_path(&path_str) != ROOT_VOLUME_ID
+        || scanner::should_exclude(&path_str, &scanner::ExclusionScope::boot_disk())
+    {
+        return Err(format!("{path_str} isn't covered by the drive index"));
+    }
+    let writer = match crate::indexing::lifecycle::state::get_writer_and_scanning_for(ROOT_VOLUME_ID) {
+        Some((writer, false)) => writer,
+        Some((_, true)) => return Err("The drive index is still scanning. Try again when it's done.".to_string()),
+        None => return Err("Drive indexing is off".to_string()),
+    };
+    // Read connection only: writes ride the writer channel (see `rescan.rs`).
+    let conn = IndexStore::open_read_connection(&writer.db_path())
+        .map_err(|e| format!("Couldn't open the drive index: {e}"))?;
+    let root = PathBuf::from(&path_str);
+    let space = IndexPathSpace::root();
+
+    let mut last = ReindexProgress::default();
+    let mut last_report = Instant::now();
+    let mut on_dir_listed = |progress: WalkProgress| {
+        last = progress.into();
+        if last_report.elapsed() >= PROGRESS_INTERVAL {
+            on_progress(last);
+            last_report = Instant::now();
+        }
+    };
+    let resume_from = RESUME_POINTS.lock_ignore_poison().remove(&root).unwrap_or_default();
+    let resumed = !resume_from.is_empty();
+    let mut control = WalkControl {
+        resume_from,
+        pace_every_dirs: PACE_EVERY_DIRS,
+        on_dir_listed: Some(&mut on_dir_listed),
+        unvisited: Vec::new(),
+    };
+    let summary = reconcile_subtree_with(&root, &space, &conn, &writer, cancelled, &mut control)?;
+    let unvisited = std::mem::take(&mut control.unvisited);
+    drop(control);
+    if summary.escalation.is_some() {
+        return Err(format!("{path_str} isn't in the drive index yet"));
+    }
+
+    // Recompute the folder's aggregates from its (now corrected) rows and repair the ancestors.
+    if let Err(e) = writer.flush_blocking() {
+        log::warn!("Reindex: flush before the subtree aggregate failed: {e}");
+    }
+    if let Ok(Some(root_id)) = space.resolve_abs(&conn, &path_str)
+        && let Err(e) = writer.send(WriteMessage::ComputeSubtreeAggregates { root_id })
+    {
+        log::warn!("Reindex: failed to send ComputeSubtreeAggregates: {e}");
+    }
+
+    let was_cancelled = cancelled.load(Ordering::Relaxed);
+    let resumable = was_cancelled && !unvisited.is_empty();
+    if resumable {
+        RESUME_POINTS.lock_ignore_poison().insert(root, unvisited);
+    }
+    log::info!(
+        "Reindex of {path_str}{}: {} added, {} removed, {} updated in {:?}{}",
+        if resumed { " (resumed)" } else { "" },
+        summary.added,
+        summary.removed,
+        summary.updated,
+        summary.duration,
+        if was_cancelled { ", cancelled" } else { "" },
+    );
+    Ok(ReindexResult {
+        progress: ReindexProgress {
+            added: summary.added,
+            removed: summary.removed,
+            updated: summary.updated,
+            ..last
+        },
+        cancelled: was_cancelled,
+        resumable,
+    })
+}
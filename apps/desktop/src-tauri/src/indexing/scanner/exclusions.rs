@@ -10,8 +10,16 @@
 //! under `/Volumes/X`, SMB, or MTP applies only tier (b); the boot-disk scan
 //! applies both) and WHERE the volume root sits, since the pseudo-filesystem rule
 //! keys on root position. See [`ExclusionTier`] for why the tier split exists.
+//!
+//! On top of both tiers sit the user's exclusion patterns (`indexing.excludePatterns`,
+//! [`ExcludeGlobs`]): folder names like `node_modules` or trailing paths like
+//! `Library/Caches`, skipped on every volume. A scan reads them off
+//! [`ScanConfig::exclude_globs`](super::ScanConfig); every other path through
+//! [`should_exclude`] reads the user's live set.
+
+use std::sync::{Arc, LazyLock, OnceLock, RwLock};
 
-use std::sync::OnceLock;
+use crate::ignore_poison::RwLockIgnorePoison;
 
 /// Which exclusion tier applies to a `should_exclude` check, derived from the
 /// volume being scanned (never from `is_volume_root` — the boot `/` scan is also
@@ -366,10 +374,153 @@ pub(in crate::indexing) fn e2e_allowlist_path() -> Option<&'static str> {
         .as_deref()
 }
 
-/// Check if a path should be excluded from scanning, given the scan's
-/// [`ExclusionScope`]. Tier (b) junk basenames are skipped under both scopes;
-/// tier (a) absolute prefixes only under [`ExclusionTier::BootDisk`].
+// ── User exclusion patterns ─────────────────────────────────────────
+
+/// The patterns a fresh install starts with: churny trees whose sizes nobody browses by.
+/// The frontend's `indexing.excludePatterns` default must match.
+pub(crate) const DEFAULT_USER_EXCLUSIONS: &[&str] = &["node_modules", ".git", "Library/Caches"];
+
+/// One user exclusion pattern: path components, each a glob with `*` and `?`. It matches a
+/// directory whose trailing components match, so `node_modules` matches every folder of that
+/// name, and `Library/Caches` matches `~/Library/Caches` but not a lone `Caches`. Never
+/// anchored: a leading `/` is ignored. Case-sensitive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct UserExclusion {
+    segments: Vec<String>,
+}
+
+impl UserExclusion {
+    /// Parses a raw pattern. `None` for an empty one or one with a `.` / `..` component.
+    pub(crate) fn parse(raw: &str) -> Option<Self> {
+        let segments: Vec<String> = raw
+            .trim()
+            .split('/')
+            .filter(|s| !s.is_empty())
+            .map(String::from)
+            .collect();
+        if segments.is_empty() || segments.iter().any(|s| s == "." || s == "..") {
+            return None;
+        }
+        Some(Self { segments })
+    }
+
+    /// The pattern in its normal form: components joined by `/`, no leading or trailing slash.
+    pub(crate) fn to_pattern(&self) -> String {
+        self.segments.join("/")
+    }
+
+    /// Whether the last `segments.len()` of `components` match, one glob per component.
+    pub(crate) fn matches_tail(&self, components: &[&str]) -> bool {
+        components.len() >= self.segments.len()
+            && self
+                .segments
+                .iter()
+                .zip(&components[components.len() - self.segments.len()..])
+                .all(|(glob, name)| wildcard_match(glob, name))
+    }
+
+    /// Whether a folder named `name` could match: its last glob does. The cheap pre-filter
+    /// before [`matches_tail`](Self::matches_tail) on the full path.
+    pub(crate) fn matches_name(&self, name: &str) -> bool {
+        self.segments.last().is_some_and(|glob| wildcard_match(glob, name))
+    }
+
+    /// Whether any directory on `components` (a path split on `/`) matches, i.e. the path is
+    /// the excluded folder or sits anywhere under one.
+    fn matches_any_ancestor(&self, components: &[&str]) -> bool {
+        (self.segments.len()..=components.len()).any(|end| self.matches_tail(&components[..end]))
+    }
+}
+
+/// `*` matches any run of characters (including none), `?` exactly one. Everything else is
+/// literal.
+fn wildcard_match(glob: &str, name: &str) -> bool {
+    let glob: Vec<char> = glob.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    let (mut g, mut n) = (0, 0);
+    // Where the last `*` was, and how much of `name` it has swallowed so far.
+    let mut backtrack: Option<(usize, usize)> = None;
+    while n < name.len() {
+        if g < glob.len() && (glob[g] == '?' || glob[g] == name[n]) {
+            g += 1;
+            n += 1;
+        } else if g < glob.len() && glob[g] == '*' {
+            backtrack = Some((g, n));
+            g += 1;
+        } else if let Some((star, swallowed)) = backtrack {
+            g = star + 1;
+            n = swallowed + 1;
+            backtrack = Some((star, swallowed + 1));
+        } else {
+            return false;
+        }
+    }
+    glob[g..].iter().all(|&c| c == '*')
+}
+
+/// The user's exclusion patterns, shared between `set_index_exclusions` and the scans, so a
+/// change reaches a walk that's already running.
+#[derive(Debug, Default)]
+pub(crate) struct ExcludeGlobs {
+    patterns: RwLock<Vec<UserExclusion>>,
+}
+
+impl ExcludeGlobs {
+    /// Replaces the patterns, dropping ones that don't parse. Returns what's now in force.
+    /// Takes effect for the next path checked; pruning what's already indexed is the caller's job.
+    pub(crate) fn set<S: AsRef<str>>(&self, patterns: &[S]) -> Vec<UserExclusion> {
+        let mut parsed: Vec<UserExclusion> = Vec::with_capacity(patterns.len());
+        for raw in patterns {
+            match UserExclusion::parse(raw.as_ref()) {
+                Some(pattern) if !parsed.contains(&pattern) => parsed.push(pattern),
+                Some(_) => {}
+                None => log::warn!("Ignoring index exclusion pattern {:?}", raw.as_ref()),
+            }
+        }
+        *self.patterns.write_ignore_poison() = parsed.clone();
+        parsed
+    }
+
+    pub(crate) fn patterns(&self) -> Vec<UserExclusion> {
+        self.patterns.read_ignore_poison().clone()
+    }
+
+    /// Whether `path_str` is, or sits under, a folder matching one of the patterns. Only the
+    /// part below the scope's volume root is matched, so a pattern can never exclude a
+    /// volume's own mount path (which would empty the index and falsely complete it).
+    pub(in crate::indexing) fn excludes(&self, path_str: &str, scope: &ExclusionScope) -> bool {
+        let patterns = self.patterns.read_ignore_poison();
+        if patterns.is_empty() {
+            return false;
+        }
+        let root = trim_trailing_slash(scope.volume_root());
+        let relative = match path_str.strip_prefix(root) {
+            Some(rest) if root == "/" || rest.is_empty() || rest.starts_with('/') => rest,
+            _ => path_str,
+        };
+        let components: Vec<&str> = relative.split('/').filter(|c| !c.is_empty()).collect();
+        patterns.iter().any(|p| p.matches_any_ancestor(&components))
+    }
+}
+
+/// The user's patterns (`indexing.excludePatterns`), which the manager hands to every local scan.
+static USER_EXCLUDE_GLOBS: LazyLock<Arc<ExcludeGlobs>> = LazyLock::new(|| Arc::new(ExcludeGlobs::default()));
+
+pub(crate) fn user_exclude_globs() -> Arc<ExcludeGlobs> {
+    Arc::clone(&USER_EXCLUDE_GLOBS)
+}
+
+/// [`should_exclude_with`] against the user's live patterns, for the paths that don't run
+/// under a [`ScanConfig`](super::ScanConfig): the reconciler, the event loop, and the verifiers.
 pub(in crate::indexing) fn should_exclude(path_str: &str, scope: &ExclusionScope) -> bool {
+    should_exclude_with(path_str, scope, &USER_EXCLUDE_GLOBS)
+}
+
+/// Check if a path should be excluded from scanning, given the scan's
+/// [`ExclusionScope`] and the user's patterns. Tier (b) junk basenames and the user's
+/// patterns are skipped under both scopes; tier (a) absolute prefixes only under
+/// [`ExclusionTier::BootDisk`].
+pub(in crate::indexing) fn should_exclude_with(path_str: &str, scope: &ExclusionScope, user: &ExcludeGlobs) -> bool {
     // E2E mode: restrict scanning to only the fixture path and its ancestors.
     // Without this, the scanner traverses the entire filesystem from `/` which
     // is too slow in Docker containers (Linux E2E tests time out). This bounds
@@ -399,6 +550,9 @@ pub(in crate::indexing) fn should_exclude(path_str: &str, scope: &ExclusionScope
     if is_pseudo_fs_at_volume_root(path_str, scope) {
         return true;
     }
+    if user.excludes(path_str, scope) {
+        return true;
+    }
 
     // Tier (a): boot-disk absolute-prefix exclusions apply ONLY to the `/`-rooted
     // boot scan. A mount-rooted scan sits under `/Volumes/X` and must index its
@@ -665,4 +819,35 @@ mod tests {
         ));
         assert!(!is_on_mounted_external_volume("/"));
     }
+
+    fn components(path: &str) -> Vec<&str> {
+        path.split('/').filter(|c| !c.is_empty()).collect()
+    }
+
+    #[test]
+    fn user_exclusion_matches_trailing_components_and_their_subtrees() {
+        let node_modules = UserExclusion::parse("node_modules").unwrap();
+        assert!(node_modules.matches_tail(&components("/Users/me/code/app/node_modules")));
+        assert!(node_modules.matches_any_ancestor(&components("/Users/me/code/app/node_modules/react/index.js")));
+        assert!(!node_modules.matches_any_ancestor(&components("/Users/me/code/app/node_modules_old")));
+
+        let caches = UserExclusion::parse("/Library/Caches/").unwrap();
+        assert!(caches.matches_tail(&components("/Users/me/Library/Caches")));
+        assert!(!caches.matches_tail(&components("/Users/me/Caches")));
+        assert!(!caches.matches_any_ancestor(&components("/Users/me/Library")));
+    }
+
+    #[test]
+    fn user_exclusion_globs_and_rejects() {
+        let build = UserExclusion::parse("*.build").unwrap();
+        assert!(build.matches_tail(&["Foo.build"]));
+        assert!(!build.matches_tail(&["Foo.builds"]));
+        assert!(UserExclusion::parse("tmp?").unwrap().matches_tail(&["tmp1"]));
+        assert!(wildcard_match("a*b*c", "aXXbYc"));
+        assert!(!wildcard_match("a*b", "aXXc"));
+
+        assert_eq!(UserExclusion::parse("  "), None);
+        assert_eq!(UserExclusion::parse("/"), None);
+        assert_eq!(UserExclusion::parse("a/../b"), None);
+    }
 }
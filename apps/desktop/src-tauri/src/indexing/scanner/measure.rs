@@ -0,0 +1,172 @@
+//! On-demand recursive folder size ("calculate size" on a folder the index doesn't cover).
+//!
+//! Runs the same guarded [`walk`] as a scan, so a hung File Provider read costs one abandoned
+//! directory rather than the whole measurement, and gates every child through the indexer's
+//! [`should_exclude`] with the folder's volume scope. The [`SizeVisitor`] only counts: nothing
+//! is written. [`folder_size`] picks between this and a real [`scan_subtree_with_progress`]
+//! when the caller wants the result kept in the index.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use super::walker::{DirTask, DirVisitor, RawDirEntry, RawFileType, WalkConfig, WalkReadError, default_reader, walk};
+use super::{
+    ExclusionScope, LOCAL_LIST_TIMEOUT, ScanError, ScanProgress, ScanSummary, is_canonicalization_alias,
+    scan_subtree_with_progress, should_exclude,
+};
+use crate::ignore_poison::IgnorePoison;
+use crate::indexing::paths::firmlinks;
+use crate::indexing::paths::routing::{exclusion_scope_for_volume, volume_id_for_local_path};
+
+/// Counting-only [`DirVisitor`]: feeds the shared [`ScanProgress`] counters with the same
+/// post-dedup physical bytes the scanner stores, so a measured size and an indexed one agree.
+struct SizeVisitor {
+    scope: ExclusionScope,
+    entries_scanned: Arc<AtomicU64>,
+    dirs_found: Arc<AtomicU64>,
+    bytes_scanned: Arc<AtomicU64>,
+    /// Inodes seen with nlink > 1, so each hardlink's size counts once.
+    seen_inodes: Mutex<HashSet<u64>>,
+}
+
+impl DirVisitor for SizeVisitor {
+    fn visit_dir(&self, _dir: &DirTask, children: Vec<RawDirEntry>) -> Vec<DirTask> {
+        let mut subdirs = Vec::new();
+        for child in children {
+            let path_str = child.path.to_string_lossy();
+            if should_exclude(&path_str, &self.scope)
+                || is_canonicalization_alias(&path_str, &firmlinks::normalize_path(&path_str))
+            {
+                continue;
+            }
+            let is_dir = child.file_type == RawFileType::Dir;
+            let is_symlink = child.file_type == RawFileType::Symlink;
+            let snap = match child.stat {
+                Some(s) => crate::indexing::metadata::metadata_from_raw(
+                    s.logical_size,
+                    s.physical_size,
+                    s.modified_at,
+                    s.inode,
+                    s.nlink,
+                    is_dir,
+                    is_symlink,
+                ),
+                None => match std::fs::symlink_metadata(&child.path) {
+                    Ok(meta) => crate::indexing::metadata::extract_metadata(&meta, is_dir, is_symlink),
+                    Err(_) => continue,
+                },
+            };
+            let is_repeat_hardlink = !is_dir
+                && !is_symlink
+                && matches!(snap.nlink, Some(n) if n > 1)
+                && !self.seen_inodes.lock_ignore_poison().insert(snap.inode.unwrap_or(0));
+            if !is_repeat_hardlink {
+                self.bytes_scanned
+                    .fetch_add(snap.physical_size.unwrap_or(0), Ordering::Relaxed);
+            }
+            if is_dir {
+                subdirs.push(DirTask {
+                    path: child.path.clone(),
+                    id: 0,
+                });
+                self.dirs_found.fetch_add(1, Ordering::Relaxed);
+            }
+            self.entries_scanned.fetch_add(1, Ordering::Relaxed);
+        }
+        subdirs
+    }
+
+    fn visit_read_error(&self, dir: &DirTask, err: &WalkReadError) {
+        if let WalkReadError::Io(e) = err {
+            log::debug!("Folder size: skipping unreadable dir {}: {e}", dir.path.display());
+        }
+    }
+}
+
+/// Measures `root` without touching the index. Blocks until done or `cancelled`; the totals
+/// are live in `progress` throughout.
+fn measure_subtree(root: &Path, cancelled: &AtomicBool, progress: &ScanProgress) -> ScanSummary {
+    let start = Instant::now();
+    let scope = exclusion_scope_for_volume(&volume_id_for_local_path(&root.to_string_lossy()));
+    let visitor = Arc::new(SizeVisitor {
+        scope,
+        entries_scanned: Arc::clone(&progress.entries_scanned),
+        dirs_found: Arc::clone(&progress.dirs_found),
+        bytes_scanned: Arc::clone(&progress.bytes_scanned),
+        seen_inodes: Mutex::new(HashSet::new()),
+    });
+    let cfg = WalkConfig {
+        stall_timeout: LOCAL_LIST_TIMEOUT,
+        ..WalkConfig::default()
+    };
+    // Same cancel bridge as `run_scan`: the walker's workers need an owned flag.
+    let walk_cancel = Arc::new(AtomicBool::new(cancelled.load(Ordering::Relaxed)));
+    std::thread::scope(|s| {
+        let done = Arc::new(AtomicBool::new(false));
+        let bridge = {
+            let done = Arc::clone(&done);
+            let walk_cancel = Arc::clone(&walk_cancel);
+            s.spawn(move || {
+                while !done.load(Ordering::Relaxed) {
+                    if cancelled.load(Ordering::Relaxed) {
+                        walk_cancel.store(true, Ordering::Relaxed);
+                        break;
+                    }
+                    std::thread::park_timeout(Duration::from_millis(100));
+                }
+            })
+        };
+        let root_task = DirTask {
+            path: root.to_path_buf(),
+            id: 0,
+        };
+        walk(root_task, cfg, default_reader(), visitor, Arc::clone(&walk_cancel));
+        done.store(true, Ordering::Relaxed);
+        bridge.thread().unpark();
+    });
+    let snap = progress.snapshot();
+    ScanSummary {
+        total_entries: snap.entries_scanned,
+        total_dirs: snap.dirs_found,
+        total_physical_bytes: snap.bytes_scanned,
+        duration_ms: start.elapsed().as_millis() as u64,
+        was_cancelled: cancelled.load(Ordering::Relaxed),
+        skipped_inaccessible: Vec::new(),
+    }
+}
+
+/// Computes `root`'s recursive size. With `write_to_index`, and when `root` is on the boot
+/// disk whose index is running and not mid-scan, it's rescanned into the index instead
+/// (`scan_subtree`, which recomputes the subtree's aggregates), so the next size query is
+/// instant. A folder the index has no row for yet falls back to the plain measurement.
+/// Returns the summary and whether the index was updated. Blocking.
+pub(crate) fn folder_size(
+    root: &Path,
+    write_to_index: bool,
+    cancelled: &AtomicBool,
+    progress: &ScanProgress,
+) -> Result<(ScanSummary, bool), ScanError> {
+    // Walk the canonical path (`/tmp` → `/private/tmp`): under an alias root every child would
+    // look like a canonicalization alias and be skipped, and the index only knows the real one.
+    let path_str = firmlinks::normalize_path(&root.to_string_lossy());
+    let root = PathBuf::from(&path_str);
+    let volume_id = volume_id_for_local_path(&path_str);
+    if write_to_index
+        && volume_id == crate::indexing::ROOT_VOLUME_ID
+        && !should_exclude(&path_str, &ExclusionScope::boot_disk())
+        && let Some((writer, false)) = crate::indexing::lifecycle::state::get_writer_and_scanning_for(&volume_id)
+    {
+        match scan_subtree_with_progress(&root, &writer, cancelled, progress) {
+            Ok(summary) => return Ok((summary, true)),
+            // Nothing was walked yet (the root resolve failed), so the counters are clean.
+            Err(ScanError::WriterSend(e)) if progress.snapshot().entries_scanned == 0 => {
+                log::debug!("Folder size: can't write {path_str} to the index ({e}), measuring only");
+            }
+            Err(e) => return Err(e),
+        }
+    }
+    Ok((measure_subtree(&root, cancelled, progress), false))
+}
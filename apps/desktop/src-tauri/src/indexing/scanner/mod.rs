@@ -24,6 +24,13 @@ use crate::pluralize::{pluralize, pluralize_with};
 mod exclusions;
 pub(in crate::indexing) use exclusions::*;
 
+mod measure;
+pub(crate) use measure::folder_size;
+
+mod throttle;
+use throttle::Pacer;
+pub use throttle::{ScanThrottle, ScanThrottleSettings, set_user_scan_throttle, user_scan_throttle};
+
 mod walker;
 use walker::{
     DEFAULT_GIVE_UP_AFTER, DEFAULT_PER_ENTRY_ALLOWANCE, DirTask, DirVisitor, RawDirEntry, RawFileType, ReadDirFn,
@@ -90,6 +97,13 @@ pub struct ScanConfig {
     /// match a reused inode. Defaults to `true`; the manager feeds it from the
     /// volume's `IndexPathSpace`. See `filesystem_kind::has_stable_inodes`.
     pub(crate) inodes_trustworthy: bool,
+    /// Rate cap and AC-only mode (see `throttle.rs`). Shared, so a change reaches a running
+    /// scan. Defaults to an unthrottled one of its own; the manager passes the user's
+    /// [`user_scan_throttle`].
+    pub throttle: Arc<ScanThrottle>,
+    /// The user's exclusion patterns (see `exclusions.rs`). Shared for the same reason as
+    /// `throttle`. Defaults to none; the manager passes the user's [`user_exclude_globs`].
+    pub(crate) exclude_globs: Arc<ExcludeGlobs>,
 }
 
 impl Default for ScanConfig {
@@ -100,6 +114,8 @@ impl Default for ScanConfig {
             num_threads: 0,
             scope: ExclusionScope::boot_disk(),
             inodes_trustworthy: true,
+            throttle: Arc::new(ScanThrottle::default()),
+            exclude_globs: Arc::new(ExcludeGlobs::default()),
         }
     }
 }
@@ -113,6 +129,12 @@ pub struct ScanProgress {
     /// follows the exact same rules as the stored physical-size sums (directories,
     /// symlinks, and second+ hardlinks contribute 0).
     pub bytes_scanned: Arc<AtomicU64>,
+    /// Whether the scan's throttle held back its latest directory: the scan is running below
+    /// its natural speed (or paused on battery) by the user's choice.
+    pub throttled: Arc<AtomicBool>,
+    /// Set while the scan is paused ([`ScanHandle::pause`]). The walk holds its workers and
+    /// the progress reporter stops emitting until it clears.
+    pub paused: Arc<AtomicBool>,
 }
 
 /// A point-in-time read of an active scan's progress counters.
@@ -129,6 +151,8 @@ impl ScanProgress {
             entries_scanned: Arc::new(AtomicU64::new(0)),
             dirs_found: Arc::new(AtomicU64::new(0)),
             bytes_scanned: Arc::new(AtomicU64::new(0)),
+            throttled: Arc::new(AtomicBool::new(false)),
+            paused: Arc::new(AtomicBool::new(false)),
         }
     }
 
@@ -146,20 +170,45 @@ impl ScanProgress {
 pub struct ScanHandle {
     pub progress: Arc<ScanProgress>,
     cancelled: Arc<AtomicBool>,
+    /// Whether the walk honors [`ScanProgress::paused`]. Only the guarded-walker fresh scan
+    /// does; the others would keep running under a "paused" flag.
+    pausable: bool,
 }
 
 impl ScanHandle {
     /// Build a handle around an existing progress + cancel pair. Used by the
     /// `Volume`-trait scanner (`network_scanner`), which owns the walk itself and
-    /// just needs the manager-facing progress/cancel surface.
+    /// just needs the manager-facing progress/cancel surface. Not pausable.
     pub(crate) fn new(progress: Arc<ScanProgress>, cancelled: Arc<AtomicBool>) -> Self {
-        Self { progress, cancelled }
+        Self {
+            progress,
+            cancelled,
+            pausable: false,
+        }
     }
 
     /// Signal the scan to stop. Already-written data remains in the DB.
     pub fn cancel(&self) {
         self.cancelled.store(true, Ordering::Relaxed);
     }
+
+    /// Holds the walk where it is: workers block after their current directory, queued
+    /// directories stay queued, and [`resume`](Self::resume) carries on from there. Returns
+    /// `false` (and does nothing) for a scan that can't pause.
+    pub fn pause(&self) -> bool {
+        if self.pausable {
+            self.progress.paused.store(true, Ordering::Relaxed);
+        }
+        self.pausable
+    }
+
+    pub fn resume(&self) {
+        self.progress.paused.store(false, Ordering::Relaxed);
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.progress.paused.load(Ordering::Relaxed)
+    }
 }
 
 /// Summary returned when a scan completes (or is cancelled).
@@ -172,6 +221,11 @@ pub struct ScanSummary {
     pub total_physical_bytes: u64,
     pub duration_ms: u64,
     pub was_cancelled: bool,
+    /// Directories whose read failed with permission denied (typically missing Full Disk
+    /// Access on macOS). They stay unlisted, so their ancestors' aggregates roll up as
+    /// incomplete rather than zero. Only the fresh local scan fills this; the other scan
+    /// paths leave it empty.
+    pub skipped_inaccessible: Vec<String>,
 }
 
 /// Errors that can occur during scanning.
@@ -248,6 +302,7 @@ pub fn scan_volume(
     let handle = ScanHandle {
         progress: Arc::clone(&progress),
         cancelled: Arc::clone(&cancelled),
+        pausable: true,
     };
 
     let writer = writer.clone();
@@ -266,7 +321,9 @@ pub fn scan_volume(
                 config.num_threads,
                 true, // volume scan: root always maps to ROOT_ID
                 config.scope.clone(),
+                config.exclude_globs,
                 config.inodes_trustworthy,
+                Pacer::new(config.throttle, Arc::clone(&progress.paused)),
                 reader,
                 LOCAL_LIST_TIMEOUT,
             );
@@ -302,12 +359,22 @@ pub fn scan_volume(
 /// Used by post-replay background verification. After scanning, sends
 /// `ComputeSubtreeAggregates` to the writer.
 pub fn scan_subtree(root: &Path, writer: &IndexWriter, cancelled: &AtomicBool) -> Result<ScanSummary, ScanError> {
-    let progress = Arc::new(ScanProgress::new());
+    scan_subtree_with_progress(root, writer, cancelled, &ScanProgress::new())
+}
+
+/// [`scan_subtree`] with caller-owned progress counters, for a caller that reports them live
+/// (on-demand folder size, see `measure.rs`).
+pub(crate) fn scan_subtree_with_progress(
+    root: &Path,
+    writer: &IndexWriter,
+    cancelled: &AtomicBool,
+    progress: &ScanProgress,
+) -> Result<ScanSummary, ScanError> {
     let reader: ReadDirFn = default_reader();
     let (summary, listed_ids, epoch, root_id) = run_scan(
         root,
         cancelled,
-        &progress,
+        progress,
         writer,
         2000,
         0,
@@ -315,9 +382,13 @@ pub fn scan_subtree(root: &Path, writer: &IndexWriter, cancelled: &AtomicBool) -
         // Subtree scans don't apply global exclusions (the subtree was chosen
         // explicitly), so the scope is inert here; pass the boot-disk one.
         ExclusionScope::boot_disk(),
+        // The user's patterns still apply: see `InsertVisitor::visit_dir`.
+        user_exclude_globs(),
         // Subtree scans back post-replay background verification, which is
         // root-only (the boot disk, APFS) — trustworthy inodes.
         true,
+        // Someone is waiting on a subtree scan (folder size, verification): never paced.
+        Pacer::new(Arc::new(ScanThrottle::default()), Arc::new(AtomicBool::new(false))),
         reader,
         LOCAL_LIST_TIMEOUT,
     )?;
@@ -344,7 +415,8 @@ pub fn scan_subtree(root: &Path, writer: &IndexWriter, cancelled: &AtomicBool) -
 /// Parent attribution needs no path→id map: [`walk`] carries each directory's id
 /// to its own read, so children take their parent's id directly. Ids come from the
 /// shared `IndexWriter` counter. The scan root maps to `ROOT_ID` (volume scans) or
-/// its existing entry id (subtree scans). `stall_timeout` is how long one read may
+/// its existing entry id (subtree scans). `pacer` applies the scan's throttle after each
+/// directory (see `throttle.rs`). `stall_timeout` is how long one read may
 /// go without delivering an entry (production passes `LOCAL_LIST_TIMEOUT`; tests
 /// pass a short one).
 #[allow(
@@ -360,7 +432,9 @@ fn run_scan(
     num_threads: usize,
     is_volume_root: bool,
     scope: ExclusionScope,
+    exclude_globs: Arc<ExcludeGlobs>,
     inodes_trustworthy: bool,
+    pacer: Pacer,
     reader: ReadDirFn,
     stall_timeout: Duration,
 ) -> Result<(ScanSummary, Vec<i64>, u64, i64), ScanError> {
@@ -402,9 +476,11 @@ fn run_scan(
         writer.clone(),
         is_volume_root,
         scope,
+        exclude_globs,
         inodes_trustworthy,
         batch_size,
         progress,
+        pacer,
         Arc::clone(&walk_cancel),
     ));
 
@@ -461,6 +537,13 @@ fn run_scan(
             stall_timeout.as_secs(),
         );
     }
+    let skipped_inaccessible = visitor.take_inaccessible();
+    if !skipped_inaccessible.is_empty() {
+        log::info!(
+            "Scanner: skipped {} with permission denied; their sizes stay unknown",
+            pluralize(skipped_inaccessible.len() as u64, "dir"),
+        );
+    }
     if walk_stats.subtrees_abandoned > 0 {
         log::warn!(
             "Scanner: gave up on {} after {DEFAULT_GIVE_UP_AFTER} consecutive failed reads each \
@@ -506,6 +589,7 @@ fn run_scan(
             total_physical_bytes: snap.bytes_scanned,
             duration_ms: start.elapsed().as_millis() as u64,
             was_cancelled,
+            skipped_inaccessible,
         },
         listed_ids,
         epoch,
@@ -527,6 +611,8 @@ struct InsertVisitor {
     is_volume_root: bool,
     /// Exclusion scope for the per-child gate (see `ScanConfig::scope`).
     scope: ExclusionScope,
+    /// The user's patterns (see `ScanConfig::exclude_globs`).
+    exclude_globs: Arc<ExcludeGlobs>,
     /// Whether the scanned volume's inode is a trustworthy identity (see
     /// `ScanConfig::inodes_trustworthy`). `false` on FAT/exFAT ⇒ every stored
     /// `inode` is nulled and hardlink dedup is skipped.
@@ -537,6 +623,9 @@ struct InsertVisitor {
     entries_scanned: Arc<AtomicU64>,
     dirs_found: Arc<AtomicU64>,
     bytes_scanned: Arc<AtomicU64>,
+    /// Paces the walk after each directory, and the live flag saying whether it's holding back.
+    pacer: Pacer,
+    throttled: Arc<AtomicBool>,
     /// Set when a writer send fails, to abort the walk promptly.
     walk_cancel: Arc<AtomicBool>,
     /// Accumulating insert batch, flushed at `batch_size`.
@@ -545,18 +634,26 @@ struct InsertVisitor {
     seen_inodes: Mutex<HashSet<u64>>,
     /// Ids of directories whose read succeeded (marked listed after the walk).
     listed_ids: Mutex<Vec<i64>>,
+    /// Paths of directories whose read failed with permission denied.
+    inaccessible: Mutex<Vec<String>>,
     /// First writer-send error, surfaced as the scan result.
     send_error: Mutex<Option<String>>,
 }
 
 impl InsertVisitor {
+    #[allow(
+        clippy::too_many_arguments,
+        reason = "mirrors `run_scan`'s parameters, which it takes apart into per-field state"
+    )]
     fn new(
         writer: IndexWriter,
         is_volume_root: bool,
         scope: ExclusionScope,
+        exclude_globs: Arc<ExcludeGlobs>,
         inodes_trustworthy: bool,
         batch_size: usize,
         progress: &ScanProgress,
+        pacer: Pacer,
         walk_cancel: Arc<AtomicBool>,
     ) -> Self {
         let next_id = Arc::clone(writer.next_id());
@@ -565,15 +662,19 @@ impl InsertVisitor {
             next_id,
             is_volume_root,
             scope,
+            exclude_globs,
             inodes_trustworthy,
             batch_size,
             entries_scanned: Arc::clone(&progress.entries_scanned),
             dirs_found: Arc::clone(&progress.dirs_found),
             bytes_scanned: Arc::clone(&progress.bytes_scanned),
+            pacer,
+            throttled: Arc::clone(&progress.throttled),
             walk_cancel,
             batch: Mutex::new(Vec::with_capacity(batch_size)),
             seen_inodes: Mutex::new(HashSet::new()),
             listed_ids: Mutex::new(Vec::new()),
+            inaccessible: Mutex::new(Vec::new()),
             send_error: Mutex::new(None),
         }
     }
@@ -619,6 +720,13 @@ impl InsertVisitor {
     fn take_listed_ids(&self) -> Vec<i64> {
         std::mem::take(&mut *self.listed_ids.lock_ignore_poison())
     }
+
+    fn take_inaccessible(&self) -> Vec<String> {
+        let mut paths = std::mem::take(&mut *self.inaccessible.lock_ignore_poison());
+        // Workers finish in any order; sort so logs and the warning event are stable.
+        paths.sort_unstable();
+        paths
+    }
 }
 
 impl DirVisitor for InsertVisitor {
@@ -626,6 +734,7 @@ impl DirVisitor for InsertVisitor {
         // This directory's read succeeded → mark it listed at scan end.
         self.listed_ids.lock_ignore_poison().push(dir.id);
 
+        let entry_count = children.len() as u64;
         let mut subdirs = Vec::new();
         for child in children {
             let path_str = child.path.to_string_lossy();
@@ -634,8 +743,16 @@ impl DirVisitor for InsertVisitor {
             // explicitly chosen, so global exclusions don't apply. The scope comes
             // from the volume kind: `BootDisk` for the `/`-rooted boot scan,
             // `MountRooted` for an external drive rooted at `/Volumes/X` (which must
-            // index its own subtree, skipping only junk basenames).
-            if self.is_volume_root && should_exclude(&path_str, &self.scope) {
+            // index its own subtree, skipping only junk basenames). The user's
+            // patterns apply to subtree scans too: a new folder the reconciler or the
+            // verifier finds is scanned this way, and a fresh clone's `node_modules`
+            // must stay out.
+            let excluded = if self.is_volume_root {
+                should_exclude_with(&path_str, &self.scope, &self.exclude_globs)
+            } else {
+                self.exclude_globs.excludes(&path_str, &self.scope)
+            };
+            if excluded {
                 continue;
             }
             // Skip canonicalization aliases (/tmp, /var, /etc, Data-volume
@@ -728,6 +845,11 @@ impl DirVisitor for InsertVisitor {
                 inode,
             });
         }
+
+        // Pace here, between reads: the watchdog only times reads, so a sleep can't be
+        // mistaken for a hung directory.
+        let held_back = self.pacer.pace(entry_count, &self.walk_cancel);
+        self.throttled.store(held_back, Ordering::Relaxed);
         subdirs
     }
 
@@ -736,8 +858,13 @@ impl DirVisitor for InsertVisitor {
             WalkReadError::Io(e) => {
                 // Surface TCC-restricted paths so the sidebar can show the "limited
                 // by macOS" styling. `record_denial` filters to known TCC prefixes.
+                // Also keep every denied dir for the scan summary, so the user learns the
+                // index is incomplete (see `ScanSummary::skipped_inaccessible`).
                 if e.kind() == std::io::ErrorKind::PermissionDenied {
                     crate::restricted_paths::record_denial(&dir.path);
+                    self.inaccessible
+                        .lock_ignore_poison()
+                        .push(dir.path.to_string_lossy().into_owned());
                 }
                 log::debug!("Scanner: skipping errored dir {}: {e}", dir.path.display());
             }
@@ -285,6 +285,48 @@ fn scan_temp_directory_tree() {
     );
 }
 
+#[test]
+fn scan_skips_folders_matching_the_configs_exclude_globs() {
+    let scan_root = scan_test_tempdir();
+    create_test_tree(scan_root.path());
+    let modules = scan_root.path().join("subdir").join("node_modules");
+    fs::create_dir_all(modules.join("left-pad")).unwrap();
+    fs::write(modules.join("left-pad").join("index.js"), "module.exports = 1").unwrap();
+
+    let (writer, db_path, _db_dir) = setup_writer();
+    let exclude_globs = Arc::new(ExcludeGlobs::default());
+    exclude_globs.set(&["node_modules"]);
+    let config = ScanConfig {
+        root: scan_root.path().to_path_buf(),
+        batch_size: 100,
+        num_threads: 1,
+        exclude_globs,
+        ..ScanConfig::default()
+    };
+
+    let (_handle, join_handle) = scan_volume(config, &writer).unwrap();
+    let summary = join_handle.join().expect("scan thread panicked").unwrap();
+    writer.flush_blocking().unwrap();
+    writer.shutdown();
+
+    // The same 6 entries as the plain tree: nothing from node_modules, not even the folder
+    assert_eq!(summary.total_entries, 6);
+    let store = IndexStore::open(&db_path).unwrap();
+    let subdir = store
+        .list_children(ROOT_ID)
+        .unwrap()
+        .into_iter()
+        .find(|e| e.name == "subdir")
+        .unwrap();
+    let names: Vec<String> = store
+        .list_children(subdir.id)
+        .unwrap()
+        .into_iter()
+        .map(|e| e.name)
+        .collect();
+    assert!(!names.contains(&"node_modules".to_string()), "got {names:?}");
+}
+
 /// After a clean local scan, EVERY directory (root + every subdir, all of
 /// which the guarded walker read successfully) has `listed_epoch == current_epoch`. This
 /// is the ordering-invariant anchor: a `MarkDirsListed` queued *behind* the
@@ -870,7 +912,9 @@ fn timed_out_dir_is_not_marked_listed() {
         4,
         true,
         ExclusionScope::boot_disk(),
+        Arc::new(ExcludeGlobs::default()),
         true, // inodes trustworthy (boot-disk-scope test)
+        Pacer::new(Arc::new(ScanThrottle::default()), Arc::new(AtomicBool::new(false))),
         reader,
         Duration::from_millis(50), // short timeout so the hang is abandoned fast
     )
@@ -922,6 +966,94 @@ fn timed_out_dir_is_not_marked_listed() {
     assert_eq!(listed_epoch(ROOT_ID), epoch_now, "root marked at current epoch");
 }
 
+#[test]
+fn permission_denied_dir_is_reported_and_rolls_up_incomplete() {
+    use crate::indexing::scanner::walker::{RawDirEntry, RawFileType, ReadDirFn, ReadProgress};
+
+    // Mock tree under "/root": "locked" (dir, read denied) and "ok" (dir, has a file).
+    let root = PathBuf::from("/root");
+    let locked = root.join("locked");
+    let reader: ReadDirFn = {
+        let root = root.clone();
+        let locked = locked.clone();
+        Arc::new(move |p: &Path, progress: &ReadProgress| {
+            let children: &[(&str, RawFileType)] = if p == root {
+                &[("locked", RawFileType::Dir), ("ok", RawFileType::Dir)]
+            } else if p == locked {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::PermissionDenied,
+                    "denied (test)",
+                ));
+            } else {
+                &[("seen.txt", RawFileType::File)]
+            };
+            progress.record_entries(children.len() as u64);
+            Ok(children
+                .iter()
+                .map(|(n, t)| RawDirEntry {
+                    path: p.join(n),
+                    file_type: *t,
+                    stat: None,
+                })
+                .collect())
+        })
+    };
+
+    let (writer, db_path, _db_dir) = setup_writer();
+    let progress = Arc::new(ScanProgress::new());
+    let cancelled = AtomicBool::new(false);
+    let (summary, listed_ids, epoch, _root_id) = run_scan(
+        &root,
+        &cancelled,
+        &progress,
+        &writer,
+        100,
+        4,
+        true,
+        ExclusionScope::boot_disk(),
+        Arc::new(ExcludeGlobs::default()),
+        true,
+        Pacer::new(Arc::new(ScanThrottle::default()), Arc::new(AtomicBool::new(false))),
+        reader,
+        Duration::from_millis(500),
+    )
+    .expect("run_scan");
+    assert_eq!(
+        summary.skipped_inaccessible,
+        vec![locked.to_string_lossy().into_owned()]
+    );
+
+    send_marks(&listed_ids, epoch, &writer);
+    writer
+        .send(WriteMessage::ComputeAllAggregates {
+            source: AggSource::Maps,
+        })
+        .unwrap();
+    writer.flush_blocking().unwrap();
+    writer.shutdown();
+
+    // The denied dir stays unlisted, so the root's subtree reads as incomplete, not as a
+    // finished total that silently counts the locked dir as empty.
+    let conn = IndexStore::open_read_connection(&db_path).unwrap();
+    let locked_id = IndexStore::resolve_component(&conn, ROOT_ID, "locked")
+        .unwrap()
+        .expect("locked dir row exists (its parent listed it)");
+    let listed_epoch: u64 = conn
+        .query_row("SELECT listed_epoch FROM entries WHERE id = ?1", [locked_id], |r| {
+            r.get(0)
+        })
+        .unwrap();
+    assert_eq!(listed_epoch, 0, "denied dir must stay listed_epoch = 0");
+    let root_min_subtree_epoch: u64 = conn
+        .query_row(
+            "SELECT min_subtree_epoch FROM dir_stats WHERE entry_id = ?1",
+            [ROOT_ID],
+            |r| r.get(0),
+        )
+        .unwrap();
+    assert_eq!(root_min_subtree_epoch, 0, "root must roll up as incomplete");
+}
+
 #[test]
 fn volume_root_that_never_lists_surfaces_root_unlistable() {
     use crate::indexing::scanner::walker::{ReadDirFn, ReadProgress};
@@ -952,7 +1084,9 @@ fn volume_root_that_never_lists_surfaces_root_unlistable() {
         4,
         true, // volume-root scan
         ExclusionScope::boot_disk(),
+        Arc::new(ExcludeGlobs::default()),
         true,
+        Pacer::new(Arc::new(ScanThrottle::default()), Arc::new(AtomicBool::new(false))),
         reader,
         Duration::from_millis(50),
     );
@@ -963,3 +1097,29 @@ fn volume_root_that_never_lists_surfaces_root_unlistable() {
         "an unreadable volume root must surface RootUnlistable, got {result:?}"
     );
 }
+
+#[test]
+fn folder_size_counts_without_writing_and_dedups_hardlinks() {
+    let scan_root = scan_test_tempdir();
+    let root = scan_root.path();
+    create_test_tree(root);
+    fs::hard_link(root.join("file1.txt"), root.join("subdir/file1-link.txt")).unwrap();
+    let progress = ScanProgress::new();
+
+    let (summary, written) = folder_size(root, false, &AtomicBool::new(false), &progress).unwrap();
+
+    // file1, file2, subdir, nested, deep, leaf, and the link.
+    assert_eq!(summary.total_entries, 7);
+    assert_eq!(summary.total_dirs, 2);
+    assert!(!written && !summary.was_cancelled);
+    let physical = |name: &str| {
+        use std::os::unix::fs::MetadataExt;
+        fs::metadata(root.join(name)).unwrap().blocks() * 512
+    };
+    let expected: u64 = ["file1.txt", "file2.txt", "subdir/nested.txt", "subdir/deep/leaf.txt"]
+        .iter()
+        .map(|name| physical(name))
+        .sum();
+    assert_eq!(summary.total_physical_bytes, expected, "the hardlink counts once");
+    assert_eq!(progress.snapshot().bytes_scanned, expected);
+}
@@ -0,0 +1,287 @@
+//! User-set pacing for the fresh full scan, so a first index on a laptop doesn't pin a core
+//! and spin the fans for minutes.
+//!
+//! Two knobs, both off by default: a cap on entries inserted per second, and "only scan on
+//! AC power", which pauses the walk while the machine runs on battery. The knobs live in a
+//! shared [`ScanThrottle`] that [`ScanConfig::throttle`](super::ScanConfig) points at, so a
+//! change from `set_index_throttle` reaches a scan that's already running. Each scan paces
+//! itself with its own [`Pacer`], so two drives scanning at once each get the full rate.
+//!
+//! The pacer is also where a paused scan ([`ScanHandle::pause`](super::ScanHandle::pause))
+//! waits: same spot, same cancel-aware sleep.
+//!
+//! Only the fresh guarded-walker scan is paced. Subtree scans back folder-size measurement
+//! and verification, where someone is waiting, and the serial reconcile walk is already
+//! gentle.
+
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::ignore_poison::IgnorePoison;
+
+/// Longest single sleep, so a cancel or a raised limit takes effect promptly.
+const SLEEP_SLICE: Duration = Duration::from_millis(100);
+
+/// Longest stretch the rate is averaged over. A scan that ran below the cap (disk-bound,
+/// or in a folder of huge files) can't bank more than this much credit for a burst later.
+const RATE_WINDOW: Duration = Duration::from_secs(5);
+
+/// How often the AC-only mode re-reads the power source while scanning or paused.
+const POWER_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+/// The throttle knobs, as the frontend sets them.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ScanThrottleSettings {
+    /// Most entries the scan inserts per second. `0` means no limit.
+    pub max_entries_per_sec: u32,
+    /// Pause the scan while the machine runs on battery.
+    pub only_on_ac_power: bool,
+}
+
+/// Live-adjustable throttle knobs, shared between the settings command and running scans.
+#[derive(Debug, Default)]
+pub struct ScanThrottle {
+    max_entries_per_sec: AtomicU32,
+    only_on_ac_power: AtomicBool,
+}
+
+impl ScanThrottle {
+    pub fn set(&self, settings: ScanThrottleSettings) {
+        self.max_entries_per_sec
+            .store(settings.max_entries_per_sec, Ordering::Relaxed);
+        self.only_on_ac_power
+            .store(settings.only_on_ac_power, Ordering::Relaxed);
+    }
+
+    pub fn settings(&self) -> ScanThrottleSettings {
+        ScanThrottleSettings {
+            max_entries_per_sec: self.max_entries_per_sec.load(Ordering::Relaxed),
+            only_on_ac_power: self.only_on_ac_power.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// The user's throttle (`indexing.maxEntriesPerSec` / `indexing.onlyOnAcPower`), which the
+/// manager hands to every fresh local scan.
+static USER_THROTTLE: LazyLock<Arc<ScanThrottle>> = LazyLock::new(|| Arc::new(ScanThrottle::default()));
+
+pub fn user_scan_throttle() -> Arc<ScanThrottle> {
+    Arc::clone(&USER_THROTTLE)
+}
+
+/// Sets the user's throttle. Running scans pick it up on their next directory.
+pub fn set_user_scan_throttle(settings: ScanThrottleSettings) {
+    USER_THROTTLE.set(settings);
+    log::info!(
+        "Scan throttle: {}, {}",
+        match settings.max_entries_per_sec {
+            0 => "no rate limit".to_string(),
+            rate => format!("at most {rate} entries/s"),
+        },
+        if settings.only_on_ac_power {
+            "only on AC power"
+        } else {
+            "on any power source"
+        },
+    );
+}
+
+/// Entries counted since `started`, against the limit that was in force then.
+struct RateWindow {
+    started: Instant,
+    entries: u64,
+    limit: u32,
+}
+
+/// The last power-source reading, so the AC-only mode doesn't query it per directory.
+struct PowerReading {
+    at: Instant,
+    on_battery: bool,
+}
+
+/// One scan's pacing state. Shared by the walker's worker threads, so the rate is the
+/// scan's total, not per worker.
+pub(super) struct Pacer {
+    throttle: Arc<ScanThrottle>,
+    /// The scan's pause flag (`ScanProgress::paused`).
+    paused: Arc<AtomicBool>,
+    on_battery: fn() -> bool,
+    window: Mutex<RateWindow>,
+    power: Mutex<Option<PowerReading>>,
+}
+
+impl Pacer {
+    pub(super) fn new(throttle: Arc<ScanThrottle>, paused: Arc<AtomicBool>) -> Self {
+        Self::with_power_source(throttle, paused, crate::power_source::on_battery)
+    }
+
+    fn with_power_source(throttle: Arc<ScanThrottle>, paused: Arc<AtomicBool>, on_battery: fn() -> bool) -> Self {
+        Self {
+            throttle,
+            paused,
+            on_battery,
+            window: Mutex::new(RateWindow {
+                started: Instant::now(),
+                entries: 0,
+                limit: 0,
+            }),
+            power: Mutex::new(None),
+        }
+    }
+
+    /// Counts `entries` just inserted, then blocks while the scan is paused, ahead of its rate,
+    /// or (in AC-only mode) on battery. Returns `true` if the throttle held the scan back; a
+    /// user pause alone doesn't count. Returns early once `cancelled` is set.
+    pub(super) fn pace(&self, entries: u64, cancelled: &AtomicBool) -> bool {
+        let was_paused = self.wait_while_paused(cancelled);
+        let settings = self.throttle.settings();
+        if settings.max_entries_per_sec == 0 && !settings.only_on_ac_power {
+            return false;
+        }
+        let mut held_back = false;
+        while settings.only_on_ac_power && self.on_battery() && !cancelled.load(Ordering::Relaxed) {
+            held_back = true;
+            std::thread::sleep(SLEEP_SLICE);
+            if !self.throttle.settings().only_on_ac_power {
+                break;
+            }
+        }
+        if held_back || was_paused {
+            // Don't let a long pause turn into a burst of banked credit.
+            self.restart_window();
+        }
+
+        let mut ahead = self.count(entries);
+        while !ahead.is_zero() && !cancelled.load(Ordering::Relaxed) {
+            held_back = true;
+            let nap = ahead.min(SLEEP_SLICE);
+            std::thread::sleep(nap);
+            ahead = ahead.saturating_sub(nap);
+        }
+        held_back
+    }
+
+    /// Blocks while the scan is paused. Returns whether it waited at all.
+    fn wait_while_paused(&self, cancelled: &AtomicBool) -> bool {
+        let mut waited = false;
+        while self.paused.load(Ordering::Relaxed) && !cancelled.load(Ordering::Relaxed) {
+            waited = true;
+            std::thread::sleep(SLEEP_SLICE);
+        }
+        waited
+    }
+
+    /// Adds `entries` to the window and returns how far the scan is ahead of the current limit.
+    fn count(&self, entries: u64) -> Duration {
+        let limit = self.throttle.settings().max_entries_per_sec;
+        let mut window = self.window.lock_ignore_poison();
+        let elapsed = window.started.elapsed();
+        if limit != window.limit || elapsed > RATE_WINDOW {
+            *window = RateWindow {
+                started: Instant::now(),
+                entries: 0,
+                limit,
+            };
+        }
+        window.entries += entries;
+        if limit == 0 {
+            return Duration::ZERO;
+        }
+        let due = Duration::from_secs_f64(window.entries as f64 / f64::from(limit));
+        due.saturating_sub(window.started.elapsed())
+    }
+
+    fn restart_window(&self) {
+        let mut window = self.window.lock_ignore_poison();
+        window.started = Instant::now();
+        window.entries = 0;
+    }
+
+    fn on_battery(&self) -> bool {
+        let mut power = self.power.lock_ignore_poison();
+        match &*power {
+            Some(reading) if reading.at.elapsed() < POWER_CHECK_INTERVAL => reading.on_battery,
+            _ => {
+                let on_battery = (self.on_battery)();
+                *power = Some(PowerReading {
+                    at: Instant::now(),
+                    on_battery,
+                });
+                on_battery
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pacer(max_entries_per_sec: u32, only_on_ac_power: bool, on_battery: fn() -> bool) -> Pacer {
+        let throttle = Arc::new(ScanThrottle::default());
+        throttle.set(ScanThrottleSettings {
+            max_entries_per_sec,
+            only_on_ac_power,
+        });
+        Pacer::with_power_source(throttle, Arc::new(AtomicBool::new(false)), on_battery)
+    }
+
+    #[test]
+    fn paused_scan_waits_until_resumed() {
+        let paused = Arc::new(AtomicBool::new(true));
+        let pacer = Pacer::with_power_source(Arc::new(ScanThrottle::default()), Arc::clone(&paused), || false);
+        let resumer = {
+            let paused = Arc::clone(&paused);
+            std::thread::spawn(move || {
+                std::thread::sleep(Duration::from_millis(150));
+                paused.store(false, Ordering::Relaxed);
+            })
+        };
+        let start = Instant::now();
+        // A pause isn't the throttle holding back.
+        assert!(!pacer.pace(1, &AtomicBool::new(false)));
+        assert!(start.elapsed() >= Duration::from_millis(100));
+        resumer.join().expect("resumer");
+    }
+
+    #[test]
+    fn unthrottled_scan_never_waits() {
+        let pacer = pacer(0, false, || true);
+        let start = Instant::now();
+        assert!(!pacer.pace(1_000_000, &AtomicBool::new(false)));
+        assert!(start.elapsed() < Duration::from_millis(50));
+    }
+
+    #[test]
+    fn rate_limit_holds_the_scan_to_the_cap() {
+        let pacer = pacer(1000, false, || false);
+        let cancelled = AtomicBool::new(false);
+        let start = Instant::now();
+        pacer.pace(10, &cancelled);
+        // 300 entries at 1000/s are due at 0.3 s.
+        assert!(pacer.pace(290, &cancelled));
+        assert!(start.elapsed() >= Duration::from_millis(250));
+    }
+
+    #[test]
+    fn ac_only_mode_pauses_on_battery_until_cancelled() {
+        let on_battery = pacer(0, true, || true);
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let canceller = {
+            let cancelled = Arc::clone(&cancelled);
+            std::thread::spawn(move || {
+                std::thread::sleep(Duration::from_millis(150));
+                cancelled.store(true, Ordering::Relaxed);
+            })
+        };
+        assert!(on_battery.pace(1, &cancelled));
+        canceller.join().expect("canceller");
+
+        let on_ac = pacer(0, true, || false);
+        assert!(!on_ac.pace(1, &AtomicBool::new(false)));
+    }
+}
@@ -49,6 +49,62 @@ impl IndexStore {
         Ok(count.max(0) as usize)
     }
 
+    /// Counts a directory's immediate children as `(files, directories)`, optionally skipping
+    /// dotfiles. Symlinks count as files (a link to a folder included), matching what a
+    /// `read_dir` file-type count sees. Reads the `parent_id` index only, no row payloads.
+    pub fn child_counts_by_id(
+        conn: &Connection,
+        parent_id: i64,
+        include_hidden: bool,
+    ) -> Result<(u64, u64), IndexStoreError> {
+        let mut stmt = conn.prepare_cached(
+            "SELECT
+                COALESCE(SUM(CASE WHEN is_directory = 1 AND is_symlink = 0 THEN 0 ELSE 1 END), 0),
+                COALESCE(SUM(CASE WHEN is_directory = 1 AND is_symlink = 0 THEN 1 ELSE 0 END), 0)
+             FROM entries WHERE parent_id = ?1 AND (?2 OR substr(name, 1, 1) <> '.')",
+        )?;
+        let counts = stmt.query_row(params![parent_id, include_hidden], |row| {
+            Ok((row.get::<_, u64>(0)?, row.get::<_, u64>(1)?))
+        })?;
+        Ok(counts)
+    }
+
+    /// A directory's real subdirectories (symlinks excluded), each with the names of its own
+    /// real subdirectories, in one self-join over the `parent_id` index. Feeds the sidebar
+    /// tree, which needs one level plus a "has children" flag per node, never file rows.
+    pub fn tree_children_by_id(conn: &Connection, parent_id: i64) -> Result<Vec<TreeChildRow>, IndexStoreError> {
+        let mut stmt = conn.prepare_cached(
+            "SELECT c.id, c.name, g.name
+             FROM entries c
+             LEFT JOIN entries g ON g.parent_id = c.id AND g.is_directory = 1 AND g.is_symlink = 0
+             WHERE c.parent_id = ?1 AND c.is_directory = 1 AND c.is_symlink = 0
+             ORDER BY c.id",
+        )?;
+        let rows = stmt.query_map(params![parent_id], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, Option<String>>(2)?,
+            ))
+        })?;
+        let mut children: Vec<TreeChildRow> = Vec::new();
+        let mut last_id = None;
+        for row in rows {
+            let (id, name, subdir) = row?;
+            if last_id != Some(id) {
+                last_id = Some(id);
+                children.push(TreeChildRow {
+                    name,
+                    subdir_names: Vec::new(),
+                });
+            }
+            if let (Some(subdir), Some(child)) = (subdir, children.last_mut()) {
+                child.subdir_names.push(subdir);
+            }
+        }
+        Ok(children)
+    }
+
     /// List up to `limit` children of a directory. The operation log's search-leaf
     /// enumeration (`journal_search`) walks a subtree BEFORE a trash / same-FS move
     /// with a bounded budget, so it reads at most `cap + 1` rows total regardless of
@@ -80,6 +136,177 @@ impl IndexStore {
         rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
     }
 
+    /// Per-extension file totals for the whole subtree under `root_id`, in one grouped query.
+    ///
+    /// Returns `(extension, file_count, logical_bytes)` rows. The extension is the text after
+    /// the last dot, case as stored (callers fold case in Rust: SQLite's `lower()` is ASCII-only).
+    /// A name with no dot, a dotfile with no further dot (`.bashrc`), or a trailing dot yields `""`.
+    /// Descends through real directories only, and counts neither directories nor symlinks, so
+    /// the totals match what a `follow_links(false)` disk walk would see.
+    pub fn extension_totals_by_id(conn: &Connection, root_id: i64) -> Result<Vec<(String, u64, u64)>, IndexStoreError> {
+        // `rtrim(name, <name without dots>)` strips back to the last dot, so its length is the
+        // index of the extension's first character.
+        let mut stmt = conn.prepare_cached(
+            "WITH RECURSIVE dirs(id) AS (
+                SELECT ?1
+                UNION ALL
+                SELECT e.id FROM entries e JOIN dirs d ON e.parent_id = d.id
+                WHERE e.is_directory = 1 AND e.is_symlink = 0
+            )
+            SELECT ext, COUNT(*), COALESCE(SUM(size), 0) FROM (
+                SELECT
+                    CASE WHEN instr(substr(f.name, 2), '.') = 0 THEN ''
+                         ELSE substr(f.name, length(rtrim(f.name, replace(f.name, '.', ''))) + 1)
+                    END AS ext,
+                    f.logical_size AS size
+                FROM entries f JOIN dirs d ON f.parent_id = d.id
+                WHERE f.is_directory = 0 AND f.is_symlink = 0
+            )
+            GROUP BY ext",
+        )?;
+        let rows = stmt.query_map(params![root_id], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, u64>(1)?, row.get::<_, u64>(2)?))
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// Files under `root_id` (whole subtree) of at least `min_size` bytes whose size is shared by
+    /// another such file: the size-grouping pass of duplicate detection, done in SQL so unique
+    /// sizes never leave the database.
+    ///
+    /// Returns `(relative_path, size, inode)` rows; `relative_path` is `/`-joined from `root_id`
+    /// with no leading slash. Like [`Self::extension_totals_by_id`], descends through real
+    /// directories only and skips symlinks.
+    pub fn files_sharing_a_size_by_id(
+        conn: &Connection,
+        root_id: i64,
+        min_size: u64,
+    ) -> Result<Vec<(String, u64, Option<u64>)>, IndexStoreError> {
+        let mut stmt = conn.prepare_cached(
+            "WITH RECURSIVE dirs(id, rel) AS (
+                SELECT ?1, ''
+                UNION ALL
+                SELECT e.id, d.rel || e.name || '/' FROM entries e JOIN dirs d ON e.parent_id = d.id
+                WHERE e.is_directory = 1 AND e.is_symlink = 0
+            )
+            SELECT rel, size, inode FROM (
+                SELECT d.rel || f.name AS rel, f.logical_size AS size, f.inode AS inode,
+                       COUNT(*) OVER (PARTITION BY f.logical_size) AS same_size
+                FROM entries f JOIN dirs d ON f.parent_id = d.id
+                WHERE f.is_directory = 0 AND f.is_symlink = 0 AND f.logical_size >= ?2
+            )
+            WHERE same_size > 1",
+        )?;
+        let rows = stmt.query_map(params![root_id, min_size], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, u64>(1)?,
+                row.get::<_, Option<u64>>(2)?,
+            ))
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// The `limit` largest files under `root_id` (whole subtree), biggest first, in one query.
+    ///
+    /// Returns `(relative_path, logical_size)` rows, `/`-joined with no leading slash. Like
+    /// [`Self::extension_totals_by_id`], descends through real directories only and skips
+    /// symlinks. Ties break by path so the order is stable.
+    pub fn largest_files_by_id(
+        conn: &Connection,
+        root_id: i64,
+        limit: u32,
+    ) -> Result<Vec<(String, u64)>, IndexStoreError> {
+        let mut stmt = conn.prepare_cached(
+            "WITH RECURSIVE dirs(id, rel) AS (
+                SELECT ?1, ''
+                UNION ALL
+                SELECT e.id, d.rel || e.name || '/' FROM entries e JOIN dirs d ON e.parent_id = d.id
+                WHERE e.is_directory = 1 AND e.is_symlink = 0
+            )
+            SELECT d.rel || f.name AS rel, f.logical_size AS size
+            FROM entries f JOIN dirs d ON f.parent_id = d.id
+            WHERE f.is_directory = 0 AND f.is_symlink = 0 AND f.logical_size IS NOT NULL
+            ORDER BY size DESC, rel
+            LIMIT ?2",
+        )?;
+        let rows = stmt.query_map(params![root_id, limit], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, u64>(1)?))
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// Ids of every real (non-symlink) directory whose name passes `keep`, in one table scan.
+    /// For a one-off sweep (the exclusion prune), not a hot path.
+    pub fn directory_ids_where(
+        conn: &Connection,
+        mut keep: impl FnMut(&str) -> bool,
+    ) -> Result<Vec<i64>, IndexStoreError> {
+        let mut stmt = conn.prepare("SELECT id, name FROM entries WHERE is_directory = 1 AND is_symlink = 0")?;
+        let mut rows = stmt.query([])?;
+        let mut ids = Vec::new();
+        while let Some(row) = rows.next()? {
+            let name: String = row.get(1)?;
+            if keep(&name) {
+                ids.push(row.get(0)?);
+            }
+        }
+        Ok(ids)
+    }
+
+    /// Recursive physical size of `root_id` and of every real directory below it down to
+    /// `max_depth` levels (0 = just the root), in one query.
+    ///
+    /// Returns `(relative_path, recursive_physical_size)` rows; the root is `""`, others are
+    /// `/`-joined with no leading slash. A directory with no `dir_stats` row yet reports 0.
+    pub fn subdir_sizes_by_id(
+        conn: &Connection,
+        root_id: i64,
+        max_depth: u32,
+    ) -> Result<Vec<(String, u64)>, IndexStoreError> {
+        let mut stmt = conn.prepare_cached(
+            "WITH RECURSIVE dirs(id, rel, depth) AS (
+                SELECT ?1, '', 0
+                UNION ALL
+                SELECT e.id, CASE WHEN d.rel = '' THEN e.name ELSE d.rel || '/' || e.name END, d.depth + 1
+                FROM entries e JOIN dirs d ON e.parent_id = d.id
+                WHERE e.is_directory = 1 AND e.is_symlink = 0 AND d.depth < ?2
+            )
+            SELECT d.rel, COALESCE(s.recursive_physical_size, 0)
+            FROM dirs d LEFT JOIN dir_stats s ON s.entry_id = d.id",
+        )?;
+        let rows = stmt.query_map(params![root_id, max_depth], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, u64>(1)?))
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// Every entry below `root_id` (whole subtree, the root itself not included), in one query.
+    /// Like [`Self::extension_totals_by_id`], descends through real directories only; symlinks
+    /// come back as entries but aren't followed. Unordered.
+    pub fn subtree_entries_by_id(conn: &Connection, root_id: i64) -> Result<Vec<SubtreeEntry>, IndexStoreError> {
+        let mut stmt = conn.prepare_cached(
+            "WITH RECURSIVE dirs(id, rel) AS (
+                SELECT ?1, ''
+                UNION ALL
+                SELECT e.id, d.rel || e.name || '/' FROM entries e JOIN dirs d ON e.parent_id = d.id
+                WHERE e.is_directory = 1 AND e.is_symlink = 0
+            )
+            SELECT d.rel || f.name, f.is_directory, f.is_symlink, f.logical_size, f.modified_at
+            FROM entries f JOIN dirs d ON f.parent_id = d.id",
+        )?;
+        let rows = stmt.query_map(params![root_id], |row| {
+            Ok(SubtreeEntry {
+                relative_path: row.get(0)?,
+                is_directory: row.get(1)?,
+                is_symlink: row.get(2)?,
+                logical_size: row.get(3)?,
+                modified_at: row.get(4)?,
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
     /// Read every entry in the index in one query.
     ///
     /// Lets a full-index consumer (the importance recompute) pull the whole tree
@@ -117,6 +117,26 @@ pub struct EntryRow {
     pub inode: Option<u64>,
 }
 
+/// One entry below a subtree root, as [`IndexStore::subtree_entries_by_id`] returns it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubtreeEntry {
+    /// `/`-joined from the subtree root, no leading slash.
+    pub relative_path: String,
+    pub is_directory: bool,
+    pub is_symlink: bool,
+    pub logical_size: Option<u64>,
+    pub modified_at: Option<u64>,
+}
+
+/// A real subdirectory of a folder with its own subdirectories' names, as
+/// [`IndexStore::tree_children_by_id`] returns it. The names let the caller decide
+/// "has children" under its own hidden / exclude rules.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TreeChildRow {
+    pub name: String,
+    pub subdir_names: Vec<String>,
+}
+
 /// Resolve the entry id to use as a scan's root, seeding the `ROOT` sentinel for
 /// a volume-root scan.
 ///
@@ -397,6 +397,202 @@ fn insert_and_list_entries() {
     assert!(dir.is_directory);
 }
 
+/// The grouped extension query covers the whole subtree, splits on the LAST dot, buckets
+/// dotfiles / dotless / trailing-dot names under `""`, and skips dirs, symlinks, and anything
+/// outside the root.
+#[test]
+fn extension_totals_group_the_subtree_by_last_extension() {
+    let (store, _dir) = open_temp_store();
+    let conn = IndexStore::open_write_connection(store.db_path()).unwrap();
+    let photos = insert_entry(&conn, ROOT_ID, "photos", true, None);
+    let nested = insert_entry(&conn, photos, "2024.trip", true, None);
+    insert_entry(&conn, photos, "a.jpg", false, Some(100));
+    insert_entry(&conn, nested, "b.JPG", false, Some(50));
+    insert_entry(&conn, nested, "archive.tar.gz", false, Some(7));
+    insert_entry(&conn, photos, ".bashrc", false, Some(1));
+    insert_entry(&conn, photos, "Makefile", false, Some(2));
+    insert_entry(&conn, photos, "odd.", false, None);
+    IndexStore::insert_entry_v2(&conn, photos, "link.jpg", false, true, Some(9), Some(9), None, None).unwrap();
+    insert_entry(&conn, ROOT_ID, "outside.jpg", false, Some(1000));
+
+    let mut totals = IndexStore::extension_totals_by_id(&conn, photos).unwrap();
+    totals.sort();
+
+    assert_eq!(
+        totals,
+        vec![
+            (String::new(), 3, 3),
+            ("JPG".to_string(), 1, 50),
+            ("gz".to_string(), 1, 7),
+            ("jpg".to_string(), 1, 100),
+        ]
+    );
+}
+
+/// Child counts split real folders from everything else (symlinks count as files), stay one
+/// level deep, and skip dotfiles only when asked.
+#[test]
+fn child_counts_split_folders_from_files_one_level_deep() {
+    let (store, _dir) = open_temp_store();
+    let conn = IndexStore::open_write_connection(store.db_path()).unwrap();
+    let root = insert_entry(&conn, ROOT_ID, "root", true, None);
+    let sub = insert_entry(&conn, root, "sub", true, None);
+    insert_entry(&conn, root, ".git", true, None);
+    insert_entry(&conn, root, "a.txt", false, Some(1));
+    insert_entry(&conn, root, ".env", false, Some(1));
+    insert_entry(&conn, sub, "deep.txt", false, Some(1));
+    IndexStore::insert_entry_v2(&conn, root, "link", true, true, None, None, None, None).unwrap();
+
+    assert_eq!(IndexStore::child_counts_by_id(&conn, root, true).unwrap(), (3, 2));
+    assert_eq!(IndexStore::child_counts_by_id(&conn, root, false).unwrap(), (2, 1));
+    assert_eq!(IndexStore::child_counts_by_id(&conn, sub, true).unwrap(), (1, 0));
+}
+
+/// Tree children are real folders only, each carrying its own real subfolders' names.
+#[test]
+fn tree_children_list_folders_with_their_subfolder_names() {
+    let (store, _dir) = open_temp_store();
+    let conn = IndexStore::open_write_connection(store.db_path()).unwrap();
+    let root = insert_entry(&conn, ROOT_ID, "root", true, None);
+    let src = insert_entry(&conn, root, "src", true, None);
+    insert_entry(&conn, src, "lib", true, None);
+    insert_entry(&conn, src, ".cache", true, None);
+    insert_entry(&conn, src, "main.rs", false, Some(1));
+    let docs = insert_entry(&conn, root, "docs", true, None);
+    insert_entry(&conn, docs, "readme.md", false, Some(1));
+    insert_entry(&conn, root, "a.txt", false, Some(1));
+    IndexStore::insert_entry_v2(&conn, root, "link", true, true, None, None, None, None).unwrap();
+
+    let mut children = IndexStore::tree_children_by_id(&conn, root).unwrap();
+    children.sort_by(|a, b| a.name.cmp(&b.name));
+    for child in &mut children {
+        child.subdir_names.sort();
+    }
+    assert_eq!(
+        children,
+        vec![
+            TreeChildRow {
+                name: "docs".into(),
+                subdir_names: vec![],
+            },
+            TreeChildRow {
+                name: "src".into(),
+                subdir_names: vec![".cache".into(), "lib".into()],
+            },
+        ]
+    );
+}
+
+/// Only sizes shared by two or more files at or above the threshold come back, with paths
+/// relative to the queried root.
+#[test]
+fn files_sharing_a_size_skip_unique_and_small_sizes() {
+    let (store, _dir) = open_temp_store();
+    let conn = IndexStore::open_write_connection(store.db_path()).unwrap();
+    let root = insert_entry(&conn, ROOT_ID, "root", true, None);
+    let sub = insert_entry(&conn, root, "sub", true, None);
+    insert_entry(&conn, root, "a.bin", false, Some(500));
+    insert_entry(&conn, sub, "b.bin", false, Some(500));
+    insert_entry(&conn, root, "unique.bin", false, Some(501));
+    insert_entry(&conn, root, "tiny1", false, Some(3));
+    insert_entry(&conn, sub, "tiny2", false, Some(3));
+
+    let mut rows = IndexStore::files_sharing_a_size_by_id(&conn, root, 10).unwrap();
+    rows.sort();
+
+    assert_eq!(
+        rows,
+        vec![("a.bin".to_string(), 500, None), ("sub/b.bin".to_string(), 500, None)]
+    );
+}
+
+/// The largest files come back biggest first and capped at the limit, with paths relative to
+/// the queried root and nothing from outside the subtree.
+#[test]
+fn largest_files_are_ordered_and_capped() {
+    let (store, _dir) = open_temp_store();
+    let conn = IndexStore::open_write_connection(store.db_path()).unwrap();
+    let root = insert_entry(&conn, ROOT_ID, "root", true, None);
+    let sub = insert_entry(&conn, root, "sub", true, None);
+    insert_entry(&conn, root, "small.bin", false, Some(10));
+    insert_entry(&conn, sub, "huge.bin", false, Some(9_000));
+    insert_entry(&conn, root, "mid.bin", false, Some(500));
+    insert_entry(&conn, ROOT_ID, "outside.bin", false, Some(99_999));
+
+    let rows = IndexStore::largest_files_by_id(&conn, root, 2).unwrap();
+
+    assert_eq!(
+        rows,
+        vec![("sub/huge.bin".to_string(), 9_000), ("mid.bin".to_string(), 500)]
+    );
+}
+
+/// Only real directories are offered to the filter; files and symlinked dirs never are.
+#[test]
+fn directory_ids_where_filters_real_directories_by_name() {
+    let (store, _dir) = open_temp_store();
+    let conn = IndexStore::open_write_connection(store.db_path()).unwrap();
+    let app = insert_entry(&conn, ROOT_ID, "app", true, None);
+    let modules = insert_entry(&conn, app, "node_modules", true, None);
+    insert_entry(&conn, app, "node_modules.txt", false, Some(1));
+    IndexStore::insert_entry_v2(&conn, ROOT_ID, "node_modules", true, true, None, None, None, None).unwrap();
+
+    let ids = IndexStore::directory_ids_where(&conn, |name| name.starts_with("node_modules")).unwrap();
+
+    assert_eq!(ids, vec![modules]);
+}
+
+/// Subtree entries come back with paths relative to the queried root, symlinked dirs not
+/// followed, and nothing from outside the subtree.
+#[test]
+fn subtree_entries_list_the_whole_subtree_relative_to_the_root() {
+    let (store, _dir) = open_temp_store();
+    let conn = IndexStore::open_write_connection(store.db_path()).unwrap();
+    let root = insert_entry(&conn, ROOT_ID, "root", true, None);
+    let sub = insert_entry(&conn, root, "sub", true, None);
+    insert_entry(&conn, sub, "deep.txt", false, Some(7));
+    let link = IndexStore::insert_entry_v2(&conn, root, "link", true, true, None, None, None, None).unwrap();
+    insert_entry(&conn, link, "through-link.txt", false, Some(1));
+    insert_entry(&conn, ROOT_ID, "outside.txt", false, Some(1));
+
+    let mut paths: Vec<String> = IndexStore::subtree_entries_by_id(&conn, root)
+        .unwrap()
+        .into_iter()
+        .map(|e| e.relative_path)
+        .collect();
+    paths.sort();
+
+    assert_eq!(paths, vec!["link", "sub", "sub/deep.txt"]);
+}
+
+/// Subdirectory sizes stop at the requested depth and fall back to 0 for dirs with no stats.
+#[test]
+fn subdir_sizes_stop_at_max_depth() {
+    let (store, _dir) = open_temp_store();
+    let conn = IndexStore::open_write_connection(store.db_path()).unwrap();
+    let root = insert_entry(&conn, ROOT_ID, "root", true, None);
+    let a = insert_entry(&conn, root, "a", true, None);
+    let deep = insert_entry(&conn, a, "deep", true, None);
+    insert_entry(&conn, root, "b", true, None);
+    insert_entry(&conn, root, "file.bin", false, Some(5));
+    let stats = |entry_id, size| DirStatsById {
+        entry_id,
+        recursive_physical_size: size,
+        ..Default::default()
+    };
+    IndexStore::upsert_dir_stats_by_id(&conn, &[stats(root, 300), stats(a, 200), stats(deep, 150)]).unwrap();
+
+    let mut depth_one = IndexStore::subdir_sizes_by_id(&conn, root, 1).unwrap();
+    depth_one.sort();
+    assert_eq!(
+        depth_one,
+        vec![(String::new(), 300), ("a".to_string(), 200), ("b".to_string(), 0)]
+    );
+
+    let depth_two = IndexStore::subdir_sizes_by_id(&conn, root, 2).unwrap();
+    assert!(depth_two.contains(&("a/deep".to_string(), 150)));
+}
+
 #[test]
 fn dir_stats_roundtrip() {
     let (_store, dir) = open_temp_store();
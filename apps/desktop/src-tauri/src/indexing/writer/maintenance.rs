@@ -4,12 +4,21 @@
 //! checkpoint truncates the WAL file once readers permit. Both are fired by a
 //! background timer (and the WAL checkpoint also right after a full scan); they
 //! mutate no `entries` rows, so they don't bump the writer generation.
+//!
+//! A full `VACUUM` ([`handle_compact`]) also rebuilds the file, defragmenting
+//! what incremental vacuum only truncates. It runs when the user asks
+//! (`compact_index`), or from a slower idle timer when the freelist has grown
+//! past [`FULL_VACUUM_FREE_RATIO`]. Being a writer message is what keeps it safe:
+//! it can't interleave with a batch, and it refuses to start inside an open
+//! transaction (SQLite can't VACUUM there anyway).
 
 use std::cell::Cell;
 use std::time::{Duration, Instant};
 
+use serde::{Deserialize, Serialize};
+
 use crate::indexing::IndexFailureSignal;
-use crate::indexing::store::IndexStoreError;
+use crate::indexing::store::{IndexStore, IndexStoreError};
 use crate::pluralize::pluralize;
 
 // ── Busy-handler checkpoint suppression ──────────────────────────────
@@ -272,6 +281,79 @@ fn handle_wal_checkpoint(conn: &rusqlite::Connection, signal: &IndexFailureSigna
     }
 }
 
+/// Freelist share of the file above which the idle check runs a full VACUUM.
+/// Incremental vacuum keeps the freelist small in steady state, so this mostly
+/// catches a post-rescan backlog it would otherwise drain over hours.
+const FULL_VACUUM_FREE_RATIO: f64 = 0.25;
+
+/// Below this many pages (~40 MB at 4 KiB) a full VACUUM isn't worth the writer
+/// stall: incremental vacuum gets there soon enough.
+const FULL_VACUUM_MIN_PAGES: u64 = 10_000;
+
+/// Index DB size around a full compaction, in bytes of the main file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct CompactStats {
+    pub bytes_before: u64,
+    pub bytes_after: u64,
+}
+
+/// Whether the idle check should VACUUM a file of `page_count` pages with
+/// `freelist` of them free. Pure so the policy is testable.
+fn wants_full_vacuum(page_count: u64, freelist: u64) -> bool {
+    page_count >= FULL_VACUUM_MIN_PAGES && freelist as f64 >= page_count as f64 * FULL_VACUUM_FREE_RATIO
+}
+
+fn db_bytes(conn: &rusqlite::Connection) -> Result<(u64, u64, u64), IndexStoreError> {
+    let page_size: u64 = conn.pragma_query_value(None, "page_size", |r| r.get(0))?;
+    let (page_count, freelist) = IndexStore::db_page_stats(conn)?;
+    Ok((page_count * page_size, page_count, freelist))
+}
+
+/// Full `VACUUM`, then a WAL TRUNCATE so the rewritten pages don't sit in the
+/// WAL. `forced` (a user request) always vacuums and errors inside an open
+/// transaction; the idle check (`!forced`) vacuums only past the freelist
+/// threshold and quietly skips while a transaction is open. Returns `None`
+/// when skipped.
+pub(super) fn handle_compact(
+    conn: &rusqlite::Connection,
+    signal: &IndexFailureSignal,
+    forced: bool,
+) -> Result<Option<CompactStats>, IndexStoreError> {
+    if !conn.is_autocommit() {
+        if !forced {
+            log::debug!("Writer: idle vacuum skipped, a transaction is open");
+            return Ok(None);
+        }
+        return Err(IndexStoreError::Io(std::io::Error::other(
+            "the index is in the middle of a batch update; try again when it's done",
+        )));
+    }
+    let (bytes_before, page_count, freelist) = db_bytes(conn)?;
+    if !forced && !wants_full_vacuum(page_count, freelist) {
+        return Ok(None);
+    }
+
+    let started = Instant::now();
+    if let Err(e) = conn.execute_batch("VACUUM") {
+        let e = IndexStoreError::from(e);
+        signal.note(&e, "vacuum");
+        return Err(e);
+    }
+    handle_wal_checkpoint(conn, signal);
+    let (bytes_after, _, _) = db_bytes(conn)?;
+    log::info!(
+        "Writer: VACUUM{} took {:?}, {bytes_before} -> {bytes_after} bytes ({} were free)",
+        if forced { "" } else { " (idle, fragmented)" },
+        started.elapsed(),
+        pluralize(freelist, "page"),
+    );
+    Ok(Some(CompactStats {
+        bytes_before,
+        bytes_after,
+    }))
+}
+
 // ── Tests ────────────────────────────────────────────────────────────
 
 #[cfg(test)]
@@ -524,6 +606,53 @@ mod tests {
         writer.shutdown();
     }
 
+    #[test]
+    fn idle_vacuum_needs_a_big_fragmented_file() {
+        assert!(
+            !wants_full_vacuum(5_000, 4_000),
+            "small files are left to incremental vacuum"
+        );
+        assert!(!wants_full_vacuum(100_000, 10_000), "10% free isn't worth a rebuild");
+        assert!(wants_full_vacuum(100_000, 25_000));
+    }
+
+    /// A forced `Compact` rebuilds the file after a mass delete, and reports the shrink.
+    #[test]
+    fn compact_shrinks_the_db_after_a_mass_delete() {
+        let (db_path, _dir) = setup_db();
+        let writer = IndexWriter::spawn(&db_path, None).unwrap();
+        let entries: Vec<EntryRow> = (0..5000)
+            .map(|i| EntryRow {
+                id: 200 + i,
+                parent_id: ROOT_ID,
+                name: format!("compact-test-entry-with-a-long-name-{i:08}"),
+                is_directory: false,
+                is_symlink: false,
+                logical_size: Some(1024),
+                physical_size: Some(1024),
+                modified_at: None,
+                inode: None,
+            })
+            .collect();
+        writer.send(WriteMessage::InsertEntriesV2(entries)).unwrap();
+        writer.send(WriteMessage::DeleteDescendantsById(ROOT_ID)).unwrap();
+
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        writer.send(WriteMessage::Compact { reply: Some(tx) }).unwrap();
+        let stats = rx
+            .blocking_recv()
+            .unwrap()
+            .unwrap()
+            .expect("a forced compaction always runs");
+
+        assert!(
+            stats.bytes_after < stats.bytes_before,
+            "VACUUM should drop the freed pages; {stats:?}"
+        );
+        assert_eq!(stats.bytes_after, std::fs::metadata(&db_path).unwrap().len());
+        writer.shutdown();
+    }
+
     /// End-to-end check: after inserts have grown the WAL, `WalCheckpoint`
     /// shrinks the on-disk WAL file. The WAL file is `db_path` + "-wal";
     /// after a successful TRUNCATE checkpoint with no readers, it should
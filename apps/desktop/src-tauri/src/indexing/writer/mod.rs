@@ -44,7 +44,8 @@ use entries::{
     handle_delete_entry_by_id, handle_delete_subtree_by_id, handle_insert_entries_v2, handle_move_entry_v2,
     handle_truncate_data, handle_upsert_entry_v2,
 };
-use maintenance::{handle_incremental_vacuum, request_wal_checkpoint, run_deferred_wal_checkpoint};
+pub use maintenance::CompactStats;
+use maintenance::{handle_compact, handle_incremental_vacuum, request_wal_checkpoint, run_deferred_wal_checkpoint};
 
 // ── Aggregation progress events ──────────────────────────────────────
 
@@ -429,6 +430,15 @@ pub enum WriteMessage {
     /// after a full scan's `ComputeAllAggregates` so the scan-time spike doesn't
     /// wait up to 30 s before being trimmed. Not counted in WriterStats.
     WalCheckpoint,
+    /// Full `VACUUM` plus a WAL TRUNCATE. With a reply it's a user request
+    /// (`compact_index`) and always runs; without one it's the idle timer's
+    /// check, which only vacuums a file whose freelist has grown past the
+    /// threshold. Runs between messages, so it never races a batch; inside an
+    /// open transaction the request errors and the check skips. Not counted in
+    /// WriterStats.
+    Compact {
+        reply: Option<oneshot::Sender<Result<Option<CompactStats>, IndexStoreError>>>,
+    },
     /// Emit `index-dir-updated` for the given paths. Enqueued after a batch
     /// of writes so the UI notification fires only after all prior messages
     /// (deletes, upserts, deltas) are committed.
@@ -716,6 +726,20 @@ impl IndexWriter {
         })
     }
 
+    /// Run a full `VACUUM` + WAL TRUNCATE once every message queued before it is done.
+    pub async fn compact(&self) -> Result<CompactStats, IndexStoreError> {
+        let (tx, rx) = oneshot::channel();
+        self.send(WriteMessage::Compact { reply: Some(tx) })?;
+        let stats = rx.await.map_err(|_| {
+            IndexStoreError::Io(std::io::Error::new(
+                std::io::ErrorKind::BrokenPipe,
+                "Writer thread dropped compact reply",
+            ))
+        })??;
+        // A forced compaction never skips, so `None` can't come back here.
+        stats.ok_or_else(|| IndexStoreError::Io(std::io::Error::other("Writer skipped a forced compaction")))
+    }
+
     /// Send a `Shutdown` message and wait for the writer thread to finish.
     ///
     /// Joins the thread to ensure all buffered writes are flushed.
@@ -1071,7 +1095,10 @@ fn writer_loop(
             Err(mpsc::RecvTimeoutError::Disconnected) => break,
         };
 
-        if !matches!(msg, WriteMessage::IncrementalVacuum | WriteMessage::WalCheckpoint) {
+        if !matches!(
+            msg,
+            WriteMessage::IncrementalVacuum | WriteMessage::WalCheckpoint | WriteMessage::Compact { .. }
+        ) {
             stats.record(&msg);
         }
 
@@ -1504,6 +1531,20 @@ fn process_message(
         WriteMessage::WalCheckpoint => {
             request_wal_checkpoint(conn, signal, deferred_checkpoint);
         }
+        WriteMessage::Compact { reply } => {
+            let forced = reply.is_some();
+            let result = handle_compact(conn, signal, forced);
+            match reply {
+                Some(reply) => {
+                    let _ = reply.send(result);
+                }
+                None => {
+                    if let Err(e) = result {
+                        log::debug!("Writer: idle vacuum check failed: {e}");
+                    }
+                }
+            }
+        }
         WriteMessage::EmitDirUpdated(paths) => {
             #[cfg(test)]
             mutation_tracker.record_emit(&paths);
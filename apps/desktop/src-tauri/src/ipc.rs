@@ -48,16 +48,23 @@
 use specta_typescript::Typescript;
 use tauri_specta::{Builder, collect_events};
 
+use crate::commands::file_actions::CloudMaterializeProgressEvent;
+use crate::commands::file_system::{
+    ChecksumProgressEvent, DirectoryCompareBatchEvent, DuplicateGroupFoundEvent, FolderSizeCompleteEvent,
+    FolderSizeProgressEvent,
+};
+use crate::commands::indexing::{ReindexCompleteEvent, ReindexProgressEvent};
 use crate::commands::search::SearchIndexReadyEvent;
 use crate::file_system::git::watcher::GitStateChangedPayload;
 use crate::file_system::listing::streaming::{
-    ListingCancelledEvent, ListingCompleteEvent, ListingErrorEvent, ListingOpeningEvent, ListingProgressEvent,
-    ListingReadCompleteEvent,
+    ListingCancelledEvent, ListingChunkEvent, ListingCompleteEvent, ListingErrorEvent, ListingOpeningEvent,
+    ListingProgressEvent, ListingReadCompleteEvent,
 };
 use crate::file_system::write_operations::{
     ConflictInfo, DryRunResult, ScanPreviewCancelledEvent, ScanPreviewCompleteEvent, ScanPreviewErrorEvent,
     ScanPreviewProgressEvent, ScanProgressEvent, WriteCancelledEvent, WriteCompleteEvent, WriteConflictEvent,
-    WriteErrorEvent, WriteProgressEvent, WriteSettledEvent, WriteSourceItemDoneEvent,
+    WriteErrorEvent, WritePausedEvent, WriteProgressEvent, WriteRenamedEvent, WriteSettledEvent,
+    WriteSourceItemDoneEvent,
 };
 use crate::file_system::write_operations::{OperationsChanged, VolumesBusyChanged};
 use crate::indexing::writer::AggregationProgressEvent;
@@ -65,19 +72,20 @@ use crate::indexing::{
     IndexAggregationCompleteEvent, IndexDirUpdatedEvent, IndexFreshnessChangedEvent, IndexMemoryWarningEvent,
     IndexPhaseChangedEvent, IndexReplayCompleteEvent, IndexReplayProgressEvent, IndexRescanNotificationEvent,
     IndexScanAbortedEvent, IndexScanCompleteEvent, IndexScanProgressEvent, IndexScanStartedEvent,
+    ScanPermissionWarningEvent,
 };
 use crate::ipc_collectors::collect_all_types;
 use crate::media_index::events::{MediaEnrichProgressEvent, MediaEnrichTerminalEvent};
 use crate::mtp::{
     MtpDeviceConnected, MtpDeviceDisconnected, MtpExclusiveAccessError, MtpPermissionError, MtpPtpcameradRestored,
-    MtpPtpcameradSuppressed, MtpStorageRemoved,
+    MtpPtpcameradSuppressed, MtpStorageMoveProgress, MtpStorageRemoved,
 };
 use crate::network::{
     NetworkDiscoveryStateChanged, NetworkHostContextAction, NetworkHostFound, NetworkHostLost, NetworkHostResolved,
     SmbConnectionChanged,
 };
 use crate::space_poller::{LowDiskSpacePayload, VolumeSpaceChanged};
-use crate::volume_broadcast::{VolumeContextAction, VolumeMounted, VolumeUnmounted, VolumesChanged};
+use crate::volume_broadcast::{VolumeContextAction, VolumeEjected, VolumeMounted, VolumeUnmounted, VolumesChanged};
 // Window-management events: emit_to-targeted window lifecycle.
 use crate::window_events::{
     CloseAbout, CloseAllFileViewers, CloseConfirmation, CloseFileViewer, ExecuteCommand, FocusAbout, FocusConfirmation,
@@ -86,7 +94,8 @@ use crate::window_events::{
 };
 // AI + system/misc events.
 use crate::ai::{
-    AiExtracting, AiInstallComplete, AiInstalling, AiServerReady, AiStarting, AiVerifying, DownloadProgress,
+    AiExtracting, AiInstallComplete, AiInstalling, AiIntegrityFailed, AiServerReady, AiStarting, AiVerifying,
+    DownloadProgress,
 };
 use crate::downloads::global_shortcut::GlobalShortcutFired;
 use crate::downloads::watcher::DownloadDetectedEvent;
@@ -119,7 +128,10 @@ pub fn builder() -> Builder<tauri::Wry> {
         greet,
         crate::commands::file_system::list_directory_start,
         crate::commands::file_system::list_directory_start_streaming,
+        crate::commands::file_system::list_directory_flat,
+        crate::commands::file_system::get_tree_children,
         crate::commands::file_system::cancel_listing,
+        crate::commands::file_system::prefetch_listing,
         crate::commands::file_system::list_directory_end,
         crate::commands::file_system::refresh_listing,
         crate::commands::file_system::get_file_range,
@@ -128,9 +140,26 @@ pub fn builder() -> Builder<tauri::Wry> {
         crate::commands::file_system::get_files_at_indices,
         crate::commands::file_system::get_total_count,
         crate::commands::file_system::get_brief_column_text_widths,
+        crate::commands::file_system::get_columns,
+        crate::commands::file_system::set_columns,
         crate::commands::file_system::find_file_index,
         crate::commands::file_system::find_file_indices,
         crate::commands::file_system::find_first_fuzzy_match,
+        crate::commands::file_system::find_next_match,
+        crate::commands::file_system::get_extension_breakdown,
+        crate::commands::file_system::get_largest_files,
+        crate::commands::file_system::get_dir_entry_count,
+        crate::commands::file_system::get_treemap_data,
+        crate::commands::file_system::find_duplicates,
+        crate::commands::file_system::cancel_find_duplicates,
+        crate::commands::file_system::compute_checksum,
+        crate::commands::file_system::cancel_checksum,
+        crate::commands::file_system::compute_tree_hash,
+        crate::commands::file_system::cancel_tree_hash,
+        crate::commands::file_system::get_file_info,
+        crate::commands::file_system::cancel_file_info,
+        crate::commands::file_system::start_folder_size,
+        crate::commands::file_system::cancel_folder_size,
         crate::commands::file_system::resort_listing,
         crate::commands::file_system::get_path_limits,
         crate::commands::file_system::enrich_tags,
@@ -139,24 +168,42 @@ pub fn builder() -> Builder<tauri::Wry> {
         crate::commands::file_system::stat_paths_kinds,
         crate::commands::file_system::create_directory,
         crate::commands::file_system::create_file,
+        crate::commands::file_system::create_symlink,
+        crate::commands::file_system::create_hardlink,
+        crate::commands::file_system::resolve_symlink,
+        crate::commands::file_system::get_permissions,
+        crate::commands::file_system::set_permissions,
         crate::commands::file_system::set_archive_password,
         crate::commands::file_system::clear_archive_password,
+        crate::commands::file_system::list_archive,
+        crate::commands::file_system::extract_archive,
+        crate::commands::file_system::compare_directories,
+        crate::commands::file_system::cancel_directory_compare,
+        crate::commands::file_system::diff_files,
+        crate::commands::file_system::cancel_file_diff,
         crate::commands::file_system::benchmark_log,
         crate::commands::file_system::copy_files,
         crate::commands::file_system::move_files,
+        crate::commands::file_system::get_recent_move_targets,
         crate::commands::file_system::delete_files,
         crate::commands::file_system::trash_files,
+        crate::commands::file_system::get_trash_info,
+        crate::commands::file_system::empty_trash,
         crate::commands::file_system::cancel_write_operation,
         crate::commands::file_system::cancel_all_write_operations,
         crate::commands::file_system::start_scan_preview,
         crate::commands::file_system::cancel_scan_preview,
         crate::commands::file_system::check_scan_preview_status,
         crate::commands::file_system::resolve_write_conflict,
+        crate::commands::file_system::set_default_conflict_policy,
+        crate::commands::file_system::list_default_conflict_policies,
+        crate::commands::file_system::clear_default_conflict_policies,
         crate::commands::file_system::list_active_operations,
         crate::commands::file_system::get_operation_status,
         crate::commands::file_system::list_operations,
         crate::commands::file_system::cancel_operation,
         crate::commands::file_system::cancel_operations,
+        crate::commands::file_system::move_operation,
         crate::commands::file_system::pause_operation,
         crate::commands::file_system::resume_operation,
         crate::commands::file_system::pause_all,
@@ -167,6 +214,7 @@ pub fn builder() -> Builder<tauri::Wry> {
         crate::commands::file_system::scan_volume_for_copy,
         crate::commands::file_system::scan_volume_for_conflicts,
         crate::commands::file_system::get_listing_stats,
+        crate::commands::file_system::get_listing_cache_stats,
         crate::commands::file_system::refresh_listing_index_sizes,
         crate::commands::file_system::start_selection_drag,
         crate::commands::file_system::start_drag_paths,
@@ -179,6 +227,8 @@ pub fn builder() -> Builder<tauri::Wry> {
         crate::commands::file_system::get_git_status_for_paths,
         crate::commands::rename::check_rename_permission,
         crate::commands::rename::check_rename_validity,
+        crate::commands::rename::preview_batch_rename_rule,
+        crate::commands::rename::batch_rename,
         crate::commands::rename::rename_file,
         crate::commands::rename::move_to_trash,
         crate::commands::restricted_paths::get_restricted_paths,
@@ -201,6 +251,7 @@ pub fn builder() -> Builder<tauri::Wry> {
         crate::commands::file_viewer::viewer_set_encoding,
         crate::commands::file_viewer::viewer_set_tail_mode,
         crate::commands::file_viewer::viewer_reload,
+        crate::commands::file_viewer::peek_file,
         crate::commands::font_metrics::store_font_metrics,
         crate::commands::font_metrics::has_font_metrics,
         crate::commands::icons::get_icons,
@@ -230,6 +281,8 @@ pub fn builder() -> Builder<tauri::Wry> {
         crate::commands::quick_look::quick_look_close,
         crate::commands::file_actions::get_info,
         crate::commands::file_actions::open_in_editor,
+        crate::commands::file_actions::get_applications_for,
+        crate::commands::file_actions::open_with,
         crate::commands::file_actions::open_path,
         #[cfg(feature = "playwright-e2e")]
         crate::commands::file_actions::e2e_opened_paths,
@@ -237,6 +290,9 @@ pub fn builder() -> Builder<tauri::Wry> {
         crate::commands::file_actions::e2e_clear_opened_paths,
         crate::commands::file_actions::cloud_make_available_offline,
         crate::commands::file_actions::cloud_remove_download,
+        crate::commands::file_actions::materialize_file,
+        crate::commands::file_actions::dematerialize_file,
+        crate::commands::file_actions::resolve_default_action,
         crate::mcp::pane_state::update_left_pane_state,
         crate::mcp::pane_state::update_right_pane_state,
         crate::mcp::pane_state::update_focused_pane,
@@ -258,12 +314,16 @@ pub fn builder() -> Builder<tauri::Wry> {
         #[cfg(any(target_os = "macos", target_os = "linux"))]
         crate::commands::mtp::get_mtp_device_info,
         #[cfg(any(target_os = "macos", target_os = "linux"))]
+        crate::commands::mtp::get_mtp_capabilities,
+        #[cfg(any(target_os = "macos", target_os = "linux"))]
         crate::commands::mtp::get_ptpcamerad_workaround_command,
         #[cfg(any(target_os = "macos", target_os = "linux"))]
         crate::commands::mtp::get_mtp_storages,
         #[cfg(any(target_os = "macos", target_os = "linux"))]
         crate::commands::mtp::list_mtp_directory,
         #[cfg(any(target_os = "macos", target_os = "linux"))]
+        crate::commands::mtp::refresh_mtp_directory,
+        #[cfg(any(target_os = "macos", target_os = "linux"))]
         #[cfg(any(target_os = "macos", target_os = "linux"))]
         #[cfg(any(target_os = "macos", target_os = "linux"))]
         crate::commands::mtp::delete_mtp_object,
@@ -274,6 +334,10 @@ pub fn builder() -> Builder<tauri::Wry> {
         #[cfg(any(target_os = "macos", target_os = "linux"))]
         crate::commands::mtp::move_mtp_object,
         #[cfg(any(target_os = "macos", target_os = "linux"))]
+        crate::commands::mtp::move_mtp_object_between_storages,
+        #[cfg(any(target_os = "macos", target_os = "linux"))]
+        crate::commands::mtp::get_mtp_thumbnail,
+        #[cfg(any(target_os = "macos", target_os = "linux"))]
         crate::commands::mtp::scan_mtp_for_copy,
         #[cfg(all(feature = "virtual-mtp", any(target_os = "macos", target_os = "linux")))]
         crate::commands::mtp::rescan_virtual_mtp,
@@ -292,12 +356,16 @@ pub fn builder() -> Builder<tauri::Wry> {
         #[cfg(not(any(target_os = "macos", target_os = "linux")))]
         crate::stubs::mtp::get_mtp_device_info,
         #[cfg(not(any(target_os = "macos", target_os = "linux")))]
+        crate::stubs::mtp::get_mtp_capabilities,
+        #[cfg(not(any(target_os = "macos", target_os = "linux")))]
         crate::stubs::mtp::get_ptpcamerad_workaround_command,
         #[cfg(not(any(target_os = "macos", target_os = "linux")))]
         crate::stubs::mtp::get_mtp_storages,
         #[cfg(not(any(target_os = "macos", target_os = "linux")))]
         crate::stubs::mtp::list_mtp_directory,
         #[cfg(not(any(target_os = "macos", target_os = "linux")))]
+        crate::stubs::mtp::refresh_mtp_directory,
+        #[cfg(not(any(target_os = "macos", target_os = "linux")))]
         #[cfg(not(any(target_os = "macos", target_os = "linux")))]
         #[cfg(not(any(target_os = "macos", target_os = "linux")))]
         crate::stubs::mtp::delete_mtp_object,
@@ -308,6 +376,10 @@ pub fn builder() -> Builder<tauri::Wry> {
         #[cfg(not(any(target_os = "macos", target_os = "linux")))]
         crate::stubs::mtp::move_mtp_object,
         #[cfg(not(any(target_os = "macos", target_os = "linux")))]
+        crate::stubs::mtp::move_mtp_object_between_storages,
+        #[cfg(not(any(target_os = "macos", target_os = "linux")))]
+        crate::stubs::mtp::get_mtp_thumbnail,
+        #[cfg(not(any(target_os = "macos", target_os = "linux")))]
         crate::stubs::mtp::scan_mtp_for_copy,
         crate::volume_broadcast::refresh_volumes,
         crate::space_poller::watch_volume_space,
@@ -543,21 +615,28 @@ pub fn builder() -> Builder<tauri::Wry> {
         crate::ai::manager::configure_ai,
         crate::ai::server::start_ai_server,
         crate::ai::server::stop_ai_server,
+        crate::ai::server::update_ai_server_config,
         crate::ai::connection_check::check_ai_connection,
         crate::system_memory::get_system_memory_info,
         crate::system_strings::get_localized_system_strings,
         crate::ai::install::start_ai_download,
         crate::ai::install::cancel_ai_download,
         crate::ai::install::uninstall_ai,
+        crate::ai::install::install_custom_model,
+        crate::ai::install::uninstall_custom_model,
         crate::ai::api_keys::save_ai_api_key,
         crate::ai::api_keys::get_ai_api_key,
         crate::ai::api_keys::delete_ai_api_key,
         crate::ai::api_keys::has_ai_api_key,
         crate::ai::suggestions::get_folder_suggestions,
+        crate::ai::suggestions::get_rename_suggestion,
         // stream_folder_suggestions / cancel_folder_suggestions: streaming via tauri Channel<T>;
         // not specta-friendly yet, kept on raw invoke (eslint opt-out at FE call sites).
         crate::ai::suggestions::stream_folder_suggestions,
         crate::ai::suggestions::cancel_folder_suggestions,
+        crate::ai::plan::propose_folder_plan,
+        crate::ai::plan::apply_folder_plan,
+        crate::ai::plan::cancel_folder_plan,
         crate::commands::mcp::set_mcp_enabled,
         crate::commands::mcp::set_mcp_port,
         crate::commands::mcp::get_mcp_running,
@@ -567,11 +646,15 @@ pub fn builder() -> Builder<tauri::Wry> {
         crate::commands::settings::find_available_port,
         crate::commands::settings::get_isolated_store_path,
         crate::commands::settings::update_file_watcher_debounce,
+        crate::commands::settings::update_file_watcher_debounce_for_volume_kind,
+        crate::commands::settings::get_watcher_stats,
         crate::commands::settings::update_service_resolve_timeout,
         crate::commands::settings::update_menu_accelerator,
         crate::commands::settings::set_direct_smb_connection,
         crate::commands::settings::set_filter_safe_save_artifacts_cmd,
         crate::commands::settings::set_smb_concurrency_cmd,
+        crate::commands::settings::set_listing_cache_memory_mb,
+        crate::commands::settings::set_default_action_settings,
         crate::commands::settings::set_log_llm_calls,
         crate::commands::settings::set_image_index_enabled,
         crate::commands::settings::set_max_log_storage_mb,
@@ -579,6 +662,9 @@ pub fn builder() -> Builder<tauri::Wry> {
         crate::commands::settings::get_restricted_window_settings,
         crate::commands::settings::persist_restricted_window_setting,
         crate::commands::settings::set_show_virtual_git_portal,
+        crate::commands::settings::set_git_ignored_mode,
+        crate::commands::settings::write_settings_export,
+        crate::commands::settings::read_settings_export,
         crate::commands::logging::batch_fe_logs,
         crate::commands::logging::set_log_level,
         crate::downloads::commands::go_to_latest_download,
@@ -600,6 +686,13 @@ pub fn builder() -> Builder<tauri::Wry> {
         crate::commands::indexing::disable_drive_index,
         crate::commands::indexing::forget_drive_index,
         crate::commands::indexing::rescan_drive_index,
+        crate::commands::indexing::reindex_subtree,
+        crate::commands::indexing::cancel_reindex_subtree,
+        crate::commands::indexing::pause_drive_index_scan,
+        crate::commands::indexing::resume_drive_index_scan,
+        crate::commands::indexing::compact_index,
+        crate::commands::indexing::set_index_exclusions,
+        crate::commands::indexing::set_index_throttle,
         crate::importance::commands::record_visit,
         crate::media_index::commands::media_index_search_ocr,
         crate::media_index::commands::media_index_volume_state,
@@ -637,11 +730,23 @@ pub fn builder() -> Builder<tauri::Wry> {
         crate::commands::search::remove_recent_search,
         crate::commands::search::clear_recent_searches,
         crate::commands::search::apply_recent_searches_max_count,
+        crate::commands::search::open_search_results_volume,
+        crate::commands::search::close_search_results_volume,
         crate::commands::go_to_path::resolve_go_to_path,
+        crate::commands::go_to_path::complete_go_to_path,
         crate::commands::go_to_path::get_recent_paths,
         crate::commands::go_to_path::add_recent_path,
         crate::commands::go_to_path::remove_recent_path,
         crate::commands::go_to_path::clear_recent_paths,
+        crate::commands::palette::get_palette_actions,
+        crate::commands::palette::invoke_palette_action,
+        crate::commands::session::save_session,
+        crate::commands::session::restore_session,
+        crate::commands::session::push_history,
+        crate::commands::session::go_back,
+        crate::commands::session::go_forward,
+        crate::commands::session::get_history,
+        crate::commands::favorites::list_favorites,
         crate::commands::favorites::add_favorite,
         crate::commands::favorites::remove_favorite,
         crate::commands::favorites::rename_favorite,
@@ -650,6 +755,10 @@ pub fn builder() -> Builder<tauri::Wry> {
         crate::commands::whats_new::whats_new_dev_override,
         crate::commands::operation_log::get_recent_operation_log_entries,
         crate::commands::operation_log::get_operation_log_detail,
+        crate::commands::operation_log::get_operation_history,
+        crate::commands::operation_log::get_undo_state,
+        crate::commands::operation_log::undo_last_operation,
+        crate::commands::operation_log::redo_last_operation,
         // ask_cmdr_send_message: streaming via tauri Channel<T>; not specta-friendly, so
         // it rides raw invoke on the frontend and is absent from ipc_collectors.
         crate::commands::agent::ask_cmdr_send_message,
@@ -695,6 +804,7 @@ pub fn builder() -> Builder<tauri::Wry> {
         crate::commands::clipboard::copy_paths_to_clipboard,
         crate::commands::clipboard::cut_paths_to_clipboard,
         crate::commands::clipboard::read_clipboard_files,
+        crate::commands::clipboard::paste_clipboard_files,
         crate::commands::clipboard::read_clipboard_text,
         crate::commands::clipboard::paste_clipboard_as_file,
         crate::commands::clipboard::clear_clipboard_cut_state,
@@ -724,15 +834,18 @@ pub fn builder() -> Builder<tauri::Wry> {
             WriteErrorEvent,
             WriteConflictEvent,
             WriteSourceItemDoneEvent,
+            WriteRenamedEvent,
             ScanProgressEvent,
             ConflictInfo, // scan-conflict
             DryRunResult, // dry-run-complete
             WriteSettledEvent,
+            WritePausedEvent,
             // Operation manager registry snapshot (write_operations/manager.rs).
             OperationsChanged,
             // Listing sink (file_system/listing/streaming.rs `TauriListingEventSink`).
             ListingOpeningEvent,
             ListingProgressEvent,
+            ListingChunkEvent,
             ListingReadCompleteEvent,
             ListingCompleteEvent,
             ListingErrorEvent,
@@ -742,11 +855,24 @@ pub fn builder() -> Builder<tauri::Wry> {
             ScanPreviewCompleteEvent,
             ScanPreviewErrorEvent,
             ScanPreviewCancelledEvent,
+            // Directory comparison (commands/file_system/compare.rs).
+            DirectoryCompareBatchEvent, // event_name = "directory-compare-batch"
+            // Duplicate search (commands/file_system/analysis.rs).
+            DuplicateGroupFoundEvent, // event_name = "duplicate-group-found"
+            // File and folder checksums (commands/file_system/checksum.rs).
+            ChecksumProgressEvent, // event_name = "checksum-progress"
+            TreeHashProgressEvent, // event_name = "tree-hash-progress"
+            // Cloud downloads (commands/file_actions.rs).
+            CloudMaterializeProgressEvent, // event_name = "cloud-materialize-progress"
+            // On-demand folder size (commands/file_system/analysis.rs).
+            FolderSizeProgressEvent, // event_name = "folder-size-progress"
+            FolderSizeCompleteEvent, // event_name = "folder-size-complete"
             // Volumes + disk space (volumes/, volumes_linux/, space_poller.rs,
             // write_operations/state.rs busy set, menu eject action).
             VolumesChanged,
             VolumeMounted,
             VolumeUnmounted,
+            VolumeEjected,
             VolumesBusyChanged,
             VolumeContextAction,
             LowDiskSpacePayload, // event_name = "low-disk-space"
@@ -758,6 +884,7 @@ pub fn builder() -> Builder<tauri::Wry> {
             IndexScanProgressEvent,        // event_name = "index-scan-progress"
             IndexScanCompleteEvent,        // event_name = "index-scan-complete"
             IndexScanAbortedEvent,         // event_name = "index-scan-aborted"
+            ScanPermissionWarningEvent,    // event_name = "scan-permission-warning"
             IndexPhaseChangedEvent,        // event_name = "index-phase-changed"
             IndexDirUpdatedEvent,          // event_name = "index-dir-updated"
             IndexReplayProgressEvent,      // event_name = "index-replay-progress"
@@ -768,6 +895,8 @@ pub fn builder() -> Builder<tauri::Wry> {
             IndexMemoryWarningEvent,       // event_name = "index-memory-warning"
             IndexFreshnessChangedEvent,    // event_name = "index-freshness-changed"
             SearchIndexReadyEvent,         // event_name = "search-index-ready"
+            ReindexProgressEvent,          // event_name = "reindex-progress"
+            ReindexCompleteEvent,          // event_name = "reindex-complete"
             // Image enrichment progress (media_index/events.rs): image
             // indexing joins the top-right indicator as a second publisher.
             MediaEnrichProgressEvent, // event_name = "media-enrich-progress"
@@ -777,6 +906,7 @@ pub fn builder() -> Builder<tauri::Wry> {
             MtpDeviceConnected,
             MtpDeviceDisconnected,
             MtpStorageRemoved,
+            MtpStorageMoveProgress,
             MtpExclusiveAccessError,
             MtpPermissionError,
             MtpPtpcameradSuppressed,
@@ -801,6 +931,7 @@ pub fn builder() -> Builder<tauri::Wry> {
             AiStarting,
             AiServerReady,
             AiVerifying,
+            AiIntegrityFailed,
             AiInstalling,
             AiInstallComplete,
             AiExtracting,
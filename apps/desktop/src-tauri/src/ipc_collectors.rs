@@ -17,7 +17,10 @@ pub(crate) fn collect_cross_platform_types(types: &mut Types) -> Vec<Function> {
         crate::ipc::greet,
         crate::commands::file_system::list_directory_start,
         crate::commands::file_system::list_directory_start_streaming,
+        crate::commands::file_system::list_directory_flat,
+        crate::commands::file_system::get_tree_children,
         crate::commands::file_system::cancel_listing,
+        crate::commands::file_system::prefetch_listing,
         crate::commands::file_system::list_directory_end,
         crate::commands::file_system::refresh_listing,
         crate::commands::file_system::get_file_range,
@@ -26,9 +29,26 @@ pub(crate) fn collect_cross_platform_types(types: &mut Types) -> Vec<Function> {
         crate::commands::file_system::get_paths_at_indices,
         crate::commands::file_system::get_total_count,
         crate::commands::file_system::get_brief_column_text_widths,
+        crate::commands::file_system::get_columns,
+        crate::commands::file_system::set_columns,
         crate::commands::file_system::find_file_index,
         crate::commands::file_system::find_file_indices,
         crate::commands::file_system::find_first_fuzzy_match,
+        crate::commands::file_system::find_next_match,
+        crate::commands::file_system::get_extension_breakdown,
+        crate::commands::file_system::get_largest_files,
+        crate::commands::file_system::get_dir_entry_count,
+        crate::commands::file_system::get_treemap_data,
+        crate::commands::file_system::find_duplicates,
+        crate::commands::file_system::cancel_find_duplicates,
+        crate::commands::file_system::compute_checksum,
+        crate::commands::file_system::cancel_checksum,
+        crate::commands::file_system::compute_tree_hash,
+        crate::commands::file_system::cancel_tree_hash,
+        crate::commands::file_system::get_file_info,
+        crate::commands::file_system::cancel_file_info,
+        crate::commands::file_system::start_folder_size,
+        crate::commands::file_system::cancel_folder_size,
         crate::commands::file_system::resort_listing,
         crate::commands::file_system::get_path_limits,
         crate::commands::file_system::enrich_tags,
@@ -37,24 +57,42 @@ pub(crate) fn collect_cross_platform_types(types: &mut Types) -> Vec<Function> {
         crate::commands::file_system::stat_paths_kinds,
         crate::commands::file_system::create_directory,
         crate::commands::file_system::create_file,
+        crate::commands::file_system::create_symlink,
+        crate::commands::file_system::create_hardlink,
+        crate::commands::file_system::resolve_symlink,
+        crate::commands::file_system::get_permissions,
+        crate::commands::file_system::set_permissions,
         crate::commands::file_system::set_archive_password,
         crate::commands::file_system::clear_archive_password,
+        crate::commands::file_system::list_archive,
+        crate::commands::file_system::extract_archive,
+        crate::commands::file_system::compare_directories,
+        crate::commands::file_system::cancel_directory_compare,
+        crate::commands::file_system::diff_files,
+        crate::commands::file_system::cancel_file_diff,
         crate::commands::file_system::benchmark_log,
         crate::commands::file_system::copy_files,
         crate::commands::file_system::move_files,
+        crate::commands::file_system::get_recent_move_targets,
         crate::commands::file_system::delete_files,
         crate::commands::file_system::trash_files,
+        crate::commands::file_system::get_trash_info,
+        crate::commands::file_system::empty_trash,
         crate::commands::file_system::cancel_write_operation,
         crate::commands::file_system::cancel_all_write_operations,
         crate::commands::file_system::start_scan_preview,
         crate::commands::file_system::cancel_scan_preview,
         crate::commands::file_system::check_scan_preview_status,
         crate::commands::file_system::resolve_write_conflict,
+        crate::commands::file_system::set_default_conflict_policy,
+        crate::commands::file_system::list_default_conflict_policies,
+        crate::commands::file_system::clear_default_conflict_policies,
         crate::commands::file_system::list_active_operations,
         crate::commands::file_system::get_operation_status,
         crate::commands::file_system::list_operations,
         crate::commands::file_system::cancel_operation,
         crate::commands::file_system::cancel_operations,
+        crate::commands::file_system::move_operation,
         crate::commands::file_system::pause_operation,
         crate::commands::file_system::resume_operation,
         crate::commands::file_system::pause_all,
@@ -65,6 +103,7 @@ pub(crate) fn collect_cross_platform_types(types: &mut Types) -> Vec<Function> {
         crate::commands::file_system::scan_volume_for_copy,
         crate::commands::file_system::scan_volume_for_conflicts,
         crate::commands::file_system::get_listing_stats,
+        crate::commands::file_system::get_listing_cache_stats,
         crate::commands::file_system::refresh_listing_index_sizes,
         crate::commands::file_system::start_selection_drag,
         crate::commands::file_system::start_drag_paths,
@@ -77,6 +116,8 @@ pub(crate) fn collect_cross_platform_types(types: &mut Types) -> Vec<Function> {
         crate::commands::file_system::get_git_status_for_paths,
         crate::commands::rename::check_rename_permission,
         crate::commands::rename::check_rename_validity,
+        crate::commands::rename::preview_batch_rename_rule,
+        crate::commands::rename::batch_rename,
         crate::commands::rename::rename_file,
         crate::commands::rename::move_to_trash,
         crate::commands::restricted_paths::get_restricted_paths,
@@ -99,6 +140,7 @@ pub(crate) fn collect_cross_platform_types(types: &mut Types) -> Vec<Function> {
         crate::commands::file_viewer::viewer_set_encoding,
         crate::commands::file_viewer::viewer_set_tail_mode,
         crate::commands::file_viewer::viewer_reload,
+        crate::commands::file_viewer::peek_file,
         // store_font_metrics is generic (<R: tauri::Runtime>): excluded from specta collection
         crate::commands::font_metrics::has_font_metrics,
         crate::commands::icons::get_icons,
@@ -122,9 +164,14 @@ pub(crate) fn collect_cross_platform_types(types: &mut Types) -> Vec<Function> {
         crate::commands::quick_look::quick_look_close,
         crate::commands::file_actions::get_info,
         crate::commands::file_actions::open_in_editor,
+        crate::commands::file_actions::get_applications_for,
+        crate::commands::file_actions::open_with,
         crate::commands::file_actions::open_path,
         crate::commands::file_actions::cloud_make_available_offline,
         crate::commands::file_actions::cloud_remove_download,
+        crate::commands::file_actions::materialize_file,
+        crate::commands::file_actions::dematerialize_file,
+        crate::commands::file_actions::resolve_default_action,
         crate::mcp::pane_state::update_left_pane_state,
         crate::mcp::pane_state::update_right_pane_state,
         crate::mcp::pane_state::update_focused_pane,
@@ -166,18 +213,24 @@ pub(crate) fn collect_cross_platform_types(types: &mut Types) -> Vec<Function> {
         crate::ai::manager::get_ai_status,
         crate::ai::state::get_ai_model_info,
         crate::ai::manager::get_ai_runtime_status,
-        // configure_ai, start_ai_server, start_ai_download are generic (<R: Runtime>): excluded
+        // configure_ai, start_ai_server, update_ai_server_config, start_ai_download, install_custom_model
+        // are generic (<R: Runtime>): excluded
         crate::ai::server::stop_ai_server,
         crate::ai::connection_check::check_ai_connection,
         crate::system_memory::get_system_memory_info,
         crate::system_strings::get_localized_system_strings,
         crate::ai::install::cancel_ai_download,
         crate::ai::install::uninstall_ai,
+        crate::ai::install::uninstall_custom_model,
         crate::ai::api_keys::save_ai_api_key,
         crate::ai::api_keys::get_ai_api_key,
         crate::ai::api_keys::delete_ai_api_key,
         crate::ai::api_keys::has_ai_api_key,
         crate::ai::suggestions::get_folder_suggestions,
+        crate::ai::suggestions::get_rename_suggestion,
+        crate::ai::plan::propose_folder_plan,
+        crate::ai::plan::apply_folder_plan,
+        crate::ai::plan::cancel_folder_plan,
         // set_mcp_enabled, set_mcp_port are generic (<R: Runtime>): excluded from specta
         crate::commands::mcp::get_mcp_running,
         crate::commands::mcp::get_mcp_port,
@@ -186,11 +239,15 @@ pub(crate) fn collect_cross_platform_types(types: &mut Types) -> Vec<Function> {
         crate::commands::settings::find_available_port,
         crate::commands::settings::get_isolated_store_path,
         crate::commands::settings::update_file_watcher_debounce,
+        crate::commands::settings::update_file_watcher_debounce_for_volume_kind,
+        crate::commands::settings::get_watcher_stats,
         crate::commands::settings::update_service_resolve_timeout,
         crate::commands::settings::update_menu_accelerator,
         crate::commands::settings::set_direct_smb_connection,
         crate::commands::settings::set_filter_safe_save_artifacts_cmd,
         crate::commands::settings::set_smb_concurrency_cmd,
+        crate::commands::settings::set_listing_cache_memory_mb,
+        crate::commands::settings::set_default_action_settings,
         crate::commands::settings::set_log_llm_calls,
         crate::commands::settings::set_image_index_enabled,
         crate::commands::settings::set_max_log_storage_mb,
@@ -198,6 +255,9 @@ pub(crate) fn collect_cross_platform_types(types: &mut Types) -> Vec<Function> {
         crate::commands::settings::get_restricted_window_settings,
         crate::commands::settings::persist_restricted_window_setting,
         crate::commands::settings::set_show_virtual_git_portal,
+        crate::commands::settings::set_git_ignored_mode,
+        crate::commands::settings::write_settings_export,
+        crate::commands::settings::read_settings_export,
         crate::commands::logging::batch_fe_logs,
         crate::commands::logging::set_log_level,
         crate::downloads::commands::go_to_latest_download,
@@ -219,6 +279,13 @@ pub(crate) fn collect_cross_platform_types(types: &mut Types) -> Vec<Function> {
         crate::commands::indexing::disable_drive_index,
         crate::commands::indexing::forget_drive_index,
         crate::commands::indexing::rescan_drive_index,
+        crate::commands::indexing::reindex_subtree,
+        crate::commands::indexing::cancel_reindex_subtree,
+        crate::commands::indexing::pause_drive_index_scan,
+        crate::commands::indexing::resume_drive_index_scan,
+        crate::commands::indexing::compact_index,
+        crate::commands::indexing::set_index_exclusions,
+        crate::commands::indexing::set_index_throttle,
         crate::importance::commands::record_visit,
         crate::media_index::commands::media_index_search_ocr,
         crate::media_index::commands::media_index_volume_state,
@@ -256,11 +323,23 @@ pub(crate) fn collect_cross_platform_types(types: &mut Types) -> Vec<Function> {
         crate::commands::search::remove_recent_search,
         crate::commands::search::clear_recent_searches,
         crate::commands::search::apply_recent_searches_max_count,
+        crate::commands::search::open_search_results_volume,
+        crate::commands::search::close_search_results_volume,
         crate::commands::go_to_path::resolve_go_to_path,
+        crate::commands::go_to_path::complete_go_to_path,
         crate::commands::go_to_path::get_recent_paths,
         crate::commands::go_to_path::add_recent_path,
         crate::commands::go_to_path::remove_recent_path,
         crate::commands::go_to_path::clear_recent_paths,
+        crate::commands::palette::get_palette_actions,
+        crate::commands::palette::invoke_palette_action,
+        crate::commands::session::save_session,
+        crate::commands::session::restore_session,
+        crate::commands::session::push_history,
+        crate::commands::session::go_back,
+        crate::commands::session::go_forward,
+        crate::commands::session::get_history,
+        crate::commands::favorites::list_favorites,
         crate::commands::favorites::add_favorite,
         crate::commands::favorites::remove_favorite,
         crate::commands::favorites::rename_favorite,
@@ -269,6 +348,10 @@ pub(crate) fn collect_cross_platform_types(types: &mut Types) -> Vec<Function> {
         crate::commands::whats_new::whats_new_dev_override,
         crate::commands::operation_log::get_recent_operation_log_entries,
         crate::commands::operation_log::get_operation_log_detail,
+        crate::commands::operation_log::get_operation_history,
+        crate::commands::operation_log::get_undo_state,
+        crate::commands::operation_log::undo_last_operation,
+        crate::commands::operation_log::redo_last_operation,
         // ask_cmdr_send_message is Channel-based (not specta); registered only in ipc.rs.
         crate::commands::agent::ask_cmdr_cancel,
         crate::commands::agent::preflight_bulk_rename,
@@ -302,6 +385,7 @@ pub(crate) fn collect_cross_platform_types(types: &mut Types) -> Vec<Function> {
         crate::commands::clipboard::copy_paths_to_clipboard,
         crate::commands::clipboard::cut_paths_to_clipboard,
         crate::commands::clipboard::read_clipboard_files,
+        crate::commands::clipboard::paste_clipboard_files,
         crate::commands::clipboard::read_clipboard_text,
         crate::commands::clipboard::paste_clipboard_as_file,
         crate::commands::clipboard::clear_clipboard_cut_state,
@@ -327,14 +411,18 @@ pub(super) fn collect_mtp_types(types: &mut Types) -> Vec<Function> {
         crate::commands::mtp::list_mtp_devices,
         crate::commands::mtp::connect_mtp_device,
         crate::commands::mtp::get_mtp_device_info,
+        crate::commands::mtp::get_mtp_capabilities,
         crate::commands::mtp::disconnect_mtp_device,
         crate::commands::mtp::get_mtp_storages,
         crate::commands::mtp::list_mtp_directory,
+        crate::commands::mtp::refresh_mtp_directory,
         crate::commands::mtp::get_ptpcamerad_workaround_command,
         crate::commands::mtp::delete_mtp_object,
         crate::commands::mtp::create_mtp_folder,
         crate::commands::mtp::rename_mtp_object,
         crate::commands::mtp::move_mtp_object,
+        crate::commands::mtp::move_mtp_object_between_storages,
+        crate::commands::mtp::get_mtp_thumbnail,
         crate::commands::mtp::scan_mtp_for_copy,
     ](types)
 }
@@ -346,14 +434,18 @@ pub(super) fn collect_mtp_types(types: &mut Types) -> Vec<Function> {
         crate::stubs::mtp::list_mtp_devices,
         crate::stubs::mtp::connect_mtp_device,
         crate::stubs::mtp::get_mtp_device_info,
+        crate::stubs::mtp::get_mtp_capabilities,
         crate::stubs::mtp::disconnect_mtp_device,
         crate::stubs::mtp::get_mtp_storages,
         crate::stubs::mtp::list_mtp_directory,
+        crate::stubs::mtp::refresh_mtp_directory,
         crate::stubs::mtp::get_ptpcamerad_workaround_command,
         crate::stubs::mtp::delete_mtp_object,
         crate::stubs::mtp::create_mtp_folder,
         crate::stubs::mtp::rename_mtp_object,
         crate::stubs::mtp::move_mtp_object,
+        crate::stubs::mtp::move_mtp_object_between_storages,
+        crate::stubs::mtp::get_mtp_thumbnail,
         crate::stubs::mtp::scan_mtp_for_copy,
     ](types)
 }
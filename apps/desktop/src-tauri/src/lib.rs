@@ -82,7 +82,11 @@ mod accent_color_linux;
 pub mod agent;
 mod ai;
 mod analytics;
+/// Public entry points for the criterion benches in `benches/`. Not an app API.
+#[doc(hidden)]
+pub mod bench_support;
 pub mod benchmark;
+mod cancel_registry;
 mod child_window_state;
 mod clipboard;
 mod commands;
@@ -129,12 +133,14 @@ mod net;
 #[cfg(any(target_os = "macos", target_os = "linux"))]
 mod network;
 pub mod operation_log;
+mod palette;
 #[cfg(target_os = "macos")]
 mod permissions;
 #[cfg(target_os = "linux")]
 mod permissions_linux;
 mod platform;
 mod pluralize;
+mod power_source;
 pub mod priority;
 mod process_memory;
 mod quick_look;
@@ -145,8 +151,10 @@ mod restricted_paths;
 pub mod search;
 mod secrets;
 pub mod selection;
+mod session;
 mod settings;
 mod short_id;
+mod shutdown;
 mod space_poller;
 mod sqlite_util;
 mod system_events;
@@ -622,6 +630,12 @@ pub fn run() {
             // Same for recent paths (Go to path dialog history).
             go_to_path::history::load_history(app.handle());
 
+            // Resolve where recent move targets persist (the move pipeline has no `AppHandle`).
+            file_system::move_targets::init(app.handle());
+
+            // Same for remembered per-folder-pair conflict policies.
+            file_system::conflict_policies::init(app.handle());
+
             // Load manually-added servers and inject into discovery state
             #[cfg(any(target_os = "macos", target_os = "linux"))]
             network::manual_servers::load_manual_servers(app.handle());
@@ -677,8 +691,21 @@ pub fn run() {
             // Apply direct SMB connection setting (default: true)
             file_system::set_direct_smb_enabled(saved_settings.direct_smb_connection.unwrap_or(true));
             file_system::git::set_virtual_portal_enabled(saved_settings.show_virtual_git_portal.unwrap_or(true));
+            file_system::listing::set_git_ignored_mode(saved_settings.git_ignored_mode.unwrap_or_default());
             file_system::set_filter_safe_save_artifacts(saved_settings.filter_safe_save_artifacts.unwrap_or(true));
             file_system::set_smb_concurrency(saved_settings.smb_concurrency.unwrap_or(10) as usize);
+            file_system::listing::eviction::set_cap_mb(
+                saved_settings
+                    .listing_cache_memory_mb
+                    .unwrap_or(file_system::listing::eviction::DEFAULT_CAP_MB),
+            );
+            file_system::default_action::set_settings(
+                file_system::default_action::DefaultActionSettings::from_setting_values(
+                    saved_settings.archive_enter_behavior.as_deref(),
+                    saved_settings.file_enter_action.as_deref(),
+                    saved_settings.open_with_apps.as_deref(),
+                ),
+            );
 
             // Initialize disk space poller (live status bar updates + low-disk-space warning)
             space_poller::init(app.handle());
@@ -735,8 +762,8 @@ pub fn run() {
             menu::set_macos_menu_icons();
 
             // Subscribe to NSWorkspace launch/terminate notifications so the "Open with"
-            // candidate cache invalidates when the user installs or removes apps.
-            #[cfg(target_os = "macos")]
+            // candidate cache invalidates when the user installs or removes apps. A no-op
+            // off macOS.
             file_system::open_with::start_invalidation_observer();
 
             // Store the CheckMenuItem references in app state
@@ -815,6 +842,11 @@ pub fn run() {
 
             // Initialize indexing state (does not start scanning until explicitly started)
             indexing::init(app.handle());
+            indexing::apply_user_exclusions(saved_settings.indexing_exclude_patterns.as_deref());
+            indexing::set_user_scan_throttle(indexing::ScanThrottleSettings {
+                max_entries_per_sec: saved_settings.indexing_max_entries_per_sec.unwrap_or(0),
+                only_on_ac_power: saved_settings.indexing_only_on_ac_power.unwrap_or(false),
+            });
 
             // Reuse the OS FDA result already captured for the gate above; this
             // call is on `/Library/Mail` which is cheap, but a fresh probe here
@@ -917,6 +949,7 @@ pub fn run() {
             if let tauri::WindowEvent::CloseRequested { .. } = event
                 && window.label() == "main"
             {
+                shutdown::shutdown_all_operations();
                 ai::manager::shutdown();
                 mcp::stop_mcp_server();
                 #[cfg(any(target_os = "macos", target_os = "linux"))]
@@ -955,6 +988,17 @@ pub fn run() {
                     drag_image_detection::install(_app.clone());
                 }
                 tauri::RunEvent::Exit => {
+                    // Checkpoint the panes for `restore_session` on next launch. Fast (one
+                    // small JSON write); a failure only costs the restore.
+                    if let Err(e) = session::save_session(_app) {
+                        log::warn!(target: "session", "Couldn't save pane session on exit: {e}");
+                    }
+
+                    // Quit paths that skip the main-window close handler (the quit
+                    // menu, the MCP `quit` tool) still stop ops at a safe point. A
+                    // no-op after the close handler already ran it.
+                    shutdown::shutdown_all_operations();
+
                     // Restore ptpcamerad before exit so we don't leave the system
                     // with the daemon disabled after Cmdr closes
                     #[cfg(target_os = "macos")]
@@ -251,6 +251,7 @@ fn operation_type_token(kind: WriteOperationType) -> &'static str {
         WriteOperationType::CreateFolder => "create_folder",
         WriteOperationType::CreateFile => "create_file",
         WriteOperationType::ArchiveEdit => "archive_edit",
+        WriteOperationType::SetPermissions => "set_permissions",
     }
 }
 
@@ -59,9 +59,10 @@ pub async fn execute_operations_get<R: Runtime>(app: &AppHandle<R>, params: &Val
     let op_id = required_operation_id(params)?;
     let limit = parse_limit(params, "limit", DEFAULT_ITEM_LIMIT)?;
     let offset = parse_offset(params)?;
+    let errors_only = params.get("errorsOnly").and_then(|v| v.as_bool()).unwrap_or(false);
     let op_id_for_read = op_id.clone();
     let detail = with_read_connection(app, None, move |conn| {
-        query::get_operation(conn, &op_id_for_read, limit, offset)
+        query::get_operation(conn, &op_id_for_read, limit, offset, errors_only)
     })
     .await?;
     match detail {
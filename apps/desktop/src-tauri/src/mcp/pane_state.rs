@@ -183,6 +183,17 @@ impl PaneStateStore {
     }
 }
 
+/// Tells the listing cache which folder a pane shows, so its memory cap never evicts it
+/// (`file_system/listing/eviction.rs`). `side` is 0 for left, 1 for right.
+fn mirror_displayed_folder(side: usize, state: &PaneState) {
+    let location = state
+        .volume_id
+        .clone()
+        .filter(|_| !state.path.is_empty())
+        .map(|volume_id| (volume_id, std::path::PathBuf::from(&state.path)));
+    crate::file_system::listing::eviction::set_displayed(side, location);
+}
+
 /// Tauri command to update left pane state from frontend.
 /// Preserves `tabs` (those are synced separately via `update_pane_tabs`).
 #[tauri::command]
@@ -190,6 +201,7 @@ impl PaneStateStore {
 pub fn update_left_pane_state(app: AppHandle, state: PaneState) {
     if let Some(store) = app.try_state::<PaneStateStore>() {
         let tabs = store.left.read_ignore_poison().tabs.clone();
+        mirror_displayed_folder(0, &state);
         let mut state = state;
         state.tabs = tabs;
         store.set_left(state);
@@ -203,6 +215,7 @@ pub fn update_left_pane_state(app: AppHandle, state: PaneState) {
 pub fn update_right_pane_state(app: AppHandle, state: PaneState) {
     if let Some(store) = app.try_state::<PaneStateStore>() {
         let tabs = store.right.read_ignore_poison().tabs.clone();
+        mirror_displayed_folder(1, &state);
         let mut state = state;
         state.tabs = tabs;
         store.set_right(state);
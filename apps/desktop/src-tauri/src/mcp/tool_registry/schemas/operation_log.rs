@@ -71,6 +71,10 @@ pub fn operations_get_schema() -> Value {
             "offset": {
                 "type": "integer",
                 "description": "Number of item rows to skip, for paging"
+            },
+            "errorsOnly": {
+                "type": "boolean",
+                "description": "Only the items that failed (and their count), e.g. after a copy that continued past errors. Default false."
             }
         },
         "required": ["operationId"]
@@ -647,8 +647,16 @@ pub fn handle_menu_event(app: &AppHandle<tauri::Wry>, event: tauri::menu::MenuEv
         if let Some(app_path) = app_path
             && !paths.is_empty()
         {
-            if let Err(e) = open_paths_with(&paths, &app_path) {
-                log::warn!("Open with failed for {bundle_id}: {e}");
+            match open_paths_with(&paths, &app_path) {
+                Ok(()) => {
+                    // Remember a non-default pick so `get_applications_for` offers it first. Off the
+                    // main thread: it may query LaunchServices.
+                    let (app, file, bundle_id) = (app.clone(), paths[0].clone(), bundle_id.to_string());
+                    tauri::async_runtime::spawn_blocking(move || {
+                        crate::file_system::open_with_recents::record_launch(&app, &file, &bundle_id);
+                    });
+                }
+                Err(e) => log::warn!("Open with failed for {bundle_id}: {e}"),
             }
         } else {
             log::warn!("Open with: missing app or paths for {bundle_id}");
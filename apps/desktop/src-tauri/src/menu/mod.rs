@@ -126,7 +126,8 @@ pub enum CommandScope {
 }
 
 /// View mode type that matches the frontend type.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
 pub enum ViewMode {
     Full,
     #[default]
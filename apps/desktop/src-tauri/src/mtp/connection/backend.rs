@@ -0,0 +1,447 @@
+//! The device calls the connection layer makes, behind a trait so its logic can run against
+//! `MockMtpDevice` (`mock_device.rs`) in tests.
+//!
+//! Production implements [`MtpBackend`] with [`LockedDevice`], which takes the per-device lock
+//! for each call (the same granularity the hand-written ops use, so a long recursive delete
+//! still lets a foreground listing slip in between objects). Logic written against the trait,
+//! like [`delete_tree`], then runs unchanged on a real phone and on the in-memory mock, with no
+//! USB and no `virtual-mtp` feature.
+//!
+//! Streaming transfers (`open_read_session`, `upload_from_stream`) and the manager's cached
+//! listings stay on `MtpDevice` directly: they depend on mtp-rs types (windowed downloads,
+//! object listings) that the mock doesn't model. The copy + delete move fallback
+//! ([`move_by_copy`]) is the exception: it spools through a local temp file, which both sides
+//! can do.
+
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use futures_util::Stream;
+use mtp_rs::{CancelToken, MtpDevice, NewObjectInfo, ObjectHandle, StorageId};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::sync::Mutex;
+
+use super::errors::MtpConnectionError;
+use super::{acquire_device_lock, map_mtp_error};
+
+/// One object as the backend reports it: the subset of `mtp_rs::ObjectInfo` the connection
+/// layer reads.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct BackendObject {
+    pub handle: ObjectHandle,
+    /// `ObjectHandle::ROOT` for a storage-root object.
+    pub parent: ObjectHandle,
+    pub name: String,
+    pub is_folder: bool,
+    /// Bytes; `0` for folders.
+    pub size: u64,
+}
+
+impl From<mtp_rs::ObjectInfo> for BackendObject {
+    fn from(info: mtp_rs::ObjectInfo) -> Self {
+        Self {
+            handle: info.handle,
+            parent: info.parent,
+            is_folder: info.is_folder(),
+            size: info.size,
+            name: info.filename,
+        }
+    }
+}
+
+/// The device operations shared by the real device and the test mock. Handles are
+/// storage-scoped; `ObjectHandle::ROOT` names the storage root.
+pub(super) trait MtpBackend: Send + Sync {
+    /// The device id errors are attributed to.
+    fn device_id(&self) -> &str;
+
+    fn object_info(
+        &self,
+        storage_id: u32,
+        handle: ObjectHandle,
+    ) -> impl Future<Output = Result<BackendObject, MtpConnectionError>> + Send;
+
+    /// Lists `parent`'s children, bailing at the next per-object boundary once `cancel` flips.
+    fn list_objects(
+        &self,
+        storage_id: u32,
+        parent: ObjectHandle,
+        cancel: Option<&CancelToken>,
+    ) -> impl Future<Output = Result<Vec<BackendObject>, MtpConnectionError>> + Send;
+
+    /// Reads up to `len` bytes of `handle` from `offset`.
+    fn download_file(
+        &self,
+        storage_id: u32,
+        handle: ObjectHandle,
+        offset: u64,
+        len: u32,
+    ) -> impl Future<Output = Result<Vec<u8>, MtpConnectionError>> + Send;
+
+    /// Uploads the local file at `local` as `name` in `parent`, streaming it rather than loading
+    /// it whole, and returns the new handle.
+    fn upload_from_file(
+        &self,
+        storage_id: u32,
+        parent: ObjectHandle,
+        name: &str,
+        local: &Path,
+    ) -> impl Future<Output = Result<ObjectHandle, MtpConnectionError>> + Send;
+
+    /// Creates a folder named `name` in `parent`, and returns its handle.
+    fn create_folder(
+        &self,
+        storage_id: u32,
+        parent: ObjectHandle,
+        name: &str,
+    ) -> impl Future<Output = Result<ObjectHandle, MtpConnectionError>> + Send;
+
+    /// Deletes one object. MTP refuses a non-empty folder, so callers delete bottom-up.
+    fn delete_object(
+        &self,
+        storage_id: u32,
+        handle: ObjectHandle,
+        cancel: Option<&CancelToken>,
+    ) -> impl Future<Output = Result<(), MtpConnectionError>> + Send;
+}
+
+/// A connected device seen through [`MtpBackend`]: every call takes the device lock for just
+/// that one USB round trip.
+pub(super) struct LockedDevice<'a> {
+    pub device: &'a Arc<Mutex<MtpDevice>>,
+    pub device_id: &'a str,
+}
+
+/// mtp-rs lists the storage root as `None`.
+fn parent_arg(parent: ObjectHandle) -> Option<ObjectHandle> {
+    (parent != ObjectHandle::ROOT).then_some(parent)
+}
+
+/// Read size for streaming a local file into an upload.
+const UPLOAD_CHUNK_SIZE: usize = 1024 * 1024;
+
+impl LockedDevice<'_> {
+    fn io_error(&self, context: &str, e: std::io::Error) -> MtpConnectionError {
+        MtpConnectionError::Other {
+            device_id: self.device_id.to_string(),
+            message: format!("{context}: {e}"),
+        }
+    }
+
+    async fn upload_stream<S>(
+        &self,
+        storage_id: u32,
+        parent: ObjectHandle,
+        info: NewObjectInfo,
+        stream: S,
+    ) -> Result<ObjectHandle, MtpConnectionError>
+    where
+        S: Stream<Item = Result<bytes::Bytes, std::io::Error>> + Unpin + Send,
+    {
+        let device = acquire_device_lock(self.device, self.device_id, "upload_from_file").await?;
+        let storage = device
+            .storage(StorageId(u64::from(storage_id)))
+            .await
+            .map_err(|e| map_mtp_error(e, self.device_id))?;
+        match storage.upload(parent_arg(parent), info, stream).await {
+            Ok(handle) => Ok(handle),
+            Err(upload_err) => {
+                // Same no-half-file policy as `upload_from_stream`.
+                if let Some(partial) = upload_err.partial {
+                    let _ = storage.delete(partial).await;
+                }
+                Err(map_mtp_error(upload_err.source, self.device_id))
+            }
+        }
+    }
+}
+
+impl MtpBackend for LockedDevice<'_> {
+    fn device_id(&self) -> &str {
+        self.device_id
+    }
+
+    async fn object_info(&self, storage_id: u32, handle: ObjectHandle) -> Result<BackendObject, MtpConnectionError> {
+        let device = acquire_device_lock(self.device, self.device_id, "object_info").await?;
+        let storage = device
+            .storage(StorageId(u64::from(storage_id)))
+            .await
+            .map_err(|e| map_mtp_error(e, self.device_id))?;
+        storage
+            .get_object_info(handle)
+            .await
+            .map(BackendObject::from)
+            .map_err(|e| map_mtp_error(e, self.device_id))
+    }
+
+    async fn list_objects(
+        &self,
+        storage_id: u32,
+        parent: ObjectHandle,
+        cancel: Option<&CancelToken>,
+    ) -> Result<Vec<BackendObject>, MtpConnectionError> {
+        let device = acquire_device_lock(self.device, self.device_id, "list_objects").await?;
+        let storage = device
+            .storage(StorageId(u64::from(storage_id)))
+            .await
+            .map_err(|e| map_mtp_error(e, self.device_id))?;
+        storage
+            .list_objects_with_cancel(parent_arg(parent), cancel)
+            .await
+            .map(|infos| infos.into_iter().map(BackendObject::from).collect())
+            .map_err(|e| map_mtp_error(e, self.device_id))
+    }
+
+    async fn download_file(
+        &self,
+        storage_id: u32,
+        handle: ObjectHandle,
+        offset: u64,
+        len: u32,
+    ) -> Result<Vec<u8>, MtpConnectionError> {
+        let device = acquire_device_lock(self.device, self.device_id, "download_file").await?;
+        let storage = device
+            .storage(StorageId(u64::from(storage_id)))
+            .await
+            .map_err(|e| map_mtp_error(e, self.device_id))?;
+        storage
+            .read_range(handle, offset, len)
+            .await
+            .map_err(|e| map_mtp_error(e, self.device_id))
+    }
+
+    async fn upload_from_file(
+        &self,
+        storage_id: u32,
+        parent: ObjectHandle,
+        name: &str,
+        local: &Path,
+    ) -> Result<ObjectHandle, MtpConnectionError> {
+        let file = tokio::fs::File::open(local)
+            .await
+            .map_err(|e| self.io_error("open spool file", e))?;
+        let size = file
+            .metadata()
+            .await
+            .map_err(|e| self.io_error("stat spool file", e))?
+            .len();
+        let stream = futures_util::stream::unfold(Some(file), |file| async move {
+            let mut file = file?;
+            let mut buf = vec![0u8; UPLOAD_CHUNK_SIZE];
+            match file.read(&mut buf).await {
+                Ok(0) => None,
+                Ok(n) => {
+                    buf.truncate(n);
+                    Some((Ok(bytes::Bytes::from(buf)), Some(file)))
+                }
+                // End the stream after the error so the upload fails instead of retrying the read.
+                Err(e) => Some((Err(e), None)),
+            }
+        });
+        self.upload_stream(storage_id, parent, NewObjectInfo::file(name, size), Box::pin(stream))
+            .await
+    }
+
+    async fn create_folder(
+        &self,
+        storage_id: u32,
+        parent: ObjectHandle,
+        name: &str,
+    ) -> Result<ObjectHandle, MtpConnectionError> {
+        let device = acquire_device_lock(self.device, self.device_id, "create_folder").await?;
+        let storage = device
+            .storage(StorageId(u64::from(storage_id)))
+            .await
+            .map_err(|e| map_mtp_error(e, self.device_id))?;
+        storage
+            .create_folder(parent_arg(parent), name)
+            .await
+            .map_err(|e| map_mtp_error(e, self.device_id))
+    }
+
+    async fn delete_object(
+        &self,
+        storage_id: u32,
+        handle: ObjectHandle,
+        cancel: Option<&CancelToken>,
+    ) -> Result<(), MtpConnectionError> {
+        let device = acquire_device_lock(self.device, self.device_id, "delete_object").await?;
+        let storage = device
+            .storage(StorageId(u64::from(storage_id)))
+            .await
+            .map_err(|e| map_mtp_error(e, self.device_id))?;
+        storage
+            .delete_with_cancel(handle, cancel)
+            .await
+            .map_err(|e| map_mtp_error(e, self.device_id))
+    }
+}
+
+/// Deletes `handle` (at `path`) and, for a folder, everything under it, children before their
+/// parent because MTP refuses to delete a non-empty folder. Every deleted object's path is
+/// pushed to `deleted` as it goes, so the caller can forget them from its caches even when the
+/// delete fails or is cancelled halfway.
+///
+/// `cancel` is checked before each child and passed into every list and delete call.
+pub(super) async fn delete_tree<B: MtpBackend>(
+    backend: &B,
+    storage_id: u32,
+    handle: ObjectHandle,
+    path: &Path,
+    cancel: Option<&CancelToken>,
+    deleted: &mut Vec<PathBuf>,
+) -> Result<(), MtpConnectionError> {
+    let info = backend.object_info(storage_id, handle).await?;
+    if info.is_folder {
+        let children = backend.list_objects(storage_id, handle, cancel).await?;
+        for child in children {
+            if cancel.is_some_and(CancelToken::is_cancelled) {
+                return Err(MtpConnectionError::Cancelled {
+                    device_id: backend.device_id().to_string(),
+                    message: "Delete cancelled between children".to_string(),
+                });
+            }
+            let child_path = path.join(&child.name);
+            Box::pin(delete_tree(
+                backend,
+                storage_id,
+                child.handle,
+                &child_path,
+                cancel,
+                deleted,
+            ))
+            .await?;
+        }
+    }
+    backend.delete_object(storage_id, handle, cancel).await?;
+    deleted.push(path.to_path_buf());
+    Ok(())
+}
+
+/// Moves `handle` (at `path` on `from_storage`) into `dest_parent` on `to_storage` by copying it
+/// there and then deleting the original, for devices that don't support `MoveObject` (or not
+/// across storages). Returns the copy's handle; the deleted source paths land in `deleted`, like
+/// [`delete_tree`].
+///
+/// The copy finishes before anything is deleted, so a failure mid-copy leaves the source intact;
+/// the partial copy is removed on a best-effort basis. Files go through a temp file in
+/// `spool_dir`, `window` bytes at a time, so a 4 GB video never sits in memory. `on_copied` gets
+/// each file's size once it's on the destination.
+#[allow(
+    clippy::too_many_arguments,
+    reason = "mirrors `delete_tree`'s parameters plus the destination, spool, and progress settings"
+)]
+pub(super) async fn move_by_copy<B: MtpBackend>(
+    backend: &B,
+    from_storage: u32,
+    handle: ObjectHandle,
+    path: &Path,
+    to_storage: u32,
+    dest_parent: ObjectHandle,
+    spool_dir: &Path,
+    window: u32,
+    on_copied: &(dyn Fn(u64) + Sync),
+    deleted: &mut Vec<PathBuf>,
+) -> Result<ObjectHandle, MtpConnectionError> {
+    let mut created = Vec::new();
+    let copy = CopyTarget {
+        from_storage,
+        to_storage,
+        spool_dir,
+        window,
+        on_copied,
+    };
+    if let Err(e) = copy_tree(backend, &copy, handle, dest_parent, &mut created).await {
+        if let Some(&partial) = created.first() {
+            let mut ignored = Vec::new();
+            if let Err(cleanup_err) = delete_tree(backend, to_storage, partial, Path::new(""), None, &mut ignored).await
+            {
+                log::warn!(
+                    "move_by_copy: couldn't remove the partial copy of {}: {cleanup_err}",
+                    path.display()
+                );
+            }
+        }
+        return Err(e);
+    }
+    delete_tree(backend, from_storage, handle, path, None, deleted).await?;
+    Ok(created[0])
+}
+
+/// The fixed settings of one [`copy_tree`] run.
+struct CopyTarget<'a> {
+    from_storage: u32,
+    to_storage: u32,
+    spool_dir: &'a Path,
+    window: u32,
+    on_copied: &'a (dyn Fn(u64) + Sync),
+}
+
+/// Copies `handle` (a file or a whole folder) into `dest_parent`. Every object it creates is
+/// pushed to `created`, top-level copy first.
+async fn copy_tree<B: MtpBackend>(
+    backend: &B,
+    copy: &CopyTarget<'_>,
+    handle: ObjectHandle,
+    dest_parent: ObjectHandle,
+    created: &mut Vec<ObjectHandle>,
+) -> Result<(), MtpConnectionError> {
+    let info = backend.object_info(copy.from_storage, handle).await?;
+    if info.is_folder {
+        let folder = backend.create_folder(copy.to_storage, dest_parent, &info.name).await?;
+        created.push(folder);
+        for child in backend.list_objects(copy.from_storage, handle, None).await? {
+            Box::pin(copy_tree(backend, copy, child.handle, folder, created)).await?;
+        }
+        return Ok(());
+    }
+
+    let spool = copy
+        .spool_dir
+        .join(format!(".cmdr-mtp-move-{}.tmp", uuid::Uuid::new_v4().simple()));
+    let result = async {
+        spool_to_file(backend, copy.from_storage, &info, &spool, copy.window).await?;
+        backend
+            .upload_from_file(copy.to_storage, dest_parent, &info.name, &spool)
+            .await
+    }
+    .await;
+    let _ = tokio::fs::remove_file(&spool).await;
+    created.push(result?);
+    (copy.on_copied)(info.size);
+    Ok(())
+}
+
+/// Downloads `info`'s bytes into a new file at `spool`, one `window` at a time.
+async fn spool_to_file<B: MtpBackend>(
+    backend: &B,
+    storage_id: u32,
+    info: &BackendObject,
+    spool: &Path,
+    window: u32,
+) -> Result<(), MtpConnectionError> {
+    let io_error = |context: &str, e: std::io::Error| MtpConnectionError::Other {
+        device_id: backend.device_id().to_string(),
+        message: format!("{context}: {e}"),
+    };
+    let mut file = tokio::fs::File::create(spool)
+        .await
+        .map_err(|e| io_error("create spool file", e))?;
+    let mut offset = 0;
+    while offset < info.size {
+        let len = u32::try_from(info.size - offset).unwrap_or(u32::MAX).min(window);
+        let chunk = backend.download_file(storage_id, info.handle, offset, len).await?;
+        if chunk.is_empty() {
+            return Err(MtpConnectionError::Other {
+                device_id: backend.device_id().to_string(),
+                message: format!("{} ended at byte {offset} of {}", info.name, info.size),
+            });
+        }
+        file.write_all(&chunk)
+            .await
+            .map_err(|e| io_error("write spool file", e))?;
+        offset += chunk.len() as u64;
+    }
+    file.flush().await.map_err(|e| io_error("flush spool file", e))
+}
@@ -2,9 +2,9 @@
 //! plus event debouncing for directory change notifications.
 
 use mtp_rs::ObjectHandle;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::path::{Path, PathBuf};
-use std::sync::RwLock;
+use std::sync::{Arc, RwLock};
 use std::time::{Duration, Instant};
 
 use crate::file_system::FileEntry;
@@ -55,6 +55,29 @@ impl PathHandleCache {
             }
         }
     }
+
+    /// Re-keys `old` and every cached path under it to sit under `new`, keeping their handles.
+    ///
+    /// For a native `MoveObject`, which reparents the object without changing any handle: the
+    /// descendants of a moved folder are still valid objects, only their paths changed.
+    pub(super) fn rename_subtree(&mut self, old: &Path, new: &Path) {
+        let moved: Vec<(PathBuf, ObjectHandle)> = self
+            .path_to_handle
+            .iter()
+            .filter(|(path, _)| path.starts_with(old))
+            .map(|(path, handle)| (path.clone(), *handle))
+            .collect();
+        for (path, handle) in moved {
+            self.remove_path(&path);
+            let relative = path.strip_prefix(old).unwrap_or(&path);
+            let new_path = if relative.as_os_str().is_empty() {
+                new.to_path_buf()
+            } else {
+                new.join(relative)
+            };
+            self.insert(new_path, handle);
+        }
+    }
 }
 
 /// Cache for directory listings.
@@ -75,9 +98,85 @@ pub(super) struct CachedListing {
 /// How long to keep cached listings (5 seconds).
 pub(super) const LISTING_CACHE_TTL_SECS: u64 = 5;
 
-/// Debounce duration for MTP directory change events (500ms).
-/// MTP devices can emit rapid events during bulk operations (like copying many files).
-pub(super) const EVENT_DEBOUNCE_MS: u64 = 500;
+/// Most thumbnails one device keeps. A DCIM grid shows a few hundred at once.
+pub(super) const THUMBNAIL_CACHE_MAX_ENTRIES: usize = 512;
+
+/// Most thumbnail bytes one device keeps. Camera thumbnails are ~10-20 KB, so the entry cap
+/// usually binds first; this one stops a device with huge embedded previews from hoarding memory.
+pub(super) const THUMBNAIL_CACHE_MAX_BYTES: usize = 16 * 1024 * 1024;
+
+/// Bounded LRU of `GetThumb` results, keyed by object handle.
+///
+/// Holds misses too (`None`, for formats with no embedded thumbnail), so scrolling back over a
+/// folder of PDFs doesn't re-ask the device for each one. MTP devices reuse handles, so an entry
+/// must go when its object changes or disappears: see
+/// [`invalidate_thumbnail`](super::MtpConnectionManager::invalidate_thumbnail).
+pub(super) struct ThumbnailCache {
+    entries: HashMap<ObjectHandle, Option<Arc<Vec<u8>>>>,
+    /// Least recently used first.
+    order: VecDeque<ObjectHandle>,
+    bytes: usize,
+    max_entries: usize,
+    max_bytes: usize,
+}
+
+impl Default for ThumbnailCache {
+    fn default() -> Self {
+        Self::with_limits(THUMBNAIL_CACHE_MAX_ENTRIES, THUMBNAIL_CACHE_MAX_BYTES)
+    }
+}
+
+impl ThumbnailCache {
+    pub(super) fn with_limits(max_entries: usize, max_bytes: usize) -> Self {
+        Self {
+            entries: HashMap::new(),
+            order: VecDeque::new(),
+            bytes: 0,
+            max_entries,
+            max_bytes,
+        }
+    }
+
+    /// The cached result for `handle`, marking it most recently used. The outer `None` means
+    /// "not cached"; `Some(None)` is a cached "this object has no thumbnail".
+    pub(super) fn get(&mut self, handle: ObjectHandle) -> Option<Option<Arc<Vec<u8>>>> {
+        let hit = self.entries.get(&handle)?.clone();
+        self.touch(handle);
+        Some(hit)
+    }
+
+    /// Stores a result, evicting least recently used entries until both limits hold. A single
+    /// thumbnail bigger than the byte limit isn't kept at all.
+    pub(super) fn insert(&mut self, handle: ObjectHandle, thumbnail: Option<Arc<Vec<u8>>>) {
+        let size = thumbnail.as_ref().map_or(0, |t| t.len());
+        self.remove(handle);
+        if size > self.max_bytes {
+            return;
+        }
+        self.entries.insert(handle, thumbnail);
+        self.order.push_back(handle);
+        self.bytes += size;
+        while self.entries.len() > self.max_entries || self.bytes > self.max_bytes {
+            let Some(oldest) = self.order.front().copied() else {
+                break;
+            };
+            self.remove(oldest);
+        }
+    }
+
+    /// Forgets `handle`, if cached.
+    pub(super) fn remove(&mut self, handle: ObjectHandle) {
+        if let Some(old) = self.entries.remove(&handle) {
+            self.bytes -= old.map_or(0, |t| t.len());
+            self.order.retain(|h| *h != handle);
+        }
+    }
+
+    fn touch(&mut self, handle: ObjectHandle) {
+        self.order.retain(|h| *h != handle);
+        self.order.push_back(handle);
+    }
+}
 
 /// Debouncer for MTP directory change events.
 ///
@@ -86,19 +185,36 @@ pub(super) const EVENT_DEBOUNCE_MS: u64 = 500;
 pub(super) struct EventDebouncer {
     /// Last emit time per device ID.
     last_emit: RwLock<HashMap<String, Instant>>,
-    /// Debounce duration.
-    debounce_duration: Duration,
+    /// Fixed debounce duration, or `None` to follow the user's MTP watcher debounce setting.
+    debounce_duration: Option<Duration>,
 }
 
 impl EventDebouncer {
     /// Creates a new debouncer with the given duration.
+    #[cfg(test)]
     pub(super) fn new(debounce_duration: Duration) -> Self {
         Self {
             last_emit: RwLock::new(HashMap::new()),
-            debounce_duration,
+            debounce_duration: Some(debounce_duration),
+        }
+    }
+
+    /// Creates a debouncer that reads the MTP watcher debounce setting on every check, so changes
+    /// apply to connected devices right away. MTP devices can emit rapid events during bulk
+    /// operations (like copying many files), so it defaults to at least 500 ms.
+    pub(super) fn from_settings() -> Self {
+        Self {
+            last_emit: RwLock::new(HashMap::new()),
+            debounce_duration: None,
         }
     }
 
+    /// The current debounce window.
+    pub(super) fn duration(&self) -> Duration {
+        self.debounce_duration
+            .unwrap_or_else(|| crate::file_system::debounce_for(crate::file_system::WatcherVolumeKind::Mtp))
+    }
+
     /// Checks if we should emit an event for the given device.
     /// Updates the last emit time if we should emit.
     pub(super) fn should_emit(&self, device_id: &str) -> bool {
@@ -106,7 +222,7 @@ impl EventDebouncer {
         let mut last_emit = self.last_emit.write_ignore_poison();
 
         if let Some(last) = last_emit.get(device_id)
-            && now.duration_since(*last) < self.debounce_duration
+            && now.duration_since(*last) < self.duration()
         {
             return false;
         }
@@ -126,6 +242,61 @@ impl EventDebouncer {
 mod tests {
     use super::*;
 
+    #[test]
+    fn rename_subtree_moves_descendants_and_keeps_handles() {
+        let mut cache = PathHandleCache::default();
+        cache.insert(PathBuf::from("/DCIM/Trip"), ObjectHandle(1));
+        cache.insert(PathBuf::from("/DCIM/Trip/a.jpg"), ObjectHandle(2));
+        cache.insert(PathBuf::from("/DCIM/Trip/Day 2/b.jpg"), ObjectHandle(3));
+        cache.insert(PathBuf::from("/DCIM/Trip2"), ObjectHandle(4));
+
+        cache.rename_subtree(Path::new("/DCIM/Trip"), Path::new("/Pictures/Trip"));
+
+        for (path, handle) in [
+            ("/Pictures/Trip", 1),
+            ("/Pictures/Trip/a.jpg", 2),
+            ("/Pictures/Trip/Day 2/b.jpg", 3),
+            ("/DCIM/Trip2", 4),
+        ] {
+            assert_eq!(cache.path_to_handle.get(Path::new(path)), Some(&ObjectHandle(handle)));
+            assert_eq!(
+                cache.handle_to_path.get(&ObjectHandle(handle)),
+                Some(&PathBuf::from(path))
+            );
+        }
+        assert!(!cache.path_to_handle.contains_key(Path::new("/DCIM/Trip")));
+        assert!(!cache.path_to_handle.contains_key(Path::new("/DCIM/Trip/a.jpg")));
+        assert_eq!(cache.path_to_handle.len(), 4);
+    }
+
+    #[test]
+    fn thumbnail_cache_evicts_least_recently_used_within_both_limits() {
+        let thumb = |n: usize| Some(Arc::new(vec![0u8; n]));
+        let mut cache = ThumbnailCache::with_limits(3, 10);
+        cache.insert(ObjectHandle(1), thumb(4));
+        cache.insert(ObjectHandle(2), None);
+        cache.insert(ObjectHandle(3), thumb(4));
+        // Touching 1 makes 2 the oldest, so the entry cap evicts 2.
+        assert!(cache.get(ObjectHandle(1)).is_some());
+        cache.insert(ObjectHandle(4), None);
+        assert!(cache.get(ObjectHandle(2)).is_none());
+
+        // 4 + 4 + 4 bytes is over the byte cap: the oldest thumbnail (3) goes.
+        cache.insert(ObjectHandle(5), thumb(4));
+        assert!(cache.get(ObjectHandle(3)).is_none());
+        assert_eq!(cache.get(ObjectHandle(1)).unwrap().unwrap().len(), 4);
+        assert_eq!(cache.get(ObjectHandle(4)), Some(None), "misses are cached too");
+
+        cache.insert(ObjectHandle(6), thumb(11));
+        assert!(
+            cache.get(ObjectHandle(6)).is_none(),
+            "an oversized thumbnail isn't kept"
+        );
+        cache.remove(ObjectHandle(1));
+        assert!(cache.get(ObjectHandle(1)).is_none());
+        assert_eq!(cache.bytes, 4);
+    }
+
     #[test]
     fn test_event_debouncer_allows_first_event() {
         let debouncer = EventDebouncer::new(Duration::from_millis(500));
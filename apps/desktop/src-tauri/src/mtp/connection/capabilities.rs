@@ -0,0 +1,77 @@
+//! Maps the device's advertised PTP/MTP operation codes to the [`MtpDeviceCapabilities`] the
+//! frontend gates actions on.
+
+use mtp_rs::ptp::OperationCode;
+
+use crate::mtp::types::MtpDeviceCapabilities;
+
+/// Derives the capabilities from `DeviceInfo::operations_supported`.
+pub(super) fn capabilities_from_operations(ops: &[OperationCode]) -> MtpDeviceCapabilities {
+    let has = |op: OperationCode| ops.contains(&op);
+    let can_send = has(OperationCode::SendObjectInfo) && has(OperationCode::SendObject);
+    MtpDeviceCapabilities {
+        can_upload: can_send,
+        can_delete: has(OperationCode::DeleteObject),
+        can_rename: has(OperationCode::SetObjectPropValue),
+        can_move: has(OperationCode::MoveObject),
+        // A folder is an association object sent with `SendObjectInfo` alone, but devices that
+        // take one without `SendObject` don't exist in practice, and a folder the user can't put
+        // files into isn't worth offering.
+        can_create_folder: can_send,
+        can_read_thumbnails: has(OperationCode::GetThumb),
+        can_read_partial: has(OperationCode::GetPartialObject64) || has(OperationCode::GetPartialObject),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn android_phone_supports_everything() {
+        let caps = capabilities_from_operations(&[
+            OperationCode::GetObjectInfo,
+            OperationCode::GetObject,
+            OperationCode::GetThumb,
+            OperationCode::DeleteObject,
+            OperationCode::SendObjectInfo,
+            OperationCode::SendObject,
+            OperationCode::MoveObject,
+            OperationCode::GetPartialObject,
+            OperationCode::GetPartialObject64,
+            OperationCode::SetObjectPropValue,
+        ]);
+        assert_eq!(
+            caps,
+            MtpDeviceCapabilities {
+                can_upload: true,
+                can_delete: true,
+                can_rename: true,
+                can_move: true,
+                can_create_folder: true,
+                can_read_thumbnails: true,
+                can_read_partial: true,
+            }
+        );
+    }
+
+    #[test]
+    fn read_only_camera_supports_reads_and_delete_only() {
+        let caps = capabilities_from_operations(&[
+            OperationCode::GetObjectInfo,
+            OperationCode::GetObject,
+            OperationCode::GetThumb,
+            OperationCode::DeleteObject,
+            OperationCode::GetPartialObject,
+        ]);
+        assert!(caps.can_delete && caps.can_read_thumbnails && caps.can_read_partial);
+        assert!(!caps.can_upload && !caps.can_create_folder && !caps.can_rename && !caps.can_move);
+    }
+
+    #[test]
+    fn send_object_info_alone_is_not_enough_to_write() {
+        let caps = capabilities_from_operations(&[OperationCode::SendObjectInfo]);
+        assert!(!caps.can_upload);
+        assert!(!caps.can_create_folder);
+    }
+}
@@ -200,8 +200,9 @@ impl MtpConnectionManager {
     ) -> Result<Vec<FileEntry>, MtpConnectionError> {
         let parent_path = normalize_mtp_path(path);
 
-        // Resolve the parent handle from cache (the scan walks top-down, so each
-        // dir was listed via its parent first and is cached).
+        // Resolve the parent handle. The scan walks top-down, so each dir was
+        // listed via its parent first and the walk below is a cache hit.
+        self.ensure_path_cached(device_id, storage_id, path).await?;
         let (device_arc, parent_handle) = {
             let devices = self.devices.lock().await;
             let entry = devices.get(device_id).ok_or_else(|| MtpConnectionError::NotConnected {
@@ -260,8 +261,8 @@ impl MtpConnectionManager {
                         cache_updates.push((child_path.clone(), info.handle));
                         entries.push(FileEntry {
                             size: if is_dir { None } else { Some(info.size) },
-                            modified_at: info.modified.map(convert_mtp_datetime),
-                            created_at: info.created.map(convert_mtp_datetime),
+                            modified_at: info.modified.and_then(convert_mtp_datetime),
+                            created_at: info.created.and_then(convert_mtp_datetime),
                             permissions: if is_dir { 0o755 } else { 0o644 },
                             icon_id: get_mtp_icon_id(is_dir, &info.filename),
                             extended_metadata_loaded: true,
@@ -369,6 +370,7 @@ impl MtpConnectionManager {
             "MTP list_directory [req#{}]: acquiring devices registry lock...",
             request_id
         );
+        self.ensure_path_cached(device_id, storage_id, path).await?;
         let (device_arc, parent_handle) = {
             let devices = self.devices.lock().await;
             debug!(
@@ -528,6 +530,7 @@ impl MtpConnectionManager {
         }
 
         // Get the device and resolve path to handle
+        self.ensure_path_cached(device_id, storage_id, path).await?;
         let (device_arc, parent_handle) = {
             let devices = self.devices.lock().await;
             let entry = devices.get(device_id).ok_or_else(|| MtpConnectionError::NotConnected {
@@ -604,8 +607,8 @@ impl MtpConnectionManager {
 
             entries.push(FileEntry {
                 size: if is_dir { None } else { Some(info.size) },
-                modified_at: info.modified.map(convert_mtp_datetime),
-                created_at: info.created.map(convert_mtp_datetime),
+                modified_at: info.modified.and_then(convert_mtp_datetime),
+                created_at: info.created.and_then(convert_mtp_datetime),
                 permissions: if is_dir { 0o755 } else { 0o644 },
                 icon_id: get_mtp_icon_id(is_dir, &info.filename),
                 extended_metadata_loaded: true,
@@ -662,7 +665,9 @@ impl MtpConnectionManager {
         Ok(entries)
     }
 
-    /// Update caches and sort entries after listing completes.
+    /// Update caches after listing completes. Entries stay in device order: the
+    /// listing pipeline sorts them by the pane's column and order (`sort_entries`),
+    /// and `resort_listing` re-sorts them like any other volume's.
     #[allow(
         clippy::too_many_arguments,
         reason = "internal helper, grouping into a struct would add complexity"
@@ -673,7 +678,7 @@ impl MtpConnectionManager {
         device_id: &str,
         storage_id: u32,
         parent_path: PathBuf,
-        mut entries: Vec<FileEntry>,
+        entries: Vec<FileEntry>,
         cache_updates: Vec<(PathBuf, ObjectHandle)>,
         call_start: Instant,
     ) -> Vec<FileEntry> {
@@ -691,13 +696,6 @@ impl MtpConnectionManager {
             }
         }
 
-        // Sort: directories first, then files, both alphabetically
-        entries.sort_by(|a, b| match (a.is_directory, b.is_directory) {
-            (true, false) => std::cmp::Ordering::Less,
-            (false, true) => std::cmp::Ordering::Greater,
-            _ => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
-        });
-
         // Store in listing cache
         {
             let devices = self.devices.lock().await;
@@ -748,21 +746,43 @@ impl MtpConnectionManager {
 
     /// Invalidates the listing cache for a specific directory.
     /// Call this after any operation that modifies the directory contents.
+    ///
+    /// `dir_path` is normalized the same way the listing keys its cache entry
+    /// (`normalize_mtp_path`), so `"DCIM"`, `"/DCIM"`, and `"/DCIM/"` all clear the
+    /// same entry. Callers hold paths in several shapes (event paths stripped of
+    /// their leading `/`, parents of normalized paths), and a key mismatch here
+    /// fails silently: the stale listing keeps serving until its TTL runs out.
     pub(super) async fn invalidate_listing_cache(&self, device_id: &str, storage_id: u32, dir_path: &Path) {
+        let key = normalize_mtp_path(dir_path.to_string_lossy().as_ref());
         let devices = self.devices.lock().await;
         if let Some(entry) = devices.get(device_id)
             && let Ok(mut cache_map) = entry.listing_cache.write()
             && let Some(storage_cache) = cache_map.get_mut(&storage_id)
-            && storage_cache.listings.remove(dir_path).is_some()
+            && storage_cache.listings.remove(&key).is_some()
         {
             debug!(
                 "Invalidated listing cache for {} on device {}",
-                dir_path.display(),
+                key.display(),
                 device_id
             );
         }
     }
 
+    /// Re-reads a directory from the device, skipping the listing cache, and caches the
+    /// fresh result. For an explicit user refresh: another app on the phone can change a
+    /// folder without the device sending an event, and the cached listing would otherwise
+    /// keep serving the old contents for up to `LISTING_CACHE_TTL_SECS`.
+    pub async fn refresh_directory(
+        &self,
+        device_id: &str,
+        storage_id: u32,
+        path: &str,
+    ) -> Result<Vec<FileEntry>, MtpConnectionError> {
+        self.invalidate_listing_cache(device_id, storage_id, Path::new(path))
+            .await;
+        self.list_directory(device_id, storage_id, path).await
+    }
+
     /// Re-resolves the object handles along `dir`'s path so a subsequent
     /// [`resolve_path_to_handle`](Self::resolve_path_to_handle) returns a FRESH
     /// handle for `dir`, not a stale cached one. Used by the upload path when
@@ -802,7 +822,10 @@ impl MtpConnectionManager {
         }
     }
 
-    /// Resolves a virtual path to an MTP object handle.
+    /// Resolves a virtual path to an MTP object handle from the path cache.
+    /// Sync, so it runs under the registry lock: callers first make the path
+    /// cached with [`ensure_path_cached`](Self::ensure_path_cached), which walks
+    /// down from the root when it isn't.
     pub(super) fn resolve_path_to_handle(
         &self,
         entry: &DeviceEntry,
@@ -824,13 +847,11 @@ impl MtpConnectionManager {
             return Ok(*handle);
         }
 
-        // Path not in cache: only paths that have been listed (browsed) are cached
-        Err(MtpConnectionError::Other {
+        // Path not in cache: the caller skipped `ensure_path_cached`, or the
+        // object vanished between the walk and this lookup.
+        Err(MtpConnectionError::ObjectNotFound {
             device_id: entry.info.id.clone(),
-            message: format!(
-                "Path not in cache: {}. Navigate through parent directories first.",
-                path.display()
-            ),
+            path: path.display().to_string(),
         })
     }
 
@@ -925,8 +946,8 @@ fn convert_object_infos(
 
         entries.push(FileEntry {
             size: if is_dir { None } else { Some(info.size) },
-            modified_at: info.modified.map(convert_mtp_datetime),
-            created_at: info.created.map(convert_mtp_datetime),
+            modified_at: info.modified.and_then(convert_mtp_datetime),
+            created_at: info.created.and_then(convert_mtp_datetime),
             permissions: if is_dir { 0o755 } else { 0o644 },
             icon_id: get_mtp_icon_id(is_dir, &info.filename),
             extended_metadata_loaded: true,
@@ -12,7 +12,6 @@ use tokio::sync::{Mutex, broadcast};
 
 use mtp_rs::ObjectHandle;
 
-use super::cache::EVENT_DEBOUNCE_MS;
 use super::{MtpConnectionManager, connection_manager, normalize_mtp_path};
 use crate::file_system::listing::{get_listings_by_volume_prefix, update_listing_entries};
 use crate::file_system::{FileEntry, compute_diff};
@@ -138,11 +137,13 @@ impl MtpConnectionManager {
                 debug!("MTP object removed: {:?} on {}", handle, device_id);
                 Self::emit_directory_changed(device_id, app);
                 Self::feed_index_removed(device_id, handle);
+                Self::drop_cached_thumbnail(device_id, handle);
             }
             DeviceEvent::ObjectInfoChanged { handle } => {
                 debug!("MTP object changed: {:?} on {}", handle, device_id);
                 Self::emit_change_for_handle(device_id, handle, app);
                 Self::feed_index_added_or_changed(device_id, handle);
+                Self::drop_cached_thumbnail(device_id, handle);
             }
             DeviceEvent::StorageInfoChanged { storage_id } => {
                 debug!("MTP storage info changed: {:?} on {}", storage_id, device_id);
@@ -189,6 +190,15 @@ impl MtpConnectionManager {
         }
     }
 
+    /// Forget `handle`'s cached thumbnail: an edited photo has a new one, and a removed
+    /// object's handle can come back as a different object.
+    fn drop_cached_thumbnail(device_id: &str, handle: ObjectHandle) {
+        let device_id = device_id.to_string();
+        tokio::spawn(async move {
+            connection_manager().invalidate_thumbnail(&device_id, handle).await;
+        });
+    }
+
     /// Feed an `ObjectAdded` / `ObjectInfoChanged` into the per-volume index, if
     /// any storage on this device is indexed.
     ///
@@ -297,7 +307,7 @@ impl MtpConnectionManager {
             let listings = get_listings_by_volume_prefix(&device_id);
             let mut storage_ids: Vec<u32> = listings
                 .iter()
-                .filter_map(|(_, volume_id, _, _)| crate::mtp::identity::storage_id_of_volume(volume_id))
+                .filter_map(|(_, volume_id, _, _, _)| crate::mtp::identity::storage_id_of_volume(volume_id))
                 .collect();
             storage_ids.sort_unstable();
             storage_ids.dedup();
@@ -343,13 +353,18 @@ impl MtpConnectionManager {
     ///
     /// Goes through the same debouncer as the blanket path so a burst of resolved
     /// events still collapses to one re-read per window.
-    fn emit_directory_changed_targeted(device_id: &str, storage_id: u32, affected_dir: &Path, app: &AppHandle) -> bool {
+    pub(super) fn emit_directory_changed_targeted(
+        device_id: &str,
+        storage_id: u32,
+        affected_dir: &Path,
+        app: &AppHandle,
+    ) -> bool {
         // Match the affected dir against this device's open listings by their
         // normalized inner MTP path. Listings carry a `mtp://…` or `/`-rooted
         // path; `listing_inner_mtp_path` reduces both to the comparable form.
-        let listings: Vec<(String, String, PathBuf, Vec<FileEntry>)> = get_listings_by_volume_prefix(device_id)
+        let listings: Vec<(String, String, PathBuf, Vec<FileEntry>, u64)> = get_listings_by_volume_prefix(device_id)
             .into_iter()
-            .filter(|(_, volume_id, path, _)| {
+            .filter(|(_, volume_id, path, _, _)| {
                 crate::mtp::identity::storage_id_of_volume(volume_id) == Some(storage_id)
                     && listing_inner_mtp_path(volume_id, path).as_deref() == Some(affected_dir)
             })
@@ -362,6 +377,7 @@ impl MtpConnectionManager {
         if !connection_manager().event_debouncer.should_emit(device_id) {
             // Within the debounce window: schedule a trailing targeted re-emit so
             // the last event in a burst isn't dropped.
+            let trailing_delay = connection_manager().event_debouncer.duration() + Duration::from_millis(50);
             debug!(
                 "MTP targeted refresh: DEBOUNCED for {}:{} dir={}, scheduling trailing emit",
                 device_id,
@@ -372,7 +388,7 @@ impl MtpConnectionManager {
             let affected_dir = affected_dir.to_path_buf();
             let app = app.clone();
             tokio::spawn(async move {
-                tokio::time::sleep(Duration::from_millis(EVENT_DEBOUNCE_MS + 50)).await;
+                tokio::time::sleep(trailing_delay).await;
                 Self::emit_directory_changed_targeted(&device_id, storage_id, &affected_dir, &app);
             });
             return true;
@@ -402,14 +418,16 @@ impl MtpConnectionManager {
         // When suppressed, schedule a trailing emit after the debounce window
         // so the last event in a burst is never permanently dropped.
         if !connection_manager().event_debouncer.should_emit(device_id) {
+            let debounce = connection_manager().event_debouncer.duration();
+            let trailing_delay = debounce + Duration::from_millis(50);
             debug!(
-                "MTP event loop: directory change DEBOUNCED for device={} (within {}ms window), scheduling trailing emit",
-                device_id, EVENT_DEBOUNCE_MS
+                "MTP event loop: directory change DEBOUNCED for device={} (within {:?} window), scheduling trailing emit",
+                device_id, debounce
             );
             let device_id_owned = device_id.to_string();
             let app_clone = app.clone();
             tokio::spawn(async move {
-                tokio::time::sleep(Duration::from_millis(EVENT_DEBOUNCE_MS + 50)).await;
+                tokio::time::sleep(trailing_delay).await;
                 // Re-emit; this goes through the debouncer again (which will pass
                 // since the window has expired) to avoid duplicate processing.
                 Self::emit_directory_changed(&device_id_owned, &app_clone);
@@ -450,8 +468,8 @@ impl MtpConnectionManager {
     /// 3. Compute the diff between old and new entries
     /// 4. Update LISTING_CACHE with new entries
     /// 5. Emit directory-diff event
-    async fn compute_and_emit_diffs(device_id: &str, listings: Vec<(String, String, PathBuf, Vec<FileEntry>)>) {
-        for (listing_id, volume_id, path, old_entries) in listings {
+    async fn compute_and_emit_diffs(device_id: &str, listings: Vec<(String, String, PathBuf, Vec<FileEntry>, u64)>) {
+        for (listing_id, volume_id, path, old_entries, generation) in listings {
             // Extract storage_id from volume_id (format: "{device_id}:{storage}").
             // rsplit-based parse via identity tolerates a `:` in a serial device id.
             let Some(storage_id) = crate::mtp::identity::storage_id_of_volume(&volume_id) else {
@@ -470,11 +488,11 @@ impl MtpConnectionManager {
                 .unwrap_or_default();
 
             // Invalidate the MTP listing cache before re-reading so we get fresh data.
-            // Must use the normalized MTP path (for example, "/Documents"), not the raw LISTING_CACHE
-            // path (for example, "mtp://mtp-device/65537/Documents"), because that's what list_directory
-            // uses as the cache key.
+            // Must use the inner MTP path (for example, "Documents"), not the raw LISTING_CACHE
+            // path (for example, "mtp://mtp-device/65537/Documents"); `invalidate_listing_cache`
+            // normalizes it to the "/Documents" key that list_directory uses.
             connection_manager()
-                .invalidate_listing_cache(device_id, storage_id, &normalize_mtp_path(&mtp_path))
+                .invalidate_listing_cache(device_id, storage_id, Path::new(&mtp_path))
                 .await;
 
             // Re-read the directory from the MTP device
@@ -499,8 +517,11 @@ impl MtpConnectionManager {
                 continue;
             }
 
-            // Update LISTING_CACHE with new entries
-            update_listing_entries(&listing_id, new_entries);
+            // Update LISTING_CACHE with new entries; skip the diff if the listing was replaced
+            // while the device was re-read (its diff would patch a listing that's gone)
+            if !update_listing_entries(&listing_id, generation, new_entries) {
+                continue;
+            }
 
             // Route through the coalescer so bursts of MTP events (large delete,
             // many file copies) don't fire one IPC event per change.
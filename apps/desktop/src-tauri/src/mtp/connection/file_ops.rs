@@ -49,7 +49,8 @@ impl MtpConnectionManager {
     /// lock is released before returning, so nothing is held between windows.
     /// `window_size` is the per-window `GetPartialObject64` `max_bytes`
     /// (`MTP_READ_WINDOW` in production; tests shrink it); `offset` is the
-    /// starting byte (0 for a fresh read, non-zero to resume).
+    /// starting byte (0 for a fresh read, non-zero to resume). A resume whose
+    /// path fell out of the cache (session reset) re-lists its ancestors first.
     ///
     /// Takes NO `foreground_guard`: a transfer is a *background* user of the
     /// device gate (it yields TO foreground), so raising `foreground_pending`
@@ -71,6 +72,7 @@ impl MtpConnectionManager {
         );
 
         // Get the device and resolve path to handle.
+        self.ensure_path_cached(device_id, storage_id, path).await?;
         let (device_arc, object_handle) = {
             let devices = self.devices.lock().await;
             let entry = devices.get(device_id).ok_or_else(|| MtpConnectionError::NotConnected {
@@ -166,6 +168,7 @@ impl MtpConnectionManager {
         offset: u64,
         len: u32,
     ) -> Result<Vec<u8>, MtpConnectionError> {
+        self.ensure_path_cached(device_id, storage_id, path).await?;
         let (device_arc, object_handle, storage_cache, cached) = {
             let devices = self.devices.lock().await;
             let entry = devices.get(device_id).ok_or_else(|| MtpConnectionError::NotConnected {
@@ -250,6 +253,7 @@ impl MtpConnectionManager {
         );
 
         // Get device and resolve parent folder
+        self.ensure_path_cached(device_id, storage_id, dest_folder).await?;
         let (device_arc, parent_handle) = {
             let devices = self.devices.lock().await;
             let entry = devices.get(device_id).ok_or_else(|| MtpConnectionError::NotConnected {
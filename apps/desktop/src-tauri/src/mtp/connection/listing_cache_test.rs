@@ -0,0 +1,122 @@
+//! The listing cache gives way to a re-read exactly when it's told to.
+//!
+//! `list_directory` serves a cached listing for `LISTING_CACHE_TTL_SECS`, so a
+//! folder another app changed on the phone stays stale until something clears
+//! the entry. These tests change the backing dir behind the cache's back and
+//! assert that an invalidation (in any of the path shapes callers pass) or an
+//! explicit `refresh_directory` makes the next listing come from the device.
+
+use super::connection_manager;
+use crate::mtp::virtual_device::{
+    VirtualDeviceFixture, rescan_virtual_device, setup_virtual_mtp_device, unregister_virtual_mtp_device,
+    virtual_device_test_lock,
+};
+use std::path::Path;
+
+/// A connected virtual device, torn down (disconnect + unregister) by `teardown`.
+struct Device {
+    id: String,
+    storage_id: u32,
+    fixture: VirtualDeviceFixture,
+}
+
+async fn connect_device() -> Device {
+    let fixture = setup_virtual_mtp_device();
+
+    let device_id = crate::mtp::list_mtp_devices()
+        .into_iter()
+        .find(|d| d.location_id == fixture.location_id)
+        .map(|d| d.id)
+        .expect("the virtual device must appear in discovery");
+    let info = connection_manager()
+        .connect(&device_id, None)
+        .await
+        .expect("virtual-mtp connect should succeed");
+    let storage_id = info.storages.first().expect("a storage").id;
+    connection_manager()
+        .list_directory(&device_id, storage_id, "/")
+        .await
+        .expect("list root should succeed");
+    Device {
+        id: device_id,
+        storage_id,
+        fixture,
+    }
+}
+
+async fn teardown(device: Device) {
+    connection_manager()
+        .disconnect(&device.id, None, super::MtpDisconnectReason::User)
+        .await
+        .ok();
+    unregister_virtual_mtp_device(device.fixture.location_id);
+}
+
+async fn names_in(device: &Device, path: &str) -> Vec<String> {
+    connection_manager()
+        .list_directory(&device.id, device.storage_id, path)
+        .await
+        .expect("list_directory should succeed")
+        .into_iter()
+        .map(|e| e.name)
+        .collect()
+}
+
+/// Writes `name` into `/Documents` on the device without going through Cmdr,
+/// like another app on the phone would.
+fn add_file_behind_the_cache(device: &Device, name: &str) {
+    std::fs::write(device.fixture.root().join("internal/Documents").join(name), b"external")
+        .expect("seed file on device");
+    rescan_virtual_device();
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn invalidation_makes_the_next_list_reread_for_every_path_shape() {
+    let _guard = virtual_device_test_lock().lock().await;
+    let device = connect_device().await;
+
+    for (i, shape) in ["/Documents", "Documents", "/Documents/"].into_iter().enumerate() {
+        names_in(&device, "/Documents").await;
+        let name = format!("external-{i}.txt");
+        add_file_behind_the_cache(&device, &name);
+        assert!(
+            !names_in(&device, "/Documents").await.contains(&name),
+            "within the TTL the cached listing is served"
+        );
+
+        connection_manager()
+            .invalidate_listing_cache(&device.id, device.storage_id, Path::new(shape))
+            .await;
+
+        assert!(
+            names_in(&device, "/Documents").await.contains(&name),
+            "invalidating via {shape:?} must clear the `/Documents` entry so the list re-reads"
+        );
+    }
+
+    teardown(device).await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn refresh_directory_rereads_and_repopulates_the_cache() {
+    let _guard = virtual_device_test_lock().lock().await;
+    let device = connect_device().await;
+
+    names_in(&device, "/Documents").await;
+    add_file_behind_the_cache(&device, "fresh.txt");
+
+    let refreshed: Vec<String> = connection_manager()
+        .refresh_directory(&device.id, device.storage_id, "Documents")
+        .await
+        .expect("refresh_directory should succeed")
+        .into_iter()
+        .map(|e| e.name)
+        .collect();
+    assert!(refreshed.contains(&"fresh.txt".to_string()));
+    assert!(
+        names_in(&device, "/Documents").await.contains(&"fresh.txt".to_string()),
+        "the refreshed listing must replace the cached one"
+    );
+
+    teardown(device).await;
+}
@@ -0,0 +1,534 @@
+//! An in-memory MTP device for tests: a flat object table behind [`MtpBackend`]. No USB, no
+//! `virtual-mtp` feature, runs on every platform.
+//!
+//! It keeps the device rules the connection layer depends on: handles are never reused within
+//! one mock, and a non-empty folder refuses deletion.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use mtp_rs::{CancelToken, ObjectHandle};
+
+use super::backend::{BackendObject, MtpBackend};
+use super::errors::MtpConnectionError;
+use crate::ignore_poison::IgnorePoison;
+
+const MOCK_DEVICE_ID: &str = "mtp-mock";
+
+/// A call the mock saw, in order. Tests assert on this to check ordering.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum MockOp {
+    List(ObjectHandle),
+    Delete(ObjectHandle),
+    Upload(String),
+    CreateFolder(String),
+}
+
+struct MockObject {
+    storage_id: u32,
+    parent: ObjectHandle,
+    name: String,
+    /// `None` for folders.
+    data: Option<Vec<u8>>,
+}
+
+struct MockState {
+    next_handle: u64,
+    objects: HashMap<ObjectHandle, MockObject>,
+    ops: Vec<MockOp>,
+    /// Flips the flag once this many deletes have gone through, to cancel mid-operation.
+    cancel_after_deletes: Option<(usize, Arc<AtomicBool>)>,
+}
+
+pub(super) struct MockMtpDevice {
+    state: Mutex<MockState>,
+}
+
+impl MockMtpDevice {
+    pub(super) fn new() -> Self {
+        Self {
+            state: Mutex::new(MockState {
+                next_handle: 1,
+                objects: HashMap::new(),
+                ops: Vec::new(),
+                cancel_after_deletes: None,
+            }),
+        }
+    }
+
+    fn insert(&self, storage_id: u32, parent: ObjectHandle, name: &str, data: Option<Vec<u8>>) -> ObjectHandle {
+        let mut state = self.state.lock_ignore_poison();
+        let handle = ObjectHandle(state.next_handle);
+        state.next_handle += 1;
+        state.objects.insert(
+            handle,
+            MockObject {
+                storage_id,
+                parent,
+                name: name.to_string(),
+                data,
+            },
+        );
+        handle
+    }
+
+    /// Seeds a folder without queueing an event.
+    pub(super) fn add_folder(&self, storage_id: u32, parent: ObjectHandle, name: &str) -> ObjectHandle {
+        self.insert(storage_id, parent, name, None)
+    }
+
+    /// Seeds a file without queueing an event.
+    pub(super) fn add_file(&self, storage_id: u32, parent: ObjectHandle, name: &str, data: &[u8]) -> ObjectHandle {
+        self.insert(storage_id, parent, name, Some(data.to_vec()))
+    }
+
+    /// The handle of `parent`'s child named `name`, if there is one.
+    pub(super) fn child(&self, parent: ObjectHandle, name: &str) -> Option<ObjectHandle> {
+        let state = self.state.lock_ignore_poison();
+        state
+            .objects
+            .iter()
+            .find(|(_, o)| o.parent == parent && o.name == name)
+            .map(|(h, _)| *h)
+    }
+
+    /// A file's bytes; `None` for a folder or a missing handle.
+    pub(super) fn data(&self, handle: ObjectHandle) -> Option<Vec<u8>> {
+        self.state.lock_ignore_poison().objects.get(&handle)?.data.clone()
+    }
+
+    pub(super) fn contains(&self, handle: ObjectHandle) -> bool {
+        self.state.lock_ignore_poison().objects.contains_key(&handle)
+    }
+
+    pub(super) fn ops(&self) -> Vec<MockOp> {
+        self.state.lock_ignore_poison().ops.clone()
+    }
+
+    pub(super) fn cancel_after_deletes(&self, count: usize, flag: Arc<AtomicBool>) {
+        self.state.lock_ignore_poison().cancel_after_deletes = Some((count, flag));
+    }
+
+    fn not_found(handle: ObjectHandle) -> MtpConnectionError {
+        MtpConnectionError::ObjectNotFound {
+            device_id: MOCK_DEVICE_ID.to_string(),
+            path: format!("handle {}", handle.0),
+        }
+    }
+
+    fn cancelled() -> MtpConnectionError {
+        MtpConnectionError::Cancelled {
+            device_id: MOCK_DEVICE_ID.to_string(),
+            message: "Mock operation cancelled".to_string(),
+        }
+    }
+}
+
+fn backend_object(handle: ObjectHandle, object: &MockObject) -> BackendObject {
+    BackendObject {
+        handle,
+        parent: object.parent,
+        name: object.name.clone(),
+        is_folder: object.data.is_none(),
+        size: object.data.as_ref().map_or(0, |d| d.len() as u64),
+    }
+}
+
+impl MtpBackend for MockMtpDevice {
+    fn device_id(&self) -> &str {
+        MOCK_DEVICE_ID
+    }
+
+    async fn object_info(&self, storage_id: u32, handle: ObjectHandle) -> Result<BackendObject, MtpConnectionError> {
+        let state = self.state.lock_ignore_poison();
+        state
+            .objects
+            .get(&handle)
+            .filter(|o| o.storage_id == storage_id)
+            .map(|o| backend_object(handle, o))
+            .ok_or_else(|| Self::not_found(handle))
+    }
+
+    async fn list_objects(
+        &self,
+        storage_id: u32,
+        parent: ObjectHandle,
+        cancel: Option<&CancelToken>,
+    ) -> Result<Vec<BackendObject>, MtpConnectionError> {
+        if cancel.is_some_and(CancelToken::is_cancelled) {
+            return Err(Self::cancelled());
+        }
+        let mut state = self.state.lock_ignore_poison();
+        state.ops.push(MockOp::List(parent));
+        let mut children: Vec<BackendObject> = state
+            .objects
+            .iter()
+            .filter(|(_, o)| o.storage_id == storage_id && o.parent == parent)
+            .map(|(h, o)| backend_object(*h, o))
+            .collect();
+        // Real devices list in handle order, which is creation order.
+        children.sort_by_key(|o| o.handle.0);
+        Ok(children)
+    }
+
+    async fn download_file(
+        &self,
+        storage_id: u32,
+        handle: ObjectHandle,
+        offset: u64,
+        len: u32,
+    ) -> Result<Vec<u8>, MtpConnectionError> {
+        let state = self.state.lock_ignore_poison();
+        let data = state
+            .objects
+            .get(&handle)
+            .filter(|o| o.storage_id == storage_id)
+            .and_then(|o| o.data.as_ref())
+            .ok_or_else(|| Self::not_found(handle))?;
+        let start = usize::try_from(offset).unwrap_or(usize::MAX).min(data.len());
+        let end = start.saturating_add(len as usize).min(data.len());
+        Ok(data[start..end].to_vec())
+    }
+
+    async fn upload_from_file(
+        &self,
+        storage_id: u32,
+        parent: ObjectHandle,
+        name: &str,
+        local: &Path,
+    ) -> Result<ObjectHandle, MtpConnectionError> {
+        let data = std::fs::read(local).map_err(|e| MtpConnectionError::Other {
+            device_id: MOCK_DEVICE_ID.to_string(),
+            message: e.to_string(),
+        })?;
+        self.state
+            .lock_ignore_poison()
+            .ops
+            .push(MockOp::Upload(name.to_string()));
+        Ok(self.insert(storage_id, parent, name, Some(data)))
+    }
+
+    async fn create_folder(
+        &self,
+        storage_id: u32,
+        parent: ObjectHandle,
+        name: &str,
+    ) -> Result<ObjectHandle, MtpConnectionError> {
+        self.state
+            .lock_ignore_poison()
+            .ops
+            .push(MockOp::CreateFolder(name.to_string()));
+        Ok(self.insert(storage_id, parent, name, None))
+    }
+
+    async fn delete_object(
+        &self,
+        storage_id: u32,
+        handle: ObjectHandle,
+        cancel: Option<&CancelToken>,
+    ) -> Result<(), MtpConnectionError> {
+        if cancel.is_some_and(CancelToken::is_cancelled) {
+            return Err(Self::cancelled());
+        }
+        {
+            let mut state = self.state.lock_ignore_poison();
+            if !state.objects.get(&handle).is_some_and(|o| o.storage_id == storage_id) {
+                return Err(Self::not_found(handle));
+            }
+            if state.objects.values().any(|o| o.parent == handle) {
+                return Err(MtpConnectionError::Protocol {
+                    device_id: MOCK_DEVICE_ID.to_string(),
+                    message: format!("Folder {} is not empty", handle.0),
+                });
+            }
+            state.objects.remove(&handle);
+            state.ops.push(MockOp::Delete(handle));
+            let deletes = state.ops.iter().filter(|op| matches!(op, MockOp::Delete(_))).count();
+            if let Some((after, flag)) = &state.cancel_after_deletes
+                && deletes >= *after
+            {
+                flag.store(true, Ordering::Relaxed);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::backend::{delete_tree, move_by_copy};
+    use super::super::cache::PathHandleCache;
+    use super::*;
+    use std::sync::atomic::AtomicU64;
+
+    const STORAGE: u32 = 65537;
+
+    /// `/DCIM/Camera/{a.jpg, b.jpg}` plus `/DCIM/notes.txt` and a sibling `/Music`.
+    struct Tree {
+        dcim: ObjectHandle,
+        camera: ObjectHandle,
+        a: ObjectHandle,
+        b: ObjectHandle,
+        notes: ObjectHandle,
+        music: ObjectHandle,
+    }
+
+    fn seed(device: &MockMtpDevice) -> Tree {
+        let dcim = device.add_folder(STORAGE, ObjectHandle::ROOT, "DCIM");
+        let camera = device.add_folder(STORAGE, dcim, "Camera");
+        let a = device.add_file(STORAGE, camera, "a.jpg", b"aaaa");
+        let b = device.add_file(STORAGE, camera, "b.jpg", b"bb");
+        let notes = device.add_file(STORAGE, dcim, "notes.txt", b"hello");
+        let music = device.add_folder(STORAGE, ObjectHandle::ROOT, "Music");
+        Tree {
+            dcim,
+            camera,
+            a,
+            b,
+            notes,
+            music,
+        }
+    }
+
+    fn cached_paths(tree: &Tree) -> PathHandleCache {
+        let mut cache = PathHandleCache::default();
+        cache.insert(PathBuf::from("/DCIM"), tree.dcim);
+        cache.insert(PathBuf::from("/DCIM/Camera"), tree.camera);
+        cache.insert(PathBuf::from("/DCIM/Camera/a.jpg"), tree.a);
+        cache.insert(PathBuf::from("/DCIM/Camera/b.jpg"), tree.b);
+        cache.insert(PathBuf::from("/DCIM/notes.txt"), tree.notes);
+        cache.insert(PathBuf::from("/Music"), tree.music);
+        cache
+    }
+
+    #[tokio::test]
+    async fn delete_tree_removes_children_before_their_parent() {
+        let device = MockMtpDevice::new();
+        let tree = seed(&device);
+        let mut deleted = Vec::new();
+
+        delete_tree(&device, STORAGE, tree.dcim, Path::new("/DCIM"), None, &mut deleted)
+            .await
+            .unwrap();
+
+        let deletes: Vec<ObjectHandle> = device
+            .ops()
+            .into_iter()
+            .filter_map(|op| match op {
+                MockOp::Delete(h) => Some(h),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(device.ops()[0], MockOp::List(tree.dcim));
+        assert_eq!(deletes, vec![tree.a, tree.b, tree.camera, tree.notes, tree.dcim]);
+        assert_eq!(
+            deleted,
+            [
+                "/DCIM/Camera/a.jpg",
+                "/DCIM/Camera/b.jpg",
+                "/DCIM/Camera",
+                "/DCIM/notes.txt",
+                "/DCIM"
+            ]
+            .map(PathBuf::from)
+        );
+        assert!(device.contains(tree.music));
+    }
+
+    #[tokio::test]
+    async fn delete_tree_stops_between_children_once_cancelled() {
+        let device = MockMtpDevice::new();
+        let tree = seed(&device);
+        let flag = Arc::new(AtomicBool::new(false));
+        device.cancel_after_deletes(1, Arc::clone(&flag));
+        let cancel = CancelToken::from_arc(flag);
+        let mut deleted = Vec::new();
+
+        let result = delete_tree(
+            &device,
+            STORAGE,
+            tree.dcim,
+            Path::new("/DCIM"),
+            Some(&cancel),
+            &mut deleted,
+        )
+        .await;
+
+        assert!(matches!(result, Err(MtpConnectionError::Cancelled { .. })));
+        assert_eq!(deleted, vec![PathBuf::from("/DCIM/Camera/a.jpg")]);
+        assert!(!device.contains(tree.a));
+        assert!(device.contains(tree.b));
+        assert!(device.contains(tree.dcim));
+    }
+
+    #[tokio::test]
+    async fn deleted_paths_drop_out_of_the_path_cache_even_after_a_partial_delete() {
+        let device = MockMtpDevice::new();
+        let tree = seed(&device);
+        let mut cache = cached_paths(&tree);
+        let flag = Arc::new(AtomicBool::new(false));
+        device.cancel_after_deletes(3, Arc::clone(&flag));
+        let cancel = CancelToken::from_arc(flag);
+        let mut deleted = Vec::new();
+
+        let _ = delete_tree(
+            &device,
+            STORAGE,
+            tree.dcim,
+            Path::new("/DCIM"),
+            Some(&cancel),
+            &mut deleted,
+        )
+        .await;
+        for path in &deleted {
+            cache.remove_path(path);
+        }
+
+        // The Camera subtree is gone from both directions; what survived on the device is
+        // still resolvable.
+        for gone in [tree.a, tree.b, tree.camera] {
+            assert!(!cache.handle_to_path.contains_key(&gone));
+        }
+        assert!(!cache.path_to_handle.contains_key(Path::new("/DCIM/Camera")));
+        assert_eq!(
+            cache.path_to_handle.get(Path::new("/DCIM/notes.txt")),
+            Some(&tree.notes)
+        );
+        assert_eq!(cache.path_to_handle.get(Path::new("/DCIM")), Some(&tree.dcim));
+        assert_eq!(cache.path_to_handle.get(Path::new("/Music")), Some(&tree.music));
+    }
+
+    #[tokio::test]
+    async fn move_by_copy_copies_the_tree_before_deleting_the_source() {
+        let device = MockMtpDevice::new();
+        let tree = seed(&device);
+        let spool = tempfile::tempdir().unwrap();
+        let mut deleted = Vec::new();
+        let copied = AtomicU64::new(0);
+
+        // A 3-byte window makes the 4- and 5-byte files span several reads.
+        let copy = move_by_copy(
+            &device,
+            STORAGE,
+            tree.dcim,
+            Path::new("/DCIM"),
+            STORAGE,
+            tree.music,
+            spool.path(),
+            3,
+            &|bytes| {
+                copied.fetch_add(bytes, Ordering::Relaxed);
+            },
+            &mut deleted,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(device.child(tree.music, "DCIM"), Some(copy));
+        let camera = device.child(copy, "Camera").unwrap();
+        assert_eq!(device.data(device.child(camera, "a.jpg").unwrap()).unwrap(), b"aaaa");
+        assert_eq!(device.data(device.child(camera, "b.jpg").unwrap()).unwrap(), b"bb");
+        assert_eq!(device.data(device.child(copy, "notes.txt").unwrap()).unwrap(), b"hello");
+        assert!(!device.contains(tree.dcim));
+        assert_eq!(deleted.last(), Some(&PathBuf::from("/DCIM")));
+        assert_eq!(
+            copied.load(Ordering::Relaxed),
+            11,
+            "every file's bytes are reported once"
+        );
+
+        let ops = device.ops();
+        let last_write = ops
+            .iter()
+            .rposition(|op| matches!(op, MockOp::Upload(_) | MockOp::CreateFolder(_)))
+            .unwrap();
+        let first_delete = ops.iter().position(|op| matches!(op, MockOp::Delete(_))).unwrap();
+        assert!(
+            last_write < first_delete,
+            "nothing is deleted until the copy is complete: {ops:?}"
+        );
+        assert_eq!(
+            std::fs::read_dir(spool.path()).unwrap().count(),
+            0,
+            "spool files are removed"
+        );
+    }
+
+    #[tokio::test]
+    async fn move_by_copy_across_storages_lands_on_the_target_storage() {
+        const SD_CARD: u32 = 131073;
+        let device = MockMtpDevice::new();
+        let tree = seed(&device);
+        let spool = tempfile::tempdir().unwrap();
+        let mut deleted = Vec::new();
+
+        let copy = move_by_copy(
+            &device,
+            STORAGE,
+            tree.camera,
+            Path::new("/DCIM/Camera"),
+            SD_CARD,
+            ObjectHandle::ROOT,
+            spool.path(),
+            3,
+            &|_| {},
+            &mut deleted,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(device.object_info(SD_CARD, copy).await.unwrap().name, "Camera");
+        assert!(device.object_info(STORAGE, copy).await.is_err());
+        let a = device.child(copy, "a.jpg").unwrap();
+        assert_eq!(device.object_info(SD_CARD, a).await.unwrap().size, 4);
+        assert!(!device.contains(tree.camera));
+        assert!(device.contains(tree.notes), "siblings on the source storage stay");
+    }
+
+    #[tokio::test]
+    async fn move_by_copy_failure_keeps_the_source_and_removes_the_partial_copy() {
+        let device = MockMtpDevice::new();
+        let tree = seed(&device);
+        let missing_spool = Path::new("/nonexistent/cmdr-spool");
+        let mut deleted = Vec::new();
+
+        let result = move_by_copy(
+            &device,
+            STORAGE,
+            tree.dcim,
+            Path::new("/DCIM"),
+            STORAGE,
+            tree.music,
+            missing_spool,
+            3,
+            &|_| {},
+            &mut deleted,
+        )
+        .await;
+
+        assert!(result.is_err());
+        assert!(deleted.is_empty());
+        for kept in [tree.dcim, tree.camera, tree.a, tree.b, tree.notes] {
+            assert!(device.contains(kept));
+        }
+        assert_eq!(
+            device.child(tree.music, "DCIM"),
+            None,
+            "the half-made copy is cleaned up"
+        );
+    }
+
+    #[tokio::test]
+    async fn non_empty_folder_refuses_a_direct_delete() {
+        let device = MockMtpDevice::new();
+        let tree = seed(&device);
+
+        let result = device.delete_object(STORAGE, tree.camera, None).await;
+
+        assert!(matches!(result, Err(MtpConnectionError::Protocol { .. })));
+        assert!(device.contains(tree.camera));
+    }
+}
@@ -12,21 +12,31 @@
 //! unified diff system as local file watching. This provides smooth UI updates
 //! without full directory reloads.
 
+mod backend;
 mod bulk_ops;
 mod cache;
+mod capabilities;
 mod directory_ops;
 pub(super) mod errors;
 mod event_loop;
 mod file_ops;
 mod handle_resolver;
+/// Drives a virtual MTP device, so it carries that feature gate.
+#[cfg(all(test, feature = "virtual-mtp"))]
+mod listing_cache_test;
+/// In-memory `MtpBackend` for tests that shouldn't need a device or the `virtual-mtp` feature.
+#[cfg(test)]
+mod mock_device;
 mod mutation_ops;
 /// Every test here drives a virtual MTP device, so it carries that feature gate.
 #[cfg(all(test, feature = "virtual-mtp"))]
 mod path_cache_sync_test;
+mod path_resolver;
 mod scheduler;
 mod session_reset;
+mod thumbnails;
 
-use cache::{EVENT_DEBOUNCE_MS, EventDebouncer, ListingCache, PathHandleCache};
+use cache::{EventDebouncer, ListingCache, PathHandleCache, ThumbnailCache};
 pub use errors::MtpConnectionError;
 use errors::map_mtp_error;
 pub(crate) use file_ops::MtpReadSession;
@@ -43,7 +53,7 @@ use tauri::AppHandle;
 use tauri_specta::Event;
 use tokio::sync::{Mutex, broadcast};
 
-use super::types::{MtpDeviceInfo, MtpStorageInfo};
+use super::types::{MtpDeviceCapabilities, MtpDeviceInfo, MtpStorageInfo};
 use crate::file_system::{MtpVolume, get_volume_manager};
 
 /// Per-USB-transfer timeout handed to mtp-rs, the ONE bound that can stop a
@@ -127,6 +137,18 @@ pub struct MtpStorageRemoved {
     pub storage_id: u32,
 }
 
+/// Emitted after each file a copy-based move between two storages of a device puts on the
+/// destination. `bytes_total` is `0` when the source couldn't be sized up front.
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type, tauri_specta::Event)]
+#[serde(rename_all = "camelCase")]
+pub struct MtpStorageMoveProgress {
+    pub device_id: String,
+    pub from_storage_id: u32,
+    pub to_storage_id: u32,
+    pub bytes_done: u64,
+    pub bytes_total: u64,
+}
+
 /// Emitted when opening a device fails because another process holds exclusive
 /// access (typically `ptpcamerad` on macOS). The frontend shows the workaround
 /// dialog with `blocking_process` (the claiming process name, from `ioreg`).
@@ -190,6 +212,8 @@ struct DeviceEntry {
     info: MtpDeviceInfo,
     /// Cached storage information.
     storages: Vec<MtpStorageInfo>,
+    /// Operations the device advertised at connect. Fixed for the session.
+    capabilities: MtpDeviceCapabilities,
     /// Path-to-handle cache per storage.
     path_cache: RwLock<HashMap<u32, PathHandleCache>>,
     /// Directory listing cache per storage.
@@ -210,6 +234,9 @@ struct DeviceEntry {
     /// on disconnect. Held behind its own `Arc` so a reader can clone the handle
     /// out without re-locking `devices` while it owns the device lock.
     storage_cache: Arc<RwLock<HashMap<u32, Arc<mtp_rs::Storage>>>>,
+    /// Recent `GetThumb` results. Dropped with the entry on disconnect or session reset, since
+    /// handles don't survive either; single entries go on `ObjectInfoChanged` / `ObjectRemoved`.
+    thumbnail_cache: std::sync::Mutex<ThumbnailCache>,
     /// Test-only tally of `GetStorageInfo` round trips the read paths issued for
     /// this device. Pins the "one storage lookup per device, not per read"
     /// contract that `read_range_direct` exists to hold.
@@ -253,7 +280,7 @@ impl MtpConnectionManager {
         Self {
             devices: Mutex::new(HashMap::new()),
             event_loop_shutdown: RwLock::new(HashMap::new()),
-            event_debouncer: EventDebouncer::new(Duration::from_millis(EVENT_DEBOUNCE_MS)),
+            event_debouncer: EventDebouncer::from_settings(),
         }
     }
 
@@ -396,6 +423,8 @@ impl MtpConnectionManager {
             "Device '{}' write support: {} (can_upload={}, can_delete={}, can_rename={})",
             mtp_info.model, device_supports_write, caps.can_upload, caps.can_delete, caps.can_rename
         );
+        let capabilities = capabilities::capabilities_from_operations(&mtp_info.operations_supported);
+        debug!("Device '{}' capabilities: {:?}", mtp_info.model, capabilities);
 
         // Get storage information
         debug!("Fetching storage information...");
@@ -424,10 +453,12 @@ impl MtpConnectionManager {
                     device: Arc::clone(&device_arc),
                     info: device_info,
                     storages,
+                    capabilities,
                     path_cache: RwLock::new(HashMap::new()),
                     listing_cache: RwLock::new(HashMap::new()),
                     priority_gate: DevicePriorityGate::default(),
                     storage_cache: Arc::new(RwLock::new(HashMap::new())),
+                    thumbnail_cache: std::sync::Mutex::new(ThumbnailCache::default()),
                     #[cfg(test)]
                     storage_lookups: Arc::new(std::sync::atomic::AtomicUsize::new(0)),
                 },
@@ -541,6 +572,12 @@ impl MtpConnectionManager {
         })
     }
 
+    /// Gets the operations a connected device supports, or `None` if it's not connected.
+    pub async fn get_capabilities(&self, device_id: &str) -> Option<MtpDeviceCapabilities> {
+        let devices = self.devices.lock().await;
+        devices.get(device_id).map(|entry| entry.capabilities)
+    }
+
     /// Returns `true` when `device_id` is currently connected, without awaiting.
     ///
     /// Used by `MtpVolume::listing_is_watched` (a sync trait method) to gate the
@@ -874,10 +911,12 @@ impl MtpConnectionManager {
 
 // Remaining impl blocks are in submodules:
 // - directory_ops.rs: list_directory, resolve_path_to_handle, handle_device_disconnected
+// - path_resolver.rs: ensure_path_cached (walks an uncached path down from the root)
 // - event_loop.rs: start_event_loop, stop_event_loop, event handling
 // - file_ops.rs: open_read_session + read_next_window (bounded-window reads), upload_from_stream
-// - mutation_ops.rs: delete_object, create_folder, rename_object, move_object
-// - bulk_ops.rs: scan_for_copy, upload_recursive
+// - mutation_ops.rs: delete_object, create_folder, rename_object, move_object, move_between_storages
+// - bulk_ops.rs: scan_for_copy (uploads go through `MtpVolume::write_from_stream`)
+// - thumbnails.rs: get_thumbnail, invalidate_thumbnail
 
 /// Global connection manager instance.
 static CONNECTION_MANAGER: LazyLock<MtpConnectionManager> = LazyLock::new(MtpConnectionManager::new);
@@ -1028,28 +1067,31 @@ fn normalize_mtp_path(path: &str) -> PathBuf {
     }
 }
 
-/// Converts MTP DateTime to Unix timestamp.
-pub(super) fn convert_mtp_datetime(dt: mtp_rs::DateTime) -> u64 {
-    // Convert the DateTime struct fields to Unix timestamp
-    // This is a simplified conversion - MTP DateTime has year, month, day, hour, minute, second
-
-    // Create a rough Unix timestamp from the date components
-    // Note: This is a simplified calculation that doesn't account for leap years perfectly
-    let year = dt.year as u64;
-    let month = dt.month as u64;
-    let day = dt.day as u64;
-    let hour = dt.hour as u64;
-    let minute = dt.minute as u64;
-    let second = dt.second as u64;
-
-    // Simplified calculation: days since epoch + time
-    // This is approximate but good enough for file listing purposes
-    let years_since_1970 = year.saturating_sub(1970);
-    let days = years_since_1970 * 365 + (years_since_1970 / 4) // leap years approximation
-        + (month.saturating_sub(1)) * 30  // approximate days per month
-        + day.saturating_sub(1);
-
-    days * 86400 + hour * 3600 + minute * 60 + second
+/// Converts an MTP DateTime to a Unix timestamp (seconds).
+///
+/// The device reports wall-clock fields with no zone, so they're read as UTC,
+/// matching what the pane shows for other zone-less sources. `None` for a date
+/// the device filled with junk (month 0, day 32), rather than a bogus timestamp
+/// that would sort it among real files.
+pub(super) fn convert_mtp_datetime(dt: mtp_rs::DateTime) -> Option<u64> {
+    unix_seconds_from_parts(
+        dt.year as i32,
+        dt.month as u32,
+        dt.day as u32,
+        dt.hour as u32,
+        dt.minute as u32,
+        dt.second as u32,
+    )
+}
+
+/// Calendar fields to Unix seconds, with real month lengths and leap years so
+/// sorting by modified/created time orders MTP files correctly.
+fn unix_seconds_from_parts(year: i32, month: u32, day: u32, hour: u32, minute: u32, second: u32) -> Option<u64> {
+    let secs = chrono::NaiveDate::from_ymd_opt(year, month, day)?
+        .and_hms_opt(hour, minute, second)?
+        .and_utc()
+        .timestamp();
+    u64::try_from(secs).ok()
 }
 
 /// Generates icon ID for MTP files.
@@ -1116,6 +1158,30 @@ mod tests {
         assert_eq!(normalize_mtp_path("Photos & Videos"), PathBuf::from("/Photos & Videos"));
     }
 
+    // ========================================================================
+    // Date conversion tests
+    // ========================================================================
+
+    #[test]
+    fn unix_seconds_from_parts_uses_real_month_lengths_and_leap_years() {
+        assert_eq!(unix_seconds_from_parts(1970, 1, 1, 0, 0, 0), Some(0));
+        assert_eq!(unix_seconds_from_parts(2024, 2, 29, 12, 0, 0), Some(1_709_208_000));
+        assert_eq!(unix_seconds_from_parts(2023, 12, 31, 23, 59, 59), Some(1_704_067_199));
+        assert_eq!(unix_seconds_from_parts(2024, 1, 1, 0, 0, 0), Some(1_704_067_200));
+        // Jan 31 must sort before Feb 1; a 30-day-month approximation made them equal.
+        let jan_31 = unix_seconds_from_parts(2023, 1, 31, 23, 0, 0).unwrap();
+        let feb_1 = unix_seconds_from_parts(2023, 2, 1, 1, 0, 0).unwrap();
+        assert!(jan_31 < feb_1);
+    }
+
+    #[test]
+    fn unix_seconds_from_parts_rejects_invalid_and_pre_epoch_dates() {
+        assert_eq!(unix_seconds_from_parts(2023, 0, 10, 0, 0, 0), None);
+        assert_eq!(unix_seconds_from_parts(2023, 2, 30, 0, 0, 0), None);
+        assert_eq!(unix_seconds_from_parts(2023, 5, 1, 24, 0, 0), None);
+        assert_eq!(unix_seconds_from_parts(1969, 12, 31, 0, 0, 0), None);
+    }
+
     // ========================================================================
     // Icon ID generation tests
     // ========================================================================
@@ -1,12 +1,21 @@
-//! MTP mutation operations: delete, create folder, rename, and move.
+//! MTP mutation operations: delete, create folder, rename, and move (within a storage or
+//! between two storages of one device).
 
-use log::{debug, warn};
+use log::{debug, info, warn};
 use mtp_rs::{CancelToken, ObjectHandle, StorageId};
-use std::path::Path;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use tauri::AppHandle;
+use tauri_specta::Event;
 
+use super::backend::{LockedDevice, MtpBackend, delete_tree, move_by_copy};
 use super::errors::MtpConnectionError;
-use super::{MtpConnectionManager, MtpObjectInfo, acquire_device_lock, map_mtp_error, normalize_mtp_path};
+use super::{
+    MTP_READ_WINDOW, MtpConnectionManager, MtpObjectInfo, MtpStorageMoveProgress, acquire_device_lock, map_mtp_error,
+    normalize_mtp_path,
+};
 
 impl MtpConnectionManager {
     /// Deletes an object (file or folder) from the MTP device.
@@ -59,9 +68,8 @@ impl MtpConnectionManager {
             });
         }
 
-        // Foreground priority: a user delete preempts the background scan. The
-        // recursive child deletes nest the guard count (harmless — it just keeps
-        // the scan yielded for the whole subtree delete).
+        // Foreground priority: a user delete preempts the background scan, and the
+        // one guard keeps it yielded for the whole subtree delete.
         let _fg = self.foreground_guard(device_id).await;
 
         debug!(
@@ -73,6 +81,7 @@ impl MtpConnectionManager {
         );
 
         // Get the device and resolve path to handle
+        self.ensure_path_cached(device_id, storage_id, object_path).await?;
         let (device_arc, object_handle) = {
             let devices = self.devices.lock().await;
             let entry = devices.get(device_id).ok_or_else(|| MtpConnectionError::NotConnected {
@@ -83,109 +92,51 @@ impl MtpConnectionManager {
             (Arc::clone(&entry.device), handle)
         };
 
-        let device = acquire_device_lock(&device_arc, device_id, "delete_object").await?;
-
-        // Get the storage
-        let storage = device
-            .storage(StorageId(u64::from(storage_id)))
-            .await
-            .map_err(|e| map_mtp_error(e, device_id))?;
-
-        // Get object info to check if it's a directory
-        let object_info = storage
-            .get_object_info(object_handle)
-            .await
-            .map_err(|e| map_mtp_error(e, device_id))?;
-
-        let is_dir = object_info.is_folder();
-
-        if is_dir {
-            // For directories, recursively delete contents first. Threading the
-            // cancel token into `list_objects_with_cancel` is what makes the
-            // 950-entry `/DCIM/Camera` listing bail at the next per-handle USB
-            // boundary instead of running all 950 GetObjectInfo roundtrips to
-            // completion.
-            let children = storage
-                .list_objects_with_cancel(Some(object_handle), cancel)
-                .await
-                .map_err(|e| map_mtp_error(e, device_id))?;
-
-            drop(storage);
-            drop(device);
-
-            // Recursively delete children. The token is checked between
-            // iterations and inside each child's own list/delete USB call.
-            let parent_path = normalize_mtp_path(object_path);
-            for child_info in children {
-                if let Some(t) = cancel
-                    && t.is_cancelled()
-                {
-                    return Err(MtpConnectionError::Cancelled {
-                        device_id: device_id.to_string(),
-                        message: "Delete cancelled between children".to_string(),
-                    });
-                }
-
-                let child_path = parent_path.join(&child_info.filename);
-                let child_path_str = child_path.to_string_lossy().to_string();
-
-                // Cache the child handle for the recursive call
-                {
-                    let devices = self.devices.lock().await;
-                    if let Some(entry) = devices.get(device_id)
-                        && let Ok(mut cache_map) = entry.path_cache.write()
-                    {
-                        let storage_cache = cache_map.entry(storage_id).or_default();
-                        storage_cache
-                            .path_to_handle
-                            .insert(child_path.clone(), child_info.handle);
-                    }
-                }
-
-                // Use Box::pin for recursive async call
-                Box::pin(self.delete_object_with_cancel(device_id, storage_id, &child_path_str, cancel)).await?;
-            }
+        // Children go before their parent (MTP refuses a non-empty folder), one device lock per
+        // USB call. The token is checked between children and inside each list/delete call, which
+        // is what makes a 950-entry `/DCIM/Camera` bail at the next per-handle boundary instead
+        // of running every GetObjectInfo roundtrip to completion.
+        let backend = LockedDevice {
+            device: &device_arc,
+            device_id,
+        };
+        let mut deleted = Vec::new();
+        let result = delete_tree(
+            &backend,
+            storage_id,
+            object_handle,
+            &normalize_mtp_path(object_path),
+            cancel,
+            &mut deleted,
+        )
+        .await;
 
-            // Re-acquire device and storage lock to delete the now-empty folder
-            let device = acquire_device_lock(&device_arc, device_id, "delete_object (empty folder)").await?;
-            let storage = device
-                .storage(StorageId(u64::from(storage_id)))
-                .await
-                .map_err(|e| map_mtp_error(e, device_id))?;
+        // Forget whatever did get deleted, even when the delete failed or was cancelled halfway.
+        self.forget_deleted_paths(device_id, storage_id, &deleted).await;
+        result?;
 
-            storage
-                .delete_with_cancel(object_handle, cancel)
-                .await
-                .map_err(|e| map_mtp_error(e, device_id))?;
-        } else {
-            // For files, just delete directly. The cancel check inside
-            // delete_with_cancel bails before the PTP `DeleteObject` request
-            // is issued, so a cancelled token never touches the wire here.
-            storage
-                .delete_with_cancel(object_handle, cancel)
-                .await
-                .map_err(|e| map_mtp_error(e, device_id))?;
-        }
+        debug!("MTP delete complete: {}", object_path);
+        Ok(())
+    }
 
-        // Remove from path cache
-        let object_path_normalized = normalize_mtp_path(object_path);
+    /// Drops deleted paths from the path cache and invalidates their parents' listings.
+    async fn forget_deleted_paths(&self, device_id: &str, storage_id: u32, deleted: &[PathBuf]) {
         {
             let devices = self.devices.lock().await;
             if let Some(entry) = devices.get(device_id)
                 && let Ok(mut cache_map) = entry.path_cache.write()
                 && let Some(storage_cache) = cache_map.get_mut(&storage_id)
             {
-                storage_cache.remove_path(&object_path_normalized);
+                for path in deleted {
+                    storage_cache.remove_path(path);
+                }
             }
         }
 
-        // Invalidate the parent directory's listing cache
-        if let Some(parent) = object_path_normalized.parent() {
+        let parents: BTreeSet<&Path> = deleted.iter().filter_map(|p| p.parent()).collect();
+        for parent in parents {
             self.invalidate_listing_cache(device_id, storage_id, parent).await;
         }
-
-        debug!("MTP delete complete: {}", object_path);
-        Ok(())
     }
 
     /// Creates a new folder on the MTP device.
@@ -212,6 +163,7 @@ impl MtpConnectionManager {
         );
 
         // Get device and resolve parent folder
+        self.ensure_path_cached(device_id, storage_id, parent_path).await?;
         let (device_arc, parent_handle) = {
             let devices = self.devices.lock().await;
             let entry = devices.get(device_id).ok_or_else(|| MtpConnectionError::NotConnected {
@@ -301,6 +253,7 @@ impl MtpConnectionManager {
         );
 
         // Get device and resolve object handle
+        self.ensure_path_cached(device_id, storage_id, object_path).await?;
         let (device_arc, object_handle) = {
             let devices = self.devices.lock().await;
             let entry = devices.get(device_id).ok_or_else(|| MtpConnectionError::NotConnected {
@@ -369,9 +322,12 @@ impl MtpConnectionManager {
         })
     }
 
-    /// Moves an object to a new parent folder on the MTP device.
+    /// Moves an object to a new parent folder on the same MTP storage.
     ///
-    /// Falls back to copy+delete if the device doesn't support MoveObject.
+    /// Uses the device's native `MoveObject` when it advertises it: the object is reparented in
+    /// place, so moving a 4 GB video between folders is instant and every handle (including a
+    /// moved folder's descendants) stays valid. Devices without it fall back to copy + delete
+    /// ([`move_by_copy`]), which spools each file through a local temp file.
     ///
     /// # Arguments
     ///
@@ -395,7 +351,9 @@ impl MtpConnectionManager {
         );
 
         // Get device and resolve both handles
-        let (device_arc, object_handle, new_parent_handle) = {
+        self.ensure_path_cached(device_id, storage_id, object_path).await?;
+        self.ensure_path_cached(device_id, storage_id, new_parent_path).await?;
+        let (device_arc, object_handle, new_parent_handle, can_move) = {
             let devices = self.devices.lock().await;
             let entry = devices.get(device_id).ok_or_else(|| MtpConnectionError::NotConnected {
                 device_id: device_id.to_string(),
@@ -403,87 +361,336 @@ impl MtpConnectionManager {
 
             let obj_handle = self.resolve_path_to_handle(entry, storage_id, object_path)?;
             let parent_handle = self.resolve_path_to_handle(entry, storage_id, new_parent_path)?;
-            (Arc::clone(&entry.device), obj_handle, parent_handle)
+            (
+                Arc::clone(&entry.device),
+                obj_handle,
+                parent_handle,
+                entry.capabilities.can_move,
+            )
         };
 
-        let device = acquire_device_lock(&device_arc, device_id, "move_object").await?;
-
-        // Get the storage
-        let storage = device
-            .storage(StorageId(u64::from(storage_id)))
-            .await
-            .map_err(|e| map_mtp_error(e, device_id))?;
-
-        // Get object info
-        let object_info = storage
-            .get_object_info(object_handle)
-            .await
-            .map_err(|e| map_mtp_error(e, device_id))?;
+        let old_path = normalize_mtp_path(object_path);
+        let new_parent = normalize_mtp_path(new_parent_path);
 
-        let is_dir = object_info.is_folder();
-        let object_size = object_info.size;
-        let object_name = object_info.filename.clone();
+        let backend = LockedDevice {
+            device: &device_arc,
+            device_id,
+        };
+        let object_info = backend.object_info(storage_id, object_handle).await?;
+        let new_path = new_parent.join(&object_info.name);
+        let new_path_str = new_path.to_string_lossy().to_string();
 
-        // Try to use MoveObject operation
-        // storage.move_object expects the new parent handle directly, not Option
-        let new_parent_for_move = if new_parent_handle == ObjectHandle::ROOT {
-            ObjectHandle::ROOT
+        let moved_handle = if can_move {
+            self.move_natively(
+                &device_arc,
+                device_id,
+                storage_id,
+                object_handle,
+                new_parent_handle,
+                None,
+            )
+            .await?;
+            let devices = self.devices.lock().await;
+            if let Some(entry) = devices.get(device_id)
+                && let Ok(mut cache_map) = entry.path_cache.write()
+                && let Some(storage_cache) = cache_map.get_mut(&storage_id)
+            {
+                storage_cache.rename_subtree(&old_path, &new_path);
+            }
+            object_handle
         } else {
-            new_parent_handle
+            info!(
+                "MTP move_object: {} doesn't support MoveObject; moving {} by copy + delete",
+                device_id, object_path
+            );
+            let mut deleted = Vec::new();
+            let result = move_by_copy(
+                &backend,
+                storage_id,
+                object_handle,
+                &old_path,
+                storage_id,
+                new_parent_handle,
+                &std::env::temp_dir(),
+                MTP_READ_WINDOW,
+                &|_| {},
+                &mut deleted,
+            )
+            .await;
+            self.forget_deleted_paths(device_id, storage_id, &deleted).await;
+            let copy_handle = result?;
+            let devices = self.devices.lock().await;
+            if let Some(entry) = devices.get(device_id)
+                && let Ok(mut cache_map) = entry.path_cache.write()
+            {
+                cache_map
+                    .entry(storage_id)
+                    .or_default()
+                    .insert(new_path.clone(), copy_handle);
+            }
+            copy_handle
         };
 
-        let move_result = storage.move_object(object_handle, new_parent_for_move, None).await;
+        // Invalidate listing cache for both old and new parent directories
+        let old_parent = old_path.parent().unwrap_or(Path::new("/"));
+        self.invalidate_listing_cache(device_id, storage_id, old_parent).await;
+        self.invalidate_listing_cache(device_id, storage_id, &new_parent).await;
 
-        // Release device and storage lock
-        drop(storage);
-        drop(device);
+        debug!("MTP move complete: {} -> {}", object_path, new_path_str);
 
-        match move_result {
-            Ok(()) => {
-                // Move succeeded
+        Ok(MtpObjectInfo {
+            handle: moved_handle.0 as u32,
+            name: object_info.name,
+            path: new_path_str,
+            is_directory: object_info.is_folder,
+            size: if object_info.is_folder {
+                None
+            } else {
+                Some(object_info.size)
+            },
+        })
+    }
+
+    /// Moves an object from one storage of a device to a folder on another (internal storage to
+    /// SD card, say), in one step.
+    ///
+    /// Tries the device's `MoveObject` with the destination storage first, which reparents the
+    /// object in place. Many devices (Android among them) only move within a storage and refuse
+    /// it; those, and devices without `MoveObject` at all, fall back to copy + delete
+    /// ([`move_by_copy`]), which spools each file through a local temp file and deletes the
+    /// source only once the whole copy is on the destination. The fallback emits
+    /// [`MtpStorageMoveProgress`] after each file when `app` is given.
+    ///
+    /// Afterwards both parents' listing caches are dropped and, with `app`, any open listing of
+    /// either folder is re-read and diffed (`directory-diff`).
+    ///
+    /// # Arguments
+    ///
+    /// * `device_id` - The connected device ID
+    /// * `from_storage` - The storage the object is on
+    /// * `object_path` - Current path of the object
+    /// * `to_storage` - The storage to move it to
+    /// * `new_parent_path` - Destination folder path on `to_storage`
+    pub async fn move_between_storages(
+        &self,
+        device_id: &str,
+        from_storage: u32,
+        object_path: &str,
+        to_storage: u32,
+        new_parent_path: &str,
+        app: Option<&AppHandle>,
+    ) -> Result<MtpObjectInfo, MtpConnectionError> {
+        if from_storage == to_storage {
+            let moved = self
+                .move_object(device_id, from_storage, object_path, new_parent_path)
+                .await?;
+            if let Some(app) = app {
                 let old_path = normalize_mtp_path(object_path);
-                let new_path = normalize_mtp_path(new_parent_path).join(&object_name);
-                let new_path_str = new_path.to_string_lossy().to_string();
-
-                // Update path cache
-                {
-                    let devices = self.devices.lock().await;
-                    if let Some(entry) = devices.get(device_id)
-                        && let Ok(mut cache_map) = entry.path_cache.write()
-                        && let Some(storage_cache) = cache_map.get_mut(&storage_id)
+                let old_parent = old_path.parent().unwrap_or(Path::new("/"));
+                Self::emit_directory_changed_targeted(device_id, from_storage, old_parent, app);
+                Self::emit_directory_changed_targeted(device_id, to_storage, &normalize_mtp_path(new_parent_path), app);
+            }
+            return Ok(moved);
+        }
+
+        // Foreground priority: a user move preempts the background scan.
+        let _fg = self.foreground_guard(device_id).await;
+
+        debug!(
+            "MTP move_between_storages: device={}, {}:{} -> {}:{}",
+            device_id, from_storage, object_path, to_storage, new_parent_path
+        );
+
+        self.ensure_path_cached(device_id, from_storage, object_path).await?;
+        self.ensure_path_cached(device_id, to_storage, new_parent_path).await?;
+        let (device_arc, object_handle, new_parent_handle, can_move) = {
+            let devices = self.devices.lock().await;
+            let entry = devices.get(device_id).ok_or_else(|| MtpConnectionError::NotConnected {
+                device_id: device_id.to_string(),
+            })?;
+            (
+                Arc::clone(&entry.device),
+                self.resolve_path_to_handle(entry, from_storage, object_path)?,
+                self.resolve_path_to_handle(entry, to_storage, new_parent_path)?,
+                entry.capabilities.can_move,
+            )
+        };
+
+        let old_path = normalize_mtp_path(object_path);
+        let new_parent = normalize_mtp_path(new_parent_path);
+        let backend = LockedDevice {
+            device: &device_arc,
+            device_id,
+        };
+        let object_info = backend.object_info(from_storage, object_handle).await?;
+        let new_path = new_parent.join(&object_info.name);
+
+        let native = if can_move {
+            match self
+                .move_natively(
+                    &device_arc,
+                    device_id,
+                    from_storage,
+                    object_handle,
+                    new_parent_handle,
+                    Some(to_storage),
+                )
+                .await
+            {
+                Ok(()) => true,
+                Err(e @ (MtpConnectionError::NotConnected { .. } | MtpConnectionError::Cancelled { .. })) => {
+                    return Err(e);
+                }
+                Err(e) => {
+                    info!(
+                        "MTP move_between_storages: {} refused a cross-storage MoveObject ({:?}); copying instead",
+                        device_id, e
+                    );
+                    false
+                }
+            }
+        } else {
+            false
+        };
+
+        let moved_handle = if native {
+            // Same handles, new storage: re-key the subtree from one storage's cache to the other's.
+            let devices = self.devices.lock().await;
+            if let Some(entry) = devices.get(device_id)
+                && let Ok(mut cache_map) = entry.path_cache.write()
+            {
+                let moved: Vec<(PathBuf, ObjectHandle)> = cache_map
+                    .get(&from_storage)
+                    .map(|sc| {
+                        sc.path_to_handle
+                            .iter()
+                            .filter(|(path, _)| path.starts_with(&old_path))
+                            .map(|(path, handle)| (path.clone(), *handle))
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                if let Some(from_cache) = cache_map.get_mut(&from_storage) {
+                    for (path, _) in &moved {
+                        from_cache.remove_path(path);
+                    }
+                }
+                let to_cache = cache_map.entry(to_storage).or_default();
+                to_cache.insert(new_path.clone(), object_handle);
+                for (path, handle) in moved {
+                    if let Ok(relative) = path.strip_prefix(&old_path)
+                        && !relative.as_os_str().is_empty()
                     {
-                        storage_cache.remove_path(&old_path);
-                        storage_cache.insert(new_path.clone(), object_handle);
+                        to_cache.insert(new_path.join(relative), handle);
                     }
                 }
-
-                // Invalidate listing cache for both old and new parent directories
-                let old_parent = old_path.parent().unwrap_or(Path::new("/"));
-                self.invalidate_listing_cache(device_id, storage_id, old_parent).await;
-                let new_parent = normalize_mtp_path(new_parent_path);
-                self.invalidate_listing_cache(device_id, storage_id, &new_parent).await;
-
-                debug!("MTP move complete: {} -> {}", object_path, new_path_str);
-
-                Ok(MtpObjectInfo {
-                    handle: object_handle.0 as u32,
-                    name: object_name,
-                    path: new_path_str,
-                    is_directory: is_dir,
-                    size: if is_dir { None } else { Some(object_size) },
-                })
             }
-            Err(e) => {
-                // Move operation returned an error - might not be supported
-                warn!(
-                    "MTP MoveObject failed for {}: {:?}. Device may not support this operation.",
-                    object_path, e
-                );
-                Err(MtpConnectionError::Other {
-                    device_id: device_id.to_string(),
-                    message: format!("Move operation not supported by device: {}", e),
-                })
+            object_handle
+        } else {
+            let bytes_total = match self.scan_for_copy(device_id, from_storage, object_path).await {
+                Ok(scan) => scan.total_bytes,
+                Err(e) => {
+                    debug!("MTP move_between_storages: size scan failed, progress has no total: {e:?}");
+                    0
+                }
+            };
+            let bytes_done = AtomicU64::new(0);
+            let report = |bytes: u64| {
+                let done = bytes_done.fetch_add(bytes, Ordering::Relaxed) + bytes;
+                if let Some(app) = app {
+                    let _ = MtpStorageMoveProgress {
+                        device_id: device_id.to_string(),
+                        from_storage_id: from_storage,
+                        to_storage_id: to_storage,
+                        bytes_done: done,
+                        bytes_total,
+                    }
+                    .emit(app);
+                }
+            };
+            let mut deleted = Vec::new();
+            let result = move_by_copy(
+                &backend,
+                from_storage,
+                object_handle,
+                &old_path,
+                to_storage,
+                new_parent_handle,
+                &std::env::temp_dir(),
+                MTP_READ_WINDOW,
+                &report,
+                &mut deleted,
+            )
+            .await;
+            self.forget_deleted_paths(device_id, from_storage, &deleted).await;
+            let copy_handle = result?;
+            let devices = self.devices.lock().await;
+            if let Some(entry) = devices.get(device_id)
+                && let Ok(mut cache_map) = entry.path_cache.write()
+            {
+                cache_map
+                    .entry(to_storage)
+                    .or_default()
+                    .insert(new_path.clone(), copy_handle);
             }
+            copy_handle
+        };
+
+        let old_parent = old_path.parent().unwrap_or(Path::new("/"));
+        self.invalidate_listing_cache(device_id, from_storage, old_parent).await;
+        self.invalidate_listing_cache(device_id, to_storage, &new_parent).await;
+        if let Some(app) = app {
+            Self::emit_directory_changed_targeted(device_id, from_storage, old_parent, app);
+            Self::emit_directory_changed_targeted(device_id, to_storage, &new_parent, app);
         }
+
+        debug!(
+            "MTP move_between_storages complete ({}): {} -> {}:{}",
+            if native { "MoveObject" } else { "copy + delete" },
+            object_path,
+            to_storage,
+            new_path.display()
+        );
+
+        Ok(MtpObjectInfo {
+            handle: moved_handle.0 as u32,
+            name: object_info.name,
+            path: new_path.to_string_lossy().to_string(),
+            is_directory: object_info.is_folder,
+            size: if object_info.is_folder {
+                None
+            } else {
+                Some(object_info.size)
+            },
+        })
+    }
+
+    /// Reparents `object_handle` under `new_parent_handle` with the device's `MoveObject`,
+    /// onto `to_storage` when given (`None` keeps it on `storage_id`).
+    async fn move_natively(
+        &self,
+        device_arc: &Arc<tokio::sync::Mutex<mtp_rs::MtpDevice>>,
+        device_id: &str,
+        storage_id: u32,
+        object_handle: ObjectHandle,
+        new_parent_handle: ObjectHandle,
+        to_storage: Option<u32>,
+    ) -> Result<(), MtpConnectionError> {
+        let device = acquire_device_lock(device_arc, device_id, "move_object").await?;
+        let storage = device
+            .storage(StorageId(u64::from(storage_id)))
+            .await
+            .map_err(|e| map_mtp_error(e, device_id))?;
+        // storage.move_object takes the new parent handle directly (ROOT for the storage root).
+        storage
+            .move_object(
+                object_handle,
+                new_parent_handle,
+                to_storage.map(|id| StorageId(u64::from(id))),
+            )
+            .await
+            .map_err(|e| {
+                warn!("MTP MoveObject failed for handle {}: {:?}", object_handle.0, e);
+                map_mtp_error(e, device_id)
+            })
     }
 }
@@ -25,8 +25,8 @@ struct Device {
     fixture: VirtualDeviceFixture,
 }
 
-/// Connects a virtual MTP device with the root listing primed, so mutations can
-/// resolve their parent handles (`resolve_path_to_handle` is cache-only).
+/// Connects a virtual MTP device with the root listing primed, so the root's
+/// children start out in the path cache.
 async fn connect_device() -> Device {
     let fixture = setup_virtual_mtp_device();
 
@@ -138,6 +138,36 @@ async fn move_updates_the_reverse_entry_to_the_new_parent() {
     teardown(device).await;
 }
 
+/// Moving a folder keeps its children's handles, so their cached paths must follow it too.
+/// Left under the old parent, the next event for a child refreshes a folder that's gone.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn folder_move_carries_its_cached_descendants_along() {
+    let _guard = virtual_device_test_lock().lock().await;
+    let device = connect_device().await;
+
+    let notes = connection_manager()
+        .list_directory(&device.id, device.storage_id, "Documents")
+        .await
+        .expect("list Documents should succeed")
+        .into_iter()
+        .find(|e| e.name == "notes.txt")
+        .expect("the fixture has /Documents/notes.txt");
+    let notes_handle = ObjectHandle(notes.inode.expect("MTP entries carry their handle"));
+
+    connection_manager()
+        .move_object(&device.id, device.storage_id, "/Documents", "/Music")
+        .await
+        .expect("move_object should succeed");
+
+    assert_eq!(
+        reverse_entry(&device, notes_handle).await.as_deref(),
+        Some(Path::new("/Music/Documents/notes.txt")),
+        "a moved folder's child must resolve to its path under the new parent"
+    );
+
+    teardown(device).await;
+}
+
 /// An upload's new object is cached from `upload_from_stream`; same requirement.
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn upload_records_the_new_object_in_both_directions() {
@@ -207,3 +237,25 @@ async fn delete_clears_the_reverse_entry_too() {
 
     teardown(device).await;
 }
+
+/// Listing a deep folder nobody browsed to walks down from the root and caches
+/// every level on the way, both ways, like a manual browse would have.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn cold_deep_path_is_resolved_by_walking_from_the_root() {
+    let _guard = virtual_device_test_lock().lock().await;
+    let device = connect_device().await;
+
+    let entries = connection_manager()
+        .list_directory(&device.id, device.storage_id, "/DCIM/Burst")
+        .await
+        .expect("an unbrowsed deep folder should list");
+    assert!(entries.iter().any(|e| e.name == "burst-001.jpg"));
+
+    let handle = connection_manager()
+        .cached_handle_for_path(&device.id, device.storage_id, Path::new("/DCIM/Burst"))
+        .await
+        .expect("the walk should cache the intermediate folder");
+    assert_eq!(reverse_entry(&device, handle).await, Some(PathBuf::from("/DCIM/Burst")));
+
+    teardown(device).await;
+}
@@ -0,0 +1,180 @@
+//! Resolve a virtual path to its MTP object handle by walking down from the storage root.
+//!
+//! MTP has no "look up by name": the only way from `/DCIM/Camera/IMG_0001.jpg` to a handle
+//! is to list `/`, find `DCIM`, list it, find `Camera`, and so on. Browsing does that one
+//! level at a time and fills [`PathHandleCache`](super::cache::PathHandleCache) as it goes,
+//! so a path the user clicked down to resolves from the cache. A path reached any other way
+//! (a copy scan of a deep folder, MCP navigation, a restored tab) isn't cached yet.
+//!
+//! [`MtpConnectionManager::ensure_path_cached`] fills that in: it starts from the deepest
+//! ancestor already in the cache (the root handle is a constant, so there's always one) and
+//! lists each level below it, caching every child's handle. A cached path costs one map
+//! lookup and no USB traffic. Each listed level costs one `GetObjectHandles` plus one
+//! `GetObjectInfo` per child, so a cold walk into a huge folder is slow, but it only happens
+//! once per folder per connection.
+//!
+//! The pure part ([`levels_to_list`]) is a closure over "is this path cached?", so it
+//! unit-tests without a device.
+
+use std::path::{Path, PathBuf};
+
+use log::debug;
+use mtp_rs::{ObjectHandle, StorageId};
+
+use super::errors::MtpConnectionError;
+use super::{MtpConnectionManager, acquire_device_lock, map_mtp_error, normalize_mtp_path};
+
+/// The folders to list, root-first, so that `path` ends up cached: every ancestor from the
+/// deepest cached one down to `path`'s parent. Empty when `path` is cached or is the root.
+fn levels_to_list(path: &Path, is_cached: impl Fn(&Path) -> bool) -> Vec<PathBuf> {
+    let mut levels = Vec::new();
+    if is_root(path) || is_cached(path) {
+        return levels;
+    }
+    for ancestor in path.ancestors().skip(1) {
+        levels.push(ancestor.to_path_buf());
+        if is_root(ancestor) || is_cached(ancestor) {
+            break;
+        }
+    }
+    levels.reverse();
+    levels
+}
+
+fn is_root(path: &Path) -> bool {
+    path.as_os_str() == "/" || path.as_os_str().is_empty()
+}
+
+impl MtpConnectionManager {
+    /// Makes sure `path`'s handle is in the path cache, listing the folders above it that
+    /// aren't. Call before [`resolve_path_to_handle`](Self::resolve_path_to_handle), outside
+    /// the registry lock.
+    ///
+    /// # Errors
+    ///
+    /// - [`MtpConnectionError::NotConnected`] if the device isn't in the registry.
+    /// - [`MtpConnectionError::ObjectNotFound`] if a component doesn't exist on the device.
+    /// - Mapped protocol errors from the listings.
+    pub(super) async fn ensure_path_cached(
+        &self,
+        device_id: &str,
+        storage_id: u32,
+        path: &str,
+    ) -> Result<(), MtpConnectionError> {
+        let path = normalize_mtp_path(path);
+        let (device_arc, levels) = {
+            let devices = self.devices.lock().await;
+            let entry = devices.get(device_id).ok_or_else(|| MtpConnectionError::NotConnected {
+                device_id: device_id.to_string(),
+            })?;
+            let cache_map = entry.path_cache.read().ok();
+            let storage_cache = cache_map.as_ref().and_then(|m| m.get(&storage_id));
+            let levels = levels_to_list(&path, |p| {
+                storage_cache.is_some_and(|sc| sc.path_to_handle.contains_key(p))
+            });
+            (std::sync::Arc::clone(&entry.device), levels)
+        };
+        if levels.is_empty() {
+            return Ok(());
+        }
+        debug!(
+            "MTP ensure_path_cached: walking {} uncached level(s) to {}",
+            levels.len(),
+            path.display()
+        );
+
+        for dir in levels {
+            // The previous level cached this folder (or it's the root, or it was cached already).
+            let dir_handle = {
+                let devices = self.devices.lock().await;
+                let entry = devices.get(device_id).ok_or_else(|| MtpConnectionError::NotConnected {
+                    device_id: device_id.to_string(),
+                })?;
+                self.resolve_path_to_handle(entry, storage_id, &dir.to_string_lossy())
+                    .map_err(|_| MtpConnectionError::ObjectNotFound {
+                        device_id: device_id.to_string(),
+                        path: dir.to_string_lossy().to_string(),
+                    })?
+            };
+            let parent_opt = if dir_handle == ObjectHandle::ROOT {
+                None
+            } else {
+                Some(dir_handle)
+            };
+
+            let children = {
+                let device = acquire_device_lock(&device_arc, device_id, "ensure_path_cached").await?;
+                let storage = device
+                    .storage(StorageId(u64::from(storage_id)))
+                    .await
+                    .map_err(|e| map_mtp_error(e, device_id))?;
+                storage
+                    .list_objects_with_cancel(parent_opt, None)
+                    .await
+                    .map_err(|e| map_mtp_error(e, device_id))?
+            };
+
+            let devices = self.devices.lock().await;
+            if let Some(entry) = devices.get(device_id)
+                && let Ok(mut cache_map) = entry.path_cache.write()
+            {
+                let storage_cache = cache_map.entry(storage_id).or_default();
+                for info in children {
+                    storage_cache.insert(dir.join(&info.filename), info.handle);
+                }
+            }
+        }
+
+        let devices = self.devices.lock().await;
+        let entry = devices.get(device_id).ok_or_else(|| MtpConnectionError::NotConnected {
+            device_id: device_id.to_string(),
+        })?;
+        self.resolve_path_to_handle(entry, storage_id, &path.to_string_lossy())
+            .map(|_| ())
+            .map_err(|_| MtpConnectionError::ObjectNotFound {
+                device_id: device_id.to_string(),
+                path: path.to_string_lossy().to_string(),
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cached<'a>(paths: &'a [&str]) -> impl Fn(&Path) -> bool + 'a {
+        move |p| paths.iter().any(|c| Path::new(c) == p)
+    }
+
+    #[test]
+    fn cached_path_and_root_need_no_listing() {
+        assert!(levels_to_list(Path::new("/"), cached(&[])).is_empty());
+        assert!(levels_to_list(Path::new("/DCIM/Camera"), cached(&["/DCIM/Camera"])).is_empty());
+    }
+
+    #[test]
+    fn cold_path_lists_every_level_from_the_root() {
+        let levels = levels_to_list(Path::new("/DCIM/Camera/IMG_1.jpg"), cached(&[]));
+        assert_eq!(
+            levels,
+            vec![
+                PathBuf::from("/"),
+                PathBuf::from("/DCIM"),
+                PathBuf::from("/DCIM/Camera")
+            ]
+        );
+    }
+
+    #[test]
+    fn walk_starts_at_the_deepest_cached_ancestor() {
+        let levels = levels_to_list(Path::new("/DCIM/Camera/2024/IMG_1.jpg"), cached(&["/DCIM"]));
+        assert_eq!(
+            levels,
+            vec![
+                PathBuf::from("/DCIM"),
+                PathBuf::from("/DCIM/Camera"),
+                PathBuf::from("/DCIM/Camera/2024")
+            ]
+        );
+    }
+}
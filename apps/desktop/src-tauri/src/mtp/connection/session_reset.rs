@@ -427,9 +427,8 @@ mod device_tests {
         );
 
         // The one-shot is spent: the device is genuinely usable again. Listing
-        // the root first isn't ceremony: `resolve_path_to_handle` is cache-only,
-        // so the emptied caches make a re-navigation start from the top, which is
-        // what the pane does anyway.
+        // the root first mirrors the pane, which re-navigates from the top after
+        // a reset; `/DCIM` would resolve on its own too, by walking down.
         connection_manager()
             .list_directory(&device.id, device.storage_id, "/")
             .await
@@ -0,0 +1,89 @@
+//! Embedded thumbnails via PTP `GetThumb`, for the grid view.
+//!
+//! Cameras and phones store a small JPEG next to each photo or video, so a grid over `DCIM`
+//! can show previews without downloading the originals. Results (hits and misses) go into the
+//! device's [`ThumbnailCache`](super::cache::ThumbnailCache), keyed by object handle; the event
+//! loop drops an entry when its object changes or disappears.
+
+use log::debug;
+use mtp_rs::{ObjectHandle, StorageId};
+use std::sync::Arc;
+
+use super::errors::MtpConnectionError;
+use super::{MtpConnectionManager, acquire_device_lock, map_mtp_error};
+use crate::ignore_poison::IgnorePoison;
+
+impl MtpConnectionManager {
+    /// Fetches the embedded thumbnail (usually a JPEG) of an object.
+    ///
+    /// Returns `Ok(None)` when there isn't one: folders, formats without an embedded thumbnail,
+    /// and devices that don't support `GetThumb` at all.
+    ///
+    /// # Arguments
+    ///
+    /// * `device_id` - The connected device ID
+    /// * `storage_id` - The storage ID within the device
+    /// * `object_path` - Virtual path on the device
+    pub async fn get_thumbnail(
+        &self,
+        device_id: &str,
+        storage_id: u32,
+        object_path: &str,
+    ) -> Result<Option<Arc<Vec<u8>>>, MtpConnectionError> {
+        // Foreground priority: the grid asks for what's on screen.
+        let _fg = self.foreground_guard(device_id).await;
+
+        self.ensure_path_cached(device_id, storage_id, object_path).await?;
+        let (device_arc, handle) = {
+            let devices = self.devices.lock().await;
+            let entry = devices.get(device_id).ok_or_else(|| MtpConnectionError::NotConnected {
+                device_id: device_id.to_string(),
+            })?;
+            if !entry.capabilities.can_read_thumbnails {
+                return Ok(None);
+            }
+            let handle = self.resolve_path_to_handle(entry, storage_id, object_path)?;
+            if let Some(cached) = entry.thumbnail_cache.lock_ignore_poison().get(handle) {
+                return Ok(cached);
+            }
+            (Arc::clone(&entry.device), handle)
+        };
+
+        let thumbnail = {
+            let device = acquire_device_lock(&device_arc, device_id, "get_thumbnail").await?;
+            let storage = device
+                .storage(StorageId(u64::from(storage_id)))
+                .await
+                .map_err(|e| map_mtp_error(e, device_id))?;
+            match storage.get_thumbnail(handle).await {
+                Ok(bytes) if !bytes.is_empty() => Some(Arc::new(bytes)),
+                Ok(_) => None,
+                // `NoThumbnailPresent` / `InvalidObjectFormatCode`: nothing to show, not a failure.
+                Err(mtp_rs::Error::NotFound | mtp_rs::Error::Unsupported) => None,
+                Err(e) => return Err(map_mtp_error(e, device_id)),
+            }
+        };
+        debug!(
+            "MTP get_thumbnail: {object_path} on {device_id} -> {} bytes",
+            thumbnail.as_ref().map_or(0, |t| t.len())
+        );
+
+        let devices = self.devices.lock().await;
+        if let Some(entry) = devices.get(device_id) {
+            entry
+                .thumbnail_cache
+                .lock_ignore_poison()
+                .insert(handle, thumbnail.clone());
+        }
+        Ok(thumbnail)
+    }
+
+    /// Drops the cached thumbnail for `handle`, if any. Called from the event loop on
+    /// `ObjectInfoChanged` (the object was edited) and `ObjectRemoved` (the handle may be reused).
+    pub(super) async fn invalidate_thumbnail(&self, device_id: &str, handle: ObjectHandle) {
+        let devices = self.devices.lock().await;
+        if let Some(entry) = devices.get(device_id) {
+            entry.thumbnail_cache.lock_ignore_poison().remove(handle);
+        }
+    }
+}
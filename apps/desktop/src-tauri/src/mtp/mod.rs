@@ -31,10 +31,10 @@ pub mod watcher;
 pub use connection::{
     ConnectedDeviceInfo, MtpConnectionError, MtpDeviceConnected, MtpDeviceDisconnected, MtpDisconnectReason,
     MtpExclusiveAccessError, MtpObjectInfo, MtpPermissionError, MtpPtpcameradRestored, MtpPtpcameradSuppressed,
-    MtpStorageRemoved, connection_manager,
+    MtpStorageMoveProgress, MtpStorageRemoved, connection_manager,
 };
 pub use discovery::list_mtp_devices;
-pub use types::{MtpDeviceInfo, MtpStorageInfo};
+pub use types::{MtpDeviceCapabilities, MtpDeviceInfo, MtpStorageInfo};
 pub use watcher::{set_mtp_enabled, set_mtp_enabled_flag, start_mtp_watcher};
 
 /// The Terminal command that users can run to work around ptpcamerad on macOS.
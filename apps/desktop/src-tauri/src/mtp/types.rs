@@ -56,6 +56,33 @@ pub struct MtpStorageInfo {
     pub is_read_only: bool,
 }
 
+/// Which operations a connected MTP device advertises, so the UI can disable an action the
+/// device would refuse (for example, Move on a camera without `MoveObject`) instead of failing it.
+///
+/// Derived once at connect from the device's `DeviceInfo` operation list. Device-wide: a storage
+/// can still be read-only (`MtpStorageInfo::is_read_only`) on a device that supports writes.
+///
+/// Only serialized (Rust → frontend).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct MtpDeviceCapabilities {
+    /// `SendObjectInfo` + `SendObject`: copying files onto the device.
+    pub can_upload: bool,
+    /// `DeleteObject`.
+    pub can_delete: bool,
+    /// `SetObjectPropValue` (renames set the file name property).
+    pub can_rename: bool,
+    /// `MoveObject`. Without it, a move between folders is a copy + delete.
+    pub can_move: bool,
+    /// `SendObjectInfo` with an association (folder) object.
+    pub can_create_folder: bool,
+    /// `GetThumb`.
+    pub can_read_thumbnails: bool,
+    /// `GetPartialObject64` or `GetPartialObject`: bounded-window reads, resuming a copy, and
+    /// browsing archives without downloading them whole.
+    pub can_read_partial: bool,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
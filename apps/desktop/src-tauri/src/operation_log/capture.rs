@@ -166,6 +166,11 @@ pub fn compute_eligibility(
                 Some(NotRollbackableReason::ZipEditUnsupported),
             ),
         },
+        // The previous modes aren't journaled, so there's nothing to restore.
+        OpKind::SetPermissions => (
+            RollbackState::NotRollbackable,
+            Some(NotRollbackableReason::PermissionChange),
+        ),
     }
 }
 
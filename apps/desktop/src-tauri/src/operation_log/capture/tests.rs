@@ -40,6 +40,13 @@ fn delete_is_never_rollbackable() {
     assert_eq!(reason, Some(NotRollbackableReason::PermanentDelete));
 }
 
+#[test]
+fn set_permissions_is_never_rollbackable() {
+    let (state, reason) = compute_eligibility(OpKind::SetPermissions, false, None, false);
+    assert_eq!(state, RollbackState::NotRollbackable);
+    assert_eq!(reason, Some(NotRollbackableReason::PermissionChange));
+}
+
 #[test]
 fn trash_rename_and_creates_open_rollbackable() {
     for kind in [OpKind::Trash, OpKind::Rename, OpKind::CreateFolder, OpKind::CreateFile] {
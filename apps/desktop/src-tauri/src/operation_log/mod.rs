@@ -1,5 +1,5 @@
 //! The operation log: a durable, cross-volume journal of every file mutation,
-//! the foundation for search, rollback, and a future undo.
+//! the foundation for search, rollback, and undo.
 //!
 //! This module is the DURABLE STORE: the schema, the forward-migration
 //! ladder, the single writer thread, and dir interning. The capture layer, the
@@ -18,6 +18,7 @@ pub mod retention;
 pub mod rollback;
 pub mod store;
 pub mod types;
+pub mod undo;
 pub mod writer;
 
 use std::sync::{Arc, RwLock};
@@ -136,6 +137,7 @@ pub(crate) fn now_secs() -> i64 {
 /// Open an operation row. No-op when no journal is installed.
 pub fn journal_open(open: OpenOperation) {
     if let Some(j) = current_journal() {
+        undo::note_open(&open);
         j.open(open);
     }
 }
@@ -159,7 +161,11 @@ pub fn journal_note_coverage(op_id: &str, coverage: SearchCoverage, reason: Opti
 /// tests). No-op — returning zero counts — when no journal is installed.
 pub fn journal_finalize(op_id: &str, inputs: FinalizeInputs) -> FinalizeOutcome {
     match current_journal() {
-        Some(j) => j.finalize(op_id, inputs),
+        Some(j) => {
+            let outcome = j.finalize(op_id, inputs);
+            undo::note_finalized(op_id);
+            outcome
+        }
         None => FinalizeOutcome {
             rollback_unit_rows: 0,
             search_only_rows: 0,
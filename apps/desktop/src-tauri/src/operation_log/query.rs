@@ -1,7 +1,7 @@
 //! The read side of the operation log (the query API): filtered/paged search, a paged
-//! operation detail, and the recent-operations feed. Every function takes a
-//! short-lived read-only [`Connection`](rusqlite::Connection) (the writer thread
-//! owns the single write connection; reads never contend under WAL).
+//! operation detail, the recent-operations feed, and the settled-operations history.
+//! Every function takes a short-lived read-only [`Connection`](rusqlite::Connection)
+//! (the writer thread owns the single write connection; reads never contend under WAL).
 //!
 //! ## Search is index-served and spans every `row_role`
 //!
@@ -82,8 +82,9 @@ pub struct OperationSearchFilters {
 pub struct OperationDetail {
     pub operation: OperationRow,
     pub items: Vec<OperationItemView>,
-    /// The op's total item count (all `row_role`s), so a paged UI knows whether
-    /// more items exist beyond the returned slice.
+    /// The op's total item count (all `row_role`s; only `failed` ones when the
+    /// detail was asked for errors only), so a paged UI knows whether more
+    /// items exist beyond the returned slice.
     pub total_items: u32,
 }
 
@@ -149,13 +150,55 @@ pub fn recent_operations(
     Ok(out)
 }
 
+/// A settled operation as the Activity panel lists it: the header plus the two things
+/// the header doesn't hold directly. Returned by [`operation_history`].
+#[derive(Debug, Clone, PartialEq, serde::Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct OperationHistoryEntry {
+    pub operation: OperationRow,
+    /// `ended_at - started_at`, in seconds. Never negative, even across a clock change.
+    pub duration_secs: i64,
+    /// The source name of the op's first `rollback_unit` item, so the UI can say
+    /// "`report.pdf` and 3 more" from this and `item_count`. `None` for an op that
+    /// journaled no items.
+    pub first_item_name: Option<String>,
+}
+
+/// The most recently settled operations (newest `ended_at` first), at most `limit`.
+/// Unlike [`recent_operations`], skips ops still queued or running.
+pub fn operation_history(conn: &Connection, limit: u32) -> Result<Vec<OperationHistoryEntry>, OperationLogStoreError> {
+    let sql = format!(
+        "SELECT {OPERATION_COLUMNS}, \
+             (SELECT i.source_name FROM operation_items i \
+              WHERE i.op_id = operations.op_id AND i.row_role = ?2 ORDER BY i.seq LIMIT 1) AS first_item_name \
+         FROM operations WHERE ended_at IS NOT NULL ORDER BY ended_at DESC, op_id DESC LIMIT ?1"
+    );
+    let mut stmt = conn.prepare_cached(&sql)?;
+    let mut rows = stmt.query(rusqlite::params![limit, RowRole::RollbackUnit.as_token()])?;
+    let mut out = Vec::new();
+    while let Some(row) = rows.next()? {
+        let operation = map_operation_row(row)?;
+        let first_item_name: Option<String> = row.get("first_item_name")?;
+        let ended_at = operation.ended_at.unwrap_or(operation.started_at);
+        out.push(OperationHistoryEntry {
+            duration_secs: (ended_at - operation.started_at).max(0),
+            first_item_name,
+            operation,
+        });
+    }
+    Ok(out)
+}
+
 /// One operation's header plus a page of its items (seq ascending, for grouped
-/// display), dir prefixes resolved. `None` if the op is absent.
+/// display), dir prefixes resolved. `None` if the op is absent. With
+/// `errors_only`, only `failed` items are paged and counted, so a UI can list
+/// just what didn't make it out of a `continue_on_error` run.
 pub fn get_operation(
     conn: &Connection,
     op_id: &str,
     item_limit: u32,
     item_offset: u32,
+    errors_only: bool,
 ) -> Result<Option<OperationDetail>, OperationLogStoreError> {
     let op_sql = format!("SELECT {OPERATION_COLUMNS} FROM operations WHERE op_id = ?1");
     let operation = {
@@ -167,17 +210,21 @@ pub fn get_operation(
         }
     };
 
+    // `?4 IS NULL` keeps one statement (and one cached plan) for both modes.
+    let outcome_filter = errors_only.then(|| ItemOutcome::Failed.as_token());
     let total_items: u32 = conn.query_row(
-        "SELECT COUNT(*) FROM operation_items WHERE op_id = ?1",
-        rusqlite::params![op_id],
+        "SELECT COUNT(*) FROM operation_items WHERE op_id = ?1 AND (?2 IS NULL OR outcome = ?2)",
+        rusqlite::params![op_id, outcome_filter],
         |row| row.get::<_, i64>(0).map(|n| n as u32),
     )?;
 
-    let item_sql =
-        format!("SELECT {ITEM_COLUMNS} FROM operation_items WHERE op_id = ?1 ORDER BY seq ASC LIMIT ?2 OFFSET ?3");
+    let item_sql = format!(
+        "SELECT {ITEM_COLUMNS} FROM operation_items WHERE op_id = ?1 AND (?4 IS NULL OR outcome = ?4) \
+         ORDER BY seq ASC LIMIT ?2 OFFSET ?3"
+    );
     let rows = {
         let mut stmt = conn.prepare_cached(&item_sql)?;
-        let mut q = stmt.query(rusqlite::params![op_id, item_limit, item_offset])?;
+        let mut q = stmt.query(rusqlite::params![op_id, item_limit, item_offset, outcome_filter])?;
         let mut collected = Vec::new();
         while let Some(row) = q.next()? {
             collected.push(map_item_row(row)?);
@@ -1,7 +1,7 @@
 //! Query-API tests: the D8 name-search benchmark (index-served, asserted via
 //! `EXPLAIN QUERY PLAN`), leaf search through `search_only` rows, the
-//! `top_level_only` known-gap flag, composed filters, stable paging, and the
-//! paged operation detail.
+//! `top_level_only` known-gap flag, composed filters, stable paging, the settled
+//! history, and the paged operation detail.
 
 use rusqlite::Connection;
 
@@ -360,6 +360,64 @@ fn recent_paging_is_stable() {
     writer.shutdown();
 }
 
+/// The history lists only settled ops, by end time, with the duration and the first
+/// top-level item's name (never a `search_only` leaf).
+#[test]
+fn history_lists_settled_ops_with_duration_and_first_item() {
+    let (store, writer, _dir) = fresh();
+    journal_op(
+        &writer,
+        "op-trash",
+        OpKind::Trash,
+        Initiator::User,
+        10,
+        70,
+        SearchCoverage::Full,
+        None,
+        vec![
+            leaf(0, "/home/pics/trip", "beach.jpg", RowRole::SearchOnly),
+            leaf(1, "/home/pics", "trip", RowRole::RollbackUnit),
+            leaf(2, "/home/pics", "dog.jpg", RowRole::RollbackUnit),
+        ],
+    );
+    journal_op(
+        &writer,
+        "op-empty",
+        OpKind::Delete,
+        Initiator::User,
+        20,
+        25,
+        SearchCoverage::Full,
+        None,
+        vec![],
+    );
+    writer
+        .open_operation(OpenOperation {
+            op_id: "op-running".to_string(),
+            kind: OpKind::Copy,
+            initiator: Initiator::User,
+            source_volume_id: Some("vol-1".to_string()),
+            dest_volume_id: None,
+            item_count: 0,
+            started_at: 90,
+            rolls_back_op_id: None,
+            execution_status: ExecutionStatus::Running,
+        })
+        .expect("open");
+    writer.flush_blocking().expect("flush");
+
+    let history = operation_history(store.conn(), 10).expect("history");
+    let ids: Vec<&str> = history.iter().map(|e| e.operation.op_id.as_str()).collect();
+    assert_eq!(ids, vec!["op-trash", "op-empty"]);
+    assert_eq!(history[0].duration_secs, 60);
+    assert_eq!(history[0].first_item_name.as_deref(), Some("trip"));
+    assert_eq!(history[1].duration_secs, 5);
+    assert_eq!(history[1].first_item_name, None);
+    assert_eq!(operation_history(store.conn(), 1).expect("limited").len(), 1);
+
+    writer.shutdown();
+}
+
 /// `get_operation` returns the header plus a page of items in seq order with dir
 /// prefixes resolved to full paths, and reports the total item count so a paged UI
 /// knows more remain.
@@ -382,7 +440,9 @@ fn get_operation_returns_header_and_paged_items() {
         ],
     );
 
-    let detail = get_operation(store.conn(), "op", 2, 0).expect("get").expect("present");
+    let detail = get_operation(store.conn(), "op", 2, 0, false)
+        .expect("get")
+        .expect("present");
     assert_eq!(detail.operation.op_id, "op");
     assert_eq!(detail.total_items, 3, "reports the full count, not the page size");
     assert_eq!(detail.items.len(), 2, "first page of two");
@@ -390,19 +450,65 @@ fn get_operation_returns_header_and_paged_items() {
     assert_eq!(detail.items[0].source_path, "/home/docs/a.txt", "dir prefix resolved");
     assert_eq!(detail.items[0].source_volume_id, "vol-1");
 
-    let page2 = get_operation(store.conn(), "op", 2, 2).expect("get").expect("present");
+    let page2 = get_operation(store.conn(), "op", 2, 2, false)
+        .expect("get")
+        .expect("present");
     assert_eq!(page2.items.len(), 1, "remaining item");
     assert_eq!(page2.items[0].seq, 2);
     assert_eq!(page2.items[0].source_path, "/home/docs/c.txt");
 
     assert!(
-        get_operation(store.conn(), "absent", 10, 0).expect("get").is_none(),
+        get_operation(store.conn(), "absent", 10, 0, false)
+            .expect("get")
+            .is_none(),
         "absent op ⇒ None"
     );
 
     writer.shutdown();
 }
 
+/// `errors_only` pages and counts just the `failed` items, for the "show what
+/// didn't make it" view after a copy that continued past errors.
+#[test]
+fn get_operation_errors_only_returns_just_the_failed_items() {
+    let (store, writer, _dir) = fresh();
+    let failed = |seq, name| JournalItem {
+        outcome: ItemOutcome::Failed,
+        ..leaf(seq, "/home/docs", name, RowRole::RollbackUnit)
+    };
+    journal_op(
+        &writer,
+        "op",
+        OpKind::Copy,
+        Initiator::User,
+        10,
+        20,
+        SearchCoverage::Full,
+        None,
+        vec![
+            leaf(0, "/home/docs", "a.txt", RowRole::RollbackUnit),
+            failed(1, "locked.txt"),
+            leaf(2, "/home/docs", "c.txt", RowRole::RollbackUnit),
+            failed(3, "gone.txt"),
+        ],
+    );
+
+    let detail = get_operation(store.conn(), "op", 10, 0, true)
+        .expect("get")
+        .expect("present");
+    assert_eq!(detail.total_items, 2, "counts only the failures");
+    let names: Vec<_> = detail.items.iter().map(|i| i.source_path.as_str()).collect();
+    assert_eq!(names, ["/home/docs/locked.txt", "/home/docs/gone.txt"]);
+    assert!(detail.items.iter().all(|i| i.outcome == ItemOutcome::Failed));
+
+    let all = get_operation(store.conn(), "op", 10, 0, false)
+        .expect("get")
+        .expect("present");
+    assert_eq!(all.total_items, 4);
+
+    writer.shutdown();
+}
+
 /// A prefix name match spans the folded b-tree range: `report` matches
 /// `report-2026.pdf` but not `annual.pdf`.
 #[test]
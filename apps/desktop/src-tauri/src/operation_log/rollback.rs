@@ -149,8 +149,9 @@ pub fn inverse_kind(kind: OpKind) -> OpKind {
         OpKind::Copy | OpKind::CreateFolder | OpKind::CreateFile | OpKind::ArchiveEdit => OpKind::Delete,
         OpKind::Move | OpKind::Trash => OpKind::Move,
         OpKind::Rename => OpKind::Rename,
-        // Delete is gated op-level (never rollbackable); its inverse is unreachable.
+        // Delete and chmod are gated op-level (never rollbackable); their inverse is unreachable.
         OpKind::Delete => OpKind::Delete,
+        OpKind::SetPermissions => OpKind::SetPermissions,
     }
 }
 
@@ -178,8 +179,8 @@ fn inverse_action(kind: OpKind, entry_type: EntryType) -> Option<InverseAction>
         OpKind::CreateFile | OpKind::ArchiveEdit => Some(InverseAction::RemoveFileIfUnchanged),
         OpKind::CreateFolder => Some(InverseAction::RemoveDirIfEmpty),
         OpKind::Move | OpKind::Trash | OpKind::Rename => Some(InverseAction::RestoreMove),
-        // Delete is never rollbackable (gated before we reach items).
-        OpKind::Delete => None,
+        // Delete and chmod are never rollbackable (gated before we reach items).
+        OpKind::Delete | OpKind::SetPermissions => None,
     }
 }
 
@@ -63,6 +63,7 @@ token_enum! {
         CreateFolder => "create_folder",
         CreateFile => "create_file",
         ArchiveEdit => "archive_edit",
+        SetPermissions => "set_permissions",
     }
 }
 
@@ -129,6 +130,8 @@ token_enum! {
         /// A `rollback_unit` row was dropped/errored, so the journal is an
         /// incomplete record of what to reverse (D4 completeness).
         JournalIncomplete => "journal_incomplete",
+        /// A recursive chmod doesn't record each item's previous mode.
+        PermissionChange => "permission_change",
     }
 }
 
@@ -215,7 +218,17 @@ mod tests {
 
         check!(
             OpKind,
-            [Copy, Move, Delete, Trash, Rename, CreateFolder, CreateFile, ArchiveEdit]
+            [
+                Copy,
+                Move,
+                Delete,
+                Trash,
+                Rename,
+                CreateFolder,
+                CreateFile,
+                ArchiveEdit,
+                SetPermissions
+            ]
         );
         check!(ArchiveSubkind, [Compress, Edit, Extract]);
         check!(Initiator, [User, AiClient, Agent]);
@@ -237,7 +250,8 @@ mod tests {
                 PermanentDelete,
                 ArchiveOverwrite,
                 ZipEditUnsupported,
-                JournalIncomplete
+                JournalIncomplete,
+                PermissionChange
             ]
         );
         check!(SearchCoverage, [Full, TopLevelOnly]);
@@ -0,0 +1,338 @@
+//! The in-memory undo / redo stack over the journal.
+//!
+//! Undo is a rollback: the stack holds journaled op ids, and undoing one dispatches
+//! the rollback engine on it (with all of its fail-safe rechecks). Redo rolls back
+//! the inverse op the undo created, since a move / rename / trash undo is itself
+//! rollbackable. The journal stays the source of truth; this is only "which op is
+//! next", lost on restart by design.
+//!
+//! Fed from the journal record points in `mod.rs`: [`note_open`] remembers each
+//! top-level op (never an inverse, which carries `rolls_back_op_id`), and
+//! [`note_finalized`] files it by kind:
+//!
+//! - move / rename / trash push an undoable entry;
+//! - copy pushes a marker that says "can't undo this" instead of silently
+//!   skipping to an older op;
+//! - a permanent delete (including emptying the trash) clears the stack: it may
+//!   have destroyed what an older inverse would restore, then it's marked too;
+//! - anything else (create, archive edit, permissions) is left out.
+//!
+//! Every new top-level op clears the redo stack, like any editor's undo.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{LazyLock, Mutex};
+
+use crate::ignore_poison::IgnorePoison;
+
+use super::rollback::RollbackRefusal;
+use super::types::OpKind;
+use super::writer::OpenOperation;
+
+/// Entries kept per stack. The oldest undo entry falls off past this.
+const MAX_UNDO_ENTRIES: usize = 50;
+
+/// One step on the undo or redo stack.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct UndoEntry {
+    /// The journaled op to roll back: the original op on the undo stack, the
+    /// inverse op on the redo stack.
+    pub op_id: String,
+    /// The kind of the user's original op, for "Undo Move" / "Redo Rename" labels.
+    pub kind: OpKind,
+    /// `false` for a copy or permanent delete: shown so the user knows why undo
+    /// stops there, never rolled back.
+    pub undoable: bool,
+    /// The op (an inverse, after an undo or redo) hasn't finalized yet, so the
+    /// journal can't roll it back until it does.
+    pub running: bool,
+}
+
+/// A dispatched undo or redo. The reversal runs as a managed op, like any rollback.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct UndoDispatch {
+    pub kind: OpKind,
+    pub inverse_op_id: String,
+}
+
+/// Why an undo or redo didn't run. Typed across IPC like [`RollbackRefusal`].
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, specta::Type)]
+#[serde(
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase",
+    tag = "kind",
+    content = "detail"
+)]
+pub enum UndoRefusal {
+    NothingToUndo,
+    NothingToRedo,
+    /// The last op was a copy or a permanent delete. Its marker is dropped, so the
+    /// next undo reaches the op before it (if a delete didn't clear the stack).
+    NotUndoable {
+        op_kind: OpKind,
+    },
+    /// The previous undo or redo hasn't finished; the entry stays for a retry.
+    StillRunning,
+    /// The rollback engine refused. A volume that's away or a rollback in flight
+    /// keeps the entry for a retry; anything else drops it.
+    Rollback(RollbackRefusal),
+}
+
+/// Whether a refusal may pass on its own, so the entry is worth keeping.
+pub(crate) fn is_transient(refusal: &RollbackRefusal) -> bool {
+    matches!(
+        refusal,
+        RollbackRefusal::AlreadyRollingBack | RollbackRefusal::VolumeUnavailable { .. }
+    )
+}
+
+/// The top of both stacks, for menu labels.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct UndoState {
+    pub next_undo: Option<UndoEntry>,
+    pub next_redo: Option<UndoEntry>,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct UndoStack {
+    undo: VecDeque<UndoEntry>,
+    redo: Vec<UndoEntry>,
+    /// Top-level ops opened but not yet finalized, by op id.
+    open: HashMap<String, OpKind>,
+    /// Inverse ops that finalized, newest last, so one that finishes before its
+    /// entry is pushed isn't stuck as running.
+    finalized_inverses: VecDeque<String>,
+}
+
+impl UndoStack {
+    fn note_open(&mut self, op_id: &str, kind: OpKind) {
+        self.open.insert(op_id.to_string(), kind);
+    }
+
+    fn note_finalized(&mut self, op_id: &str) {
+        let Some(kind) = self.open.remove(op_id) else {
+            // An inverse op (or one opened before a restart).
+            self.inverse_finalized(op_id);
+            return;
+        };
+        let undoable = match kind {
+            OpKind::Move | OpKind::Rename | OpKind::Trash => true,
+            OpKind::Copy => false,
+            OpKind::Delete => {
+                self.undo.clear();
+                false
+            }
+            OpKind::CreateFolder | OpKind::CreateFile | OpKind::ArchiveEdit | OpKind::SetPermissions => {
+                self.redo.clear();
+                return;
+            }
+        };
+        self.redo.clear();
+        self.push_undo(UndoEntry {
+            op_id: op_id.to_string(),
+            kind,
+            undoable,
+            running: false,
+        });
+    }
+
+    fn inverse_finalized(&mut self, op_id: &str) {
+        let entry = self
+            .undo
+            .iter_mut()
+            .chain(self.redo.iter_mut())
+            .find(|e| e.op_id == op_id);
+        match entry {
+            Some(entry) => entry.running = false,
+            None => {
+                if self.finalized_inverses.len() == MAX_UNDO_ENTRIES {
+                    self.finalized_inverses.pop_front();
+                }
+                self.finalized_inverses.push_back(op_id.to_string());
+            }
+        }
+    }
+
+    /// An entry for a just-dispatched inverse op, running unless it already finalized.
+    fn inverse_entry(&mut self, entry: UndoEntry, inverse_op_id: String) -> UndoEntry {
+        let finished = self.finalized_inverses.iter().position(|id| *id == inverse_op_id);
+        if let Some(index) = finished {
+            self.finalized_inverses.remove(index);
+        }
+        UndoEntry {
+            op_id: inverse_op_id,
+            running: finished.is_none(),
+            ..entry
+        }
+    }
+
+    fn push_undo(&mut self, entry: UndoEntry) {
+        if self.undo.len() == MAX_UNDO_ENTRIES {
+            self.undo.pop_front();
+        }
+        self.undo.push_back(entry);
+    }
+
+    fn push_redo(&mut self, entry: UndoEntry) {
+        if self.redo.len() == MAX_UNDO_ENTRIES {
+            self.redo.remove(0);
+        }
+        self.redo.push(entry);
+    }
+
+    pub(crate) fn pop_undo(&mut self) -> Option<UndoEntry> {
+        self.undo.pop_back()
+    }
+
+    pub(crate) fn pop_redo(&mut self) -> Option<UndoEntry> {
+        self.redo.pop()
+    }
+
+    /// Records a dispatched undo: redoing it rolls back `inverse_op_id`.
+    pub(crate) fn undone(&mut self, entry: UndoEntry, inverse_op_id: String) {
+        let entry = self.inverse_entry(entry, inverse_op_id);
+        self.push_redo(entry);
+    }
+
+    /// Records a dispatched redo: undoing it again rolls back `inverse_op_id`.
+    pub(crate) fn redone(&mut self, entry: UndoEntry, inverse_op_id: String) {
+        let entry = self.inverse_entry(entry, inverse_op_id);
+        self.push_undo(entry);
+    }
+
+    /// Puts back an entry whose rollback was refused for a reason that may pass
+    /// (the op is still rolling back, a volume is disconnected).
+    pub(crate) fn restore_undo(&mut self, entry: UndoEntry) {
+        self.push_undo(entry);
+    }
+
+    pub(crate) fn restore_redo(&mut self, entry: UndoEntry) {
+        self.push_redo(entry);
+    }
+
+    pub(crate) fn state(&self) -> UndoState {
+        UndoState {
+            next_undo: self.undo.back().cloned(),
+            next_redo: self.redo.last().cloned(),
+        }
+    }
+}
+
+static UNDO_STACK: LazyLock<Mutex<UndoStack>> = LazyLock::new(|| Mutex::new(UndoStack::default()));
+
+/// Runs `f` on the process-global stack.
+pub(crate) fn with_stack<T>(f: impl FnOnce(&mut UndoStack) -> T) -> T {
+    f(&mut UNDO_STACK.lock_ignore_poison())
+}
+
+/// Remembers a top-level op so its finalize can file it. Inverse ops are skipped:
+/// undo and redo file those themselves.
+pub(crate) fn note_open(open: &OpenOperation) {
+    if open.rolls_back_op_id.is_none() {
+        with_stack(|stack| stack.note_open(&open.op_id, open.kind));
+    }
+}
+
+/// Files a finalized op onto the stack (see the module docs for the per-kind rules).
+pub(crate) fn note_finalized(op_id: &str) {
+    with_stack(|stack| stack.note_finalized(op_id));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn finish(stack: &mut UndoStack, op_id: &str, kind: OpKind) {
+        stack.note_open(op_id, kind);
+        stack.note_finalized(op_id);
+    }
+
+    #[test]
+    fn undo_then_redo_round_trips_through_inverse_ids() {
+        let mut stack = UndoStack::default();
+        finish(&mut stack, "move-1", OpKind::Move);
+
+        let entry = stack.pop_undo().unwrap();
+        assert_eq!(entry.op_id, "move-1");
+        stack.undone(entry, "inverse-1".to_string());
+        assert_eq!(stack.state().next_undo, None);
+
+        assert!(stack.state().next_redo.unwrap().running);
+        stack.note_finalized("inverse-1");
+
+        let entry = stack.pop_redo().unwrap();
+        assert_eq!(entry.op_id, "inverse-1");
+        assert_eq!(entry.kind, OpKind::Move);
+        assert!(!entry.running);
+        stack.redone(entry, "inverse-2".to_string());
+        assert_eq!(stack.state().next_undo.unwrap().op_id, "inverse-2");
+    }
+
+    #[test]
+    fn copies_are_marked_not_undoable() {
+        let mut stack = UndoStack::default();
+        finish(&mut stack, "rename-1", OpKind::Rename);
+        finish(&mut stack, "copy-1", OpKind::Copy);
+
+        let top = stack.pop_undo().unwrap();
+        assert_eq!(top.kind, OpKind::Copy);
+        assert!(!top.undoable);
+        assert!(stack.pop_undo().unwrap().undoable);
+    }
+
+    #[test]
+    fn permanent_delete_clears_older_entries() {
+        let mut stack = UndoStack::default();
+        finish(&mut stack, "trash-1", OpKind::Trash);
+        finish(&mut stack, "delete-1", OpKind::Delete);
+
+        assert!(!stack.pop_undo().unwrap().undoable);
+        assert_eq!(stack.pop_undo(), None);
+    }
+
+    #[test]
+    fn a_new_op_clears_redo() {
+        let mut stack = UndoStack::default();
+        finish(&mut stack, "move-1", OpKind::Move);
+        let entry = stack.pop_undo().unwrap();
+        stack.undone(entry, "inverse-1".to_string());
+
+        finish(&mut stack, "folder-1", OpKind::CreateFolder);
+        assert_eq!(stack.state(), UndoState::default());
+    }
+
+    #[test]
+    fn unfinished_or_unknown_ops_are_ignored() {
+        let mut stack = UndoStack::default();
+        stack.note_finalized("never-opened");
+        assert_eq!(stack.state(), UndoState::default());
+    }
+
+    #[test]
+    fn an_inverse_that_finishes_before_its_entry_is_not_running() {
+        let mut stack = UndoStack::default();
+        finish(&mut stack, "rename-1", OpKind::Rename);
+        let entry = stack.pop_undo().unwrap();
+        stack.note_finalized("inverse-1");
+        stack.undone(entry, "inverse-1".to_string());
+        assert!(!stack.state().next_redo.unwrap().running);
+    }
+
+    #[test]
+    fn stack_is_bounded() {
+        let mut stack = UndoStack::default();
+        for i in 0..=MAX_UNDO_ENTRIES {
+            finish(&mut stack, &format!("move-{i}"), OpKind::Move);
+        }
+        let mut count = 0;
+        let mut last = None;
+        while let Some(entry) = stack.pop_undo() {
+            count += 1;
+            last = Some(entry.op_id);
+        }
+        assert_eq!(count, MAX_UNDO_ENTRIES);
+        assert_eq!(last.as_deref(), Some("move-1"));
+    }
+}
@@ -0,0 +1,467 @@
+//! The backend registry of command-palette actions.
+//!
+//! One table ([`ACTIONS`]) of the commands the palette offers, each with its
+//! registry id (the same `CommandId` the menu and MCP emit through
+//! `execute-command`), an English label, search keywords, and a [`Requires`]
+//! predicate. [`actions_for`] evaluates the predicates against a
+//! [`PaletteContext`]; [`invoke`] dispatches an id to the main window exactly like
+//! a menu click, so the palette, the menu, and MCP tools land in the same
+//! frontend dispatcher.
+//!
+//! The ids are drift-checked against the frontend `COMMAND_IDS` by
+//! `src/lib/commands/rust-command-id-drift.test.ts`, which parses the `id`
+//! literals out of the `ACTIONS` table.
+
+use tauri::{AppHandle, Runtime};
+use tauri_specta::Event as _;
+
+use crate::window_events::ExecuteCommand;
+
+/// What must hold for an action to be enabled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Requires {
+    Nothing,
+    /// A selection, or a real entry (not `..`) under the cursor.
+    Targets,
+    /// A real entry under the cursor; selections are ignored.
+    EntryUnderCursor,
+    /// File paths on the system clipboard.
+    ClipboardPaths,
+    CanGoBack,
+    CanGoForward,
+    /// The focused pane isn't at a volume root.
+    Parent,
+    /// More than one tab in the focused pane.
+    OtherTabs,
+}
+
+struct ActionSpec {
+    id: &'static str,
+    label: &'static str,
+    keywords: &'static [&'static str],
+    requires: Requires,
+}
+
+/// Every palette action, grouped like the menus. Keep ids in sync with the
+/// frontend registry (the drift test catches a typo).
+const ACTIONS: &[ActionSpec] = &[
+    // Files
+    ActionSpec {
+        id: "nav.open",
+        label: "Open",
+        keywords: &["enter", "launch"],
+        requires: Requires::EntryUnderCursor,
+    },
+    ActionSpec {
+        id: "file.view",
+        label: "View",
+        keywords: &["preview", "read"],
+        requires: Requires::EntryUnderCursor,
+    },
+    ActionSpec {
+        id: "file.edit",
+        label: "Edit",
+        keywords: &["editor", "modify"],
+        requires: Requires::EntryUnderCursor,
+    },
+    ActionSpec {
+        id: "file.rename",
+        label: "Rename",
+        keywords: &["name"],
+        requires: Requires::EntryUnderCursor,
+    },
+    ActionSpec {
+        id: "file.copy",
+        label: "Copy to other pane",
+        keywords: &["duplicate", "transfer"],
+        requires: Requires::Targets,
+    },
+    ActionSpec {
+        id: "file.move",
+        label: "Move to other pane",
+        keywords: &["transfer", "relocate"],
+        requires: Requires::Targets,
+    },
+    ActionSpec {
+        id: "file.compress",
+        label: "Compress",
+        keywords: &["zip", "archive"],
+        requires: Requires::Targets,
+    },
+    ActionSpec {
+        id: "file.newFolder",
+        label: "New folder",
+        keywords: &["create", "directory", "mkdir"],
+        requires: Requires::Nothing,
+    },
+    ActionSpec {
+        id: "file.delete",
+        label: "Move to trash",
+        keywords: &["delete", "remove", "bin"],
+        requires: Requires::Targets,
+    },
+    ActionSpec {
+        id: "file.deletePermanently",
+        label: "Delete permanently",
+        keywords: &["remove", "erase"],
+        requires: Requires::Targets,
+    },
+    ActionSpec {
+        id: "file.showInFinder",
+        label: "Show in Finder",
+        keywords: &["reveal", "file manager"],
+        requires: Requires::EntryUnderCursor,
+    },
+    ActionSpec {
+        id: "file.copyPath",
+        label: "Copy path",
+        keywords: &["clipboard", "location"],
+        requires: Requires::Targets,
+    },
+    ActionSpec {
+        id: "file.copyFilename",
+        label: "Copy filename",
+        keywords: &["clipboard", "name"],
+        requires: Requires::Targets,
+    },
+    ActionSpec {
+        id: "file.copyCurrentDirectoryPath",
+        label: "Copy current folder path",
+        keywords: &["clipboard", "directory", "location"],
+        requires: Requires::Nothing,
+    },
+    ActionSpec {
+        id: "file.getInfo",
+        label: "Get info",
+        keywords: &["properties", "details"],
+        requires: Requires::EntryUnderCursor,
+    },
+    ActionSpec {
+        id: "file.quickLook",
+        label: "Quick look",
+        keywords: &["preview"],
+        requires: Requires::EntryUnderCursor,
+    },
+    // Clipboard
+    ActionSpec {
+        id: "edit.cut",
+        label: "Cut",
+        keywords: &["clipboard", "move"],
+        requires: Requires::Targets,
+    },
+    ActionSpec {
+        id: "edit.copy",
+        label: "Copy",
+        keywords: &["clipboard"],
+        requires: Requires::Targets,
+    },
+    ActionSpec {
+        id: "edit.paste",
+        label: "Paste",
+        keywords: &["clipboard"],
+        requires: Requires::ClipboardPaths,
+    },
+    ActionSpec {
+        id: "edit.pasteAsMove",
+        label: "Move here",
+        keywords: &["clipboard", "paste", "cut"],
+        requires: Requires::ClipboardPaths,
+    },
+    // Selection
+    ActionSpec {
+        id: "selection.selectAll",
+        label: "Select all",
+        keywords: &["mark"],
+        requires: Requires::Nothing,
+    },
+    ActionSpec {
+        id: "selection.deselectAll",
+        label: "Deselect all",
+        keywords: &["unmark", "clear"],
+        requires: Requires::Targets,
+    },
+    ActionSpec {
+        id: "selection.selectFiles",
+        label: "Select files…",
+        keywords: &["pattern", "mark", "filter"],
+        requires: Requires::Nothing,
+    },
+    ActionSpec {
+        id: "selection.deselectFiles",
+        label: "Deselect files…",
+        keywords: &["pattern", "unmark"],
+        requires: Requires::Targets,
+    },
+    // Navigation
+    ActionSpec {
+        id: "nav.back",
+        label: "Back",
+        keywords: &["history", "previous"],
+        requires: Requires::CanGoBack,
+    },
+    ActionSpec {
+        id: "nav.forward",
+        label: "Forward",
+        keywords: &["history", "next"],
+        requires: Requires::CanGoForward,
+    },
+    ActionSpec {
+        id: "nav.parent",
+        label: "Go to parent folder",
+        keywords: &["up", "enclosing"],
+        requires: Requires::Parent,
+    },
+    ActionSpec {
+        id: "nav.goToPath",
+        label: "Go to path…",
+        keywords: &["jump", "folder", "location"],
+        requires: Requires::Nothing,
+    },
+    ActionSpec {
+        id: "downloads.goToLatest",
+        label: "Go to latest download",
+        keywords: &["downloads", "recent"],
+        requires: Requires::Nothing,
+    },
+    ActionSpec {
+        id: "favorites.add",
+        label: "Add to favorites",
+        keywords: &["bookmark", "pin"],
+        requires: Requires::Nothing,
+    },
+    ActionSpec {
+        id: "search.open",
+        label: "Search files",
+        keywords: &["find", "locate"],
+        requires: Requires::Nothing,
+    },
+    // Panes and tabs
+    ActionSpec {
+        id: "pane.switch",
+        label: "Switch pane",
+        keywords: &["focus", "other side"],
+        requires: Requires::Nothing,
+    },
+    ActionSpec {
+        id: "pane.swap",
+        label: "Swap panes",
+        keywords: &["exchange", "flip"],
+        requires: Requires::Nothing,
+    },
+    ActionSpec {
+        id: "tab.new",
+        label: "New tab",
+        keywords: &["open"],
+        requires: Requires::Nothing,
+    },
+    ActionSpec {
+        id: "tab.close",
+        label: "Close tab",
+        keywords: &[],
+        requires: Requires::OtherTabs,
+    },
+    ActionSpec {
+        id: "tab.closeOthers",
+        label: "Close other tabs",
+        keywords: &[],
+        requires: Requires::OtherTabs,
+    },
+    ActionSpec {
+        id: "tab.reopen",
+        label: "Reopen closed tab",
+        keywords: &["restore", "undo"],
+        requires: Requires::Nothing,
+    },
+    ActionSpec {
+        id: "tab.next",
+        label: "Next tab",
+        keywords: &[],
+        requires: Requires::OtherTabs,
+    },
+    ActionSpec {
+        id: "tab.prev",
+        label: "Previous tab",
+        keywords: &[],
+        requires: Requires::OtherTabs,
+    },
+    // App
+    ActionSpec {
+        id: "app.settings",
+        label: "Settings",
+        keywords: &["preferences", "options"],
+        requires: Requires::Nothing,
+    },
+    ActionSpec {
+        id: "queue.show",
+        label: "Show operation queue",
+        keywords: &["transfers", "progress"],
+        requires: Requires::Nothing,
+    },
+    ActionSpec {
+        id: "log.operationLog",
+        label: "Operation log",
+        keywords: &["history", "undo", "journal"],
+        requires: Requires::Nothing,
+    },
+    ActionSpec {
+        id: "help.openShortcuts",
+        label: "Keyboard shortcuts",
+        keywords: &["keys", "bindings", "help"],
+        requires: Requires::Nothing,
+    },
+    ActionSpec {
+        id: "view.zoom.in",
+        label: "Zoom in",
+        keywords: &["bigger", "text size"],
+        requires: Requires::Nothing,
+    },
+    ActionSpec {
+        id: "view.zoom.out",
+        label: "Zoom out",
+        keywords: &["smaller", "text size"],
+        requires: Requires::Nothing,
+    },
+];
+
+/// The focused pane's state the enabled predicates read. The frontend sends it;
+/// the clipboard part is filled in by the command, never trusted from the caller.
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct PaletteContext {
+    pub selected_count: u32,
+    /// The cursor is on a real entry, not on `..` or an empty listing.
+    pub cursor_on_entry: bool,
+    pub can_go_back: bool,
+    pub can_go_forward: bool,
+    pub has_parent: bool,
+    pub tab_count: u32,
+    #[serde(skip)]
+    pub clipboard_has_paths: bool,
+}
+
+impl PaletteContext {
+    fn satisfies(&self, requires: Requires) -> bool {
+        match requires {
+            Requires::Nothing => true,
+            Requires::Targets => self.selected_count > 0 || self.cursor_on_entry,
+            Requires::EntryUnderCursor => self.cursor_on_entry,
+            Requires::ClipboardPaths => self.clipboard_has_paths,
+            Requires::CanGoBack => self.can_go_back,
+            Requires::CanGoForward => self.can_go_forward,
+            Requires::Parent => self.has_parent,
+            Requires::OtherTabs => self.tab_count > 1,
+        }
+    }
+}
+
+/// One palette row. Disabled actions are still listed so the palette can show
+/// them greyed out rather than making them vanish.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct PaletteAction {
+    pub id: String,
+    pub label: String,
+    pub keywords: Vec<String>,
+    pub enabled: bool,
+}
+
+/// Why `invoke_palette_action` didn't dispatch.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, specta::Type)]
+#[serde(rename_all = "camelCase", rename_all_fields = "camelCase", tag = "kind")]
+pub enum PaletteInvokeError {
+    UnknownAction {
+        id: String,
+    },
+    /// The main window is gone (or the emit failed), so nothing can run it.
+    DispatchFailed {
+        message: String,
+    },
+}
+
+/// Every registered action, with `enabled` evaluated against `context`.
+pub fn actions_for(context: &PaletteContext) -> Vec<PaletteAction> {
+    ACTIONS
+        .iter()
+        .map(|spec| PaletteAction {
+            id: spec.id.to_string(),
+            label: spec.label.to_string(),
+            keywords: spec.keywords.iter().map(|k| (*k).to_string()).collect(),
+            enabled: context.satisfies(spec.requires),
+        })
+        .collect()
+}
+
+/// Whether `id` is a registered action.
+fn is_registered(id: &str) -> bool {
+    ACTIONS.iter().any(|spec| spec.id == id)
+}
+
+/// Dispatches a registered action to the main window's command dispatcher,
+/// the same `execute-command` route a menu click takes.
+pub fn invoke<R: Runtime>(app: &AppHandle<R>, id: &str) -> Result<(), PaletteInvokeError> {
+    if !is_registered(id) {
+        return Err(PaletteInvokeError::UnknownAction { id: id.to_string() });
+    }
+    ExecuteCommand {
+        command_id: id.to_string(),
+    }
+    .emit_to(app, "main")
+    .map_err(|e| PaletteInvokeError::DispatchFailed { message: e.to_string() })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    fn enabled(context: &PaletteContext, id: &str) -> bool {
+        actions_for(context)
+            .into_iter()
+            .find(|a| a.id == id)
+            .unwrap_or_else(|| panic!("{id} not registered"))
+            .enabled
+    }
+
+    #[test]
+    fn ids_are_unique() {
+        let mut seen = HashSet::new();
+        for spec in ACTIONS {
+            assert!(seen.insert(spec.id), "duplicate palette action {}", spec.id);
+        }
+    }
+
+    #[test]
+    fn paste_needs_clipboard_paths() {
+        let mut context = PaletteContext::default();
+        assert!(!enabled(&context, "edit.paste"));
+        context.clipboard_has_paths = true;
+        assert!(enabled(&context, "edit.paste"));
+        assert!(enabled(&context, "edit.pasteAsMove"));
+    }
+
+    #[test]
+    fn file_actions_follow_selection_and_cursor() {
+        let mut context = PaletteContext::default();
+        assert!(!enabled(&context, "file.copy"));
+        assert!(!enabled(&context, "file.rename"));
+
+        context.selected_count = 3;
+        assert!(enabled(&context, "file.copy"));
+        assert!(
+            !enabled(&context, "file.rename"),
+            "rename acts on the cursor entry only"
+        );
+
+        context.cursor_on_entry = true;
+        assert!(enabled(&context, "file.rename"));
+        assert!(enabled(&context, "file.newFolder"));
+    }
+
+    #[test]
+    fn clipboard_state_is_never_read_from_the_caller() {
+        let json = r#"{"selectedCount":0,"cursorOnEntry":false,"canGoBack":false,"canGoForward":false,
+            "hasParent":false,"tabCount":1,"clipboardHasPaths":true}"#;
+        let context: PaletteContext = serde_json::from_str(json).unwrap();
+        assert!(!context.clipboard_has_paths);
+    }
+}
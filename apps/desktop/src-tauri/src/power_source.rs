@@ -0,0 +1,57 @@
+//! Whether the machine is running on battery, for background work that should hold off
+//! until it's plugged in (the drive index's "only scan on AC power" throttle).
+//!
+//! macOS asks IOKit's power-source API; Linux reads `/sys/class/power_supply`. Anything we
+//! can't read counts as AC power, so a desktop (or an unknown platform) is never paused.
+
+/// `true` when the machine runs on battery. Cheap, but not free: callers poll it every few
+/// seconds, not per item.
+pub fn on_battery() -> bool {
+    #[cfg(target_os = "macos")]
+    {
+        on_battery_macos()
+    }
+    #[cfg(target_os = "linux")]
+    {
+        on_battery_linux()
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "linux")))]
+    {
+        false
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn on_battery_macos() -> bool {
+    /// `kIOPSTimeRemainingUnlimited`: the machine is on an external power source.
+    const TIME_REMAINING_UNLIMITED: f64 = -2.0;
+
+    #[link(name = "IOKit", kind = "framework")]
+    unsafe extern "C" {
+        fn IOPSGetTimeRemainingEstimate() -> f64;
+    }
+
+    // SAFETY: takes no arguments and returns a plain `CFTimeInterval`; no ownership involved.
+    let remaining = unsafe { IOPSGetTimeRemainingEstimate() };
+    // Anything else (`kIOPSTimeRemainingUnknown` while the estimate settles, or a real
+    // estimate) means the battery is draining.
+    remaining != TIME_REMAINING_UNLIMITED
+}
+
+/// On battery when a battery exists and no mains supply reports `online`.
+#[cfg(target_os = "linux")]
+fn on_battery_linux() -> bool {
+    let Ok(supplies) = std::fs::read_dir("/sys/class/power_supply") else {
+        return false;
+    };
+    let mut has_battery = false;
+    for supply in supplies.flatten() {
+        let read = |name: &str| std::fs::read_to_string(supply.path().join(name)).unwrap_or_default();
+        match read("type").trim() {
+            "Mains" | "USB" if read("online").trim() == "1" => return false,
+            "Battery" => has_battery = true,
+            _ => {}
+        }
+    }
+    has_battery
+}
@@ -0,0 +1,215 @@
+//! Per-pane navigation history (back / forward / recent locations), owned by the backend.
+//!
+//! Browser-style, mirroring the frontend's `navigation-history.ts`: `push` adds an entry and
+//! truncates forward history; `back` / `forward` move the cursor. Pushing the current location
+//! again is a no-op (a reload isn't a navigation). The stack is capped at
+//! [`MAX_HISTORY_PER_PANE`], dropping the oldest entries.
+//!
+//! Living here (rather than only in frontend memory) lets the history survive a webview reload and
+//! a restart: `save_session` persists the last [`PERSISTED_HISTORY_PER_PANE`] entries of each pane
+//! and `restore_session` loads them back.
+
+use std::sync::{LazyLock, Mutex};
+
+use serde::{Deserialize, Serialize};
+
+use crate::ignore_poison::IgnorePoison;
+
+/// In-memory cap per pane. Matches the frontend's `MAX_HISTORY_PER_TAB`.
+const MAX_HISTORY_PER_PANE: usize = 100;
+
+/// How many of the most recent entries per pane survive a restart. Enough for a "recent locations"
+/// dropdown without dragging a long-stale trail across sessions.
+pub(super) const PERSISTED_HISTORY_PER_PANE: usize = 20;
+
+/// Which pane a history command targets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub enum HistoryPane {
+    Left,
+    Right,
+}
+
+/// One visited location.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct NavigationEntry {
+    pub volume_id: String,
+    pub path: String,
+}
+
+/// A pane's history: oldest entry first, `current_index` points at the location shown now.
+/// An empty stack has `current_index` 0.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct NavigationHistory {
+    pub entries: Vec<NavigationEntry>,
+    pub current_index: usize,
+}
+
+impl NavigationHistory {
+    /// Records a navigation to `entry`. Drops forward history, then the oldest entries over the cap.
+    pub fn push(&mut self, entry: NavigationEntry) {
+        if self.current() == Some(&entry) {
+            return;
+        }
+        if !self.entries.is_empty() {
+            self.entries.truncate(self.current_index + 1);
+        }
+        self.entries.push(entry);
+        let overflow = self.entries.len().saturating_sub(MAX_HISTORY_PER_PANE);
+        self.entries.drain(..overflow);
+        self.current_index = self.entries.len() - 1;
+    }
+
+    /// Steps back and returns the location to show, or `None` at the oldest entry.
+    pub fn back(&mut self) -> Option<NavigationEntry> {
+        self.current_index = self.current_index.checked_sub(1)?;
+        self.current().cloned()
+    }
+
+    /// Steps forward and returns the location to show, or `None` at the newest entry.
+    pub fn forward(&mut self) -> Option<NavigationEntry> {
+        if self.current_index + 1 >= self.entries.len() {
+            return None;
+        }
+        self.current_index += 1;
+        self.current().cloned()
+    }
+
+    fn current(&self) -> Option<&NavigationEntry> {
+        self.entries.get(self.current_index)
+    }
+
+    /// The newest `limit` entries, with the cursor clamped into them. What gets persisted.
+    pub(super) fn tail(&self, limit: usize) -> NavigationHistory {
+        let start = self.entries.len().saturating_sub(limit);
+        NavigationHistory {
+            entries: self.entries[start..].to_vec(),
+            current_index: self.current_index.saturating_sub(start),
+        }
+    }
+
+    /// Repairs a cursor that points past the end (a hand-edited or truncated file).
+    pub(super) fn sanitized(mut self) -> NavigationHistory {
+        self.current_index = self.current_index.min(self.entries.len().saturating_sub(1));
+        self
+    }
+}
+
+static HISTORIES: LazyLock<Mutex<[NavigationHistory; 2]>> = LazyLock::new(Default::default);
+
+fn slot(pane: HistoryPane) -> usize {
+    match pane {
+        HistoryPane::Left => 0,
+        HistoryPane::Right => 1,
+    }
+}
+
+/// Runs `f` on `pane`'s history under the lock.
+pub fn with_history<T>(pane: HistoryPane, f: impl FnOnce(&mut NavigationHistory) -> T) -> T {
+    f(&mut HISTORIES.lock_ignore_poison()[slot(pane)])
+}
+
+/// A snapshot of `pane`'s history.
+pub fn get(pane: HistoryPane) -> NavigationHistory {
+    with_history(pane, |history| history.clone())
+}
+
+/// Replaces `pane`'s history (used by `restore_session`).
+pub(super) fn set(pane: HistoryPane, history: NavigationHistory) {
+    with_history(pane, |current| *current = history.sanitized());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(path: &str) -> NavigationEntry {
+        NavigationEntry {
+            volume_id: "root".to_string(),
+            path: path.to_string(),
+        }
+    }
+
+    fn history_of(paths: &[&str]) -> NavigationHistory {
+        let mut history = NavigationHistory::default();
+        for path in paths {
+            history.push(entry(path));
+        }
+        history
+    }
+
+    #[test]
+    fn back_and_forward_walk_the_stack_and_stop_at_the_ends() {
+        let mut history = history_of(&["/a", "/b", "/c"]);
+
+        assert_eq!(history.forward(), None);
+        assert_eq!(history.back(), Some(entry("/b")));
+        assert_eq!(history.back(), Some(entry("/a")));
+        assert_eq!(history.back(), None);
+        assert_eq!(history.current_index, 0);
+        assert_eq!(history.forward(), Some(entry("/b")));
+    }
+
+    #[test]
+    fn push_after_back_truncates_forward_history() {
+        let mut history = history_of(&["/a", "/b", "/c"]);
+        history.back();
+        history.back();
+
+        history.push(entry("/d"));
+
+        assert_eq!(history.entries, vec![entry("/a"), entry("/d")]);
+        assert_eq!(history.current_index, 1);
+        assert_eq!(history.forward(), None);
+    }
+
+    #[test]
+    fn pushing_the_current_location_is_a_no_op() {
+        let mut history = history_of(&["/a", "/b"]);
+        history.push(entry("/b"));
+        assert_eq!(history.entries.len(), 2);
+
+        // Same path on another volume is a different location.
+        history.push(NavigationEntry {
+            volume_id: "mtp-1:65537".to_string(),
+            path: "/b".to_string(),
+        });
+        assert_eq!(history.entries.len(), 3);
+    }
+
+    #[test]
+    fn the_stack_is_capped_by_dropping_the_oldest() {
+        let paths: Vec<String> = (0..MAX_HISTORY_PER_PANE + 5).map(|i| format!("/{i}")).collect();
+        let history = history_of(&paths.iter().map(String::as_str).collect::<Vec<_>>());
+
+        assert_eq!(history.entries.len(), MAX_HISTORY_PER_PANE);
+        assert_eq!(history.entries[0], entry("/5"));
+        assert_eq!(history.current_index, MAX_HISTORY_PER_PANE - 1);
+    }
+
+    #[test]
+    fn tail_keeps_the_newest_entries_and_clamps_the_cursor() {
+        let mut history = history_of(&["/a", "/b", "/c", "/d"]);
+        history.back();
+
+        let tail = history.tail(2);
+        assert_eq!(tail.entries, vec![entry("/c"), entry("/d")]);
+        assert_eq!(tail.current_index, 0);
+
+        // A cursor older than the kept window lands on the oldest kept entry.
+        history.back();
+        history.back();
+        assert_eq!(history.tail(2).current_index, 0);
+    }
+
+    #[test]
+    fn sanitized_pulls_an_out_of_range_cursor_back_in() {
+        let history = NavigationHistory {
+            entries: vec![entry("/a")],
+            current_index: 7,
+        };
+        assert_eq!(history.sanitized().current_index, 0);
+    }
+}
@@ -0,0 +1,415 @@
+//! Pane session persistence: what each pane showed at quit, restored on launch.
+//!
+//! The window-state plugin restores geometry only. This module snapshots the
+//! two panes from the MCP [`PaneStateStore`] (which the frontend already keeps
+//! current) into `{app_data_dir}/pane-session.json`, and on launch hands back
+//! the panes that are still reachable.
+//!
+//! ## Design notes
+//!
+//! - The snapshot is taken from `PaneStateStore`, not sent by the frontend, so
+//!   `save_session` needs no arguments and MCP and the session agree on the state.
+//! - The scroll anchor is the NAME under the cursor, not its index: indices
+//!   shift when the folder changes between runs, and the frontend already
+//!   resolves names via `find_file_index`.
+//! - Restore is best-effort per pane. A pane whose volume isn't registered (after
+//!   one attempt to reconnect an MTP device) or whose path no longer exists comes
+//!   back as `None`, and the frontend keeps its default for that pane. Every
+//!   probe is timeout-bounded so a dead mount can't hold up startup.
+//! - Corrupt or schema-mismatched files are quarantined to `.json.broken` and
+//!   treated as "no session", mirroring `go_to_path/history.rs`.
+//! - The same file carries the tail of each pane's back/forward history
+//!   ([`history`]), loaded back into memory by `restore_session`.
+
+pub mod history;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::file_system::get_volume_manager;
+use crate::mcp::PaneStateStore;
+use crate::mcp::pane_state::PaneState;
+use history::{HistoryPane, NavigationHistory, PERSISTED_HISTORY_PER_PANE};
+
+/// Bump when the on-disk shape changes in an incompatible way.
+const CURRENT_SCHEMA_VERSION: u32 = 1;
+
+/// Filename inside `{app_data_dir}/`.
+const SESSION_FILENAME: &str = "pane-session.json";
+
+/// Upper bound for one pane's availability probe (MTP reconnect + `exists`).
+/// Startup waits on this, so it's kept short: a slow volume is skipped, not awaited.
+const PANE_PROBE_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// One tab of a pane, as persisted.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionTab {
+    pub path: String,
+    pub pinned: bool,
+    pub active: bool,
+}
+
+/// One pane, as persisted.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionPane {
+    /// `None` means the default local volume.
+    pub volume_id: Option<String>,
+    pub path: String,
+    pub sort_field: String,
+    pub sort_order: String,
+    pub view_mode: String,
+    #[serde(default)]
+    pub show_hidden: bool,
+    /// Name of the entry under the cursor, if any. The frontend re-finds it by name.
+    #[serde(default)]
+    pub scroll_anchor: Option<String>,
+    #[serde(default)]
+    pub tabs: Vec<SessionTab>,
+}
+
+/// The whole session. `left` / `right` are `None` when there's nothing to save
+/// (pane never reported) or, on restore, when the pane's location is unavailable.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct Session {
+    pub focused_pane: String,
+    pub left: Option<SessionPane>,
+    pub right: Option<SessionPane>,
+}
+
+/// On-disk shape. `_schemaVersion` lets future versions detect incompatible files.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SessionFile {
+    #[serde(rename = "_schemaVersion")]
+    schema_version: u32,
+    session: Session,
+    #[serde(default)]
+    left_history: NavigationHistory,
+    #[serde(default)]
+    right_history: NavigationHistory,
+}
+
+impl SessionFile {
+    fn new(session: Session, left_history: NavigationHistory, right_history: NavigationHistory) -> Self {
+        Self {
+            schema_version: CURRENT_SCHEMA_VERSION,
+            session,
+            left_history,
+            right_history,
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Snapshot (pure so it can be unit-tested without an AppHandle).
+// ---------------------------------------------------------------------------
+
+/// Maps a reported pane state onto its persisted form. `None` for a pane the
+/// frontend hasn't reported yet (empty path).
+fn session_pane_from(state: &PaneState) -> Option<SessionPane> {
+    if state.path.is_empty() {
+        return None;
+    }
+    // `files` is only the loaded window, so the cursor maps into it via `loaded_start`.
+    let scroll_anchor = state
+        .cursor_index
+        .checked_sub(state.loaded_start)
+        .and_then(|i| state.files.get(i))
+        .map(|f| f.name.clone());
+    Some(SessionPane {
+        volume_id: state.volume_id.clone(),
+        path: state.path.clone(),
+        sort_field: state.sort_field.clone(),
+        sort_order: state.sort_order.clone(),
+        view_mode: state.view_mode.clone(),
+        show_hidden: state.show_hidden,
+        scroll_anchor,
+        tabs: state
+            .tabs
+            .iter()
+            .map(|t| SessionTab {
+                path: t.path.clone(),
+                pinned: t.pinned,
+                active: t.active,
+            })
+            .collect(),
+    })
+}
+
+fn session_from_store(store: &PaneStateStore) -> Session {
+    Session {
+        focused_pane: store.get_focused_pane(),
+        left: session_pane_from(&store.get_left()),
+        right: session_pane_from(&store.get_right()),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// File I/O (mirrors `go_to_path/history.rs`).
+// ---------------------------------------------------------------------------
+
+/// Renames the file to a `.broken` sibling so we keep one bad snapshot for
+/// debugging; drops it outright if the rename fails.
+fn quarantine_broken(path: &Path) {
+    let broken = path.with_extension("json.broken");
+    let _ = fs::remove_file(&broken);
+    if let Err(e) = fs::rename(path, &broken) {
+        log::warn!(target: "session", "Couldn't quarantine pane session at {path:?} (will delete instead): {e}");
+        let _ = fs::remove_file(path);
+    }
+}
+
+/// Reads the saved session. Missing, corrupt, or schema-mismatched files all
+/// yield `None`; the bad ones are quarantined so the next launch starts clean.
+fn read_session_from_path(path: &Path) -> Option<SessionFile> {
+    let _ = fs::remove_file(path.with_extension("json.tmp"));
+    let contents = fs::read_to_string(path).ok()?;
+    match serde_json::from_str::<SessionFile>(&contents) {
+        Ok(file) if file.schema_version == CURRENT_SCHEMA_VERSION => Some(file),
+        Ok(file) => {
+            log::warn!(
+                target: "session",
+                "Pane session schema mismatch (file: {}, expected: {CURRENT_SCHEMA_VERSION}); ignoring it",
+                file.schema_version
+            );
+            quarantine_broken(path);
+            None
+        }
+        Err(e) => {
+            log::warn!(target: "session", "Couldn't parse pane session at {path:?}, ignoring it: {e}");
+            quarantine_broken(path);
+            None
+        }
+    }
+}
+
+fn write_session_to_path(path: &Path, file: &SessionFile) -> std::io::Result<()> {
+    let json = serde_json::to_string_pretty(file).map_err(std::io::Error::other)?;
+    crate::config::durable_write_json(path, &path.with_extension("json.tmp"), &json)
+}
+
+fn get_session_path<R: tauri::Runtime>(app: &tauri::AppHandle<R>) -> Result<PathBuf, String> {
+    crate::config::resolved_app_data_dir(app).map(|dir| dir.join(SESSION_FILENAME))
+}
+
+// ---------------------------------------------------------------------------
+// AppHandle-bound public API.
+// ---------------------------------------------------------------------------
+
+/// Snapshots both panes from the `PaneStateStore`, plus the tail of each pane's
+/// navigation history, and writes them to disk. Returns the saved session.
+pub fn save_session<R: tauri::Runtime>(app: &tauri::AppHandle<R>) -> Result<Session, String> {
+    use tauri::Manager;
+
+    let store = app
+        .try_state::<PaneStateStore>()
+        .ok_or_else(|| "Pane state store isn't initialized".to_string())?;
+    let session = session_from_store(&store);
+    let path = get_session_path(app)?;
+    let file = SessionFile::new(
+        session.clone(),
+        history::get(HistoryPane::Left).tail(PERSISTED_HISTORY_PER_PANE),
+        history::get(HistoryPane::Right).tail(PERSISTED_HISTORY_PER_PANE),
+    );
+    write_session_to_path(&path, &file).map_err(|e| format!("Couldn't write pane session: {e}"))?;
+    log::debug!(target: "session", "Saved pane session to {path:?}");
+    Ok(session)
+}
+
+/// Loads the saved session and drops every pane whose location is unavailable.
+/// Also reinstates each pane's saved navigation history (entries on unavailable
+/// volumes included: going back to one is a normal navigation error).
+/// `None` when there's no usable session file. Never errors: a broken session
+/// must not block startup.
+pub async fn restore_session(app: &tauri::AppHandle) -> Option<Session> {
+    let path = get_session_path(app).ok()?;
+    let file = tokio::task::spawn_blocking(move || read_session_from_path(&path))
+        .await
+        .ok()??;
+    history::set(HistoryPane::Left, file.left_history);
+    history::set(HistoryPane::Right, file.right_history);
+    let session = file.session;
+    let (left, right) = tokio::join!(restore_pane(app, session.left), restore_pane(app, session.right));
+    Some(Session {
+        focused_pane: session.focused_pane,
+        left,
+        right,
+    })
+}
+
+async fn restore_pane(app: &tauri::AppHandle, pane: Option<SessionPane>) -> Option<SessionPane> {
+    let pane = pane?;
+    match tokio::time::timeout(PANE_PROBE_TIMEOUT, is_pane_available(app, &pane)).await {
+        Ok(true) => Some(pane),
+        Ok(false) => {
+            log::info!(target: "session", "Skipping saved pane at {} (no longer available)", pane.path);
+            None
+        }
+        Err(_) => {
+            log::info!(target: "session", "Skipping saved pane at {} (availability check timed out)", pane.path);
+            None
+        }
+    }
+}
+
+/// Whether the pane's volume is registered (reconnecting its MTP device if
+/// needed) and its path still exists there.
+async fn is_pane_available(app: &tauri::AppHandle, pane: &SessionPane) -> bool {
+    let volume_id = pane.volume_id.as_deref().unwrap_or("root");
+    if get_volume_manager().get(volume_id).is_none() {
+        reconnect_volume(app, volume_id).await;
+    }
+    let Some(volume) = get_volume_manager()
+        .resolve(volume_id, Path::new(&pane.path))
+        .await
+        .volume
+    else {
+        return false;
+    };
+    volume.exists(Path::new(&pane.path)).await
+}
+
+/// Brings back a volume that can be re-registered without user input. Today
+/// that's MTP storages (connecting the device registers all its storages);
+/// network shares need credentials and a mount, so they're left for the user.
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+async fn reconnect_volume(app: &tauri::AppHandle, volume_id: &str) {
+    let Some(device_id) = crate::mtp::identity::device_id_of_volume(volume_id) else {
+        return;
+    };
+    if !device_id.starts_with(crate::mtp::identity::MTP_DEVICE_ID_PREFIX) {
+        return;
+    }
+    if let Err(e) = crate::mtp::connection_manager().connect(device_id, Some(app)).await {
+        log::info!(target: "session", "Couldn't reconnect MTP device {device_id} for the saved pane: {e}");
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux")))]
+async fn reconnect_volume(_app: &tauri::AppHandle, _volume_id: &str) {}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mcp::pane_state::{PaneFileEntry, TabInfo};
+
+    fn file(name: &str) -> PaneFileEntry {
+        PaneFileEntry {
+            name: name.to_string(),
+            path: format!("/data/{name}"),
+            is_directory: false,
+            size: None,
+            recursive_size: None,
+            modified: None,
+            recursive_size_pending: None,
+            tags: vec![],
+        }
+    }
+
+    fn pane_state() -> PaneState {
+        PaneState {
+            path: "/data".to_string(),
+            volume_id: Some("root".to_string()),
+            files: vec![file("a.txt"), file("b.txt"), file("c.txt")],
+            cursor_index: 101,
+            loaded_start: 100,
+            view_mode: "full".to_string(),
+            sort_field: "modified".to_string(),
+            sort_order: "desc".to_string(),
+            show_hidden: true,
+            tabs: vec![TabInfo {
+                id: "t1".to_string(),
+                path: "/data".to_string(),
+                pinned: true,
+                active: true,
+            }],
+            ..PaneState::default()
+        }
+    }
+
+    #[test]
+    fn snapshot_keeps_location_view_and_the_name_under_the_cursor() {
+        let pane = session_pane_from(&pane_state()).expect("reported pane");
+
+        assert_eq!(pane.volume_id.as_deref(), Some("root"));
+        assert_eq!(pane.path, "/data");
+        assert_eq!(
+            (pane.sort_field.as_str(), pane.sort_order.as_str()),
+            ("modified", "desc")
+        );
+        assert_eq!(pane.view_mode, "full");
+        assert!(pane.show_hidden);
+        // Cursor 101 in a window starting at 100 is the second loaded file.
+        assert_eq!(pane.scroll_anchor.as_deref(), Some("b.txt"));
+        assert_eq!(
+            pane.tabs,
+            vec![SessionTab {
+                path: "/data".to_string(),
+                pinned: true,
+                active: true
+            }]
+        );
+    }
+
+    #[test]
+    fn snapshot_skips_unreported_panes_and_cursors_outside_the_loaded_window() {
+        assert_eq!(session_pane_from(&PaneState::default()), None);
+
+        let mut state = pane_state();
+        state.cursor_index = 5;
+        assert_eq!(session_pane_from(&state).expect("reported pane").scroll_anchor, None);
+    }
+
+    #[test]
+    fn save_then_load_round_trip() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join(SESSION_FILENAME);
+        let session = Session {
+            focused_pane: "right".to_string(),
+            left: session_pane_from(&pane_state()),
+            right: None,
+        };
+        let mut left_history = NavigationHistory::default();
+        left_history.push(history::NavigationEntry {
+            volume_id: "root".to_string(),
+            path: "/data".to_string(),
+        });
+        let file = SessionFile::new(session, left_history, NavigationHistory::default());
+
+        write_session_to_path(&path, &file).expect("write");
+
+        assert_eq!(read_session_from_path(&path), Some(file));
+    }
+
+    #[test]
+    fn corrupt_or_stale_files_are_ignored_and_quarantined() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join(SESSION_FILENAME);
+        let broken = path.with_extension("json.broken");
+
+        fs::write(&path, "{not json").expect("write garbage");
+        assert_eq!(read_session_from_path(&path), None);
+        assert!(broken.exists() && !path.exists());
+
+        let v2 = r#"{"_schemaVersion": 2, "session": {"focusedPane": "left", "left": null, "right": null}}"#;
+        fs::write(&path, v2).expect("write");
+        assert_eq!(read_session_from_path(&path), None);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn missing_file_yields_no_session() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        assert_eq!(read_session_from_path(&dir.path().join(SESSION_FILENAME)), None);
+    }
+}
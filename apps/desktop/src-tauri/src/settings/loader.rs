@@ -52,6 +52,18 @@ pub struct Settings {
     #[serde(alias = "indexing.staleNotify", default)]
     #[allow(dead_code, reason = "FE-gating setting; parsed for completeness/crash correlation")]
     pub indexing_stale_notify: Option<bool>,
+    /// Folders the drive index skips (`indexing.excludePatterns`). `None` (never saved) means
+    /// the defaults, which an empty list doesn't. Seeded at startup; live changes flow
+    /// through `set_index_exclusions`.
+    #[serde(alias = "indexing.excludePatterns", default)]
+    pub indexing_exclude_patterns: Option<Vec<String>>,
+    /// Fresh-scan rate cap in entries per second (`indexing.maxEntriesPerSec`), 0 for none.
+    /// Seeded at startup; live changes flow through `set_index_throttle`.
+    #[serde(alias = "indexing.maxEntriesPerSec", default)]
+    pub indexing_max_entries_per_sec: Option<u32>,
+    /// Pause fresh scans on battery (`indexing.onlyOnAcPower`). Same flow as the rate cap.
+    #[serde(alias = "indexing.onlyOnAcPower", default)]
+    pub indexing_only_on_ac_power: Option<bool>,
     #[serde(alias = "updates.crashReports", default)]
     #[allow(
         dead_code,
@@ -78,6 +90,8 @@ pub struct Settings {
     pub low_disk_space_threshold_percent: Option<u64>,
     #[serde(alias = "network.smbConcurrency", default)]
     pub smb_concurrency: Option<u16>,
+    #[serde(alias = "advanced.listingCacheMemoryMb", default)]
+    pub listing_cache_memory_mb: Option<u64>,
     #[serde(alias = "advanced.maxLogStorageMb", default)]
     #[allow(
         dead_code,
@@ -88,6 +102,8 @@ pub struct Settings {
     pub error_reports_enabled: Option<bool>,
     #[serde(alias = "fileExplorer.git.showVirtualGitPortal", default)]
     pub show_virtual_git_portal: Option<bool>,
+    #[serde(alias = "fileExplorer.git.ignoredFiles", default)]
+    pub git_ignored_mode: Option<crate::file_system::listing::GitIgnoredMode>,
     #[serde(alias = "network.enabled", default)]
     pub network_enabled: Option<bool>,
     #[serde(alias = "network.firstTriggerDone", default)]
@@ -150,6 +166,14 @@ pub struct Settings {
     /// `media_index_set_semantic_search_enabled`.
     #[serde(alias = "mediaIndex.semanticSearch.enabled", default)]
     pub media_index_semantic_search_enabled: Option<bool>,
+    /// The three raw values `file_system::default_action` parses. Seeded at startup; live
+    /// changes flow through `set_default_action_settings`.
+    #[serde(alias = "behavior.archiveEnterBehavior", default)]
+    pub archive_enter_behavior: Option<String>,
+    #[serde(alias = "behavior.fileEnterAction", default)]
+    pub file_enter_action: Option<String>,
+    #[serde(alias = "behavior.openWithApps", default)]
+    pub open_with_apps: Option<String>,
 }
 
 fn default_show_hidden() -> bool {
@@ -174,6 +198,9 @@ impl Default for Settings {
             indexing_enabled: None,
             indexing_ask_for_each_drive: None,
             indexing_stale_notify: None,
+            indexing_exclude_patterns: None,
+            indexing_max_entries_per_sec: None,
+            indexing_only_on_ac_power: None,
             crash_reports_enabled: None,
             ai_provider: None,
             verbose_logging: None,
@@ -184,9 +211,11 @@ impl Default for Settings {
             low_disk_space_notifications: None,
             low_disk_space_threshold_percent: None,
             smb_concurrency: None,
+            listing_cache_memory_mb: None,
             max_log_storage_mb: None,
             error_reports_enabled: None,
             show_virtual_git_portal: None,
+            git_ignored_mode: None,
             network_enabled: None,
             network_first_trigger_done: None,
             analytics_enabled: None,
@@ -199,6 +228,9 @@ impl Default for Settings {
             media_index_excluded_folders: Vec::new(),
             media_index_scope: None,
             media_index_semantic_search_enabled: None,
+            archive_enter_behavior: None,
+            file_enter_action: None,
+            open_with_apps: None,
         }
     }
 }
@@ -243,6 +275,15 @@ fn parse_settings(contents: &str) -> Result<Settings, serde_json::Error> {
     let indexing_enabled = json.get("indexing.enabled").and_then(|v| v.as_bool());
     let indexing_ask_for_each_drive = json.get("indexing.askForEachDrive").and_then(|v| v.as_bool());
     let indexing_stale_notify = json.get("indexing.staleNotify").and_then(|v| v.as_bool());
+    let indexing_exclude_patterns = json
+        .get("indexing.excludePatterns")
+        .is_some()
+        .then(|| parse_string_array(&json, "indexing.excludePatterns"));
+    let indexing_max_entries_per_sec = json
+        .get("indexing.maxEntriesPerSec")
+        .and_then(|v| v.as_u64())
+        .and_then(|v| u32::try_from(v).ok());
+    let indexing_only_on_ac_power = json.get("indexing.onlyOnAcPower").and_then(|v| v.as_bool());
 
     let crash_reports_enabled = json.get("updates.crashReports").and_then(|v| v.as_bool());
     let ai_provider = json.get("ai.provider").and_then(|v| v.as_str()).map(String::from);
@@ -262,11 +303,15 @@ fn parse_settings(contents: &str) -> Result<Settings, serde_json::Error> {
         .get("network.smbConcurrency")
         .and_then(|v| v.as_u64())
         .and_then(|v| u16::try_from(v).ok());
+    let listing_cache_memory_mb = json.get("advanced.listingCacheMemoryMb").and_then(|v| v.as_u64());
     let max_log_storage_mb = json.get("advanced.maxLogStorageMb").and_then(|v| v.as_u64());
     let error_reports_enabled = json.get("updates.errorReports").and_then(|v| v.as_bool());
     let show_virtual_git_portal = json
         .get("fileExplorer.git.showVirtualGitPortal")
         .and_then(|v| v.as_bool());
+    let git_ignored_mode = json
+        .get("fileExplorer.git.ignoredFiles")
+        .and_then(|v| serde_json::from_value(v.clone()).ok());
     let network_enabled = json.get("network.enabled").and_then(|v| v.as_bool());
     let network_first_trigger_done = json.get("network.firstTriggerDone").and_then(|v| v.as_bool());
     let analytics_enabled = json.get("analytics.enabled").and_then(|v| v.as_bool());
@@ -285,6 +330,10 @@ fn parse_settings(contents: &str) -> Result<Settings, serde_json::Error> {
         .and_then(|v| v.as_str())
         .map(|s| s.to_string());
     let media_index_semantic_search_enabled = json.get("mediaIndex.semanticSearch.enabled").and_then(|v| v.as_bool());
+    let string_at = |key: &str| json.get(key).and_then(|v| v.as_str()).map(String::from);
+    let archive_enter_behavior = string_at("behavior.archiveEnterBehavior");
+    let file_enter_action = string_at("behavior.fileEnterAction");
+    let open_with_apps = string_at("behavior.openWithApps");
 
     Ok(Settings {
         show_hidden_files,
@@ -294,6 +343,9 @@ fn parse_settings(contents: &str) -> Result<Settings, serde_json::Error> {
         indexing_enabled,
         indexing_ask_for_each_drive,
         indexing_stale_notify,
+        indexing_exclude_patterns,
+        indexing_max_entries_per_sec,
+        indexing_only_on_ac_power,
         crash_reports_enabled,
         ai_provider,
         verbose_logging,
@@ -304,9 +356,11 @@ fn parse_settings(contents: &str) -> Result<Settings, serde_json::Error> {
         low_disk_space_notifications,
         low_disk_space_threshold_percent,
         smb_concurrency,
+        listing_cache_memory_mb,
         max_log_storage_mb,
         error_reports_enabled,
         show_virtual_git_portal,
+        git_ignored_mode,
         network_enabled,
         network_first_trigger_done,
         analytics_enabled,
@@ -319,6 +373,9 @@ fn parse_settings(contents: &str) -> Result<Settings, serde_json::Error> {
         media_index_excluded_folders,
         media_index_scope,
         media_index_semantic_search_enabled,
+        archive_enter_behavior,
+        file_enter_action,
+        open_with_apps,
     })
 }
 
@@ -1,6 +1,8 @@
-//! Settings module: loads settings from tauri-plugin-store's JSON file at startup.
+//! Settings module: loads settings from tauri-plugin-store's JSON file at startup, and moves
+//! settings export files in and out.
 
 pub mod loader;
+pub mod transfer;
 
 pub use loader::{
     FullDiskAccessChoice, RestrictedWindowSettings, early_load_global_go_to_latest_shortcut,
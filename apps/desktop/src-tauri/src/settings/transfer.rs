@@ -0,0 +1,80 @@
+//! Settings export files: the portable JSON a user carries to another machine.
+//!
+//! The frontend builds and applies the contents: the registry validates each field, and only
+//! the frontend store writes `settings.json` (see `CLAUDE.md` § One-way read only). This
+//! module only moves the file's bytes, refusing anything too large or not a JSON object, so a
+//! wrong pick in the file dialog fails here with a clear message instead of deep in the import.
+
+use std::fs;
+use std::path::Path;
+
+/// Largest export file accepted. A full export is a few KB; anything near this is not one.
+pub const MAX_EXPORT_BYTES: u64 = 1024 * 1024;
+
+/// Writes `contents` to `path` through a sibling temp file and a rename (see
+/// `crate::config::durable_write_json`), so an interrupted export never leaves a half-written
+/// file where a good one used to be.
+pub fn write_export(path: &Path, contents: &str) -> Result<(), String> {
+    ensure_json_object(contents)?;
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| format!("{} isn't a file path", path.display()))?;
+    let mut tmp_name = file_name.to_os_string();
+    tmp_name.push(".tmp");
+    let tmp = path.with_file_name(tmp_name);
+    crate::config::durable_write_json(path, &tmp, contents).map_err(|e| {
+        let _ = fs::remove_file(&tmp);
+        format!("Couldn't save {}: {e}", path.display())
+    })
+}
+
+/// Reads an export file back, checking its size and that it's a JSON object.
+pub fn read_export(path: &Path) -> Result<String, String> {
+    let metadata = fs::metadata(path).map_err(|e| format!("Couldn't read {}: {e}", path.display()))?;
+    if metadata.len() > MAX_EXPORT_BYTES {
+        return Err(format!("{} is too large to be a settings export", path.display()));
+    }
+    let contents = fs::read_to_string(path).map_err(|e| format!("Couldn't read {}: {e}", path.display()))?;
+    ensure_json_object(&contents)?;
+    Ok(contents)
+}
+
+fn ensure_json_object(contents: &str) -> Result<(), String> {
+    match serde_json::from_str::<serde_json::Value>(contents) {
+        Ok(serde_json::Value::Object(_)) => Ok(()),
+        _ => Err("This file isn't a settings export".to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_an_export() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("cmdr-settings.json");
+        let contents = r#"{"format":"cmdr-settings","settings":{"showHiddenFiles":false}}"#;
+        write_export(&path, contents).expect("write");
+        assert_eq!(read_export(&path).expect("read"), contents);
+        // Overwrites in place, leaving no temp files behind.
+        write_export(&path, "{}").expect("overwrite");
+        assert_eq!(read_export(&path).expect("read"), "{}");
+        assert_eq!(fs::read_dir(dir.path()).expect("read_dir").count(), 1);
+    }
+
+    #[test]
+    fn refuses_non_objects_and_oversized_files() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("export.json");
+        assert!(write_export(&path, "[1, 2]").is_err());
+        assert!(!path.exists());
+
+        fs::write(&path, "not json").expect("write");
+        assert!(read_export(&path).is_err());
+
+        let oversized = format!(r#"{{"pad":"{}"}}"#, "x".repeat(MAX_EXPORT_BYTES as usize));
+        fs::write(&path, oversized).expect("write");
+        assert!(read_export(&path).unwrap_err().contains("too large"));
+    }
+}
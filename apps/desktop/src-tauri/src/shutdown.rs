@@ -0,0 +1,51 @@
+//! Coordinated shutdown of background work on quit.
+//!
+//! `exit(0)` ends the process wherever each task happens to be: a copy or MTP
+//! transfer mid-file leaves a truncated destination, and an index writer
+//! mid-batch loses rows it hadn't committed. `shutdown_all_operations` asks every
+//! registered op and index to stop at a safe point, then waits a bounded time
+//! for them to get there, so a hung device can't hold quit hostage.
+
+use std::time::{Duration, Instant};
+
+/// Upper bound on how long quit waits for write ops to settle once they're
+/// cancelled. Index drains are bounded separately (5 s each, inside
+/// `IndexManager::shutdown`) and count against this budget.
+const SHUTDOWN_GRACE: Duration = Duration::from_secs(5);
+
+/// Cancels every write op (copy, move, delete, trash, MTP transfers) and stops
+/// every volume's indexing, then blocks until the ops settle or
+/// [`SHUTDOWN_GRACE`] passes. Cancelled ops take the keep-partials path, so the
+/// one file in flight is deleted rather than left truncated. Stopping an index
+/// cancels its scan, drains its event loop, and joins the writer, which commits
+/// the pending batch.
+///
+/// Idempotent and cheap when nothing runs, so both the main-window close
+/// handler and `RunEvent::Exit` (the quit menu, the MCP `quit` tool) call it.
+pub(crate) fn shutdown_all_operations() {
+    let started = Instant::now();
+
+    // Cancel first so the ops wind down while the index drains below.
+    let ops = crate::file_system::cancel_all_operations();
+
+    let volume_ids = crate::indexing::all_registered_volume_ids();
+    for volume_id in &volume_ids {
+        if let Err(e) = crate::indexing::stop_indexing(volume_id) {
+            log::warn!(target: "shutdown", "Couldn't stop indexing for '{volume_id}': {e}");
+        }
+    }
+
+    let left = SHUTDOWN_GRACE.saturating_sub(started.elapsed());
+    if !crate::file_system::wait_for_operations_to_settle(left) {
+        log::warn!(target: "shutdown", "Quitting with write ops still running after {:?}", started.elapsed());
+        return;
+    }
+    if ops > 0 || !volume_ids.is_empty() {
+        log::info!(
+            target: "shutdown",
+            "Stopped {ops} write op(s) and {} index(es) in {:?}",
+            volume_ids.len(),
+            started.elapsed()
+        );
+    }
+}
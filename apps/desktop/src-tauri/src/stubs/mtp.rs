@@ -38,6 +38,19 @@ pub struct ConnectedDeviceInfo {
     pub storages: Vec<MtpStorageInfo>,
 }
 
+/// Operations a connected MTP device supports (stub version).
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct MtpDeviceCapabilities {
+    pub can_upload: bool,
+    pub can_delete: bool,
+    pub can_rename: bool,
+    pub can_move: bool,
+    pub can_create_folder: bool,
+    pub can_read_thumbnails: bool,
+    pub can_read_partial: bool,
+}
+
 /// Error types for MTP connection operations (stub version).
 #[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
 #[serde(rename_all = "camelCase", rename_all_fields = "camelCase", tag = "type")]
@@ -93,6 +106,13 @@ pub fn get_mtp_device_info(_device_id: String) -> Option<ConnectedDeviceInfo> {
     None
 }
 
+/// Gets the operations a connected MTP device supports (stub - returns None).
+#[tauri::command]
+#[specta::specta]
+pub fn get_mtp_capabilities(_device_id: String) -> Option<MtpDeviceCapabilities> {
+    None
+}
+
 /// Gets the ptpcamerad workaround command (stub - returns empty string).
 #[tauri::command]
 #[specta::specta]
@@ -141,6 +161,19 @@ pub async fn list_mtp_directory(
     })
 }
 
+/// Re-reads an MTP directory, bypassing the cache (stub - returns error).
+#[tauri::command]
+#[specta::specta]
+pub async fn refresh_mtp_directory(
+    _device_id: String,
+    _storage_id: u32,
+    _path: String,
+) -> Result<Vec<FileEntry>, MtpConnectionError> {
+    Err(MtpConnectionError::NotSupported {
+        message: "MTP is not supported on this platform".to_string(),
+    })
+}
+
 // ============================================================================
 // Phase 4: File Operation stubs
 // ============================================================================
@@ -211,6 +244,34 @@ pub async fn move_mtp_object(
     })
 }
 
+/// Fetches an MTP object's thumbnail (stub - returns error).
+#[tauri::command]
+#[specta::specta]
+pub async fn get_mtp_thumbnail(
+    _device_id: String,
+    _storage_id: u32,
+    _object_path: String,
+) -> Result<Option<String>, MtpConnectionError> {
+    Err(MtpConnectionError::NotSupported {
+        message: "MTP is not supported on this platform".to_string(),
+    })
+}
+
+/// Moves an object between two storages of an MTP device (stub - returns error).
+#[tauri::command]
+#[specta::specta]
+pub async fn move_mtp_object_between_storages(
+    _device_id: String,
+    _from_storage_id: u32,
+    _object_path: String,
+    _to_storage_id: u32,
+    _new_parent_path: String,
+) -> Result<MtpObjectInfo, MtpConnectionError> {
+    Err(MtpConnectionError::NotSupported {
+        message: "MTP is not supported on this platform".to_string(),
+    })
+}
+
 // ============================================================================
 // Phase 5: Copy/Export Operation stubs
 // ============================================================================
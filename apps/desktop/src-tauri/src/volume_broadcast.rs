@@ -119,6 +119,28 @@ pub struct VolumeUnmounted {
     pub volume_path: String,
 }
 
+/// Typed `volume-ejected` Tauri event. Emitted once `eject_volume` finished the
+/// unmount or disconnect it asked for. `volume-unmounted` still follows from the
+/// OS watcher for disk volumes; this one says the eject was ours and succeeded.
+#[derive(Clone, Serialize, Deserialize, specta::Type, Event)]
+#[serde(rename_all = "camelCase")]
+pub struct VolumeEjected {
+    pub volume_id: String,
+}
+
+/// Emits `volume-ejected` for `volume_id`. No-op before `init` (unit tests).
+pub fn emit_volume_ejected(volume_id: &str) {
+    let Some(app) = APP_HANDLE.get() else {
+        return;
+    };
+    let payload = VolumeEjected {
+        volume_id: volume_id.to_string(),
+    };
+    if let Err(e) = payload.emit(app) {
+        error!("Failed to emit volume-ejected event: {}", e);
+    }
+}
+
 /// Typed `volume-context-action` Tauri event. Emitted to the `main` window when
 /// the user picks an action ("eject", "rename-favorite", or "remove-favorite") from
 /// the native breadcrumb / volume-selector row context menu. Window-scoped, so it's
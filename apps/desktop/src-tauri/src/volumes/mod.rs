@@ -191,6 +191,11 @@ fn get_favorites() -> Vec<LocationInfo> {
 
     crate::favorites::store::list()
         .into_iter()
+        // Follow a favorite on a remounted volume to its new mount point.
+        .map(|mut favorite| {
+            favorite.path = crate::favorites::store::resolve_path(&favorite);
+            favorite
+        })
         .filter(|favorite| {
             // While FDA is pending, MUST skip stat on TCC-protected paths: even `Path::exists()`
             // trips TCC for the protected-folder service once `permissions::check_full_disk_access`
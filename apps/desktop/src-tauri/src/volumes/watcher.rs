@@ -295,7 +295,8 @@ fn register_volume_with_manager(volume_path: &str) {
     }
 }
 
-/// Unregister a volume from the `VolumeManager`.
+/// Unregister a volume from the `VolumeManager`, first releasing the write ops and
+/// listings still pointing at it (`file_system::release_unmounted_volume`).
 ///
 /// If `registered_id` is `Some`, unregister that exact entry. Use this when the
 /// caller has already looked up the volume via `find_by_root` (the unmount path
@@ -308,6 +309,7 @@ fn unregister_volume_from_manager(volume_path: &str, registered_id: Option<&str>
     let volume_id = registered_id
         .map(|s| s.to_string())
         .unwrap_or_else(|| super::volume_id_for_mount(volume_path));
+    crate::file_system::release_unmounted_volume(&volume_id, volume_path);
     get_volume_manager().unregister(&volume_id);
     debug!("Unregistered volume: {} ({})", volume_id, volume_path);
 }
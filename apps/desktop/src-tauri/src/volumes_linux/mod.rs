@@ -235,6 +235,11 @@ pub fn list_mounted_volumes() -> Vec<LocationInfo> {
 fn get_favorites(mounts: &[MountEntry]) -> Vec<LocationInfo> {
     crate::favorites::store::list()
         .into_iter()
+        // Follow a favorite on a remounted volume to its new mount point.
+        .map(|mut favorite| {
+            favorite.path = crate::favorites::store::resolve_path(&favorite);
+            favorite
+        })
         .filter(|favorite| Path::new(&favorite.path).exists())
         .map(|favorite| {
             let fs_type = linux_mounts::fs_type_for_path_from_entries(Path::new(&favorite.path), mounts);
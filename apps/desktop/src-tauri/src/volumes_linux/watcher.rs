@@ -338,7 +338,8 @@ fn register_volume_with_manager(volume_path: &str) {
     debug!("Registered mounted volume: {} -> {}", volume_id, volume_path);
 }
 
-/// Unregister a volume from the global VolumeManager.
+/// Unregister a volume from the global VolumeManager, first cancelling the write
+/// ops and ending the listings that still point at it.
 ///
 /// Looks up the volume by `root()` first (works even after the mount is gone,
 /// when `volume_id_for_mount`'s SMB branch can no longer recover the right ID).
@@ -351,6 +352,7 @@ fn unregister_volume_from_manager(volume_path: &str) {
         .find_by_root(Path::new(volume_path))
         .map(|(id, _)| id)
         .unwrap_or_else(|| super::volume_id_for_mount(volume_path));
+    crate::file_system::release_unmounted_volume(&volume_id, volume_path);
     manager.unregister(&volume_id);
     debug!("Unregistered volume: {} ({})", volume_id, volume_path);
 }
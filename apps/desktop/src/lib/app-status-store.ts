@@ -79,7 +79,7 @@ function parseViewMode(raw: unknown): ViewMode {
 }
 
 function parseSortColumn(raw: unknown): SortColumn {
-  const validColumns: SortColumn[] = ['name', 'extension', 'size', 'modified', 'created']
+  const validColumns: SortColumn[] = ['name', 'extension', 'size', 'modified', 'created', 'captured']
   if (typeof raw === 'string' && validColumns.includes(raw as SortColumn)) {
     return raw as SortColumn
   }
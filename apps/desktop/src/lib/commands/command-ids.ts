@@ -84,6 +84,7 @@ export const COMMAND_IDS = [
   'sort.byModified',
   'sort.bySize',
   'sort.byCreated',
+  'sort.byCaptured',
   'sort.ascending',
   'sort.descending',
   'sort.toggleOrder',
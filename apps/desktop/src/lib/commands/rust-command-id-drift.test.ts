@@ -65,6 +65,23 @@ function crossWindowEmittedCommandIds(): string[] {
   return [...ids]
 }
 
+/** Command ids in the backend palette registry (`palette/mod.rs` § `ACTIONS`). */
+function paletteRegistryCommandIds(): string[] {
+  const source = readFileSync(path.join(desktopRoot, 'src-tauri/src/palette/mod.rs'), 'utf8')
+  const tableStart = source.indexOf('const ACTIONS: &[ActionSpec] = &[')
+  expect(tableStart, 'ACTIONS not found in palette/mod.rs').toBeGreaterThan(-1)
+  const tableEnd = source.indexOf('\n];', tableStart)
+  const body = source.slice(tableStart, tableEnd)
+
+  const ids = new Set<string>()
+  const re = /id: "([^"]+)"/g
+  let match: RegExpExecArray | null
+  while ((match = re.exec(body)) !== null) {
+    ids.add(match[1])
+  }
+  return [...ids]
+}
+
 describe('Rust↔FE command-id drift', () => {
   it('every menu-emitted command id is a registry CommandId', () => {
     const menuIds = menuEmittedCommandIds()
@@ -83,6 +100,14 @@ describe('Rust↔FE command-id drift', () => {
     const unknown = emittedIds.filter((id) => !isCommandId(id))
     expect(unknown, 'cross-window-emitted ids not present in COMMAND_IDS').toEqual([])
   })
+
+  it('every backend palette action id is a registry CommandId', () => {
+    const paletteIds = paletteRegistryCommandIds()
+    expect(paletteIds.length).toBeGreaterThan(20)
+
+    const unknown = paletteIds.filter((id) => !isCommandId(id))
+    expect(unknown, 'palette registry ids not present in COMMAND_IDS').toEqual([])
+  })
 })
 
 /** Command ids in `command_id_to_menu_id` (the accelerator-sync reverse map). */
@@ -183,6 +183,13 @@ export const mainWindowCommands: CommandSource[] = [
     showInPalette: true,
     shortcuts: [],
   },
+  {
+    id: 'sort.byCaptured',
+    nameKey: 'commands.sortByCaptured.label',
+    scope: 'Main window',
+    showInPalette: true,
+    shortcuts: [],
+  },
   {
     id: 'sort.ascending',
     nameKey: 'commands.sortAscending.label',
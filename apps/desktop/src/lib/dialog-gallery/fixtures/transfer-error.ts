@@ -155,6 +155,10 @@ const perVariant: Record<WriteOperationError['type'], TransferErrorFixture> = {
       totalCount: 3,
     },
   },
+  verification_failed: {
+    operationType: 'copy',
+    error: { type: 'verification_failed', path: '/Volumes/USB-STICK/backups/photos-2026.tar' },
+  },
   io_error: {
     operationType: 'copy',
     error: {
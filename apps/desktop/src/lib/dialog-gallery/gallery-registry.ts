@@ -172,6 +172,7 @@ export const DIALOG_GALLERY_ENTRIES: DialogGalleryEntry[] = [
       { id: 'delete_pending', label: 'Delete pending' },
       { id: 'files_too_large_for_filesystem', label: 'Too large for the filesystem (three files)' },
       { id: 'files_too_large_for_filesystem-single', label: 'Too large for the filesystem (one file)' },
+      { id: 'verification_failed', label: 'Verification failed' },
       { id: 'io_error', label: 'I/O error' },
       { id: 'archive_needs_password', label: 'Archive needs a password' },
     ],
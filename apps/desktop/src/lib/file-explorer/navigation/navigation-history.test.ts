@@ -11,6 +11,7 @@ import {
   canGoForward,
   setCurrentIndex,
   getEntryAt,
+  historyFromPersisted,
   MAX_HISTORY_PER_TAB,
   type NavigationHistory,
   type HistoryEntry,
@@ -455,4 +456,33 @@ describe('NavigationHistory', () => {
       expect(result.stack[result.stack.length - 1].path).toBe('/overflow')
     })
   })
+
+  describe('historyFromPersisted', () => {
+    it('keeps the persisted stack and cursor when the tab opens at the current entry', () => {
+      const entries = [
+        { volumeId: ROOT_VOLUME, path: '/a' },
+        { volumeId: EXT_VOLUME, path: '/b' },
+        { volumeId: ROOT_VOLUME, path: '/c' },
+      ]
+      const history = historyFromPersisted({ entries, currentIndex: 1 }, { volumeId: EXT_VOLUME, path: '/b' })
+      expect(history).toEqual({ stack: entries, currentIndex: 1 })
+    })
+
+    it('returns null when the tab opens somewhere else', () => {
+      const entries = [{ volumeId: ROOT_VOLUME, path: '/a' }]
+      expect(historyFromPersisted({ entries, currentIndex: 0 }, { volumeId: ROOT_VOLUME, path: '/z' })).toBeNull()
+      expect(historyFromPersisted({ entries: [], currentIndex: 0 }, { volumeId: ROOT_VOLUME, path: '/a' })).toBeNull()
+    })
+
+    it('drops search-results entries and moves the cursor with them', () => {
+      const entries = [
+        { volumeId: ROOT_VOLUME, path: '/a' },
+        { volumeId: 'search-results', path: 'search-results://s1' },
+        { volumeId: ROOT_VOLUME, path: '/c' },
+      ]
+      const history = historyFromPersisted({ entries, currentIndex: 2 }, { volumeId: ROOT_VOLUME, path: '/c' })
+      expect(history?.stack.map((e) => e.path)).toEqual(['/a', '/c'])
+      expect(history?.currentIndex).toBe(1)
+    })
+  })
 })
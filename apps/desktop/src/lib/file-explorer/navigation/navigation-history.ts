@@ -213,3 +213,23 @@ export function setCurrentIndex(history: NavigationHistory, index: number): Navi
     currentIndex: clampedIndex,
   }
 }
+
+/**
+ * Rebuilds a history from the backend's per-pane history (persisted in the session
+ * file), so Back works right after a restart. `null` when the persisted current entry
+ * isn't `at`, the location the tab opens at. Search-results entries are dropped:
+ * their snapshots don't outlive the app.
+ */
+export function historyFromPersisted(
+  persisted: { entries: Location[]; currentIndex: number },
+  at: Location,
+): NavigationHistory | null {
+  const current = persisted.entries.at(persisted.currentIndex)
+  if (!current || current.volumeId !== at.volumeId || current.path !== at.path) return null
+  const kept = persisted.entries
+    .map((entry, index) => ({ entry, index }))
+    .filter(({ entry }) => entry.volumeId !== 'search-results')
+  const currentIndex = kept.findIndex(({ index }) => index === persisted.currentIndex)
+  if (currentIndex < 0) return null
+  return { stack: kept.map(({ entry }) => ({ volumeId: entry.volumeId, path: entry.path })), currentIndex }
+}
@@ -1,11 +1,22 @@
 import { loadAppStatus, loadPaneTabs } from '$lib/app-status-store'
 import { hydrateRail } from '$lib/ask-cmdr/ask-cmdr-trigger.svelte'
 import { loadSettings } from '$lib/settings-store'
-import { pathExists, getDefaultVolumeId, resolvePathVolume, getE2eStartPath } from '$lib/tauri-commands'
+import {
+  pathExists,
+  getDefaultVolumeId,
+  resolvePathVolume,
+  getE2eStartPath,
+  restoreSession,
+  getPaneHistory,
+  type PaneNavigationHistory,
+  type SessionPane,
+} from '$lib/tauri-commands'
 import { getAppLogger } from '$lib/logging/logger'
 import { createTabManagerFromPersisted } from './tab-operations'
-import { getAllTabs, type TabManager } from '../tabs/tab-state-manager.svelte'
+import { getActiveTab, getAllTabs, type TabManager } from '../tabs/tab-state-manager.svelte'
+import { historyFromPersisted } from '../navigation/navigation-history'
 import type { PersistedTab, PersistedPaneTabs } from '../tabs/tab-types'
+import type { SortColumn } from '../types'
 
 const log = getAppLogger('fileExplorer')
 
@@ -14,6 +25,37 @@ interface VolumeResolution {
   timedOut: boolean
 }
 
+/** The session stores sort fields in the MCP vocabulary (see `pane-mcp-sync.svelte.ts`). */
+const sessionSortColumns: Record<string, SortColumn> = {
+  name: 'name',
+  ext: 'extension',
+  size: 'size',
+  modified: 'modified',
+  created: 'created',
+  captured: 'captured',
+}
+
+/**
+ * Points the pane's active tab at what the backend session restored: its location
+ * (already probed, and for MTP reconnected), sort, and view mode. Returns the tab and
+ * the name to put the cursor on, or `null` for a `null` pane (unavailable, or no
+ * session), which leaves the tab as app-status had it.
+ */
+function applySessionPane(
+  paneTabs: PersistedPaneTabs,
+  pane: SessionPane | null,
+  defaultVolumeId: string,
+): { tabId: string; cursorName: string | null } | null {
+  if (!pane) return null
+  const tab = paneTabs.tabs.find((t) => t.id === paneTabs.activeTabId) ?? paneTabs.tabs[0]
+  tab.path = pane.path
+  tab.volumeId = pane.volumeId ?? defaultVolumeId
+  tab.sortBy = sessionSortColumns[pane.sortField] ?? tab.sortBy
+  tab.sortOrder = pane.sortOrder === 'asc' ? 'ascending' : 'descending'
+  if (pane.viewMode === 'full' || pane.viewMode === 'brief') tab.viewMode = pane.viewMode
+  return { tabId: tab.id, cursorName: pane.scrollAnchor }
+}
+
 export interface InitializedState {
   leftTabMgr: TabManager
   rightTabMgr: TabManager
@@ -22,17 +64,28 @@ export interface InitializedState {
   leftPaneWidthPercent: number
 }
 
+/**
+ * Gives the pane's active tab the back/forward trail the backend kept across the restart,
+ * when that trail ends where the tab opens. Otherwise the tab keeps its one-entry history.
+ */
+function seedActiveTabHistory(mgr: TabManager, persisted: PaneNavigationHistory | null): void {
+  if (!persisted) return
+  const tab = getActiveTab(mgr)
+  tab.history = historyFromPersisted(persisted, tab) ?? tab.history
+}
+
 /**
  * Loads persisted state and resolves volumes for all tabs.
  * Returns fully initialized tab managers and app state, ready to use.
  */
 export async function loadPersistedState(): Promise<InitializedState> {
-  // Load persisted state (tabs + app status + settings) in parallel
-  const [leftPaneTabs, rightPaneTabs, status, settings] = await Promise.all([
+  // Load persisted state (tabs + app status + settings + the backend pane session) in parallel
+  const [leftPaneTabs, rightPaneTabs, status, settings, session] = await Promise.all([
     loadPaneTabs('left', pathExists),
     loadPaneTabs('right', pathExists),
     loadAppStatus(pathExists),
     loadSettings(),
+    restoreSession(),
   ])
 
   // Restore the Ask Cmdr rail's persisted open/width (reopening loads its active thread).
@@ -106,6 +159,12 @@ export async function loadPersistedState(): Promise<InitializedState> {
     activeTabId: rightPaneTabs.activeTabId,
   }
 
+  // The backend session (saved at quit) wins over app-status for each pane's active tab: it
+  // has the cursor name, and it already probed the location. E2E runs keep their fixtures.
+  const restoredLeft = session && !e2eStartPath ? applySessionPane(resolvedLeftPaneTabs, session.left, defaultId) : null
+  const restoredRight =
+    session && !e2eStartPath ? applySessionPane(resolvedRightPaneTabs, session.right, defaultId) : null
+
   // E2E override: apply fixture paths to the active tab data BEFORE creating tab managers,
   // so the managers are initialized with the correct paths from the start.
   // Must override both path AND volumeId. Persisted state may have a non-root volume
@@ -127,10 +186,24 @@ export async function loadPersistedState(): Promise<InitializedState> {
   const leftTabMgr = createTabManagerFromPersisted(resolvedLeftPaneTabs)
   const rightTabMgr = createTabManagerFromPersisted(resolvedRightPaneTabs)
 
-  // Apply unreachable state to tabs that timed out during volume resolution
+  // Put the cursor back on the saved name once each pane's first listing lands
+  getActiveTab(leftTabMgr).cursorFilename = restoredLeft?.cursorName ?? null
+  getActiveTab(rightTabMgr).cursorFilename = restoredRight?.cursorName ?? null
+
+  // `restoreSession` reinstated each pane's backend history; only a restored pane can match it
+  const [leftHistory, rightHistory] = await Promise.all([
+    restoredLeft ? getPaneHistory('left') : null,
+    restoredRight ? getPaneHistory('right') : null,
+  ])
+  seedActiveTabHistory(leftTabMgr, leftHistory)
+  seedActiveTabHistory(rightTabMgr, rightHistory)
+
+  // Apply unreachable state to tabs that timed out during volume resolution, except the
+  // ones the session just pointed at a location it found reachable
+  const sessionTabIds = new Set([restoredLeft?.tabId, restoredRight?.tabId])
   for (const tab of [...getAllTabs(leftTabMgr), ...getAllTabs(rightTabMgr)]) {
     const originalPath = unreachableByTabId[tab.id]
-    if (originalPath) {
+    if (originalPath && !sessionTabIds.has(tab.id)) {
       tab.unreachable = { originalPath, retrying: false }
     }
   }
@@ -138,7 +211,8 @@ export async function loadPersistedState(): Promise<InitializedState> {
   return {
     leftTabMgr,
     rightTabMgr,
-    focusedPane: status.focusedPane,
+    focusedPane:
+      session?.focusedPane === 'left' || session?.focusedPane === 'right' ? session.focusedPane : status.focusedPane,
     showHiddenFiles: settings.showHiddenFiles,
     leftPaneWidthPercent: status.leftPaneWidthPercent,
   }
@@ -272,11 +272,15 @@ export interface NavigateDeps {
  * subscriber's per-pane effects watch the store mutation `commit` makes), so the
  * trigger is a no-op for it; `last-used-path` is a DELTA (the old path of the old
  * volume on a switch) the subscriber can't derive from a snapshot, so it's
- * forwarded explicitly.
+ * forwarded explicitly. `history-push` / `history-step` mirror each history
+ * push and back/forward walk into the backend's per-pane history, which the
+ * session file persists across restarts.
  */
 export type PersistEvent =
   | { kind: 'pane-state'; pane: 'left' | 'right' }
   | { kind: 'last-used-path'; record: LastUsedPathRecord }
+  | { kind: 'history-push'; pane: 'left' | 'right'; location: Location }
+  | { kind: 'history-step'; pane: 'left' | 'right'; direction: 'back' | 'forward' }
 
 /** Exact refusal strings — contract (L12). Pinned byte-for-byte by the navigate suites. */
 function onNetworkRefusal(volumeLabel: string): NavigateRefusal {
@@ -365,6 +369,10 @@ function commit(deps: NavigateDeps, c: NavigateCommit): void {
   }
 
   deps.persist({ kind: 'pane-state', pane: c.pane })
+  if (c.history !== 'none') {
+    const location = { volumeId: deps.getPaneVolumeId(c.pane), path: c.path }
+    deps.persist({ kind: 'history-push', pane: c.pane, location })
+  }
 }
 
 /**
@@ -647,6 +655,7 @@ function navigateHistory(
 
   const target = getCurrentEntry(newHistory)
   commitHistoryWalk(deps, pane, newHistory, target.path)
+  deps.persist({ kind: 'history-step', pane, direction: action })
   return { status: 'started', settled: SETTLED_NOOP }
 }
 
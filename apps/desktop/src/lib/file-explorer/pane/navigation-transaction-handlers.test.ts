@@ -137,6 +137,10 @@ vi.mock('$lib/tauri-commands', async (importOriginal) => {
     getVolumeSpace: vi.fn().mockResolvedValue({ data: null, timedOut: false }),
     DEFAULT_VOLUME_ID: 'root',
     getE2eStartPath: vi.fn().mockResolvedValue(null),
+    restoreSession: vi.fn().mockResolvedValue(null),
+    getPaneHistory: vi.fn().mockResolvedValue(null),
+    recordHistoryNavigation: vi.fn().mockResolvedValue(undefined),
+    recordHistoryStep: vi.fn().mockResolvedValue(undefined),
     updateFocusedPane: vi.fn().mockResolvedValue(undefined),
     updatePaneTabs: vi.fn().mockResolvedValue(undefined),
     updatePinTabMenu: vi.fn().mockResolvedValue(undefined),
@@ -116,6 +116,10 @@ vi.mock('$lib/tauri-commands', async (importOriginal) => {
     refreshListing: vi.fn().mockResolvedValue({ data: null, timedOut: false }),
     DEFAULT_VOLUME_ID: 'root',
     getE2eStartPath: vi.fn().mockResolvedValue(null),
+    restoreSession: vi.fn().mockResolvedValue(null),
+    getPaneHistory: vi.fn().mockResolvedValue(null),
+    recordHistoryNavigation: vi.fn().mockResolvedValue(undefined),
+    recordHistoryStep: vi.fn().mockResolvedValue(undefined),
     formatBytes: vi.fn().mockReturnValue('0 B'),
     updateFocusedPane: vi.fn().mockResolvedValue(undefined),
     resortListing: vi.fn().mockResolvedValue({}),
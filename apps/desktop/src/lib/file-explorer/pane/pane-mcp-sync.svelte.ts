@@ -59,6 +59,7 @@ export function createPaneMcpSync(deps: PaneMcpSyncDeps) {
     size: 'size',
     modified: 'modified',
     created: 'created',
+    captured: 'captured',
   }
 
   /**
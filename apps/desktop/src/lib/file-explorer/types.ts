@@ -22,6 +22,8 @@ export interface FileEntry {
   addedAt?: number
   /** When the file was last opened (macOS only) */
   openedAt?: number
+  /** When the photo was taken (EXIF), image files only */
+  capturedAt?: number
   permissions: number
   owner: string
   group: string
@@ -81,6 +83,16 @@ export interface FileEntry {
    * Doubles as the aria-label for screen readers.
    */
   displaySizeTooltip?: string
+  /**
+   * Whether the repo's `.gitignore` rules match this entry. Set by the backend for local
+   * listings when `fileExplorer.git.ignoredFiles` is on; the list views grey these rows out.
+   */
+  isGitIgnored?: boolean
+  /**
+   * `online_only` when the backend saw a cloud placeholder while listing (macOS). Other
+   * states come from `getSyncStatus` into the pane's `syncStatusMap`, which wins.
+   */
+  syncStatus?: SyncStatus | null
   /**
    * Parent directory path. Optional on FileEntry because normal directory
    * listings derive it implicitly from the containing folder, but search-results
@@ -269,7 +281,7 @@ export function describeUsbSpeed(speed: UsbSpeed): UsbSpeedDisplay {
 // ============================================================================
 
 /** Column to sort files by. Must match Rust enum. */
-export type SortColumn = 'name' | 'extension' | 'size' | 'modified' | 'created'
+export type SortColumn = 'name' | 'extension' | 'size' | 'modified' | 'created' | 'captured'
 
 /** Sort order. Must match Rust enum. */
 export type SortOrder = 'ascending' | 'descending'
@@ -281,6 +293,7 @@ export const defaultSortOrders: Record<SortColumn, SortOrder> = {
   size: 'descending',
   modified: 'descending',
   created: 'descending',
+  captured: 'descending',
 }
 
 /** Default sort column when opening a new directory. */
@@ -425,7 +438,7 @@ export type MountError =
 
 /** Type of write operation. Mirrors the `WriteOperationType` enum in `bindings.ts`
  *  (the managed instant ops `rename` / `create_folder` / `create_file`, plus
- *  `archive_edit` — the zip mutation op — included). */
+ *  `archive_edit` — the zip mutation op — and the recursive chmod `set_permissions` included). */
 export type WriteOperationType =
   | 'copy'
   | 'move'
@@ -435,6 +448,7 @@ export type WriteOperationType =
   | 'create_folder'
   | 'create_file'
   | 'archive_edit'
+  | 'set_permissions'
 
 /** Transfer-style operations that share the progress UI. `archive_edit` (a zip
  *  temp+rename rewrite) reports progress like a transfer, so it rides this set.
@@ -443,7 +457,15 @@ export type WriteOperationType =
 export type TransferOperationType = 'copy' | 'move' | 'delete' | 'trash' | 'archive_edit' | 'compress'
 
 /** Phase of a write operation. */
-export type WriteOperationPhase = 'scanning' | 'copying' | 'deleting' | 'trashing' | 'rolling_back' | 'flushing'
+export type WriteOperationPhase =
+  | 'scanning'
+  | 'materializing'
+  | 'copying'
+  | 'deleting'
+  | 'trashing'
+  | 'rolling_back'
+  | 'flushing'
+  | 'setting_permissions'
 
 /** How to handle conflicts when destination files already exist. */
 export type ConflictResolution = 'stop' | 'skip' | 'overwrite' | 'rename' | 'overwrite_smaller' | 'overwrite_older'
@@ -521,6 +543,7 @@ export type WriteOperationError =
       files: OversizedFile[]
       totalCount: number
     }
+  | { type: 'verification_failed'; path: string }
   | { type: 'io_error'; path: string; message: string }
   // Extracting from a password-protected archive. `wrongAttempt` is true when the
   // stored password was rejected. The FE should intercept this before the generic
@@ -26,5 +26,8 @@ export function operationTypeIcon(operationType: WriteOperationType): IconName {
     // A zip edit (add/delete/rename inside, or copy/move into/out of a `.zip`).
     case 'archive_edit':
       return 'file-archive'
+    // A recursive chmod.
+    case 'set_permissions':
+      return 'lock'
   }
 }
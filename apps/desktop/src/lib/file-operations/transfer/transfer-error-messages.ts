@@ -128,6 +128,11 @@ const simpleMessageFactories: Partial<
     message: w('invalidName.message'),
     suggestion: w('invalidName.suggestion'),
   }),
+  verification_failed: () => ({
+    title: w('verificationFailed.title'),
+    message: w('verificationFailed.message'),
+    suggestion: w('verificationFailed.suggestion'),
+  }),
   io_error: (op) => ({
     title: w(`ioError.title.${op}`),
     message: w(`ioError.message.${op}`),
@@ -160,6 +165,7 @@ const errorDisplayMetaMap: Record<WriteOperationError['type'], ErrorDisplayMeta>
   read_error: { category: 'serious', retryHint: true },
   write_error: { category: 'serious', retryHint: true },
   io_error: { category: 'serious', retryHint: true },
+  verification_failed: { category: 'serious', retryHint: true },
   symlink_loop: { category: 'serious', retryHint: false },
   source_not_found: { category: 'needs_action', retryHint: false },
   same_location: { category: 'needs_action', retryHint: false },
@@ -295,6 +301,7 @@ const pathOnlyTypes = new Set<WriteOperationError['type']>([
   'connection_interrupted',
   'name_too_long',
   'delete_pending',
+  'verification_failed',
 ])
 
 /** Error types where technical details include path + error message. */
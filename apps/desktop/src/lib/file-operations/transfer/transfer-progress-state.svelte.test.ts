@@ -392,7 +392,7 @@ describe('createTransferProgressState: progress + complete', () => {
     const { state, config } = await startedState()
     if (!errorCb) throw new Error('error subscriber never registered')
     const error: WriteOperationError = { type: 'io_error', path: '/src/file.txt', message: 'boom' }
-    errorCb({ operationId: 'op-1', operationType: 'copy', error })
+    errorCb({ operationId: 'op-1', operationType: 'copy', code: 'IO_ERROR', error })
     expect(state.operationSettled).toBe(true)
     expect(config.onError).toHaveBeenCalledWith(error)
   })
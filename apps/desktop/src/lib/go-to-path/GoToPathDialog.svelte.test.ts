@@ -57,7 +57,7 @@ function goMock(resolution: GoToPathResolution): ReturnType<typeof vi.fn<GoFn>>
 }
 
 function setup(overrides: { baseDir?: string; onGo?: ReturnType<typeof vi.fn<GoFn>>; onCancel?: () => void } = {}) {
-  const onGo = overrides.onGo ?? goMock({ kind: 'directory', path: '/x' })
+  const onGo = overrides.onGo ?? goMock({ kind: 'directory', path: '/x', volumeId: null })
   const onCancel = overrides.onCancel ?? vi.fn()
   const target = document.createElement('div')
   document.body.appendChild(target)
@@ -111,7 +111,7 @@ describe('GoToPathDialog', () => {
 
   it('clicking a recent row jumps and closes the dialog', async () => {
     getRecentPathsListMock.mockReturnValue([{ id: 'a', path: '/recent/one', timestamp: 1 }])
-    const onGo = goMock({ kind: 'directory', path: '/recent/one' })
+    const onGo = goMock({ kind: 'directory', path: '/recent/one', volumeId: null })
     const { target, onCancel, cleanup } = setup({ onGo })
     await tick()
     const row = target.querySelector('.recent-main') as HTMLButtonElement
@@ -128,7 +128,7 @@ describe('GoToPathDialog', () => {
       { id: 'a', path: '/first', timestamp: 1 },
       { id: 'b', path: '/second', timestamp: 2 },
     ])
-    const onGo = goMock({ kind: 'directory', path: '/second' })
+    const onGo = goMock({ kind: 'directory', path: '/second', volumeId: null })
     const { target, cleanup } = setup({ onGo })
     await tick()
     const input = target.querySelector('input') as HTMLInputElement
@@ -141,7 +141,7 @@ describe('GoToPathDialog', () => {
 
   it('a digit with text in the box is ordinary input (no jump)', async () => {
     getRecentPathsListMock.mockReturnValue([{ id: 'a', path: '/first', timestamp: 1 }])
-    const onGo = goMock({ kind: 'directory', path: '/x' })
+    const onGo = goMock({ kind: 'directory', path: '/x', volumeId: null })
     const { target, cleanup } = setup({ onGo })
     await tick()
     const input = target.querySelector('input') as HTMLInputElement
@@ -156,7 +156,7 @@ describe('GoToPathDialog', () => {
 
   it('the remove button removes the entry without jumping', async () => {
     getRecentPathsListMock.mockReturnValue([{ id: 'a', path: '/first', timestamp: 1 }])
-    const onGo = goMock({ kind: 'directory', path: '/x' })
+    const onGo = goMock({ kind: 'directory', path: '/x', volumeId: null })
     const { target, cleanup } = setup({ onGo })
     await tick()
     const removeButton = target.querySelector('.remove-button') as HTMLButtonElement
@@ -169,7 +169,7 @@ describe('GoToPathDialog', () => {
 
   it('the remove button is keyboard-reachable and operable', async () => {
     getRecentPathsListMock.mockReturnValue([{ id: 'a', path: '/first', timestamp: 1 }])
-    const onGo = goMock({ kind: 'directory', path: '/x' })
+    const onGo = goMock({ kind: 'directory', path: '/x', volumeId: null })
     const { target, cleanup } = setup({ onGo })
     await tick()
     const removeButton = target.querySelector('.remove-button') as HTMLButtonElement
@@ -189,7 +189,7 @@ describe('GoToPathDialog', () => {
   })
 
   it('Enter confirms: jumps and closes on a directory outcome', async () => {
-    const onGo = goMock({ kind: 'directory', path: '/typed' })
+    const onGo = goMock({ kind: 'directory', path: '/typed', volumeId: null })
     const { target, onCancel, cleanup } = setup({ onGo })
     await tick()
     const input = target.querySelector('input') as HTMLInputElement
@@ -222,7 +222,7 @@ describe('GoToPathDialog', () => {
 
   it('prefills the box from a clipboard path that resolves to a real target', async () => {
     readClipboardTextMock.mockResolvedValue('/Users/me/Documents')
-    resolveGoToPathMock.mockResolvedValue({ status: 'ok', data: { kind: 'directory', path: '/Users/me/Documents' } })
+    resolveGoToPathMock.mockResolvedValue({ status: 'ok', data: { kind: 'directory', path: '/Users/me/Documents' }, volumeId: null })
     const { target, cleanup } = setup()
     await flush()
     const input = target.querySelector('input') as HTMLInputElement
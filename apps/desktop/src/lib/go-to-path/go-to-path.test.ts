@@ -96,13 +96,13 @@ describe('goToPath handler', () => {
   })
 
   it('resolves against the focused pane path', async () => {
-    okResolve({ kind: 'directory', path: '/tmp' })
+    okResolve({ kind: 'directory', path: '/tmp', volumeId: null })
     await goToPath(makeExplorerStub(), '/tmp')
     expect(resolveGoToPathMock).toHaveBeenCalledWith('/tmp', '/home/me')
   })
 
   it('directory → resolves the dir volume + navigateToDirInPane + records the dir', async () => {
-    okResolve({ kind: 'directory', path: '/tmp/here' })
+    okResolve({ kind: 'directory', path: '/tmp/here', volumeId: null })
     await goToPath(makeExplorerStub(), '/tmp/here')
     expect(resolveLocationOrToastMock).toHaveBeenCalledWith('/tmp/here')
     expect(navigateToDirMock).toHaveBeenCalledWith(expect.anything(), 'left', { volumeId: 'root', path: '/tmp/here' })
@@ -111,7 +111,7 @@ describe('goToPath handler', () => {
   })
 
   it('file → resolves the parent volume + navigateToFileInPane + records the backend-authoritative path', async () => {
-    okResolve({ kind: 'file', path: '/tmp/a.txt', parentDir: '/tmp', fileName: 'a.txt' })
+    okResolve({ kind: 'file', path: '/tmp/a.txt', parentDir: '/tmp', fileName: 'a.txt', volumeId: null })
     await goToPath(makeExplorerStub(), '/tmp/a.txt')
     expect(resolveLocationOrToastMock).toHaveBeenCalledWith('/tmp')
     expect(navigateToFileMock).toHaveBeenCalledWith(
@@ -132,7 +132,7 @@ describe('goToPath handler', () => {
   })
 
   it('unresolvable volume → no navigation, no recents (the shared helper owns the toast)', async () => {
-    okResolve({ kind: 'directory', path: '/Volumes/Gone/dir' })
+    okResolve({ kind: 'directory', path: '/Volumes/Gone/dir', volumeId: null })
     resolveLocationOrToastMock.mockResolvedValue(null)
     await goToPath(makeExplorerStub(), '/Volumes/Gone/dir')
     expect(navigateToDirMock).not.toHaveBeenCalled()
@@ -140,6 +140,14 @@ describe('goToPath handler', () => {
     expect(addRecentPathStateMock).not.toHaveBeenCalled()
   })
 
+  it('notMounted → no navigation, no recents', async () => {
+    okResolve({ kind: 'notMounted', requested: '/Volumes/Backup/docs', mountPoint: '/Volumes/Backup' })
+    await goToPath(makeExplorerStub(), '/Volumes/Backup/docs')
+    expect(resolveLocationOrToastMock).not.toHaveBeenCalled()
+    expect(navigateToDirMock).not.toHaveBeenCalled()
+    expect(addRecentPathStateMock).not.toHaveBeenCalled()
+  })
+
   it('nearestAncestor → builds the toast with the SNAPSHOTTED nav.back shortcut', async () => {
     // A non-default binding proves the toast reads the live effective shortcut
     // rather than hardcoding `⌘[`.
@@ -214,8 +222,10 @@ describe('digitToRecentIndex', () => {
 
 describe('shouldPrefillClipboard', () => {
   it('is true for directory and file resolutions', () => {
-    expect(shouldPrefillClipboard({ kind: 'directory', path: '/x' })).toBe(true)
-    expect(shouldPrefillClipboard({ kind: 'file', path: '/x/a', parentDir: '/x', fileName: 'a' })).toBe(true)
+    expect(shouldPrefillClipboard({ kind: 'directory', path: '/x', volumeId: null })).toBe(true)
+    expect(
+      shouldPrefillClipboard({ kind: 'file', path: '/x/a', parentDir: '/x', fileName: 'a', volumeId: null }),
+    ).toBe(true)
   })
 
   it('is false for nearestAncestor and invalid resolutions', () => {
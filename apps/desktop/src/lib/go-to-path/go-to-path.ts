@@ -38,6 +38,8 @@ const log = getAppLogger('go-to-path')
  * - `file` → navigate to the parent dir and select the file (don't open it).
  * - `nearestAncestor` → navigate to the nearest existing ancestor, then fire an
  *   INFO toast whose back-shortcut is snapshotted at toast-creation.
+ * - `notMounted` → no-op: the path is on a volume that isn't mounted, and
+ *   jumping would land in the empty leftover mount folder.
  * - `invalid` → no-op (empty/unresolvable input; the dialog gates this anyway).
  *
  * On `directory` / `file` / `nearestAncestor` success the resolved target is
@@ -45,7 +47,7 @@ const log = getAppLogger('go-to-path')
  * (HMR or pre-mount).
  *
  * Returns the resolution so callers (the dialog) can react (close on success,
- * stay open on `invalid`).
+ * stay open on `invalid` / `notMounted`).
  */
 export async function goToPath(
   explorer: ExplorerAPI | undefined,
@@ -106,6 +108,12 @@ export async function goToPath(
       await recordRecent(resolution.ancestorDir)
       return resolution
     }
+    case 'notMounted':
+      log.info('goToPath: {mountPoint} is not mounted; not navigating to {requested}', {
+        mountPoint: resolution.mountPoint,
+        requested: resolution.requested,
+      })
+      return resolution
     case 'invalid':
       log.debug('goToPath: invalid input {input}: {reason}', { input, reason: resolution.reason })
       return resolution
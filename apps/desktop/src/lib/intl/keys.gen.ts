@@ -44,6 +44,7 @@ export type MessageKey =
   | 'ai.local.installStepExtracting'
   | 'ai.local.installStepStarting'
   | 'ai.local.installStepVerifying'
+  | 'ai.local.integrityFailed'
   | 'ai.local.memoryWarningAria'
   | 'ai.local.modelLabel'
   | 'ai.local.modelSizeUnknown'
@@ -56,6 +57,8 @@ export type MessageKey =
   | 'ai.local.ramLegendFreed'
   | 'ai.local.ramLegendProjected'
   | 'ai.local.ramLegendSystem'
+  | 'ai.local.restartServer'
+  | 'ai.local.serverConfigFallback'
   | 'ai.local.serverLabel'
   | 'ai.local.startServer'
   | 'ai.local.startingDownload'
@@ -346,6 +349,7 @@ export type MessageKey =
   | 'commands.shareBack.label'
   | 'commands.shareSelectShare.label'
   | 'commands.sortAscending.label'
+  | 'commands.sortByCaptured.label'
   | 'commands.sortByCreated.label'
   | 'commands.sortByExtension.label'
   | 'commands.sortByModified.label'
@@ -823,6 +827,9 @@ export type MessageKey =
   | 'errors.write.trashNotSupported.message'
   | 'errors.write.trashNotSupported.suggestion'
   | 'errors.write.trashNotSupported.title'
+  | 'errors.write.verificationFailed.message'
+  | 'errors.write.verificationFailed.suggestion'
+  | 'errors.write.verificationFailed.title'
   | 'errors.write.writeError.message'
   | 'errors.write.writeError.suggestion'
   | 'errors.write.writeError.title.copy'
@@ -1332,6 +1339,7 @@ export type MessageKey =
   | 'fileOperations.transferProgress.titleCancellingSlow'
   | 'fileOperations.transferProgress.titleConflict'
   | 'fileOperations.transferProgress.titleFlushing'
+  | 'fileOperations.transferProgress.titleMaterializing'
   | 'fileOperations.transferProgress.titlePaused'
   | 'fileOperations.transferProgress.titleRollingBack'
   | 'fileOperations.transferProgress.warningFileOverFolder'
@@ -1351,6 +1359,7 @@ export type MessageKey =
   | 'goToPath.dialog.confirm'
   | 'goToPath.dialog.inputAriaLabel'
   | 'goToPath.dialog.inputPlaceholder'
+  | 'goToPath.dialog.notMountedHint'
   | 'goToPath.dialog.recentsAriaLabel'
   | 'goToPath.dialog.removeFromList'
   | 'goToPath.dialog.title'
@@ -1916,10 +1925,18 @@ export type MessageKey =
   | 'settings.advanced.dragThreshold.label'
   | 'settings.advanced.fileWatcherDebounce.description'
   | 'settings.advanced.fileWatcherDebounce.label'
+  | 'settings.advanced.fileWatcherDebounceLocal.description'
+  | 'settings.advanced.fileWatcherDebounceLocal.label'
+  | 'settings.advanced.fileWatcherDebounceMtp.description'
+  | 'settings.advanced.fileWatcherDebounceMtp.label'
+  | 'settings.advanced.fileWatcherDebounceNetwork.description'
+  | 'settings.advanced.fileWatcherDebounceNetwork.label'
   | 'settings.advanced.filterSafeSaveArtifacts.description'
   | 'settings.advanced.filterSafeSaveArtifacts.label'
   | 'settings.advanced.logLlmCalls.description'
   | 'settings.advanced.logLlmCalls.label'
+  | 'settings.advanced.listingCacheMemoryMb.description'
+  | 'settings.advanced.listingCacheMemoryMb.label'
   | 'settings.advanced.maxLogStorageMb.description'
   | 'settings.advanced.maxLogStorageMb.label'
   | 'settings.advanced.mountTimeout.description'
@@ -1945,6 +1962,12 @@ export type MessageKey =
   | 'settings.ai.loading'
   | 'settings.ai.localContextSize.description'
   | 'settings.ai.localContextSize.label'
+  | 'settings.ai.localGpuLayers.description'
+  | 'settings.ai.localGpuLayers.label'
+  | 'settings.ai.localPort.description'
+  | 'settings.ai.localPort.label'
+  | 'settings.ai.localThreads.description'
+  | 'settings.ai.localThreads.label'
   | 'settings.ai.provider.description'
   | 'settings.ai.provider.label'
   | 'settings.ai.provider.opt.cloud'
@@ -2069,6 +2092,10 @@ export type MessageKey =
   | 'settings.behavior.doubleClickOnPaneNotificationSeen.label'
   | 'settings.behavior.doubleClickPaneNavigatesToParent.description'
   | 'settings.behavior.doubleClickPaneNavigatesToParent.label'
+  | 'settings.behavior.fileEnterAction.description'
+  | 'settings.behavior.fileEnterAction.label'
+  | 'settings.behavior.fileEnterAction.opt.open'
+  | 'settings.behavior.fileEnterAction.opt.viewer'
   | 'settings.behavior.fileSystemWatching.downloadsNotifications.description'
   | 'settings.behavior.fileSystemWatching.downloadsNotifications.label'
   | 'settings.behavior.fileSystemWatching.downloadsNotifications.opt.both'
@@ -2090,6 +2117,8 @@ export type MessageKey =
   | 'settings.behavior.fileSystemWatching.lowDiskSpaceNotifications.opt.off'
   | 'settings.behavior.fileSystemWatching.lowDiskSpaceThresholdPercent.description'
   | 'settings.behavior.fileSystemWatching.lowDiskSpaceThresholdPercent.label'
+  | 'settings.behavior.openWithApps.description'
+  | 'settings.behavior.openWithApps.label'
   | 'settings.control.backToPresetsAriaLabel'
   | 'settings.control.customOption'
   | 'settings.control.customPrefix'
@@ -2102,6 +2131,11 @@ export type MessageKey =
   | 'settings.developer.mcpPort.label'
   | 'settings.developer.verboseLogging.description'
   | 'settings.developer.verboseLogging.label'
+  | 'settings.fileExplorer.git.ignoredFiles.description'
+  | 'settings.fileExplorer.git.ignoredFiles.label'
+  | 'settings.fileExplorer.git.ignoredFiles.opt.dim'
+  | 'settings.fileExplorer.git.ignoredFiles.opt.hide'
+  | 'settings.fileExplorer.git.ignoredFiles.opt.off'
   | 'settings.fileExplorer.git.showRepoChip.description'
   | 'settings.fileExplorer.git.showRepoChip.label'
   | 'settings.fileExplorer.git.showStatusColumn.description'
@@ -2146,8 +2180,14 @@ export type MessageKey =
   | 'settings.indexing.askForEachDrive.label'
   | 'settings.indexing.enabled.description'
   | 'settings.indexing.enabled.label'
+  | 'settings.indexing.excludePatterns.description'
+  | 'settings.indexing.excludePatterns.label'
   | 'settings.indexing.firstStaleDialogShown.description'
   | 'settings.indexing.firstStaleDialogShown.label'
+  | 'settings.indexing.maxEntriesPerSec.description'
+  | 'settings.indexing.maxEntriesPerSec.label'
+  | 'settings.indexing.onlyOnAcPower.description'
+  | 'settings.indexing.onlyOnAcPower.label'
   | 'settings.indexing.reEnableNotifications.button'
   | 'settings.indexing.reEnableNotifications.description'
   | 'settings.indexing.reEnableNotifications.disabledTooltip'
@@ -2590,6 +2630,8 @@ export type MessageKey =
   | 'viewer.selection.toEndOfFile'
   | 'viewer.srHeading'
   | 'viewer.statusBar.ariaLabel'
+  | 'viewer.statusBar.badge.hex'
+  | 'viewer.statusBar.badge.hexTooltip'
   | 'viewer.statusBar.badge.inMemory'
   | 'viewer.statusBar.badge.inMemoryTooltip'
   | 'viewer.statusBar.badge.indexed'
@@ -2604,6 +2646,7 @@ export type MessageKey =
   | 'viewer.statusBar.hint.text'
   | 'viewer.statusBar.lineCount'
   | 'viewer.toolbar.encoding.detectedSuffix'
+  | 'viewer.toolbar.encoding.group.japanese'
   | 'viewer.toolbar.encoding.group.unicode'
   | 'viewer.toolbar.encoding.group.western'
   | 'viewer.toolbar.encoding.placeholder'
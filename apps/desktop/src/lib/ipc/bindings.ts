@@ -57,6 +57,35 @@ export const commands = {
         listingId,
       }),
     ),
+  /**
+   *  Starts a flattened listing of everything below `path` (see `listing/flat.rs`). Returns
+   *  immediately and emits the same events as `list_directory_start_streaming`; entry names
+   *  are paths relative to `path`. `cancel_listing` and `list_directory_end` apply as usual.
+   */
+  listDirectoryFlat: (
+    volumeId: string,
+    path: string,
+    options: FlatListingOptions,
+    sortBy: SortColumn,
+    sortOrder: SortOrder,
+    directorySortMode: DirectorySortMode | null,
+    listingId: string,
+  ) =>
+    __TAURI_INVOKE<StreamingListingStartResult>('list_directory_flat', {
+      volumeId,
+      path,
+      options,
+      sortBy,
+      sortOrder,
+      directorySortMode,
+      listingId,
+    }),
+  /**
+   *  Returns the subfolders of `path` for the sidebar tree, each with a `has_children` flag
+   *  (see `listing/tree.rs`). One level per call; the frontend fetches deeper levels on expand.
+   */
+  getTreeChildren: (volumeId: string, path: string, options: TreeChildrenOptions) =>
+    typedError<TreeNode[], IpcError>(__TAURI_INVOKE('get_tree_children', { volumeId, path, options })),
   cancelListing: (listingId: string) => __TAURI_INVOKE<void>('cancel_listing', { listingId }),
   listDirectoryEnd: (listingId: string) => __TAURI_INVOKE<void>('list_directory_end', { listingId }),
   /**
@@ -126,6 +155,12 @@ export const commands = {
         addedAt: number | null
         // When the file was last opened (macOS only)
         openedAt: number | null
+        /**
+         *  When the photo was taken (EXIF `DateTimeOriginal`), for image files only. Filled
+         *  by the extended-metadata load and, on demand, for the Captured sort. `None` for
+         *  non-images, images without an EXIF date, and entries not enriched yet.
+         */
+        capturedAt: number | null
         permissions: number
         owner: string
         group: string
@@ -199,6 +234,20 @@ export const commands = {
          *  `origin/main`". Doubles as the aria-label for screen readers.
          */
         displaySizeTooltip: string | null
+        /**
+         *  Whether the repo's ignore rules (`.gitignore`, `.git/info/exclude`, global excludes)
+         *  match this entry. Only computed for local listings with the
+         *  `fileExplorer.git.ignoredFiles` setting on; `false` otherwise. The frontend greys these
+         *  rows out; in `Hide` mode the backend leaves them out instead. See `git_ignore.rs`.
+         */
+        isGitIgnored: boolean
+        /**
+         *  Cloud state read from the stat flags during listing: `OnlineOnly` for a cloud
+         *  placeholder (iCloud Drive, Dropbox, Google Drive), `None` otherwise. Telling synced
+         *  from uploading or downloading takes an XPC call per file, so that stays with the
+         *  visible-range `get_sync_status`. Always `None` off macOS. See `sync_status.rs`.
+         */
+        syncStatus: SyncStatus | null
       } | null,
       string
     >(__TAURI_INVOKE('get_file_at', { listingId, index, includeHidden })),
@@ -232,6 +281,7 @@ export const commands = {
    *    retries after `ensureFontMetricsLoaded` resolves.
    *  - `invalid_items_per_column`: caller sent 0; FE clamps to >= 1 normally.
    *  - `listing_not_found:{id}`: listing already ended (or never started).
+   *  - `listing_not_ready:{id}`: listing was evicted and is being re-read; a `directory-diff` follows.
    *  - Anything else is a pass-through (cache-lock poisoning etc.).
    */
   getBriefColumnTextWidths: (
@@ -244,6 +294,17 @@ export const commands = {
     typedError<number[], IpcError>(
       __TAURI_INVOKE('get_brief_column_text_widths', { listingId, itemsPerColumn, hasParent, fontId, includeHidden }),
     ),
+  /**
+   *  The ordered column set (ids + widths) for `view_mode`, or the defaults if the user never
+   *  chose one. Backed by `listing-columns.json`.
+   */
+  getColumns: (viewMode: ViewMode) => __TAURI_INVOKE<ColumnSetting[]>('get_columns', { viewMode }),
+  /**
+   *  Stores the ordered column set for `view_mode` and returns it as stored (widths clamped).
+   *  Refused when the Name column is missing or a column is listed twice.
+   */
+  setColumns: (viewMode: ViewMode, columns: ColumnSetting[]) =>
+    typedError<ColumnSetting[], ColumnsError>(__TAURI_INVOKE('set_columns', { viewMode, columns })),
   findFileIndex: (listingId: string, name: string, includeHidden: boolean) =>
     typedError<number | null, string>(__TAURI_INVOKE('find_file_index', { listingId, name, includeHidden })),
   findFileIndices: (listingId: string, names: string[], includeHidden: boolean) =>
@@ -549,9 +610,31 @@ export const commands = {
        */
       estimatedCompressedBytes?: CompressedSizeEstimate | null
     } | null>('check_scan_preview_status', { previewId }),
-  // In Stop mode, the operation pauses on conflict and waits for this call to proceed.
-  resolveWriteConflict: (operationId: string, resolution: ConflictResolution, applyToAll: boolean) =>
-    __TAURI_INVOKE<void>('resolve_write_conflict', { operationId, resolution, applyToAll }),
+  /**
+   *  In Stop mode, the operation pauses on conflict and waits for this call to proceed.
+   *  `remember_for_pair` also stores `resolution` as the default for that folder pair, so later
+   *  operations between the same folders don't prompt. The operation resolves even if that save fails.
+   */
+  resolveWriteConflict: (
+    operationId: string,
+    resolution: ConflictResolution,
+    applyToAll: boolean,
+    rememberForPair: DirPair | null,
+  ) => __TAURI_INVOKE<void>('resolve_write_conflict', { operationId, resolution, applyToAll, rememberForPair }),
+  /**
+   *  Remembers `policy` as the conflict policy for copies and moves from `pair.source_dir` into
+   *  `pair.destination_dir`. Operations that would prompt use it instead. `stop` forgets the pair.
+   */
+  setDefaultConflictPolicy: (pair: DirPair, policy: ConflictResolution) =>
+    typedError<null, IpcError>(__TAURI_INVOKE('set_default_conflict_policy', { pair, policy })),
+  // The remembered per-folder-pair conflict policies, most recently set first.
+  listDefaultConflictPolicies: () => __TAURI_INVOKE<DirPairPolicy[]>('list_default_conflict_policies'),
+  /**
+   *  Forgets the remembered policy for `pair`, or all of them when `pair` is omitted. Returns how
+   *  many were removed.
+   */
+  clearDefaultConflictPolicies: (pair: DirPair | null) =>
+    typedError<number, IpcError>(__TAURI_INVOKE('clear_default_conflict_policies', { pair })),
   listActiveOperations: () => __TAURI_INVOKE<OperationSummary[]>('list_active_operations'),
   getOperationStatus: (operationId: string) =>
     __TAURI_INVOKE<{
@@ -587,6 +670,13 @@ export const commands = {
    *  "Cancel selected".
    */
   cancelOperations: (operationIds: string[]) => __TAURI_INVOKE<void>('cancel_operations', { operationIds }),
+  /**
+   *  Moves a queued operation ahead of `before_operation_id`, or to the back of the
+   *  queue when it's `None`. Backs the queue window's drag-reorder. Running/paused
+   *  ops and stale ids are a no-op.
+   */
+  moveOperation: (operationId: string, beforeOperationId: string | null) =>
+    __TAURI_INVOKE<void>('move_operation', { operationId, beforeOperationId }),
   /**
    *  Pauses one Running operation. It parks at the next between-files boundary and
    *  its lifecycle status flips to `paused` in `operations-changed`. A paused op
@@ -783,6 +873,11 @@ export const commands = {
     typedError<ListingStats, string>(
       __TAURI_INVOKE('get_listing_stats', { listingId, includeHidden, selectedIndices }),
     ),
+  /**
+   *  Approximate listing cache memory against its cap, plus how many listings are held and how
+   *  many were evicted (they re-list on their next access).
+   */
+  getListingCacheStats: () => __TAURI_INVOKE<ListingCacheStats>('get_listing_cache_stats'),
   // Re-enriches cached listing entries with fresh drive index data.
   refreshListingIndexSizes: (listingId: string) =>
     typedError<null, string>(__TAURI_INVOKE('refresh_listing_index_sizes', { listingId })),
@@ -920,8 +1015,8 @@ export const commands = {
    *  that never fires the FE `viewer_close` IPC). Pass an empty string when there's
    *  no owning window (no mapping is recorded).
    */
-  viewerOpen: (path: string, volumeId: string, windowLabel: string) =>
-    typedError<ViewerOpenResult, ViewerError>(__TAURI_INVOKE('viewer_open', { path, volumeId, windowLabel })),
+  viewerOpen: (path: string, volumeId: string, windowLabel: string, mode: ViewerOpenMode | null) =>
+    typedError<ViewerOpenResult, ViewerError>(__TAURI_INVOKE('viewer_open', { path, volumeId, windowLabel, mode })),
   /**
    *  Opens a fresh, full **text** session for `path`, ignoring media classification.
    *
@@ -1040,6 +1135,13 @@ export const commands = {
    *  encoding. Called by the FE reload toast and on file rotation.
    */
   viewerReload: (sessionId: string) => typedError<null, string>(__TAURI_INVOKE('viewer_reload', { sessionId })),
+  /**
+   *  A bounded preview of `path` for the inactive pane's quick look: text, an image token,
+   *  a hex dump, or a folder's entry count and indexed size. No session is opened, so the FE
+   *  can call this on every (debounced) cursor move. `max_bytes` of 0 picks the default.
+   */
+  peekFile: (path: string, maxBytes: number) =>
+    typedError<PeekResult, IpcError>(__TAURI_INVOKE('peek_file', { path, maxBytes })),
   /**
    *  Checks if font metrics are available for a font ID.
    *
@@ -1142,6 +1244,12 @@ export const commands = {
    *  `open_path`, so specs assert intent via `e2e_opened_paths`.
    */
   openInEditor: (path: string) => typedError<null, string>(__TAURI_INVOKE('open_in_editor', { path })),
+  /**
+   *  Opens `path` with the app whose bundle id is `app_identifier` (from `get_applications_for`), and
+   *  remembers it for the extension when it isn't the default.
+   */
+  openWith: (path: string, appIdentifier: string) =>
+    typedError<null, IpcError>(__TAURI_INVOKE('open_with', { path, appIdentifier })),
   /**
    *  Open a file (or folder) with the system's default application.
    *
@@ -1164,6 +1272,27 @@ export const commands = {
    *  `cloud_make_available_offline`.
    */
   cloudRemoveDownload: (path: string) => typedError<null, string>(__TAURI_INVOKE('cloud_remove_download', { path })),
+  /**
+   *  Downloads an online-only file (iCloud Drive, Dropbox, Google Drive) so it's available
+   *  offline, streaming `cloud-materialize-progress` events. Returns `already_local` without
+   *  downloading when the content is already on disk. Not timeout-wrapped: a big file
+   *  legitimately takes minutes. A download that stops growing fails after two minutes instead.
+   */
+  materializeFile: (path: string) =>
+    typedError<MaterializeOutcome, string>(__TAURI_INVOKE('materialize_file', { path })),
+  /**
+   *  Evicts a file's local copy back to online-only. Counterpart to `materialize_file`; a
+   *  no-op for a file that's already online-only. Only iCloud Drive supports it.
+   */
+  dematerializeFile: (path: string) => typedError<null, string>(__TAURI_INVOKE('dematerialize_file', { path })),
+  /**
+   *  Resolves what Enter or a double-click should do on `path` (see
+   *  `file_system/default_action.rs`). Pass `is_directory` from the listing entry when known;
+   *  `None` stats the path (2 s read timeout), and a path that can't be stat'ed, such as an
+   *  entry inside an archive, counts as a file.
+   */
+  resolveDefaultAction: (path: string, isDirectory: boolean | null) =>
+    typedError<DefaultAction, IpcError>(__TAURI_INVOKE('resolve_default_action', { path, isDirectory })),
   /**
    *  Tauri command to update left pane state from frontend.
    *  Preserves `tabs` (those are synced separately via `update_pane_tabs`).
@@ -1445,6 +1574,13 @@ export const commands = {
    *  Async because file deletion may block briefly.
    */
   uninstallAi: () => __TAURI_INVOKE<void>('uninstall_ai'),
+  /**
+   *  Removes a user model's file and entry. Built-in models are refused; removing them is
+   *  `uninstall_ai`'s job. If the model is the active one, the server stops and the
+   *  installation falls back to the default model (not installed yet).
+   */
+  uninstallCustomModel: (modelId: string) =>
+    typedError<null, string>(__TAURI_INVOKE('uninstall_custom_model', { modelId })),
   saveAiApiKey: (providerId: string, apiKey: string) =>
     typedError<null, AiApiKeyError>(__TAURI_INVOKE('save_ai_api_key', { providerId, apiKey })),
   /**
@@ -1466,6 +1602,40 @@ export const commands = {
    */
   getFolderSuggestions: (listingId: string, currentPath: string, includeHidden: boolean) =>
     typedError<string[], string>(__TAURI_INVOKE('get_folder_suggestions', { listingId, currentPath, includeHidden })),
+  /**
+   *  Suggests up to [`RENAME_SUGGESTIONS`] new names for the file or folder at `path`.
+   *
+   *  The prompt carries the name, its extension, some sibling names, and for a text file its
+   *  first bytes. Every candidate keeps the original extension and passes
+   *  [`check_rename_validity`](crate::commands::rename::check_rename_validity) without a
+   *  conflict, so the UI can offer each one as-is. Like folder suggestions, a missing backend
+   *  or a failed AI call returns `Ok(Vec::new())`.
+   */
+  getRenameSuggestion: (path: string) => typedError<string[], string>(__TAURI_INVOKE('get_rename_suggestion', { path })),
+  /**
+   *  Asks the AI for a plan that does what `request` says to the folder at `current_path` on
+   *  `volume_id`, and stages it for preview. Unlike suggestions this surfaces errors: the user asked for it.
+   */
+  proposeFolderPlan: (
+    listingId: string,
+    volumeId: string,
+    currentPath: string,
+    includeHidden: boolean,
+    request: string,
+  ) =>
+    typedError<FolderPlan, string>(
+      __TAURI_INVOKE('propose_folder_plan', { listingId, volumeId, currentPath, includeHidden, request }),
+    ),
+  /**
+   *  Applies a staged plan after the user confirmed it. The plan is consumed first, so a second
+   *  click can't replay it. It's re-checked against the folder as it is now (the user may have
+   *  changed it while the preview was open); then creates and renames run in order, and the moves
+   *  are queued last, once every folder they target exists.
+   */
+  applyFolderPlan: (planId: string) =>
+    typedError<FolderPlanOutcome, string>(__TAURI_INVOKE('apply_folder_plan', { planId })),
+  // Drops a staged plan the user dismissed.
+  cancelFolderPlan: (planId: string) => __TAURI_INVOKE<null>('cancel_folder_plan', { planId }),
   // Returns whether the MCP server is currently running.
   getMcpRunning: () => __TAURI_INVOKE<boolean>('get_mcp_running'),
   // Returns the port the MCP server is actually listening on, or null if not running.
@@ -1513,11 +1683,22 @@ export const commands = {
    */
   getIsolatedStorePath: (storeName: string) => __TAURI_INVOKE<string | null>('get_isolated_store_path', { storeName }),
   /**
-   *  Updates the file watcher debounce duration in milliseconds.
-   *  This affects newly created watchers; existing watchers keep their original duration.
+   *  Updates the general file watcher debounce duration in milliseconds, the default for every
+   *  volume kind without an override. Active watchers pick it up right away.
    */
   updateFileWatcherDebounce: (debounceMs: number) =>
     __TAURI_INVOKE<void>('update_file_watcher_debounce', { debounceMs }),
+  /**
+   *  Sets (`Some`) or clears (`None`) the file watcher debounce override for one volume kind.
+   *  Active watchers pick it up right away.
+   */
+  updateFileWatcherDebounceForVolumeKind: (volumeKind: WatcherVolumeKind, debounceMs: number | null) =>
+    __TAURI_INVOKE<void>('update_file_watcher_debounce_for_volume_kind', { volumeKind, debounceMs }),
+  /**
+   *  Per-listing watcher counters (events received, diffs emitted, effective debounce) for tuning
+   *  the debounce and diagnosing flickering folders. Counters reset when a listing closes.
+   */
+  getWatcherStats: () => __TAURI_INVOKE<WatcherListingStats[]>('get_watcher_stats'),
   /**
    *  Updates the mDNS service resolve timeout in milliseconds.
    *  This affects future service resolutions; ongoing resolutions keep their original timeout.
@@ -1547,6 +1728,20 @@ export const commands = {
    *  whenever `network.smbConcurrency` changes.
    */
   setSmbConcurrencyCmd: (value: number) => __TAURI_INVOKE<void>('set_smb_concurrency_cmd', { value }),
+  /**
+   *  Update the listing cache's memory cap. Inactive listings past it are evicted right away
+   *  and re-listed when next touched. Pushed live from the frontend whenever
+   *  `advanced.listingCacheMemoryMb` changes.
+   */
+  setListingCacheMemoryMb: (value: number) => __TAURI_INVOKE<void>('set_listing_cache_memory_mb', { value }),
+  /**
+   *  Update the settings `resolve_default_action` reads. Takes the raw stored values and
+   *  parses them leniently (see `file_system/default_action.rs`). Pushed live from the
+   *  frontend whenever `behavior.archiveEnterBehavior`, `behavior.fileEnterAction`, or
+   *  `behavior.openWithApps` changes; all three travel together.
+   */
+  setDefaultActionSettings: (archiveEnterBehavior: string, fileEnterAction: string, openWithApps: string) =>
+    __TAURI_INVOKE<void>('set_default_action_settings', { archiveEnterBehavior, fileEnterAction, openWithApps }),
   /**
    *  Turn LLM call logging on or off. When on, every AI model request and response is written
    *  to `{app data dir}/llm-logs/` for debugging (local only, never transmitted). Pushed live
@@ -1615,6 +1810,25 @@ export const commands = {
    *  Marking the command `async` puts it on a Tokio worker, where `spawn` works.
    */
   setShowVirtualGitPortal: (enabled: boolean) => __TAURI_INVOKE<void>('set_show_virtual_git_portal', { enabled }),
+  /**
+   *  Sets how listings treat git-ignored entries: not at all, greyed out, or left out. Pushed
+   *  live from the frontend whenever `fileExplorer.git.ignoredFiles` changes. Open local
+   *  listings are re-marked right away and get a diff where their rows changed.
+   */
+  setGitIgnoredMode: (mode: GitIgnoredMode) => __TAURI_INVOKE<void>('set_git_ignored_mode', { mode }),
+  /**
+   *  Writes a settings export (built by the frontend's `settings-transfer.ts`) to `out_path`.
+   *  The backend never builds or applies exports itself: the frontend registry validates every
+   *  field, and only the frontend store writes `settings.json`. 5 s write timeout.
+   */
+  writeSettingsExport: (outPath: string, contents: string) =>
+    typedError<null, IpcError>(__TAURI_INVOKE('write_settings_export', { outPath, contents })),
+  /**
+   *  Reads a settings export for the frontend to validate and apply. Refuses files over
+   *  1 MB and anything that isn't a JSON object. 2 s read timeout.
+   */
+  readSettingsExport: (inPath: string) =>
+    typedError<string, IpcError>(__TAURI_INVOKE('read_settings_export', { inPath })),
   /**
    *  Receives batched log entries from the frontend and re-emits them through the Rust `log` facade.
    *  This ensures frontend logs appear in the terminal and log file alongside Rust logs.
@@ -1818,6 +2032,49 @@ export const commands = {
    */
   rescanDriveIndex: (volumeId: string) =>
     typedError<EnableIndexingOutcome, string>(__TAURI_INVOKE('rescan_drive_index', { volumeId })),
+  /**
+   *  Re-walks one folder of the local `root` index and corrects its entries and sizes ("Refresh
+   *  sizes for this folder"), leaving the rest of the drive alone. Returns the operation id right
+   *  away; counts stream as `reindex-progress` and end with one `reindex-complete`. Stop it with
+   *  [`cancel_reindex_subtree`]; re-indexing the same folder again resumes where it stopped.
+   */
+  reindexSubtree: (path: string) => __TAURI_INVOKE<string>('reindex_subtree', { path }),
+  /**
+   *  Cancels a running folder re-index. Returns `false` if it already finished. The re-index
+   *  still ends with a `reindex-complete`, flagged `cancelled`.
+   */
+  cancelReindexSubtree: (operationId: string) => __TAURI_INVOKE<boolean>('cancel_reindex_subtree', { operationId }),
+  /**
+   *  Pauses a drive's running full scan without tearing it down: the walk holds where it is
+   *  and the watcher keeps buffering, so resuming doesn't re-read finished folders. Progress
+   *  events stop while paused. Only a fresh local scan can pause; errors otherwise.
+   */
+  pauseDriveIndexScan: (volumeId: string) =>
+    typedError<null, string>(__TAURI_INVOKE('pause_drive_index_scan', { volumeId })),
+  /**
+   *  Resumes a scan paused with `pause_drive_index_scan`. A no-op when none is paused.
+   */
+  resumeDriveIndexScan: (volumeId: string) =>
+    typedError<null, string>(__TAURI_INVOKE('resume_drive_index_scan', { volumeId })),
+  /**
+   *  Compacts a volume's index DB: a full `VACUUM` (run on the index writer, between its
+   *  batches) then a WAL truncate. Returns the file size before and after. Errors mid-scan.
+   */
+  compactIndex: (volumeId: string) =>
+    typedError<CompactStats, string>(__TAURI_INVOKE('compact_index', { volumeId })),
+  /**
+   *  Replaces the index exclusion patterns (`indexing.excludePatterns`: folder names like
+   *  `node_modules`, or trailing paths like `Library/Caches`) and prunes folders they newly
+   *  cover from every index, in the background. Live-applied; the frontend persists the setting
+   *  and calls this on change. Removing a pattern doesn't re-add anything until the folder's
+   *  parent is opened or the drive is rescanned.
+   */
+  setIndexExclusions: (patterns: string[]) => __TAURI_INVOKE<null>('set_index_exclusions', { patterns }),
+  /**
+   *  Sets the fresh-scan throttle (`indexing.maxEntriesPerSec` and `indexing.onlyOnAcPower`).
+   *  Live-applied: a scan that's already running picks it up on its next folder.
+   */
+  setIndexThrottle: (throttle: ScanThrottleSettings) => __TAURI_INVOKE<null>('set_index_throttle', { throttle }),
   /**
    *  Record that the user navigated into `location`. Fire-and-forget and
    *  failure-silent: never blocks or breaks navigation.
@@ -2160,6 +2417,12 @@ export const commands = {
    */
   resolveGoToPath: (input: string, baseDir: string) =>
     typedError<GoToPathResolution, IpcError>(__TAURI_INVOKE('resolve_go_to_path', { input, baseDir })),
+  /**
+   *  Lists children of the folder `partial` points into that match its last
+   *  segment, for the dialog's as-you-type suggestions.
+   */
+  completeGoToPath: (partial: string, baseDir: string) =>
+    typedError<PathCompletion[], IpcError>(__TAURI_INVOKE('complete_go_to_path', { partial, baseDir })),
   // Reads the persisted recent-path entries (newest first).
   getRecentPaths: () => __TAURI_INVOKE<RecentPathEntry[]>('get_recent_paths'),
   /**
@@ -2171,6 +2434,41 @@ export const commands = {
   removeRecentPath: (id: string) => typedError<null, string>(__TAURI_INVOKE('remove_recent_path', { id })),
   // Clears every recent-path entry.
   clearRecentPaths: () => typedError<null, string>(__TAURI_INVOKE('clear_recent_paths')),
+  /**
+   *  Lists every palette action with `enabled` evaluated against the focused
+   *  pane's `context` and the current clipboard.
+   */
+  getPaletteActions: (context: PaletteContext) =>
+    __TAURI_INVOKE<PaletteAction[]>('get_palette_actions', { context }),
+  /**
+   *  Runs a palette action by id, through the same `execute-command` route a menu
+   *  click takes.
+   */
+  invokePaletteAction: (id: string) =>
+    typedError<null, PaletteInvokeError>(__TAURI_INVOKE('invoke_palette_action', { id })),
+  /**
+   *  Snapshots both panes (volume, path, sort, view mode, cursor name, tabs) to app
+   *  data. The frontend calls it on quit and whenever it wants a checkpoint.
+   */
+  saveSession: () => typedError<Session, string>(__TAURI_INVOKE('save_session')),
+  /**
+   *  Returns the saved session with unavailable panes set to `None`, reconnecting
+   *  MTP devices first. `None` when there's no usable session; never blocks
+   *  startup on a corrupt file or a dead mount.
+   */
+  restoreSession: () => __TAURI_INVOKE<Session | null>('restore_session'),
+  /**
+   *  Records a navigation in `pane`'s history (dropping any forward entries) and returns the
+   *  updated history. Pushing the location already shown is a no-op.
+   */
+  pushHistory: (pane: HistoryPane, volumeId: string, path: string) =>
+    __TAURI_INVOKE<NavigationHistory>('push_history', { pane, volumeId, path }),
+  // Steps `pane` back one entry and returns the location to navigate to, or `None` at the start.
+  goBack: (pane: HistoryPane) => __TAURI_INVOKE<NavigationEntry | null>('go_back', { pane }),
+  // Steps `pane` forward one entry and returns the location to navigate to, or `None` at the end.
+  goForward: (pane: HistoryPane) => __TAURI_INVOKE<NavigationEntry | null>('go_forward', { pane }),
+  // Returns `pane`'s full history, oldest first, for back/forward menus and "recent locations".
+  getHistory: (pane: HistoryPane) => __TAURI_INVOKE<NavigationHistory>('get_history', { pane }),
   /**
    *  Adds a favorite for `path`, deduping by normalized path. When `name` is omitted, the label
    *  defaults to the path's file name.
@@ -2226,6 +2524,15 @@ export const commands = {
       } | null,
       string
     >(__TAURI_INVOKE('get_operation_log_detail', { operationId, itemLimit, itemOffset })),
+  // The next undo and redo, for the Edit menu labels.
+  getUndoState: () => __TAURI_INVOKE<UndoState>('get_undo_state'),
+  /**
+   *  Undo the most recent move, rename, or trash by rolling it back. Returns after
+   *  dispatch; the reversal runs as a managed op.
+   */
+  undoLastOperation: () => typedError<UndoDispatch, UndoRefusal>(__TAURI_INVOKE('undo_last_operation')),
+  // Redo the most recently undone operation. Same dispatch contract as undo.
+  redoLastOperation: () => typedError<UndoDispatch, UndoRefusal>(__TAURI_INVOKE('redo_last_operation')),
   /**
    *  Stop the in-flight turn for a thread. Idempotent: an unknown id (already finished) is a
    *  no-op. A clean stop at the next tool boundary or stream chunk, not a hard abort.
@@ -2470,6 +2777,17 @@ export const commands = {
    *  something else), the stale cut state is automatically cleared.
    */
   readClipboardFiles: () => typedError<ClipboardReadResult, string>(__TAURI_INVOKE('read_clipboard_files')),
+  /**
+   *  Pastes the clipboard's files into `destination` through the write pipeline: a
+   *  move when they were cut in Cmdr (or `force_move`), a copy otherwise. The
+   *  clipboard may come from Finder or any other app (pasteboard file URLs). Emits
+   *  the usual write-progress / write-complete events, so the paste shows progress
+   *  like any transfer. `None` when the clipboard holds no files.
+   */
+  pasteClipboardFiles: (destination: string, forceMove: boolean | null, config: WriteOperationConfig | null) =>
+    typedError<WriteOperationStartResult | null, WriteOperationError>(
+      __TAURI_INVOKE('paste_clipboard_files', { destination, forceMove, config }),
+    ),
   /**
    *  Reads plain text from the system clipboard.
    *
@@ -2552,6 +2870,18 @@ export const commands = {
       // Available storages on the device.
       storages: MtpStorageInfo[]
     } | null>('get_mtp_device_info', { deviceId }),
+  /**
+   *  Gets the operations a connected MTP device supports.
+   *
+   *  Lets the UI disable actions the device would refuse (rename, move, create folder, and so on)
+   *  instead of failing them at runtime. Returns `None` if the device is not connected.
+   *
+   *  # Arguments
+   *
+   *  * `device_id` - The device ID to query
+   */
+  getMtpCapabilities: (deviceId: string) =>
+    __TAURI_INVOKE<MtpDeviceCapabilities | null>('get_mtp_capabilities', { deviceId }),
   /**
    *  Disconnects from an MTP device.
    *
@@ -2590,10 +2920,27 @@ export const commands = {
    *
    *  # Returns
    *
-   *  A vector of FileEntry objects, sorted with directories first.
+   *  A vector of FileEntry objects in device order. Panes sort them through the
+   *  listing pipeline, by the pane's sort column and order.
    */
   listMtpDirectory: (deviceId: string, storageId: number, path: string) =>
     typedError<FileEntry[], MtpConnectionError>(__TAURI_INVOKE('list_mtp_directory', { deviceId, storageId, path })),
+  /**
+   *  Re-reads a directory on an MTP device, bypassing the listing cache.
+   *
+   *  Use for an explicit refresh: `list_mtp_directory` may serve a cached listing
+   *  for a few seconds, which hides changes another app made on the phone.
+   *
+   *  # Arguments
+   *
+   *  * `device_id` - The connected device ID
+   *  * `storage_id` - The storage ID within the device
+   *  * `path` - Virtual path to re-read (for example, "/" or "/DCIM")
+   */
+  refreshMtpDirectory: (deviceId: string, storageId: number, path: string) =>
+    typedError<FileEntry[], MtpConnectionError>(
+      __TAURI_INVOKE('refresh_mtp_directory', { deviceId, storageId, path }),
+    ),
   /**
    *  Gets the ptpcamerad workaround command for macOS.
    *
@@ -2659,6 +3006,51 @@ export const commands = {
     typedError<MtpObjectInfo, MtpConnectionError>(
       __TAURI_INVOKE('move_mtp_object', { deviceId, storageId, objectPath, newParentPath }),
     ),
+  /**
+   *  Moves an object from one storage of an MTP device to a folder on another (internal
+   *  storage to SD card, say). Uses a cross-storage `MoveObject` when the device accepts it,
+   *  else copies through a temp file and deletes the source, emitting `mtp-storage-move-progress`
+   *  after each file. Open listings of both folders get a `directory-diff`.
+   *
+   *  # Arguments
+   *
+   *  * `device_id` - The connected device ID
+   *  * `from_storage_id` - The storage the object is on
+   *  * `object_path` - Current path of the object
+   *  * `to_storage_id` - The storage to move it to
+   *  * `new_parent_path` - Destination folder path on `to_storage_id`
+   */
+  moveMtpObjectBetweenStorages: (
+    deviceId: string,
+    fromStorageId: number,
+    objectPath: string,
+    toStorageId: number,
+    newParentPath: string,
+  ) =>
+    typedError<MtpObjectInfo, MtpConnectionError>(
+      __TAURI_INVOKE('move_mtp_object_between_storages', {
+        deviceId,
+        fromStorageId,
+        objectPath,
+        toStorageId,
+        newParentPath,
+      }),
+    ),
+  /**
+   *  Returns the embedded thumbnail of an MTP object as base64-encoded JPEG bytes, or `None` when
+   *  it has none (folders, formats without one, devices without `GetThumb`). Thumbnails are cached
+   *  per device, so re-rendering a grid doesn't go back to the device.
+   *
+   *  # Arguments
+   *
+   *  * `device_id` - The connected device ID
+   *  * `storage_id` - The storage ID within the device
+   *  * `object_path` - Virtual path on the device
+   */
+  getMtpThumbnail: (deviceId: string, storageId: number, objectPath: string) =>
+    typedError<string | null, MtpConnectionError>(
+      __TAURI_INVOKE('get_mtp_thumbnail', { deviceId, storageId, objectPath }),
+    ),
   /**
    *  Scans an MTP path for copy statistics.
    *
@@ -2981,10 +3373,12 @@ export const commands = {
   /**
    *  Ejects a volume. Picks the right teardown for the volume's kind.
    *
-   *  Returns `Ok(())` once the unmount or disconnect is initiated. The frontend
-   *  shouldn't wait for the volume to fully disappear — `volume-unmounted` (for
-   *  disk volumes) or `mtp-device-disconnected` (for MTP) will fire shortly
-   *  after and panes rooted at the volume redirect to root.
+   *  Returns `Ok(())` once the unmount or disconnect is done, after
+   *  `volume-ejected` fired. Refuses with the blocking operation ids when a write
+   *  op still touches the volume. The frontend shouldn't wait for the volume to
+   *  fully disappear — `volume-unmounted` (for disk volumes) or
+   *  `mtp-device-disconnected` (for MTP) will fire shortly after and panes
+   *  rooted at the volume redirect to root.
    */
   ejectVolume: (volumeId: string) => typedError<null, IpcError>(__TAURI_INVOKE('eject_volume', { volumeId })),
   /**
@@ -3164,6 +3558,7 @@ export const events = {
   aiExtracting: makeEvent<AiExtracting>('ai-extracting'),
   aiInstallComplete: makeEvent<AiInstallComplete>('ai-install-complete'),
   aiInstalling: makeEvent<AiInstalling>('ai-installing'),
+  aiIntegrityFailed: makeEvent<AiIntegrityFailed>('ai-integrity-failed'),
   aiServerReady: makeEvent<AiServerReady>('ai-server-ready'),
   aiStarting: makeEvent<AiStarting>('ai-starting'),
   aiVerifying: makeEvent<AiVerifying>('ai-verifying'),
@@ -3171,6 +3566,7 @@ export const events = {
   closeAllFileViewers: makeEvent<CloseAllFileViewers>('close-all-file-viewers'),
   closeConfirmation: makeEvent<CloseConfirmation>('close-confirmation'),
   closeFileViewer: makeEvent<CloseFileViewer>('close-file-viewer'),
+  cloudMaterializeProgress: makeEvent<CloudMaterializeProgressEvent>('cloud-materialize-progress'),
   directoryDeleted: makeEvent<DirectoryDeletedEvent>('directory-deleted'),
   directoryDiff: makeEvent<DirectoryDiff>('directory-diff'),
   downloadDetected: makeEvent<DownloadDetectedEvent>('download-detected'),
@@ -3219,6 +3615,7 @@ export const events = {
   mtpPermissionError: makeEvent<MtpPermissionError>('mtp-permission-error'),
   mtpPtpcameradRestored: makeEvent<MtpPtpcameradRestored>('mtp-ptpcamerad-restored'),
   mtpPtpcameradSuppressed: makeEvent<MtpPtpcameradSuppressed>('mtp-ptpcamerad-suppressed'),
+  mtpStorageMoveProgress: makeEvent<MtpStorageMoveProgress>('mtp-storage-move-progress'),
   mtpStorageRemoved: makeEvent<MtpStorageRemoved>('mtp-storage-removed'),
   networkDiscoveryStateChanged: makeEvent<NetworkDiscoveryStateChanged>('network-discovery-state-changed'),
   networkHostContextAction: makeEvent<NetworkHostContextAction>('network-host-context-action'),
@@ -3232,8 +3629,11 @@ export const events = {
   quickLookClosed: makeEvent<QuickLookClosed>('quick-look-closed'),
   quickLookKey: makeEvent<QuickLookKeyEvent>('quick-look-key'),
   reduceTransparencyChanged: makeEvent<ReduceTransparencyChanged>('reduce-transparency-changed'),
+  reindexComplete: makeEvent<ReindexCompleteEvent>('reindex-complete'),
+  reindexProgress: makeEvent<ReindexProgressEvent>('reindex-progress'),
   restrictedPathsChanged: makeEvent<RestrictedPathsChangedPayload>('restricted-paths-changed'),
   scanConflict: makeEvent<ConflictInfo>('scan-conflict'),
+  scanPermissionWarning: makeEvent<ScanPermissionWarningEvent>('scan-permission-warning'),
   scanPreviewCancelled: makeEvent<ScanPreviewCancelledEvent>('scan-preview-cancelled'),
   scanPreviewComplete: makeEvent<ScanPreviewCompleteEvent>('scan-preview-complete'),
   scanPreviewError: makeEvent<ScanPreviewErrorEvent>('scan-preview-error'),
@@ -3247,6 +3647,7 @@ export const events = {
   viewModeChanged: makeEvent<ViewModeChanged>('view-mode-changed'),
   viewerWordWrapToggled: makeEvent<ViewerWordWrapToggled>('viewer-word-wrap-toggled'),
   volumeContextAction: makeEvent<VolumeContextAction>('volume-context-action'),
+  volumeEjected: makeEvent<VolumeEjected>('volume-ejected'),
   volumeMounted: makeEvent<VolumeMounted>('volume-mounted'),
   volumeSpaceChanged: makeEvent<VolumeSpaceChanged>('volume-space-changed'),
   volumeUnmounted: makeEvent<VolumeUnmounted>('volume-unmounted'),
@@ -3256,7 +3657,9 @@ export const events = {
   writeComplete: makeEvent<WriteCompleteEvent>('write-complete'),
   writeConflict: makeEvent<WriteConflictEvent>('write-conflict'),
   writeError: makeEvent<WriteErrorEvent>('write-error'),
+  writePaused: makeEvent<WritePausedEvent>('write-paused'),
   writeProgress: makeEvent<WriteProgressEvent>('write-progress'),
+  writeRenamed: makeEvent<WriteRenamedEvent>('write-renamed'),
   writeSettled: makeEvent<WriteSettledEvent>('write-settled'),
   writeSourceItemDone: makeEvent<WriteSourceItemDoneEvent>('write-source-item-done'),
 }
@@ -3337,6 +3740,9 @@ export type AiInstallComplete = null
 
 export type AiInstalling = null
 
+// A downloaded model's SHA-256 didn't match; the file was deleted.
+export type AiIntegrityFailed = null
+
 // Model info returned to frontend.
 export type AiModelInfo = {
   id: string
@@ -3500,7 +3906,14 @@ export type BackendCapabilities = {
 }
 
 // Which backend strategy is active for a session.
-export type BackendType = 'fullLoad' | 'byteSeek' | 'lineIndex'
+export type BackendType =
+  | 'fullLoad'
+  | 'byteSeek'
+  | 'lineIndex'
+  /**
+   *  Hex dump rows (`HexBackend`). Binary files open in it; never upgraded.
+   */
+  | 'hex'
 
 /**
  *  The signup outcome, returned across IPC so the frontend reacts on a typed `kind` discriminant
@@ -3635,6 +4048,16 @@ export type CloseFileViewer = {
   path: string | null
 }
 
+/**
+ *  Progress of a running `materialize_file`, at most every 200 ms. `bytes_done` is how much
+ *  the provider has written so far, so it's approximate.
+ */
+export type CloudMaterializeProgressEvent = {
+  path: string
+  bytesDone: number
+  bytesTotal: number
+}
+
 /**
  *  Estimated compressed output size for a Compress operation, split by
  *  compressibility class so the frontend can re-scale to the selected deflate
@@ -3645,6 +4068,32 @@ export type CloseFileViewer = {
  *  `compress_estimate::CompressEstimator`; see
  *  `docs/notes/compress-size-estimate-spike.md` for the accuracy evidence.
  */
+/**
+ *  A column the file list can show.
+ */
+export type ColumnId = 'name' | 'extension' | 'size' | 'modified' | 'created'
+
+export type ColumnSetting = {
+  id: ColumnId
+  // Width in CSS pixels, or `None` to shrink-wrap to the content (the default).
+  width: number | null
+}
+
+/**
+ *  Why a column set was refused.
+ */
+export type ColumnsError =
+  /**
+   *  The Name column can't be hidden: it's the row's click and rename target.
+   */
+  | { kind: 'missingName' }
+  | { kind: 'duplicate'; id: ColumnId }
+
+/**
+ *  Index DB size around a full compaction, in bytes of the main file.
+ */
+export type CompactStats = { bytesBefore: number; bytesAfter: number }
+
 export type CompressedSizeEstimate = {
   // Estimated level-6 bytes for files whose sampled ratio is < 0.35.
   compressibleBytes: number
@@ -3929,6 +4378,23 @@ export type DedupCluster = {
   paths: string[]
 }
 
+/**
+ *  What the frontend should do with the entry.
+ */
+export type DefaultAction =
+  // Step into the folder (or bundle).
+  | { kind: 'navigate' }
+  // Step into the archive as a read-only folder.
+  | { kind: 'browseArchive' }
+  // Let the user pick Browse or Open (the archive/bundle popup).
+  | { kind: 'ask' }
+  // Open the file in the built-in viewer.
+  | { kind: 'openInViewer' }
+  // Hand the entry to its default app.
+  | { kind: 'openWithDefaultApp' }
+  // Open the entry with the app the user chose for its extension.
+  | { kind: 'openWithApp'; bundleId: string; appPath: string }
+
 export type DfsCacheEntryDto = {
   path_prefix: string
   target_count: number
@@ -3966,6 +4432,22 @@ export type DiffChange = {
   index: number
 }
 
+// A source folder and a destination folder, each on its volume.
+export type DirPair = {
+  sourceVolumeId: string
+  sourceDir: string
+  destinationVolumeId: string
+  destinationDir: string
+}
+
+// A remembered policy, as stored and as returned to the frontend.
+export type DirPairPolicy = {
+  pair: DirPair
+  policy: ConflictResolution
+  // Unix epoch milliseconds of when the policy was last set.
+  updatedAt: number
+}
+
 /**
  *  Dir stats keyed by path string. Used at the IPC boundary and by
  *  the IPC boundary (frontend expects path-keyed dir stats).
@@ -4144,7 +4626,7 @@ export type EncodingChoice = {
 }
 
 // Coarse grouping for the encoding dropdown's `<optgroup>` split.
-export type EncodingGroup = 'unicode' | 'western'
+export type EncodingGroup = 'unicode' | 'western' | 'japanese'
 
 /**
  *  Returned by `viewer_get_encoding_options`: current selection, detected encoding, and
@@ -4259,6 +4741,7 @@ export type FileEncoding =
   | 'usAscii'
   | 'utf16Le'
   | 'utf16Be'
+  | 'shiftJis'
 
 /**
  *  Represents a file or directory entry with extended metadata.
@@ -4304,6 +4787,12 @@ export type FileEntry = {
   addedAt: number | null
   // When the file was last opened (macOS only)
   openedAt: number | null
+  /**
+   *  When the photo was taken (EXIF `DateTimeOriginal`), for image files only. Filled
+   *  by the extended-metadata load and, on demand, for the Captured sort. `None` for
+   *  non-images, images without an EXIF date, and entries not enriched yet.
+   */
+  capturedAt: number | null
   permissions: number
   owner: string
   group: string
@@ -4377,6 +4866,20 @@ export type FileEntry = {
    *  `origin/main`". Doubles as the aria-label for screen readers.
    */
   displaySizeTooltip: string | null
+  /**
+   *  Whether the repo's ignore rules (`.gitignore`, `.git/info/exclude`, global excludes)
+   *  match this entry. Only computed for local listings with the
+   *  `fileExplorer.git.ignoredFiles` setting on; `false` otherwise. The frontend greys these
+   *  rows out; in `Hide` mode the backend leaves them out instead. See `git_ignore.rs`.
+   */
+  isGitIgnored: boolean
+  /**
+   *  Cloud state read from the stat flags during listing: `OnlineOnly` for a cloud
+   *  placeholder (iCloud Drive, Dropbox, Google Drive), `None` otherwise. Telling synced
+   *  from uploading or downloading takes an XPC call per file, so that stays with the
+   *  visible-range `get_sync_status`. Always `None` off macOS. See `sync_status.rs`.
+   */
+  syncStatus: SyncStatus | null
 }
 
 /**
@@ -4458,6 +4961,24 @@ export type FilesystemKind =
    */
   | 'other'
 
+// How to flatten. Every field has a default, so the frontend can send `{}`.
+export type FlatListingOptions = {
+  /**
+   *  Levels below the root to include: 1 is the root's children only. 0 picks
+   *  [`DEFAULT_MAX_DEPTH`]; anything above 64 is clamped.
+   */
+  maxDepth?: number
+  /**
+   *  Gitignore-style patterns (`node_modules/`, `*.log`, `build/**`) matched against paths
+   *  relative to the root. An excluded folder isn't descended into.
+   */
+  excludeGlobs?: string[]
+  // Whether dotfiles and dot-folders (and everything below them) are included.
+  includeHidden?: boolean
+  // Whether folders get a row of their own. Off by default: a branch view lists files.
+  includeDirectories?: boolean
+}
+
 // `focus-about`: ensure the (soft, main-window-overlay) about dialog is visible.
 export type FocusAbout = null
 
@@ -4492,6 +5013,15 @@ export type FolderCoverage = {
   accounted: number
 }
 
+// A staged plan as the preview shows it.
+export type FolderPlan = { planId: string; actions: PlannedAction[] }
+
+/**
+ *  What `apply_folder_plan` got through. Moves are queued write operations that finish in the
+ *  background; `error` is set when a step failed and the rest were skipped.
+ */
+export type FolderPlanOutcome = { applied: number; moveOperationIds: string[]; error: string | null }
+
 /**
  *  One volume's index freshness. Carried by a `Running` index instance; gray /
  *  not-indexed is the absence of an instance, not a variant here (see module
@@ -4567,6 +5097,15 @@ export type FrontendLogEntry = {
  *  `RepoInfo` snapshot. The `…Payload` suffix wouldn't kebab-case to the existing
  *  wire string, so the name is pinned via `event_name`.
  */
+// How listings treat entries the repo's ignore rules match.
+export type GitIgnoredMode =
+  // Don't read ignore files at all. `is_git_ignored` stays `false`.
+  | 'off'
+  // Flag ignored entries so the frontend can grey them out.
+  | 'dim'
+  // Flag ignored entries and leave them out of the listing.
+  | 'hide'
+
 export type GitStateChangedPayload = {
   repoRoot: string
   info: RepoInfo
@@ -4621,19 +5160,27 @@ export type GoToLatestError =
  *  side. Enforced by the `ipc-enum-camelcase` check.
  */
 export type GoToPathResolution =
-  // The resolved path is an existing directory. Navigate into it.
-  | { kind: 'directory'; path: string }
+  /**
+   *  The resolved path is an existing directory. Navigate into it. `volume_id` is
+   *  the registered volume containing it (`None` if the registry doesn't know one).
+   */
+  | { kind: 'directory'; path: string; volumeId: string | null }
   /**
    *  The resolved path is an existing file. `path` is the canonical normalized
    *  full path (recorded into recents); `parent_dir` / `file_name` drive the
    *  navigate-to-parent + select.
    */
-  | { kind: 'file'; path: string; parentDir: string; fileName: string }
+  | { kind: 'file'; path: string; parentDir: string; fileName: string; volumeId: string | null }
   /**
    *  The resolved path doesn't exist. `ancestor_dir` is the nearest existing
    *  ancestor (worst case `/`). Navigate there and fire an INFO toast.
    */
   | { kind: 'nearestAncestor'; requested: string; ancestorDir: string }
+  /**
+   *  The path is on a volume that isn't mounted (`mount_point` is missing or a
+   *  leftover empty folder). Nothing navigates; the dialog says so.
+   */
+  | { kind: 'notMounted'; requested: string; mountPoint: string }
   // Defensive: the input was empty or couldn't be turned into a path.
   | { kind: 'invalid'; reason: string }
 
@@ -4669,6 +5216,9 @@ export type HistoryFilters = {
 // Search modes recorded in history. Mirrors the frontend `SearchMode` union.
 export type HistoryMode = 'ai' | 'filename' | 'regex'
 
+// Which pane a history command targets.
+export type HistoryPane = 'left' | 'right'
+
 // Whether a host was discovered via mDNS or added manually by the user.
 export type HostSource = 'discovered' | 'manual'
 
@@ -4866,6 +5416,15 @@ export type IndexScanProgressEvent = {
    *  numerator (apples-to-apples with `volume_used_bytes`).
    */
   bytesScanned: number
+  /**
+   *  Entries scanned per second since the previous progress event.
+   */
+  entriesPerSec: number
+  /**
+   *  `true` while the user's scan throttle (rate cap, or AC-only mode on battery) is
+   *  holding the scan back, so the UI can say it's indexing slowly on purpose.
+   */
+  throttled: boolean
 }
 
 export type IndexScanStartedEvent = {
@@ -5058,6 +5617,23 @@ export type LineChunk = {
   totalBytes: number
 }
 
+// Listing cache memory, for `get_listing_cache_stats`.
+export type ListingCacheStats = {
+  /**
+   *  Listings currently held in memory.
+   */
+  listingCount: number
+  /**
+   *  Listings evicted and waiting to be re-listed on their next access.
+   */
+  evictedCount: number
+  /**
+   *  Approximate bytes held by all in-memory listings.
+   */
+  approxBytes: number
+  capBytes: number
+}
+
 // Cancelled event payload
 export type ListingCancelledEvent = {
   listingId: string
@@ -5400,6 +5976,14 @@ export type ManualConnectResult = {
  */
 export type McpSettingsClose = null
 
+/**
+ *  What [`materialize_file`] did.
+ */
+export type MaterializeOutcome =
+  // The file already had its content locally; nothing was downloaded.
+  | 'already_local'
+  | 'downloaded'
+
 // Image pixel dimensions, read header-only at open time.
 export type MediaDimensions = {
   width: number
@@ -5692,6 +6276,35 @@ export type MtpConnectionError =
   | { type: 'sessionReset'; device_id: string }
   | { type: 'other'; device_id: string; message: string }
 
+/**
+ *  Which operations a connected MTP device advertises, so the UI can disable an action the
+ *  device would refuse (for example, Move on a camera without `MoveObject`) instead of failing it.
+ *
+ *  Derived once at connect from the device's `DeviceInfo` operation list. Device-wide: a storage
+ *  can still be read-only (`MtpStorageInfo::is_read_only`) on a device that supports writes.
+ *
+ *  Only serialized (Rust → frontend).
+ */
+export type MtpDeviceCapabilities = {
+  // `SendObjectInfo` + `SendObject`: copying files onto the device.
+  canUpload: boolean
+  // `DeleteObject`.
+  canDelete: boolean
+  // `SetObjectPropValue` (renames set the file name property).
+  canRename: boolean
+  // `MoveObject`. Without it, a move between folders is a copy + delete.
+  canMove: boolean
+  // `SendObjectInfo` with an association (folder) object.
+  canCreateFolder: boolean
+  // `GetThumb`.
+  canReadThumbnails: boolean
+  /**
+   *  `GetPartialObject64` or `GetPartialObject`: bounded-window reads, resuming a copy, and
+   *  browsing archives without downloading them whole.
+   */
+  canReadPartial: boolean
+}
+
 /**
  *  Emitted when an MTP device connects, or when a late-arriving storage is
  *  registered on an already-connected device (in which case `device_name` is
@@ -5830,12 +6443,37 @@ export type MtpStorageInfo = {
   isReadOnly: boolean
 }
 
+// Emitted after each file a copy-based move between two storages of a device puts on the
+// destination. `bytes_total` is `0` when the source couldn't be sized up front.
+export type MtpStorageMoveProgress = {
+  deviceId: string
+  fromStorageId: number
+  toStorageId: number
+  bytesDone: number
+  bytesTotal: number
+}
+
 // Emitted when a storage area is removed from a connected device.
 export type MtpStorageRemoved = {
   deviceId: string
   storageId: number
 }
 
+// One visited location.
+export type NavigationEntry = {
+  volumeId: string
+  path: string
+}
+
+/**
+ *  A pane's history: oldest entry first, `current_index` points at the location shown now.
+ *  An empty stack has `current_index` 0.
+ */
+export type NavigationHistory = {
+  entries: NavigationEntry[]
+  currentIndex: number
+}
+
 export type NegotiatedSummaryDto = {
   dialect: string
   max_read_size: number
@@ -6092,6 +6730,32 @@ export type OversizedFile = {
   size: number
 }
 
+/**
+ *  One palette row. Disabled actions are still listed so the palette can show
+ *  them greyed out rather than making them vanish.
+ */
+export type PaletteAction = { id: string; label: string; keywords: string[]; enabled: boolean }
+
+/**
+ *  The focused pane's state the enabled predicates read. The frontend sends it;
+ *  the clipboard part is filled in by the command, never trusted from the caller.
+ */
+export type PaletteContext = {
+  selectedCount: number
+  // The cursor is on a real entry, not on `..` or an empty listing.
+  cursorOnEntry: boolean
+  canGoBack: boolean
+  canGoForward: boolean
+  hasParent: boolean
+  tabCount: number
+}
+
+// Why `invoke_palette_action` didn't dispatch.
+export type PaletteInvokeError =
+  | { kind: 'unknownAction'; id: string }
+  // The main window is gone (or the emit failed), so nothing can run it.
+  | { kind: 'dispatchFailed'; message: string }
+
 // Represents a file entry in a pane (simplified subset of the main FileEntry).
 export type PaneFileEntry = {
   name: string
@@ -6171,6 +6835,14 @@ export type PastedClipboardFile = {
  */
 export type PastedKind = 'text' | 'image' | 'pdf'
 
+// One as-you-type suggestion: a child of the folder the input points into.
+export type PathCompletion = {
+  name: string
+  // The full normalized path, so accepting a suggestion is one `resolve`.
+  path: string
+  isDirectory: boolean
+}
+
 export type PathLimits = {
   maxNameBytes: number
   maxPathBytes: number
@@ -6188,6 +6860,49 @@ export type PathVolumeResolution = {
 
 export type PatternType = 'glob' | 'regex'
 
+/**
+ *  What the peek pane should render. Tagged by `kind` so the frontend can switch on it.
+ */
+export type PeekResult =
+  | {
+      kind: 'text'
+      text: string
+      encoding: FileEncoding
+      /**
+       *  File size in bytes.
+       */
+      size: number
+      /**
+       *  `true` when the file is longer than the preview.
+       */
+      truncated: boolean
+    }
+  | { kind: 'image'; mediaToken: string; mime: string; size: number }
+  | {
+      kind: 'binary'
+      /**
+       *  Classic `offset  hex  |ascii|` rows for the first bytes of the file.
+       */
+      hexDump: string
+      size: number
+    }
+  | {
+      kind: 'directory'
+      /**
+       *  Immediate children (files and folders), or `None` when the folder can't be read.
+       */
+      entryCount: number | null
+      /**
+       *  Recursive logical size from the drive index, or `None` when the index doesn't
+       *  cover the folder. Never computed by walking.
+       */
+      totalSize: number | null
+      /**
+       *  `false` when `total_size` is a lower bound (the index hasn't finished the subtree).
+       */
+      totalSizeComplete: boolean
+    }
+
 /**
  *  `persist-restricted-setting`: the viewer (a restricted-capability window with
  *  no store access) forwards an allowlisted setting write to the main window,
@@ -6215,6 +6930,18 @@ export type PhaseRecord = {
   stats: [string, string][]
 }
 
+// One step of a confirmed plan. Names are entries directly inside the plan's folder.
+export type PlannedAction =
+  // Create an empty folder.
+  | { kind: 'createFolder'; name: string }
+  /**
+   *  Move these entries into `destination`, an existing folder or one the plan creates
+   *  earlier. A pattern in the model's output is resolved to names when the plan is parsed.
+   */
+  | { kind: 'move'; sources: string[]; destination: string }
+  // Rename an entry in place.
+  | { kind: 'rename'; from: string; to: string }
+
 export type PrepareResult = {
   ready: boolean
   entryCount: number
@@ -6402,6 +7129,41 @@ export type RegistrationStatus =
    */
   | 'notRegistered'
 
+/**
+ *  The end of a folder re-index: exactly one per `reindex_subtree`. `result` is `null` when
+ *  the re-index couldn't run, with the reason in `error`.
+ */
+export type ReindexCompleteEvent = {
+  operationId: string
+  result: ReindexResult | null
+  error: string | null
+}
+
+// Running or final counts of a re-index.
+export type ReindexProgress = {
+  dirsListed: number
+  // Dirs found but not listed yet.
+  dirsQueued: number
+  // Index rows added, removed, and updated so far.
+  added: number
+  removed: number
+  updated: number
+}
+
+// Running counts of a folder re-index, at most every 200 ms.
+export type ReindexProgressEvent = {
+  operationId: string
+  progress: ReindexProgress
+}
+
+export type ReindexResult = {
+  progress: ReindexProgress
+  // `true` when the walk stopped early.
+  cancelled: boolean
+  // `true` when a cancelled walk left dirs to resume from on the next re-index of this folder.
+  resumable: boolean
+}
+
 // Result of a rename validity check.
 export type RenameValidityResult = {
   // Whether the new name is valid (passes filename validation).
@@ -6536,6 +7298,29 @@ export type RestrictedWindowSettings = {
  *  of [`ExecutionStatus`]. `RollingBack` is the transient in-flight guard
  *  (rollback); a fresh op sits at `NotRollbackable` until finalize proves otherwise.
  */
+/**
+ *  Why a rollback request is refused at the operation level (before any item
+ *  runs). Typed across IPC/MCP — never a message string (`no-string-matching`).
+ */
+export type RollbackRefusal =
+  // No operation with this id in the journal.
+  | { kind: 'unknownOperation' }
+  // The op is already being rolled back — the double-rollback guard (Finding 7).
+  | { kind: 'alreadyRollingBack' }
+  // The op was already fully reversed; there's nothing to undo.
+  | { kind: 'alreadyRolledBack' }
+  /**
+   *  The op is not rollbackable; carries the stored reason (delete, overwrote,
+   *  archive-overwrite, zip-edit-unsupported, journal-incomplete).
+   */
+  | { kind: 'notRollbackable'; detail: NotRollbackableReason }
+  /**
+   *  A volume the rollback needs isn't currently connected. Computed at rollback
+   *  time from mount state, never stored (D3); names the missing volume so the
+   *  UI/agent can say "Volume 'Backup' is not connected".
+   */
+  | { kind: 'volumeUnavailable'; detail: { volumeId: string } }
+
 export type RollbackState = 'notRollbackable' | 'rollbackable' | 'rollingBack' | 'rolledBack' | 'partiallyRolledBack'
 
 /**
@@ -6634,6 +7419,19 @@ export type ScanPreviewProgressEvent = {
   expectedBytesTotal?: number | null
 }
 
+/**
+ *  Emitted once per app session, after the first local scan that had to skip
+ *  directories with permission denied. Tells the user the index is incomplete, so
+ *  the frontend can offer Full Disk Access (see `permissions.rs`).
+ */
+export type ScanPermissionWarningEvent = {
+  volumeId: string
+  // How many directories the scan couldn't read.
+  skippedCount: number
+  // The first few skipped paths (sorted), at most [`PERMISSION_WARNING_SAMPLE_LEN`].
+  samplePaths: string[]
+}
+
 // Result of starting a scan preview.
 export type ScanPreviewStartResult = {
   previewId: string
@@ -6675,6 +7473,20 @@ export type ScanProgressEvent = {
   currentPath: string | null
 }
 
+/**
+ *  The throttle knobs, as the frontend sets them.
+ */
+export type ScanThrottleSettings = {
+  /**
+   *  Most entries the scan inserts per second. `0` means no limit.
+   */
+  maxEntriesPerSec: number
+  /**
+   *  Pause the scan while the machine runs on battery.
+   */
+  onlyOnAcPower: boolean
+}
+
 /**
  *  Whether the journal holds every leaf of the operation (search honesty,
  *  D-granularity). `Full` requires the drive index to have been present AND
@@ -6919,6 +7731,16 @@ export type SendResult = {
   id: string
 }
 
+/**
+ *  The whole session. `left` / `right` are `None` when there's nothing to save
+ *  (pane never reported) or, on restore, when the pane's location is unavailable.
+ */
+export type Session = {
+  focusedPane: string
+  left: SessionPane | null
+  right: SessionPane | null
+}
+
 /**
  *  `drag-out-session-complete`: the session drained (gesture ended AND no
  *  in-flight fulfillment). The FE replaces the in-progress toast with a
@@ -6944,6 +7766,20 @@ export type SessionDiagnosticsDto = {
   signing_algorithm: string
 }
 
+// One pane, as persisted.
+export type SessionPane = {
+  // `None` means the default local volume.
+  volumeId: string | null
+  path: string
+  sortField: string
+  sortOrder: string
+  viewMode: string
+  showHidden: boolean
+  // Name of the entry under the cursor, if any. The frontend re-finds it by name.
+  scrollAnchor: string | null
+  tabs: SessionTab[]
+}
+
 /**
  *  `drag-out-session-started`: the FE raises a signs-of-life in-progress toast
  *  when the FIRST fulfillment of a drag-out-to-Finder session begins (macOS,
@@ -6962,6 +7798,13 @@ export type SessionStartedEvent = {
   totalItems: number
 }
 
+// One tab of a pane, as persisted.
+export type SessionTab = {
+  path: string
+  pinned: boolean
+  active: boolean
+}
+
 /**
  *  Settings registry default values pushed from FE. The wire format matches JSON
  *  primitives via `#[serde(untagged)]`; TS sees `boolean | number | string`.
@@ -7122,7 +7965,7 @@ export type SmbVolumeRef = {
 }
 
 // Column to sort files by.
-export type SortColumn = 'name' | 'extension' | 'size' | 'modified' | 'created'
+export type SortColumn = 'name' | 'extension' | 'size' | 'modified' | 'created' | 'captured'
 
 // Sort order (ascending or descending).
 export type SortOrder = 'ascending' | 'descending'
@@ -7330,6 +8173,25 @@ export type TranslatedQuery = {
   excludeSystemDirs: boolean | null
 }
 
+// Which subfolders a tree level shows. Both fields default, so the frontend can send `{}`.
+export type TreeChildrenOptions = {
+  // Whether dot-folders are shown (and count toward `has_children`).
+  includeHidden?: boolean
+  /**
+   *  Gitignore-style patterns (`node_modules`, `/Volumes`) matched against paths relative
+   *  to the volume root. An excluded folder is neither shown nor counted.
+   */
+  excludeGlobs?: string[]
+}
+
+// One subfolder in the sidebar tree.
+export type TreeNode = {
+  name: string
+  path: string
+  // Whether the folder has at least one visible subfolder, so the node can expand.
+  hasChildren: boolean
+}
+
 /**
  *  Snapshot of a pane's type-to-jump state for MCP exposure.
  *
@@ -7359,6 +8221,50 @@ export type TypeToJumpInfo = {
 }
 
 // Update metadata returned to the frontend when a newer version is available.
+// A dispatched undo or redo. The reversal runs as a managed op, like any rollback.
+export type UndoDispatch = { kind: OpKind; inverseOpId: string }
+
+// One step on the undo or redo stack.
+export type UndoEntry = {
+  /**
+   *  The journaled op to roll back: the original op on the undo stack, the
+   *  inverse op on the redo stack.
+   */
+  opId: string
+  // The kind of the user's original op, for "Undo Move" / "Redo Rename" labels.
+  kind: OpKind
+  /**
+   *  `false` for a copy or permanent delete: shown so the user knows why undo
+   *  stops there, never rolled back.
+   */
+  undoable: boolean
+  /**
+   *  The op (an inverse, after an undo or redo) hasn't finalized yet, so the
+   *  journal can't roll it back until it does.
+   */
+  running: boolean
+}
+
+// Why an undo or redo didn't run. Typed across IPC like [`RollbackRefusal`].
+export type UndoRefusal =
+  | { kind: 'nothingToUndo' }
+  | { kind: 'nothingToRedo' }
+  /**
+   *  The last op was a copy or a permanent delete. Its marker is dropped, so the
+   *  next undo reaches the op before it (if a delete didn't clear the stack).
+   */
+  | { kind: 'notUndoable'; detail: { opKind: OpKind } }
+  // The previous undo or redo hasn't finished; the entry stays for a retry.
+  | { kind: 'stillRunning' }
+  /**
+   *  The rollback engine refused. A volume that's away or a rollback in flight
+   *  keeps the entry for a retry; anything else drops it.
+   */
+  | { kind: 'rollback'; detail: RollbackRefusal }
+
+// The top of both stacks, for menu labels.
+export type UndoState = { nextUndo: UndoEntry | null; nextRedo: UndoEntry | null }
+
 export type UpdateInfo = {
   version: string
   url: string
@@ -7422,6 +8328,11 @@ export type VerifyResult = {
   shortCode: string | null
 }
 
+/**
+ *  View mode type that matches the frontend type.
+ */
+export type ViewMode = 'full' | 'brief'
+
 /**
  *  `view-mode-changed`: a per-pane view-mode CheckMenuItem (Full / Brief)
  *  flipped from the native menu. Carries the target pane so the FE updates that
@@ -7481,6 +8392,12 @@ export type ViewerError =
    */
   | { kind: 'archive'; message: string }
 
+/**
+ *  How `viewer_open` should present the file. `Auto` picks from the content: media
+ *  inline, binary (a NUL in the head) as hex, everything else as text.
+ */
+export type ViewerOpenMode = 'auto' | 'text' | 'hex'
+
 // Result returned when opening a viewer session.
 export type ViewerOpenResult = {
   sessionId: string
@@ -7520,6 +8437,15 @@ export type ViewerSessionStatus = {
   backendType: BackendType
   isIndexing: boolean
   totalLines: number | null
+  /**
+   *  Encoding the lines are decoded with: the detected one unless the user picked
+   *  another via `viewer_set_encoding`.
+   */
+  encoding: FileEncoding
+  /**
+   *  What auto-detection chose at open time.
+   */
+  detectedEncoding: FileEncoding
 }
 
 /**
@@ -7644,6 +8570,15 @@ export type VolumeIndexStatus = {
   nextSweepDueAt: number | null
 }
 
+/**
+ *  Typed `volume-ejected` Tauri event. Emitted once `eject_volume` finished the
+ *  unmount or disconnect it asked for. `volume-unmounted` still follows from the
+ *  OS watcher for disk volumes; this one says the eject was ours and succeeded.
+ */
+export type VolumeEjected = {
+  volumeId: string
+}
+
 /**
  *  Typed `volume-mounted` Tauri event (per-volume, carries the mount path).
  *  Emitted by both the macOS (`NSWorkspace`) and Linux (`/proc/mounts` + GVFS)
@@ -7720,6 +8655,39 @@ export type WatcherGateError =
    */
   { kind: 'watcherStartFailed'; message: string }
 
+// How one listing's file-system events were coalesced, for diagnosing flicker reports.
+export type WatcherListingStats = {
+  listingId: string
+  path: string
+  /**
+   *  File-system events the debouncer delivered. It already merges repeats per path, so a
+   *  burst of writes to one file counts once per window.
+   */
+  eventsReceived: number
+  // Debounce windows that fired with events.
+  batches: number
+  // Batches handled by re-reading the whole folder (too many or ambiguous events).
+  fullRereads: number
+  // `directory-diff` events sent to the frontend, after the diff emitter's own coalescing.
+  diffsEmitted: number
+  // Changes those diffs carried in total.
+  changesEmitted: number
+  /**
+   *  The debounce this listing's watcher runs with. `None` when the volume watches through its
+   *  own backend (direct SMB, MTP), which only the diff counters cover.
+   */
+  debounceMs: number | null
+}
+
+// Which debounce setting a watcher follows.
+export type WatcherVolumeKind =
+  // Local disks: the FSEvents / inotify watcher in this module.
+  | 'local'
+  // OS-mounted network shares (same watcher as local) and direct SMB volumes (`smb_watcher`).
+  | 'network'
+  // MTP devices (the device event loop's debouncer).
+  | 'mtp'
+
 // One released version's user-facing notes.
 export type WhatsNewRelease = {
   // Semver string, for example `"0.26.0"`.
@@ -7823,6 +8791,8 @@ export type WriteConflictEvent = {
 export type WriteErrorEvent = {
   operationId: string
   operationType: WriteOperationType
+  // Stable code for `error` (`WriteOperationError::code`), like `"DISK_FULL"`.
+  code: string
   error: WriteOperationError
 }
 
@@ -7847,6 +8817,18 @@ export type WriteOperationConfig = {
    *  `VolumeCopyConfig::pre_known_conflicts` for the full rationale.
    */
   preKnownConflicts?: string[]
+  /**
+   *  Rewrite destination names the target filesystem would reject (FAT32,
+   *  exFAT, NTFS, SMB) instead of failing on them. Each rename is reported via
+   *  `write-renamed`. Local copy only.
+   */
+  sanitizeNames?: boolean
+  /**
+   *  Re-read each copied file and compare its BLAKE3 hash with the source's. A
+   *  mismatch removes the copy and fails with `verification_failed`. Local copy
+   *  only.
+   */
+  verify?: boolean
 }
 
 // Errors that can occur during write operations.
@@ -7910,6 +8892,9 @@ export type WriteOperationError =
    *  `set_archive_password` and retries the operation.
    */
   | { type: 'archive_needs_password'; path: string; wrongAttempt: boolean }
+  // The copy's bytes didn't match the source's when read back (`verify` on).
+  // `path` is the destination file, already removed.
+  | { type: 'verification_failed'; path: string }
   // Catch-all for genuinely unexpected IO errors.
   | { type: 'io_error'; path: string; message: string }
 
@@ -7917,6 +8902,8 @@ export type WriteOperationError =
 export type WriteOperationPhase =
   // Scanning source files to calculate total size
   | 'scanning'
+  // Downloading online-only cloud sources before copying them (see `cloud_materialize.rs`)
+  | 'materializing'
   // Copying files (for copy and cross-filesystem move)
   | 'copying'
   // Deleting files (for delete, and cleanup phase of cross-filesystem move)
@@ -7965,6 +8952,17 @@ export type WriteOperationType =
   | 'create_file'
   | 'archive_edit'
 
+/**
+ *  Emitted when an operation is paused or resumed (`pause_operation` /
+ *  `resume_operation`). `paused_ms` is the total time it has spent paused so
+ *  far; the ETA in `write-progress` already leaves that time out.
+ */
+export type WritePausedEvent = {
+  operationId: string
+  paused: boolean
+  pausedMs: number
+}
+
 /**
  *  Progress event payload for write operations.
  *
@@ -8015,6 +9013,18 @@ export type WriteProgressEvent = {
   expectedBytesTotal?: number | null
 }
 
+/**
+ *  Emitted once per destination path component that `sanitize_names` rewrote,
+ *  so the FE can tell the user which names changed on the way.
+ */
+export type WriteRenamedEvent = {
+  operationId: string
+  // The source file or folder whose name was rewritten.
+  sourcePath: string
+  originalName: string
+  newName: string
+}
+
 /**
  *  Settled event payload. Emitted exactly once per write operation, after the
  *  spawned background task has fully returned (success, error, cancelled, or
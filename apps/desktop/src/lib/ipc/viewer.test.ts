@@ -53,27 +53,37 @@ const openResult: ViewerOpenResult = {
 }
 
 describe('commands.viewerOpen', () => {
-  it('invokes viewer_open with the path, volume-id, and window-label positional args', async () => {
+  it('invokes viewer_open with the path, volume-id, window-label, and mode positional args', async () => {
     const ipc = installIpcMock()
     ipc.mock('viewer_open', () => openResult)
 
-    const result = await commands.viewerOpen('/path/to/README.md', 'root', 'viewer-123')
+    const result = await commands.viewerOpen('/path/to/README.md', 'root', 'viewer-123', null)
 
     expect(result).toEqual({ status: 'ok', data: openResult })
     expect(ipc.lastCall('viewer_open')?.payload).toEqual({
       path: '/path/to/README.md',
       volumeId: 'root',
       windowLabel: 'viewer-123',
+      mode: null,
     })
   })
 
+  it('passes a forced hex mode through', async () => {
+    const ipc = installIpcMock()
+    ipc.mock('viewer_open', () => openResult)
+
+    await commands.viewerOpen('/path/to/app.bin', 'root', 'viewer-123', 'hex')
+
+    expect(ipc.lastCall('viewer_open')?.payload).toMatchObject({ mode: 'hex' })
+  })
+
   it('surfaces IpcError on the error branch (timedOut: false for non-blocking errors)', async () => {
     const ipc = installIpcMock()
     ipc.mock('viewer_open', () => {
       throw { message: 'File not found', timedOut: false }
     })
 
-    const result = await commands.viewerOpen('/nope.txt', 'root', 'viewer-123')
+    const result = await commands.viewerOpen('/nope.txt', 'root', 'viewer-123', null)
 
     expect(result.status).toBe('error')
     if (result.status === 'error') {
@@ -12,6 +12,7 @@
  */
 
 import { describe, it, expect, vi, beforeEach } from 'vitest'
+import type { AiServerConfig } from '$lib/tauri-commands'
 
 const saveAiApiKey = vi.fn<(id: string, key: string) => Promise<null>>(() => Promise.resolve(null))
 const getAiApiKey = vi.fn<(id: string) => Promise<string>>(() => Promise.resolve(''))
@@ -19,7 +20,7 @@ const hasAiApiKey = vi.fn<(id: string) => Promise<boolean>>(() => Promise.resolv
 const configureAi = vi.fn<
   (
     provider: string,
-    contextSize: number,
+    serverConfig: AiServerConfig,
     apiKey: string,
     baseUrl: string,
     model: string,
@@ -33,12 +34,12 @@ vi.mock('$lib/tauri-commands', () => ({
   hasAiApiKey: (id: string) => hasAiApiKey(id),
   configureAi: (
     provider: string,
-    contextSize: number,
+    serverConfig: AiServerConfig,
     apiKey: string,
     baseUrl: string,
     model: string,
     requiresApiKey: boolean,
-  ) => configureAi(provider, contextSize, apiKey, baseUrl, model, requiresApiKey),
+  ) => configureAi(provider, serverConfig, apiKey, baseUrl, model, requiresApiKey),
 }))
 
 const settingsMap: Record<string, string> = {}
@@ -261,7 +262,7 @@ describe('pushConfigToBackend', () => {
     // OpenAI requires a key, so requiresApiKey is true.
     expect(configureAi).toHaveBeenCalledWith(
       'cloud',
-      8192,
+      expect.objectContaining({ contextSize: 8192 }),
       'sk-fresh',
       expect.stringContaining('openai.com'),
       'gpt-4o',
@@ -278,7 +279,14 @@ describe('pushConfigToBackend', () => {
 
     await pushConfigToBackend()
 
-    expect(configureAi).toHaveBeenCalledWith('cloud', 8192, '', expect.stringContaining('localhost'), 'llama3.2', false)
+    expect(configureAi).toHaveBeenCalledWith(
+      'cloud',
+      expect.objectContaining({ contextSize: 8192 }),
+      '',
+      expect.stringContaining('localhost'),
+      'llama3.2',
+      false,
+    )
   })
 
   it('surfaces a persistent toast and keeps pushing when the secret store read fails', async () => {
@@ -295,7 +303,14 @@ describe('pushConfigToBackend', () => {
     expect(typeof body).toBe('string')
     expect(opts).toMatchObject({ dismissal: 'persistent' })
     // Still pushed with an empty key so the rest of the config reaches the backend.
-    expect(configureAi).toHaveBeenCalledWith('cloud', 4096, '', expect.any(String), 'gpt-4o', true)
+    expect(configureAi).toHaveBeenCalledWith(
+      'cloud',
+      expect.objectContaining({ contextSize: 4096 }),
+      '',
+      expect.any(String),
+      'gpt-4o',
+      true,
+    )
     expect(loggerError).toHaveBeenCalled()
   })
 
@@ -318,6 +333,28 @@ describe('pushConfigToBackend', () => {
 
     await pushConfigToBackend()
 
-    expect(configureAi).toHaveBeenCalledWith('local', 16384, '', expect.any(String), expect.any(String), false)
+    expect(configureAi).toHaveBeenCalledWith(
+      'local',
+      expect.objectContaining({ contextSize: 16384 }),
+      '',
+      expect.any(String),
+      expect.any(String),
+      false,
+    )
+  })
+
+  it('pushes the llama-server launch settings, mapping 0 threads / port to null', async () => {
+    settingsMap['ai.provider'] = 'local'
+    settingsMap['ai.cloudProvider'] = ''
+    settingsMap['ai.cloudProviderConfigs'] = '{}'
+    settingsMap['ai.localContextSize'] = '8192'
+    settingsMap['ai.localThreads'] = '0'
+    settingsMap['ai.localGpuLayers'] = '40'
+    settingsMap['ai.localPort'] = '8089'
+
+    await pushConfigToBackend()
+
+    const serverConfig = configureAi.mock.calls[0][1]
+    expect(serverConfig).toEqual({ contextSize: 8192, threads: null, gpuLayers: 40, port: 8089 })
   })
 })
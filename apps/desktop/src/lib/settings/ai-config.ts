@@ -5,8 +5,8 @@
  * Two responsibilities:
  *
  * 1. **`pushConfigToBackend()`** — read-fresh push of the current AI provider config to
- *    Rust. Re-reads `ai.provider` / `ai.cloudProvider` / `ai.cloudProviderConfigs` /
- *    `ai.localContextSize` from `getSetting(...)` on every call, fetches the matching
+ *    Rust. Re-reads `ai.provider` / `ai.cloudProvider` / `ai.cloudProviderConfigs` and
+ *    the llama-server launch settings (`getAiServerConfig()`) on every call, fetches the matching
  *    API key from the OS secret store, calls `configureAi(...)`. Surfaces secret-store
  *    failures via a deduped persistent toast so a silently-broken keyring isn't invisible.
  *    Callers MUST NOT pass cached values: the helper has read-fresh semantics so that the
@@ -31,13 +31,28 @@ import {
   getRawStoreValue,
   deleteRawStoreKeys,
 } from '$lib/settings'
-import { configureAi, getAiApiKey, saveAiApiKey, hasAiApiKey } from '$lib/tauri-commands'
+import { configureAi, getAiApiKey, saveAiApiKey, hasAiApiKey, type AiServerConfig } from '$lib/tauri-commands'
 import { getAppLogger } from '$lib/logging/logger'
 import { addToast } from '$lib/ui/toast'
 import { describeSecretError } from './sections/ai-secret-error'
 
 const logger = getAppLogger('ai-settings')
 
+/**
+ * Reads the llama-server launch parameters from settings, read-fresh. `0` in the threads
+ * and port settings means "let the backend pick".
+ */
+export function getAiServerConfig(): AiServerConfig {
+  const threads = Number(getSetting('ai.localThreads'))
+  const port = Number(getSetting('ai.localPort'))
+  return {
+    contextSize: Number(getSetting('ai.localContextSize')),
+    threads: threads > 0 ? threads : null,
+    gpuLayers: Number(getSetting('ai.localGpuLayers')),
+    port: port > 0 ? port : null,
+  }
+}
+
 /** Stable id so the same toast replaces in place across consecutive failed startup attempts. */
 const secretErrorToastId = 'ai-secret-store-error'
 
@@ -167,7 +182,7 @@ export async function pushConfigToBackend(): Promise<void> {
 
     await configureAi(
       getSetting('ai.provider'),
-      Number(getSetting('ai.localContextSize')),
+      getAiServerConfig(),
       apiKey,
       resolved.baseUrl,
       resolved.model,
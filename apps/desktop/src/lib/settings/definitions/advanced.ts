@@ -126,6 +126,54 @@ export const advancedSettings: SettingDefinitionSource[] = [
       maxMs: 2000,
     },
   },
+  {
+    id: 'advanced.fileWatcherDebounceLocal',
+    section: ['Advanced'],
+    cardKey: 'settings.advanced.card.fileWatching',
+    labelKey: 'settings.advanced.fileWatcherDebounceLocal.label',
+    descriptionKey: 'settings.advanced.fileWatcherDebounceLocal.description',
+    keywords: ['watcher', 'debounce', 'refresh', 'delay', 'local'],
+    type: 'duration',
+    default: 0, // 0 ms = follow `advanced.fileWatcherDebounce`
+    component: 'duration',
+    constraints: {
+      unit: 'ms',
+      minMs: 0,
+      maxMs: 5000,
+    },
+  },
+  {
+    id: 'advanced.fileWatcherDebounceNetwork',
+    section: ['Advanced'],
+    cardKey: 'settings.advanced.card.fileWatching',
+    labelKey: 'settings.advanced.fileWatcherDebounceNetwork.label',
+    descriptionKey: 'settings.advanced.fileWatcherDebounceNetwork.description',
+    keywords: ['watcher', 'debounce', 'refresh', 'delay', 'network'],
+    type: 'duration',
+    default: 0, // 0 ms = follow `advanced.fileWatcherDebounce`
+    component: 'duration',
+    constraints: {
+      unit: 'ms',
+      minMs: 0,
+      maxMs: 5000,
+    },
+  },
+  {
+    id: 'advanced.fileWatcherDebounceMtp',
+    section: ['Advanced'],
+    cardKey: 'settings.advanced.card.fileWatching',
+    labelKey: 'settings.advanced.fileWatcherDebounceMtp.label',
+    descriptionKey: 'settings.advanced.fileWatcherDebounceMtp.description',
+    keywords: ['watcher', 'debounce', 'refresh', 'delay', 'mtp'],
+    type: 'duration',
+    default: 0, // 0 ms = follow `advanced.fileWatcherDebounce`
+    component: 'duration',
+    constraints: {
+      unit: 'ms',
+      minMs: 0,
+      maxMs: 5000,
+    },
+  },
   {
     id: 'advanced.diskSpaceChangeThreshold',
     section: ['Advanced'],
@@ -273,6 +321,22 @@ export const advancedSettings: SettingDefinitionSource[] = [
       step: 50,
     },
   },
+  {
+    id: 'advanced.listingCacheMemoryMb',
+    section: ['Advanced'],
+    cardKey: 'settings.advanced.card.historyAndLimits',
+    labelKey: 'settings.advanced.listingCacheMemoryMb.label',
+    descriptionKey: 'settings.advanced.listingCacheMemoryMb.description',
+    keywords: ['memory', 'ram', 'cache', 'listing', 'folder', 'mb', 'cap', 'limit'],
+    type: 'number',
+    default: 512,
+    component: 'number-input',
+    constraints: {
+      min: 64,
+      max: 8192,
+      step: 64,
+    },
+  },
   {
     id: 'advanced.mountTimeout',
     section: ['Advanced'],
@@ -70,6 +70,7 @@ export const aiSettings: SettingDefinitionSource[] = [
     type: 'string',
     default: '{}',
     component: 'text-input',
+    sensitive: true,
   },
   {
     id: 'ai.localContextSize',
@@ -94,6 +95,56 @@ export const aiSettings: SettingDefinitionSource[] = [
       ],
     },
   },
+  // The other llama-server launch parameters. Pushed with the context size via
+  // `getAiServerConfig()`; they take effect on the next server (re)start.
+  {
+    id: 'ai.localThreads',
+    section: ['AI', 'Provider'],
+    labelKey: 'settings.ai.localThreads.label',
+    descriptionKey: 'settings.ai.localThreads.description',
+    keywords: ['threads', 'cpu', 'cores', 'local', 'llama'],
+    type: 'number',
+    // 0 = let llama-server pick. The backend rejects more than the core count.
+    default: 0,
+    component: 'number-input',
+    constraints: {
+      min: 0,
+      max: 256,
+      step: 1,
+    },
+  },
+  {
+    id: 'ai.localGpuLayers',
+    section: ['AI', 'Provider'],
+    labelKey: 'settings.ai.localGpuLayers.label',
+    descriptionKey: 'settings.ai.localGpuLayers.description',
+    keywords: ['gpu', 'layers', 'offload', 'metal', 'local', 'llama'],
+    type: 'number',
+    // More than any model has, so everything is offloaded; llama-server clamps it.
+    default: 99,
+    component: 'number-input',
+    constraints: {
+      min: 0,
+      max: 999,
+      step: 1,
+    },
+  },
+  {
+    id: 'ai.localPort',
+    section: ['AI', 'Provider'],
+    labelKey: 'settings.ai.localPort.label',
+    descriptionKey: 'settings.ai.localPort.description',
+    keywords: ['port', 'network', 'local', 'llama', 'server'],
+    type: 'number',
+    // 0 = a free port on each start. The backend rejects 1-1023.
+    default: 0,
+    component: 'number-input',
+    constraints: {
+      min: 0,
+      max: 65535,
+      step: 1,
+    },
+  },
 
   // ========================================================================
   // AI › Ask Cmdr
@@ -132,6 +183,7 @@ export const aiSettings: SettingDefinitionSource[] = [
     type: 'boolean',
     default: false,
     component: 'switch',
+    sensitive: true,
   },
   {
     id: 'developer.mcpPort',
@@ -146,6 +198,7 @@ export const aiSettings: SettingDefinitionSource[] = [
     // `docs/tooling/instance-isolation.md` § "Per-resource breakdown" (Cmdr MCP HTTP port row).
     default: 0,
     component: 'number-input',
+    sensitive: true,
     constraints: {
       min: 0,
       max: 65535,
@@ -46,6 +46,38 @@ export const behaviorSettings: SettingDefinitionSource[] = [
     component: 'switch',
     hidden: true,
   },
+  {
+    // What Enter or a double-click does on an ordinary file. Read by the Rust
+    // `resolve_default_action` policy, pushed live by `settings-applier`.
+    id: 'behavior.fileEnterAction',
+    section: ['Behavior', 'Navigation & file ops'],
+    cardKey: 'settings.navigationAndFileOps.card.navigation',
+    labelKey: 'settings.behavior.fileEnterAction.label',
+    descriptionKey: 'settings.behavior.fileEnterAction.description',
+    keywords: ['enter', 'double-click', 'open', 'file', 'viewer', 'default', 'app', 'application'],
+    type: 'enum',
+    default: 'open',
+    component: 'toggle-group',
+    constraints: {
+      options: [
+        { value: 'open', labelKey: 'settings.behavior.fileEnterAction.opt.open' },
+        { value: 'viewer', labelKey: 'settings.behavior.fileEnterAction.opt.viewer' },
+      ],
+    },
+  },
+  {
+    // Per-extension app overrides: pinned-shape JSON of lowercased extension →
+    // app bundle id, `{ md: 'com.example.editor' }`. No UI row yet.
+    id: 'behavior.openWithApps',
+    section: ['Behavior', 'Navigation & file ops'],
+    labelKey: 'settings.behavior.openWithApps.label',
+    descriptionKey: 'settings.behavior.openWithApps.description',
+    keywords: [],
+    type: 'string',
+    default: '{}',
+    component: 'text-input',
+    hidden: true,
+  },
   {
     id: 'fileOperations.allowFileExtensionChanges',
     section: ['Behavior', 'Navigation & file ops'],
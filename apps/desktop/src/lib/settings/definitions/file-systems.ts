@@ -167,4 +167,21 @@ export const fileSystemsSettings: SettingDefinitionSource[] = [
     default: true,
     component: 'switch',
   },
+  {
+    id: 'fileExplorer.git.ignoredFiles',
+    section: ['File systems', 'Git'],
+    labelKey: 'settings.fileExplorer.git.ignoredFiles.label',
+    descriptionKey: 'settings.fileExplorer.git.ignoredFiles.description',
+    keywords: ['git', 'gitignore', 'ignored', 'ignore', 'hide', 'dim', 'grey', 'build', 'node_modules'],
+    type: 'enum',
+    default: 'off',
+    component: 'toggle-group',
+    constraints: {
+      options: [
+        { value: 'off', labelKey: 'settings.fileExplorer.git.ignoredFiles.opt.off' },
+        { value: 'dim', labelKey: 'settings.fileExplorer.git.ignoredFiles.opt.dim' },
+        { value: 'hide', labelKey: 'settings.fileExplorer.git.ignoredFiles.opt.hide' },
+      ],
+    },
+  },
 ]
@@ -74,6 +74,45 @@ export const indexingSettings: SettingDefinitionSource[] = [
     default: true,
     component: 'switch',
   },
+  {
+    // Folders the indexer skips on every drive (scan, reconcile, live events). Default must
+    // match the backend `DEFAULT_USER_EXCLUSIONS`. No UI row yet, so `hidden`. Live-applied
+    // via the `settings-applier.ts` passthrough → `set_index_exclusions`, which also prunes.
+    id: 'indexing.excludePatterns',
+    section: ['Indexing', 'Drive indexing'],
+    labelKey: 'settings.indexing.excludePatterns.label',
+    descriptionKey: 'settings.indexing.excludePatterns.description',
+    keywords: ['exclude', 'ignore', 'node_modules', 'git', 'cache'],
+    type: 'string-array',
+    default: ['node_modules', '.git', 'Library/Caches'],
+    hidden: true,
+  },
+  {
+    // Throttles the fresh full scan so a first index doesn't pin a core on a laptop. 0 = no
+    // cap. No UI row yet, so `hidden`. Live-applied via the `settings-applier.ts`
+    // passthrough → `set_index_throttle` (together with `indexing.onlyOnAcPower`).
+    id: 'indexing.maxEntriesPerSec',
+    section: ['Indexing', 'Drive indexing'],
+    labelKey: 'settings.indexing.maxEntriesPerSec.label',
+    descriptionKey: 'settings.indexing.maxEntriesPerSec.description',
+    keywords: ['throttle', 'speed', 'rate', 'cpu', 'battery', 'fan', 'scan'],
+    type: 'number',
+    default: 0,
+    constraints: { min: 0, max: 1_000_000, step: 1000 },
+    hidden: true,
+  },
+  {
+    // Pauses the fresh full scan while on battery. Same flow as `indexing.maxEntriesPerSec`.
+    id: 'indexing.onlyOnAcPower',
+    section: ['Indexing', 'Drive indexing'],
+    labelKey: 'settings.indexing.onlyOnAcPower.label',
+    descriptionKey: 'settings.indexing.onlyOnAcPower.description',
+    keywords: ['battery', 'power', 'plugged in', 'charger', 'ac', 'scan'],
+    type: 'boolean',
+    default: false,
+    component: 'switch',
+    hidden: true,
+  },
   {
     // Internal (FE-owned): JSON array of volume ids the user silenced via
     // "Don't ask again for this drive". Never a UI row; the "Re-enable
@@ -17,6 +17,7 @@ export type {
   ExtensionChangePolicy,
   FileSizeFormat,
   FileSizeUnit,
+  GitIgnoredFilesMode,
   NetworkTimeoutMode,
   SettingConstraints,
   SettingDefinition,
@@ -69,6 +70,17 @@ export {
   setSetting,
 } from './settings-store'
 
+// Export / import (portable settings files)
+export {
+  applySettingsImport,
+  buildSettingsExport,
+  exportSettings,
+  importSettings,
+  parseSettingsExport,
+  SETTINGS_EXPORT_FORMAT,
+} from './settings-transfer'
+export type { SettingsExportFile, SettingsImportResult, SettingsTransferOptions } from './settings-transfer'
+
 // Search
 export {
   clearSearchIndex,
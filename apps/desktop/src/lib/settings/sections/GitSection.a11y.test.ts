@@ -12,6 +12,7 @@ vi.mock('$lib/settings/settings-store', () => ({
     if (key === 'fileExplorer.git.showRepoChip') return true
     if (key === 'fileExplorer.git.showStatusColumn') return false
     if (key === 'fileExplorer.git.showVirtualGitPortal') return true
+    if (key === 'fileExplorer.git.ignoredFiles') return 'dim'
     return undefined
   }),
   setSetting: vi.fn(() => Promise.resolve()),
@@ -11,12 +11,16 @@ import {
   type SizeColorsPalette,
   type DateColorsPalette,
   type ThemeMode,
+  type GitIgnoredFilesMode,
 } from '$lib/settings'
 import { getAppLogger, setVerboseLogging } from '$lib/logging/logger'
 import {
   updateFileWatcherDebounce,
+  updateFileWatcherDebounceForVolumeKind,
   updateServiceResolveTimeout,
   setIndexingEnabled,
+  setIndexExclusions,
+  setIndexThrottle,
   setImageIndexEnabled,
   setImageImportanceThreshold,
   setImageParallelism,
@@ -28,9 +32,12 @@ import {
   setFilterSafeSaveArtifacts,
   setLogLlmCalls,
   setSmbConcurrency,
+  setListingCacheMemoryMb,
+  setDefaultActionSettings,
   setMaxLogStorageMb,
   setErrorReportsEnabled,
   setShowVirtualGitPortal,
+  setGitIgnoredMode,
   setNetworkEnabled,
   applyRecentSearchesMaxCount,
   applyRecentSelectionsMaxCount,
@@ -98,6 +105,22 @@ function applyLanguage(value: string): void {
   log.debug('Applied language: {value}', { value })
 }
 
+/**
+ * Pushes the three settings the Rust Enter/double-click policy (`resolve_default_action`)
+ * reads. Re-reads all three at call time, so any one changing sends the current trio.
+ */
+async function pushDefaultActionSettingsToBackend(): Promise<void> {
+  try {
+    await setDefaultActionSettings(
+      getSetting('behavior.archiveEnterBehavior'),
+      getSetting('behavior.fileEnterAction'),
+      getSetting('behavior.openWithApps'),
+    )
+  } catch (err) {
+    log.warn('Failed to push default-action settings: {err}', { err: String(err) })
+  }
+}
+
 /**
  * Applies Rust backend settings that need to be synced on startup.
  */
@@ -105,6 +128,9 @@ async function applyBackendSettings(): Promise<void> {
   // File watcher debounce
   const debounceMs = getSetting('advanced.fileWatcherDebounce')
   await updateFileWatcherDebounce(debounceMs)
+  await updateFileWatcherDebounceForVolumeKind('local', getSetting('advanced.fileWatcherDebounceLocal'))
+  await updateFileWatcherDebounceForVolumeKind('network', getSetting('advanced.fileWatcherDebounceNetwork'))
+  await updateFileWatcherDebounceForVolumeKind('mtp', getSetting('advanced.fileWatcherDebounceMtp'))
 
   // Service resolve timeout
   const resolveTimeoutMs = getSetting('advanced.serviceResolveTimeout')
@@ -118,6 +144,7 @@ async function applyBackendSettings(): Promise<void> {
   // Virtual `.git` portal toggle. Same rationale as above: backend reads at startup,
   // but a re-push keeps dev/hot-reload aligned with whatever the user persisted.
   await setShowVirtualGitPortal(getSetting('fileExplorer.git.showVirtualGitPortal'))
+  await setGitIgnoredMode(getSetting('fileExplorer.git.ignoredFiles'))
 
   log.debug('Applied backend settings: debounce={debounce}ms, resolveTimeout={timeout}ms', {
     debounce: debounceMs,
@@ -181,8 +208,14 @@ function applyAllSettings(): void {
 const passthroughBackendHandlers: Partial<Record<string, (value: unknown) => void>> = {
   'developer.verboseLogging': (v) => void setVerboseLogging(v as boolean),
   'advanced.fileWatcherDebounce': (v) => void updateFileWatcherDebounce(v as number),
+  'advanced.fileWatcherDebounceLocal': (v) => void updateFileWatcherDebounceForVolumeKind('local', v as number),
+  'advanced.fileWatcherDebounceNetwork': (v) => void updateFileWatcherDebounceForVolumeKind('network', v as number),
+  'advanced.fileWatcherDebounceMtp': (v) => void updateFileWatcherDebounceForVolumeKind('mtp', v as number),
   'advanced.serviceResolveTimeout': (v) => void updateServiceResolveTimeout(v as number),
   'indexing.enabled': (v) => void setIndexingEnabled(v as boolean),
+  'indexing.excludePatterns': (v) => void setIndexExclusions(v as string[]),
+  'indexing.maxEntriesPerSec': (v) => void setIndexThrottle(v as number, getSetting('indexing.onlyOnAcPower')),
+  'indexing.onlyOnAcPower': (v) => void setIndexThrottle(getSetting('indexing.maxEntriesPerSec'), v as boolean),
   'mediaIndex.enabled': (v) => void setImageIndexEnabled(v as boolean),
   'mediaIndex.importanceThreshold': (v) => void setImageImportanceThreshold(v as number),
   'mediaIndex.parallelism': (v) => void setImageParallelism(v as number),
@@ -199,8 +232,14 @@ const passthroughBackendHandlers: Partial<Record<string, (value: unknown) => voi
   'advanced.filterSafeSaveArtifacts': (v) => void setFilterSafeSaveArtifacts(v as boolean),
   'advanced.logLlmCalls': (v) => void setLogLlmCalls(v as boolean),
   'network.smbConcurrency': (v) => void setSmbConcurrency(v as number),
+  'advanced.listingCacheMemoryMb': (v) => void setListingCacheMemoryMb(v as number),
+  // Enter/double-click policy trio: one shared handler pushes all three, like the AI triplet below.
+  'behavior.archiveEnterBehavior': () => void pushDefaultActionSettingsToBackend(),
+  'behavior.fileEnterAction': () => void pushDefaultActionSettingsToBackend(),
+  'behavior.openWithApps': () => void pushDefaultActionSettingsToBackend(),
   'updates.errorReports': (v) => void setErrorReportsEnabled(v as boolean),
   'fileExplorer.git.showVirtualGitPortal': (v) => void setShowVirtualGitPortal(v as boolean),
+  'fileExplorer.git.ignoredFiles': (v) => void setGitIgnoredMode(v as GitIgnoredFilesMode),
   'network.enabled': (v) => void setNetworkEnabled(v as boolean),
   'search.recentSearches.maxCount': (v) => void applyRecentSearchesMaxCount(v as number),
   'selection.recentSelections.maxCount': (v) => void applyRecentSelectionsMaxCount(v as number),
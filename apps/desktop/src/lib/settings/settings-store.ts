@@ -41,7 +41,7 @@ interface SettingChangedPayload {
 // Store Configuration
 // ============================================================================
 
-const SCHEMA_VERSION = 3
+export const SCHEMA_VERSION = 3
 
 let storeInstance: Store | null = null
 let saveTimeout: ReturnType<typeof setTimeout> | null = null
@@ -546,6 +546,19 @@ export function resetSetting(id: SettingId): void {
   void emit(SETTING_CHANGED_EVENT, { id, value: defaultValue, explicit: false } satisfies SettingChangedPayload)
 }
 
+/**
+ * The explicitly-set settings and their values: exactly what `settings.json` holds (or will
+ * after the pending save). Settings at their registry default are absent. Used by
+ * `settings-transfer.ts` so an export carries the user's choices, never pinned defaults.
+ */
+export function getExplicitSettings(): Partial<Record<SettingId, unknown>> {
+  const values: Partial<Record<SettingId, unknown>> = {}
+  for (const id of explicitlySet) {
+    if (settingsCache.has(id)) values[id] = settingsCache.get(id)
+  }
+  return values
+}
+
 /**
  * Check if a setting has been modified from its default value.
  */
@@ -0,0 +1,146 @@
+/**
+ * Settings export/import: exports carry only explicit choices (sensitive ones on opt-in),
+ * imports validate each field, and merge vs replace differ only in resetting what the file
+ * doesn't mention. Same fake plugin-store as `settings-store.persistence.test.ts`, with a
+ * fresh module graph per test so the store's cache and explicit-set start empty.
+ */
+import { describe, it, expect, vi, beforeAll, beforeEach } from 'vitest'
+
+const disk = vi.hoisted(() => new Map<string, unknown>())
+const exportFiles = vi.hoisted(() => new Map<string, string>())
+
+vi.mock('@tauri-apps/plugin-store', () => ({
+  load: vi.fn(() =>
+    Promise.resolve({
+      get: (key: string) => Promise.resolve(disk.get(key)),
+      set: (key: string, value: unknown) => {
+        disk.set(key, value)
+        return Promise.resolve()
+      },
+      delete: (key: string) => Promise.resolve(disk.delete(key)),
+      has: (key: string) => Promise.resolve(disk.has(key)),
+      keys: () => Promise.resolve([...disk.keys()]),
+      save: () => Promise.resolve(),
+    }),
+  ),
+}))
+
+vi.mock('@tauri-apps/api/event', () => ({
+  listen: vi.fn(() => Promise.resolve(() => {})),
+  emit: vi.fn(() => Promise.resolve()),
+}))
+
+vi.mock('./store-path', () => ({
+  resolveStorePath: (name: string) => Promise.resolve(name),
+}))
+
+vi.mock('$lib/tauri-commands/settings', () => ({
+  recordSettingsDefaults: vi.fn(() => Promise.resolve()),
+  getRestrictedWindowSettings: vi.fn(() => Promise.resolve({})),
+  persistRestrictedWindowSetting: vi.fn(() => Promise.resolve({ status: 'ok' })),
+  writeSettingsExport: vi.fn((path: string, contents: string) => {
+    exportFiles.set(path, contents)
+    return Promise.resolve()
+  }),
+  readSettingsExport: vi.fn((path: string) => Promise.resolve(exportFiles.get(path) ?? '')),
+}))
+
+// Warm the transform cache (the store pulls the whole intl catalog) under the hook budget.
+beforeAll(async () => {
+  await import('./settings-transfer')
+})
+
+async function load() {
+  const store = await import('./settings-store')
+  const transfer = await import('./settings-transfer')
+  await store.initializeSettings()
+  return { store, transfer }
+}
+
+beforeEach(() => {
+  disk.clear()
+  exportFiles.clear()
+  vi.resetModules()
+})
+
+describe('settings export', () => {
+  it('carries explicit settings only, and sensitive ones only on opt-in', async () => {
+    const { store, transfer } = await load()
+    store.setSetting('appearance.uiDensity', 'compact')
+    store.setSetting('network.enabled', true) // equals the default, but explicit
+    store.setSetting('developer.mcpEnabled', true)
+
+    const file = transfer.buildSettingsExport()
+    expect(file.format).toBe('cmdr-settings')
+    expect(file.schemaVersion).toBe(store.SCHEMA_VERSION)
+    expect(file.settings).toEqual({ 'appearance.uiDensity': 'compact', 'network.enabled': true })
+
+    const withSensitive = transfer.buildSettingsExport({ includeSensitive: true })
+    expect(withSensitive.settings['developer.mcpEnabled']).toBe(true)
+  })
+
+  it('round-trips through the export file', async () => {
+    const { store, transfer } = await load()
+    store.setSetting('advanced.maxLogStorageMb', 321)
+    await transfer.exportSettings('/tmp/cmdr-settings.json')
+
+    store.resetSetting('advanced.maxLogStorageMb')
+    const result = await transfer.importSettings('/tmp/cmdr-settings.json', true)
+    expect(result.applied).toEqual(['advanced.maxLogStorageMb'])
+    expect(store.getSetting('advanced.maxLogStorageMb')).toBe(321)
+  })
+})
+
+describe('settings import', () => {
+  function exportOf(settings: Record<string, unknown>) {
+    return { format: 'cmdr-settings' as const, schemaVersion: 1, exportedAt: '', settings }
+  }
+
+  it('merge applies valid fields and skips unknown, invalid, and sensitive ones', async () => {
+    const { store, transfer } = await load()
+    store.setSetting('network.enabled', false)
+
+    const result = transfer.applySettingsImport(
+      exportOf({
+        'appearance.uiDensity': 'spacious',
+        'appearance.notASetting': 1,
+        'advanced.maxLogStorageMb': 'lots',
+        'developer.mcpEnabled': true,
+      }),
+      true,
+    )
+
+    expect(result.applied).toEqual(['appearance.uiDensity'])
+    expect(result.reset).toEqual([])
+    expect(result.skipped.map((s) => s.id)).toEqual([
+      'appearance.notASetting',
+      'advanced.maxLogStorageMb',
+      'developer.mcpEnabled',
+    ])
+    expect(store.getSetting('appearance.uiDensity')).toBe('spacious')
+    expect(store.getSetting('network.enabled')).toBe(false)
+    expect(store.getSetting('developer.mcpEnabled')).toBe(false)
+  })
+
+  it('replace resets settings the file lacks, but not sensitive ones without the opt-in', async () => {
+    const { store, transfer } = await load()
+    store.setSetting('network.enabled', false)
+    store.setSetting('developer.mcpEnabled', true)
+
+    const result = transfer.applySettingsImport(exportOf({ 'appearance.uiDensity': 'compact' }), false)
+    expect(result.reset).toEqual(['network.enabled'])
+    expect(store.getSetting('network.enabled')).toBe(true)
+    expect(store.getSetting('developer.mcpEnabled')).toBe(true)
+
+    transfer.applySettingsImport(exportOf({}), false, { includeSensitive: true })
+    expect(store.getSetting('developer.mcpEnabled')).toBe(false)
+  })
+
+  it('refuses files that are not exports or come from a newer schema', async () => {
+    const { store, transfer } = await load()
+    expect(() => transfer.parseSettingsExport('{"settings":{}}')).toThrow()
+    expect(() => transfer.parseSettingsExport('[]')).toThrow()
+    const newer = JSON.stringify({ ...exportOf({}), schemaVersion: store.SCHEMA_VERSION + 1 })
+    expect(() => transfer.parseSettingsExport(newer)).toThrow(/newer version/)
+  })
+})
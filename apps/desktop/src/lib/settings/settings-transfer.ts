@@ -0,0 +1,150 @@
+/**
+ * Settings export and import: the portable JSON a user carries to a second machine.
+ *
+ * An export holds the explicitly-set settings only (sparse, like `settings.json`), so a
+ * default the user never touched keeps tracking future releases on the new machine too.
+ * Settings marked `sensitive` in the registry stay out unless the caller opts in.
+ *
+ * An import validates every field against the registry and reports what it skipped. Merge
+ * applies the file's settings over the current ones; replace also resets every other setting
+ * to its default. Without the opt-in, sensitive settings are neither applied nor reset.
+ * Writes go through `setSetting` / `resetSetting`, so live-apply and cross-window sync work
+ * as for any other change. Run it from the main or Settings window: a restricted window's
+ * writes are session-only.
+ *
+ * The backend only moves the file (`write_settings_export` / `read_settings_export`).
+ */
+
+import type { SettingId, SettingsValues } from './types'
+import { getSettingDefinition, validateSettingValue } from './settings-registry'
+import { getExplicitSettings, resetSetting, SCHEMA_VERSION, setSetting } from './settings-store'
+import { getAppLogger } from '$lib/logging/logger'
+import { readSettingsExport, writeSettingsExport } from '$lib/tauri-commands/settings'
+
+const log = getAppLogger('settings-transfer')
+
+/** The `format` tag every export carries, so a random JSON file is refused up front. */
+export const SETTINGS_EXPORT_FORMAT = 'cmdr-settings'
+
+export interface SettingsExportFile {
+  format: typeof SETTINGS_EXPORT_FORMAT
+  /** The settings schema the values follow (`SCHEMA_VERSION` at export time). */
+  schemaVersion: number
+  exportedAt: string
+  settings: Record<string, unknown>
+}
+
+export interface SettingsTransferOptions {
+  /** Export, or apply and reset on import, the settings marked `sensitive`. Off by default. */
+  includeSensitive?: boolean
+}
+
+export interface SettingsImportResult {
+  /** Settings set from the file. */
+  applied: SettingId[]
+  /** Settings reset to their default because a replace import didn't carry them. */
+  reset: SettingId[]
+  /** Entries left alone, with why: unknown id, failed validation, or sensitive. */
+  skipped: { id: string; reason: string }[]
+}
+
+/** Builds an export of the current explicitly-set settings. */
+export function buildSettingsExport(options: SettingsTransferOptions = {}): SettingsExportFile {
+  const settings: Record<string, unknown> = {}
+  for (const [id, value] of Object.entries(getExplicitSettings())) {
+    if (getSettingDefinition(id)?.sensitive && !options.includeSensitive) continue
+    settings[id] = value
+  }
+  return {
+    format: SETTINGS_EXPORT_FORMAT,
+    schemaVersion: SCHEMA_VERSION,
+    exportedAt: new Date().toISOString(),
+    settings,
+  }
+}
+
+/**
+ * Parses export JSON, checking its envelope. Throws on a file that isn't an export or comes
+ * from a newer settings schema (its values may mean something this version can't tell).
+ */
+export function parseSettingsExport(json: string): SettingsExportFile {
+  const parsed: unknown = JSON.parse(json)
+  if (typeof parsed !== 'object' || parsed === null) throw new Error("This file isn't a settings export")
+  const file = parsed as Partial<SettingsExportFile>
+  if (file.format !== SETTINGS_EXPORT_FORMAT || typeof file.settings !== 'object' || file.settings === null) {
+    throw new Error("This file isn't a settings export")
+  }
+  if (typeof file.schemaVersion !== 'number' || file.schemaVersion > SCHEMA_VERSION) {
+    throw new Error('This settings export is from a newer version of Cmdr')
+  }
+  return file as SettingsExportFile
+}
+
+/**
+ * Applies a parsed export. `merge` keeps settings the file doesn't mention; otherwise they're
+ * reset to their defaults. Each field is validated on its own, so one bad value only skips itself.
+ */
+export function applySettingsImport(
+  file: SettingsExportFile,
+  merge: boolean,
+  options: SettingsTransferOptions = {},
+): SettingsImportResult {
+  const result: SettingsImportResult = { applied: [], reset: [], skipped: [] }
+  const isSkippedAsSensitive = (id: string) => Boolean(getSettingDefinition(id)?.sensitive) && !options.includeSensitive
+
+  for (const [id, value] of Object.entries(file.settings)) {
+    if (!getSettingDefinition(id)) {
+      result.skipped.push({ id, reason: 'Unknown setting' })
+      continue
+    }
+    if (isSkippedAsSensitive(id)) {
+      result.skipped.push({ id, reason: 'Sensitive setting' })
+      continue
+    }
+    try {
+      validateSettingValue(id, value)
+    } catch (error) {
+      result.skipped.push({ id, reason: error instanceof Error ? error.message : String(error) })
+      continue
+    }
+    const settingId = id as SettingId
+    setSetting(settingId, value as SettingsValues[typeof settingId])
+    result.applied.push(settingId)
+  }
+
+  if (!merge) {
+    for (const id of Object.keys(getExplicitSettings()) as SettingId[]) {
+      if (id in file.settings || isSkippedAsSensitive(id)) continue
+      resetSetting(id)
+      result.reset.push(id)
+    }
+  }
+
+  log.info('Imported settings: {applied} applied, {reset} reset, {skipped} skipped', {
+    applied: result.applied.length,
+    reset: result.reset.length,
+    skipped: result.skipped.length,
+  })
+  return result
+}
+
+/** Writes an export of the current settings to `outPath`. Returns what was written. */
+export async function exportSettings(
+  outPath: string,
+  options: SettingsTransferOptions = {},
+): Promise<SettingsExportFile> {
+  const file = buildSettingsExport(options)
+  await writeSettingsExport(outPath, JSON.stringify(file, null, 2))
+  log.info('Exported {count} settings to {outPath}', { count: Object.keys(file.settings).length, outPath })
+  return file
+}
+
+/** Reads the export at `inPath` and applies it (see `applySettingsImport`). */
+export async function importSettings(
+  inPath: string,
+  merge: boolean,
+  options: SettingsTransferOptions = {},
+): Promise<SettingsImportResult> {
+  const file = parseSettingsExport(await readSettingsExport(inPath))
+  return applySettingsImport(file, merge, options)
+}
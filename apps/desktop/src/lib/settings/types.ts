@@ -120,6 +120,13 @@ export interface SettingDefinition {
   disabledReason?: string
   /** Internal state that should not appear in any section (main tree or Advanced). Persisted via the same store. */
   hidden?: boolean
+  /**
+   * Left out of settings exports unless the user opts in (`settings-transfer.ts`), and left
+   * alone by an import that doesn't. For settings that open this machine up or point at
+   * private endpoints. Secrets never live in the store at all (API keys are in the OS
+   * secret store, the MCP token is per-run).
+   */
+  sensitive?: boolean
 
   // UI hints
   component?:
@@ -152,6 +159,8 @@ export type PasteClipboardAsFileMode = 'doNothing' | 'createFile' | 'createFileA
 export type DirectorySortMode = 'likeFiles' | 'alwaysByName'
 export type SizeDisplayMode = 'smart' | 'logical' | 'physical'
 export type BriefColumnWidthMode = 'paneWidth' | 'limited'
+/** How listings treat entries a repo's `.gitignore` matches: as usual, greyed out, or left out. */
+export type GitIgnoredFilesMode = 'off' | 'dim' | 'hide'
 export type AppColor = 'system' | 'cmdr-gold'
 export type SizeColorsPalette = 'none' | 'app' | 'rainbow'
 export type DateColorsPalette = 'none' | 'app' | 'wilting'
@@ -239,6 +248,7 @@ export interface SettingsValues {
   'fileExplorer.git.showRepoChip': boolean
   'fileExplorer.git.showStatusColumn': boolean
   'fileExplorer.git.showVirtualGitPortal': boolean
+  'fileExplorer.git.ignoredFiles': GitIgnoredFilesMode
 
   // Type-to-jump
   'fileExplorer.typeToJump.resetDelay': number
@@ -249,6 +259,10 @@ export interface SettingsValues {
   // Navigation
   'behavior.doubleClickPaneNavigatesToParent': boolean
   'behavior.doubleClickOnPaneNotificationSeen': boolean
+  // Enter / double-click on an ordinary file; read by the Rust `resolve_default_action` policy
+  'behavior.fileEnterAction': 'open' | 'viewer'
+  // Per-extension app overrides (pinned-shape JSON, `{ md: 'com.example.editor' }`)
+  'behavior.openWithApps': string
 
   // Archives (Enter behavior per format: pinned-shape JSON, `{ zip: 'ask', … }`)
   'behavior.archiveEnterBehavior': string
@@ -302,6 +316,15 @@ export interface SettingsValues {
   'indexing.askForEachDrive': boolean
   /** Gates the one-time "your drive went stale" dialog (D2). The yellow badge shows regardless. On by default. */
   'indexing.staleNotify': boolean
+  /**
+   * Folders the drive index skips on every drive: names (`node_modules`) or trailing paths
+   * (`Library/Caches`), `*` and `?` allowed. Their sizes don't count toward any folder's size.
+   */
+  'indexing.excludePatterns': string[]
+  /** Fresh-scan rate cap in entries per second. 0 means no cap. */
+  'indexing.maxEntriesPerSec': number
+  /** Pause fresh scans while the machine runs on battery. */
+  'indexing.onlyOnAcPower': boolean
   /**
    * Internal (FE-owned): JSON array of volume ids the user silenced via "Don''t ask
    * again for this drive". The first-connect notification skips a silenced drive.
@@ -420,6 +443,9 @@ export interface SettingsValues {
   // AI
   'ai.provider': AiProvider
   'ai.localContextSize': AiLocalContextSize
+  'ai.localThreads': number
+  'ai.localGpuLayers': number
+  'ai.localPort': number
   'ai.cloudProvider': string
   'ai.cloudProviderConfigs': string // JSON blob
 
@@ -440,6 +466,9 @@ export interface SettingsValues {
   'advanced.virtualizationBufferRows': number
   'advanced.virtualizationBufferColumns': number
   'advanced.fileWatcherDebounce': number
+  'advanced.fileWatcherDebounceLocal': number
+  'advanced.fileWatcherDebounceNetwork': number
+  'advanced.fileWatcherDebounceMtp': number
   'advanced.serviceResolveTimeout': number
   'advanced.mountTimeout': number
   'advanced.updateCheckInterval': number
@@ -447,6 +476,7 @@ export interface SettingsValues {
   'advanced.logLlmCalls': boolean
   'advanced.diskSpaceChangeThreshold': number
   'advanced.maxLogStorageMb': number
+  'advanced.listingCacheMemoryMb': number
   'fileExplorer.tabs.closedTabHistorySize': number
 
   // Search
@@ -35,6 +35,13 @@ export function onAiVerifying(handler: () => void): Promise<UnlistenFn> {
   })
 }
 
+/** The downloaded model failed its SHA-256 check and was deleted. The download rejects too. */
+export function onAiIntegrityFailed(handler: () => void): Promise<UnlistenFn> {
+  return events.aiIntegrityFailed.listen(() => {
+    handler()
+  })
+}
+
 /** Server startup (health-check polling) began. */
 export function onAiInstalling(handler: () => void): Promise<UnlistenFn> {
   return events.aiInstalling.listen(() => {
@@ -1,11 +1,20 @@
 // App-level state: MCP pane state, dialog tracking, menu context, window lifecycle
 
 import { invoke } from '@tauri-apps/api/core'
-import { commands, type ChildWindowRect, type PaneFileEntry, type PaneState } from '$lib/ipc/bindings'
+import {
+  commands,
+  type ChildWindowRect,
+  type HistoryPane,
+  type NavigationHistory as PaneNavigationHistory,
+  type PaneFileEntry,
+  type PaneState,
+  type Session,
+  type SessionPane,
+} from '$lib/ipc/bindings'
 import type { SoftDialogId } from '$lib/ui/dialog-registry'
 import { throwIpcError } from './ipc-types'
 
-export type { PaneFileEntry, PaneState }
+export type { PaneFileEntry, PaneNavigationHistory, PaneState, Session, SessionPane }
 
 // ============================================================================
 // MCP pane state
@@ -59,6 +68,49 @@ export async function setReopenClosedTabEnabled(enabled: boolean): Promise<void>
   await invoke('set_reopen_closed_tab_enabled', { enabled })
 }
 
+// ============================================================================
+// Pane session
+// ============================================================================
+
+/**
+ * Loads the pane session saved at the last quit. Panes whose volume or path is
+ * gone come back `null`; the whole result is `null` when there's no usable
+ * session (or outside Tauri). The backend bounds every probe, so this can't hang startup.
+ */
+export async function restoreSession(): Promise<Session | null> {
+  try {
+    return await commands.restoreSession()
+  } catch {
+    return null
+  }
+}
+
+/**
+ * Records a pane navigation in the backend's per-pane history, the copy `save_session`
+ * persists. Pushing the location already shown is a no-op there.
+ */
+export async function recordHistoryNavigation(pane: HistoryPane, volumeId: string, path: string): Promise<void> {
+  await commands.pushHistory(pane, volumeId, path)
+}
+
+/** Moves the backend history's cursor along with a back/forward walk, so a later push truncates the same entries. */
+export async function recordHistoryStep(pane: HistoryPane, direction: 'back' | 'forward'): Promise<void> {
+  if (direction === 'back') await commands.goBack(pane)
+  else await commands.goForward(pane)
+}
+
+/**
+ * Loads a pane's backend history, which `restoreSession` reinstated from the last quit.
+ * `null` outside Tauri or when the backend can't answer.
+ */
+export async function getPaneHistory(pane: HistoryPane): Promise<PaneNavigationHistory | null> {
+  try {
+    return await commands.getHistory(pane)
+  } catch {
+    return null
+  }
+}
+
 // ============================================================================
 // Dialog tracking
 // ============================================================================
@@ -1,4 +1,9 @@
-import { commands, type PastedClipboardFile } from '$lib/ipc/bindings'
+import {
+  commands,
+  type PastedClipboardFile,
+  type WriteOperationConfig,
+  type WriteOperationStartResult,
+} from '$lib/ipc/bindings'
 import { throwIpcError } from './ipc-types'
 
 export type { PastedClipboardFile } from '$lib/ipc/bindings'
@@ -63,6 +68,22 @@ export async function readClipboardFiles(): Promise<ClipboardReadResult> {
   return res.data
 }
 
+/**
+ * Pastes the clipboard's files (from Cmdr, Finder, or any app) into `destination`
+ * as a copy, or a move when they were cut in Cmdr or `forceMove` is set. Runs
+ * through the write pipeline, so the usual write-* progress events follow.
+ * Resolves to `null` when the clipboard holds no files.
+ */
+export async function pasteClipboardFiles(
+  destination: string,
+  forceMove?: boolean,
+  config?: WriteOperationConfig,
+): Promise<WriteOperationStartResult | null> {
+  const res = await commands.pasteClipboardFiles(destination, forceMove ?? null, config ?? null)
+  if (res.status === 'error') throwIpcError(res.error)
+  return res.data
+}
+
 export async function readClipboardText(): Promise<string | null> {
   const res = await commands.readClipboardText()
   if (res.status === 'error') throwIpcError(res.error)
@@ -1,8 +1,15 @@
 // File actions: open, reveal, preview, and context menu commands
 
 import { invoke } from '@tauri-apps/api/core'
+import { type UnlistenFn } from '@tauri-apps/api/event'
 import { openUrl } from '@tauri-apps/plugin-opener'
-import { commands } from '$lib/ipc/bindings'
+import {
+  commands,
+  events,
+  type CloudMaterializeProgressEvent,
+  type DefaultAction,
+  type MaterializeOutcome,
+} from '$lib/ipc/bindings'
 import { throwIpcError } from './ipc-types'
 
 /**
@@ -19,6 +26,30 @@ export async function openFile(path: string): Promise<void> {
   if (res.status === 'error') throwIpcError(res.error)
 }
 
+/**
+ * Opens a file with a specific app, by bundle ID (macOS only).
+ * @param path - Path to the file to open.
+ * @param appIdentifier - The app's bundle ID (like "com.apple.TextEdit").
+ */
+export async function openWithApp(path: string, appIdentifier: string): Promise<void> {
+  const res = await commands.openWith(path, appIdentifier)
+  if (res.status === 'error') throwIpcError(res.error)
+}
+
+/**
+ * Asks the backend what Enter or a double-click should do on an entry: navigate, browse an
+ * archive, ask, open in the viewer, or open with the default or a per-extension app. The
+ * policy reads `behavior.archiveEnterBehavior`, `behavior.fileEnterAction`, and
+ * `behavior.openWithApps`.
+ * @param path - Path to the entry.
+ * @param isDirectory - Whether it's a folder, from the listing entry. `null` makes the backend stat it.
+ */
+export async function resolveDefaultAction(path: string, isDirectory: boolean | null): Promise<DefaultAction> {
+  const res = await commands.resolveDefaultAction(path, isDirectory)
+  if (res.status === 'error') throwIpcError(res.error)
+  return res.data
+}
+
 /**
  * Opens a URL in the system's default browser.
  * @param url - URL to open (like "https://getcmdr.com/renew")
@@ -80,6 +111,35 @@ export async function cloudRemoveDownload(path: string): Promise<void> {
   if (res.status === 'error') throwIpcError(res.error)
 }
 
+/**
+ * Downloads an online-only file (iCloud Drive, Dropbox, Google Drive) so it's available
+ * offline. Resolves to `'already_local'` without downloading when the content is already on
+ * disk. Follow progress with `onCloudMaterializeProgress`.
+ */
+export async function materializeFile(path: string): Promise<MaterializeOutcome> {
+  const res = await commands.materializeFile(path)
+  if (res.status === 'error') throwIpcError(res.error)
+  return res.data
+}
+
+/**
+ * Evicts a file's local copy back to online-only. Counterpart to `materializeFile`.
+ * iCloud Drive only; other providers reject with an error that names them.
+ */
+export async function dematerializeFile(path: string): Promise<void> {
+  const res = await commands.dematerializeFile(path)
+  if (res.status === 'error') throwIpcError(res.error)
+}
+
+/** Subscribes to `materializeFile` progress, at most every 200 ms per download. */
+export function onCloudMaterializeProgress(
+  handler: (payload: CloudMaterializeProgressEvent) => void,
+): Promise<UnlistenFn> {
+  return events.cloudMaterializeProgress.listen((event) => {
+    handler(event.payload)
+  })
+}
+
 /**
  * Shows a native context menu for the breadcrumb path bar.
  *
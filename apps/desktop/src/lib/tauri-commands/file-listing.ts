@@ -1,7 +1,17 @@
 // On-demand virtual scrolling API (listing-based), sync status, font metrics
 
 import { type UnlistenFn } from '@tauri-apps/api/event'
-import { commands, events, type Initiator } from '$lib/ipc/bindings'
+import {
+  commands,
+  events,
+  type ColumnSetting,
+  type FlatListingOptions,
+  type Initiator,
+  type ListingCacheStats,
+  type TreeChildrenOptions,
+  type TreeNode,
+  type ViewMode,
+} from '$lib/ipc/bindings'
 import type {
   FileEntry,
   ListingStats,
@@ -68,6 +78,60 @@ export async function listDirectoryStart(
   return res.data
 }
 
+/**
+ * Starts a flattened listing of everything below `path`, named by relative path
+ * (`src/lib/main.rs`). Returns immediately; emits the same events as `listDirectoryStart`,
+ * with unsorted chunks while a non-indexed tree is walked. End it with `listDirectoryEnd`.
+ * @param volumeId - Volume ID. Only local volumes can be flattened.
+ * @param path - Folder to flatten. Supports tilde expansion (~) for local volumes.
+ * @param options - Depth limit, exclude globs (gitignore syntax), hidden files, folder rows.
+ * @param sortBy - Column to sort by.
+ * @param sortOrder - Ascending or descending.
+ * @param listingId - Unique identifier for the listing (used for cancellation)
+ * @param directorySortMode - How to sort directories: like files or always by name.
+ */
+export async function listDirectoryFlat(
+  volumeId: string,
+  path: string,
+  options: FlatListingOptions,
+  sortBy: SortColumn,
+  sortOrder: SortOrder,
+  listingId: string,
+  directorySortMode?: DirectorySortMode,
+): Promise<StreamingListingStartResult> {
+  return commands.listDirectoryFlat(volumeId, path, options, sortBy, sortOrder, directorySortMode ?? null, listingId)
+}
+
+/**
+ * Lists the subfolders of `path` for the sidebar tree, one level per call, each with a
+ * `hasChildren` flag for the expand arrow. Pass the pane's hidden-files setting as
+ * `options.includeHidden`. Refresh expanded nodes with `onTreeNodesUpdated`.
+ */
+export async function getTreeChildren(
+  volumeId: string,
+  path: string,
+  options: TreeChildrenOptions = {},
+): Promise<TreeNode[]> {
+  const res = await commands.getTreeChildren(volumeId, path, options)
+  if (res.status === 'error') throwIpcError(res.error)
+  return res.data
+}
+
+/**
+ * Calls `handler` with the expanded tree nodes an `index-dir-updated` event names, so the
+ * tree re-fetches just those levels. `isExpanded` is asked per path; unexpanded ones are
+ * dropped, and an event that names none of them doesn't call `handler`.
+ */
+export function onTreeNodesUpdated(
+  isExpanded: (path: string) => boolean,
+  handler: (paths: string[]) => void,
+): Promise<UnlistenFn> {
+  return events.indexDirUpdated.listen((event) => {
+    const paths = event.payload.paths.filter(isExpanded)
+    if (paths.length > 0) handler(paths)
+  })
+}
+
 /**
  * Cancels an in-progress streaming directory listing.
  * The task will emit a listing-cancelled event when it stops.
@@ -273,6 +337,11 @@ export async function getListingStats(
   return res.data
 }
 
+/** Approximate listing cache memory against its cap, with held and evicted listing counts. */
+export async function getListingCacheStats(): Promise<ListingCacheStats> {
+  return commands.getListingCacheStats()
+}
+
 /**
  * Starts a native drag operation for selected files from a cached listing.
  * This initiates the drag from Rust directly, avoiding IPC transfer of file paths.
@@ -514,7 +583,7 @@ export async function onListingCancelled(callback: (event: ListingCancelledEvent
 /**
  * Measures per-column text widths for Brief view, given the current font metrics.
  * Passthrough: the caller branches on the typed error (`font_metrics_not_ready`,
- * `invalid_items_per_column`, `listing_not_found:{id}`, or a pass-through).
+ * `invalid_items_per_column`, `listing_not_found:{id}`, `listing_not_ready:{id}`, or a pass-through).
  */
 export function getBriefColumnTextWidths(
   listingId: string,
@@ -525,3 +594,17 @@ export function getBriefColumnTextWidths(
 ) {
   return commands.getBriefColumnTextWidths(listingId, itemsPerColumn, hasParent, fontId, includeHidden)
 }
+
+/** The ordered column set (ids + widths) for a view mode, or the defaults if the user never chose one. */
+export function getColumns(viewMode: ViewMode): Promise<ColumnSetting[]> {
+  return commands.getColumns(viewMode)
+}
+
+/**
+ * Stores the ordered column set for a view mode. Passthrough: the caller branches on the
+ * typed `ColumnsError` (`missingName`, `duplicate`); on success the data is the set as
+ * stored, with widths clamped.
+ */
+export function setColumns(viewMode: ViewMode, columns: ColumnSetting[]) {
+  return commands.setColumns(viewMode, columns)
+}
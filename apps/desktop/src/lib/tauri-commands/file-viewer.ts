@@ -4,20 +4,24 @@ import {
   commands,
   type FileEncoding,
   type MediaDimensions,
+  type PeekResult,
   type RangeEnd,
   type SearchMode as ViewerSearchMode,
   type SearchStatus as ViewerSearchStatus,
   type ViewerContentKind,
   type ViewerError,
+  type ViewerOpenMode,
 } from '$lib/ipc/bindings'
 import { throwIpcError } from './ipc-types'
 
 export type {
   FileEncoding,
   MediaDimensions,
+  PeekResult,
   RangeEnd,
   ViewerContentKind,
   ViewerError,
+  ViewerOpenMode,
   ViewerSearchMode,
   ViewerSearchStatus,
 }
@@ -47,7 +51,7 @@ export interface ViewerOpenResult {
   totalLines: number | null
   /** Estimated total lines based on initial sample (for ByteSeek where totalLines is unknown) */
   estimatedTotalLines: number
-  backendType: 'fullLoad' | 'byteSeek' | 'lineIndex'
+  backendType: 'fullLoad' | 'byteSeek' | 'lineIndex' | 'hex'
   capabilities: BackendCapabilities
   initialLines: LineChunk
   /** Whether background indexing is in progress */
@@ -74,9 +78,13 @@ export interface ViewerOpenResult {
 
 /** Current status of a viewer session. */
 export interface ViewerSessionStatus {
-  backendType: 'fullLoad' | 'byteSeek' | 'lineIndex'
+  backendType: 'fullLoad' | 'byteSeek' | 'lineIndex' | 'hex'
   isIndexing: boolean
   totalLines: number | null
+  /** Encoding in use: the detected one unless overridden via `viewerSetEncoding`. */
+  encoding: FileEncoding
+  /** What auto-detection chose at open time. */
+  detectedEncoding: FileEncoding
 }
 
 /** A search match found in the file. */
@@ -117,9 +125,17 @@ export interface SearchPollResult {
  * free the session when the window is closed via the titlebar X (a path that never
  * fires `viewerClose`). Pass `getCurrentWindow().label`. Defaults to `''` (no
  * mapping) for callers without an owning window.
+ *
+ * `mode` forces `'text'` or `'hex'`; `null` lets the backend pick from the content
+ * (binary files open as hex).
  */
-export async function viewerOpen(path: string, volumeId = 'root', windowLabel = ''): Promise<ViewerOpenResult> {
-  const res = await commands.viewerOpen(path, volumeId, windowLabel)
+export async function viewerOpen(
+  path: string,
+  volumeId = 'root',
+  windowLabel = '',
+  mode: ViewerOpenMode | null = null,
+): Promise<ViewerOpenResult> {
+  const res = await commands.viewerOpen(path, volumeId, windowLabel, mode)
   if (res.status === 'error') throwViewerOpenError(res.error)
   return res.data
 }
@@ -191,6 +207,17 @@ export async function viewerClose(sessionId: string): Promise<void> {
   if (res.status === 'error') throwIpcError(res.error)
 }
 
+/**
+ * A bounded, session-free preview of `path` for the inactive pane's quick look. Cheap enough to
+ * call on every debounced cursor move. An `image` result's `mediaToken` must be released with
+ * `mediaIndexDropThumbnailTokens` once the preview is gone. `maxBytes` of 0 picks the backend default.
+ */
+export async function peekFile(path: string, maxBytes = 0): Promise<PeekResult> {
+  const res = await commands.peekFile(path, maxBytes)
+  if (res.status === 'error') throwIpcError(res.error)
+  return res.data
+}
+
 /** Sets up a viewer-specific menu on the given window (adds "Word wrap" to View submenu). */
 export async function viewerSetupMenu(label: string): Promise<void> {
   const res = await commands.viewerSetupMenu(label)
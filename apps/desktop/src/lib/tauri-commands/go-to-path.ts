@@ -12,6 +12,14 @@ export function resolveGoToPath(input: string, baseDir: string) {
   return commands.resolveGoToPath(input, baseDir)
 }
 
+/**
+ * Lists the children of the folder `partial` points into that match its last
+ * segment (folders first). Empty on any miss, including an unmounted volume.
+ */
+export function completeGoToPath(partial: string, baseDir: string) {
+  return commands.completeGoToPath(partial, baseDir)
+}
+
 /** Reads the persisted recent-path entries (newest first). */
 export function getRecentPaths(): Promise<RecentPathEntry[]> {
   return commands.getRecentPaths()
@@ -4,6 +4,9 @@
 // File listing (on-demand virtual scrolling API, sync status, font metrics)
 export {
   listDirectoryStart,
+  listDirectoryFlat,
+  getTreeChildren,
+  onTreeNodesUpdated,
   cancelListing,
   resortListing,
   getFileRange,
@@ -17,6 +20,7 @@ export {
   listDirectoryEnd,
   refreshListing,
   getListingStats,
+  getListingCacheStats,
   refreshListingIndexSizes,
   startSelectionDrag,
   startDragPaths,
@@ -39,6 +43,8 @@ export {
   onListingError,
   onListingCancelled,
   getBriefColumnTextWidths,
+  getColumns,
+  setColumns,
 } from './file-listing'
 // Streaming-listing event payload types now flow from the typed-events bindings
 // via the `file-listing.ts` re-export.
@@ -50,6 +56,7 @@ export type {
   ListingErrorEvent,
   ListingCancelledEvent,
 } from './file-listing'
+export type { FlatListingOptions, TreeChildrenOptions, TreeNode } from '$lib/ipc/bindings'
 
 // File viewer (session management, search, seeking)
 export {
@@ -70,13 +77,16 @@ export {
   viewerSetTailMode,
   viewerReload,
   viewerGetEncodingOptions,
+  peekFile,
 } from './file-viewer'
 export type {
   LineChunk,
   BackendCapabilities,
   ViewerOpenResult,
+  ViewerOpenMode,
   ViewerContentKind,
   MediaDimensions,
+  PeekResult,
   ViewerSessionStatus,
   ViewerSearchMatch,
   ViewerSearchMode,
@@ -89,6 +99,8 @@ export type {
 // File actions (open, reveal, preview, context menu)
 export {
   openFile,
+  openWithApp,
+  resolveDefaultAction,
   openExternalUrl,
   showFileContextMenu,
   showBreadcrumbContextMenu,
@@ -103,7 +115,11 @@ export {
   openInEditor,
   cloudMakeAvailableOffline,
   cloudRemoveDownload,
+  materializeFile,
+  dematerializeFile,
+  onCloudMaterializeProgress,
 } from './file-actions'
+export type { DefaultAction } from '$lib/ipc/bindings'
 
 // Favorites (user-editable switcher favorites)
 export { addFavorite, removeFavorite, renameFavorite, reorderFavorites, stripFavoritePrefix } from './favorites'
@@ -138,8 +154,12 @@ export {
   getChildWindowRect,
   setChildWindowRect,
   updateMenuAccelerator,
+  restoreSession,
+  recordHistoryNavigation,
+  recordHistoryStep,
+  getPaneHistory,
 } from './app-state'
-export type { PaneFileEntry, PaneState, McpTabInfo } from './app-state'
+export type { PaneFileEntry, PaneNavigationHistory, PaneState, McpTabInfo, Session, SessionPane } from './app-state'
 
 // Shared IPC types (timeout-aware wrappers)
 export type { TimedOut, IpcError } from './ipc-types'
@@ -158,6 +178,7 @@ export {
   getBusyVolumeIds,
   onVolumesChanged,
   onVolumeUnmounted,
+  onVolumeEjected,
   onVolumesBusyChanged,
   onVolumeContextAction,
   watchVolumeSpace,
@@ -234,6 +255,9 @@ export {
   cancelWriteOperation,
   cancelAllWriteOperations,
   resolveWriteConflict,
+  setDefaultConflictPolicy,
+  listDefaultConflictPolicies,
+  clearDefaultConflictPolicies,
   onWriteProgress,
   onWriteComplete,
   onWriteError,
@@ -241,18 +265,20 @@ export {
   onWriteSettled,
   onWriteConflict,
   onWriteSourceItemDone,
+  onWriteRenamed,
   formatBytes,
   formatDuration,
   formatFilesPerSecond,
 } from './write-operations'
 export type { Event, UnlistenFn } from './write-operations'
 
-// Operation manager (queue window): list + pause/resume/cancel + the thin
+// Operation manager (queue window): list + reorder/pause/resume/cancel + the thin
 // `operations-changed` membership/status event.
 export {
   listOperations,
   cancelOperation,
   cancelOperations,
+  moveOperation,
   pauseOperation,
   resumeOperation,
   pauseAll,
@@ -277,6 +303,7 @@ export type {
   WriteOperationError,
   WriteOperationStartResult,
   WriteProgressEvent,
+  WriteRenamedEvent,
   WriteSettledEvent,
   WriteSourceItemDoneEvent,
   ConflictInfo,
@@ -291,6 +318,8 @@ export type {
   ScanPreviewErrorEvent,
   ScanPreviewCancelledEvent,
   CompressedSizeEstimate,
+  DirPair,
+  DirPairPolicy,
 } from './write-operations'
 
 // Analytics (PostHog feature events through the single backend path)
@@ -367,6 +396,7 @@ export {
   onAiStarting,
   onAiServerReady,
   onAiVerifying,
+  onAiIntegrityFailed,
   onAiInstalling,
   onAiInstallComplete,
   onAiExtracting,
@@ -463,6 +493,7 @@ export {
   connectMtpDevice,
   disconnectMtpDevice,
   getMtpDeviceInfo,
+  getMtpCapabilities,
   getPtpcameradWorkaroundCommand,
   getMtpStorages,
   onMtpExclusiveAccessError,
@@ -470,10 +501,13 @@ export {
   onMtpDeviceConnected,
   onMtpDeviceDisconnected,
   listMtpDirectory,
+  refreshMtpDirectory,
   deleteMtpObject,
   createMtpFolder,
   renameMtpObject,
   moveMtpObject,
+  moveMtpObjectBetweenStorages,
+  getMtpThumbnail,
   scanMtpForCopy,
   copyBetweenVolumes,
   moveBetweenVolumes,
@@ -486,6 +520,7 @@ export { setArchivePassword, clearArchivePassword } from './archive'
 export type {
   MtpDeviceInfo,
   MtpStorageInfo,
+  MtpDeviceCapabilities,
   ConnectedMtpDeviceInfo,
   MtpConnectionError,
   MtpExclusiveAccessErrorEvent,
@@ -514,15 +549,24 @@ export {
   getMcpRunning,
   getMcpPort,
   updateFileWatcherDebounce,
+  updateFileWatcherDebounceForVolumeKind,
+  getWatcherStats,
   updateServiceResolveTimeout,
   setDirectSmbConnection,
   setFilterSafeSaveArtifacts,
   setLogLlmCalls,
   setSmbConcurrency,
+  setListingCacheMemoryMb,
+  setDefaultActionSettings,
+  writeSettingsExport,
+  readSettingsExport,
   setMaxLogStorageMb,
   setErrorReportsEnabled,
   setShowVirtualGitPortal,
+  setGitIgnoredMode,
   setIndexingEnabled,
+  setIndexExclusions,
+  setIndexThrottle,
   setImageIndexEnabled,
   startIndexingAfterFdaDecision,
   getDirStatsBatch,
@@ -534,12 +578,16 @@ export {
   startAiDownload,
   cancelAiDownload,
   uninstallAi,
+  installCustomModel,
+  uninstallCustomModel,
   getFolderSuggestions,
+  getRenameSuggestion,
   streamFolderSuggestions,
   getAiRuntimeStatus,
   configureAi,
   stopAiServer,
   startAiServer,
+  updateAiServerConfig,
   checkAiConnection,
   saveAiApiKey,
   getAiApiKey,
@@ -557,6 +605,7 @@ export type {
   AiModelInfo,
   AiRuntimeStatus,
   AiConnectionCheckResult,
+  AiServerConfig,
   DirStats,
   SystemMemoryInfo,
   SuggestionStreamEvent,
@@ -574,6 +623,7 @@ export {
   copyPathsToClipboard,
   cutPathsToClipboard,
   readClipboardFiles,
+  pasteClipboardFiles,
   readClipboardText,
   clearClipboardCutState,
   pasteClipboardAsFile,
@@ -623,6 +673,11 @@ export {
   disableDriveIndex,
   forgetDriveIndex,
   rescanDriveIndex,
+  reindexSubtree,
+  cancelReindexSubtree,
+  pauseDriveIndexScan,
+  resumeDriveIndexScan,
+  compactIndex,
   clearDriveIndex,
   recordVisit,
 } from './indexing'
@@ -686,6 +741,7 @@ export {
   onIndexReplayComplete,
   onIndexDirUpdated,
   onIndexMemoryWarning,
+  onScanPermissionWarning,
 } from './indexing'
 export type {
   IndexScanStartedEvent,
@@ -697,10 +753,14 @@ export type {
   IndexReplayCompleteEvent,
   IndexDirUpdatedEvent,
   IndexMemoryWarningEvent,
+  ScanPermissionWarningEvent,
 } from '$lib/ipc/bindings'
 
 // "Go to path" (⌘G): resolving typed input, and the persisted recents list
-export { resolveGoToPath, getRecentPaths, addRecentPath, removeRecentPath } from './go-to-path'
+export { resolveGoToPath, completeGoToPath, getRecentPaths, addRecentPath, removeRecentPath } from './go-to-path'
+
+// Command palette: the backend action registry
+export { getPaletteActions, invokePaletteAction } from './palette'
 
 // macOS Finder color tags
 export { toggleTags, enrichTags } from './tags'
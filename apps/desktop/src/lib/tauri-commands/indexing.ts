@@ -20,6 +20,7 @@ import {
   type IndexScanCompleteEvent,
   type IndexScanProgressEvent,
   type IndexScanStartedEvent,
+  type ScanPermissionWarningEvent,
 } from '$lib/ipc/bindings'
 
 /** Fires when a full scan starts, carrying the per-scan calibration. */
@@ -119,6 +120,13 @@ export function onIndexMemoryWarning(callback: (payload: IndexMemoryWarningEvent
   })
 }
 
+/** Fires once per session when a scan skipped directories it had no permission to read. */
+export function onScanPermissionWarning(callback: (payload: ScanPermissionWarningEvent) => void): Promise<UnlistenFn> {
+  return events.scanPermissionWarning.listen((event) => {
+    callback(event.payload)
+  })
+}
+
 /**
  * Fires when a volume's index freshness changes to a NEW value (the badge
  * refreshes; the one-time stale dialog fires on the exact Fresh→Stale edge).
@@ -178,6 +186,38 @@ export function rescanDriveIndex(volumeId: string) {
   return commands.rescanDriveIndex(volumeId)
 }
 
+/**
+ * Re-walks one folder of the local drive index and corrects its entries and sizes, without
+ * rescanning the whole drive. Returns the operation id; listen for `reindex-progress` and
+ * `reindex-complete`. Running it again on the same folder after a cancel resumes the walk.
+ */
+export function reindexSubtree(path: string) {
+  return commands.reindexSubtree(path)
+}
+
+/** Cancels a running folder re-index. Returns `false` if it already finished. */
+export function cancelReindexSubtree(operationId: string) {
+  return commands.cancelReindexSubtree(operationId)
+}
+
+/**
+ * Pauses a drive's running full scan in place. The watcher keeps buffering, and
+ * `resumeDriveIndexScan` continues without re-reading finished folders.
+ */
+export function pauseDriveIndexScan(volumeId: string) {
+  return commands.pauseDriveIndexScan(volumeId)
+}
+
+/** Resumes a scan paused with `pauseDriveIndexScan`. */
+export function resumeDriveIndexScan(volumeId: string) {
+  return commands.resumeDriveIndexScan(volumeId)
+}
+
+/** Compacts a drive's index DB (full VACUUM + WAL truncate) and returns its size before and after. */
+export function compactIndex(volumeId: string) {
+  return commands.compactIndex(volumeId)
+}
+
 /** Clears the local (`root`) drive index entirely. */
 export function clearDriveIndex() {
   return commands.clearDriveIndex()
@@ -5,6 +5,7 @@ import {
   commands,
   events,
   type Initiator,
+  type MtpDeviceCapabilities as MtpDeviceCapabilitiesBinding,
   type MtpDeviceConnected,
   type MtpDeviceDisconnected,
   type MtpExclusiveAccessError,
@@ -75,6 +76,9 @@ export async function listMtpDevices(): Promise<MtpDeviceInfo[]> {
  */
 export type MtpStorageInfo = MtpStorageInfoBinding
 
+/** Which operations a connected MTP device supports. Aliased to the `tauri-specta`-generated type. */
+export type MtpDeviceCapabilities = MtpDeviceCapabilitiesBinding
+
 /** Information about a connected MTP device including its storages. */
 export interface ConnectedMtpDeviceInfo {
   /** Device information. */
@@ -144,6 +148,20 @@ export async function getMtpDeviceInfo(deviceId: string): Promise<ConnectedMtpDe
   }
 }
 
+/**
+ * Gets the operations a connected MTP device supports (rename, move, create folder, thumbnails,
+ * partial reads), so the UI can disable actions the device would refuse.
+ * Returns null if the device is not connected.
+ * @param deviceId - The device ID to query
+ */
+export async function getMtpCapabilities(deviceId: string): Promise<MtpDeviceCapabilities | null> {
+  try {
+    return await commands.getMtpCapabilities(deviceId)
+  } catch {
+    return null
+  }
+}
+
 /**
  * Gets the ptpcamerad workaround command for macOS.
  * Returns the Terminal command users can run to work around ptpcamerad blocking MTP.
@@ -229,7 +247,7 @@ export async function onMtpDeviceDisconnected(
  * @param deviceId - The connected device ID
  * @param storageId - The storage ID within the device
  * @param path - Virtual path to list (for example, "/" or "/DCIM")
- * @returns Array of FileEntry objects, sorted with directories first
+ * @returns Array of FileEntry objects in device order (panes sort via the listing pipeline)
  */
 export async function listMtpDirectory(deviceId: string, storageId: number, path: string): Promise<FileEntry[]> {
   const res = await commands.listMtpDirectory(deviceId, storageId, path)
@@ -240,6 +258,23 @@ export async function listMtpDirectory(deviceId: string, storageId: number, path
   return res.data as FileEntry[]
 }
 
+/**
+ * Re-reads a directory on a connected MTP device, bypassing the backend's listing cache.
+ * Use for an explicit refresh, where `listMtpDirectory` could serve a listing that's a few seconds old.
+ * @param deviceId - The connected device ID
+ * @param storageId - The storage ID within the device
+ * @param path - Virtual path to re-read (for example, "/" or "/DCIM")
+ * @returns Array of FileEntry objects in device order (panes sort via the listing pipeline)
+ */
+export async function refreshMtpDirectory(deviceId: string, storageId: number, path: string): Promise<FileEntry[]> {
+  const res = await commands.refreshMtpDirectory(deviceId, storageId, path)
+  if (res.status === 'error') {
+    // eslint-disable-next-line @typescript-eslint/only-throw-error -- tagged-union error consumed via isMtpConnectionError() guard
+    throw res.error
+  }
+  return res.data as FileEntry[]
+}
+
 // ============================================================================
 // MTP File Operations (Phase 4)
 // ============================================================================
@@ -325,6 +360,47 @@ export async function moveMtpObject(
   return res.data
 }
 
+/**
+ * Moves an object from one storage of an MTP device to a folder on another (say, internal
+ * storage to SD card). Falls back to copy + delete when the device can't move across storages;
+ * listen to `mtp-storage-move-progress` for per-file progress then.
+ * @param deviceId - The connected device ID
+ * @param fromStorageId - The storage the object is on
+ * @param objectPath - Current path of the object
+ * @param toStorageId - The storage to move it to
+ * @param newParentPath - Destination folder path on the target storage
+ */
+export async function moveMtpObjectBetweenStorages(
+  deviceId: string,
+  fromStorageId: number,
+  objectPath: string,
+  toStorageId: number,
+  newParentPath: string,
+): Promise<MtpObjectInfo> {
+  const res = await commands.moveMtpObjectBetweenStorages(
+    deviceId,
+    fromStorageId,
+    objectPath,
+    toStorageId,
+    newParentPath,
+  )
+  if (res.status === 'error') throwIpcError(res.error)
+  return res.data
+}
+
+/**
+ * Fetches the embedded thumbnail of an MTP object, for the grid view.
+ * @param deviceId - The connected device ID
+ * @param storageId - The storage ID within the device
+ * @param objectPath - Virtual path on the device
+ * @returns Base64-encoded JPEG bytes, or null when the object has no thumbnail
+ */
+export async function getMtpThumbnail(deviceId: string, storageId: number, objectPath: string): Promise<string | null> {
+  const res = await commands.getMtpThumbnail(deviceId, storageId, objectPath)
+  if (res.status === 'error') throwIpcError(res.error)
+  return res.data
+}
+
 /** Result of scanning MTP files/directories for copy operation. */
 export interface MtpScanResult {
   fileCount: number
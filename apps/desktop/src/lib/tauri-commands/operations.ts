@@ -1,5 +1,5 @@
 // Operation-manager IPC: the queue window's view of every running/queued
-// copy, move, delete, and trash operation, plus the pause/resume/cancel
+// copy, move, delete, and trash operation, plus the reorder/pause/resume/cancel
 // controls. The thin `operations-changed` event carries membership + lifecycle
 // status; live per-row bars come from the separate `write-progress` stream
 // (see `onWriteProgress` in `write-operations.ts`).
@@ -29,6 +29,13 @@ export async function cancelOperations(operationIds: string[]): Promise<void> {
   await commands.cancelOperations(operationIds)
 }
 
+/** Move a queued operation ahead of `beforeOperationId`, or to the back of the
+ *  queue when it's `null`. It's admitted in that order once its lanes free.
+ *  Running/paused ops stay where they are. */
+export async function moveOperation(operationId: string, beforeOperationId: string | null): Promise<void> {
+  await commands.moveOperation(operationId, beforeOperationId)
+}
+
 /** Pause one running operation in place. It keeps its lane slot and can be
  *  resumed; its snapshot status flips to `paused`. */
 export async function pauseOperation(operationId: string): Promise<void> {
@@ -0,0 +1,17 @@
+// Command palette commands: the backend action registry (labels, keywords, and
+// enabled state) and dispatching an action through `execute-command`.
+
+import { commands, type PaletteContext } from '$lib/ipc/bindings'
+
+/**
+ * Lists every backend-registered palette action with `enabled` evaluated against
+ * the focused pane's `context`. The clipboard part of "Paste" is checked in Rust.
+ */
+export function getPaletteActions(context: PaletteContext) {
+  return commands.getPaletteActions(context)
+}
+
+/** Runs a palette action by id, the same way a menu click does. */
+export function invokePaletteAction(id: string) {
+  return commands.invokePaletteAction(id)
+}
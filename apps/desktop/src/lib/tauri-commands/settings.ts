@@ -5,10 +5,13 @@ import { type UnlistenFn } from '@tauri-apps/api/event'
 import {
   commands,
   events,
+  type GitIgnoredMode,
   type RestrictedWindowPersistableSetting,
   type RestrictedWindowSettings,
   type SettingsChanged,
   type SettingValue,
+  type WatcherListingStats,
+  type WatcherVolumeKind,
 } from '$lib/ipc/bindings'
 import { throwIpcError } from './ipc-types'
 
@@ -65,14 +68,31 @@ export async function findAvailablePort(startPort: number): Promise<number | nul
 }
 
 /**
- * Updates the file watcher debounce duration in the Rust backend.
- * This affects newly created watchers; existing watchers keep their original duration.
+ * Updates the general file watcher debounce duration in the Rust backend.
+ * Active watchers without a per-volume-kind override pick it up right away.
  * @param debounceMs - Debounce duration in milliseconds
  */
 export async function updateFileWatcherDebounce(debounceMs: number): Promise<void> {
   await commands.updateFileWatcherDebounce(debounceMs)
 }
 
+/**
+ * Sets or clears the file watcher debounce override for one volume kind. Active watchers pick it up right away.
+ * @param volumeKind - Which volumes the override applies to
+ * @param debounceMs - Debounce duration in milliseconds, or 0 to follow the general debounce
+ */
+export async function updateFileWatcherDebounceForVolumeKind(
+  volumeKind: WatcherVolumeKind,
+  debounceMs: number,
+): Promise<void> {
+  await commands.updateFileWatcherDebounceForVolumeKind(volumeKind, debounceMs > 0 ? debounceMs : null)
+}
+
+/** Per-listing watcher counters (events received, diffs emitted, effective debounce), sorted by path. */
+export async function getWatcherStats(): Promise<WatcherListingStats[]> {
+  return commands.getWatcherStats()
+}
+
 /**
  * Updates the Bonjour service resolve timeout in the Rust backend.
  * This affects future service resolutions; ongoing resolutions keep their original timeout.
@@ -110,6 +130,54 @@ export async function setSmbConcurrency(value: number): Promise<void> {
   await commands.setSmbConcurrencyCmd(value)
 }
 
+/**
+ * Updates the listing cache's memory cap. Inactive listings past it are evicted
+ * at once and re-listed when next touched. Pushed live whenever
+ * `advanced.listingCacheMemoryMb` changes.
+ * @param value - Cap in MB (the Rust side enforces a 64 MB floor)
+ */
+export async function setListingCacheMemoryMb(value: number): Promise<void> {
+  await commands.setListingCacheMemoryMb(value)
+}
+
+/**
+ * Updates the settings the backend's Enter/double-click policy (`resolveDefaultAction`)
+ * reads. Takes the raw stored values; the Rust side parses them leniently. Pushed live
+ * whenever any of the three changes.
+ * @param archiveEnterBehavior - `behavior.archiveEnterBehavior` JSON
+ * @param fileEnterAction - `behavior.fileEnterAction`
+ * @param openWithApps - `behavior.openWithApps` JSON
+ */
+export async function setDefaultActionSettings(
+  archiveEnterBehavior: string,
+  fileEnterAction: string,
+  openWithApps: string,
+): Promise<void> {
+  await commands.setDefaultActionSettings(archiveEnterBehavior, fileEnterAction, openWithApps)
+}
+
+/**
+ * Writes a settings export file. The contents come from `settings-transfer.ts`; the backend
+ * only checks it's a JSON object and writes it atomically.
+ * @param outPath - Where to write the export
+ * @param contents - The export JSON
+ */
+export async function writeSettingsExport(outPath: string, contents: string): Promise<void> {
+  const res = await commands.writeSettingsExport(outPath, contents)
+  if (res.status === 'error') throwIpcError(res.error)
+}
+
+/**
+ * Reads a settings export file back (1 MB cap, must be a JSON object) for
+ * `settings-transfer.ts` to validate and apply.
+ * @param inPath - The export file to read
+ */
+export async function readSettingsExport(inPath: string): Promise<string> {
+  const res = await commands.readSettingsExport(inPath)
+  if (res.status === 'error') throwIpcError(res.error)
+  return res.data
+}
+
 /**
  * Turns LLM call logging on or off. When on, every AI request and response is written to
  * `{app data dir}/llm-logs/` for debugging (local only, never transmitted). Pushed live from
@@ -161,6 +229,16 @@ export async function setShowVirtualGitPortal(enabled: boolean): Promise<void> {
   await commands.setShowVirtualGitPortal(enabled)
 }
 
+/**
+ * Sets how listings treat git-ignored entries: not at all, greyed out, or left out. Open local
+ * listings update right away.
+ *
+ * Pushed live from the settings UI whenever `fileExplorer.git.ignoredFiles` changes.
+ */
+export async function setGitIgnoredMode(mode: GitIgnoredMode): Promise<void> {
+  await commands.setGitIgnoredMode(mode)
+}
+
 // ============================================================================
 // MCP server commands
 // ============================================================================
@@ -221,6 +299,22 @@ export async function setIndexingEnabled(enabled: boolean): Promise<void> {
   if (res.status === 'error') throwIpcError(res.error)
 }
 
+/**
+ * Live-applies the index exclusion patterns (`indexing.excludePatterns`). The backend also
+ * prunes already-indexed folders the patterns now cover, in the background.
+ */
+export async function setIndexExclusions(patterns: string[]): Promise<void> {
+  await commands.setIndexExclusions(patterns)
+}
+
+/**
+ * Live-applies the fresh-scan throttle (`indexing.maxEntriesPerSec`, 0 for no cap, and
+ * `indexing.onlyOnAcPower`). A scan that's already running picks it up on its next folder.
+ */
+export async function setIndexThrottle(maxEntriesPerSec: number, onlyOnAcPower: boolean): Promise<void> {
+  await commands.setIndexThrottle({ maxEntriesPerSec, onlyOnAcPower })
+}
+
 /**
  * Live-applies the master "Index image contents" toggle (`mediaIndex.enabled`) to the
  * backend `media_index` enrichment scheduler. Enabling clears any prior memory-watchdog
@@ -380,22 +474,52 @@ export async function uninstallAi(): Promise<void> {
   await commands.uninstallAi()
 }
 
+/**
+ * Installs a user-supplied GGUF from a local path or an http(s) URL and makes it the active
+ * local model. URL downloads report progress and cancel like `startAiDownload`. Returns the
+ * new model's ID.
+ */
+export async function installCustomModel(pathOrUrl: string, displayName: string): Promise<string> {
+  // eslint-disable-next-line cmdr/no-raw-tauri-invoke -- generic <R: Runtime> command, excluded from specta bindings (see ipc_collectors.rs)
+  return invoke<string>('install_custom_model', { pathOrUrl, displayName })
+}
+
+/** Removes a user-installed model. Built-in models are refused (use `uninstallAi`). */
+export async function uninstallCustomModel(modelId: string): Promise<void> {
+  const res = await commands.uninstallCustomModel(modelId)
+  if (res.status === 'error') throwIpcError(res.error)
+}
+
 /** Returns the full runtime status of the AI subsystem. */
 export async function getAiRuntimeStatus(): Promise<AiRuntimeStatus> {
   return commands.getAiRuntimeStatus()
 }
 
-/** Pushes AI config to the backend. Triggers server start if provider is local + model installed. */
+/**
+ * llama-server launch parameters (mirrors Rust `AiServerConfig`). `null` threads / port let
+ * the backend pick.
+ */
+export interface AiServerConfig {
+  contextSize: number
+  threads: number | null
+  gpuLayers: number
+  port: number | null
+}
+
+/**
+ * Pushes AI config to the backend. Triggers server start if provider is local + model installed.
+ * An invalid `serverConfig` is replaced with the defaults (logged on the Rust side).
+ */
 export async function configureAi(
   provider: string,
-  contextSize: number,
+  serverConfig: AiServerConfig,
   cloudApiKey: string,
   cloudBaseUrl: string,
   cloudModel: string,
   cloudRequiresApiKey: boolean,
 ): Promise<void> {
   // eslint-disable-next-line cmdr/no-raw-tauri-invoke -- generic <R: Runtime> command, excluded from specta bindings (see ipc_collectors.rs)
-  await invoke('configure_ai', { provider, contextSize, cloudApiKey, cloudBaseUrl, cloudModel, cloudRequiresApiKey })
+  await invoke('configure_ai', { provider, serverConfig, cloudApiKey, cloudBaseUrl, cloudModel, cloudRequiresApiKey })
 }
 
 /** Stops the local llama-server without uninstalling. */
@@ -409,6 +533,16 @@ export async function startAiServer(ctxSize: number): Promise<void> {
   await invoke('start_ai_server', { ctxSize })
 }
 
+/**
+ * Applies new launch parameters and (re)starts the local server with them, waiting for it to
+ * become healthy. Rejects on out-of-range values. If the server won't start, the backend falls
+ * back to the defaults and returns those, so compare the result with what you passed.
+ */
+export async function updateAiServerConfig(config: AiServerConfig): Promise<AiServerConfig> {
+  // eslint-disable-next-line cmdr/no-raw-tauri-invoke -- generic <R: Runtime> command, excluded from specta bindings (see ipc_collectors.rs)
+  return invoke<AiServerConfig>('update_ai_server_config', { config })
+}
+
 /** Result of checking connectivity to an AI API endpoint. */
 export interface AiConnectionCheckResult {
   connected: boolean
@@ -508,6 +642,20 @@ export async function getFolderSuggestions(
   }
 }
 
+/**
+ * Gets up to three AI-suggested new names for the file or folder at `path`. Each keeps the
+ * original extension and is already checked to be a legal, conflict-free rename.
+ */
+export async function getRenameSuggestion(path: string): Promise<string[]> {
+  try {
+    const res = await commands.getRenameSuggestion(path)
+    if (res.status === 'error') return []
+    return res.data
+  } catch {
+    return []
+  }
+}
+
 /** Wire-format event for streaming folder suggestions. Mirrors the Rust enum. */
 export type SuggestionStreamEvent =
   | { type: 'suggestion'; name: string }
@@ -13,6 +13,7 @@ import {
   type VolumeContextAction,
   type VolumesBusyChanged,
   type VolumeSpaceChanged,
+  type VolumeEjected,
   type VolumeUnmounted,
 } from '$lib/ipc/bindings'
 import { throwIpcError } from './ipc-types'
@@ -139,10 +140,11 @@ export async function getVolumeSpace(path: string): Promise<TimedOut<VolumeSpace
  * teardown), and physical or disk-image volumes run `diskutil eject` (powers
  * down USB devices, detaches DMGs).
  *
- * Resolves once the unmount or disconnect is initiated. The volume disappears
- * from the picker shortly after, via `volume-unmounted` or
+ * Resolves once the unmount or disconnect is done (after `volume-ejected`). The
+ * volume disappears from the picker shortly after, via `volume-unmounted` or
  * `mtp-device-disconnected`. Throws an `IpcError`-shaped exception on failure
- * (e.g. "Resource busy" if Finder still has the volume open).
+ * (e.g. "Resource busy" if Finder still has the volume open, or the ids of the
+ * operations still using the volume).
  */
 export async function ejectVolume(volumeId: string): Promise<void> {
   const res = await commands.ejectVolume(volumeId)
@@ -188,6 +190,17 @@ export function onVolumeUnmounted(handler: (payload: VolumeUnmounted) => void):
   })
 }
 
+/**
+ * Subscribes to successful ejects started by `ejectVolume` (or the MCP `eject`
+ * tool). The handler receives the ejected volume's ID.
+ * Call the returned `UnlistenFn` on component destroy to avoid leaks.
+ */
+export function onVolumeEjected(handler: (payload: VolumeEjected) => void): Promise<UnlistenFn> {
+  return events.volumeEjected.listen((event) => {
+    handler(event.payload)
+  })
+}
+
 /**
  * Subscribes to busy-volume-set changes. The handler receives the sorted list of
  * volume IDs with an in-flight copy / move / delete operation.
@@ -18,6 +18,8 @@ import type {
 import type {
   CompressedSizeEstimate,
   ConflictInfo,
+  DirPair,
+  DirPairPolicy,
   DryRunResult,
   Initiator,
   OperationStatus,
@@ -32,6 +34,7 @@ import type {
   WriteConflictEvent,
   WriteErrorEvent,
   WriteProgressEvent,
+  WriteRenamedEvent,
   WriteSettledEvent,
   WriteSourceItemDoneEvent,
 } from '$lib/ipc/bindings'
@@ -49,6 +52,7 @@ export type {
   WriteOperationError,
   WriteOperationStartResult,
   WriteProgressEvent,
+  WriteRenamedEvent,
   WriteSettledEvent,
   WriteSourceItemDoneEvent,
   ConflictInfo,
@@ -64,6 +68,8 @@ export type {
   ScanPreviewCancelledEvent,
   ScanPreviewTotals,
   CompressedSizeEstimate,
+  DirPair,
+  DirPairPolicy,
 }
 
 // ============================================================================
@@ -190,13 +196,36 @@ export async function cancelAllWriteOperations(): Promise<void> {
   await commands.cancelAllWriteOperations()
 }
 
-/** In Stop mode, the operation pauses on conflict and waits for this call to proceed. */
+/**
+ * In Stop mode, the operation pauses on conflict and waits for this call to proceed.
+ * `rememberForPair` also makes `resolution` the default for that folder pair, so later
+ * copies and moves between the same folders don't prompt.
+ */
 export async function resolveWriteConflict(
   operationId: string,
   resolution: ConflictResolution,
   applyToAll: boolean,
+  rememberForPair?: DirPair,
 ): Promise<void> {
-  await commands.resolveWriteConflict(operationId, resolution, applyToAll)
+  await commands.resolveWriteConflict(operationId, resolution, applyToAll, rememberForPair ?? null)
+}
+
+/** Remembers the conflict policy for copies and moves from one folder into another. `stop` forgets it. */
+export async function setDefaultConflictPolicy(pair: DirPair, policy: ConflictResolution): Promise<void> {
+  const res = await commands.setDefaultConflictPolicy(pair, policy)
+  if (res.status === 'error') throwIpcError(res.error)
+}
+
+/** The remembered per-folder-pair conflict policies, most recently set first. */
+export async function listDefaultConflictPolicies(): Promise<DirPairPolicy[]> {
+  return commands.listDefaultConflictPolicies()
+}
+
+/** Forgets the remembered policy for `pair`, or every one when omitted. Returns how many went. */
+export async function clearDefaultConflictPolicies(pair?: DirPair): Promise<number> {
+  const res = await commands.clearDefaultConflictPolicies(pair ?? null)
+  if (res.status === 'error') throwIpcError(res.error)
+  return res.data
 }
 
 // ============================================================================
@@ -250,6 +279,13 @@ export async function onWriteSourceItemDone(callback: (event: WriteSourceItemDon
   })
 }
 
+/** Emitted when `sanitizeNames` rewrote a name the destination filesystem would reject. */
+export async function onWriteRenamed(callback: (event: WriteRenamedEvent) => void): Promise<UnlistenFn> {
+  return events.writeRenamed.listen((event) => {
+    callback(event.payload)
+  })
+}
+
 /**
  * Formats bytes as human-readable string (like "1.5 GB").
  */
@@ -27,6 +27,10 @@ export const sortHandlers = {
     explorerRef?.setSortColumn('created')
   },
 
+  'sort.byCaptured': ({ explorerRef }) => {
+    explorerRef?.setSortColumn('captured')
+  },
+
   'sort.ascending': ({ explorerRef }) => {
     explorerRef?.setSortOrder('asc')
   },
@@ -71,10 +71,10 @@ export interface ExplorerAPI {
    */
   routePanelKey: (payload: QuickLookKeyEventPayload) => void
   openItemUnderCursor: () => Promise<void>
-  setSortColumn: (column: 'name' | 'extension' | 'size' | 'modified' | 'created', pane?: 'left' | 'right') => void
+  setSortColumn: (column: 'name' | 'extension' | 'size' | 'modified' | 'created' | 'captured', pane?: 'left' | 'right') => void
   setSortOrder: (order: 'asc' | 'desc' | 'toggle', pane?: 'left' | 'right') => void
   setSort: (
-    column: 'name' | 'extension' | 'size' | 'modified' | 'created',
+    column: 'name' | 'extension' | 'size' | 'modified' | 'created' | 'captured',
     order: 'asc' | 'desc',
     pane: 'left' | 'right',
   ) => Promise<void>
@@ -224,6 +224,7 @@ function menuSortToCommand(action: unknown, value: unknown): CommandId | undefin
       size: 'sort.bySize',
       modified: 'sort.byModified',
       created: 'sort.byCreated',
+      captured: 'sort.byCaptured',
     }
     return typeof value === 'string' ? byColumn[value] : undefined
   }
@@ -13,7 +13,7 @@ beforeEach(() => {
 })
 
 function mountStatusBar(props: {
-  currentMode: 'fullLoad' | 'byteSeek' | 'lineIndex'
+  currentMode: 'fullLoad' | 'byteSeek' | 'lineIndex' | 'hex'
   isIndexing: boolean
   wordWrap: boolean
   totalLines: number | null
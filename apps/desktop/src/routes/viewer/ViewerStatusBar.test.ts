@@ -25,7 +25,7 @@ interface MountOpts {
   mediaDimensions?: MediaDimensions | null
   totalLines?: number | null
   totalBytes?: number
-  currentMode?: 'fullLoad' | 'byteSeek' | 'lineIndex'
+  currentMode?: 'fullLoad' | 'byteSeek' | 'lineIndex' | 'hex'
   isIndexing?: boolean
   wordWrap?: boolean
 }
@@ -92,6 +92,15 @@ describe('ViewerStatusBar', () => {
     void unmount(instance)
   })
 
+  it('shows the hex badge in hex mode', async () => {
+    const { target, instance } = mountStatusBar({ currentMode: 'hex' })
+    await tick()
+
+    expect(target.querySelector('.backend-badge')?.textContent).toBe('hex')
+
+    void unmount(instance)
+  })
+
   it('shows the streaming-indexing badge when byteSeek and isIndexing', async () => {
     const { target, instance } = mountStatusBar({ currentMode: 'byteSeek', isIndexing: true })
     await tick()
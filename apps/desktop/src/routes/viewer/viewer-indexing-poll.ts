@@ -7,7 +7,7 @@ const INDEXING_POLL_INTERVAL = 500
 interface IndexingPollDeps {
   getSessionId: () => string
   onStatus: (status: {
-    backendType: 'fullLoad' | 'byteSeek' | 'lineIndex'
+    backendType: 'fullLoad' | 'byteSeek' | 'lineIndex' | 'hex'
     isIndexing: boolean
     totalLines: number | null
   }) => void
@@ -24,7 +24,7 @@ interface ScrollDeps {
   getTotalLines: () => number | null
   setTotalLines: (v: number) => void
   getEstimatedLines: () => number
-  getBackendType: () => 'fullLoad' | 'byteSeek' | 'lineIndex'
+  getBackendType: () => 'fullLoad' | 'byteSeek' | 'lineIndex' | 'hex'
   onTimeoutError: () => void
   getAllLines: () => string[] | null
   getTextWidth: () => number